@@ -0,0 +1,363 @@
+// abuse_report_handlers.rs
+// Abuse reports against contracts and the moderation queue they feed: anyone
+// may report a contract, and admins dismiss the reports, quarantine the
+// contract (hidden from listings) or take it down (410 Gone).
+
+use axum::{
+    extract::{rejection::JsonRejection, Path, State},
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use sha2::{Digest, Sha256};
+use shared::{
+    AbuseReason, AbuseReport, AbuseReportReceipt, AbuseReportStatus, ModerateContractRequest,
+    ModerationAction, ModerationOutcome, ModerationQueueEntry, ModerationStatus,
+    ReportContractRequest,
+};
+use uuid::Uuid;
+
+use crate::challenge::ensure_challenge_passed;
+use crate::error::{ApiError, ApiResult};
+use crate::handlers::{db_internal_error, fetch_contract_identity, map_json_rejection};
+use crate::ip_allowlist::ClientIp;
+use crate::private_registry::{ensure_contract_visible, Viewer};
+use crate::state::AppState;
+use crate::tenancy::CurrentTenant;
+
+const MAX_DETAILS_LENGTH: usize = 2000;
+const MAX_CONTACT_LENGTH: usize = 200;
+const MAX_NOTE_LENGTH: usize = 2000;
+
+/// Reports accepted from one reporter per hour, across all contracts
+const MAX_REPORTS_PER_HOUR: i64 = 5;
+
+const REPORT_COLUMNS: &str = "r.id, r.contract_id, r.reason, r.details, r.reporter_contact, \
+     r.reporter_address, r.status, r.resolution_note, r.created_at, r.resolved_at";
+
+/// Trimmed text, None when blank; an error when longer than `max` characters
+fn optional_text(field: &str, value: Option<&str>, max: usize) -> ApiResult<Option<String>> {
+    let value = value.map(str::trim).filter(|v| !v.is_empty());
+    if value.is_some_and(|v| v.chars().count() > max) {
+        return Err(ApiError::bad_request(
+            "InvalidReport",
+            format!("{} must be at most {} characters", field, max),
+        ));
+    }
+    Ok(value.map(str::to_string))
+}
+
+/// Who is reporting: the signed-in account, or a hash of the client IP so
+/// anonymous reports can be deduplicated and rate limited without storing it
+fn reporter_key(address: Option<&str>, ip: &str) -> String {
+    match address {
+        Some(address) => format!("account:{}", address),
+        None => format!("ip:{}", hex::encode(Sha256::digest(ip.as_bytes()))),
+    }
+}
+
+/// POST /api/contracts/:id/report — report a contract to the moderators.
+/// A reporter's repeat report on a contract is not recorded twice; the
+/// receipt then points at the open one. Anonymous reporters may have to
+/// answer a challenge first.
+pub async fn report_contract(
+    State(state): State<AppState>,
+    viewer: Viewer,
+    ClientIp(client): ClientIp,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+    payload: Result<Json<ReportContractRequest>, JsonRejection>,
+) -> ApiResult<(StatusCode, Json<AbuseReportReceipt>)> {
+    let Json(req) = payload.map_err(map_json_rejection)?;
+    let details = optional_text("details", req.details.as_deref(), MAX_DETAILS_LENGTH)?;
+    let contact = optional_text(
+        "reporter_contact",
+        req.reporter_contact.as_deref(),
+        MAX_CONTACT_LENGTH,
+    )?;
+    if req.reason == AbuseReason::Other && details.is_none() {
+        return Err(ApiError::bad_request(
+            "InvalidReport",
+            "details are required when the reason is `other`",
+        ));
+    }
+
+    ensure_challenge_passed(&state, &viewer, &headers).await?;
+
+    let (contract_uuid, _) = fetch_contract_identity(&state, viewer.tenant_id, &id).await?;
+    ensure_contract_visible(&state, &viewer, contract_uuid).await?;
+    // The TCP peer, or the client behind a trusted proxy; a forwarded header
+    // from anyone else could pick a fresh rate-limit bucket per report
+    let ip = client.map_or_else(|| "unknown".to_string(), |ip| ip.to_string());
+    let key = reporter_key(viewer.address.as_deref(), &ip);
+
+    let recent: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM abuse_reports \
+         WHERE reporter_key = $1 AND created_at > NOW() - INTERVAL '1 hour'",
+    )
+    .bind(&key)
+    .fetch_one(&state.db)
+    .await
+    .map_err(|err| db_internal_error("count recent reports", err))?;
+    if recent >= MAX_REPORTS_PER_HOUR {
+        return Err(ApiError::new(
+            StatusCode::TOO_MANY_REQUESTS,
+            "RateLimitExceeded",
+            format!(
+                "At most {} reports are accepted per hour; try again later",
+                MAX_REPORTS_PER_HOUR
+            ),
+        ));
+    }
+
+    let inserted: Option<Uuid> = sqlx::query_scalar(
+        "INSERT INTO abuse_reports \
+             (contract_id, reason, details, reporter_contact, reporter_address, reporter_key) \
+         VALUES ($1, $2, $3, $4, $5, $6) \
+         ON CONFLICT (contract_id, reporter_key) WHERE status = 'open' DO NOTHING \
+         RETURNING id",
+    )
+    .bind(contract_uuid)
+    .bind(req.reason)
+    .bind(&details)
+    .bind(&contact)
+    .bind(viewer.address.as_deref())
+    .bind(&key)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|err| db_internal_error("record abuse report", err))?;
+
+    let receipt = match inserted {
+        Some(report_id) => {
+            tracing::info!(contract = %contract_uuid, reason = ?req.reason, "abuse report filed");
+            AbuseReportReceipt {
+                report_id,
+                duplicate: false,
+            }
+        }
+        None => AbuseReportReceipt {
+            report_id: sqlx::query_scalar(
+                "SELECT id FROM abuse_reports \
+                 WHERE contract_id = $1 AND reporter_key = $2 AND status = 'open'",
+            )
+            .bind(contract_uuid)
+            .bind(&key)
+            .fetch_one(&state.db)
+            .await
+            .map_err(|err| db_internal_error("fetch open abuse report", err))?,
+            duplicate: true,
+        },
+    };
+    Ok((StatusCode::ACCEPTED, Json(receipt)))
+}
+
+#[derive(sqlx::FromRow)]
+struct QueuedReport {
+    contract_address: String,
+    name: String,
+    moderation_status: ModerationStatus,
+    #[sqlx(flatten)]
+    report: AbuseReport,
+}
+
+/// Groups open reports by contract, most reported first and then the one
+/// waiting longest
+fn build_queue(rows: Vec<QueuedReport>) -> Vec<ModerationQueueEntry> {
+    let mut queue: Vec<ModerationQueueEntry> = Vec::new();
+    for row in rows {
+        match queue
+            .iter_mut()
+            .find(|entry| entry.contract_id == row.report.contract_id)
+        {
+            Some(entry) => entry.reports.push(row.report),
+            None => queue.push(ModerationQueueEntry {
+                contract_id: row.report.contract_id,
+                contract_address: row.contract_address,
+                name: row.name,
+                moderation_status: row.moderation_status,
+                reports: vec![row.report],
+            }),
+        }
+    }
+    for entry in &mut queue {
+        entry.reports.sort_by_key(|report| report.created_at);
+    }
+    queue.sort_by(|a, b| {
+        b.reports
+            .len()
+            .cmp(&a.reports.len())
+            .then(a.reports[0].created_at.cmp(&b.reports[0].created_at))
+    });
+    queue
+}
+
+/// GET /api/admin/reports — contracts with open abuse reports
+pub async fn list_reports(
+    State(state): State<AppState>,
+) -> ApiResult<Json<Vec<ModerationQueueEntry>>> {
+    let rows: Vec<QueuedReport> = sqlx::query_as(&format!(
+        "SELECT {}, c.contract_id AS contract_address, c.name, c.moderation_status \
+         FROM abuse_reports r JOIN contracts c ON c.id = r.contract_id \
+         WHERE r.status = 'open'",
+        REPORT_COLUMNS
+    ))
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("list abuse reports", err))?;
+    Ok(Json(build_queue(rows)))
+}
+
+/// The contract's new moderation status (None to leave it) and the status
+/// its open reports are closed with (None to leave them open)
+fn action_effects(
+    action: ModerationAction,
+) -> (Option<ModerationStatus>, Option<AbuseReportStatus>) {
+    match action {
+        ModerationAction::Dismiss => (None, Some(AbuseReportStatus::Dismissed)),
+        ModerationAction::Quarantine => (
+            Some(ModerationStatus::Quarantined),
+            Some(AbuseReportStatus::Quarantined),
+        ),
+        ModerationAction::Takedown => (
+            Some(ModerationStatus::TakenDown),
+            Some(AbuseReportStatus::TakenDown),
+        ),
+        ModerationAction::Restore => (Some(ModerationStatus::Active), None),
+    }
+}
+
+/// POST /api/admin/contracts/:id/moderate
+pub async fn moderate_contract(
+    State(state): State<AppState>,
+    tenant: CurrentTenant,
+    Path(id): Path<String>,
+    payload: Result<Json<ModerateContractRequest>, JsonRejection>,
+) -> ApiResult<Json<ModerationOutcome>> {
+    let Json(req) = payload.map_err(map_json_rejection)?;
+    let note = optional_text("note", req.note.as_deref(), MAX_NOTE_LENGTH)?;
+    let (contract_uuid, _) = fetch_contract_identity(&state, tenant.id, &id).await?;
+    let (contract_status, report_status) = action_effects(req.action);
+
+    let mut tx = state
+        .db
+        .begin()
+        .await
+        .map_err(|err| db_internal_error("begin moderation", err))?;
+
+    let moderation_status: ModerationStatus = sqlx::query_scalar(
+        "UPDATE contracts SET moderation_status = COALESCE($2, moderation_status) \
+         WHERE id = $1 RETURNING moderation_status",
+    )
+    .bind(contract_uuid)
+    .bind(contract_status)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|err| db_internal_error("moderate contract", err))?;
+
+    let resolved_reports = match report_status {
+        Some(status) => sqlx::query(
+            "UPDATE abuse_reports SET status = $2, resolution_note = $3, resolved_at = NOW() \
+             WHERE contract_id = $1 AND status = 'open'",
+        )
+        .bind(contract_uuid)
+        .bind(status)
+        .bind(&note)
+        .execute(&mut *tx)
+        .await
+        .map_err(|err| db_internal_error("resolve abuse reports", err))?
+        .rows_affected(),
+        None => 0,
+    };
+
+    tx.commit()
+        .await
+        .map_err(|err| db_internal_error("commit moderation", err))?;
+    // Quarantined and taken-down contracts drop out of the sparse index, and
+    // restored ones come back
+    crate::sparse_index::spawn_refresh(&state, contract_uuid);
+    tracing::info!(
+        contract = %contract_uuid,
+        action = ?req.action,
+        resolved_reports,
+        "contract moderated"
+    );
+    Ok(Json(ModerationOutcome {
+        contract_id: contract_uuid,
+        moderation_status,
+        resolved_reports,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Utc};
+
+    fn queued(contract: u128, minutes_ago: i64) -> QueuedReport {
+        QueuedReport {
+            contract_address: format!("C{}", contract),
+            name: format!("contract-{}", contract),
+            moderation_status: ModerationStatus::Active,
+            report: AbuseReport {
+                id: Uuid::new_v4(),
+                contract_id: Uuid::from_u128(contract),
+                reason: AbuseReason::Scam,
+                details: None,
+                reporter_contact: None,
+                reporter_address: None,
+                status: AbuseReportStatus::Open,
+                resolution_note: None,
+                created_at: Utc::now() - Duration::minutes(minutes_ago),
+                resolved_at: None,
+            },
+        }
+    }
+
+    #[test]
+    fn anonymous_reporters_are_keyed_by_a_hash_of_their_ip() {
+        let key = reporter_key(None, "203.0.113.10");
+        assert!(key.starts_with("ip:"));
+        assert!(!key.contains("203.0.113.10"));
+        assert_eq!(key, reporter_key(None, "203.0.113.10"));
+        assert_ne!(key, reporter_key(None, "203.0.113.11"));
+        assert_eq!(reporter_key(Some("GABC"), "203.0.113.10"), "account:GABC");
+    }
+
+    #[test]
+    fn report_text_is_trimmed_and_bounded() {
+        assert_eq!(optional_text("details", Some("  "), 10).unwrap(), None);
+        assert_eq!(
+            optional_text("details", Some(" drains funds "), 20).unwrap(),
+            Some("drains funds".to_string())
+        );
+        assert!(optional_text("details", Some(&"x".repeat(11)), 10).is_err());
+    }
+
+    #[test]
+    fn most_reported_contracts_come_first() {
+        let queue = build_queue(vec![
+            queued(1, 300),
+            queued(2, 10),
+            queued(3, 60),
+            queued(2, 20),
+        ]);
+        let order: Vec<u128> = queue.iter().map(|e| e.contract_id.as_u128()).collect();
+        assert_eq!(order, vec![2, 1, 3]);
+        assert!(queue[0].reports[0].created_at < queue[0].reports[1].created_at);
+    }
+
+    #[test]
+    fn only_restore_leaves_reports_open() {
+        assert_eq!(
+            action_effects(ModerationAction::Dismiss),
+            (None, Some(AbuseReportStatus::Dismissed))
+        );
+        assert_eq!(
+            action_effects(ModerationAction::Takedown).0,
+            Some(ModerationStatus::TakenDown)
+        );
+        assert_eq!(
+            action_effects(ModerationAction::Restore),
+            (Some(ModerationStatus::Active), None)
+        );
+    }
+}
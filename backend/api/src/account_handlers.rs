@@ -0,0 +1,331 @@
+// account_handlers.rs
+// The signed-in account's own data: a downloadable export of everything tied to
+// it, and deletion after a grace period during which it can be cancelled.
+
+use axum::{
+    extract::State,
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    Json,
+};
+use chrono::{Duration, Utc};
+use shared::{
+    AccountDeletion, AccountExport, AccountMembership, AccountStar, ApiKey, ContractAuditLog,
+    ContractReview, Publisher, SavedSearch, ACCOUNT_DELETION_GRACE_DAYS,
+};
+use uuid::Uuid;
+
+use crate::api_key_handlers::KEY_COLUMNS;
+use crate::collection_handlers::load_detail;
+use crate::error::{ApiError, ApiResult};
+use crate::handlers::db_internal_error;
+use crate::private_registry::Viewer;
+use crate::review_handlers::REVIEW_COLUMNS;
+use crate::state::AppState;
+use crate::tenancy::CurrentTenant;
+use crate::user_auth::AuthUser;
+
+/// What erasing an account removes, each bound to ($1 address, $2 tenant).
+/// Rows without a tenant of their own belong to the tenant of their contract.
+/// Contracts, versions and the audit log stay: they are the public record of
+/// what was published, so the publisher profile is anonymized instead, unless
+/// the profile is still shown by another tenant.
+const ERASE_STATEMENTS: &[&str] = &[
+    "DELETE FROM api_keys WHERE owner_address = $1 AND tenant_id = $2",
+    "DELETE FROM publish_sessions s USING contracts c \
+     WHERE c.id = s.contract_id AND s.owner_address = $1 AND c.tenant_id = $2",
+    "DELETE FROM saved_searches WHERE owner_address = $1 AND tenant_id = $2",
+    "DELETE FROM collections WHERE owner_address = $1 AND tenant_id = $2",
+    "DELETE FROM contract_stars s USING contracts c \
+     WHERE c.id = s.contract_id AND s.user_address = $1 AND c.tenant_id = $2",
+    "DELETE FROM review_flags f USING reviews r, contracts c \
+     WHERE r.id = f.review_id AND c.id = r.contract_id \
+       AND f.reporter_address = $1 AND c.tenant_id = $2",
+    "DELETE FROM reviews r USING contracts c \
+     WHERE c.id = r.contract_id AND r.reviewer_address = $1 AND c.tenant_id = $2",
+    "DELETE FROM organization_members m USING organizations o \
+     WHERE o.id = m.organization_id AND m.address = $1 AND o.tenant_id = $2",
+    "UPDATE publishers p SET username = NULL, email = NULL, github_url = NULL, website = NULL, \
+     verified_account = NULL, identity_verified_at = NULL \
+     WHERE p.stellar_address = $1 AND COALESCE(p.namespace_tenant_id, $2) = $2 \
+       AND NOT EXISTS (SELECT 1 FROM contracts c \
+                       WHERE c.publisher_id = p.id AND c.tenant_id <> $2)",
+];
+
+fn export_filename(address: &str, date: chrono::NaiveDate) -> String {
+    let address: String = address
+        .chars()
+        .filter(char::is_ascii_alphanumeric)
+        .take(12)
+        .collect();
+    format!(
+        "account-export-{}-{}.json",
+        address,
+        date.format("%Y-%m-%d")
+    )
+}
+
+async fn fetch_deletion(
+    state: &AppState,
+    user: &AuthUser,
+    tenant: &CurrentTenant,
+) -> ApiResult<Option<AccountDeletion>> {
+    sqlx::query_as(
+        "SELECT address, requested_at, scheduled_for FROM account_deletions \
+         WHERE address = $1 AND tenant_id = $2",
+    )
+    .bind(&user.address)
+    .bind(tenant.id)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|err| db_internal_error("fetch account deletion", err))
+}
+
+/// GET /api/me/export — served as a JSON attachment
+pub async fn export_account(
+    State(state): State<AppState>,
+    user: AuthUser,
+    tenant: CurrentTenant,
+) -> ApiResult<(HeaderMap, Json<AccountExport>)> {
+    user.require_session()?;
+    let address = user.address.as_str();
+
+    let profile: Option<Publisher> =
+        sqlx::query_as("SELECT * FROM publishers WHERE stellar_address = $1")
+            .bind(address)
+            .fetch_optional(&state.db)
+            .await
+            .map_err(|err| db_internal_error("export profile", err))?;
+
+    let api_keys: Vec<ApiKey> = sqlx::query_as(&format!(
+        "SELECT {} FROM api_keys WHERE owner_address = $1 AND tenant_id = $2 \
+         ORDER BY created_at",
+        KEY_COLUMNS
+    ))
+    .bind(address)
+    .bind(tenant.id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("export api keys", err))?;
+
+    let reviews: Vec<ContractReview> = sqlx::query_as(&format!(
+        "SELECT {} FROM reviews r JOIN contracts c ON c.id = r.contract_id \
+         WHERE r.reviewer_address = $1 AND c.tenant_id = $2 ORDER BY r.created_at",
+        REVIEW_COLUMNS
+    ))
+    .bind(address)
+    .bind(tenant.id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("export reviews", err))?;
+
+    let stars: Vec<AccountStar> = sqlx::query_as(
+        "SELECT s.contract_id, s.created_at AS starred_at FROM contract_stars s \
+         JOIN contracts c ON c.id = s.contract_id \
+         WHERE s.user_address = $1 AND c.tenant_id = $2 ORDER BY s.created_at",
+    )
+    .bind(address)
+    .bind(tenant.id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("export stars", err))?;
+
+    let saved_searches: Vec<SavedSearch> = sqlx::query_as(
+        "SELECT * FROM saved_searches WHERE owner_address = $1 AND tenant_id = $2 ORDER BY name",
+    )
+    .bind(address)
+    .bind(tenant.id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("export saved searches", err))?;
+
+    let collection_ids: Vec<Uuid> = sqlx::query_scalar(
+        "SELECT id FROM collections WHERE owner_address = $1 AND tenant_id = $2 ORDER BY name",
+    )
+    .bind(address)
+    .bind(tenant.id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("export collections", err))?;
+    let viewer = Viewer {
+        address: Some(user.address.clone()),
+        tenant_id: tenant.id,
+    };
+    let mut collections = Vec::with_capacity(collection_ids.len());
+    for id in collection_ids {
+        collections.push(load_detail(&state, &viewer, id).await?);
+    }
+
+    let organizations: Vec<AccountMembership> = sqlx::query_as(
+        "SELECT o.name AS organization, m.role, m.added_at \
+         FROM organization_members m JOIN organizations o ON o.id = m.organization_id \
+         WHERE m.address = $1 AND o.tenant_id = $2 ORDER BY o.name",
+    )
+    .bind(address)
+    .bind(tenant.id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("export organizations", err))?;
+
+    let audit_entries: Vec<ContractAuditLog> = sqlx::query_as(
+        "SELECT id, contract_id, action_type, old_value, new_value, changed_by, timestamp, \
+                previous_hash, hash, signature \
+         FROM contract_audit_log WHERE changed_by = $1 ORDER BY timestamp",
+    )
+    .bind(address)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("export audit entries", err))?;
+
+    let pending_deletion = fetch_deletion(&state, &user, &tenant).await?;
+
+    let exported_at = Utc::now();
+    let mut headers = HeaderMap::new();
+    let disposition = format!(
+        "attachment; filename=\"{}\"",
+        export_filename(address, exported_at.date_naive())
+    );
+    if let Ok(value) = HeaderValue::from_str(&disposition) {
+        headers.insert(header::CONTENT_DISPOSITION, value);
+    }
+
+    Ok((
+        headers,
+        Json(AccountExport {
+            address: user.address.clone(),
+            exported_at,
+            profile,
+            api_keys,
+            reviews,
+            stars,
+            saved_searches,
+            collections,
+            organizations,
+            audit_entries,
+            pending_deletion,
+        }),
+    ))
+}
+
+/// DELETE /api/me — schedule the account's data for deletion. Asking again
+/// keeps the original schedule.
+pub async fn request_account_deletion(
+    State(state): State<AppState>,
+    user: AuthUser,
+    tenant: CurrentTenant,
+) -> ApiResult<(StatusCode, Json<AccountDeletion>)> {
+    user.require_session()?;
+    let scheduled_for = Utc::now() + Duration::days(ACCOUNT_DELETION_GRACE_DAYS);
+    sqlx::query(
+        "INSERT INTO account_deletions (tenant_id, address, scheduled_for) VALUES ($1, $2, $3) \
+         ON CONFLICT (tenant_id, address) DO NOTHING",
+    )
+    .bind(tenant.id)
+    .bind(&user.address)
+    .bind(scheduled_for)
+    .execute(&state.db)
+    .await
+    .map_err(|err| db_internal_error("schedule account deletion", err))?;
+
+    let deletion = fetch_deletion(&state, &user, &tenant)
+        .await?
+        .ok_or_else(|| ApiError::internal("Account deletion was not recorded"))?;
+    Ok((StatusCode::ACCEPTED, Json(deletion)))
+}
+
+/// GET /api/me/deletion
+pub async fn get_account_deletion(
+    State(state): State<AppState>,
+    user: AuthUser,
+    tenant: CurrentTenant,
+) -> ApiResult<Json<AccountDeletion>> {
+    user.require_session()?;
+    fetch_deletion(&state, &user, &tenant)
+        .await?
+        .map(Json)
+        .ok_or_else(no_pending_deletion)
+}
+
+/// DELETE /api/me/deletion — cancel a pending deletion
+pub async fn cancel_account_deletion(
+    State(state): State<AppState>,
+    user: AuthUser,
+    tenant: CurrentTenant,
+) -> ApiResult<StatusCode> {
+    user.require_session()?;
+    let result = sqlx::query("DELETE FROM account_deletions WHERE address = $1 AND tenant_id = $2")
+        .bind(&user.address)
+        .bind(tenant.id)
+        .execute(&state.db)
+        .await
+        .map_err(|err| db_internal_error("cancel account deletion", err))?;
+    if result.rows_affected() == 0 {
+        return Err(no_pending_deletion());
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+fn no_pending_deletion() -> ApiError {
+    ApiError::not_found("DeletionNotFound", "This account has no pending deletion")
+}
+
+/// Erase accounts whose grace period has passed; run hourly by the aggregation task
+pub async fn purge_due_deletions(pool: &sqlx::PgPool) -> Result<u64, sqlx::Error> {
+    let due: Vec<(Uuid, String)> = sqlx::query_as(
+        "SELECT tenant_id, address FROM account_deletions WHERE scheduled_for <= NOW()",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut erased = 0;
+    for (tenant_id, address) in due {
+        let mut tx = pool.begin().await?;
+        for statement in ERASE_STATEMENTS {
+            sqlx::query(statement)
+                .bind(&address)
+                .bind(tenant_id)
+                .execute(&mut *tx)
+                .await?;
+        }
+        sqlx::query("DELETE FROM account_deletions WHERE tenant_id = $1 AND address = $2")
+            .bind(tenant_id)
+            .bind(&address)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        erased += 1;
+    }
+    Ok(erased)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn erasure_is_scoped_to_the_account_and_keeps_the_audit_log() {
+        for statement in ERASE_STATEMENTS {
+            assert!(statement.contains("= $1"), "{}", statement);
+            assert!(statement.contains("$2"), "not tenant-scoped: {}", statement);
+            assert!(!statement.contains("contract_audit_log"), "{}", statement);
+            assert!(
+                !statement.contains("DELETE FROM publishers"),
+                "{}",
+                statement
+            );
+            assert!(
+                !statement.contains("DELETE FROM contracts"),
+                "{}",
+                statement
+            );
+        }
+    }
+
+    #[test]
+    fn export_filename_is_header_safe() {
+        let date = chrono::NaiveDate::from_ymd_opt(2026, 3, 1).unwrap();
+        assert_eq!(
+            export_filename("GABC\"DEF/../x", date),
+            "account-export-GABCDEFx-2026-03-01.json"
+        );
+    }
+}
@@ -1,6 +1,7 @@
 use crate::{
     error::{ApiError, ApiResult},
     handlers::db_internal_error,
+    private_registry::Viewer,
     state::AppState,
 };
 use axum::{
@@ -30,6 +31,7 @@ fn default_limit() -> i64 {
 
 pub async fn get_activity_feed(
     State(state): State<AppState>,
+    viewer: Viewer,
     Query(query): Query<ActivityFeedQuery>,
 ) -> ApiResult<Json<PaginatedResponse<ActivityFeedEntry>>> {
     let limit = query.limit.clamp(1, 100);
@@ -56,6 +58,8 @@ pub async fn get_activity_feed(
         "#,
     );
 
+    sql.push_str(&viewer.visibility_clause("c."));
+
     let mut bind_index = 2; // $1 is start_time
 
     if !query.event_types.is_empty() {
@@ -0,0 +1,72 @@
+// admin_auth.rs
+// Bearer-token guard for operator-only /api/admin endpoints.
+
+use axum::{
+    extract::Request,
+    http::{header, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+
+use crate::error::ApiError;
+
+/// Environment variable holding the shared admin token
+pub const ADMIN_TOKEN_ENV: &str = "ADMIN_API_TOKEN";
+
+/// Compare without short-circuiting so the token can't be recovered by timing
+fn tokens_match(given: &[u8], expected: &[u8]) -> bool {
+    given.len() == expected.len()
+        && given
+            .iter()
+            .zip(expected)
+            .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+/// Reject requests that don't carry `Authorization: Bearer $ADMIN_API_TOKEN`.
+/// Admin endpoints stay disabled entirely when the variable is unset.
+pub async fn require_admin(request: Request, next: Next) -> Response {
+    let expected = match std::env::var(ADMIN_TOKEN_ENV) {
+        Ok(token) if !token.is_empty() => token,
+        _ => {
+            return ApiError::new(
+                StatusCode::FORBIDDEN,
+                "Forbidden",
+                format!("Admin endpoints are disabled; set {}", ADMIN_TOKEN_ENV),
+            )
+            .into_response()
+        }
+    };
+
+    let given = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .map(str::trim);
+
+    match given {
+        Some(token) if tokens_match(token.as_bytes(), expected.as_bytes()) => {
+            next.run(request).await
+        }
+        _ => ApiError::new(
+            StatusCode::UNAUTHORIZED,
+            "Unauthorized",
+            "A valid admin bearer token is required",
+        )
+        .into_response(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn token_comparison_requires_exact_match() {
+        assert!(tokens_match(b"secret", b"secret"));
+        assert!(!tokens_match(b"secret", b"secreT"));
+        assert!(!tokens_match(b"secret", b"secret-longer"));
+        assert!(!tokens_match(b"", b"secret"));
+    }
+}
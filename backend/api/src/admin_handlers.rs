@@ -0,0 +1,89 @@
+// admin_handlers.rs
+// Operational overview for registry operators.
+
+use std::collections::BTreeMap;
+
+use axum::{extract::State, Json};
+use chrono::Utc;
+use shared::{AdminOverview, LargestContract, PublishRate};
+
+use crate::error::ApiResult;
+use crate::handlers::db_internal_error;
+use crate::state::AppState;
+
+/// Number of contracts and endpoints listed in each ranking
+const OVERVIEW_TOP_N: i64 = 10;
+
+/// GET /api/admin/overview
+pub async fn get_overview(State(state): State<AppState>) -> ApiResult<Json<AdminOverview>> {
+    let database_size_bytes: i64 =
+        sqlx::query_scalar("SELECT pg_database_size(current_database())")
+            .fetch_one(&state.db)
+            .await
+            .map_err(|err| db_internal_error("fetch database size", err))?;
+
+    let largest_contracts: Vec<LargestContract> = sqlx::query_as(
+        "SELECT c.id, c.contract_id, c.name, \
+                (SELECT COUNT(*) FROM contract_versions cv WHERE cv.contract_id = c.id) \
+                    AS version_count, \
+                COALESCE((SELECT SUM(pg_column_size(a.abi)) FROM contract_abis a \
+                          WHERE a.contract_id = c.id), 0)::BIGINT AS abi_bytes \
+         FROM contracts c \
+         ORDER BY abi_bytes DESC, version_count DESC \
+         LIMIT $1",
+    )
+    .bind(OVERVIEW_TOP_N)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("fetch largest contracts", err))?;
+
+    let (last_hour, last_24h, last_7d): (i64, i64, i64) = sqlx::query_as(
+        "SELECT COUNT(*) FILTER (WHERE created_at > NOW() - INTERVAL '1 hour'), \
+                COUNT(*) FILTER (WHERE created_at > NOW() - INTERVAL '24 hours'), \
+                COUNT(*) \
+         FROM contract_versions \
+         WHERE created_at > NOW() - INTERVAL '7 days'",
+    )
+    .fetch_one(&state.db)
+    .await
+    .map_err(|err| db_internal_error("fetch publish rate", err))?;
+
+    let failed_verifications: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM ( \
+             SELECT DISTINCT ON (contract_id) status FROM verifications \
+             ORDER BY contract_id, created_at DESC \
+         ) latest WHERE status = 'failed'",
+    )
+    .fetch_one(&state.db)
+    .await
+    .map_err(|err| db_internal_error("count failed verifications", err))?;
+
+    let pending_jobs: BTreeMap<String, i64> = sqlx::query_as::<_, (String, i64)>(
+        "SELECT 'verifications'::TEXT, COUNT(*) FROM verifications WHERE status = 'pending' \
+         UNION ALL \
+         SELECT 'regression_test_runs', COUNT(*) FROM regression_test_runs \
+             WHERE status IN ('pending', 'running') \
+         UNION ALL \
+         SELECT 'canary_releases', COUNT(*) FROM canary_releases WHERE status = 'pending'",
+    )
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("count pending jobs", err))?
+    .into_iter()
+    .collect();
+
+    Ok(Json(AdminOverview {
+        database_size_bytes,
+        largest_contracts,
+        publish_rate: PublishRate {
+            last_hour,
+            last_24h,
+            last_7d,
+        },
+        failed_verifications,
+        pending_jobs,
+        slowest_endpoints: crate::metrics::slowest_endpoints(OVERVIEW_TOP_N as usize),
+        rpc: state.rpc.health(),
+        generated_at: Utc::now(),
+    }))
+}
@@ -7,6 +7,11 @@ use chrono::Timelike;
 /// Runs every hour:
 ///   1. Aggregate raw events into daily summaries (yesterday + today).
 ///   2. Delete raw events older than 90 days.
+///   3. Purge idempotency keys past their 24h replay window and telemetry
+///      events past their 90-day retention.
+///   4. Cancel expired publish sessions and collect orphaned artifacts.
+///   5. Snapshot daily registry statistics for the time-series endpoint.
+///   6. Refresh the search vocabulary used for "did you mean" suggestions.
 pub fn spawn_aggregation_task(pool: PgPool) {
     tokio::spawn(async move {
         let mut interval = tokio::time::interval(Duration::from_secs(3600));
@@ -23,6 +28,65 @@ pub fn spawn_aggregation_task(pool: PgPool) {
                 tracing::error!(error = ?err, "aggregation: retention cleanup failed");
             }
 
+            match crate::idempotency::purge_expired(&pool).await {
+                Ok(deleted) if deleted > 0 => {
+                    tracing::info!(deleted, "aggregation: purged expired idempotency keys")
+                }
+                Ok(_) => {}
+                Err(err) => {
+                    tracing::error!(error = ?err, "aggregation: idempotency key purge failed")
+                }
+            }
+
+            match crate::telemetry_handlers::purge_expired(&pool).await {
+                Ok(deleted) if deleted > 0 => {
+                    tracing::info!(deleted, "aggregation: purged expired telemetry events")
+                }
+                Ok(_) => {}
+                Err(err) => {
+                    tracing::error!(error = ?err, "aggregation: telemetry purge failed")
+                }
+            }
+
+            match crate::api_usage::purge_expired(&pool).await {
+                Ok(deleted) if deleted > 0 => {
+                    tracing::info!(deleted, "aggregation: purged expired api usage rows")
+                }
+                Ok(_) => {}
+                Err(err) => {
+                    tracing::error!(error = ?err, "aggregation: api usage purge failed")
+                }
+            }
+
+            match crate::account_handlers::purge_due_deletions(&pool).await {
+                Ok(erased) if erased > 0 => {
+                    tracing::info!(erased, "aggregation: erased accounts past deletion date")
+                }
+                Ok(_) => {}
+                Err(err) => {
+                    tracing::error!(error = ?err, "aggregation: account deletion failed")
+                }
+            }
+
+            match crate::artifact_gc::collect(&pool, false).await {
+                Ok(report) if report.total_bytes > 0 => tracing::info!(
+                    reclaimed_bytes = report.total_bytes,
+                    "aggregation: collected orphaned artifacts"
+                ),
+                Ok(_) => {}
+                Err(err) => {
+                    tracing::error!(error = ?err, "aggregation: artifact collection failed")
+                }
+            }
+
+            if let Err(err) = crate::stats_handlers::snapshot_daily(&pool).await {
+                tracing::error!(error = ?err, "aggregation: stats snapshot failed");
+            }
+
+            if let Err(err) = crate::search_suggestions::refresh_terms(&pool).await {
+                tracing::error!(error = ?err, "aggregation: search term refresh failed");
+            }
+
             if let Err(err) = run_custom_metrics_aggregation(&pool).await {
                 tracing::error!(error = ?err, "aggregation: custom metrics aggregation failed");
             }
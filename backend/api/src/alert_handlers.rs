@@ -0,0 +1,555 @@
+// alert_handlers.rs
+// Security advisories against a contract's versions, and the alerts they (and
+// critical patches) raise for every contract depending on an affected version.
+
+use std::collections::{BTreeMap, HashSet, VecDeque};
+use std::time::Duration;
+
+use axum::{
+    extract::{rejection::JsonRejection, Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use serde_json::json;
+use shared::{
+    AdvisoryCreated, AlertSettings, AlertSource, AlertsQuery, ApiScope, CreateAdvisoryRequest,
+    DependencyAlert, PatchSeverity, SecurityAdvisory, SemVer, VersionRange,
+};
+use sqlx::PgConnection;
+use uuid::Uuid;
+
+use crate::error::{ApiError, ApiResult};
+use crate::handlers::{db_internal_error, fetch_contract_identity, map_json_rejection};
+use crate::private_registry::{ensure_contract_visible, Viewer};
+use crate::state::AppState;
+use crate::tenancy::CurrentTenant;
+use crate::user_auth::AuthUser;
+use crate::webhook_target::{validate_webhook_url, webhook_client};
+
+const MAX_TITLE_LENGTH: usize = 255;
+const MAX_RANGE_LENGTH: usize = 100;
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// `dependent` declares a dependency on `dependency` with `version_constraint`
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub(crate) struct DependencyEdge {
+    pub dependent: Uuid,
+    pub dependency: Uuid,
+    pub version_constraint: String,
+}
+
+/// A contract reached from the vulnerable one through the dependents graph
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct AffectedDependent {
+    pub contract_id: Uuid,
+    /// From the vulnerable contract to this one
+    pub path: Vec<Uuid>,
+    pub constraint: String,
+}
+
+/// What an alert is about, shared by every dependent it reaches
+pub(crate) struct AlertCause<'a> {
+    pub source: AlertSource,
+    pub source_id: Uuid,
+    pub severity: PatchSeverity,
+    pub summary: &'a str,
+}
+
+/// Whether a dependency constraint admits one of the affected versions.
+/// Constraints that aren't SemVer ranges are assumed to.
+fn constraint_admits(constraint: &str, affected: &[String]) -> bool {
+    match VersionRange::parse(constraint) {
+        Some(range) => affected.iter().any(|version| range.matches_str(version)),
+        None => true,
+    }
+}
+
+/// Walk the dependents graph out from `source`. Direct dependents are affected
+/// when their constraint admits an affected version; anything depending on an
+/// affected dependent is affected too, since dependencies aren't tracked per
+/// version.
+fn propagate(
+    source: Uuid,
+    affected: &[String],
+    edges: &[DependencyEdge],
+) -> Vec<AffectedDependent> {
+    let mut found = Vec::new();
+    let mut seen = HashSet::from([source]);
+    let mut queue = VecDeque::from([vec![source]]);
+    while let Some(path) = queue.pop_front() {
+        let current = *path.last().expect("paths are never empty");
+        for edge in edges.iter().filter(|edge| edge.dependency == current) {
+            if seen.contains(&edge.dependent)
+                || (current == source && !constraint_admits(&edge.version_constraint, affected))
+            {
+                continue;
+            }
+            seen.insert(edge.dependent);
+            let mut next = path.clone();
+            next.push(edge.dependent);
+            found.push(AffectedDependent {
+                contract_id: edge.dependent,
+                path: next.clone(),
+                constraint: edge.version_constraint.clone(),
+            });
+            queue.push_back(next);
+        }
+    }
+    found
+}
+
+/// Record an alert for every contract depending on an affected version of one
+/// of `vulnerable` (contract → affected versions). Alerts already raised for
+/// the same source are skipped; the new ones are returned for delivery.
+pub async fn raise_alerts(
+    conn: &mut PgConnection,
+    source: AlertSource,
+    source_id: Uuid,
+    severity: PatchSeverity,
+    summary: &str,
+    vulnerable: &BTreeMap<Uuid, Vec<String>>,
+) -> ApiResult<Vec<DependencyAlert>> {
+    let edges: Vec<DependencyEdge> = sqlx::query_as(
+        "SELECT contract_id AS dependent, dependency_contract_id AS dependency, \
+                version_constraint \
+         FROM contract_dependencies WHERE dependency_contract_id IS NOT NULL",
+    )
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|err| db_internal_error("load dependency graph", err))?;
+
+    let cause = AlertCause {
+        source,
+        source_id,
+        severity,
+        summary,
+    };
+    let mut alerts = Vec::new();
+    for (&vulnerable_id, versions) in vulnerable {
+        for dependent in propagate(vulnerable_id, versions, &edges) {
+            alerts.extend(record_alert(conn, &cause, vulnerable_id, versions, &dependent).await?);
+        }
+    }
+    Ok(alerts)
+}
+
+/// Insert an alert for `dependent` with its current deployments; `None` when
+/// one was already raised for the same source
+pub(crate) async fn record_alert(
+    conn: &mut PgConnection,
+    cause: &AlertCause<'_>,
+    vulnerable_id: Uuid,
+    versions: &[String],
+    dependent: &AffectedDependent,
+) -> ApiResult<Option<DependencyAlert>> {
+    let deployments: Vec<(String, String, String)> = sqlx::query_as(
+        "SELECT environment::text, status::text, wasm_hash FROM contract_deployments \
+         WHERE contract_id = $1 ORDER BY environment",
+    )
+    .bind(dependent.contract_id)
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|err| db_internal_error("list dependent deployments", err))?;
+    let deployments: Vec<_> = deployments
+        .into_iter()
+        .map(|(environment, status, wasm_hash)| {
+            json!({ "environment": environment, "status": status, "wasm_hash": wasm_hash })
+        })
+        .collect();
+
+    sqlx::query_as(
+        "INSERT INTO dependency_alerts \
+             (owner_address, source, source_id, severity, summary, vulnerable_contract_id, \
+              affected_versions, contract_id, dependency_path, version_constraint, \
+              deployments) \
+         SELECT p.stellar_address, $1, $2, $3, $4, $5, $6, c.id, $7, $8, $9 \
+         FROM contracts c JOIN publishers p ON p.id = c.publisher_id \
+         WHERE c.id = $10 \
+         ON CONFLICT (source_id, vulnerable_contract_id, contract_id) DO NOTHING \
+         RETURNING *",
+    )
+    .bind(cause.source)
+    .bind(cause.source_id)
+    .bind(cause.severity)
+    .bind(cause.summary)
+    .bind(vulnerable_id)
+    .bind(versions)
+    .bind(&dependent.path)
+    .bind(&dependent.constraint)
+    .bind(serde_json::Value::from(deployments))
+    .bind(dependent.contract_id)
+    .fetch_optional(&mut *conn)
+    .await
+    .map_err(|err| db_internal_error("record dependency alert", err))
+}
+
+/// Push new alerts to their owners' webhook and email. Runs in the background
+/// once the alerts are committed; failures are logged.
+pub async fn deliver_alerts(state: AppState, alerts: Vec<DependencyAlert>) {
+    for alert in &alerts {
+        if let Err(err) = deliver_alert(&state, alert).await {
+            tracing::warn!(alert = %alert.id, error = ?err, "failed to deliver dependency alert");
+        }
+    }
+}
+
+async fn deliver_alert(state: &AppState, alert: &DependencyAlert) -> Result<(), sqlx::Error> {
+    let settings: AlertSettings =
+        sqlx::query_as("SELECT webhook_url, email FROM alert_settings WHERE owner_address = $1")
+            .bind(&alert.owner_address)
+            .fetch_optional(&state.db)
+            .await?
+            .unwrap_or_default();
+
+    if let Some(url) = &settings.webhook_url {
+        let event = json!({ "event": "dependency.alert", "alert": alert });
+        // The host is checked again here: DNS may have moved since the URL was saved
+        let status = match webhook_client(url, WEBHOOK_TIMEOUT).await {
+            Err(err) => {
+                tracing::warn!(url = %url, error = %err, "alert webhook refused");
+                "failed"
+            }
+            Ok((client, target)) => match client.post(target).json(&event).send().await {
+                Ok(response) if response.status().is_success() => "delivered",
+                Ok(response) => {
+                    tracing::warn!(url = %url, status = %response.status(), "alert webhook rejected");
+                    "failed"
+                }
+                Err(err) => {
+                    tracing::warn!(url = %url, error = %err, "alert webhook failed");
+                    "failed"
+                }
+            },
+        };
+        sqlx::query("UPDATE dependency_alerts SET webhook_status = $1 WHERE id = $2")
+            .bind(status)
+            .bind(alert.id)
+            .execute(&state.db)
+            .await?;
+    }
+
+    let email: Option<String> =
+        match settings.email {
+            Some(email) => Some(email),
+            None => sqlx::query_scalar(
+                "SELECT email FROM publishers WHERE stellar_address = $1 AND email IS NOT NULL \
+             LIMIT 1",
+            )
+            .bind(&alert.owner_address)
+            .fetch_optional(&state.db)
+            .await?,
+        };
+    if let Some(email) = email {
+        sqlx::query(
+            "INSERT INTO notification_logs \
+                 (contract_id, notification_type, recipients, message, status) \
+             VALUES ($1, 'dependency_alert', $2, $3, 'pending')",
+        )
+        .bind(alert.contract_id)
+        .bind(vec![email])
+        .bind(&alert.summary)
+        .execute(&state.db)
+        .await?;
+    }
+    Ok(())
+}
+
+fn invalid(message: impl Into<String>) -> ApiError {
+    ApiError::bad_request("InvalidAdvisory", message)
+}
+
+fn validate_advisory(req: &CreateAdvisoryRequest) -> ApiResult<()> {
+    let range = req.affected_versions.trim();
+    if range.is_empty() || range.len() > MAX_RANGE_LENGTH || VersionRange::parse(range).is_none() {
+        return Err(invalid(
+            "affected_versions must be a SemVer version or range, e.g. \">=1.0.0, <1.4.2\"",
+        ));
+    }
+    let title = req.title.trim();
+    if title.is_empty() || title.len() > MAX_TITLE_LENGTH {
+        return Err(invalid(format!(
+            "title must be 1-{} characters",
+            MAX_TITLE_LENGTH
+        )));
+    }
+    if req
+        .patched_version
+        .as_deref()
+        .is_some_and(|v| SemVer::parse(v).is_none())
+    {
+        return Err(invalid("patched_version must be a SemVer version"));
+    }
+    Ok(())
+}
+
+/// POST /api/contracts/:id/advisories — disclose a vulnerability and alert
+/// the owners of every contract depending on an affected version
+pub async fn create_advisory(
+    State(state): State<AppState>,
+    tenant: CurrentTenant,
+    user: AuthUser,
+    Path(id): Path<String>,
+    payload: Result<Json<CreateAdvisoryRequest>, JsonRejection>,
+) -> ApiResult<(StatusCode, Json<AdvisoryCreated>)> {
+    user.require(ApiScope::PatchAdmin)?;
+    let Json(req) = payload.map_err(map_json_rejection)?;
+    validate_advisory(&req)?;
+    let (contract_uuid, contract_id) = fetch_contract_identity(&state, tenant.id, &id).await?;
+
+    let mut tx = state
+        .db
+        .begin()
+        .await
+        .map_err(|err| db_internal_error("begin advisory", err))?;
+    let advisory: SecurityAdvisory = sqlx::query_as(
+        "INSERT INTO security_advisories \
+             (contract_id, affected_versions, severity, title, description, patched_version, \
+              created_by) \
+         VALUES ($1, $2, $3, $4, $5, $6, $7) \
+         RETURNING *",
+    )
+    .bind(contract_uuid)
+    .bind(req.affected_versions.trim())
+    .bind(req.severity)
+    .bind(req.title.trim())
+    .bind(&req.description)
+    .bind(&req.patched_version)
+    .bind(&user.address)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|err| db_internal_error("create advisory", err))?;
+
+    let published: Vec<String> =
+        sqlx::query_scalar("SELECT version FROM contract_versions WHERE contract_id = $1")
+            .bind(contract_uuid)
+            .fetch_all(&mut *tx)
+            .await
+            .map_err(|err| db_internal_error("list advised versions", err))?;
+    let range = VersionRange::parse(&advisory.affected_versions);
+    let affected: Vec<String> = published
+        .into_iter()
+        .filter(|version| range.as_ref().is_some_and(|r| r.matches_str(version)))
+        .collect();
+
+    let alerts = if affected.is_empty() {
+        Vec::new()
+    } else {
+        let summary = format!(
+            "{} advisory for {} {}: {}",
+            severity_label(advisory.severity),
+            contract_id,
+            advisory.affected_versions,
+            advisory.title
+        );
+        raise_alerts(
+            &mut tx,
+            AlertSource::Advisory,
+            advisory.id,
+            advisory.severity,
+            &summary,
+            &BTreeMap::from([(contract_uuid, affected)]),
+        )
+        .await?
+    };
+    tx.commit()
+        .await
+        .map_err(|err| db_internal_error("commit advisory", err))?;
+
+    let alerts_created = alerts.len();
+    tokio::spawn(deliver_alerts(state.clone(), alerts));
+    Ok((
+        StatusCode::CREATED,
+        Json(AdvisoryCreated {
+            advisory,
+            alerts_created,
+        }),
+    ))
+}
+
+fn severity_label(severity: PatchSeverity) -> &'static str {
+    match severity {
+        PatchSeverity::Critical => "Critical",
+        PatchSeverity::High => "High",
+        PatchSeverity::Medium => "Medium",
+        PatchSeverity::Low => "Low",
+    }
+}
+
+/// GET /api/contracts/:id/advisories — newest first
+pub async fn list_advisories(
+    State(state): State<AppState>,
+    viewer: Viewer,
+    Path(id): Path<String>,
+) -> ApiResult<Json<Vec<SecurityAdvisory>>> {
+    let (contract_uuid, _) = fetch_contract_identity(&state, viewer.tenant_id, &id).await?;
+    ensure_contract_visible(&state, &viewer, contract_uuid).await?;
+    let advisories: Vec<SecurityAdvisory> = sqlx::query_as(
+        "SELECT * FROM security_advisories WHERE contract_id = $1 ORDER BY created_at DESC",
+    )
+    .bind(contract_uuid)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("list advisories", err))?;
+    Ok(Json(advisories))
+}
+
+/// GET /api/me/alerts?all= — alerts for the caller's contracts, newest first;
+/// acknowledged ones only with `all=true`
+pub async fn list_my_alerts(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Query(query): Query<AlertsQuery>,
+) -> ApiResult<Json<Vec<DependencyAlert>>> {
+    user.require(ApiScope::Read)?;
+    let alerts: Vec<DependencyAlert> = sqlx::query_as(
+        "SELECT * FROM dependency_alerts \
+         WHERE owner_address = $1 AND ($2 OR acknowledged_at IS NULL) \
+         ORDER BY created_at DESC",
+    )
+    .bind(&user.address)
+    .bind(query.all)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("list alerts", err))?;
+    Ok(Json(alerts))
+}
+
+/// POST /api/me/alerts/:id/acknowledge
+pub async fn acknowledge_alert(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(id): Path<Uuid>,
+) -> ApiResult<Json<DependencyAlert>> {
+    user.require(ApiScope::Read)?;
+    sqlx::query_as(
+        "UPDATE dependency_alerts SET acknowledged_at = COALESCE(acknowledged_at, NOW()) \
+         WHERE id = $1 AND owner_address = $2 \
+         RETURNING *",
+    )
+    .bind(id)
+    .bind(&user.address)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|err| db_internal_error("acknowledge alert", err))?
+    .map(Json)
+    .ok_or_else(|| ApiError::not_found("AlertNotFound", format!("No alert found with ID: {}", id)))
+}
+
+/// GET /api/me/alerts/settings
+pub async fn get_alert_settings(
+    State(state): State<AppState>,
+    user: AuthUser,
+) -> ApiResult<Json<AlertSettings>> {
+    user.require(ApiScope::Read)?;
+    let settings: Option<AlertSettings> =
+        sqlx::query_as("SELECT webhook_url, email FROM alert_settings WHERE owner_address = $1")
+            .bind(&user.address)
+            .fetch_optional(&state.db)
+            .await
+            .map_err(|err| db_internal_error("fetch alert settings", err))?;
+    Ok(Json(settings.unwrap_or_default()))
+}
+
+/// PUT /api/me/alerts/settings — where alerts are pushed; omitted fields are cleared
+pub async fn update_alert_settings(
+    State(state): State<AppState>,
+    user: AuthUser,
+    payload: Result<Json<AlertSettings>, JsonRejection>,
+) -> ApiResult<Json<AlertSettings>> {
+    user.require(ApiScope::Publish)?;
+    let Json(req) = payload.map_err(map_json_rejection)?;
+    if let Some(url) = &req.webhook_url {
+        validate_webhook_url(url).await?;
+    }
+    if req
+        .email
+        .as_deref()
+        .is_some_and(|email| !email.contains('@'))
+    {
+        return Err(ApiError::bad_request(
+            "InvalidEmail",
+            "email must be an email address",
+        ));
+    }
+    let settings: AlertSettings = sqlx::query_as(
+        "INSERT INTO alert_settings (owner_address, webhook_url, email) VALUES ($1, $2, $3) \
+         ON CONFLICT (owner_address) DO UPDATE \
+         SET webhook_url = EXCLUDED.webhook_url, email = EXCLUDED.email, updated_at = NOW() \
+         RETURNING webhook_url, email",
+    )
+    .bind(&user.address)
+    .bind(&req.webhook_url)
+    .bind(&req.email)
+    .fetch_one(&state.db)
+    .await
+    .map_err(|err| db_internal_error("update alert settings", err))?;
+    Ok(Json(settings))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edge(dependent: Uuid, dependency: Uuid, constraint: &str) -> DependencyEdge {
+        DependencyEdge {
+            dependent,
+            dependency,
+            version_constraint: constraint.to_string(),
+        }
+    }
+
+    #[test]
+    fn alerts_follow_matching_constraints_and_their_dependents() {
+        let [token, amm, router, vault, pinned] = [(); 5].map(|_| Uuid::new_v4());
+        let edges = vec![
+            edge(amm, token, "^1.2.0"),
+            edge(router, amm, "^2.0.0"),
+            edge(vault, token, ">=2.0.0"),
+            edge(pinned, token, "=1.0.0"),
+            // Cycles back to the vulnerable contract are ignored
+            edge(token, router, "*"),
+        ];
+        let affected = vec!["1.2.3".to_string(), "1.3.0".to_string()];
+        let found = propagate(token, &affected, &edges);
+        assert_eq!(
+            found,
+            vec![
+                AffectedDependent {
+                    contract_id: amm,
+                    path: vec![token, amm],
+                    constraint: "^1.2.0".to_string(),
+                },
+                AffectedDependent {
+                    contract_id: router,
+                    path: vec![token, amm, router],
+                    constraint: "^2.0.0".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn unparseable_constraints_are_assumed_affected() {
+        let affected = vec!["1.0.0".to_string()];
+        assert!(constraint_admits("git:main", &affected));
+        assert!(!constraint_admits("^2.0.0", &affected));
+        assert!(constraint_admits("^1.0.0", &affected));
+    }
+
+    #[test]
+    fn advisories_need_a_range_and_a_title() {
+        let mut req = CreateAdvisoryRequest {
+            affected_versions: ">=1.0.0, <1.4.2".to_string(),
+            severity: PatchSeverity::High,
+            title: "Rounding lets swaps drain the pool".to_string(),
+            description: None,
+            patched_version: Some("1.4.2".to_string()),
+        };
+        assert!(validate_advisory(&req).is_ok());
+        req.affected_versions = "latest".to_string();
+        assert!(validate_advisory(&req).is_err());
+        req.affected_versions = "1.0.0".to_string();
+        req.title = " ".to_string();
+        assert!(validate_advisory(&req).is_err());
+    }
+}
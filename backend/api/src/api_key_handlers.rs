@@ -0,0 +1,296 @@
+// api_key_handlers.rs
+// Scoped, expiring API keys for the signed-in account, with rotation and revocation.
+
+use axum::{
+    extract::{rejection::JsonRejection, Path, State},
+    http::StatusCode,
+    Json,
+};
+use chrono::{DateTime, Duration, Utc};
+use rand::{distributions::Alphanumeric, Rng};
+use shared::{ApiKey, ApiScope, CreateApiKeyRequest, IssuedApiKey, RotateApiKeyRequest};
+use uuid::Uuid;
+
+use crate::error::{ApiError, ApiResult};
+use crate::handlers::{db_internal_error, map_json_rejection};
+use crate::state::AppState;
+use crate::tenancy::CurrentTenant;
+use crate::user_auth::{hash_api_key, AuthUser, API_KEY_PREFIX};
+
+const KEY_SECRET_LENGTH: usize = 40;
+/// Characters of the key kept in plaintext for display (`srk_` plus eight)
+const KEY_DISPLAY_PREFIX_LENGTH: usize = 12;
+const MAX_KEY_NAME_LENGTH: usize = 64;
+const MAX_EXPIRY_DAYS: i64 = 365;
+const DEFAULT_GRACE_PERIOD_HOURS: i64 = 24;
+const MAX_GRACE_PERIOD_HOURS: i64 = 30 * 24;
+
+/// Columns of `api_keys` in `ApiKey` shape
+pub(crate) const KEY_COLUMNS: &str =
+    "id, name, prefix, scopes, expires_at, last_used_at, rotated_from, created_at";
+
+fn generate_key() -> String {
+    let secret: String = rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(KEY_SECRET_LENGTH)
+        .map(char::from)
+        .collect();
+    format!("{}{}", API_KEY_PREFIX, secret)
+}
+
+fn validate_request(req: &CreateApiKeyRequest) -> ApiResult<()> {
+    let name = req.name.trim();
+    if name.is_empty() || name.len() > MAX_KEY_NAME_LENGTH {
+        return Err(ApiError::bad_request(
+            "InvalidKeyName",
+            format!("name must be 1-{} characters", MAX_KEY_NAME_LENGTH),
+        ));
+    }
+    if req.scopes.is_empty() {
+        return Err(ApiError::bad_request(
+            "InvalidScopes",
+            "At least one scope (read, publish, patch-admin) is required",
+        ));
+    }
+    if req
+        .expires_in_days
+        .is_some_and(|days| !(1..=MAX_EXPIRY_DAYS).contains(&days))
+    {
+        return Err(ApiError::bad_request(
+            "InvalidExpiry",
+            format!("expires_in_days must be 1-{}", MAX_EXPIRY_DAYS),
+        ));
+    }
+    Ok(())
+}
+
+/// Scope names in a stable order, without duplicates
+fn scope_names(scopes: &[ApiScope]) -> Vec<String> {
+    let mut names: Vec<String> = scopes.iter().map(|s| s.as_str().to_string()).collect();
+    names.sort();
+    names.dedup();
+    names
+}
+
+async fn insert_key(
+    executor: impl sqlx::PgExecutor<'_>,
+    tenant_id: Uuid,
+    owner: &str,
+    name: &str,
+    scopes: &[String],
+    expires_at: Option<DateTime<Utc>>,
+    rotated_from: Option<Uuid>,
+) -> ApiResult<(ApiKey, String)> {
+    let token = generate_key();
+    let key: ApiKey = sqlx::query_as(&format!(
+        "INSERT INTO api_keys \
+             (owner_address, name, prefix, key_hash, scopes, expires_at, rotated_from, tenant_id) \
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8) \
+         RETURNING {}",
+        KEY_COLUMNS
+    ))
+    .bind(owner)
+    .bind(name)
+    .bind(&token[..KEY_DISPLAY_PREFIX_LENGTH])
+    .bind(hash_api_key(&token))
+    .bind(scopes)
+    .bind(expires_at)
+    .bind(rotated_from)
+    .bind(tenant_id)
+    .fetch_one(executor)
+    .await
+    .map_err(|err| db_internal_error("create api key", err))?;
+    Ok((key, token))
+}
+
+/// POST /api/tokens — the plaintext key is only ever returned here and on rotation
+pub async fn create_api_key(
+    State(state): State<AppState>,
+    tenant: CurrentTenant,
+    user: AuthUser,
+    payload: Result<Json<CreateApiKeyRequest>, JsonRejection>,
+) -> ApiResult<(StatusCode, Json<IssuedApiKey>)> {
+    user.require_session()?;
+    let Json(req) = payload.map_err(map_json_rejection)?;
+    validate_request(&req)?;
+
+    let expires_at = req
+        .expires_in_days
+        .map(|days| Utc::now() + Duration::days(days));
+    let (key, token) = insert_key(
+        &state.db,
+        tenant.id,
+        &user.address,
+        req.name.trim(),
+        &scope_names(&req.scopes),
+        expires_at,
+        None,
+    )
+    .await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(IssuedApiKey {
+            key,
+            token,
+            previous_key_expires_at: None,
+        }),
+    ))
+}
+
+/// GET /api/tokens — keys that still work, including ones in a rotation grace period
+pub async fn list_api_keys(
+    State(state): State<AppState>,
+    tenant: CurrentTenant,
+    user: AuthUser,
+) -> ApiResult<Json<Vec<ApiKey>>> {
+    user.require_session()?;
+    let keys: Vec<ApiKey> = sqlx::query_as(&format!(
+        "SELECT {} FROM api_keys \
+         WHERE owner_address = $1 AND tenant_id = $2 AND revoked_at IS NULL \
+           AND (expires_at IS NULL OR expires_at > NOW()) \
+         ORDER BY created_at DESC",
+        KEY_COLUMNS
+    ))
+    .bind(&user.address)
+    .bind(tenant.id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("list api keys", err))?;
+    Ok(Json(keys))
+}
+
+/// POST /api/tokens/:id/rotate — issue a replacement with the same name, scopes
+/// and lifetime; the old key keeps working for the grace period.
+pub async fn rotate_api_key(
+    State(state): State<AppState>,
+    tenant: CurrentTenant,
+    user: AuthUser,
+    Path(id): Path<Uuid>,
+    payload: Option<Json<RotateApiKeyRequest>>,
+) -> ApiResult<Json<IssuedApiKey>> {
+    user.require_session()?;
+    let req = payload.map(|Json(req)| req).unwrap_or_default();
+    let grace_hours = req.grace_period_hours.unwrap_or(DEFAULT_GRACE_PERIOD_HOURS);
+    if !(0..=MAX_GRACE_PERIOD_HOURS).contains(&grace_hours) {
+        return Err(ApiError::bad_request(
+            "InvalidGracePeriod",
+            format!("grace_period_hours must be 0-{}", MAX_GRACE_PERIOD_HOURS),
+        ));
+    }
+
+    let mut tx = state
+        .db
+        .begin()
+        .await
+        .map_err(|err| db_internal_error("begin key rotation", err))?;
+
+    let old: ApiKey = sqlx::query_as(&format!(
+        "SELECT {} FROM api_keys \
+         WHERE id = $1 AND owner_address = $2 AND tenant_id = $3 AND revoked_at IS NULL \
+           AND (expires_at IS NULL OR expires_at > NOW()) \
+         FOR UPDATE",
+        KEY_COLUMNS
+    ))
+    .bind(id)
+    .bind(&user.address)
+    .bind(tenant.id)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|err| db_internal_error("fetch api key", err))?
+    .ok_or_else(|| api_key_not_found(id))?;
+
+    // Keep the original lifetime: a 90-day key rotates into another 90-day key
+    let now = Utc::now();
+    let new_expiry = old.expires_at.map(|exp| now + (exp - old.created_at));
+    let (key, token) = insert_key(
+        &mut *tx,
+        tenant.id,
+        &user.address,
+        &old.name,
+        &old.scopes,
+        new_expiry,
+        Some(old.id),
+    )
+    .await?;
+
+    let grace_end = now + Duration::hours(grace_hours);
+    let old_expiry = old.expires_at.map_or(grace_end, |exp| exp.min(grace_end));
+    sqlx::query("UPDATE api_keys SET expires_at = $1 WHERE id = $2")
+        .bind(old_expiry)
+        .bind(old.id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|err| db_internal_error("expire rotated api key", err))?;
+
+    tx.commit()
+        .await
+        .map_err(|err| db_internal_error("commit key rotation", err))?;
+
+    Ok(Json(IssuedApiKey {
+        key,
+        token,
+        previous_key_expires_at: Some(old_expiry),
+    }))
+}
+
+/// DELETE /api/tokens/:id — revoke immediately, e.g. after a leak
+pub async fn revoke_api_key(
+    State(state): State<AppState>,
+    tenant: CurrentTenant,
+    user: AuthUser,
+    Path(id): Path<Uuid>,
+) -> ApiResult<StatusCode> {
+    user.require_session()?;
+    let result = sqlx::query(
+        "UPDATE api_keys SET revoked_at = NOW() \
+         WHERE id = $1 AND owner_address = $2 AND tenant_id = $3 AND revoked_at IS NULL",
+    )
+    .bind(id)
+    .bind(&user.address)
+    .bind(tenant.id)
+    .execute(&state.db)
+    .await
+    .map_err(|err| db_internal_error("revoke api key", err))?;
+    if result.rows_affected() == 0 {
+        return Err(api_key_not_found(id));
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+fn api_key_not_found(id: Uuid) -> ApiError {
+    ApiError::not_found(
+        "ApiKeyNotFound",
+        format!("No active API key with ID: {}", id),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generated_keys_are_prefixed_and_unique() {
+        let (a, b) = (generate_key(), generate_key());
+        assert!(a.starts_with(API_KEY_PREFIX));
+        assert_eq!(a.len(), API_KEY_PREFIX.len() + KEY_SECRET_LENGTH);
+        assert_ne!(a, b);
+        assert_ne!(hash_api_key(&a), hash_api_key(&b));
+    }
+
+    #[test]
+    fn scopes_are_deduplicated_and_validated() {
+        assert_eq!(
+            scope_names(&[ApiScope::Publish, ApiScope::Read, ApiScope::Publish]),
+            vec!["publish".to_string(), "read".to_string()]
+        );
+        let request = |scopes: Vec<ApiScope>, days| CreateApiKeyRequest {
+            name: "ci".to_string(),
+            scopes,
+            expires_in_days: days,
+        };
+        assert!(validate_request(&request(vec![ApiScope::Read], Some(90))).is_ok());
+        assert!(validate_request(&request(vec![], None)).is_err());
+        assert!(validate_request(&request(vec![ApiScope::Read], Some(0))).is_err());
+    }
+}
@@ -0,0 +1,377 @@
+// api_usage.rs
+// Per-token API usage for GET /api/me/usage. Requests carrying a bearer token are
+// counted in memory by caller, endpoint and day and written to `api_usage` once a
+// minute, so recording adds no query to the request path. The layer sits outside
+// the rate limiter so 429s and the limit headers are counted too.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use axum::{
+    body::Body,
+    extract::{MatchedPath, Query, State},
+    http::{header, HeaderMap, Method, Request, StatusCode},
+    middleware::Next,
+    response::Response,
+    Json,
+};
+use chrono::{NaiveDate, Utc};
+use shared::{ApiScope, ApiUsageQuery, ApiUsageReport, DailyUsage, EndpointUsage, TokenUsage};
+use uuid::Uuid;
+
+use crate::error::ApiResult;
+use crate::handlers::db_internal_error;
+use crate::rate_limit::{HEADER_RATE_LIMIT_LIMIT, HEADER_RATE_LIMIT_REMAINING};
+use crate::state::AppState;
+use crate::tenancy::CurrentTenant;
+use crate::user_auth::{hash_api_key, AuthUser, API_KEY_PREFIX};
+
+/// Days of usage reported and kept
+pub const USAGE_WINDOW_DAYS: i64 = 30;
+const FLUSH_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Adds one flushed counter to its row; `api_usage` is unique per
+/// (tenant, owner, key, endpoint, day)
+const UPSERT_CONFLICT: &str =
+    "ON CONFLICT (tenant_id, owner_address, api_key_id, endpoint, day) DO UPDATE SET \
+     requests = api_usage.requests + EXCLUDED.requests, \
+     errors = api_usage.errors + EXCLUDED.errors, \
+     rate_limited = api_usage.rate_limited + EXCLUDED.rate_limited, \
+     rate_limit = COALESCE(EXCLUDED.rate_limit, api_usage.rate_limit), \
+     peak_window_usage = GREATEST(api_usage.peak_window_usage, EXCLUDED.peak_window_usage)";
+
+/// Who made a request, as far as can be told without a database lookup. Keys
+/// are matched to their owner when the counters are flushed.
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+enum Caller {
+    ApiKey { key_hash: String },
+    Session { address: String },
+}
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+struct UsageKey {
+    tenant_id: Uuid,
+    caller: Caller,
+    endpoint: String,
+    day: NaiveDate,
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+struct UsageCounts {
+    requests: i64,
+    errors: i64,
+    rate_limited: i64,
+    rate_limit: Option<i32>,
+    peak_window_usage: Option<i32>,
+}
+
+impl UsageCounts {
+    /// Count one response; `rate_limit` is the (limit, remaining) it reported
+    fn record(&mut self, status: StatusCode, rate_limit: Option<(i32, i32)>) {
+        self.requests += 1;
+        if status == StatusCode::TOO_MANY_REQUESTS {
+            self.rate_limited += 1;
+        } else if status.is_client_error() || status.is_server_error() {
+            self.errors += 1;
+        }
+        if let Some((limit, remaining)) = rate_limit {
+            let used = limit.saturating_sub(remaining);
+            self.rate_limit = Some(limit);
+            self.peak_window_usage = Some(self.peak_window_usage.map_or(used, |p| p.max(used)));
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct ApiUsageRecorder {
+    state: AppState,
+    pending: Arc<Mutex<HashMap<UsageKey, UsageCounts>>>,
+}
+
+impl ApiUsageRecorder {
+    pub fn new(state: AppState) -> Self {
+        Self {
+            state,
+            pending: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Write the counters to the database every minute
+    pub fn spawn_flush_task(&self) {
+        let recorder = self.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(FLUSH_INTERVAL);
+            loop {
+                interval.tick().await;
+                recorder.flush().await;
+            }
+        });
+    }
+
+    fn caller(&self, headers: &HeaderMap, tenant: &CurrentTenant) -> Option<Caller> {
+        let token = bearer_token(headers)?;
+        if token.starts_with(API_KEY_PREFIX) {
+            return Some(Caller::ApiKey {
+                key_hash: hash_api_key(token),
+            });
+        }
+        let claims = self.state.auth_mgr.read().ok()?.validate_jwt(token).ok()?;
+        (claims.realm == tenant.slug).then_some(Caller::Session {
+            address: claims.sub,
+        })
+    }
+
+    fn record(&self, key: UsageKey, status: StatusCode, rate_limit: Option<(i32, i32)>) {
+        let mut pending = self.pending.lock().expect("api usage mutex poisoned");
+        pending.entry(key).or_default().record(status, rate_limit);
+    }
+
+    async fn flush(&self) {
+        let batch = std::mem::take(&mut *self.pending.lock().expect("api usage mutex poisoned"));
+        let mut failed = 0;
+        for (key, counts) in batch {
+            if let Err(err) = self.write(&key, &counts).await {
+                tracing::warn!(error = ?err, endpoint = %key.endpoint, "api usage: write failed");
+                failed += 1;
+            }
+        }
+        if failed > 0 {
+            tracing::error!(
+                failed,
+                "api usage: dropped counters that could not be written"
+            );
+        }
+    }
+
+    async fn write(&self, key: &UsageKey, counts: &UsageCounts) -> Result<(), sqlx::Error> {
+        let (sql, caller) = match &key.caller {
+            // Keys that no longer resolve (revoked and purged, or made up) insert nothing
+            Caller::ApiKey { key_hash } => (
+                format!(
+                    "INSERT INTO api_usage (tenant_id, owner_address, api_key_id, endpoint, day, \
+                     requests, errors, rate_limited, rate_limit, peak_window_usage) \
+                     SELECT k.tenant_id, k.owner_address, k.id, $3, $4, $5, $6, $7, $8, $9 \
+                     FROM api_keys k WHERE k.key_hash = $1 AND k.tenant_id = $2 {}",
+                    UPSERT_CONFLICT
+                ),
+                key_hash,
+            ),
+            Caller::Session { address } => (
+                format!(
+                    "INSERT INTO api_usage (owner_address, tenant_id, endpoint, day, \
+                     requests, errors, rate_limited, rate_limit, peak_window_usage) \
+                     VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9) {}",
+                    UPSERT_CONFLICT
+                ),
+                address,
+            ),
+        };
+        sqlx::query(&sql)
+            .bind(caller)
+            .bind(key.tenant_id)
+            .bind(&key.endpoint)
+            .bind(key.day)
+            .bind(counts.requests)
+            .bind(counts.errors)
+            .bind(counts.rate_limited)
+            .bind(counts.rate_limit)
+            .bind(counts.peak_window_usage)
+            .execute(&self.state.db)
+            .await?;
+        Ok(())
+    }
+}
+
+fn bearer_token(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(header::AUTHORIZATION)?
+        .to_str()
+        .ok()?
+        .strip_prefix("Bearer ")
+        .map(str::trim)
+}
+
+/// `GET /api/contracts/:id`; requests no route matched share one label
+fn endpoint_label(method: &Method, matched_path: Option<&str>) -> String {
+    format!("{} {}", method, matched_path.unwrap_or("(unmatched)"))
+}
+
+/// (limit, remaining) from the rate limiter's response headers
+fn rate_limit_of(headers: &HeaderMap) -> Option<(i32, i32)> {
+    let value = |name| headers.get(name)?.to_str().ok()?.parse::<i32>().ok();
+    Some((
+        value(HEADER_RATE_LIMIT_LIMIT)?,
+        value(HEADER_RATE_LIMIT_REMAINING)?,
+    ))
+}
+
+pub async fn api_usage_middleware(
+    State(recorder): State<ApiUsageRecorder>,
+    request: Request<Body>,
+    next: Next,
+) -> Response {
+    let tenant = CurrentTenant::of(request.extensions());
+    let Some(caller) = recorder.caller(request.headers(), &tenant) else {
+        return next.run(request).await;
+    };
+    let endpoint = endpoint_label(
+        request.method(),
+        request
+            .extensions()
+            .get::<MatchedPath>()
+            .map(MatchedPath::as_str),
+    );
+
+    let response = next.run(request).await;
+    recorder.record(
+        UsageKey {
+            tenant_id: tenant.id,
+            caller,
+            endpoint,
+            day: Utc::now().date_naive(),
+        },
+        response.status(),
+        rate_limit_of(response.headers()),
+    );
+    response
+}
+
+fn error_rate(errors: i64, requests: i64) -> f64 {
+    if requests == 0 {
+        0.0
+    } else {
+        errors as f64 / requests as f64
+    }
+}
+
+/// GET /api/me/usage?token=<api key id>
+pub async fn my_api_usage(
+    State(state): State<AppState>,
+    user: AuthUser,
+    tenant: CurrentTenant,
+    Query(params): Query<ApiUsageQuery>,
+) -> ApiResult<Json<ApiUsageReport>> {
+    user.require(ApiScope::Read)?;
+    let since = Utc::now().date_naive() - chrono::Duration::days(USAGE_WINDOW_DAYS - 1);
+    let filter = "WHERE u.tenant_id = $1 AND u.owner_address = $2 AND u.day >= $3 \
+                  AND ($4::uuid IS NULL OR u.api_key_id = $4)";
+    let sums = "SUM(u.requests)::bigint AS requests, SUM(u.errors)::bigint AS errors, \
+                SUM(u.rate_limited)::bigint AS rate_limited";
+
+    let endpoints: Vec<EndpointUsage> = sqlx::query_as(&format!(
+        "SELECT u.endpoint, {}, \
+                COALESCE(SUM(u.errors)::float8 / NULLIF(SUM(u.requests), 0), 0) AS error_rate, \
+                MAX(u.rate_limit) AS rate_limit, MAX(u.peak_window_usage) AS peak_window_usage \
+         FROM api_usage u {} GROUP BY u.endpoint ORDER BY requests DESC, u.endpoint",
+        sums, filter
+    ))
+    .bind(tenant.id)
+    .bind(&user.address)
+    .bind(since)
+    .bind(params.token)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("api usage by endpoint", err))?;
+
+    let tokens: Vec<TokenUsage> = sqlx::query_as(&format!(
+        "SELECT u.api_key_id, k.name, k.prefix, {} \
+         FROM api_usage u LEFT JOIN api_keys k ON k.id = u.api_key_id {} \
+         GROUP BY u.api_key_id, k.name, k.prefix ORDER BY requests DESC",
+        sums, filter
+    ))
+    .bind(tenant.id)
+    .bind(&user.address)
+    .bind(since)
+    .bind(params.token)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("api usage by token", err))?;
+
+    let daily: Vec<DailyUsage> = sqlx::query_as(&format!(
+        "SELECT u.day, {} FROM api_usage u {} GROUP BY u.day ORDER BY u.day",
+        sums, filter
+    ))
+    .bind(tenant.id)
+    .bind(&user.address)
+    .bind(since)
+    .bind(params.token)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("api usage by day", err))?;
+
+    let requests = daily.iter().map(|d| d.requests).sum();
+    let errors = daily.iter().map(|d| d.errors).sum();
+    Ok(Json(ApiUsageReport {
+        since,
+        window_days: USAGE_WINDOW_DAYS,
+        requests,
+        errors,
+        error_rate: error_rate(errors, requests),
+        rate_limited: daily.iter().map(|d| d.rate_limited).sum(),
+        endpoints,
+        tokens,
+        daily,
+    }))
+}
+
+pub async fn purge_expired(pool: &sqlx::PgPool) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query("DELETE FROM api_usage WHERE day < CURRENT_DATE - $1::int")
+        .bind(USAGE_WINDOW_DAYS as i32)
+        .execute(pool)
+        .await?;
+    Ok(result.rows_affected())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    #[test]
+    fn counts_errors_and_rate_limiting_separately() {
+        let mut counts = UsageCounts::default();
+        counts.record(StatusCode::OK, Some((100, 99)));
+        counts.record(StatusCode::NOT_FOUND, Some((100, 40)));
+        counts.record(StatusCode::TOO_MANY_REQUESTS, Some((100, 0)));
+        counts.record(StatusCode::INTERNAL_SERVER_ERROR, None);
+        assert_eq!(
+            counts,
+            UsageCounts {
+                requests: 4,
+                errors: 2,
+                rate_limited: 1,
+                rate_limit: Some(100),
+                peak_window_usage: Some(100),
+            }
+        );
+    }
+
+    #[test]
+    fn labels_use_the_route_pattern() {
+        assert_eq!(
+            endpoint_label(&Method::GET, Some("/api/contracts/:id")),
+            "GET /api/contracts/:id"
+        );
+        assert_eq!(endpoint_label(&Method::POST, None), "POST (unmatched)");
+    }
+
+    #[test]
+    fn reads_the_rate_limit_headers() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(rate_limit_of(&headers), None);
+        headers.insert(HEADER_RATE_LIMIT_LIMIT, HeaderValue::from_static("1000"));
+        headers.insert(HEADER_RATE_LIMIT_REMAINING, HeaderValue::from_static("997"));
+        assert_eq!(rate_limit_of(&headers), Some((1000, 997)));
+
+        headers.insert(
+            header::AUTHORIZATION,
+            HeaderValue::from_static("Bearer srk_abc "),
+        );
+        assert_eq!(bearer_token(&headers), Some("srk_abc"));
+        assert_eq!(error_rate(0, 0), 0.0);
+        assert_eq!(error_rate(1, 4), 0.25);
+    }
+}
@@ -0,0 +1,123 @@
+// app.rs
+// The HTTP application: every route group behind the shared middleware stack.
+// Built by `main` for the server and by `testkit` for integration tests.
+
+use axum::extract::DefaultBodyLimit;
+use axum::http::{header, HeaderValue, Method};
+use axum::{middleware, Router};
+use shared::repository::postgres::PgRepository;
+use std::sync::Arc;
+use tower_http::cors::CorsLayer;
+
+use crate::api_usage::{self, ApiUsageRecorder};
+use crate::rate_limit::{self, RateLimitState};
+use crate::settings::Settings;
+use crate::state::AppState;
+use crate::{
+    activity_feed_routes, body_limit, client_version, handlers, idempotency, ip_allowlist,
+    maintenance_mode, private_registry, release_notes_routes, request_tracing, routes, tenancy,
+    web_ui,
+};
+
+/// The router for `state`, with limits and policies taken from `settings`.
+/// Usage is counted into `api_usage`; its flush task is the caller's to spawn.
+pub fn build(state: AppState, settings: &Settings, api_usage: ApiUsageRecorder) -> Router {
+    let rate_limit_state = RateLimitState::from_settings(&settings.rate_limit);
+    let body_limits = body_limit::BodyLimitState::from_settings(&settings.body_limits);
+    let admin_allowlist = ip_allowlist::AdminAllowlist::from_settings(&settings.admin);
+    if admin_allowlist.is_enabled() {
+        tracing::info!(
+            allowed = ?settings.admin.allowed_cidrs,
+            "Admin endpoints restricted to the configured address ranges"
+        );
+    }
+    let client_version_policy =
+        client_version::ClientVersionPolicy::new(settings.server.min_cli_version.as_deref());
+
+    let cors = CorsLayer::new()
+        .allow_origin([
+            HeaderValue::from_static("http://localhost:3000"),
+            HeaderValue::from_static("https://soroban-registry.vercel.app"),
+        ])
+        .allow_methods([Method::GET, Method::POST, Method::PATCH, Method::OPTIONS])
+        .allow_headers([
+            header::CONTENT_TYPE,
+            header::AUTHORIZATION,
+            header::IF_MATCH,
+            idempotency::IDEMPOTENCY_KEY_HEADER,
+        ]);
+
+    let mut app = Router::new()
+        .merge(routes::contract_routes())
+        .merge(routes::publisher_routes())
+        .merge(routes::health_routes())
+        .merge(routes::migration_routes())
+        .merge(routes::admin_routes())
+        .merge(routes::compatibility_dashboard_routes())
+        .merge(routes::compat_routes())
+        .merge(routes::feed_routes())
+        .merge(routes::cli_routes())
+        .merge(routes::auth_routes())
+        .merge(routes::api_key_routes())
+        .merge(routes::saved_search_routes())
+        .merge(routes::collection_routes())
+        .merge(routes::account_routes())
+        .merge(routes::patch_routes())
+        .merge(routes::publish_session_routes())
+        .merge(routes::org_routes())
+        .merge(routes::index_routes())
+        .merge(release_notes_routes::release_notes_routes())
+        .nest("/api", activity_feed_routes::routes());
+    if settings.server.web_ui {
+        let repository = Arc::new(PgRepository::new(state.db.clone()));
+        app = app.merge(web_ui::routes(repository));
+    }
+    let app = app
+        .fallback(handlers::route_not_found)
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            idempotency::idempotency_middleware,
+        ))
+        .layer(middleware::from_fn_with_state(
+            body_limits,
+            body_limit::body_limit_middleware,
+        ))
+        // Replaced by the per-route limits above
+        .layer(DefaultBodyLimit::disable())
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            maintenance_mode::maintenance_mode_middleware,
+        ))
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            private_registry::private_mode_middleware,
+        ))
+        .layer(middleware::from_fn_with_state(
+            admin_allowlist,
+            ip_allowlist::admin_allowlist_middleware,
+        ))
+        .layer(middleware::from_fn(request_tracing::tracing_middleware))
+        .layer(middleware::from_fn_with_state(
+            client_version_policy,
+            client_version::client_version_middleware,
+        ))
+        .layer(middleware::from_fn_with_state(
+            rate_limit_state,
+            rate_limit::rate_limit_middleware,
+        ))
+        // Outside the rate limiter so refused requests are counted too
+        .layer(middleware::from_fn_with_state(
+            api_usage,
+            api_usage::api_usage_middleware,
+        ))
+        .layer(CorsLayer::permissive())
+        .layer(cors)
+        .with_state(state.clone());
+
+    // Tenant routing strips `/t/<slug>`, so it has to wrap the router rather than
+    // run as a route layer
+    Router::new().fallback_service(tower::Layer::layer(
+        &middleware::from_fn_with_state(state, tenancy::tenant_middleware),
+        app,
+    ))
+}
@@ -0,0 +1,378 @@
+// archive_handlers.rs
+// Server-built contract archives: everything the registry holds for a contract
+// version, packaged in the layout `soroban-registry export` writes and `import` reads.
+// Archives are reproducible and each distinct one is kept, so its hash can be
+// fetched from /api/artifacts.
+
+use axum::{
+    extract::{Path, Query, State},
+    http::{header, HeaderValue},
+    response::{IntoResponse, Response},
+};
+use chrono::{DateTime, Utc};
+use flate2::{write::GzEncoder, Compression};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use shared::{Contract, ContractVersion};
+
+use crate::error::{ApiError, ApiResult};
+use crate::handlers::{db_internal_error, fetch_contract_identity, fetch_contract_readme};
+use crate::private_registry::{ensure_contract_visible, Viewer};
+use crate::state::AppState;
+use crate::type_safety::{generate_openapi, parse_json_spec, to_json};
+
+const ARCHIVE_CONTENT_TYPE: &str = "application/gzip";
+/// Manifest schema understood by the CLI's `import`
+const MANIFEST_SCHEMA_VERSION: &str = "1.0";
+
+#[derive(Debug, Deserialize)]
+pub struct ArchiveQuery {
+    /// Version to package; defaults to the most recently published one
+    pub version: Option<String>,
+}
+
+/// One file inside the inner `contract.tar.gz`
+#[derive(Debug)]
+struct ArchiveFile {
+    path: String,
+    content: Vec<u8>,
+}
+
+impl ArchiveFile {
+    fn new(path: impl Into<String>, content: impl Into<Vec<u8>>) -> Self {
+        Self {
+            path: path.into(),
+            content: content.into(),
+        }
+    }
+}
+
+/// Where a stored version artifact lands in the archive
+fn artifact_path(kind: &str) -> String {
+    match kind {
+        "wasm" => "contract.wasm".to_string(),
+        "readme" => "README.md".to_string(),
+        "abi" => "abi.json".to_string(),
+        "signature" => "contract.wasm.sig".to_string(),
+        other => format!("artifacts/{}", other),
+    }
+}
+
+fn tar_gz(files: &[(&str, &[u8])], modified_at: DateTime<Utc>) -> std::io::Result<Vec<u8>> {
+    let mut builder = tar::Builder::new(GzEncoder::new(Vec::new(), Compression::default()));
+    for (path, content) in files {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(content.len() as u64);
+        header.set_mode(0o644);
+        header.set_mtime(modified_at.timestamp().max(0) as u64);
+        header.set_cksum();
+        builder.append_data(&mut header, path, *content)?;
+    }
+    builder.into_inner()?.finish()
+}
+
+/// Outer tar.gz holding `manifest.json` and `contract.tar.gz`, with the
+/// manifest's checksum covering the inner archive
+fn build_archive(
+    contract: &Contract,
+    files: &[ArchiveFile],
+    exported_at: DateTime<Utc>,
+) -> std::io::Result<Vec<u8>> {
+    let entries: Vec<(&str, &[u8])> = files
+        .iter()
+        .map(|f| (f.path.as_str(), f.content.as_slice()))
+        .collect();
+    let inner = tar_gz(&entries, exported_at)?;
+
+    let manifest = json!({
+        "schema_version": MANIFEST_SCHEMA_VERSION,
+        "contract_id": contract.contract_id,
+        "name": contract.name,
+        "network": contract.network.to_string(),
+        "exported_at": exported_at,
+        "sha256": hex::encode(Sha256::digest(&inner)),
+        "contents": files
+            .iter()
+            .map(|f| json!({
+                "path": f.path,
+                "size": f.content.len(),
+                "modified_at": exported_at,
+            }))
+            .collect::<Vec<_>>(),
+        "audit_trail": [{
+            "action": "export_created",
+            "timestamp": exported_at,
+            "actor": "soroban-registry-api",
+        }],
+        "signature": null,
+    });
+    let manifest = serde_json::to_vec_pretty(&manifest)?;
+
+    tar_gz(
+        &[
+            ("manifest.json", manifest.as_slice()),
+            ("contract.tar.gz", inner.as_slice()),
+        ],
+        exported_at,
+    )
+}
+
+pub(crate) async fn fetch_version(
+    state: &AppState,
+    contract_uuid: uuid::Uuid,
+    version: Option<&str>,
+) -> ApiResult<Option<ContractVersion>> {
+    let Some(version) = version else {
+        return sqlx::query_as(
+            "SELECT * FROM contract_versions WHERE contract_id = $1 \
+             ORDER BY created_at DESC LIMIT 1",
+        )
+        .bind(contract_uuid)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(|err| db_internal_error("fetch latest contract version", err));
+    };
+    let found: Option<ContractVersion> =
+        sqlx::query_as("SELECT * FROM contract_versions WHERE contract_id = $1 AND version = $2")
+            .bind(contract_uuid)
+            .bind(version)
+            .fetch_optional(&state.db)
+            .await
+            .map_err(|err| db_internal_error("fetch contract version", err))?;
+    found.map(Some).ok_or_else(|| {
+        ApiError::not_found(
+            "VersionNotFound",
+            format!("Contract has no version {}", version),
+        )
+    })
+}
+
+/// The version's ABI, falling back to the contract-level one
+async fn fetch_abi(
+    state: &AppState,
+    contract_uuid: uuid::Uuid,
+    version: Option<&ContractVersion>,
+) -> ApiResult<Option<Value>> {
+    if let Some(version) = version {
+        let abi: Option<Value> = sqlx::query_scalar(
+            "SELECT abi FROM contract_abis WHERE contract_id = $1 AND version = $2",
+        )
+        .bind(contract_uuid)
+        .bind(&version.version)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(|err| db_internal_error("fetch version abi", err))?;
+        if abi.is_some() {
+            return Ok(abi);
+        }
+    }
+    let abi: Option<Option<Value>> = sqlx::query_scalar("SELECT abi FROM contracts WHERE id = $1")
+        .bind(contract_uuid)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(|err| db_internal_error("fetch contract abi", err))?;
+    Ok(abi.flatten())
+}
+
+/// Gather metadata, stored artifacts, release notes, ABI, README and generated docs
+async fn collect_files(
+    state: &AppState,
+    contract: &Contract,
+    version: Option<&ContractVersion>,
+) -> ApiResult<Vec<ArchiveFile>> {
+    let metadata = json!({ "contract": contract, "version": version });
+    let metadata = serde_json::to_vec_pretty(&metadata)
+        .map_err(|e| ApiError::internal(format!("Failed to encode metadata: {}", e)))?;
+    let mut files = vec![ArchiveFile::new("metadata.json", metadata)];
+
+    if let Some(version) = version {
+        let artifacts: Vec<(String, Vec<u8>)> = sqlx::query_as(
+            "SELECT kind, content FROM contract_version_artifacts \
+             WHERE version_id = $1 ORDER BY kind",
+        )
+        .bind(version.id)
+        .fetch_all(&state.db)
+        .await
+        .map_err(|err| db_internal_error("fetch version artifacts", err))?;
+        files.extend(
+            artifacts
+                .into_iter()
+                .map(|(kind, content)| ArchiveFile::new(artifact_path(&kind), content)),
+        );
+        if let Some(notes) = &version.release_notes {
+            files.push(ArchiveFile::new(
+                shared::RELEASE_NOTES_FILE,
+                notes.as_bytes(),
+            ));
+        }
+    }
+
+    // The editable contract README supersedes the one published with the version
+    if let Some(readme) = fetch_contract_readme(state, contract.id).await? {
+        files.retain(|f| f.path != "README.md");
+        files.push(ArchiveFile::new("README.md", readme));
+    }
+
+    if let Some(abi) = fetch_abi(state, contract.id, version).await? {
+        let abi_json = abi.to_string();
+        match parse_json_spec(&abi_json, &contract.contract_id) {
+            Ok(spec) => match to_json(&generate_openapi(&spec, Some("/invoke"))) {
+                Ok(doc) => files.push(ArchiveFile::new("docs/openapi.json", doc)),
+                Err(e) => tracing::warn!(error = %e, "skipping archive docs"),
+            },
+            Err(e) => tracing::warn!(error = %e, "skipping archive docs for unparseable ABI"),
+        }
+        if !files.iter().any(|f| f.path == "abi.json") {
+            let pretty = serde_json::to_vec_pretty(&abi)
+                .map_err(|e| ApiError::internal(format!("Failed to encode ABI: {}", e)))?;
+            files.push(ArchiveFile::new("abi.json", pretty));
+        }
+    }
+
+    Ok(files)
+}
+
+/// Build the archive and keep a copy under its SHA-256. Timestamps come from the
+/// version, so the bytes only change when the packaged content does.
+pub async fn snapshot_archive(
+    state: &AppState,
+    contract: &Contract,
+    version: Option<&ContractVersion>,
+) -> ApiResult<(Vec<u8>, String)> {
+    let files = collect_files(state, contract, version).await?;
+    let built_at = version.map_or(contract.updated_at, |v| v.created_at);
+    let archive = build_archive(contract, &files, built_at)
+        .map_err(|e| ApiError::internal(format!("Failed to build archive: {}", e)))?;
+    let sha256 = hex::encode(Sha256::digest(&archive));
+
+    sqlx::query(
+        "INSERT INTO contract_archives (sha256, contract_id, version_id, content) \
+         VALUES ($1, $2, $3, $4) ON CONFLICT (sha256) DO NOTHING",
+    )
+    .bind(&sha256)
+    .bind(contract.id)
+    .bind(version.map(|v| v.id))
+    .bind(&archive)
+    .execute(&state.db)
+    .await
+    .map_err(|err| db_internal_error("store archive snapshot", err))?;
+    Ok((archive, sha256))
+}
+
+/// GET /api/contracts/:id/archive — a `.tar.gz` bundle that `import` accepts
+pub async fn get_contract_archive(
+    State(state): State<AppState>,
+    viewer: Viewer,
+    Path(id): Path<String>,
+    Query(query): Query<ArchiveQuery>,
+) -> ApiResult<Response> {
+    let (contract_uuid, _) = fetch_contract_identity(&state, viewer.tenant_id, &id).await?;
+    ensure_contract_visible(&state, &viewer, contract_uuid).await?;
+    let contract: Contract = sqlx::query_as("SELECT * FROM contracts WHERE id = $1")
+        .bind(contract_uuid)
+        .fetch_one(&state.db)
+        .await
+        .map_err(|err| db_internal_error("fetch contract", err))?;
+    let version = fetch_version(&state, contract_uuid, query.version.as_deref()).await?;
+
+    let (archive, sha256) = snapshot_archive(&state, &contract, version.as_ref()).await?;
+
+    let filename = match &version {
+        Some(v) => format!("{}-{}.tar.gz", contract.contract_id, v.version),
+        None => format!("{}.tar.gz", contract.contract_id),
+    };
+    let disposition = HeaderValue::from_str(&format!("attachment; filename=\"{}\"", filename))
+        .map_err(|_| ApiError::internal("Failed to build response"))?;
+    Ok((
+        [
+            (
+                header::CONTENT_TYPE,
+                HeaderValue::from_static(ARCHIVE_CONTENT_TYPE),
+            ),
+            (header::CONTENT_DISPOSITION, disposition),
+            (
+                header::ETAG,
+                crate::artifact_handlers::artifact_etag(&sha256),
+            ),
+        ],
+        archive,
+    )
+        .into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    fn entries(archive: &[u8]) -> Vec<(String, Vec<u8>)> {
+        let mut reader = tar::Archive::new(flate2::read::GzDecoder::new(archive));
+        reader
+            .entries()
+            .unwrap()
+            .map(|entry| {
+                let mut entry = entry.unwrap();
+                let path = entry.path().unwrap().to_string_lossy().into_owned();
+                let mut content = Vec::new();
+                entry.read_to_end(&mut content).unwrap();
+                (path, content)
+            })
+            .collect()
+    }
+
+    fn contract() -> Contract {
+        serde_json::from_value(json!({
+            "id": uuid::Uuid::nil(),
+            "contract_id": "CABC",
+            "wasm_hash": "00",
+            "name": "amm",
+            "description": null,
+            "publisher_id": uuid::Uuid::nil(),
+            "network": "testnet",
+            "is_verified": false,
+            "category": null,
+            "tags": [],
+            "created_at": Utc::now(),
+            "updated_at": Utc::now(),
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn artifacts_map_to_conventional_paths() {
+        assert_eq!(artifact_path("wasm"), "contract.wasm");
+        assert_eq!(artifact_path("readme"), "README.md");
+        assert_eq!(artifact_path("sbom"), "artifacts/sbom");
+    }
+
+    #[test]
+    fn archive_matches_the_cli_import_layout() {
+        let files = vec![
+            ArchiveFile::new("metadata.json", b"{}".to_vec()),
+            ArchiveFile::new("contract.wasm", b"\0asm".to_vec()),
+        ];
+        let archive = build_archive(&contract(), &files, Utc::now()).unwrap();
+
+        let outer = entries(&archive);
+        assert_eq!(outer[0].0, "manifest.json");
+        assert_eq!(outer[1].0, "contract.tar.gz");
+
+        let manifest: Value = serde_json::from_slice(&outer[0].1).unwrap();
+        assert_eq!(manifest["contract_id"], "CABC");
+        assert_eq!(manifest["sha256"], hex::encode(Sha256::digest(&outer[1].1)));
+        assert_eq!(manifest["contents"][1]["size"], 4);
+
+        let inner = entries(&outer[1].1);
+        assert_eq!(inner[1], ("contract.wasm".to_string(), b"\0asm".to_vec()));
+    }
+
+    #[test]
+    fn same_content_builds_the_same_archive() {
+        let files = vec![ArchiveFile::new("contract.wasm", b"\0asm".to_vec())];
+        let built_at = Utc::now();
+        let first = build_archive(&contract(), &files, built_at).unwrap();
+        let second = build_archive(&contract(), &files, built_at).unwrap();
+        assert_eq!(Sha256::digest(&first), Sha256::digest(&second));
+    }
+}
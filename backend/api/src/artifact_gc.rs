@@ -0,0 +1,137 @@
+// artifact_gc.rs
+// Garbage collection of stored artifacts that no contract version references:
+// parts left behind by finished or abandoned publish sessions, and ABIs whose
+// version was removed. Runs hourly; admins can preview or trigger it.
+
+use axum::{extract::State, Json};
+use chrono::Utc;
+use shared::{ArtifactGcReport, OrphanedArtifacts};
+use sqlx::PgPool;
+
+use crate::error::ApiResult;
+use crate::handlers::db_internal_error;
+use crate::state::AppState;
+
+/// One kind of collectable content: how to measure it and how to delete it.
+/// Both statements return `(count, bytes)`.
+struct OrphanKind {
+    kind: &'static str,
+    measure: &'static str,
+    delete: &'static str,
+}
+
+const ORPHAN_KINDS: &[OrphanKind] = &[
+    OrphanKind {
+        kind: "staged_parts",
+        measure: "SELECT COUNT(*), COALESCE(SUM(OCTET_LENGTH(p.content)), 0)::BIGINT \
+                  FROM publish_session_parts p JOIN publish_sessions s ON s.id = p.session_id \
+                  WHERE s.status IN ('committed', 'cancelled') OR s.expires_at < NOW()",
+        delete: "WITH removed AS (\
+                     DELETE FROM publish_session_parts p USING publish_sessions s \
+                     WHERE p.session_id = s.id AND s.status IN ('committed', 'cancelled') \
+                     RETURNING OCTET_LENGTH(p.content) AS size) \
+                 SELECT COUNT(*), COALESCE(SUM(size), 0)::BIGINT FROM removed",
+    },
+    OrphanKind {
+        kind: "abis",
+        measure: "SELECT COUNT(*), COALESCE(SUM(pg_column_size(a.abi)), 0)::BIGINT \
+                  FROM contract_abis a WHERE NOT EXISTS (\
+                      SELECT 1 FROM contract_versions v \
+                      WHERE v.contract_id = a.contract_id AND v.version = a.version)",
+        delete: "WITH removed AS (\
+                     DELETE FROM contract_abis a WHERE NOT EXISTS (\
+                         SELECT 1 FROM contract_versions v \
+                         WHERE v.contract_id = a.contract_id AND v.version = a.version) \
+                     RETURNING pg_column_size(a.abi) AS size) \
+                 SELECT COUNT(*), COALESCE(SUM(size), 0)::BIGINT FROM removed",
+    },
+];
+
+fn report(dry_run: bool, orphans: Vec<OrphanedArtifacts>) -> ArtifactGcReport {
+    ArtifactGcReport {
+        dry_run,
+        total_bytes: orphans.iter().map(|o| o.bytes).sum(),
+        orphans,
+        generated_at: Utc::now(),
+    }
+}
+
+/// Measure (`dry_run`) or delete every kind of orphaned artifact
+pub async fn collect(pool: &PgPool, dry_run: bool) -> Result<ArtifactGcReport, sqlx::Error> {
+    if !dry_run {
+        // Expired sessions can no longer be committed; closing them frees their parts
+        sqlx::query(
+            "UPDATE publish_sessions SET status = 'cancelled' \
+             WHERE status IN ('open', 'committing') AND expires_at < NOW()",
+        )
+        .execute(pool)
+        .await?;
+    }
+
+    let mut orphans = Vec::with_capacity(ORPHAN_KINDS.len());
+    for kind in ORPHAN_KINDS {
+        let sql = if dry_run { kind.measure } else { kind.delete };
+        let (count, bytes): (i64, i64) = sqlx::query_as(sql).fetch_one(pool).await?;
+        orphans.push(OrphanedArtifacts {
+            kind: kind.kind.to_string(),
+            count,
+            bytes,
+        });
+    }
+    Ok(report(dry_run, orphans))
+}
+
+/// GET /api/admin/gc — what a collection would reclaim, without deleting anything
+pub async fn preview_gc(State(state): State<AppState>) -> ApiResult<Json<ArtifactGcReport>> {
+    let report = collect(&state.db, true)
+        .await
+        .map_err(|err| db_internal_error("measure orphaned artifacts", err))?;
+    Ok(Json(report))
+}
+
+/// POST /api/admin/gc — collect now instead of waiting for the hourly run
+pub async fn run_gc(State(state): State<AppState>) -> ApiResult<Json<ArtifactGcReport>> {
+    let report = collect(&state.db, false)
+        .await
+        .map_err(|err| db_internal_error("collect orphaned artifacts", err))?;
+    tracing::info!(
+        reclaimed_bytes = report.total_bytes,
+        "artifact gc: manual run completed"
+    );
+    Ok(Json(report))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report_totals_every_kind() {
+        let report = report(
+            true,
+            vec![
+                OrphanedArtifacts {
+                    kind: "staged_parts".to_string(),
+                    count: 2,
+                    bytes: 2048,
+                },
+                OrphanedArtifacts {
+                    kind: "abis".to_string(),
+                    count: 1,
+                    bytes: 300,
+                },
+            ],
+        );
+        assert!(report.dry_run);
+        assert_eq!(report.total_bytes, 2348);
+    }
+
+    #[test]
+    fn deletes_only_what_the_dry_run_measures() {
+        for kind in ORPHAN_KINDS {
+            assert!(kind.measure.starts_with("SELECT COUNT(*)"), "{}", kind.kind);
+            assert!(kind.delete.contains("RETURNING"), "{}", kind.kind);
+            assert!(kind.delete.ends_with("FROM removed"), "{}", kind.kind);
+        }
+    }
+}
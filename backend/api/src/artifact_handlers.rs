@@ -0,0 +1,205 @@
+// artifact_handlers.rs
+// Content-addressed artifacts: version files and archives served by SHA-256.
+// The bytes behind a hash never change, so responses are cacheable forever.
+
+use axum::{
+    extract::{Path, Query, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use shared::{ArtifactRef, Contract, VersionArtifacts};
+
+use crate::archive_handlers::{fetch_version, snapshot_archive, ArchiveQuery};
+use crate::error::{ApiError, ApiResult};
+use crate::handlers::{db_internal_error, fetch_contract_identity};
+use crate::private_registry::{ensure_contract_visible, Viewer};
+use crate::state::AppState;
+use crate::support_handlers::version_support;
+
+const PUBLIC_CACHE_CONTROL: &str = "public, max-age=31536000, immutable";
+/// Private contracts' artifacts must not land in shared caches
+const PRIVATE_CACHE_CONTROL: &str = "private, max-age=31536000, immutable";
+
+/// Strong validator for content named by its hash
+pub fn artifact_etag(sha256: &str) -> HeaderValue {
+    HeaderValue::from_str(&format!("\"{}\"", sha256))
+        .unwrap_or_else(|_| HeaderValue::from_static("\"\""))
+}
+
+/// Lowercase hex SHA-256, or 400
+fn parse_sha256(raw: &str) -> ApiResult<String> {
+    let hash = raw.to_ascii_lowercase();
+    if hash.len() == 64 && hash.bytes().all(|b| b.is_ascii_hexdigit()) {
+        Ok(hash)
+    } else {
+        Err(ApiError::bad_request(
+            "InvalidArtifactHash",
+            "Artifacts are addressed by a 64-character hex SHA-256",
+        ))
+    }
+}
+
+fn content_type(kind: &str) -> &'static str {
+    match kind {
+        "wasm" => "application/wasm",
+        "archive" => "application/gzip",
+        "abi" | "tests" | "budgets" => "application/json",
+        "readme" => "text/markdown; charset=utf-8",
+        _ => "application/octet-stream",
+    }
+}
+
+/// Does an `If-None-Match` value name `etag` (or `*`)?
+pub(crate) fn if_none_match_hits(headers: &HeaderMap, etag: &HeaderValue) -> bool {
+    let Some(Ok(value)) = headers.get(header::IF_NONE_MATCH).map(|v| v.to_str()) else {
+        return false;
+    };
+    let Ok(etag) = etag.to_str() else {
+        return false;
+    };
+    value.split(',').map(str::trim).any(|candidate| {
+        candidate == "*" || candidate.strip_prefix("W/").unwrap_or(candidate) == etag
+    })
+}
+
+/// GET /api/artifacts/:sha256 — the stored file or archive with that hash
+pub async fn get_artifact(
+    State(state): State<AppState>,
+    viewer: Viewer,
+    Path(raw): Path<String>,
+    headers: HeaderMap,
+) -> ApiResult<Response> {
+    let sha256 = parse_sha256(&raw)?;
+
+    // Prefer a public copy when the same bytes were published more than once
+    let sql = format!(
+        "SELECT kind, content, is_private FROM (\
+             SELECT a.kind, a.content, c.is_private FROM contract_version_artifacts a \
+             JOIN contract_versions v ON v.id = a.version_id \
+             JOIN contracts c ON c.id = v.contract_id \
+             WHERE a.sha256 = $1{visible} \
+             UNION ALL \
+             SELECT 'archive', x.content, c.is_private FROM contract_archives x \
+             JOIN contracts c ON c.id = x.contract_id \
+             WHERE x.sha256 = $1{visible}\
+         ) found ORDER BY is_private LIMIT 1",
+        visible = viewer.visibility_clause("c.")
+    );
+    let (kind, content, is_private): (String, Vec<u8>, bool) = sqlx::query_as(&sql)
+        .bind(&sha256)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(|err| db_internal_error("fetch artifact", err))?
+        .ok_or_else(|| {
+            ApiError::not_found(
+                "ArtifactNotFound",
+                format!("No artifact with SHA-256 {}", sha256),
+            )
+        })?;
+
+    let etag = artifact_etag(&sha256);
+    let cache_control = HeaderValue::from_static(if is_private {
+        PRIVATE_CACHE_CONTROL
+    } else {
+        PUBLIC_CACHE_CONTROL
+    });
+    if if_none_match_hits(&headers, &etag) {
+        return Ok((
+            StatusCode::NOT_MODIFIED,
+            [(header::ETAG, etag), (header::CACHE_CONTROL, cache_control)],
+        )
+            .into_response());
+    }
+    Ok((
+        [
+            (
+                header::CONTENT_TYPE,
+                HeaderValue::from_static(content_type(&kind)),
+            ),
+            (header::ETAG, etag),
+            (header::CACHE_CONTROL, cache_control),
+        ],
+        content,
+    )
+        .into_response())
+}
+
+/// GET /api/contracts/:id/artifacts — hashes of a version's files and archive
+/// (`?version=`, default latest), for fetching them from /api/artifacts
+pub async fn list_version_artifacts(
+    State(state): State<AppState>,
+    viewer: Viewer,
+    Path(id): Path<String>,
+    Query(query): Query<ArchiveQuery>,
+) -> ApiResult<Json<VersionArtifacts>> {
+    let (contract_uuid, contract_id) =
+        fetch_contract_identity(&state, viewer.tenant_id, &id).await?;
+    ensure_contract_visible(&state, &viewer, contract_uuid).await?;
+    let version = fetch_version(&state, contract_uuid, query.version.as_deref())
+        .await?
+        .ok_or_else(|| {
+            ApiError::not_found(
+                "VersionNotFound",
+                format!("Contract {} has no published versions", contract_id),
+            )
+        })?;
+
+    let mut artifacts: Vec<ArtifactRef> = sqlx::query_as(
+        "SELECT kind, sha256, OCTET_LENGTH(content)::BIGINT AS size_bytes \
+         FROM contract_version_artifacts WHERE version_id = $1 ORDER BY kind",
+    )
+    .bind(version.id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("list version artifacts", err))?;
+
+    let contract: Contract = sqlx::query_as("SELECT * FROM contracts WHERE id = $1")
+        .bind(contract_uuid)
+        .fetch_one(&state.db)
+        .await
+        .map_err(|err| db_internal_error("fetch contract", err))?;
+    let (archive, sha256) = snapshot_archive(&state, &contract, Some(&version)).await?;
+    artifacts.push(ArtifactRef {
+        kind: "archive".to_string(),
+        sha256,
+        size_bytes: archive.len() as i64,
+    });
+
+    let support = version_support(&state, version.id).await?;
+    Ok(Json(VersionArtifacts {
+        contract_id,
+        version: version.version,
+        artifacts,
+        support,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hashes_are_normalized_hex() {
+        let upper = "AB".repeat(32);
+        assert_eq!(parse_sha256(&upper).unwrap(), "ab".repeat(32));
+        assert!(parse_sha256("abc").is_err());
+        assert!(parse_sha256(&"zz".repeat(32)).is_err());
+    }
+
+    #[test]
+    fn revalidation_matches_the_hash_etag() {
+        let etag = artifact_etag(&"0".repeat(64));
+        let mut headers = HeaderMap::new();
+        assert!(!if_none_match_hits(&headers, &etag));
+
+        headers.insert(
+            header::IF_NONE_MATCH,
+            HeaderValue::from_str(&format!("\"x\", W/\"{}\"", "0".repeat(64))).unwrap(),
+        );
+        assert!(if_none_match_hits(&headers, &etag));
+
+        headers.insert(header::IF_NONE_MATCH, HeaderValue::from_static("\"x\""));
+        assert!(!if_none_match_hits(&headers, &etag));
+    }
+}
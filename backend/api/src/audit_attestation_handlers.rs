@@ -0,0 +1,231 @@
+// audit_attestation_handlers.rs
+// Formal third-party audit reports attached to contracts (human audit provenance).
+
+use axum::{
+    extract::{rejection::JsonRejection, Path, State},
+    http::StatusCode,
+    Json,
+};
+use shared::{ApiScope, AuditAttestation, CreateAuditAttestationRequest};
+use uuid::Uuid;
+
+use crate::error::{ApiError, ApiResult};
+use crate::handlers::{db_internal_error, fetch_contract_identity, map_json_rejection};
+use crate::private_registry::{ensure_contract_visible, Viewer};
+use crate::state::AppState;
+use crate::tenancy::CurrentTenant;
+use crate::user_auth::AuthUser;
+
+const MAX_AUDITOR_NAME_LENGTH: usize = 255;
+const MAX_SCOPE_LENGTH: usize = 4000;
+const MAX_SUMMARY_LENGTH: usize = 10_000;
+
+fn invalid(message: impl Into<String>) -> ApiError {
+    ApiError::bad_request("InvalidAuditAttestation", message)
+}
+
+fn is_http_url(url: &str) -> bool {
+    url.starts_with("https://") || url.starts_with("http://")
+}
+
+fn validate_attestation(req: &CreateAuditAttestationRequest) -> ApiResult<()> {
+    let auditor = req.auditor_name.trim();
+    if auditor.is_empty() || auditor.len() > MAX_AUDITOR_NAME_LENGTH {
+        return Err(invalid(format!(
+            "auditor_name must be 1-{} characters",
+            MAX_AUDITOR_NAME_LENGTH
+        )));
+    }
+    if !is_http_url(&req.report_url) {
+        return Err(invalid("report_url must be an http(s) URL"));
+    }
+    if req.auditor_url.as_deref().is_some_and(|u| !is_http_url(u)) {
+        return Err(invalid("auditor_url must be an http(s) URL"));
+    }
+    if let Some(digest) = &req.report_sha256 {
+        if digest.len() != 64 || !digest.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(invalid("report_sha256 must be 64 hex characters"));
+        }
+    }
+    let scope = req.scope.trim();
+    if scope.is_empty() || scope.len() > MAX_SCOPE_LENGTH {
+        return Err(invalid(format!(
+            "scope must be 1-{} characters",
+            MAX_SCOPE_LENGTH
+        )));
+    }
+    if req
+        .findings_summary
+        .as_deref()
+        .is_some_and(|s| s.len() > MAX_SUMMARY_LENGTH)
+    {
+        return Err(invalid(format!(
+            "findings_summary must be at most {} characters",
+            MAX_SUMMARY_LENGTH
+        )));
+    }
+    let counts = [
+        req.critical_findings,
+        req.high_findings,
+        req.medium_findings,
+        req.low_findings,
+    ];
+    if counts.iter().any(|&n| n < 0) {
+        return Err(invalid("finding counts cannot be negative"));
+    }
+    if req.audited_at > chrono::Utc::now().date_naive() {
+        return Err(invalid("audited_at cannot be in the future"));
+    }
+    Ok(())
+}
+
+/// Reports are attached by the contract's publisher, who commissioned the audit
+async fn ensure_publisher(state: &AppState, user: &AuthUser, contract_uuid: Uuid) -> ApiResult<()> {
+    let owner: Option<String> = sqlx::query_scalar(
+        "SELECT p.stellar_address FROM contracts c \
+         JOIN publishers p ON p.id = c.publisher_id WHERE c.id = $1",
+    )
+    .bind(contract_uuid)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|err| db_internal_error("fetch contract publisher", err))?;
+    if owner.as_deref() == Some(user.address.as_str()) {
+        Ok(())
+    } else {
+        Err(ApiError::new(
+            StatusCode::FORBIDDEN,
+            "Forbidden",
+            "Only the contract's publisher can attach audit reports",
+        ))
+    }
+}
+
+/// POST /api/contracts/:id/audits
+pub async fn create_audit_attestation(
+    State(state): State<AppState>,
+    tenant: CurrentTenant,
+    user: AuthUser,
+    Path(id): Path<String>,
+    payload: Result<Json<CreateAuditAttestationRequest>, JsonRejection>,
+) -> ApiResult<(StatusCode, Json<AuditAttestation>)> {
+    user.require(ApiScope::Publish)?;
+    let Json(req) = payload.map_err(map_json_rejection)?;
+    validate_attestation(&req)?;
+    let (contract_uuid, _) = fetch_contract_identity(&state, tenant.id, &id).await?;
+    ensure_publisher(&state, &user, contract_uuid).await?;
+
+    if let Some(version) = &req.version {
+        let exists: bool = sqlx::query_scalar(
+            "SELECT EXISTS(SELECT 1 FROM contract_versions \
+             WHERE contract_id = $1 AND version = $2)",
+        )
+        .bind(contract_uuid)
+        .bind(version)
+        .fetch_one(&state.db)
+        .await
+        .map_err(|err| db_internal_error("check audited version", err))?;
+        if !exists {
+            return Err(ApiError::not_found(
+                "VersionNotFound",
+                format!(
+                    "Version {} has not been published for this contract",
+                    version
+                ),
+            ));
+        }
+    }
+
+    let attestation: AuditAttestation = sqlx::query_as(
+        "INSERT INTO audit_attestations \
+             (contract_id, version, auditor_name, auditor_url, report_url, report_sha256, \
+              scope, findings_summary, critical_findings, high_findings, medium_findings, \
+              low_findings, audited_at, submitted_by) \
+         VALUES ($1, $2, $3, $4, $5, LOWER($6), $7, $8, $9, $10, $11, $12, $13, $14) \
+         RETURNING *",
+    )
+    .bind(contract_uuid)
+    .bind(&req.version)
+    .bind(req.auditor_name.trim())
+    .bind(&req.auditor_url)
+    .bind(&req.report_url)
+    .bind(&req.report_sha256)
+    .bind(req.scope.trim())
+    .bind(&req.findings_summary)
+    .bind(req.critical_findings)
+    .bind(req.high_findings)
+    .bind(req.medium_findings)
+    .bind(req.low_findings)
+    .bind(req.audited_at)
+    .bind(&user.address)
+    .fetch_one(&state.db)
+    .await
+    .map_err(|err| db_internal_error("create audit attestation", err))?;
+
+    Ok((StatusCode::CREATED, Json(attestation)))
+}
+
+/// GET /api/contracts/:id/audits — most recent audit first
+pub async fn list_audit_attestations(
+    State(state): State<AppState>,
+    viewer: Viewer,
+    Path(id): Path<String>,
+) -> ApiResult<Json<Vec<AuditAttestation>>> {
+    let (contract_uuid, _) = fetch_contract_identity(&state, viewer.tenant_id, &id).await?;
+    ensure_contract_visible(&state, &viewer, contract_uuid).await?;
+    let attestations: Vec<AuditAttestation> = sqlx::query_as(
+        "SELECT * FROM audit_attestations WHERE contract_id = $1 \
+         ORDER BY audited_at DESC, created_at DESC",
+    )
+    .bind(contract_uuid)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("list audit attestations", err))?;
+    Ok(Json(attestations))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn request() -> CreateAuditAttestationRequest {
+        CreateAuditAttestationRequest {
+            version: Some("1.0.0".to_string()),
+            auditor_name: "Acme Security".to_string(),
+            auditor_url: None,
+            report_url: "https://acme.example/reports/amm.pdf".to_string(),
+            report_sha256: None,
+            scope: "src/lib.rs at 1.0.0".to_string(),
+            findings_summary: None,
+            critical_findings: 0,
+            high_findings: 1,
+            medium_findings: 2,
+            low_findings: 0,
+            audited_at: NaiveDate::from_ymd_opt(2025, 6, 1).unwrap(),
+        }
+    }
+
+    #[test]
+    fn accepts_a_complete_report() {
+        assert!(validate_attestation(&request()).is_ok());
+    }
+
+    #[test]
+    fn rejects_bad_links_digests_and_counts() {
+        let mut req = request();
+        req.report_url = "ftp://acme.example/report.pdf".to_string();
+        assert!(validate_attestation(&req).is_err());
+
+        let mut req = request();
+        req.report_sha256 = Some("abc".to_string());
+        assert!(validate_attestation(&req).is_err());
+
+        let mut req = request();
+        req.high_findings = -1;
+        assert!(validate_attestation(&req).is_err());
+
+        let mut req = request();
+        req.scope = "  ".to_string();
+        assert!(validate_attestation(&req).is_err());
+    }
+}
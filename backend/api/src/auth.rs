@@ -10,6 +10,14 @@ pub struct AuthClaims {
     pub sub: String,
     pub iat: i64,
     pub exp: i64,
+    /// Slug of the tenant the token was issued by; tokens from before tenants
+    /// existed belong to the default registry
+    #[serde(default = "default_realm")]
+    pub realm: String,
+}
+
+fn default_realm() -> String {
+    crate::tenancy::DEFAULT_TENANT_SLUG.to_string()
 }
 
 #[derive(Debug, Clone)]
@@ -60,6 +68,7 @@ impl AuthManager {
         address: &str,
         public_key_hex: &str,
         signature_hex: &str,
+        realm: &str,
     ) -> Result<String, &'static str> {
         let challenge = self
             .challenges
@@ -83,6 +92,7 @@ impl AuthManager {
             sub: address.to_string(),
             iat,
             exp,
+            realm: realm.to_string(),
         };
         encode(&Header::default(), &claims, &self.encoding_key).map_err(|_| "jwt_encode_failed")
     }
@@ -146,10 +156,11 @@ mod tests {
         let nonce = auth.create_challenge(&vk_hex);
         let sig = sk.sign(nonce.as_bytes());
         let token = auth
-            .verify_and_issue_jwt(&vk_hex, &vk_hex, &hex_encode(&sig.to_bytes()))
+            .verify_and_issue_jwt(&vk_hex, &vk_hex, &hex_encode(&sig.to_bytes()), "acme")
             .expect("jwt must be issued");
         let claims = auth.validate_jwt(&token).expect("token must be valid");
         assert_eq!(claims.sub, vk_hex);
+        assert_eq!(claims.realm, "acme");
     }
 
     #[test]
@@ -161,9 +172,9 @@ mod tests {
         let nonce = auth.create_challenge(&vk_hex);
         let sig = sk.sign(nonce.as_bytes());
         let sig_hex = hex_encode(&sig.to_bytes());
-        let first = auth.verify_and_issue_jwt(&vk_hex, &vk_hex, &sig_hex);
+        let first = auth.verify_and_issue_jwt(&vk_hex, &vk_hex, &sig_hex, "public");
         assert!(first.is_ok());
-        let second = auth.verify_and_issue_jwt(&vk_hex, &vk_hex, &sig_hex);
+        let second = auth.verify_and_issue_jwt(&vk_hex, &vk_hex, &sig_hex, "public");
         assert!(second.is_err());
     }
 }
@@ -8,6 +8,7 @@ use serde::{Deserialize, Serialize};
 use crate::{
     error::{ApiError, ApiResult},
     state::AppState,
+    tenancy::CurrentTenant,
 };
 
 #[derive(Debug, Deserialize)]
@@ -57,6 +58,7 @@ pub async fn get_challenge(
 
 pub async fn verify_challenge(
     State(state): State<AppState>,
+    tenant: CurrentTenant,
     Json(payload): Json<VerifyRequest>,
 ) -> Result<(StatusCode, Json<VerifyResponse>), ApiError> {
     if payload.address.trim().is_empty()
@@ -70,7 +72,12 @@ pub async fn verify_challenge(
     }
     let mut mgr = state.auth_mgr.write().unwrap();
     let token = mgr
-        .verify_and_issue_jwt(&payload.address, &payload.public_key, &payload.signature)
+        .verify_and_issue_jwt(
+            &payload.address,
+            &payload.public_key,
+            &payload.signature,
+            &tenant.slug,
+        )
         .map_err(|_| {
             ApiError::new(
                 StatusCode::UNAUTHORIZED,
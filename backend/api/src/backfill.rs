@@ -0,0 +1,629 @@
+// backfill.rs
+// Resumable jobs that repair legacy rows in batches: missing WASM hashes, specs
+// of binaries uploaded before ABIs were required, and free-text categories and
+// tags. Each batch records a checkpoint, so runs can stop and pick up later.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use shared::{BackfillState, BackfillStatus};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::error::{ApiError, ApiResult};
+use crate::handlers::db_internal_error;
+use crate::state::AppState;
+use crate::type_safety::{extract_wasm_spec, parse_contract_abi};
+
+/// Rows per batch; every batch commits and checkpoints on its own
+const BATCH_SIZE: i64 = 100;
+/// A running job whose heartbeat is older than this is presumed dead
+const STALE_AFTER_SECONDS: i64 = 300;
+const CATEGORY_TAG_PREFIX: &str = "category";
+
+/// What one batch did
+#[derive(Debug, Default)]
+pub struct BatchOutcome {
+    /// Last row examined; None once the job has run out of rows
+    pub last: Option<Uuid>,
+    pub processed: i64,
+    pub updated: i64,
+    pub failed: i64,
+}
+
+impl BatchOutcome {
+    /// Continue after `ids` unless the batch came back short
+    fn after(ids: &[Uuid], limit: i64) -> Self {
+        Self {
+            last: ids.last().copied().filter(|_| ids.len() as i64 >= limit),
+            processed: ids.len() as i64,
+            ..Default::default()
+        }
+    }
+}
+
+/// A backfill over rows ordered by UUID. Batches must be idempotent: a batch
+/// interrupted before its checkpoint was saved runs again.
+#[async_trait]
+pub trait BackfillJob: Send + Sync {
+    fn name(&self) -> &'static str;
+    fn description(&self) -> &'static str;
+    /// Process up to `limit` rows after `after`
+    async fn run_batch(
+        &self,
+        pool: &PgPool,
+        after: Option<Uuid>,
+        limit: i64,
+    ) -> Result<BatchOutcome, sqlx::Error>;
+}
+
+/// The jobs admins can run, built-ins first
+pub struct Backfills {
+    jobs: Vec<Arc<dyn BackfillJob>>,
+}
+
+impl Default for Backfills {
+    fn default() -> Self {
+        let mut backfills = Self { jobs: Vec::new() };
+        backfills.register(WasmHashes);
+        backfills.register(WasmSpecs);
+        backfills.register(Taxonomy);
+        backfills
+    }
+}
+
+impl Backfills {
+    pub fn register(&mut self, job: impl BackfillJob + 'static) {
+        self.jobs.push(Arc::new(job));
+    }
+
+    fn get(&self, name: &str) -> ApiResult<Arc<dyn BackfillJob>> {
+        self.jobs
+            .iter()
+            .find(|job| job.name() == name)
+            .cloned()
+            .ok_or_else(|| {
+                ApiError::not_found("BackfillNotFound", format!("No backfill named '{}'", name))
+            })
+    }
+}
+
+fn is_sha256(value: &str) -> bool {
+    value.len() == 64 && value.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+/// Fill in `wasm_hash` on versions (and their contracts) from the stored binary
+struct WasmHashes;
+
+#[async_trait]
+impl BackfillJob for WasmHashes {
+    fn name(&self) -> &'static str {
+        "wasm_hashes"
+    }
+
+    fn description(&self) -> &'static str {
+        "Compute missing WASM hashes of versions and contracts from uploaded binaries"
+    }
+
+    async fn run_batch(
+        &self,
+        pool: &PgPool,
+        after: Option<Uuid>,
+        limit: i64,
+    ) -> Result<BatchOutcome, sqlx::Error> {
+        let rows: Vec<(Uuid, Uuid, String, Option<String>)> = sqlx::query_as(
+            "SELECT v.id, v.contract_id, v.wasm_hash, a.sha256 FROM contract_versions v \
+             LEFT JOIN contract_version_artifacts a ON a.version_id = v.id AND a.kind = 'wasm' \
+             WHERE $1::uuid IS NULL OR v.id > $1 ORDER BY v.id LIMIT $2",
+        )
+        .bind(after)
+        .bind(limit)
+        .fetch_all(pool)
+        .await?;
+        let ids: Vec<Uuid> = rows.iter().map(|row| row.0).collect();
+        let mut outcome = BatchOutcome::after(&ids, limit);
+
+        let mut tx = pool.begin().await?;
+        for (version_id, contract_uuid, wasm_hash, stored) in rows {
+            if is_sha256(&wasm_hash) {
+                continue;
+            }
+            let Some(sha256) = stored else {
+                // Nothing uploaded to hash
+                outcome.failed += 1;
+                continue;
+            };
+            sqlx::query("UPDATE contract_versions SET wasm_hash = $2 WHERE id = $1")
+                .bind(version_id)
+                .bind(&sha256)
+                .execute(&mut *tx)
+                .await?;
+            sqlx::query(
+                "UPDATE contracts SET wasm_hash = (\
+                     SELECT wasm_hash FROM contract_versions WHERE contract_id = $1 \
+                     ORDER BY created_at DESC LIMIT 1) \
+                 WHERE id = $1 AND wasm_hash !~* '^[0-9a-f]{64}$'",
+            )
+            .bind(contract_uuid)
+            .execute(&mut *tx)
+            .await?;
+            outcome.updated += 1;
+        }
+        tx.commit().await?;
+        Ok(outcome)
+    }
+}
+
+/// Store the ABI embedded in binaries of versions that were published without one
+struct WasmSpecs;
+
+#[async_trait]
+impl BackfillJob for WasmSpecs {
+    fn name(&self) -> &'static str {
+        "wasm_specs"
+    }
+
+    fn description(&self) -> &'static str {
+        "Extract contract specs from uploaded binaries of versions that have no ABI"
+    }
+
+    async fn run_batch(
+        &self,
+        pool: &PgPool,
+        after: Option<Uuid>,
+        limit: i64,
+    ) -> Result<BatchOutcome, sqlx::Error> {
+        let rows: Vec<(Uuid, Uuid, String, String, Vec<u8>)> = sqlx::query_as(
+            "SELECT v.id, v.contract_id, v.version, c.contract_id, a.content \
+             FROM contract_versions v \
+             JOIN contracts c ON c.id = v.contract_id \
+             JOIN contract_version_artifacts a ON a.version_id = v.id AND a.kind = 'wasm' \
+             WHERE ($1::uuid IS NULL OR v.id > $1) \
+               AND NOT EXISTS (SELECT 1 FROM contract_abis x \
+                               WHERE x.contract_id = v.contract_id AND x.version = v.version) \
+             ORDER BY v.id LIMIT $2",
+        )
+        .bind(after)
+        .bind(limit)
+        .fetch_all(pool)
+        .await?;
+        let ids: Vec<Uuid> = rows.iter().map(|row| row.0).collect();
+        let mut outcome = BatchOutcome::after(&ids, limit);
+
+        for (_, contract_uuid, version, contract_id, wasm) in rows {
+            let specs = match extract_wasm_spec(&wasm) {
+                Ok(Some(specs)) => specs,
+                // Not built with the Soroban SDK; there is nothing to extract
+                Ok(None) => continue,
+                Err(err) => {
+                    tracing::warn!(
+                        contract = %contract_id,
+                        %version,
+                        error = %err,
+                        "backfill: unreadable contract spec"
+                    );
+                    outcome.failed += 1;
+                    continue;
+                }
+            };
+            if let Err(err) = parse_contract_abi(&specs, &contract_id) {
+                tracing::warn!(
+                    contract = %contract_id,
+                    %version,
+                    error = %err,
+                    "backfill: extracted spec does not parse"
+                );
+                outcome.failed += 1;
+                continue;
+            }
+            let abi = serde_json::to_value(&specs).unwrap_or_default();
+            sqlx::query(
+                "INSERT INTO contract_abis (contract_id, version, abi) VALUES ($1, $2, $3) \
+                 ON CONFLICT (contract_id, version) DO NOTHING",
+            )
+            .bind(contract_uuid)
+            .bind(&version)
+            .bind(&abi)
+            .execute(pool)
+            .await?;
+            outcome.updated += 1;
+        }
+        Ok(outcome)
+    }
+}
+
+/// Lowercase, hyphen-separated form of a free-text label; None when blank
+pub fn normalize_label(raw: &str) -> Option<String> {
+    let label = raw
+        .to_lowercase()
+        .split(|c: char| c.is_whitespace() || c == '_' || c == '-')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+    (!label.is_empty()).then_some(label)
+}
+
+/// `prefix:name` tags keep their prefix; aliases map to their canonical tag
+fn normalize_tag(raw: &str, aliases: &HashMap<String, String>) -> Option<String> {
+    let tag = match raw.split_once(':') {
+        Some((prefix, name)) => match (normalize_label(prefix), normalize_label(name)) {
+            (Some(prefix), Some(name)) => format!("{}:{}", prefix, name),
+            (None, Some(name)) => name,
+            (_, None) => return None,
+        },
+        None => normalize_label(raw)?,
+    };
+    Some(aliases.get(&tag).cloned().unwrap_or(tag))
+}
+
+/// Normalized tags in their original order, without duplicates
+fn normalize_tags(tags: &[String], aliases: &HashMap<String, String>) -> Vec<String> {
+    let mut normalized = Vec::new();
+    for tag in tags.iter().filter_map(|tag| normalize_tag(tag, aliases)) {
+        if !normalized.contains(&tag) {
+            normalized.push(tag);
+        }
+    }
+    normalized
+}
+
+/// A contract tag as a `tags` row
+fn split_tag(tag: &str) -> (&str, &str) {
+    tag.split_once(':').unwrap_or(("", tag))
+}
+
+/// Normalize free-text categories and tags and record them in the tag tables
+struct Taxonomy;
+
+#[async_trait]
+impl BackfillJob for Taxonomy {
+    fn name(&self) -> &'static str {
+        "taxonomy"
+    }
+
+    fn description(&self) -> &'static str {
+        "Normalize legacy free-text categories and tags into the tags and tag_aliases tables"
+    }
+
+    async fn run_batch(
+        &self,
+        pool: &PgPool,
+        after: Option<Uuid>,
+        limit: i64,
+    ) -> Result<BatchOutcome, sqlx::Error> {
+        let aliases: HashMap<String, String> = sqlx::query_as::<_, (String, String, String)>(
+            "SELECT ta.alias, t.prefix, t.name FROM tag_aliases ta \
+             JOIN tags t ON t.id = ta.canonical_tag_id",
+        )
+        .fetch_all(pool)
+        .await?
+        .into_iter()
+        .filter_map(|(alias, prefix, name)| {
+            let canonical = if prefix.is_empty() {
+                name
+            } else {
+                format!("{}:{}", prefix, name)
+            };
+            normalize_tag(&alias, &HashMap::new()).map(|alias| (alias, canonical))
+        })
+        .collect();
+
+        let rows: Vec<(Uuid, Option<String>, Vec<String>)> = sqlx::query_as(
+            "SELECT id, category, tags FROM contracts \
+             WHERE $1::uuid IS NULL OR id > $1 ORDER BY id LIMIT $2",
+        )
+        .bind(after)
+        .bind(limit)
+        .fetch_all(pool)
+        .await?;
+        let ids: Vec<Uuid> = rows.iter().map(|row| row.0).collect();
+        let mut outcome = BatchOutcome::after(&ids, limit);
+
+        let mut tx = pool.begin().await?;
+        // Normalizing is not an edit by the publisher
+        sqlx::query("SET LOCAL registry.preserve_updated_at = 'on'")
+            .execute(&mut *tx)
+            .await?;
+        let (mut prefixes, mut names) = (Vec::new(), Vec::new());
+        for (id, category, tags) in rows {
+            let new_category = category.as_deref().and_then(normalize_label);
+            let new_tags = normalize_tags(&tags, &aliases);
+            if let Some(category) = &new_category {
+                prefixes.push(CATEGORY_TAG_PREFIX.to_string());
+                names.push(category.clone());
+            }
+            for tag in &new_tags {
+                let (prefix, name) = split_tag(tag);
+                prefixes.push(prefix.to_string());
+                names.push(name.to_string());
+            }
+            if new_category == category && new_tags == tags {
+                continue;
+            }
+            sqlx::query("UPDATE contracts SET category = $2, tags = $3 WHERE id = $1")
+                .bind(id)
+                .bind(&new_category)
+                .bind(&new_tags)
+                .execute(&mut *tx)
+                .await?;
+            outcome.updated += 1;
+        }
+        sqlx::query(
+            "INSERT INTO tags (prefix, name) SELECT * FROM UNNEST($1::text[], $2::text[]) \
+             ON CONFLICT (prefix, name) DO NOTHING",
+        )
+        .bind(&prefixes)
+        .bind(&names)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(outcome)
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct JobRow {
+    name: String,
+    state: BackfillState,
+    checkpoint: Option<Uuid>,
+    processed: i64,
+    updated: i64,
+    failed: i64,
+    last_error: Option<String>,
+    started_at: DateTime<Utc>,
+    heartbeat_at: DateTime<Utc>,
+    completed_at: Option<DateTime<Utc>>,
+}
+
+fn status_of(job: &dyn BackfillJob, row: Option<JobRow>) -> BackfillStatus {
+    let mut status = BackfillStatus {
+        name: job.name().to_string(),
+        description: job.description().to_string(),
+        state: None,
+        checkpoint: None,
+        processed: 0,
+        updated: 0,
+        failed: 0,
+        last_error: None,
+        started_at: None,
+        heartbeat_at: None,
+        completed_at: None,
+    };
+    if let Some(row) = row {
+        status.state = Some(row.state);
+        status.checkpoint = row.checkpoint;
+        status.processed = row.processed;
+        status.updated = row.updated;
+        status.failed = row.failed;
+        status.last_error = row.last_error;
+        status.started_at = Some(row.started_at);
+        status.heartbeat_at = Some(row.heartbeat_at);
+        status.completed_at = row.completed_at;
+    }
+    status
+}
+
+async fn fetch_row(state: &AppState, name: &str) -> ApiResult<Option<JobRow>> {
+    sqlx::query_as("SELECT * FROM backfill_jobs WHERE name = $1")
+        .bind(name)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(|err| db_internal_error("fetch backfill status", err))
+}
+
+/// Mark the job running unless a live run holds it. Completed jobs and
+/// restarts begin again from the first row; paused and failed ones resume.
+async fn claim(state: &AppState, name: &str, restart: bool) -> ApiResult<Option<JobRow>> {
+    sqlx::query_as(
+        "INSERT INTO backfill_jobs (name, state) VALUES ($1, 'running') \
+         ON CONFLICT (name) DO UPDATE SET \
+             state = 'running', last_error = NULL, heartbeat_at = NOW(), completed_at = NULL, \
+             started_at = CASE WHEN $2 OR backfill_jobs.state = 'completed' \
+                 THEN NOW() ELSE backfill_jobs.started_at END, \
+             checkpoint = CASE WHEN $2 OR backfill_jobs.state = 'completed' \
+                 THEN NULL ELSE backfill_jobs.checkpoint END, \
+             processed = CASE WHEN $2 OR backfill_jobs.state = 'completed' \
+                 THEN 0 ELSE backfill_jobs.processed END, \
+             updated = CASE WHEN $2 OR backfill_jobs.state = 'completed' \
+                 THEN 0 ELSE backfill_jobs.updated END, \
+             failed = CASE WHEN $2 OR backfill_jobs.state = 'completed' \
+                 THEN 0 ELSE backfill_jobs.failed END \
+         WHERE backfill_jobs.state <> 'running' \
+            OR backfill_jobs.heartbeat_at < NOW() - make_interval(secs => $3) \
+         RETURNING *",
+    )
+    .bind(name)
+    .bind(restart)
+    .bind(STALE_AFTER_SECONDS as f64)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|err| db_internal_error("claim backfill", err))
+}
+
+async fn run(
+    state: AppState,
+    job: Arc<dyn BackfillJob>,
+    mut after: Option<Uuid>,
+    batches: Option<u32>,
+) {
+    let name = job.name();
+    let mut done = 0u32;
+    loop {
+        let outcome = match job.run_batch(&state.db, after, BATCH_SIZE).await {
+            Ok(outcome) => outcome,
+            Err(err) => {
+                tracing::error!(backfill = name, error = ?err, "backfill batch failed");
+                let _ = sqlx::query(
+                    "UPDATE backfill_jobs SET state = 'failed', last_error = $2, \
+                         heartbeat_at = NOW() WHERE name = $1",
+                )
+                .bind(name)
+                .bind(err.to_string())
+                .execute(&state.db)
+                .await;
+                return;
+            }
+        };
+        done += 1;
+        let finished = outcome.last.is_none();
+        let paused = !finished && batches.is_some_and(|max| done >= max);
+        let next_state = if finished {
+            BackfillState::Completed
+        } else if paused {
+            BackfillState::Paused
+        } else {
+            BackfillState::Running
+        };
+        if let Err(err) = sqlx::query(
+            "UPDATE backfill_jobs SET state = $2, checkpoint = COALESCE($3, checkpoint), \
+                 processed = processed + $4, updated = updated + $5, failed = failed + $6, \
+                 heartbeat_at = NOW(), completed_at = CASE WHEN $7 THEN NOW() END \
+             WHERE name = $1",
+        )
+        .bind(name)
+        .bind(next_state)
+        .bind(outcome.last)
+        .bind(outcome.processed)
+        .bind(outcome.updated)
+        .bind(outcome.failed)
+        .bind(finished)
+        .execute(&state.db)
+        .await
+        {
+            // The batch is idempotent; a later run repeats it
+            tracing::error!(backfill = name, error = ?err, "saving backfill checkpoint failed");
+            return;
+        }
+        if finished || paused {
+            tracing::info!(backfill = name, state = ?next_state, "backfill stopped");
+            return;
+        }
+        after = outcome.last;
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RunBackfillQuery {
+    /// Start over from the first row instead of the checkpoint
+    #[serde(default)]
+    pub restart: bool,
+    /// Pause after this many batches of 100 rows
+    pub batches: Option<u32>,
+}
+
+/// GET /api/admin/backfills — every backfill job and its progress
+pub async fn list_backfills(State(state): State<AppState>) -> ApiResult<Json<Vec<BackfillStatus>>> {
+    let mut rows: HashMap<String, JobRow> =
+        sqlx::query_as::<_, JobRow>("SELECT * FROM backfill_jobs")
+            .fetch_all(&state.db)
+            .await
+            .map_err(|err| db_internal_error("list backfills", err))?
+            .into_iter()
+            .map(|row| (row.name.clone(), row))
+            .collect();
+    Ok(Json(
+        state
+            .backfills
+            .jobs
+            .iter()
+            .map(|job| status_of(job.as_ref(), rows.remove(job.name())))
+            .collect(),
+    ))
+}
+
+/// GET /api/admin/backfills/:name — one job's progress
+pub async fn get_backfill(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+) -> ApiResult<Json<BackfillStatus>> {
+    let job = state.backfills.get(&name)?;
+    let row = fetch_row(&state, job.name()).await?;
+    Ok(Json(status_of(job.as_ref(), row)))
+}
+
+/// POST /api/admin/backfills/:name/run — start or resume a job in the background
+pub async fn run_backfill(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+    Query(query): Query<RunBackfillQuery>,
+) -> ApiResult<(StatusCode, Json<BackfillStatus>)> {
+    let job = state.backfills.get(&name)?;
+    if query.batches == Some(0) {
+        return Err(ApiError::bad_request(
+            "InvalidBatches",
+            "batches must be greater than 0",
+        ));
+    }
+    let Some(row) = claim(&state, job.name(), query.restart).await? else {
+        return Err(ApiError::conflict(
+            "BackfillRunning",
+            format!("Backfill '{}' is already running", job.name()),
+        ));
+    };
+    tracing::info!(backfill = job.name(), checkpoint = ?row.checkpoint, "backfill started");
+    tokio::spawn(run(
+        state.clone(),
+        job.clone(),
+        row.checkpoint,
+        query.batches,
+    ));
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(status_of(job.as_ref(), Some(row))),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn labels_are_lowercase_and_hyphenated() {
+        assert_eq!(
+            normalize_label("  DeFi  Lending_Pool ").unwrap(),
+            "defi-lending-pool"
+        );
+        assert_eq!(normalize_label("nft--market").unwrap(), "nft-market");
+        assert_eq!(normalize_label(" _ "), None);
+    }
+
+    #[test]
+    fn tags_resolve_aliases_and_drop_duplicates() {
+        let aliases = HashMap::from([("stablecoin".to_string(), "token:stable".to_string())]);
+        let tags = vec![
+            "StableCoin".to_string(),
+            "Token : Stable".to_string(),
+            "DEX".to_string(),
+            ":".to_string(),
+            "dex".to_string(),
+        ];
+        assert_eq!(normalize_tags(&tags, &aliases), vec!["token:stable", "dex"]);
+        assert_eq!(split_tag("token:stable"), ("token", "stable"));
+        assert_eq!(split_tag("dex"), ("", "dex"));
+    }
+
+    #[test]
+    fn short_batches_finish_the_job() {
+        let ids = vec![Uuid::new_v4(), Uuid::new_v4()];
+        assert_eq!(BatchOutcome::after(&ids, 2).last, Some(ids[1]));
+        assert_eq!(BatchOutcome::after(&ids, 3).last, None);
+        assert_eq!(BatchOutcome::after(&[], 3).processed, 0);
+    }
+
+    #[test]
+    fn builtin_jobs_are_registered() {
+        let backfills = Backfills::default();
+        for name in ["wasm_hashes", "wasm_specs", "taxonomy"] {
+            assert!(backfills.get(name).is_ok());
+        }
+        assert!(backfills.get("everything").is_err());
+    }
+}
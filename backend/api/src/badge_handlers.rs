@@ -0,0 +1,341 @@
+// badge_handlers.rs
+// Embeddable SVG badges showing a contract's latest version, download count or
+// verification status, or whether a publisher has verified their Stellar account.
+// `/badge/<kind>/<name>.svg` serves the contract ones shields.io-style, by name.
+
+use axum::{
+    extract::{Path, Query, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use crate::artifact_handlers::if_none_match_hits;
+use crate::error::{ApiError, ApiResult};
+use crate::feed_handlers::xml_escape;
+use crate::handlers::{db_internal_error, fetch_contract_identity};
+use crate::private_registry::{ensure_contract_visible, Viewer};
+use crate::state::AppState;
+
+const SVG_CONTENT_TYPE: &str = "image/svg+xml; charset=utf-8";
+/// Badges are embedded in READMEs on other sites; let their caches hold them briefly
+const BADGE_CACHE_CONTROL: &str = "public, max-age=300";
+
+const COLOR_OK: &str = "#4c1";
+const COLOR_INFO: &str = "#007ec6";
+const COLOR_NONE: &str = "#9f9f9f";
+
+const COLOR_WARN: &str = "#dfb317";
+
+#[derive(Debug, Deserialize)]
+pub struct BadgeQuery {
+    /// `version` (default), `downloads` or `verified`
+    pub metric: Option<String>,
+    /// Text for the left half; defaults to the metric name
+    pub label: Option<String>,
+}
+
+/// Approximate rendered width of `text` at 11px Verdana
+fn text_width(text: &str) -> usize {
+    text.chars()
+        .map(|c| match c {
+            'i' | 'l' | 'j' | '.' | ',' | ':' | '|' | '!' | '\'' => 4,
+            'm' | 'w' | 'M' | 'W' => 10,
+            c if c.is_ascii_uppercase() => 8,
+            _ => 7,
+        })
+        .sum()
+}
+
+/// Two-part badge in the common flat style
+fn render_badge(label: &str, value: &str, color: &str) -> String {
+    let label_width = text_width(label) + 10;
+    let value_width = text_width(value) + 10;
+    let width = label_width + value_width;
+    let (label, value) = (xml_escape(label), xml_escape(value));
+    format!(
+        concat!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"20\" ",
+            "role=\"img\" aria-label=\"{l}: {v}\">",
+            "<title>{l}: {v}</title>",
+            "<linearGradient id=\"s\" x2=\"0\" y2=\"100%\">",
+            "<stop offset=\"0\" stop-color=\"#bbb\" stop-opacity=\".1\"/>",
+            "<stop offset=\"1\" stop-opacity=\".1\"/></linearGradient>",
+            "<clipPath id=\"r\">",
+            "<rect width=\"{w}\" height=\"20\" rx=\"3\" fill=\"#fff\"/></clipPath>",
+            "<g clip-path=\"url(#r)\">",
+            "<rect width=\"{lw}\" height=\"20\" fill=\"#555\"/>",
+            "<rect x=\"{lw}\" width=\"{vw}\" height=\"20\" fill=\"{c}\"/>",
+            "<rect width=\"{w}\" height=\"20\" fill=\"url(#s)\"/></g>",
+            "<g fill=\"#fff\" text-anchor=\"middle\" ",
+            "font-family=\"Verdana,Geneva,DejaVu Sans,sans-serif\" font-size=\"11\">",
+            "<text x=\"{lx}\" y=\"14\">{l}</text>",
+            "<text x=\"{vx}\" y=\"14\">{v}</text></g></svg>"
+        ),
+        w = width,
+        lw = label_width,
+        vw = value_width,
+        c = color,
+        l = label,
+        v = value,
+        lx = label_width / 2,
+        vx = label_width + value_width / 2,
+    )
+}
+
+/// 1234 → "1.2k", 5600000 → "5.6M"
+fn compact_count(count: i64) -> String {
+    match count {
+        n if n >= 1_000_000 => format!("{:.1}M", n as f64 / 1_000_000.0),
+        n if n >= 1_000 => format!("{:.1}k", n as f64 / 1_000.0),
+        n => n.to_string(),
+    }
+}
+
+/// What a contract badge shows
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Metric {
+    Version,
+    Downloads,
+    Verified,
+}
+
+impl Metric {
+    /// `?metric=` values, plus the shields.io-style `v` of `/badge/v/...`
+    fn parse(raw: &str) -> ApiResult<Self> {
+        match raw {
+            "version" | "v" => Ok(Metric::Version),
+            "downloads" => Ok(Metric::Downloads),
+            "verified" => Ok(Metric::Verified),
+            other => Err(ApiError::bad_request(
+                "InvalidQuery",
+                format!(
+                    "Unknown badge metric '{}'; use version, downloads or verified",
+                    other
+                ),
+            )),
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Metric::Version => "version",
+            Metric::Downloads => "downloads",
+            Metric::Verified => "registry",
+        }
+    }
+}
+
+async fn metric_value(
+    state: &AppState,
+    contract_uuid: Uuid,
+    metric: Metric,
+) -> ApiResult<(String, &'static str)> {
+    match metric {
+        Metric::Version => {
+            let latest: Option<String> = sqlx::query_scalar(
+                "SELECT version FROM contract_versions WHERE contract_id = $1 \
+                 ORDER BY created_at DESC LIMIT 1",
+            )
+            .bind(contract_uuid)
+            .fetch_optional(&state.db)
+            .await
+            .map_err(|err| db_internal_error("fetch latest version for badge", err))?;
+            Ok(match latest {
+                Some(version) => (format!("v{}", version), COLOR_OK),
+                None => ("unpublished".to_string(), COLOR_NONE),
+            })
+        }
+        Metric::Downloads => {
+            let downloads: i64 = sqlx::query_scalar(
+                "SELECT COUNT(*) FROM contract_interactions WHERE contract_id = $1",
+            )
+            .bind(contract_uuid)
+            .fetch_one(&state.db)
+            .await
+            .map_err(|err| db_internal_error("count downloads for badge", err))?;
+            Ok((compact_count(downloads), COLOR_INFO))
+        }
+        Metric::Verified => {
+            let verified: bool =
+                sqlx::query_scalar("SELECT is_verified FROM contracts WHERE id = $1")
+                    .bind(contract_uuid)
+                    .fetch_one(&state.db)
+                    .await
+                    .map_err(|err| db_internal_error("fetch verification for badge", err))?;
+            Ok(if verified {
+                ("verified".to_string(), COLOR_OK)
+            } else {
+                ("unverified".to_string(), COLOR_WARN)
+            })
+        }
+    }
+}
+
+/// GET /api/contracts/:id/badge.svg?metric=version|downloads|verified
+pub async fn contract_badge(
+    State(state): State<AppState>,
+    viewer: Viewer,
+    Path(id): Path<String>,
+    Query(query): Query<BadgeQuery>,
+    headers: HeaderMap,
+) -> ApiResult<Response> {
+    let metric = Metric::parse(query.metric.as_deref().unwrap_or("version"))?;
+    let (contract_uuid, _) = fetch_contract_identity(&state, viewer.tenant_id, &id).await?;
+    ensure_contract_visible(&state, &viewer, contract_uuid).await?;
+
+    let (value, color) = metric_value(&state, contract_uuid, metric).await?;
+    let label = query.label.as_deref().unwrap_or(metric.label());
+    Ok(svg_response(render_badge(label, &value, color), &headers))
+}
+
+/// Contract named in a badge URL: a registry UUID, an on-chain address,
+/// `@namespace/name`, or a legacy unscoped name
+async fn resolve_badge_contract(state: &AppState, tenant_id: Uuid, name: &str) -> ApiResult<Uuid> {
+    if Uuid::parse_str(name).is_ok()
+        || name.starts_with('@')
+        || crate::validation::validate_contract_id(name).is_ok()
+    {
+        return fetch_contract_identity(state, tenant_id, name)
+            .await
+            .map(|(id, _)| id);
+    }
+    let found: Option<Uuid> = sqlx::query_scalar(
+        "SELECT id FROM contracts WHERE name = $1 AND namespace IS NULL AND tenant_id = $2 \
+         ORDER BY created_at LIMIT 1",
+    )
+    .bind(name)
+    .bind(tenant_id)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|err| db_internal_error("fetch contract for badge", err))?;
+    found.ok_or_else(|| {
+        ApiError::not_found(
+            "ContractNotFound",
+            format!("No contract found named {}", name),
+        )
+    })
+}
+
+/// GET /badge/:kind/*name — `/badge/v/@alice/amm.svg`, `/badge/downloads/...`
+/// or `/badge/verified/...`, for README embeds
+pub async fn named_badge(
+    State(state): State<AppState>,
+    viewer: Viewer,
+    Path((kind, name)): Path<(String, String)>,
+    Query(query): Query<BadgeQuery>,
+    headers: HeaderMap,
+) -> ApiResult<Response> {
+    let name = name.trim_start_matches('/');
+    let name = name.strip_suffix(".svg").ok_or_else(|| {
+        ApiError::not_found("BadgeNotFound", "Badge URLs end in .svg".to_string())
+    })?;
+    let metric = Metric::parse(&kind)?;
+    let contract_uuid = resolve_badge_contract(&state, viewer.tenant_id, name).await?;
+    ensure_contract_visible(&state, &viewer, contract_uuid).await?;
+
+    let (value, color) = metric_value(&state, contract_uuid, metric).await?;
+    let label = query.label.as_deref().unwrap_or(metric.label());
+    Ok(svg_response(render_badge(label, &value, color), &headers))
+}
+
+/// GET /api/publishers/:id/badge.svg — "verified" once the publisher has signed
+/// an identity challenge with their Stellar account key
+pub async fn publisher_badge(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+) -> ApiResult<Response> {
+    let verified: Option<Option<String>> =
+        sqlx::query_scalar("SELECT verified_account FROM publishers WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&state.db)
+            .await
+            .map_err(|err| db_internal_error("fetch publisher for badge", err))?;
+    let verified = verified.ok_or_else(|| {
+        ApiError::not_found(
+            "PublisherNotFound",
+            format!("No publisher found with ID: {}", id),
+        )
+    })?;
+
+    let (value, color) = match verified {
+        Some(_) => ("verified", COLOR_OK),
+        None => ("unverified", COLOR_NONE),
+    };
+    Ok(svg_response(
+        render_badge("stellar account", value, color),
+        &headers,
+    ))
+}
+
+/// Validator over the rendered badge, so caches can revalidate cheaply
+fn badge_etag(svg: &str) -> HeaderValue {
+    let digest = hex::encode(Sha256::digest(svg.as_bytes()));
+    HeaderValue::from_str(&format!("\"{}\"", &digest[..16]))
+        .unwrap_or_else(|_| HeaderValue::from_static("\"\""))
+}
+
+/// The badge, or 304 when `request` already holds it
+fn svg_response(svg: String, request: &HeaderMap) -> Response {
+    let etag = badge_etag(&svg);
+    let mut response = if if_none_match_hits(request, &etag) {
+        StatusCode::NOT_MODIFIED.into_response()
+    } else {
+        svg.into_response()
+    };
+    let headers = response.headers_mut();
+    headers.insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static(SVG_CONTENT_TYPE),
+    );
+    headers.insert(
+        header::CACHE_CONTROL,
+        HeaderValue::from_static(BADGE_CACHE_CONTROL),
+    );
+    headers.insert(header::ETAG, etag);
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn badge_escapes_text_and_sizes_both_halves() {
+        let svg = render_badge("version", "v1.2.0<beta>", COLOR_OK);
+        assert!(svg.starts_with("<svg "));
+        assert!(svg.contains("v1.2.0&lt;beta&gt;"));
+        assert!(!svg.contains("<beta>"));
+        let width = text_width("version") + text_width("v1.2.0<beta>") + 20;
+        assert!(svg.contains(&format!("width=\"{}\"", width)));
+    }
+
+    #[test]
+    fn download_counts_are_compacted() {
+        assert_eq!(compact_count(999), "999");
+        assert_eq!(compact_count(1_234), "1.2k");
+        assert_eq!(compact_count(5_600_000), "5.6M");
+    }
+
+    #[test]
+    fn badge_kinds_map_to_metrics() {
+        assert_eq!(Metric::parse("v").unwrap(), Metric::Version);
+        assert_eq!(Metric::parse("verified").unwrap(), Metric::Verified);
+        assert!(Metric::parse("stars").is_err());
+    }
+
+    #[test]
+    fn unchanged_badges_revalidate_with_304() {
+        let svg = render_badge("version", "v1.0.0", COLOR_OK);
+        let mut request = HeaderMap::new();
+        assert_eq!(svg_response(svg.clone(), &request).status(), StatusCode::OK);
+
+        request.insert(header::IF_NONE_MATCH, badge_etag(&svg));
+        let response = svg_response(svg, &request);
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+        assert!(response.headers().contains_key(header::CACHE_CONTROL));
+    }
+}
@@ -0,0 +1,247 @@
+// batch_handlers.rs
+// Resolve many contracts (optionally pinned to a version) in one request.
+
+use std::collections::{HashMap, HashSet};
+
+use axum::{
+    extract::{rejection::JsonRejection, State},
+    Json,
+};
+use shared::{
+    ArtifactRef, BatchContractLookupRequest, BatchContractLookupResponse,
+    BatchContractLookupResult, Contract, ContractName, ContractVersion, MAX_BATCH_LOOKUP,
+};
+use uuid::Uuid;
+
+use crate::error::{ApiError, ApiResult};
+use crate::handlers::{db_internal_error, map_json_rejection, resolve_contract_alias};
+use crate::private_registry::Viewer;
+use crate::state::AppState;
+use crate::support_handlers::version_support;
+
+/// An artifact row, with the version it belongs to
+#[derive(sqlx::FromRow)]
+struct VersionArtifactRow {
+    version_id: Uuid,
+    #[sqlx(flatten)]
+    artifact: ArtifactRef,
+}
+
+/// Split `selector@version` into its parts; a bare selector has no version
+fn parse_lookup(query: &str) -> (&str, Option<&str>) {
+    match query.trim().rsplit_once('@') {
+        Some((selector, version)) if !selector.is_empty() && !version.is_empty() => {
+            (selector, Some(version))
+        }
+        _ => (query.trim(), None),
+    }
+}
+
+fn matches_selector(contract: &Contract, selector: &str) -> bool {
+    contract.id.to_string() == selector
+        || contract.contract_id == selector
+        || contract.name == selector
+}
+
+/// POST /api/contracts/batch
+pub async fn batch_lookup_contracts(
+    State(state): State<AppState>,
+    viewer: Viewer,
+    payload: Result<Json<BatchContractLookupRequest>, JsonRejection>,
+) -> ApiResult<Json<BatchContractLookupResponse>> {
+    let Json(req) = payload.map_err(map_json_rejection)?;
+
+    if req.ids.is_empty() {
+        return Err(ApiError::bad_request(
+            "InvalidRequest",
+            "ids must contain at least one contract",
+        ));
+    }
+    if req.ids.len() > MAX_BATCH_LOOKUP {
+        return Err(ApiError::bad_request(
+            "BatchTooLarge",
+            format!(
+                "At most {} contracts can be looked up per request",
+                MAX_BATCH_LOOKUP
+            ),
+        ));
+    }
+
+    let mut uuids: Vec<Uuid> = Vec::new();
+    let mut selectors: Vec<String> = Vec::new();
+    for query in &req.ids {
+        let (selector, _) = parse_lookup(query);
+        match Uuid::parse_str(selector) {
+            Ok(uuid) => uuids.push(uuid),
+            Err(_) => selectors.push(selector.to_string()),
+        }
+    }
+
+    let mut contracts: Vec<Contract> = sqlx::query_as(&format!(
+        "SELECT * FROM contracts \
+         WHERE (id = ANY($1) OR contract_id = ANY($2) OR name = ANY($2)){} \
+         ORDER BY created_at",
+        viewer.visibility_clause("")
+    ))
+    .bind(&uuids)
+    .bind(&selectors)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("batch fetch contracts", err))?;
+
+    // A selector that matches no current name may be a contract's former one
+    let mut renamed: HashMap<&str, Uuid> = HashMap::new();
+    for selector in &selectors {
+        if contracts.iter().any(|c| matches_selector(c, selector)) {
+            continue;
+        }
+        let Ok(name) = ContractName::parse(selector) else {
+            continue;
+        };
+        if let Some(alias) = resolve_contract_alias(&state, viewer.tenant_id, &name).await? {
+            renamed.insert(selector.as_str(), alias.id);
+        }
+    }
+    let missing: Vec<Uuid> = renamed
+        .values()
+        .filter(|id| !contracts.iter().any(|c| c.id == **id))
+        .copied()
+        .collect();
+    if !missing.is_empty() {
+        let aliased: Vec<Contract> = sqlx::query_as(&format!(
+            "SELECT * FROM contracts WHERE id = ANY($1){}",
+            viewer.visibility_clause("")
+        ))
+        .bind(&missing)
+        .fetch_all(&state.db)
+        .await
+        .map_err(|err| db_internal_error("batch fetch renamed contracts", err))?;
+        contracts.extend(aliased);
+    }
+    let find_contract = |selector: &str| {
+        contracts
+            .iter()
+            .find(|c| matches_selector(c, selector) || renamed.get(selector) == Some(&c.id))
+    };
+
+    // Only load versions for contracts that were looked up with a pinned
+    // version, or that have to be resolved to their latest one
+    let pinned: HashSet<Uuid> = req
+        .ids
+        .iter()
+        .filter_map(|query| match parse_lookup(query) {
+            (selector, Some(_)) => find_contract(selector).map(|c| c.id),
+            (selector, None) if req.resolve_artifacts => find_contract(selector).map(|c| c.id),
+            _ => None,
+        })
+        .collect();
+
+    let versions: Vec<ContractVersion> = if pinned.is_empty() {
+        Vec::new()
+    } else {
+        sqlx::query_as(
+            "SELECT * FROM contract_versions WHERE contract_id = ANY($1) ORDER BY created_at DESC",
+        )
+        .bind(pinned.into_iter().collect::<Vec<_>>())
+        .fetch_all(&state.db)
+        .await
+        .map_err(|err| db_internal_error("batch fetch contract versions", err))?
+    };
+
+    let mut results: Vec<BatchContractLookupResult> = req
+        .ids
+        .iter()
+        .map(|query| {
+            let (selector, version) = parse_lookup(query);
+            let contract = find_contract(selector);
+            let mut result = BatchContractLookupResult {
+                query: query.clone(),
+                contract: contract.cloned(),
+                version: None,
+                artifacts: Vec::new(),
+                support: None,
+                error: None,
+            };
+
+            match (contract, version) {
+                (None, _) => result.error = Some(format!("No contract found for '{}'", selector)),
+                (Some(contract), Some(version)) => {
+                    result.version = versions
+                        .iter()
+                        .find(|v| v.contract_id == contract.id && v.version == version)
+                        .cloned();
+                    if result.version.is_none() {
+                        result.error = Some(format!(
+                            "No version {} found for contract {}",
+                            version, selector
+                        ));
+                    }
+                }
+                (Some(contract), None) if req.resolve_artifacts => {
+                    // Versions are newest first
+                    result.version = versions
+                        .iter()
+                        .find(|v| v.contract_id == contract.id)
+                        .cloned();
+                    if result.version.is_none() {
+                        result.error =
+                            Some(format!("Contract {} has no published versions", selector));
+                    }
+                }
+                (Some(_), None) => {}
+            }
+            result
+        })
+        .collect();
+
+    if req.resolve_artifacts {
+        attach_artifacts(&state, &mut results).await?;
+    }
+
+    Ok(Json(BatchContractLookupResponse { results }))
+}
+
+/// Fill in the stored artifacts and support policy of each resolved version
+async fn attach_artifacts(
+    state: &AppState,
+    results: &mut [BatchContractLookupResult],
+) -> ApiResult<()> {
+    let version_ids: Vec<Uuid> = results
+        .iter()
+        .filter_map(|result| result.version.as_ref().map(|v| v.id))
+        .collect();
+    let rows: Vec<VersionArtifactRow> = sqlx::query_as(
+        "SELECT version_id, kind, sha256, OCTET_LENGTH(content)::BIGINT AS size_bytes \
+         FROM contract_version_artifacts WHERE version_id = ANY($1) ORDER BY kind",
+    )
+    .bind(&version_ids)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("batch fetch version artifacts", err))?;
+
+    for result in results.iter_mut() {
+        let Some(version_id) = result.version.as_ref().map(|v| v.id) else {
+            continue;
+        };
+        result.artifacts = rows
+            .iter()
+            .filter(|row| row.version_id == version_id)
+            .map(|row| row.artifact.clone())
+            .collect();
+        result.support = version_support(state, version_id).await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_pinned_and_bare_lookups() {
+        assert_eq!(parse_lookup("token@1.2.0"), ("token", Some("1.2.0")));
+        assert_eq!(parse_lookup(" token "), ("token", None));
+        assert_eq!(parse_lookup("token@"), ("token@", None));
+        assert_eq!(parse_lookup("@scope/token"), ("@scope/token", None));
+    }
+}
@@ -0,0 +1,351 @@
+// body_limit.rs
+// Request body limits per route (`[body_limits]`) and content-type checks.
+// A declared Content-Length over the limit is rejected before any of the body
+// is read; chunked bodies are cut off once they pass it.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use axum::{
+    body::Body,
+    extract::{MatchedPath, State},
+    http::{header, HeaderMap, Method, Request, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use http_body_util::Limited;
+use shared::PROBLEM_JSON_CONTENT_TYPE;
+
+use crate::error::ApiError;
+use crate::rate_limit::endpoint_key;
+use crate::settings::BodyLimitSettings;
+
+pub(crate) const DEFAULT_JSON_BODY_BYTES: u64 = 2 * 1024 * 1024;
+pub(crate) const DEFAULT_UPLOAD_BODY_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Routes taking a raw artifact (WASM, README, ...) rather than JSON
+const UPLOAD_ROUTES: &[(Method, &str)] = &[(Method::PUT, "/api/publish/sessions/:id/parts/:kind")];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BodyKind {
+    Json,
+    Upload,
+}
+
+#[derive(Clone)]
+pub struct BodyLimitState {
+    config: Arc<BodyLimitConfig>,
+}
+
+struct BodyLimitConfig {
+    json_bytes: u64,
+    upload_bytes: u64,
+    endpoint_limits: HashMap<String, u64>,
+}
+
+impl BodyLimitState {
+    pub fn from_settings(settings: &BodyLimitSettings) -> Self {
+        Self {
+            config: Arc::new(BodyLimitConfig {
+                json_bytes: settings.json_bytes,
+                upload_bytes: settings.upload_bytes,
+                endpoint_limits: settings
+                    .endpoints
+                    .iter()
+                    .map(|(key, limit)| (key.clone(), *limit))
+                    .collect(),
+            }),
+        }
+    }
+
+    fn select<B>(&self, request: &Request<B>) -> (BodyKind, u64) {
+        let method = request.method();
+        let path = request
+            .extensions()
+            .get::<MatchedPath>()
+            .map(|p| p.as_str())
+            .unwrap_or_else(|| request.uri().path());
+        let kind = if UPLOAD_ROUTES
+            .iter()
+            .any(|(route_method, route)| route_method == method && *route == path)
+        {
+            BodyKind::Upload
+        } else {
+            BodyKind::Json
+        };
+        let limit = match self.config.endpoint_limits.get(&endpoint_key(method, path)) {
+            Some(limit) => *limit,
+            None if kind == BodyKind::Upload => self.config.upload_bytes,
+            None => self.config.json_bytes,
+        };
+        (kind, limit)
+    }
+}
+
+/// Whether the request carries a body at all; without Content-Length or
+/// Transfer-Encoding an HTTP/1.1 request has none
+fn has_body(headers: &HeaderMap) -> bool {
+    match content_length(headers) {
+        Some(length) => length > 0,
+        None => headers.contains_key(header::TRANSFER_ENCODING),
+    }
+}
+
+fn content_length(headers: &HeaderMap) -> Option<u64> {
+    headers
+        .get(header::CONTENT_LENGTH)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse()
+        .ok()
+}
+
+/// Media type without parameters, lowercased
+fn media_type(headers: &HeaderMap) -> Option<String> {
+    let value = headers.get(header::CONTENT_TYPE)?.to_str().ok()?;
+    let essence = value.split(';').next().unwrap_or_default().trim();
+    Some(essence.to_ascii_lowercase())
+}
+
+/// JSON routes need a JSON body. Uploads are stored verbatim, so form encodings
+/// (e.g. `curl -F`) are refused rather than saved with their envelope.
+fn content_type_error(kind: BodyKind, media_type: Option<&str>) -> Option<String> {
+    match (kind, media_type) {
+        (BodyKind::Json, Some(media))
+            if media == "application/json"
+                || (media.starts_with("application/") && media.ends_with("+json")) =>
+        {
+            None
+        }
+        (BodyKind::Json, media) => Some(format!(
+            "Expected an application/json body, got {}",
+            media.unwrap_or("no Content-Type")
+        )),
+        (BodyKind::Upload, Some(media))
+            if media.starts_with("multipart/") || media == "application/x-www-form-urlencoded" =>
+        {
+            Some(format!(
+                "Send the file as the raw request body (e.g. application/octet-stream), not {}",
+                media
+            ))
+        }
+        (BodyKind::Upload, _) => None,
+    }
+}
+
+fn payload_too_large(limit: u64) -> Response {
+    ApiError::new(
+        StatusCode::PAYLOAD_TOO_LARGE,
+        "PayloadTooLarge",
+        format!(
+            "Request body exceeds the {} byte limit for this endpoint",
+            limit
+        ),
+    )
+    .into_response()
+}
+
+fn is_problem(response: &Response) -> bool {
+    response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .is_some_and(|value| value.as_bytes() == PROBLEM_JSON_CONTENT_TYPE.as_bytes())
+}
+
+/// Enforce the body limit and content type of the matched route. Runs as a
+/// router layer so `MatchedPath` is known; axum's own 2 MB default is disabled.
+pub async fn body_limit_middleware(
+    State(limits): State<BodyLimitState>,
+    request: Request<Body>,
+    next: Next,
+) -> Response {
+    if !has_body(request.headers()) {
+        return next.run(request).await;
+    }
+
+    let (kind, limit) = limits.select(&request);
+    if content_length(request.headers()).is_some_and(|length| length > limit) {
+        return payload_too_large(limit);
+    }
+    if let Some(message) = content_type_error(kind, media_type(request.headers()).as_deref()) {
+        return ApiError::new(
+            StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            "UnsupportedMediaType",
+            message,
+        )
+        .into_response();
+    }
+
+    let request = request.map(|body| {
+        Body::new(Limited::new(
+            body,
+            usize::try_from(limit).unwrap_or(usize::MAX),
+        ))
+    });
+    let response = next.run(request).await;
+    // Extractors that hit the limit mid-stream answer with a plain-text 413
+    if response.status() == StatusCode::PAYLOAD_TOO_LARGE && !is_problem(&response) {
+        return payload_too_large(limit);
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        body::Bytes,
+        extract::DefaultBodyLimit,
+        middleware,
+        routing::{post, put},
+        Json, Router,
+    };
+    use tower::Service;
+
+    fn app(endpoints: &[(&str, u64)]) -> Router<()> {
+        let limits = BodyLimitState::from_settings(&BodyLimitSettings {
+            json_bytes: 16,
+            upload_bytes: 64,
+            endpoints: endpoints
+                .iter()
+                .map(|(key, limit)| (key.to_string(), *limit))
+                .collect(),
+        });
+        Router::new()
+            .route(
+                "/api/contracts",
+                post(|Json(value): Json<serde_json::Value>| async move { Json(value) }),
+            )
+            .route(
+                "/api/publish/sessions/:id/parts/:kind",
+                put(|body: Bytes| async move { body.len().to_string() }),
+            )
+            .layer(middleware::from_fn_with_state(
+                limits,
+                body_limit_middleware,
+            ))
+            .layer(DefaultBodyLimit::disable())
+    }
+
+    async fn send(app: &Router<()>, request: Request<Body>) -> Response {
+        app.clone().call(request).await.unwrap()
+    }
+
+    fn request(
+        method: &str,
+        uri: &str,
+        content_type: Option<&str>,
+        body: &'static str,
+    ) -> Request<Body> {
+        let mut builder = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header(header::CONTENT_LENGTH, body.len());
+        if let Some(content_type) = content_type {
+            builder = builder.header(header::CONTENT_TYPE, content_type);
+        }
+        builder.body(Body::from(body)).unwrap()
+    }
+
+    async fn code(response: Response) -> String {
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let problem: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        problem["code"].as_str().unwrap_or_default().to_string()
+    }
+
+    #[tokio::test]
+    async fn limits_depend_on_the_route() {
+        let app = app(&[]);
+        let small = r#"{"name":"a"}"#;
+        let large = r#"{"name":"a much longer contract name"}"#;
+
+        let response = send(
+            &app,
+            request("POST", "/api/contracts", Some("application/json"), small),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = send(
+            &app,
+            request("POST", "/api/contracts", Some("application/json"), large),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(code(response).await, "PayloadTooLarge");
+
+        // The same bytes fit the upload limit
+        let response = send(
+            &app,
+            request("PUT", "/api/publish/sessions/1/parts/abi", None, large),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let app = self::app(&[("POST_API_CONTRACTS", 128)]);
+        let response = send(
+            &app,
+            request("POST", "/api/contracts", Some("application/json"), large),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn streamed_bodies_are_cut_off_with_a_problem() {
+        let app = app(&[]);
+        let chunks = futures_util::stream::iter(vec![
+            Ok::<_, std::io::Error>(Bytes::from_static(b"{\"name\":")),
+            Ok(Bytes::from_static(b"\"a much longer contract name\"}")),
+        ]);
+        let request = Request::builder()
+            .method("POST")
+            .uri("/api/contracts")
+            .header(header::CONTENT_TYPE, "application/json")
+            .header(header::TRANSFER_ENCODING, "chunked")
+            .body(Body::from_stream(chunks))
+            .unwrap();
+        let response = send(&app, request).await;
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(code(response).await, "PayloadTooLarge");
+    }
+
+    #[tokio::test]
+    async fn content_type_is_checked_per_route() {
+        let app = app(&[]);
+        let response = send(
+            &app,
+            request("POST", "/api/contracts", Some("text/plain"), "{}"),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+        assert_eq!(code(response).await, "UnsupportedMediaType");
+
+        let response = send(
+            &app,
+            request(
+                "PUT",
+                "/api/publish/sessions/1/parts/wasm",
+                Some("multipart/form-data; boundary=x"),
+                "--x--",
+            ),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+
+        let response = send(
+            &app,
+            request(
+                "PUT",
+                "/api/publish/sessions/1/parts/wasm",
+                Some("application/wasm"),
+                "\0asm",
+            ),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+}
@@ -10,7 +10,8 @@ use crate::error::{ApiError, ApiResult};
 use crate::state::AppState;
 use crate::type_safety::parser::parse_json_spec;
 use crate::type_safety::types::{
-    ContractABI, ContractFunction, EnumVariant, SorobanType, StructField,
+    ContractABI, ContractEvent, ContractFunction, EnumVariant, FunctionParam, SorobanType,
+    StructField,
 };
 
 #[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
@@ -117,10 +118,62 @@ pub fn diff_abi(old: &ContractABI, new: &ContractABI) -> Vec<BreakingChange> {
     }
 
     diff_types(&mut changes, &old.types, &new.types);
+    diff_events(&mut changes, &old.events, &new.events);
 
     changes
 }
 
+/// Indexers match events by name and decode their topics and data positionally,
+/// so any change to an existing event's shape breaks them
+fn diff_events(changes: &mut Vec<BreakingChange>, old: &[ContractEvent], new: &[ContractEvent]) {
+    fn shape(params: &[FunctionParam]) -> Vec<(&str, &SorobanType)> {
+        params
+            .iter()
+            .map(|p| (p.name.as_str(), &p.param_type))
+            .collect()
+    }
+    let event = |name: &str, severity, category: &str, message: String| BreakingChange {
+        severity,
+        category: category.to_string(),
+        message,
+        function: None,
+        type_name: Some(name.to_string()),
+    };
+
+    for old_event in old {
+        match new.iter().find(|e| e.name == old_event.name) {
+            None => changes.push(event(
+                &old_event.name,
+                ChangeSeverity::Breaking,
+                "event_removed",
+                format!("Event '{}' was removed", old_event.name),
+            )),
+            Some(new_event)
+                if shape(&old_event.topics) != shape(&new_event.topics)
+                    || shape(&old_event.data) != shape(&new_event.data) =>
+            {
+                changes.push(event(
+                    &old_event.name,
+                    ChangeSeverity::Breaking,
+                    "event_changed",
+                    format!("Event '{}' topics or data changed", old_event.name),
+                ))
+            }
+            Some(_) => {}
+        }
+    }
+    for new_event in new {
+        if !old.iter().any(|e| e.name == new_event.name) {
+            changes.push(event(
+                &new_event.name,
+                ChangeSeverity::NonBreaking,
+                "event_added",
+                format!("Event '{}' was added", new_event.name),
+            ));
+        }
+    }
+}
+
 fn diff_function(
     changes: &mut Vec<BreakingChange>,
     old_func: &ContractFunction,
@@ -515,6 +568,35 @@ mod tests {
         }
     }
 
+    #[test]
+    fn event_shape_changes_are_breaking_and_additions_are_not() {
+        let transfer = |amount: SorobanType| ContractEvent {
+            name: "transfer".to_string(),
+            topics: vec![param("from", SorobanType::Address)],
+            data: vec![param("amount", amount)],
+            doc: None,
+        };
+        let mut old = ContractABI::new("Old".to_string());
+        old.events.push(transfer(SorobanType::I128));
+        let mut new = ContractABI::new("New".to_string());
+        new.events.push(transfer(SorobanType::U64));
+        new.events.push(ContractEvent {
+            name: "mint".to_string(),
+            topics: Vec::new(),
+            data: Vec::new(),
+            doc: None,
+        });
+
+        let changes = diff_abi(&old, &new);
+        assert!(changes
+            .iter()
+            .any(|c| c.category == "event_changed" && c.severity == ChangeSeverity::Breaking));
+        assert!(changes
+            .iter()
+            .any(|c| c.category == "event_added" && c.severity == ChangeSeverity::NonBreaking));
+        assert!(diff_abi(&old, &old).is_empty());
+    }
+
     #[test]
     fn detects_function_removal_as_breaking() {
         let mut old = ContractABI::new("Old".to_string());
@@ -0,0 +1,403 @@
+// budget_handlers.rs
+// Performance budgets: a version's `budgets` part names functions to benchmark
+// in the simulation sandbox on publish and the limits they must stay within.
+// Measurements are stored per version so the next one can be compared.
+
+use std::collections::{HashMap, HashSet};
+
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use shared::{
+    BenchmarkMeasurement, ContractBenchmarksResponse, HookSeverity, PerformanceBudgets,
+    PublishHookFinding, SemVer, VersionBenchmarks,
+};
+use sqlx::PgConnection;
+use uuid::Uuid;
+
+use crate::error::{ApiError, ApiResult};
+use crate::handlers::{db_internal_error, fetch_contract_identity};
+use crate::private_registry::{ensure_contract_visible, Viewer};
+use crate::simulation;
+use crate::state::AppState;
+use crate::type_safety::parse_json_spec;
+
+/// Reported as the hook of budget warnings on the published version
+const BUDGET_HOOK: &str = "performance-budget";
+
+/// Parse a `budgets` part and check it is usable
+pub(crate) fn parse_budgets(content: &[u8]) -> Result<PerformanceBudgets, String> {
+    let budgets: PerformanceBudgets = serde_json::from_slice(content)
+        .map_err(|e| format!("performance budgets are invalid: {}", e))?;
+    if budgets.functions.is_empty() {
+        return Err("performance budgets must list at least one function".to_string());
+    }
+    if let Some(percent) = budgets.max_regression_percent {
+        if !percent.is_finite() || percent < 0.0 {
+            return Err("max_regression_percent must be zero or more".to_string());
+        }
+    }
+    let mut seen = HashSet::new();
+    for budget in &budgets.functions {
+        if !seen.insert(budget.function.as_str()) {
+            return Err(format!("`{}` is budgeted more than once", budget.function));
+        }
+        let limited = budget.max_cpu_instructions.is_some() || budget.max_memory_bytes.is_some();
+        if !limited && budgets.max_regression_percent.is_none() {
+            return Err(format!("`{}` has no limit to check", budget.function));
+        }
+    }
+    Ok(budgets)
+}
+
+fn clamp(n: u64) -> i64 {
+    i64::try_from(n).unwrap_or(i64::MAX)
+}
+
+/// A benchmark call's measurement and, when it failed, why
+struct BenchmarkRun {
+    measurement: BenchmarkMeasurement,
+    error: Option<String>,
+}
+
+/// Call every budgeted function once, each in a fresh sandbox
+async fn run_benchmarks(
+    wasm: &[u8],
+    abi: &serde_json::Value,
+    contract_id: &str,
+    budgets: &PerformanceBudgets,
+) -> ApiResult<Vec<BenchmarkRun>> {
+    let abi = parse_json_spec(&abi.to_string(), contract_id).map_err(|e| {
+        ApiError::bad_request("InvalidABI", format!("Invalid ABI part: {}", e.message))
+    })?;
+    let mut calls = Vec::with_capacity(budgets.functions.len());
+    for budget in &budgets.functions {
+        let args = simulation::encode_call(&abi, &budget.function, &budget.args).map_err(|e| {
+            ApiError::bad_request(
+                "InvalidBudgets",
+                format!("Benchmark of `{}`: {}", budget.function, e),
+            )
+        })?;
+        calls.push((budget.clone(), args));
+    }
+    let error_names = simulation::error_names(&abi);
+    let wasm = wasm.to_vec();
+
+    // Publishing waits for a sandbox rather than failing when all are busy
+    let _slot = simulation::SANDBOX_SLOTS
+        .acquire()
+        .await
+        .map_err(|_| ApiError::internal("The simulation sandbox is unavailable"))?;
+    tokio::task::spawn_blocking(move || {
+        calls
+            .into_iter()
+            .map(|(budget, args)| {
+                let outcome = simulation::simulate(&wasm, &budget.function, &args, &error_names)?;
+                Ok(BenchmarkRun {
+                    measurement: BenchmarkMeasurement {
+                        function: budget.function,
+                        success: outcome.result.is_ok(),
+                        cpu_instructions: clamp(outcome.resources.cpu_instructions),
+                        memory_bytes: clamp(outcome.resources.memory_bytes),
+                        max_cpu_instructions: budget.max_cpu_instructions.map(clamp),
+                        max_memory_bytes: budget.max_memory_bytes.map(clamp),
+                    },
+                    error: outcome.result.err(),
+                })
+            })
+            .collect::<Result<Vec<_>, String>>()
+    })
+    .await
+    .map_err(|_| {
+        ApiError::unprocessable(
+            "BenchmarkFailed",
+            "The wasm part could not be loaded into the simulation sandbox",
+        )
+    })?
+    .map_err(|reason| ApiError::unprocessable("BenchmarkFailed", reason))
+}
+
+fn growth_percent(before: i64, after: i64) -> Option<f64> {
+    (before > 0).then(|| (after - before) as f64 * 100.0 / before as f64)
+}
+
+/// Every exceeded limit. `previous` holds the last benchmarked version's
+/// `(cpu_instructions, memory_bytes)` per function.
+fn violations(
+    budgets: &PerformanceBudgets,
+    runs: &[BenchmarkRun],
+    previous: &HashMap<String, (i64, i64)>,
+) -> Vec<String> {
+    let mut problems = Vec::new();
+    for run in runs {
+        let m = &run.measurement;
+        if let Some(error) = &run.error {
+            problems.push(format!(
+                "`{}` failed during its benchmark: {}",
+                m.function, error
+            ));
+            continue;
+        }
+        if let Some(max) = m
+            .max_cpu_instructions
+            .filter(|max| m.cpu_instructions > *max)
+        {
+            problems.push(format!(
+                "`{}` used {} CPU instructions; the budget is {}",
+                m.function, m.cpu_instructions, max
+            ));
+        }
+        if let Some(max) = m.max_memory_bytes.filter(|max| m.memory_bytes > *max) {
+            problems.push(format!(
+                "`{}` used {} bytes of memory; the budget is {}",
+                m.function, m.memory_bytes, max
+            ));
+        }
+        let (Some(allowed), Some(&(cpu, memory))) =
+            (budgets.max_regression_percent, previous.get(&m.function))
+        else {
+            continue;
+        };
+        let metrics = [
+            ("CPU instructions", cpu, m.cpu_instructions),
+            ("memory", memory, m.memory_bytes),
+        ];
+        for (metric, before, after) in metrics {
+            if let Some(growth) = growth_percent(before, after).filter(|g| *g > allowed) {
+                problems.push(format!(
+                    "`{}` {} grew {:.1}% over the previous version ({} to {}); {}% is allowed",
+                    m.function, metric, growth, before, after, allowed
+                ));
+            }
+        }
+    }
+    problems
+}
+
+/// Successful measurements of the contract's most recently benchmarked version
+async fn previous_measurements(
+    state: &AppState,
+    contract_uuid: Uuid,
+) -> ApiResult<HashMap<String, (i64, i64)>> {
+    let rows: Vec<(String, i64, i64)> = sqlx::query_as(
+        "SELECT b.function, b.cpu_instructions, b.memory_bytes \
+         FROM version_benchmarks b \
+         WHERE b.success AND b.version_id = ( \
+             SELECT v.id FROM contract_versions v \
+             WHERE v.contract_id = $1 \
+               AND EXISTS (SELECT 1 FROM version_benchmarks vb WHERE vb.version_id = v.id) \
+             ORDER BY v.created_at DESC LIMIT 1)",
+    )
+    .bind(contract_uuid)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("fetch previous benchmarks", err))?;
+    Ok(rows
+        .into_iter()
+        .map(|(function, cpu, memory)| (function, (cpu, memory)))
+        .collect())
+}
+
+/// Benchmark a version about to be published against its `budgets` part.
+/// Returns the measurements to store with the version and any warnings;
+/// exceeded budgets fail the publish unless the part sets `on_exceed = "warn"`.
+pub(crate) async fn enforce_budgets(
+    state: &AppState,
+    contract_uuid: Uuid,
+    contract_id: &str,
+    wasm: &[u8],
+    abi: &serde_json::Value,
+    part: &[u8],
+) -> ApiResult<(Vec<BenchmarkMeasurement>, Vec<PublishHookFinding>)> {
+    let budgets =
+        parse_budgets(part).map_err(|message| ApiError::bad_request("InvalidBudgets", message))?;
+    let runs = run_benchmarks(wasm, abi, contract_id, &budgets).await?;
+    let previous = previous_measurements(state, contract_uuid).await?;
+    let problems = violations(&budgets, &runs, &previous);
+    if !problems.is_empty() && budgets.on_exceed == HookSeverity::Reject {
+        return Err(ApiError::unprocessable(
+            "PerformanceBudgetExceeded",
+            format!("Performance budgets exceeded: {}", problems.join("; ")),
+        ));
+    }
+    let warnings = problems
+        .into_iter()
+        .map(|message| PublishHookFinding {
+            hook: BUDGET_HOOK.to_string(),
+            severity: HookSeverity::Warn,
+            message,
+        })
+        .collect();
+    Ok((
+        runs.into_iter().map(|run| run.measurement).collect(),
+        warnings,
+    ))
+}
+
+/// Store a version's measurements; called inside the transaction that creates it
+pub(crate) async fn store_measurements(
+    conn: &mut PgConnection,
+    version_id: Uuid,
+    measurements: &[BenchmarkMeasurement],
+) -> ApiResult<()> {
+    for m in measurements {
+        sqlx::query(
+            "INSERT INTO version_benchmarks \
+                 (version_id, function, success, cpu_instructions, memory_bytes, \
+                  max_cpu_instructions, max_memory_bytes) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7)",
+        )
+        .bind(version_id)
+        .bind(&m.function)
+        .bind(m.success)
+        .bind(m.cpu_instructions)
+        .bind(m.memory_bytes)
+        .bind(m.max_cpu_instructions)
+        .bind(m.max_memory_bytes)
+        .execute(&mut *conn)
+        .await
+        .map_err(|err| db_internal_error("store benchmark measurement", err))?;
+    }
+    Ok(())
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BenchmarksQuery {
+    /// Only this version, e.g. `1.3.0`
+    pub version: Option<String>,
+}
+
+#[derive(sqlx::FromRow)]
+struct MeasurementRow {
+    version: String,
+    recorded_at: DateTime<Utc>,
+    #[sqlx(flatten)]
+    measurement: BenchmarkMeasurement,
+}
+
+/// GET /api/contracts/:id/budgets — benchmark measurements taken at publish time,
+/// newest version first; `?version=` selects one
+pub async fn get_contract_budgets(
+    State(state): State<AppState>,
+    viewer: Viewer,
+    Path(id): Path<String>,
+    Query(query): Query<BenchmarksQuery>,
+) -> ApiResult<Json<ContractBenchmarksResponse>> {
+    let (contract_uuid, _) = fetch_contract_identity(&state, viewer.tenant_id, &id).await?;
+    ensure_contract_visible(&state, &viewer, contract_uuid).await?;
+
+    let rows: Vec<MeasurementRow> = sqlx::query_as(
+        "SELECT v.version, b.recorded_at, b.function, b.success, b.cpu_instructions, \
+                b.memory_bytes, b.max_cpu_instructions, b.max_memory_bytes \
+         FROM version_benchmarks b \
+         JOIN contract_versions v ON v.id = b.version_id \
+         WHERE v.contract_id = $1 AND ($2::TEXT IS NULL OR v.version = $2) \
+         ORDER BY b.function",
+    )
+    .bind(contract_uuid)
+    .bind(query.version.as_deref())
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("fetch benchmarks", err))?;
+
+    if let (Some(version), true) = (&query.version, rows.is_empty()) {
+        return Err(ApiError::not_found(
+            "BenchmarksNotFound",
+            format!("Version '{}' was not benchmarked", version),
+        ));
+    }
+    let mut by_version: HashMap<String, VersionBenchmarks> = HashMap::new();
+    for row in rows {
+        by_version
+            .entry(row.version.clone())
+            .or_insert_with(|| VersionBenchmarks {
+                version: row.version,
+                recorded_at: row.recorded_at,
+                measurements: Vec::new(),
+            })
+            .measurements
+            .push(row.measurement);
+    }
+    let mut versions: Vec<VersionBenchmarks> = by_version.into_values().collect();
+    versions.sort_by(|a, b| SemVer::parse(&b.version).cmp(&SemVer::parse(&a.version)));
+
+    Ok(Json(ContractBenchmarksResponse {
+        contract_id: contract_uuid,
+        versions,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn budgets(json: &str) -> PerformanceBudgets {
+        parse_budgets(json.as_bytes()).unwrap()
+    }
+
+    fn run(function: &str, cpu: i64, max_cpu: Option<i64>) -> BenchmarkRun {
+        BenchmarkRun {
+            measurement: BenchmarkMeasurement {
+                function: function.to_string(),
+                success: true,
+                cpu_instructions: cpu,
+                memory_bytes: 1_000,
+                max_cpu_instructions: max_cpu,
+                max_memory_bytes: None,
+            },
+            error: None,
+        }
+    }
+
+    #[test]
+    fn parses_budgets_and_defaults_to_rejecting() {
+        let parsed = budgets(r#"{"functions": [{"function": "swap", "max_cpu_instructions": 5}]}"#);
+        assert_eq!(parsed.on_exceed, HookSeverity::Reject);
+        assert_eq!(parsed.functions[0].max_cpu_instructions, Some(5));
+
+        assert!(parse_budgets(br#"{"functions": []}"#).is_err());
+        assert!(parse_budgets(br#"{"functions": [{"function": "swap"}]}"#).is_err());
+        let twice = br#"{"max_regression_percent": 5,
+            "functions": [{"function": "swap"}, {"function": "swap"}]}"#;
+        assert!(parse_budgets(twice).unwrap_err().contains("more than once"));
+    }
+
+    #[test]
+    fn reports_exceeded_limits_and_failures() {
+        let parsed = budgets(r#"{"functions": [{"function": "swap", "max_cpu_instructions": 5}]}"#);
+        let mut failed = run("deposit", 1, None);
+        failed.error = Some("contract error 3 (Paused)".to_string());
+        let problems = violations(
+            &parsed,
+            &[run("swap", 10, Some(5)), run("quote", 4, Some(5)), failed],
+            &HashMap::new(),
+        );
+        assert_eq!(problems.len(), 2);
+        assert!(problems[0].contains("used 10 CPU instructions; the budget is 5"));
+        assert!(problems[1].contains("(Paused)"));
+    }
+
+    #[test]
+    fn regressions_are_measured_against_the_previous_version() {
+        let parsed = budgets(
+            r#"{"max_regression_percent": 10, "on_exceed": "warn",
+                "functions": [{"function": "swap"}, {"function": "quote"}]}"#,
+        );
+        let previous = HashMap::from([
+            ("swap".to_string(), (100, 1_000)),
+            ("quote".to_string(), (100, 1_000)),
+        ]);
+        let problems = violations(
+            &parsed,
+            &[run("swap", 125, None), run("quote", 110, None)],
+            &previous,
+        );
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].contains("`swap` CPU instructions grew 25.0%"));
+        // Nothing to compare against on the first benchmarked version
+        assert!(violations(&parsed, &[run("swap", 125, None)], &HashMap::new()).is_empty());
+    }
+}
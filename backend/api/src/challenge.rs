@@ -0,0 +1,294 @@
+// challenge.rs
+// Optional challenge anonymous writes (abuse reports, telemetry) must pass
+// to deter spam (`[challenge]`): a hashcash-style proof of work, or a CAPTCHA
+// checked with the provider's siteverify endpoint. Unanswered writes get 428
+// with the challenge in the `x-registry-challenge*` headers.
+//
+// Proof-of-work challenges are JWTs signed with `JWT_SECRET`, so any instance
+// can check an answer; each instance accepts a given challenge only once.
+
+use std::time::Duration;
+
+use axum::http::{HeaderMap, HeaderName, HeaderValue, StatusCode};
+use chrono::Utc;
+use jsonwebtoken::{decode, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use moka::future::Cache as MokaCache;
+use rand::{distributions::Alphanumeric, Rng};
+use serde::{Deserialize, Serialize};
+use shared::{
+    pow_is_solved, ChallengeKind, CHALLENGE_DIFFICULTY_HEADER, CHALLENGE_HEADER,
+    CHALLENGE_SITE_KEY_HEADER, CHALLENGE_SOLUTION_HEADER, CHALLENGE_TOKEN_HEADER,
+};
+
+use crate::error::{ApiError, ApiResult};
+use crate::private_registry::Viewer;
+use crate::settings::{ChallengeMode, ChallengeSettings};
+use crate::state::AppState;
+
+/// Keeps challenge tokens from passing for session tokens and vice versa
+const CHALLENGE_AUDIENCE: &str = "registry-challenge";
+
+const CAPTCHA_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Answered challenges remembered at once, per instance
+const MAX_SPENT_CHALLENGES: u64 = 100_000;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ChallengeClaims {
+    aud: String,
+    exp: i64,
+    nonce: String,
+    difficulty: u32,
+}
+
+#[derive(Deserialize)]
+struct SiteVerifyResponse {
+    success: bool,
+}
+
+pub struct ChallengeGate {
+    settings: ChallengeSettings,
+    encoding_key: EncodingKey,
+    decoding_key: DecodingKey,
+    /// Proof-of-work tokens already answered, until they expire
+    spent: MokaCache<String, ()>,
+    client: reqwest::Client,
+}
+
+impl Default for ChallengeGate {
+    fn default() -> Self {
+        Self::from_settings(&ChallengeSettings::default())
+    }
+}
+
+impl ChallengeGate {
+    pub fn from_settings(settings: &ChallengeSettings) -> Self {
+        let secret = std::env::var("JWT_SECRET").unwrap_or_else(|_| "dev-only-secret".to_string());
+        Self::new(settings, &secret)
+    }
+
+    fn new(settings: &ChallengeSettings, secret: &str) -> Self {
+        Self {
+            settings: settings.clone(),
+            encoding_key: EncodingKey::from_secret(secret.as_bytes()),
+            decoding_key: DecodingKey::from_secret(secret.as_bytes()),
+            spent: MokaCache::builder()
+                .max_capacity(MAX_SPENT_CHALLENGES)
+                .time_to_live(Duration::from_secs(settings.ttl_seconds))
+                .build(),
+            client: reqwest::Client::builder()
+                .timeout(CAPTCHA_TIMEOUT)
+                .build()
+                .unwrap_or_default(),
+        }
+    }
+
+    fn kind(&self) -> Option<ChallengeKind> {
+        match self.settings.mode {
+            ChallengeMode::Off => None,
+            ChallengeMode::Pow => Some(ChallengeKind::Pow),
+            ChallengeMode::Captcha => Some(ChallengeKind::Captcha),
+        }
+    }
+
+    fn issue_pow_token(&self) -> String {
+        let claims = ChallengeClaims {
+            aud: CHALLENGE_AUDIENCE.to_string(),
+            exp: Utc::now().timestamp() + self.settings.ttl_seconds as i64,
+            nonce: rand::thread_rng()
+                .sample_iter(&Alphanumeric)
+                .take(16)
+                .map(char::from)
+                .collect(),
+            difficulty: self.settings.pow_difficulty,
+        };
+        encode(&Header::default(), &claims, &self.encoding_key)
+            .expect("HS256 signing of plain claims does not fail")
+    }
+
+    /// 428 carrying a fresh challenge of `kind`
+    fn challenge_required(&self, kind: ChallengeKind, message: &str) -> ApiError {
+        let mut headers = HeaderMap::new();
+        let mut set = |name: &'static str, value: &str| {
+            if let Ok(value) = HeaderValue::from_str(value) {
+                headers.insert(HeaderName::from_static(name), value);
+            }
+        };
+        set(CHALLENGE_HEADER, kind.as_str());
+        match kind {
+            ChallengeKind::Pow => {
+                set(CHALLENGE_TOKEN_HEADER, &self.issue_pow_token());
+                set(
+                    CHALLENGE_DIFFICULTY_HEADER,
+                    &self.settings.pow_difficulty.to_string(),
+                );
+            }
+            ChallengeKind::Captcha => {
+                set(
+                    CHALLENGE_SITE_KEY_HEADER,
+                    self.settings
+                        .captcha_site_key
+                        .as_deref()
+                        .unwrap_or_default(),
+                );
+            }
+        }
+        ApiError::new(
+            StatusCode::PRECONDITION_REQUIRED,
+            "ChallengeRequired",
+            message,
+        )
+        .with_headers(headers)
+    }
+
+    async fn check_pow(&self, token: &str, solution: &str) -> Result<(), &'static str> {
+        let mut validation = Validation::new(Algorithm::HS256);
+        validation.set_audience(&[CHALLENGE_AUDIENCE]);
+        let claims = decode::<ChallengeClaims>(token, &self.decoding_key, &validation)
+            .map_err(|_| "The challenge is invalid or expired; answer the new one")?
+            .claims;
+        if !pow_is_solved(token, solution, claims.difficulty) {
+            return Err("The proof of work does not solve the challenge");
+        }
+        if !self
+            .spent
+            .entry(token.to_string())
+            .or_insert(())
+            .await
+            .is_fresh()
+        {
+            return Err("The challenge was already answered; answer the new one");
+        }
+        Ok(())
+    }
+
+    async fn check_captcha(&self, response: &str) -> Result<(), &'static str> {
+        let (Some(url), Some(secret)) = (
+            self.settings.captcha_verify_url.as_deref(),
+            self.settings.captcha_secret.as_deref(),
+        ) else {
+            return Err("CAPTCHA verification is not configured");
+        };
+        let verified = match self
+            .client
+            .post(url)
+            .form(&[("secret", secret), ("response", response)])
+            .send()
+            .await
+            .and_then(|resp| resp.error_for_status())
+        {
+            Ok(resp) => resp
+                .json::<SiteVerifyResponse>()
+                .await
+                .map(|body| body.success),
+            Err(err) => Err(err),
+        };
+        match verified {
+            Ok(true) => Ok(()),
+            Ok(false) => Err("The CAPTCHA was not solved"),
+            Err(err) => {
+                tracing::warn!(error = %err, "CAPTCHA verification failed");
+                Err("The CAPTCHA could not be verified; try again")
+            }
+        }
+    }
+
+    /// Ok when challenges are off or the request answers one; otherwise 428
+    /// with a new challenge
+    pub async fn verify(&self, headers: &HeaderMap) -> ApiResult<()> {
+        let Some(kind) = self.kind() else {
+            return Ok(());
+        };
+        let header = |name: &str| headers.get(name).and_then(|value| value.to_str().ok());
+        let result = match (
+            kind,
+            header(CHALLENGE_TOKEN_HEADER),
+            header(CHALLENGE_SOLUTION_HEADER),
+        ) {
+            (ChallengeKind::Pow, Some(token), Some(solution)) => {
+                self.check_pow(token, solution).await
+            }
+            (ChallengeKind::Captcha, _, Some(response)) => self.check_captcha(response).await,
+            _ => Err("Anonymous requests to this endpoint must answer a challenge"),
+        };
+        result.map_err(|message| self.challenge_required(kind, message))
+    }
+}
+
+/// Anonymous writes must pass the configured challenge; signed-in callers
+/// are accountable already and skip it
+pub async fn ensure_challenge_passed(
+    state: &AppState,
+    viewer: &Viewer,
+    headers: &HeaderMap,
+) -> ApiResult<()> {
+    if viewer.address.is_some() {
+        return Ok(());
+    }
+    state.challenge.verify(headers).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::response::IntoResponse;
+
+    fn pow_gate() -> ChallengeGate {
+        let settings = ChallengeSettings {
+            mode: ChallengeMode::Pow,
+            pow_difficulty: 8,
+            ..Default::default()
+        };
+        ChallengeGate::new(&settings, "test-secret")
+    }
+
+    fn answer(token: &str, solution: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(CHALLENGE_TOKEN_HEADER, token.parse().unwrap());
+        headers.insert(CHALLENGE_SOLUTION_HEADER, solution.parse().unwrap());
+        headers
+    }
+
+    #[tokio::test]
+    async fn challenges_are_off_by_default() {
+        assert!(ChallengeGate::default()
+            .verify(&HeaderMap::new())
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn unanswered_writes_get_a_challenge_to_solve() {
+        let gate = pow_gate();
+        let response = gate
+            .verify(&HeaderMap::new())
+            .await
+            .unwrap_err()
+            .into_response();
+        assert_eq!(response.status(), StatusCode::PRECONDITION_REQUIRED);
+        assert_eq!(response.headers()[CHALLENGE_HEADER], "pow");
+        assert_eq!(response.headers()[CHALLENGE_DIFFICULTY_HEADER], "8");
+
+        let token = response.headers()[CHALLENGE_TOKEN_HEADER].to_str().unwrap();
+        let solution = shared::solve_pow(token, 8);
+        assert!(gate.verify(&answer(token, &solution)).await.is_ok());
+        // Each challenge is accepted once
+        assert!(gate.verify(&answer(token, &solution)).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn forged_or_unsolved_challenges_are_rejected() {
+        let gate = pow_gate();
+        let token = gate.issue_pow_token();
+        let unsolved = (0u64..)
+            .map(|n| n.to_string())
+            .find(|n| !pow_is_solved(&token, n, 8))
+            .unwrap();
+        assert!(gate.verify(&answer(&token, &unsolved)).await.is_err());
+
+        let other = ChallengeGate::new(&gate.settings, "another-secret");
+        let forged = other.issue_pow_token();
+        let solution = shared::solve_pow(&forged, 8);
+        assert!(gate.verify(&answer(&forged, &solution)).await.is_err());
+    }
+}
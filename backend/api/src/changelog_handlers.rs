@@ -0,0 +1,354 @@
+// changelog_handlers.rs
+// Changelogs generated at publish time: each version's interface diff against
+// the previous version, changed metadata and WASM size delta.
+
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use serde::Deserialize;
+use shared::{
+    ChangelogChange, ContractChangelogEntry, ContractChangelogResponse, ContractVersion,
+    MetadataChange, SemVer,
+};
+use sqlx::{types::Json as SqlJson, PgConnection};
+use uuid::Uuid;
+
+use crate::breaking_changes::{diff_abi, ChangeSeverity};
+use crate::error::{ApiError, ApiResult};
+use crate::handlers::{db_internal_error, fetch_contract_identity};
+use crate::private_registry::{ensure_contract_visible, Viewer};
+use crate::state::AppState;
+use crate::type_safety::parser::parse_json_spec;
+
+#[derive(Debug, Deserialize)]
+pub struct ChangelogQuery {
+    /// Only this version's entry, e.g. `1.3.0`
+    pub version: Option<String>,
+}
+
+/// The highest version below `version`, by semver
+fn previous_version<'a>(
+    version: &ContractVersion,
+    others: &'a [ContractVersion],
+) -> Option<&'a ContractVersion> {
+    let current = SemVer::parse(&version.version)?;
+    others
+        .iter()
+        .filter_map(|v| SemVer::parse(&v.version).map(|parsed| (parsed, v)))
+        .filter(|(parsed, _)| *parsed < current)
+        .max_by(|(a, _), (b, _)| a.cmp(b))
+        .map(|(_, v)| v)
+}
+
+/// Interface diff between two ABIs; empty when either cannot be parsed
+fn interface_changes(contract_id: &str, old_abi: &str, new_abi: &str) -> Vec<ChangelogChange> {
+    let (Ok(old), Ok(new)) = (
+        parse_json_spec(old_abi, contract_id),
+        parse_json_spec(new_abi, contract_id),
+    ) else {
+        return Vec::new();
+    };
+    let mut changes: Vec<ChangelogChange> = diff_abi(&old, &new)
+        .into_iter()
+        .map(|change| ChangelogChange {
+            severity: match change.severity {
+                ChangeSeverity::Breaking => "breaking",
+                ChangeSeverity::NonBreaking => "non_breaking",
+            }
+            .to_string(),
+            category: change.category,
+            message: change.message,
+        })
+        .collect();
+    // diff_abi walks hash maps; keep stored changelogs stable
+    changes.sort_by(|a, b| {
+        (a.severity != "breaking", &a.category, &a.message).cmp(&(
+            b.severity != "breaking",
+            &b.category,
+            &b.message,
+        ))
+    });
+    changes
+}
+
+/// Version-level fields that differ between `old` and `new`
+fn metadata_changes(old: &ContractVersion, new: &ContractVersion) -> Vec<MetadataChange> {
+    [
+        ("wasm_hash", Some(&old.wasm_hash), Some(&new.wasm_hash)),
+        (
+            "source_url",
+            old.source_url.as_ref(),
+            new.source_url.as_ref(),
+        ),
+        (
+            "commit_hash",
+            old.commit_hash.as_ref(),
+            new.commit_hash.as_ref(),
+        ),
+        (
+            "publisher_key",
+            old.publisher_key.as_ref(),
+            new.publisher_key.as_ref(),
+        ),
+        (
+            "signature_algorithm",
+            old.signature_algorithm.as_ref(),
+            new.signature_algorithm.as_ref(),
+        ),
+    ]
+    .into_iter()
+    .filter(|(_, old, new)| old != new)
+    .map(|(field, old, new)| MetadataChange {
+        field: field.to_string(),
+        old: old.cloned(),
+        new: new.cloned(),
+    })
+    .collect()
+}
+
+async fn fetch_abi(
+    conn: &mut PgConnection,
+    version: &ContractVersion,
+) -> Result<Option<String>, sqlx::Error> {
+    let abi: Option<serde_json::Value> =
+        sqlx::query_scalar("SELECT abi FROM contract_abis WHERE contract_id = $1 AND version = $2")
+            .bind(version.contract_id)
+            .bind(&version.version)
+            .fetch_optional(&mut *conn)
+            .await?;
+    Ok(abi.map(|abi| abi.to_string()))
+}
+
+async fn fetch_wasm_size(
+    conn: &mut PgConnection,
+    version_id: Uuid,
+) -> Result<Option<i64>, sqlx::Error> {
+    sqlx::query_scalar(
+        "SELECT OCTET_LENGTH(content)::BIGINT FROM contract_version_artifacts \
+         WHERE version_id = $1 AND kind = 'wasm'",
+    )
+    .bind(version_id)
+    .fetch_optional(&mut *conn)
+    .await
+}
+
+/// Build and store the changelog entry of one version. Runs inside the publish
+/// transaction, and lazily for versions published before changelogs existed.
+pub(crate) async fn generate_changelog(
+    conn: &mut PgConnection,
+    contract_id: &str,
+    version_id: Uuid,
+) -> Result<(), sqlx::Error> {
+    let version: ContractVersion = sqlx::query_as("SELECT * FROM contract_versions WHERE id = $1")
+        .bind(version_id)
+        .fetch_one(&mut *conn)
+        .await?;
+    let others: Vec<ContractVersion> =
+        sqlx::query_as("SELECT * FROM contract_versions WHERE contract_id = $1 AND id <> $2")
+            .bind(version.contract_id)
+            .bind(version_id)
+            .fetch_all(&mut *conn)
+            .await?;
+    let previous = previous_version(&version, &others);
+
+    let wasm_size = fetch_wasm_size(conn, version_id).await?;
+    let (changes, metadata, size_delta) = match previous {
+        Some(previous) => {
+            let changes = match (
+                fetch_abi(conn, previous).await?,
+                fetch_abi(conn, &version).await?,
+            ) {
+                (Some(old), Some(new)) => interface_changes(contract_id, &old, &new),
+                _ => Vec::new(),
+            };
+            let previous_size = fetch_wasm_size(conn, previous.id).await?;
+            let delta = wasm_size.zip(previous_size).map(|(new, old)| new - old);
+            (changes, metadata_changes(previous, &version), delta)
+        }
+        None => (Vec::new(), Vec::new(), None),
+    };
+
+    sqlx::query(
+        "INSERT INTO contract_version_changelogs \
+             (version_id, previous_version, interface_changes, metadata_changes, \
+              wasm_size_bytes, wasm_size_delta) \
+         VALUES ($1, $2, $3, $4, $5, $6) \
+         ON CONFLICT (version_id) DO UPDATE SET \
+             previous_version = EXCLUDED.previous_version, \
+             interface_changes = EXCLUDED.interface_changes, \
+             metadata_changes = EXCLUDED.metadata_changes, \
+             wasm_size_bytes = EXCLUDED.wasm_size_bytes, \
+             wasm_size_delta = EXCLUDED.wasm_size_delta, \
+             generated_at = NOW()",
+    )
+    .bind(version_id)
+    .bind(previous.map(|p| &p.version))
+    .bind(SqlJson(&changes))
+    .bind(SqlJson(&metadata))
+    .bind(wasm_size)
+    .bind(size_delta)
+    .execute(&mut *conn)
+    .await?;
+    Ok(())
+}
+
+type ChangelogRow = (
+    String,
+    chrono::DateTime<chrono::Utc>,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+    SqlJson<Vec<ChangelogChange>>,
+    SqlJson<Vec<MetadataChange>>,
+    Option<i64>,
+    Option<i64>,
+);
+
+fn entry_from_row(row: ChangelogRow) -> ContractChangelogEntry {
+    let (version, created_at, commit_hash, source_url, release_notes, previous_version) =
+        (row.0, row.1, row.2, row.3, row.4, row.5);
+    let changes = row.6 .0;
+    let breaking_changes: Vec<String> = changes
+        .iter()
+        .filter(|c| c.severity == "breaking")
+        .map(|c| c.message.clone())
+        .collect();
+    ContractChangelogEntry {
+        version,
+        created_at,
+        commit_hash,
+        source_url,
+        release_notes,
+        breaking: !breaking_changes.is_empty(),
+        breaking_changes,
+        previous_version,
+        changes,
+        metadata_changes: row.7 .0,
+        wasm_size_bytes: row.8,
+        wasm_size_delta: row.9,
+    }
+}
+
+/// GET /api/contracts/:id/changelog (and /contracts/:id/changelog) — generated
+/// release history, newest version first; `?version=` selects one entry
+pub async fn get_contract_changelog(
+    State(state): State<AppState>,
+    viewer: Viewer,
+    Path(id): Path<String>,
+    Query(query): Query<ChangelogQuery>,
+) -> ApiResult<Json<ContractChangelogResponse>> {
+    let (contract_uuid, contract_id) =
+        fetch_contract_identity(&state, viewer.tenant_id, &id).await?;
+    ensure_contract_visible(&state, &viewer, contract_uuid).await?;
+
+    let missing: Vec<Uuid> = sqlx::query_scalar(
+        "SELECT v.id FROM contract_versions v WHERE v.contract_id = $1 AND NOT EXISTS \
+         (SELECT 1 FROM contract_version_changelogs l WHERE l.version_id = v.id)",
+    )
+    .bind(contract_uuid)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("find versions without changelog", err))?;
+    if !missing.is_empty() {
+        let mut conn = state
+            .db
+            .acquire()
+            .await
+            .map_err(|err| db_internal_error("acquire connection for changelog", err))?;
+        for version_id in missing {
+            generate_changelog(&mut conn, &contract_id, version_id)
+                .await
+                .map_err(|err| db_internal_error("generate changelog", err))?;
+        }
+    }
+
+    let rows: Vec<ChangelogRow> = sqlx::query_as(
+        "SELECT v.version, v.created_at, v.commit_hash, v.source_url, v.release_notes, \
+                l.previous_version, l.interface_changes, l.metadata_changes, \
+                l.wasm_size_bytes, l.wasm_size_delta \
+         FROM contract_versions v \
+         JOIN contract_version_changelogs l ON l.version_id = v.id \
+         WHERE v.contract_id = $1 AND ($2::TEXT IS NULL OR v.version = $2)",
+    )
+    .bind(contract_uuid)
+    .bind(query.version.as_deref())
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("fetch contract changelog", err))?;
+
+    if let (Some(version), true) = (&query.version, rows.is_empty()) {
+        return Err(ApiError::not_found(
+            "VersionNotFound",
+            format!("No version '{}' found for this contract", version),
+        ));
+    }
+
+    let mut entries: Vec<ContractChangelogEntry> = rows.into_iter().map(entry_from_row).collect();
+    entries.sort_by(|a, b| {
+        SemVer::parse(&b.version)
+            .cmp(&SemVer::parse(&a.version))
+            .then(b.created_at.cmp(&a.created_at))
+    });
+
+    Ok(Json(ContractChangelogResponse {
+        contract_id: contract_uuid,
+        entries,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn version(v: &str) -> ContractVersion {
+        ContractVersion {
+            id: Uuid::new_v4(),
+            contract_id: Uuid::nil(),
+            version: v.to_string(),
+            wasm_hash: format!("hash-{}", v),
+            source_url: None,
+            commit_hash: None,
+            release_notes: None,
+            created_at: chrono::Utc::now(),
+            state_schema: None,
+            signature: None,
+            publisher_key: None,
+            signature_algorithm: None,
+        }
+    }
+
+    #[test]
+    fn previous_version_is_the_next_lower_semver() {
+        let others = vec![version("1.10.0"), version("1.2.0"), version("2.0.0")];
+        let previous = previous_version(&version("1.11.0"), &others).unwrap();
+        assert_eq!(previous.version, "1.10.0");
+        assert!(previous_version(&version("1.0.0"), &others).is_none());
+    }
+
+    #[test]
+    fn metadata_changes_list_only_differing_fields() {
+        let old = version("1.0.0");
+        let mut new = version("1.1.0");
+        new.source_url = old.source_url.clone();
+        new.commit_hash = Some("abc123".to_string());
+        let changes = metadata_changes(&old, &new);
+        let fields: Vec<&str> = changes.iter().map(|c| c.field.as_str()).collect();
+        assert_eq!(fields, vec!["wasm_hash", "commit_hash"]);
+        assert_eq!(changes[1].old, None);
+        assert_eq!(changes[1].new.as_deref(), Some("abc123"));
+    }
+
+    #[test]
+    fn interface_changes_list_breaking_first() {
+        let old = r#"[{"type":"function","name":"transfer","inputs":[]}]"#;
+        let new = r#"[{"type":"function","name":"mint","inputs":[]}]"#;
+        let changes = interface_changes("C1", old, new);
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0].category, "function_removed");
+        assert_eq!(changes[0].severity, "breaking");
+        assert_eq!(changes[1].category, "function_added");
+        assert!(interface_changes("C1", "not json", new).is_empty());
+    }
+}
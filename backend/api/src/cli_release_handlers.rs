@@ -0,0 +1,130 @@
+// cli_release_handlers.rs
+// Release metadata for CLI self-update.
+
+use axum::{
+    extract::{
+        rejection::{JsonRejection, QueryRejection},
+        Query, State,
+    },
+    http::StatusCode,
+    Json,
+};
+use shared::{CliRelease, CliReleaseQuery, PublishCliReleaseRequest, SemVer};
+
+use crate::error::{ApiError, ApiResult};
+use crate::handlers::{db_internal_error, map_json_rejection};
+use crate::state::AppState;
+
+/// Highest semantic version among `releases`; unparseable versions never win
+fn newest(releases: Vec<CliRelease>) -> Option<CliRelease> {
+    releases
+        .into_iter()
+        .filter_map(|release| SemVer::parse(&release.version).map(|v| (v, release)))
+        .max_by(|(a, _), (b, _)| a.cmp(b))
+        .map(|(_, release)| release)
+}
+
+/// GET /api/cli/releases/latest?target=
+pub async fn latest_release(
+    State(state): State<AppState>,
+    query: Result<Query<CliReleaseQuery>, QueryRejection>,
+) -> ApiResult<Json<CliRelease>> {
+    let Query(query) = query.map_err(|err| {
+        ApiError::bad_request("InvalidRequest", format!("Invalid query: {}", err))
+    })?;
+
+    let releases: Vec<CliRelease> = sqlx::query_as(
+        "SELECT version, target, download_url, sha256, signature, release_notes, published_at \
+         FROM cli_releases WHERE target = $1",
+    )
+    .bind(&query.target)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("fetch cli releases", err))?;
+
+    newest(releases).map(Json).ok_or_else(|| {
+        ApiError::not_found(
+            "NotFound",
+            format!("No CLI release published for target {}", query.target),
+        )
+    })
+}
+
+/// POST /api/admin/cli/releases
+pub async fn publish_release(
+    State(state): State<AppState>,
+    payload: Result<Json<PublishCliReleaseRequest>, JsonRejection>,
+) -> ApiResult<(StatusCode, Json<CliRelease>)> {
+    let Json(req) = payload.map_err(map_json_rejection)?;
+
+    if SemVer::parse(&req.version).is_none() {
+        return Err(ApiError::bad_request(
+            "InvalidVersion",
+            "version must be MAJOR.MINOR.PATCH",
+        ));
+    }
+    if req.sha256.len() != 64 || hex::decode(&req.sha256).is_err() {
+        return Err(ApiError::bad_request(
+            "InvalidRequest",
+            "sha256 must be 64 hex characters",
+        ));
+    }
+    if req.target.trim().is_empty() || req.download_url.trim().is_empty() {
+        return Err(ApiError::bad_request(
+            "InvalidRequest",
+            "target and download_url are required",
+        ));
+    }
+
+    let release: CliRelease = sqlx::query_as(
+        "INSERT INTO cli_releases \
+             (version, target, download_url, sha256, signature, release_notes) \
+         VALUES ($1, $2, $3, $4, $5, $6) \
+         RETURNING version, target, download_url, sha256, signature, release_notes, published_at",
+    )
+    .bind(&req.version)
+    .bind(req.target.trim())
+    .bind(req.download_url.trim())
+    .bind(req.sha256.to_ascii_lowercase())
+    .bind(&req.signature)
+    .bind(req.release_notes.as_deref())
+    .fetch_one(&state.db)
+    .await
+    .map_err(|err| match err {
+        sqlx::Error::Database(ref e) if e.is_unique_violation() => ApiError::conflict(
+            "VersionAlreadyExists",
+            format!(
+                "CLI {} is already published for {}",
+                req.version, req.target
+            ),
+        ),
+        _ => db_internal_error("publish cli release", err),
+    })?;
+
+    Ok((StatusCode::CREATED, Json(release)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn release(version: &str) -> CliRelease {
+        CliRelease {
+            version: version.to_string(),
+            target: "x86_64-unknown-linux-gnu".to_string(),
+            download_url: format!("https://example.com/{}", version),
+            sha256: "00".repeat(32),
+            signature: String::new(),
+            release_notes: None,
+            published_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn newest_compares_semantically() {
+        let latest = newest(vec![release("0.9.0"), release("0.10.0"), release("bogus")]);
+        assert_eq!(latest.unwrap().version, "0.10.0");
+        assert!(newest(vec![release("bogus")]).is_none());
+    }
+}
@@ -0,0 +1,142 @@
+// client_version.rs
+// Rejects CLI clients older than the configured minimum with 426 Upgrade Required.
+
+use std::sync::Arc;
+
+use axum::{
+    body::Body,
+    extract::State,
+    http::{
+        header::{HeaderMap, USER_AGENT},
+        HeaderName, HeaderValue, Request, StatusCode,
+    },
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use shared::SemVer;
+
+use crate::error::ApiError;
+
+/// Version the CLI reports on every request
+pub const CLIENT_VERSION_HEADER: HeaderName = HeaderName::from_static("x-client-version");
+/// Minimum accepted version, returned alongside a 426
+pub const MIN_CLIENT_VERSION_HEADER: HeaderName = HeaderName::from_static("x-min-client-version");
+
+/// User-Agent product token the CLI sends, e.g. `soroban-registry/0.4.1`
+const CLI_USER_AGENT_PRODUCT: &str = "soroban-registry/";
+
+/// Paths an outdated client must still reach, so it can upgrade itself
+const EXEMPT_PATHS: &[&str] = &["/api/cli/releases/latest"];
+
+#[derive(Clone, Default)]
+pub struct ClientVersionPolicy {
+    minimum: Option<Arc<SemVer>>,
+}
+
+impl ClientVersionPolicy {
+    /// Build from the validated `server.min_cli_version` setting
+    pub fn new(min_cli_version: Option<&str>) -> Self {
+        Self {
+            minimum: min_cli_version.and_then(SemVer::parse).map(Arc::new),
+        }
+    }
+
+    /// The minimum version when `headers` come from a client older than it
+    fn rejects(&self, headers: &HeaderMap) -> Option<&SemVer> {
+        let minimum = self.minimum.as_deref()?;
+        let reported = client_version(headers)?;
+        (reported < *minimum).then_some(minimum)
+    }
+}
+
+/// Version reported by `X-Client-Version`, falling back to the CLI's User-Agent.
+/// Clients that report neither (browsers, scripts) are not version-gated.
+fn client_version(headers: &HeaderMap) -> Option<SemVer> {
+    if let Some(value) = headers
+        .get(&CLIENT_VERSION_HEADER)
+        .and_then(|v| v.to_str().ok())
+    {
+        return SemVer::parse(value.trim());
+    }
+    headers
+        .get(USER_AGENT)
+        .and_then(|v| v.to_str().ok())?
+        .split_whitespace()
+        .find_map(|token| token.strip_prefix(CLI_USER_AGENT_PRODUCT))
+        .and_then(SemVer::parse)
+}
+
+pub async fn client_version_middleware(
+    State(policy): State<ClientVersionPolicy>,
+    request: Request<Body>,
+    next: Next,
+) -> Response {
+    let path = request.uri().path();
+    if path.starts_with("/health") || EXEMPT_PATHS.contains(&path) {
+        return next.run(request).await;
+    }
+
+    if let Some(minimum) = policy.rejects(request.headers()) {
+        let mut response = ApiError::new(
+            StatusCode::UPGRADE_REQUIRED,
+            "UpgradeRequired",
+            format!(
+                "This registry requires soroban-registry {} or newer",
+                minimum
+            ),
+        )
+        .into_response();
+        if let Ok(value) = HeaderValue::from_str(&minimum.to_string()) {
+            response
+                .headers_mut()
+                .insert(MIN_CLIENT_VERSION_HEADER, value);
+        }
+        return response;
+    }
+
+    next.run(request).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(pairs: &[(HeaderName, &str)]) -> HeaderMap {
+        let mut map = HeaderMap::new();
+        for (name, value) in pairs {
+            map.insert(name.clone(), HeaderValue::from_str(value).unwrap());
+        }
+        map
+    }
+
+    #[test]
+    fn reads_version_header_then_user_agent() {
+        assert_eq!(
+            client_version(&headers(&[(CLIENT_VERSION_HEADER, "0.4.1")])),
+            SemVer::parse("0.4.1")
+        );
+        assert_eq!(
+            client_version(&headers(&[(USER_AGENT, "soroban-registry/0.3.0 (linux)")])),
+            SemVer::parse("0.3.0")
+        );
+        assert_eq!(
+            client_version(&headers(&[(USER_AGENT, "curl/8.4.0")])),
+            None
+        );
+    }
+
+    #[test]
+    fn rejects_only_older_clients() {
+        let policy = ClientVersionPolicy::new(Some("0.4.0"));
+        assert!(policy
+            .rejects(&headers(&[(CLIENT_VERSION_HEADER, "0.3.9")]))
+            .is_some());
+        assert!(policy
+            .rejects(&headers(&[(CLIENT_VERSION_HEADER, "0.4.0")]))
+            .is_none());
+        assert!(policy.rejects(&headers(&[])).is_none());
+        assert!(ClientVersionPolicy::default()
+            .rejects(&headers(&[(CLIENT_VERSION_HEADER, "0.0.1")]))
+            .is_none());
+    }
+}
@@ -0,0 +1,559 @@
+// collection_handlers.rs
+// Collections: named, ordered lists of contracts curated by signed-in users,
+// public or private. Items only show contracts the caller may see.
+
+use axum::{
+    extract::{rejection::JsonRejection, Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use serde::Deserialize;
+use shared::{
+    AddCollectionItemRequest, ApiScope, Collection, CollectionDetail, CollectionItem,
+    CreateCollectionRequest, ReorderCollectionRequest, UpdateCollectionRequest,
+};
+use uuid::Uuid;
+
+use crate::error::{ApiError, ApiResult};
+use crate::handlers::{db_internal_error, fetch_contract_identity, map_json_rejection};
+use crate::private_registry::{ensure_contract_visible, Viewer};
+use crate::state::AppState;
+use crate::tenancy::CurrentTenant;
+use crate::user_auth::AuthUser;
+
+const MAX_COLLECTION_NAME_LENGTH: usize = 64;
+const MAX_DESCRIPTION_LENGTH: usize = 1000;
+const MAX_NOTE_LENGTH: usize = 500;
+const MAX_COLLECTIONS_PER_USER: i64 = 100;
+const MAX_ITEMS_PER_COLLECTION: i64 = 200;
+const MAX_LISTED_COLLECTIONS: i64 = 100;
+
+fn validate_name(name: &str) -> ApiResult<&str> {
+    let name = name.trim();
+    if name.is_empty()
+        || name.chars().count() > MAX_COLLECTION_NAME_LENGTH
+        || name.chars().any(char::is_control)
+    {
+        return Err(ApiError::bad_request(
+            "InvalidCollectionName",
+            format!(
+                "Collection names must be 1-{} characters without control characters",
+                MAX_COLLECTION_NAME_LENGTH
+            ),
+        ));
+    }
+    Ok(name)
+}
+
+fn validate_text(field: &str, value: Option<&str>, max: usize) -> ApiResult<()> {
+    if value.is_some_and(|value| value.chars().count() > max) {
+        return Err(ApiError::bad_request(
+            "ValidationError",
+            format!("{} must be at most {} characters", field, max),
+        ));
+    }
+    Ok(())
+}
+
+/// `current` rearranged so `requested` comes first, in that order; the rest
+/// keep their relative order after them
+fn reordered(current: &[Uuid], requested: &[Uuid]) -> Result<Vec<Uuid>, String> {
+    let mut seen = std::collections::HashSet::new();
+    for id in requested {
+        if !current.contains(id) {
+            return Err(format!("{} is not in this collection", id));
+        }
+        if !seen.insert(*id) {
+            return Err(format!("{} is listed more than once", id));
+        }
+    }
+    let mut order = requested.to_vec();
+    order.extend(current.iter().filter(|id| !seen.contains(*id)));
+    Ok(order)
+}
+
+fn collection_not_found(id: Uuid) -> ApiError {
+    ApiError::not_found(
+        "CollectionNotFound",
+        format!("No collection found with ID: {}", id),
+    )
+}
+
+fn collection_columns(viewer: &Viewer) -> String {
+    format!(
+        "c.id, c.owner_address, c.name, c.description, c.is_public, c.created_at, c.updated_at, \
+         (SELECT COUNT(*) FROM collection_items i JOIN contracts ct ON ct.id = i.contract_id \
+          WHERE i.collection_id = c.id{}) AS item_count",
+        viewer.visibility_clause("ct.")
+    )
+}
+
+/// The collection if the viewer may see it: public ones, or their own
+async fn fetch_visible_collection(
+    state: &AppState,
+    viewer: &Viewer,
+    id: Uuid,
+) -> ApiResult<Collection> {
+    let sql = format!(
+        "SELECT {} FROM collections c \
+         WHERE c.id = $1 AND c.tenant_id = $2 AND (c.is_public OR c.owner_address = $3)",
+        collection_columns(viewer)
+    );
+    sqlx::query_as(&sql)
+        .bind(id)
+        .bind(viewer.tenant_id)
+        .bind(viewer.address.as_deref())
+        .fetch_optional(&state.db)
+        .await
+        .map_err(|err| db_internal_error("fetch collection", err))?
+        .ok_or_else(|| collection_not_found(id))
+}
+
+async fn fetch_items(
+    state: &AppState,
+    viewer: &Viewer,
+    id: Uuid,
+) -> ApiResult<Vec<CollectionItem>> {
+    let sql = format!(
+        "SELECT i.position, c.id, c.contract_id, c.namespace, c.name, c.network, c.is_verified, \
+                i.note, i.added_at \
+         FROM collection_items i JOIN contracts c ON c.id = i.contract_id \
+         WHERE i.collection_id = $1{} \
+         ORDER BY i.position, i.added_at",
+        viewer.visibility_clause("c.")
+    );
+    sqlx::query_as(&sql)
+        .bind(id)
+        .fetch_all(&state.db)
+        .await
+        .map_err(|err| db_internal_error("fetch collection items", err))
+}
+
+pub(crate) async fn load_detail(
+    state: &AppState,
+    viewer: &Viewer,
+    id: Uuid,
+) -> ApiResult<CollectionDetail> {
+    let collection = fetch_visible_collection(state, viewer, id).await?;
+    let items = fetch_items(state, viewer, id).await?;
+    Ok(CollectionDetail { collection, items })
+}
+
+/// Writes are for the owner only. Someone else's private collection is a 404,
+/// so its existence isn't revealed; a public one is a 403.
+async fn ensure_owner(
+    state: &AppState,
+    user: &AuthUser,
+    tenant: &CurrentTenant,
+    id: Uuid,
+) -> ApiResult<()> {
+    let row: Option<(String, bool)> = sqlx::query_as(
+        "SELECT owner_address, is_public FROM collections WHERE id = $1 AND tenant_id = $2",
+    )
+    .bind(id)
+    .bind(tenant.id)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|err| db_internal_error("fetch collection owner", err))?;
+    match row {
+        Some((owner, _)) if owner == user.address => Ok(()),
+        Some((_, true)) => Err(ApiError::new(
+            StatusCode::FORBIDDEN,
+            "Forbidden",
+            "Only the owner can change this collection",
+        )),
+        _ => Err(collection_not_found(id)),
+    }
+}
+
+fn owner_viewer(user: &AuthUser, tenant: &CurrentTenant) -> Viewer {
+    Viewer {
+        address: Some(user.address.clone()),
+        tenant_id: tenant.id,
+    }
+}
+
+fn duplicate_name(name: &str) -> ApiError {
+    ApiError::conflict(
+        "CollectionExists",
+        format!("You already have a collection named '{}'", name),
+    )
+}
+
+/// POST /api/collections
+pub async fn create_collection(
+    State(state): State<AppState>,
+    user: AuthUser,
+    tenant: CurrentTenant,
+    payload: Result<Json<CreateCollectionRequest>, JsonRejection>,
+) -> ApiResult<(StatusCode, Json<CollectionDetail>)> {
+    user.require(ApiScope::Publish)?;
+    let Json(req) = payload.map_err(map_json_rejection)?;
+    let name = validate_name(&req.name)?;
+    validate_text(
+        "description",
+        req.description.as_deref(),
+        MAX_DESCRIPTION_LENGTH,
+    )?;
+
+    let existing: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM collections WHERE tenant_id = $1 AND owner_address = $2",
+    )
+    .bind(tenant.id)
+    .bind(&user.address)
+    .fetch_one(&state.db)
+    .await
+    .map_err(|err| db_internal_error("count collections", err))?;
+    if existing >= MAX_COLLECTIONS_PER_USER {
+        return Err(ApiError::unprocessable(
+            "TooManyCollections",
+            format!(
+                "At most {} collections are allowed per user",
+                MAX_COLLECTIONS_PER_USER
+            ),
+        ));
+    }
+
+    let id: Uuid = sqlx::query_scalar(
+        "INSERT INTO collections (tenant_id, owner_address, name, description, is_public) \
+         VALUES ($1, $2, $3, $4, $5) RETURNING id",
+    )
+    .bind(tenant.id)
+    .bind(&user.address)
+    .bind(name)
+    .bind(req.description.as_deref())
+    .bind(req.is_public)
+    .fetch_one(&state.db)
+    .await
+    .map_err(|err| match err {
+        sqlx::Error::Database(ref e) if e.is_unique_violation() => duplicate_name(name),
+        err => db_internal_error("create collection", err),
+    })?;
+
+    let detail = load_detail(&state, &owner_viewer(&user, &tenant), id).await?;
+    Ok((StatusCode::CREATED, Json(detail)))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListCollectionsQuery {
+    /// Only collections owned by this address
+    pub owner: Option<String>,
+}
+
+/// GET /api/collections?owner= — public collections and the caller's own,
+/// most recently changed first
+pub async fn list_collections(
+    State(state): State<AppState>,
+    viewer: Viewer,
+    Query(params): Query<ListCollectionsQuery>,
+) -> ApiResult<Json<Vec<Collection>>> {
+    let sql = format!(
+        "SELECT {} FROM collections c \
+         WHERE c.tenant_id = $1 AND (c.is_public OR c.owner_address = $2) \
+         AND ($3::text IS NULL OR c.owner_address = $3) \
+         ORDER BY c.updated_at DESC LIMIT {}",
+        collection_columns(&viewer),
+        MAX_LISTED_COLLECTIONS
+    );
+    let collections = sqlx::query_as(&sql)
+        .bind(viewer.tenant_id)
+        .bind(viewer.address.as_deref())
+        .bind(params.owner.as_deref())
+        .fetch_all(&state.db)
+        .await
+        .map_err(|err| db_internal_error("list collections", err))?;
+    Ok(Json(collections))
+}
+
+/// GET /api/collections/:id
+pub async fn get_collection(
+    State(state): State<AppState>,
+    viewer: Viewer,
+    Path(id): Path<Uuid>,
+) -> ApiResult<Json<CollectionDetail>> {
+    Ok(Json(load_detail(&state, &viewer, id).await?))
+}
+
+/// PATCH /api/collections/:id
+pub async fn update_collection(
+    State(state): State<AppState>,
+    user: AuthUser,
+    tenant: CurrentTenant,
+    Path(id): Path<Uuid>,
+    payload: Result<Json<UpdateCollectionRequest>, JsonRejection>,
+) -> ApiResult<Json<CollectionDetail>> {
+    user.require(ApiScope::Publish)?;
+    let Json(req) = payload.map_err(map_json_rejection)?;
+    let name = req.name.as_deref().map(validate_name).transpose()?;
+    validate_text(
+        "description",
+        req.description.as_deref(),
+        MAX_DESCRIPTION_LENGTH,
+    )?;
+    ensure_owner(&state, &user, &tenant, id).await?;
+
+    sqlx::query(
+        "UPDATE collections SET \
+             name = COALESCE($2, name), \
+             description = COALESCE($3, description), \
+             is_public = COALESCE($4, is_public), \
+             updated_at = NOW() \
+         WHERE id = $1",
+    )
+    .bind(id)
+    .bind(name)
+    .bind(req.description.as_deref())
+    .bind(req.is_public)
+    .execute(&state.db)
+    .await
+    .map_err(|err| match err {
+        sqlx::Error::Database(ref e) if e.is_unique_violation() => {
+            duplicate_name(name.unwrap_or_default())
+        }
+        err => db_internal_error("update collection", err),
+    })?;
+
+    Ok(Json(
+        load_detail(&state, &owner_viewer(&user, &tenant), id).await?,
+    ))
+}
+
+/// DELETE /api/collections/:id
+pub async fn delete_collection(
+    State(state): State<AppState>,
+    user: AuthUser,
+    tenant: CurrentTenant,
+    Path(id): Path<Uuid>,
+) -> ApiResult<StatusCode> {
+    user.require(ApiScope::Publish)?;
+    ensure_owner(&state, &user, &tenant, id).await?;
+    sqlx::query("DELETE FROM collections WHERE id = $1")
+        .bind(id)
+        .execute(&state.db)
+        .await
+        .map_err(|err| db_internal_error("delete collection", err))?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// POST /api/collections/:id/items
+pub async fn add_collection_item(
+    State(state): State<AppState>,
+    user: AuthUser,
+    tenant: CurrentTenant,
+    Path(id): Path<Uuid>,
+    payload: Result<Json<AddCollectionItemRequest>, JsonRejection>,
+) -> ApiResult<(StatusCode, Json<CollectionDetail>)> {
+    user.require(ApiScope::Publish)?;
+    let Json(req) = payload.map_err(map_json_rejection)?;
+    validate_text("note", req.note.as_deref(), MAX_NOTE_LENGTH)?;
+    if req.position.is_some_and(|position| position < 0) {
+        return Err(ApiError::bad_request(
+            "ValidationError",
+            "position must be 0 or greater",
+        ));
+    }
+    ensure_owner(&state, &user, &tenant, id).await?;
+
+    let viewer = owner_viewer(&user, &tenant);
+    let (contract_uuid, _) =
+        fetch_contract_identity(&state, tenant.id, req.contract.trim()).await?;
+    ensure_contract_visible(&state, &viewer, contract_uuid).await?;
+
+    let mut tx = state
+        .db
+        .begin()
+        .await
+        .map_err(|err| db_internal_error("begin collection update", err))?;
+    // Serialize concurrent edits of one collection so positions stay dense
+    sqlx::query("SELECT id FROM collections WHERE id = $1 FOR UPDATE")
+        .bind(id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|err| db_internal_error("lock collection", err))?;
+    let count: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM collection_items WHERE collection_id = $1")
+            .bind(id)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(|err| db_internal_error("count collection items", err))?;
+    if count >= MAX_ITEMS_PER_COLLECTION {
+        return Err(ApiError::unprocessable(
+            "CollectionFull",
+            format!(
+                "A collection holds at most {} contracts",
+                MAX_ITEMS_PER_COLLECTION
+            ),
+        ));
+    }
+    let position = req
+        .position
+        .map_or(count, |position| i64::from(position).min(count)) as i32;
+    sqlx::query(
+        "UPDATE collection_items SET position = position + 1 \
+         WHERE collection_id = $1 AND position >= $2",
+    )
+    .bind(id)
+    .bind(position)
+    .execute(&mut *tx)
+    .await
+    .map_err(|err| db_internal_error("shift collection items", err))?;
+    sqlx::query(
+        "INSERT INTO collection_items (collection_id, contract_id, position, note) \
+         VALUES ($1, $2, $3, $4)",
+    )
+    .bind(id)
+    .bind(contract_uuid)
+    .bind(position)
+    .bind(req.note.as_deref())
+    .execute(&mut *tx)
+    .await
+    .map_err(|err| match err {
+        sqlx::Error::Database(ref e) if e.is_unique_violation() => ApiError::conflict(
+            "AlreadyInCollection",
+            format!("{} is already in this collection", req.contract),
+        ),
+        err => db_internal_error("add collection item", err),
+    })?;
+    sqlx::query("UPDATE collections SET updated_at = NOW() WHERE id = $1")
+        .bind(id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|err| db_internal_error("touch collection", err))?;
+    tx.commit()
+        .await
+        .map_err(|err| db_internal_error("commit collection update", err))?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(load_detail(&state, &viewer, id).await?),
+    ))
+}
+
+/// DELETE /api/collections/:id/items/:contract
+pub async fn remove_collection_item(
+    State(state): State<AppState>,
+    user: AuthUser,
+    tenant: CurrentTenant,
+    Path((id, contract)): Path<(Uuid, String)>,
+) -> ApiResult<StatusCode> {
+    user.require(ApiScope::Publish)?;
+    ensure_owner(&state, &user, &tenant, id).await?;
+    let (contract_uuid, _) = fetch_contract_identity(&state, tenant.id, &contract).await?;
+
+    let mut tx = state
+        .db
+        .begin()
+        .await
+        .map_err(|err| db_internal_error("begin collection update", err))?;
+    let removed: Option<i32> = sqlx::query_scalar(
+        "DELETE FROM collection_items WHERE collection_id = $1 AND contract_id = $2 \
+         RETURNING position",
+    )
+    .bind(id)
+    .bind(contract_uuid)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|err| db_internal_error("remove collection item", err))?;
+    let Some(position) = removed else {
+        return Err(ApiError::not_found(
+            "NotInCollection",
+            format!("{} is not in this collection", contract),
+        ));
+    };
+    sqlx::query(
+        "UPDATE collection_items SET position = position - 1 \
+         WHERE collection_id = $1 AND position > $2",
+    )
+    .bind(id)
+    .bind(position)
+    .execute(&mut *tx)
+    .await
+    .map_err(|err| db_internal_error("shift collection items", err))?;
+    sqlx::query("UPDATE collections SET updated_at = NOW() WHERE id = $1")
+        .bind(id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|err| db_internal_error("touch collection", err))?;
+    tx.commit()
+        .await
+        .map_err(|err| db_internal_error("commit collection update", err))?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// PUT /api/collections/:id/items — reorder
+pub async fn reorder_collection(
+    State(state): State<AppState>,
+    user: AuthUser,
+    tenant: CurrentTenant,
+    Path(id): Path<Uuid>,
+    payload: Result<Json<ReorderCollectionRequest>, JsonRejection>,
+) -> ApiResult<Json<CollectionDetail>> {
+    user.require(ApiScope::Publish)?;
+    let Json(req) = payload.map_err(map_json_rejection)?;
+    ensure_owner(&state, &user, &tenant, id).await?;
+
+    let mut tx = state
+        .db
+        .begin()
+        .await
+        .map_err(|err| db_internal_error("begin collection update", err))?;
+    let current: Vec<Uuid> = sqlx::query_scalar(
+        "SELECT contract_id FROM collection_items WHERE collection_id = $1 \
+         ORDER BY position, added_at FOR UPDATE",
+    )
+    .bind(id)
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(|err| db_internal_error("fetch collection order", err))?;
+    let order = reordered(&current, &req.contracts)
+        .map_err(|message| ApiError::bad_request("InvalidOrder", message))?;
+
+    sqlx::query(
+        "UPDATE collection_items i SET position = (o.position - 1)::INTEGER \
+         FROM UNNEST($2::uuid[]) WITH ORDINALITY AS o(contract_id, position) \
+         WHERE i.collection_id = $1 AND i.contract_id = o.contract_id",
+    )
+    .bind(id)
+    .bind(&order)
+    .execute(&mut *tx)
+    .await
+    .map_err(|err| db_internal_error("reorder collection", err))?;
+    sqlx::query("UPDATE collections SET updated_at = NOW() WHERE id = $1")
+        .bind(id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|err| db_internal_error("touch collection", err))?;
+    tx.commit()
+        .await
+        .map_err(|err| db_internal_error("commit collection update", err))?;
+
+    Ok(Json(
+        load_detail(&state, &owner_viewer(&user, &tenant), id).await?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reorder_moves_listed_items_first() {
+        let [a, b, c, d] = [1u128, 2, 3, 4].map(Uuid::from_u128);
+        assert_eq!(reordered(&[a, b, c, d], &[c, a]).unwrap(), vec![c, a, b, d]);
+        assert_eq!(reordered(&[a, b], &[b, a]).unwrap(), vec![b, a]);
+        assert!(reordered(&[a, b], &[b, b]).is_err());
+        assert!(reordered(&[a, b], &[c]).is_err());
+    }
+
+    #[test]
+    fn names_are_trimmed_and_bounded() {
+        assert_eq!(
+            validate_name("  DeFi starter kit ").unwrap(),
+            "DeFi starter kit"
+        );
+        assert!(validate_name("   ").is_err());
+        assert!(validate_name("tab\tname").is_err());
+        assert!(validate_name(&"x".repeat(MAX_COLLECTION_NAME_LENGTH + 1)).is_err());
+    }
+}
@@ -0,0 +1,231 @@
+// compat_handlers.rs
+// Interface compatibility checker used by dependents and by `publish --check`.
+
+use axum::{extract::State, Json};
+use serde::{Deserialize, Serialize};
+use shared::SemVer;
+use uuid::Uuid;
+
+use crate::breaking_changes::{diff_abi, resolve_abi, BreakingChange, ChangeSeverity};
+use crate::error::{ApiError, ApiResult};
+use crate::private_registry::{ensure_contract_visible, Viewer};
+use crate::state::AppState;
+use crate::type_safety::parser::parse_json_spec;
+
+/// Overall verdict of comparing two contract interfaces.
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CompatibilityLevel {
+    /// Both interfaces expose exactly the same functions and types
+    Identical,
+    /// Only backwards-compatible additions were found
+    Additive,
+    /// At least one change breaks existing callers
+    Breaking,
+}
+
+/// Request body for POST /api/compat/check.
+///
+/// Each side is either a version reference (UUID, `contract_id` or
+/// `contract_id@version`) or an uploaded contract spec JSON.
+#[derive(Debug, Deserialize)]
+pub struct CompatCheckRequest {
+    pub old: Option<String>,
+    pub new: Option<String>,
+    pub old_spec: Option<serde_json::Value>,
+    pub new_spec: Option<serde_json::Value>,
+    /// Version label of the uploaded new spec, used for the semver bump check
+    pub new_version: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CompatReport {
+    pub old: String,
+    pub new: String,
+    pub compatibility: CompatibilityLevel,
+    /// Resolved version of the old side, when it is known
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub old_version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub new_version: Option<String>,
+    /// True when the interface breaks but the major version was not bumped
+    pub requires_major_bump: bool,
+    pub breaking_count: usize,
+    pub additive_count: usize,
+    pub changes: Vec<BreakingChange>,
+}
+
+pub fn classify(changes: &[BreakingChange]) -> CompatibilityLevel {
+    if changes.is_empty() {
+        CompatibilityLevel::Identical
+    } else if changes
+        .iter()
+        .any(|c| c.severity == ChangeSeverity::Breaking)
+    {
+        CompatibilityLevel::Breaking
+    } else {
+        CompatibilityLevel::Additive
+    }
+}
+
+/// A breaking change must come with a major bump (or any bump below 1.0.0).
+pub fn missing_major_bump(level: CompatibilityLevel, old: Option<&str>, new: Option<&str>) -> bool {
+    if level != CompatibilityLevel::Breaking {
+        return false;
+    }
+    match (old.and_then(SemVer::parse), new.and_then(SemVer::parse)) {
+        (Some(old), Some(new)) if old.major == 0 && new.major == 0 => new.minor <= old.minor,
+        (Some(old), Some(new)) => new.major <= old.major,
+        _ => false,
+    }
+}
+
+pub async fn check_compatibility(
+    State(state): State<AppState>,
+    viewer: Viewer,
+    Json(req): Json<CompatCheckRequest>,
+) -> ApiResult<Json<CompatReport>> {
+    let (old_label, old_abi) = load_side(&state, &viewer, "old", req.old, req.old_spec).await?;
+    let (new_label, new_abi) = load_side(&state, &viewer, "new", req.new, req.new_spec).await?;
+
+    let old_spec = parse_json_spec(&old_abi, &old_label).map_err(|e| {
+        ApiError::bad_request("InvalidABI", format!("Failed to parse old ABI: {}", e))
+    })?;
+    let new_spec = parse_json_spec(&new_abi, &new_label).map_err(|e| {
+        ApiError::bad_request("InvalidABI", format!("Failed to parse new ABI: {}", e))
+    })?;
+
+    let changes = diff_abi(&old_spec, &new_spec);
+    let compatibility = classify(&changes);
+    let breaking_count = changes
+        .iter()
+        .filter(|c| c.severity == ChangeSeverity::Breaking)
+        .count();
+
+    let old_version = resolve_version(&state, &old_label).await?;
+    let new_version = match req.new_version {
+        Some(version) => Some(version),
+        None => resolve_version(&state, &new_label).await?,
+    };
+
+    Ok(Json(CompatReport {
+        requires_major_bump: missing_major_bump(
+            compatibility,
+            old_version.as_deref(),
+            new_version.as_deref(),
+        ),
+        old: old_label,
+        new: new_label,
+        compatibility,
+        old_version,
+        new_version,
+        breaking_count,
+        additive_count: changes.len() - breaking_count,
+        changes,
+    }))
+}
+
+async fn load_side(
+    state: &AppState,
+    viewer: &Viewer,
+    side: &str,
+    reference: Option<String>,
+    spec: Option<serde_json::Value>,
+) -> ApiResult<(String, String)> {
+    match (reference, spec) {
+        (_, Some(spec)) => Ok((format!("{}-upload", side), spec.to_string())),
+        (Some(reference), None) => {
+            if let Some(contract_uuid) = owning_contract(state, &reference).await? {
+                ensure_contract_visible(state, viewer, contract_uuid).await?;
+            }
+            let abi = resolve_abi(state, &reference).await?;
+            Ok((reference, abi))
+        }
+        (None, None) => Err(ApiError::bad_request(
+            "MissingInterface",
+            format!("Provide either '{}' or '{}_spec'", side, side),
+        )),
+    }
+}
+
+/// Finds the contract a version reference belongs to, so private contracts
+/// are checked before their interface is read.
+async fn owning_contract(state: &AppState, reference: &str) -> ApiResult<Option<Uuid>> {
+    let selector = reference.split_once('@').map_or(reference, |(id, _)| id);
+    sqlx::query_scalar::<_, Uuid>(
+        "SELECT c.id FROM contracts c
+         WHERE c.contract_id = $1 OR c.id::text = $1
+            OR c.id = (SELECT cv.contract_id FROM contract_versions cv WHERE cv.id::text = $1)
+         LIMIT 1",
+    )
+    .bind(selector)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|e| ApiError::internal(format!("Database error: {}", e)))
+}
+
+/// Works out which version a selector points at so the bump check can run.
+async fn resolve_version(state: &AppState, selector: &str) -> ApiResult<Option<String>> {
+    if let Some((_, version)) = selector.split_once('@') {
+        return Ok(Some(version.to_string()));
+    }
+    if selector.ends_with("-upload") {
+        return Ok(None);
+    }
+
+    sqlx::query_scalar::<_, String>(
+        "SELECT ca.version FROM contract_abis ca
+         JOIN contracts c ON c.id = ca.contract_id
+         WHERE c.contract_id = $1 OR c.id::text = $1
+         ORDER BY ca.created_at DESC LIMIT 1",
+    )
+    .bind(selector)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|e| ApiError::internal(format!("Database error: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn change(severity: ChangeSeverity) -> BreakingChange {
+        BreakingChange {
+            severity,
+            category: "test".to_string(),
+            message: "test".to_string(),
+            function: None,
+            type_name: None,
+        }
+    }
+
+    #[test]
+    fn classifies_reports() {
+        assert_eq!(classify(&[]), CompatibilityLevel::Identical);
+        assert_eq!(
+            classify(&[change(ChangeSeverity::NonBreaking)]),
+            CompatibilityLevel::Additive
+        );
+        assert_eq!(
+            classify(&[
+                change(ChangeSeverity::NonBreaking),
+                change(ChangeSeverity::Breaking)
+            ]),
+            CompatibilityLevel::Breaking
+        );
+    }
+
+    #[test]
+    fn breaking_change_needs_major_bump() {
+        let level = CompatibilityLevel::Breaking;
+        assert!(missing_major_bump(level, Some("1.2.0"), Some("1.3.0")));
+        assert!(!missing_major_bump(level, Some("1.2.0"), Some("2.0.0")));
+        assert!(missing_major_bump(level, Some("0.2.0"), Some("0.2.1")));
+        assert!(!missing_major_bump(level, Some("0.2.0"), Some("0.3.0")));
+        assert!(!missing_major_bump(
+            CompatibilityLevel::Additive,
+            Some("1.2.0"),
+            Some("1.2.1")
+        ));
+    }
+}
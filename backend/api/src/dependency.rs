@@ -157,6 +157,19 @@ pub async fn resolve_contract_id(pool: &PgPool, identifier: &str) -> Result<Opti
         .fetch_optional(pool)
         .await?;
 
+    if id.is_some() {
+        return Ok(id);
+    }
+
+    // Try a name the contract had before it was renamed
+    let id: Option<Uuid> = sqlx::query_scalar(
+        "SELECT contract_id FROM contract_aliases \
+         WHERE namespace IS NULL AND name = $1 ORDER BY created_at DESC LIMIT 1",
+    )
+    .bind(identifier)
+    .fetch_optional(pool)
+    .await?;
+
     Ok(id)
 }
 
@@ -7,13 +7,16 @@ use shared::{DeprecateContractRequest, DeprecationInfo, DeprecationStatus};
 use uuid::Uuid;
 
 use crate::error::{ApiError, ApiResult};
+use crate::handlers::fetch_contract_identity;
 use crate::state::AppState;
+use crate::tenancy::CurrentTenant;
 
 pub async fn get_deprecation_info(
     State(state): State<AppState>,
+    tenant: CurrentTenant,
     Path(id): Path<String>,
 ) -> ApiResult<Json<DeprecationInfo>> {
-    let (contract_uuid, contract_id) = fetch_contract_identity(&state, &id).await?;
+    let (contract_uuid, contract_id) = fetch_contract_identity(&state, tenant.id, &id).await?;
 
     let record = sqlx::query_as::<
         _,
@@ -84,10 +87,11 @@ pub async fn get_deprecation_info(
 
 pub async fn deprecate_contract(
     State(state): State<AppState>,
+    tenant: CurrentTenant,
     Path(id): Path<String>,
     Json(req): Json<DeprecateContractRequest>,
 ) -> ApiResult<Json<DeprecationInfo>> {
-    let (contract_uuid, contract_id) = fetch_contract_identity(&state, &id).await?;
+    let (contract_uuid, contract_id) = fetch_contract_identity(&state, tenant.id, &id).await?;
 
     if req.migration_guide_url.is_none() && req.replacement_contract_id.is_none() {
         return Err(ApiError::bad_request(
@@ -104,7 +108,8 @@ pub async fn deprecate_contract(
     }
 
     let replacement_uuid = if let Some(ref selector) = req.replacement_contract_id {
-        Some(fetch_contract_uuid(&state, selector).await?)
+        let (uuid, _) = fetch_contract_identity(&state, tenant.id, selector).await?;
+        Some(uuid)
     } else {
         None
     };
@@ -130,7 +135,7 @@ pub async fn deprecate_contract(
 
     notify_dependents(&state, contract_uuid, &contract_id, req.retirement_at).await?;
 
-    get_deprecation_info(State(state), Path(contract_id)).await
+    get_deprecation_info(State(state), tenant, Path(contract_id)).await
 }
 
 async fn notify_dependents(
@@ -200,59 +205,6 @@ async fn notify_dependents(
     Ok(())
 }
 
-async fn fetch_contract_identity(state: &AppState, id: &str) -> ApiResult<(Uuid, String)> {
-    if let Ok(uuid) = Uuid::parse_str(id) {
-        let row = sqlx::query_as::<_, (Uuid, String)>(
-            "SELECT id, contract_id FROM contracts WHERE id = $1",
-        )
-        .bind(uuid)
-        .fetch_optional(&state.db)
-        .await
-        .map_err(|err| db_internal_error("fetch contract", err))?;
-        return row.ok_or_else(|| {
-            ApiError::not_found(
-                "ContractNotFound",
-                format!("No contract found with ID: {}", id),
-            )
-        });
-    }
-
-    let row = sqlx::query_as::<_, (Uuid, String)>(
-        "SELECT id, contract_id FROM contracts WHERE contract_id = $1",
-    )
-    .bind(id)
-    .fetch_optional(&state.db)
-    .await
-    .map_err(|err| db_internal_error("fetch contract", err))?;
-
-    row.ok_or_else(|| {
-        ApiError::not_found(
-            "ContractNotFound",
-            format!("No contract found with ID: {}", id),
-        )
-    })
-}
-
-async fn fetch_contract_uuid(state: &AppState, contract_id: &str) -> ApiResult<Uuid> {
-    if let Ok(uuid) = Uuid::parse_str(contract_id) {
-        return Ok(uuid);
-    }
-
-    let uuid = sqlx::query_scalar::<_, Uuid>("SELECT id FROM contracts WHERE contract_id = $1")
-        .bind(contract_id)
-        .fetch_optional(&state.db)
-        .await
-        .map_err(|err| db_internal_error("fetch contract", err))?
-        .ok_or_else(|| {
-            ApiError::not_found(
-                "ContractNotFound",
-                format!("Contract '{}' not found", contract_id),
-            )
-        })?;
-
-    Ok(uuid)
-}
-
 fn db_internal_error(operation: &str, err: sqlx::Error) -> ApiError {
     tracing::error!(operation = operation, error = ?err, "database operation failed");
     ApiError::internal("Database operation failed")
@@ -0,0 +1,95 @@
+// discover_handlers.rs
+// "Surprise me": a random verified contract. Contract ids are random UUIDs, so
+// picking the first id at or after a random pivot walks the primary key index
+// instead of scanning and sorting the table the way ORDER BY random() would.
+
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    Json,
+};
+use shared::{Contract, Network, RandomContractQuery};
+use uuid::Uuid;
+
+use crate::error::{ApiError, ApiResult};
+use crate::handlers::db_internal_error;
+use crate::private_registry::Viewer;
+use crate::state::AppState;
+
+/// Verified, listed contracts the viewer may see: deprecated contracts and
+/// contracts under maintenance are held back, as are private/other-tenant ones
+fn sample_query(viewer: &Viewer) -> String {
+    format!(
+        "SELECT c.* FROM contracts c \
+         WHERE c.is_verified = TRUE AND c.is_maintenance IS NOT TRUE \
+         AND NOT EXISTS (SELECT 1 FROM contract_deprecations d WHERE d.contract_id = c.id){} \
+         AND ($1::text IS NULL OR c.category = $1) \
+         AND ($2::network_type IS NULL OR c.network = $2) \
+         AND c.id >= $3 \
+         ORDER BY c.id LIMIT 1",
+        viewer.visibility_clause("c.")
+    )
+}
+
+async fn first_at_or_after(
+    state: &AppState,
+    sql: &str,
+    category: Option<&str>,
+    network: Option<&Network>,
+    pivot: Uuid,
+) -> ApiResult<Option<Contract>> {
+    sqlx::query_as::<_, Contract>(sql)
+        .bind(category)
+        .bind(network)
+        .bind(pivot)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(|err| db_internal_error("sample random contract", err))
+}
+
+/// GET /api/contracts/random?category=...&network=...
+pub async fn random_contract(
+    State(state): State<AppState>,
+    viewer: Viewer,
+    Query(params): Query<RandomContractQuery>,
+) -> ApiResult<Json<Contract>> {
+    let category = params
+        .category
+        .as_deref()
+        .map(str::trim)
+        .filter(|category| !category.is_empty());
+    let sql = sample_query(&viewer);
+
+    let pivot = Uuid::new_v4();
+    let mut contract =
+        first_at_or_after(&state, &sql, category, params.network.as_ref(), pivot).await?;
+    if contract.is_none() {
+        // Nothing past the pivot; wrap around to the start of the key range
+        contract =
+            first_at_or_after(&state, &sql, category, params.network.as_ref(), Uuid::nil()).await?;
+    }
+
+    contract.map(Json).ok_or_else(|| {
+        let message = match category {
+            Some(category) => format!("No verified contracts in category '{}'", category),
+            None => "No verified contracts to pick from".to_string(),
+        };
+        ApiError::new(StatusCode::NOT_FOUND, "ContractNotFound", message)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sample_holds_back_hidden_contracts_and_walks_the_key() {
+        let sql = sample_query(&Viewer::default());
+        assert!(sql.contains("c.is_verified = TRUE"));
+        assert!(sql.contains("c.is_maintenance IS NOT TRUE"));
+        assert!(sql.contains("contract_deprecations"));
+        assert!(sql.contains("c.is_private IS NOT TRUE"));
+        assert!(sql.contains("c.id >= $3 ORDER BY c.id LIMIT 1"));
+        assert!(!sql.contains("random()"));
+    }
+}
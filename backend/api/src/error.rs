@@ -1,17 +1,20 @@
 use axum::{
-    http::{header, HeaderValue, StatusCode},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
     response::{IntoResponse, Response},
     Json,
 };
-use chrono::{SecondsFormat, Utc};
-use serde::Serialize;
-use uuid::Uuid;
+use shared::{ErrorCode, FieldProblem, ProblemDetails, PROBLEM_JSON_CONTENT_TYPE};
 
 #[derive(Debug)]
 pub struct ApiError {
     status: StatusCode,
     error: String,
     message: String,
+    /// Per-field problems, rendered as the problem's `errors` list
+    errors: Vec<FieldProblem>,
+    /// Sent along with the problem, e.g. the challenge a client must answer;
+    /// boxed so errors stay small
+    headers: Option<Box<HeaderMap>>,
 }
 
 impl std::fmt::Display for ApiError {
@@ -20,24 +23,28 @@ impl std::fmt::Display for ApiError {
     }
 }
 
-#[derive(Debug, Serialize)]
-struct ErrorResponse {
-    error: String,
-    message: String,
-    code: u16,
-    timestamp: String,
-    correlation_id: String,
-}
-
 impl ApiError {
     pub fn new(status: StatusCode, error: impl Into<String>, message: impl Into<String>) -> Self {
         Self {
             status,
             error: error.into(),
             message: message.into(),
+            errors: Vec::new(),
+            headers: None,
         }
     }
 
+    /// Attach the individual problems behind this error
+    pub fn with_field_errors(mut self, errors: Vec<FieldProblem>) -> Self {
+        self.errors = errors;
+        self
+    }
+
+    pub fn with_headers(mut self, headers: HeaderMap) -> Self {
+        self.headers = Some(Box::new(headers));
+        self
+    }
+
     pub fn bad_request(error: impl Into<String>, message: impl Into<String>) -> Self {
         Self::new(StatusCode::BAD_REQUEST, error, message)
     }
@@ -65,27 +72,83 @@ impl ApiError {
     pub fn db_error(message: impl Into<String>) -> Self {
         Self::new(StatusCode::INTERNAL_SERVER_ERROR, "DatabaseError", message)
     }
+
+    pub fn code(&self) -> ErrorCode {
+        ErrorCode::from(self.error.as_str())
+    }
+}
+
+/// Render an RFC 7807 problem with the `application/problem+json` content type
+pub fn problem_response(status: StatusCode, problem: ProblemDetails) -> Response {
+    let correlation_id = problem.correlation_id.clone();
+    let mut response = (status, Json(problem)).into_response();
+    response.headers_mut().insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static(PROBLEM_JSON_CONTENT_TYPE),
+    );
+    if let Ok(value) = HeaderValue::from_str(&correlation_id) {
+        response
+            .headers_mut()
+            .insert(header::HeaderName::from_static("x-correlation-id"), value);
+    }
+    response
 }
 
 impl IntoResponse for ApiError {
     fn into_response(self) -> Response {
-        let correlation_id = Uuid::new_v4().to_string();
-        let payload = ErrorResponse {
-            error: self.error,
-            message: self.message,
-            code: self.status.as_u16(),
-            timestamp: Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true),
-            correlation_id: correlation_id.clone(),
-        };
-
-        let mut response = (self.status, Json(payload)).into_response();
-        if let Ok(value) = HeaderValue::from_str(&correlation_id) {
-            response
-                .headers_mut()
-                .insert(header::HeaderName::from_static("x-correlation-id"), value);
+        let mut problem = ProblemDetails::new(self.status.as_u16(), self.code(), self.message);
+        problem.errors = self.errors;
+        let mut response = problem_response(self.status, problem);
+        if let Some(headers) = self.headers {
+            response.headers_mut().extend(*headers);
         }
         response
     }
 }
 
 pub type ApiResult<T> = std::result::Result<T, ApiError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_problem_json() {
+        let response = ApiError::not_found("ContractNotFound", "missing").into_response();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(
+            response.headers()[header::CONTENT_TYPE],
+            PROBLEM_JSON_CONTENT_TYPE
+        );
+        assert!(response.headers().contains_key("x-correlation-id"));
+    }
+
+    #[tokio::test]
+    async fn renders_field_errors() {
+        let response = ApiError::bad_request("InvalidABI", "bad")
+            .with_field_errors(vec![FieldProblem {
+                field: "abi[0].name".to_string(),
+                message: "is required".to_string(),
+            }])
+            .into_response();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let problem: ProblemDetails = serde_json::from_slice(&body).unwrap();
+        assert_eq!(problem.code, ErrorCode::InvalidABI);
+        assert_eq!(problem.errors.len(), 1);
+        assert_eq!(problem.errors[0].field, "abi[0].name");
+    }
+
+    #[test]
+    fn maps_known_and_unknown_codes() {
+        assert_eq!(
+            ApiError::bad_request("InvalidVersion", "bad").code(),
+            ErrorCode::InvalidVersion
+        );
+        assert_eq!(
+            ApiError::bad_request("MissingProposer", "bad").code(),
+            ErrorCode::Other("MissingProposer".to_string())
+        );
+    }
+}
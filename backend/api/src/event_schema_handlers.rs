@@ -0,0 +1,240 @@
+// event_schema_handlers.rs
+// Event schemas publishers register per version, and decoding of raw
+// on-chain events (XDR) against them, so explorers and indexers can show
+// named, typed fields for any registry contract.
+
+use std::collections::HashSet;
+
+use axum::{
+    extract::{rejection::JsonRejection, Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use serde_json::Value;
+use shared::{
+    decode_event, ApiScope, DecodeEventRequest, DecodedEvent, EventSchema, EventSchemasQuery,
+    EventSchemasResponse, RegisterEventSchemasRequest, MAX_EVENT_SCHEMAS,
+};
+use sqlx::types::Json as SqlJson;
+use uuid::Uuid;
+
+use crate::error::{ApiError, ApiResult};
+use crate::handlers::{db_internal_error, fetch_contract_identity, map_json_rejection};
+use crate::private_registry::{ensure_contract_visible, Viewer};
+use crate::state::AppState;
+use crate::tenancy::CurrentTenant;
+use crate::user_auth::AuthUser;
+
+fn invalid(message: impl Into<String>) -> ApiError {
+    ApiError::unprocessable("InvalidEventSchema", message)
+}
+
+fn validate_schemas(events: &[EventSchema]) -> ApiResult<()> {
+    if events.len() > MAX_EVENT_SCHEMAS {
+        return Err(invalid(format!(
+            "A version registers at most {} events",
+            MAX_EVENT_SCHEMAS
+        )));
+    }
+    let mut names = HashSet::new();
+    for event in events {
+        event.validate().map_err(invalid)?;
+        if !names.insert(event.name.as_str()) {
+            return Err(invalid(format!(
+                "Event `{}` is registered twice",
+                event.name
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// The given version, or the newest one with registered schemas (falling
+/// back to the newest version)
+async fn resolve_version(
+    state: &AppState,
+    contract_uuid: Uuid,
+    id: &str,
+    version: Option<&str>,
+) -> ApiResult<(Uuid, String)> {
+    sqlx::query_as(
+        "SELECT v.id, v.version FROM contract_versions v \
+         WHERE v.contract_id = $1 AND ($2::TEXT IS NULL OR v.version = $2) \
+         ORDER BY EXISTS (SELECT 1 FROM contract_event_schemas s WHERE s.version_id = v.id) DESC, \
+                  v.created_at DESC \
+         LIMIT 1",
+    )
+    .bind(contract_uuid)
+    .bind(version)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|err| db_internal_error("fetch version for event schemas", err))?
+    .ok_or_else(|| {
+        ApiError::not_found(
+            "VersionNotFound",
+            match version {
+                Some(version) => format!("Contract {} has no version '{}'", id, version),
+                None => format!("Contract {} has no versions", id),
+            },
+        )
+    })
+}
+
+async fn fetch_schemas(state: &AppState, version_id: Uuid) -> ApiResult<Vec<EventSchema>> {
+    let rows: Vec<SqlJson<EventSchema>> = sqlx::query_scalar(
+        "SELECT schema FROM contract_event_schemas WHERE version_id = $1 ORDER BY name",
+    )
+    .bind(version_id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("fetch event schemas", err))?;
+    Ok(rows.into_iter().map(|SqlJson(schema)| schema).collect())
+}
+
+/// GET /api/contracts/:id/events?version= — the event schemas of a version;
+/// by default the newest one that registered any
+pub async fn get_event_schemas(
+    State(state): State<AppState>,
+    viewer: Viewer,
+    Path(id): Path<String>,
+    Query(query): Query<EventSchemasQuery>,
+) -> ApiResult<Json<EventSchemasResponse>> {
+    let (contract_uuid, contract_id) =
+        fetch_contract_identity(&state, viewer.tenant_id, &id).await?;
+    ensure_contract_visible(&state, &viewer, contract_uuid).await?;
+    let (version_id, version) =
+        resolve_version(&state, contract_uuid, &id, query.version.as_deref()).await?;
+    Ok(Json(EventSchemasResponse {
+        contract_id,
+        version,
+        events: fetch_schemas(&state, version_id).await?,
+    }))
+}
+
+/// PUT /api/contracts/:id/versions/:version/events — the contract's publisher
+/// replaces a version's event schemas
+pub async fn register_event_schemas(
+    State(state): State<AppState>,
+    tenant: CurrentTenant,
+    user: AuthUser,
+    Path((id, version)): Path<(String, String)>,
+    payload: Result<Json<RegisterEventSchemasRequest>, JsonRejection>,
+) -> ApiResult<Json<EventSchemasResponse>> {
+    user.require(ApiScope::Publish)?;
+    let Json(req) = payload.map_err(map_json_rejection)?;
+    validate_schemas(&req.events)?;
+    let (contract_uuid, contract_id) = fetch_contract_identity(&state, tenant.id, &id).await?;
+
+    let row: Option<(Uuid, String)> = sqlx::query_as(
+        "SELECT v.id, p.stellar_address FROM contract_versions v \
+         JOIN contracts c ON c.id = v.contract_id \
+         JOIN publishers p ON p.id = c.publisher_id \
+         WHERE v.contract_id = $1 AND v.version = $2",
+    )
+    .bind(contract_uuid)
+    .bind(&version)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|err| db_internal_error("fetch contract version", err))?;
+    let (version_id, owner) = row.ok_or_else(|| {
+        ApiError::not_found(
+            "VersionNotFound",
+            format!("Contract has no version {}", version),
+        )
+    })?;
+    if owner != user.address {
+        return Err(ApiError::new(
+            StatusCode::FORBIDDEN,
+            "Forbidden",
+            "Only the contract's publisher can register its event schemas",
+        ));
+    }
+
+    let mut tx = state
+        .db
+        .begin()
+        .await
+        .map_err(|err| db_internal_error("begin transaction", err))?;
+    sqlx::query("DELETE FROM contract_event_schemas WHERE version_id = $1")
+        .bind(version_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|err| db_internal_error("clear event schemas", err))?;
+    for event in &req.events {
+        sqlx::query(
+            "INSERT INTO contract_event_schemas (version_id, name, schema, updated_by) \
+             VALUES ($1, $2, $3, $4)",
+        )
+        .bind(version_id)
+        .bind(&event.name)
+        .bind(SqlJson(event))
+        .bind(&user.address)
+        .execute(&mut *tx)
+        .await
+        .map_err(|err| db_internal_error("save event schema", err))?;
+    }
+    tx.commit()
+        .await
+        .map_err(|err| db_internal_error("commit event schemas", err))?;
+
+    let mut events = req.events;
+    events.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(Json(EventSchemasResponse {
+        contract_id,
+        version,
+        events,
+    }))
+}
+
+/// POST /api/contracts/:id/events/decode — decode a raw event against the
+/// schemas of a version (by default the newest that registered any). Events
+/// no schema matches still come back with untyped topics and data.
+pub async fn decode_contract_event(
+    State(state): State<AppState>,
+    viewer: Viewer,
+    Path(id): Path<String>,
+    payload: Result<Json<DecodeEventRequest>, JsonRejection>,
+) -> ApiResult<Json<DecodedEvent>> {
+    let Json(req) = payload.map_err(map_json_rejection)?;
+    let raw = req
+        .raw_event()
+        .map_err(|reason| ApiError::bad_request("InvalidEventXdr", reason))?;
+    let (contract_uuid, _) = fetch_contract_identity(&state, viewer.tenant_id, &id).await?;
+    ensure_contract_visible(&state, &viewer, contract_uuid).await?;
+    let (version_id, version) =
+        resolve_version(&state, contract_uuid, &id, req.version.as_deref()).await?;
+    let schemas = fetch_schemas(&state, version_id).await?;
+
+    // User-defined types in the schemas resolve against the version's ABI
+    let abi: Option<Value> =
+        sqlx::query_scalar("SELECT abi FROM contract_abis WHERE contract_id = $1 AND version = $2")
+            .bind(contract_uuid)
+            .bind(&version)
+            .fetch_optional(&state.db)
+            .await
+            .map_err(|err| db_internal_error("fetch version abi", err))?;
+    let abi = abi
+        .as_ref()
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+    Ok(Json(decode_event(&schemas, abi, &raw)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn schema(name: &str) -> EventSchema {
+        serde_json::from_value(json!({ "name": name })).unwrap()
+    }
+
+    #[test]
+    fn schemas_must_be_valid_and_distinct() {
+        assert!(validate_schemas(&[schema("mint"), schema("burn")]).is_ok());
+        let err = validate_schemas(&[schema("mint"), schema("mint")]).unwrap_err();
+        assert!(err.to_string().contains("registered twice"));
+        assert!(validate_schemas(&[schema("not valid")]).is_err());
+    }
+}
@@ -0,0 +1,369 @@
+// example_handlers.rs
+// Usage examples publishers attach to a contract: named sequences of calls
+// with sample arguments, checked against a version's interface when saved and
+// run through the simulation sandbox on request.
+
+use axum::{
+    extract::{rejection::JsonRejection, Path, State},
+    http::StatusCode,
+    Json,
+};
+use shared::{
+    ApiScope, ContractExample, ExampleRun, ExampleStep, RunExampleRequest, SimulationResult,
+    UpsertExampleRequest,
+};
+use sqlx::types::Json as SqlJson;
+use uuid::Uuid;
+
+use crate::error::{ApiError, ApiResult};
+use crate::handlers::{db_internal_error, fetch_contract_identity, map_json_rejection};
+use crate::private_registry::{ensure_contract_visible, Viewer};
+use crate::simulation::{self, Sandbox};
+use crate::simulation_handlers::{acquire_sandbox_slot, load_abi, load_version_for_sandbox};
+use crate::state::AppState;
+use crate::tenancy::CurrentTenant;
+use crate::type_safety::ContractABI;
+use crate::user_auth::AuthUser;
+
+const MAX_EXAMPLE_NAME_LENGTH: usize = 64;
+const MAX_DESCRIPTION_LENGTH: usize = 2000;
+const MAX_STEPS: usize = 20;
+
+fn invalid(message: impl Into<String>) -> ApiError {
+    ApiError::unprocessable("InvalidExample", message)
+}
+
+fn example_not_found(name: &str) -> ApiError {
+    ApiError::not_found(
+        "ExampleNotFound",
+        format!("The contract has no example named '{}'", name),
+    )
+}
+
+/// Names appear in URLs and on the command line: lowercase letters, digits,
+/// `-` and `_`
+fn validate_name(name: &str) -> ApiResult<()> {
+    let valid = !name.is_empty()
+        && name.len() <= MAX_EXAMPLE_NAME_LENGTH
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(ApiError::bad_request(
+            "InvalidExampleName",
+            format!(
+                "Example names must be 1-{} characters of a-z, 0-9, '-' and '_'",
+                MAX_EXAMPLE_NAME_LENGTH
+            ),
+        ))
+    }
+}
+
+/// Every step must call a function of the interface with arguments it accepts
+fn validate_steps(steps: &[ExampleStep], abi: &ContractABI) -> ApiResult<()> {
+    if steps.is_empty() || steps.len() > MAX_STEPS {
+        return Err(invalid(format!("An example needs 1-{} steps", MAX_STEPS)));
+    }
+    for (i, step) in steps.iter().enumerate() {
+        simulation::encode_call(abi, &step.function, &step.args).map_err(|reason| {
+            invalid(format!(
+                "Step {} (`{}`) does not match the interface: {}",
+                i + 1,
+                step.function,
+                reason
+            ))
+        })?;
+    }
+    Ok(())
+}
+
+/// GET /api/contracts/:id/examples — alphabetical
+pub async fn list_examples(
+    State(state): State<AppState>,
+    viewer: Viewer,
+    Path(id): Path<String>,
+) -> ApiResult<Json<Vec<ContractExample>>> {
+    let (contract_uuid, _) = fetch_contract_identity(&state, viewer.tenant_id, &id).await?;
+    ensure_contract_visible(&state, &viewer, contract_uuid).await?;
+    let examples: Vec<ContractExample> =
+        sqlx::query_as("SELECT * FROM contract_examples WHERE contract_id = $1 ORDER BY name")
+            .bind(contract_uuid)
+            .fetch_all(&state.db)
+            .await
+            .map_err(|err| db_internal_error("list examples", err))?;
+    Ok(Json(examples))
+}
+
+async fn fetch_example(
+    state: &AppState,
+    contract_uuid: Uuid,
+    name: &str,
+) -> ApiResult<ContractExample> {
+    sqlx::query_as("SELECT * FROM contract_examples WHERE contract_id = $1 AND name = $2")
+        .bind(contract_uuid)
+        .bind(name)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(|err| db_internal_error("fetch example", err))?
+        .ok_or_else(|| example_not_found(name))
+}
+
+/// GET /api/contracts/:id/examples/:name
+pub async fn get_example(
+    State(state): State<AppState>,
+    viewer: Viewer,
+    Path((id, name)): Path<(String, String)>,
+) -> ApiResult<Json<ContractExample>> {
+    let (contract_uuid, _) = fetch_contract_identity(&state, viewer.tenant_id, &id).await?;
+    ensure_contract_visible(&state, &viewer, contract_uuid).await?;
+    fetch_example(&state, contract_uuid, &name).await.map(Json)
+}
+
+/// Writes are for the contract's publisher only
+async fn ensure_publisher(state: &AppState, user: &AuthUser, contract_uuid: Uuid) -> ApiResult<()> {
+    let owner: Option<String> = sqlx::query_scalar(
+        "SELECT p.stellar_address FROM contracts c \
+         JOIN publishers p ON p.id = c.publisher_id WHERE c.id = $1",
+    )
+    .bind(contract_uuid)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|err| db_internal_error("fetch contract publisher", err))?;
+    if owner.as_deref() == Some(user.address.as_str()) {
+        Ok(())
+    } else {
+        Err(ApiError::new(
+            StatusCode::FORBIDDEN,
+            "Forbidden",
+            "Only the contract's publisher can change its examples",
+        ))
+    }
+}
+
+/// PUT /api/contracts/:id/examples/:name — create or replace an example after
+/// checking its steps against the interface of the latest (or a given) version
+pub async fn upsert_example(
+    State(state): State<AppState>,
+    tenant: CurrentTenant,
+    user: AuthUser,
+    Path((id, name)): Path<(String, String)>,
+    payload: Result<Json<UpsertExampleRequest>, JsonRejection>,
+) -> ApiResult<Json<ContractExample>> {
+    user.require(ApiScope::Publish)?;
+    let Json(req) = payload.map_err(map_json_rejection)?;
+    validate_name(&name)?;
+    if req
+        .description
+        .as_deref()
+        .is_some_and(|description| description.chars().count() > MAX_DESCRIPTION_LENGTH)
+    {
+        return Err(ApiError::bad_request(
+            "ValidationError",
+            format!(
+                "description must be at most {} characters",
+                MAX_DESCRIPTION_LENGTH
+            ),
+        ));
+    }
+    let (contract_uuid, _) = fetch_contract_identity(&state, tenant.id, &id).await?;
+    ensure_publisher(&state, &user, contract_uuid).await?;
+
+    let version: String = sqlx::query_scalar(
+        "SELECT version FROM contract_versions \
+         WHERE contract_id = $1 AND ($2::TEXT IS NULL OR version = $2) \
+         ORDER BY created_at DESC LIMIT 1",
+    )
+    .bind(contract_uuid)
+    .bind(req.version.as_deref())
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|err| db_internal_error("fetch version to validate against", err))?
+    .ok_or_else(|| {
+        ApiError::not_found(
+            "VersionNotFound",
+            match &req.version {
+                Some(version) => format!("Contract {} has no version '{}'", id, version),
+                None => format!("Contract {} has no versions", id),
+            },
+        )
+    })?;
+    let abi = load_abi(&state, contract_uuid, &id, &version).await?;
+    validate_steps(&req.steps, &abi)?;
+
+    let example: ContractExample = sqlx::query_as(
+        "INSERT INTO contract_examples \
+             (contract_id, name, description, version, steps, created_by) \
+         VALUES ($1, $2, $3, $4, $5, $6) \
+         ON CONFLICT (contract_id, name) DO UPDATE \
+         SET description = EXCLUDED.description, version = EXCLUDED.version, \
+             steps = EXCLUDED.steps, updated_at = NOW() \
+         RETURNING *",
+    )
+    .bind(contract_uuid)
+    .bind(&name)
+    .bind(&req.description)
+    .bind(&version)
+    .bind(SqlJson(&req.steps))
+    .bind(&user.address)
+    .fetch_one(&state.db)
+    .await
+    .map_err(|err| db_internal_error("save example", err))?;
+    Ok(Json(example))
+}
+
+/// DELETE /api/contracts/:id/examples/:name
+pub async fn delete_example(
+    State(state): State<AppState>,
+    tenant: CurrentTenant,
+    user: AuthUser,
+    Path((id, name)): Path<(String, String)>,
+) -> ApiResult<StatusCode> {
+    user.require(ApiScope::Publish)?;
+    let (contract_uuid, _) = fetch_contract_identity(&state, tenant.id, &id).await?;
+    ensure_publisher(&state, &user, contract_uuid).await?;
+    let deleted = sqlx::query("DELETE FROM contract_examples WHERE contract_id = $1 AND name = $2")
+        .bind(contract_uuid)
+        .bind(&name)
+        .execute(&state.db)
+        .await
+        .map_err(|err| db_internal_error("delete example", err))?;
+    if deleted.rows_affected() == 0 {
+        return Err(example_not_found(&name));
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// POST /api/contracts/:id/examples/:name/run — run the steps in order in one
+/// sandbox against the latest (or a given) version, stopping at the first
+/// failure. Authorization is mocked and nothing persists.
+pub async fn run_example(
+    State(state): State<AppState>,
+    viewer: Viewer,
+    Path((id, name)): Path<(String, String)>,
+    payload: Option<Json<RunExampleRequest>>,
+) -> ApiResult<Json<ExampleRun>> {
+    let req = payload.map(|Json(req)| req).unwrap_or_default();
+    let (contract_uuid, _) = fetch_contract_identity(&state, viewer.tenant_id, &id).await?;
+    ensure_contract_visible(&state, &viewer, contract_uuid).await?;
+    let example = fetch_example(&state, contract_uuid, &name).await?;
+    let (version, wasm, abi) =
+        load_version_for_sandbox(&state, contract_uuid, &id, req.version.as_deref()).await?;
+
+    // Re-checked, since the version run may not be the one the example was saved against
+    let mut calls = Vec::with_capacity(example.steps.len());
+    for (i, step) in example.steps.iter().enumerate() {
+        let args = simulation::encode_call(&abi, &step.function, &step.args).map_err(|reason| {
+            ApiError::bad_request(
+                "InvalidArguments",
+                format!(
+                    "Step {} (`{}`) does not match version {}: {}",
+                    i + 1,
+                    step.function,
+                    version,
+                    reason
+                ),
+            )
+        })?;
+        calls.push((step.function.clone(), args));
+    }
+    let error_names = simulation::error_names(&abi);
+
+    let _slot = acquire_sandbox_slot()?;
+    let sandbox_version = version.clone();
+    let steps = tokio::task::spawn_blocking(move || {
+        let mut sandbox = Sandbox::deploy(&wasm);
+        let mut steps = Vec::with_capacity(calls.len());
+        for (function, args) in calls {
+            let outcome = sandbox.call(&function, &args, &error_names)?;
+            let (success, result, error) = match outcome.result {
+                Ok(value) => (true, Some(value), None),
+                Err(reason) => (false, None, Some(reason)),
+            };
+            steps.push(SimulationResult {
+                version: sandbox_version.clone(),
+                function,
+                success,
+                result,
+                result_xdr: outcome.result_xdr,
+                error,
+                events: outcome.events,
+                resources: outcome.resources,
+            });
+            if !success {
+                break;
+            }
+        }
+        Ok::<_, String>(steps)
+    })
+    .await
+    // The host panics on WASM it cannot instantiate
+    .map_err(|_| {
+        ApiError::unprocessable(
+            "SimulationFailed",
+            format!("Version {} could not be loaded into the sandbox", version),
+        )
+    })?
+    .map_err(|reason| ApiError::unprocessable("SimulationFailed", reason))?;
+
+    Ok(Json(ExampleRun {
+        example: example.name,
+        version,
+        success: steps.len() == example.steps.len() && steps.iter().all(|step| step.success),
+        steps,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::type_safety::parse_json_spec;
+
+    fn token_abi() -> ContractABI {
+        parse_json_spec(
+            r#"[
+                {"type": "function", "name": "initialize",
+                 "inputs": [{"name": "admin", "value": {"type": "Address"}}], "outputs": []},
+                {"type": "function", "name": "mint",
+                 "inputs": [{"name": "amount", "value": {"type": "i128"}}], "outputs": []}
+            ]"#,
+            "Token",
+        )
+        .unwrap()
+    }
+
+    fn step(function: &str, args: &[&str]) -> ExampleStep {
+        ExampleStep {
+            function: function.to_string(),
+            args: args.iter().map(|arg| arg.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn names_are_url_safe() {
+        assert!(validate_name("mint-and-transfer").is_ok());
+        assert!(validate_name("basic_usage2").is_ok());
+        assert!(validate_name("").is_err());
+        assert!(validate_name("Mint").is_err());
+        assert!(validate_name("mint all").is_err());
+        assert!(validate_name(&"a".repeat(65)).is_err());
+    }
+
+    #[test]
+    fn steps_are_checked_against_the_interface() {
+        let abi = token_abi();
+        let admin = "GAAZI4TCR3TY5OJHCTJC2A4QSY6CJWJH5IAJTGKIN2ER7LBNVKOCCWN7";
+        assert!(validate_steps(
+            &[step("initialize", &[admin]), step("mint", &["100"])],
+            &abi
+        )
+        .is_ok());
+        assert!(validate_steps(&[], &abi).is_err());
+
+        let err = validate_steps(&[step("initialize", &[admin]), step("burn", &["1"])], &abi)
+            .unwrap_err();
+        assert!(err.to_string().contains("Step 2 (`burn`)"), "{}", err);
+        assert!(validate_steps(&[step("mint", &["lots"])], &abi).is_err());
+    }
+}
@@ -0,0 +1,446 @@
+// feed_handlers.rs
+// Atom feeds of new releases and an XML sitemap for aggregators and crawlers.
+
+use axum::{
+    extract::{Path, State},
+    http::{header, HeaderValue},
+    response::{IntoResponse, Response},
+};
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::error::{ApiError, ApiResult};
+use crate::handlers::db_internal_error;
+use crate::private_registry::Viewer;
+use crate::state::AppState;
+
+const ATOM_CONTENT_TYPE: &str = "application/atom+xml; charset=utf-8";
+const XML_CONTENT_TYPE: &str = "application/xml; charset=utf-8";
+
+/// Number of releases included in each feed
+const FEED_LIMIT: i64 = 50;
+
+/// Most URLs a single sitemap file may list (sitemaps.org protocol limit)
+const SITEMAP_MAX_URLS: i64 = 50_000;
+
+/// Frontend pages listed in the first sitemap file
+const STATIC_PAGES: [&str; 4] = ["", "/contracts", "/publish", "/stats"];
+
+pub(crate) fn xml_escape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for ch in value.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            _ => out.push(ch),
+        }
+    }
+    out
+}
+
+/// One published version, as it appears in a feed
+#[derive(Debug, sqlx::FromRow)]
+struct ReleaseEntry {
+    contract_id: Uuid,
+    name: String,
+    description: Option<String>,
+    version: String,
+    release_notes: Option<String>,
+    publisher_address: String,
+    created_at: DateTime<Utc>,
+}
+
+fn render_atom(base: &str, title: &str, self_path: &str, entries: &[ReleaseEntry]) -> String {
+    let updated = entries
+        .first()
+        .map(|e| e.created_at)
+        .unwrap_or_else(Utc::now)
+        .to_rfc3339();
+
+    let mut xml = String::new();
+    xml.push_str("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+    xml.push_str("<feed xmlns=\"http://www.w3.org/2005/Atom\">\n");
+    xml.push_str(&format!("  <title>{}</title>\n", xml_escape(title)));
+    xml.push_str(&format!("  <id>{}{}</id>\n", base, self_path));
+    xml.push_str(&format!(
+        "  <link rel=\"self\" href=\"{}{}\"/>\n",
+        base, self_path
+    ));
+    xml.push_str(&format!("  <link href=\"{}\"/>\n", base));
+    xml.push_str(&format!("  <updated>{}</updated>\n", updated));
+
+    for entry in entries {
+        let link = format!("{}/contracts/{}", base, entry.contract_id);
+        let summary = entry
+            .release_notes
+            .as_deref()
+            .or(entry.description.as_deref())
+            .unwrap_or("");
+        xml.push_str("  <entry>\n");
+        xml.push_str(&format!(
+            "    <title>{} {}</title>\n",
+            xml_escape(&entry.name),
+            xml_escape(&entry.version)
+        ));
+        xml.push_str(&format!(
+            "    <id>{}/releases/{}</id>\n",
+            link,
+            xml_escape(&entry.version)
+        ));
+        xml.push_str(&format!("    <link href=\"{}\"/>\n", link));
+        xml.push_str(&format!(
+            "    <updated>{}</updated>\n",
+            entry.created_at.to_rfc3339()
+        ));
+        xml.push_str(&format!(
+            "    <author><name>{}</name></author>\n",
+            xml_escape(&entry.publisher_address)
+        ));
+        xml.push_str(&format!("    <summary>{}</summary>\n", xml_escape(summary)));
+        xml.push_str("  </entry>\n");
+    }
+
+    xml.push_str("</feed>\n");
+    xml
+}
+
+fn xml_response(content_type: &'static str, body: String) -> Response {
+    let mut response = body.into_response();
+    response
+        .headers_mut()
+        .insert(header::CONTENT_TYPE, HeaderValue::from_static(content_type));
+    response
+}
+
+/// Releases of contracts `viewer` may see; further conditions start with ` AND`
+fn releases_query(viewer: &Viewer) -> String {
+    format!(
+        "SELECT c.id AS contract_id, c.name, c.description, cv.version, \
+                cv.release_notes, p.stellar_address AS publisher_address, cv.created_at \
+         FROM contract_versions cv \
+         JOIN contracts c ON c.id = cv.contract_id \
+         JOIN publishers p ON p.id = c.publisher_id \
+         WHERE TRUE{}",
+        viewer.visibility_clause("c.")
+    )
+}
+
+/// GET /feed/releases.atom
+pub async fn releases_feed(State(state): State<AppState>, viewer: Viewer) -> ApiResult<Response> {
+    let entries: Vec<ReleaseEntry> = sqlx::query_as(&format!(
+        "{} ORDER BY cv.created_at DESC LIMIT $1",
+        releases_query(&viewer)
+    ))
+    .bind(FEED_LIMIT)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("fetch releases feed", err))?;
+
+    let xml = render_atom(
+        &state.site_url,
+        "Soroban Registry: new releases",
+        "/feed/releases.atom",
+        &entries,
+    );
+    Ok(xml_response(ATOM_CONTENT_TYPE, xml))
+}
+
+/// GET /feed/publishers/:id.atom — `:id` is a publisher UUID or Stellar address
+pub async fn publisher_feed(
+    State(state): State<AppState>,
+    viewer: Viewer,
+    Path(file): Path<String>,
+) -> ApiResult<Response> {
+    let id = file
+        .strip_suffix(".atom")
+        .ok_or_else(|| ApiError::not_found("NotFound", format!("No feed found at {}", file)))?;
+
+    let publisher: Option<(Uuid, String)> = sqlx::query_as(
+        "SELECT id, stellar_address FROM publishers \
+         WHERE id::text = $1 OR stellar_address = $1",
+    )
+    .bind(id)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|err| db_internal_error("fetch publisher for feed", err))?;
+    let (publisher_id, address) = publisher.ok_or_else(|| {
+        ApiError::not_found("PublisherNotFound", format!("No publisher found: {}", id))
+    })?;
+
+    let entries: Vec<ReleaseEntry> = sqlx::query_as(&format!(
+        "{} AND c.publisher_id = $1 ORDER BY cv.created_at DESC LIMIT $2",
+        releases_query(&viewer)
+    ))
+    .bind(publisher_id)
+    .bind(FEED_LIMIT)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("fetch publisher feed", err))?;
+
+    let xml = render_atom(
+        &state.site_url,
+        &format!("Soroban Registry: releases by {}", address),
+        &format!("/feed/publishers/{}.atom", id),
+        &entries,
+    );
+    Ok(xml_response(ATOM_CONTENT_TYPE, xml))
+}
+
+/// Kind of URL listed in one sitemap file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SitemapKind {
+    Contracts,
+    Publishers,
+}
+
+impl SitemapKind {
+    fn prefix(self) -> &'static str {
+        match self {
+            SitemapKind::Contracts => "contracts",
+            SitemapKind::Publishers => "publishers",
+        }
+    }
+}
+
+/// `contracts-3.xml` → (Contracts, 3); pages are numbered from 1
+fn parse_sitemap_file(file: &str) -> Option<(SitemapKind, i64)> {
+    let (prefix, page) = file.strip_suffix(".xml")?.rsplit_once('-')?;
+    let kind = [SitemapKind::Contracts, SitemapKind::Publishers]
+        .into_iter()
+        .find(|kind| kind.prefix() == prefix)?;
+    let page = page.parse::<i64>().ok().filter(|page| *page >= 1)?;
+    Some((kind, page))
+}
+
+/// Files needed for `count` URLs; the first contracts file also carries the
+/// static pages, and every kind gets at least one file
+fn sitemap_pages(count: i64) -> i64 {
+    ((count + SITEMAP_MAX_URLS - 1) / SITEMAP_MAX_URLS).max(1)
+}
+
+fn render_sitemap_index(base: &str, files: &[String]) -> String {
+    let mut xml = String::new();
+    xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str("<sitemapindex xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n");
+    for file in files {
+        xml.push_str(&format!(
+            "  <sitemap><loc>{}/sitemaps/{}</loc></sitemap>\n",
+            base, file
+        ));
+    }
+    xml.push_str("</sitemapindex>\n");
+    xml
+}
+
+/// Publishers with at least one contract `viewer` may see
+fn sitemap_publishers_clause(viewer: &Viewer) -> String {
+    format!(
+        "EXISTS (SELECT 1 FROM contracts c WHERE c.publisher_id = p.id{})",
+        viewer.visibility_clause("c.")
+    )
+}
+
+/// GET /sitemap.xml — a sitemap index pointing at files of at most
+/// [`SITEMAP_MAX_URLS`] URLs each
+pub async fn sitemap(State(state): State<AppState>, viewer: Viewer) -> ApiResult<Response> {
+    let contracts: i64 = sqlx::query_scalar(&format!(
+        "SELECT COUNT(*) FROM contracts c WHERE TRUE{}",
+        viewer.visibility_clause("c.")
+    ))
+    .fetch_one(&state.db)
+    .await
+    .map_err(|err| db_internal_error("count contracts for sitemap", err))?;
+    let publishers: i64 = sqlx::query_scalar(&format!(
+        "SELECT COUNT(*) FROM publishers p WHERE {}",
+        sitemap_publishers_clause(&viewer)
+    ))
+    .fetch_one(&state.db)
+    .await
+    .map_err(|err| db_internal_error("count publishers for sitemap", err))?;
+
+    // The first contracts file also holds the static pages
+    let contract_urls = contracts + STATIC_PAGES.len() as i64;
+    let files: Vec<String> = [
+        (SitemapKind::Contracts, contract_urls),
+        (SitemapKind::Publishers, publishers),
+    ]
+    .into_iter()
+    .flat_map(|(kind, count)| {
+        (1..=sitemap_pages(count)).map(move |page| format!("{}-{}.xml", kind.prefix(), page))
+    })
+    .collect();
+
+    Ok(xml_response(
+        XML_CONTENT_TYPE,
+        render_sitemap_index(&state.site_url, &files),
+    ))
+}
+
+/// GET /sitemaps/:file — one page of contract or publisher URLs
+pub async fn sitemap_file(
+    State(state): State<AppState>,
+    viewer: Viewer,
+    Path(file): Path<String>,
+) -> ApiResult<Response> {
+    let (kind, page) = parse_sitemap_file(&file)
+        .ok_or_else(|| ApiError::not_found("NotFound", format!("No sitemap found at {}", file)))?;
+    let base = &state.site_url;
+    let mut urls = Vec::new();
+
+    match kind {
+        SitemapKind::Contracts => {
+            // Static pages take the first slots of the first file
+            let static_count = STATIC_PAGES.len() as i64;
+            let (offset, limit) = if page == 1 {
+                for path in STATIC_PAGES {
+                    urls.push(format!("  <url><loc>{}{}</loc></url>\n", base, path));
+                }
+                (0, SITEMAP_MAX_URLS - static_count)
+            } else {
+                (
+                    (page - 1) * SITEMAP_MAX_URLS - static_count,
+                    SITEMAP_MAX_URLS,
+                )
+            };
+            let contracts: Vec<(Uuid, DateTime<Utc>)> = sqlx::query_as(&format!(
+                "SELECT c.id, c.updated_at FROM contracts c WHERE TRUE{} \
+                 ORDER BY c.created_at, c.id LIMIT $1 OFFSET $2",
+                viewer.visibility_clause("c.")
+            ))
+            .bind(limit)
+            .bind(offset)
+            .fetch_all(&state.db)
+            .await
+            .map_err(|err| db_internal_error("fetch contracts for sitemap", err))?;
+            for (id, updated_at) in &contracts {
+                urls.push(format!(
+                    "  <url><loc>{}/contracts/{}</loc><lastmod>{}</lastmod></url>\n",
+                    base,
+                    id,
+                    updated_at.format("%Y-%m-%d")
+                ));
+            }
+        }
+        SitemapKind::Publishers => {
+            let publishers: Vec<String> = sqlx::query_scalar(&format!(
+                "SELECT p.stellar_address FROM publishers p WHERE {} \
+                 ORDER BY p.created_at, p.id LIMIT $1 OFFSET $2",
+                sitemap_publishers_clause(&viewer)
+            ))
+            .bind(SITEMAP_MAX_URLS)
+            .bind((page - 1) * SITEMAP_MAX_URLS)
+            .fetch_all(&state.db)
+            .await
+            .map_err(|err| db_internal_error("fetch publishers for sitemap", err))?;
+            for address in &publishers {
+                urls.push(format!(
+                    "  <url><loc>{}/publishers/{}</loc></url>\n",
+                    base,
+                    xml_escape(address)
+                ));
+            }
+        }
+    }
+
+    if urls.is_empty() && page > 1 {
+        return Err(ApiError::not_found(
+            "NotFound",
+            format!("No sitemap found at {}", file),
+        ));
+    }
+
+    let mut xml = String::new();
+    xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str("<urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n");
+    for url in &urls {
+        xml.push_str(url);
+    }
+    xml.push_str("</urlset>\n");
+
+    Ok(xml_response(XML_CONTENT_TYPE, xml))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escapes_xml_special_characters() {
+        assert_eq!(
+            xml_escape("<a href=\"x\">&'</a>"),
+            "&lt;a href=&quot;x&quot;&gt;&amp;&apos;&lt;/a&gt;"
+        );
+    }
+
+    #[test]
+    fn renders_atom_entries() {
+        let entries = vec![ReleaseEntry {
+            contract_id: Uuid::nil(),
+            name: "Token & Co".to_string(),
+            description: Some("A token".to_string()),
+            version: "1.0.0".to_string(),
+            release_notes: None,
+            publisher_address: "GABC".to_string(),
+            created_at: Utc::now(),
+        }];
+        let xml = render_atom(
+            "https://example.com",
+            "Releases",
+            "/feed/releases.atom",
+            &entries,
+        );
+        assert!(xml.contains("<title>Token &amp; Co 1.0.0</title>"));
+        assert!(
+            xml.contains("<link rel=\"self\" href=\"https://example.com/feed/releases.atom\"/>")
+        );
+        assert!(xml.contains("<summary>A token</summary>"));
+        assert_eq!(xml.matches("<entry>").count(), 1);
+    }
+
+    #[test]
+    fn parses_sitemap_file_names() {
+        assert_eq!(
+            parse_sitemap_file("contracts-1.xml"),
+            Some((SitemapKind::Contracts, 1))
+        );
+        assert_eq!(
+            parse_sitemap_file("publishers-12.xml"),
+            Some((SitemapKind::Publishers, 12))
+        );
+        assert_eq!(parse_sitemap_file("contracts-0.xml"), None);
+        assert_eq!(parse_sitemap_file("contracts.xml"), None);
+        assert_eq!(parse_sitemap_file("versions-1.xml"), None);
+    }
+
+    #[test]
+    fn sitemap_files_stay_under_the_url_limit() {
+        assert_eq!(sitemap_pages(0), 1);
+        assert_eq!(sitemap_pages(SITEMAP_MAX_URLS), 1);
+        assert_eq!(sitemap_pages(SITEMAP_MAX_URLS + 1), 2);
+        assert_eq!(sitemap_pages(3 * SITEMAP_MAX_URLS), 3);
+
+        let xml = render_sitemap_index(
+            "https://example.com",
+            &[
+                "contracts-1.xml".to_string(),
+                "publishers-1.xml".to_string(),
+            ],
+        );
+        assert!(xml.contains("<loc>https://example.com/sitemaps/contracts-1.xml</loc>"));
+        assert_eq!(xml.matches("<sitemap>").count(), 2);
+    }
+
+    #[test]
+    fn feeds_only_list_visible_releases() {
+        let viewer = Viewer {
+            address: None,
+            tenant_id: Uuid::nil(),
+        };
+        let query = releases_query(&viewer);
+        assert!(query.contains("c.is_private IS NOT TRUE"));
+        assert!(query.contains("COALESCE(c.moderation_status, 'active') = 'active'"));
+    }
+}
@@ -0,0 +1,204 @@
+// fork_handlers.rs
+// Fork tracking: contracts based on another registry contract record it at
+// publish; lineage and fork trees trace where copied code came from.
+
+use std::collections::HashMap;
+
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use chrono::{DateTime, Utc};
+use shared::{ContractForksResponse, ContractName, ForkNode, ForkReference};
+use uuid::Uuid;
+
+use crate::error::{ApiError, ApiResult};
+use crate::handlers::{db_internal_error, fetch_contract_identity};
+use crate::private_registry::{ensure_contract_visible, Viewer};
+use crate::state::AppState;
+
+/// Deepest fork chain followed in either direction
+const MAX_FORK_DEPTH: i32 = 16;
+
+#[derive(Debug, sqlx::FromRow)]
+struct ForkRow {
+    id: Uuid,
+    forked_from: Option<Uuid>,
+    contract_id: String,
+    namespace: Option<String>,
+    name: String,
+    created_at: DateTime<Utc>,
+}
+
+impl ForkRow {
+    fn reference(&self) -> ForkReference {
+        ForkReference {
+            id: self.id,
+            contract_id: self.contract_id.clone(),
+            name: ContractName {
+                namespace: self.namespace.clone(),
+                name: self.name.clone(),
+            }
+            .to_string(),
+        }
+    }
+}
+
+/// The contract a new one is forked from. The publisher must be able to see it,
+/// so forks never cross tenants.
+pub(crate) async fn resolve_fork_source(
+    state: &AppState,
+    upstream: &str,
+    publisher_address: &str,
+    tenant_id: Uuid,
+) -> ApiResult<Uuid> {
+    let (upstream_uuid, _) = fetch_contract_identity(state, tenant_id, upstream)
+        .await
+        .map_err(|_| {
+            ApiError::unprocessable(
+                "ForkSourceNotFound",
+                format!("forked_from '{}' is not a registry contract", upstream),
+            )
+        })?;
+    let publisher = Viewer {
+        address: Some(publisher_address.to_string()),
+        tenant_id,
+    };
+    ensure_contract_visible(state, &publisher, upstream_uuid)
+        .await
+        .map_err(|_| {
+            ApiError::unprocessable(
+                "ForkSourceNotFound",
+                format!("forked_from '{}' is not a registry contract", upstream),
+            )
+        })?;
+    Ok(upstream_uuid)
+}
+
+/// Arrange descendants of `root` into a tree, oldest fork first. Rows whose
+/// parent is not in `rows` (e.g. hidden from the viewer) are left out.
+fn build_tree(root: Uuid, rows: &[ForkRow]) -> Vec<ForkNode> {
+    let mut children: HashMap<Uuid, Vec<&ForkRow>> = HashMap::new();
+    for row in rows {
+        if let Some(parent) = row.forked_from {
+            children.entry(parent).or_default().push(row);
+        }
+    }
+    fn nodes(parent: Uuid, children: &HashMap<Uuid, Vec<&ForkRow>>) -> Vec<ForkNode> {
+        let mut forks: Vec<ForkNode> = children
+            .get(&parent)
+            .into_iter()
+            .flatten()
+            .map(|row| ForkNode {
+                contract: row.reference(),
+                created_at: row.created_at,
+                forks: nodes(row.id, children),
+            })
+            .collect();
+        forks.sort_by_key(|fork| fork.created_at);
+        forks
+    }
+    nodes(root, &children)
+}
+
+fn count(forks: &[ForkNode]) -> usize {
+    forks.iter().map(|fork| 1 + count(&fork.forks)).sum()
+}
+
+/// GET /api/contracts/:id/forks — what the contract was forked from and the
+/// tree of contracts forked from it
+pub async fn get_contract_forks(
+    State(state): State<AppState>,
+    viewer: Viewer,
+    Path(id): Path<String>,
+) -> ApiResult<Json<ContractForksResponse>> {
+    let (contract_uuid, _) = fetch_contract_identity(&state, viewer.tenant_id, &id).await?;
+    ensure_contract_visible(&state, &viewer, contract_uuid).await?;
+    let visible = viewer.visibility_clause("c.");
+
+    let lineage: Vec<ForkRow> = sqlx::query_as(&format!(
+        "WITH RECURSIVE upstream AS ( \
+             SELECT forked_from AS id, 1 AS depth FROM contracts \
+             WHERE id = $1 AND forked_from IS NOT NULL \
+             UNION ALL \
+             SELECT c.forked_from, u.depth + 1 FROM contracts c \
+             JOIN upstream u ON c.id = u.id \
+             WHERE c.forked_from IS NOT NULL AND u.depth < $2) \
+         SELECT c.id, c.forked_from, c.contract_id, c.namespace, c.name, c.created_at \
+         FROM upstream u JOIN contracts c ON c.id = u.id \
+         WHERE TRUE{} ORDER BY u.depth",
+        visible
+    ))
+    .bind(contract_uuid)
+    .bind(MAX_FORK_DEPTH)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("fetch fork lineage", err))?;
+
+    let descendants: Vec<ForkRow> = sqlx::query_as(&format!(
+        "WITH RECURSIVE downstream AS ( \
+             SELECT id, 1 AS depth FROM contracts WHERE forked_from = $1 \
+             UNION ALL \
+             SELECT c.id, d.depth + 1 FROM contracts c \
+             JOIN downstream d ON c.forked_from = d.id \
+             WHERE d.depth < $2) \
+         SELECT c.id, c.forked_from, c.contract_id, c.namespace, c.name, c.created_at \
+         FROM downstream d JOIN contracts c ON c.id = d.id \
+         WHERE TRUE{}",
+        visible
+    ))
+    .bind(contract_uuid)
+    .bind(MAX_FORK_DEPTH)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("fetch forks", err))?;
+
+    let forks = build_tree(contract_uuid, &descendants);
+    Ok(Json(ContractForksResponse {
+        contract_id: contract_uuid,
+        lineage: lineage.iter().map(ForkRow::reference).collect(),
+        fork_count: forks.len(),
+        total_forks: count(&forks),
+        forks,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn row(id: u128, forked_from: u128, name: &str, day: u32) -> ForkRow {
+        ForkRow {
+            id: Uuid::from_u128(id),
+            forked_from: Some(Uuid::from_u128(forked_from)),
+            contract_id: format!("C{}", id),
+            namespace: Some("alice".to_string()),
+            name: name.to_string(),
+            created_at: Utc.with_ymd_and_hms(2026, 3, day, 0, 0, 0).unwrap(),
+        }
+    }
+
+    #[test]
+    fn builds_nested_tree_oldest_first() {
+        let rows = vec![
+            row(3, 1, "amm-v2", 5),
+            row(2, 1, "amm-lite", 2),
+            row(4, 2, "amm-lite-fee", 9),
+            // Parent hidden from the viewer
+            row(6, 5, "orphan", 3),
+        ];
+        let forks = build_tree(Uuid::from_u128(1), &rows);
+        assert_eq!(forks.len(), 2);
+        assert_eq!(forks[0].contract.name, "@alice/amm-lite");
+        assert_eq!(forks[0].forks[0].contract.contract_id, "C4");
+        assert!(forks[1].forks.is_empty());
+        assert_eq!(count(&forks), 3);
+    }
+
+    #[test]
+    fn no_forks_is_an_empty_tree() {
+        assert!(build_tree(Uuid::from_u128(1), &[]).is_empty());
+        assert_eq!(count(&[]), 0);
+    }
+}
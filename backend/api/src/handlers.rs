@@ -10,13 +10,16 @@ use axum::{
 use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
 use ed25519_dalek::{Signature, Verifier, VerifyingKey};
 use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
 use shared::{
     Contract, ContractAnalyticsResponse, ContractGetResponse, ContractInteractionResponse,
+    ContractLicenseResponse, ContractSearchHit, ContractSearchResponse, FacetCount,
+    SearchFacets, SearchHighlight, VerificationFacet,
     ContractSearchParams, ContractVersion, CreateContractVersionRequest,
     CreateInteractionBatchRequest, CreateInteractionRequest, DeploymentStats,
     InteractionsListResponse, InteractionsQueryParams, InteractorStats, Network, NetworkConfig,
-    PaginatedResponse, PublishRequest, Publisher, SemVer, TimelineEntry, TopUser,
-    ContractChangelogEntry, ContractChangelogResponse,
+    PaginatedResponse, PublishRequest, Publisher, PublisherContract, PublisherContractSummary,
+    PublisherContractsQuery, PublisherContractsResponse, SemVer, TimelineEntry, TopUser,
 };
 use std::time::Duration;
 use uuid::Uuid;
@@ -27,14 +30,27 @@ pub struct GetContractQuery {
     pub network: Option<Network>,
 }
 
+/// `?dry_run=true` on mutating endpoints: run every check, change nothing
+#[derive(Debug, Default, serde::Deserialize)]
+pub struct DryRunQuery {
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
 use crate::{
     analytics,
     breaking_changes::{diff_abi, has_breaking_changes, resolve_abi},
     dependency,
     error::{ApiError, ApiResult},
+    interfaces,
+    precondition::{check_if_match, concurrent_modification, with_etag, WithEtag},
+    private_registry::{ensure_contract_visible, Viewer},
+    registry_flags, search_suggestions,
     state::AppState,
+    tenancy::CurrentTenant,
     type_safety::parser::parse_json_spec,
     type_safety::{generate_openapi, to_json, to_yaml},
+    validation::ValidatedJson,
 };
 
 pub(crate) fn db_internal_error(operation: &str, err: sqlx::Error) -> ApiError {
@@ -42,7 +58,14 @@ pub(crate) fn db_internal_error(operation: &str, err: sqlx::Error) -> ApiError {
     ApiError::internal("An unexpected database error occurred")
 }
 
-fn map_json_rejection(err: JsonRejection) -> ApiError {
+pub(crate) fn map_json_rejection(err: JsonRejection) -> ApiError {
+    if err.status() == StatusCode::PAYLOAD_TOO_LARGE {
+        return ApiError::new(
+            StatusCode::PAYLOAD_TOO_LARGE,
+            "PayloadTooLarge",
+            "Request body exceeds the limit for this endpoint",
+        );
+    }
     ApiError::bad_request(
         "InvalidRequest",
         format!("Invalid JSON payload: {}", err.body_text()),
@@ -95,6 +118,10 @@ pub struct UpdateContractMetadataRequest {
     pub description: Option<String>,
     pub category: Option<String>,
     pub tags: Option<Vec<String>>,
+    /// Replaces the contract's links; labels map to URLs
+    pub links: Option<std::collections::BTreeMap<String, String>>,
+    /// Contract-level README in Markdown; an empty string removes it
+    pub readme: Option<String>,
     pub user_id: Option<Uuid>,
 }
 
@@ -111,7 +138,7 @@ pub struct UpdateContractStatusRequest {
     pub user_id: Option<Uuid>,
 }
 
-fn extract_ip_address(headers: &HeaderMap) -> String {
+pub(crate) fn extract_ip_address(headers: &HeaderMap) -> String {
     if let Some(forwarded_for) = headers
         .get("x-forwarded-for")
         .and_then(|value| value.to_str().ok())
@@ -242,9 +269,174 @@ pub async fn get_stats(State(state): State<AppState>) -> ApiResult<Json<Value>>
     })))
 }
 
+/// `ts_headline` options: names are highlighted whole, descriptions cut to short fragments
+const NAME_HEADLINE_OPTIONS: &str = "StartSel=<mark>, StopSel=</mark>, HighlightAll=true";
+const DESCRIPTION_HEADLINE_OPTIONS: &str = "StartSel=<mark>, StopSel=</mark>, \
+     MaxWords=25, MinWords=8, MaxFragments=2, FragmentDelimiter=\" … \"";
+
+/// Highlighted snippets for one page of search results, keyed by contract id.
+/// Fields without a match are left out. Highlighting is best-effort: on a
+/// database error the results are returned without it.
+async fn fetch_search_highlights(
+    db: &sqlx::PgPool,
+    query: &str,
+    ids: &[Uuid],
+) -> std::collections::HashMap<Uuid, SearchHighlight> {
+    let rows: Vec<(Uuid, Option<String>, Option<String>)> = match sqlx::query_as(
+        "SELECT c.id, \
+             ts_headline('english', c.name, q, $3), \
+             ts_headline('english', COALESCE(c.description, ''), q, $4) \
+         FROM contracts c, contracts_build_tsquery($1) AS q \
+         WHERE c.id = ANY($2)",
+    )
+    .bind(query)
+    .bind(ids)
+    .bind(NAME_HEADLINE_OPTIONS)
+    .bind(DESCRIPTION_HEADLINE_OPTIONS)
+    .fetch_all(db)
+    .await
+    {
+        Ok(rows) => rows,
+        Err(err) => {
+            tracing::warn!(error = ?err, "search highlighting failed");
+            return Default::default();
+        }
+    };
+
+    let matched =
+        |fragment: Option<String>| fragment.filter(|f| f.contains(shared::HIGHLIGHT_START));
+    rows.into_iter()
+        .filter_map(|(id, name, description)| {
+            let highlight = SearchHighlight {
+                name: matched(name),
+                description: matched(description),
+            };
+            (highlight != SearchHighlight::default()).then_some((id, highlight))
+        })
+        .collect()
+}
+
+/// Values listed per facet, most common first
+const FACET_LIMIT: i64 = 20;
+
+/// `contract_interactions.interaction_type` of a recorded download; other
+/// interactions (invocations and the like) do not count as downloads
+const DOWNLOAD_INTERACTION: &str = "download";
+
+/// Category, tag and verification counts over every contract matching
+/// `filters` (a WHERE clause over `contracts c`), not just the current page
+async fn fetch_search_facets(
+    db: &sqlx::PgPool,
+    filters: &str,
+) -> Result<SearchFacets, sqlx::Error> {
+    let categories: Vec<FacetCount> = sqlx::query_as(&format!(
+        "SELECT c.category AS value, COUNT(*) AS count FROM contracts c{} \
+           AND c.category IS NOT NULL \
+         GROUP BY c.category ORDER BY count DESC, value LIMIT $1",
+        filters
+    ))
+    .bind(FACET_LIMIT)
+    .fetch_all(db)
+    .await?;
+
+    let tags: Vec<FacetCount> = sqlx::query_as(&format!(
+        "SELECT t.tag AS value, COUNT(*) AS count \
+         FROM contracts c CROSS JOIN LATERAL unnest(c.tags) AS t(tag){} \
+         GROUP BY t.tag ORDER BY count DESC, value LIMIT $1",
+        filters
+    ))
+    .bind(FACET_LIMIT)
+    .fetch_all(db)
+    .await?;
+
+    let (verified, unverified): (i64, i64) = sqlx::query_as(&format!(
+        "SELECT COUNT(*) FILTER (WHERE c.is_verified), COUNT(*) FILTER (WHERE NOT c.is_verified) \
+         FROM contracts c{}",
+        filters
+    ))
+    .fetch_one(db)
+    .await?;
+
+    Ok(SearchFacets {
+        categories,
+        tags,
+        verified: VerificationFacet {
+            verified,
+            unverified,
+        },
+    })
+}
+
+/// ORDER BY expression for a `?sort=` key in the contract listing query
+fn contract_sort_column(sort: shared::ContractSort) -> &'static str {
+    match sort {
+        shared::ContractSort::Downloads => "COUNT(DISTINCT ci.id)",
+        shared::ContractSort::Updated => "c.updated_at",
+        shared::ContractSort::Created => "c.created_at",
+        shared::ContractSort::Name => "LOWER(c.name)",
+        shared::ContractSort::Rating => {
+            "(SELECT COALESCE(AVG(r.rating), 0) FROM reviews r \
+              WHERE r.contract_id = c.id AND r.reviewer_address IS NOT NULL \
+                AND NOT COALESCE(r.is_flagged, FALSE))"
+        }
+        shared::ContractSort::Stars => "c.star_count",
+        shared::ContractSort::Quality => "c.quality_score",
+    }
+}
+
 /// List and search contracts
+/// Filters `list_contracts` applied, echoed back under `filters`
+fn applied_search_filters(
+    params: &ContractSearchParams,
+    networks: Option<&[shared::Network]>,
+) -> std::collections::BTreeMap<String, serde_json::Value> {
+    let mut filters = std::collections::BTreeMap::new();
+    let mut set = |key: &str, value: serde_json::Value| {
+        filters.insert(key.to_string(), value);
+    };
+    if let Some(query) = params.query.as_deref().filter(|q| !q.trim().is_empty()) {
+        set("query", query.into());
+    }
+    if let Some(networks) = networks {
+        let names: Vec<String> = networks.iter().map(|n| n.to_string()).collect();
+        set("networks", names.into());
+    }
+    if params.verified_only == Some(true) {
+        set("verified_only", true.into());
+    }
+    if let Some(category) = &params.category {
+        set("category", category.as_str().into());
+    }
+    if let Some(license) = &params.license {
+        set("license", license.as_str().into());
+    }
+    if let Some(implements) = &params.implements {
+        set("implements", implements.as_str().into());
+    }
+    if let Some(namespace) = &params.namespace {
+        set("namespace", namespace.trim_start_matches('@').into());
+    }
+    if params.audited == Some(true) {
+        set("audited", true.into());
+    }
+    if let Some(after) = params.created_after {
+        set("created_after", after.to_rfc3339().into());
+    }
+    if let Some(after) = params.updated_after {
+        set("updated_after", after.to_rfc3339().into());
+    }
+    if let Some(min_downloads) = params.min_downloads {
+        set("min_downloads", min_downloads.into());
+    }
+    if let Some(max_wasm_size) = params.max_wasm_size {
+        set("max_wasm_size", max_wasm_size.into());
+    }
+    filters
+}
+
 pub async fn list_contracts(
     State(state): State<AppState>,
+    viewer: Viewer,
     params: Result<Query<ContractSearchParams>, QueryRejection>,
 ) -> axum::response::Response {
     let Query(params) = match params {
@@ -252,15 +444,29 @@ pub async fn list_contracts(
         Err(err) => return map_query_rejection(err).into_response(),
     };
 
-    let page = params.page.unwrap_or(1).max(1);
-    let limit = params.limit.unwrap_or(20).clamp(1, 100);
+    let (page, limit) = match params.cursor.as_deref() {
+        Some(raw) => match shared::PageCursor::decode(raw) {
+            Some(cursor) => (cursor.page, cursor.limit.clamp(1, 100)),
+            None => {
+                return ApiError::bad_request(
+                    "InvalidCursor",
+                    "cursor must be a next_cursor value from a previous page",
+                )
+                .into_response()
+            }
+        },
+        None => (
+            params.page.unwrap_or(1).max(1),
+            params.limit.unwrap_or(20).clamp(1, 100),
+        ),
+    };
     let offset = (page - 1).max(0) * limit;
 
     let sort_by = params.sort_by.clone().unwrap_or_else(|| {
         if params.query.is_some() {
             shared::SortBy::Relevance
         } else {
-            shared::SortBy::CreatedAt
+            shared::SortBy::Quality
         }
     });
     let sort_order = params.sort_order.clone().unwrap_or(shared::SortOrder::Desc);
@@ -273,28 +479,109 @@ pub async fn list_contracts(
          LEFT JOIN contract_versions cv ON c.id = cv.contract_id
          WHERE 1=1",
     );
-    let mut count_query = String::from("SELECT COUNT(*) FROM contracts WHERE 1=1");
+    // Filters shared by the count and facet queries, over `contracts c`
+    let mut filters = String::from(" WHERE 1=1");
+
+    let visibility_clause = viewer.visibility_clause("c.");
+    query.push_str(&visibility_clause);
+    filters.push_str(&visibility_clause);
 
     if let Some(ref q) = params.query {
-        let search_clause = format!(
-            " AND (c.name ILIKE '%{}%' OR c.description ILIKE '%{}%')",
-            q, q
-        );
-        query.push_str(&search_clause);
-        count_query.push_str(&search_clause);
+        query.push_str(&search_suggestions::match_clause(q, "c."));
+        filters.push_str(&search_suggestions::match_clause(q, ""));
     }
 
     if let Some(verified) = params.verified_only {
         if verified {
             query.push_str(" AND c.is_verified = true");
-            count_query.push_str(" AND is_verified = true");
+            filters.push_str(" AND is_verified = true");
         }
     }
 
     if let Some(ref category) = params.category {
         let category_clause = format!(" AND c.category = '{}'", category);
         query.push_str(&category_clause);
-        count_query.push_str(&category_clause);
+        filters.push_str(&category_clause);
+    }
+
+    if let Some(ref license) = params.license {
+        // Any term of the expression counts: `MIT` finds `Apache-2.0 OR MIT`
+        let license_clause = format!(
+            " AND c.license ~* '{}'",
+            shared::license_component_pattern(license).replace('\'', "''")
+        );
+        query.push_str(&license_clause);
+        filters.push_str(&license_clause);
+    }
+
+    if let Some(ref implements) = params.implements {
+        let Some(iface) = interfaces::find_interface(implements) else {
+            return ApiError::bad_request(
+                "UnknownInterface",
+                format!("Unknown interface '{}'", implements),
+            )
+            .into_response();
+        };
+        query.push_str(&format!(" AND '{}' = ANY(c.implements)", iface.id));
+        filters.push_str(&format!(" AND '{}' = ANY(implements)", iface.id));
+    }
+
+    if let Some(ref namespace) = params.namespace {
+        let namespace = namespace.trim_start_matches('@');
+        if let Err(e) = shared::validate_namespace(namespace) {
+            return ApiError::bad_request("InvalidNamespace", e).into_response();
+        }
+        query.push_str(&format!(" AND c.namespace = '{}'", namespace));
+        filters.push_str(&format!(" AND namespace = '{}'", namespace));
+    }
+
+    if params.audited == Some(true) {
+        let audited_clause =
+            " AND EXISTS (SELECT 1 FROM audit_attestations aa WHERE aa.contract_id = c.id)";
+        query.push_str(audited_clause);
+        filters.push_str(audited_clause);
+    }
+
+    for (column, after) in [
+        ("created_at", params.created_after),
+        ("updated_at", params.updated_after),
+    ] {
+        if let Some(after) = after {
+            let clause = format!(" AND c.{} >= '{}'", column, after.to_rfc3339());
+            query.push_str(&clause);
+            filters.push_str(&clause);
+        }
+    }
+
+    if let Some(min_downloads) = params.min_downloads {
+        if min_downloads < 0 {
+            return ApiError::bad_request("InvalidFilter", "min_downloads must not be negative")
+                .into_response();
+        }
+        // Downloads are recorded download interactions, as on publisher pages
+        let clause = format!(
+            " AND (SELECT COUNT(*) FROM contract_interactions di \
+               WHERE di.contract_id = c.id AND di.interaction_type = '{}') >= {}",
+            DOWNLOAD_INTERACTION, min_downloads
+        );
+        query.push_str(&clause);
+        filters.push_str(&clause);
+    }
+
+    if let Some(max_wasm_size) = params.max_wasm_size {
+        if max_wasm_size <= 0 {
+            return ApiError::bad_request("InvalidFilter", "max_wasm_size must be positive")
+                .into_response();
+        }
+        // Contracts without an uploaded WASM have no known size and are left out
+        let clause = format!(
+            " AND (SELECT OCTET_LENGTH(wa.content) FROM contract_versions wv \
+               JOIN contract_version_artifacts wa ON wa.version_id = wv.id AND wa.kind = 'wasm' \
+               WHERE wv.contract_id = c.id ORDER BY wv.created_at DESC LIMIT 1) <= {}",
+            max_wasm_size
+        );
+        query.push_str(&clause);
+        filters.push_str(&clause);
     }
 
     // Filter by network(s) (Issue #43)
@@ -303,7 +590,7 @@ pub async fn list_contracts(
         .as_ref()
         .filter(|n| !n.is_empty())
         .cloned()
-        .or_else(|| params.network.map(|n| vec![n]));
+        .or_else(|| params.network.clone().map(|n| vec![n]));
     if let Some(ref nets) = network_list {
         let net_list: Vec<String> = nets.iter().map(|n| n.to_string()).collect();
         let in_clause = net_list
@@ -313,11 +600,23 @@ pub async fn list_contracts(
             .join(", ");
         let network_clause = format!(" AND c.network IN ({})", in_clause);
         query.push_str(&network_clause);
-        count_query.push_str(&network_clause);
+        filters.push_str(&network_clause);
     }
 
     query.push_str(" GROUP BY c.id");
 
+    // Percentage rollouts key on the caller, or on the query for anonymous searches
+    let ranking_v2 = match params.query {
+        Some(ref q) if matches!(sort_by, shared::SortBy::Relevance) => {
+            let subject = viewer.address.as_deref().unwrap_or(q);
+            state
+                .flags
+                .is_enabled(&state.db, registry_flags::SEARCH_RANKING_V2, subject)
+                .await
+        }
+        _ => false,
+    };
+
     // Sorting logic using aggregations in ORDER BY
     let order_by = match sort_by {
         shared::SortBy::CreatedAt => "c.created_at".to_string(),
@@ -326,18 +625,21 @@ pub async fn list_contracts(
             "COUNT(DISTINCT ci.id)".to_string()
         }
         shared::SortBy::Deployments => "COUNT(DISTINCT cv.id)".to_string(),
-        shared::SortBy::Relevance => {
-            if let Some(ref q) = params.query {
-                format!(
-                    "CASE WHEN c.name ILIKE '{}' THEN 0 
-                          WHEN c.name ILIKE '%{}%' THEN 1 
-                          ELSE 2 END",
-                    q, q
-                )
-            } else {
-                "c.created_at".to_string()
-            }
-        }
+        shared::SortBy::Quality => "c.quality_score".to_string(),
+        shared::SortBy::Relevance => match params.query {
+            Some(ref q) if ranking_v2 => search_suggestions::ranked_relevance_score(q),
+            Some(ref q) => search_suggestions::relevance_score(q),
+            None => "c.created_at".to_string(),
+        },
+    };
+
+    // `?sort=` supersedes the older `sort_by`/`sort_order` pair
+    let (order_by, sort_order) = match params.sort {
+        Some(sort) => (
+            contract_sort_column(sort).to_string(),
+            params.order.clone().unwrap_or_else(|| sort.default_order()),
+        ),
+        None => (order_by, sort_order),
     };
 
     let direction = if sort_order == shared::SortOrder::Asc {
@@ -346,9 +648,10 @@ pub async fn list_contracts(
         "DESC"
     };
 
+    // Ties rank by quality, then id so pages stay stable when sort keys collide
     query.push_str(&format!(
-        " ORDER BY {} {}, c.id DESC LIMIT {} OFFSET {}",
-        order_by, direction, limit, offset
+        " ORDER BY {} {}, c.quality_score DESC, c.id {} LIMIT {} OFFSET {}",
+        order_by, direction, direction, limit, offset
     ));
 
     let contracts: Vec<Contract> = match sqlx::query_as(&query).fetch_all(&state.db).await {
@@ -356,14 +659,56 @@ pub async fn list_contracts(
         Err(err) => return db_internal_error("list contracts", err).into_response(),
     };
 
+    let count_query = format!("SELECT COUNT(*) FROM contracts c{}", filters);
     let total: i64 = match sqlx::query_scalar(&count_query).fetch_one(&state.db).await {
         Ok(v) => v,
         Err(err) => return db_internal_error("count filtered contracts", err).into_response(),
     };
 
+    let facets = if params.facets == Some(true) {
+        match fetch_search_facets(&state.db, &filters).await {
+            Ok(facets) => Some(facets),
+            Err(err) => return db_internal_error("count search facets", err).into_response(),
+        }
+    } else {
+        None
+    };
+
+    let mut highlights = match params.query.as_deref().map(str::trim) {
+        Some(q) if !q.is_empty() && !contracts.is_empty() => {
+            let ids: Vec<Uuid> = contracts.iter().map(|c| c.id).collect();
+            fetch_search_highlights(&state.db, q, &ids).await
+        }
+        _ => Default::default(),
+    };
+    let mut test_summaries = if contracts.is_empty() {
+        Default::default()
+    } else {
+        let ids: Vec<Uuid> = contracts.iter().map(|c| c.id).collect();
+        crate::test_results_handlers::latest_summaries(&state.db, &ids).await
+    };
+    let hits: Vec<ContractSearchHit> = contracts
+        .into_iter()
+        .map(|contract| ContractSearchHit {
+            highlight: highlights.remove(&contract.id),
+            tests: test_summaries.remove(&contract.id),
+            contract,
+        })
+        .collect();
+
+    let did_you_mean = match params.query.as_deref().map(str::trim) {
+        Some(q) if !q.is_empty() => search_suggestions::suggest(&state.db, &viewer, q).await,
+        _ => None,
+    };
+
     (
         StatusCode::OK,
-        Json(PaginatedResponse::new(contracts, total, page, limit)),
+        Json(ContractSearchResponse {
+            results: PaginatedResponse::new(hits, total, page, limit)
+                .with_filters(applied_search_filters(&params, network_list.as_deref())),
+            did_you_mean,
+            facets,
+        }),
     )
         .into_response()
 }
@@ -371,15 +716,34 @@ pub async fn list_contracts(
 /// Get a specific contract by ID. Optional ?network= returns network-specific config (Issue #43).
 pub async fn get_contract(
     State(state): State<AppState>,
+    viewer: Viewer,
     Path(id): Path<String>,
     Query(query): Query<GetContractQuery>,
-) -> ApiResult<Json<ContractGetResponse>> {
-    let contract_uuid = Uuid::parse_str(&id).map_err(|_| {
-        ApiError::bad_request(
-            "InvalidContractId",
-            format!("Invalid contract ID format: {}", id),
-        )
-    })?;
+) -> ApiResult<WithEtag<ContractGetResponse>> {
+    let mut alias_notice = None;
+    let contract_uuid = match Uuid::parse_str(&id) {
+        Ok(uuid) => uuid,
+        Err(_) => {
+            let name = shared::ContractName::parse(&id)
+                .map_err(|e| ApiError::bad_request("InvalidContractName", e))?;
+            let found = resolve_contract_name(&state, viewer.tenant_id, &name)
+                .await?
+                .ok_or_else(|| {
+                    ApiError::not_found(
+                        "ContractNotFound",
+                        format!("No contract found with name: {}", id),
+                    )
+                })?;
+            if let Some(current) = found.renamed_to {
+                alias_notice = Some(format!(
+                    "'{}' has been renamed to '{}'; the old name is deprecated",
+                    id, current
+                ));
+            }
+            found.id
+        }
+    };
+    ensure_contract_visible(&state, &viewer, contract_uuid).await?;
 
     let mut contract: Contract = sqlx::query_as("SELECT * FROM contracts WHERE id = $1")
         .bind(contract_uuid)
@@ -411,15 +775,23 @@ pub async fn get_contract(
         None
     };
 
-    Ok(Json(ContractGetResponse {
-        contract,
-        current_network,
-        network_config,
-    }))
+    let rating = crate::review_handlers::contract_rating(&state, contract.id).await?;
+
+    Ok(with_etag(
+        contract.updated_at,
+        ContractGetResponse {
+            contract,
+            current_network,
+            network_config,
+            alias_notice,
+            rating,
+        },
+    ))
 }
 
 pub async fn get_contract_versions(
     State(state): State<AppState>,
+    viewer: Viewer,
     Path(id): Path<String>,
 ) -> ApiResult<Json<Vec<ContractVersion>>> {
     let contract_uuid = Uuid::parse_str(&id).map_err(|_| {
@@ -428,6 +800,7 @@ pub async fn get_contract_versions(
             format!("Invalid contract ID format: {}", id),
         )
     })?;
+    ensure_contract_visible(&state, &viewer, contract_uuid).await?;
 
     let versions: Vec<ContractVersion> = sqlx::query_as(
         "SELECT * FROM contract_versions WHERE contract_id = $1 ORDER BY created_at DESC",
@@ -440,84 +813,15 @@ pub async fn get_contract_versions(
     Ok(Json(versions))
 }
 
-/// GET /api/contracts/:id/changelog (and /contracts/:id/changelog) — release history with breaking-change markers.
-pub async fn get_contract_changelog(
-    State(state): State<AppState>,
-    Path(id): Path<String>,
-) -> ApiResult<Json<ContractChangelogResponse>> {
-    let (contract_uuid, contract_id) = fetch_contract_identity(&state, &id).await?;
-
-    // Ascending order makes it easy to compute diffs against the previous version.
-    let versions: Vec<ContractVersion> = sqlx::query_as(
-        "SELECT * FROM contract_versions WHERE contract_id = $1 ORDER BY created_at ASC",
-    )
-    .bind(contract_uuid)
-    .fetch_all(&state.db)
-    .await
-    .map_err(|err| db_internal_error("get contract versions for changelog", err))?;
-
-    let mut entries: Vec<ContractChangelogEntry> = Vec::with_capacity(versions.len());
-
-    let mut prev_version: Option<String> = None;
-    for v in &versions {
-        let mut breaking = false;
-        let mut breaking_changes: Vec<String> = Vec::new();
-
-        if let Some(prev) = prev_version.as_deref() {
-            let old_selector = format!("{}@{}", contract_id, prev);
-            let new_selector = format!("{}@{}", contract_id, v.version);
-
-            let old_abi = resolve_abi(&state, &old_selector).await?;
-            let new_abi = resolve_abi(&state, &new_selector).await?;
-
-            let old_spec = crate::type_safety::parser::parse_json_spec(&old_abi, &old_selector)
-                .map_err(|e| {
-                    ApiError::bad_request("InvalidABI", format!("Failed to parse old ABI: {}", e))
-                })?;
-            let new_spec = crate::type_safety::parser::parse_json_spec(&new_abi, &new_selector)
-                .map_err(|e| {
-                    ApiError::bad_request("InvalidABI", format!("Failed to parse new ABI: {}", e))
-                })?;
-
-            let changes = diff_abi(&old_spec, &new_spec);
-            breaking = has_breaking_changes(&changes);
-            breaking_changes = changes
-                .into_iter()
-                .filter(|c| c.severity == crate::breaking_changes::ChangeSeverity::Breaking)
-                .map(|c| c.message)
-                .collect();
-        }
-
-        entries.push(ContractChangelogEntry {
-            version: v.version.clone(),
-            created_at: v.created_at,
-            commit_hash: v.commit_hash.clone(),
-            source_url: v.source_url.clone(),
-            release_notes: v.release_notes.clone(),
-            breaking,
-            breaking_changes,
-        });
-
-        prev_version = Some(v.version.clone());
-    }
-
-    // Most APIs return newest-first for timelines.
-    entries.reverse();
-
-    Ok(Json(ContractChangelogResponse {
-        contract_id: contract_uuid,
-        entries,
-    }))
-}
-
 pub async fn create_contract_version(
     State(state): State<AppState>,
+    tenant: CurrentTenant,
     Path(id): Path<String>,
     payload: Result<Json<CreateContractVersionRequest>, JsonRejection>,
 ) -> ApiResult<Json<ContractVersion>> {
     let Json(req) = payload.map_err(map_json_rejection)?;
 
-    let (contract_uuid, contract_id) = fetch_contract_identity(&state, &id).await?;
+    let (contract_uuid, contract_id) = fetch_contract_identity(&state, tenant.id, &id).await?;
     if !req.contract_id.trim().is_empty() && req.contract_id != contract_id {
         return Err(ApiError::bad_request(
             "ContractMismatch",
@@ -525,12 +829,60 @@ pub async fn create_contract_version(
         ));
     }
 
+    let version_row =
+        create_version_record(&state, contract_uuid, &contract_id, &req, &[], &[]).await?;
+    Ok(Json(version_row))
+}
+
+/// Reject an ABI that doesn't match `shared::abi_schema()`, listing every problem
+pub(crate) fn validate_abi_schema(abi: &Value) -> ApiResult<()> {
+    shared::validate_abi(abi).map_err(|problems| {
+        let message = match problems.as_slice() {
+            [problem] => format!("ABI is invalid: {} {}", problem.field, problem.message),
+            _ => format!("ABI is invalid: {} problems found", problems.len()),
+        };
+        ApiError::bad_request("InvalidABI", message).with_field_errors(problems)
+    })
+}
+
+/// A file stored alongside a version, e.g. the WASM or README from a publish session
+pub(crate) struct VersionArtifact<'a> {
+    pub kind: &'a str,
+    pub content: &'a [u8],
+    pub sha256: &'a str,
+}
+
+/// Validate and insert a contract version with its ABI, signature, provenance,
+/// `artifacts` and `benchmarks` in one transaction, so a failure leaves nothing
+/// behind.
+pub(crate) async fn create_version_record(
+    state: &AppState,
+    contract_uuid: Uuid,
+    contract_id: &str,
+    req: &CreateContractVersionRequest,
+    artifacts: &[VersionArtifact<'_>],
+    benchmarks: &[shared::BenchmarkMeasurement],
+) -> ApiResult<ContractVersion> {
+    let contract_id = contract_id.to_string();
+    if let Some(notes) = &req.release_notes {
+        if notes.len() > shared::MAX_RELEASE_NOTES_BYTES {
+            return Err(ApiError::bad_request(
+                "ReleaseNotesTooLarge",
+                format!(
+                    "release_notes is {} bytes; the limit is {}",
+                    notes.len(),
+                    shared::MAX_RELEASE_NOTES_BYTES
+                ),
+            ));
+        }
+    }
     let new_version = SemVer::parse(&req.version).ok_or_else(|| {
         ApiError::bad_request(
             "InvalidVersion",
             "Version must be valid semver (e.g. 1.2.3)",
         )
     })?;
+    validate_abi_schema(&req.abi)?;
 
     // Optional Ed25519 signature verification for this contract version.
     // When a signature is provided, we require a matching publisher_key and
@@ -640,7 +992,7 @@ pub async fn create_contract_version(
 
         if let Some(old_version) = latest_version {
             let old_selector = format!("{}@{}", contract_id, old_version);
-            let old_abi = resolve_abi(&state, &old_selector).await?;
+            let old_abi = resolve_abi(state, &old_selector).await?;
             let old_spec = crate::type_safety::parser::parse_json_spec(&old_abi, &contract_id)
                 .map_err(|e| {
                     ApiError::bad_request("InvalidABI", format!("Failed to parse old ABI: {}", e))
@@ -668,6 +1020,9 @@ pub async fn create_contract_version(
         }
     }
 
+    let artifact_bytes = artifacts.iter().map(|a| a.content.len()).sum();
+    crate::quota_handlers::ensure_version_quota(state, contract_uuid, artifact_bytes).await?;
+
     let mut tx = state
         .db
         .begin()
@@ -676,8 +1031,8 @@ pub async fn create_contract_version(
 
     let version_row: ContractVersion = sqlx::query_as(
         "INSERT INTO contract_versions \
-            (contract_id, version, wasm_hash, source_url, commit_hash, release_notes, signature, publisher_key, signature_algorithm) \
-         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9) \
+            (contract_id, version, wasm_hash, source_url, commit_hash, release_notes, signature, publisher_key, signature_algorithm, cargo_lock, build_toolchain) \
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11) \
          RETURNING *",
     )
     .bind(contract_uuid)
@@ -689,6 +1044,8 @@ pub async fn create_contract_version(
     .bind(&version_signature)
     .bind(&version_publisher_key)
     .bind(&version_algorithm)
+    .bind(&req.cargo_lock)
+    .bind(req.toolchain.as_ref().map(sqlx::types::Json))
     .fetch_one(&mut *tx)
     .await
     .map_err(|err| match err {
@@ -714,6 +1071,54 @@ pub async fn create_contract_version(
     .await
     .map_err(|err| db_internal_error("insert contract abi", err))?;
 
+    // Record which well-known interfaces the new spec implements
+    if let Ok(spec) = parse_json_spec(&req.abi.to_string(), &contract_id) {
+        sqlx::query("UPDATE contracts SET implements = $1 WHERE id = $2")
+            .bind(interfaces::detect_interfaces(&spec))
+            .bind(contract_uuid)
+            .execute(&mut *tx)
+            .await
+            .map_err(|err| db_internal_error("update contract interfaces", err))?;
+    }
+
+    if let Some(ref provenance) = req.provenance {
+        let (_, publisher_key) =
+            crate::provenance_handlers::fetch_publisher_key(&mut *tx, contract_uuid).await?;
+        crate::provenance_handlers::store_attestation(
+            &mut *tx,
+            contract_uuid,
+            &contract_id,
+            &req.version,
+            &req.wasm_hash,
+            provenance,
+            publisher_key,
+        )
+        .await?;
+    }
+
+    for artifact in artifacts {
+        sqlx::query(
+            "INSERT INTO contract_version_artifacts (version_id, kind, content, sha256) \
+             VALUES ($1, $2, $3, $4)",
+        )
+        .bind(version_row.id)
+        .bind(artifact.kind)
+        .bind(artifact.content)
+        .bind(artifact.sha256)
+        .execute(&mut *tx)
+        .await
+        .map_err(|err| db_internal_error("insert version artifact", err))?;
+        if artifact.kind == "tests" {
+            crate::test_results_handlers::store_results(&mut tx, version_row.id, artifact.content)
+                .await?;
+        }
+    }
+    crate::budget_handlers::store_measurements(&mut tx, version_row.id, benchmarks).await?;
+
+    crate::changelog_handlers::generate_changelog(&mut tx, &contract_id, version_row.id)
+        .await
+        .map_err(|err| db_internal_error("generate changelog", err))?;
+
     tx.commit()
         .await
         .map_err(|err| db_internal_error("commit contract version", err))?;
@@ -724,6 +1129,7 @@ pub async fn create_contract_version(
         .cache
         .invalidate_abi(&format!("{}@{}", contract_id, req.version))
         .await;
+    crate::sparse_index::spawn_refresh(state, contract_uuid);
 
     // Post-commit dependency analysis
     let detected_deps = dependency::detect_dependencies_from_abi(&req.abi);
@@ -755,15 +1161,114 @@ pub async fn create_contract_version(
     )
     .await;
 
-    Ok(Json(version_row))
+    Ok(version_row)
+}
+
+/// A contract found through one of its former names
+#[derive(Debug, sqlx::FromRow)]
+pub(crate) struct AliasedContract {
+    pub id: Uuid,
+    pub contract_id: String,
+    pub namespace: Option<String>,
+    pub name: String,
+}
+
+impl AliasedContract {
+    /// Current qualified name of the contract the alias points to
+    pub fn current_name(&self) -> String {
+        shared::ContractName {
+            namespace: self.namespace.clone(),
+            name: self.name.clone(),
+        }
+        .to_string()
+    }
+}
+
+/// Look up a renamed contract by a legacy name recorded in `contract_aliases`
+pub(crate) async fn resolve_contract_alias(
+    state: &AppState,
+    tenant_id: Uuid,
+    name: &shared::ContractName,
+) -> ApiResult<Option<AliasedContract>> {
+    let alias: Option<AliasedContract> = sqlx::query_as(
+        "SELECT c.id, c.contract_id, c.namespace, c.name \
+         FROM contract_aliases a JOIN contracts c ON c.id = a.contract_id \
+         WHERE a.namespace IS NOT DISTINCT FROM $1 AND a.name = $2 AND a.tenant_id = $3 \
+         ORDER BY a.created_at DESC LIMIT 1",
+    )
+    .bind(&name.namespace)
+    .bind(&name.name)
+    .bind(tenant_id)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|err| db_internal_error("resolve contract alias", err))?;
+
+    if let Some(ref found) = alias {
+        tracing::info!(
+            alias = %name,
+            current = %found.current_name(),
+            "resolved contract through deprecated alias"
+        );
+    }
+    Ok(alias)
+}
+
+/// A contract found by `@namespace/name` or plain name
+pub(crate) struct NamedContract {
+    pub id: Uuid,
+    pub contract_id: String,
+    /// Current name, when the contract was found through a former one
+    pub renamed_to: Option<String>,
+}
+
+/// Look a contract up by name, falling back to the names contracts had before
+/// a rename. Of several contracts with one name, the oldest wins.
+pub(crate) async fn resolve_contract_name(
+    state: &AppState,
+    tenant_id: Uuid,
+    name: &shared::ContractName,
+) -> ApiResult<Option<NamedContract>> {
+    let direct: Option<(Uuid, String)> = sqlx::query_as(
+        "SELECT id, contract_id FROM contracts \
+         WHERE namespace IS NOT DISTINCT FROM $1 AND name = $2 AND tenant_id = $3 \
+         ORDER BY created_at LIMIT 1",
+    )
+    .bind(&name.namespace)
+    .bind(&name.name)
+    .bind(tenant_id)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|err| db_internal_error("fetch contract by name", err))?;
+    if let Some((id, contract_id)) = direct {
+        return Ok(Some(NamedContract {
+            id,
+            contract_id,
+            renamed_to: None,
+        }));
+    }
+    Ok(resolve_contract_alias(state, tenant_id, name)
+        .await?
+        .map(|alias| NamedContract {
+            renamed_to: Some(alias.current_name()),
+            id: alias.id,
+            contract_id: alias.contract_id,
+        }))
 }
 
-async fn fetch_contract_identity(state: &AppState, id: &str) -> ApiResult<(Uuid, String)> {
+/// Resolve a registry UUID, on-chain contract ID, `@namespace/name` or plain
+/// name (current or former) to the contract's UUID and on-chain ID. Contracts
+/// of other tenants are not found.
+pub(crate) async fn fetch_contract_identity(
+    state: &AppState,
+    tenant_id: Uuid,
+    id: &str,
+) -> ApiResult<(Uuid, String)> {
     if let Ok(uuid) = Uuid::parse_str(id) {
         let row = sqlx::query_as::<_, (Uuid, String)>(
-            "SELECT id, contract_id FROM contracts WHERE id = $1",
+            "SELECT id, contract_id FROM contracts WHERE id = $1 AND tenant_id = $2",
         )
         .bind(uuid)
+        .bind(tenant_id)
         .fetch_optional(&state.db)
         .await
         .map_err(|err| db_internal_error("fetch contract", err))?;
@@ -776,12 +1281,23 @@ async fn fetch_contract_identity(state: &AppState, id: &str) -> ApiResult<(Uuid,
     }
 
     let row = sqlx::query_as::<_, (Uuid, String)>(
-        "SELECT id, contract_id FROM contracts WHERE contract_id = $1",
+        "SELECT id, contract_id FROM contracts WHERE contract_id = $1 AND tenant_id = $2",
     )
     .bind(id)
+    .bind(tenant_id)
     .fetch_optional(&state.db)
     .await
     .map_err(|err| db_internal_error("fetch contract", err))?;
+    let row = match row {
+        Some(row) => Some(row),
+        None => {
+            let name = shared::ContractName::parse(id)
+                .map_err(|e| ApiError::bad_request("InvalidContractName", e))?;
+            resolve_contract_name(state, tenant_id, &name)
+                .await?
+                .map(|found| (found.id, found.contract_id))
+        }
+    };
 
     row.ok_or_else(|| {
         ApiError::not_found(
@@ -791,16 +1307,143 @@ async fn fetch_contract_identity(state: &AppState, id: &str) -> ApiResult<(Uuid,
     })
 }
 
+/// Refusal for a publisher whose namespace is not `namespace` in this tenant
+fn namespace_mismatch(publisher: &Publisher, tenant_id: Uuid, namespace: &str) -> Option<ApiError> {
+    let owned = publisher.namespace.as_deref()?;
+    if owned == namespace && publisher.namespace_tenant_id == Some(tenant_id) {
+        return None;
+    }
+    let message = if owned == namespace {
+        format!(
+            "Publisher's namespace @{} belongs to another registry",
+            owned
+        )
+    } else {
+        format!(
+            "Publisher already owns namespace @{}; cannot publish into @{}",
+            owned, namespace
+        )
+    };
+    Some(ApiError::new(
+        StatusCode::FORBIDDEN,
+        "NamespaceNotOwned",
+        message,
+    ))
+}
+
+/// Namespaces belong to a wallet, so only a caller signed in as `publisher_address`
+/// may claim one or publish into it
+fn ensure_namespace_publisher(
+    user: Option<&crate::user_auth::AuthUser>,
+    publisher_address: &str,
+) -> ApiResult<()> {
+    match user {
+        None => Err(ApiError::new(
+            StatusCode::UNAUTHORIZED,
+            "Unauthorized",
+            "Sign in as the publisher to publish under a @namespace",
+        )),
+        Some(user) if user.address != publisher_address => Err(ApiError::new(
+            StatusCode::FORBIDDEN,
+            "NamespaceNotOwned",
+            "publisher_address must be the account you are signed in as",
+        )),
+        Some(_) => Ok(()),
+    }
+}
+
+/// Ensure `publisher` owns `namespace` in the tenant, claiming it if no one has yet
+async fn claim_namespace(
+    state: &AppState,
+    tenant_id: Uuid,
+    publisher: &Publisher,
+    namespace: &str,
+) -> ApiResult<()> {
+    if publisher.namespace.is_some() {
+        return match namespace_mismatch(publisher, tenant_id, namespace) {
+            Some(err) => Err(err),
+            None => Ok(()),
+        };
+    }
+
+    let claimed = sqlx::query(
+        "UPDATE publishers SET namespace = $1, namespace_tenant_id = $3 \
+         WHERE id = $2 AND namespace IS NULL \
+         AND NOT EXISTS (SELECT 1 FROM publishers \
+                         WHERE namespace = $1 AND namespace_tenant_id = $3)",
+    )
+    .bind(namespace)
+    .bind(publisher.id)
+    .bind(tenant_id)
+    .execute(&state.db)
+    .await
+    .map_err(|err| match err {
+        sqlx::Error::Database(ref e) if e.constraint() == Some("publishers_namespace_key") => {
+            ApiError::new(
+                StatusCode::FORBIDDEN,
+                "NamespaceNotOwned",
+                format!("Namespace @{} belongs to another publisher", namespace),
+            )
+        }
+        _ => db_internal_error("claim namespace", err),
+    })?;
+
+    if claimed.rows_affected() == 0 {
+        return Err(ApiError::new(
+            StatusCode::FORBIDDEN,
+            "NamespaceNotOwned",
+            format!("Namespace @{} belongs to another publisher", namespace),
+        ));
+    }
+    Ok(())
+}
+
 pub async fn publish_contract(
     State(state): State<AppState>,
+    tenant: CurrentTenant,
     headers: HeaderMap,
+    Query(params): Query<DryRunQuery>,
     payload: Result<Json<PublishRequest>, JsonRejection>,
-) -> ApiResult<Json<Contract>> {
+) -> ApiResult<Response> {
+    let user = crate::user_auth::authenticate(&state, &headers, &tenant).await?;
+    if let Some(ref user) = user {
+        user.require(shared::ApiScope::Publish)?;
+    }
     let Json(req) = payload.map_err(map_json_rejection)?;
 
     crate::validation::validate_contract_id(&req.contract_id)
         .map_err(|e| ApiError::bad_request("InvalidContractId", e))?;
 
+    if let Some(ref license) = req.license {
+        shared::validate_spdx_expression(license)
+            .map_err(|e| ApiError::bad_request("InvalidLicense", e))?;
+    }
+    req.urls
+        .validate()
+        .map_err(|(field, e)| ApiError::bad_request("InvalidUrl", format!("{} {}", field, e)))?;
+    let contract_name = shared::ContractName::parse(&req.name)
+        .map_err(|e| ApiError::bad_request("InvalidContractName", e))?;
+    if contract_name.is_scoped() {
+        ensure_namespace_publisher(user.as_ref(), &req.publisher_address)?;
+    }
+    let forked_from = match &req.forked_from {
+        Some(upstream) => Some(
+            crate::fork_handlers::resolve_fork_source(
+                &state,
+                upstream,
+                &req.publisher_address,
+                tenant.id,
+            )
+            .await?,
+        ),
+        None => None,
+    };
+
+    if params.dry_run {
+        let preview = preview_publish(&state, tenant.id, &req, &contract_name).await?;
+        return Ok(Json(preview).into_response());
+    }
+
     let publisher: Publisher = sqlx::query_as(
         "INSERT INTO publishers (stellar_address) VALUES ($1)
          ON CONFLICT (stellar_address) DO UPDATE SET stellar_address = EXCLUDED.stellar_address
@@ -811,6 +1454,19 @@ pub async fn publish_contract(
     .await
     .map_err(|err| db_internal_error("upsert publisher", err))?;
 
+    if let Some(ref namespace) = contract_name.namespace {
+        claim_namespace(&state, tenant.id, &publisher, namespace).await?;
+    }
+
+    crate::quota_handlers::ensure_contract_quota(
+        &state,
+        &crate::quota_handlers::QuotaAccount::Publisher {
+            id: publisher.id,
+            address: publisher.stellar_address.clone(),
+        },
+    )
+    .await?;
+
     let wasm_hash = "placeholder_hash".to_string();
     let network_key = req.network.to_string();
     let mut config_map = serde_json::Map::new();
@@ -826,13 +1482,14 @@ pub async fn publish_contract(
     let network_configs = serde_json::Value::Object(config_map);
 
     let contract: Contract = sqlx::query_as(
-        "INSERT INTO contracts (contract_id, wasm_hash, name, description, publisher_id, network, category, tags, logical_id, network_configs)
-         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+        "INSERT INTO contracts (contract_id, wasm_hash, name, description, publisher_id, network, category, tags, logical_id, network_configs, license, license_text, namespace,
+                                repository_url, homepage, documentation_url, forked_from, tenant_id)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18)
          RETURNING *"
     )
     .bind(&req.contract_id)
     .bind(&wasm_hash)
-    .bind(&req.name)
+    .bind(&contract_name.name)
     .bind(&req.description)
     .bind(publisher.id)
     .bind(&req.network)
@@ -840,19 +1497,23 @@ pub async fn publish_contract(
     .bind(&req.tags)
     .bind(Option::<Uuid>::None as Option<Uuid>)
     .bind(&network_configs)
+    .bind(&req.license)
+    .bind(&req.license_file)
+    .bind(&contract_name.namespace)
+    .bind(&req.urls.repository_url)
+    .bind(&req.urls.homepage)
+    .bind(&req.urls.documentation_url)
+    .bind(forked_from)
+    .bind(tenant.id)
     .fetch_one(&state.db)
     .await
     .map_err(|err| {
         if let sqlx::Error::Database(ref e) = err {
+            if e.constraint() == Some("idx_contracts_namespace_name_network") {
+                return contract_name_taken(&contract_name, &req.network);
+            }
             if e.constraint() == Some("contracts_contract_id_network_key") {
-                return ApiError::conflict(
-                    "ContractAlreadyRegistered",
-                    format!(
-                        "Contract {} is already registered for network {}",
-                        req.contract_id,
-                        req.network
-                    ),
-                );
+                return contract_already_registered(&req.contract_id, &req.network);
             }
         }
         db_internal_error("create contract", err)
@@ -921,7 +1582,116 @@ pub async fn publish_contract(
     )
     .await;
 
-    Ok(Json(contract))
+    tokio::spawn(crate::saved_search_handlers::notify_saved_searches(
+        state.clone(),
+        contract.clone(),
+    ));
+
+    Ok(Json(contract).into_response())
+}
+
+fn contract_name_taken(name: &shared::ContractName, network: &Network) -> ApiError {
+    ApiError::conflict(
+        "ContractNameTaken",
+        format!("{} is already registered for network {}", name, network),
+    )
+}
+
+fn contract_already_registered(contract_id: &str, network: &Network) -> ApiError {
+    ApiError::conflict(
+        "ContractAlreadyRegistered",
+        format!(
+            "Contract {} is already registered for network {}",
+            contract_id, network
+        ),
+    )
+}
+
+/// publish_contract's checks without its writes: namespace ownership, quota and
+/// uniqueness are read instead of claimed or inserted
+async fn preview_publish(
+    state: &AppState,
+    tenant_id: Uuid,
+    req: &PublishRequest,
+    contract_name: &shared::ContractName,
+) -> ApiResult<shared::PublishPreview> {
+    let publisher: Option<Publisher> =
+        sqlx::query_as("SELECT * FROM publishers WHERE stellar_address = $1")
+            .bind(&req.publisher_address)
+            .fetch_optional(&state.db)
+            .await
+            .map_err(|err| db_internal_error("fetch publisher", err))?;
+
+    let mut claims_namespace = false;
+    if let Some(ref namespace) = contract_name.namespace {
+        match publisher.as_ref().filter(|p| p.namespace.is_some()) {
+            Some(publisher) => {
+                if let Some(err) = namespace_mismatch(publisher, tenant_id, namespace) {
+                    return Err(err);
+                }
+            }
+            None => {
+                let taken: bool = sqlx::query_scalar(
+                    "SELECT EXISTS(SELECT 1 FROM publishers \
+                     WHERE namespace = $1 AND namespace_tenant_id = $2)",
+                )
+                .bind(namespace)
+                .bind(tenant_id)
+                .fetch_one(&state.db)
+                .await
+                .map_err(|err| db_internal_error("check namespace owner", err))?;
+                if taken {
+                    return Err(ApiError::new(
+                        StatusCode::FORBIDDEN,
+                        "NamespaceNotOwned",
+                        format!("Namespace @{} belongs to another publisher", namespace),
+                    ));
+                }
+                claims_namespace = true;
+            }
+        }
+    }
+
+    // A publisher that doesn't exist yet has no usage to count against its quota
+    if let Some(ref publisher) = publisher {
+        crate::quota_handlers::ensure_contract_quota(
+            state,
+            &crate::quota_handlers::QuotaAccount::Publisher {
+                id: publisher.id,
+                address: publisher.stellar_address.clone(),
+            },
+        )
+        .await?;
+    }
+
+    let (id_taken, name_taken): (bool, bool) = sqlx::query_as(
+        "SELECT \
+             EXISTS(SELECT 1 FROM contracts WHERE contract_id = $1 AND network = $2), \
+             EXISTS(SELECT 1 FROM contracts \
+                    WHERE namespace = $3 AND name = $4 AND network = $2)",
+    )
+    .bind(&req.contract_id)
+    .bind(&req.network)
+    .bind(&contract_name.namespace)
+    .bind(&contract_name.name)
+    .fetch_one(&state.db)
+    .await
+    .map_err(|err| db_internal_error("check contract uniqueness", err))?;
+    if id_taken {
+        return Err(contract_already_registered(&req.contract_id, &req.network));
+    }
+    if name_taken {
+        return Err(contract_name_taken(contract_name, &req.network));
+    }
+
+    Ok(shared::PublishPreview {
+        contract_id: req.contract_id.clone(),
+        name: contract_name.to_string(),
+        network: req.network.clone(),
+        publisher_address: req.publisher_address.clone(),
+        creates_publisher: publisher.is_none(),
+        claims_namespace,
+    })
 }
 
 pub async fn create_publisher(
@@ -984,10 +1754,34 @@ pub async fn get_publisher(
     Ok(Json(publisher))
 }
 
+/// Aggregate header for a publisher's contract listing
+fn publisher_contract_summary(
+    total_contracts: i64,
+    verified_contracts: i64,
+    total_downloads: i64,
+) -> PublisherContractSummary {
+    let verified_percentage = if total_contracts > 0 {
+        (verified_contracts as f64 * 1000.0 / total_contracts as f64).round() / 10.0
+    } else {
+        0.0
+    };
+    PublisherContractSummary {
+        total_contracts,
+        verified_contracts,
+        verified_percentage,
+        total_downloads,
+    }
+}
+
+/// GET /api/publishers/:id/contracts?page=&limit= — a page of the publisher's
+/// contracts with download totals, plus totals across all of them
 pub async fn get_publisher_contracts(
     State(state): State<AppState>,
+    viewer: Viewer,
     Path(id): Path<String>,
-) -> ApiResult<Json<Vec<Contract>>> {
+    query: Result<Query<PublisherContractsQuery>, QueryRejection>,
+) -> ApiResult<Json<PublisherContractsResponse>> {
+    let Query(query) = query.map_err(map_query_rejection)?;
     let publisher_uuid = Uuid::parse_str(&id).map_err(|_| {
         ApiError::bad_request(
             "InvalidPublisherId",
@@ -995,14 +1789,70 @@ pub async fn get_publisher_contracts(
         )
     })?;
 
-    let contracts: Vec<Contract> =
-        sqlx::query_as("SELECT * FROM contracts WHERE publisher_id = $1 ORDER BY created_at DESC")
+    let verified_account: Option<Option<String>> =
+        sqlx::query_scalar("SELECT verified_account FROM publishers WHERE id = $1")
             .bind(publisher_uuid)
-            .fetch_all(&state.db)
+            .fetch_optional(&state.db)
             .await
-            .map_err(|err| db_internal_error("get publisher contracts", err))?;
+            .map_err(|err| db_internal_error("check publisher exists", err))?;
+    let Some(verified_account) = verified_account else {
+        return Err(ApiError::not_found(
+            "PublisherNotFound",
+            format!("No publisher found with ID: {}", id),
+        ));
+    };
 
-    Ok(Json(contracts))
+    let page = query.page.unwrap_or(1).max(1);
+    let limit = query.limit.unwrap_or(20).clamp(1, 100);
+    let offset = (page - 1) * limit;
+    let visibility = viewer.visibility_clause("c.");
+
+    let (total_contracts, verified_contracts, total_downloads): (i64, i64, i64) =
+        sqlx::query_as(&format!(
+            "SELECT COUNT(*), \
+                    COUNT(*) FILTER (WHERE c.is_verified), \
+                    COALESCE(SUM(d.downloads), 0)::BIGINT \
+             FROM contracts c \
+             LEFT JOIN LATERAL ( \
+                 SELECT COUNT(*) AS downloads FROM contract_interactions ci \
+                 WHERE ci.contract_id = c.id AND ci.interaction_type = $2 \
+             ) d ON TRUE \
+             WHERE c.publisher_id = $1{}",
+            visibility
+        ))
+        .bind(publisher_uuid)
+        .bind(DOWNLOAD_INTERACTION)
+        .fetch_one(&state.db)
+        .await
+        .map_err(|err| db_internal_error("summarize publisher contracts", err))?;
+
+    let contracts: Vec<PublisherContract> = sqlx::query_as(&format!(
+        "SELECT c.*, COUNT(ci.id) AS downloads \
+         FROM contracts c \
+         LEFT JOIN contract_interactions ci \
+             ON ci.contract_id = c.id AND ci.interaction_type = $4 \
+         WHERE c.publisher_id = $1{} \
+         GROUP BY c.id \
+         ORDER BY c.created_at DESC, c.id DESC \
+         LIMIT $2 OFFSET $3",
+        visibility
+    ))
+    .bind(publisher_uuid)
+    .bind(limit)
+    .bind(offset)
+    .bind(DOWNLOAD_INTERACTION)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("get publisher contracts", err))?;
+
+    Ok(Json(PublisherContractsResponse {
+        publisher_id: publisher_uuid,
+        verified_account,
+        summary: publisher_contract_summary(total_contracts, verified_contracts, total_downloads),
+        contracts,
+        page,
+        pages: (total_contracts + limit - 1) / limit,
+    }))
 }
 
 /// Query for contract ABI and OpenAPI (optional version)
@@ -1011,12 +1861,15 @@ pub struct ContractAbiQuery {
     pub version: Option<String>,
 }
 
-/// Fetch ABI JSON string for contract (by id or id@version)
+/// Fetch ABI JSON string for contract (by id or id@version), if `viewer` may see it
 async fn resolve_contract_abi(
     state: &AppState,
+    viewer: &Viewer,
     id: &str,
     version: Option<&str>,
 ) -> ApiResult<String> {
+    let (contract_uuid, _) = fetch_contract_identity(state, viewer.tenant_id, id).await?;
+    ensure_contract_visible(state, viewer, contract_uuid).await?;
     let selector = match version {
         Some(v) => format!("{}@{}", id, v),
         None => id.to_string(),
@@ -1025,23 +1878,67 @@ async fn resolve_contract_abi(
 }
 
 // Contract ABI and OpenAPI endpoints
+/// Get the license expression and, when uploaded, the full license text
+pub async fn get_contract_license(
+    State(state): State<AppState>,
+    viewer: Viewer,
+    Path(id): Path<String>,
+) -> ApiResult<Json<ContractLicenseResponse>> {
+    let (contract_uuid, contract_id) =
+        fetch_contract_identity(&state, viewer.tenant_id, &id).await?;
+    ensure_contract_visible(&state, &viewer, contract_uuid).await?;
+
+    let (license, license_text): (Option<String>, Option<String>) =
+        sqlx::query_as("SELECT license, license_text FROM contracts WHERE id = $1")
+            .bind(contract_uuid)
+            .fetch_one(&state.db)
+            .await
+            .map_err(|err| db_internal_error("fetch contract license", err))?;
+
+    Ok(Json(ContractLicenseResponse {
+        contract_id,
+        license,
+        license_text,
+    }))
+}
+
 pub async fn get_contract_abi(
     State(state): State<AppState>,
+    viewer: Viewer,
     Path(id): Path<String>,
     Query(query): Query<ContractAbiQuery>,
 ) -> ApiResult<Json<Value>> {
-    let abi_json = resolve_contract_abi(&state, &id, query.version.as_deref()).await?;
+    let abi_json = resolve_contract_abi(&state, &viewer, &id, query.version.as_deref()).await?;
     let abi: Value = serde_json::from_str(&abi_json)
         .map_err(|e| ApiError::internal(format!("Invalid ABI JSON: {}", e)))?;
-    Ok(Json(json!({ "abi": abi })))
+    // Label functions belonging to well-known interfaces for wallets and explorers
+    let Ok(spec) = parse_json_spec(&abi_json, &id) else {
+        return Ok(Json(json!({ "abi": abi })));
+    };
+    Ok(Json(json!({
+        "abi": abi,
+        "implements": interfaces::detect_interfaces(&spec),
+        "function_interfaces": interfaces::function_interfaces(&spec),
+    })))
+}
+
+/// GET /api/schema/abi — the JSON schema uploaded ABIs are validated against,
+/// for tools that want to check a spec before publishing
+pub async fn get_abi_schema() -> Response {
+    (
+        [(header::CONTENT_TYPE, "application/schema+json")],
+        Json(shared::abi_schema()),
+    )
+        .into_response()
 }
 
 pub async fn get_contract_openapi_yaml(
     State(state): State<AppState>,
+    viewer: Viewer,
     Path(id): Path<String>,
     Query(query): Query<ContractAbiQuery>,
 ) -> ApiResult<Response> {
-    let abi_json = resolve_contract_abi(&state, &id, query.version.as_deref()).await?;
+    let abi_json = resolve_contract_abi(&state, &viewer, &id, query.version.as_deref()).await?;
     let abi = parse_json_spec(&abi_json, &id)
         .map_err(|e| ApiError::bad_request("InvalidABI", format!("Failed to parse ABI: {}", e)))?;
     let doc = generate_openapi(&abi, Some("/invoke"));
@@ -1055,10 +1952,11 @@ pub async fn get_contract_openapi_yaml(
 
 pub async fn get_contract_openapi_json(
     State(state): State<AppState>,
+    viewer: Viewer,
     Path(id): Path<String>,
     Query(query): Query<ContractAbiQuery>,
 ) -> ApiResult<Response> {
-    let abi_json = resolve_contract_abi(&state, &id, query.version.as_deref()).await?;
+    let abi_json = resolve_contract_abi(&state, &viewer, &id, query.version.as_deref()).await?;
     let abi = parse_json_spec(&abi_json, &id)
         .map_err(|e| ApiError::bad_request("InvalidABI", format!("Failed to parse ABI: {}", e)))?;
     let doc = generate_openapi(&abi, Some("/invoke"));
@@ -1178,10 +2076,11 @@ pub async fn get_trust_score() -> impl IntoResponse {
 
 pub async fn get_contract_dependencies(
     State(state): State<AppState>,
+    viewer: Viewer,
     Path(id): Path<String>,
 ) -> ApiResult<Json<Value>> {
-    let contract_uuid = Uuid::parse_str(&id)
-        .map_err(|_| ApiError::bad_request("InvalidContractId", format!("Invalid ID: {}", id)))?;
+    let (contract_uuid, _) = fetch_contract_identity(&state, viewer.tenant_id, &id).await?;
+    ensure_contract_visible(&state, &viewer, contract_uuid).await?;
 
     let deps: Vec<shared::ContractDependency> =
         sqlx::query_as("SELECT * FROM contract_dependencies WHERE contract_id = $1")
@@ -1384,21 +2283,69 @@ pub async fn verify_contract(
     Ok(Json(json!({
         "verified": true,
         "verification_id": verification_id,
+        "job_id": verification_id,
         "contract_id": contract.id
     })))
 }
 
+pub(crate) async fn fetch_contract_readme(
+    state: &AppState,
+    contract_uuid: Uuid,
+) -> ApiResult<Option<String>> {
+    sqlx::query_scalar("SELECT content FROM contract_readmes WHERE contract_id = $1")
+        .bind(contract_uuid)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(|err| db_internal_error("fetch contract readme", err))
+}
+
+/// GET /api/contracts/:id/readme — the contract-level README, falling back to
+/// the one staged with the latest version
+pub async fn get_contract_readme(
+    State(state): State<AppState>,
+    viewer: Viewer,
+    Path(id): Path<String>,
+) -> ApiResult<Json<Value>> {
+    let (contract_uuid, _) = fetch_contract_identity(&state, viewer.tenant_id, &id).await?;
+    ensure_contract_visible(&state, &viewer, contract_uuid).await?;
+    if let Some(content) = fetch_contract_readme(&state, contract_uuid).await? {
+        return Ok(Json(json!({ "content": content, "source": "contract" })));
+    }
+    let staged: Option<(Vec<u8>, String)> = sqlx::query_as(
+        "SELECT a.content, v.version FROM contract_version_artifacts a \
+         JOIN contract_versions v ON v.id = a.version_id \
+         WHERE v.contract_id = $1 AND a.kind = 'readme' \
+         ORDER BY v.created_at DESC LIMIT 1",
+    )
+    .bind(contract_uuid)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|err| db_internal_error("fetch version readme", err))?;
+    let (content, version) = staged.ok_or_else(|| {
+        ApiError::not_found("ReadmeNotFound", format!("Contract {} has no README", id))
+    })?;
+    Ok(Json(json!({
+        "content": String::from_utf8_lossy(&content),
+        "source": "version",
+        "version": version,
+    })))
+}
+
 pub async fn update_contract_metadata(
     State(state): State<AppState>,
+    tenant: CurrentTenant,
+    user: crate::user_auth::AuthUser,
     Path(id): Path<String>,
     headers: HeaderMap,
-    payload: Result<Json<UpdateContractMetadataRequest>, JsonRejection>,
-) -> ApiResult<Json<Contract>> {
-    let Json(req) = payload.map_err(map_json_rejection)?;
+    ValidatedJson(req): ValidatedJson<UpdateContractMetadataRequest>,
+) -> ApiResult<WithEtag<Contract>> {
+    user.require(shared::ApiScope::PatchAdmin)?;
     if req.name.is_none()
         && req.description.is_none()
         && req.category.is_none()
         && req.tags.is_none()
+        && req.links.is_none()
+        && req.readme.is_none()
     {
         return Err(ApiError::bad_request(
             "InvalidRequest",
@@ -1413,36 +2360,139 @@ pub async fn update_contract_metadata(
         )
     })?;
 
-    let before: Contract = sqlx::query_as("SELECT * FROM contracts WHERE id = $1")
-        .bind(contract_uuid)
-        .fetch_one(&state.db)
+    let before: Contract =
+        sqlx::query_as("SELECT * FROM contracts WHERE id = $1 AND tenant_id = $2")
+            .bind(contract_uuid)
+            .bind(tenant.id)
+            .fetch_one(&state.db)
+            .await
+            .map_err(|err| match err {
+                sqlx::Error::RowNotFound => ApiError::not_found(
+                    "ContractNotFound",
+                    format!("No contract found with ID: {}", id),
+                ),
+                _ => db_internal_error("fetch contract for metadata update", err),
+            })?;
+    crate::org_handlers::ensure_contract_maintainer(&state, contract_uuid, &user.address).await?;
+    let expected_updated_at = check_if_match(&headers, before.updated_at)?;
+    let readme_before = match req.readme {
+        Some(_) => fetch_contract_readme(&state, contract_uuid).await?,
+        None => None,
+    };
+
+    let new_name = match req.name.as_deref() {
+        Some(raw) => {
+            let parsed = shared::ContractName::parse(raw)
+                .map_err(|e| ApiError::bad_request("InvalidContractName", e))?;
+            if parsed.is_scoped() && parsed.namespace != before.namespace {
+                return Err(ApiError::bad_request(
+                    "NamespaceChangeNotAllowed",
+                    "Renaming cannot move a contract to another namespace",
+                ));
+            }
+            Some(parsed.name)
+        }
+        None => None,
+    };
+
+    let mut tx = state
+        .db
+        .begin()
         .await
-        .map_err(|err| match err {
-            sqlx::Error::RowNotFound => ApiError::not_found(
-                "ContractNotFound",
-                format!("No contract found with ID: {}", id),
-            ),
-            _ => db_internal_error("fetch contract for metadata update", err),
-        })?;
+        .map_err(|err| db_internal_error("begin metadata update", err))?;
 
-    let after: Contract = sqlx::query_as(
+    let after: Option<Contract> = sqlx::query_as(
         "UPDATE contracts
             SET name = COALESCE($2, name),
                 description = COALESCE($3, description),
                 category = COALESCE($4, category),
                 tags = COALESCE($5, tags),
+                links = COALESCE($7, links),
                 updated_at = NOW()
-          WHERE id = $1
+          WHERE id = $1 AND ($6::timestamptz IS NULL OR updated_at = $6)
           RETURNING *",
     )
     .bind(contract_uuid)
-    .bind(req.name.as_deref())
+    .bind(new_name.as_deref())
     .bind(req.description.as_deref())
     .bind(req.category.as_deref())
     .bind(req.tags.as_ref())
-    .fetch_one(&state.db)
+    .bind(expected_updated_at)
+    .bind(req.links.as_ref().map(|links| json!(links)))
+    .fetch_optional(&mut *tx)
     .await
-    .map_err(|err| db_internal_error("update contract metadata", err))?;
+    .map_err(|err| match err {
+        sqlx::Error::Database(ref e)
+            if e.constraint() == Some("idx_contracts_namespace_name_network") =>
+        {
+            ApiError::conflict(
+                "ContractNameTaken",
+                "Another contract in this namespace already uses that name",
+            )
+        }
+        _ => db_internal_error("update contract metadata", err),
+    })?;
+    let Some(after) = after else {
+        return Err(concurrent_modification(&state, contract_uuid).await);
+    };
+
+    // Keep the old name resolvable as a deprecated alias after a rename
+    if before.name != after.name {
+        sqlx::query(
+            "DELETE FROM contract_aliases \
+             WHERE namespace IS NOT DISTINCT FROM $1 AND name = $2 AND tenant_id = $3",
+        )
+        .bind(&after.namespace)
+        .bind(&after.name)
+        .bind(tenant.id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|err| db_internal_error("release reused contract alias", err))?;
+
+        sqlx::query(
+            "INSERT INTO contract_aliases (contract_id, namespace, name, tenant_id) \
+             VALUES ($1, $2, $3, $4) \
+             ON CONFLICT (tenant_id, (COALESCE(namespace, '')), name) \
+             DO UPDATE SET contract_id = EXCLUDED.contract_id, created_at = NOW()",
+        )
+        .bind(after.id)
+        .bind(&before.namespace)
+        .bind(&before.name)
+        .bind(tenant.id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|err| db_internal_error("record contract alias", err))?;
+    }
+
+    match req.readme.as_deref() {
+        Some("") => {
+            sqlx::query("DELETE FROM contract_readmes WHERE contract_id = $1")
+                .bind(contract_uuid)
+                .execute(&mut *tx)
+                .await
+                .map_err(|err| db_internal_error("remove contract readme", err))?;
+        }
+        Some(readme) => {
+            sqlx::query(
+                "INSERT INTO contract_readmes (contract_id, content) VALUES ($1, $2) \
+                 ON CONFLICT (contract_id) DO UPDATE \
+                     SET content = EXCLUDED.content, updated_at = NOW()",
+            )
+            .bind(contract_uuid)
+            .bind(readme)
+            .execute(&mut *tx)
+            .await
+            .map_err(|err| db_internal_error("update contract readme", err))?;
+        }
+        None => {}
+    }
+
+    tx.commit()
+        .await
+        .map_err(|err| db_internal_error("commit metadata update", err))?;
+    if before.name != after.name {
+        crate::sparse_index::spawn_refresh_renamed(&state, after.id, Some(before.name.clone()));
+    }
 
     let mut changes = serde_json::Map::new();
     if before.name != after.name {
@@ -1469,6 +2519,26 @@ pub async fn update_contract_metadata(
             json!({ "before": before.tags, "after": after.tags }),
         );
     }
+    if before.links != after.links {
+        changes.insert(
+            "links".to_string(),
+            json!({ "before": before.links, "after": after.links }),
+        );
+    }
+    if let Some(readme) = req.readme.as_deref() {
+        // The audit log keeps digests rather than copies of the README text
+        let digest = |text: Option<&str>| text.map(|t| hex::encode(Sha256::digest(t)));
+        let after_readme = Some(readme).filter(|r| !r.is_empty());
+        if readme_before.as_deref() != after_readme {
+            changes.insert(
+                "readme".to_string(),
+                json!({
+                    "before_sha256": digest(readme_before.as_deref()),
+                    "after_sha256": digest(after_readme),
+                }),
+            );
+        }
+    }
 
     if !changes.is_empty() {
         write_contract_audit_log(
@@ -1494,15 +2564,23 @@ pub async fn update_contract_metadata(
         .await;
     }
 
-    Ok(Json(after))
+    Ok(with_etag(after.updated_at, after))
 }
 
 pub async fn change_contract_publisher(
     State(state): State<AppState>,
+    tenant: CurrentTenant,
     Path(id): Path<String>,
     headers: HeaderMap,
     payload: Result<Json<ChangePublisherRequest>, JsonRejection>,
-) -> ApiResult<Json<Contract>> {
+) -> ApiResult<WithEtag<Contract>> {
+    crate::user_auth::check_optional_scope(
+        &state,
+        &headers,
+        &tenant,
+        shared::ApiScope::PatchAdmin,
+    )
+    .await?;
     let Json(req) = payload.map_err(map_json_rejection)?;
     let contract_uuid = Uuid::parse_str(&id).map_err(|_| {
         ApiError::bad_request(
@@ -1522,6 +2600,7 @@ pub async fn change_contract_publisher(
             ),
             _ => db_internal_error("fetch contract for publisher change", err),
         })?;
+    let expected_updated_at = check_if_match(&headers, before.updated_at)?;
 
     let old_publisher_address: String =
         sqlx::query_scalar("SELECT stellar_address FROM publishers WHERE id = $1")
@@ -1541,18 +2620,22 @@ pub async fn change_contract_publisher(
     .await
     .map_err(|err| db_internal_error("upsert new publisher", err))?;
 
-    let after: Contract = sqlx::query_as(
+    let after: Option<Contract> = sqlx::query_as(
         "UPDATE contracts
             SET publisher_id = $2,
                 updated_at = NOW()
-          WHERE id = $1
+          WHERE id = $1 AND ($3::timestamptz IS NULL OR updated_at = $3)
           RETURNING *",
     )
     .bind(contract_uuid)
     .bind(new_publisher.id)
-    .fetch_one(&state.db)
+    .bind(expected_updated_at)
+    .fetch_optional(&state.db)
     .await
     .map_err(|err| db_internal_error("update contract publisher", err))?;
+    let Some(after) = after else {
+        return Err(concurrent_modification(&state, contract_uuid).await);
+    };
 
     if before.publisher_id != after.publisher_id {
         let changes = json!({
@@ -1571,15 +2654,23 @@ pub async fn change_contract_publisher(
         .map_err(|err| db_internal_error("write publisher_changed audit log", err))?;
     }
 
-    Ok(Json(after))
+    Ok(with_etag(after.updated_at, after))
 }
 
 pub async fn update_contract_status(
     State(state): State<AppState>,
+    tenant: CurrentTenant,
     Path(id): Path<String>,
     headers: HeaderMap,
     payload: Result<Json<UpdateContractStatusRequest>, JsonRejection>,
-) -> ApiResult<Json<Value>> {
+) -> ApiResult<WithEtag<Value>> {
+    crate::user_auth::check_optional_scope(
+        &state,
+        &headers,
+        &tenant,
+        shared::ApiScope::PatchAdmin,
+    )
+    .await?;
     let Json(req) = payload.map_err(map_json_rejection)?;
     let normalized_status = req.status.to_ascii_lowercase();
     if normalized_status != "pending"
@@ -1610,6 +2701,7 @@ pub async fn update_contract_status(
             ),
             _ => db_internal_error("fetch contract for status update", err),
         })?;
+    let expected_updated_at = check_if_match(&headers, contract.updated_at)?;
 
     let previous_status: Option<String> = sqlx::query_scalar(
         "SELECT status::text FROM verifications WHERE contract_id = $1 ORDER BY created_at DESC LIMIT 1",
@@ -1626,6 +2718,22 @@ pub async fn update_contract_status(
     };
     let is_verified_after = normalized_status == "verified";
 
+    // Guard the contract row first so a stale If-Match leaves no verification behind
+    let updated_at: Option<chrono::DateTime<chrono::Utc>> = sqlx::query_scalar(
+        "UPDATE contracts SET is_verified = $2, updated_at = NOW() \
+         WHERE id = $1 AND ($3::timestamptz IS NULL OR updated_at = $3) \
+         RETURNING updated_at",
+    )
+    .bind(contract_uuid)
+    .bind(is_verified_after)
+    .bind(expected_updated_at)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|err| db_internal_error("update contract verification flag from status", err))?;
+    let Some(updated_at) = updated_at else {
+        return Err(concurrent_modification(&state, contract_uuid).await);
+    };
+
     let verification_id: Uuid = sqlx::query_scalar(
         "INSERT INTO verifications (contract_id, status, source_code, build_params, compiler_version, verified_at, error_message)
          VALUES ($1, $2::verification_status, NULL, NULL, NULL, $3, $4)
@@ -1639,13 +2747,6 @@ pub async fn update_contract_status(
     .await
     .map_err(|err| db_internal_error("insert status verification row", err))?;
 
-    sqlx::query("UPDATE contracts SET is_verified = $2, updated_at = NOW() WHERE id = $1")
-        .bind(contract_uuid)
-        .bind(is_verified_after)
-        .execute(&state.db)
-        .await
-        .map_err(|err| db_internal_error("update contract verification flag from status", err))?;
-
     let before_status = previous_status.unwrap_or_else(|| "pending".to_string());
     if before_status != normalized_status || contract.is_verified != is_verified_after {
         let changes = json!({
@@ -1665,12 +2766,15 @@ pub async fn update_contract_status(
         .map_err(|err| db_internal_error("write status_changed audit log", err))?;
     }
 
-    Ok(Json(json!({
-        "contract_id": contract_uuid,
-        "verification_id": verification_id,
-        "status": normalized_status,
-        "is_verified": is_verified_after
-    })))
+    Ok(with_etag(
+        updated_at,
+        json!({
+            "contract_id": contract_uuid,
+            "verification_id": verification_id,
+            "status": normalized_status,
+            "is_verified": is_verified_after
+        }),
+    ))
 }
 
 pub async fn get_contract_audit_log(
@@ -2023,4 +3127,62 @@ mod tests {
         let value = json.0;
         assert_eq!(value["status"], "shutting_down");
     }
+
+    #[test]
+    fn namespaces_need_the_signed_in_publisher() {
+        let user = crate::user_auth::AuthUser {
+            address: "GALICE".to_string(),
+            scopes: None,
+        };
+        assert!(ensure_namespace_publisher(Some(&user), "GALICE").is_ok());
+        let err = ensure_namespace_publisher(Some(&user), "GMALLORY").unwrap_err();
+        assert_eq!(err.into_response().status(), StatusCode::FORBIDDEN);
+        let err = ensure_namespace_publisher(None, "GALICE").unwrap_err();
+        assert_eq!(err.into_response().status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[test]
+    fn aliased_contract_reports_current_qualified_name() {
+        let aliased = AliasedContract {
+            id: Uuid::nil(),
+            contract_id: "CABC".to_string(),
+            namespace: Some("alice".to_string()),
+            name: "token-v2".to_string(),
+        };
+        assert_eq!(aliased.current_name(), "@alice/token-v2");
+    }
+
+    #[test]
+    fn publisher_summary_rounds_verified_share() {
+        let summary = publisher_contract_summary(3, 2, 41);
+        assert_eq!(summary.verified_percentage, 66.7);
+        assert_eq!(summary.total_downloads, 41);
+        assert_eq!(publisher_contract_summary(0, 0, 0).verified_percentage, 0.0);
+    }
+
+    #[test]
+    fn sort_keys_parse_and_default_direction() {
+        let params: ContractSearchParams =
+            serde_json::from_value(json!({ "sort": "name", "order": "desc" })).unwrap();
+        assert_eq!(params.sort, Some(shared::ContractSort::Name));
+        assert_eq!(params.order, Some(shared::SortOrder::Desc));
+        assert_eq!(
+            shared::ContractSort::Name.default_order(),
+            shared::SortOrder::Asc
+        );
+        assert_eq!(
+            shared::ContractSort::Downloads.default_order(),
+            shared::SortOrder::Desc
+        );
+        assert_eq!(
+            contract_sort_column(shared::ContractSort::Downloads),
+            "COUNT(DISTINCT ci.id)"
+        );
+        let params: ContractSearchParams =
+            serde_json::from_value(json!({ "sort": "stars" })).unwrap();
+        assert_eq!(contract_sort_column(params.sort.unwrap()), "c.star_count");
+        let params: ContractSearchParams =
+            serde_json::from_value(json!({ "sort": "quality" })).unwrap();
+        assert_eq!(contract_sort_column(params.sort.unwrap()), "c.quality_score");
+    }
 }
@@ -229,9 +229,21 @@ mod tests {
             tags: vec![],
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            health_score: 0,
             is_maintenance: false,
             logical_id: None,
             network_configs: None,
+            implements: vec![],
+            license: None,
+            namespace: None,
+            is_private: false,
+            organization_id: None,
+            links: serde_json::json!({}),
+            forked_from: None,
+            star_count: 0,
+            quality_score: 0.0,
+            moderation_status: Default::default(),
+            urls: Default::default(),
         }
     }
 
@@ -0,0 +1,270 @@
+// idempotency.rs
+// Replays the stored response for retried publish, patch and migration requests
+// that carry the same `Idempotency-Key`.
+
+use axum::{
+    body::{to_bytes, Body},
+    extract::State,
+    http::{header, HeaderName, HeaderValue, Method, Request, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use sha2::{Digest, Sha256};
+
+use crate::error::ApiError;
+use crate::handlers::db_internal_error;
+use crate::state::AppState;
+use crate::tenancy::CurrentTenant;
+
+pub const IDEMPOTENCY_KEY_HEADER: HeaderName = HeaderName::from_static("idempotency-key");
+/// Set on responses served from the key store rather than the handler
+pub const IDEMPOTENT_REPLAYED_HEADER: HeaderName = HeaderName::from_static("idempotent-replayed");
+
+const MAX_KEY_LENGTH: usize = 255;
+/// Largest request body buffered for hashing
+const MAX_BODY_BYTES: usize = 10 * 1024 * 1024;
+
+/// Mutating endpoints where a retry must not create a duplicate
+fn is_covered(method: &Method, path: &str) -> bool {
+    if method != Method::POST {
+        return false;
+    }
+    let segments: Vec<&str> = path.trim_end_matches('/').split('/').collect();
+    matches!(
+        segments.as_slice(),
+        ["", "api", "contracts"]
+            | ["", "api", "patches"]
+            | ["", "api", "patches", _, "apply"]
+            | ["", "api", "admin", "migrations", "register"]
+            | ["", "api", "admin", "migrations", _, "rollback"]
+    )
+}
+
+fn sha256_hex(parts: &[&[u8]]) -> String {
+    let mut hasher = Sha256::new();
+    for part in parts {
+        hasher.update(part);
+        hasher.update(b"\n");
+    }
+    hex::encode(hasher.finalize())
+}
+
+fn key_error(status: StatusCode, code: &str, message: &str) -> Response {
+    ApiError::new(status, code, message).into_response()
+}
+
+pub async fn idempotency_middleware(
+    State(state): State<AppState>,
+    request: Request<Body>,
+    next: Next,
+) -> Response {
+    if !is_covered(request.method(), request.uri().path()) {
+        return next.run(request).await;
+    }
+    let Some(key) = request.headers().get(&IDEMPOTENCY_KEY_HEADER) else {
+        return next.run(request).await;
+    };
+    let key = match key.to_str() {
+        Ok(key) if !key.is_empty() && key.len() <= MAX_KEY_LENGTH => key.to_string(),
+        _ => {
+            return key_error(
+                StatusCode::BAD_REQUEST,
+                "InvalidIdempotencyKey",
+                "Idempotency-Key must be 1-255 visible ASCII characters",
+            )
+        }
+    };
+
+    // Keys are per caller and per tenant, so registries never replay each other's writes
+    let tenant = CurrentTenant::of(request.extensions());
+    let caller_hash = sha256_hex(&[
+        request
+            .headers()
+            .get(header::AUTHORIZATION)
+            .map_or(&[][..], HeaderValue::as_bytes),
+        tenant.id.as_bytes(),
+    ]);
+
+    let (parts, body) = request.into_parts();
+    let body = match to_bytes(body, MAX_BODY_BYTES).await {
+        Ok(body) => body,
+        Err(_) => {
+            return key_error(
+                StatusCode::PAYLOAD_TOO_LARGE,
+                "PayloadTooLarge",
+                "Request body is too large",
+            )
+        }
+    };
+    let path = parts
+        .uri
+        .path_and_query()
+        .map_or(parts.uri.path(), |pq| pq.as_str());
+    let request_hash = sha256_hex(&[parts.method.as_str().as_bytes(), path.as_bytes(), &body]);
+
+    match claim(&state, &key, &caller_hash, &request_hash).await {
+        Ok(true) => {}
+        Ok(false) => return replay(&state, &key, &caller_hash, &request_hash).await,
+        Err(err) => return err.into_response(),
+    }
+
+    let response = next.run(Request::from_parts(parts, Body::from(body))).await;
+    record(&state, &key, &caller_hash, response).await
+}
+
+/// Take ownership of `key`, returning false when a live entry already holds it.
+/// Entries past the 24h window are overwritten as if they did not exist.
+async fn claim(
+    state: &AppState,
+    key: &str,
+    caller_hash: &str,
+    request_hash: &str,
+) -> Result<bool, ApiError> {
+    let claimed: Option<i32> = sqlx::query_scalar(
+        "INSERT INTO idempotency_keys (idempotency_key, caller_hash, request_hash) \
+         VALUES ($1, $2, $3) \
+         ON CONFLICT (idempotency_key, caller_hash) DO UPDATE \
+             SET request_hash = EXCLUDED.request_hash, status_code = NULL, \
+                 content_type = NULL, response_body = NULL, created_at = NOW() \
+             WHERE idempotency_keys.created_at < NOW() - INTERVAL '24 hours' \
+         RETURNING 1",
+    )
+    .bind(key)
+    .bind(caller_hash)
+    .bind(request_hash)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|err| db_internal_error("claim idempotency key", err))?;
+    Ok(claimed.is_some())
+}
+
+/// Request hash, then the status, content type and body once the request finished
+type StoredResponse = (String, Option<i16>, Option<String>, Option<Vec<u8>>);
+
+async fn replay(state: &AppState, key: &str, caller_hash: &str, request_hash: &str) -> Response {
+    let row: Option<StoredResponse> = match sqlx::query_as(
+        "SELECT request_hash, status_code, content_type, response_body \
+         FROM idempotency_keys WHERE idempotency_key = $1 AND caller_hash = $2",
+    )
+    .bind(key)
+    .bind(caller_hash)
+    .fetch_optional(&state.db)
+    .await
+    {
+        Ok(row) => row,
+        Err(err) => return db_internal_error("fetch idempotency key", err).into_response(),
+    };
+
+    match row {
+        Some((stored_hash, _, _, _)) if stored_hash != request_hash => key_error(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "IdempotencyKeyReused",
+            "This Idempotency-Key was already used for a different request",
+        ),
+        Some((_, Some(status), content_type, body)) => {
+            let mut response = Response::new(Body::from(body.unwrap_or_default()));
+            *response.status_mut() = StatusCode::from_u16(status as u16).unwrap_or(StatusCode::OK);
+            if let Some(value) = content_type.and_then(|ct| HeaderValue::from_str(&ct).ok()) {
+                response.headers_mut().insert(header::CONTENT_TYPE, value);
+            }
+            response
+                .headers_mut()
+                .insert(IDEMPOTENT_REPLAYED_HEADER, HeaderValue::from_static("true"));
+            response
+        }
+        // Still running, or released by a failure between our claim and this read
+        _ => key_error(
+            StatusCode::CONFLICT,
+            "IdempotencyKeyInProgress",
+            "A request with this Idempotency-Key is still being processed; retry shortly",
+        ),
+    }
+}
+
+/// Store the handler's response for replay. Server errors release the key so
+/// the client can retry the operation for real.
+async fn record(state: &AppState, key: &str, caller_hash: &str, response: Response) -> Response {
+    let status = response.status();
+    if status.is_server_error() {
+        let released = sqlx::query(
+            "DELETE FROM idempotency_keys WHERE idempotency_key = $1 AND caller_hash = $2",
+        )
+        .bind(key)
+        .bind(caller_hash)
+        .execute(&state.db)
+        .await;
+        if let Err(err) = released {
+            tracing::warn!(error = %err, "failed to release idempotency key");
+        }
+        return response;
+    }
+
+    let (parts, body) = response.into_parts();
+    let body = match to_bytes(body, usize::MAX).await {
+        Ok(body) => body,
+        Err(err) => {
+            tracing::error!(error = %err, "failed to buffer response for idempotency key");
+            return ApiError::internal("Failed to read response").into_response();
+        }
+    };
+    let content_type = parts
+        .headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok());
+
+    let stored = sqlx::query(
+        "UPDATE idempotency_keys \
+         SET status_code = $3, content_type = $4, response_body = $5 \
+         WHERE idempotency_key = $1 AND caller_hash = $2",
+    )
+    .bind(key)
+    .bind(caller_hash)
+    .bind(status.as_u16() as i16)
+    .bind(content_type)
+    .bind(body.as_ref())
+    .execute(&state.db)
+    .await;
+    if let Err(err) = stored {
+        tracing::warn!(error = %err, "failed to store idempotent response");
+    }
+
+    Response::from_parts(parts, Body::from(body))
+}
+
+/// Drop entries older than the 24h replay window
+pub async fn purge_expired(pool: &sqlx::PgPool) -> Result<u64, sqlx::Error> {
+    Ok(
+        sqlx::query("DELETE FROM idempotency_keys WHERE created_at < NOW() - INTERVAL '24 hours'")
+            .execute(pool)
+            .await?
+            .rows_affected(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_publish_patch_and_migration_writes_are_covered() {
+        assert!(is_covered(&Method::POST, "/api/contracts"));
+        assert!(is_covered(&Method::POST, "/api/patches"));
+        assert!(is_covered(&Method::POST, "/api/patches/abc/apply"));
+        assert!(is_covered(&Method::POST, "/api/admin/migrations/register"));
+        assert!(is_covered(
+            &Method::POST,
+            "/api/admin/migrations/42/rollback"
+        ));
+        assert!(!is_covered(&Method::GET, "/api/contracts"));
+        assert!(!is_covered(&Method::POST, "/api/patches/abc/approve"));
+        assert!(!is_covered(&Method::POST, "/api/contracts/abc/reviews"));
+    }
+
+    #[test]
+    fn request_hash_covers_every_part() {
+        let base = sha256_hex(&[b"POST", b"/api/patches", b"{}"]);
+        assert_eq!(base, sha256_hex(&[b"POST", b"/api/patches", b"{}"]));
+        assert_ne!(base, sha256_hex(&[b"POST", b"/api/patches", b"{ }"]));
+        assert_ne!(base, sha256_hex(&[b"POST", b"/api/patches/", b"{}"]));
+    }
+}
@@ -0,0 +1,187 @@
+// interfaces.rs
+// Well-known contract interface fingerprints (SEP-41 token, etc.).
+//
+// A fingerprint is the set of functions (name + arity) an interface requires.
+// Specs are matched at publish time and the resulting ids are stored on the
+// contract row so `/api/contracts?implements=sep-41` can filter cheaply.
+
+use std::collections::BTreeMap;
+
+use axum::Json;
+use serde::Serialize;
+
+use crate::type_safety::types::ContractABI;
+
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct RequiredFunction {
+    pub name: &'static str,
+    pub arity: usize,
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct InterfaceFingerprint {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub description: &'static str,
+    pub functions: &'static [RequiredFunction],
+}
+
+const fn func(name: &'static str, arity: usize) -> RequiredFunction {
+    RequiredFunction { name, arity }
+}
+
+pub const KNOWN_INTERFACES: &[InterfaceFingerprint] = &[
+    InterfaceFingerprint {
+        id: "sep-41",
+        name: "SEP-41 Token",
+        description: "Standard fungible token interface",
+        functions: &[
+            func("allowance", 2),
+            func("approve", 4),
+            func("balance", 1),
+            func("transfer", 3),
+            func("transfer_from", 4),
+            func("burn", 2),
+            func("burn_from", 3),
+            func("decimals", 0),
+            func("name", 0),
+            func("symbol", 0),
+        ],
+    },
+    InterfaceFingerprint {
+        id: "sep-40",
+        name: "SEP-40 Price Oracle",
+        description: "Price feed interface for on-chain oracles",
+        functions: &[
+            func("base", 0),
+            func("assets", 0),
+            func("decimals", 0),
+            func("resolution", 0),
+            func("price", 2),
+            func("prices", 2),
+            func("lastprice", 1),
+        ],
+    },
+    InterfaceFingerprint {
+        id: "sac-admin",
+        name: "Stellar Asset Contract Admin",
+        description: "Administrative extension exposed by the Stellar Asset Contract",
+        functions: &[
+            func("set_admin", 1),
+            func("admin", 0),
+            func("set_authorized", 2),
+            func("authorized", 1),
+            func("mint", 2),
+            func("clawback", 2),
+        ],
+    },
+];
+
+/// Look up an interface by id, ignoring case and dashes (`SEP41` finds `sep-41`)
+pub fn find_interface(id: &str) -> Option<&'static InterfaceFingerprint> {
+    let normalize = |s: &str| s.replace('-', "").to_ascii_lowercase();
+    let wanted = normalize(id);
+    KNOWN_INTERFACES
+        .iter()
+        .find(|iface| normalize(iface.id) == wanted)
+}
+
+impl InterfaceFingerprint {
+    pub fn is_implemented_by(&self, abi: &ContractABI) -> bool {
+        self.functions.iter().all(|required| {
+            abi.functions
+                .iter()
+                .any(|f| f.name == required.name && f.params.len() == required.arity)
+        })
+    }
+}
+
+/// Returns the ids of every known interface the spec fully implements.
+pub fn detect_interfaces(abi: &ContractABI) -> Vec<String> {
+    KNOWN_INTERFACES
+        .iter()
+        .filter(|iface| iface.is_implemented_by(abi))
+        .map(|iface| iface.id.to_string())
+        .collect()
+}
+
+/// The interfaces each function belongs to, for the ABI viewer. Only
+/// interfaces the spec fully implements are labelled.
+pub fn function_interfaces(abi: &ContractABI) -> BTreeMap<String, Vec<&'static str>> {
+    let mut labels: BTreeMap<String, Vec<&'static str>> = BTreeMap::new();
+    for iface in KNOWN_INTERFACES
+        .iter()
+        .filter(|iface| iface.is_implemented_by(abi))
+    {
+        for required in iface.functions {
+            labels
+                .entry(required.name.to_string())
+                .or_default()
+                .push(iface.id);
+        }
+    }
+    labels
+}
+
+/// GET /api/interfaces
+pub async fn list_interfaces() -> Json<&'static [InterfaceFingerprint]> {
+    Json(KNOWN_INTERFACES)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::type_safety::types::{
+        ContractFunction, FunctionParam, FunctionVisibility, SorobanType,
+    };
+
+    fn abi_with(functions: &[RequiredFunction]) -> ContractABI {
+        let mut abi = ContractABI::new("Token".to_string());
+        for required in functions {
+            abi.functions.push(ContractFunction {
+                name: required.name.to_string(),
+                visibility: FunctionVisibility::Public,
+                params: (0..required.arity)
+                    .map(|i| FunctionParam {
+                        name: format!("arg{}", i),
+                        param_type: SorobanType::Address,
+                        doc: None,
+                    })
+                    .collect(),
+                return_type: SorobanType::Void,
+                doc: None,
+                is_mutable: true,
+            });
+        }
+        abi
+    }
+
+    #[test]
+    fn detects_sep41_token() {
+        let sep41 = find_interface("sep41").unwrap();
+        let abi = abi_with(sep41.functions);
+        assert_eq!(detect_interfaces(&abi), vec!["sep-41".to_string()]);
+    }
+
+    #[test]
+    fn partial_token_is_not_sep41() {
+        let sep41 = find_interface("SEP41").unwrap();
+        let abi = abi_with(&sep41.functions[..3]);
+        assert!(detect_interfaces(&abi).is_empty());
+    }
+
+    #[test]
+    fn labels_functions_of_implemented_interfaces() {
+        let oracle = find_interface("SEP-40").unwrap();
+        let mut functions = oracle.functions.to_vec();
+        functions.push(func("upgrade", 1));
+        let labels = function_interfaces(&abi_with(&functions));
+        assert_eq!(labels["lastprice"], vec!["sep-40"]);
+        // `decimals` is shared with SEP-41, which this spec does not implement
+        assert_eq!(labels["decimals"], vec!["sep-40"]);
+        assert!(!labels.contains_key("upgrade"));
+
+        let partial = abi_with(&oracle.functions[..2]);
+        assert!(function_interfaces(&partial).is_empty());
+    }
+}
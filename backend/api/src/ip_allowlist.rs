@@ -0,0 +1,248 @@
+// ip_allowlist.rs
+// CIDR allowlist for /api/admin (`[admin] allowed_cidrs`). The client address is
+// the TCP peer; X-Forwarded-For is only believed when that peer is one of
+// `trusted_proxies`, so the header can't be used to talk past the list. The
+// same address is handed to handlers as [`ClientIp`].
+
+use std::convert::Infallible;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use axum::{
+    body::Body,
+    extract::{connect_info::ConnectInfo, FromRequestParts, State},
+    http::{request::Parts, HeaderMap, Request, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use ipnet::IpNet;
+
+use crate::error::ApiError;
+use crate::settings::AdminSettings;
+
+/// Paths guarded by the allowlist
+const ADMIN_PATH_PREFIX: &str = "/api/admin";
+
+/// A CIDR range, or a single address standing for its host-only range
+pub fn parse_network(raw: &str) -> Result<IpNet, String> {
+    let raw = raw.trim();
+    raw.parse::<IpNet>()
+        .or_else(|_| raw.parse::<IpAddr>().map(IpNet::from))
+        .map_err(|_| format!("'{}' is not an IP address or CIDR range", raw))
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct AdminAllowlist {
+    inner: Arc<Ranges>,
+}
+
+#[derive(Debug, Default)]
+struct Ranges {
+    /// Empty allows every address
+    allowed: Vec<IpNet>,
+    trusted_proxies: Vec<IpNet>,
+}
+
+impl AdminAllowlist {
+    /// Entries are checked by `Settings::validate`, so unparsable ones are skipped
+    pub fn from_settings(settings: &AdminSettings) -> Self {
+        let parse = |entries: &[String]| -> Vec<IpNet> {
+            entries
+                .iter()
+                .filter_map(|entry| parse_network(entry).ok())
+                .collect()
+        };
+        Self {
+            inner: Arc::new(Ranges {
+                allowed: parse(&settings.allowed_cidrs),
+                trusted_proxies: parse(&settings.trusted_proxies),
+            }),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        !self.inner.allowed.is_empty()
+    }
+
+    fn allows(&self, ip: IpAddr) -> bool {
+        let ip = canonical(ip);
+        self.inner.allowed.iter().any(|net| net.contains(&ip))
+    }
+
+    fn is_trusted_proxy(&self, ip: IpAddr) -> bool {
+        let ip = canonical(ip);
+        self.inner
+            .trusted_proxies
+            .iter()
+            .any(|net| net.contains(&ip))
+    }
+
+    /// The TCP peer, or when it is a trusted proxy the right-most
+    /// X-Forwarded-For hop that isn't one
+    fn client_ip(&self, peer: IpAddr, headers: &HeaderMap) -> Option<IpAddr> {
+        if !self.is_trusted_proxy(peer) {
+            return Some(peer);
+        }
+        let forwarded: Vec<&str> = headers
+            .get_all("x-forwarded-for")
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .map(str::trim)
+            .collect();
+        for hop in forwarded.iter().rev() {
+            let ip = hop.parse::<IpAddr>().ok()?;
+            if !self.is_trusted_proxy(ip) {
+                return Some(ip);
+            }
+        }
+        Some(peer)
+    }
+}
+
+/// Client address of a request, worked out by [`admin_allowlist_middleware`]
+/// the same way as for the allowlist; None when the peer is unknown
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClientIp(pub Option<IpAddr>);
+
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for ClientIp {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Infallible> {
+        Ok(parts
+            .extensions
+            .get::<ClientIp>()
+            .copied()
+            .unwrap_or(ClientIp(None)))
+    }
+}
+
+/// IPv4 clients reaching a dual-stack socket show up as `::ffff:a.b.c.d`
+fn canonical(ip: IpAddr) -> IpAddr {
+    match ip {
+        IpAddr::V6(v6) => v6.to_ipv4_mapped().map(IpAddr::V4).unwrap_or(ip),
+        v4 => v4,
+    }
+}
+
+/// Record the [`ClientIp`] of every request, and turn away admin requests
+/// from addresses outside `[admin] allowed_cidrs` before the admin token is
+/// even looked at
+pub async fn admin_allowlist_middleware(
+    State(allowlist): State<AdminAllowlist>,
+    mut request: Request<Body>,
+    next: Next,
+) -> Response {
+    let client = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .and_then(|ConnectInfo(peer)| allowlist.client_ip(peer.ip(), request.headers()));
+    request.extensions_mut().insert(ClientIp(client));
+
+    if !allowlist.is_enabled() || !request.uri().path().starts_with(ADMIN_PATH_PREFIX) {
+        return next.run(request).await;
+    }
+
+    match client {
+        Some(ip) if allowlist.allows(ip) => next.run(request).await,
+        client => {
+            tracing::warn!(client = ?client, path = %request.uri().path(), "Admin request from a disallowed address");
+            ApiError::new(
+                StatusCode::FORBIDDEN,
+                "Forbidden",
+                "Admin endpoints are not reachable from this address",
+            )
+            .into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn allowlist(allowed: &[&str], proxies: &[&str]) -> AdminAllowlist {
+        AdminAllowlist::from_settings(&AdminSettings {
+            allowed_cidrs: allowed.iter().map(|s| s.to_string()).collect(),
+            trusted_proxies: proxies.iter().map(|s| s.to_string()).collect(),
+        })
+    }
+
+    fn ip(raw: &str) -> IpAddr {
+        raw.parse().unwrap()
+    }
+
+    #[test]
+    fn matches_ranges_and_single_addresses() {
+        let list = allowlist(&["10.0.0.0/8", "192.0.2.7", "2001:db8::/32"], &[]);
+        assert!(list.allows(ip("10.20.30.40")));
+        assert!(list.allows(ip("192.0.2.7")));
+        assert!(!list.allows(ip("192.0.2.8")));
+        assert!(list.allows(ip("2001:db8::1")));
+        assert!(list.allows(ip("::ffff:10.1.2.3")));
+        assert!(parse_network("10.0.0.0/33").is_err());
+    }
+
+    #[test]
+    fn forwarded_for_is_only_believed_from_trusted_proxies() {
+        let list = allowlist(&["10.0.0.0/8"], &["172.16.0.0/12"]);
+        let mut headers = HeaderMap::new();
+        headers.insert("x-forwarded-for", "10.1.1.1, 203.0.113.9".parse().unwrap());
+
+        // A direct client can't claim an allowed address
+        assert_eq!(
+            list.client_ip(ip("203.0.113.50"), &headers),
+            Some(ip("203.0.113.50"))
+        );
+        // Through the proxy, the hop the proxy saw is the client
+        assert_eq!(
+            list.client_ip(ip("172.16.0.2"), &headers),
+            Some(ip("203.0.113.9"))
+        );
+
+        headers.insert("x-forwarded-for", "10.1.1.1, 172.16.0.3".parse().unwrap());
+        assert_eq!(
+            list.client_ip(ip("172.16.0.2"), &headers),
+            Some(ip("10.1.1.1"))
+        );
+    }
+
+    #[tokio::test]
+    async fn handlers_see_the_client_behind_trusted_proxies_only() {
+        use axum::{middleware, routing::get, Router};
+        use tower::ServiceExt;
+
+        let app = Router::new()
+            .route(
+                "/api/contracts",
+                get(|ClientIp(client): ClientIp| async move { format!("{:?}", client) }),
+            )
+            .layer(middleware::from_fn_with_state(
+                allowlist(&[], &["172.16.0.0/12"]),
+                admin_allowlist_middleware,
+            ));
+        let client_of = |peer: &str| {
+            let mut request = Request::builder()
+                .uri("/api/contracts")
+                .header("x-forwarded-for", "198.51.100.1, 203.0.113.9")
+                .body(Body::empty())
+                .unwrap();
+            request
+                .extensions_mut()
+                .insert(ConnectInfo(SocketAddr::new(ip(peer), 4000)));
+            let app = app.clone();
+            async move {
+                let response = app.oneshot(request).await.unwrap();
+                let body = axum::body::to_bytes(response.into_body(), 1024)
+                    .await
+                    .unwrap();
+                String::from_utf8(body.to_vec()).unwrap()
+            }
+        };
+
+        assert_eq!(client_of("203.0.113.50").await, "Some(203.0.113.50)");
+        assert_eq!(client_of("172.16.0.2").await, "Some(203.0.113.9)");
+    }
+}
@@ -0,0 +1,115 @@
+// job_handlers.rs
+// One status endpoint for work that outlives its request, so clients poll
+// verifications and reindexes the same way: GET /api/jobs/:id until the state
+// is `succeeded` or `failed`.
+
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use chrono::{DateTime, Utc};
+use shared::{JobKind, JobState, JobStatus, ReindexState, ReindexStatus};
+use uuid::Uuid;
+
+use crate::error::{ApiError, ApiResult};
+use crate::handlers::db_internal_error;
+use crate::private_registry::{ensure_contract_visible, Viewer};
+use crate::state::AppState;
+
+#[derive(sqlx::FromRow)]
+struct VerificationJob {
+    id: Uuid,
+    contract_uuid: Uuid,
+    contract_id: String,
+    status: String,
+    error_message: Option<String>,
+    created_at: DateTime<Utc>,
+    verified_at: Option<DateTime<Utc>>,
+}
+
+fn verification_state(status: &str) -> JobState {
+    match status {
+        "verified" => JobState::Succeeded,
+        "failed" => JobState::Failed,
+        _ => JobState::Queued,
+    }
+}
+
+impl From<VerificationJob> for JobStatus {
+    fn from(job: VerificationJob) -> Self {
+        let state = verification_state(&job.status);
+        JobStatus {
+            id: job.id,
+            kind: JobKind::Verification,
+            state,
+            subject: Some(job.contract_id),
+            error: job.error_message,
+            created_at: job.created_at,
+            finished_at: if state.is_finished() {
+                job.verified_at
+            } else {
+                None
+            },
+        }
+    }
+}
+
+fn reindex_job(reindex: ReindexStatus) -> JobStatus {
+    JobStatus {
+        id: reindex.id,
+        kind: JobKind::Reindex,
+        state: match reindex.state {
+            ReindexState::Running => JobState::Running,
+            ReindexState::Completed => JobState::Succeeded,
+            ReindexState::Failed => JobState::Failed,
+        },
+        subject: None,
+        error: reindex.error,
+        created_at: reindex.started_at,
+        finished_at: reindex.finished_at,
+    }
+}
+
+/// GET /api/jobs/:id — state of a verification or reindex started earlier
+pub async fn get_job(
+    State(state): State<AppState>,
+    viewer: Viewer,
+    Path(id): Path<Uuid>,
+) -> ApiResult<Json<JobStatus>> {
+    let verification: Option<VerificationJob> = sqlx::query_as(
+        "SELECT v.id, v.contract_id AS contract_uuid, c.contract_id, v.status::text AS status,
+                v.error_message, v.created_at, v.verified_at
+         FROM verifications v
+         JOIN contracts c ON c.id = v.contract_id
+         WHERE v.id = $1",
+    )
+    .bind(id)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|err| db_internal_error("fetch verification job", err))?;
+    if let Some(job) = verification {
+        ensure_contract_visible(&state, &viewer, job.contract_uuid).await?;
+        return Ok(Json(job.into()));
+    }
+
+    match state.reindexer.status() {
+        Some(reindex) if reindex.id == id => Ok(Json(reindex_job(reindex))),
+        _ => Err(ApiError::not_found(
+            "JobNotFound",
+            format!("No job found with id: {}", id),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verification_statuses_map_to_job_states() {
+        assert_eq!(verification_state("pending"), JobState::Queued);
+        assert_eq!(verification_state("verified"), JobState::Succeeded);
+        assert_eq!(verification_state("failed"), JobState::Failed);
+        assert!(!JobState::Queued.is_finished());
+    }
+}
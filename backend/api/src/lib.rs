@@ -1,13 +1,112 @@
 #![allow(dead_code, unused)]
 
+pub mod abuse_report_handlers;
+pub mod account_handlers;
+pub mod activity_feed_handlers;
+pub mod activity_feed_routes;
+pub mod admin_auth;
+pub mod admin_handlers;
+pub mod aggregation;
+pub mod alert_handlers;
+pub mod analytics;
+pub mod api_key_handlers;
+pub mod api_usage;
+pub mod app;
+pub mod archive_handlers;
+pub mod artifact_gc;
+pub mod artifact_handlers;
+pub mod audit_attestation_handlers;
+pub mod auth;
+pub mod auth_handlers;
+pub mod backfill;
 pub mod backup_handlers;
 pub mod backup_routes;
+pub mod badge_handlers;
+pub mod batch_handlers;
+pub mod body_limit;
+pub mod breaking_changes;
+pub mod budget_handlers;
 pub mod cache;
+pub mod challenge;
+pub mod changelog_handlers;
+pub mod cli_release_handlers;
+pub mod client_version;
+pub mod collection_handlers;
+pub mod compat_handlers;
+pub mod compatibility_testing_handlers;
+pub mod custom_metrics_handlers;
+pub mod db_monitoring;
+pub mod dependency;
+pub mod deprecation_handlers;
 pub mod disaster_recovery_models;
+pub mod discover_handlers;
 pub mod error;
+pub mod event_schema_handlers;
+pub mod example_handlers;
+pub mod feed_handlers;
+pub mod fork_handlers;
+pub mod handlers;
+pub mod health;
+pub mod health_monitor;
+pub mod idempotency;
+pub mod interfaces;
+pub mod ip_allowlist;
+pub mod job_handlers;
+pub mod lite;
+pub mod maintenance_mode;
+pub mod metrics;
+pub mod metrics_handler;
+pub mod migration_handlers;
 pub mod notification_handlers;
 pub mod notification_routes;
+pub mod org_handlers;
+pub mod patch_handlers;
 pub mod post_incident_handlers;
 pub mod post_incident_routes;
+pub mod precondition;
+pub mod private_registry;
+pub mod probe_handlers;
+pub mod provenance_handlers;
+pub mod publish_hooks;
+pub mod publish_session_handlers;
+pub mod publisher_identity;
+pub mod quality_score;
+pub mod quota_handlers;
+pub mod rate_limit;
+pub mod registry_flag_handlers;
+pub mod registry_flags;
+pub mod reindex;
+pub mod release_notes_handlers;
+pub mod release_notes_routes;
+pub mod request_tracing;
+pub mod review_handlers;
+pub mod routes;
+pub mod saved_search_handlers;
+pub mod sbom_handlers;
+pub mod search_suggestions;
+pub mod settings;
+pub mod signing_handlers;
+pub mod simulation;
+pub mod simulation_handlers;
+pub mod soroban_rpc;
+pub mod sparse_index;
+pub mod star_handlers;
 pub mod state;
-pub mod metrics;
+pub mod stats_handlers;
+pub mod stream_handlers;
+pub mod support_handlers;
+pub mod telemetry_handlers;
+pub mod tenancy;
+pub mod test_results_handlers;
+pub mod tls;
+pub mod token_handlers;
+pub mod toolchain_handlers;
+pub mod type_safety;
+pub mod user_auth;
+pub mod validation;
+pub mod web_ui;
+pub mod webhook_target;
+
+/// Ephemeral registries for integration tests (`--features testkit`)
+#[cfg(feature = "testkit")]
+pub mod testkit;
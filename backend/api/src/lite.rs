@@ -0,0 +1,446 @@
+// lite.rs
+// Lite mode: the core registry endpoints (publish, list, get, versions) and the
+// web UI served straight from a `RegistryRepository`. Used when `database.url`
+// points at SQLite, for demo and single-user deployments that do not run Postgres.
+
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    routing::get,
+    Json, Router,
+};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use shared::repository::{ContractFilter, NewContract, NewVersion, RegistryRepository};
+use shared::{
+    Contract, ContractVersion, CreateContractVersionRequest, Network, PaginatedResponse,
+    PublishRequest,
+};
+use uuid::Uuid;
+
+use crate::error::{ApiError, ApiResult};
+use crate::settings::Settings;
+
+type Repository = Arc<dyn RegistryRepository>;
+
+const MAX_PAGE_SIZE: i64 = 100;
+
+#[derive(Debug, Default, Deserialize)]
+pub struct ListQuery {
+    pub query: Option<String>,
+    pub network: Option<Network>,
+    pub category: Option<String>,
+    #[serde(default)]
+    pub verified_only: bool,
+    pub page: Option<i64>,
+    pub limit: Option<i64>,
+}
+
+fn repository_error(operation: &str, err: sqlx::Error) -> ApiError {
+    tracing::error!(operation = operation, error = ?err, "repository operation failed");
+    ApiError::internal(format!("Failed to {}", operation))
+}
+
+fn parse_id(id: &str) -> ApiResult<Uuid> {
+    Uuid::parse_str(id).map_err(|_| {
+        ApiError::bad_request(
+            "InvalidContractId",
+            format!("Invalid contract ID format: {}", id),
+        )
+    })
+}
+
+async fn health(State(repository): State<Repository>) -> Json<Value> {
+    Json(json!({
+        "status": "ok",
+        "mode": "lite",
+        "backend": format!("{:?}", repository.backend()).to_lowercase(),
+    }))
+}
+
+async fn list_contracts(
+    State(repository): State<Repository>,
+    Query(params): Query<ListQuery>,
+) -> ApiResult<Json<PaginatedResponse<Contract>>> {
+    let page = params.page.unwrap_or(1).max(1);
+    let limit = params.limit.unwrap_or(20).clamp(1, MAX_PAGE_SIZE);
+    let filter = ContractFilter {
+        query: params.query.filter(|q| !q.trim().is_empty()),
+        network: params.network,
+        category: params.category,
+        verified_only: params.verified_only,
+        limit,
+        offset: (page - 1) * limit,
+        // Lite mode serves a single tenant
+        ..Default::default()
+    };
+    let (contracts, total) = repository
+        .list_contracts(&filter)
+        .await
+        .map_err(|err| repository_error("list contracts", err))?;
+    Ok(Json(PaginatedResponse::new(contracts, total, page, limit)))
+}
+
+async fn publish_contract(
+    State(repository): State<Repository>,
+    Json(req): Json<PublishRequest>,
+) -> ApiResult<(StatusCode, Json<Contract>)> {
+    crate::validation::validate_contract_id(&req.contract_id)
+        .map_err(|e| ApiError::bad_request("InvalidContractId", e))?;
+    if let Some(ref license) = req.license {
+        shared::validate_spdx_expression(license)
+            .map_err(|e| ApiError::bad_request("InvalidLicense", e))?;
+    }
+    req.urls
+        .validate()
+        .map_err(|(field, e)| ApiError::bad_request("InvalidUrl", format!("{} {}", field, e)))?;
+    let name = shared::ContractName::parse(&req.name)
+        .map_err(|e| ApiError::bad_request("InvalidContractName", e))?;
+
+    let existing = repository
+        .contract_by_address(&req.contract_id, &req.network)
+        .await
+        .map_err(|err| repository_error("look up contract", err))?;
+    if existing.is_some() {
+        return Err(ApiError::conflict(
+            "ContractAlreadyExists",
+            format!(
+                "Contract {} is already registered on {}",
+                req.contract_id, req.network
+            ),
+        ));
+    }
+
+    let publisher = repository
+        .upsert_publisher(&req.publisher_address)
+        .await
+        .map_err(|err| repository_error("upsert publisher", err))?;
+    let contract = repository
+        .insert_contract(
+            publisher.id,
+            &NewContract::from_request(&req, &name.name, name.namespace.as_deref()),
+        )
+        .await
+        .map_err(|err| repository_error("create contract", err))?;
+    Ok((StatusCode::CREATED, Json(contract)))
+}
+
+async fn get_contract(
+    State(repository): State<Repository>,
+    Path(id): Path<String>,
+) -> ApiResult<Json<Contract>> {
+    let contract_uuid = parse_id(&id)?;
+    repository
+        .contract(contract_uuid)
+        .await
+        .map_err(|err| repository_error("get contract", err))?
+        .map(Json)
+        .ok_or_else(|| {
+            ApiError::not_found(
+                "ContractNotFound",
+                format!("No contract found with ID: {}", id),
+            )
+        })
+}
+
+async fn get_contract_versions(
+    State(repository): State<Repository>,
+    Path(id): Path<String>,
+) -> ApiResult<Json<Vec<ContractVersion>>> {
+    let contract_uuid = parse_id(&id)?;
+    let versions = repository
+        .versions(contract_uuid)
+        .await
+        .map_err(|err| repository_error("get contract versions", err))?;
+    Ok(Json(versions))
+}
+
+async fn create_contract_version(
+    State(repository): State<Repository>,
+    Path(id): Path<String>,
+    Json(req): Json<CreateContractVersionRequest>,
+) -> ApiResult<(StatusCode, Json<ContractVersion>)> {
+    let contract_uuid = parse_id(&id)?;
+    if shared::SemVer::parse(&req.version).is_none() {
+        return Err(ApiError::bad_request(
+            "InvalidVersion",
+            format!("'{}' is not a MAJOR.MINOR.PATCH version", req.version),
+        ));
+    }
+    crate::handlers::validate_abi_schema(&req.abi)?;
+
+    let contract = repository
+        .contract(contract_uuid)
+        .await
+        .map_err(|err| repository_error("get contract", err))?
+        .ok_or_else(|| {
+            ApiError::not_found(
+                "ContractNotFound",
+                format!("No contract found with ID: {}", id),
+            )
+        })?;
+    let version = repository
+        .insert_version(&NewVersion {
+            contract: contract.id,
+            version: req.version.clone(),
+            wasm_hash: req.wasm_hash,
+            source_url: req.source_url,
+            commit_hash: req.commit_hash,
+            release_notes: req.release_notes,
+            abi: Some(req.abi),
+        })
+        .await
+        .map_err(|err| match err {
+            sqlx::Error::Database(db) if db.is_unique_violation() => ApiError::conflict(
+                "VersionAlreadyExists",
+                format!("Version {} already exists", req.version),
+            ),
+            err => repository_error("create contract version", err),
+        })?;
+    Ok((StatusCode::CREATED, Json(version)))
+}
+
+/// The lite API, plus the web UI under `/` when `web_ui` is set
+pub fn router(repository: Repository, web_ui: bool) -> Router {
+    let api = Router::new()
+        .route("/health", get(health))
+        .route("/api/contracts", get(list_contracts).post(publish_contract))
+        .route("/api/contracts/:id", get(get_contract))
+        .route(
+            "/api/contracts/:id/versions",
+            get(get_contract_versions).post(create_contract_version),
+        )
+        .with_state(repository.clone());
+    if web_ui {
+        api.merge(crate::web_ui::routes(repository))
+    } else {
+        api
+    }
+}
+
+/// Sample contracts for `soroban-registry serve --seed demo`: name, network,
+/// category, tags, description
+const DEMO_CONTRACTS: &[(&str, Network, &str, &[&str], &str)] = &[
+    (
+        "token-swap",
+        Network::Testnet,
+        "DeFi",
+        &["defi", "swap"],
+        "A decentralized token swap protocol enabling seamless asset exchanges",
+    ),
+    (
+        "price-oracle",
+        Network::Testnet,
+        "Infrastructure",
+        &["oracle"],
+        "Real-time price oracle aggregating data from multiple sources",
+    ),
+    (
+        "multisig-wallet",
+        Network::Mainnet,
+        "Infrastructure",
+        &["wallet", "multisig"],
+        "Multi-signature wallet with configurable threshold requirements",
+    ),
+    (
+        "nft-marketplace",
+        Network::Futurenet,
+        "NFT",
+        &["nft", "marketplace"],
+        "NFT marketplace with royalty enforcement and batch operations",
+    ),
+];
+
+const DEMO_PUBLISHER: &str = "GDEMOPUBLISHERAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA";
+
+/// Load the demo contracts, each with a 1.0.0 version; contracts already present
+/// are left alone. Returns how many were added.
+pub async fn seed_demo(repository: &dyn RegistryRepository) -> Result<usize, sqlx::Error> {
+    let publisher = repository.upsert_publisher(DEMO_PUBLISHER).await?;
+    let mut added = 0;
+    for (index, (name, network, category, tags, description)) in DEMO_CONTRACTS.iter().enumerate() {
+        let contract_id = format!("C{:A>55}", index);
+        if repository
+            .contract_by_address(&contract_id, network)
+            .await?
+            .is_some()
+        {
+            continue;
+        }
+        let contract = repository
+            .insert_contract(
+                publisher.id,
+                &NewContract {
+                    contract_id,
+                    wasm_hash: "placeholder_hash".to_string(),
+                    name: name.to_string(),
+                    namespace: None,
+                    description: Some(description.to_string()),
+                    network: network.clone(),
+                    category: Some(category.to_string()),
+                    tags: tags.iter().map(|tag| tag.to_string()).collect(),
+                    license: Some("MIT".to_string()),
+                    urls: Default::default(),
+                },
+            )
+            .await?;
+        repository
+            .insert_version(&NewVersion {
+                contract: contract.id,
+                version: "1.0.0".to_string(),
+                wasm_hash: format!("{:0>64x}", index + 1),
+                source_url: None,
+                commit_hash: None,
+                release_notes: Some("Demo release".to_string()),
+                abi: Some(json!([{
+                    "type": "function",
+                    "name": "version",
+                    "inputs": [],
+                    "outputs": [{"type": "u32"}],
+                }])),
+            })
+            .await?;
+        repository
+            .set_readme(
+                contract.id,
+                &format!(
+                    "# {}
+
+{}
+
+Sample contract loaded by `--seed demo`.
+",
+                    name, description
+                ),
+            )
+            .await?;
+        added += 1;
+    }
+    Ok(added)
+}
+
+/// Serve `app` on `addr` until Ctrl+C
+pub async fn run(app: Router, addr: std::net::SocketAddr) -> anyhow::Result<()> {
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    tracing::info!("API server listening on {}", addr);
+    axum::serve(listener, app)
+        .with_graceful_shutdown(async {
+            let _ = tokio::signal::ctrl_c().await;
+        })
+        .await?;
+    Ok(())
+}
+
+/// Open the configured database, bring its schema up to date and serve until Ctrl+C
+pub async fn serve(settings: &Settings) -> anyhow::Result<()> {
+    let repository = shared::repository::connect(
+        &settings.database.url,
+        settings.database.max_pool_size.unwrap_or(5),
+    )
+    .await?;
+    repository.migrate().await?;
+    tracing::info!(
+        backend = ?repository.backend(),
+        "Lite mode: serving the core registry endpoints only"
+    );
+    run(
+        router(repository, settings.server.web_ui),
+        settings.server.bind_address,
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::http::Request;
+    use tower::ServiceExt;
+
+    async fn app() -> Router {
+        let repository = shared::repository::connect("sqlite::memory:", 1)
+            .await
+            .unwrap();
+        repository.migrate().await.unwrap();
+        router(repository, true)
+    }
+
+    async fn send(app: &Router, method: &str, uri: &str, body: Value) -> (StatusCode, Value) {
+        let request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("content-type", "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (
+            status,
+            serde_json::from_slice(&bytes).unwrap_or(Value::Null),
+        )
+    }
+
+    #[tokio::test]
+    async fn publishes_and_lists_contracts_over_sqlite() {
+        let app = app().await;
+        let contract_id = format!("C{}", "A".repeat(55));
+        let publish = json!({
+            "contract_id": contract_id,
+            "name": "amm",
+            "network": "testnet",
+            "tags": ["swap"],
+            "publisher_address": format!("G{}", "B".repeat(55)),
+        });
+
+        let (status, created) = send(&app, "POST", "/api/contracts", publish.clone()).await;
+        assert_eq!(status, StatusCode::CREATED, "{}", created);
+        let (status, _) = send(&app, "POST", "/api/contracts", publish).await;
+        assert_eq!(status, StatusCode::CONFLICT);
+
+        let id = created["id"].as_str().unwrap();
+        let version = json!({
+            "contract_id": contract_id,
+            "version": "1.0.0",
+            "wasm_hash": "ab".repeat(32),
+            "abi": [{"type": "function", "name": "swap"}],
+        });
+        let uri = format!("/api/contracts/{}/versions", id);
+        let (status, _) = send(&app, "POST", &uri, version).await;
+        assert_eq!(status, StatusCode::CREATED);
+
+        let (status, listed) =
+            send(&app, "GET", "/api/contracts?network=testnet", Value::Null).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(listed["total"], 1);
+        assert_eq!(listed["contracts"][0]["wasm_hash"], "ab".repeat(32));
+
+        let (status, versions) = send(&app, "GET", &uri, Value::Null).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(versions[0]["version"], "1.0.0");
+    }
+
+    #[tokio::test]
+    async fn demo_seed_is_idempotent() {
+        let repository = shared::repository::connect("sqlite::memory:", 1)
+            .await
+            .unwrap();
+        repository.migrate().await.unwrap();
+        assert_eq!(
+            seed_demo(repository.as_ref()).await.unwrap(),
+            DEMO_CONTRACTS.len()
+        );
+        assert_eq!(seed_demo(repository.as_ref()).await.unwrap(), 0);
+
+        let (contracts, total) = repository
+            .list_contracts(&ContractFilter::default())
+            .await
+            .unwrap();
+        assert_eq!(total as usize, DEMO_CONTRACTS.len());
+        assert!(contracts.iter().all(|c| c.wasm_hash != "placeholder_hash"));
+    }
+}
@@ -1,9 +1,34 @@
 #![allow(dead_code, unused)]
 
+mod abuse_report_handlers;
+mod account_handlers;
+mod admin_auth;
+mod admin_handlers;
 mod aggregation;
+mod alert_handlers;
 mod analytics;
+mod api_key_handlers;
+mod api_usage;
+mod app;
+mod archive_handlers;
+mod artifact_handlers;
+mod artifact_gc;
+mod audit_attestation_handlers;
+mod auth;
+mod auth_handlers;
+mod backfill;
+mod badge_handlers;
+mod batch_handlers;
+mod body_limit;
 mod breaking_changes;
+mod budget_handlers;
 mod cache;
+mod challenge;
+mod changelog_handlers;
+mod cli_release_handlers;
+mod client_version;
+mod collection_handlers;
+mod compat_handlers;
 mod compatibility_testing_handlers;
 mod db_monitoring;
 
@@ -12,42 +37,85 @@ mod activity_feed_routes;
 mod custom_metrics_handlers;
 mod dependency;
 mod deprecation_handlers;
+mod discover_handlers;
 mod error;
+mod event_schema_handlers;
+mod example_handlers;
+mod feed_handlers;
+mod fork_handlers;
 mod handlers;
 mod health;
+mod idempotency;
+mod interfaces;
+mod ip_allowlist;
+mod job_handlers;
+mod lite;
+mod maintenance_mode;
 pub mod health_monitor;
 #[cfg(test)]
 mod health_tests;
 mod metrics;
 mod metrics_handler;
 mod migration_handlers;
+mod org_handlers;
+mod patch_handlers;
+mod precondition;
+mod private_registry;
+mod publish_hooks;
+mod publish_session_handlers;
+mod publisher_identity;
+mod probe_handlers;
+mod provenance_handlers;
+mod quality_score;
+mod quota_handlers;
 mod rate_limit;
+mod registry_flag_handlers;
+mod registry_flags;
+mod reindex;
 mod release_notes_handlers;
 mod release_notes_routes;
+mod review_handlers;
 pub mod request_tracing;
 mod routes;
+mod saved_search_handlers;
+mod sbom_handlers;
+mod search_suggestions;
+mod settings;
 pub mod signing_handlers;
+mod simulation;
+mod simulation_handlers;
+mod soroban_rpc;
+mod sparse_index;
+mod star_handlers;
 mod state;
+mod stats_handlers;
+mod stream_handlers;
+mod support_handlers;
+mod telemetry_handlers;
+mod tenancy;
+mod tls;
+mod test_results_handlers;
+mod token_handlers;
+mod toolchain_handlers;
 mod type_safety;
+mod user_auth;
 mod validation;
-// mod auth;
-// mod auth_handlers;
+mod web_ui;
+mod webhook_target;
 // mod resource_handlers;
 // mod resource_tracking;
 
 use anyhow::Result;
-use axum::http::{header, HeaderValue, Method};
-use axum::{middleware, Router};
+use axum::extract::Request;
+use axum::ServiceExt;
 use dotenv::dotenv;
 use prometheus::Registry;
 use sqlx::postgres::PgPoolOptions;
 use std::net::SocketAddr;
 use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
-use tower_http::cors::CorsLayer;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
-use crate::rate_limit::RateLimitState;
 use crate::state::AppState;
 
 #[tokio::main]
@@ -58,17 +126,31 @@ async fn main() -> Result<()> {
     // Initialize structured JSON tracing (ELK/Splunk compatible)
     request_tracing::init_json_tracing();
 
-    // Database connection with dynamic pool size
-    let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    // Typed configuration: registry.toml plus env overrides, validated up front
+    let settings = match settings::Settings::load() {
+        Ok(settings) => settings,
+        Err(err) => {
+            eprintln!("{}", err);
+            std::process::exit(2);
+        }
+    };
+    if std::env::args().any(|arg| arg == "--print-config") {
+        print!("{}", settings.to_redacted_toml());
+        return Ok(());
+    }
+    if settings.database.backend() == Some(shared::repository::Backend::Sqlite) {
+        return lite::serve(&settings).await;
+    }
 
+    // Database connection with dynamic pool size
     let logical_cores = std::thread::available_parallelism()
         .map(|n| n.get())
         .unwrap_or(4);
 
     let default_max_pool = (logical_cores * 2).max(10);
-    let max_pool_size = std::env::var("DB_MAX_POOL_SIZE")
-        .ok()
-        .and_then(|s| s.parse::<u32>().ok())
+    let max_pool_size = settings
+        .database
+        .max_pool_size
         .unwrap_or(default_max_pool as u32);
 
     tracing::info!(
@@ -80,7 +162,7 @@ async fn main() -> Result<()> {
     let pool = PgPoolOptions::new()
         .max_connections(max_pool_size)
         .acquire_timeout(std::time::Duration::from_secs(30))
-        .connect(&database_url)
+        .connect(&settings.database.url)
         .await?;
 
     // Run migrations
@@ -104,55 +186,76 @@ async fn main() -> Result<()> {
 
     // Create app state
     let is_shutting_down = Arc::new(AtomicBool::new(false));
-    let state = AppState::new(pool.clone(), registry, is_shutting_down.clone());
+    let flags = registry_flags::RegistryFlags::new(
+        settings.server.environment.clone(),
+        settings.features.clone(),
+    );
+    let state = AppState::new(pool.clone(), registry, is_shutting_down.clone())
+        .with_flags(flags)
+        .with_private_mode(settings.server.private_mode)
+        .with_quotas(settings.quotas.clone())
+        .with_publish_hooks(publish_hooks::PublishHooks::from_rules(
+            &settings.publish_hooks,
+        ))
+        .with_maintenance(maintenance_mode::MaintenanceMode::from_settings(
+            &settings.maintenance,
+        ))
+        .with_index(sparse_index::SparseIndex::from_settings(
+            &settings.index,
+            &settings.storage,
+        ))
+        .with_rpc(soroban_rpc::SorobanRpc::from_settings(&settings.rpc))
+        .with_challenge(challenge::ChallengeGate::from_settings(&settings.challenge))
+        .with_patch_maintainers(patch_handlers::PatchMaintainers::from_settings(
+            &settings.patches,
+        ))
+        .with_site_url(&settings.server.public_site_url);
+    if state.private_mode {
+        tracing::info!("Private registry mode: every endpoint requires authentication");
+    }
+    if settings.maintenance.enabled {
+        tracing::warn!("Maintenance mode: the registry is read-only");
+    }
 
     // Spawn the background DB and cache monitoring task
     db_monitoring::spawn_db_monitoring_task(pool.clone(), state.cache.clone());
 
+    // Probe each network's Soroban RPC in the background
+    soroban_rpc::spawn_rpc_monitor(state.rpc.clone());
+
     // Warm up the cache
     state.cache.clone().warm_up(pool.clone());
 
-    let rate_limit_state = RateLimitState::from_env();
-
-    let cors = CorsLayer::new()
-        .allow_origin([
-            HeaderValue::from_static("http://localhost:3000"),
-            HeaderValue::from_static("https://soroban-registry.vercel.app"),
-        ])
-        .allow_methods([Method::GET, Method::POST, Method::OPTIONS])
-        .allow_headers([header::CONTENT_TYPE, header::AUTHORIZATION]);
-
-    // Build router
-    let app = Router::new()
-        .merge(routes::contract_routes())
-        .merge(routes::publisher_routes())
-        .merge(routes::health_routes())
-        .merge(routes::migration_routes())
-        .merge(routes::compatibility_dashboard_routes())
-        .merge(release_notes_routes::release_notes_routes())
-        .nest("/api", activity_feed_routes::routes())
-        .fallback(handlers::route_not_found)
-        .layer(middleware::from_fn(request_tracing::tracing_middleware))
-        .layer(middleware::from_fn_with_state(
-            rate_limit_state,
-            rate_limit::rate_limit_middleware,
-        ))
-        .layer(CorsLayer::permissive())
-        .layer(cors)
-        .with_state(state);
-
-    // Start server
-    let addr = SocketAddr::from(([0, 0, 0, 0], 3001));
-    tracing::info!("API server listening on {}", addr);
+    // Write the sparse index if this instance has none yet
+    sparse_index::spawn_initial_build(&state);
+
+    // Alert dependents of versions reaching end of life, daily
+    support_handlers::spawn_eol_alert_task(state.clone());
+
+    // Recompute contract quality scores nightly
+    quality_score::spawn_quality_score_task(pool.clone());
+
+    let api_usage = api_usage::ApiUsageRecorder::new(state.clone());
+    api_usage.spawn_flush_task();
+    let app = app::build(state, &settings, api_usage);
+
+    // Start server; certificate problems fail startup rather than the first handshake
+    let tls_acceptor = if settings.server.tls.enabled {
+        Some(tls::acceptor(&settings.server.tls)?)
+    } else {
+        None
+    };
+    let addr = settings.server.bind_address;
+    match (&tls_acceptor, &settings.server.tls.client_ca_path) {
+        (Some(_), Some(_)) => tracing::info!("API server listening on {} (mutual TLS)", addr),
+        (Some(_), None) => tracing::info!("API server listening on {} (TLS)", addr),
+        (None, _) => tracing::info!("API server listening on {}", addr),
+    }
 
     let listener = tokio::net::TcpListener::bind(addr).await?;
     let (tx, rx) = tokio::sync::oneshot::channel::<()>();
 
-    let server = axum::serve(
-        listener,
-        app.into_make_service_with_connect_info::<SocketAddr>(),
-    )
-    .with_graceful_shutdown(async move {
+    let shutdown = async move {
         let ctrl_c = async {
             tokio::signal::ctrl_c()
                 .await
@@ -180,7 +283,21 @@ async fn main() -> Result<()> {
         );
         is_shutting_down.store(true, std::sync::atomic::Ordering::SeqCst);
         let _ = tx.send(());
-    });
+    };
+
+    let server = async move {
+        match tls_acceptor {
+            Some(acceptor) => tls::serve(listener, acceptor, app, shutdown).await,
+            None => {
+                axum::serve(
+                    listener,
+                    ServiceExt::<Request>::into_make_service_with_connect_info::<SocketAddr>(app),
+                )
+                .with_graceful_shutdown(shutdown)
+                .await
+            }
+        }
+    };
 
     tokio::select! {
         res = server => {
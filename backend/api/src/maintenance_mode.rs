@@ -0,0 +1,182 @@
+// maintenance_mode.rs
+// Registry-wide read-only switch: writes get 503 with the operator's notice and
+// Retry-After while reads keep working, e.g. during schema migrations.
+
+use std::sync::RwLock;
+
+use axum::{
+    body::Body,
+    extract::{rejection::JsonRejection, Request, State},
+    http::{header::RETRY_AFTER, HeaderValue, Method, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::Utc;
+use shared::{MaintenanceStatus, UpdateMaintenanceRequest};
+
+use crate::error::{ApiError, ApiResult};
+use crate::handlers::map_json_rejection;
+use crate::settings::MaintenanceSettings;
+use crate::state::AppState;
+
+const DEFAULT_MESSAGE: &str = "The registry is in maintenance mode and is read-only";
+const MAX_MESSAGE_LENGTH: usize = 500;
+
+/// Current maintenance state of this instance, seeded from `[maintenance]`
+pub struct MaintenanceMode {
+    status: RwLock<MaintenanceStatus>,
+}
+
+impl Default for MaintenanceMode {
+    fn default() -> Self {
+        Self::from_settings(&MaintenanceSettings::default())
+    }
+}
+
+impl MaintenanceMode {
+    pub fn from_settings(settings: &MaintenanceSettings) -> Self {
+        Self {
+            status: RwLock::new(MaintenanceStatus {
+                enabled: settings.enabled,
+                message: settings.message.clone(),
+                retry_after_seconds: settings.retry_after_seconds,
+                updated_by: None,
+                updated_at: None,
+            }),
+        }
+    }
+
+    pub fn status(&self) -> MaintenanceStatus {
+        self.status
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
+
+    fn set(&self, status: MaintenanceStatus) {
+        *self
+            .status
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = status;
+    }
+}
+
+/// Whether maintenance mode turns this request away. Admin endpoints stay open
+/// so the operator can switch it off; simulations are POSTs that write nothing.
+fn blocks(status: &MaintenanceStatus, method: &Method, path: &str) -> bool {
+    status.enabled
+        && !matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS)
+        && !path.starts_with("/api/admin/")
+        && !path.ends_with("/simulate")
+}
+
+pub async fn maintenance_mode_middleware(
+    State(state): State<AppState>,
+    request: Request<Body>,
+    next: Next,
+) -> Response {
+    let status = state.maintenance.status();
+    if !blocks(&status, request.method(), request.uri().path()) {
+        return next.run(request).await;
+    }
+
+    let mut response = ApiError::new(
+        StatusCode::SERVICE_UNAVAILABLE,
+        "MaintenanceMode",
+        status.message.as_deref().unwrap_or(DEFAULT_MESSAGE),
+    )
+    .into_response();
+    response.headers_mut().insert(
+        RETRY_AFTER,
+        HeaderValue::from_str(&status.retry_after_seconds.to_string())
+            .unwrap_or_else(|_| HeaderValue::from_static("300")),
+    );
+    response
+}
+
+/// GET /api/admin/maintenance
+pub async fn get_maintenance(State(state): State<AppState>) -> Json<MaintenanceStatus> {
+    Json(state.maintenance.status())
+}
+
+/// PUT /api/admin/maintenance — switch read-only mode on or off for this instance
+pub async fn update_maintenance(
+    State(state): State<AppState>,
+    payload: Result<Json<UpdateMaintenanceRequest>, JsonRejection>,
+) -> ApiResult<Json<MaintenanceStatus>> {
+    let Json(req) = payload.map_err(map_json_rejection)?;
+    let changed_by = req.changed_by.trim();
+    if changed_by.is_empty() {
+        return Err(ApiError::bad_request(
+            "MissingActor",
+            "changed_by is required so the change can be audited",
+        ));
+    }
+    let message = req
+        .message
+        .map(|message| message.trim().to_string())
+        .filter(|message| !message.is_empty());
+    if message.as_deref().map_or(0, str::len) > MAX_MESSAGE_LENGTH {
+        return Err(ApiError::bad_request(
+            "InvalidMessage",
+            format!("message must be at most {} characters", MAX_MESSAGE_LENGTH),
+        ));
+    }
+    if req.retry_after_seconds == Some(0) {
+        return Err(ApiError::bad_request(
+            "InvalidRetryAfter",
+            "retry_after_seconds must be greater than 0",
+        ));
+    }
+
+    let previous = state.maintenance.status();
+    let status = MaintenanceStatus {
+        enabled: req.enabled,
+        message,
+        retry_after_seconds: req
+            .retry_after_seconds
+            .unwrap_or(previous.retry_after_seconds),
+        updated_by: Some(changed_by.to_string()),
+        updated_at: Some(Utc::now()),
+    };
+    state.maintenance.set(status.clone());
+
+    tracing::warn!(
+        enabled = status.enabled,
+        changed_by = %changed_by,
+        "maintenance mode updated"
+    );
+    Ok(Json(status))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status(enabled: bool) -> MaintenanceStatus {
+        MaintenanceStatus {
+            enabled,
+            message: None,
+            retry_after_seconds: 60,
+            updated_by: None,
+            updated_at: None,
+        }
+    }
+
+    #[test]
+    fn only_writes_are_blocked() {
+        let on = status(true);
+        assert!(blocks(&on, &Method::POST, "/api/contracts"));
+        assert!(blocks(&on, &Method::DELETE, "/api/contracts/abc"));
+        assert!(!blocks(&on, &Method::GET, "/api/contracts"));
+        assert!(!blocks(&status(false), &Method::POST, "/api/contracts"));
+    }
+
+    #[test]
+    fn admin_and_simulation_stay_open() {
+        let on = status(true);
+        assert!(!blocks(&on, &Method::PUT, "/api/admin/maintenance"));
+        assert!(!blocks(&on, &Method::POST, "/api/contracts/abc/simulate"));
+    }
+}
@@ -351,6 +351,42 @@ pub fn observe_db_query(query: &str, duration_secs: f64) {
     DB_TRANSACTIONS_TOTAL.inc();
 }
 
+/// Routes with the highest mean latency recorded by `observe_http`
+pub fn slowest_endpoints(limit: usize) -> Vec<shared::EndpointLatency> {
+    use prometheus::core::Collector;
+
+    let mut endpoints: Vec<shared::EndpointLatency> = HTTP_REQUEST_DURATION
+        .collect()
+        .iter()
+        .flat_map(|family| family.get_metric())
+        .filter_map(|metric| {
+            let histogram = metric.get_histogram();
+            let requests = histogram.get_sample_count();
+            if requests == 0 {
+                return None;
+            }
+            let label = |name: &str| {
+                metric
+                    .get_label()
+                    .iter()
+                    .find(|l| l.get_name() == name)
+                    .map(|l| l.get_value().to_string())
+                    .unwrap_or_default()
+            };
+            Some(shared::EndpointLatency {
+                method: label("method"),
+                path: label("path"),
+                requests,
+                mean_ms: histogram.get_sample_sum() * 1000.0 / requests as f64,
+            })
+        })
+        .collect();
+
+    endpoints.sort_by(|a, b| b.mean_ms.total_cmp(&a.mean_ms));
+    endpoints.truncate(limit);
+    endpoints
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -440,4 +476,14 @@ mod tests {
             .get_sample_count();
         assert!(sample_count >= 1);
     }
+
+    #[test]
+    fn test_slowest_endpoints_ranks_by_mean_latency() {
+        observe_http("GET", "/api/test/slow", 200, 2.0);
+        observe_http("GET", "/api/test/fast", 200, 0.001);
+        let slowest = slowest_endpoints(usize::MAX);
+        let rank = |path: &str| slowest.iter().position(|e| e.path == path).unwrap();
+        assert!(rank("/api/test/slow") < rank("/api/test/fast"));
+        assert!(slowest[rank("/api/test/slow")].mean_ms >= 2000.0);
+    }
 }
@@ -20,23 +20,20 @@ pub async fn metrics_endpoint(State(state): State<AppState>) -> impl IntoRespons
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::cache::{CacheConfig, CacheLayer};
     use axum::extract::State;
     use axum::response::IntoResponse;
     use prometheus::Registry;
-    use std::sync::{Arc, RwLock};
-    use std::time::Instant;
+    use std::sync::atomic::AtomicBool;
+    use std::sync::Arc;
 
     fn test_state() -> AppState {
         let registry = Registry::new_custom(Some("test".into()), None).unwrap();
         metrics::register_all(&registry).unwrap();
-        AppState {
-            db: create_test_pool(),
-            started_at: Instant::now(),
-            cache: Arc::new(CacheLayer::new(CacheConfig::default())),
+        AppState::new(
+            create_test_pool(),
             registry,
-            is_shutting_down: Arc::new(std::sync::atomic::AtomicBool::new(false)),
-        }
+            Arc::new(AtomicBool::new(false)),
+        )
     }
 
     fn create_test_pool() -> sqlx::PgPool {
@@ -0,0 +1,373 @@
+// org_handlers.rs
+// Organizations for private registries: membership management and marking
+// contracts private to an organization.
+
+use axum::{
+    extract::{rejection::JsonRejection, Path, State},
+    http::StatusCode,
+    Json,
+};
+use shared::{
+    AddOrganizationMemberRequest, ApiScope, Contract, CreateOrganizationRequest, Organization,
+    OrganizationDetail, OrganizationMember, UpdateContractVisibilityRequest,
+};
+use uuid::Uuid;
+
+use crate::error::{ApiError, ApiResult};
+use crate::handlers::{db_internal_error, fetch_contract_identity, map_json_rejection};
+use crate::quota_handlers::{account_for_contract, ensure_contract_quota, QuotaAccount};
+use crate::state::AppState;
+use crate::tenancy::CurrentTenant;
+use crate::user_auth::AuthUser;
+
+const ROLE_OWNER: &str = "owner";
+const ROLE_MEMBER: &str = "member";
+
+fn forbidden(message: impl Into<String>) -> ApiError {
+    ApiError::new(StatusCode::FORBIDDEN, "Forbidden", message)
+}
+
+/// 403 unless `address` published the contract or owns the organization it
+/// belongs to; only they may publish versions or change its metadata
+pub(crate) async fn ensure_contract_maintainer(
+    state: &AppState,
+    contract_uuid: Uuid,
+    address: &str,
+) -> ApiResult<()> {
+    let maintainer: bool = sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM contracts c \
+         LEFT JOIN publishers p ON p.id = c.publisher_id \
+         WHERE c.id = $1 AND (p.stellar_address = $2 OR EXISTS (\
+             SELECT 1 FROM organization_members m \
+             WHERE m.organization_id = c.organization_id AND m.address = $2 \
+               AND m.role = 'owner')))",
+    )
+    .bind(contract_uuid)
+    .bind(address)
+    .fetch_one(&state.db)
+    .await
+    .map_err(|err| db_internal_error("check contract maintainer", err))?;
+    if !maintainer {
+        return Err(forbidden(
+            "Only the contract's publisher or its organization's owners can do this",
+        ));
+    }
+    Ok(())
+}
+
+/// Organization names follow the namespace rules: lowercase letters, digits, single hyphens
+fn validate_org_name(name: &str) -> ApiResult<()> {
+    shared::validate_namespace(name).map_err(|e| {
+        ApiError::bad_request(
+            "InvalidOrganizationName",
+            e.replace("namespace", "organization name"),
+        )
+    })
+}
+
+fn parse_role(role: Option<&str>) -> ApiResult<&'static str> {
+    match role.unwrap_or(ROLE_MEMBER) {
+        ROLE_OWNER => Ok(ROLE_OWNER),
+        ROLE_MEMBER => Ok(ROLE_MEMBER),
+        other => Err(ApiError::bad_request(
+            "InvalidRole",
+            format!("Unknown role '{}'; use owner or member", other),
+        )),
+    }
+}
+
+/// The organization named `name` and the caller's role in it. Non-members get
+/// a 404 so private organizations are not revealed.
+async fn fetch_membership(
+    state: &AppState,
+    name: &str,
+    address: &str,
+) -> ApiResult<(Organization, String)> {
+    let row: Option<(Uuid, String, String, chrono::DateTime<chrono::Utc>, String)> =
+        sqlx::query_as(
+            "SELECT o.id, o.name, o.created_by, o.created_at, m.role \
+             FROM organizations o \
+             JOIN organization_members m ON m.organization_id = o.id \
+             WHERE o.name = $1 AND m.address = $2",
+        )
+        .bind(name)
+        .bind(address)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(|err| db_internal_error("fetch organization membership", err))?;
+    let (id, name, created_by, created_at, role) = row.ok_or_else(|| {
+        ApiError::not_found(
+            "OrganizationNotFound",
+            format!("No organization named '{}'", name),
+        )
+    })?;
+    Ok((
+        Organization {
+            id,
+            name,
+            created_by,
+            created_at,
+        },
+        role,
+    ))
+}
+
+async fn require_owner(state: &AppState, name: &str, address: &str) -> ApiResult<Organization> {
+    let (org, role) = fetch_membership(state, name, address).await?;
+    if role != ROLE_OWNER {
+        return Err(forbidden("Only organization owners can manage members"));
+    }
+    Ok(org)
+}
+
+/// POST /api/orgs — the caller becomes the first owner
+pub async fn create_organization(
+    State(state): State<AppState>,
+    tenant: CurrentTenant,
+    user: AuthUser,
+    payload: Result<Json<CreateOrganizationRequest>, JsonRejection>,
+) -> ApiResult<(StatusCode, Json<OrganizationDetail>)> {
+    user.require(ApiScope::PatchAdmin)?;
+    let Json(req) = payload.map_err(map_json_rejection)?;
+    validate_org_name(&req.name)?;
+
+    let mut tx = state
+        .db
+        .begin()
+        .await
+        .map_err(|err| db_internal_error("begin organization transaction", err))?;
+    let organization: Organization = sqlx::query_as(
+        "INSERT INTO organizations (name, created_by, tenant_id) VALUES ($1, $2, $3) \
+         RETURNING id, name, created_by, created_at",
+    )
+    .bind(&req.name)
+    .bind(&user.address)
+    .bind(tenant.id)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|err| match err {
+        sqlx::Error::Database(ref e) if e.constraint() == Some("organizations_name_key") => {
+            ApiError::conflict(
+                "OrganizationExists",
+                format!("An organization named '{}' already exists", req.name),
+            )
+        }
+        _ => db_internal_error("create organization", err),
+    })?;
+    let owner: OrganizationMember = sqlx::query_as(
+        "INSERT INTO organization_members (organization_id, address, role) \
+         VALUES ($1, $2, $3) RETURNING address, role, added_at",
+    )
+    .bind(organization.id)
+    .bind(&user.address)
+    .bind(ROLE_OWNER)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|err| db_internal_error("add organization owner", err))?;
+    tx.commit()
+        .await
+        .map_err(|err| db_internal_error("commit organization", err))?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(OrganizationDetail {
+            organization,
+            members: vec![owner],
+        }),
+    ))
+}
+
+/// GET /api/orgs/:name — members only
+pub async fn get_organization(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(name): Path<String>,
+) -> ApiResult<Json<OrganizationDetail>> {
+    user.require(ApiScope::Read)?;
+    let (organization, _) = fetch_membership(&state, &name, &user.address).await?;
+    let members: Vec<OrganizationMember> = sqlx::query_as(
+        "SELECT address, role, added_at FROM organization_members \
+         WHERE organization_id = $1 ORDER BY added_at",
+    )
+    .bind(organization.id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("list organization members", err))?;
+    Ok(Json(OrganizationDetail {
+        organization,
+        members,
+    }))
+}
+
+/// POST /api/orgs/:name/members — owners add a member, or change their role
+pub async fn add_organization_member(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(name): Path<String>,
+    payload: Result<Json<AddOrganizationMemberRequest>, JsonRejection>,
+) -> ApiResult<Json<OrganizationMember>> {
+    user.require(ApiScope::PatchAdmin)?;
+    let Json(req) = payload.map_err(map_json_rejection)?;
+    let role = parse_role(req.role.as_deref())?;
+    let address = req.address.trim();
+    if address.is_empty() {
+        return Err(ApiError::bad_request(
+            "InvalidAddress",
+            "address must not be empty",
+        ));
+    }
+    let organization = require_owner(&state, &name, &user.address).await?;
+
+    // Demoting the last owner would leave nobody able to manage the organization
+    let member: Option<OrganizationMember> = sqlx::query_as(
+        "INSERT INTO organization_members (organization_id, address, role) \
+         VALUES ($1, $2, $3) \
+         ON CONFLICT (organization_id, address) DO UPDATE SET role = EXCLUDED.role \
+         WHERE EXCLUDED.role = 'owner' OR EXISTS (\
+             SELECT 1 FROM organization_members o \
+             WHERE o.organization_id = $1 AND o.role = 'owner' AND o.address <> $2) \
+         RETURNING address, role, added_at",
+    )
+    .bind(organization.id)
+    .bind(address)
+    .bind(role)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|err| db_internal_error("add organization member", err))?;
+    member.map(Json).ok_or_else(|| {
+        ApiError::conflict("LastOwner", "An organization must keep at least one owner")
+    })
+}
+
+/// DELETE /api/orgs/:name/members/:address — owners remove anyone but the
+/// last owner; members may remove themselves
+pub async fn remove_organization_member(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path((name, address)): Path<(String, String)>,
+) -> ApiResult<StatusCode> {
+    user.require(ApiScope::PatchAdmin)?;
+    let (organization, role) = fetch_membership(&state, &name, &user.address).await?;
+    if address != user.address && role != ROLE_OWNER {
+        return Err(forbidden("Only organization owners can manage members"));
+    }
+
+    let removed = sqlx::query(
+        "DELETE FROM organization_members \
+         WHERE organization_id = $1 AND address = $2 \
+           AND (role <> 'owner' OR (SELECT COUNT(*) FROM organization_members \
+                                    WHERE organization_id = $1 AND role = 'owner') > 1)",
+    )
+    .bind(organization.id)
+    .bind(&address)
+    .execute(&state.db)
+    .await
+    .map_err(|err| db_internal_error("remove organization member", err))?;
+    if removed.rows_affected() == 0 {
+        let is_member: bool = sqlx::query_scalar(
+            "SELECT EXISTS(SELECT 1 FROM organization_members \
+             WHERE organization_id = $1 AND address = $2)",
+        )
+        .bind(organization.id)
+        .bind(&address)
+        .fetch_one(&state.db)
+        .await
+        .map_err(|err| db_internal_error("check organization member", err))?;
+        return Err(if is_member {
+            ApiError::conflict("LastOwner", "An organization must keep at least one owner")
+        } else {
+            ApiError::not_found(
+                "MemberNotFound",
+                format!("{} is not a member of '{}'", address, name),
+            )
+        });
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// PATCH /api/contracts/:id/visibility — the contract's publisher makes it
+/// private to one of their organizations, or public again
+pub async fn update_contract_visibility(
+    State(state): State<AppState>,
+    tenant: CurrentTenant,
+    user: AuthUser,
+    Path(id): Path<String>,
+    payload: Result<Json<UpdateContractVisibilityRequest>, JsonRejection>,
+) -> ApiResult<Json<Contract>> {
+    user.require(ApiScope::PatchAdmin)?;
+    let Json(req) = payload.map_err(map_json_rejection)?;
+    let (contract_uuid, _) = fetch_contract_identity(&state, tenant.id, &id).await?;
+
+    let is_publisher: bool = sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM contracts c JOIN publishers p ON p.id = c.publisher_id \
+         WHERE c.id = $1 AND p.stellar_address = $2)",
+    )
+    .bind(contract_uuid)
+    .bind(&user.address)
+    .fetch_one(&state.db)
+    .await
+    .map_err(|err| db_internal_error("check contract publisher", err))?;
+    if !is_publisher {
+        return Err(forbidden(
+            "Only the contract's publisher can change its visibility",
+        ));
+    }
+
+    let organization = match (req.private, req.organization.as_deref()) {
+        (true, None) => {
+            return Err(ApiError::bad_request(
+                "OrganizationRequired",
+                "organization is required to make a contract private",
+            ))
+        }
+        (_, Some(name)) => Some(fetch_membership(&state, name, &user.address).await?.0),
+        (false, None) => None,
+    };
+    if let Some(ref org) = organization {
+        // Moving a contract into an organization counts against its quota
+        let account = account_for_contract(&state, contract_uuid).await?;
+        if !matches!(account, QuotaAccount::Organization { id, .. } if id == org.id) {
+            ensure_contract_quota(
+                &state,
+                &QuotaAccount::Organization {
+                    id: org.id,
+                    name: org.name.clone(),
+                },
+            )
+            .await?;
+        }
+    }
+
+    let contract: Contract = sqlx::query_as(
+        "UPDATE contracts SET is_private = $2, \
+             organization_id = COALESCE($3, organization_id), updated_at = NOW() \
+         WHERE id = $1 RETURNING *",
+    )
+    .bind(contract_uuid)
+    .bind(req.private)
+    .bind(organization.map(|org| org.id))
+    .fetch_one(&state.db)
+    .await
+    .map_err(|err| db_internal_error("update contract visibility", err))?;
+    crate::sparse_index::spawn_refresh(&state, contract_uuid);
+    Ok(Json(contract))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roles_default_to_member() {
+        assert_eq!(parse_role(None).unwrap(), ROLE_MEMBER);
+        assert_eq!(parse_role(Some("owner")).unwrap(), ROLE_OWNER);
+        assert!(parse_role(Some("admin")).is_err());
+    }
+
+    #[test]
+    fn organization_names_use_namespace_rules() {
+        assert!(validate_org_name("acme-labs").is_ok());
+        assert!(validate_org_name("Acme").is_err());
+        assert!(validate_org_name("-acme").is_err());
+    }
+}
@@ -0,0 +1,773 @@
+// patch_handlers.rs
+// Security patch creation, staged rollout, and second-maintainer approval for critical patches.
+
+use axum::{
+    extract::{rejection::JsonRejection, Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::Serialize;
+use shared::{
+    AffectedContract, AffectedDeployment, AlertSource, ApiScope, ApplyPatchRequest, ContractName,
+    ContractPatchEntry, ContractPatchHistory, CreatePatchRequest, DeploymentEnvironment,
+    DeploymentStatus, Network, PatchAffectedResponse, PatchApproval, PatchAudit, PatchDetail,
+    PatchSeverity, SecurityPatch, VersionRange,
+};
+use uuid::Uuid;
+
+use crate::error::{ApiError, ApiResult};
+use crate::handlers::{
+    db_internal_error, fetch_contract_identity, map_json_rejection, DryRunQuery,
+};
+use crate::private_registry::{ensure_contract_visible, Viewer};
+use crate::settings::PatchSettings;
+use crate::state::AppState;
+use crate::tenancy::CurrentTenant;
+use crate::user_auth::AuthUser;
+
+/// Share of eligible contracts a critical patch may reach before a second
+/// maintainer has to approve it
+const CRITICAL_UNAPPROVED_ROLLOUT_PERCENT: i64 = 10;
+const MAX_TARGET_VERSION_LENGTH: usize = 100;
+
+/// Accounts allowed to create, approve and apply patches (`[patches]`).
+/// The `patch-admin` scope only narrows API keys; session tokens carry every
+/// scope, so the account itself has to be on this list.
+#[derive(Debug, Default)]
+pub struct PatchMaintainers {
+    addresses: BTreeSet<String>,
+}
+
+impl PatchMaintainers {
+    pub fn from_settings(settings: &PatchSettings) -> Self {
+        Self {
+            addresses: settings.maintainers.iter().cloned().collect(),
+        }
+    }
+
+    /// Reject callers who are not maintainers, or API keys without `patch-admin`
+    fn ensure(&self, user: &AuthUser) -> ApiResult<()> {
+        user.require(ApiScope::PatchAdmin)?;
+        if !self.addresses.contains(&user.address) {
+            return Err(ApiError::new(
+                StatusCode::FORBIDDEN,
+                "NotPatchMaintainer",
+                "Only accounts listed in patches.maintainers can manage security patches",
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum RolloutBlock {
+    QuotaExceeded { max_allowed: i64 },
+    ApprovalRequired,
+}
+
+/// Decide whether one more contract may receive the patch, given how many
+/// already have it out of `eligible` contracts on the target version.
+fn check_rollout(
+    severity: PatchSeverity,
+    rollout_percentage: i32,
+    applied: i64,
+    eligible: i64,
+    approved: bool,
+) -> Result<(), RolloutBlock> {
+    let max_allowed = (eligible * rollout_percentage as i64 + 99) / 100;
+    if applied >= max_allowed {
+        return Err(RolloutBlock::QuotaExceeded { max_allowed });
+    }
+    if severity == PatchSeverity::Critical
+        && !approved
+        && (applied + 1) * 100 > eligible * CRITICAL_UNAPPROVED_ROLLOUT_PERCENT
+    {
+        return Err(RolloutBlock::ApprovalRequired);
+    }
+    Ok(())
+}
+
+fn validate_patch(req: &CreatePatchRequest) -> ApiResult<()> {
+    let version = req.target_version.trim();
+    if version.is_empty() || version.len() > MAX_TARGET_VERSION_LENGTH {
+        return Err(ApiError::bad_request(
+            "InvalidPatch",
+            format!(
+                "target_version must be 1-{} characters",
+                MAX_TARGET_VERSION_LENGTH
+            ),
+        ));
+    }
+    if VersionRange::parse(version).is_none() {
+        return Err(ApiError::bad_request(
+            "InvalidPatch",
+            "target_version must be a SemVer version or range, e.g. \">=1.0.0, <1.4.2\"",
+        ));
+    }
+    if req.new_wasm_hash.len() != 64 || !req.new_wasm_hash.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(ApiError::bad_request(
+            "InvalidPatch",
+            "new_wasm_hash must be 64 hex characters",
+        ));
+    }
+    if !(0..=100).contains(&req.rollout_percentage) {
+        return Err(ApiError::bad_request(
+            "InvalidPatch",
+            "rollout_percentage must be 0-100",
+        ));
+    }
+    Ok(())
+}
+
+/// Versions a patch covers. Patches created before ranges were accepted may
+/// name a version that is not SemVer; those still match it literally.
+enum PatchTarget {
+    Range(VersionRange),
+    Literal(String),
+}
+
+impl PatchTarget {
+    fn parse(target_version: &str) -> Self {
+        VersionRange::parse(target_version)
+            .map(PatchTarget::Range)
+            .unwrap_or_else(|| PatchTarget::Literal(target_version.to_string()))
+    }
+
+    fn matches(&self, version: &str) -> bool {
+        match self {
+            PatchTarget::Range(range) => range.matches_str(version),
+            PatchTarget::Literal(literal) => literal == version,
+        }
+    }
+}
+
+/// Published versions of the tenant's contracts inside the patch's target, as
+/// (version, wasm_hash) oldest first, keyed by contract
+async fn affected_versions(
+    executor: impl sqlx::PgExecutor<'_>,
+    tenant_id: Uuid,
+    target_version: &str,
+) -> ApiResult<BTreeMap<Uuid, Vec<(String, String)>>> {
+    let rows: Vec<(Uuid, String, String)> = sqlx::query_as(
+        "SELECT v.contract_id, v.version, v.wasm_hash \
+         FROM contract_versions v JOIN contracts c ON c.id = v.contract_id \
+         WHERE c.tenant_id = $1 \
+         ORDER BY v.created_at",
+    )
+    .bind(tenant_id)
+    .fetch_all(executor)
+    .await
+    .map_err(|err| db_internal_error("list versions for patch", err))?;
+
+    let target = PatchTarget::parse(target_version);
+    let mut affected: BTreeMap<Uuid, Vec<(String, String)>> = BTreeMap::new();
+    for (contract_id, version, wasm_hash) in rows {
+        if target.matches(&version) {
+            affected
+                .entry(contract_id)
+                .or_default()
+                .push((version, wasm_hash));
+        }
+    }
+    Ok(affected)
+}
+
+/// Deployments whose WASM is one of the affected versions
+fn affected_deployments(
+    versions: &[(String, String)],
+    deployments: Vec<(DeploymentEnvironment, DeploymentStatus, String)>,
+) -> Vec<AffectedDeployment> {
+    deployments
+        .into_iter()
+        .filter_map(|(environment, status, wasm_hash)| {
+            let (version, _) = versions.iter().rev().find(|(_, hash)| *hash == wasm_hash)?;
+            Some(AffectedDeployment {
+                environment,
+                status,
+                version: version.clone(),
+                wasm_hash,
+            })
+        })
+        .collect()
+}
+
+fn patch_not_found(id: Uuid) -> ApiError {
+    ApiError::not_found("PatchNotFound", format!("No patch found with ID: {}", id))
+}
+
+/// Patches of other tenants are reported as missing
+async fn fetch_patch(
+    executor: impl sqlx::PgExecutor<'_>,
+    id: Uuid,
+    tenant_id: Uuid,
+    lock: bool,
+) -> ApiResult<SecurityPatch> {
+    let sql = if lock {
+        "SELECT * FROM security_patches WHERE id = $1 AND tenant_id = $2 FOR UPDATE"
+    } else {
+        "SELECT * FROM security_patches WHERE id = $1 AND tenant_id = $2"
+    };
+    sqlx::query_as(sql)
+        .bind(id)
+        .bind(tenant_id)
+        .fetch_optional(executor)
+        .await
+        .map_err(|err| db_internal_error("fetch patch", err))?
+        .ok_or_else(|| patch_not_found(id))
+}
+
+async fn fetch_approvals(
+    executor: impl sqlx::PgExecutor<'_>,
+    id: Uuid,
+) -> ApiResult<Vec<PatchApproval>> {
+    sqlx::query_as("SELECT * FROM patch_approvals WHERE patch_id = $1 ORDER BY created_at")
+        .bind(id)
+        .fetch_all(executor)
+        .await
+        .map_err(|err| db_internal_error("list patch approvals", err))
+}
+
+/// An approval counts only when it comes from someone other than the patch author
+fn has_independent_approval(patch: &SecurityPatch, approvals: &[PatchApproval]) -> bool {
+    approvals
+        .iter()
+        .any(|a| patch.created_by.as_deref() != Some(a.approver_address.as_str()))
+}
+
+/// POST /api/patches
+pub async fn create_patch(
+    State(state): State<AppState>,
+    tenant: CurrentTenant,
+    user: AuthUser,
+    payload: Result<Json<CreatePatchRequest>, JsonRejection>,
+) -> ApiResult<(StatusCode, Json<SecurityPatch>)> {
+    state.patch_maintainers.ensure(&user)?;
+    let Json(req) = payload.map_err(map_json_rejection)?;
+    validate_patch(&req)?;
+
+    let mut tx = state
+        .db
+        .begin()
+        .await
+        .map_err(|err| db_internal_error("begin patch create", err))?;
+    let patch: SecurityPatch = sqlx::query_as(
+        "INSERT INTO security_patches \
+             (target_version, severity, new_wasm_hash, rollout_percentage, description, \
+              created_by, tenant_id) \
+         VALUES ($1, $2, LOWER($3), $4, $5, $6, $7) \
+         RETURNING *",
+    )
+    .bind(req.target_version.trim())
+    .bind(req.severity)
+    .bind(&req.new_wasm_hash)
+    .bind(req.rollout_percentage)
+    .bind(&req.description)
+    .bind(&user.address)
+    .bind(tenant.id)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|err| db_internal_error("create patch", err))?;
+
+    // Critical patches also alert everyone depending on a patched version
+    let mut alerts = Vec::new();
+    if patch.severity == PatchSeverity::Critical {
+        let vulnerable: BTreeMap<Uuid, Vec<String>> =
+            affected_versions(&mut *tx, tenant.id, &patch.target_version)
+                .await?
+                .into_iter()
+                .map(|(contract, versions)| {
+                    (contract, versions.into_iter().map(|(v, _)| v).collect())
+                })
+                .collect();
+        let summary = format!(
+            "Critical patch for versions {}: {}",
+            patch.target_version,
+            patch
+                .description
+                .as_deref()
+                .unwrap_or("upgrade to the patched WASM")
+        );
+        alerts = crate::alert_handlers::raise_alerts(
+            &mut tx,
+            AlertSource::Patch,
+            patch.id,
+            patch.severity,
+            &summary,
+            &vulnerable,
+        )
+        .await?;
+    }
+    tx.commit()
+        .await
+        .map_err(|err| db_internal_error("commit patch create", err))?;
+    if !alerts.is_empty() {
+        tokio::spawn(crate::alert_handlers::deliver_alerts(state.clone(), alerts));
+    }
+
+    Ok((StatusCode::CREATED, Json(patch)))
+}
+
+/// GET /api/patches/:id
+pub async fn get_patch(
+    State(state): State<AppState>,
+    tenant: CurrentTenant,
+    Path(id): Path<Uuid>,
+) -> ApiResult<Json<PatchDetail>> {
+    let patch = fetch_patch(&state.db, id, tenant.id, false).await?;
+    let approvals = fetch_approvals(&state.db, id).await?;
+    let approval_pending =
+        patch.severity == PatchSeverity::Critical && !has_independent_approval(&patch, &approvals);
+    Ok(Json(PatchDetail {
+        patch,
+        approvals,
+        approval_pending,
+    }))
+}
+
+#[derive(Debug, Serialize)]
+pub struct PatchAuditsResponse {
+    pub items: Vec<PatchAudit>,
+    pub total: i64,
+}
+
+/// GET /api/patches/:id/audits — contracts the patch has been applied to
+pub async fn list_patch_audits(
+    State(state): State<AppState>,
+    tenant: CurrentTenant,
+    Path(id): Path<Uuid>,
+) -> ApiResult<Json<PatchAuditsResponse>> {
+    fetch_patch(&state.db, id, tenant.id, false).await?;
+    let items: Vec<PatchAudit> =
+        sqlx::query_as("SELECT * FROM patch_audits WHERE patch_id = $1 ORDER BY applied_at")
+            .bind(id)
+            .fetch_all(&state.db)
+            .await
+            .map_err(|err| db_internal_error("list patch audits", err))?;
+    let total = items.len() as i64;
+    Ok(Json(PatchAuditsResponse { items, total }))
+}
+
+#[derive(sqlx::FromRow)]
+struct AffectedContractRow {
+    id: Uuid,
+    contract_id: String,
+    namespace: Option<String>,
+    name: String,
+    network: Network,
+    publisher_address: String,
+}
+
+/// GET /api/patches/:id/affected — contracts with a published version in the
+/// patch's range, their publishers, and deployments running those versions
+pub async fn list_affected(
+    State(state): State<AppState>,
+    viewer: Viewer,
+    Path(id): Path<Uuid>,
+) -> ApiResult<Json<PatchAffectedResponse>> {
+    let patch = fetch_patch(&state.db, id, viewer.tenant_id, false).await?;
+    let mut versions =
+        affected_versions(&state.db, viewer.tenant_id, &patch.target_version).await?;
+    let contract_ids: Vec<Uuid> = versions.keys().copied().collect();
+
+    let contracts: Vec<AffectedContractRow> = sqlx::query_as(&format!(
+        "SELECT c.id, c.contract_id, c.namespace, c.name, c.network, \
+                p.stellar_address AS publisher_address \
+         FROM contracts c JOIN publishers p ON p.id = c.publisher_id \
+         WHERE c.id = ANY($1){} \
+         ORDER BY c.name, c.network",
+        viewer.visibility_clause("c.")
+    ))
+    .bind(&contract_ids)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("list affected contracts", err))?;
+
+    let rows: Vec<(Uuid, DeploymentEnvironment, DeploymentStatus, String)> = sqlx::query_as(
+        "SELECT contract_id, environment, status, wasm_hash FROM contract_deployments \
+         WHERE contract_id = ANY($1) ORDER BY environment",
+    )
+    .bind(&contract_ids)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("list affected deployments", err))?;
+    let mut deployments: BTreeMap<Uuid, Vec<_>> = BTreeMap::new();
+    for (contract_id, environment, status, wasm_hash) in rows {
+        deployments
+            .entry(contract_id)
+            .or_default()
+            .push((environment, status, wasm_hash));
+    }
+
+    let items: Vec<AffectedContract> = contracts
+        .into_iter()
+        .map(|row| {
+            let matched = versions.remove(&row.id).unwrap_or_default();
+            AffectedContract {
+                deployments: affected_deployments(
+                    &matched,
+                    deployments.remove(&row.id).unwrap_or_default(),
+                ),
+                versions: matched.into_iter().map(|(version, _)| version).collect(),
+                id: row.id,
+                contract_id: row.contract_id,
+                name: ContractName {
+                    namespace: row.namespace,
+                    name: row.name,
+                }
+                .to_string(),
+                network: row.network,
+                publisher_address: row.publisher_address,
+            }
+        })
+        .collect();
+
+    let total_deployments = items.iter().map(|item| item.deployments.len()).sum();
+    Ok(Json(PatchAffectedResponse {
+        patch_id: id,
+        target_version: patch.target_version,
+        total_contracts: items.len(),
+        total_deployments,
+        items,
+    }))
+}
+
+/// GET /api/contracts/:id/patches — which patches reached the contract, when,
+/// by whom and at what point of each rollout
+pub async fn list_contract_patches(
+    State(state): State<AppState>,
+    viewer: Viewer,
+    Path(id): Path<String>,
+) -> ApiResult<Json<ContractPatchHistory>> {
+    let (contract_uuid, _) = fetch_contract_identity(&state, viewer.tenant_id, &id).await?;
+    ensure_contract_visible(&state, &viewer, contract_uuid).await?;
+
+    let items: Vec<ContractPatchEntry> = sqlx::query_as(
+        "SELECT a.patch_id, p.target_version, p.severity, p.new_wasm_hash, p.description, \
+                p.rollout_percentage, a.applied_at, a.applied_by, a.rollout_position, \
+                a.eligible_contracts \
+         FROM patch_audits a JOIN security_patches p ON p.id = a.patch_id \
+         WHERE a.contract_id = $1 \
+         ORDER BY a.applied_at",
+    )
+    .bind(contract_uuid)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("list contract patches", err))?;
+
+    let total = items.len();
+    Ok(Json(ContractPatchHistory {
+        contract_id: contract_uuid,
+        items,
+        total,
+    }))
+}
+
+/// POST /api/patches/:id/approve — second-maintainer sign-off on a critical patch
+pub async fn approve_patch(
+    State(state): State<AppState>,
+    tenant: CurrentTenant,
+    user: AuthUser,
+    Path(id): Path<Uuid>,
+) -> ApiResult<(StatusCode, Json<PatchApproval>)> {
+    state.patch_maintainers.ensure(&user)?;
+    let patch = fetch_patch(&state.db, id, tenant.id, false).await?;
+    if patch.severity != PatchSeverity::Critical {
+        return Err(ApiError::unprocessable(
+            "ApprovalNotRequired",
+            "Only critical patches need a second maintainer's approval",
+        ));
+    }
+    if patch.created_by.as_deref() == Some(user.address.as_str()) {
+        return Err(ApiError::new(
+            StatusCode::FORBIDDEN,
+            "SelfApprovalNotAllowed",
+            "A patch must be approved by a maintainer other than its author",
+        ));
+    }
+
+    let approval: PatchApproval = sqlx::query_as(
+        "INSERT INTO patch_approvals (patch_id, approver_address) VALUES ($1, $2) \
+         ON CONFLICT (patch_id, approver_address) DO NOTHING \
+         RETURNING *",
+    )
+    .bind(id)
+    .bind(&user.address)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|err| db_internal_error("approve patch", err))?
+    .ok_or_else(|| ApiError::conflict("AlreadyApproved", "You have already approved this patch"))?;
+
+    tracing::info!(patch_id = %id, approver = %user.address, "critical patch approved");
+    Ok((StatusCode::CREATED, Json(approval)))
+}
+
+/// POST /api/patches/:id/apply — record the patch on one contract, within the
+/// rollout quota and, for critical patches, the approval gate
+pub async fn apply_patch(
+    State(state): State<AppState>,
+    tenant: CurrentTenant,
+    user: AuthUser,
+    Path(id): Path<Uuid>,
+    Query(params): Query<DryRunQuery>,
+    payload: Result<Json<ApplyPatchRequest>, JsonRejection>,
+) -> ApiResult<(StatusCode, Json<PatchAudit>)> {
+    state.patch_maintainers.ensure(&user)?;
+    let Json(req) = payload.map_err(map_json_rejection)?;
+
+    let mut tx = state
+        .db
+        .begin()
+        .await
+        .map_err(|err| db_internal_error("begin patch apply", err))?;
+
+    // Lock the patch so concurrent applies cannot overshoot the quota together
+    let patch = fetch_patch(&mut *tx, id, tenant.id, true).await?;
+
+    let affected = affected_versions(&mut *tx, tenant.id, &patch.target_version).await?;
+    if !affected.contains_key(&req.contract_id) {
+        return Err(ApiError::unprocessable(
+            "ContractNotAffected",
+            format!(
+                "Contract {} has no release matching {}",
+                req.contract_id, patch.target_version
+            ),
+        ));
+    }
+    let eligible = affected.len() as i64;
+
+    let applied: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM patch_audits WHERE patch_id = $1")
+        .bind(id)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|err| db_internal_error("count patch rollout", err))?;
+
+    let approvals = fetch_approvals(&mut *tx, id).await?;
+    let approved = has_independent_approval(&patch, &approvals);
+    match check_rollout(
+        patch.severity,
+        patch.rollout_percentage,
+        applied,
+        eligible,
+        approved,
+    ) {
+        Ok(()) => {}
+        Err(RolloutBlock::QuotaExceeded { max_allowed }) => {
+            return Err(ApiError::conflict(
+                "RolloutQuotaExceeded",
+                format!(
+                    "Rollout quota reached: {}/{} ({}% of {} eligible)",
+                    applied, max_allowed, patch.rollout_percentage, eligible
+                ),
+            ));
+        }
+        Err(RolloutBlock::ApprovalRequired) => {
+            return Err(ApiError::new(
+                StatusCode::FORBIDDEN,
+                "PatchApprovalRequired",
+                format!(
+                    "Critical patches need a second maintainer's approval beyond {}% rollout; \
+                     run `patch approve --patch-id {}` from another account",
+                    CRITICAL_UNAPPROVED_ROLLOUT_PERCENT, id
+                ),
+            ));
+        }
+    }
+
+    let audit: PatchAudit = sqlx::query_as(
+        "INSERT INTO patch_audits \
+             (contract_id, patch_id, applied_by, rollout_position, eligible_contracts) \
+         VALUES ($1, $2, $3, $4, $5) \
+         ON CONFLICT (contract_id, patch_id) DO NOTHING \
+         RETURNING *",
+    )
+    .bind(req.contract_id)
+    .bind(id)
+    .bind(&user.address)
+    .bind((applied + 1) as i32)
+    .bind(eligible as i32)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|err| db_internal_error("apply patch", err))?
+    .ok_or_else(|| {
+        ApiError::conflict(
+            "PatchAlreadyApplied",
+            "This patch has already been applied to the contract",
+        )
+    })?;
+
+    if params.dry_run {
+        // Dropping the transaction rolls the audit row back
+        return Ok((StatusCode::OK, Json(audit)));
+    }
+    tx.commit()
+        .await
+        .map_err(|err| db_internal_error("commit patch apply", err))?;
+    Ok((StatusCode::CREATED, Json(audit)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn critical_patches_stop_at_the_unapproved_limit() {
+        let critical = PatchSeverity::Critical;
+        assert_eq!(check_rollout(critical, 100, 9, 100, false), Ok(()));
+        assert_eq!(
+            check_rollout(critical, 100, 10, 100, false),
+            Err(RolloutBlock::ApprovalRequired)
+        );
+        assert_eq!(check_rollout(critical, 100, 10, 100, true), Ok(()));
+        // Below ten eligible contracts even the first one needs approval
+        assert_eq!(
+            check_rollout(critical, 100, 0, 5, false),
+            Err(RolloutBlock::ApprovalRequired)
+        );
+    }
+
+    #[test]
+    fn other_severities_only_respect_the_quota() {
+        assert_eq!(
+            check_rollout(PatchSeverity::High, 100, 50, 100, false),
+            Ok(())
+        );
+        assert_eq!(
+            check_rollout(PatchSeverity::Low, 50, 2, 3, false),
+            Err(RolloutBlock::QuotaExceeded { max_allowed: 2 })
+        );
+        assert_eq!(
+            check_rollout(PatchSeverity::Critical, 50, 50, 100, true),
+            Err(RolloutBlock::QuotaExceeded { max_allowed: 50 })
+        );
+    }
+
+    #[test]
+    fn targets_ranges_and_legacy_literals() {
+        let range = PatchTarget::parse(">=1.0.0, <1.4.2");
+        assert!(range.matches("1.2.0"));
+        assert!(!range.matches("1.4.2"));
+        let literal = PatchTarget::parse("v1-beta");
+        assert!(literal.matches("v1-beta"));
+        assert!(!literal.matches("1.0.0"));
+    }
+
+    #[test]
+    fn deployments_match_affected_wasm() {
+        let versions = vec![
+            ("1.0.0".to_string(), "aa".to_string()),
+            ("1.1.0".to_string(), "bb".to_string()),
+        ];
+        let deployments = affected_deployments(
+            &versions,
+            vec![
+                (
+                    DeploymentEnvironment::Blue,
+                    DeploymentStatus::Active,
+                    "bb".to_string(),
+                ),
+                (
+                    DeploymentEnvironment::Green,
+                    DeploymentStatus::Testing,
+                    "cc".to_string(),
+                ),
+            ],
+        );
+        assert_eq!(deployments.len(), 1);
+        assert_eq!(deployments[0].version, "1.1.0");
+        assert_eq!(deployments[0].environment, DeploymentEnvironment::Blue);
+    }
+
+    #[test]
+    fn self_approval_does_not_count() {
+        let patch = SecurityPatch {
+            id: Uuid::nil(),
+            target_version: "1.0.0".to_string(),
+            severity: PatchSeverity::Critical,
+            new_wasm_hash: "a".repeat(64),
+            rollout_percentage: 100,
+            description: None,
+            created_by: Some("GAUTHOR".to_string()),
+            created_at: chrono::Utc::now(),
+        };
+        let approval = |address: &str| PatchApproval {
+            id: Uuid::new_v4(),
+            patch_id: Uuid::nil(),
+            approver_address: address.to_string(),
+            created_at: chrono::Utc::now(),
+        };
+        assert!(!has_independent_approval(&patch, &[approval("GAUTHOR")]));
+        assert!(has_independent_approval(&patch, &[approval("GREVIEWER")]));
+    }
+
+    fn maintainer_state(maintainers: &[&str]) -> AppState {
+        let db = sqlx::postgres::PgPoolOptions::new()
+            .connect_lazy("postgres://localhost/unused")
+            .expect("lazy pool");
+        let settings = PatchSettings {
+            maintainers: maintainers.iter().map(|a| a.to_string()).collect(),
+        };
+        AppState::new(
+            db,
+            prometheus::Registry::new(),
+            std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false)),
+        )
+        .with_patch_maintainers(PatchMaintainers::from_settings(&settings))
+    }
+
+    fn session(address: &str) -> AuthUser {
+        AuthUser {
+            address: address.to_string(),
+            scopes: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn session_tokens_of_non_maintainers_cannot_manage_patches() {
+        let state = maintainer_state(&["GMAINTAINER"]);
+        let err = approve_patch(
+            State(state.clone()),
+            CurrentTenant::default(),
+            session("GOUTSIDER"),
+            Path(Uuid::new_v4()),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.code(), shared::ErrorCode::from("NotPatchMaintainer"));
+
+        let err = apply_patch(
+            State(state),
+            CurrentTenant::default(),
+            session("GOUTSIDER"),
+            Path(Uuid::new_v4()),
+            Query(DryRunQuery::default()),
+            Ok(Json(ApplyPatchRequest {
+                contract_id: Uuid::new_v4(),
+            })),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.code(), shared::ErrorCode::from("NotPatchMaintainer"));
+    }
+
+    #[test]
+    fn maintainers_still_need_the_scope_on_api_keys() {
+        let maintainers = PatchMaintainers::from_settings(&PatchSettings {
+            maintainers: vec!["GMAINTAINER".to_string()],
+        });
+        assert!(maintainers.ensure(&session("GMAINTAINER")).is_ok());
+        let key = AuthUser {
+            address: "GMAINTAINER".to_string(),
+            scopes: Some(vec![ApiScope::Read]),
+        };
+        assert_eq!(
+            maintainers.ensure(&key).unwrap_err().code(),
+            shared::ErrorCode::from("InsufficientScope")
+        );
+        // Nobody manages patches until maintainers are configured
+        assert!(PatchMaintainers::default()
+            .ensure(&session("GMAINTAINER"))
+            .is_err());
+    }
+}
@@ -0,0 +1,126 @@
+// precondition.rs
+// ETag / If-Match optimistic concurrency for contract updates, so concurrent
+// edits get 412 Precondition Failed instead of overwriting each other.
+
+use axum::{
+    http::{header, HeaderMap, HeaderName, StatusCode},
+    Json,
+};
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::error::{ApiError, ApiResult};
+use crate::handlers::db_internal_error;
+use crate::state::AppState;
+
+/// A JSON body sent with the `ETag` of the contract it describes
+pub type WithEtag<T> = ([(HeaderName, String); 1], Json<T>);
+
+/// Strong validator derived from `contracts.updated_at`, which every update bumps
+pub fn contract_etag(updated_at: DateTime<Utc>) -> String {
+    format!("\"{:x}\"", updated_at.timestamp_micros())
+}
+
+pub fn with_etag<T>(updated_at: DateTime<Utc>, body: T) -> WithEtag<T> {
+    ([(header::ETAG, contract_etag(updated_at))], Json(body))
+}
+
+/// Does an `If-Match` value (a list of entity tags, or `*`) match `etag`?
+/// If-Match uses strong comparison (RFC 9110 §13.1.1), so weak `W/` tags never match.
+fn if_match_allows(if_match: &str, etag: &str) -> bool {
+    if_match
+        .split(',')
+        .map(str::trim)
+        .any(|candidate| candidate == "*" || candidate == etag)
+}
+
+/// Enforce the request's `If-Match` header against the contract's current
+/// `updated_at`. Returns the timestamp to use as an `updated_at = $n` guard on
+/// the UPDATE when a precondition was given, so a write that lands between
+/// this check and the update is still caught.
+pub fn check_if_match(
+    headers: &HeaderMap,
+    current_updated_at: DateTime<Utc>,
+) -> ApiResult<Option<DateTime<Utc>>> {
+    let Some(value) = headers.get(header::IF_MATCH) else {
+        return Ok(None);
+    };
+    let if_match = value.to_str().map_err(|_| {
+        ApiError::bad_request("InvalidRequest", "If-Match must be a list of entity tags")
+    })?;
+    let etag = contract_etag(current_updated_at);
+    if !if_match_allows(if_match, &etag) {
+        return Err(precondition_failed(current_updated_at));
+    }
+    Ok(Some(current_updated_at))
+}
+
+/// 412 naming the current version, so the client can re-fetch and reapply its edit
+pub fn precondition_failed(current_updated_at: DateTime<Utc>) -> ApiError {
+    ApiError::new(
+        StatusCode::PRECONDITION_FAILED,
+        "PreconditionFailed",
+        format!(
+            "The contract was modified at {} (current ETag {}); fetch it again and reapply \
+             your changes",
+            current_updated_at.to_rfc3339(),
+            contract_etag(current_updated_at)
+        ),
+    )
+}
+
+/// The 412 for a guarded UPDATE that matched no row because another write got
+/// there between `check_if_match` and the update
+pub async fn concurrent_modification(state: &AppState, contract_id: Uuid) -> ApiError {
+    match sqlx::query_scalar("SELECT updated_at FROM contracts WHERE id = $1")
+        .bind(contract_id)
+        .fetch_one(&state.db)
+        .await
+    {
+        Ok(updated_at) => precondition_failed(updated_at),
+        Err(err) => db_internal_error("fetch contract after failed precondition", err),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+    use axum::response::IntoResponse;
+
+    fn headers(if_match: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::IF_MATCH, HeaderValue::from_str(if_match).unwrap());
+        headers
+    }
+
+    #[test]
+    fn matching_or_wildcard_if_match_passes() {
+        let now = Utc::now();
+        let etag = contract_etag(now);
+        assert_eq!(check_if_match(&headers(&etag), now).unwrap(), Some(now));
+        assert!(check_if_match(&headers(&format!("\"0\", {}", etag)), now).is_ok());
+        assert!(check_if_match(&headers("*"), now).is_ok());
+        assert_eq!(check_if_match(&HeaderMap::new(), now).unwrap(), None);
+    }
+
+    #[test]
+    fn weak_if_match_never_matches() {
+        let now = Utc::now();
+        let weak = format!("W/{}", contract_etag(now));
+        assert!(check_if_match(&headers(&weak), now).is_err());
+        assert!(check_if_match(&headers(&format!("\"0\", {}", weak)), now).is_err());
+    }
+
+    #[test]
+    fn stale_if_match_is_rejected_with_412() {
+        let earlier = Utc::now() - chrono::Duration::seconds(5);
+        let now = Utc::now();
+        let err = check_if_match(&headers(&contract_etag(earlier)), now).unwrap_err();
+        assert_eq!(err.code(), shared::ErrorCode::PreconditionFailed);
+        assert_eq!(
+            err.into_response().status(),
+            StatusCode::PRECONDITION_FAILED
+        );
+    }
+}
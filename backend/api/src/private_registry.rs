@@ -0,0 +1,207 @@
+// private_registry.rs
+// Access control for private deployments and private contracts: a middleware
+// that makes every endpoint require authentication when `server.private_mode`
+// is set, and the visibility rules that hide private contracts from non-members.
+
+use async_trait::async_trait;
+use axum::{
+    body::Body,
+    extract::{FromRequestParts, State},
+    http::{request::Parts, Method, Request, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use shared::{ApiScope, ModerationStatus};
+use uuid::Uuid;
+
+use crate::error::{ApiError, ApiResult};
+use crate::handlers::db_internal_error;
+use crate::state::AppState;
+use crate::tenancy::CurrentTenant;
+use crate::user_auth::authenticate;
+
+/// Paths reachable without credentials even on a private registry: probes,
+/// signing in, and the CLI's self-update check
+const PUBLIC_PATH_PREFIXES: &[&str] = &["/health", "/api/auth/", "/api/cli/releases/latest"];
+
+fn is_public_path(path: &str) -> bool {
+    PUBLIC_PATH_PREFIXES
+        .iter()
+        .any(|prefix| path.starts_with(prefix))
+}
+
+/// Rejects anonymous callers when the registry runs in private mode. Reads
+/// additionally need the `read` scope when made with an API key.
+pub async fn private_mode_middleware(
+    State(state): State<AppState>,
+    request: Request<Body>,
+    next: Next,
+) -> Response {
+    if !state.private_mode || is_public_path(request.uri().path()) {
+        return next.run(request).await;
+    }
+
+    let tenant = CurrentTenant::of(request.extensions());
+    let user = match authenticate(&state, request.headers(), &tenant).await {
+        Ok(Some(user)) => user,
+        Ok(None) => {
+            return ApiError::new(
+                StatusCode::UNAUTHORIZED,
+                "Unauthorized",
+                "This registry is private; sign in with /api/auth/challenge or send an API key",
+            )
+            .into_response()
+        }
+        Err(err) => return err.into_response(),
+    };
+    if matches!(*request.method(), Method::GET | Method::HEAD) {
+        if let Err(err) = user.require(ApiScope::Read) {
+            return err.into_response();
+        }
+    }
+    next.run(request).await
+}
+
+/// Who is asking, for deciding which private contracts they may see.
+/// Anonymous callers are allowed; an invalid bearer token is still rejected.
+#[derive(Debug, Clone, Default)]
+pub struct Viewer {
+    pub address: Option<String>,
+    /// Contracts of other tenants are never visible
+    pub tenant_id: Uuid,
+}
+
+impl Viewer {
+    /// SQL condition (starting with ` AND`) keeping only contracts this viewer
+    /// may see. `alias` prefixes the contract columns, e.g. `"c."`. Rows from
+    /// an outer join with no contract are kept. Quarantined and taken-down
+    /// contracts are left out for everyone.
+    pub fn visibility_clause(&self, alias: &str) -> String {
+        let tenant = format!(
+            " AND COALESCE({a}tenant_id, '{id}') = '{id}' \
+             AND COALESCE({a}moderation_status, 'active') = 'active'",
+            a = alias,
+            id = self.tenant_id
+        );
+        match &self.address {
+            None => format!("{} AND {}is_private IS NOT TRUE", tenant, alias),
+            Some(address) => format!(
+                "{} AND ({a}is_private IS NOT TRUE OR {a}organization_id IN \
+                 (SELECT organization_id FROM organization_members WHERE address = '{}'))",
+                tenant,
+                address.replace('\'', "''"),
+                a = alias
+            ),
+        }
+    }
+}
+
+#[async_trait]
+impl FromRequestParts<AppState> for Viewer {
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &AppState) -> Result<Self, ApiError> {
+        let tenant = CurrentTenant::of(&parts.extensions);
+        let user = authenticate(state, &parts.headers, &tenant).await?;
+        Ok(Viewer {
+            address: user.map(|u| u.address),
+            tenant_id: tenant.id,
+        })
+    }
+}
+
+/// 404 unless `viewer` may see the contract; private contracts and those of
+/// other tenants are reported as missing so their existence is not revealed.
+/// Contracts taken down by moderators are 410 Gone.
+pub async fn ensure_contract_visible(
+    state: &AppState,
+    viewer: &Viewer,
+    contract_uuid: Uuid,
+) -> ApiResult<()> {
+    let visible: Option<(bool, ModerationStatus)> = sqlx::query_as(
+        "SELECT NOT c.is_private OR EXISTS (\
+             SELECT 1 FROM organization_members m \
+             WHERE m.organization_id = c.organization_id AND m.address = $2), \
+                c.moderation_status \
+         FROM contracts c WHERE c.id = $1 AND c.tenant_id = $3",
+    )
+    .bind(contract_uuid)
+    .bind(viewer.address.as_deref())
+    .bind(viewer.tenant_id)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|err| db_internal_error("check contract visibility", err))?;
+    match visible {
+        Some((true, ModerationStatus::TakenDown)) => Err(ApiError::new(
+            StatusCode::GONE,
+            "ContractTakenDown",
+            format!(
+                "Contract {} was taken down by the registry's moderators",
+                contract_uuid
+            ),
+        )),
+        Some((true, _)) => Ok(()),
+        _ => Err(ApiError::not_found(
+            "ContractNotFound",
+            format!("No contract found with ID: {}", contract_uuid),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn probes_and_sign_in_stay_public() {
+        assert!(is_public_path("/health/ready"));
+        assert!(is_public_path("/api/auth/challenge"));
+        assert!(is_public_path("/api/cli/releases/latest"));
+        assert!(!is_public_path("/api/contracts"));
+        assert!(!is_public_path("/api/contracts/abc/abi"));
+    }
+
+    #[test]
+    fn anonymous_viewers_only_see_public_contracts() {
+        let clause = Viewer::default().visibility_clause("c.");
+        assert!(clause.ends_with(" AND c.is_private IS NOT TRUE"));
+    }
+
+    #[test]
+    fn members_see_their_organizations_private_contracts() {
+        let viewer = Viewer {
+            address: Some("G'X".to_string()),
+            ..Default::default()
+        };
+        let clause = viewer.visibility_clause("");
+        assert!(clause.contains(" AND (is_private IS NOT TRUE OR organization_id IN"));
+        assert!(clause.contains("address = 'G''X'"));
+    }
+
+    #[test]
+    fn viewers_only_see_their_tenants_contracts() {
+        let viewer = Viewer {
+            address: None,
+            tenant_id: Uuid::from_u128(7),
+        };
+        let clause = viewer.visibility_clause("c.");
+        assert!(clause.starts_with(
+            " AND COALESCE(c.tenant_id, '00000000-0000-0000-0000-000000000007') \
+             = '00000000-0000-0000-0000-000000000007'"
+        ));
+    }
+
+    #[test]
+    fn moderated_contracts_are_hidden_from_everyone() {
+        let member = Viewer {
+            address: Some("GMEMBER".to_string()),
+            ..Default::default()
+        };
+        for clause in [
+            Viewer::default().visibility_clause("c."),
+            member.visibility_clause("c."),
+        ] {
+            assert!(clause.contains(" AND COALESCE(c.moderation_status, 'active') = 'active'"));
+        }
+    }
+}
@@ -0,0 +1,271 @@
+// probe_handlers.rs
+// Liveness and readiness probes with per-dependency detail.
+
+use std::time::{Duration, Instant};
+
+use axum::{extract::State, http::StatusCode, Json};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use shared::{Network, RpcHealthStatus};
+use tokio::net::TcpStream;
+
+use crate::state::AppState;
+
+/// Upper bound on each dependency check so a hung dependency can't stall the probe
+const CHECK_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Pending verifications older than this mark the job queue as degraded
+const QUEUE_STALL_SECS: i64 = 15 * 60;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DependencyStatus {
+    Ok,
+    Degraded,
+    Down,
+    NotConfigured,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DependencyCheck {
+    pub name: &'static str,
+    pub status: DependencyStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latency_ms: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+impl DependencyCheck {
+    fn new(name: &'static str, status: DependencyStatus, started: Option<Instant>) -> Self {
+        Self {
+            name,
+            status,
+            latency_ms: started.map(|s| s.elapsed().as_millis() as u64),
+            detail: None,
+        }
+    }
+
+    fn with_detail(mut self, detail: impl Into<String>) -> Self {
+        self.detail = Some(detail.into());
+        self
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReadinessReport {
+    pub status: &'static str,
+    pub timestamp: String,
+    pub uptime_secs: u64,
+    pub checks: Vec<DependencyCheck>,
+}
+
+/// Ready unless shutting down or a configured dependency is down;
+/// degraded dependencies are reported but keep the pod in rotation
+fn is_ready(shutting_down: bool, checks: &[DependencyCheck]) -> bool {
+    !shutting_down && checks.iter().all(|c| c.status != DependencyStatus::Down)
+}
+
+/// Extract `host:port` from a URL, defaulting the port from the scheme
+fn socket_address(url: &str) -> Option<String> {
+    let (scheme, rest) = url.split_once("://").unwrap_or(("http", url));
+    let authority = rest.split(['/', '?', '#']).next()?;
+    let authority = authority.rsplit('@').next()?;
+    if authority.is_empty() {
+        return None;
+    }
+    let has_port = match authority.rfind(']') {
+        Some(end) => authority[end..].contains(':'),
+        None => authority.contains(':'),
+    };
+    if has_port {
+        return Some(authority.to_string());
+    }
+    let port = match scheme {
+        "https" | "wss" => 443,
+        _ => 80,
+    };
+    Some(format!("{}:{}", authority, port))
+}
+
+async fn check_database(state: &AppState) -> DependencyCheck {
+    let started = Instant::now();
+    let query = sqlx::query_scalar::<_, i32>("SELECT 1").fetch_one(&state.db);
+    match tokio::time::timeout(CHECK_TIMEOUT, query).await {
+        Ok(Ok(_)) => DependencyCheck::new("database", DependencyStatus::Ok, Some(started)),
+        Ok(Err(err)) => DependencyCheck::new("database", DependencyStatus::Down, Some(started))
+            .with_detail(err.to_string()),
+        Err(_) => DependencyCheck::new("database", DependencyStatus::Down, Some(started))
+            .with_detail("timed out"),
+    }
+}
+
+/// TCP reachability of an endpoint configured through `env_var`
+async fn check_endpoint(name: &'static str, env_var: &str) -> DependencyCheck {
+    let Some(url) = std::env::var(env_var).ok().filter(|v| !v.is_empty()) else {
+        return DependencyCheck::new(name, DependencyStatus::NotConfigured, None)
+            .with_detail(format!("{} is not set", env_var));
+    };
+    let Some(address) = socket_address(&url) else {
+        return DependencyCheck::new(name, DependencyStatus::Down, None)
+            .with_detail(format!("{} is not a valid URL", env_var));
+    };
+
+    let started = Instant::now();
+    match tokio::time::timeout(CHECK_TIMEOUT, TcpStream::connect(&address)).await {
+        Ok(Ok(_)) => DependencyCheck::new(name, DependencyStatus::Ok, Some(started)),
+        Ok(Err(err)) => DependencyCheck::new(name, DependencyStatus::Down, Some(started))
+            .with_detail(format!("{}: {}", address, err)),
+        Err(_) => DependencyCheck::new(name, DependencyStatus::Down, Some(started))
+            .with_detail(format!("{}: timed out", address)),
+    }
+}
+
+/// Each network's Soroban RPC as the monitor last saw it. An RPC that is down
+/// only degrades readiness: every instance shares it, and the endpoints that
+/// need it fail fast with `RpcUnavailable` on their own.
+fn check_rpc(state: &AppState) -> Vec<DependencyCheck> {
+    state
+        .rpc
+        .health()
+        .into_iter()
+        .map(|health| {
+            let name = match health.network {
+                Network::Mainnet => "rpc_mainnet",
+                Network::Testnet => "rpc_testnet",
+                Network::Futurenet => "rpc_futurenet",
+            };
+            let status = match health.status {
+                RpcHealthStatus::Healthy => DependencyStatus::Ok,
+                _ => DependencyStatus::Degraded,
+            };
+            let detail = match (health.status, health.error) {
+                (RpcHealthStatus::Unknown, _) => "not probed yet".to_string(),
+                (RpcHealthStatus::Lagging, _) => format!(
+                    "ledger {} has not advanced for {}s",
+                    health.latest_ledger.unwrap_or_default(),
+                    health.ledger_lag_secs.unwrap_or_default()
+                ),
+                (_, Some(error)) => error,
+                (_, None) => format!("ledger {}", health.latest_ledger.unwrap_or_default()),
+            };
+            DependencyCheck {
+                name,
+                status,
+                latency_ms: health.latency_ms,
+                detail: Some(detail),
+            }
+        })
+        .collect()
+}
+
+/// The verification queue lives in Postgres; report its depth and flag stalls
+async fn check_job_queue(state: &AppState) -> DependencyCheck {
+    let started = Instant::now();
+    let query = sqlx::query_as::<_, (i64, Option<DateTime<Utc>>)>(
+        "SELECT COUNT(*), MIN(created_at) FROM verifications WHERE status = 'pending'",
+    )
+    .fetch_one(&state.db);
+
+    match tokio::time::timeout(CHECK_TIMEOUT, query).await {
+        Ok(Ok((depth, oldest))) => {
+            let stalled = oldest
+                .map(|ts| (Utc::now() - ts).num_seconds() > QUEUE_STALL_SECS)
+                .unwrap_or(false);
+            let status = if stalled {
+                DependencyStatus::Degraded
+            } else {
+                DependencyStatus::Ok
+            };
+            DependencyCheck::new("job_queue", status, Some(started))
+                .with_detail(format!("{} pending", depth))
+        }
+        Ok(Err(err)) => DependencyCheck::new("job_queue", DependencyStatus::Down, Some(started))
+            .with_detail(err.to_string()),
+        Err(_) => DependencyCheck::new("job_queue", DependencyStatus::Down, Some(started))
+            .with_detail("timed out"),
+    }
+}
+
+/// GET /health/live — the process is up and serving requests.
+/// Never touches dependencies, so a database blip doesn't restart the pod.
+pub async fn liveness(State(state): State<AppState>) -> Json<serde_json::Value> {
+    Json(serde_json::json!({
+        "status": "alive",
+        "uptime_secs": state.started_at.elapsed().as_secs(),
+    }))
+}
+
+/// GET /health/ready — whether this instance should receive traffic
+pub async fn readiness(State(state): State<AppState>) -> (StatusCode, Json<ReadinessReport>) {
+    let (database, object_store, job_queue) = tokio::join!(
+        check_database(&state),
+        check_endpoint("object_store", "OBJECT_STORE_URL"),
+        check_job_queue(&state),
+    );
+    let mut checks = vec![database, object_store, job_queue];
+    checks.extend(check_rpc(&state));
+
+    let shutting_down = state
+        .is_shutting_down
+        .load(std::sync::atomic::Ordering::SeqCst);
+    let ready = is_ready(shutting_down, &checks);
+    if !ready {
+        tracing::warn!(shutting_down, ?checks, "readiness check failing");
+    }
+
+    let status = if ready {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (
+        status,
+        Json(ReadinessReport {
+            status: if ready { "ready" } else { "not_ready" },
+            timestamp: Utc::now().to_rfc3339(),
+            uptime_secs: state.started_at.elapsed().as_secs(),
+            checks,
+        }),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn socket_address_defaults_port_from_scheme() {
+        assert_eq!(
+            socket_address("https://soroban-testnet.stellar.org").as_deref(),
+            Some("soroban-testnet.stellar.org:443")
+        );
+        assert_eq!(
+            socket_address("http://minio:9000/bucket").as_deref(),
+            Some("minio:9000")
+        );
+        assert_eq!(
+            socket_address("s3://user:pass@[::1]/x").as_deref(),
+            Some("[::1]:80")
+        );
+        assert_eq!(socket_address("https://"), None);
+    }
+
+    #[test]
+    fn degraded_dependencies_do_not_fail_readiness() {
+        let checks = vec![
+            DependencyCheck::new("database", DependencyStatus::Ok, None),
+            DependencyCheck::new("job_queue", DependencyStatus::Degraded, None),
+            DependencyCheck::new("rpc", DependencyStatus::NotConfigured, None),
+        ];
+        assert!(is_ready(false, &checks));
+        assert!(!is_ready(true, &checks));
+
+        let down = vec![DependencyCheck::new(
+            "database",
+            DependencyStatus::Down,
+            None,
+        )];
+        assert!(!is_ready(false, &down));
+    }
+}
@@ -0,0 +1,341 @@
+// provenance_handlers.rs
+// SLSA-style build provenance attestations per contract version.
+
+use axum::{
+    extract::{rejection::JsonRejection, Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use shared::{
+    decode_account_id, provenance_statement, statement_bytes, ApiScope, ProvenanceAttestation,
+    ProvenanceInput, ProvenanceQuery, ProvenanceResponse, SubmitProvenanceRequest,
+};
+use uuid::Uuid;
+
+use crate::error::{ApiError, ApiResult};
+use crate::handlers::{db_internal_error, fetch_contract_identity, map_json_rejection};
+use crate::private_registry::{ensure_contract_visible, Viewer};
+use crate::state::AppState;
+use crate::tenancy::CurrentTenant;
+use crate::user_auth::AuthUser;
+
+/// Verify the optional Ed25519 signature over the canonical statement against
+/// the publisher's account key; a `public_key` in the request must be that key.
+/// Returns `Ok(false)` for unsigned attestations and an error for a bad signature.
+fn verify_attestation(
+    statement: &serde_json::Value,
+    input: &ProvenanceInput,
+    publisher_key: Option<[u8; 32]>,
+) -> ApiResult<bool> {
+    let Some(sig) = input
+        .signature
+        .as_deref()
+        .map(str::trim)
+        .filter(|sig| !sig.is_empty())
+    else {
+        return Ok(false);
+    };
+
+    let key_bytes = publisher_key.ok_or_else(|| {
+        ApiError::unprocessable(
+            "InvalidPublicKey",
+            "The contract's publisher has no Stellar account key to verify the signature with",
+        )
+    })?;
+    if let Some(pk) = input
+        .public_key
+        .as_deref()
+        .map(str::trim)
+        .filter(|pk| !pk.is_empty())
+    {
+        if BASE64.decode(pk).ok().as_deref() != Some(key_bytes.as_slice()) {
+            return Err(ApiError::unprocessable(
+                "InvalidPublicKey",
+                "public_key is not the publisher's Stellar account key",
+            ));
+        }
+    }
+    let verifying_key = VerifyingKey::from_bytes(&key_bytes).map_err(|_| {
+        ApiError::unprocessable(
+            "InvalidPublicKey",
+            "The publisher's account key is not a valid Ed25519 key",
+        )
+    })?;
+
+    let sig_array: [u8; 64] = BASE64
+        .decode(sig)
+        .ok()
+        .and_then(|bytes| bytes.as_slice().try_into().ok())
+        .ok_or_else(|| {
+            ApiError::bad_request(
+                "InvalidSignature",
+                "signature must be a base64-encoded 64-byte Ed25519 signature",
+            )
+        })?;
+
+    verifying_key
+        .verify(
+            &statement_bytes(statement),
+            &Signature::from_bytes(&sig_array),
+        )
+        .map_err(|_| {
+            ApiError::unprocessable(
+                "InvalidSignature",
+                "Provenance signature does not match the attestation statement",
+            )
+        })?;
+
+    Ok(true)
+}
+
+/// The contract's publisher address and the key attestations are checked
+/// against: the verified Stellar account, else the publisher's own address
+pub(crate) async fn fetch_publisher_key(
+    executor: impl sqlx::PgExecutor<'_>,
+    contract_uuid: Uuid,
+) -> ApiResult<(String, Option<[u8; 32]>)> {
+    let (address, account): (String, String) = sqlx::query_as(
+        "SELECT p.stellar_address, COALESCE(p.verified_account, p.stellar_address) \
+         FROM contracts c JOIN publishers p ON p.id = c.publisher_id WHERE c.id = $1",
+    )
+    .bind(contract_uuid)
+    .fetch_one(executor)
+    .await
+    .map_err(|err| db_internal_error("fetch contract publisher key", err))?;
+    Ok((address, decode_account_id(&account)))
+}
+
+/// Build, verify and store an attestation for `version`, whose artifact hash is `wasm_hash`
+pub(crate) async fn store_attestation<'e, E>(
+    executor: E,
+    contract_uuid: Uuid,
+    contract_id: &str,
+    version: &str,
+    wasm_hash: &str,
+    input: &ProvenanceInput,
+    publisher_key: Option<[u8; 32]>,
+) -> ApiResult<ProvenanceAttestation>
+where
+    E: sqlx::PgExecutor<'e>,
+{
+    if input.builder_id.trim().is_empty()
+        || input.source_repo.trim().is_empty()
+        || input.source_commit.trim().is_empty()
+    {
+        return Err(ApiError::bad_request(
+            "InvalidProvenance",
+            "builder_id, source_repo and source_commit are required",
+        ));
+    }
+
+    let statement = provenance_statement(contract_id, version, wasm_hash, input);
+    let verified = verify_attestation(&statement, input, publisher_key)?;
+    // Record the key the signature was checked against, not the one sent
+    let public_key = publisher_key
+        .filter(|_| verified)
+        .map(|key| BASE64.encode(key));
+
+    sqlx::query_as(
+        "INSERT INTO contract_provenance \
+            (contract_id, version, subject_digest, builder_id, source_repo, source_commit, \
+             build_parameters, statement, signature, public_key, verified) \
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11) \
+         RETURNING *",
+    )
+    .bind(contract_uuid)
+    .bind(version)
+    .bind(wasm_hash)
+    .bind(input.builder_id.trim())
+    .bind(input.source_repo.trim())
+    .bind(input.source_commit.trim())
+    .bind(&input.build_parameters)
+    .bind(&statement)
+    .bind(&input.signature)
+    .bind(&public_key)
+    .bind(verified)
+    .fetch_one(executor)
+    .await
+    .map_err(|err| db_internal_error("insert provenance attestation", err))
+}
+
+async fn fetch_version_hash(
+    state: &AppState,
+    contract_uuid: Uuid,
+    version: Option<&str>,
+) -> ApiResult<Option<(String, String)>> {
+    match version {
+        Some(version) => {
+            sqlx::query_as(
+                "SELECT version, wasm_hash FROM contract_versions
+                 WHERE contract_id = $1 AND version = $2",
+            )
+            .bind(contract_uuid)
+            .bind(version)
+            .fetch_optional(&state.db)
+            .await
+        }
+        None => {
+            sqlx::query_as(
+                "SELECT version, wasm_hash FROM contract_versions
+                 WHERE contract_id = $1 ORDER BY created_at DESC LIMIT 1",
+            )
+            .bind(contract_uuid)
+            .fetch_optional(&state.db)
+            .await
+        }
+    }
+    .map_err(|err| db_internal_error("fetch contract version for provenance", err))
+}
+
+/// POST /api/contracts/:id/provenance — the contract's publisher attests a
+/// version; signatures are checked against the publisher's account key
+pub async fn submit_provenance(
+    State(state): State<AppState>,
+    tenant: CurrentTenant,
+    user: AuthUser,
+    Path(id): Path<String>,
+    payload: Result<Json<SubmitProvenanceRequest>, JsonRejection>,
+) -> ApiResult<(StatusCode, Json<ProvenanceAttestation>)> {
+    user.require(ApiScope::Publish)?;
+    let Json(req) = payload.map_err(map_json_rejection)?;
+    let (contract_uuid, contract_id) = fetch_contract_identity(&state, tenant.id, &id).await?;
+    let (publisher, publisher_key) = fetch_publisher_key(&state.db, contract_uuid).await?;
+    if publisher != user.address {
+        return Err(ApiError::new(
+            StatusCode::FORBIDDEN,
+            "Forbidden",
+            "Only the contract's publisher can attest its builds",
+        ));
+    }
+
+    let (version, wasm_hash) = fetch_version_hash(&state, contract_uuid, Some(&req.version))
+        .await?
+        .ok_or_else(|| {
+            ApiError::not_found(
+                "VersionNotFound",
+                format!("No version {} found for contract {}", req.version, id),
+            )
+        })?;
+
+    let attestation = store_attestation(
+        &state.db,
+        contract_uuid,
+        &contract_id,
+        &version,
+        &wasm_hash,
+        &req.provenance,
+        publisher_key,
+    )
+    .await?;
+
+    Ok((StatusCode::CREATED, Json(attestation)))
+}
+
+/// GET /api/contracts/:id/provenance?version=
+pub async fn get_provenance(
+    State(state): State<AppState>,
+    viewer: Viewer,
+    Path(id): Path<String>,
+    Query(query): Query<ProvenanceQuery>,
+) -> ApiResult<Json<ProvenanceResponse>> {
+    let (contract_uuid, contract_id) =
+        fetch_contract_identity(&state, viewer.tenant_id, &id).await?;
+    ensure_contract_visible(&state, &viewer, contract_uuid).await?;
+
+    let (version, wasm_hash) = fetch_version_hash(&state, contract_uuid, query.version.as_deref())
+        .await?
+        .ok_or_else(|| {
+            ApiError::not_found(
+                "VersionNotFound",
+                format!(
+                    "No version {} found for contract {}",
+                    query.version.as_deref().unwrap_or("(latest)"),
+                    id
+                ),
+            )
+        })?;
+
+    let attestations: Vec<ProvenanceAttestation> = sqlx::query_as(
+        "SELECT * FROM contract_provenance
+         WHERE contract_id = $1 AND version = $2 ORDER BY created_at DESC",
+    )
+    .bind(contract_uuid)
+    .bind(&version)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("fetch provenance attestations", err))?;
+
+    Ok(Json(ProvenanceResponse {
+        contract_id,
+        version,
+        wasm_hash,
+        attestations,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::{Signer, SigningKey};
+
+    fn input() -> ProvenanceInput {
+        ProvenanceInput {
+            builder_id: "ci".to_string(),
+            source_repo: "https://github.com/example/token".to_string(),
+            source_commit: "abc123".to_string(),
+            build_parameters: serde_json::json!({}),
+            signature: None,
+            public_key: None,
+        }
+    }
+
+    #[test]
+    fn unsigned_attestation_is_not_verified() {
+        let statement = provenance_statement("CABC", "1.0.0", "deadbeef", &input());
+        assert!(!verify_attestation(&statement, &input(), None).unwrap());
+    }
+
+    #[test]
+    fn signed_attestation_verifies_and_tampering_fails() {
+        let key = SigningKey::from_bytes(&[7u8; 32]);
+        let publisher_key = Some(key.verifying_key().to_bytes());
+        let statement = provenance_statement("CABC", "1.0.0", "deadbeef", &input());
+        let mut signed = input();
+        signed.signature = Some(BASE64.encode(key.sign(&statement_bytes(&statement)).to_bytes()));
+
+        assert!(verify_attestation(&statement, &signed, publisher_key).unwrap());
+
+        let other = provenance_statement("CABC", "1.0.0", "cafebabe", &input());
+        assert!(verify_attestation(&other, &signed, publisher_key).is_err());
+    }
+
+    #[test]
+    fn signatures_only_count_from_the_publisher_key() {
+        let publisher = SigningKey::from_bytes(&[7u8; 32]);
+        let stranger = SigningKey::from_bytes(&[9u8; 32]);
+        let statement = provenance_statement("CABC", "1.0.0", "deadbeef", &input());
+        let mut signed = input();
+        signed.signature =
+            Some(BASE64.encode(stranger.sign(&statement_bytes(&statement)).to_bytes()));
+        signed.public_key = Some(BASE64.encode(stranger.verifying_key().to_bytes()));
+
+        // Naming the stranger's key does not make their signature acceptable
+        let err = verify_attestation(
+            &statement,
+            &signed,
+            Some(publisher.verifying_key().to_bytes()),
+        )
+        .unwrap_err();
+        assert_eq!(err.code(), shared::ErrorCode::from("InvalidPublicKey"));
+
+        signed.public_key = None;
+        assert!(verify_attestation(
+            &statement,
+            &signed,
+            Some(publisher.verifying_key().to_bytes())
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,433 @@
+// publish_hooks.rs
+// Operator-defined checks run on a version's WASM before it is published. Rules
+// from `[[publish_hooks]]` are built in; deployments can register their own
+// `PublishHook` implementations alongside them.
+
+use std::sync::Arc;
+
+use shared::{HookSeverity, PublishHookFinding};
+
+use crate::error::ApiError;
+use crate::settings::PublishHookRule;
+
+const WASM_HEADER: &[u8] = b"\0asm\x01\0\0\0";
+const CUSTOM_SECTION: u8 = 0;
+const IMPORT_SECTION: u8 = 2;
+const EXPORT_SECTION: u8 = 7;
+
+/// The parts of a WebAssembly module hooks look at
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct WasmModule {
+    pub size_bytes: usize,
+    /// `(module, name)` of every import
+    pub imports: Vec<(String, String)>,
+    pub exports: Vec<String>,
+}
+
+/// Cursor over a module's bytes; every read fails cleanly on truncated input
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn byte(&mut self) -> Result<u8, String> {
+        let byte = *self
+            .bytes
+            .get(self.pos)
+            .ok_or_else(|| "unexpected end of module".to_string())?;
+        self.pos += 1;
+        Ok(byte)
+    }
+
+    fn u32(&mut self) -> Result<u32, String> {
+        let mut result = 0u32;
+        for shift in (0..35).step_by(7) {
+            let byte = self.byte()?;
+            result |= u32::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(result);
+            }
+        }
+        Err("integer too long".to_string())
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], String> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|end| *end <= self.bytes.len())
+            .ok_or_else(|| "unexpected end of module".to_string())?;
+        let slice = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(slice)
+    }
+
+    fn name(&mut self) -> Result<String, String> {
+        let len = self.u32()? as usize;
+        String::from_utf8(self.take(len)?.to_vec()).map_err(|_| "name is not UTF-8".to_string())
+    }
+
+    fn limits(&mut self) -> Result<(), String> {
+        let flags = self.byte()?;
+        self.u32()?;
+        if flags & 0x01 != 0 {
+            self.u32()?;
+        }
+        Ok(())
+    }
+}
+
+impl WasmModule {
+    /// Read the import and export sections; other sections are skipped
+    pub fn parse(bytes: &[u8]) -> Result<Self, String> {
+        if !bytes.starts_with(WASM_HEADER) {
+            return Err("not a WebAssembly 1.0 module".to_string());
+        }
+        let mut module = WasmModule {
+            size_bytes: bytes.len(),
+            ..Default::default()
+        };
+        let mut reader = Reader {
+            bytes,
+            pos: WASM_HEADER.len(),
+        };
+        while reader.pos < bytes.len() {
+            let id = reader.byte()?;
+            let len = reader.u32()? as usize;
+            let mut section = Reader {
+                bytes: reader.take(len)?,
+                pos: 0,
+            };
+            match id {
+                IMPORT_SECTION => {
+                    for _ in 0..section.u32()? {
+                        let import = (section.name()?, section.name()?);
+                        match section.byte()? {
+                            0x00 => {
+                                section.u32()?;
+                            }
+                            0x01 => {
+                                section.byte()?;
+                                section.limits()?;
+                            }
+                            0x02 => section.limits()?,
+                            0x03 => {
+                                section.take(2)?;
+                            }
+                            0x04 => {
+                                section.byte()?;
+                                section.u32()?;
+                            }
+                            kind => return Err(format!("unknown import kind {:#04x}", kind)),
+                        }
+                        module.imports.push(import);
+                    }
+                }
+                EXPORT_SECTION => {
+                    for _ in 0..section.u32()? {
+                        let name = section.name()?;
+                        section.byte()?;
+                        section.u32()?;
+                        module.exports.push(name);
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(module)
+    }
+
+    /// Contents of the first custom section called `name`, e.g. `contractspecv0`
+    pub fn custom_section<'a>(bytes: &'a [u8], name: &str) -> Result<Option<&'a [u8]>, String> {
+        if !bytes.starts_with(WASM_HEADER) {
+            return Err("not a WebAssembly 1.0 module".to_string());
+        }
+        let mut reader = Reader {
+            bytes,
+            pos: WASM_HEADER.len(),
+        };
+        while reader.pos < bytes.len() {
+            let id = reader.byte()?;
+            let len = reader.u32()? as usize;
+            let contents = reader.take(len)?;
+            if id == CUSTOM_SECTION {
+                let mut section = Reader {
+                    bytes: contents,
+                    pos: 0,
+                };
+                if section.name()? == name {
+                    return Ok(Some(&contents[section.pos..]));
+                }
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// A version about to be published
+pub struct PublishCandidate<'a> {
+    pub contract_id: &'a str,
+    /// Publisher namespace of `@namespace/name` contracts
+    pub namespace: Option<&'a str>,
+    pub version: &'a str,
+    pub publisher: &'a str,
+    pub wasm: &'a WasmModule,
+}
+
+/// A failed check, before the hook's name is attached
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    pub severity: HookSeverity,
+    pub message: String,
+}
+
+/// A check run on every published version. Return one violation per problem;
+/// an empty list passes.
+pub trait PublishHook: Send + Sync {
+    /// Reported with each finding, e.g. `max-wasm-size`
+    fn name(&self) -> &str;
+    fn check(&self, candidate: &PublishCandidate<'_>) -> Vec<Violation>;
+}
+
+impl PublishHook for PublishHookRule {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn check(&self, candidate: &PublishCandidate<'_>) -> Vec<Violation> {
+        if let Some(namespace) = &self.namespace {
+            if candidate.namespace != Some(namespace.trim_start_matches('@')) {
+                return Vec::new();
+            }
+        }
+        let wasm = candidate.wasm;
+        let mut problems = Vec::new();
+        if let Some(max) = self.max_wasm_bytes {
+            if wasm.size_bytes as u64 > max {
+                problems.push(format!(
+                    "WASM is {} bytes; the limit is {}",
+                    wasm.size_bytes, max
+                ));
+            }
+        }
+        for export in &self.required_exports {
+            if !wasm.exports.contains(export) {
+                problems.push(format!("missing required export `{}`", export));
+            }
+        }
+        for banned in &self.banned_imports {
+            let used = wasm
+                .imports
+                .iter()
+                .any(|(module, name)| match banned.split_once('.') {
+                    Some((banned_module, banned_name)) => {
+                        module == banned_module && name == banned_name
+                    }
+                    None => module == banned,
+                });
+            if used {
+                problems.push(format!("imports banned host function `{}`", banned));
+            }
+        }
+        problems
+            .into_iter()
+            .map(|problem| Violation {
+                severity: self.severity,
+                message: match &self.message {
+                    Some(message) => format!("{} ({})", message, problem),
+                    None => problem,
+                },
+            })
+            .collect()
+    }
+}
+
+/// The hooks of this deployment, run in registration order
+#[derive(Clone, Default)]
+pub struct PublishHooks {
+    hooks: Vec<Arc<dyn PublishHook>>,
+}
+
+impl PublishHooks {
+    /// The rules configured under `[[publish_hooks]]`
+    pub fn from_rules(rules: &[PublishHookRule]) -> Self {
+        let mut hooks = Self::default();
+        for rule in rules {
+            hooks.register(rule.clone());
+        }
+        hooks
+    }
+
+    /// Add a compiled-in hook
+    pub fn register(&mut self, hook: impl PublishHook + 'static) {
+        self.hooks.push(Arc::new(hook));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.hooks.is_empty()
+    }
+
+    /// Every hook's findings, in order
+    pub fn check(&self, candidate: &PublishCandidate<'_>) -> Vec<PublishHookFinding> {
+        self.hooks
+            .iter()
+            .flat_map(|hook| {
+                hook.check(candidate)
+                    .into_iter()
+                    .map(|violation| PublishHookFinding {
+                        hook: hook.name().to_string(),
+                        severity: violation.severity,
+                        message: violation.message,
+                    })
+            })
+            .collect()
+    }
+
+    /// Warnings to report with the new version, or an error listing every rejection
+    pub fn enforce(
+        &self,
+        candidate: &PublishCandidate<'_>,
+    ) -> Result<Vec<PublishHookFinding>, ApiError> {
+        let (rejections, warnings): (Vec<_>, Vec<_>) = self
+            .check(candidate)
+            .into_iter()
+            .partition(|finding| finding.severity == HookSeverity::Reject);
+        if rejections.is_empty() {
+            return Ok(warnings);
+        }
+        let reasons: Vec<String> = rejections
+            .iter()
+            .map(|finding| format!("[{}] {}", finding.hook, finding.message))
+            .collect();
+        Err(ApiError::unprocessable(
+            "PublishHookRejected",
+            format!("Rejected by publish policy: {}", reasons.join("; ")),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A module importing `x._` and `l.1`, exporting `hello` and `__constructor`
+    fn module_bytes() -> Vec<u8> {
+        let mut bytes = WASM_HEADER.to_vec();
+        let imports: &[u8] = &[
+            2, //
+            1, b'x', 1, b'_', 0x00, 0, //
+            1, b'l', 1, b'1', 0x00, 0,
+        ];
+        bytes.extend([IMPORT_SECTION, imports.len() as u8]);
+        bytes.extend(imports);
+        let mut exports = vec![2, 5];
+        exports.extend(b"hello");
+        exports.extend([0x00, 0, 13]);
+        exports.extend(b"__constructor");
+        exports.extend([0x00, 1]);
+        bytes.extend([EXPORT_SECTION, exports.len() as u8]);
+        bytes.extend(exports);
+        bytes
+    }
+
+    fn rule(name: &str) -> PublishHookRule {
+        PublishHookRule {
+            name: name.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn candidate<'a>(wasm: &'a WasmModule, namespace: Option<&'a str>) -> PublishCandidate<'a> {
+        PublishCandidate {
+            contract_id: "CABC",
+            namespace,
+            version: "1.0.0",
+            publisher: "GABC",
+            wasm,
+        }
+    }
+
+    #[test]
+    fn parses_imports_and_exports() {
+        let module = WasmModule::parse(&module_bytes()).unwrap();
+        assert_eq!(
+            module.imports,
+            vec![
+                ("x".to_string(), "_".to_string()),
+                ("l".to_string(), "1".to_string())
+            ]
+        );
+        assert_eq!(module.exports, vec!["hello", "__constructor"]);
+    }
+
+    #[test]
+    fn finds_custom_sections_by_name() {
+        let mut bytes = module_bytes();
+        bytes.extend([CUSTOM_SECTION, 7, 4]);
+        bytes.extend(b"spec");
+        bytes.extend([1, 2]);
+        assert_eq!(
+            WasmModule::custom_section(&bytes, "spec").unwrap(),
+            Some(&[1u8, 2][..])
+        );
+        assert_eq!(WasmModule::custom_section(&bytes, "other").unwrap(), None);
+        assert!(WasmModule::parse(&bytes).is_ok());
+    }
+
+    #[test]
+    fn rejects_truncated_modules() {
+        let bytes = module_bytes();
+        assert!(WasmModule::parse(&bytes[..bytes.len() - 3]).is_err());
+        assert!(WasmModule::parse(b"\0asm").is_err());
+    }
+
+    #[test]
+    fn rules_report_each_problem() {
+        let wasm = WasmModule::parse(&module_bytes()).unwrap();
+        let mut strict = rule("strict");
+        strict.max_wasm_bytes = Some(10);
+        strict.required_exports = vec!["hello".to_string(), "upgrade".to_string()];
+        strict.banned_imports = vec!["l.1".to_string(), "x.0".to_string()];
+        let messages: Vec<String> = strict
+            .check(&candidate(&wasm, None))
+            .into_iter()
+            .map(|v| v.message)
+            .collect();
+        assert_eq!(messages.len(), 3);
+        assert!(messages[1].contains("`upgrade`"));
+        assert!(messages[2].contains("`l.1`"));
+    }
+
+    #[test]
+    fn namespace_rules_only_apply_to_their_namespace() {
+        let wasm = WasmModule::parse(&module_bytes()).unwrap();
+        let mut policy = rule("acme");
+        policy.namespace = Some("acme".to_string());
+        policy.banned_imports = vec!["x".to_string()];
+        assert!(policy.check(&candidate(&wasm, Some("other"))).is_empty());
+        assert_eq!(policy.check(&candidate(&wasm, Some("acme"))).len(), 1);
+    }
+
+    #[test]
+    fn warnings_pass_and_rejections_fail() {
+        let wasm = WasmModule::parse(&module_bytes()).unwrap();
+        let mut warn = rule("needs-upgrade");
+        warn.required_exports = vec!["upgrade".to_string()];
+        warn.severity = HookSeverity::Warn;
+        warn.message = Some("Contracts should be upgradeable".to_string());
+        let mut hooks = PublishHooks::from_rules(&[warn]);
+        let warnings = hooks.enforce(&candidate(&wasm, None)).unwrap();
+        assert_eq!(warnings[0].hook, "needs-upgrade");
+        assert!(warnings[0]
+            .message
+            .starts_with("Contracts should be upgradeable"));
+
+        let mut size = rule("max-size");
+        size.max_wasm_bytes = Some(1);
+        hooks.register(size);
+        let err = hooks.enforce(&candidate(&wasm, None)).unwrap_err();
+        assert_eq!(err.code(), shared::ErrorCode::from("PublishHookRejected"));
+        assert!(err.to_string().contains("[max-size]"));
+    }
+}
@@ -0,0 +1,515 @@
+// publish_session_handlers.rs
+// Staged, resumable publishing: upload a version's artifacts to a session, then
+// commit them as one version or cancel.
+
+use std::collections::HashMap;
+
+use axum::{
+    body::Bytes,
+    extract::{rejection::JsonRejection, Path, State},
+    http::StatusCode,
+    Json,
+};
+use sha2::{Digest, Sha256};
+use shared::{
+    ApiScope, BuildToolchain, CreateContractVersionRequest, CreatePublishSessionRequest,
+    PublishCommitResult, PublishSession, PublishSessionDetail, SemVer, SignaturePart, StagedPart,
+    PUBLISH_PART_KINDS,
+};
+use sqlx::types::Json as SqlJson;
+use uuid::Uuid;
+
+use crate::error::{ApiError, ApiResult};
+use crate::handlers::{
+    create_version_record, db_internal_error, fetch_contract_identity, map_json_rejection,
+    VersionArtifact,
+};
+use crate::org_handlers::ensure_contract_maintainer;
+use crate::publish_hooks::{PublishCandidate, WasmModule};
+use crate::state::AppState;
+use crate::tenancy::CurrentTenant;
+use crate::user_auth::AuthUser;
+
+/// Columns of `publish_sessions` in `PublishSession` shape
+const SESSION_COLUMNS: &str = "id, contract_id, version, status, source_url, commit_hash, \
+     release_notes, version_id, created_at, expires_at";
+
+/// Parts copied onto the version as artifacts at commit
+const ARTIFACT_KINDS: &[&str] = &["wasm", "readme", "tests", "budgets"];
+const WASM_MAGIC: &[u8] = b"\0asm";
+
+fn session_not_found(id: Uuid) -> ApiError {
+    ApiError::not_found(
+        "PublishSessionNotFound",
+        format!("No publish session found with ID: {}", id),
+    )
+}
+
+fn session_closed(session: &PublishSession) -> ApiError {
+    ApiError::conflict(
+        "PublishSessionClosed",
+        format!("Publish session {} is {}", session.id, session.status),
+    )
+}
+
+/// Check an uploaded part's content before it is staged
+fn validate_part(kind: &str, content: &[u8]) -> ApiResult<()> {
+    if !PUBLISH_PART_KINDS.contains(&kind) {
+        return Err(ApiError::bad_request(
+            "InvalidPartKind",
+            format!("Part must be one of: {}", PUBLISH_PART_KINDS.join(", ")),
+        ));
+    }
+    if content.is_empty() {
+        return Err(ApiError::bad_request(
+            "EmptyPart",
+            format!("The {} part is empty", kind),
+        ));
+    }
+    if kind == "abi" {
+        let abi: serde_json::Value = serde_json::from_slice(content).map_err(|e| {
+            ApiError::bad_request(
+                "InvalidPart",
+                format!("The abi part must be a JSON contract spec: {}", e),
+            )
+        })?;
+        return crate::handlers::validate_abi_schema(&abi);
+    }
+    let valid = match kind {
+        "wasm" => content.starts_with(WASM_MAGIC),
+        "readme" => std::str::from_utf8(content).is_ok(),
+        "tests" => crate::test_results_handlers::parse_report(content).is_ok(),
+        "budgets" => crate::budget_handlers::parse_budgets(content).is_ok(),
+        _ => serde_json::from_slice::<SignaturePart>(content).is_ok(),
+    };
+    if !valid {
+        let expected = match kind {
+            "wasm" => "a WebAssembly module",
+            "readme" => "UTF-8 text",
+            "tests" => "a JUnit XML report or a JSON summary of passed and failed tests",
+            "budgets" => "JSON listing functions to benchmark and their limits",
+            _ => "JSON with signature and publisher_key",
+        };
+        return Err(ApiError::bad_request(
+            "InvalidPart",
+            format!("The {} part must be {}", kind, expected),
+        ));
+    }
+    Ok(())
+}
+
+async fn load_session(state: &AppState, id: Uuid, owner: &str) -> ApiResult<PublishSession> {
+    sqlx::query_as(&format!(
+        "SELECT {} FROM publish_sessions WHERE id = $1 AND owner_address = $2",
+        SESSION_COLUMNS
+    ))
+    .bind(id)
+    .bind(owner)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|err| db_internal_error("fetch publish session", err))?
+    .ok_or_else(|| session_not_found(id))
+}
+
+fn ensure_open(session: &PublishSession) -> ApiResult<()> {
+    if session.status != "open" {
+        return Err(session_closed(session));
+    }
+    if session.expires_at < chrono::Utc::now() {
+        return Err(ApiError::new(
+            StatusCode::GONE,
+            "PublishSessionExpired",
+            "This publish session has expired; start a new one",
+        ));
+    }
+    Ok(())
+}
+
+async fn session_detail(
+    state: &AppState,
+    session: PublishSession,
+) -> ApiResult<PublishSessionDetail> {
+    let parts: Vec<StagedPart> = sqlx::query_as(
+        "SELECT kind, sha256, OCTET_LENGTH(content)::BIGINT AS size_bytes, uploaded_at \
+         FROM publish_session_parts WHERE session_id = $1 ORDER BY kind",
+    )
+    .bind(session.id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("list publish session parts", err))?;
+    Ok(PublishSessionDetail { session, parts })
+}
+
+async fn set_status(state: &AppState, id: Uuid, status: &str, version_id: Option<Uuid>) {
+    let updated = sqlx::query(
+        "UPDATE publish_sessions SET status = $2, version_id = COALESCE($3, version_id) \
+         WHERE id = $1",
+    )
+    .bind(id)
+    .bind(status)
+    .bind(version_id)
+    .execute(&state.db)
+    .await;
+    if let Err(err) = updated {
+        tracing::error!(session_id = %id, status, error = %err, "failed to update publish session");
+    }
+}
+
+async fn drop_parts(state: &AppState, id: Uuid) {
+    let dropped = sqlx::query("DELETE FROM publish_session_parts WHERE session_id = $1")
+        .bind(id)
+        .execute(&state.db)
+        .await;
+    if let Err(err) = dropped {
+        tracing::warn!(session_id = %id, error = %err, "failed to drop publish session parts");
+    }
+}
+
+/// POST /api/publish/sessions — start staging a version, or resume the open
+/// session this account already has for it
+pub async fn create_publish_session(
+    State(state): State<AppState>,
+    tenant: CurrentTenant,
+    user: AuthUser,
+    payload: Result<Json<CreatePublishSessionRequest>, JsonRejection>,
+) -> ApiResult<(StatusCode, Json<PublishSessionDetail>)> {
+    user.require(ApiScope::Publish)?;
+    let Json(req) = payload.map_err(map_json_rejection)?;
+    if SemVer::parse(&req.version).is_none() {
+        return Err(ApiError::bad_request(
+            "InvalidVersion",
+            "Version must be valid semver (e.g. 1.2.3)",
+        ));
+    }
+    let (contract_uuid, _) = fetch_contract_identity(&state, tenant.id, &req.contract_id).await?;
+    ensure_contract_maintainer(&state, contract_uuid, &user.address).await?;
+
+    let published: bool = sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM contract_versions WHERE contract_id = $1 AND version = $2)",
+    )
+    .bind(contract_uuid)
+    .bind(&req.version)
+    .fetch_one(&state.db)
+    .await
+    .map_err(|err| db_internal_error("check published version", err))?;
+    if published {
+        return Err(ApiError::unprocessable(
+            "VersionAlreadyExists",
+            format!("Version '{}' already exists for this contract", req.version),
+        ));
+    }
+
+    // A stale session must not block a fresh start
+    sqlx::query(
+        "UPDATE publish_sessions SET status = 'cancelled' \
+         WHERE contract_id = $1 AND version = $2 AND owner_address = $3 \
+           AND status IN ('open', 'committing') AND expires_at < NOW()",
+    )
+    .bind(contract_uuid)
+    .bind(&req.version)
+    .bind(&user.address)
+    .execute(&state.db)
+    .await
+    .map_err(|err| db_internal_error("expire stale publish session", err))?;
+
+    let created: Option<PublishSession> = sqlx::query_as(&format!(
+        "INSERT INTO publish_sessions \
+             (contract_id, version, owner_address, source_url, commit_hash, release_notes, \
+              cargo_lock, build_toolchain) \
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8) \
+         ON CONFLICT (contract_id, version, owner_address) \
+             WHERE status IN ('open', 'committing') DO NOTHING \
+         RETURNING {}",
+        SESSION_COLUMNS
+    ))
+    .bind(contract_uuid)
+    .bind(&req.version)
+    .bind(&user.address)
+    .bind(&req.source_url)
+    .bind(&req.commit_hash)
+    .bind(&req.release_notes)
+    .bind(&req.cargo_lock)
+    .bind(req.toolchain.as_ref().map(SqlJson))
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|err| db_internal_error("create publish session", err))?;
+
+    if let Some(session) = created {
+        return Ok((
+            StatusCode::CREATED,
+            Json(session_detail(&state, session).await?),
+        ));
+    }
+
+    let resumed: PublishSession = sqlx::query_as(&format!(
+        "UPDATE publish_sessions \
+         SET source_url = COALESCE($4, source_url), commit_hash = COALESCE($5, commit_hash), \
+             release_notes = COALESCE($6, release_notes), cargo_lock = COALESCE($7, cargo_lock), \
+             build_toolchain = COALESCE($8, build_toolchain) \
+         WHERE contract_id = $1 AND version = $2 AND owner_address = $3 \
+           AND status IN ('open', 'committing') \
+         RETURNING {}",
+        SESSION_COLUMNS
+    ))
+    .bind(contract_uuid)
+    .bind(&req.version)
+    .bind(&user.address)
+    .bind(&req.source_url)
+    .bind(&req.commit_hash)
+    .bind(&req.release_notes)
+    .bind(&req.cargo_lock)
+    .bind(req.toolchain.as_ref().map(SqlJson))
+    .fetch_one(&state.db)
+    .await
+    .map_err(|err| db_internal_error("resume publish session", err))?;
+    Ok((StatusCode::OK, Json(session_detail(&state, resumed).await?)))
+}
+
+/// GET /api/publish/sessions/:id
+pub async fn get_publish_session(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(id): Path<Uuid>,
+) -> ApiResult<Json<PublishSessionDetail>> {
+    user.require(ApiScope::Publish)?;
+    let session = load_session(&state, id, &user.address).await?;
+    Ok(Json(session_detail(&state, session).await?))
+}
+
+/// PUT /api/publish/sessions/:id/parts/:kind — raw body; re-uploading a part replaces it
+pub async fn upload_publish_part(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path((id, kind)): Path<(Uuid, String)>,
+    body: Bytes,
+) -> ApiResult<Json<StagedPart>> {
+    user.require(ApiScope::Publish)?;
+    validate_part(&kind, &body)?;
+    let session = load_session(&state, id, &user.address).await?;
+    ensure_open(&session)?;
+
+    let sha256 = hex::encode(Sha256::digest(&body));
+    let part: StagedPart = sqlx::query_as(
+        "INSERT INTO publish_session_parts (session_id, kind, content, sha256) \
+         SELECT $1, $2, $3, $4 \
+         WHERE EXISTS (SELECT 1 FROM publish_sessions WHERE id = $1 AND status = 'open') \
+         ON CONFLICT (session_id, kind) DO UPDATE \
+             SET content = EXCLUDED.content, sha256 = EXCLUDED.sha256, uploaded_at = NOW() \
+         RETURNING kind, sha256, OCTET_LENGTH(content)::BIGINT AS size_bytes, uploaded_at",
+    )
+    .bind(id)
+    .bind(&kind)
+    .bind(body.as_ref())
+    .bind(&sha256)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|err| db_internal_error("stage publish part", err))?
+    .ok_or_else(|| session_closed(&session))?;
+    Ok(Json(part))
+}
+
+/// Run the publish hooks, then build the version from the staged parts and
+/// insert it in one transaction
+async fn commit_parts(
+    state: &AppState,
+    tenant_id: Uuid,
+    session: &PublishSession,
+    publisher: &str,
+) -> ApiResult<PublishCommitResult> {
+    let rows: Vec<(String, Vec<u8>, String)> = sqlx::query_as(
+        "SELECT kind, content, sha256 FROM publish_session_parts WHERE session_id = $1",
+    )
+    .bind(session.id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("load publish session parts", err))?;
+    let parts: HashMap<&str, (&[u8], &str)> = rows
+        .iter()
+        .map(|(kind, content, sha)| (kind.as_str(), (content.as_slice(), sha.as_str())))
+        .collect();
+
+    let (Some(&(wasm, wasm_hash)), Some(&(abi, _))) = (parts.get("wasm"), parts.get("abi")) else {
+        return Err(ApiError::unprocessable(
+            "IncompletePublishSession",
+            "Upload the wasm and abi parts before committing",
+        ));
+    };
+    let abi: serde_json::Value = serde_json::from_slice(abi)
+        .map_err(|e| ApiError::bad_request("InvalidABI", format!("Invalid ABI part: {}", e)))?;
+    let signature: Option<SignaturePart> = parts
+        .get("signature")
+        .map(|&(content, _)| serde_json::from_slice(content))
+        .transpose()
+        .map_err(|e| ApiError::bad_request("InvalidPart", format!("Invalid signature: {}", e)))?;
+    let (cargo_lock, toolchain): (Option<String>, Option<SqlJson<BuildToolchain>>) =
+        sqlx::query_as("SELECT cargo_lock, build_toolchain FROM publish_sessions WHERE id = $1")
+            .bind(session.id)
+            .fetch_one(&state.db)
+            .await
+            .map_err(|err| db_internal_error("load publish session lockfile", err))?;
+
+    let (contract_uuid, contract_id) =
+        fetch_contract_identity(state, tenant_id, &session.contract_id.to_string()).await?;
+
+    let mut warnings = Vec::new();
+    if !state.publish_hooks.is_empty() {
+        let module = WasmModule::parse(wasm).map_err(|e| {
+            ApiError::bad_request("InvalidPart", format!("The wasm part is malformed: {}", e))
+        })?;
+        let namespace: Option<String> =
+            sqlx::query_scalar("SELECT namespace FROM contracts WHERE id = $1")
+                .bind(contract_uuid)
+                .fetch_one(&state.db)
+                .await
+                .map_err(|err| db_internal_error("load contract namespace", err))?;
+        warnings = state.publish_hooks.enforce(&PublishCandidate {
+            contract_id: &contract_id,
+            namespace: namespace.as_deref(),
+            version: &session.version,
+            publisher,
+            wasm: &module,
+        })?;
+    }
+
+    let mut benchmarks = Vec::new();
+    if let Some(&(budgets, _)) = parts.get("budgets") {
+        let (measured, exceeded) = crate::budget_handlers::enforce_budgets(
+            state,
+            contract_uuid,
+            &contract_id,
+            wasm,
+            &abi,
+            budgets,
+        )
+        .await?;
+        benchmarks = measured;
+        warnings.extend(exceeded);
+    }
+
+    let req = CreateContractVersionRequest {
+        contract_id: contract_id.clone(),
+        version: session.version.clone(),
+        wasm_hash: wasm_hash.to_string(),
+        abi,
+        source_url: session.source_url.clone(),
+        commit_hash: session.commit_hash.clone(),
+        release_notes: session.release_notes.clone(),
+        signature: signature.as_ref().map(|s| s.signature.clone()),
+        publisher_key: signature.as_ref().map(|s| s.publisher_key.clone()),
+        signature_algorithm: signature.and_then(|s| s.signature_algorithm),
+        cargo_lock,
+        toolchain: toolchain.map(|json| json.0),
+        provenance: None,
+    };
+    let artifacts: Vec<VersionArtifact<'_>> = ARTIFACT_KINDS
+        .iter()
+        .filter_map(|&kind| {
+            parts.get(kind).map(|&(content, sha256)| VersionArtifact {
+                kind,
+                content,
+                sha256,
+            })
+        })
+        .collect();
+
+    let version = create_version_record(
+        state,
+        contract_uuid,
+        &contract_id,
+        &req,
+        &artifacts,
+        &benchmarks,
+    )
+    .await?;
+    Ok(PublishCommitResult { version, warnings })
+}
+
+/// POST /api/publish/sessions/:id/commit — all-or-nothing: either the version
+/// exists with every staged artifact, or the session stays open for a retry
+pub async fn commit_publish_session(
+    State(state): State<AppState>,
+    tenant: CurrentTenant,
+    user: AuthUser,
+    Path(id): Path<Uuid>,
+) -> ApiResult<(StatusCode, Json<PublishCommitResult>)> {
+    user.require(ApiScope::Publish)?;
+    let session = load_session(&state, id, &user.address).await?;
+    ensure_open(&session)?;
+    // Ownership may have changed since the session was opened
+    ensure_contract_maintainer(&state, session.contract_id, &user.address).await?;
+
+    // Claim the session so concurrent commits and late uploads are refused
+    let claimed: Option<Uuid> = sqlx::query_scalar(
+        "UPDATE publish_sessions SET status = 'committing' \
+         WHERE id = $1 AND status = 'open' RETURNING id",
+    )
+    .bind(id)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|err| db_internal_error("claim publish session", err))?;
+    if claimed.is_none() {
+        return Err(session_closed(&session));
+    }
+
+    match commit_parts(&state, tenant.id, &session, &user.address).await {
+        Ok(result) => {
+            set_status(&state, id, "committed", Some(result.version.id)).await;
+            drop_parts(&state, id).await;
+            Ok((StatusCode::CREATED, Json(result)))
+        }
+        Err(err) => {
+            set_status(&state, id, "open", None).await;
+            Err(err)
+        }
+    }
+}
+
+/// POST /api/publish/sessions/:id/cancel — discard everything staged
+pub async fn cancel_publish_session(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(id): Path<Uuid>,
+) -> ApiResult<StatusCode> {
+    user.require(ApiScope::Publish)?;
+    let session = load_session(&state, id, &user.address).await?;
+    let cancelled = sqlx::query(
+        "UPDATE publish_sessions SET status = 'cancelled' WHERE id = $1 AND status = 'open'",
+    )
+    .bind(id)
+    .execute(&state.db)
+    .await
+    .map_err(|err| db_internal_error("cancel publish session", err))?;
+    if cancelled.rows_affected() == 0 {
+        return Err(session_closed(&session));
+    }
+    drop_parts(&state, id).await;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parts_are_checked_by_kind() {
+        assert!(validate_part("wasm", b"\0asm\x01\0\0\0").is_ok());
+        assert!(validate_part("wasm", b"MZ not wasm").is_err());
+        assert!(validate_part("abi", br#"[{"type":"function","name":"swap"}]"#).is_ok());
+        assert!(validate_part("abi", b"{not json").is_err());
+        let err = validate_part("abi", br#"[{"type":"function"}]"#).unwrap_err();
+        assert_eq!(err.code(), shared::ErrorCode::InvalidABI);
+        assert!(validate_part("readme", "# AMM ✓".as_bytes()).is_ok());
+        assert!(validate_part(
+            "signature",
+            br#"{"signature":"c2ln","publisher_key":"a2V5"}"#
+        )
+        .is_ok());
+        assert!(validate_part("signature", b"{}").is_err());
+    }
+
+    #[test]
+    fn unknown_and_empty_parts_are_rejected() {
+        assert!(validate_part("source", b"fn main() {}").is_err());
+        assert!(validate_part("readme", b"").is_err());
+    }
+}
@@ -0,0 +1,223 @@
+// publisher_identity.rs
+// Publishers prove control of their Stellar account by signing a server-issued
+// challenge with the account key; the verified account earns a badge.
+
+use axum::{
+    extract::{rejection::JsonRejection, Path, State},
+    http::StatusCode,
+    Json,
+};
+use chrono::{Duration, Utc};
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use rand::{distributions::Alphanumeric, Rng};
+use sha2::{Digest, Sha256};
+use shared::{decode_account_id, IdentityChallenge, Publisher, VerifyIdentityRequest};
+use uuid::Uuid;
+
+use crate::error::{ApiError, ApiResult};
+use crate::handlers::{db_internal_error, map_json_rejection};
+use crate::state::AppState;
+use crate::user_auth::AuthUser;
+
+const CHALLENGE_TTL_MINUTES: i64 = 10;
+const NONCE_LENGTH: usize = 32;
+
+/// The text a publisher signs; binding the publisher, account and nonce stops a
+/// signature from being replayed for anyone else
+fn challenge_message(publisher_id: Uuid, account: &str, nonce: &str) -> String {
+    format!(
+        "soroban-registry identity verification\npublisher: {}\naccount: {}\nnonce: {}",
+        publisher_id, account, nonce
+    )
+}
+
+/// Whether `signature_hex` is the account key's ed25519 signature of `message`
+/// or of its SHA-256 digest, which is all hardware wallets will sign
+fn signature_matches(account: &str, message: &str, signature_hex: &str) -> ApiResult<bool> {
+    let public_key = decode_account_id(account).ok_or_else(|| {
+        ApiError::unprocessable(
+            "InvalidStellarAccount",
+            format!("{} is not a valid Stellar account ID", account),
+        )
+    })?;
+    let signature: [u8; 64] = hex::decode(signature_hex.trim())
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| {
+            ApiError::bad_request(
+                "InvalidSignature",
+                "signature must be a hex-encoded 64-byte ed25519 signature",
+            )
+        })?;
+    let Ok(key) = VerifyingKey::from_bytes(&public_key) else {
+        return Ok(false);
+    };
+    let signature = Signature::from_bytes(&signature);
+    Ok(key.verify(message.as_bytes(), &signature).is_ok()
+        || key
+            .verify(&Sha256::digest(message.as_bytes()), &signature)
+            .is_ok())
+}
+
+fn challenge_not_found() -> ApiError {
+    ApiError::unprocessable(
+        "ChallengeNotFound",
+        "No open identity challenge; request one from /verify-identity/challenge",
+    )
+}
+
+async fn fetch_publisher(state: &AppState, id: Uuid) -> ApiResult<Publisher> {
+    sqlx::query_as("SELECT * FROM publishers WHERE id = $1")
+        .bind(id)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(|err| db_internal_error("fetch publisher", err))?
+        .ok_or_else(|| {
+            ApiError::not_found(
+                "PublisherNotFound",
+                format!("No publisher found with ID: {}", id),
+            )
+        })
+}
+
+/// POST /api/publishers/:id/verify-identity/challenge — issue a message for the
+/// publisher's Stellar account key to sign; replaces any earlier challenge.
+/// Only the account itself may ask, so nobody else can keep replacing its nonce.
+pub async fn create_identity_challenge(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(id): Path<Uuid>,
+) -> ApiResult<Json<IdentityChallenge>> {
+    let publisher = fetch_publisher(&state, id).await?;
+    if user.address != publisher.stellar_address {
+        return Err(ApiError::new(
+            StatusCode::FORBIDDEN,
+            "Forbidden",
+            "Sign in as the publisher's Stellar account to request its identity challenge",
+        ));
+    }
+    if decode_account_id(&publisher.stellar_address).is_none() {
+        return Err(ApiError::unprocessable(
+            "InvalidStellarAccount",
+            format!(
+                "{} is not a valid Stellar account ID",
+                publisher.stellar_address
+            ),
+        ));
+    }
+
+    let nonce: String = rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(NONCE_LENGTH)
+        .map(char::from)
+        .collect();
+    let expires_at = Utc::now() + Duration::minutes(CHALLENGE_TTL_MINUTES);
+    sqlx::query(
+        "INSERT INTO publisher_identity_challenges (publisher_id, nonce, expires_at) \
+         VALUES ($1, $2, $3) \
+         ON CONFLICT (publisher_id) DO UPDATE \
+             SET nonce = EXCLUDED.nonce, expires_at = EXCLUDED.expires_at",
+    )
+    .bind(id)
+    .bind(&nonce)
+    .bind(expires_at)
+    .execute(&state.db)
+    .await
+    .map_err(|err| db_internal_error("store identity challenge", err))?;
+
+    Ok(Json(IdentityChallenge {
+        publisher_id: id,
+        message: challenge_message(id, &publisher.stellar_address, &nonce),
+        account: publisher.stellar_address,
+        expires_at,
+    }))
+}
+
+/// POST /api/publishers/:id/verify-identity — check the signed challenge and
+/// record the account as verified
+pub async fn verify_identity(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    payload: Result<Json<VerifyIdentityRequest>, JsonRejection>,
+) -> ApiResult<Json<Publisher>> {
+    let Json(req) = payload.map_err(map_json_rejection)?;
+    let publisher = fetch_publisher(&state, id).await?;
+
+    let nonce: Option<String> = sqlx::query_scalar(
+        "SELECT nonce FROM publisher_identity_challenges \
+         WHERE publisher_id = $1 AND expires_at > NOW()",
+    )
+    .bind(id)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|err| db_internal_error("fetch identity challenge", err))?;
+    let nonce = nonce.ok_or_else(challenge_not_found)?;
+
+    let account = &publisher.stellar_address;
+    let message = challenge_message(id, account, &nonce);
+    if !signature_matches(account, &message, &req.signature)? {
+        return Err(ApiError::unprocessable(
+            "SignatureMismatch",
+            format!("The signature was not made by the key of {}", account),
+        ));
+    }
+
+    // Only a verified signature uses the challenge up; matching the nonce means
+    // a concurrent reissue or a replayed request finds nothing to consume
+    let consumed = sqlx::query(
+        "DELETE FROM publisher_identity_challenges \
+         WHERE publisher_id = $1 AND nonce = $2 AND expires_at > NOW()",
+    )
+    .bind(id)
+    .bind(&nonce)
+    .execute(&state.db)
+    .await
+    .map_err(|err| db_internal_error("consume identity challenge", err))?;
+    if consumed.rows_affected() == 0 {
+        return Err(challenge_not_found());
+    }
+
+    let verified: Publisher = sqlx::query_as(
+        "UPDATE publishers SET verified_account = stellar_address, identity_verified_at = NOW() \
+         WHERE id = $1 RETURNING *",
+    )
+    .bind(id)
+    .fetch_one(&state.db)
+    .await
+    .map_err(|err| db_internal_error("record verified identity", err))?;
+
+    tracing::info!(publisher_id = %id, account = %account, "publisher identity verified");
+    Ok(Json(verified))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::{Signer, SigningKey};
+    use shared::encode_account_id;
+
+    #[test]
+    fn accepts_only_the_account_keys_signature() {
+        let key = SigningKey::from_bytes(&[3u8; 32]);
+        let account = encode_account_id(key.verifying_key().as_bytes());
+        let message = challenge_message(Uuid::nil(), &account, "nonce");
+        let signature = hex::encode(key.sign(message.as_bytes()).to_bytes());
+        assert!(signature_matches(&account, &message, &signature).unwrap());
+
+        let other = SigningKey::from_bytes(&[4u8; 32]);
+        let forged = hex::encode(other.sign(message.as_bytes()).to_bytes());
+        assert!(!signature_matches(&account, &message, &forged).unwrap());
+        assert!(!signature_matches(&account, "other message", &signature).unwrap());
+
+        let digest = Sha256::digest(message.as_bytes());
+        let hardware = hex::encode(key.sign(&digest).to_bytes());
+        assert!(signature_matches(&account, &message, &hardware).unwrap());
+    }
+
+    #[test]
+    fn rejects_malformed_input() {
+        let account = encode_account_id(&[9u8; 32]);
+        assert!(signature_matches(&account, "m", "zz").is_err());
+        assert!(signature_matches("GABC", "m", &"00".repeat(64)).is_err());
+    }
+}
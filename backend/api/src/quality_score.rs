@@ -0,0 +1,94 @@
+// quality_score.rs
+// Nightly recomputation of every contract's quality score from its decayed
+// downloads, last update, verification, audits and stars (the model is
+// `shared::QualityInputs`). Listings rank by it unless asked otherwise.
+
+use std::time::Duration;
+
+use shared::{QualityInputs, DOWNLOAD_DECAY_DAYS};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+const QUALITY_SCORE_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+
+#[derive(sqlx::FromRow)]
+struct QualityRow {
+    id: Uuid,
+    decayed_downloads: f64,
+    days_since_update: f64,
+    verified: bool,
+    audited: bool,
+    stars: i64,
+}
+
+impl QualityRow {
+    fn inputs(&self) -> QualityInputs {
+        QualityInputs {
+            decayed_downloads: self.decayed_downloads,
+            days_since_update: self.days_since_update,
+            verified: self.verified,
+            audited: self.audited,
+            stars: self.stars,
+        }
+    }
+}
+
+/// Score every contract; returns how many were updated
+pub async fn recalculate_quality_scores(pool: &PgPool) -> Result<u64, sqlx::Error> {
+    // Downloads older than a year weigh under 1e-5 and are left out
+    let rows: Vec<QualityRow> = sqlx::query_as(
+        "SELECT c.id, \
+                COALESCE((SELECT SUM(EXP(-EXTRACT(EPOCH FROM (NOW() - ci.created_at)) \
+                                         / 86400.0 / $1)) \
+                          FROM contract_interactions ci \
+                          WHERE ci.contract_id = c.id \
+                            AND ci.created_at > NOW() - INTERVAL '1 year'), 0)::FLOAT8 \
+                    AS decayed_downloads, \
+                (EXTRACT(EPOCH FROM (NOW() - GREATEST(c.updated_at, \
+                    (SELECT MAX(cv.created_at) FROM contract_versions cv \
+                     WHERE cv.contract_id = c.id)))) / 86400.0)::FLOAT8 AS days_since_update, \
+                c.is_verified AS verified, \
+                EXISTS (SELECT 1 FROM audit_attestations aa WHERE aa.contract_id = c.id) \
+                    AS audited, \
+                c.star_count AS stars \
+         FROM contracts c",
+    )
+    .bind(DOWNLOAD_DECAY_DAYS)
+    .fetch_all(pool)
+    .await?;
+
+    let (ids, scores): (Vec<Uuid>, Vec<f64>) = rows
+        .iter()
+        .map(|row| (row.id, row.inputs().score()))
+        .unzip();
+    // Rescoring is not an update of the contract, so updated_at is left alone
+    let mut tx = pool.begin().await?;
+    sqlx::query("SET LOCAL registry.preserve_updated_at = 'on'")
+        .execute(&mut *tx)
+        .await?;
+    let result = sqlx::query(
+        "UPDATE contracts c SET quality_score = s.score, quality_scored_at = NOW() \
+         FROM UNNEST($1::UUID[], $2::FLOAT8[]) AS s(id, score) \
+         WHERE c.id = s.id",
+    )
+    .bind(&ids)
+    .bind(&scores)
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await?;
+    Ok(result.rows_affected())
+}
+
+/// Spawn the nightly quality score job; it also runs once at startup
+pub fn spawn_quality_score_task(pool: PgPool) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(QUALITY_SCORE_INTERVAL);
+        loop {
+            interval.tick().await;
+            match recalculate_quality_scores(&pool).await {
+                Ok(updated) => tracing::info!(updated, "quality: scores recalculated"),
+                Err(err) => tracing::error!(error = ?err, "quality: recalculation failed"),
+            }
+        }
+    });
+}
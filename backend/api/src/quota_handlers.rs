@@ -0,0 +1,289 @@
+// quota_handlers.rs
+// Publishing quotas for shared registries: limits on contracts, stored artifact
+// bytes and daily versions per account, and endpoints reporting current usage.
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use shared::{ApiScope, QuotaMetric, QuotaUsage};
+use uuid::Uuid;
+
+use crate::error::{ApiError, ApiResult};
+use crate::handlers::db_internal_error;
+use crate::settings::QuotaSettings;
+use crate::state::AppState;
+use crate::user_auth::AuthUser;
+
+/// Who a contract's usage counts against: its organization, or its publisher
+/// when it belongs to none
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum QuotaAccount {
+    Organization { id: Uuid, name: String },
+    Publisher { id: Uuid, address: String },
+}
+
+impl QuotaAccount {
+    fn kind(&self) -> &'static str {
+        match self {
+            QuotaAccount::Organization { .. } => "organization",
+            QuotaAccount::Publisher { .. } => "publisher",
+        }
+    }
+
+    fn label(&self) -> &str {
+        match self {
+            QuotaAccount::Organization { name, .. } => name,
+            QuotaAccount::Publisher { address, .. } => address,
+        }
+    }
+
+    /// SQL condition selecting the account's contracts from `contracts c`, with
+    /// the account ID bound as `$1`
+    fn contracts_clause(&self) -> &'static str {
+        match self {
+            QuotaAccount::Organization { .. } => "c.organization_id = $1",
+            QuotaAccount::Publisher { .. } => "c.publisher_id = $1 AND c.organization_id IS NULL",
+        }
+    }
+
+    fn id(&self) -> Uuid {
+        match self {
+            QuotaAccount::Organization { id, .. } | QuotaAccount::Publisher { id, .. } => *id,
+        }
+    }
+}
+
+fn to_i64(limit: Option<u64>) -> Option<i64> {
+    limit.map(|value| i64::try_from(value).unwrap_or(i64::MAX))
+}
+
+fn quota_exceeded(account: &QuotaAccount, message: String) -> ApiError {
+    ApiError::new(
+        StatusCode::FORBIDDEN,
+        "QuotaExceeded",
+        format!("{} {}: {}", account.kind(), account.label(), message),
+    )
+}
+
+/// Reject adding `added` to `metric` when that would pass its limit
+fn check_metric(
+    account: &QuotaAccount,
+    metric: QuotaMetric,
+    added: i64,
+    setting: &str,
+    unit: &str,
+) -> ApiResult<()> {
+    match metric.limit {
+        Some(limit) if metric.used.saturating_add(added) > limit => Err(quota_exceeded(
+            account,
+            format!(
+                "{} of {} {} already used, this publish adds {} (quotas.{})",
+                metric.used, limit, unit, added, setting
+            ),
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// The account charged for a contract's versions
+pub(crate) async fn account_for_contract(
+    state: &AppState,
+    contract_uuid: Uuid,
+) -> ApiResult<QuotaAccount> {
+    let (org_id, org_name, publisher_id, address): (Option<Uuid>, Option<String>, Uuid, String) =
+        sqlx::query_as(
+            "SELECT o.id, o.name, p.id, p.stellar_address FROM contracts c \
+         JOIN publishers p ON p.id = c.publisher_id \
+         LEFT JOIN organizations o ON o.id = c.organization_id \
+         WHERE c.id = $1",
+        )
+        .bind(contract_uuid)
+        .fetch_one(&state.db)
+        .await
+        .map_err(|err| db_internal_error("fetch quota account", err))?;
+    Ok(match (org_id, org_name) {
+        (Some(id), Some(name)) => QuotaAccount::Organization { id, name },
+        _ => QuotaAccount::Publisher {
+            id: publisher_id,
+            address,
+        },
+    })
+}
+
+/// Current usage of `account` against the configured limits
+pub(crate) async fn usage(state: &AppState, account: &QuotaAccount) -> ApiResult<QuotaUsage> {
+    let limits: &QuotaSettings = &state.quotas;
+    let clause = account.contracts_clause();
+    let (contracts, artifact_bytes, versions_today): (i64, i64, i64) = sqlx::query_as(&format!(
+        "SELECT \
+             (SELECT COUNT(*) FROM contracts c WHERE {clause}), \
+             (SELECT COALESCE(SUM(OCTET_LENGTH(a.content)), 0)::BIGINT \
+              FROM contract_version_artifacts a \
+              JOIN contract_versions v ON v.id = a.version_id \
+              JOIN contracts c ON c.id = v.contract_id WHERE {clause}), \
+             (SELECT COUNT(*) FROM contract_versions v \
+              JOIN contracts c ON c.id = v.contract_id \
+              WHERE {clause} AND v.created_at > NOW() - INTERVAL '24 hours')",
+        clause = clause
+    ))
+    .bind(account.id())
+    .fetch_one(&state.db)
+    .await
+    .map_err(|err| db_internal_error("compute quota usage", err))?;
+
+    Ok(QuotaUsage {
+        account_type: account.kind().to_string(),
+        account: account.label().to_string(),
+        contracts: QuotaMetric {
+            used: contracts,
+            limit: to_i64(limits.max_contracts),
+        },
+        artifact_bytes: QuotaMetric {
+            used: artifact_bytes,
+            limit: to_i64(limits.max_artifact_bytes),
+        },
+        versions_today: QuotaMetric {
+            used: versions_today,
+            limit: to_i64(limits.max_versions_per_day),
+        },
+    })
+}
+
+/// Called before registering a new contract for `account`
+pub(crate) async fn ensure_contract_quota(
+    state: &AppState,
+    account: &QuotaAccount,
+) -> ApiResult<()> {
+    if state.quotas.max_contracts.is_none() {
+        return Ok(());
+    }
+    let usage = usage(state, account).await?;
+    check_metric(account, usage.contracts, 1, "max_contracts", "contracts")
+}
+
+/// Called before inserting a version of `contract_uuid` that stores
+/// `artifact_bytes` of artifacts
+pub(crate) async fn ensure_version_quota(
+    state: &AppState,
+    contract_uuid: Uuid,
+    artifact_bytes: usize,
+) -> ApiResult<()> {
+    let limits = &state.quotas;
+    if limits.max_versions_per_day.is_none() && limits.max_artifact_bytes.is_none() {
+        return Ok(());
+    }
+    let account = account_for_contract(state, contract_uuid).await?;
+    let usage = usage(state, &account).await?;
+    check_metric(
+        &account,
+        usage.versions_today,
+        1,
+        "max_versions_per_day",
+        "versions in the last 24 hours",
+    )?;
+    check_metric(
+        &account,
+        usage.artifact_bytes,
+        i64::try_from(artifact_bytes).unwrap_or(i64::MAX),
+        "max_artifact_bytes",
+        "artifact bytes",
+    )
+}
+
+/// GET /api/usage — the caller's own publisher account
+pub async fn get_my_usage(
+    State(state): State<AppState>,
+    user: AuthUser,
+) -> ApiResult<Json<QuotaUsage>> {
+    user.require(ApiScope::Read)?;
+    let publisher_id: Option<Uuid> =
+        sqlx::query_scalar("SELECT id FROM publishers WHERE stellar_address = $1")
+            .bind(&user.address)
+            .fetch_optional(&state.db)
+            .await
+            .map_err(|err| db_internal_error("fetch publisher for usage", err))?;
+    let account = QuotaAccount::Publisher {
+        // Someone who has never published has no usage to count
+        id: publisher_id.unwrap_or(Uuid::nil()),
+        address: user.address,
+    };
+    Ok(Json(usage(&state, &account).await?))
+}
+
+/// GET /api/orgs/:name/usage — members only
+pub async fn get_organization_usage(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(name): Path<String>,
+) -> ApiResult<Json<QuotaUsage>> {
+    user.require(ApiScope::Read)?;
+    let id: Option<Uuid> = sqlx::query_scalar(
+        "SELECT o.id FROM organizations o \
+         JOIN organization_members m ON m.organization_id = o.id \
+         WHERE o.name = $1 AND m.address = $2",
+    )
+    .bind(&name)
+    .bind(&user.address)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|err| db_internal_error("fetch organization for usage", err))?;
+    let id = id.ok_or_else(|| {
+        ApiError::not_found(
+            "OrganizationNotFound",
+            format!("No organization named '{}'", name),
+        )
+    })?;
+    let account = QuotaAccount::Organization { id, name };
+    Ok(Json(usage(&state, &account).await?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn org() -> QuotaAccount {
+        QuotaAccount::Organization {
+            id: Uuid::nil(),
+            name: "acme".to_string(),
+        }
+    }
+
+    #[test]
+    fn unlimited_metrics_never_fail() {
+        let metric = QuotaMetric {
+            used: i64::MAX,
+            limit: None,
+        };
+        assert!(check_metric(&org(), metric, 1, "max_contracts", "contracts").is_ok());
+    }
+
+    #[test]
+    fn limits_allow_reaching_but_not_passing_the_ceiling() {
+        let metric = QuotaMetric {
+            used: 9,
+            limit: Some(10),
+        };
+        assert!(check_metric(&org(), metric, 1, "max_contracts", "contracts").is_ok());
+
+        let err = check_metric(&org(), metric, 2, "max_contracts", "contracts").unwrap_err();
+        assert_eq!(err.code(), shared::ErrorCode::QuotaExceeded);
+        let message = err.to_string();
+        assert!(message.contains("organization acme"));
+        assert!(message.contains("9 of 10 contracts"));
+        assert!(message.contains("quotas.max_contracts"));
+    }
+
+    #[test]
+    fn publisher_accounts_exclude_organization_contracts() {
+        let account = QuotaAccount::Publisher {
+            id: Uuid::nil(),
+            address: "GABC".to_string(),
+        };
+        assert!(account
+            .contracts_clause()
+            .contains("organization_id IS NULL"));
+        assert_eq!(to_i64(Some(u64::MAX)), Some(i64::MAX));
+    }
+}
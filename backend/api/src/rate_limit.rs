@@ -1,6 +1,5 @@
 use std::{
     collections::HashMap,
-    env,
     net::{IpAddr, SocketAddr},
     sync::{Arc, Mutex},
     time::{Duration, Instant},
@@ -14,20 +13,21 @@ use axum::{
         HeaderName, HeaderValue, Method, Request, StatusCode,
     },
     middleware::Next,
-    response::{IntoResponse, Response},
-    Json,
+    response::Response,
 };
-use serde_json::json;
+use shared::{ErrorCode, ProblemDetails};
 
-const DEFAULT_READ_LIMIT_PER_MINUTE: u32 = 100;
-const DEFAULT_WRITE_LIMIT_PER_MINUTE: u32 = 20;
-const DEFAULT_AUTH_LIMIT_PER_MINUTE: u32 = 1_000;
-const DEFAULT_HEALTH_LIMIT_PER_MINUTE: u32 = 10_000;
-const DEFAULT_WINDOW_SECONDS: u64 = 60;
-const ENDPOINT_LIMIT_ENV_PREFIX: &str = "RATE_LIMIT_ENDPOINT_";
+use crate::settings::RateLimitSettings;
 
-const HEADER_RATE_LIMIT_LIMIT: HeaderName = HeaderName::from_static("x-ratelimit-limit");
-const HEADER_RATE_LIMIT_REMAINING: HeaderName = HeaderName::from_static("x-ratelimit-remaining");
+pub(crate) const DEFAULT_READ_LIMIT_PER_MINUTE: u32 = 100;
+pub(crate) const DEFAULT_WRITE_LIMIT_PER_MINUTE: u32 = 20;
+pub(crate) const DEFAULT_AUTH_LIMIT_PER_MINUTE: u32 = 1_000;
+pub(crate) const DEFAULT_HEALTH_LIMIT_PER_MINUTE: u32 = 10_000;
+pub(crate) const DEFAULT_WINDOW_SECONDS: u64 = 60;
+
+pub(crate) const HEADER_RATE_LIMIT_LIMIT: HeaderName = HeaderName::from_static("x-ratelimit-limit");
+pub(crate) const HEADER_RATE_LIMIT_REMAINING: HeaderName =
+    HeaderName::from_static("x-ratelimit-remaining");
 const HEADER_RATE_LIMIT_RESET: HeaderName = HeaderName::from_static("x-ratelimit-reset");
 
 #[derive(Clone)]
@@ -37,8 +37,8 @@ pub struct RateLimitState {
 }
 
 impl RateLimitState {
-    pub fn from_env() -> Self {
-        Self::new(RateLimitConfig::from_env())
+    pub fn from_settings(settings: &RateLimitSettings) -> Self {
+        Self::new(RateLimitConfig::from_settings(settings))
     }
 
     fn new(config: RateLimitConfig) -> Self {
@@ -131,36 +131,17 @@ struct RateLimitConfig {
 }
 
 impl RateLimitConfig {
-    fn from_env() -> Self {
-        let read_limit = env_u32("RATE_LIMIT_READ_PER_MINUTE", DEFAULT_READ_LIMIT_PER_MINUTE);
-        let write_limit = env_u32(
-            "RATE_LIMIT_WRITE_PER_MINUTE",
-            DEFAULT_WRITE_LIMIT_PER_MINUTE,
-        );
-        let auth_limit = env_u32("RATE_LIMIT_AUTH_PER_MINUTE", DEFAULT_AUTH_LIMIT_PER_MINUTE);
-        let health_limit = env_u32(
-            "RATE_LIMIT_HEALTH_PER_MINUTE",
-            DEFAULT_HEALTH_LIMIT_PER_MINUTE,
-        );
-        let window_seconds = env_u64("RATE_LIMIT_WINDOW_SECONDS", DEFAULT_WINDOW_SECONDS).max(1);
-
-        let mut endpoint_limits = HashMap::new();
-        for (key, value) in env::vars() {
-            let Some(endpoint_key) = key.strip_prefix(ENDPOINT_LIMIT_ENV_PREFIX) else {
-                continue;
-            };
-
-            let Ok(limit) = value.parse::<u32>() else {
-                tracing::warn!("Ignoring invalid endpoint rate limit `{key}`: `{value}`");
-                continue;
-            };
-            if limit == 0 {
-                tracing::warn!("Ignoring zero endpoint rate limit `{key}`");
-                continue;
-            }
-
-            endpoint_limits.insert(endpoint_key.to_string(), limit);
-        }
+    fn from_settings(settings: &RateLimitSettings) -> Self {
+        let read_limit = settings.read_per_minute;
+        let write_limit = settings.write_per_minute;
+        let auth_limit = settings.auth_per_minute;
+        let health_limit = settings.health_per_minute;
+        let window_seconds = settings.window_seconds.max(1);
+        let endpoint_limits: HashMap<String, u32> = settings
+            .endpoints
+            .iter()
+            .map(|(key, limit)| (key.clone(), *limit))
+            .collect();
 
         tracing::info!(
             read_limit,
@@ -221,17 +202,14 @@ pub async fn rate_limit_middleware(
     let decision = rate_limiter.check_request(&request);
 
     if !decision.allowed {
-        let mut response = (
+        let mut response = crate::error::problem_response(
             StatusCode::TOO_MANY_REQUESTS,
-            Json(json!({
-                "error": "RateLimitExceeded",
-                "message": "Too many requests. Please retry after the indicated time.",
-                "code": 429,
-                "timestamp": chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
-                "correlation_id": uuid::Uuid::new_v4().to_string()
-            })),
-        )
-            .into_response();
+            ProblemDetails::new(
+                StatusCode::TOO_MANY_REQUESTS.as_u16(),
+                ErrorCode::RateLimitExceeded,
+                "Too many requests. Please retry after the indicated time.",
+            ),
+        );
         attach_rate_limit_headers(&mut response, &decision);
         response.headers_mut().insert(
             RETRY_AFTER,
@@ -307,7 +285,7 @@ fn is_write_method(method: &Method) -> bool {
     )
 }
 
-fn endpoint_key(method: &Method, path: &str) -> String {
+pub(crate) fn endpoint_key(method: &Method, path: &str) -> String {
     let normalized_path = path
         .chars()
         .map(|c| {
@@ -332,32 +310,6 @@ fn endpoint_key(method: &Method, path: &str) -> String {
     }
 }
 
-fn env_u32(key: &str, default: u32) -> u32 {
-    match env::var(key) {
-        Ok(raw) => match raw.parse::<u32>() {
-            Ok(value) if value > 0 => value,
-            _ => {
-                tracing::warn!("Invalid value for {key} (`{raw}`), using default {default}");
-                default
-            }
-        },
-        Err(_) => default,
-    }
-}
-
-fn env_u64(key: &str, default: u64) -> u64 {
-    match env::var(key) {
-        Ok(raw) => match raw.parse::<u64>() {
-            Ok(value) if value > 0 => value,
-            _ => {
-                tracing::warn!("Invalid value for {key} (`{raw}`), using default {default}");
-                default
-            }
-        },
-        Err(_) => default,
-    }
-}
-
 fn ceil_duration_to_seconds(duration: Duration) -> u64 {
     let secs = duration.as_secs();
     if duration.subsec_nanos() > 0 {
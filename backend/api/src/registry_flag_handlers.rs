@@ -0,0 +1,174 @@
+// registry_flag_handlers.rs
+// Admin endpoints to inspect and flip registry feature flags, with an audit trail.
+
+use axum::{
+    extract::{rejection::JsonRejection, Path, State},
+    Json,
+};
+use serde::Serialize;
+use shared::{RegistryFeatureFlag, RegistryFlagAuditEntry, UpdateRegistryFlagRequest};
+
+use crate::error::{ApiError, ApiResult};
+use crate::handlers::{db_internal_error, map_json_rejection};
+use crate::state::AppState;
+
+const MAX_FLAG_NAME_LENGTH: usize = 64;
+
+fn validate_flag_name(name: &str) -> ApiResult<()> {
+    if name.is_empty()
+        || name.len() > MAX_FLAG_NAME_LENGTH
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+    {
+        return Err(ApiError::bad_request(
+            "InvalidFlagName",
+            format!(
+                "Flag names must be 1-{} lowercase letters, digits or underscores",
+                MAX_FLAG_NAME_LENGTH
+            ),
+        ));
+    }
+    Ok(())
+}
+
+#[derive(Debug, Serialize)]
+pub struct RegistryFlagListResponse {
+    /// Environment this instance evaluates flags for
+    pub environment: String,
+    pub flags: Vec<RegistryFeatureFlag>,
+}
+
+/// GET /api/admin/flags
+pub async fn list_flags(
+    State(state): State<AppState>,
+) -> ApiResult<Json<RegistryFlagListResponse>> {
+    let flags: Vec<RegistryFeatureFlag> =
+        sqlx::query_as("SELECT * FROM registry_feature_flags ORDER BY name")
+            .fetch_all(&state.db)
+            .await
+            .map_err(|err| db_internal_error("list feature flags", err))?;
+
+    Ok(Json(RegistryFlagListResponse {
+        environment: state.flags.environment().to_string(),
+        flags,
+    }))
+}
+
+/// PUT /api/admin/flags/:name — create or update a flag and record the change
+pub async fn update_flag(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+    payload: Result<Json<UpdateRegistryFlagRequest>, JsonRejection>,
+) -> ApiResult<Json<RegistryFeatureFlag>> {
+    let Json(req) = payload.map_err(map_json_rejection)?;
+    validate_flag_name(&name)?;
+    if req.changed_by.trim().is_empty() {
+        return Err(ApiError::bad_request(
+            "MissingActor",
+            "changed_by is required so the change can be audited",
+        ));
+    }
+    if let Some(pct) = req.rollout_percentage {
+        if !(0..=100).contains(&pct) {
+            return Err(ApiError::bad_request(
+                "InvalidRollout",
+                "rollout_percentage must be between 0 and 100",
+            ));
+        }
+    }
+
+    let mut tx = state
+        .db
+        .begin()
+        .await
+        .map_err(|err| db_internal_error("begin feature flag update", err))?;
+
+    let previous: Option<RegistryFeatureFlag> =
+        sqlx::query_as("SELECT * FROM registry_feature_flags WHERE name = $1 FOR UPDATE")
+            .bind(&name)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|err| db_internal_error("fetch feature flag", err))?;
+
+    let current: RegistryFeatureFlag = sqlx::query_as(
+        "INSERT INTO registry_feature_flags \
+             (name, description, enabled, rollout_percentage, environments, updated_by) \
+         VALUES ($1, $2, COALESCE($3, FALSE), COALESCE($4, 100), COALESCE($5, '{}'), $6) \
+         ON CONFLICT (name) DO UPDATE SET \
+             description = COALESCE($2, registry_feature_flags.description), \
+             enabled = COALESCE($3, registry_feature_flags.enabled), \
+             rollout_percentage = COALESCE($4, registry_feature_flags.rollout_percentage), \
+             environments = COALESCE($5, registry_feature_flags.environments), \
+             updated_by = $6, \
+             updated_at = NOW() \
+         RETURNING *",
+    )
+    .bind(&name)
+    .bind(req.description.as_deref())
+    .bind(req.enabled)
+    .bind(req.rollout_percentage)
+    .bind(req.environments.as_ref())
+    .bind(req.changed_by.trim())
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|err| db_internal_error("upsert feature flag", err))?;
+
+    sqlx::query(
+        "INSERT INTO registry_feature_flag_audit (flag_name, changed_by, previous, current) \
+         VALUES ($1, $2, $3, $4)",
+    )
+    .bind(&name)
+    .bind(req.changed_by.trim())
+    .bind(previous.map(|flag| serde_json::to_value(flag).unwrap_or_default()))
+    .bind(serde_json::to_value(&current).unwrap_or_default())
+    .execute(&mut *tx)
+    .await
+    .map_err(|err| db_internal_error("record feature flag audit", err))?;
+
+    tx.commit()
+        .await
+        .map_err(|err| db_internal_error("commit feature flag update", err))?;
+
+    state.flags.invalidate(&name).await;
+    tracing::info!(
+        flag = %name,
+        enabled = current.enabled,
+        rollout_percentage = current.rollout_percentage,
+        changed_by = %req.changed_by.trim(),
+        "feature flag updated"
+    );
+
+    Ok(Json(current))
+}
+
+/// GET /api/admin/flags/:name/audit
+pub async fn get_flag_audit(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+) -> ApiResult<Json<Vec<RegistryFlagAuditEntry>>> {
+    validate_flag_name(&name)?;
+    let entries: Vec<RegistryFlagAuditEntry> = sqlx::query_as(
+        "SELECT * FROM registry_feature_flag_audit WHERE flag_name = $1 \
+         ORDER BY created_at DESC LIMIT 200",
+    )
+    .bind(&name)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("fetch feature flag audit", err))?;
+
+    Ok(Json(entries))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flag_names_are_snake_case() {
+        assert!(validate_flag_name("search_ranking_v2").is_ok());
+        assert!(validate_flag_name("").is_err());
+        assert!(validate_flag_name("Search-Ranking").is_err());
+        assert!(validate_flag_name(&"a".repeat(65)).is_err());
+    }
+}
@@ -0,0 +1,148 @@
+// registry_flags.rs
+// Runtime feature flags for the registry itself, read from Postgres through a short-lived cache.
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use moka::future::Cache as MokaCache;
+use sha2::{Digest, Sha256};
+use shared::RegistryFeatureFlag;
+use sqlx::PgPool;
+
+/// Contract search ranks by quality within each relevance tier
+pub const SEARCH_RANKING_V2: &str = "search_ranking_v2";
+
+/// How long a flag read is reused before going back to the database
+const FLAG_CACHE_TTL: Duration = Duration::from_secs(30);
+
+/// Decide whether `flag` is on for `subject` (a publisher, IP or request key).
+/// Percentage rollouts hash the flag name with the subject so each subject
+/// gets a stable answer and different flags roll out to different subjects.
+pub fn evaluate(flag: &RegistryFeatureFlag, environment: &str, subject: &str) -> bool {
+    if !flag.enabled {
+        return false;
+    }
+    if !flag.environments.is_empty() && !flag.environments.iter().any(|e| e == environment) {
+        return false;
+    }
+    match flag.rollout_percentage {
+        p if p >= 100 => true,
+        p if p <= 0 => false,
+        p => rollout_bucket(&flag.name, subject) < p as u8,
+    }
+}
+
+/// Stable bucket in 0..100 for a flag/subject pair
+fn rollout_bucket(flag: &str, subject: &str) -> u8 {
+    let digest = Sha256::digest(format!("{}:{}", flag, subject).as_bytes());
+    let value = u32::from_be_bytes([digest[0], digest[1], digest[2], digest[3]]);
+    (value % 100) as u8
+}
+
+pub struct RegistryFlags {
+    environment: String,
+    /// Fallback values from the `[features]` config section for flags not in the database
+    defaults: BTreeMap<String, bool>,
+    cache: MokaCache<String, Option<RegistryFeatureFlag>>,
+}
+
+impl Default for RegistryFlags {
+    fn default() -> Self {
+        Self::new("development", BTreeMap::new())
+    }
+}
+
+impl RegistryFlags {
+    pub fn new(environment: impl Into<String>, defaults: BTreeMap<String, bool>) -> Self {
+        Self {
+            environment: environment.into(),
+            defaults,
+            cache: MokaCache::builder()
+                .max_capacity(1_000)
+                .time_to_live(FLAG_CACHE_TTL)
+                .build(),
+        }
+    }
+
+    pub fn environment(&self) -> &str {
+        &self.environment
+    }
+
+    async fn load(&self, db: &PgPool, name: &str) -> Option<RegistryFeatureFlag> {
+        if let Some(cached) = self.cache.get(name).await {
+            return cached;
+        }
+        match sqlx::query_as("SELECT * FROM registry_feature_flags WHERE name = $1")
+            .bind(name)
+            .fetch_optional(db)
+            .await
+        {
+            Ok(flag) => {
+                self.cache.insert(name.to_string(), flag.clone()).await;
+                flag
+            }
+            Err(err) => {
+                // Serve the configured default rather than failing the request
+                tracing::warn!(flag = name, error = ?err, "feature flag lookup failed");
+                None
+            }
+        }
+    }
+
+    /// Whether `name` is on for `subject` in this environment
+    pub async fn is_enabled(&self, db: &PgPool, name: &str, subject: &str) -> bool {
+        match self.load(db, name).await {
+            Some(flag) => evaluate(&flag, &self.environment, subject),
+            None => self.defaults.get(name).copied().unwrap_or(false),
+        }
+    }
+
+    /// Drop a cached flag after it changes so this instance sees the update immediately
+    pub async fn invalidate(&self, name: &str) {
+        self.cache.invalidate(name).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn flag(enabled: bool, rollout_percentage: i16, environments: &[&str]) -> RegistryFeatureFlag {
+        RegistryFeatureFlag {
+            name: SEARCH_RANKING_V2.to_string(),
+            description: None,
+            enabled,
+            rollout_percentage,
+            environments: environments.iter().map(|e| e.to_string()).collect(),
+            updated_by: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn disabled_or_other_environment_is_off() {
+        assert!(!evaluate(&flag(false, 100, &[]), "production", "GABC"));
+        assert!(!evaluate(
+            &flag(true, 100, &["staging"]),
+            "production",
+            "GABC"
+        ));
+        assert!(evaluate(&flag(true, 100, &["staging"]), "staging", "GABC"));
+        assert!(evaluate(&flag(true, 100, &[]), "production", "GABC"));
+        assert!(!evaluate(&flag(true, 0, &[]), "production", "GABC"));
+    }
+
+    #[test]
+    fn percentage_rollout_is_stable_and_proportional() {
+        let half = flag(true, 50, &[]);
+        let first = evaluate(&half, "production", "subject-42");
+        assert_eq!(first, evaluate(&half, "production", "subject-42"));
+
+        let on = (0..1_000)
+            .filter(|i| evaluate(&half, "production", &format!("subject-{}", i)))
+            .count();
+        assert!((400..600).contains(&on), "{} of 1000 enabled", on);
+    }
+}
@@ -0,0 +1,313 @@
+// reindex.rs
+// Admin job that rebuilds derived search data from scratch: search vectors,
+// interface fingerprints and dependency links. Needed after changing analyzers
+// or backfilling data on existing rows; progress is polled from the admin API.
+
+use std::sync::RwLock;
+
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    Json,
+};
+use chrono::Utc;
+use serde::Deserialize;
+use shared::{ReindexState, ReindexStatus, ReindexStep, ReindexStepProgress};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::dependency;
+use crate::error::{ApiError, ApiResult};
+use crate::interfaces;
+use crate::search_suggestions;
+use crate::state::AppState;
+use crate::type_safety::parse_json_spec;
+
+/// Contracts rewritten per transaction, so a reindex never holds long locks
+const BATCH_SIZE: i64 = 200;
+
+/// The running or most recent reindex of this instance
+#[derive(Default)]
+pub struct Reindexer {
+    status: RwLock<Option<ReindexStatus>>,
+}
+
+impl Reindexer {
+    pub fn status(&self) -> Option<ReindexStatus> {
+        self.status
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
+
+    fn update(&self, change: impl FnOnce(&mut ReindexStatus)) {
+        let mut status = self
+            .status
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(status) = status.as_mut() {
+            change(status);
+        }
+    }
+
+    /// Record a new run unless one is still going
+    fn start(&self, steps: &[ReindexStep]) -> Option<ReindexStatus> {
+        let mut status = self
+            .status
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if matches!(&*status, Some(s) if s.state == ReindexState::Running) {
+            return None;
+        }
+        let started = ReindexStatus {
+            id: Uuid::new_v4(),
+            state: ReindexState::Running,
+            steps: steps
+                .iter()
+                .map(|&step| ReindexStepProgress {
+                    step,
+                    total: 0,
+                    processed: 0,
+                    failed: 0,
+                    finished: false,
+                })
+                .collect(),
+            started_at: Utc::now(),
+            finished_at: None,
+            error: None,
+        };
+        *status = Some(started.clone());
+        Some(started)
+    }
+
+    fn progress(&self, step: ReindexStep, change: impl FnOnce(&mut ReindexStepProgress)) {
+        self.update(|status| {
+            if let Some(progress) = status.steps.iter_mut().find(|p| p.step == step) {
+                change(progress);
+            }
+        });
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReindexQuery {
+    /// Comma-separated steps to run; all of them when absent
+    pub steps: Option<String>,
+}
+
+fn parse_steps(raw: Option<&str>) -> ApiResult<Vec<ReindexStep>> {
+    let Some(raw) = raw.filter(|raw| !raw.trim().is_empty()) else {
+        return Ok(ReindexStep::ALL.to_vec());
+    };
+    let mut steps = Vec::new();
+    for name in raw.split(',').map(str::trim) {
+        let step = name
+            .parse::<ReindexStep>()
+            .map_err(|err| ApiError::bad_request("InvalidReindexStep", err))?;
+        if !steps.contains(&step) {
+            steps.push(step);
+        }
+    }
+    // Run in the canonical order whatever the request said
+    steps.sort_by_key(|step| ReindexStep::ALL.iter().position(|s| s == step));
+    Ok(steps)
+}
+
+/// Contract IDs after `after`, in a stable order for keyset pagination
+async fn next_batch(pool: &PgPool, after: Option<Uuid>) -> Result<Vec<Uuid>, sqlx::Error> {
+    sqlx::query_scalar(
+        "SELECT id FROM contracts WHERE $1::uuid IS NULL OR id > $1 ORDER BY id LIMIT $2",
+    )
+    .bind(after)
+    .bind(BATCH_SIZE)
+    .fetch_all(pool)
+    .await
+}
+
+/// Rewriting a row regenerates its stored tsvector columns
+async fn reindex_search(pool: &PgPool, batch: &[Uuid]) -> Result<i64, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    sqlx::query("SET LOCAL registry.preserve_updated_at = 'on'")
+        .execute(&mut *tx)
+        .await?;
+    sqlx::query("UPDATE contracts SET name = name WHERE id = ANY($1)")
+        .bind(batch)
+        .execute(&mut *tx)
+        .await?;
+    tx.commit().await?;
+    Ok(0)
+}
+
+/// Latest stored ABI of each contract in the batch, with its on-chain ID
+async fn latest_abis(
+    pool: &PgPool,
+    batch: &[Uuid],
+) -> Result<Vec<(Uuid, String, serde_json::Value)>, sqlx::Error> {
+    sqlx::query_as(
+        "SELECT DISTINCT ON (a.contract_id) a.contract_id, c.contract_id, a.abi \
+         FROM contract_abis a JOIN contracts c ON c.id = a.contract_id \
+         WHERE a.contract_id = ANY($1) \
+         ORDER BY a.contract_id, a.created_at DESC",
+    )
+    .bind(batch)
+    .fetch_all(pool)
+    .await
+}
+
+/// Re-detect implemented interfaces; contracts without an ABI implement none
+async fn reindex_interfaces(pool: &PgPool, batch: &[Uuid]) -> Result<i64, sqlx::Error> {
+    let abis = latest_abis(pool, batch).await?;
+    let mut failed = 0;
+    let mut tx = pool.begin().await?;
+    sqlx::query("SET LOCAL registry.preserve_updated_at = 'on'")
+        .execute(&mut *tx)
+        .await?;
+    sqlx::query("UPDATE contracts SET implements = '{}' WHERE id = ANY($1)")
+        .bind(batch)
+        .execute(&mut *tx)
+        .await?;
+    for (id, contract_id, abi) in abis {
+        let Ok(spec) = parse_json_spec(&abi.to_string(), &contract_id) else {
+            tracing::warn!(contract = %contract_id, "reindex: stored ABI does not parse");
+            failed += 1;
+            continue;
+        };
+        sqlx::query("UPDATE contracts SET implements = $1 WHERE id = $2")
+            .bind(interfaces::detect_interfaces(&spec))
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+    }
+    tx.commit().await?;
+    Ok(failed)
+}
+
+/// Re-resolve dependencies declared in each contract's latest ABI
+async fn reindex_dependencies(pool: &PgPool, batch: &[Uuid]) -> Result<i64, sqlx::Error> {
+    let mut failed = 0;
+    for (id, contract_id, abi) in latest_abis(pool, batch).await? {
+        let declared = dependency::detect_dependencies_from_abi(&abi);
+        if let Err(err) = dependency::save_dependencies(pool, id, &declared).await {
+            tracing::warn!(
+                contract = %contract_id,
+                error = %err,
+                "reindex: saving dependencies failed"
+            );
+            failed += 1;
+        }
+    }
+    Ok(failed)
+}
+
+async fn run_step(state: &AppState, step: ReindexStep) -> Result<(), sqlx::Error> {
+    let pool = &state.db;
+    let total: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM contracts")
+        .fetch_one(pool)
+        .await?;
+    state.reindexer.progress(step, |p| p.total = total);
+
+    let mut after = None;
+    loop {
+        let batch = next_batch(pool, after).await?;
+        let Some(&last) = batch.last() else {
+            break;
+        };
+        let failed = match step {
+            ReindexStep::Search => reindex_search(pool, &batch).await?,
+            ReindexStep::Interfaces => reindex_interfaces(pool, &batch).await?,
+            ReindexStep::Dependencies => reindex_dependencies(pool, &batch).await?,
+        };
+        state.reindexer.progress(step, |p| {
+            p.processed += batch.len() as i64;
+            p.failed += failed;
+        });
+        after = Some(last);
+    }
+
+    match step {
+        ReindexStep::Search => search_suggestions::refresh_terms(pool).await?,
+        ReindexStep::Interfaces => {}
+        ReindexStep::Dependencies => {
+            state
+                .cache
+                .invalidate("system", "global:dependency_graph")
+                .await
+        }
+    }
+    state.reindexer.progress(step, |p| p.finished = true);
+    Ok(())
+}
+
+async fn run(state: AppState, steps: Vec<ReindexStep>) {
+    for step in steps {
+        tracing::info!(step = step.as_str(), "reindex step started");
+        if let Err(err) = run_step(&state, step).await {
+            tracing::error!(step = step.as_str(), error = ?err, "reindex failed");
+            state.reindexer.update(|status| {
+                status.state = ReindexState::Failed;
+                status.finished_at = Some(Utc::now());
+                status.error = Some(format!("{} step failed: {}", step.as_str(), err));
+            });
+            return;
+        }
+    }
+    state.reindexer.update(|status| {
+        status.state = ReindexState::Completed;
+        status.finished_at = Some(Utc::now());
+    });
+    tracing::info!("reindex completed");
+}
+
+/// POST /api/admin/reindex — start rebuilding search data (`?steps=search,interfaces`)
+pub async fn start_reindex(
+    State(state): State<AppState>,
+    Query(query): Query<ReindexQuery>,
+) -> ApiResult<(StatusCode, Json<ReindexStatus>)> {
+    let steps = parse_steps(query.steps.as_deref())?;
+    let Some(status) = state.reindexer.start(&steps) else {
+        return Err(ApiError::conflict(
+            "ReindexRunning",
+            "A reindex is already running; poll GET /api/admin/reindex for its progress",
+        ));
+    };
+    tracing::info!(id = %status.id, steps = ?steps, "reindex started");
+    tokio::spawn(run(state.clone(), steps));
+    Ok((StatusCode::ACCEPTED, Json(status)))
+}
+
+/// GET /api/admin/reindex — progress of the running or most recent reindex
+pub async fn get_reindex(State(state): State<AppState>) -> ApiResult<Json<ReindexStatus>> {
+    state
+        .reindexer
+        .status()
+        .map(Json)
+        .ok_or_else(|| ApiError::not_found("NoReindex", "No reindex has run since startup"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn steps_default_to_all_in_order() {
+        assert_eq!(parse_steps(None).unwrap(), ReindexStep::ALL.to_vec());
+        assert_eq!(
+            parse_steps(Some("dependencies, search,search")).unwrap(),
+            vec![ReindexStep::Search, ReindexStep::Dependencies]
+        );
+        assert!(parse_steps(Some("search,vectors")).is_err());
+    }
+
+    #[test]
+    fn only_one_reindex_runs_at_a_time() {
+        let reindexer = Reindexer::default();
+        assert!(reindexer.status().is_none());
+        let started = reindexer.start(&[ReindexStep::Search]).unwrap();
+        assert!(reindexer.start(&[ReindexStep::Search]).is_none());
+
+        reindexer.progress(ReindexStep::Search, |p| p.processed = 5);
+        reindexer.update(|s| s.state = ReindexState::Completed);
+        assert_eq!(reindexer.status().unwrap().steps[0].processed, 5);
+        assert_ne!(reindexer.start(&ReindexStep::ALL).unwrap().id, started.id);
+    }
+}
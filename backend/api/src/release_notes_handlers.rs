@@ -11,7 +11,9 @@ use shared::{
 use uuid::Uuid;
 
 use crate::error::{ApiError, ApiResult};
+use crate::handlers::fetch_contract_identity;
 use crate::state::AppState;
+use crate::tenancy::CurrentTenant;
 
 // ─────────────────────────────────────────────────────────────────────────────
 // GET  /api/contracts/:id/release-notes/:version
@@ -20,9 +22,10 @@ use crate::state::AppState;
 /// Retrieve generated release notes for a specific contract version
 pub async fn get_release_notes(
     State(state): State<AppState>,
+    tenant: CurrentTenant,
     Path((id, version)): Path<(String, String)>,
 ) -> ApiResult<Json<ReleaseNotesResponse>> {
-    let (contract_uuid, _contract_id) = fetch_contract_identity(&state, &id).await?;
+    let (contract_uuid, _contract_id) = fetch_contract_identity(&state, tenant.id, &id).await?;
 
     let record = sqlx::query_as::<_, ReleaseNotesGenerated>(
         "SELECT * FROM release_notes_generated WHERE contract_id = $1 AND version = $2",
@@ -52,9 +55,10 @@ pub async fn get_release_notes(
 /// List all generated release notes for a contract
 pub async fn list_release_notes(
     State(state): State<AppState>,
+    tenant: CurrentTenant,
     Path(id): Path<String>,
 ) -> ApiResult<Json<Vec<ReleaseNotesResponse>>> {
-    let (contract_uuid, _contract_id) = fetch_contract_identity(&state, &id).await?;
+    let (contract_uuid, _contract_id) = fetch_contract_identity(&state, tenant.id, &id).await?;
 
     let records = sqlx::query_as::<_, ReleaseNotesGenerated>(
         "SELECT * FROM release_notes_generated WHERE contract_id = $1 ORDER BY created_at DESC",
@@ -75,10 +79,11 @@ pub async fn list_release_notes(
 /// Auto-generate release notes from code diff, changelog, and version metadata
 pub async fn generate_release_notes(
     State(state): State<AppState>,
+    tenant: CurrentTenant,
     Path(id): Path<String>,
     Json(req): Json<GenerateReleaseNotesRequest>,
 ) -> ApiResult<Json<ReleaseNotesResponse>> {
-    let (contract_uuid, contract_id) = fetch_contract_identity(&state, &id).await?;
+    let (contract_uuid, contract_id) = fetch_contract_identity(&state, tenant.id, &id).await?;
 
     // Validate the requested version is valid semver
     let target_ver = SemVer::parse(&req.version).ok_or_else(|| {
@@ -210,10 +215,11 @@ pub async fn generate_release_notes(
 /// Manually edit release notes (only while in draft status)
 pub async fn update_release_notes(
     State(state): State<AppState>,
+    tenant: CurrentTenant,
     Path((id, version)): Path<(String, String)>,
     Json(req): Json<UpdateReleaseNotesRequest>,
 ) -> ApiResult<Json<ReleaseNotesResponse>> {
-    let (contract_uuid, _contract_id) = fetch_contract_identity(&state, &id).await?;
+    let (contract_uuid, _contract_id) = fetch_contract_identity(&state, tenant.id, &id).await?;
 
     // Ensure the record exists and is in draft status
     let existing = sqlx::query_as::<_, ReleaseNotesGenerated>(
@@ -268,10 +274,11 @@ pub async fn update_release_notes(
 /// updates the `release_notes` column on `contract_versions`.
 pub async fn publish_release_notes(
     State(state): State<AppState>,
+    tenant: CurrentTenant,
     Path((id, version)): Path<(String, String)>,
     Json(req): Json<PublishReleaseNotesRequest>,
 ) -> ApiResult<Json<ReleaseNotesResponse>> {
-    let (contract_uuid, _contract_id) = fetch_contract_identity(&state, &id).await?;
+    let (contract_uuid, _contract_id) = fetch_contract_identity(&state, tenant.id, &id).await?;
 
     let existing = sqlx::query_as::<_, ReleaseNotesGenerated>(
         "SELECT * FROM release_notes_generated WHERE contract_id = $1 AND version = $2",
@@ -781,39 +788,6 @@ fn to_response(record: ReleaseNotesGenerated) -> ReleaseNotesResponse {
 
 // ── Shared helpers (same pattern as deprecation_handlers) ────────────────
 
-async fn fetch_contract_identity(state: &AppState, id: &str) -> ApiResult<(Uuid, String)> {
-    if let Ok(uuid) = Uuid::parse_str(id) {
-        let row = sqlx::query_as::<_, (Uuid, String)>(
-            "SELECT id, contract_id FROM contracts WHERE id = $1",
-        )
-        .bind(uuid)
-        .fetch_optional(&state.db)
-        .await
-        .map_err(|err| db_internal_error("fetch contract", err))?;
-        return row.ok_or_else(|| {
-            ApiError::not_found(
-                "ContractNotFound",
-                format!("No contract found with ID: {}", id),
-            )
-        });
-    }
-
-    let row = sqlx::query_as::<_, (Uuid, String)>(
-        "SELECT id, contract_id FROM contracts WHERE contract_id = $1",
-    )
-    .bind(id)
-    .fetch_optional(&state.db)
-    .await
-    .map_err(|err| db_internal_error("fetch contract", err))?;
-
-    row.ok_or_else(|| {
-        ApiError::not_found(
-            "ContractNotFound",
-            format!("No contract found with ID: {}", id),
-        )
-    })
-}
-
 fn db_internal_error(operation: &str, err: sqlx::Error) -> ApiError {
     tracing::error!(operation = operation, error = ?err, "database operation failed");
     ApiError::internal("Database operation failed")
@@ -11,7 +11,7 @@
 
 use axum::{
     body::Body,
-    extract::ConnectInfo,
+    extract::{ConnectInfo, MatchedPath},
     http::{HeaderName, HeaderValue, Request},
     middleware::Next,
     response::Response,
@@ -37,26 +37,35 @@ pub async fn tracing_middleware(
     let method = req.method().to_string();
     let path = req.uri().path().to_owned();
     let user_ip = addr.ip().to_string();
+    // Label metrics by route template so ids in the path don't explode cardinality
+    let route = req
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|p| p.as_str().to_owned());
 
     // Inject the request ID into extensions so handlers / DB layers can read it
     req.extensions_mut().insert(RequestId(request_id.clone()));
 
     let start = Instant::now();
     let mut response = next.run(req).await;
-    let duration_ms = start.elapsed().as_millis() as u64;
+    let elapsed = start.elapsed();
+    let duration_ms = elapsed.as_millis() as u64;
 
     // Attach X-Request-ID to the response so clients can correlate logs
     if let Ok(val) = HeaderValue::from_str(&request_id) {
         response.headers_mut().insert(X_REQUEST_ID.clone(), val);
     }
 
+    let status = response.status().as_u16();
+    if let Some(route) = route {
+        crate::metrics::observe_http(&method, &route, status, elapsed.as_secs_f64());
+    }
+
     // Skip noisy health-check paths
     if SKIP_LOG_PATHS.iter().any(|p| path.starts_with(p)) {
         return response;
     }
 
-    let status = response.status().as_u16();
-
     // Emit a single structured JSON log line per request
     tracing::info!(
         request_id = %request_id,
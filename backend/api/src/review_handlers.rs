@@ -0,0 +1,331 @@
+// review_handlers.rs
+// User reviews and ratings for contracts, plus the moderation queue for reported reviews.
+
+use axum::{
+    extract::{
+        rejection::{JsonRejection, QueryRejection},
+        Path, Query, State,
+    },
+    http::StatusCode,
+    Json,
+};
+use serde::Deserialize;
+use shared::{
+    ApiScope, ContractRating, ContractReview, ContractReviewsResponse, CreateReviewRequest,
+    FlagReviewRequest, FlaggedReview, ModerateReviewRequest, ReviewModerationAction,
+    MAX_REVIEW_RATING, MIN_REVIEW_RATING,
+};
+use uuid::Uuid;
+
+use crate::error::{ApiError, ApiResult};
+use crate::handlers::{db_internal_error, fetch_contract_identity, map_json_rejection};
+use crate::private_registry::{ensure_contract_visible, Viewer};
+use crate::state::AppState;
+use crate::tenancy::CurrentTenant;
+use crate::user_auth::AuthUser;
+
+const MAX_COMMENT_LENGTH: usize = 2000;
+const MAX_FLAG_REASON_LENGTH: usize = 500;
+
+/// Columns of `reviews` in `ContractReview` shape
+pub(crate) const REVIEW_COLUMNS: &str = "r.id, r.contract_id, r.reviewer_address, r.rating, \
+     r.review_text AS comment, COALESCE(r.is_flagged, FALSE) AS hidden, \
+     COALESCE(r.created_at, NOW()) AS created_at, COALESCE(r.updated_at, NOW()) AS updated_at";
+
+fn validate_review(req: &CreateReviewRequest) -> ApiResult<()> {
+    if !(MIN_REVIEW_RATING..=MAX_REVIEW_RATING).contains(&req.rating) {
+        return Err(ApiError::bad_request(
+            "InvalidRating",
+            format!(
+                "rating must be between {} and {}",
+                MIN_REVIEW_RATING, MAX_REVIEW_RATING
+            ),
+        ));
+    }
+    if req
+        .comment
+        .as_deref()
+        .is_some_and(|c| c.chars().count() > MAX_COMMENT_LENGTH)
+    {
+        return Err(ApiError::bad_request(
+            "InvalidComment",
+            format!("comment must be at most {} characters", MAX_COMMENT_LENGTH),
+        ));
+    }
+    Ok(())
+}
+
+/// Average and count of a contract's visible reviews, `None` when it has none
+pub(crate) async fn contract_rating(
+    state: &AppState,
+    contract_id: Uuid,
+) -> ApiResult<Option<ContractRating>> {
+    let rating: ContractRating = sqlx::query_as(
+        "SELECT COALESCE(AVG(rating), 0)::FLOAT8 AS average, COUNT(*) AS count \
+         FROM reviews \
+         WHERE contract_id = $1 AND reviewer_address IS NOT NULL \
+           AND NOT COALESCE(is_flagged, FALSE)",
+    )
+    .bind(contract_id)
+    .fetch_one(&state.db)
+    .await
+    .map_err(|err| db_internal_error("aggregate contract rating", err))?;
+    Ok((rating.count > 0).then_some(rating))
+}
+
+/// Contract the signed-in user may see, resolved from a UUID, on-chain ID or `@namespace/name`
+async fn reviewable_contract(
+    state: &AppState,
+    user: &AuthUser,
+    tenant: &CurrentTenant,
+    id: &str,
+) -> ApiResult<Uuid> {
+    let (contract_uuid, _) = fetch_contract_identity(state, tenant.id, id).await?;
+    let viewer = Viewer {
+        address: Some(user.address.clone()),
+        tenant_id: tenant.id,
+    };
+    ensure_contract_visible(state, &viewer, contract_uuid).await?;
+    Ok(contract_uuid)
+}
+
+/// POST /api/contracts/:id/reviews
+pub async fn create_review(
+    State(state): State<AppState>,
+    tenant: CurrentTenant,
+    user: AuthUser,
+    Path(id): Path<String>,
+    payload: Result<Json<CreateReviewRequest>, JsonRejection>,
+) -> ApiResult<(StatusCode, Json<ContractReview>)> {
+    user.require(ApiScope::Publish)?;
+    let Json(req) = payload.map_err(map_json_rejection)?;
+    validate_review(&req)?;
+    let contract_uuid = reviewable_contract(&state, &user, &tenant, &id).await?;
+    let comment = req
+        .comment
+        .as_deref()
+        .map(str::trim)
+        .filter(|c| !c.is_empty());
+
+    let review: ContractReview = sqlx::query_as(&format!(
+        "INSERT INTO reviews AS r (contract_id, reviewer_address, rating, review_text) \
+         VALUES ($1, $2, $3, $4) \
+         RETURNING {}",
+        REVIEW_COLUMNS
+    ))
+    .bind(contract_uuid)
+    .bind(&user.address)
+    .bind(req.rating)
+    .bind(comment)
+    .fetch_one(&state.db)
+    .await
+    .map_err(|err| {
+        if let sqlx::Error::Database(ref e) = err {
+            if e.constraint() == Some("idx_reviews_contract_reviewer") {
+                return ApiError::conflict(
+                    "ReviewAlreadyExists",
+                    "You have already reviewed this contract",
+                );
+            }
+        }
+        db_internal_error("create review", err)
+    })?;
+
+    Ok((StatusCode::CREATED, Json(review)))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReviewListQuery {
+    pub page: Option<i64>,
+    pub limit: Option<i64>,
+}
+
+/// GET /api/contracts/:id/reviews?page=&limit= — newest first, hidden reviews excluded
+pub async fn list_reviews(
+    State(state): State<AppState>,
+    viewer: Viewer,
+    Path(id): Path<String>,
+    query: Result<Query<ReviewListQuery>, QueryRejection>,
+) -> ApiResult<Json<ContractReviewsResponse>> {
+    let Query(query) = query.map_err(|err| {
+        ApiError::bad_request("InvalidRequest", format!("Invalid query: {}", err))
+    })?;
+    let (contract_uuid, _) = fetch_contract_identity(&state, viewer.tenant_id, &id).await?;
+    ensure_contract_visible(&state, &viewer, contract_uuid).await?;
+    let page = query.page.unwrap_or(1).max(1);
+    let limit = query.limit.unwrap_or(20).clamp(1, 100);
+
+    let reviews: Vec<ContractReview> = sqlx::query_as(&format!(
+        "SELECT {} FROM reviews r \
+         WHERE r.contract_id = $1 AND r.reviewer_address IS NOT NULL \
+           AND NOT COALESCE(r.is_flagged, FALSE) \
+         ORDER BY r.created_at DESC, r.id DESC \
+         LIMIT $2 OFFSET $3",
+        REVIEW_COLUMNS
+    ))
+    .bind(contract_uuid)
+    .bind(limit)
+    .bind((page - 1) * limit)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("list reviews", err))?;
+
+    let rating = contract_rating(&state, contract_uuid).await?;
+    let total = rating.as_ref().map_or(0, |r| r.count);
+
+    Ok(Json(ContractReviewsResponse {
+        rating,
+        reviews,
+        page,
+        pages: (total + limit - 1) / limit,
+    }))
+}
+
+/// POST /api/contracts/:id/reviews/:review_id/flag — report a review for moderation
+pub async fn flag_review(
+    State(state): State<AppState>,
+    tenant: CurrentTenant,
+    user: AuthUser,
+    Path((id, review_id)): Path<(String, i32)>,
+    payload: Result<Json<FlagReviewRequest>, JsonRejection>,
+) -> ApiResult<StatusCode> {
+    user.require(ApiScope::Publish)?;
+    let Json(req) = payload.map_err(map_json_rejection)?;
+    let reason = req.reason.trim();
+    if reason.is_empty() || reason.chars().count() > MAX_FLAG_REASON_LENGTH {
+        return Err(ApiError::bad_request(
+            "InvalidReason",
+            format!("reason must be 1-{} characters", MAX_FLAG_REASON_LENGTH),
+        ));
+    }
+    let contract_uuid = reviewable_contract(&state, &user, &tenant, &id).await?;
+
+    let exists: bool = sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM reviews WHERE id = $1 AND contract_id = $2)",
+    )
+    .bind(review_id)
+    .bind(contract_uuid)
+    .fetch_one(&state.db)
+    .await
+    .map_err(|err| db_internal_error("fetch review", err))?;
+    if !exists {
+        return Err(review_not_found(review_id));
+    }
+
+    // Repeat reports from the same account are accepted but not counted twice
+    sqlx::query(
+        "INSERT INTO review_flags (review_id, reporter_address, reason) VALUES ($1, $2, $3) \
+         ON CONFLICT (review_id, reporter_address) WHERE reporter_address IS NOT NULL \
+         DO NOTHING",
+    )
+    .bind(review_id)
+    .bind(&user.address)
+    .bind(reason)
+    .execute(&state.db)
+    .await
+    .map_err(|err| db_internal_error("flag review", err))?;
+
+    Ok(StatusCode::ACCEPTED)
+}
+
+fn review_not_found(review_id: i32) -> ApiError {
+    ApiError::not_found(
+        "ReviewNotFound",
+        format!("No review found with ID: {}", review_id),
+    )
+}
+
+/// GET /api/admin/reviews/flagged — reviews with open reports in this tenant, most reported first
+pub async fn list_flagged_reviews(
+    State(state): State<AppState>,
+    tenant: CurrentTenant,
+) -> ApiResult<Json<Vec<FlaggedReview>>> {
+    let reviews: Vec<FlaggedReview> = sqlx::query_as(&format!(
+        "SELECT {}, COUNT(f.id) AS open_flags, ARRAY_AGG(f.reason ORDER BY f.id) AS reasons \
+         FROM reviews r \
+         JOIN review_flags f ON f.review_id = r.id AND NOT COALESCE(f.resolved, FALSE) \
+         JOIN contracts c ON c.id = r.contract_id AND c.tenant_id = $1 \
+         GROUP BY r.id \
+         ORDER BY open_flags DESC, r.id",
+        REVIEW_COLUMNS
+    ))
+    .bind(tenant.id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("list flagged reviews", err))?;
+    Ok(Json(reviews))
+}
+
+/// POST /api/admin/reviews/:review_id/moderate
+pub async fn moderate_review(
+    State(state): State<AppState>,
+    tenant: CurrentTenant,
+    Path(review_id): Path<i32>,
+    payload: Result<Json<ModerateReviewRequest>, JsonRejection>,
+) -> ApiResult<Json<ContractReview>> {
+    let Json(req) = payload.map_err(map_json_rejection)?;
+    let mut tx = state
+        .db
+        .begin()
+        .await
+        .map_err(|err| db_internal_error("begin moderation", err))?;
+
+    let hidden = match req.action {
+        ReviewModerationAction::Hide => Some(true),
+        ReviewModerationAction::Restore => Some(false),
+        ReviewModerationAction::Dismiss => None,
+    };
+    let review: ContractReview = sqlx::query_as(&format!(
+        "UPDATE reviews AS r SET is_flagged = COALESCE($2, is_flagged), updated_at = NOW() \
+         WHERE r.id = $1 \
+           AND r.contract_id IN (SELECT id FROM contracts WHERE tenant_id = $3) \
+         RETURNING {}",
+        REVIEW_COLUMNS
+    ))
+    .bind(review_id)
+    .bind(hidden)
+    .bind(tenant.id)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|err| db_internal_error("moderate review", err))?
+    .ok_or_else(|| review_not_found(review_id))?;
+
+    if req.action != ReviewModerationAction::Restore {
+        sqlx::query("UPDATE review_flags SET resolved = TRUE WHERE review_id = $1")
+            .bind(review_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|err| db_internal_error("resolve review flags", err))?;
+    }
+
+    tx.commit()
+        .await
+        .map_err(|err| db_internal_error("commit moderation", err))?;
+    Ok(Json(review))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn review(rating: i16, comment: Option<&str>) -> CreateReviewRequest {
+        CreateReviewRequest {
+            rating,
+            comment: comment.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn ratings_must_be_whole_stars_in_range() {
+        assert!(validate_review(&review(1, None)).is_ok());
+        assert!(validate_review(&review(5, Some("solid"))).is_ok());
+        assert!(validate_review(&review(0, None)).is_err());
+        assert!(validate_review(&review(6, None)).is_err());
+    }
+
+    #[test]
+    fn long_comments_are_rejected() {
+        let long = "x".repeat(MAX_COMMENT_LENGTH + 1);
+        assert!(validate_review(&review(4, Some(&long))).is_err());
+    }
+}
@@ -1,11 +1,23 @@
 use axum::{
-    routing::{get, patch, post},
+    middleware,
+    routing::{delete, get, patch, post, put},
     Router,
 };
 
 use crate::{
-    breaking_changes, compatibility_testing_handlers, custom_metrics_handlers,
-    deprecation_handlers, handlers, metrics_handler, migration_handlers, state::AppState,
+    abuse_report_handlers, account_handlers, admin_auth, admin_handlers, alert_handlers,
+    api_key_handlers, api_usage, archive_handlers, artifact_gc, artifact_handlers,
+    audit_attestation_handlers, auth_handlers, backfill, badge_handlers, batch_handlers,
+    breaking_changes, budget_handlers, changelog_handlers, cli_release_handlers,
+    collection_handlers, compat_handlers, compatibility_testing_handlers, custom_metrics_handlers,
+    deprecation_handlers, discover_handlers, event_schema_handlers, example_handlers,
+    feed_handlers, fork_handlers, handlers, interfaces, job_handlers, maintenance_mode,
+    metrics_handler, migration_handlers, org_handlers, patch_handlers, probe_handlers,
+    provenance_handlers, publish_session_handlers, publisher_identity, quota_handlers,
+    registry_flag_handlers, reindex, review_handlers, saved_search_handlers, sbom_handlers,
+    simulation_handlers, sparse_index, star_handlers, state::AppState, stats_handlers,
+    stream_handlers, support_handlers, telemetry_handlers, tenancy, test_results_handlers,
+    token_handlers, toolchain_handlers,
 };
 
 pub fn observability_routes() -> Router<AppState> {
@@ -20,12 +32,39 @@ pub fn contract_routes() -> Router<AppState> {
             "/api/contracts/trending",
             get(handlers::get_trending_contracts),
         )
+        .route(
+            "/api/contracts/random",
+            get(discover_handlers::random_contract),
+        )
         .route("/api/contracts/graph", get(handlers::get_contract_graph))
-        .route("/api/contracts/:id", get(handlers::get_contract))
+        .route(
+            "/api/contracts/stream",
+            get(stream_handlers::stream_contracts),
+        )
+        .route(
+            "/api/contracts/batch",
+            post(batch_handlers::batch_lookup_contracts),
+        )
+        .route(
+            "/api/contracts/:id",
+            get(handlers::get_contract).patch(handlers::update_contract_metadata),
+        )
         .route(
             "/api/contracts/:id/metadata",
             patch(handlers::update_contract_metadata),
         )
+        .route("/api/contracts/:id/readme", get(handlers::get_contract_readme))
+        .route("/api/contracts/:id/badge.svg", get(badge_handlers::contract_badge))
+        .route("/badge/:kind/*name", get(badge_handlers::named_badge))
+        .route(
+            "/api/contracts/:id/archive",
+            get(archive_handlers::get_contract_archive),
+        )
+        .route(
+            "/api/contracts/:id/artifacts",
+            get(artifact_handlers::list_version_artifacts),
+        )
+        .route("/api/artifacts/:sha256", get(artifact_handlers::get_artifact))
         .route(
             "/api/contracts/:id/publisher",
             patch(handlers::change_contract_publisher),
@@ -39,6 +78,31 @@ pub fn contract_routes() -> Router<AppState> {
             get(handlers::get_contract_audit_log),
         )
         .route("/api/contracts/:id/abi", get(handlers::get_contract_abi))
+        .route("/api/schema/abi", get(handlers::get_abi_schema))
+        .route(
+            "/api/contracts/:id/license",
+            get(handlers::get_contract_license),
+        )
+        .route(
+            "/api/contracts/:id/sbom",
+            get(sbom_handlers::get_contract_sbom),
+        )
+        .route(
+            "/api/contracts/:id/toolchain",
+            get(toolchain_handlers::get_contract_toolchain),
+        )
+        .route(
+            "/api/contracts/:id/support",
+            get(support_handlers::get_support),
+        )
+        .route(
+            "/api/contracts/:id/versions/:version/support",
+            put(support_handlers::set_support_policy),
+        )
+        .route(
+            "/api/contracts/:id/provenance",
+            get(provenance_handlers::get_provenance).post(provenance_handlers::submit_provenance),
+        )
         .route(
             "/api/contracts/:id/openapi.yaml",
             get(handlers::get_contract_openapi_yaml),
@@ -53,12 +117,59 @@ pub fn contract_routes() -> Router<AppState> {
         )
         .route(
             "/api/contracts/:id/changelog",
-            get(handlers::get_contract_changelog),
+            get(changelog_handlers::get_contract_changelog),
         )
+        .route(
+            "/api/contracts/:id/tests",
+            get(test_results_handlers::get_contract_tests),
+        )
+        .route(
+            "/api/contracts/:id/forks",
+            get(fork_handlers::get_contract_forks),
+        )
+        .route(
+            "/api/contracts/:id/budgets",
+            get(budget_handlers::get_contract_budgets),
+        )
+        .route(
+            "/api/contracts/:id/simulate",
+            post(simulation_handlers::simulate_contract),
+        )
+        .route(
+            "/api/contracts/:id/examples",
+            get(example_handlers::list_examples),
+        )
+        .route(
+            "/api/contracts/:id/examples/:name",
+            get(example_handlers::get_example)
+                .put(example_handlers::upsert_example)
+                .delete(example_handlers::delete_example),
+        )
+        .route(
+            "/api/contracts/:id/examples/:name/run",
+            post(example_handlers::run_example),
+        )
+        .route(
+            "/api/contracts/:id/events",
+            get(event_schema_handlers::get_event_schemas),
+        )
+        .route(
+            "/api/contracts/:id/events/decode",
+            post(event_schema_handlers::decode_contract_event),
+        )
+        .route(
+            "/api/contracts/:id/versions/:version/events",
+            put(event_schema_handlers::register_event_schemas),
+        )
+        .route(
+            "/api/contracts/:id/token",
+            get(token_handlers::get_token_metadata),
+        )
+        .route("/api/tokenlist.json", get(token_handlers::get_tokenlist))
         // Compatibility alias (spec asks for /contracts/{id}/changelog)
         .route(
             "/contracts/:id/changelog",
-            get(handlers::get_contract_changelog),
+            get(changelog_handlers::get_contract_changelog),
         )
         .route(
             "/api/contracts/breaking-changes",
@@ -117,6 +228,7 @@ pub fn contract_routes() -> Router<AppState> {
         .route("/api/contracts/:id/dependents", get(handlers::get_contract_dependents))
         .route("/api/contracts/:id/impact", get(handlers::get_impact_analysis))
         .route("/api/contracts/verify", post(handlers::verify_contract))
+        .route("/api/jobs/:id", get(job_handlers::get_job))
         .route("/api/admin/audit-logs", get(handlers::get_all_audit_logs))
         .route(
             "/api/contracts/:id/performance",
@@ -161,6 +273,28 @@ pub fn contract_routes() -> Router<AppState> {
             get(handlers::get_deployment_status),
         )
         .route("/api/deployments/green", post(handlers::deploy_green))
+        .route(
+            "/api/contracts/:id/reviews",
+            get(review_handlers::list_reviews).post(review_handlers::create_review),
+        )
+        .route(
+            "/api/contracts/:id/reviews/:review_id/flag",
+            post(review_handlers::flag_review),
+        )
+        .route(
+            "/api/contracts/:id/report",
+            post(abuse_report_handlers::report_contract),
+        )
+        .route(
+            "/api/contracts/:id/star",
+            put(star_handlers::star_contract).delete(star_handlers::unstar_contract),
+        )
+        .route("/api/me/stars", get(star_handlers::list_my_stars))
+        .route(
+            "/api/contracts/:id/audits",
+            get(audit_attestation_handlers::list_audit_attestations)
+                .post(audit_attestation_handlers::create_audit_attestation),
+        )
     // TODO: backup_routes, notification_routes, and post_incident_routes
     // are available in the api library crate but need architectural refactoring
     // to be integrated with the main AppState
@@ -174,12 +308,271 @@ pub fn publisher_routes() -> Router<AppState> {
             "/api/publishers/:id/contracts",
             get(handlers::get_publisher_contracts),
         )
+        .route(
+            "/api/publishers/:id/verify-identity",
+            post(publisher_identity::verify_identity),
+        )
+        .route(
+            "/api/publishers/:id/verify-identity/challenge",
+            post(publisher_identity::create_identity_challenge),
+        )
+        .route(
+            "/api/publishers/:id/badge.svg",
+            get(badge_handlers::publisher_badge),
+        )
 }
 
 pub fn health_routes() -> Router<AppState> {
     Router::new()
         .route("/health", get(handlers::health_check))
+        .route("/health/live", get(probe_handlers::liveness))
+        .route("/health/ready", get(probe_handlers::readiness))
         .route("/api/stats", get(handlers::get_stats))
+        .route(
+            "/api/stats/timeseries",
+            get(stats_handlers::get_stats_timeseries),
+        )
+}
+
+pub fn cli_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api/cli/releases/latest",
+            get(cli_release_handlers::latest_release),
+        )
+        .route("/api/telemetry", post(telemetry_handlers::ingest_telemetry))
+}
+
+pub fn auth_routes() -> Router<AppState> {
+    Router::new()
+        .route("/api/auth/challenge", get(auth_handlers::get_challenge))
+        .route("/api/auth/verify", post(auth_handlers::verify_challenge))
+}
+
+pub fn api_key_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api/tokens",
+            get(api_key_handlers::list_api_keys).post(api_key_handlers::create_api_key),
+        )
+        .route("/api/tokens/:id", delete(api_key_handlers::revoke_api_key))
+        .route(
+            "/api/tokens/:id/rotate",
+            post(api_key_handlers::rotate_api_key),
+        )
+}
+
+pub fn saved_search_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api/searches",
+            get(saved_search_handlers::list_saved_searches)
+                .post(saved_search_handlers::create_saved_search),
+        )
+        .route(
+            "/api/searches/:name",
+            delete(saved_search_handlers::delete_saved_search),
+        )
+        .route(
+            "/api/searches/:name/matches",
+            get(saved_search_handlers::list_saved_search_matches),
+        )
+}
+
+pub fn collection_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api/collections",
+            get(collection_handlers::list_collections)
+                .post(collection_handlers::create_collection),
+        )
+        .route(
+            "/api/collections/:id",
+            get(collection_handlers::get_collection)
+                .patch(collection_handlers::update_collection)
+                .delete(collection_handlers::delete_collection),
+        )
+        .route(
+            "/api/collections/:id/items",
+            post(collection_handlers::add_collection_item)
+                .put(collection_handlers::reorder_collection),
+        )
+        .route(
+            "/api/collections/:id/items/:contract",
+            delete(collection_handlers::remove_collection_item),
+        )
+}
+
+pub fn account_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api/me",
+            delete(account_handlers::request_account_deletion),
+        )
+        .route("/api/me/export", get(account_handlers::export_account))
+        .route("/api/me/usage", get(api_usage::my_api_usage))
+        .route("/api/me/alerts", get(alert_handlers::list_my_alerts))
+        .route(
+            "/api/me/alerts/settings",
+            get(alert_handlers::get_alert_settings).put(alert_handlers::update_alert_settings),
+        )
+        .route(
+            "/api/me/alerts/:id/acknowledge",
+            post(alert_handlers::acknowledge_alert),
+        )
+        .route(
+            "/api/me/deletion",
+            get(account_handlers::get_account_deletion)
+                .delete(account_handlers::cancel_account_deletion),
+        )
+}
+
+pub fn patch_routes() -> Router<AppState> {
+    Router::new()
+        .route("/api/patches", post(patch_handlers::create_patch))
+        .route("/api/patches/:id", get(patch_handlers::get_patch))
+        .route(
+            "/api/patches/:id/audits",
+            get(patch_handlers::list_patch_audits),
+        )
+        .route(
+            "/api/patches/:id/affected",
+            get(patch_handlers::list_affected),
+        )
+        .route(
+            "/api/contracts/:id/advisories",
+            get(alert_handlers::list_advisories).post(alert_handlers::create_advisory),
+        )
+        .route(
+            "/api/patches/:id/approve",
+            post(patch_handlers::approve_patch),
+        )
+        .route("/api/patches/:id/apply", post(patch_handlers::apply_patch))
+        .route(
+            "/api/contracts/:id/patches",
+            get(patch_handlers::list_contract_patches),
+        )
+}
+
+pub fn publish_session_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api/publish/sessions",
+            post(publish_session_handlers::create_publish_session),
+        )
+        .route(
+            "/api/publish/sessions/:id",
+            get(publish_session_handlers::get_publish_session),
+        )
+        .route(
+            "/api/publish/sessions/:id/parts/:kind",
+            put(publish_session_handlers::upload_publish_part),
+        )
+        .route(
+            "/api/publish/sessions/:id/commit",
+            post(publish_session_handlers::commit_publish_session),
+        )
+        .route(
+            "/api/publish/sessions/:id/cancel",
+            post(publish_session_handlers::cancel_publish_session),
+        )
+}
+
+pub fn org_routes() -> Router<AppState> {
+    Router::new()
+        .route("/api/orgs", post(org_handlers::create_organization))
+        .route("/api/orgs/:name", get(org_handlers::get_organization))
+        .route(
+            "/api/orgs/:name/usage",
+            get(quota_handlers::get_organization_usage),
+        )
+        .route(
+            "/api/orgs/:name/members",
+            post(org_handlers::add_organization_member),
+        )
+        .route(
+            "/api/orgs/:name/members/:address",
+            delete(org_handlers::remove_organization_member),
+        )
+        .route(
+            "/api/contracts/:id/visibility",
+            patch(org_handlers::update_contract_visibility),
+        )
+        .route("/api/usage", get(quota_handlers::get_my_usage))
+}
+
+pub fn feed_routes() -> Router<AppState> {
+    Router::new()
+        .route("/feed/releases.atom", get(feed_handlers::releases_feed))
+        .route(
+            "/feed/publishers/:file",
+            get(feed_handlers::publisher_feed),
+        )
+        .route("/sitemap.xml", get(feed_handlers::sitemap))
+        .route("/sitemaps/:file", get(feed_handlers::sitemap_file))
+}
+
+pub fn admin_routes() -> Router<AppState> {
+    Router::new()
+        .route("/api/admin/overview", get(admin_handlers::get_overview))
+        .route(
+            "/api/admin/gc",
+            get(artifact_gc::preview_gc).post(artifact_gc::run_gc),
+        )
+        .route("/api/admin/flags", get(registry_flag_handlers::list_flags))
+        .route(
+            "/api/admin/flags/:name",
+            put(registry_flag_handlers::update_flag),
+        )
+        .route(
+            "/api/admin/flags/:name/audit",
+            get(registry_flag_handlers::get_flag_audit),
+        )
+        .route(
+            "/api/admin/maintenance",
+            get(maintenance_mode::get_maintenance).put(maintenance_mode::update_maintenance),
+        )
+        .route(
+            "/api/admin/reindex",
+            get(reindex::get_reindex).post(reindex::start_reindex),
+        )
+        .route("/api/admin/backfills", get(backfill::list_backfills))
+        .route("/api/admin/backfills/:name", get(backfill::get_backfill))
+        .route(
+            "/api/admin/backfills/:name/run",
+            post(backfill::run_backfill),
+        )
+        .route(
+            "/api/admin/index/rebuild",
+            post(sparse_index::rebuild_index),
+        )
+        .route(
+            "/api/admin/tenants",
+            get(tenancy::list_tenants).post(tenancy::create_tenant),
+        )
+        .route(
+            "/api/admin/cli/releases",
+            post(cli_release_handlers::publish_release),
+        )
+        .route(
+            "/api/admin/reviews/flagged",
+            get(review_handlers::list_flagged_reviews),
+        )
+        .route(
+            "/api/admin/reviews/:review_id/moderate",
+            post(review_handlers::moderate_review),
+        )
+        .route("/api/admin/reports", get(abuse_report_handlers::list_reports))
+        .route(
+            "/api/admin/contracts/:id/moderate",
+            post(abuse_report_handlers::moderate_contract),
+        )
+        .route_layer(middleware::from_fn(admin_auth::require_admin))
+}
+
+/// Static sparse index files, meant to sit behind a CDN
+pub fn index_routes() -> Router<AppState> {
+    Router::new().route("/index/*path", get(sparse_index::get_index_file))
 }
 
 pub fn migration_routes() -> Router<AppState> {
@@ -219,6 +612,15 @@ pub fn compatibility_dashboard_routes() -> Router<AppState> {
         )
 }
 
+pub fn compat_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api/compat/check",
+            post(compat_handlers::check_compatibility),
+        )
+        .route("/api/interfaces", get(interfaces::list_interfaces))
+}
+
 pub fn canary_routes() -> Router<AppState> {
     Router::new()
 }
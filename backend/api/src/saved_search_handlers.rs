@@ -0,0 +1,419 @@
+// saved_search_handlers.rs
+// Saved search queries per signed-in user, and notifications when new contracts match them.
+
+use std::time::Duration;
+
+use axum::{
+    extract::{
+        rejection::{JsonRejection, QueryRejection},
+        Path, Query, State,
+    },
+    http::StatusCode,
+    Json,
+};
+use serde::Deserialize;
+use serde_json::json;
+use shared::{ApiScope, Contract, CreateSavedSearchRequest, SavedSearch, SavedSearchMatch};
+
+use crate::error::{ApiError, ApiResult};
+use crate::handlers::{db_internal_error, map_json_rejection};
+use crate::state::AppState;
+use crate::tenancy::CurrentTenant;
+use crate::user_auth::AuthUser;
+use crate::webhook_target::{validate_webhook_url, webhook_client};
+
+const MAX_SEARCH_NAME_LENGTH: usize = 64;
+const MAX_QUERY_LENGTH: usize = 200;
+const MAX_SAVED_SEARCHES_PER_USER: i64 = 50;
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+fn validate_request(req: &CreateSavedSearchRequest) -> ApiResult<()> {
+    let name = req.name.trim();
+    if name.is_empty()
+        || name.len() > MAX_SEARCH_NAME_LENGTH
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(ApiError::bad_request(
+            "InvalidSearchName",
+            format!(
+                "Search names must be 1-{} letters, digits, '-' or '_'",
+                MAX_SEARCH_NAME_LENGTH
+            ),
+        ));
+    }
+    let query = req.query.trim();
+    if query.is_empty() || query.len() > MAX_QUERY_LENGTH {
+        return Err(ApiError::bad_request(
+            "InvalidQuery",
+            format!("query must be 1-{} characters", MAX_QUERY_LENGTH),
+        ));
+    }
+    if let Some(email) = &req.email {
+        if !email.contains('@') {
+            return Err(ApiError::bad_request(
+                "InvalidEmail",
+                "email must be an email address",
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Whether a newly published contract satisfies a saved search. Uses the same
+/// case-insensitive substring match as `GET /api/contracts?query=`.
+pub fn search_matches(search: &SavedSearch, contract: &Contract) -> bool {
+    if search
+        .network
+        .as_ref()
+        .is_some_and(|network| *network != contract.network)
+    {
+        return false;
+    }
+    if let Some(category) = &search.category {
+        if !contract
+            .category
+            .as_deref()
+            .is_some_and(|c| c.eq_ignore_ascii_case(category))
+        {
+            return false;
+        }
+    }
+    let needle = search.query.to_lowercase();
+    contract.name.to_lowercase().contains(&needle)
+        || contract
+            .description
+            .as_deref()
+            .is_some_and(|d| d.to_lowercase().contains(&needle))
+}
+
+/// POST /api/searches
+pub async fn create_saved_search(
+    State(state): State<AppState>,
+    user: AuthUser,
+    tenant: CurrentTenant,
+    payload: Result<Json<CreateSavedSearchRequest>, JsonRejection>,
+) -> ApiResult<(StatusCode, Json<SavedSearch>)> {
+    user.require(ApiScope::Publish)?;
+    let Json(req) = payload.map_err(map_json_rejection)?;
+    validate_request(&req)?;
+    if let Some(url) = &req.webhook_url {
+        validate_webhook_url(url).await?;
+    }
+
+    let existing: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM saved_searches WHERE owner_address = $1 AND tenant_id = $2",
+    )
+    .bind(&user.address)
+    .bind(tenant.id)
+    .fetch_one(&state.db)
+    .await
+    .map_err(|err| db_internal_error("count saved searches", err))?;
+    if existing >= MAX_SAVED_SEARCHES_PER_USER {
+        return Err(ApiError::unprocessable(
+            "TooManySavedSearches",
+            format!(
+                "At most {} saved searches are allowed per user",
+                MAX_SAVED_SEARCHES_PER_USER
+            ),
+        ));
+    }
+
+    let search: SavedSearch = sqlx::query_as(
+        "INSERT INTO saved_searches \
+             (owner_address, name, query, network, category, webhook_url, email, tenant_id) \
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8) \
+         ON CONFLICT (tenant_id, owner_address, name) DO UPDATE SET \
+             query = EXCLUDED.query, network = EXCLUDED.network, \
+             category = EXCLUDED.category, webhook_url = EXCLUDED.webhook_url, \
+             email = EXCLUDED.email \
+         RETURNING *",
+    )
+    .bind(&user.address)
+    .bind(req.name.trim())
+    .bind(req.query.trim())
+    .bind(&req.network)
+    .bind(req.category.as_deref())
+    .bind(req.webhook_url.as_deref())
+    .bind(req.email.as_deref())
+    .bind(tenant.id)
+    .fetch_one(&state.db)
+    .await
+    .map_err(|err| db_internal_error("save search", err))?;
+
+    Ok((StatusCode::CREATED, Json(search)))
+}
+
+/// GET /api/searches
+pub async fn list_saved_searches(
+    State(state): State<AppState>,
+    user: AuthUser,
+    tenant: CurrentTenant,
+) -> ApiResult<Json<Vec<SavedSearch>>> {
+    user.require(ApiScope::Read)?;
+    let searches: Vec<SavedSearch> = sqlx::query_as(
+        "SELECT * FROM saved_searches WHERE owner_address = $1 AND tenant_id = $2 ORDER BY name",
+    )
+    .bind(&user.address)
+    .bind(tenant.id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("list saved searches", err))?;
+    Ok(Json(searches))
+}
+
+/// DELETE /api/searches/:name
+pub async fn delete_saved_search(
+    State(state): State<AppState>,
+    user: AuthUser,
+    tenant: CurrentTenant,
+    Path(name): Path<String>,
+) -> ApiResult<StatusCode> {
+    user.require(ApiScope::Publish)?;
+    let result = sqlx::query(
+        "DELETE FROM saved_searches WHERE owner_address = $1 AND name = $2 AND tenant_id = $3",
+    )
+    .bind(&user.address)
+    .bind(&name)
+    .bind(tenant.id)
+    .execute(&state.db)
+    .await
+    .map_err(|err| db_internal_error("delete saved search", err))?;
+    if result.rows_affected() == 0 {
+        return Err(saved_search_not_found(&name));
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+fn saved_search_not_found(name: &str) -> ApiError {
+    ApiError::not_found(
+        "SavedSearchNotFound",
+        format!("No saved search named '{}'", name),
+    )
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SavedSearchMatchesQuery {
+    /// Only return matches with an id greater than this, for polling
+    pub after: Option<i64>,
+    pub limit: Option<i64>,
+}
+
+/// GET /api/searches/:name/matches?after=&limit= — newest first
+pub async fn list_saved_search_matches(
+    State(state): State<AppState>,
+    user: AuthUser,
+    tenant: CurrentTenant,
+    Path(name): Path<String>,
+    query: Result<Query<SavedSearchMatchesQuery>, QueryRejection>,
+) -> ApiResult<Json<Vec<SavedSearchMatch>>> {
+    user.require(ApiScope::Read)?;
+    let Query(query) = query
+        .map_err(|err| ApiError::bad_request("InvalidQuery", format!("Invalid query: {}", err)))?;
+
+    let search_id: uuid::Uuid = sqlx::query_scalar(
+        "SELECT id FROM saved_searches WHERE owner_address = $1 AND name = $2 AND tenant_id = $3",
+    )
+    .bind(&user.address)
+    .bind(&name)
+    .bind(tenant.id)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|err| db_internal_error("fetch saved search", err))?
+    .ok_or_else(|| saved_search_not_found(&name))?;
+
+    let matches: Vec<SavedSearchMatch> = sqlx::query_as(
+        "SELECT m.id, m.saved_search_id, m.contract_id, c.name AS contract_name, \
+                m.matched_at \
+         FROM saved_search_matches m \
+         JOIN contracts c ON c.id = m.contract_id \
+         WHERE m.saved_search_id = $1 AND m.id > $2 \
+         ORDER BY m.id DESC \
+         LIMIT $3",
+    )
+    .bind(search_id)
+    .bind(query.after.unwrap_or(0))
+    .bind(query.limit.unwrap_or(50).clamp(1, 200))
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("list saved search matches", err))?;
+
+    Ok(Json(matches))
+}
+
+/// Record `contract` against every saved search it satisfies and deliver the
+/// notifications. Runs in the background after a publish; failures are logged.
+pub async fn notify_saved_searches(state: AppState, contract: Contract) {
+    // Private contracts are only announced to members of their organization,
+    // and contracts only to searches saved in their own tenant
+    let candidates: Vec<SavedSearch> = match sqlx::query_as(
+        "SELECT * FROM saved_searches WHERE (network IS NULL OR network = $1) \
+           AND (NOT $2 OR owner_address IN \
+                (SELECT address FROM organization_members WHERE organization_id = $3)) \
+           AND tenant_id = (SELECT tenant_id FROM contracts WHERE id = $4)",
+    )
+    .bind(&contract.network)
+    .bind(contract.is_private)
+    .bind(contract.organization_id)
+    .bind(contract.id)
+    .fetch_all(&state.db)
+    .await
+    {
+        Ok(rows) => rows,
+        Err(err) => {
+            tracing::warn!(error = ?err, "failed to load saved searches");
+            return;
+        }
+    };
+
+    for search in candidates
+        .iter()
+        .filter(|search| search_matches(search, &contract))
+    {
+        if let Err(err) = deliver_match(&state, search, &contract).await {
+            tracing::warn!(
+                saved_search = %search.id,
+                contract = %contract.id,
+                error = ?err,
+                "failed to record saved search match"
+            );
+        }
+    }
+}
+
+async fn deliver_match(
+    state: &AppState,
+    search: &SavedSearch,
+    contract: &Contract,
+) -> Result<(), sqlx::Error> {
+    let match_id: Option<i64> = sqlx::query_scalar(
+        "INSERT INTO saved_search_matches (saved_search_id, contract_id) VALUES ($1, $2) \
+         ON CONFLICT (saved_search_id, contract_id) DO NOTHING RETURNING id",
+    )
+    .bind(search.id)
+    .bind(contract.id)
+    .fetch_optional(&state.db)
+    .await?;
+    // Already notified for this contract
+    let Some(match_id) = match_id else {
+        return Ok(());
+    };
+
+    if let Some(url) = &search.webhook_url {
+        let event = json!({
+            "event": "saved_search.match",
+            "saved_search": search.name,
+            "query": search.query,
+            "contract": contract,
+        });
+        // The host is checked again here: DNS may have moved since the search was saved
+        let status = match webhook_client(url, WEBHOOK_TIMEOUT).await {
+            Err(err) => {
+                tracing::warn!(url = %url, error = %err, "saved search webhook refused");
+                "failed"
+            }
+            Ok((client, target)) => match client.post(target).json(&event).send().await {
+                Ok(response) if response.status().is_success() => "delivered",
+                Ok(response) => {
+                    tracing::warn!(
+                        url = %url,
+                        status = %response.status(),
+                        "saved search webhook rejected"
+                    );
+                    "failed"
+                }
+                Err(err) => {
+                    tracing::warn!(url = %url, error = %err, "saved search webhook failed");
+                    "failed"
+                }
+            },
+        };
+        sqlx::query("UPDATE saved_search_matches SET webhook_status = $1 WHERE id = $2")
+            .bind(status)
+            .bind(match_id)
+            .execute(&state.db)
+            .await?;
+    }
+
+    if let Some(email) = &search.email {
+        sqlx::query(
+            "INSERT INTO notification_logs \
+                 (contract_id, notification_type, recipients, message, status) \
+             VALUES ($1, 'saved_search_match', $2, $3, 'pending')",
+        )
+        .bind(contract.id)
+        .bind(vec![email.clone()])
+        .bind(format!(
+            "{} matches your saved search '{}' ({})",
+            contract.name, search.name, search.query
+        ))
+        .execute(&state.db)
+        .await?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use shared::Network;
+    use uuid::Uuid;
+
+    fn search(query: &str, network: Option<Network>, category: Option<&str>) -> SavedSearch {
+        SavedSearch {
+            id: Uuid::nil(),
+            owner_address: "GABC".to_string(),
+            name: "amm".to_string(),
+            query: query.to_string(),
+            network,
+            category: category.map(str::to_string),
+            webhook_url: None,
+            email: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    fn contract() -> Contract {
+        serde_json::from_value(json!({
+            "id": Uuid::nil(),
+            "contract_id": "CABC",
+            "wasm_hash": "00",
+            "name": "stable-swap",
+            "description": "Constant-product AMM for stablecoins",
+            "publisher_id": Uuid::nil(),
+            "network": "testnet",
+            "is_verified": false,
+            "category": "DEX",
+            "tags": [],
+            "created_at": Utc::now(),
+            "updated_at": Utc::now(),
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn matches_name_or_description_case_insensitively() {
+        assert!(search_matches(&search("SWAP", None, None), &contract()));
+        assert!(search_matches(&search("amm", None, None), &contract()));
+        assert!(!search_matches(&search("lending", None, None), &contract()));
+    }
+
+    #[test]
+    fn network_and_category_filters_apply() {
+        assert!(search_matches(
+            &search("amm", Some(Network::Testnet), Some("dex")),
+            &contract()
+        ));
+        assert!(!search_matches(
+            &search("amm", Some(Network::Mainnet), None),
+            &contract()
+        ));
+        assert!(!search_matches(
+            &search("amm", None, Some("oracle")),
+            &contract()
+        ));
+    }
+}
@@ -0,0 +1,147 @@
+// sbom_handlers.rs
+// Server-generated SBOMs (CycloneDX / SPDX) per contract version.
+
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use serde_json::Value;
+use shared::{
+    build_sbom, parse_cargo_lock, ComponentKind, SbomComponent, SbomFormat, SbomQuery, SemVer,
+    VersionRange,
+};
+
+use crate::error::{ApiError, ApiResult};
+use crate::handlers::{db_internal_error, fetch_contract_identity};
+use crate::private_registry::{ensure_contract_visible, Viewer};
+use crate::state::AppState;
+
+/// The newest published version admitted by a dependency's constraint. An
+/// exact constraint is its own version; anything unresolvable is
+/// `NOASSERTION`, as SPDX spells "unknown".
+fn resolved_version(constraint: &str, published: &[String]) -> String {
+    let newest = VersionRange::parse(constraint).and_then(|range| {
+        published
+            .iter()
+            .filter_map(|version| SemVer::parse(version.trim()))
+            .filter(|version| range.matches(version))
+            .max()
+    });
+    match newest.or_else(|| SemVer::parse(constraint.trim())) {
+        Some(version) => version.to_string(),
+        None => "NOASSERTION".to_string(),
+    }
+}
+
+/// GET /api/contracts/:id/sbom?version=&format=
+pub async fn get_contract_sbom(
+    State(state): State<AppState>,
+    viewer: Viewer,
+    Path(id): Path<String>,
+    Query(query): Query<SbomQuery>,
+) -> ApiResult<Json<Value>> {
+    let format: SbomFormat = query
+        .format
+        .as_deref()
+        .unwrap_or("cyclonedx")
+        .parse()
+        .map_err(|e: String| ApiError::bad_request("InvalidSbomFormat", e))?;
+
+    let (contract_uuid, _) = fetch_contract_identity(&state, viewer.tenant_id, &id).await?;
+    ensure_contract_visible(&state, &viewer, contract_uuid).await?;
+
+    let name: String = sqlx::query_scalar("SELECT name FROM contracts WHERE id = $1")
+        .bind(contract_uuid)
+        .fetch_one(&state.db)
+        .await
+        .map_err(|err| db_internal_error("fetch contract name", err))?;
+
+    let row: Option<(String, Option<String>)> = match query.version {
+        Some(ref version) => {
+            sqlx::query_as(
+                "SELECT version, cargo_lock FROM contract_versions
+             WHERE contract_id = $1 AND version = $2",
+            )
+            .bind(contract_uuid)
+            .bind(version)
+            .fetch_optional(&state.db)
+            .await
+        }
+        None => {
+            sqlx::query_as(
+                "SELECT version, cargo_lock FROM contract_versions
+             WHERE contract_id = $1 ORDER BY created_at DESC LIMIT 1",
+            )
+            .bind(contract_uuid)
+            .fetch_optional(&state.db)
+            .await
+        }
+    }
+    .map_err(|err| db_internal_error("fetch contract version for sbom", err))?;
+
+    let (version, cargo_lock) = row.ok_or_else(|| {
+        ApiError::not_found(
+            "VersionNotFound",
+            format!(
+                "No version {} found for contract {}",
+                query.version.as_deref().unwrap_or("(latest)"),
+                id
+            ),
+        )
+    })?;
+
+    let registry_deps: Vec<(String, String, Vec<String>)> = sqlx::query_as(
+        "SELECT d.dependency_name, d.version_constraint,
+                ARRAY(SELECT v.version FROM contract_versions v
+                      WHERE v.contract_id = d.dependency_contract_id)
+         FROM contract_dependencies d
+         WHERE d.contract_id = $1 ORDER BY d.dependency_name",
+    )
+    .bind(contract_uuid)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("fetch dependencies for sbom", err))?;
+
+    let mut components: Vec<SbomComponent> = registry_deps
+        .into_iter()
+        .map(|(name, constraint, published)| SbomComponent {
+            kind: ComponentKind::Registry,
+            name,
+            version: resolved_version(&constraint, &published),
+            checksum: None,
+        })
+        .collect();
+    if let Some(ref lock) = cargo_lock {
+        components.extend(parse_cargo_lock(lock));
+    }
+
+    Ok(Json(build_sbom(format, &name, &version, &components)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn published(versions: &[&str]) -> Vec<String> {
+        versions.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn components_carry_the_newest_admitted_version() {
+        let versions = published(&["1.0.0", "1.4.2", "2.0.0"]);
+        assert_eq!(resolved_version("^1.0.0", &versions), "1.4.2");
+        assert_eq!(resolved_version(">=1.0.0", &versions), "2.0.0");
+        assert_eq!(resolved_version("1.0.0", &versions), "1.0.0");
+    }
+
+    #[test]
+    fn unresolvable_constraints_are_not_asserted() {
+        assert_eq!(
+            resolved_version("^3.0.0", &published(&["1.0.0"])),
+            "NOASSERTION"
+        );
+        assert_eq!(resolved_version("latest", &[]), "NOASSERTION");
+        // Not published (yet), but an exact pin still names its version
+        assert_eq!(resolved_version("2.1.0", &[]), "2.1.0");
+    }
+}
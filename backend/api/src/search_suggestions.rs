@@ -0,0 +1,166 @@
+// search_suggestions.rs
+// Typo tolerance for contract search: fuzzy match clauses and "did you mean"
+// suggestions drawn from the words that appear in indexed contracts.
+
+use crate::private_registry::Viewer;
+
+/// Minimum pg_trgm word similarity for a fuzzy match ("tokn" ~ "token" is 0.6)
+pub const FUZZY_MATCH_THRESHOLD: f32 = 0.5;
+
+/// Lowercase words of a search query, as they appear in `contract_search_terms`
+fn query_terms(query: &str) -> Vec<String> {
+    query
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .map(str::to_ascii_lowercase)
+        .collect()
+}
+
+/// Rebuild the query with each word swapped for its closest known term;
+/// None when nothing changed
+fn compose_suggestion(terms: &[String], closest: &[Option<String>]) -> Option<String> {
+    let mut changed = false;
+    let words: Vec<&str> = terms
+        .iter()
+        .zip(closest)
+        .map(|(term, best)| match best {
+            Some(best) if best != term => {
+                changed = true;
+                best.as_str()
+            }
+            _ => term.as_str(),
+        })
+        .collect();
+    changed.then(|| words.join(" "))
+}
+
+/// SQL condition matching `query` exactly (substring) or approximately in a
+/// contract's name, description or tags. `prefix` qualifies the columns, e.g. `"c."`.
+pub fn match_clause(query: &str, prefix: &str) -> String {
+    let q = query.replace('\'', "''");
+    format!(
+        " AND ({p}name ILIKE '%{q}%' OR {p}description ILIKE '%{q}%' \
+           OR word_similarity('{q}', {p}name) >= {t} \
+           OR word_similarity('{q}', COALESCE({p}description, '')) >= {t} \
+           OR EXISTS (SELECT 1 FROM unnest({p}tags) AS tag WHERE similarity(tag, '{q}') >= {t}))",
+        p = prefix,
+        q = q,
+        t = FUZZY_MATCH_THRESHOLD
+    )
+}
+
+/// Relevance score for ordering (higher is better): exact name, name
+/// substring, description substring, then fuzzy similarity below 1
+pub fn relevance_score(query: &str) -> String {
+    let q = query.replace('\'', "''");
+    format!(
+        "CASE WHEN c.name ILIKE '{q}' THEN 4.0 \
+              WHEN c.name ILIKE '%{q}%' THEN 3.0 \
+              WHEN c.description ILIKE '%{q}%' THEN 2.0 \
+              ELSE GREATEST(word_similarity('{q}', c.name), \
+                            word_similarity('{q}', COALESCE(c.description, '')))::float8 END",
+        q = q
+    )
+}
+
+/// Relevance behind the `search_ranking_v2` flag: the same match tiers, with
+/// quality (0-100) breaking ties inside a tier instead of after it
+pub fn ranked_relevance_score(query: &str) -> String {
+    format!("({}) + c.quality_score / 100.0", relevance_score(query))
+}
+
+/// "Did you mean" text for a query without exact matches; None when the query
+/// already matches something or no known word is close to it. Only contracts
+/// the viewer can see count, so private names never leak through suggestions.
+pub async fn suggest(db: &sqlx::PgPool, viewer: &Viewer, query: &str) -> Option<String> {
+    let pattern = format!(
+        "%{}%",
+        query
+            .replace('\\', "\\\\")
+            .replace('%', "\\%")
+            .replace('_', "\\_")
+    );
+    let exact: Result<bool, _> = sqlx::query_scalar(&format!(
+        "SELECT EXISTS(SELECT 1 FROM contracts c \
+         WHERE (c.name ILIKE $1 OR c.description ILIKE $1){})",
+        viewer.visibility_clause("c.")
+    ))
+    .bind(&pattern)
+    .fetch_one(db)
+    .await;
+    match exact {
+        Ok(false) => {}
+        Ok(true) => return None,
+        Err(err) => {
+            tracing::warn!(error = ?err, "search suggestion lookup failed");
+            return None;
+        }
+    }
+
+    let terms = query_terms(query);
+    if terms.is_empty() {
+        return None;
+    }
+    let closest: Vec<Option<String>> = match sqlx::query_scalar(
+        "SELECT (SELECT s.term FROM contract_search_terms s \
+                 WHERE s.tenant_id = $2 AND s.term % t.term \
+                 ORDER BY similarity(s.term, t.term) DESC, s.term LIMIT 1) \
+         FROM unnest($1::text[]) WITH ORDINALITY AS t(term, pos) \
+         ORDER BY t.pos",
+    )
+    .bind(&terms)
+    .bind(viewer.tenant_id)
+    .fetch_all(db)
+    .await
+    {
+        Ok(rows) => rows,
+        Err(err) => {
+            tracing::warn!(error = ?err, "search suggestion lookup failed");
+            return None;
+        }
+    };
+    compose_suggestion(&terms, &closest)
+}
+
+/// Pick up words from contracts published or edited since the last refresh
+pub async fn refresh_terms(pool: &sqlx::PgPool) -> Result<(), sqlx::Error> {
+    sqlx::query("REFRESH MATERIALIZED VIEW CONCURRENTLY contract_search_terms")
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn query_is_split_into_lowercase_terms() {
+        assert_eq!(
+            query_terms("Liqudity-Pool  v2"),
+            vec!["liqudity", "pool", "v2"]
+        );
+        assert!(query_terms("  --- ").is_empty());
+    }
+
+    #[test]
+    fn suggestion_replaces_only_misspelled_terms() {
+        let terms = query_terms("liqudity pool");
+        let closest = vec![Some("liquidity".to_string()), Some("pool".to_string())];
+        assert_eq!(
+            compose_suggestion(&terms, &closest).as_deref(),
+            Some("liquidity pool")
+        );
+        let unchanged = vec![None, Some("pool".to_string())];
+        assert_eq!(compose_suggestion(&terms, &unchanged), None);
+    }
+
+    #[test]
+    fn clauses_escape_quotes() {
+        let clause = match_clause("o'brien", "c.");
+        assert!(clause.contains("c.name ILIKE '%o''brien%'"));
+        assert!(!clause.contains("o'brien"));
+        assert!(relevance_score("o'brien").contains("'o''brien'"));
+        assert!(ranked_relevance_score("o'brien").contains("'o''brien'"));
+    }
+}
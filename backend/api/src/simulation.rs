@@ -0,0 +1,551 @@
+// simulation.rs
+// Sandboxed contract calls: a stored WASM is loaded into an in-process Soroban
+// host with a fresh ledger, invoked once (or for an example, a few times in a
+// row) and thrown away. Arguments are encoded from the contract spec; results
+// and events are decoded to JSON.
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use once_cell::sync::Lazy;
+use serde_json::{json, Value};
+use shared::{scval_to_json, scval_to_xdr, SimulationEvent, SimulationResources};
+use soroban_sdk::testutils::Events as _;
+use soroban_sdk::xdr::{
+    Duration as XdrDuration, Int128Parts, Int256Parts, ScAddress, ScBytes, ScErrorType, ScMap,
+    ScMapEntry, ScString, ScSymbol, ScVal, ScVec, TimePoint, UInt128Parts, UInt256Parts,
+};
+use soroban_sdk::{Env, IntoVal, Symbol, TryFromVal, Val};
+use tokio::sync::Semaphore;
+
+use crate::type_safety::{
+    parse_value_string, CallValidator, ContractABI, ParsedValue, SorobanType,
+};
+
+/// Sandboxes running at once; each holds a blocking thread until its budget runs out
+const MAX_CONCURRENT_SANDBOXES: usize = 4;
+
+pub static SANDBOX_SLOTS: Lazy<Semaphore> = Lazy::new(|| Semaphore::new(MAX_CONCURRENT_SANDBOXES));
+
+fn signed(value: &ParsedValue) -> Option<i128> {
+    match value {
+        ParsedValue::Integer(n) => Some(*n),
+        ParsedValue::UnsignedInteger(n) => i128::try_from(*n).ok(),
+        _ => None,
+    }
+}
+
+fn unsigned(value: &ParsedValue) -> Option<u128> {
+    match value {
+        ParsedValue::Integer(n) => u128::try_from(*n).ok(),
+        ParsedValue::UnsignedInteger(n) => Some(*n),
+        _ => None,
+    }
+}
+
+fn text(value: &ParsedValue) -> Option<&str> {
+    match value {
+        ParsedValue::String(s) | ParsedValue::Symbol(s) | ParsedValue::Address(s) => Some(s),
+        _ => None,
+    }
+}
+
+fn symbol(name: &str) -> Result<ScVal, String> {
+    let symbol = name
+        .to_string()
+        .try_into()
+        .map_err(|_| format!("`{}` is not a valid symbol", name))?;
+    Ok(ScVal::Symbol(ScSymbol(symbol)))
+}
+
+fn vec_of(items: Vec<ScVal>) -> Result<ScVal, String> {
+    let items = items
+        .try_into()
+        .map_err(|_| "too many elements".to_string())?;
+    Ok(ScVal::Vec(Some(ScVec(items))))
+}
+
+/// Map entries must be sorted by key for the host to accept them
+fn map_of(mut entries: Vec<ScMapEntry>) -> Result<ScVal, String> {
+    entries.sort_by(|a, b| a.key.cmp(&b.key));
+    let entries = entries
+        .try_into()
+        .map_err(|_| "too many entries".to_string())?;
+    Ok(ScVal::Map(Some(ScMap(entries))))
+}
+
+/// Encode a validated argument as the XDR value the contract expects. `types`
+/// resolves named types from the contract spec.
+pub fn to_scval(
+    value: &ParsedValue,
+    ty: &SorobanType,
+    types: &HashMap<String, SorobanType>,
+) -> Result<ScVal, String> {
+    let mismatch = || format!("expected {}", ty.display_name());
+    let scval = match ty {
+        SorobanType::Custom { name } => {
+            let resolved = types
+                .get(name)
+                .ok_or_else(|| format!("type `{}` is not defined in the contract spec", name))?;
+            return to_scval(value, resolved, types);
+        }
+        SorobanType::Void => ScVal::Void,
+        SorobanType::Option { value_type } => match value {
+            ParsedValue::Null => ScVal::Void,
+            _ => return to_scval(value, value_type, types),
+        },
+        SorobanType::Bool => match value {
+            ParsedValue::Bool(b) => ScVal::Bool(*b),
+            _ => return Err(mismatch()),
+        },
+        SorobanType::I32 => ScVal::I32(
+            signed(value)
+                .and_then(|n| n.try_into().ok())
+                .ok_or_else(mismatch)?,
+        ),
+        SorobanType::I64 => ScVal::I64(
+            signed(value)
+                .and_then(|n| n.try_into().ok())
+                .ok_or_else(mismatch)?,
+        ),
+        SorobanType::U32 => ScVal::U32(
+            unsigned(value)
+                .and_then(|n| n.try_into().ok())
+                .ok_or_else(mismatch)?,
+        ),
+        SorobanType::U64 => ScVal::U64(
+            unsigned(value)
+                .and_then(|n| n.try_into().ok())
+                .ok_or_else(mismatch)?,
+        ),
+        SorobanType::Timepoint => ScVal::Timepoint(TimePoint(
+            unsigned(value)
+                .and_then(|n| n.try_into().ok())
+                .ok_or_else(mismatch)?,
+        )),
+        SorobanType::Duration => ScVal::Duration(XdrDuration(
+            unsigned(value)
+                .and_then(|n| n.try_into().ok())
+                .ok_or_else(mismatch)?,
+        )),
+        SorobanType::I128 => {
+            let n = signed(value).ok_or_else(mismatch)?;
+            ScVal::I128(Int128Parts {
+                hi: (n >> 64) as i64,
+                lo: n as u64,
+            })
+        }
+        SorobanType::U128 => {
+            let n = unsigned(value).ok_or_else(mismatch)?;
+            ScVal::U128(UInt128Parts {
+                hi: (n >> 64) as u64,
+                lo: n as u64,
+            })
+        }
+        // Arguments are parsed as 128-bit values; sign-extend them to 256 bits
+        SorobanType::I256 => {
+            let n = signed(value).ok_or_else(mismatch)?;
+            let extension = if n < 0 { -1 } else { 0 };
+            ScVal::I256(Int256Parts {
+                hi_hi: extension,
+                hi_lo: extension as u64,
+                lo_hi: (n >> 64) as u64,
+                lo_lo: n as u64,
+            })
+        }
+        SorobanType::U256 => {
+            let n = unsigned(value).ok_or_else(mismatch)?;
+            ScVal::U256(UInt256Parts {
+                hi_hi: 0,
+                hi_lo: 0,
+                lo_hi: (n >> 64) as u64,
+                lo_lo: n as u64,
+            })
+        }
+        SorobanType::Symbol => symbol(text(value).ok_or_else(mismatch)?)?,
+        SorobanType::String => {
+            let s = text(value).ok_or_else(mismatch)?;
+            ScVal::String(ScString(s.to_string().try_into().map_err(|_| mismatch())?))
+        }
+        SorobanType::Bytes | SorobanType::BytesN { .. } => match value {
+            ParsedValue::Bytes(bytes) => {
+                ScVal::Bytes(ScBytes(bytes.clone().try_into().map_err(|_| mismatch())?))
+            }
+            _ => return Err(mismatch()),
+        },
+        SorobanType::Address => {
+            let address = text(value).ok_or_else(mismatch)?;
+            ScVal::Address(
+                ScAddress::from_str(address)
+                    .map_err(|_| format!("`{}` is not a valid address", address))?,
+            )
+        }
+        SorobanType::Vec { element_type } => match value {
+            ParsedValue::Array(items) => vec_of(
+                items
+                    .iter()
+                    .map(|item| to_scval(item, element_type, types))
+                    .collect::<Result<_, _>>()?,
+            )?,
+            _ => return Err(mismatch()),
+        },
+        SorobanType::Tuple { elements } => match value {
+            ParsedValue::Array(items) if items.len() == elements.len() => vec_of(
+                items
+                    .iter()
+                    .zip(elements)
+                    .map(|(item, element)| to_scval(item, element, types))
+                    .collect::<Result<_, _>>()?,
+            )?,
+            _ => return Err(mismatch()),
+        },
+        SorobanType::Map {
+            key_type,
+            value_type,
+        } => {
+            let entries = match value {
+                ParsedValue::Map(pairs) => pairs
+                    .iter()
+                    .map(|(k, v)| {
+                        Ok(ScMapEntry {
+                            key: to_scval(k, key_type, types)?,
+                            val: to_scval(v, value_type, types)?,
+                        })
+                    })
+                    .collect::<Result<Vec<_>, String>>()?,
+                // JSON objects arrive with string keys; parse them as the key type
+                ParsedValue::Struct(fields) => fields
+                    .iter()
+                    .map(|(k, v)| {
+                        let key = parse_value_string(k, key_type).map_err(|e| e.message)?;
+                        Ok(ScMapEntry {
+                            key: to_scval(&key, key_type, types)?,
+                            val: to_scval(v, value_type, types)?,
+                        })
+                    })
+                    .collect::<Result<Vec<_>, String>>()?,
+                _ => return Err(mismatch()),
+            };
+            map_of(entries)?
+        }
+        SorobanType::Struct { name, fields } => {
+            let ParsedValue::Struct(values) = value else {
+                return Err(mismatch());
+            };
+            let field_value = |field: &str| {
+                values
+                    .get(field)
+                    .ok_or_else(|| format!("{} is missing field `{}`", name, field))
+            };
+            // Tuple structs (fields named 0, 1, ...) are encoded as vectors
+            let tuple_struct = !fields.is_empty()
+                && fields
+                    .iter()
+                    .all(|f| f.name.chars().all(|c| c.is_ascii_digit()));
+            if tuple_struct {
+                vec_of(
+                    fields
+                        .iter()
+                        .map(|f| to_scval(field_value(&f.name)?, &f.field_type, types))
+                        .collect::<Result<_, _>>()?,
+                )?
+            } else {
+                map_of(
+                    fields
+                        .iter()
+                        .map(|f| {
+                            Ok(ScMapEntry {
+                                key: symbol(&f.name)?,
+                                val: to_scval(field_value(&f.name)?, &f.field_type, types)?,
+                            })
+                        })
+                        .collect::<Result<_, String>>()?,
+                )?
+            }
+        }
+        SorobanType::Enum { name, variants } => {
+            // A unit variant by name, or `["Variant", field, ...]`
+            let (variant_name, fields) = match value {
+                ParsedValue::Array(items) => match items.split_first() {
+                    Some((head, rest)) => (text(head).ok_or_else(mismatch)?, rest),
+                    None => return Err(mismatch()),
+                },
+                _ => (text(value).ok_or_else(mismatch)?, &[][..]),
+            };
+            let variant = variants
+                .iter()
+                .find(|v| v.name == variant_name)
+                .ok_or_else(|| format!("{} has no variant `{}`", name, variant_name))?;
+            if let Some(discriminant) = variant.value {
+                ScVal::U32(discriminant)
+            } else {
+                let types_of_fields = variant.fields.as_deref().unwrap_or_default();
+                if fields.len() != types_of_fields.len() {
+                    return Err(format!(
+                        "{}::{} takes {} field(s)",
+                        name,
+                        variant_name,
+                        types_of_fields.len()
+                    ));
+                }
+                let mut items = vec![symbol(variant_name)?];
+                for (field, spec) in fields.iter().zip(types_of_fields) {
+                    items.push(to_scval(field, &spec.field_type, types)?);
+                }
+                vec_of(items)?
+            }
+        }
+        SorobanType::Result { .. } => {
+            return Err("Result values cannot be passed as arguments".to_string())
+        }
+    };
+    Ok(scval)
+}
+
+/// Validate string arguments against `function`'s spec and encode them
+pub fn encode_call(
+    abi: &ContractABI,
+    function: &str,
+    args: &[String],
+) -> Result<Vec<ScVal>, String> {
+    let validation = CallValidator::new(abi.clone()).validate_call(function, args);
+    if !validation.valid {
+        let reasons: Vec<String> = validation.errors.into_iter().map(|e| e.message).collect();
+        return Err(reasons.join("; "));
+    }
+    let spec = abi
+        .find_function(function)
+        .ok_or_else(|| format!("function `{}` is not in the contract spec", function))?;
+    spec.params
+        .iter()
+        .zip(validation.parsed_params.unwrap_or_default())
+        .map(|(param, parsed)| {
+            to_scval(&parsed.value, &param.param_type, &abi.types)
+                .map_err(|reason| format!("{}: {}", param.name, reason))
+        })
+        .collect()
+}
+
+/// Contract error codes to their names in the spec
+pub fn error_names(abi: &ContractABI) -> HashMap<u32, String> {
+    abi.errors
+        .iter()
+        .map(|e| (e.code, e.name.clone()))
+        .collect()
+}
+
+fn val_to_json(env: &Env, val: Val) -> Value {
+    match ScVal::try_from_val(env, &val) {
+        Ok(scval) => scval_to_json(&scval),
+        Err(_) => json!(format!("{:?}", val)),
+    }
+}
+
+/// A contract error by its name in the spec when there is one
+fn describe_error(err: soroban_sdk::Error, error_names: &HashMap<u32, String>) -> String {
+    if err.is_type(ScErrorType::Contract) {
+        let code = err.get_code();
+        return match error_names.get(&code) {
+            Some(name) => format!("contract error {} ({})", code, name),
+            None => format!("contract error {}", code),
+        };
+    }
+    format!("host error: {:?}", err)
+}
+
+/// What a simulated call returned, emitted and consumed
+pub struct SimulationOutcome {
+    /// The decoded return value, or why the call failed
+    pub result: Result<Value, String>,
+    /// The return value as base64 XDR, for `xdr decode`
+    pub result_xdr: Option<String>,
+    pub events: Vec<SimulationEvent>,
+    pub resources: SimulationResources,
+}
+
+/// A stored WASM deployed to an empty ledger. Calls share its storage, so a
+/// sequence such as `initialize` then `deposit` sees earlier writes.
+/// Authorization is mocked so any `require_auth` passes; the host's default
+/// budget bounds CPU and memory per call. Blocking: run it off the async runtime.
+pub struct Sandbox {
+    env: Env,
+    contract: soroban_sdk::Address,
+    /// Events reported so far, so each call reports only its own
+    emitted: Vec<SimulationEvent>,
+}
+
+impl Sandbox {
+    pub fn deploy(wasm: &[u8]) -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract = env.register(wasm, ());
+        Sandbox {
+            env,
+            contract,
+            emitted: Vec::new(),
+        }
+    }
+
+    pub fn call(
+        &mut self,
+        function: &str,
+        args: &[ScVal],
+        error_names: &HashMap<u32, String>,
+    ) -> Result<SimulationOutcome, String> {
+        let env = &self.env;
+        let mut call_args = soroban_sdk::Vec::<Val>::new(env);
+        for arg in args {
+            let val = Val::try_from_val(env, arg)
+                .map_err(|e| format!("argument could not be encoded: {:?}", e))?;
+            call_args.push_back(val);
+        }
+
+        // Measure the call alone, not the deployment or earlier calls
+        env.cost_estimate().budget().reset_default();
+        let outcome = env.try_invoke_contract::<Val, soroban_sdk::Error>(
+            &self.contract,
+            &Symbol::new(env, function),
+            call_args,
+        );
+        let budget = env.cost_estimate().budget();
+        let resources = SimulationResources {
+            cpu_instructions: budget.cpu_instruction_cost(),
+            memory_bytes: budget.memory_bytes_cost(),
+        };
+
+        let mut result_xdr = None;
+        let result = match outcome {
+            Ok(Ok(val)) => match ScVal::try_from_val(env, &val) {
+                Ok(scval) => {
+                    result_xdr = scval_to_xdr(&scval).ok();
+                    Ok(scval_to_json(&scval))
+                }
+                Err(_) => Ok(json!(format!("{:?}", val))),
+            },
+            Ok(Err(err)) => Err(format!("return value could not be decoded: {:?}", err)),
+            Err(Ok(err)) => Err(describe_error(err, error_names)),
+            Err(Err(err)) => Err(format!("invocation failed: {:?}", err)),
+        };
+        let all: Vec<SimulationEvent> = env
+            .events()
+            .all()
+            .iter()
+            .map(|(contract_id, topics, data)| SimulationEvent {
+                contract_id: match val_to_json(env, contract_id.into_val(env)) {
+                    Value::String(id) => id,
+                    other => other.to_string(),
+                },
+                topics: topics.iter().map(|t| val_to_json(env, t)).collect(),
+                data: val_to_json(env, data),
+            })
+            .collect();
+        // The host may or may not clear events between top-level calls
+        let events = if all.starts_with(&self.emitted) {
+            all[self.emitted.len()..].to_vec()
+        } else {
+            all.clone()
+        };
+        self.emitted = all;
+
+        Ok(SimulationOutcome {
+            result,
+            result_xdr,
+            events,
+            resources,
+        })
+    }
+}
+
+/// Deploy `wasm` to an empty ledger and call `function` once
+pub fn simulate(
+    wasm: &[u8],
+    function: &str,
+    args: &[ScVal],
+    error_names: &HashMap<u32, String>,
+) -> Result<SimulationOutcome, String> {
+    Sandbox::deploy(wasm).call(function, args, error_names)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::type_safety::{EnumVariant, StructField};
+
+    fn encode(value: ParsedValue, ty: SorobanType) -> Result<ScVal, String> {
+        to_scval(&value, &ty, &HashMap::new())
+    }
+
+    #[test]
+    fn integers_round_trip_through_json() {
+        let big = encode(ParsedValue::Integer(-5), SorobanType::I128).unwrap();
+        assert_eq!(scval_to_json(&big), json!("-5"));
+        let wide = encode(ParsedValue::UnsignedInteger(u128::MAX), SorobanType::U128).unwrap();
+        assert_eq!(scval_to_json(&wide), json!(u128::MAX.to_string()));
+        assert!(encode(ParsedValue::Integer(-1), SorobanType::U32).is_err());
+        assert!(encode(
+            ParsedValue::Integer(i128::from(i64::MAX) + 1),
+            SorobanType::I64
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn structs_become_sorted_symbol_maps() {
+        let ty = SorobanType::Struct {
+            name: "Pool".to_string(),
+            fields: vec![
+                StructField {
+                    name: "reserve".to_string(),
+                    field_type: SorobanType::U64,
+                    doc: None,
+                },
+                StructField {
+                    name: "fee".to_string(),
+                    field_type: SorobanType::U32,
+                    doc: None,
+                },
+            ],
+        };
+        let value = ParsedValue::Struct(HashMap::from([
+            ("reserve".to_string(), ParsedValue::UnsignedInteger(100)),
+            ("fee".to_string(), ParsedValue::UnsignedInteger(30)),
+        ]));
+        let encoded = encode(value, ty.clone()).unwrap();
+        assert_eq!(scval_to_json(&encoded), json!({"fee": 30, "reserve": 100}));
+        let ScVal::Map(Some(map)) = &encoded else {
+            panic!("expected a map");
+        };
+        assert_eq!(map[0].key, symbol("fee").unwrap());
+
+        let missing = ParsedValue::Struct(HashMap::new());
+        assert!(encode(missing, ty).unwrap_err().contains("`reserve`"));
+    }
+
+    #[test]
+    fn enums_are_encoded_by_variant() {
+        let ty = SorobanType::Enum {
+            name: "Side".to_string(),
+            variants: vec![EnumVariant {
+                name: "Buy".to_string(),
+                value: None,
+                fields: None,
+                doc: None,
+            }],
+        };
+        let encoded = encode(ParsedValue::String("Buy".to_string()), ty.clone()).unwrap();
+        assert_eq!(scval_to_json(&encoded), json!(["Buy"]));
+        assert!(encode(ParsedValue::String("Sell".to_string()), ty).is_err());
+    }
+
+    #[test]
+    fn options_and_vectors() {
+        let ty = SorobanType::Option {
+            value_type: Box::new(SorobanType::Vec {
+                element_type: Box::new(SorobanType::Symbol),
+            }),
+        };
+        assert_eq!(encode(ParsedValue::Null, ty.clone()).unwrap(), ScVal::Void);
+        let items = ParsedValue::Array(vec![ParsedValue::String("a".to_string())]);
+        assert_eq!(scval_to_json(&encode(items, ty).unwrap()), json!(["a"]));
+    }
+}
@@ -0,0 +1,154 @@
+// simulation_handlers.rs
+// Trying a contract before depending on it: one function call against its
+// stored WASM in a throwaway sandbox.
+
+use axum::{
+    extract::{rejection::JsonRejection, Path, State},
+    http::StatusCode,
+    Json,
+};
+use shared::{SimulateRequest, SimulationResult};
+use uuid::Uuid;
+
+use crate::error::{ApiError, ApiResult};
+use crate::handlers::{db_internal_error, fetch_contract_identity, map_json_rejection};
+use crate::private_registry::{ensure_contract_visible, Viewer};
+use crate::simulation;
+use crate::state::AppState;
+use crate::type_safety::{parse_json_spec, ContractABI};
+
+/// The WASM and parsed ABI of the latest (or a given) version, for a sandbox
+pub(crate) async fn load_version_for_sandbox(
+    state: &AppState,
+    contract_uuid: Uuid,
+    id: &str,
+    version: Option<&str>,
+) -> ApiResult<(String, Vec<u8>, ContractABI)> {
+    let (version_id, version): (Uuid, String) = sqlx::query_as(
+        "SELECT id, version FROM contract_versions \
+         WHERE contract_id = $1 AND ($2::TEXT IS NULL OR version = $2) \
+         ORDER BY created_at DESC LIMIT 1",
+    )
+    .bind(contract_uuid)
+    .bind(version)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|err| db_internal_error("fetch version to simulate", err))?
+    .ok_or_else(|| match version {
+        Some(version) => ApiError::not_found(
+            "VersionNotFound",
+            format!("Contract {} has no version '{}'", id, version),
+        ),
+        None => ApiError::not_found(
+            "VersionNotFound",
+            format!("Contract {} has no versions", id),
+        ),
+    })?;
+
+    // Only versions pushed through a publish session carry their WASM
+    let wasm: Vec<u8> = sqlx::query_scalar(
+        "SELECT content FROM contract_version_artifacts \
+         WHERE version_id = $1 AND kind = 'wasm'",
+    )
+    .bind(version_id)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|err| db_internal_error("fetch version wasm", err))?
+    .ok_or_else(|| {
+        ApiError::not_found(
+            "WasmNotAvailable",
+            format!("The registry does not hold the WASM of version {}", version),
+        )
+    })?;
+
+    let abi = load_abi(state, contract_uuid, id, &version).await?;
+    Ok((version, wasm, abi))
+}
+
+/// A version's stored ABI, parsed for encoding arguments
+pub(crate) async fn load_abi(
+    state: &AppState,
+    contract_uuid: Uuid,
+    id: &str,
+    version: &str,
+) -> ApiResult<ContractABI> {
+    let abi: serde_json::Value =
+        sqlx::query_scalar("SELECT abi FROM contract_abis WHERE contract_id = $1 AND version = $2")
+            .bind(contract_uuid)
+            .bind(version)
+            .fetch_optional(&state.db)
+            .await
+            .map_err(|err| db_internal_error("fetch version abi", err))?
+            .ok_or_else(|| {
+                ApiError::not_found(
+                    "AbiNotFound",
+                    format!("Version {} has no ABI to encode arguments with", version),
+                )
+            })?;
+    parse_json_spec(&abi.to_string(), id).map_err(|err| {
+        ApiError::unprocessable(
+            "InvalidAbi",
+            format!("The stored ABI could not be parsed: {}", err.message),
+        )
+    })
+}
+
+/// A slot to run a sandbox in, or 503 when all are busy
+pub(crate) fn acquire_sandbox_slot() -> ApiResult<tokio::sync::SemaphorePermit<'static>> {
+    simulation::SANDBOX_SLOTS.try_acquire().map_err(|_| {
+        ApiError::new(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "SimulationBusy",
+            "Too many simulations are running; try again shortly",
+        )
+    })
+}
+
+/// POST /api/contracts/:id/simulate — call a function of the latest (or a given)
+/// version on an ephemeral ledger. Authorization is mocked and nothing persists.
+pub async fn simulate_contract(
+    State(state): State<AppState>,
+    viewer: Viewer,
+    Path(id): Path<String>,
+    payload: Result<Json<SimulateRequest>, JsonRejection>,
+) -> ApiResult<Json<SimulationResult>> {
+    let Json(req) = payload.map_err(map_json_rejection)?;
+    let (contract_uuid, _) = fetch_contract_identity(&state, viewer.tenant_id, &id).await?;
+    ensure_contract_visible(&state, &viewer, contract_uuid).await?;
+    let (version, wasm, abi) =
+        load_version_for_sandbox(&state, contract_uuid, &id, req.version.as_deref()).await?;
+
+    let args = simulation::encode_call(&abi, &req.function, &req.args)
+        .map_err(|reason| ApiError::bad_request("InvalidArguments", reason))?;
+    let error_names = simulation::error_names(&abi);
+
+    let _slot = acquire_sandbox_slot()?;
+    let function = req.function.clone();
+    let outcome = tokio::task::spawn_blocking(move || {
+        simulation::simulate(&wasm, &function, &args, &error_names)
+    })
+    .await
+    // The host panics on WASM it cannot instantiate
+    .map_err(|_| {
+        ApiError::unprocessable(
+            "SimulationFailed",
+            format!("Version {} could not be loaded into the sandbox", version),
+        )
+    })?
+    .map_err(|reason| ApiError::unprocessable("SimulationFailed", reason))?;
+
+    let (success, result, error) = match outcome.result {
+        Ok(value) => (true, Some(value), None),
+        Err(reason) => (false, None, Some(reason)),
+    };
+    Ok(Json(SimulationResult {
+        version,
+        function: req.function,
+        success,
+        result,
+        result_xdr: outcome.result_xdr,
+        error,
+        events: outcome.events,
+        resources: outcome.resources,
+    }))
+}
@@ -0,0 +1,364 @@
+// soroban_rpc.rs
+// Client for the Soroban RPC of each network (`[rpc]`), for read-only
+// contract calls: an invocation is simulated, never submitted, and the value
+// it returns is read from the simulation.
+//
+// A background monitor probes every network's RPC for availability and
+// ledger lag. Calls to a network whose RPC is down fail at once with
+// `RpcUnavailable` instead of waiting out the request timeout.
+
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+use axum::http::StatusCode;
+use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use shared::{Network, RpcHealthStatus, RpcNetworkHealth, ScVal};
+
+use crate::error::ApiError;
+use crate::settings::RpcSettings;
+
+const RPC_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How often the monitor probes each network
+const MONITOR_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Probes answer faster than calls, or the RPC counts as down
+const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A latest ledger that hasn't advanced for this long marks the RPC lagging
+/// (ledgers close every ~5 seconds)
+const MAX_LEDGER_LAG_SECS: i64 = 60;
+
+const NETWORKS: [Network; 3] = [Network::Mainnet, Network::Testnet, Network::Futurenet];
+
+#[derive(Debug, thiserror::Error)]
+pub enum RpcCallError {
+    #[error("Soroban RPC for {network} is unavailable: {reason}")]
+    Unavailable { network: Network, reason: String },
+    #[error("RPC request failed: {0}")]
+    Request(String),
+    #[error("RPC returned an error: {0}")]
+    Rpc(String),
+    #[error("Invalid RPC response: {0}")]
+    InvalidResponse(String),
+}
+
+impl From<RpcCallError> for ApiError {
+    fn from(err: RpcCallError) -> Self {
+        match err {
+            RpcCallError::Unavailable { .. } => ApiError::new(
+                StatusCode::SERVICE_UNAVAILABLE,
+                "RpcUnavailable",
+                err.to_string(),
+            ),
+            _ => ApiError::new(StatusCode::BAD_GATEWAY, "RpcError", err.to_string()),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct JsonRpcResponse<T> {
+    result: Option<T>,
+    error: Option<JsonRpcError>,
+}
+
+#[derive(Deserialize)]
+struct JsonRpcError {
+    message: String,
+}
+
+#[derive(Deserialize)]
+struct SimulateResult {
+    /// Why the invocation failed, when it did
+    error: Option<String>,
+    #[serde(default)]
+    results: Vec<SimulateHostFunctionResult>,
+}
+
+#[derive(Deserialize)]
+struct SimulateHostFunctionResult {
+    xdr: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct HealthResult {
+    status: String,
+    latest_ledger: Option<u32>,
+}
+
+/// What the monitor last saw of one network
+struct ProbeState {
+    status: RpcHealthStatus,
+    latest_ledger: Option<u32>,
+    /// When `latest_ledger` last changed
+    advanced_at: Option<DateTime<Utc>>,
+    latency_ms: Option<u64>,
+    checked_at: Option<DateTime<Utc>>,
+    error: Option<String>,
+}
+
+impl Default for ProbeState {
+    fn default() -> Self {
+        Self {
+            status: RpcHealthStatus::Unknown,
+            latest_ledger: None,
+            advanced_at: None,
+            latency_ms: None,
+            checked_at: None,
+            error: None,
+        }
+    }
+}
+
+impl ProbeState {
+    fn ledger_lag_secs(&self, now: DateTime<Utc>) -> Option<i64> {
+        self.advanced_at.map(|at| (now - at).num_seconds())
+    }
+
+    /// Record a probe that reached the RPC and got its `latest_ledger`
+    fn observe(&mut self, latest_ledger: Option<u32>, now: DateTime<Utc>) {
+        if latest_ledger.is_some() && latest_ledger != self.latest_ledger {
+            self.latest_ledger = latest_ledger;
+            self.advanced_at = Some(now);
+        }
+        self.status = match self.ledger_lag_secs(now) {
+            Some(lag) if lag > MAX_LEDGER_LAG_SECS => RpcHealthStatus::Lagging,
+            _ => RpcHealthStatus::Healthy,
+        };
+        self.error = None;
+    }
+
+    fn fail(&mut self, reason: String) {
+        self.status = RpcHealthStatus::Unavailable;
+        self.error = Some(reason);
+    }
+}
+
+fn slot(network: &Network) -> usize {
+    match network {
+        Network::Mainnet => 0,
+        Network::Testnet => 1,
+        Network::Futurenet => 2,
+    }
+}
+
+pub struct SorobanRpc {
+    client: reqwest::Client,
+    settings: RpcSettings,
+    probes: [RwLock<ProbeState>; 3],
+}
+
+impl Default for SorobanRpc {
+    fn default() -> Self {
+        Self::from_settings(&RpcSettings::default())
+    }
+}
+
+impl SorobanRpc {
+    pub fn from_settings(settings: &RpcSettings) -> Self {
+        Self {
+            client: reqwest::Client::builder()
+                .timeout(RPC_TIMEOUT)
+                .build()
+                .unwrap_or_default(),
+            settings: settings.clone(),
+            probes: Default::default(),
+        }
+    }
+
+    pub fn url(&self, network: &Network) -> &str {
+        match network {
+            Network::Mainnet => &self.settings.mainnet,
+            Network::Testnet => &self.settings.testnet,
+            Network::Futurenet => &self.settings.futurenet,
+        }
+    }
+
+    async fn request<T: DeserializeOwned>(
+        &self,
+        network: &Network,
+        method: &str,
+        params: Value,
+        timeout: Duration,
+    ) -> Result<T, RpcCallError> {
+        let mut body = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": method,
+        });
+        if !params.is_null() {
+            body["params"] = params;
+        }
+        let response: JsonRpcResponse<T> = self
+            .client
+            .post(self.url(network))
+            .timeout(timeout)
+            .json(&body)
+            .send()
+            .await
+            .and_then(|resp| resp.error_for_status())
+            .map_err(|err| RpcCallError::Request(err.to_string()))?
+            .json()
+            .await
+            .map_err(|err| RpcCallError::InvalidResponse(err.to_string()))?;
+
+        if let Some(error) = response.error {
+            return Err(RpcCallError::Rpc(error.message));
+        }
+        response
+            .result
+            .ok_or_else(|| RpcCallError::InvalidResponse("no result".to_string()))
+    }
+
+    /// `Unavailable` when the monitor last found the network's RPC down;
+    /// endpoints that need the chain check this before doing any work
+    pub fn ensure_available(&self, network: &Network) -> Result<(), RpcCallError> {
+        let probe = self.probes[slot(network)].read().unwrap();
+        match probe.status {
+            RpcHealthStatus::Unavailable => Err(RpcCallError::Unavailable {
+                network: network.clone(),
+                reason: probe.error.clone().unwrap_or_default(),
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// What `function` returns when called on a deployed contract
+    pub async fn call(
+        &self,
+        network: &Network,
+        contract_id: &str,
+        function: &str,
+        args: Vec<ScVal>,
+    ) -> Result<ScVal, RpcCallError> {
+        self.ensure_available(network)?;
+        let envelope = shared::invocation_transaction(contract_id, function, args)
+            .and_then(|envelope| shared::transaction_to_xdr(&envelope))
+            .map_err(RpcCallError::Request)?;
+        let result: SimulateResult = match self
+            .request(
+                network,
+                "simulateTransaction",
+                json!({ "transaction": envelope }),
+                RPC_TIMEOUT,
+            )
+            .await
+        {
+            // The RPC couldn't be reached: fail later calls fast until the
+            // monitor sees it answer again
+            Err(RpcCallError::Request(reason)) => {
+                self.probes[slot(network)]
+                    .write()
+                    .unwrap()
+                    .fail(reason.clone());
+                return Err(RpcCallError::Request(reason));
+            }
+            other => other?,
+        };
+        if let Some(error) = result.error {
+            return Err(RpcCallError::Rpc(error));
+        }
+        let value = result
+            .results
+            .first()
+            .ok_or_else(|| RpcCallError::InvalidResponse("no invocation result".to_string()))?;
+        shared::scval_from_xdr(&value.xdr).map_err(RpcCallError::InvalidResponse)
+    }
+
+    /// Ask the network's RPC for its health and record the answer
+    pub async fn probe(&self, network: &Network) {
+        let started = Instant::now();
+        let result: Result<HealthResult, RpcCallError> = self
+            .request(network, "getHealth", Value::Null, PROBE_TIMEOUT)
+            .await;
+        let now = Utc::now();
+        let mut probe = self.probes[slot(network)].write().unwrap();
+        probe.checked_at = Some(now);
+        probe.latency_ms = Some(started.elapsed().as_millis() as u64);
+        match result {
+            Ok(health) if health.status == "healthy" => probe.observe(health.latest_ledger, now),
+            Ok(health) => probe.fail(format!("reports status `{}`", health.status)),
+            Err(err) => probe.fail(err.to_string()),
+        }
+        if probe.status != RpcHealthStatus::Healthy {
+            tracing::warn!(
+                network = %network,
+                status = ?probe.status,
+                error = ?probe.error,
+                "Soroban RPC is not healthy"
+            );
+        }
+    }
+
+    /// Health of every network's RPC as of its last probe
+    pub fn health(&self) -> Vec<RpcNetworkHealth> {
+        let now = Utc::now();
+        NETWORKS
+            .iter()
+            .map(|network| {
+                let probe = self.probes[slot(network)].read().unwrap();
+                RpcNetworkHealth {
+                    network: network.clone(),
+                    status: probe.status,
+                    latest_ledger: probe.latest_ledger,
+                    ledger_lag_secs: probe.ledger_lag_secs(now),
+                    latency_ms: probe.latency_ms,
+                    checked_at: probe.checked_at,
+                    error: probe.error.clone(),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Probe every network's RPC now and then every [`MONITOR_INTERVAL`]
+pub fn spawn_rpc_monitor(rpc: Arc<SorobanRpc>) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(MONITOR_INTERVAL);
+        loop {
+            interval.tick().await;
+            let [mainnet, testnet, futurenet] = &NETWORKS;
+            tokio::join!(rpc.probe(mainnet), rpc.probe(testnet), rpc.probe(futurenet));
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_ledger_that_stops_advancing_marks_the_rpc_lagging() {
+        let start = Utc::now();
+        let mut probe = ProbeState::default();
+        probe.observe(Some(100), start);
+        assert_eq!(probe.status, RpcHealthStatus::Healthy);
+
+        let later = start + chrono::Duration::seconds(MAX_LEDGER_LAG_SECS + 5);
+        probe.observe(Some(100), later);
+        assert_eq!(probe.status, RpcHealthStatus::Lagging);
+        assert_eq!(probe.ledger_lag_secs(later), Some(MAX_LEDGER_LAG_SECS + 5));
+
+        probe.observe(Some(112), later);
+        assert_eq!(probe.status, RpcHealthStatus::Healthy);
+    }
+
+    #[test]
+    fn calls_fail_fast_while_the_rpc_is_down() {
+        let rpc = SorobanRpc::default();
+        assert!(rpc.ensure_available(&Network::Testnet).is_ok());
+
+        rpc.probes[slot(&Network::Testnet)]
+            .write()
+            .unwrap()
+            .fail("connection refused".to_string());
+        let err = rpc.ensure_available(&Network::Testnet).unwrap_err();
+        assert!(matches!(err, RpcCallError::Unavailable { .. }));
+        assert_eq!(ApiError::from(err).code().as_str(), "RpcUnavailable");
+        assert!(rpc.ensure_available(&Network::Mainnet).is_ok());
+    }
+}
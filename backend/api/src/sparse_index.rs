@@ -0,0 +1,483 @@
+// sparse_index.rs
+// Cargo-style sparse index: one static file per contract name, rewritten after
+// every publish, so resolvers and mirrors can read metadata through a CDN.
+
+use std::collections::BTreeMap;
+use std::path::{Path as FsPath, PathBuf};
+
+use axum::{
+    extract::{Path, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::{DateTime, Utc};
+use sha2::{Digest, Sha256};
+use shared::{IndexConfig, IndexEntry, IndexRebuildSummary, Network};
+use sqlx::types::Json as DbJson;
+use uuid::Uuid;
+
+use crate::artifact_handlers::if_none_match_hits;
+use crate::error::{ApiError, ApiResult};
+use crate::handlers::db_internal_error;
+use crate::settings::{IndexSettings, StorageSettings};
+use crate::state::AppState;
+use crate::tenancy::{CurrentTenant, DEFAULT_TENANT_SLUG};
+
+const SCHEMA_VERSION: u32 = 1;
+const CONFIG_FILE: &str = "config.json";
+/// Short enough that a publish shows up quickly, long enough to absorb resolver bursts
+const CACHE_CONTROL: &str = "public, max-age=60";
+
+/// Where the index lives on disk and how `config.json` addresses it
+pub struct SparseIndex {
+    root: PathBuf,
+    enabled: bool,
+    public_url: Option<String>,
+}
+
+impl Default for SparseIndex {
+    fn default() -> Self {
+        Self::from_settings(&IndexSettings::default(), &StorageSettings::default())
+    }
+}
+
+impl SparseIndex {
+    pub fn from_settings(index: &IndexSettings, storage: &StorageSettings) -> Self {
+        Self {
+            root: FsPath::new(&storage.path).join("index"),
+            enabled: index.enabled,
+            public_url: index
+                .public_url
+                .as_deref()
+                .map(|url| url.trim_end_matches('/').to_string()),
+        }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Nothing has been written yet, e.g. on the first start after an upgrade
+    pub fn is_empty(&self) -> bool {
+        !self.root.exists()
+    }
+
+    fn tenant_dir(&self, slug: &str) -> PathBuf {
+        self.root.join(slug)
+    }
+
+    /// URL templates for a tenant's index, absolute when `public_url` is set
+    pub fn config(&self, slug: &str) -> IndexConfig {
+        let mut base = self.public_url.clone().unwrap_or_default();
+        if slug != DEFAULT_TENANT_SLUG {
+            base.push_str(&format!("/t/{}", slug));
+        }
+        IndexConfig {
+            schema_version: SCHEMA_VERSION,
+            index: format!("{}/index/{{path}}", base),
+            artifact: format!("{}/api/artifacts/{{sha256}}", base),
+            archive: format!("{}/api/contracts/{{id}}/archive?version={{version}}", base),
+            api: format!("{}/api", base),
+        }
+    }
+}
+
+/// Lowercase the name and escape anything but `[a-z0-9_-]` as `~xx`, so index
+/// paths are case-insensitive and safe in URLs and on every filesystem
+fn escape_name(name: &str) -> String {
+    let mut escaped = String::with_capacity(name.len());
+    for byte in name.to_lowercase().bytes() {
+        match byte {
+            b'a'..=b'z' | b'0'..=b'9' | b'_' | b'-' => escaped.push(byte as char),
+            _ => escaped.push_str(&format!("~{:02x}", byte)),
+        }
+    }
+    escaped
+}
+
+/// Cargo's directory layout: `1/`, `2/`, `3/{c}/`, then `{cc}/{cc}/`
+fn prefix(escaped: &str) -> String {
+    match escaped.len() {
+        1 => "1".to_string(),
+        2 => "2".to_string(),
+        3 => format!("3/{}", &escaped[..1]),
+        _ => format!("{}/{}", &escaped[..2], &escaped[2..4]),
+    }
+}
+
+/// Path of a name's file under the tenant's index root
+pub fn index_path(namespace: Option<&str>, name: &str) -> String {
+    let escaped = escape_name(name);
+    let path = format!("{}/{}", prefix(&escaped), escaped);
+    match namespace {
+        Some(namespace) => format!("@{}/{}", escape_name(namespace), path),
+        None => path,
+    }
+}
+
+/// Only paths the index could have written: no traversal, no hidden files
+fn is_safe_path(path: &str) -> bool {
+    !path.is_empty()
+        && path.split('/').all(|segment| {
+            !segment.is_empty()
+                && !segment.starts_with('.')
+                && segment
+                    .bytes()
+                    .all(|b| b.is_ascii_alphanumeric() || b"_-~@.".contains(&b))
+        })
+}
+
+/// The contracts sharing one index file
+#[derive(Debug, Clone, sqlx::FromRow)]
+struct IndexKey {
+    tenant_id: Uuid,
+    namespace: Option<String>,
+    name: String,
+}
+
+#[derive(sqlx::FromRow)]
+struct EntryRow {
+    id: Uuid,
+    name: String,
+    namespace: Option<String>,
+    network: Network,
+    contract_id: String,
+    version: String,
+    wasm_hash: String,
+    artifacts: DbJson<BTreeMap<String, String>>,
+    created_at: DateTime<Utc>,
+}
+
+impl From<EntryRow> for IndexEntry {
+    fn from(row: EntryRow) -> Self {
+        IndexEntry {
+            name: row.name,
+            namespace: row.namespace,
+            network: row.network,
+            id: row.id,
+            contract_id: row.contract_id,
+            version: row.version,
+            wasm_hash: row.wasm_hash,
+            artifacts: row.artifacts.0,
+            published_at: row.created_at,
+        }
+    }
+}
+
+/// Every published version under a name; private and moderated contracts stay
+/// out of the index
+async fn fetch_entries(state: &AppState, key: &IndexKey) -> ApiResult<Vec<IndexEntry>> {
+    let rows: Vec<EntryRow> = sqlx::query_as(
+        "SELECT c.id, c.name, c.namespace, c.network, c.contract_id, v.version, v.wasm_hash, \
+             COALESCE((SELECT jsonb_object_agg(a.kind, a.sha256) \
+                       FROM contract_version_artifacts a WHERE a.version_id = v.id), \
+                      '{}'::jsonb) AS artifacts, \
+             v.created_at \
+         FROM contracts c JOIN contract_versions v ON v.contract_id = c.id \
+         WHERE c.tenant_id = $1 AND c.namespace IS NOT DISTINCT FROM $2 \
+           AND LOWER(c.name) = LOWER($3) AND NOT c.is_private \
+           AND c.moderation_status = 'active' \
+         ORDER BY v.created_at, c.network, v.version",
+    )
+    .bind(key.tenant_id)
+    .bind(&key.namespace)
+    .bind(&key.name)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("fetch index entries", err))?;
+    Ok(rows.into_iter().map(IndexEntry::from).collect())
+}
+
+/// One JSON object per line, so mirrors can append-diff files
+fn render(entries: &[IndexEntry]) -> ApiResult<String> {
+    let mut body = String::new();
+    for entry in entries {
+        let line = serde_json::to_string(entry)
+            .map_err(|err| ApiError::internal(format!("Failed to encode index entry: {}", err)))?;
+        body.push_str(&line);
+        body.push('\n');
+    }
+    Ok(body)
+}
+
+fn io_error(action: &str, path: &FsPath, err: std::io::Error) -> ApiError {
+    tracing::error!(path = %path.display(), error = %err, "failed to {}", action);
+    ApiError::internal(format!("Failed to {}", action))
+}
+
+/// Replace `path` in one step, so readers never see a partial file
+async fn write_atomic(path: &FsPath, contents: &[u8]) -> ApiResult<()> {
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent)
+            .await
+            .map_err(|err| io_error("create index directory", parent, err))?;
+    }
+    let tmp = path.with_extension(format!("tmp-{}", Uuid::new_v4()));
+    tokio::fs::write(&tmp, contents)
+        .await
+        .map_err(|err| io_error("write index file", &tmp, err))?;
+    tokio::fs::rename(&tmp, path)
+        .await
+        .map_err(|err| io_error("write index file", path, err))
+}
+
+async fn write_config(index: &SparseIndex, dir: &FsPath, slug: &str) -> ApiResult<()> {
+    let config = serde_json::to_vec_pretty(&index.config(slug))
+        .map_err(|err| ApiError::internal(format!("Failed to encode index config: {}", err)))?;
+    write_atomic(&dir.join(CONFIG_FILE), &config).await
+}
+
+async fn tenant_slug(state: &AppState, tenant_id: Uuid) -> ApiResult<String> {
+    sqlx::query_scalar("SELECT slug FROM tenants WHERE id = $1")
+        .bind(tenant_id)
+        .fetch_one(&state.db)
+        .await
+        .map_err(|err| db_internal_error("fetch tenant slug", err))
+}
+
+/// Rewrite the file for one name, or remove it once nothing public is left
+async fn refresh_key(state: &AppState, key: &IndexKey) -> ApiResult<()> {
+    let index = &state.index;
+    let slug = tenant_slug(state, key.tenant_id).await?;
+    let dir = index.tenant_dir(&slug);
+    let path = dir.join(index_path(key.namespace.as_deref(), &key.name));
+    let entries = fetch_entries(state, key).await?;
+    if entries.is_empty() {
+        return match tokio::fs::remove_file(&path).await {
+            Err(err) if err.kind() != std::io::ErrorKind::NotFound => {
+                Err(io_error("remove index file", &path, err))
+            }
+            _ => Ok(()),
+        };
+    }
+    write_atomic(&path, render(&entries)?.as_bytes()).await?;
+    if !dir.join(CONFIG_FILE).exists() {
+        write_config(index, &dir, &slug).await?;
+    }
+    Ok(())
+}
+
+/// Refresh the contract's file, and the one for the name it had before a rename
+async fn refresh_contract(
+    state: &AppState,
+    contract_uuid: Uuid,
+    previous_name: Option<String>,
+) -> ApiResult<()> {
+    let key: Option<IndexKey> =
+        sqlx::query_as("SELECT tenant_id, namespace, name FROM contracts WHERE id = $1")
+            .bind(contract_uuid)
+            .fetch_optional(&state.db)
+            .await
+            .map_err(|err| db_internal_error("fetch contract name", err))?;
+    let Some(key) = key else {
+        return Ok(());
+    };
+    refresh_key(state, &key).await?;
+    if let Some(name) = previous_name {
+        refresh_key(state, &IndexKey { name, ..key }).await?;
+    }
+    Ok(())
+}
+
+/// Regenerate a contract's index file in the background after a publish or a
+/// visibility change. A failed write only delays the index until the next one.
+pub fn spawn_refresh(state: &AppState, contract_uuid: Uuid) {
+    spawn_refresh_renamed(state, contract_uuid, None);
+}
+
+/// Like [`spawn_refresh`], also dropping the contract from its old name's file
+pub fn spawn_refresh_renamed(state: &AppState, contract_uuid: Uuid, previous_name: Option<String>) {
+    if !state.index.enabled() {
+        return;
+    }
+    let state = state.clone();
+    tokio::spawn(async move {
+        if let Err(err) = refresh_contract(&state, contract_uuid, previous_name).await {
+            tracing::warn!(contract = %contract_uuid, error = ?err, "sparse index refresh failed");
+        }
+    });
+}
+
+/// Write every tenant's index into a scratch directory, then swap it in
+pub async fn rebuild_all(state: &AppState) -> ApiResult<IndexRebuildSummary> {
+    let index = &state.index;
+    let tenants: Vec<(Uuid, String)> = sqlx::query_as("SELECT id, slug FROM tenants")
+        .fetch_all(&state.db)
+        .await
+        .map_err(|err| db_internal_error("list tenants", err))?;
+
+    let mut summary = IndexRebuildSummary {
+        tenants: tenants.len(),
+        files: 0,
+        versions: 0,
+    };
+    for (tenant_id, slug) in tenants {
+        let scratch = index.root.join(format!(".rebuild-{}", Uuid::new_v4()));
+        let keys: Vec<IndexKey> = sqlx::query_as(
+            "SELECT DISTINCT ON (c.namespace, LOWER(c.name)) c.tenant_id, c.namespace, c.name \
+             FROM contracts c \
+             WHERE c.tenant_id = $1 AND NOT c.is_private AND c.moderation_status = 'active' \
+               AND EXISTS (SELECT 1 FROM contract_versions v WHERE v.contract_id = c.id)",
+        )
+        .bind(tenant_id)
+        .fetch_all(&state.db)
+        .await
+        .map_err(|err| db_internal_error("list index names", err))?;
+
+        for key in &keys {
+            let entries = fetch_entries(state, key).await?;
+            if entries.is_empty() {
+                continue;
+            }
+            let path = scratch.join(index_path(key.namespace.as_deref(), &key.name));
+            write_atomic(&path, render(&entries)?.as_bytes()).await?;
+            summary.files += 1;
+            summary.versions += entries.len();
+        }
+        write_config(index, &scratch, &slug).await?;
+
+        let live = index.tenant_dir(&slug);
+        let retired = index.root.join(format!(".retired-{}", Uuid::new_v4()));
+        if live.exists() {
+            tokio::fs::rename(&live, &retired)
+                .await
+                .map_err(|err| io_error("replace index", &live, err))?;
+        }
+        tokio::fs::rename(&scratch, &live)
+            .await
+            .map_err(|err| io_error("replace index", &live, err))?;
+        let _ = tokio::fs::remove_dir_all(&retired).await;
+    }
+    Ok(summary)
+}
+
+/// Build the index at startup when there is none yet
+pub fn spawn_initial_build(state: &AppState) {
+    if !state.index.enabled() || !state.index.is_empty() {
+        return;
+    }
+    let state = state.clone();
+    tokio::spawn(async move {
+        match rebuild_all(&state).await {
+            Ok(summary) => tracing::info!(
+                files = summary.files,
+                versions = summary.versions,
+                "sparse index built"
+            ),
+            Err(err) => tracing::warn!(error = ?err, "sparse index build failed"),
+        }
+    });
+}
+
+/// POST /api/admin/index/rebuild — regenerate the whole index from the database
+pub async fn rebuild_index(State(state): State<AppState>) -> ApiResult<Json<IndexRebuildSummary>> {
+    if !state.index.enabled() {
+        return Err(index_disabled());
+    }
+    let summary = rebuild_all(&state).await?;
+    tracing::info!(
+        files = summary.files,
+        versions = summary.versions,
+        "sparse index rebuilt"
+    );
+    Ok(Json(summary))
+}
+
+fn index_disabled() -> ApiError {
+    ApiError::not_found(
+        "IndexDisabled",
+        "The sparse index is disabled on this registry",
+    )
+}
+
+/// GET /index/*path — a static index file; never touches the database
+pub async fn get_index_file(
+    State(state): State<AppState>,
+    tenant: CurrentTenant,
+    Path(path): Path<String>,
+    headers: HeaderMap,
+) -> ApiResult<Response> {
+    if !state.index.enabled() {
+        return Err(index_disabled());
+    }
+    let not_found = || ApiError::not_found("IndexFileNotFound", format!("No index file {}", path));
+    if !is_safe_path(&path) {
+        return Err(not_found());
+    }
+
+    let (body, content_type) = if path == CONFIG_FILE {
+        let config = serde_json::to_vec_pretty(&state.index.config(&tenant.slug))
+            .map_err(|err| ApiError::internal(format!("Failed to encode index config: {}", err)))?;
+        (config, "application/json")
+    } else {
+        let file = state.index.tenant_dir(&tenant.slug).join(&path);
+        match tokio::fs::read(&file).await {
+            Ok(body) => (body, "application/x-ndjson"),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Err(not_found()),
+            Err(err) => return Err(io_error("read index file", &file, err)),
+        }
+    };
+
+    let etag = HeaderValue::from_str(&format!("\"{}\"", hex::encode(Sha256::digest(&body))))
+        .unwrap_or_else(|_| HeaderValue::from_static("\"\""));
+    let cache_control = HeaderValue::from_static(CACHE_CONTROL);
+    if if_none_match_hits(&headers, &etag) {
+        return Ok((
+            StatusCode::NOT_MODIFIED,
+            [(header::ETAG, etag), (header::CACHE_CONTROL, cache_control)],
+        )
+            .into_response());
+    }
+    Ok((
+        [
+            (header::CONTENT_TYPE, HeaderValue::from_static(content_type)),
+            (header::ETAG, etag),
+            (header::CACHE_CONTROL, cache_control),
+        ],
+        body,
+    )
+        .into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn paths_follow_the_cargo_layout() {
+        assert_eq!(index_path(None, "a"), "1/a");
+        assert_eq!(index_path(None, "ab"), "2/ab");
+        assert_eq!(index_path(None, "Amm"), "3/a/amm");
+        assert_eq!(index_path(None, "token"), "to/ke/token");
+        assert_eq!(index_path(Some("Acme"), "token"), "@acme/to/ke/token");
+    }
+
+    #[test]
+    fn unsafe_names_are_escaped() {
+        assert_eq!(escape_name("My Token"), "my~20token");
+        assert_eq!(index_path(None, ".."), "~2/e~/~2e~2e");
+        assert!(is_safe_path(&index_path(Some("acme"), "a/b")));
+        assert!(!is_safe_path("../secrets"));
+        assert!(!is_safe_path("to//token"));
+        assert!(!is_safe_path(".rebuild-x/to/ke/token"));
+    }
+
+    #[test]
+    fn config_templates_follow_the_tenant() {
+        let mut settings = IndexSettings::default();
+        let index = SparseIndex::from_settings(&settings, &StorageSettings::default());
+        assert_eq!(index.config("public").index, "/index/{path}");
+        assert_eq!(
+            index.config("acme").artifact,
+            "/t/acme/api/artifacts/{sha256}"
+        );
+
+        settings.public_url = Some("https://cdn.example.com/".to_string());
+        let index = SparseIndex::from_settings(&settings, &StorageSettings::default());
+        assert_eq!(
+            index.config("public").index,
+            "https://cdn.example.com/index/{path}"
+        );
+    }
+}
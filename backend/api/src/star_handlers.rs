@@ -0,0 +1,115 @@
+// star_handlers.rs
+// Stars: signed-in users bookmark contracts. The per-contract count lives in
+// `contracts.star_count` (kept by trigger) and is a `?sort=stars` key.
+
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use shared::{ApiScope, StarStatus, StarredContract};
+use uuid::Uuid;
+
+use crate::error::ApiResult;
+use crate::handlers::{db_internal_error, fetch_contract_identity};
+use crate::private_registry::{ensure_contract_visible, Viewer};
+use crate::state::AppState;
+use crate::tenancy::CurrentTenant;
+use crate::user_auth::AuthUser;
+
+/// Contract the user may see, resolved from a UUID, on-chain ID or `@namespace/name`
+async fn starrable_contract(
+    state: &AppState,
+    user: &AuthUser,
+    tenant: &CurrentTenant,
+    id: &str,
+) -> ApiResult<Uuid> {
+    let (contract_uuid, _) = fetch_contract_identity(state, tenant.id, id).await?;
+    let viewer = Viewer {
+        address: Some(user.address.clone()),
+        tenant_id: tenant.id,
+    };
+    ensure_contract_visible(state, &viewer, contract_uuid).await?;
+    Ok(contract_uuid)
+}
+
+async fn star_status(
+    state: &AppState,
+    contract_uuid: Uuid,
+    starred: bool,
+) -> ApiResult<StarStatus> {
+    let star_count: i64 = sqlx::query_scalar("SELECT star_count FROM contracts WHERE id = $1")
+        .bind(contract_uuid)
+        .fetch_one(&state.db)
+        .await
+        .map_err(|err| db_internal_error("fetch star count", err))?;
+    Ok(StarStatus {
+        contract_id: contract_uuid,
+        starred,
+        star_count,
+    })
+}
+
+/// PUT /api/contracts/:id/star — idempotent
+pub async fn star_contract(
+    State(state): State<AppState>,
+    user: AuthUser,
+    tenant: CurrentTenant,
+    Path(id): Path<String>,
+) -> ApiResult<Json<StarStatus>> {
+    user.require(ApiScope::Publish)?;
+    let contract_uuid = starrable_contract(&state, &user, &tenant, &id).await?;
+    sqlx::query(
+        "INSERT INTO contract_stars (contract_id, user_address) VALUES ($1, $2) \
+         ON CONFLICT DO NOTHING",
+    )
+    .bind(contract_uuid)
+    .bind(&user.address)
+    .execute(&state.db)
+    .await
+    .map_err(|err| db_internal_error("star contract", err))?;
+    Ok(Json(star_status(&state, contract_uuid, true).await?))
+}
+
+/// DELETE /api/contracts/:id/star — idempotent
+pub async fn unstar_contract(
+    State(state): State<AppState>,
+    user: AuthUser,
+    tenant: CurrentTenant,
+    Path(id): Path<String>,
+) -> ApiResult<Json<StarStatus>> {
+    user.require(ApiScope::Publish)?;
+    let contract_uuid = starrable_contract(&state, &user, &tenant, &id).await?;
+    sqlx::query("DELETE FROM contract_stars WHERE contract_id = $1 AND user_address = $2")
+        .bind(contract_uuid)
+        .bind(&user.address)
+        .execute(&state.db)
+        .await
+        .map_err(|err| db_internal_error("unstar contract", err))?;
+    Ok(Json(star_status(&state, contract_uuid, false).await?))
+}
+
+/// GET /api/me/stars — the caller's starred contracts they can still see
+pub async fn list_my_stars(
+    State(state): State<AppState>,
+    user: AuthUser,
+    tenant: CurrentTenant,
+) -> ApiResult<Json<Vec<StarredContract>>> {
+    user.require(ApiScope::Read)?;
+    let viewer = Viewer {
+        address: Some(user.address.clone()),
+        tenant_id: tenant.id,
+    };
+    let sql = format!(
+        "SELECT c.*, s.created_at AS starred_at \
+         FROM contract_stars s JOIN contracts c ON c.id = s.contract_id \
+         WHERE s.user_address = $1{} \
+         ORDER BY s.created_at DESC",
+        viewer.visibility_clause("c.")
+    );
+    let stars = sqlx::query_as(&sql)
+        .bind(&user.address)
+        .fetch_all(&state.db)
+        .await
+        .map_err(|err| db_internal_error("list starred contracts", err))?;
+    Ok(Json(stars))
+}
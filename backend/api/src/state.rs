@@ -1,8 +1,20 @@
+use crate::auth::AuthManager;
+use crate::backfill::Backfills;
 use crate::cache::{CacheConfig, CacheLayer};
+use crate::challenge::ChallengeGate;
+use crate::maintenance_mode::MaintenanceMode;
+use crate::patch_handlers::PatchMaintainers;
+use crate::publish_hooks::PublishHooks;
+use crate::registry_flags::RegistryFlags;
+use crate::reindex::Reindexer;
+use crate::settings::{QuotaSettings, ServerSettings};
+use crate::soroban_rpc::SorobanRpc;
+use crate::sparse_index::SparseIndex;
+use crate::tenancy::TenantDirectory;
 use prometheus::Registry;
 use sqlx::PgPool;
 use std::sync::atomic::AtomicBool;
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
 use std::time::Instant;
 
 /// Application state shared across handlers
@@ -13,6 +25,34 @@ pub struct AppState {
     pub cache: Arc<CacheLayer>,
     pub registry: Registry,
     pub is_shutting_down: Arc<AtomicBool>,
+    pub flags: Arc<RegistryFlags>,
+    /// Wallet challenge/JWT issuer for user-scoped endpoints
+    pub auth_mgr: Arc<RwLock<AuthManager>>,
+    /// Every endpoint requires authentication (`server.private_mode`)
+    pub private_mode: bool,
+    /// Per-account publishing limits (`quotas`)
+    pub quotas: Arc<QuotaSettings>,
+    /// Checks run on the WASM of every published version
+    pub publish_hooks: Arc<PublishHooks>,
+    /// Read-only switch for operator maintenance
+    pub maintenance: Arc<MaintenanceMode>,
+    /// Slug and host lookups for tenant routing
+    pub tenants: Arc<TenantDirectory>,
+    /// Static index files rewritten on publish
+    pub index: Arc<SparseIndex>,
+    /// Progress of the admin reindex job
+    pub reindexer: Arc<Reindexer>,
+    /// Jobs that repair legacy rows
+    pub backfills: Arc<Backfills>,
+    /// Read-only contract calls on each network (`[rpc]`)
+    pub rpc: Arc<SorobanRpc>,
+    /// Anti-spam challenge for anonymous writes (`[challenge]`)
+    pub challenge: Arc<ChallengeGate>,
+    /// Accounts allowed to manage security patches (`[patches]`)
+    pub patch_maintainers: Arc<PatchMaintainers>,
+    /// Web frontend linked from feeds and the sitemap (`server.public_site_url`),
+    /// without a trailing slash
+    pub site_url: Arc<str>,
 }
 
 impl AppState {
@@ -24,6 +64,80 @@ impl AppState {
             cache: Arc::new(CacheLayer::new(config)),
             registry,
             is_shutting_down,
+            flags: Arc::new(RegistryFlags::default()),
+            auth_mgr: Arc::new(RwLock::new(AuthManager::from_env())),
+            private_mode: false,
+            quotas: Arc::new(QuotaSettings::default()),
+            publish_hooks: Arc::new(PublishHooks::default()),
+            maintenance: Arc::new(MaintenanceMode::default()),
+            tenants: Arc::new(TenantDirectory::default()),
+            index: Arc::new(SparseIndex::default()),
+            reindexer: Arc::new(Reindexer::default()),
+            backfills: Arc::new(Backfills::default()),
+            rpc: Arc::new(SorobanRpc::default()),
+            challenge: Arc::new(ChallengeGate::default()),
+            patch_maintainers: Arc::new(PatchMaintainers::default()),
+            site_url: Arc::from(ServerSettings::default().public_site_url.as_str()),
         }
     }
+
+    /// Replace the default flag evaluator with one configured for this deployment
+    pub fn with_flags(mut self, flags: RegistryFlags) -> Self {
+        self.flags = Arc::new(flags);
+        self
+    }
+
+    /// Require authentication for reads as well as writes
+    pub fn with_private_mode(mut self, enabled: bool) -> Self {
+        self.private_mode = enabled;
+        self
+    }
+
+    /// Enforce these publishing limits; the default is unlimited
+    pub fn with_quotas(mut self, quotas: QuotaSettings) -> Self {
+        self.quotas = Arc::new(quotas);
+        self
+    }
+
+    /// Run these checks before a version is published; the default runs none
+    pub fn with_publish_hooks(mut self, hooks: PublishHooks) -> Self {
+        self.publish_hooks = Arc::new(hooks);
+        self
+    }
+
+    /// Start in (or out of) maintenance mode; the default accepts writes
+    pub fn with_maintenance(mut self, maintenance: MaintenanceMode) -> Self {
+        self.maintenance = Arc::new(maintenance);
+        self
+    }
+
+    /// Write the sparse index where `[storage]` and `[index]` say
+    pub fn with_index(mut self, index: SparseIndex) -> Self {
+        self.index = Arc::new(index);
+        self
+    }
+
+    /// Call contracts through these RPC endpoints; the default is the public ones
+    pub fn with_rpc(mut self, rpc: SorobanRpc) -> Self {
+        self.rpc = Arc::new(rpc);
+        self
+    }
+
+    /// Challenge anonymous writes as configured; the default lets them through
+    pub fn with_challenge(mut self, challenge: ChallengeGate) -> Self {
+        self.challenge = Arc::new(challenge);
+        self
+    }
+
+    /// Let these accounts manage security patches; the default lets nobody
+    pub fn with_patch_maintainers(mut self, maintainers: PatchMaintainers) -> Self {
+        self.patch_maintainers = Arc::new(maintainers);
+        self
+    }
+
+    /// Link feeds and the sitemap to this frontend instead of the public registry
+    pub fn with_site_url(mut self, url: &str) -> Self {
+        self.site_url = Arc::from(url.trim_end_matches('/'));
+        self
+    }
 }
@@ -0,0 +1,159 @@
+// stats_handlers.rs
+// Daily snapshots of registry statistics and the time series served from them.
+
+use axum::{
+    extract::{Query, State},
+    Json,
+};
+use chrono::{Duration, NaiveDate, Utc};
+use serde::Deserialize;
+use shared::{StatsPoint, StatsTimeseries};
+use sqlx::PgPool;
+
+use crate::error::{ApiError, ApiResult};
+use crate::handlers::db_internal_error;
+use crate::state::AppState;
+
+/// Metrics a series can be requested for; each is a `stats_daily` column
+const METRICS: &[&str] = &[
+    "contracts",
+    "publishers",
+    "publishes",
+    "versions",
+    "downloads",
+];
+const DEFAULT_RANGE_DAYS: i64 = 30;
+const MAX_RANGE_DAYS: i64 = 365;
+
+#[derive(Debug, Deserialize)]
+pub struct TimeseriesQuery {
+    /// One of [`METRICS`]; defaults to `publishes`
+    pub metric: Option<String>,
+    /// `90d`, `12w` or a plain number of days; defaults to 30 days
+    pub range: Option<String>,
+}
+
+/// Number of days in a `range` parameter, at most a year
+fn parse_range(range: &str) -> Result<i64, String> {
+    let range = range.trim();
+    let (number, unit_days) = match range.strip_suffix('w') {
+        Some(weeks) => (weeks, 7),
+        None => (range.strip_suffix('d').unwrap_or(range), 1),
+    };
+    let days = number
+        .parse::<i64>()
+        .ok()
+        .and_then(|n| n.checked_mul(unit_days))
+        .filter(|days| *days > 0)
+        .ok_or_else(|| format!("range must look like 90d or 12w, got {:?}", range))?;
+    if days > MAX_RANGE_DAYS {
+        return Err(format!("range is limited to {} days", MAX_RANGE_DAYS));
+    }
+    Ok(days)
+}
+
+/// Recompute snapshots for every day in `from..=to`; re-running overwrites them
+async fn snapshot_range(pool: &PgPool, from: NaiveDate, to: NaiveDate) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query(
+        "INSERT INTO stats_daily (date, contracts, publishers, publishes, versions, downloads) \
+         SELECT day, \
+             (SELECT COUNT(*) FROM contracts WHERE created_at < day + 1), \
+             (SELECT COUNT(*) FROM publishers WHERE created_at < day + 1), \
+             (SELECT COUNT(*) FROM contracts \
+              WHERE created_at >= day AND created_at < day + 1), \
+             (SELECT COUNT(*) FROM contract_versions \
+              WHERE created_at >= day AND created_at < day + 1), \
+             (SELECT COUNT(*) FROM contract_interactions \
+              WHERE created_at >= day AND created_at < day + 1) \
+         FROM generate_series($1::DATE, $2::DATE, INTERVAL '1 day') AS g(d), \
+              LATERAL (SELECT d::DATE AS day) AS days \
+         ON CONFLICT (date) DO UPDATE SET \
+             contracts = EXCLUDED.contracts, \
+             publishers = EXCLUDED.publishers, \
+             publishes = EXCLUDED.publishes, \
+             versions = EXCLUDED.versions, \
+             downloads = EXCLUDED.downloads, \
+             updated_at = NOW()",
+    )
+    .bind(from)
+    .bind(to)
+    .execute(pool)
+    .await?;
+    Ok(result.rows_affected())
+}
+
+/// Snapshot yesterday and today; the first run backfills the last year
+pub async fn snapshot_daily(pool: &PgPool) -> Result<u64, sqlx::Error> {
+    let empty: bool = sqlx::query_scalar("SELECT NOT EXISTS(SELECT 1 FROM stats_daily)")
+        .fetch_one(pool)
+        .await?;
+    let today = Utc::now().date_naive();
+    let days_back = if empty { MAX_RANGE_DAYS - 1 } else { 1 };
+    snapshot_range(pool, today - Duration::days(days_back), today).await
+}
+
+/// GET /api/stats/timeseries?metric=publishes&range=90d
+pub async fn get_stats_timeseries(
+    State(state): State<AppState>,
+    Query(query): Query<TimeseriesQuery>,
+) -> ApiResult<Json<StatsTimeseries>> {
+    let metric = query.metric.as_deref().unwrap_or("publishes");
+    // The column name is interpolated below, so it must come from the allowlist
+    let column = METRICS
+        .iter()
+        .copied()
+        .find(|m| *m == metric)
+        .ok_or_else(|| {
+            ApiError::bad_request(
+                "InvalidMetric",
+                format!("metric must be one of: {}", METRICS.join(", ")),
+            )
+        })?;
+    let range_days = match query.range.as_deref() {
+        Some(range) => parse_range(range).map_err(|e| ApiError::bad_request("InvalidRange", e))?,
+        None => DEFAULT_RANGE_DAYS,
+    };
+
+    let today = Utc::now().date_naive();
+    let rows: Vec<(NaiveDate, i64)> = sqlx::query_as(&format!(
+        "SELECT g.d::DATE, COALESCE(s.{}, 0) \
+         FROM generate_series($1::DATE, $2::DATE, INTERVAL '1 day') AS g(d) \
+         LEFT JOIN stats_daily s ON s.date = g.d::DATE \
+         ORDER BY g.d",
+        column
+    ))
+    .bind(today - Duration::days(range_days - 1))
+    .bind(today)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("fetch stats time series", err))?;
+
+    Ok(Json(StatsTimeseries {
+        metric: column.to_string(),
+        range_days,
+        points: rows
+            .into_iter()
+            .map(|(date, value)| StatsPoint { date, value })
+            .collect(),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ranges_accept_days_and_weeks() {
+        assert_eq!(parse_range("90d"), Ok(90));
+        assert_eq!(parse_range("12w"), Ok(84));
+        assert_eq!(parse_range("7"), Ok(7));
+    }
+
+    #[test]
+    fn ranges_are_bounded() {
+        assert!(parse_range("0d").is_err());
+        assert!(parse_range("-5d").is_err());
+        assert!(parse_range("366d").is_err());
+        assert!(parse_range("3m").is_err());
+    }
+}
@@ -0,0 +1,201 @@
+// stream_handlers.rs
+// Full catalog sync as newline-delimited JSON.
+
+use axum::{
+    body::{Body, Bytes},
+    extract::{Query, State},
+    http::{header, HeaderValue},
+    response::Response,
+};
+use chrono::{DateTime, Utc};
+use futures_util::stream;
+use serde::Deserialize;
+use shared::Contract;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::private_registry::Viewer;
+use crate::state::AppState;
+
+pub const NDJSON_CONTENT_TYPE: &str = "application/x-ndjson";
+
+/// Rows fetched per round trip while streaming
+const STREAM_PAGE_SIZE: i64 = 500;
+
+/// Query params for GET /api/contracts/stream
+#[derive(Debug, Deserialize)]
+pub struct ContractStreamQuery {
+    /// Only emit contracts updated at or after this instant
+    pub updated_since: Option<DateTime<Utc>>,
+}
+
+/// Keyset position in the `(updated_at, id)` ordering
+struct StreamCursor {
+    /// Caller's [`Viewer::visibility_clause`]; private and other tenants'
+    /// contracts are left out
+    visibility: String,
+    updated_since: Option<DateTime<Utc>>,
+    after: Option<(DateTime<Utc>, Uuid)>,
+    done: bool,
+}
+
+impl StreamCursor {
+    /// Move past `page`; a short page means the table is exhausted
+    fn advance(&mut self, page: &[Contract]) {
+        self.done = (page.len() as i64) < STREAM_PAGE_SIZE;
+        self.after = page.last().map(|c| (c.updated_at, c.id));
+    }
+}
+
+fn page_query(visibility: &str) -> String {
+    format!(
+        "SELECT c.* FROM contracts c \
+         WHERE ($1::timestamptz IS NULL OR c.updated_at >= $1) \
+           AND ($2::timestamptz IS NULL OR (c.updated_at, c.id) > ($2, $3)){} \
+         ORDER BY c.updated_at, c.id \
+         LIMIT $4",
+        visibility
+    )
+}
+
+async fn fetch_page(db: &PgPool, cursor: &StreamCursor) -> Result<Vec<Contract>, sqlx::Error> {
+    sqlx::query_as(&page_query(&cursor.visibility))
+        .bind(cursor.updated_since)
+        .bind(cursor.after.map(|(ts, _)| ts))
+        .bind(cursor.after.map(|(_, id)| id))
+        .bind(STREAM_PAGE_SIZE)
+        .fetch_all(db)
+        .await
+}
+
+fn encode_page(contracts: &[Contract]) -> Bytes {
+    let mut buf = Vec::new();
+    for contract in contracts {
+        if serde_json::to_writer(&mut buf, contract).is_ok() {
+            buf.push(b'\n');
+        }
+    }
+    Bytes::from(buf)
+}
+
+/// GET /api/contracts/stream?updated_since=
+///
+/// Streams every matching contract as one JSON object per line, paging through
+/// the table by keyset so no long-lived transaction is held. The
+/// `X-Sync-Watermark` header carries the time the stream started; pass it as
+/// `updated_since` on the next sync.
+pub async fn stream_contracts(
+    State(state): State<AppState>,
+    viewer: Viewer,
+    Query(query): Query<ContractStreamQuery>,
+) -> Response {
+    let watermark = Utc::now().to_rfc3339();
+    let cursor = StreamCursor {
+        visibility: viewer.visibility_clause("c."),
+        updated_since: query.updated_since,
+        after: None,
+        done: false,
+    };
+
+    let body = stream::unfold((state.db, cursor), |(db, mut cursor)| async move {
+        if cursor.done {
+            return None;
+        }
+        match fetch_page(&db, &cursor).await {
+            Ok(page) if page.is_empty() => None,
+            Ok(page) => {
+                cursor.advance(&page);
+                Some((Ok(encode_page(&page)), (db, cursor)))
+            }
+            Err(err) => {
+                tracing::error!(error = ?err, "catalog stream page query failed");
+                cursor.done = true;
+                Some((Err(err), (db, cursor)))
+            }
+        }
+    });
+
+    let mut response = Response::new(Body::from_stream(body));
+    let headers = response.headers_mut();
+    headers.insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static(NDJSON_CONTENT_TYPE),
+    );
+    if let Ok(value) = HeaderValue::from_str(&watermark) {
+        headers.insert(header::HeaderName::from_static("x-sync-watermark"), value);
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn contract(updated_at: DateTime<Utc>) -> Contract {
+        serde_json::from_value(json!({
+            "id": Uuid::new_v4(),
+            "contract_id": "CABC",
+            "wasm_hash": "00",
+            "name": "stable-swap",
+            "description": null,
+            "publisher_id": Uuid::nil(),
+            "network": "testnet",
+            "is_verified": false,
+            "category": null,
+            "tags": [],
+            "created_at": updated_at,
+            "updated_at": updated_at,
+        }))
+        .unwrap()
+    }
+
+    fn cursor() -> StreamCursor {
+        StreamCursor {
+            visibility: String::new(),
+            updated_since: None,
+            after: None,
+            done: false,
+        }
+    }
+
+    #[test]
+    fn full_pages_continue_after_their_last_row() {
+        let now = Utc::now();
+        let page: Vec<Contract> = (0..STREAM_PAGE_SIZE)
+            .map(|i| contract(now + chrono::Duration::seconds(i)))
+            .collect();
+        let last = page.last().unwrap();
+
+        let mut cursor = cursor();
+        cursor.advance(&page);
+        assert!(!cursor.done);
+        assert_eq!(cursor.after, Some((last.updated_at, last.id)));
+
+        cursor.advance(&page[..3]);
+        assert!(cursor.done);
+        assert_eq!(cursor.after, Some((page[2].updated_at, page[2].id)));
+    }
+
+    #[test]
+    fn pages_only_hold_contracts_the_viewer_may_see() {
+        let tenant_id = Uuid::new_v4();
+        let anonymous = Viewer {
+            address: None,
+            tenant_id,
+        };
+        let query = page_query(&anonymous.visibility_clause("c."));
+        assert!(query.contains(&format!("COALESCE(c.tenant_id, '{}')", tenant_id)));
+        assert!(query.contains("c.is_private IS NOT TRUE"));
+        assert!(query.contains("COALESCE(c.moderation_status, 'active') = 'active'"));
+        assert!(!query.contains("organization_members"));
+
+        let member = Viewer {
+            address: Some("GMEMBER".to_string()),
+            tenant_id,
+        };
+        let query = page_query(&member.visibility_clause("c."));
+        assert!(query.contains("organization_members WHERE address = 'GMEMBER'"));
+        assert!(query.find("organization_members") < query.find("ORDER BY"));
+    }
+}
@@ -0,0 +1,292 @@
+// support_handlers.rs
+// Per-version support policies (supported-until date, LTS flag), the
+// end-of-life status computed from them, and the daily job alerting
+// dependents whose resolved version is reaching end of life.
+
+use std::collections::btree_map::Entry;
+use std::collections::{BTreeMap, HashSet};
+use std::time::Duration;
+
+use axum::{
+    extract::{rejection::JsonRejection, Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use chrono::{NaiveDate, Utc};
+use shared::{
+    AlertSource, ApiScope, DependencyAlert, PatchSeverity, SemVer, SupportPolicyRequest,
+    SupportQuery, SupportResponse, SupportStatus, VersionRange, VersionSupport,
+};
+use sqlx::PgConnection;
+use uuid::Uuid;
+
+use crate::alert_handlers::{
+    deliver_alerts, record_alert, AffectedDependent, AlertCause, DependencyEdge,
+};
+use crate::error::{ApiError, ApiResult};
+use crate::handlers::{db_internal_error, fetch_contract_identity, map_json_rejection};
+use crate::private_registry::{ensure_contract_visible, Viewer};
+use crate::state::AppState;
+use crate::tenancy::CurrentTenant;
+use crate::user_auth::AuthUser;
+
+const EOL_CHECK_INTERVAL: Duration = Duration::from_secs(24 * 3600);
+
+/// A published version and its declared policy, if any
+#[derive(Debug, Clone, sqlx::FromRow)]
+struct VersionPolicyRow {
+    id: Uuid,
+    version: String,
+    supported_until: Option<NaiveDate>,
+    lts: Option<bool>,
+}
+
+impl VersionPolicyRow {
+    fn support(&self, today: NaiveDate) -> VersionSupport {
+        VersionSupport::new(
+            self.version.clone(),
+            self.supported_until,
+            self.lts.unwrap_or(false),
+            today,
+        )
+    }
+}
+
+/// The version a dependency constraint resolves to: the highest one it
+/// admits. Constraints that aren't SemVer ranges resolve to the highest version.
+fn resolve<'a>(constraint: &str, versions: &'a [VersionPolicyRow]) -> Option<&'a VersionPolicyRow> {
+    let range = VersionRange::parse(constraint);
+    versions
+        .iter()
+        .filter_map(|row| SemVer::parse(&row.version).map(|semver| (semver, row)))
+        .filter(|(semver, _)| range.as_ref().is_none_or(|range| range.matches(semver)))
+        .max_by(|(a, _), (b, _)| a.cmp(b))
+        .map(|(_, row)| row)
+}
+
+async fn fetch_policies(
+    conn: &mut PgConnection,
+    contract_uuid: Uuid,
+) -> ApiResult<Vec<VersionPolicyRow>> {
+    sqlx::query_as(
+        "SELECT v.id, v.version, s.supported_until, s.lts FROM contract_versions v \
+         LEFT JOIN version_support_policies s ON s.version_id = v.id \
+         WHERE v.contract_id = $1 ORDER BY v.created_at DESC",
+    )
+    .bind(contract_uuid)
+    .fetch_all(conn)
+    .await
+    .map_err(|err| db_internal_error("fetch version support policies", err))
+}
+
+/// The declared policy of one version, for resolution responses
+pub(crate) async fn version_support(
+    state: &AppState,
+    version_id: Uuid,
+) -> ApiResult<Option<VersionSupport>> {
+    let row: Option<VersionPolicyRow> = sqlx::query_as(
+        "SELECT v.id, v.version, s.supported_until, s.lts FROM contract_versions v \
+         JOIN version_support_policies s ON s.version_id = v.id WHERE v.id = $1",
+    )
+    .bind(version_id)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|err| db_internal_error("fetch version support policy", err))?;
+    Ok(row.map(|row| row.support(Utc::now().date_naive())))
+}
+
+/// GET /api/contracts/:id/support?version= — every version's policy and
+/// end-of-life status, newest first
+pub async fn get_support(
+    State(state): State<AppState>,
+    viewer: Viewer,
+    Path(id): Path<String>,
+    Query(query): Query<SupportQuery>,
+) -> ApiResult<Json<SupportResponse>> {
+    let (contract_uuid, contract_id) =
+        fetch_contract_identity(&state, viewer.tenant_id, &id).await?;
+    ensure_contract_visible(&state, &viewer, contract_uuid).await?;
+    let mut conn = state
+        .db
+        .acquire()
+        .await
+        .map_err(|err| db_internal_error("acquire connection", err))?;
+    let mut rows = fetch_policies(&mut conn, contract_uuid).await?;
+    if let Some(ref version) = query.version {
+        rows.retain(|row| &row.version == version);
+        if rows.is_empty() {
+            return Err(ApiError::not_found(
+                "VersionNotFound",
+                format!("Contract has no version {}", version),
+            ));
+        }
+    }
+    let today = Utc::now().date_naive();
+    Ok(Json(SupportResponse {
+        contract_id,
+        versions: rows.iter().map(|row| row.support(today)).collect(),
+    }))
+}
+
+/// PUT /api/contracts/:id/versions/:version/support — the contract's publisher
+/// declares how long a version is supported
+pub async fn set_support_policy(
+    State(state): State<AppState>,
+    tenant: CurrentTenant,
+    user: AuthUser,
+    Path((id, version)): Path<(String, String)>,
+    payload: Result<Json<SupportPolicyRequest>, JsonRejection>,
+) -> ApiResult<Json<VersionSupport>> {
+    user.require(ApiScope::Publish)?;
+    let Json(req) = payload.map_err(map_json_rejection)?;
+    let (contract_uuid, _) = fetch_contract_identity(&state, tenant.id, &id).await?;
+
+    let row: Option<(Uuid, String)> = sqlx::query_as(
+        "SELECT v.id, p.stellar_address FROM contract_versions v \
+         JOIN contracts c ON c.id = v.contract_id \
+         JOIN publishers p ON p.id = c.publisher_id \
+         WHERE v.contract_id = $1 AND v.version = $2",
+    )
+    .bind(contract_uuid)
+    .bind(&version)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|err| db_internal_error("fetch contract version", err))?;
+    let (version_id, owner) = row.ok_or_else(|| {
+        ApiError::not_found(
+            "VersionNotFound",
+            format!("Contract has no version {}", version),
+        )
+    })?;
+    if owner != user.address {
+        return Err(ApiError::new(
+            StatusCode::FORBIDDEN,
+            "Forbidden",
+            "Only the contract's publisher can set its support policy",
+        ));
+    }
+
+    sqlx::query(
+        "INSERT INTO version_support_policies (version_id, supported_until, lts, updated_by) \
+         VALUES ($1, $2, $3, $4) \
+         ON CONFLICT (version_id) DO UPDATE \
+         SET supported_until = EXCLUDED.supported_until, lts = EXCLUDED.lts, \
+             updated_by = EXCLUDED.updated_by, updated_at = NOW()",
+    )
+    .bind(version_id)
+    .bind(req.supported_until)
+    .bind(req.lts)
+    .bind(&user.address)
+    .execute(&state.db)
+    .await
+    .map_err(|err| db_internal_error("set version support policy", err))?;
+
+    Ok(Json(VersionSupport::new(
+        version,
+        req.supported_until,
+        req.lts,
+        Utc::now().date_naive(),
+    )))
+}
+
+/// Alert direct dependents whose constraint resolves to a version that is
+/// ending soon or past end of life. Each version alerts a dependent once.
+pub async fn raise_eol_alerts(conn: &mut PgConnection) -> ApiResult<Vec<DependencyAlert>> {
+    let edges: Vec<DependencyEdge> = sqlx::query_as(
+        "SELECT contract_id AS dependent, dependency_contract_id AS dependency, \
+                version_constraint \
+         FROM contract_dependencies WHERE dependency_contract_id IN ( \
+             SELECT v.contract_id FROM contract_versions v \
+             JOIN version_support_policies s ON s.version_id = v.id \
+             WHERE s.supported_until <= CURRENT_DATE + $1::INT)",
+    )
+    .bind(shared::EOL_WARNING_DAYS as i32)
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|err| db_internal_error("load dependencies on ending versions", err))?;
+
+    let today = Utc::now().date_naive();
+    let mut versions: BTreeMap<Uuid, Vec<VersionPolicyRow>> = BTreeMap::new();
+    let mut alerts = Vec::new();
+    let mut seen = HashSet::new();
+    for edge in &edges {
+        if !seen.insert((edge.dependent, edge.dependency)) {
+            continue;
+        }
+        if let Entry::Vacant(entry) = versions.entry(edge.dependency) {
+            entry.insert(fetch_policies(conn, edge.dependency).await?);
+        }
+        let Some(resolved) = resolve(&edge.version_constraint, &versions[&edge.dependency]) else {
+            continue;
+        };
+        let support = resolved.support(today);
+        let severity = match support.status {
+            SupportStatus::EndOfLife => PatchSeverity::High,
+            SupportStatus::EndingSoon => PatchSeverity::Medium,
+            _ => continue,
+        };
+        let summary = format!(
+            "Dependency version {} is {}",
+            support.version,
+            support.describe()
+        );
+        let cause = AlertCause {
+            source: AlertSource::EndOfLife,
+            source_id: resolved.id,
+            severity,
+            summary: &summary,
+        };
+        let dependent = AffectedDependent {
+            contract_id: edge.dependent,
+            path: vec![edge.dependency, edge.dependent],
+            constraint: edge.version_constraint.clone(),
+        };
+        let affected = [support.version.clone()];
+        alerts.extend(record_alert(conn, &cause, edge.dependency, &affected, &dependent).await?);
+    }
+    Ok(alerts)
+}
+
+/// Spawn the daily task raising and delivering end-of-life alerts
+pub fn spawn_eol_alert_task(state: AppState) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(EOL_CHECK_INTERVAL);
+        loop {
+            interval.tick().await;
+            let alerts = match state.db.acquire().await {
+                Ok(mut conn) => raise_eol_alerts(&mut conn).await,
+                Err(err) => Err(db_internal_error("acquire connection", err)),
+            };
+            match alerts {
+                Ok(alerts) if !alerts.is_empty() => {
+                    tracing::info!(count = alerts.len(), "support: raised end-of-life alerts");
+                    deliver_alerts(state.clone(), alerts).await;
+                }
+                Ok(_) => {}
+                Err(err) => tracing::error!(error = ?err, "support: end-of-life check failed"),
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(version: &str) -> VersionPolicyRow {
+        VersionPolicyRow {
+            id: Uuid::new_v4(),
+            version: version.to_string(),
+            supported_until: None,
+            lts: None,
+        }
+    }
+
+    #[test]
+    fn constraints_resolve_to_the_highest_admitted_version() {
+        let versions = vec![row("2.1.0"), row("1.4.2"), row("1.10.0"), row("not-semver")];
+        assert_eq!(resolve("^1.0.0", &versions).unwrap().version, "1.10.0");
+        assert_eq!(resolve("latest", &versions).unwrap().version, "2.1.0");
+        assert!(resolve("^3.0.0", &versions).is_none());
+    }
+}
@@ -0,0 +1,133 @@
+// telemetry_handlers.rs
+// Ingestion of anonymous CLI usage metrics from users who opted in, and their
+// 90-day retention.
+
+use axum::{
+    extract::{rejection::JsonRejection, State},
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use shared::{TelemetryBatch, TelemetryEvent};
+
+use crate::error::{ApiError, ApiResult};
+use crate::handlers::{db_internal_error, map_json_rejection};
+use crate::state::AppState;
+
+/// The CLI flushes in batches far smaller than this
+const MAX_BATCH_EVENTS: usize = 200;
+const MAX_COMMAND_LENGTH: usize = 64;
+const MAX_CLI_VERSION_LENGTH: usize = 32;
+
+/// Commands are lowercase subcommand paths; anything else could smuggle arguments in
+fn validate_event(event: &TelemetryEvent) -> Result<(), String> {
+    let command_ok = !event.command.is_empty()
+        && event.command.len() <= MAX_COMMAND_LENGTH
+        && event.command.split(' ').all(|word| {
+            !word.is_empty()
+                && word
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+        });
+    if !command_ok {
+        return Err(format!(
+            "command must be a subcommand path such as \"patch apply\", got {:?}",
+            event.command
+        ));
+    }
+    if event.cli_version.is_empty() || event.cli_version.len() > MAX_CLI_VERSION_LENGTH {
+        return Err("cli_version must be 1-32 characters".to_string());
+    }
+    if event.duration_ms < 0 {
+        return Err("duration_ms must not be negative".to_string());
+    }
+    Ok(())
+}
+
+/// POST /api/telemetry — store a batch of CLI usage events. Always
+/// anonymous, so it answers the configured challenge if there is one.
+pub async fn ingest_telemetry(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    payload: Result<Json<TelemetryBatch>, JsonRejection>,
+) -> ApiResult<StatusCode> {
+    let Json(batch) = payload.map_err(map_json_rejection)?;
+    if batch.events.is_empty() || batch.events.len() > MAX_BATCH_EVENTS {
+        return Err(ApiError::bad_request(
+            "InvalidTelemetryBatch",
+            format!("A batch holds 1-{} events", MAX_BATCH_EVENTS),
+        ));
+    }
+    for event in &batch.events {
+        validate_event(event)
+            .map_err(|message| ApiError::bad_request("InvalidTelemetryEvent", message))?;
+    }
+    state.challenge.verify(&headers).await?;
+
+    let events = &batch.events;
+    let commands: Vec<&str> = events.iter().map(|e| e.command.as_str()).collect();
+    let versions: Vec<&str> = events.iter().map(|e| e.cli_version.as_str()).collect();
+    sqlx::query(
+        "INSERT INTO cli_telemetry_events \
+             (command, duration_ms, success, cli_version, recorded_at) \
+         SELECT * FROM UNNEST($1::TEXT[], $2::BIGINT[], $3::BOOL[], $4::TEXT[], \
+                              $5::TIMESTAMPTZ[])",
+    )
+    .bind(&commands)
+    .bind(events.iter().map(|e| e.duration_ms).collect::<Vec<_>>())
+    .bind(events.iter().map(|e| e.success).collect::<Vec<_>>())
+    .bind(&versions)
+    .bind(events.iter().map(|e| e.recorded_at).collect::<Vec<_>>())
+    .execute(&state.db)
+    .await
+    .map_err(|err| db_internal_error("store telemetry events", err))?;
+
+    Ok(StatusCode::ACCEPTED)
+}
+
+/// Drop events older than the 90-day retention window
+pub async fn purge_expired(pool: &sqlx::PgPool) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query(
+        "DELETE FROM cli_telemetry_events WHERE received_at < NOW() - INTERVAL '90 days'",
+    )
+    .execute(pool)
+    .await?;
+    Ok(result.rows_affected())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn event(command: &str) -> TelemetryEvent {
+        TelemetryEvent {
+            command: command.to_string(),
+            duration_ms: 120,
+            success: true,
+            cli_version: "0.4.0".to_string(),
+            recorded_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn accepts_subcommand_paths() {
+        assert!(validate_event(&event("search")).is_ok());
+        assert!(validate_event(&event("patch apply")).is_ok());
+        assert!(validate_event(&event("self-update")).is_ok());
+    }
+
+    #[test]
+    fn rejects_anything_that_looks_like_arguments() {
+        assert!(validate_event(&event("")).is_err());
+        assert!(validate_event(&event("info CABC123")).is_err());
+        assert!(validate_event(&event("search  --json")).is_err());
+        assert!(validate_event(&event("publish @alice/amm")).is_err());
+    }
+
+    #[test]
+    fn rejects_negative_durations() {
+        let mut negative = event("search");
+        negative.duration_ms = -1;
+        assert!(validate_event(&negative).is_err());
+    }
+}
@@ -0,0 +1,323 @@
+// tenancy.rs
+// Several logical registries in one deployment: each request is routed to a
+// tenant by a `/t/<slug>` path prefix or its Host header, defaulting to "public".
+
+use std::convert::Infallible;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use axum::{
+    body::Body,
+    extract::{rejection::JsonRejection, FromRequestParts, State},
+    http::{header, request::Parts, Extensions, Request, StatusCode, Uri},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
+use moka::future::Cache as MokaCache;
+use shared::{CreateTenantRequest, Tenant};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::error::{ApiError, ApiResult};
+use crate::handlers::{db_internal_error, map_json_rejection};
+use crate::state::AppState;
+
+/// Tenant of rows created before tenants existed and of requests naming none
+pub const DEFAULT_TENANT_ID: Uuid = Uuid::nil();
+pub const DEFAULT_TENANT_SLUG: &str = "public";
+
+const TENANT_PATH_PREFIX: &str = "/t/";
+const MAX_SLUG_LENGTH: usize = 32;
+const MAX_NAME_LENGTH: usize = 128;
+
+/// How long a slug or host lookup is reused before going back to the database
+const TENANT_CACHE_TTL: Duration = Duration::from_secs(60);
+
+/// The tenant a request was routed to; also the auth realm of its tokens
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CurrentTenant {
+    pub id: Uuid,
+    pub slug: String,
+}
+
+impl Default for CurrentTenant {
+    fn default() -> Self {
+        Self {
+            id: DEFAULT_TENANT_ID,
+            slug: DEFAULT_TENANT_SLUG.to_string(),
+        }
+    }
+}
+
+impl CurrentTenant {
+    /// The tenant `tenant_middleware` attached, or the default one
+    pub fn of(extensions: &Extensions) -> Self {
+        extensions
+            .get::<CurrentTenant>()
+            .cloned()
+            .unwrap_or_default()
+    }
+}
+
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for CurrentTenant {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Infallible> {
+        Ok(CurrentTenant::of(&parts.extensions))
+    }
+}
+
+/// Slug and host lookups, cached briefly so routing does not query every request
+pub struct TenantDirectory {
+    cache: MokaCache<String, Option<CurrentTenant>>,
+}
+
+impl Default for TenantDirectory {
+    fn default() -> Self {
+        Self {
+            cache: MokaCache::builder()
+                .max_capacity(1_000)
+                .time_to_live(TENANT_CACHE_TTL)
+                .build(),
+        }
+    }
+}
+
+impl TenantDirectory {
+    async fn lookup(
+        &self,
+        db: &PgPool,
+        key: String,
+        sql: &str,
+        value: &str,
+    ) -> ApiResult<Option<CurrentTenant>> {
+        if let Some(cached) = self.cache.get(&key).await {
+            return Ok(cached);
+        }
+        let tenant = sqlx::query_as::<_, (Uuid, String)>(sql)
+            .bind(value)
+            .fetch_optional(db)
+            .await
+            .map_err(|err| db_internal_error("resolve tenant", err))?
+            .map(|(id, slug)| CurrentTenant { id, slug });
+        self.cache.insert(key, tenant.clone()).await;
+        Ok(tenant)
+    }
+
+    pub async fn by_slug(&self, db: &PgPool, slug: &str) -> ApiResult<Option<CurrentTenant>> {
+        self.lookup(
+            db,
+            format!("slug:{}", slug),
+            "SELECT id, slug FROM tenants WHERE slug = $1",
+            slug,
+        )
+        .await
+    }
+
+    pub async fn by_host(&self, db: &PgPool, host: &str) -> ApiResult<Option<CurrentTenant>> {
+        self.lookup(
+            db,
+            format!("host:{}", host),
+            "SELECT id, slug FROM tenants WHERE $1 = ANY(hosts)",
+            host,
+        )
+        .await
+    }
+
+    /// Forget every lookup after tenants change
+    pub fn invalidate(&self) {
+        self.cache.invalidate_all();
+    }
+}
+
+/// Split `/t/<slug>/rest` into the slug and `/rest`
+fn split_tenant_path(path: &str) -> Option<(&str, &str)> {
+    let rest = path.strip_prefix(TENANT_PATH_PREFIX)?;
+    let (slug, rest) = match rest.find('/') {
+        Some(end) => (&rest[..end], &rest[end..]),
+        None => (rest, "/"),
+    };
+    (!slug.is_empty()).then_some((slug, rest))
+}
+
+/// Hostname of the request without the port, lowercased
+fn request_host(request: &Request<Body>) -> Option<String> {
+    let host = request.headers().get(header::HOST)?.to_str().ok()?;
+    let host = host.rsplit_once(':').map_or(host, |(name, port)| {
+        if port.chars().all(|c| c.is_ascii_digit()) {
+            name
+        } else {
+            host
+        }
+    });
+    Some(host.to_ascii_lowercase())
+}
+
+fn strip_tenant_prefix(uri: &Uri, rest: &str) -> Uri {
+    let path_and_query = match uri.query() {
+        Some(query) => format!("{}?{}", rest, query),
+        None => rest.to_string(),
+    };
+    path_and_query.parse().unwrap_or_else(|_| uri.clone())
+}
+
+/// Attach the request's tenant and strip a `/t/<slug>` prefix so routes match.
+/// Runs outside the router, since routing happens on the rewritten path.
+pub async fn tenant_middleware(
+    State(state): State<AppState>,
+    mut request: Request<Body>,
+    next: Next,
+) -> Response {
+    let path = request.uri().path().to_string();
+    let tenant = if let Some((slug, rest)) = split_tenant_path(&path) {
+        match state.tenants.by_slug(&state.db, slug).await {
+            Ok(Some(tenant)) => {
+                let uri = strip_tenant_prefix(request.uri(), rest);
+                *request.uri_mut() = uri;
+                tenant
+            }
+            Ok(None) => {
+                return ApiError::not_found(
+                    "TenantNotFound",
+                    format!("No registry named '{}' is hosted here", slug),
+                )
+                .into_response()
+            }
+            Err(err) => return err.into_response(),
+        }
+    } else if let Some(host) = request_host(&request) {
+        match state.tenants.by_host(&state.db, &host).await {
+            Ok(tenant) => tenant.unwrap_or_default(),
+            Err(err) => return err.into_response(),
+        }
+    } else {
+        CurrentTenant::default()
+    };
+
+    request.extensions_mut().insert(tenant);
+    next.run(request).await
+}
+
+/// Slugs appear in URLs and token realms: lowercase letters, digits and '-'
+fn validate_slug(slug: &str) -> ApiResult<()> {
+    let valid = !slug.is_empty()
+        && slug.len() <= MAX_SLUG_LENGTH
+        && !slug.starts_with('-')
+        && slug
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+    if valid {
+        Ok(())
+    } else {
+        Err(ApiError::bad_request(
+            "InvalidTenantSlug",
+            format!(
+                "slug must be 1-{} lowercase letters, digits or '-'",
+                MAX_SLUG_LENGTH
+            ),
+        ))
+    }
+}
+
+/// GET /api/admin/tenants
+pub async fn list_tenants(State(state): State<AppState>) -> ApiResult<Json<Vec<Tenant>>> {
+    let tenants = sqlx::query_as::<_, Tenant>(
+        "SELECT id, slug, name, hosts, created_at FROM tenants ORDER BY created_at",
+    )
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("list tenants", err))?;
+    Ok(Json(tenants))
+}
+
+/// POST /api/admin/tenants — host another registry in this deployment
+pub async fn create_tenant(
+    State(state): State<AppState>,
+    payload: Result<Json<CreateTenantRequest>, JsonRejection>,
+) -> ApiResult<(StatusCode, Json<Tenant>)> {
+    let Json(req) = payload.map_err(map_json_rejection)?;
+    let slug = req.slug.trim();
+    validate_slug(slug)?;
+    let name = req.name.trim();
+    if name.is_empty() || name.len() > MAX_NAME_LENGTH {
+        return Err(ApiError::bad_request(
+            "InvalidTenantName",
+            format!("name must be 1-{} characters", MAX_NAME_LENGTH),
+        ));
+    }
+    let hosts: Vec<String> = req
+        .hosts
+        .iter()
+        .map(|host| host.trim().to_ascii_lowercase())
+        .filter(|host| !host.is_empty())
+        .collect();
+
+    let tenant = sqlx::query_as::<_, Tenant>(
+        "INSERT INTO tenants (slug, name, hosts) VALUES ($1, $2, $3) \
+         RETURNING id, slug, name, hosts, created_at",
+    )
+    .bind(slug)
+    .bind(name)
+    .bind(&hosts)
+    .fetch_one(&state.db)
+    .await
+    .map_err(|err| match err {
+        sqlx::Error::Database(ref e) if e.constraint() == Some("tenants_slug_key") => {
+            ApiError::conflict(
+                "TenantExists",
+                format!("A registry named '{}' already exists", slug),
+            )
+        }
+        _ => db_internal_error("create tenant", err),
+    })?;
+
+    state.tenants.invalidate();
+    tracing::info!(tenant = %tenant.slug, hosts = ?tenant.hosts, "tenant created");
+    Ok((StatusCode::CREATED, Json(tenant)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_tenant_prefix() {
+        assert_eq!(
+            split_tenant_path("/t/acme/api/contracts"),
+            Some(("acme", "/api/contracts"))
+        );
+        assert_eq!(split_tenant_path("/t/acme"), Some(("acme", "/")));
+        assert_eq!(split_tenant_path("/t//api"), None);
+        assert_eq!(split_tenant_path("/api/contracts"), None);
+    }
+
+    #[test]
+    fn rewrite_keeps_the_query() {
+        let uri: Uri = "/t/acme/api/contracts?limit=5".parse().unwrap();
+        let rewritten = strip_tenant_prefix(&uri, "/api/contracts");
+        assert_eq!(rewritten.to_string(), "/api/contracts?limit=5");
+    }
+
+    #[test]
+    fn slugs_are_url_safe() {
+        assert!(validate_slug("acme-internal").is_ok());
+        assert!(validate_slug("").is_err());
+        assert!(validate_slug("Acme").is_err());
+        assert!(validate_slug("-acme").is_err());
+        assert!(validate_slug(&"a".repeat(33)).is_err());
+    }
+
+    #[test]
+    fn host_ignores_port_and_case() {
+        let request = Request::builder()
+            .header(header::HOST, "Registry.Acme.Internal:8443")
+            .body(Body::empty())
+            .unwrap();
+        assert_eq!(
+            request_host(&request).as_deref(),
+            Some("registry.acme.internal")
+        );
+    }
+}
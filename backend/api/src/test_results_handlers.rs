@@ -0,0 +1,250 @@
+// test_results_handlers.rs
+// Test run results attached to versions at publish time: parsing JUnit XML and
+// JSON summaries, storing their counts and serving them to CI dashboards.
+
+use std::collections::HashMap;
+
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use serde::Deserialize;
+use shared::{ContractTestResultsResponse, SemVer, TestSummary, VersionTestResults};
+use sqlx::PgConnection;
+use uuid::Uuid;
+
+use crate::error::{ApiError, ApiResult};
+use crate::handlers::{db_internal_error, fetch_contract_identity};
+use crate::private_registry::{ensure_contract_visible, Viewer};
+use crate::state::AppState;
+
+/// A JSON summary report, e.g. `{"passed": 124, "failed": 0, "coverage": 87.5}`
+#[derive(Debug, Deserialize)]
+struct JsonReport {
+    passed: u32,
+    #[serde(default)]
+    failed: u32,
+    #[serde(default)]
+    skipped: u32,
+    #[serde(default, alias = "coverage_percent")]
+    coverage: Option<f64>,
+}
+
+/// Count test cases in a JUnit XML report. A case containing `<failure>` or
+/// `<error>` failed, one containing `<skipped>` was skipped.
+fn parse_junit(xml: &str) -> Result<TestSummary, String> {
+    let mut summary = TestSummary::default();
+    let mut cases = 0;
+    for chunk in xml.split("<testcase").skip(1) {
+        // Skip other elements sharing the prefix, e.g. `<testcases>`
+        if !chunk.starts_with(|c: char| c.is_whitespace() || c == '>' || c == '/') {
+            continue;
+        }
+        cases += 1;
+        let open_end = chunk
+            .find('>')
+            .ok_or_else(|| "unterminated <testcase> element".to_string())?;
+        let body = if chunk[..open_end].ends_with('/') {
+            ""
+        } else {
+            let close = chunk.find("</testcase>").unwrap_or(chunk.len());
+            &chunk[open_end..close]
+        };
+        if body.contains("<failure") || body.contains("<error") {
+            summary.failed += 1;
+        } else if body.contains("<skipped") {
+            summary.skipped += 1;
+        } else {
+            summary.passed += 1;
+        }
+    }
+    if cases == 0 {
+        return Err("JUnit report contains no <testcase> elements".to_string());
+    }
+    Ok(summary)
+}
+
+fn parse_json(content: &str) -> Result<TestSummary, String> {
+    let report: JsonReport = serde_json::from_str(content)
+        .map_err(|e| format!("JSON test summary is invalid: {}", e))?;
+    if let Some(coverage) = report.coverage {
+        if !(0.0..=100.0).contains(&coverage) {
+            return Err("coverage must be a percentage between 0 and 100".to_string());
+        }
+    }
+    let count = |n: u32| i32::try_from(n).map_err(|_| "test counts are too large".to_string());
+    Ok(TestSummary {
+        passed: count(report.passed)?,
+        failed: count(report.failed)?,
+        skipped: count(report.skipped)?,
+        coverage_percent: report.coverage,
+    })
+}
+
+/// The report's format (`junit` or `json`) and counts
+pub(crate) fn parse_report(content: &[u8]) -> Result<(&'static str, TestSummary), String> {
+    let text = std::str::from_utf8(content).map_err(|_| "test report must be UTF-8".to_string())?;
+    let trimmed = text.trim_start_matches('\u{feff}').trim_start();
+    if trimmed.starts_with('{') {
+        Ok(("json", parse_json(trimmed)?))
+    } else if trimmed.starts_with('<') {
+        Ok(("junit", parse_junit(trimmed)?))
+    } else {
+        Err("test report must be JUnit XML or a JSON summary".to_string())
+    }
+}
+
+/// Store the counts from a version's `tests` artifact; called inside the
+/// transaction that creates the version
+pub(crate) async fn store_results(
+    conn: &mut PgConnection,
+    version_id: Uuid,
+    report: &[u8],
+) -> ApiResult<()> {
+    let (format, summary) = parse_report(report)
+        .map_err(|message| ApiError::bad_request("InvalidTestReport", message))?;
+    sqlx::query(
+        "INSERT INTO version_test_results \
+             (version_id, format, passed, failed, skipped, coverage_percent) \
+         VALUES ($1, $2, $3, $4, $5, $6)",
+    )
+    .bind(version_id)
+    .bind(format)
+    .bind(summary.passed)
+    .bind(summary.failed)
+    .bind(summary.skipped)
+    .bind(summary.coverage_percent)
+    .execute(&mut *conn)
+    .await
+    .map_err(|err| db_internal_error("store test results", err))?;
+    Ok(())
+}
+
+/// Contract, then passed, failed and skipped counts and coverage of its latest version
+type SummaryRow = (Uuid, Option<i32>, Option<i32>, Option<i32>, Option<f64>);
+
+/// Results of each contract's latest version, for one page of search results.
+/// Best-effort: on a database error the results are returned without them.
+pub(crate) async fn latest_summaries(
+    db: &sqlx::PgPool,
+    contract_ids: &[Uuid],
+) -> HashMap<Uuid, TestSummary> {
+    let rows: Vec<SummaryRow> = match sqlx::query_as(
+        "SELECT DISTINCT ON (v.contract_id) \
+             v.contract_id, t.passed, t.failed, t.skipped, t.coverage_percent \
+         FROM contract_versions v \
+         LEFT JOIN version_test_results t ON t.version_id = v.id \
+         WHERE v.contract_id = ANY($1) \
+         ORDER BY v.contract_id, v.created_at DESC",
+    )
+    .bind(contract_ids)
+    .fetch_all(db)
+    .await
+    {
+        Ok(rows) => rows,
+        Err(err) => {
+            tracing::warn!(error = ?err, "fetching test summaries failed");
+            return HashMap::new();
+        }
+    };
+    rows.into_iter()
+        .filter_map(|(id, passed, failed, skipped, coverage_percent)| {
+            Some((
+                id,
+                TestSummary {
+                    passed: passed?,
+                    failed: failed?,
+                    skipped: skipped?,
+                    coverage_percent,
+                },
+            ))
+        })
+        .collect()
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TestResultsQuery {
+    /// Only this version, e.g. `1.3.0`
+    pub version: Option<String>,
+}
+
+/// GET /api/contracts/:id/tests — attached test results, newest version first;
+/// `?version=` selects one
+pub async fn get_contract_tests(
+    State(state): State<AppState>,
+    viewer: Viewer,
+    Path(id): Path<String>,
+    Query(query): Query<TestResultsQuery>,
+) -> ApiResult<Json<ContractTestResultsResponse>> {
+    let (contract_uuid, _) = fetch_contract_identity(&state, viewer.tenant_id, &id).await?;
+    ensure_contract_visible(&state, &viewer, contract_uuid).await?;
+
+    let mut versions: Vec<VersionTestResults> = sqlx::query_as(
+        "SELECT v.version, t.format, t.passed, t.failed, t.skipped, t.coverage_percent, \
+                t.recorded_at \
+         FROM version_test_results t \
+         JOIN contract_versions v ON v.id = t.version_id \
+         WHERE v.contract_id = $1 AND ($2::TEXT IS NULL OR v.version = $2)",
+    )
+    .bind(contract_uuid)
+    .bind(query.version.as_deref())
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("fetch test results", err))?;
+
+    if let (Some(version), true) = (&query.version, versions.is_empty()) {
+        return Err(ApiError::not_found(
+            "TestResultsNotFound",
+            format!("No test results were attached to version '{}'", version),
+        ));
+    }
+    versions.sort_by(|a, b| {
+        SemVer::parse(&b.version)
+            .cmp(&SemVer::parse(&a.version))
+            .then(b.recorded_at.cmp(&a.recorded_at))
+    });
+
+    Ok(Json(ContractTestResultsResponse {
+        contract_id: contract_uuid,
+        versions,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn junit_counts_each_case_once() {
+        let xml = r#"<?xml version="1.0"?>
+            <testsuites>
+              <testsuite name="amm" tests="5">
+                <testcase name="swap"/>
+                <testcase name="deposit"></testcase>
+                <testcase name="withdraw"><failure message="boom"/><error/></testcase>
+                <testcase name="fees"><skipped/></testcase>
+                <testcase name="oracle"><error message="panic"/></testcase>
+              </testsuite>
+            </testsuites>"#;
+        let (format, summary) = parse_report(xml.as_bytes()).unwrap();
+        assert_eq!(format, "junit");
+        assert_eq!((summary.passed, summary.failed, summary.skipped), (2, 2, 1));
+    }
+
+    #[test]
+    fn json_summaries_accept_coverage() {
+        let (format, summary) =
+            parse_report(br#"{"passed": 124, "failed": 0, "coverage": 87.5}"#).unwrap();
+        assert_eq!(format, "json");
+        assert_eq!(summary.passed, 124);
+        assert_eq!(summary.coverage_percent, Some(87.5));
+        assert!(parse_report(br#"{"passed": 1, "coverage": 120}"#).is_err());
+    }
+
+    #[test]
+    fn rejects_other_content() {
+        assert!(parse_report(b"all tests passed").is_err());
+        assert!(parse_report(b"<testsuites></testsuites>").is_err());
+        assert!(parse_report(&[0xff, 0xfe]).is_err());
+    }
+}
@@ -0,0 +1,498 @@
+// testkit.rs
+// Ephemeral registries for integration tests: the real router over a throwaway
+// database, with fixture seeding and snapshot/restore between test cases.
+//
+// Each `TestRegistry` gets its own database cloned from a migrated template, so
+// tests can run in parallel against one Postgres server. The server comes from
+// `TESTKIT_DATABASE_URL`, falling back to `DATABASE_URL`. There is no sqlite
+// mode: handlers rely on Postgres types, arrays and full-text search.
+
+use std::net::SocketAddr;
+use std::str::FromStr;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use axum::body::Body;
+use axum::http::{header, Method, Request, Response, StatusCode};
+use axum::Router;
+use prometheus::Registry;
+use sha2::Digest;
+use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
+use sqlx::{ConnectOptions, Connection, PgConnection, PgPool};
+use tokio::sync::OnceCell;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+use crate::api_usage::ApiUsageRecorder;
+use crate::settings::Settings;
+use crate::state::AppState;
+use crate::user_auth::{hash_api_key, API_KEY_PREFIX};
+use shared::{ApiScope, Contract, Network};
+
+const TEMPLATE_DATABASE: &str = "registry_testkit_template";
+const SNAPSHOT_SCHEMA: &str = "testkit_snapshot";
+
+static TEMPLATE: OnceCell<()> = OnceCell::const_new();
+
+/// Connection options for the server named by the environment
+fn server_options() -> Result<PgConnectOptions> {
+    let url = std::env::var("TESTKIT_DATABASE_URL")
+        .or_else(|_| std::env::var("DATABASE_URL"))
+        .context("Set TESTKIT_DATABASE_URL (or DATABASE_URL) to a Postgres server")?;
+    Ok(PgConnectOptions::from_str(&url)?)
+}
+
+/// Name of a fresh per-registry database
+fn database_name(id: Uuid) -> String {
+    format!("registry_test_{}", id.simple())
+}
+
+/// Quote an identifier for interpolation into DDL
+fn quote_ident(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+async fn admin_connection(server: &PgConnectOptions) -> Result<PgConnection> {
+    server
+        .clone()
+        .database("postgres")
+        .connect()
+        .await
+        .context("Failed to connect to the testkit Postgres server")
+}
+
+/// Create and migrate the template database once per process
+async fn ensure_template(server: &PgConnectOptions) -> Result<()> {
+    TEMPLATE
+        .get_or_try_init(|| async {
+            let mut admin = admin_connection(server).await?;
+            let exists: bool =
+                sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM pg_database WHERE datname = $1)")
+                    .bind(TEMPLATE_DATABASE)
+                    .fetch_one(&mut admin)
+                    .await?;
+            if !exists {
+                let created = sqlx::query(&format!(
+                    "CREATE DATABASE {}",
+                    quote_ident(TEMPLATE_DATABASE)
+                ))
+                .execute(&mut admin)
+                .await;
+                // Another test binary may have won the race
+                if let Err(err) = created {
+                    if !err.to_string().contains("already exists") {
+                        return Err(err.into());
+                    }
+                }
+            }
+            admin.close().await?;
+
+            let pool = PgPoolOptions::new()
+                .max_connections(1)
+                .connect_with(server.clone().database(TEMPLATE_DATABASE))
+                .await?;
+            sqlx::migrate!("../../database/migrations")
+                .run(&pool)
+                .await
+                .context("Failed to migrate the testkit template database")?;
+            // Postgres refuses to clone a template that has open connections
+            pool.close().await;
+            Ok::<(), anyhow::Error>(())
+        })
+        .await
+        .copied()
+}
+
+/// A contract to insert with [`TestRegistry::seed_contract`]
+#[derive(Debug, Clone)]
+pub struct ContractFixture {
+    name: String,
+    namespace: Option<String>,
+    publisher: String,
+    network: Network,
+    category: Option<String>,
+    tags: Vec<String>,
+    versions: Vec<String>,
+    verified: bool,
+}
+
+impl ContractFixture {
+    /// An unverified testnet contract with a single 1.0.0 version
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            namespace: None,
+            publisher: "GTESTKITPUBLISHER".to_string(),
+            network: Network::Testnet,
+            category: None,
+            tags: Vec::new(),
+            versions: vec!["1.0.0".to_string()],
+            verified: false,
+        }
+    }
+
+    pub fn namespace(mut self, namespace: &str) -> Self {
+        self.namespace = Some(namespace.to_string());
+        self
+    }
+
+    /// Stellar address of the publisher, created on first use
+    pub fn publisher(mut self, address: &str) -> Self {
+        self.publisher = address.to_string();
+        self
+    }
+
+    pub fn network(mut self, network: Network) -> Self {
+        self.network = network;
+        self
+    }
+
+    pub fn category(mut self, category: &str) -> Self {
+        self.category = Some(category.to_string());
+        self
+    }
+
+    pub fn tags(mut self, tags: &[&str]) -> Self {
+        self.tags = tags.iter().map(|t| t.to_string()).collect();
+        self
+    }
+
+    /// Versions to publish, oldest first; replaces the default 1.0.0
+    pub fn versions(mut self, versions: &[&str]) -> Self {
+        self.versions = versions.iter().map(|v| v.to_string()).collect();
+        self
+    }
+
+    pub fn verified(mut self) -> Self {
+        self.verified = true;
+        self
+    }
+}
+
+/// A registry running over its own database
+pub struct TestRegistry {
+    pub state: AppState,
+    pub router: Router,
+    server: PgConnectOptions,
+    database: String,
+}
+
+impl TestRegistry {
+    /// Clone the migrated template into a new database and build the router over it
+    pub async fn start() -> Result<Self> {
+        Self::start_with(Settings::default()).await
+    }
+
+    /// As [`start`](Self::start), with limits and policies taken from `settings`
+    pub async fn start_with(settings: Settings) -> Result<Self> {
+        let server = server_options()?;
+        ensure_template(&server).await?;
+
+        let database = database_name(Uuid::new_v4());
+        let mut admin = admin_connection(&server).await?;
+        sqlx::query(&format!(
+            "CREATE DATABASE {} TEMPLATE {}",
+            quote_ident(&database),
+            quote_ident(TEMPLATE_DATABASE)
+        ))
+        .execute(&mut admin)
+        .await
+        .context("Failed to create the test database")?;
+        admin.close().await?;
+
+        let pool = PgPoolOptions::new()
+            .max_connections(5)
+            .connect_with(server.clone().database(&database))
+            .await?;
+        let state = AppState::new(pool, Registry::new(), Arc::new(AtomicBool::new(false)));
+        let api_usage = ApiUsageRecorder::new(state.clone());
+        let router = crate::app::build(state.clone(), &settings, api_usage);
+
+        Ok(Self {
+            state,
+            router,
+            server,
+            database,
+        })
+    }
+
+    pub fn db(&self) -> &PgPool {
+        &self.state.db
+    }
+
+    /// Send one request through the full middleware stack
+    pub async fn request(&self, request: Request<Body>) -> Response<Body> {
+        self.router
+            .clone()
+            .oneshot(request)
+            .await
+            .expect("router is infallible")
+    }
+
+    /// Send a JSON request, as the holder of `token` when given, and decode the
+    /// reply; an empty body decodes as `null`
+    pub async fn send_json(
+        &self,
+        method: Method,
+        uri: &str,
+        token: Option<&str>,
+        body: Option<serde_json::Value>,
+    ) -> Result<(StatusCode, serde_json::Value)> {
+        let mut request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header(header::CONTENT_TYPE, "application/json");
+        if let Some(token) = token {
+            request = request.header(header::AUTHORIZATION, format!("Bearer {}", token));
+        }
+        let request = match body {
+            Some(body) => request.body(Body::from(body.to_string()))?,
+            None => request.body(Body::empty())?,
+        };
+        let response = self.request(request).await;
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await?;
+        if bytes.is_empty() {
+            return Ok((status, serde_json::Value::Null));
+        }
+        Ok((status, serde_json::from_slice(&bytes)?))
+    }
+
+    /// Serve the router on a random local port and return its base URL, for
+    /// tests that drive the registry over real HTTP (e.g. the CLI)
+    pub async fn serve(&self) -> Result<String> {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+        let addr = listener.local_addr()?;
+        let app = self
+            .router
+            .clone()
+            .into_make_service_with_connect_info::<SocketAddr>();
+        tokio::spawn(async move {
+            if let Err(err) = axum::serve(listener, app).await {
+                tracing::error!(error = %err, "testkit server stopped");
+            }
+        });
+        Ok(format!("http://{}", addr))
+    }
+
+    /// Insert a publisher, contract and versions the way a publish would leave them
+    pub async fn seed_contract(&self, fixture: ContractFixture) -> Result<Contract> {
+        let mut tx = self.db().begin().await?;
+
+        let publisher_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO publishers (stellar_address)
+             VALUES ($1)
+             ON CONFLICT (stellar_address) DO UPDATE SET stellar_address = EXCLUDED.stellar_address
+             RETURNING id",
+        )
+        .bind(&fixture.publisher)
+        .fetch_one(&mut *tx)
+        .await
+        .context("Failed to seed publisher")?;
+
+        let wasm_hash = fixture_hash(&fixture.name, fixture.versions.last());
+        let contract: Contract = sqlx::query_as(
+            "INSERT INTO contracts (
+                contract_id, wasm_hash, name, publisher_id, network,
+                category, tags, is_verified, namespace
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            RETURNING *",
+        )
+        .bind(fixture_contract_id(&fixture.name, &fixture.network))
+        .bind(&wasm_hash)
+        .bind(&fixture.name)
+        .bind(publisher_id)
+        .bind(&fixture.network)
+        .bind(&fixture.category)
+        .bind(&fixture.tags)
+        .bind(fixture.verified)
+        .bind(&fixture.namespace)
+        .fetch_one(&mut *tx)
+        .await
+        .context("Failed to seed contract")?;
+
+        for version in &fixture.versions {
+            sqlx::query(
+                "INSERT INTO contract_versions (contract_id, version, wasm_hash)
+                 VALUES ($1, $2, $3)",
+            )
+            .bind(contract.id)
+            .bind(version)
+            .bind(fixture_hash(&fixture.name, Some(version)))
+            .execute(&mut *tx)
+            .await
+            .with_context(|| format!("Failed to seed {} {}", fixture.name, version))?;
+        }
+
+        tx.commit().await?;
+        Ok(contract)
+    }
+
+    /// Mint an API key for `address` in the default registry, to send as
+    /// `Authorization: Bearer <key>`
+    pub async fn api_key(&self, address: &str, scopes: &[ApiScope]) -> Result<String> {
+        let key = format!("{}testkit{}", API_KEY_PREFIX, Uuid::new_v4().simple());
+        sqlx::query(
+            "INSERT INTO api_keys (owner_address, name, prefix, key_hash, scopes)
+             VALUES ($1, 'testkit', $2, $3, $4)",
+        )
+        .bind(address)
+        .bind(&key[..12])
+        .bind(hash_api_key(&key))
+        .bind(scopes.iter().map(|s| s.as_str()).collect::<Vec<_>>())
+        .execute(self.db())
+        .await
+        .context("Failed to mint API key")?;
+        Ok(key)
+    }
+
+    /// Copy every table and sequence aside so [`restore`](Self::restore) can
+    /// return to this point. Taking a new snapshot replaces the previous one.
+    pub async fn snapshot(&self) -> Result<()> {
+        let mut tx = self.db().begin().await?;
+        sqlx::query(&format!(
+            "DROP SCHEMA IF EXISTS {} CASCADE",
+            SNAPSHOT_SCHEMA
+        ))
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query(&format!("CREATE SCHEMA {}", SNAPSHOT_SCHEMA))
+            .execute(&mut *tx)
+            .await?;
+        for table in self.tables().await? {
+            sqlx::query(&format!(
+                "CREATE TABLE {}.{} AS TABLE public.{}",
+                SNAPSHOT_SCHEMA,
+                quote_ident(&table),
+                quote_ident(&table)
+            ))
+            .execute(&mut *tx)
+            .await
+            .with_context(|| format!("Failed to snapshot {}", table))?;
+        }
+        sqlx::query(&format!(
+            "CREATE TABLE {}.testkit_sequences AS
+             SELECT sequencename AS name, last_value FROM pg_sequences WHERE schemaname = 'public'",
+            SNAPSHOT_SCHEMA
+        ))
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Put every table and sequence back to the last snapshot and drop cached
+    /// reads. Requires a superuser role, since triggers and foreign keys are
+    /// suspended while rows are copied back.
+    pub async fn restore(&self) -> Result<()> {
+        let tables = self.tables().await?;
+        let mut tx = self.db().begin().await?;
+        sqlx::query("SET LOCAL session_replication_role = replica")
+            .execute(&mut *tx)
+            .await
+            .context("Restoring a snapshot needs a superuser role")?;
+        let quoted: Vec<String> = tables
+            .iter()
+            .map(|t| format!("public.{}", quote_ident(t)))
+            .collect();
+        if !quoted.is_empty() {
+            sqlx::query(&format!("TRUNCATE {} CASCADE", quoted.join(", ")))
+                .execute(&mut *tx)
+                .await?;
+        }
+        for table in &tables {
+            sqlx::query(&format!(
+                "INSERT INTO public.{} SELECT * FROM {}.{}",
+                quote_ident(table),
+                SNAPSHOT_SCHEMA,
+                quote_ident(table)
+            ))
+            .execute(&mut *tx)
+            .await
+            .with_context(|| format!("Failed to restore {}; was a snapshot taken?", table))?;
+        }
+        sqlx::query(&format!(
+            "SELECT setval(format('public.%I', name), last_value, true)
+             FROM {}.testkit_sequences WHERE last_value IS NOT NULL",
+            SNAPSHOT_SCHEMA
+        ))
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        self.state.cache.abi_cache.invalidate_all();
+        self.state.cache.verification_cache.invalidate_all();
+        Ok(())
+    }
+
+    /// Drop the database. Registries that are never cleaned up leave their
+    /// database behind for inspection.
+    pub async fn cleanup(self) -> Result<()> {
+        self.state.db.close().await;
+        let mut admin = admin_connection(&self.server).await?;
+        sqlx::query(&format!(
+            "DROP DATABASE IF EXISTS {} WITH (FORCE)",
+            quote_ident(&self.database)
+        ))
+        .execute(&mut admin)
+        .await?;
+        admin.close().await?;
+        Ok(())
+    }
+
+    /// Application tables, excluding sqlx's migration bookkeeping
+    async fn tables(&self) -> Result<Vec<String>> {
+        Ok(sqlx::query_scalar(
+            "SELECT tablename FROM pg_tables
+             WHERE schemaname = 'public' AND tablename <> '_sqlx_migrations'
+             ORDER BY tablename",
+        )
+        .fetch_all(self.db())
+        .await?)
+    }
+}
+
+/// Deterministic 56-character contract address for a fixture
+fn fixture_contract_id(name: &str, network: &Network) -> String {
+    let digest = hex::encode(sha2::Sha256::digest(format!("{}:{}", network, name)));
+    format!("C{}", digest[..55].to_uppercase())
+}
+
+/// Deterministic WASM hash for a fixture version
+fn fixture_hash(name: &str, version: Option<&String>) -> String {
+    hex::encode(sha2::Sha256::digest(format!(
+        "{}@{}",
+        name,
+        version.map(String::as_str).unwrap_or_default()
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn database_names_are_unique_valid_identifiers() {
+        let a = database_name(Uuid::new_v4());
+        let b = database_name(Uuid::new_v4());
+        assert_ne!(a, b);
+        assert!(a.len() < 64, "Postgres truncates identifiers at 63 bytes");
+        assert!(a.chars().all(|c| c.is_ascii_alphanumeric() || c == '_'));
+    }
+
+    #[test]
+    fn identifiers_are_quoted() {
+        assert_eq!(quote_ident("contracts"), "\"contracts\"");
+        assert_eq!(quote_ident("odd\"name"), "\"odd\"\"name\"");
+    }
+
+    #[test]
+    fn fixture_ids_are_stable_per_network() {
+        let testnet = fixture_contract_id("amm", &Network::Testnet);
+        assert_eq!(testnet.len(), 56);
+        assert!(testnet.starts_with('C'));
+        assert_eq!(testnet, fixture_contract_id("amm", &Network::Testnet));
+        assert_ne!(testnet, fixture_contract_id("amm", &Network::Mainnet));
+    }
+}
@@ -0,0 +1,140 @@
+// tls.rs
+// HTTPS termination for `[server.tls]`. With `client_ca_path` set, clients must
+// present a certificate signed by that CA (mutual TLS), as service meshes and
+// regulated deployments expect.
+
+use std::convert::Infallible;
+use std::future::Future;
+use std::sync::Arc;
+
+use anyhow::Context;
+use axum::{
+    body::Body,
+    extract::connect_info::ConnectInfo,
+    http::{Request, Response},
+};
+use hyper::body::Incoming;
+use hyper_util::rt::TokioIo;
+use rustls_pki_types::{pem::PemObject, CertificateDer, PrivateKeyDer};
+use tokio::net::TcpListener;
+use tokio_rustls::rustls::{
+    crypto::ring, server::WebPkiClientVerifier, RootCertStore, ServerConfig,
+};
+use tokio_rustls::TlsAcceptor;
+use tower::Service;
+
+use crate::settings::TlsSettings;
+
+/// Build the acceptor for `[server.tls]`; errors name the file that failed
+pub fn acceptor(settings: &TlsSettings) -> anyhow::Result<TlsAcceptor> {
+    let cert_path = settings
+        .cert_path
+        .as_deref()
+        .context("server.tls.cert_path must be set")?;
+    let key_path = settings
+        .key_path
+        .as_deref()
+        .context("server.tls.key_path must be set")?;
+
+    let certs = CertificateDer::pem_file_iter(cert_path)
+        .and_then(|certs| certs.collect::<Result<Vec<_>, _>>())
+        .with_context(|| format!("Failed to read server.tls.cert_path {}", cert_path))?;
+    let key = PrivateKeyDer::from_pem_file(key_path)
+        .with_context(|| format!("Failed to read server.tls.key_path {}", key_path))?;
+
+    let provider = Arc::new(ring::default_provider());
+    let builder = ServerConfig::builder_with_provider(provider.clone())
+        .with_safe_default_protocol_versions()
+        .context("No supported TLS protocol versions")?;
+    let builder = match settings.client_ca_path.as_deref() {
+        Some(ca_path) => {
+            let mut roots = RootCertStore::empty();
+            let cas = CertificateDer::pem_file_iter(ca_path)
+                .and_then(|certs| certs.collect::<Result<Vec<_>, _>>())
+                .with_context(|| format!("Failed to read server.tls.client_ca_path {}", ca_path))?;
+            for ca in cas {
+                roots
+                    .add(ca)
+                    .with_context(|| format!("Invalid CA certificate in {}", ca_path))?;
+            }
+            let verifier = WebPkiClientVerifier::builder_with_provider(Arc::new(roots), provider);
+            let verifier = if settings.client_cert_optional {
+                verifier.allow_unauthenticated()
+            } else {
+                verifier
+            };
+            builder.with_client_cert_verifier(
+                verifier
+                    .build()
+                    .context("Failed to set up client certificate verification")?,
+            )
+        }
+        None => builder.with_no_client_auth(),
+    };
+
+    let mut config = builder
+        .with_single_cert(certs, key)
+        .context("server.tls.cert_path and key_path do not form a valid certificate")?;
+    config.alpn_protocols = vec![b"http/1.1".to_vec()];
+    Ok(TlsAcceptor::from(Arc::new(config)))
+}
+
+/// Serve `app` over TLS until `shutdown` resolves. Each request carries the
+/// peer address as `ConnectInfo<SocketAddr>`, like the plain-HTTP listener.
+pub async fn serve<S>(
+    listener: TcpListener,
+    acceptor: TlsAcceptor,
+    app: S,
+    shutdown: impl Future<Output = ()>,
+) -> std::io::Result<()>
+where
+    S: Service<Request<Body>, Response = Response<Body>, Error = Infallible>
+        + Clone
+        + Send
+        + 'static,
+    S::Future: Send,
+{
+    tokio::pin!(shutdown);
+    loop {
+        let (stream, peer) = tokio::select! {
+            accepted = listener.accept() => match accepted {
+                Ok(accepted) => accepted,
+                Err(err) => {
+                    // e.g. out of file descriptors; back off instead of spinning
+                    tracing::warn!(error = %err, "Failed to accept a connection");
+                    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+                    continue;
+                }
+            },
+            _ = &mut shutdown => return Ok(()),
+        };
+
+        let acceptor = acceptor.clone();
+        let app = app.clone();
+        tokio::spawn(async move {
+            // Handshake failures include clients without an acceptable certificate
+            let stream = match acceptor.accept(stream).await {
+                Ok(stream) => stream,
+                Err(err) => {
+                    tracing::debug!(peer = %peer, error = %err, "TLS handshake failed");
+                    return;
+                }
+            };
+            let service = hyper::service::service_fn(move |mut request: Request<Incoming>| {
+                request.extensions_mut().insert(ConnectInfo(peer));
+                let mut app = app.clone();
+                async move {
+                    std::future::poll_fn(|cx| app.poll_ready(cx)).await?;
+                    app.call(request.map(Body::new)).await
+                }
+            });
+            if let Err(err) = hyper::server::conn::http1::Builder::new()
+                .serve_connection(TokioIo::new(stream), service)
+                .with_upgrades()
+                .await
+            {
+                tracing::debug!(peer = %peer, error = %err, "Connection closed with an error");
+            }
+        });
+    }
+}
@@ -0,0 +1,258 @@
+// token_handlers.rs
+// Metadata of SEP-41 tokens (name, symbol, decimals) read from the chain
+// through the network's RPC and cached, and the registry's tokens as a SEP-42
+// asset list so wallets can use the registry as a token list source.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use chrono::{DateTime, Duration, Utc};
+use shared::{
+    asset_list_network, AssetList, AssetListEntry, Network, TokenDetails, TokenListQuery,
+    TokenMetadata, SEP41_INTERFACE,
+};
+use uuid::Uuid;
+
+use crate::error::{ApiError, ApiResult};
+use crate::handlers::{db_internal_error, fetch_contract_identity};
+use crate::private_registry::{ensure_contract_visible, Viewer};
+use crate::soroban_rpc::RpcCallError;
+use crate::state::AppState;
+
+/// Cached metadata older than this is read from the chain again
+const TOKEN_METADATA_TTL_HOURS: i64 = 24;
+
+/// Tokens refreshed in the background per asset list request
+const TOKENLIST_REFRESH_BATCH: usize = 20;
+
+/// Set while an asset list refresh runs, so requests don't stack them
+static TOKENLIST_REFRESHING: AtomicBool = AtomicBool::new(false);
+
+#[derive(sqlx::FromRow)]
+struct CachedToken {
+    network: Network,
+    is_token: bool,
+    name: Option<String>,
+    symbol: Option<String>,
+    decimals: Option<i32>,
+    fetched_at: Option<DateTime<Utc>>,
+}
+
+#[derive(sqlx::FromRow)]
+struct ListedToken {
+    id: Uuid,
+    contract_id: String,
+    org: Option<String>,
+    name: Option<String>,
+    symbol: Option<String>,
+    decimals: Option<i32>,
+    fetched_at: Option<DateTime<Utc>>,
+}
+
+fn is_fresh(fetched_at: Option<DateTime<Utc>>) -> bool {
+    fetched_at.is_some_and(|at| Utc::now() - at < Duration::hours(TOKEN_METADATA_TTL_HOURS))
+}
+
+fn cached_details(
+    name: Option<String>,
+    symbol: Option<String>,
+    decimals: Option<i32>,
+) -> Option<TokenDetails> {
+    Some(TokenDetails {
+        name: name?,
+        symbol: symbol?,
+        decimals: u32::try_from(decimals?).ok()?,
+    })
+}
+
+/// Calls the token's metadata functions on its network
+async fn fetch_details(
+    state: &AppState,
+    network: &Network,
+    contract_id: &str,
+) -> Result<TokenDetails, RpcCallError> {
+    let call = |function| state.rpc.call(network, contract_id, function, Vec::new());
+    let (name, symbol, decimals) =
+        tokio::try_join!(call("name"), call("symbol"), call("decimals"))?;
+    TokenDetails::from_scvals(&name, &symbol, &decimals).map_err(RpcCallError::InvalidResponse)
+}
+
+async fn store_details(
+    state: &AppState,
+    contract_uuid: Uuid,
+    details: &TokenDetails,
+) -> ApiResult<DateTime<Utc>> {
+    sqlx::query_scalar(
+        "INSERT INTO token_metadata (contract_id, name, symbol, decimals) \
+         VALUES ($1, $2, $3, $4) \
+         ON CONFLICT (contract_id) DO UPDATE SET name = EXCLUDED.name, \
+             symbol = EXCLUDED.symbol, decimals = EXCLUDED.decimals, fetched_at = NOW() \
+         RETURNING fetched_at",
+    )
+    .bind(contract_uuid)
+    .bind(&details.name)
+    .bind(&details.symbol)
+    .bind(details.decimals as i32)
+    .fetch_one(&state.db)
+    .await
+    .map_err(|err| db_internal_error("store token metadata", err))
+}
+
+/// GET /api/contracts/:id/token — the token's metadata, from the cache while
+/// fresh. An expired copy is served (as `stale`) when the chain can't be read.
+pub async fn get_token_metadata(
+    State(state): State<AppState>,
+    viewer: Viewer,
+    Path(id): Path<String>,
+) -> ApiResult<Json<TokenMetadata>> {
+    let (contract_uuid, contract_id) =
+        fetch_contract_identity(&state, viewer.tenant_id, &id).await?;
+    ensure_contract_visible(&state, &viewer, contract_uuid).await?;
+
+    let cached: CachedToken = sqlx::query_as(
+        "SELECT c.network, COALESCE($2 = ANY(c.implements), FALSE) AS is_token, \
+                t.name, t.symbol, t.decimals, t.fetched_at \
+         FROM contracts c LEFT JOIN token_metadata t ON t.contract_id = c.id \
+         WHERE c.id = $1",
+    )
+    .bind(contract_uuid)
+    .bind(SEP41_INTERFACE)
+    .fetch_one(&state.db)
+    .await
+    .map_err(|err| db_internal_error("fetch token metadata", err))?;
+    if !cached.is_token {
+        return Err(ApiError::not_found(
+            "NotAToken",
+            format!("Contract {} does not implement SEP-41", id),
+        ));
+    }
+
+    let fresh = is_fresh(cached.fetched_at);
+    let details = cached_details(cached.name, cached.symbol, cached.decimals);
+    let metadata = |details, fetched_at, stale| TokenMetadata {
+        contract_id: contract_id.clone(),
+        network: cached.network.clone(),
+        details,
+        fetched_at,
+        stale,
+    };
+    if let (true, Some(details), Some(fetched_at)) = (fresh, details.clone(), cached.fetched_at) {
+        return Ok(Json(metadata(details, fetched_at, false)));
+    }
+
+    match fetch_details(&state, &cached.network, &contract_id).await {
+        Ok(details) => {
+            let fetched_at = store_details(&state, contract_uuid, &details).await?;
+            Ok(Json(metadata(details, fetched_at, false)))
+        }
+        Err(err) => {
+            tracing::warn!(contract = %contract_id, error = %err, "failed to read token metadata");
+            match (details, cached.fetched_at) {
+                (Some(details), Some(fetched_at)) => Ok(Json(metadata(details, fetched_at, true))),
+                _ => Err(err.into()),
+            }
+        }
+    }
+}
+
+/// Reads tokens with missing or expired metadata; failures are logged and
+/// retried on a later request
+async fn refresh_tokens(state: AppState, network: Network, tokens: Vec<(Uuid, String)>) {
+    for (contract_uuid, contract_id) in tokens {
+        let result = match fetch_details(&state, &network, &contract_id).await {
+            Ok(details) => store_details(&state, contract_uuid, &details)
+                .await
+                .map(drop)
+                .map_err(|err| err.to_string()),
+            Err(err) => Err(err.to_string()),
+        };
+        if let Err(reason) = result {
+            tracing::warn!(contract = %contract_id, error = %reason, "failed to refresh token metadata");
+        }
+    }
+    TOKENLIST_REFRESHING.store(false, Ordering::SeqCst);
+}
+
+/// GET /api/tokenlist.json?network= — the SEP-41 tokens registered on a
+/// network (mainnet by default) as a SEP-42 asset list. Listed from the cache;
+/// tokens not read yet are fetched in the background and appear once cached.
+pub async fn get_tokenlist(
+    State(state): State<AppState>,
+    viewer: Viewer,
+    Query(query): Query<TokenListQuery>,
+) -> ApiResult<Json<AssetList>> {
+    let network = query.network.unwrap_or(Network::Mainnet);
+    let rows: Vec<ListedToken> = sqlx::query_as(&format!(
+        "SELECT c.id, c.contract_id, p.username AS org, \
+                t.name, t.symbol, t.decimals, t.fetched_at \
+         FROM contracts c \
+         LEFT JOIN publishers p ON p.id = c.publisher_id \
+         LEFT JOIN token_metadata t ON t.contract_id = c.id \
+         WHERE c.network = $1 AND $2 = ANY(c.implements){} \
+         ORDER BY t.symbol, c.contract_id",
+        viewer.visibility_clause("c.")
+    ))
+    .bind(&network)
+    .bind(SEP41_INTERFACE)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("list tokens", err))?;
+
+    let mut assets = Vec::with_capacity(rows.len());
+    let mut expired = Vec::new();
+    for row in rows {
+        if !is_fresh(row.fetched_at) {
+            expired.push((row.id, row.contract_id.clone()));
+        }
+        if let Some(details) = cached_details(row.name, row.symbol, row.decimals) {
+            assets.push(AssetListEntry {
+                contract: row.contract_id,
+                code: details.symbol,
+                name: details.name,
+                decimals: details.decimals,
+                org: row.org,
+            });
+        }
+    }
+    if !expired.is_empty()
+        && state.rpc.ensure_available(&network).is_ok()
+        && !TOKENLIST_REFRESHING.swap(true, Ordering::SeqCst)
+    {
+        expired.truncate(TOKENLIST_REFRESH_BATCH);
+        tokio::spawn(refresh_tokens(state.clone(), network.clone(), expired));
+    }
+
+    Ok(Json(AssetList {
+        name: "Soroban Registry".to_string(),
+        provider: "Soroban Registry".to_string(),
+        description: format!("SEP-41 tokens published to the registry on {}", network),
+        version: "1.0".to_string(),
+        network: asset_list_network(&network).to_string(),
+        assets,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn metadata_expires_after_the_ttl() {
+        assert!(is_fresh(Some(Utc::now() - Duration::hours(1))));
+        assert!(!is_fresh(Some(
+            Utc::now() - Duration::hours(TOKEN_METADATA_TTL_HOURS + 1)
+        )));
+        assert!(!is_fresh(None));
+    }
+
+    #[test]
+    fn partial_cache_rows_have_no_details() {
+        assert!(cached_details(Some("Coin".into()), None, Some(7)).is_none());
+        assert!(cached_details(Some("Coin".into()), Some("C".into()), Some(-1)).is_none());
+        let details = cached_details(Some("Coin".into()), Some("C".into()), Some(7)).unwrap();
+        assert_eq!(details.decimals, 7);
+    }
+}
@@ -0,0 +1,98 @@
+// toolchain_handlers.rs
+// The toolchain a version was built with, so verifiers and other users can
+// rebuild the exact binary from its source.
+
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use shared::{BuildToolchain, ToolchainQuery, ToolchainResponse};
+use sqlx::types::Json as SqlJson;
+
+use crate::error::{ApiError, ApiResult};
+use crate::handlers::{db_internal_error, fetch_contract_identity};
+use crate::private_registry::{ensure_contract_visible, Viewer};
+use crate::state::AppState;
+
+type VersionToolchainRow = (
+    String,
+    String,
+    Option<String>,
+    Option<String>,
+    Option<SqlJson<BuildToolchain>>,
+);
+
+/// GET /api/contracts/:id/toolchain?version=
+pub async fn get_contract_toolchain(
+    State(state): State<AppState>,
+    viewer: Viewer,
+    Path(id): Path<String>,
+    Query(query): Query<ToolchainQuery>,
+) -> ApiResult<Json<ToolchainResponse>> {
+    let (contract_uuid, contract_id) =
+        fetch_contract_identity(&state, viewer.tenant_id, &id).await?;
+    ensure_contract_visible(&state, &viewer, contract_uuid).await?;
+
+    let row: Option<VersionToolchainRow> = match query.version {
+        Some(ref version) => {
+            sqlx::query_as(
+                "SELECT version, wasm_hash, source_url, commit_hash, build_toolchain
+             FROM contract_versions
+             WHERE contract_id = $1 AND version = $2",
+            )
+            .bind(contract_uuid)
+            .bind(version)
+            .fetch_optional(&state.db)
+            .await
+        }
+        None => {
+            sqlx::query_as(
+                "SELECT version, wasm_hash, source_url, commit_hash, build_toolchain
+             FROM contract_versions
+             WHERE contract_id = $1 ORDER BY created_at DESC LIMIT 1",
+            )
+            .bind(contract_uuid)
+            .fetch_optional(&state.db)
+            .await
+        }
+    }
+    .map_err(|err| db_internal_error("fetch contract version toolchain", err))?;
+
+    let (version, wasm_hash, source_url, commit_hash, toolchain) = row.ok_or_else(|| {
+        ApiError::not_found(
+            "VersionNotFound",
+            format!(
+                "No version {} found for contract {}",
+                query.version.as_deref().unwrap_or("(latest)"),
+                id
+            ),
+        )
+    })?;
+    let SqlJson(toolchain) = toolchain.ok_or_else(|| {
+        ApiError::not_found(
+            "ToolchainNotRecorded",
+            format!(
+                "Version {} of {} was published without a toolchain fingerprint",
+                version, id
+            ),
+        )
+    })?;
+
+    let mut warnings: Vec<String> = toolchain
+        .reproducibility_warnings()
+        .into_iter()
+        .map(str::to_string)
+        .collect();
+    if commit_hash.is_none() {
+        warnings.push("no source commit was recorded".to_string());
+    }
+    Ok(Json(ToolchainResponse {
+        contract_id,
+        version,
+        wasm_hash,
+        source_url,
+        commit_hash,
+        toolchain,
+        warnings,
+    }))
+}
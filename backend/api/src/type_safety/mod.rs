@@ -15,9 +15,11 @@ pub mod openapi;
 pub mod parser;
 pub mod types;
 pub mod validator;
+pub mod wasm_spec;
 
 pub use bindings::*;
 pub use openapi::*;
 pub use parser::*;
 pub use types::*;
 pub use validator::*;
+pub use wasm_spec::*;
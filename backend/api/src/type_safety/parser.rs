@@ -142,11 +142,15 @@ pub fn parse_contract_abi(
         }
     }
 
-    // Second pass: parse functions with resolved types
+    // Second pass: parse functions and events with resolved types
     for spec in specs {
-        if spec.spec_type == "function" {
-            let func = parse_function(spec, &abi.types)?;
-            abi.functions.push(func);
+        match spec.spec_type.as_str() {
+            "function" => {
+                let func = parse_function(spec, &abi.types)?;
+                abi.functions.push(func);
+            }
+            "event" => abi.events.push(parse_event(spec)),
+            _ => {}
         }
     }
 
@@ -250,6 +254,29 @@ fn parse_function(
     })
 }
 
+/// Parse an event specification; its `inputs` are the topics and its `fields` the data
+fn parse_event(spec: &RawContractSpec) -> ContractEvent {
+    let to_param = |name: &str, value: &RawTypeValue, doc: &Option<String>| FunctionParam {
+        name: name.to_string(),
+        param_type: parse_type_value(value),
+        doc: doc.clone(),
+    };
+    ContractEvent {
+        name: spec.name.clone(),
+        topics: spec
+            .inputs
+            .iter()
+            .map(|t| to_param(&t.name, &t.value, &t.doc))
+            .collect(),
+        data: spec
+            .fields
+            .iter()
+            .map(|f| to_param(&f.name, &f.value, &f.doc))
+            .collect(),
+        doc: spec.doc.clone(),
+    }
+}
+
 /// Parse a raw type value into SorobanType
 fn parse_type_value(value: &RawTypeValue) -> SorobanType {
     match value.type_name.to_lowercase().as_str() {
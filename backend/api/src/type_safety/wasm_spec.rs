@@ -0,0 +1,377 @@
+//! Contract specs embedded in WASM binaries
+//!
+//! Soroban contracts carry their interface as XDR `ScSpecEntry` values in the
+//! `contractspecv0` custom section. This decodes them into the same
+//! `RawContractSpec` JSON that publishers upload as the ABI.
+
+use super::parser::{
+    ParseError, RawContractSpec, RawEnumCase, RawFieldSpec, RawInputSpec, RawOutputSpec,
+    RawTypeValue,
+};
+use crate::publish_hooks::WasmModule;
+
+const SPEC_SECTION: &str = "contractspecv0";
+
+/// Big-endian XDR cursor; every read fails cleanly on truncated input
+struct XdrReader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> XdrReader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], ParseError> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|end| *end <= self.bytes.len())
+            .ok_or_else(|| ParseError::new("unexpected end of contract spec"))?;
+        let slice = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(slice)
+    }
+
+    fn u32(&mut self) -> Result<u32, ParseError> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    /// Strings are length-prefixed and padded to four bytes
+    fn string(&mut self) -> Result<String, ParseError> {
+        let len = self.u32()? as usize;
+        let bytes = self.take(len)?;
+        self.take((4 - len % 4) % 4)?;
+        String::from_utf8(bytes.to_vec())
+            .map_err(|_| ParseError::new("contract spec string is not UTF-8"))
+    }
+
+    fn doc(&mut self) -> Result<Option<String>, ParseError> {
+        Ok(Some(self.string()?).filter(|doc| !doc.is_empty()))
+    }
+
+    fn array<T>(
+        &mut self,
+        mut item: impl FnMut(&mut Self) -> Result<T, ParseError>,
+    ) -> Result<Vec<T>, ParseError> {
+        let count = self.u32()? as usize;
+        // Every element takes at least four bytes, which bounds bogus counts
+        if count > (self.bytes.len() - self.pos) / 4 {
+            return Err(ParseError::new(
+                "contract spec array is longer than the section",
+            ));
+        }
+        (0..count).map(|_| item(self)).collect()
+    }
+
+    fn type_value(&mut self) -> Result<RawTypeValue, ParseError> {
+        let simple = |name: &str| RawTypeValue {
+            type_name: name.to_string(),
+            element: None,
+            key: None,
+            val: None,
+            n: None,
+        };
+        let kind = self.u32()?;
+        Ok(match kind {
+            0 => simple("val"),
+            1 => simple("bool"),
+            2 => simple("void"),
+            3 => simple("error"),
+            4 => simple("u32"),
+            5 => simple("i32"),
+            6 => simple("u64"),
+            7 => simple("i64"),
+            8 => simple("timepoint"),
+            9 => simple("duration"),
+            10 => simple("u128"),
+            11 => simple("i128"),
+            12 => simple("u256"),
+            13 => simple("i256"),
+            14 => simple("bytes"),
+            16 => simple("string"),
+            17 => simple("symbol"),
+            19 => simple("address"),
+            20 => simple("muxed_address"),
+            1000 => RawTypeValue {
+                element: Some(Box::new(self.type_value()?)),
+                ..simple("option")
+            },
+            1001 => RawTypeValue {
+                element: Some(Box::new(self.type_value()?)),
+                val: Some(Box::new(self.type_value()?)),
+                ..simple("result")
+            },
+            1002 => RawTypeValue {
+                element: Some(Box::new(self.type_value()?)),
+                ..simple("vec")
+            },
+            1004 => RawTypeValue {
+                key: Some(Box::new(self.type_value()?)),
+                val: Some(Box::new(self.type_value()?)),
+                ..simple("map")
+            },
+            1005 => {
+                let items = self.array(Self::type_value)?;
+                simple(&format!(
+                    "({})",
+                    items.iter().map(type_string).collect::<Vec<_>>().join(", ")
+                ))
+            }
+            1006 => RawTypeValue {
+                n: Some(self.u32()?),
+                ..simple("bytesN")
+            },
+            2000 => simple(&self.string()?),
+            other => {
+                return Err(ParseError::new(format!(
+                    "unknown contract spec type {}",
+                    other
+                )))
+            }
+        })
+    }
+
+    fn field(&mut self) -> Result<RawFieldSpec, ParseError> {
+        let doc = self.doc()?;
+        let name = self.string()?;
+        Ok(RawFieldSpec {
+            name,
+            value: self.type_value()?,
+            doc,
+        })
+    }
+
+    fn entry(&mut self) -> Result<RawContractSpec, ParseError> {
+        let kind = self.u32()?;
+        let doc = self.doc()?;
+        let mut spec = RawContractSpec {
+            spec_type: String::new(),
+            name: String::new(),
+            inputs: Vec::new(),
+            outputs: Vec::new(),
+            doc,
+            fields: Vec::new(),
+            cases: Vec::new(),
+        };
+        match kind {
+            // FunctionV0
+            0 => {
+                spec.spec_type = "function".to_string();
+                spec.name = self.string()?;
+                spec.inputs = self.array(|r| {
+                    let field = r.field()?;
+                    Ok(RawInputSpec {
+                        name: field.name,
+                        value: field.value,
+                        doc: field.doc,
+                    })
+                })?;
+                spec.outputs = self.array(|r| {
+                    Ok(RawOutputSpec {
+                        type_name: type_string(&r.type_value()?),
+                    })
+                })?;
+            }
+            // UdtStructV0
+            1 => {
+                self.string()?;
+                spec.spec_type = "struct".to_string();
+                spec.name = self.string()?;
+                spec.fields = self.array(Self::field)?;
+            }
+            // UdtUnionV0: void cases, or tuple cases whose fields are positional
+            2 => {
+                self.string()?;
+                spec.spec_type = "union".to_string();
+                spec.name = self.string()?;
+                spec.cases = self.array(|r| {
+                    let tuple = r.u32()? == 1;
+                    let doc = r.doc()?;
+                    let name = r.string()?;
+                    let fields = if tuple {
+                        r.array(Self::type_value)?
+                            .into_iter()
+                            .enumerate()
+                            .map(|(i, value)| RawFieldSpec {
+                                name: i.to_string(),
+                                value,
+                                doc: None,
+                            })
+                            .collect()
+                    } else {
+                        Vec::new()
+                    };
+                    Ok(RawEnumCase {
+                        name,
+                        value: None,
+                        fields,
+                        doc,
+                    })
+                })?;
+            }
+            // UdtEnumV0, UdtErrorEnumV0
+            3 | 4 => {
+                self.string()?;
+                spec.spec_type = if kind == 3 { "enum" } else { "error_enum" }.to_string();
+                spec.name = self.string()?;
+                spec.cases = self.array(|r| {
+                    let doc = r.doc()?;
+                    let name = r.string()?;
+                    Ok(RawEnumCase {
+                        name,
+                        value: Some(r.u32()?),
+                        fields: Vec::new(),
+                        doc,
+                    })
+                })?;
+            }
+            // EventV0: topic params become inputs, data params fields
+            5 => {
+                self.string()?;
+                spec.spec_type = "event".to_string();
+                spec.name = self.string()?;
+                self.array(Self::string)?;
+                for (field, in_topics) in self.array(|r| {
+                    let field = r.field()?;
+                    Ok((field, r.u32()? == 1))
+                })? {
+                    if in_topics {
+                        spec.inputs.push(RawInputSpec {
+                            name: field.name,
+                            value: field.value,
+                            doc: field.doc,
+                        });
+                    } else {
+                        spec.fields.push(field);
+                    }
+                }
+                self.u32()?;
+            }
+            other => {
+                return Err(ParseError::new(format!(
+                    "unknown contract spec entry {}",
+                    other
+                )))
+            }
+        }
+        Ok(spec)
+    }
+}
+
+/// Type as written in function outputs, e.g. `Option<Vec<u32>>`
+fn type_string(value: &RawTypeValue) -> String {
+    let inner = |v: &Option<Box<RawTypeValue>>| v.as_deref().map(type_string).unwrap_or_default();
+    match value.type_name.as_str() {
+        "option" => format!("Option<{}>", inner(&value.element)),
+        "vec" => format!("Vec<{}>", inner(&value.element)),
+        "result" => format!("Result<{}, {}>", inner(&value.element), inner(&value.val)),
+        "map" => format!("Map<{}, {}>", inner(&value.key), inner(&value.val)),
+        "bytesN" => format!("BytesN<{}>", value.n.unwrap_or(32)),
+        other => other.to_string(),
+    }
+}
+
+/// Decode a binary's embedded spec; `None` when it has no spec section
+pub fn extract_wasm_spec(wasm: &[u8]) -> Result<Option<Vec<RawContractSpec>>, ParseError> {
+    let section = WasmModule::custom_section(wasm, SPEC_SECTION).map_err(ParseError::new)?;
+    let Some(section) = section else {
+        return Ok(None);
+    };
+    let mut reader = XdrReader {
+        bytes: section,
+        pos: 0,
+    };
+    let mut specs = Vec::new();
+    while reader.pos < section.len() {
+        specs.push(reader.entry()?);
+    }
+    Ok(Some(specs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn xdr_string(out: &mut Vec<u8>, s: &str) {
+        out.extend((s.len() as u32).to_be_bytes());
+        out.extend(s.as_bytes());
+        out.extend(std::iter::repeat_n(0, (4 - s.len() % 4) % 4));
+    }
+
+    fn xdr_u32(out: &mut Vec<u8>, n: u32) {
+        out.extend(n.to_be_bytes());
+    }
+
+    /// `fn balance(id: Address) -> Option<i128>` and an error enum
+    fn spec_section() -> Vec<u8> {
+        let mut spec = Vec::new();
+        xdr_u32(&mut spec, 0);
+        xdr_string(&mut spec, "Balance of an account");
+        xdr_string(&mut spec, "balance");
+        xdr_u32(&mut spec, 1);
+        xdr_string(&mut spec, "");
+        xdr_string(&mut spec, "id");
+        xdr_u32(&mut spec, 19);
+        xdr_u32(&mut spec, 1);
+        xdr_u32(&mut spec, 1000);
+        xdr_u32(&mut spec, 11);
+
+        xdr_u32(&mut spec, 4);
+        xdr_string(&mut spec, "");
+        xdr_string(&mut spec, "");
+        xdr_string(&mut spec, "Error");
+        xdr_u32(&mut spec, 1);
+        xdr_string(&mut spec, "");
+        xdr_string(&mut spec, "NotFound");
+        xdr_u32(&mut spec, 1);
+        spec
+    }
+
+    fn module_with(section: &[u8]) -> Vec<u8> {
+        let mut name = Vec::new();
+        name.push(SPEC_SECTION.len() as u8);
+        name.extend(SPEC_SECTION.as_bytes());
+        let len = name.len() + section.len();
+        let mut wasm = b"\0asm\x01\0\0\0".to_vec();
+        wasm.push(0);
+        // LEB128 section length
+        let mut remaining = len;
+        loop {
+            let byte = (remaining & 0x7f) as u8;
+            remaining >>= 7;
+            if remaining == 0 {
+                wasm.push(byte);
+                break;
+            }
+            wasm.push(byte | 0x80);
+        }
+        wasm.extend(name);
+        wasm.extend(section);
+        wasm
+    }
+
+    #[test]
+    fn decodes_functions_and_error_enums() {
+        let specs = extract_wasm_spec(&module_with(&spec_section()))
+            .unwrap()
+            .unwrap();
+        assert_eq!(specs.len(), 2);
+        assert_eq!(specs[0].spec_type, "function");
+        assert_eq!(specs[0].name, "balance");
+        assert_eq!(specs[0].doc.as_deref(), Some("Balance of an account"));
+        assert_eq!(specs[0].inputs[0].value.type_name, "address");
+        assert_eq!(specs[0].outputs[0].type_name, "Option<i128>");
+        assert_eq!(specs[1].spec_type, "error_enum");
+        assert_eq!(specs[1].cases[0].value, Some(1));
+
+        let json = serde_json::to_string(&specs).unwrap();
+        let abi = super::super::parse_json_spec(&json, "token").unwrap();
+        assert_eq!(abi.errors.len(), 1);
+    }
+
+    #[test]
+    fn modules_without_a_spec_have_none() {
+        assert!(extract_wasm_spec(b"\0asm\x01\0\0\0").unwrap().is_none());
+        let section = spec_section();
+        assert!(extract_wasm_spec(&module_with(&section[..section.len() - 2])).is_err());
+    }
+}
@@ -0,0 +1,168 @@
+// user_auth.rs
+// Extractor for endpoints scoped to a signed-in wallet: a session JWT from
+// /api/auth/verify or a scoped API key from /api/tokens.
+
+use async_trait::async_trait;
+use axum::{
+    extract::FromRequestParts,
+    http::{header, request::Parts, HeaderMap, StatusCode},
+};
+use sha2::{Digest, Sha256};
+use shared::ApiScope;
+
+use crate::error::{ApiError, ApiResult};
+use crate::handlers::db_internal_error;
+use crate::state::AppState;
+use crate::tenancy::CurrentTenant;
+
+/// Prefix that distinguishes API keys from session JWTs
+pub const API_KEY_PREFIX: &str = "srk_";
+
+/// The Stellar address a request is authenticated as
+#[derive(Debug, Clone)]
+pub struct AuthUser {
+    pub address: String,
+    /// Scopes of the API key used; `None` for a session token, which may do anything
+    pub scopes: Option<Vec<ApiScope>>,
+}
+
+impl AuthUser {
+    /// Reject API keys that were not granted `scope`
+    pub fn require(&self, scope: ApiScope) -> ApiResult<()> {
+        match &self.scopes {
+            Some(scopes) if !scopes.contains(&scope) => Err(ApiError::new(
+                StatusCode::FORBIDDEN,
+                "InsufficientScope",
+                format!("This API key lacks the '{}' scope", scope),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Reject API keys outright, for endpoints that manage the keys themselves
+    /// or the account as a whole
+    pub fn require_session(&self) -> ApiResult<()> {
+        if self.scopes.is_some() {
+            return Err(ApiError::new(
+                StatusCode::FORBIDDEN,
+                "InsufficientScope",
+                "API keys cannot be used here; sign in with /api/auth/challenge",
+            ));
+        }
+        Ok(())
+    }
+}
+
+fn unauthorized(message: &str) -> ApiError {
+    ApiError::new(StatusCode::UNAUTHORIZED, "Unauthorized", message)
+}
+
+/// Hex SHA-256 of an API key, as stored in `api_keys.key_hash`
+pub fn hash_api_key(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
+
+/// Resolve the bearer token in `headers`, or `None` when there is no bearer token.
+/// Keys and session tokens only work in the tenant that issued them.
+pub async fn authenticate(
+    state: &AppState,
+    headers: &HeaderMap,
+    tenant: &CurrentTenant,
+) -> ApiResult<Option<AuthUser>> {
+    let Some(token) = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .map(str::trim)
+    else {
+        return Ok(None);
+    };
+
+    if token.starts_with(API_KEY_PREFIX) {
+        let row: Option<(String, Vec<String>)> = sqlx::query_as(
+            "UPDATE api_keys SET last_used_at = NOW() \
+             WHERE key_hash = $1 AND tenant_id = $2 AND revoked_at IS NULL \
+               AND (expires_at IS NULL OR expires_at > NOW()) \
+             RETURNING owner_address, scopes",
+        )
+        .bind(hash_api_key(token))
+        .bind(tenant.id)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(|err| db_internal_error("authenticate api key", err))?;
+        let (address, scopes) =
+            row.ok_or_else(|| unauthorized("The API key is invalid, revoked or expired"))?;
+        return Ok(Some(AuthUser {
+            address,
+            scopes: Some(scopes.iter().filter_map(|s| ApiScope::parse(s)).collect()),
+        }));
+    }
+
+    let claims = state
+        .auth_mgr
+        .read()
+        .map_err(|_| ApiError::internal("Authentication is unavailable"))?
+        .validate_jwt(token)
+        .map_err(|_| unauthorized("The bearer token is invalid or has expired"))?;
+    if claims.realm != tenant.slug {
+        return Err(unauthorized(
+            "The bearer token was issued by a different registry",
+        ));
+    }
+    Ok(Some(AuthUser {
+        address: claims.sub,
+        scopes: None,
+    }))
+}
+
+/// For endpoints that still accept anonymous callers: when a bearer token is
+/// sent it must be valid and carry `scope`.
+pub async fn check_optional_scope(
+    state: &AppState,
+    headers: &HeaderMap,
+    tenant: &CurrentTenant,
+    scope: ApiScope,
+) -> ApiResult<()> {
+    match authenticate(state, headers, tenant).await? {
+        Some(user) => user.require(scope),
+        None => Ok(()),
+    }
+}
+
+#[async_trait]
+impl FromRequestParts<AppState> for AuthUser {
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &AppState) -> Result<Self, ApiError> {
+        let tenant = CurrentTenant::of(&parts.extensions);
+        authenticate(state, &parts.headers, &tenant)
+            .await?
+            .ok_or_else(|| {
+                unauthorized(
+                    "Sign in with /api/auth/challenge and send the token as a Bearer header",
+                )
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn user(scopes: Option<Vec<ApiScope>>) -> AuthUser {
+        AuthUser {
+            address: "GABC".to_string(),
+            scopes,
+        }
+    }
+
+    #[test]
+    fn sessions_have_every_scope_and_keys_only_their_own() {
+        assert!(user(None).require(ApiScope::PatchAdmin).is_ok());
+        let key = user(Some(vec![ApiScope::Read]));
+        assert!(key.require(ApiScope::Read).is_ok());
+        assert!(key.require(ApiScope::Publish).is_err());
+        assert!(key.require_session().is_err());
+        assert!(user(None).require_session().is_ok());
+    }
+}
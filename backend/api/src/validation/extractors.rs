@@ -9,9 +9,8 @@ use axum::{
     http::StatusCode,
     Json,
 };
-use chrono::{SecondsFormat, Utc};
 use serde::{de::DeserializeOwned, Serialize};
-use uuid::Uuid;
+use shared::{ErrorCode, FieldProblem, ProblemDetails};
 
 /// A field-level validation error
 #[derive(Debug, Clone, Serialize)]
@@ -29,34 +28,27 @@ impl FieldError {
     }
 }
 
-/// Validation error response body
-#[derive(Debug, Serialize)]
-pub struct ValidationErrorResponse {
-    pub error: String,
-    pub message: String,
-    pub errors: Vec<FieldError>,
-    pub code: u16,
-    pub timestamp: String,
-    pub correlation_id: String,
-}
-
-impl ValidationErrorResponse {
-    pub fn new(errors: Vec<FieldError>) -> Self {
-        let error_summary = if errors.len() == 1 {
-            format!("Validation failed for field '{}'", errors[0].field)
-        } else {
-            format!("Validation failed for {} fields", errors.len())
-        };
-
-        Self {
-            error: "ValidationError".to_string(),
-            message: error_summary,
-            errors,
-            code: 400,
-            timestamp: Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true),
-            correlation_id: Uuid::new_v4().to_string(),
-        }
-    }
+/// Build the problem+json body for a set of field errors
+pub fn validation_problem(errors: Vec<FieldError>) -> ProblemDetails {
+    let error_summary = if errors.len() == 1 {
+        format!("Validation failed for field '{}'", errors[0].field)
+    } else {
+        format!("Validation failed for {} fields", errors.len())
+    };
+
+    let mut problem = ProblemDetails::new(
+        StatusCode::BAD_REQUEST.as_u16(),
+        ErrorCode::ValidationError,
+        error_summary,
+    );
+    problem.errors = errors
+        .into_iter()
+        .map(|e| FieldProblem {
+            field: e.field,
+            message: e.message,
+        })
+        .collect();
+    problem
 }
 
 /// Validation error that converts to an HTTP response
@@ -79,8 +71,7 @@ impl ValidationError {
 
 impl axum::response::IntoResponse for ValidationError {
     fn into_response(self) -> axum::response::Response {
-        let response = ValidationErrorResponse::new(self.errors);
-        (StatusCode::BAD_REQUEST, Json(response)).into_response()
+        crate::error::problem_response(StatusCode::BAD_REQUEST, validation_problem(self.errors))
     }
 }
 
@@ -273,19 +264,19 @@ mod tests {
             FieldError::new("name", "must be at least 1 character"),
         ];
 
-        let response = ValidationErrorResponse::new(errors);
+        let response = validation_problem(errors);
 
-        assert_eq!(response.error, "ValidationError");
-        assert_eq!(response.code, 400);
+        assert_eq!(response.code, ErrorCode::ValidationError);
+        assert_eq!(response.status, 400);
         assert_eq!(response.errors.len(), 2);
-        assert!(response.message.contains("2 fields"));
+        assert!(response.detail.contains("2 fields"));
     }
 
     #[test]
     fn test_single_error_response() {
         let errors = vec![FieldError::new("name", "is required")];
-        let response = ValidationErrorResponse::new(errors);
+        let response = validation_problem(errors);
 
-        assert!(response.message.contains("field 'name'"));
+        assert!(response.detail.contains("field 'name'"));
     }
 }
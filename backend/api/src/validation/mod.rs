@@ -41,17 +41,20 @@
 //!
 //! ## Validation Error Response
 //!
-//! When validation fails, a 400 Bad Request is returned:
+//! When validation fails, a 400 Bad Request is returned as
+//! `application/problem+json` (RFC 7807):
 //!
 //! ```json
 //! {
-//!   "error": "ValidationError",
-//!   "message": "Validation failed for 2 fields",
+//!   "type": "https://soroban-registry.dev/problems/validation-error",
+//!   "title": "Request validation failed",
+//!   "status": 400,
+//!   "detail": "Validation failed for 2 fields",
+//!   "code": "ValidationError",
 //!   "errors": [
 //!     {"field": "contract_id", "message": "must be a valid Stellar contract ID"},
 //!     {"field": "name", "message": "must be at least 1 character"}
 //!   ],
-//!   "code": 400,
 //!   "timestamp": "2026-02-20T10:30:00Z",
 //!   "correlation_id": "uuid-here"
 //! }
@@ -8,6 +8,8 @@ use shared::models::{
     VerifyRequest,
 };
 
+use crate::handlers::UpdateContractMetadataRequest;
+
 use super::extractors::{FieldError, Validatable, ValidationBuilder};
 use super::sanitizers::{
     normalize_contract_id, normalize_stellar_address, sanitize_description_optional, sanitize_name,
@@ -15,29 +17,19 @@ use super::sanitizers::{
 };
 use super::validators::{
     validate_contract_id, validate_json_depth, validate_length, validate_no_xss, validate_semver,
-    validate_source_code_size, validate_stellar_address, validate_tags, validate_url_optional,
+    validate_source_code_size, validate_stellar_address, validate_url,
 };
 
 // ─────────────────────────────────────────────────────────────────────────────
 // Constants for validation rules
 // ─────────────────────────────────────────────────────────────────────────────
 
-/// Maximum length for contract name
-const MAX_NAME_LENGTH: usize = 255;
-/// Minimum length for contract name
-const MIN_NAME_LENGTH: usize = 1;
-/// Maximum length for description
-const MAX_DESCRIPTION_LENGTH: usize = 5000;
-/// Maximum number of tags allowed
-const MAX_TAGS_COUNT: usize = 10;
-/// Maximum length for each tag
-const MAX_TAG_LENGTH: usize = 50;
 /// Maximum source code size (1 MB)
 const MAX_SOURCE_CODE_BYTES: usize = 1024 * 1024;
 /// Maximum JSON nesting depth
 const MAX_JSON_DEPTH: usize = 10;
-/// Maximum length for category
-const MAX_CATEGORY_LENGTH: usize = 100;
+/// Maximum length for uploaded license text
+const MAX_LICENSE_TEXT_LENGTH: usize = 100_000;
 /// Maximum length for wasm hash
 const MAX_WASM_HASH_LENGTH: usize = 64;
 /// Maximum length for dependency name
@@ -46,6 +38,12 @@ const MAX_DEPENDENCY_NAME_LENGTH: usize = 255;
 const MAX_VERSION_CONSTRAINT_LENGTH: usize = 100;
 /// Maximum number of dependencies
 const MAX_DEPENDENCIES_COUNT: usize = 50;
+/// Maximum number of project links
+const MAX_LINKS_COUNT: usize = 10;
+/// Maximum length for a link label
+const MAX_LINK_LABEL_LENGTH: usize = 50;
+/// Maximum length for a contract README (200 KB)
+const MAX_README_LENGTH: usize = 200 * 1024;
 
 // ─────────────────────────────────────────────────────────────────────────────
 // PublishRequest validation
@@ -79,6 +77,19 @@ impl Validatable for PublishRequest {
         // Sanitize tags
         self.tags = sanitize_tags(&self.tags);
 
+        // Sanitize project URLs
+        sanitize_url_optional(&mut self.urls.repository_url);
+        sanitize_url_optional(&mut self.urls.homepage);
+        sanitize_url_optional(&mut self.urls.documentation_url);
+
+        // Sanitize fork reference
+        if let Some(ref mut upstream) = self.forked_from {
+            *upstream = trim(upstream);
+            if upstream.is_empty() {
+                self.forked_from = None;
+            }
+        }
+
         // Sanitize dependencies
         for dep in &mut self.dependencies {
             dep.name = trim(&dep.name);
@@ -92,23 +103,10 @@ impl Validatable for PublishRequest {
         // contract_id: required, valid Stellar contract ID format
         builder.check("contract_id", || validate_contract_id(&self.contract_id));
 
-        // name: required, 1-255 characters
-        builder.check("name", || {
-            if self.name.is_empty() {
-                return Err("name is required".to_string());
-            }
-            validate_length(&self.name, MIN_NAME_LENGTH, MAX_NAME_LENGTH)
-        });
-
-        // name: no XSS patterns
-        builder.check("name", || validate_no_xss(&self.name));
-
-        // description: optional, max 5000 characters
-        if let Some(ref desc) = self.description {
-            builder.check("description", || {
-                validate_length(desc, 0, MAX_DESCRIPTION_LENGTH)
-            });
-            builder.check("description", || validate_no_xss(desc));
+        // name, description, category, tags, license and URLs: the rules the
+        // CLI also checks before sending
+        for issue in self.metadata().validate().issues {
+            builder.add_error(issue.field, issue.message);
         }
 
         // publisher_address: required, valid Stellar address
@@ -116,20 +114,13 @@ impl Validatable for PublishRequest {
             validate_stellar_address(&self.publisher_address)
         });
 
-        // source_url: optional, valid URL format
-        builder.check("source_url", || validate_url_optional(&self.source_url));
-
-        // category: optional, max length
-        if let Some(ref cat) = self.category {
-            builder.check("category", || validate_length(cat, 1, MAX_CATEGORY_LENGTH));
-            builder.check("category", || validate_no_xss(cat));
+        // license_file: optional, bounded size
+        if let Some(ref text) = self.license_file {
+            builder.check("license_file", || {
+                validate_length(text, 1, MAX_LICENSE_TEXT_LENGTH)
+            });
         }
 
-        // tags: max count, each max length
-        builder.check("tags", || {
-            validate_tags(&self.tags, MAX_TAGS_COUNT, MAX_TAG_LENGTH)
-        });
-
         // dependencies: validate each
         builder.check("dependencies", || {
             if self.dependencies.len() > MAX_DEPENDENCIES_COUNT {
@@ -298,6 +289,82 @@ impl Validatable for DependencyDeclaration {
     }
 }
 
+// ─────────────────────────────────────────────────────────────────────────────
+// UpdateContractMetadataRequest validation
+// ─────────────────────────────────────────────────────────────────────────────
+
+impl Validatable for UpdateContractMetadataRequest {
+    fn sanitize(&mut self) {
+        if let Some(ref mut name) = self.name {
+            *name = sanitize_name(name);
+        }
+        sanitize_description_optional(&mut self.description);
+        if let Some(ref mut cat) = self.category {
+            *cat = trim(cat);
+        }
+        if let Some(ref mut tags) = self.tags {
+            *tags = sanitize_tags(tags);
+        }
+        if let Some(ref mut links) = self.links {
+            *links = std::mem::take(links)
+                .into_iter()
+                .map(|(label, url)| (sanitize_name(&label), trim(&url)))
+                .collect();
+        }
+        if let Some(ref mut readme) = self.readme {
+            // Markdown is kept as written; only surrounding whitespace goes
+            *readme = trim(readme);
+        }
+    }
+
+    fn validate(&self) -> Result<(), Vec<FieldError>> {
+        let mut builder = ValidationBuilder::new();
+
+        if let Some(ref name) = self.name {
+            builder.check("name", || shared::validate_contract_name(name));
+        }
+
+        if let Some(ref desc) = self.description {
+            builder.check("description", || shared::validate_description(desc));
+        }
+
+        if let Some(ref cat) = self.category {
+            builder.check("category", || shared::validate_category(cat));
+        }
+
+        if let Some(ref tags) = self.tags {
+            builder.check("tags", || shared::validate_contract_tags(tags));
+        }
+
+        if let Some(ref links) = self.links {
+            builder.check_condition(
+                links.len() > MAX_LINKS_COUNT,
+                "links",
+                format!("at most {} links are allowed", MAX_LINKS_COUNT),
+            );
+            for (label, url) in links {
+                let field_name = format!("links.{}", label);
+                builder.check(&field_name, || {
+                    validate_length(label, 1, MAX_LINK_LABEL_LENGTH)
+                });
+                builder.check(&field_name, || {
+                    if url.is_empty() {
+                        return Err("URL is required".to_string());
+                    }
+                    validate_url(url)
+                });
+            }
+        }
+
+        if let Some(ref readme) = self.readme {
+            builder.check("readme", || validate_length(readme, 0, MAX_README_LENGTH));
+            builder.check("readme", || validate_no_xss(readme));
+        }
+
+        builder.build()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -323,6 +390,10 @@ mod tests {
             source_url: Some("https://github.com/user/repo".to_string()),
             publisher_address: valid_stellar_address(),
             dependencies: vec![],
+            license: None,
+            license_file: None,
+            urls: Default::default(),
+            forked_from: None,
         };
 
         assert!(req.validate().is_ok());
@@ -340,6 +411,10 @@ mod tests {
             source_url: None,
             publisher_address: valid_stellar_address(),
             dependencies: vec![],
+            license: None,
+            license_file: None,
+            urls: Default::default(),
+            forked_from: None,
         };
 
         let result = req.validate();
@@ -348,6 +423,62 @@ mod tests {
         assert!(errors.iter().any(|e| e.field == "contract_id"));
     }
 
+    #[test]
+    fn test_publish_request_invalid_license() {
+        let req = PublishRequest {
+            contract_id: valid_contract_id(),
+            name: "My Contract".to_string(),
+            description: None,
+            network: Network::Testnet,
+            category: None,
+            tags: vec![],
+            source_url: None,
+            publisher_address: valid_stellar_address(),
+            dependencies: vec![],
+            license: Some("MIT OR Bogus-1.0".to_string()),
+            license_file: None,
+            urls: Default::default(),
+            forked_from: None,
+        };
+
+        let result = req.validate();
+        assert!(result.is_err());
+        let errors = result.unwrap_err();
+        assert!(errors.iter().any(|e| e.field == "license"));
+    }
+
+    #[test]
+    fn test_publish_request_project_url_schemes() {
+        let mut req = PublishRequest {
+            contract_id: valid_contract_id(),
+            name: "My Contract".to_string(),
+            description: None,
+            network: Network::Testnet,
+            category: None,
+            tags: vec![],
+            source_url: None,
+            publisher_address: valid_stellar_address(),
+            dependencies: vec![],
+            license: None,
+            license_file: None,
+            urls: shared::models::ProjectUrls {
+                repository_url: Some(" https://github.com/alice/amm ".to_string()),
+                homepage: Some("javascript:alert(1)".to_string()),
+                documentation_url: Some("https://".to_string()),
+            },
+            forked_from: None,
+        };
+        req.sanitize();
+
+        let errors = req.validate().unwrap_err();
+        let fields: Vec<&str> = errors.iter().map(|e| e.field.as_str()).collect();
+        assert_eq!(fields, vec!["homepage", "documentation_url"]);
+        assert_eq!(
+            req.urls.repository_url.as_deref(),
+            Some("https://github.com/alice/amm")
+        );
+    }
+
     #[test]
     fn test_publish_request_empty_name() {
         let req = PublishRequest {
@@ -360,6 +491,10 @@ mod tests {
             source_url: None,
             publisher_address: valid_stellar_address(),
             dependencies: vec![],
+            license: None,
+            license_file: None,
+            urls: Default::default(),
+            forked_from: None,
         };
 
         let result = req.validate();
@@ -368,6 +503,56 @@ mod tests {
         assert!(errors.iter().any(|e| e.field == "name"));
     }
 
+    #[test]
+    fn test_publish_request_namespaced_name() {
+        let mut req = PublishRequest {
+            contract_id: valid_contract_id(),
+            name: "@alice/token".to_string(),
+            description: None,
+            network: Network::Testnet,
+            category: None,
+            tags: vec![],
+            source_url: None,
+            publisher_address: valid_stellar_address(),
+            dependencies: vec![],
+            license: None,
+            license_file: None,
+            urls: Default::default(),
+            forked_from: None,
+        };
+        assert!(req.validate().is_ok());
+
+        req.name = "@Alice/".to_string();
+        let errors = req.validate().unwrap_err();
+        assert!(errors.iter().any(|e| e.field == "name"));
+    }
+
+    #[test]
+    fn test_publish_request_uses_shared_metadata_rules() {
+        let mut req = PublishRequest {
+            contract_id: valid_contract_id(),
+            name: "My Contract".to_string(),
+            description: None,
+            network: Network::Testnet,
+            category: Some("memes".to_string()),
+            tags: vec!["two words".to_string()],
+            source_url: None,
+            publisher_address: valid_stellar_address(),
+            dependencies: vec![],
+            license: None,
+            license_file: None,
+            urls: Default::default(),
+            forked_from: None,
+        };
+        let errors = req.validate().unwrap_err();
+        let fields: Vec<&str> = errors.iter().map(|e| e.field.as_str()).collect();
+        assert_eq!(fields, vec!["category", "tags"]);
+
+        req.category = Some("Lending".to_string());
+        req.tags = vec!["standard:sep-41".to_string()];
+        assert!(req.validate().is_ok());
+    }
+
     #[test]
     fn test_publish_request_sanitization() {
         let mut req = PublishRequest {
@@ -381,6 +566,10 @@ mod tests {
             publisher_address: "  gdlzfc3syjydzt7k67vz75hpjvieuvnixf47zg2fb2rmqqvu2hhgcysc  "
                 .to_string(),
             dependencies: vec![],
+            license: None,
+            license_file: None,
+            urls: Default::default(),
+            forked_from: None,
         };
 
         req.sanitize();
@@ -464,6 +653,10 @@ mod tests {
             source_url: None,
             publisher_address: valid_stellar_address(),
             dependencies: vec![],
+            license: None,
+            license_file: None,
+            urls: Default::default(),
+            forked_from: None,
         };
 
         let result = req.validate();
@@ -471,4 +664,52 @@ mod tests {
         let errors = result.unwrap_err();
         assert!(errors.iter().any(|e| e.field == "tags"));
     }
+
+    fn metadata_update() -> UpdateContractMetadataRequest {
+        UpdateContractMetadataRequest {
+            name: None,
+            description: None,
+            category: None,
+            tags: None,
+            links: None,
+            readme: None,
+            user_id: None,
+        }
+    }
+
+    #[test]
+    fn test_metadata_update_reports_each_invalid_field() {
+        let mut req = metadata_update();
+        req.category = Some("   ".to_string());
+        req.links = Some(
+            [
+                ("homepage".to_string(), "ftp://example.com".to_string()),
+                ("docs".to_string(), "https://docs.example.com".to_string()),
+            ]
+            .into_iter()
+            .collect(),
+        );
+        req.readme = Some("# AMM\n<script>alert(1)</script>".to_string());
+        req.sanitize();
+
+        let errors = req.validate().unwrap_err();
+        let fields: Vec<&str> = errors.iter().map(|e| e.field.as_str()).collect();
+        assert_eq!(fields, vec!["category", "links.homepage", "readme"]);
+    }
+
+    #[test]
+    fn test_metadata_update_sanitizes_links_and_readme() {
+        let mut req = metadata_update();
+        req.links = Some(
+            [(" repo ".to_string(), " https://github.com/a/b ".to_string())]
+                .into_iter()
+                .collect(),
+        );
+        req.readme = Some("\n# AMM\n\nSwap tokens.\n\n".to_string());
+        req.sanitize();
+
+        assert!(req.validate().is_ok());
+        assert_eq!(req.links.unwrap()["repo"], "https://github.com/a/b");
+        assert_eq!(req.readme.unwrap(), "# AMM\n\nSwap tokens.");
+    }
 }
@@ -0,0 +1,312 @@
+// web_ui.rs
+// Built-in web frontend: server-rendered contract browsing, search and detail
+// pages under `/`, so a registry is usable without deploying `frontend/`.
+// Pages read through `RegistryRepository`, so lite mode serves them too. ABIs are
+// rendered with `shared::format_abi`, the same renderer as `info --abi` and docs.
+
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::{Html, IntoResponse, Response},
+    routing::get,
+    Router,
+};
+use serde::Deserialize;
+use shared::repository::{ContractFilter, RegistryRepository};
+use shared::{AbiSyntax, Contract, ContractVersion, Network};
+use uuid::Uuid;
+
+use crate::tenancy::CurrentTenant;
+
+type Repository = Arc<dyn RegistryRepository>;
+
+const PAGE_SIZE: i64 = 25;
+
+const STYLE: &str = "body{font-family:system-ui,sans-serif;max-width:60rem;margin:0 auto;padding:1rem;color:#1f2328}\
+a{color:#0969da;text-decoration:none}a:hover{text-decoration:underline}\
+header{display:flex;gap:1rem;align-items:center;border-bottom:1px solid #d0d7de;padding-bottom:.75rem}\
+header form{margin-left:auto}input,select,button{font:inherit;padding:.25rem .5rem}\
+table{border-collapse:collapse;width:100%}td,th{text-align:left;padding:.4rem;border-bottom:1px solid #d0d7de}\
+pre{background:#f6f8fa;padding:.75rem;overflow-x:auto}.tag{background:#ddf4ff;border-radius:1rem;padding:0 .5rem;margin-right:.25rem}\
+.muted{color:#656d76}.badge{color:#1a7f37;font-weight:600}";
+
+#[derive(Debug, Default, Deserialize)]
+pub struct BrowseQuery {
+    pub q: Option<String>,
+    pub network: Option<Network>,
+    pub page: Option<i64>,
+}
+
+/// Escape text for HTML element content and quoted attribute values
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Percent-encode a query string value
+fn encode_query(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            b' ' => "+".to_string(),
+            b => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+fn layout(title: &str, query: &str, body: &str) -> Html<String> {
+    Html(format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+         <title>{title} · Soroban Registry</title>\n<style>{STYLE}</style>\n</head>\n<body>\n\
+         <header><a href=\"/\"><strong>Soroban Registry</strong></a>\
+         <form action=\"/\" method=\"get\"><input type=\"search\" name=\"q\" value=\"{query}\" \
+         placeholder=\"Search contracts\"> <button>Search</button></form></header>\n\
+         <main>\n{body}\n</main>\n</body>\n</html>\n",
+        title = escape(title),
+        query = escape(query),
+    ))
+}
+
+fn error_page(status: StatusCode, message: &str) -> Response {
+    let body = format!("<h1>{}</h1>\n<p>{}</p>", status, escape(message));
+    (status, layout(status.as_str(), "", &body)).into_response()
+}
+
+fn repository_error(operation: &str, err: sqlx::Error) -> Response {
+    tracing::error!(operation = operation, error = ?err, "web UI query failed");
+    error_page(StatusCode::INTERNAL_SERVER_ERROR, "Something went wrong")
+}
+
+fn tags(contract: &Contract) -> String {
+    contract
+        .tags
+        .iter()
+        .map(|tag| format!("<span class=\"tag\">{}</span>", escape(tag)))
+        .collect()
+}
+
+fn contract_row(contract: &Contract) -> String {
+    format!(
+        "<tr><td><a href=\"{href}\">{name}</a>{verified}<br>\
+         <span class=\"muted\">{description}</span></td><td>{network}</td><td>{tags}</td></tr>",
+        href = shared::contract_page_path(&contract.id),
+        name = escape(&contract.name),
+        verified = if contract.is_verified {
+            " <span class=\"badge\">✓ verified</span>"
+        } else {
+            ""
+        },
+        description = escape(contract.description.as_deref().unwrap_or("")),
+        network = contract.network,
+        tags = tags(contract),
+    )
+}
+
+/// GET / — newest contracts, or search results for `?q=`
+async fn browse(
+    State(repository): State<Repository>,
+    tenant: CurrentTenant,
+    Query(params): Query<BrowseQuery>,
+) -> Response {
+    let query = params.q.unwrap_or_default();
+    let page = params.page.unwrap_or(1).max(1);
+    let filter = ContractFilter {
+        query: Some(query.trim().to_string()).filter(|q| !q.is_empty()),
+        network: params.network.clone(),
+        tenant_id: tenant.id,
+        limit: PAGE_SIZE,
+        offset: (page - 1) * PAGE_SIZE,
+        ..Default::default()
+    };
+    let (contracts, total) = match repository.list_contracts(&filter).await {
+        Ok(found) => found,
+        Err(err) => return repository_error("list contracts", err),
+    };
+
+    let heading = match &filter.query {
+        Some(q) => format!("{} result(s) for “{}”", total, escape(q)),
+        None => format!("{} contract(s)", total),
+    };
+    let mut body = format!("<h1>{}</h1>\n", heading);
+    if contracts.is_empty() {
+        body.push_str("<p class=\"muted\">No contracts found.</p>\n");
+    } else {
+        body.push_str("<table>\n<tr><th>Contract</th><th>Network</th><th>Tags</th></tr>\n");
+        for contract in &contracts {
+            body.push_str(&contract_row(contract));
+            body.push('\n');
+        }
+        body.push_str("</table>\n");
+    }
+
+    let link = |page: i64| {
+        let mut href = format!("/?page={}", page);
+        if let Some(q) = &filter.query {
+            href.push_str(&format!("&amp;q={}", encode_query(q)));
+        }
+        if let Some(network) = &filter.network {
+            href.push_str(&format!("&amp;network={}", network));
+        }
+        href
+    };
+    let mut pager = Vec::new();
+    if page > 1 {
+        pager.push(format!("<a href=\"{}\">← Newer</a>", link(page - 1)));
+    }
+    if page * PAGE_SIZE < total {
+        pager.push(format!("<a href=\"{}\">Older →</a>", link(page + 1)));
+    }
+    if !pager.is_empty() {
+        body.push_str(&format!("<p>{}</p>\n", pager.join(" · ")));
+    }
+
+    layout("Contracts", &query, &body).into_response()
+}
+
+fn details_table(contract: &Contract) -> String {
+    let mut rows = vec![
+        (
+            "Contract ID",
+            format!("<code>{}</code>", escape(&contract.contract_id)),
+        ),
+        ("Network", contract.network.to_string()),
+        (
+            "WASM hash",
+            format!("<code>{}</code>", escape(&contract.wasm_hash)),
+        ),
+    ];
+    if let Some(category) = &contract.category {
+        rows.push(("Category", escape(category)));
+    }
+    if !contract.tags.is_empty() {
+        rows.push(("Tags", tags(contract)));
+    }
+    if let Some(license) = &contract.license {
+        rows.push(("License", escape(license)));
+    }
+    for (label, url) in [
+        ("Repository", &contract.urls.repository_url),
+        ("Homepage", &contract.urls.homepage),
+        ("Documentation", &contract.urls.documentation_url),
+    ] {
+        if let Some(url) = url {
+            rows.push((label, format!("<a href=\"{0}\">{0}</a>", escape(url))));
+        }
+    }
+    let rows: String = rows
+        .into_iter()
+        .map(|(label, value)| format!("<tr><th>{}</th><td>{}</td></tr>\n", label, value))
+        .collect();
+    format!("<table>\n{}</table>\n", rows)
+}
+
+fn versions_table(versions: &[ContractVersion]) -> String {
+    let rows: String = versions
+        .iter()
+        .map(|v| {
+            format!(
+                "<tr><td>{}</td><td>{}</td><td class=\"muted\">{}</td></tr>\n",
+                escape(&v.version),
+                v.created_at.format("%Y-%m-%d"),
+                escape(v.release_notes.as_deref().unwrap_or("")),
+            )
+        })
+        .collect();
+    format!(
+        "<table>\n<tr><th>Version</th><th>Published</th><th>Notes</th></tr>\n{}</table>\n",
+        rows
+    )
+}
+
+/// GET /contracts/:id — metadata, versions, README and ABI of one contract
+async fn contract_page(
+    State(repository): State<Repository>,
+    tenant: CurrentTenant,
+    Path(id): Path<String>,
+) -> Response {
+    let not_found = || error_page(StatusCode::NOT_FOUND, "No such contract");
+    let Ok(contract_uuid) = Uuid::parse_str(&id) else {
+        return not_found();
+    };
+    // Private and moderated contracts, and those of other tenants, are only
+    // served to API clients allowed to see them
+    let contract = match repository.public_contract(contract_uuid, tenant.id).await {
+        Ok(Some(contract)) => contract,
+        Ok(None) => return not_found(),
+        Err(err) => return repository_error("get contract", err),
+    };
+    let (versions, readme, abi) = match tokio::try_join!(
+        repository.versions(contract.id),
+        repository.readme(contract.id),
+        repository.abi(contract.id),
+    ) {
+        Ok(found) => found,
+        Err(err) => return repository_error("get contract details", err),
+    };
+
+    let mut body = format!("<h1>{}</h1>\n", escape(&contract.name));
+    if let Some(description) = &contract.description {
+        body.push_str(&format!("<p>{}</p>\n", escape(description)));
+    }
+    body.push_str(&details_table(&contract));
+    if !versions.is_empty() {
+        body.push_str("<h2>Versions</h2>\n");
+        body.push_str(&versions_table(&versions));
+    }
+    if let Some(readme) = readme.filter(|r| !r.trim().is_empty()) {
+        body.push_str(&format!(
+            "<h2>README</h2>\n<pre>{}</pre>\n",
+            escape(&readme)
+        ));
+    }
+    if let Some(abi) = abi {
+        body.push_str(&format!(
+            "<h2>Interface</h2>\n<pre><code>{}</code></pre>\n",
+            escape(&shared::format_abi(&abi, AbiSyntax::Rust))
+        ));
+    }
+
+    layout(&contract.name, "", &body).into_response()
+}
+
+/// The UI's pages, for merging into either the full or the lite router
+pub fn routes<S: Clone + Send + Sync + 'static>(repository: Repository) -> Router<S> {
+    Router::new()
+        .route("/", get(browse))
+        .route("/contracts/:id", get(contract_page))
+        .with_state(repository)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escapes_markup() {
+        assert_eq!(
+            escape("<script>alert(\"x&y\")</script>"),
+            "&lt;script&gt;alert(&quot;x&amp;y&quot;)&lt;/script&gt;"
+        );
+    }
+
+    #[test]
+    fn encodes_search_terms_for_links() {
+        assert_eq!(encode_query("token swap&v=2"), "token+swap%26v%3D2");
+    }
+}
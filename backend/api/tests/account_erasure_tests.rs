@@ -0,0 +1,94 @@
+// tests/account_erasure_tests.rs
+// Erasing an account in one tenant must leave its rows in other tenants alone.
+// Needs a Postgres server: cargo test --features testkit --test account_erasure_tests
+
+#![cfg(feature = "testkit")]
+
+use api::account_handlers::purge_due_deletions;
+use api::testkit::{ContractFixture, TestRegistry};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+const ACCOUNT: &str = "GERASEDACCOUNT";
+
+/// Rows of `ACCOUNT` left in `tenant`, per table
+async fn remaining(db: &PgPool, tenant: Uuid) -> anyhow::Result<Vec<i64>> {
+    let mut counts = Vec::new();
+    for query in [
+        "SELECT COUNT(*) FROM contract_stars s JOIN contracts c ON c.id = s.contract_id \
+         WHERE s.user_address = $1 AND c.tenant_id = $2",
+        "SELECT COUNT(*) FROM reviews r JOIN contracts c ON c.id = r.contract_id \
+         WHERE r.reviewer_address = $1 AND c.tenant_id = $2",
+        "SELECT COUNT(*) FROM saved_searches WHERE owner_address = $1 AND tenant_id = $2",
+    ] {
+        let count: i64 = sqlx::query_scalar(query)
+            .bind(ACCOUNT)
+            .bind(tenant)
+            .fetch_one(db)
+            .await?;
+        counts.push(count);
+    }
+    Ok(counts)
+}
+
+#[tokio::test]
+async fn erasure_keeps_the_account_in_other_tenants() -> anyhow::Result<()> {
+    let registry = TestRegistry::start().await?;
+    let db = registry.db();
+
+    let acme: Uuid =
+        sqlx::query_scalar("INSERT INTO tenants (slug, name) VALUES ('acme', 'Acme') RETURNING id")
+            .fetch_one(db)
+            .await?;
+    let public_contract = registry
+        .seed_contract(ContractFixture::new("public-amm"))
+        .await?;
+    let acme_contract = registry
+        .seed_contract(ContractFixture::new("acme-amm"))
+        .await?;
+    sqlx::query("UPDATE contracts SET tenant_id = $1 WHERE id = $2")
+        .bind(acme)
+        .bind(acme_contract.id)
+        .execute(db)
+        .await?;
+
+    for (tenant, contract_id) in [(Uuid::nil(), public_contract.id), (acme, acme_contract.id)] {
+        sqlx::query("INSERT INTO contract_stars (contract_id, user_address) VALUES ($1, $2)")
+            .bind(contract_id)
+            .bind(ACCOUNT)
+            .execute(db)
+            .await?;
+        sqlx::query(
+            "INSERT INTO reviews (contract_id, user_id, version, rating, reviewer_address) \
+             VALUES ($1, $2, '1.0.0', 5, $3)",
+        )
+        .bind(contract_id)
+        .bind(Uuid::new_v4())
+        .bind(ACCOUNT)
+        .execute(db)
+        .await?;
+        sqlx::query(
+            "INSERT INTO saved_searches (owner_address, name, query, tenant_id) \
+             VALUES ($1, 'amm', 'amm', $2)",
+        )
+        .bind(ACCOUNT)
+        .bind(tenant)
+        .execute(db)
+        .await?;
+    }
+
+    sqlx::query(
+        "INSERT INTO account_deletions (tenant_id, address, scheduled_for) \
+         VALUES ($1, $2, NOW() - INTERVAL '1 minute')",
+    )
+    .bind(Uuid::nil())
+    .bind(ACCOUNT)
+    .execute(db)
+    .await?;
+
+    assert_eq!(purge_due_deletions(db).await?, 1);
+    assert_eq!(remaining(db, Uuid::nil()).await?, vec![0, 0, 0]);
+    assert_eq!(remaining(db, acme).await?, vec![1, 1, 1]);
+
+    registry.cleanup().await
+}
@@ -0,0 +1,107 @@
+// tests/contract_alias_tests.rs
+// Renaming a contract keeps its old name resolvable as a deprecated alias.
+// Needs a Postgres server: cargo test --features testkit --test contract_alias_tests
+
+#![cfg(feature = "testkit")]
+
+use api::testkit::{ContractFixture, TestRegistry};
+use axum::http::{Method, StatusCode};
+use serde_json::json;
+use shared::ApiScope;
+
+const PUBLISHER: &str = "GALIASPUBLISHER";
+
+#[tokio::test]
+async fn renamed_contract_resolves_through_its_old_name() -> anyhow::Result<()> {
+    let registry = TestRegistry::start().await?;
+    let contract = registry
+        .seed_contract(
+            ContractFixture::new("token")
+                .namespace("alice")
+                .publisher(PUBLISHER),
+        )
+        .await?;
+    let token = registry.api_key(PUBLISHER, &[ApiScope::PatchAdmin]).await?;
+
+    let (status, renamed) = registry
+        .send_json(
+            Method::PATCH,
+            &format!("/api/contracts/{}", contract.id),
+            Some(&token),
+            Some(json!({ "name": "token-v2" })),
+        )
+        .await?;
+    assert_eq!(status, StatusCode::OK, "{}", renamed);
+    assert_eq!(renamed["name"], "token-v2");
+
+    let aliases: Vec<(Option<String>, String)> =
+        sqlx::query_as("SELECT namespace, name FROM contract_aliases WHERE contract_id = $1")
+            .bind(contract.id)
+            .fetch_all(registry.db())
+            .await?;
+    assert_eq!(
+        aliases,
+        vec![(Some("alice".to_string()), "token".to_string())]
+    );
+
+    let (status, found) = registry
+        .send_json(Method::GET, "/api/contracts/@alice%2Ftoken", None, None)
+        .await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(found["id"], contract.id.to_string());
+    assert_eq!(found["name"], "token-v2");
+    let notice = found["alias_notice"].as_str().unwrap_or_default();
+    assert!(notice.contains("@alice/token-v2"), "{}", notice);
+
+    let (status, current) = registry
+        .send_json(Method::GET, "/api/contracts/@alice%2Ftoken-v2", None, None)
+        .await?;
+    assert_eq!(status, StatusCode::OK);
+    assert!(current.get("alias_notice").is_none());
+
+    registry.cleanup().await
+}
+
+#[tokio::test]
+async fn unscoped_rename_resolves_through_its_old_name() -> anyhow::Result<()> {
+    let registry = TestRegistry::start().await?;
+    let contract = registry
+        .seed_contract(ContractFixture::new("vault").publisher(PUBLISHER))
+        .await?;
+    let token = registry.api_key(PUBLISHER, &[ApiScope::PatchAdmin]).await?;
+
+    let (status, renamed) = registry
+        .send_json(
+            Method::PATCH,
+            &format!("/api/contracts/{}", contract.id),
+            Some(&token),
+            Some(json!({ "name": "vault-v2" })),
+        )
+        .await?;
+    assert_eq!(status, StatusCode::OK, "{}", renamed);
+
+    let (status, found) = registry
+        .send_json(Method::GET, "/api/contracts/vault", None, None)
+        .await?;
+    assert_eq!(status, StatusCode::OK, "{}", found);
+    assert_eq!(found["id"], contract.id.to_string());
+    assert_eq!(found["name"], "vault-v2");
+    let notice = found["alias_notice"].as_str().unwrap_or_default();
+    assert!(notice.contains("vault-v2"), "{}", notice);
+
+    let (status, batch) = registry
+        .send_json(
+            Method::POST,
+            "/api/contracts/batch",
+            None,
+            Some(json!({ "ids": ["vault"] })),
+        )
+        .await?;
+    assert_eq!(status, StatusCode::OK, "{}", batch);
+    assert_eq!(
+        batch["results"][0]["contract"]["id"],
+        contract.id.to_string()
+    );
+
+    registry.cleanup().await
+}
@@ -0,0 +1,43 @@
+// tests/contract_metadata_tests.rs
+// Only a contract's publisher (or its organization's owners) may edit its metadata.
+// Needs a Postgres server: cargo test --features testkit --test contract_metadata_tests
+
+#![cfg(feature = "testkit")]
+
+use api::testkit::{ContractFixture, TestRegistry};
+use axum::http::{Method, StatusCode};
+use serde_json::json;
+use shared::ApiScope;
+
+const PUBLISHER: &str = "GMETADATAPUBLISHER";
+const STRANGER: &str = "GMETADATASTRANGER";
+
+#[tokio::test]
+async fn only_maintainers_can_edit_metadata() -> anyhow::Result<()> {
+    let registry = TestRegistry::start().await?;
+    let contract = registry
+        .seed_contract(ContractFixture::new("oracle").publisher(PUBLISHER))
+        .await?;
+    let uri = format!("/api/contracts/{}", contract.id);
+    let body = json!({ "description": "Rewritten" });
+
+    let (status, _) = registry
+        .send_json(Method::PATCH, &uri, None, Some(body.clone()))
+        .await?;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    let stranger = registry.api_key(STRANGER, &[ApiScope::PatchAdmin]).await?;
+    let (status, problem) = registry
+        .send_json(Method::PATCH, &uri, Some(&stranger), Some(body.clone()))
+        .await?;
+    assert_eq!(status, StatusCode::FORBIDDEN, "{}", problem);
+
+    let publisher = registry.api_key(PUBLISHER, &[ApiScope::PatchAdmin]).await?;
+    let (status, updated) = registry
+        .send_json(Method::PATCH, &uri, Some(&publisher), Some(body))
+        .await?;
+    assert_eq!(status, StatusCode::OK, "{}", updated);
+    assert_eq!(updated["description"], "Rewritten");
+
+    registry.cleanup().await
+}
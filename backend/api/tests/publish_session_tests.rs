@@ -0,0 +1,46 @@
+// tests/publish_session_tests.rs
+// Only a contract's publisher (or its organization's owners) may stage versions of it.
+// Needs a Postgres server: cargo test --features testkit --test publish_session_tests
+
+#![cfg(feature = "testkit")]
+
+use api::testkit::{ContractFixture, TestRegistry};
+use axum::http::{Method, StatusCode};
+use serde_json::json;
+use shared::ApiScope;
+
+const PUBLISHER: &str = "GSESSIONPUBLISHER";
+const STRANGER: &str = "GSESSIONSTRANGER";
+
+#[tokio::test]
+async fn only_maintainers_can_open_publish_sessions() -> anyhow::Result<()> {
+    let registry = TestRegistry::start().await?;
+    let contract = registry
+        .seed_contract(ContractFixture::new("vault").publisher(PUBLISHER))
+        .await?;
+    let body = json!({ "contract_id": contract.id.to_string(), "version": "2.0.0" });
+
+    let stranger = registry.api_key(STRANGER, &[ApiScope::Publish]).await?;
+    let (status, problem) = registry
+        .send_json(
+            Method::POST,
+            "/api/publish/sessions",
+            Some(&stranger),
+            Some(body.clone()),
+        )
+        .await?;
+    assert_eq!(status, StatusCode::FORBIDDEN, "{}", problem);
+
+    let publisher = registry.api_key(PUBLISHER, &[ApiScope::Publish]).await?;
+    let (status, session) = registry
+        .send_json(
+            Method::POST,
+            "/api/publish/sessions",
+            Some(&publisher),
+            Some(body),
+        )
+        .await?;
+    assert_eq!(status, StatusCode::CREATED, "{}", session);
+
+    registry.cleanup().await
+}
@@ -0,0 +1,87 @@
+// tests/publisher_identity_tests.rs
+// Only the publisher's account can request an identity challenge, and only a
+// verifying signature uses it up.
+// Needs a Postgres server: cargo test --features testkit --test publisher_identity_tests
+
+#![cfg(feature = "testkit")]
+
+use api::testkit::{ContractFixture, TestRegistry};
+use axum::http::{Method, StatusCode};
+use ed25519_dalek::{Signer, SigningKey};
+use serde_json::json;
+use shared::{encode_account_id, ApiScope};
+
+const STRANGER: &str = "GIDENTITYSTRANGER";
+
+#[tokio::test]
+async fn challenge_needs_the_account_and_survives_bad_signatures() -> anyhow::Result<()> {
+    let registry = TestRegistry::start().await?;
+    let key = SigningKey::from_bytes(&[5u8; 32]);
+    let account = encode_account_id(key.verifying_key().as_bytes());
+    let contract = registry
+        .seed_contract(ContractFixture::new("identity").publisher(&account))
+        .await?;
+    let base = format!("/api/publishers/{}/verify-identity", contract.publisher_id);
+
+    let stranger = registry.api_key(STRANGER, &[ApiScope::Read]).await?;
+    let (status, _) = registry
+        .send_json(
+            Method::POST,
+            &format!("{}/challenge", base),
+            Some(&stranger),
+            None,
+        )
+        .await?;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let owner = registry.api_key(&account, &[ApiScope::Read]).await?;
+    let (status, challenge) = registry
+        .send_json(
+            Method::POST,
+            &format!("{}/challenge", base),
+            Some(&owner),
+            None,
+        )
+        .await?;
+    assert_eq!(status, StatusCode::OK, "{}", challenge);
+    let message = challenge["message"]
+        .as_str()
+        .unwrap_or_default()
+        .to_string();
+
+    let forged = SigningKey::from_bytes(&[6u8; 32]).sign(message.as_bytes());
+    let (status, _) = registry
+        .send_json(
+            Method::POST,
+            &base,
+            None,
+            Some(json!({ "signature": hex::encode(forged.to_bytes()) })),
+        )
+        .await?;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+
+    let signature = hex::encode(key.sign(message.as_bytes()).to_bytes());
+    let (status, verified) = registry
+        .send_json(
+            Method::POST,
+            &base,
+            None,
+            Some(json!({ "signature": signature })),
+        )
+        .await?;
+    assert_eq!(status, StatusCode::OK, "{}", verified);
+    assert_eq!(verified["verified_account"], account);
+
+    // The verified signature used the challenge up
+    let (status, _) = registry
+        .send_json(
+            Method::POST,
+            &base,
+            None,
+            Some(json!({ "signature": signature })),
+        )
+        .await?;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+
+    registry.cleanup().await
+}
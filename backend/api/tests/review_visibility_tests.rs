@@ -0,0 +1,41 @@
+// tests/review_visibility_tests.rs
+// Reviews of a private contract are as hidden as the contract itself.
+// Needs a Postgres server: cargo test --features testkit --test review_visibility_tests
+
+#![cfg(feature = "testkit")]
+
+use api::testkit::{ContractFixture, TestRegistry};
+use axum::http::{Method, StatusCode};
+use serde_json::json;
+use shared::ApiScope;
+
+const STRANGER: &str = "GREVIEWSTRANGER";
+
+#[tokio::test]
+async fn private_contract_reviews_are_not_found() -> anyhow::Result<()> {
+    let registry = TestRegistry::start().await?;
+    let contract = registry
+        .seed_contract(ContractFixture::new("treasury"))
+        .await?;
+    sqlx::query("UPDATE contracts SET is_private = TRUE WHERE id = $1")
+        .bind(contract.id)
+        .execute(registry.db())
+        .await?;
+    let uri = format!("/api/contracts/{}/reviews", contract.id);
+
+    let (status, _) = registry.send_json(Method::GET, &uri, None, None).await?;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let stranger = registry.api_key(STRANGER, &[ApiScope::Publish]).await?;
+    let (status, problem) = registry
+        .send_json(
+            Method::POST,
+            &uri,
+            Some(&stranger),
+            Some(json!({ "rating": 1, "comment": "never saw it" })),
+        )
+        .await?;
+    assert_eq!(status, StatusCode::NOT_FOUND, "{}", problem);
+
+    registry.cleanup().await
+}
@@ -1,3 +1,4 @@
+use crate::abi_format::{format_function, AbiSyntax};
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use std::process::Command;
@@ -56,6 +57,13 @@ pub fn generate_markdown(specs: &[ContractSpec], name: &str) -> String {
             md.push_str(&format!("{}\n\n", doc));
         }
 
+        if let Ok(entry) = serde_json::to_value(spec) {
+            md.push_str(&format!(
+                "```rust\n{}\n```\n\n",
+                format_function(&entry, AbiSyntax::Rust)
+            ));
+        }
+
         md.push_str("**Parameters:**\n");
         if spec.inputs.is_empty() {
             md.push_str("- None\n");
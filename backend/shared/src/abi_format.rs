@@ -0,0 +1,364 @@
+//! Human-readable rendering of stored contract ABIs (the JSON checked by
+//! `validate_abi`): function signatures and type definitions in Rust-like or
+//! TypeScript-like syntax. Shared by the CLI's `info --abi` and generated docs.
+
+use serde_json::Value;
+
+/// Syntax ABI signatures are rendered in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AbiSyntax {
+    Rust,
+    TypeScript,
+}
+
+impl std::str::FromStr for AbiSyntax {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "rust" | "rs" => Ok(AbiSyntax::Rust),
+            "typescript" | "ts" => Ok(AbiSyntax::TypeScript),
+            other => Err(format!(
+                "Unknown ABI syntax '{}'. Use: rust, typescript",
+                other
+            )),
+        }
+    }
+}
+
+fn str_field<'a>(value: &'a Value, key: &str) -> &'a str {
+    value[key].as_str().unwrap_or("")
+}
+
+/// Stand-in for a type the ABI leaves out
+fn unknown_type(syntax: AbiSyntax) -> String {
+    match syntax {
+        AbiSyntax::Rust => "Val".to_string(),
+        AbiSyntax::TypeScript => "any".to_string(),
+    }
+}
+
+fn inner_type(value: &Value, key: &str, syntax: AbiSyntax) -> String {
+    match value.get(key) {
+        Some(inner) if inner.is_object() => format_type(inner, syntax),
+        _ => unknown_type(syntax),
+    }
+}
+
+/// Built-in Soroban scalar types, named as in `soroban-sdk`
+fn rust_scalar(name: &str) -> Option<&'static str> {
+    Some(match name {
+        "void" | "()" => "()",
+        "bool" => "bool",
+        "u32" => "u32",
+        "i32" => "i32",
+        "u64" => "u64",
+        "i64" => "i64",
+        "u128" => "u128",
+        "i128" => "i128",
+        "u256" => "U256",
+        "i256" => "I256",
+        "timepoint" => "Timepoint",
+        "duration" => "Duration",
+        "symbol" => "Symbol",
+        "string" => "String",
+        "address" => "Address",
+        "bytes" => "Bytes",
+        "val" => "Val",
+        _ => return None,
+    })
+}
+
+/// Built-in Soroban scalar types, named as in generated `stellar-sdk` bindings
+fn typescript_scalar(name: &str) -> Option<&'static str> {
+    Some(match name {
+        "void" | "()" => "void",
+        "bool" => "boolean",
+        "u32" | "i32" => "number",
+        "u64" | "i64" | "u128" | "i128" | "u256" | "i256" | "timepoint" | "duration" => "bigint",
+        "symbol" | "string" | "address" => "string",
+        "bytes" | "bytesn" | "bytes_n" => "Buffer",
+        "val" => "any",
+        _ => return None,
+    })
+}
+
+/// A type value (`{"type": "vec", "element": {...}}`) in the given syntax.
+/// Names the registry doesn't recognise are user-defined types and pass through.
+pub fn format_type(value: &Value, syntax: AbiSyntax) -> String {
+    let name = str_field(value, "type");
+    let lower = name.to_lowercase();
+    let scalar = match syntax {
+        AbiSyntax::Rust => rust_scalar(&lower),
+        AbiSyntax::TypeScript => typescript_scalar(&lower),
+    };
+    if let Some(scalar) = scalar {
+        return scalar.to_string();
+    }
+    let rust = syntax == AbiSyntax::Rust;
+    match lower.as_str() {
+        "option" if rust => format!("Option<{}>", inner_type(value, "element", syntax)),
+        "option" => format!("{} | undefined", inner_type(value, "element", syntax)),
+        "vec" if rust => format!("Vec<{}>", inner_type(value, "element", syntax)),
+        "vec" => format!("Array<{}>", inner_type(value, "element", syntax)),
+        "map" => format!(
+            "Map<{}, {}>",
+            inner_type(value, "key", syntax),
+            inner_type(value, "val", syntax)
+        ),
+        "bytesn" | "bytes_n" => format!("BytesN<{}>", value["n"].as_u64().unwrap_or(32)),
+        "" => unknown_type(syntax),
+        _ => name.to_string(),
+    }
+}
+
+/// What a function returns; several outputs form a tuple
+fn format_outputs(entry: &Value, syntax: AbiSyntax) -> Option<String> {
+    let outputs: Vec<String> = entry["outputs"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|output| format_type(output, syntax))
+        .filter(|ty| ty != "()" && ty != "void")
+        .collect();
+    match (outputs.len(), syntax) {
+        (0, _) => None,
+        (1, _) => outputs.into_iter().next(),
+        (_, AbiSyntax::Rust) => Some(format!("({})", outputs.join(", "))),
+        (_, AbiSyntax::TypeScript) => Some(format!("[{}]", outputs.join(", "))),
+    }
+}
+
+/// `name: Type` pairs for a function's inputs or a struct's fields
+fn format_params(params: &Value, syntax: AbiSyntax) -> Vec<String> {
+    params
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|param| {
+            format!(
+                "{}: {}",
+                str_field(param, "name"),
+                format_type(&param["value"], syntax)
+            )
+        })
+        .collect()
+}
+
+/// One-line signature of a function entry, e.g. `fn swap(to: Address) -> i128`
+/// or `swap(to: string): bigint`
+pub fn format_function(entry: &Value, syntax: AbiSyntax) -> String {
+    let name = str_field(entry, "name");
+    let params = format_params(&entry["inputs"], syntax).join(", ");
+    let output = format_outputs(entry, syntax);
+    match syntax {
+        AbiSyntax::Rust => match output {
+            Some(output) => format!("fn {}({}) -> {}", name, params, output),
+            None => format!("fn {}({})", name, params),
+        },
+        AbiSyntax::TypeScript => format!(
+            "{}({}): {}",
+            name,
+            params,
+            output.unwrap_or_else(|| "void".to_string())
+        ),
+    }
+}
+
+fn push_doc(out: &mut String, entry: &Value, indent: &str, syntax: AbiSyntax) {
+    let Some(doc) = entry["doc"].as_str().filter(|doc| !doc.trim().is_empty()) else {
+        return;
+    };
+    match syntax {
+        AbiSyntax::Rust => {
+            for line in doc.lines() {
+                out.push_str(&format!("{}/// {}\n", indent, line.trim_end()));
+            }
+        }
+        AbiSyntax::TypeScript => {
+            out.push_str(&format!("{}/**\n", indent));
+            for line in doc.lines() {
+                out.push_str(&format!("{} * {}\n", indent, line.trim_end()));
+            }
+            out.push_str(&format!("{} */\n", indent));
+        }
+    }
+}
+
+fn case_types(case: &Value, syntax: AbiSyntax) -> Vec<String> {
+    case["fields"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|field| format_type(&field["value"], syntax))
+        .collect()
+}
+
+/// Definition of a struct, enum, union, error enum or event entry
+fn format_definition(entry: &Value, syntax: AbiSyntax) -> String {
+    let kind = str_field(entry, "type");
+    let name = str_field(entry, "name");
+    let cases: Vec<&Value> = entry["cases"].as_array().into_iter().flatten().collect();
+    let mut out = String::new();
+    match (kind, syntax) {
+        ("struct" | "event", _) => {
+            let keyword = match (kind, syntax) {
+                ("event", AbiSyntax::Rust) => "event",
+                (_, AbiSyntax::Rust) => "struct",
+                (_, AbiSyntax::TypeScript) => "interface",
+            };
+            let separator = if syntax == AbiSyntax::Rust { "," } else { ";" };
+            out.push_str(&format!("{} {} {{\n", keyword, name));
+            let fields = entry["fields"].as_array().into_iter().flatten();
+            for (field, line) in fields.zip(format_params(&entry["fields"], syntax)) {
+                push_doc(&mut out, field, "    ", syntax);
+                out.push_str(&format!("    {}{}\n", line, separator));
+            }
+            out.push('}');
+        }
+        ("union", AbiSyntax::TypeScript) => {
+            let variants: Vec<String> = cases
+                .iter()
+                .map(|case| {
+                    let types = case_types(case, syntax);
+                    let values = if types.is_empty() {
+                        "void".to_string()
+                    } else {
+                        format!("readonly [{}]", types.join(", "))
+                    };
+                    format!(
+                        "  | {{ tag: \"{}\"; values: {} }}",
+                        str_field(case, "name"),
+                        values
+                    )
+                })
+                .collect();
+            out.push_str(&format!("type {} =\n{};", name, variants.join("\n")));
+        }
+        (_, _) => {
+            out.push_str(&format!("enum {} {{\n", name));
+            for case in cases {
+                push_doc(&mut out, case, "    ", syntax);
+                let types = case_types(case, syntax);
+                let line = match case["value"].as_u64() {
+                    Some(value) => format!("{} = {}", str_field(case, "name"), value),
+                    None if types.is_empty() => str_field(case, "name").to_string(),
+                    None => format!("{}({})", str_field(case, "name"), types.join(", ")),
+                };
+                out.push_str(&format!("    {},\n", line));
+            }
+            out.push('}');
+        }
+    }
+    out
+}
+
+/// The whole ABI as source-like text: type definitions, then function
+/// signatures, each preceded by its doc comment
+pub fn format_abi(abi: &Value, syntax: AbiSyntax) -> String {
+    let entries: Vec<&Value> = abi.as_array().into_iter().flatten().collect();
+    let mut blocks = Vec::new();
+    for entry in entries.iter().filter(|e| e["type"] != "function") {
+        let mut block = String::new();
+        push_doc(&mut block, entry, "", syntax);
+        block.push_str(&format_definition(entry, syntax));
+        blocks.push(block);
+    }
+    for entry in entries.iter().filter(|e| e["type"] == "function") {
+        let mut block = String::new();
+        push_doc(&mut block, entry, "", syntax);
+        block.push_str(&format_function(entry, syntax));
+        if syntax == AbiSyntax::TypeScript {
+            block.push(';');
+        }
+        blocks.push(block);
+    }
+    let mut out = blocks.join("\n\n");
+    out.push('\n');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn token_abi() -> Value {
+        json!([
+            {"type": "struct", "name": "Allowance", "fields": [
+                {"name": "amount", "value": {"type": "i128"}, "doc": "Remaining amount"},
+                {"name": "expires", "value": {"type": "u32"}}
+            ]},
+            {"type": "error_enum", "name": "Error", "cases": [
+                {"name": "Insufficient", "value": 1},
+                {"name": "Expired", "value": 2}
+            ]},
+            {"type": "union", "name": "Key", "cases": [
+                {"name": "Admin"},
+                {"name": "Balance", "fields": [{"name": "0", "value": {"type": "address"}}]}
+            ]},
+            {"type": "function", "name": "transfer", "doc": "Move tokens", "inputs": [
+                {"name": "from", "value": {"type": "address"}},
+                {"name": "to", "value": {"type": "address"}},
+                {"name": "amount", "value": {"type": "i128"}}
+            ], "outputs": []},
+            {"type": "function", "name": "balances", "inputs": [
+                {"name": "ids", "value": {"type": "vec", "element": {"type": "address"}}}
+            ], "outputs": [
+                {"type": "map", "key": {"type": "address"}, "val": {"type": "option", "element": {"type": "i128"}}}
+            ]}
+        ])
+    }
+
+    #[test]
+    fn function_signatures_in_both_syntaxes() {
+        let abi = token_abi();
+        assert_eq!(
+            format_function(&abi[3], AbiSyntax::Rust),
+            "fn transfer(from: Address, to: Address, amount: i128)"
+        );
+        assert_eq!(
+            format_function(&abi[3], AbiSyntax::TypeScript),
+            "transfer(from: string, to: string, amount: bigint): void"
+        );
+        assert_eq!(
+            format_function(&abi[4], AbiSyntax::Rust),
+            "fn balances(ids: Vec<Address>) -> Map<Address, Option<i128>>"
+        );
+        assert_eq!(
+            format_function(&abi[4], AbiSyntax::TypeScript),
+            "balances(ids: Array<string>): Map<string, bigint | undefined>"
+        );
+    }
+
+    #[test]
+    fn types_map_to_each_syntax() {
+        let bytes = json!({"type": "bytesn", "n": 32});
+        assert_eq!(format_type(&bytes, AbiSyntax::Rust), "BytesN<32>");
+        assert_eq!(format_type(&bytes, AbiSyntax::TypeScript), "Buffer");
+        let custom = json!({"type": "Allowance"});
+        assert_eq!(format_type(&custom, AbiSyntax::TypeScript), "Allowance");
+        let untyped = json!({"type": "vec"});
+        assert_eq!(format_type(&untyped, AbiSyntax::Rust), "Vec<Val>");
+    }
+
+    #[test]
+    fn whole_abi_renders_definitions_before_functions() {
+        let rust = format_abi(&token_abi(), AbiSyntax::Rust);
+        assert!(
+            rust.starts_with("struct Allowance {\n    /// Remaining amount\n    amount: i128,\n")
+        );
+        assert!(rust.contains("enum Error {\n    Insufficient = 1,\n    Expired = 2,\n}"));
+        assert!(rust.contains("enum Key {\n    Admin,\n    Balance(Address),\n}"));
+        assert!(rust.contains("/// Move tokens\nfn transfer("));
+
+        let ts = format_abi(&token_abi(), AbiSyntax::TypeScript);
+        assert!(ts.starts_with("interface Allowance {\n    /**\n"));
+        assert!(ts.contains(
+            "type Key =\n  | { tag: \"Admin\"; values: void }\n  | { tag: \"Balance\"; values: readonly [string] };"
+        ));
+        assert!(ts.contains("): void;\n"));
+        assert!("rs".parse::<AbiSyntax>() == Ok(AbiSyntax::Rust));
+        assert!("python".parse::<AbiSyntax>().is_err());
+    }
+}
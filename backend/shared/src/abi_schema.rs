@@ -0,0 +1,377 @@
+//! JSON schema for stored contract ABIs: the array of spec entries emitted by
+//! `soroban contract bindings json`. `validate_abi` enforces the same rules
+//! and reports every problem with its path, e.g. `abi[2].inputs[0].value.type`.
+
+use serde_json::{json, Map, Value};
+
+use crate::problem::FieldProblem;
+
+/// Kinds of ABI entry the registry understands
+pub const ABI_ENTRY_TYPES: &[&str] =
+    &["function", "struct", "union", "enum", "error_enum", "event"];
+/// Longest function or type name Soroban allows
+pub const MAX_ABI_NAME_LENGTH: usize = 60;
+/// Deepest nesting of container types (`vec<map<...>>`) accepted
+pub const MAX_ABI_TYPE_DEPTH: usize = 16;
+/// Problems reported before validation stops
+const MAX_REPORTED_PROBLEMS: usize = 50;
+
+const NAME_PATTERN: &str = "^[A-Za-z_][A-Za-z0-9_]*$";
+
+/// The JSON schema served at GET /api/schema/abi
+pub fn abi_schema() -> Value {
+    let name = json!({
+        "type": "string",
+        "pattern": NAME_PATTERN,
+        "maxLength": MAX_ABI_NAME_LENGTH,
+    });
+    let doc = json!({ "type": ["string", "null"] });
+    json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "$id": "/api/schema/abi",
+        "title": "Soroban contract ABI",
+        "description": "Contract spec entries as produced by `soroban contract bindings json`",
+        "type": "array",
+        "items": { "$ref": "#/$defs/entry" },
+        "$defs": {
+            "entry": {
+                "type": "object",
+                "required": ["type", "name"],
+                "properties": {
+                    "type": { "enum": ABI_ENTRY_TYPES },
+                    "name": name,
+                    "doc": doc,
+                    "inputs": { "type": "array", "items": { "$ref": "#/$defs/param" } },
+                    "outputs": { "type": "array", "items": { "$ref": "#/$defs/output" } },
+                    "fields": { "type": "array", "items": { "$ref": "#/$defs/param" } },
+                    "cases": { "type": "array", "items": { "$ref": "#/$defs/case" } },
+                },
+            },
+            "param": {
+                "type": "object",
+                "required": ["name", "value"],
+                "properties": {
+                    "name": name,
+                    "value": { "$ref": "#/$defs/typeValue" },
+                    "doc": doc,
+                },
+            },
+            "output": {
+                "type": "object",
+                "required": ["type"],
+                "properties": { "type": { "type": "string", "minLength": 1 } },
+            },
+            "case": {
+                "type": "object",
+                "required": ["name"],
+                "properties": {
+                    "name": name,
+                    "value": { "type": ["integer", "null"], "minimum": 0, "maximum": u32::MAX },
+                    "fields": { "type": "array", "items": { "$ref": "#/$defs/param" } },
+                    "doc": doc,
+                },
+            },
+            "typeValue": {
+                "type": "object",
+                "required": ["type"],
+                "properties": {
+                    "type": { "type": "string", "minLength": 1 },
+                    "element": { "$ref": "#/$defs/typeValue" },
+                    "key": { "$ref": "#/$defs/typeValue" },
+                    "val": { "$ref": "#/$defs/typeValue" },
+                    "n": { "type": "integer", "minimum": 0, "maximum": u32::MAX },
+                },
+            },
+        },
+    })
+}
+
+struct Problems(Vec<FieldProblem>);
+
+impl Problems {
+    fn add(&mut self, field: &str, message: impl Into<String>) {
+        if self.0.len() < MAX_REPORTED_PROBLEMS {
+            self.0.push(FieldProblem {
+                field: field.to_string(),
+                message: message.into(),
+            });
+        }
+    }
+
+    fn full(&self) -> bool {
+        self.0.len() >= MAX_REPORTED_PROBLEMS
+    }
+}
+
+fn is_valid_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn check_name(object: &Map<String, Value>, path: &str, problems: &mut Problems) {
+    let field = format!("{}.name", path);
+    match object.get("name") {
+        None => problems.add(&field, "is required"),
+        Some(Value::String(name)) if name.len() > MAX_ABI_NAME_LENGTH => problems.add(
+            &field,
+            format!("must be at most {} characters", MAX_ABI_NAME_LENGTH),
+        ),
+        Some(Value::String(name)) if !is_valid_name(name) => problems.add(
+            &field,
+            "must start with a letter or underscore and contain only letters, digits and underscores",
+        ),
+        Some(Value::String(_)) => {}
+        Some(_) => problems.add(&field, "must be a string"),
+    }
+}
+
+fn check_doc(object: &Map<String, Value>, path: &str, problems: &mut Problems) {
+    if let Some(doc) = object.get("doc") {
+        if !doc.is_string() && !doc.is_null() {
+            problems.add(&format!("{}.doc", path), "must be a string");
+        }
+    }
+}
+
+fn check_u32(value: &Value, field: &str, problems: &mut Problems) {
+    if value.as_u64().and_then(|n| u32::try_from(n).ok()).is_none() {
+        problems.add(field, "must be an integer between 0 and 4294967295");
+    }
+}
+
+/// `key` of `object` as an array, each item checked by `check`
+fn check_array(
+    object: &Map<String, Value>,
+    key: &str,
+    path: &str,
+    problems: &mut Problems,
+    check: fn(&Value, &str, &mut Problems),
+) {
+    match object.get(key) {
+        None => {}
+        Some(Value::Array(items)) => {
+            for (i, item) in items.iter().enumerate() {
+                if problems.full() {
+                    return;
+                }
+                check(item, &format!("{}.{}[{}]", path, key, i), problems);
+            }
+        }
+        Some(_) => problems.add(&format!("{}.{}", path, key), "must be an array"),
+    }
+}
+
+fn check_type_value(value: &Value, path: &str, depth: usize, problems: &mut Problems) {
+    let Some(object) = value.as_object() else {
+        problems.add(path, "must be an object with a `type`");
+        return;
+    };
+    if depth > MAX_ABI_TYPE_DEPTH {
+        problems.add(
+            path,
+            format!("types may nest at most {} levels", MAX_ABI_TYPE_DEPTH),
+        );
+        return;
+    }
+    match object.get("type") {
+        Some(Value::String(name)) if !name.is_empty() => {}
+        Some(Value::String(_)) => problems.add(&format!("{}.type", path), "must not be empty"),
+        Some(_) => problems.add(&format!("{}.type", path), "must be a string"),
+        None => problems.add(&format!("{}.type", path), "is required"),
+    }
+    for key in ["element", "key", "val"] {
+        if let Some(inner) = object.get(key) {
+            check_type_value(inner, &format!("{}.{}", path, key), depth + 1, problems);
+        }
+    }
+    if let Some(n) = object.get("n") {
+        check_u32(n, &format!("{}.n", path), problems);
+    }
+}
+
+fn check_param(value: &Value, path: &str, problems: &mut Problems) {
+    let Some(object) = value.as_object() else {
+        problems.add(path, "must be an object");
+        return;
+    };
+    check_name(object, path, problems);
+    match object.get("value") {
+        Some(value) => check_type_value(value, &format!("{}.value", path), 1, problems),
+        None => problems.add(&format!("{}.value", path), "is required"),
+    }
+    check_doc(object, path, problems);
+}
+
+fn check_output(value: &Value, path: &str, problems: &mut Problems) {
+    match value.get("type") {
+        Some(Value::String(name)) if !name.is_empty() => {}
+        Some(_) => problems.add(&format!("{}.type", path), "must be a non-empty string"),
+        None if value.is_object() => problems.add(&format!("{}.type", path), "is required"),
+        None => problems.add(path, "must be an object with a `type`"),
+    }
+}
+
+fn check_case(value: &Value, path: &str, problems: &mut Problems) {
+    let Some(object) = value.as_object() else {
+        problems.add(path, "must be an object");
+        return;
+    };
+    check_name(object, path, problems);
+    if let Some(discriminant) = object.get("value").filter(|v| !v.is_null()) {
+        check_u32(discriminant, &format!("{}.value", path), problems);
+    }
+    check_array(object, "fields", path, problems, check_param);
+    check_doc(object, path, problems);
+}
+
+fn check_entry(value: &Value, path: &str, problems: &mut Problems) {
+    let Some(object) = value.as_object() else {
+        problems.add(path, "must be an object");
+        return;
+    };
+    match object.get("type") {
+        Some(Value::String(kind)) if ABI_ENTRY_TYPES.contains(&kind.as_str()) => {}
+        Some(_) => problems.add(
+            &format!("{}.type", path),
+            format!("must be one of: {}", ABI_ENTRY_TYPES.join(", ")),
+        ),
+        None => problems.add(&format!("{}.type", path), "is required"),
+    }
+    check_name(object, path, problems);
+    check_doc(object, path, problems);
+    check_array(object, "inputs", path, problems, check_param);
+    check_array(object, "outputs", path, problems, check_output);
+    check_array(object, "fields", path, problems, check_param);
+    check_array(object, "cases", path, problems, check_case);
+}
+
+/// Check `abi` against `abi_schema()`, plus that function names are unique.
+/// Returns every problem found (up to 50), each with its path.
+pub fn validate_abi(abi: &Value) -> Result<(), Vec<FieldProblem>> {
+    let mut problems = Problems(Vec::new());
+    let Some(entries) = abi.as_array() else {
+        problems.add("abi", "must be an array of spec entries");
+        return Err(problems.0);
+    };
+
+    let mut functions = std::collections::HashSet::new();
+    for (i, entry) in entries.iter().enumerate() {
+        if problems.full() {
+            break;
+        }
+        let path = format!("abi[{}]", i);
+        check_entry(entry, &path, &mut problems);
+        if entry.get("type").and_then(Value::as_str) == Some("function") {
+            if let Some(name) = entry.get("name").and_then(Value::as_str) {
+                if !functions.insert(name) {
+                    problems.add(
+                        &format!("{}.name", path),
+                        format!("function '{}' is defined more than once", name),
+                    );
+                }
+            }
+        }
+    }
+
+    if problems.0.is_empty() {
+        Ok(())
+    } else {
+        Err(problems.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields(result: Result<(), Vec<FieldProblem>>) -> Vec<String> {
+        result
+            .unwrap_err()
+            .into_iter()
+            .map(|problem| problem.field)
+            .collect()
+    }
+
+    #[test]
+    fn accepts_a_bindings_spec() {
+        let abi = json!([
+            {
+                "type": "function",
+                "name": "transfer",
+                "doc": "Move tokens",
+                "inputs": [
+                    { "name": "from", "value": { "type": "address" } },
+                    { "name": "amounts", "value": { "type": "vec", "element": { "type": "i128" } } }
+                ],
+                "outputs": [{ "type": "bool" }]
+            },
+            { "type": "struct", "name": "Config", "fields": [
+                { "name": "hash", "value": { "type": "bytesN", "n": 32 } }
+            ] },
+            { "type": "error_enum", "name": "Error", "cases": [
+                { "name": "NotAllowed", "value": 1 }
+            ] }
+        ]);
+        assert!(validate_abi(&abi).is_ok());
+        assert!(validate_abi(&json!([])).is_ok());
+    }
+
+    #[test]
+    fn reports_each_problem_with_its_path() {
+        let abi = json!([
+            { "type": "function", "name": "1bad", "inputs": [{ "name": "x" }] },
+            { "type": "method", "name": "ok", "outputs": [{}] },
+            { "type": "struct", "name": "S", "fields": [
+                { "name": "n", "value": { "type": "bytesN", "n": -1 } }
+            ] },
+            { "type": "function", "name": "1bad" }
+        ]);
+        assert_eq!(
+            fields(validate_abi(&abi)),
+            vec![
+                "abi[0].name",
+                "abi[0].inputs[0].value",
+                "abi[1].type",
+                "abi[1].outputs[0].type",
+                "abi[2].fields[0].value.n",
+                "abi[3].name",
+                "abi[3].name",
+            ]
+        );
+        assert_eq!(fields(validate_abi(&json!({}))), vec!["abi"]);
+    }
+
+    #[test]
+    fn limits_nesting() {
+        let mut value = json!({ "type": "u32" });
+        for _ in 0..=MAX_ABI_TYPE_DEPTH {
+            value = json!({ "type": "vec", "element": value });
+        }
+        let abi = json!([{ "type": "function", "name": "f", "inputs": [
+            { "name": "deep", "value": value }
+        ] }]);
+        let problems = validate_abi(&abi).unwrap_err();
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].message.contains("nest"));
+    }
+
+    #[test]
+    fn schema_lists_the_validated_entry_types() {
+        let schema = abi_schema();
+        assert_eq!(
+            schema["$defs"]["entry"]["properties"]["type"]["enum"],
+            json!(ABI_ENTRY_TYPES)
+        );
+        assert_eq!(
+            schema["$defs"]["entry"]["required"],
+            json!(["type", "name"])
+        );
+        assert_eq!(
+            schema["$defs"]["param"]["properties"]["name"]["pattern"],
+            NAME_PATTERN
+        );
+    }
+}
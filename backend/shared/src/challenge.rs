@@ -0,0 +1,106 @@
+//! Challenges that anonymous writes (abuse reports, telemetry) may have to
+//! pass to deter spam. A registry that requires one answers an unanswered
+//! write with `428 Precondition Required`, code `ChallengeRequired`, and
+//! describes the challenge in the `x-registry-challenge*` headers; the client
+//! repeats the request with the token and its solution.
+//!
+//! Proof of work is hashcash-style: find a solution such that
+//! `sha256("<token>:<solution>")` starts with `difficulty` zero bits.
+
+use sha2::{Digest, Sha256};
+
+/// Kind of challenge: `pow` or `captcha`
+pub const CHALLENGE_HEADER: &str = "x-registry-challenge";
+/// Opaque, signed challenge; echoed back with the solution
+pub const CHALLENGE_TOKEN_HEADER: &str = "x-registry-challenge-token";
+/// Leading zero bits a proof of work needs
+pub const CHALLENGE_DIFFICULTY_HEADER: &str = "x-registry-challenge-difficulty";
+/// Site key for rendering the CAPTCHA widget
+pub const CHALLENGE_SITE_KEY_HEADER: &str = "x-registry-challenge-site-key";
+/// The proof-of-work solution, or the CAPTCHA response token
+pub const CHALLENGE_SOLUTION_HEADER: &str = "x-registry-challenge-solution";
+
+/// Hardest proof of work a registry may ask for; clients refuse anything
+/// harder rather than spin on it
+pub const MAX_POW_DIFFICULTY: u32 = 32;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChallengeKind {
+    Pow,
+    Captcha,
+}
+
+impl ChallengeKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChallengeKind::Pow => "pow",
+            ChallengeKind::Captcha => "captcha",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim() {
+            "pow" => Some(ChallengeKind::Pow),
+            "captcha" => Some(ChallengeKind::Captcha),
+            _ => None,
+        }
+    }
+}
+
+fn pow_digest(token: &str, solution: &str) -> [u8; 32] {
+    Sha256::digest(format!("{}:{}", token, solution).as_bytes()).into()
+}
+
+fn leading_zero_bits(digest: &[u8]) -> u32 {
+    let mut bits = 0;
+    for byte in digest {
+        bits += byte.leading_zeros();
+        if *byte != 0 {
+            break;
+        }
+    }
+    bits
+}
+
+/// Whether `solution` answers the proof-of-work challenge `token`
+pub fn pow_is_solved(token: &str, solution: &str, difficulty: u32) -> bool {
+    leading_zero_bits(&pow_digest(token, solution)) >= difficulty
+}
+
+/// Search for a proof-of-work solution; takes about `2^difficulty` hashes
+pub fn solve_pow(token: &str, difficulty: u32) -> String {
+    (0u64..)
+        .map(|counter| counter.to_string())
+        .find(|solution| pow_is_solved(token, solution, difficulty))
+        .expect("the counter space outlasts any difficulty")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_leading_zero_bits_across_bytes() {
+        assert_eq!(leading_zero_bits(&[0xff, 0x00]), 0);
+        assert_eq!(leading_zero_bits(&[0x00, 0x10, 0x00]), 11);
+        assert_eq!(leading_zero_bits(&[0x00, 0x00]), 16);
+    }
+
+    #[test]
+    fn solutions_verify_against_their_token() {
+        let solution = solve_pow("challenge-a", 12);
+        assert!(pow_is_solved("challenge-a", &solution, 12));
+        // The solver returns the first counter that works
+        let first: u64 = solution.parse().unwrap();
+        assert!((0..first).all(|n| !pow_is_solved("challenge-a", &n.to_string(), 12)));
+        assert!(pow_is_solved("challenge-b", "anything", 0));
+    }
+
+    #[test]
+    fn kinds_round_trip() {
+        for kind in [ChallengeKind::Pow, ChallengeKind::Captcha] {
+            assert_eq!(ChallengeKind::parse(kind.as_str()), Some(kind));
+        }
+        assert_eq!(ChallengeKind::parse("puzzle"), None);
+    }
+}
@@ -0,0 +1,390 @@
+//! Event schemas publishers register per version, and decoding of raw
+//! on-chain events against them. A schema follows the Soroban spec's event
+//! entry: fixed prefix topics (by default the event name), then parameters
+//! carried either as further topics or in the data, which holds a single
+//! value, a vector or a symbol-keyed map.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use stellar_xdr::curr::{
+    ContractEvent, ContractEventBody, DiagnosticEvent, Hash, Limits, ReadXdr, ScVal,
+};
+
+use crate::stellar_key::{decode_contract_id, encode_contract_id};
+use crate::xdr::{scval_from_xdr, scval_to_json, scval_to_typed_json, SpecType};
+
+/// Events one version may register
+pub const MAX_EVENT_SCHEMAS: usize = 100;
+/// Soroban allows at most four topics per event
+pub const MAX_EVENT_TOPICS: usize = 4;
+const MAX_EVENT_NAME_LENGTH: usize = 60;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventDataFormat {
+    SingleValue,
+    Vec,
+    #[default]
+    Map,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventParamLocation {
+    Topic,
+    Data,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EventParam {
+    pub name: String,
+    /// Spec type, e.g. `{"type": "address"}`
+    pub value: Value,
+    pub location: EventParamLocation,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub doc: Option<String>,
+}
+
+/// One event a contract emits
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EventSchema {
+    pub name: String,
+    /// Symbols the topics start with; defaults to the event name
+    #[serde(default)]
+    pub prefix_topics: Vec<String>,
+    #[serde(default)]
+    pub params: Vec<EventParam>,
+    #[serde(default)]
+    pub data_format: EventDataFormat,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub doc: Option<String>,
+}
+
+fn is_symbol(s: &str) -> bool {
+    !s.is_empty() && s.len() <= 32 && s.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+impl EventSchema {
+    pub fn prefix(&self) -> Vec<&str> {
+        if self.prefix_topics.is_empty() {
+            vec![self.name.as_str()]
+        } else {
+            self.prefix_topics.iter().map(String::as_str).collect()
+        }
+    }
+
+    fn params_at(&self, location: EventParamLocation) -> impl Iterator<Item = &EventParam> {
+        self.params.iter().filter(move |p| p.location == location)
+    }
+
+    /// Names, prefix symbols and topic count must be what the host can emit
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.is_empty()
+            || self.name.len() > MAX_EVENT_NAME_LENGTH
+            || !self
+                .name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_')
+        {
+            return Err(format!(
+                "event name `{}` must be 1-{} characters of A-Z, a-z, 0-9 and _",
+                self.name, MAX_EVENT_NAME_LENGTH
+            ));
+        }
+        for prefix in self.prefix() {
+            if !is_symbol(prefix) {
+                return Err(format!(
+                    "{}: prefix topic `{}` is not a symbol",
+                    self.name, prefix
+                ));
+            }
+        }
+        let topics = self.prefix().len() + self.params_at(EventParamLocation::Topic).count();
+        if topics > MAX_EVENT_TOPICS {
+            return Err(format!(
+                "{}: events carry at most {} topics, this one has {}",
+                self.name, MAX_EVENT_TOPICS, topics
+            ));
+        }
+        let mut names = std::collections::HashSet::new();
+        for param in &self.params {
+            if !is_symbol(&param.name) {
+                return Err(format!(
+                    "{}: parameter name `{}` is not a symbol",
+                    self.name, param.name
+                ));
+            }
+            if !names.insert(param.name.as_str()) {
+                return Err(format!(
+                    "{}: parameter `{}` appears twice",
+                    self.name, param.name
+                ));
+            }
+            if param.value["type"].as_str().is_none_or(str::is_empty) {
+                return Err(format!("{}.{}: value needs a type", self.name, param.name));
+            }
+        }
+        if self.data_format == EventDataFormat::SingleValue
+            && self.params_at(EventParamLocation::Data).count() > 1
+        {
+            return Err(format!(
+                "{}: a single_value event has at most one data parameter",
+                self.name
+            ));
+        }
+        Ok(())
+    }
+
+    /// Named values of an event with this schema's shape, `None` otherwise
+    pub fn decode(
+        &self,
+        topics: &[ScVal],
+        data: &ScVal,
+        abi: &[Value],
+    ) -> Option<Map<String, Value>> {
+        let prefix = self.prefix();
+        let topic_params: Vec<&EventParam> = self.params_at(EventParamLocation::Topic).collect();
+        if topics.len() != prefix.len() + topic_params.len() {
+            return None;
+        }
+        let prefix_matches = topics
+            .iter()
+            .zip(&prefix)
+            .all(|(topic, expected)| match topic {
+                ScVal::Symbol(s) => s.0.to_utf8_string_lossy() == *expected,
+                ScVal::String(s) => s.0.to_utf8_string_lossy() == *expected,
+                _ => false,
+            });
+        if !prefix_matches {
+            return None;
+        }
+
+        let typed = |value: &ScVal, param: &EventParam| {
+            scval_to_typed_json(value, SpecType::new(&param.value, abi))
+        };
+        let mut fields = Map::new();
+        for (topic, param) in topics[prefix.len()..].iter().zip(topic_params) {
+            fields.insert(param.name.clone(), typed(topic, param));
+        }
+        let data_params: Vec<&EventParam> = self.params_at(EventParamLocation::Data).collect();
+        match (self.data_format, data) {
+            (EventDataFormat::SingleValue, ScVal::Void) if data_params.is_empty() => {}
+            (EventDataFormat::SingleValue, value) => {
+                let param = data_params.first()?;
+                fields.insert(param.name.clone(), typed(value, param));
+            }
+            (EventDataFormat::Vec, ScVal::Void) if data_params.is_empty() => {}
+            (EventDataFormat::Vec, ScVal::Vec(Some(items))) if items.len() == data_params.len() => {
+                for (item, param) in items.iter().zip(data_params) {
+                    fields.insert(param.name.clone(), typed(item, param));
+                }
+            }
+            (EventDataFormat::Map, ScVal::Void) if data_params.is_empty() => {}
+            (EventDataFormat::Map, ScVal::Map(Some(entries)))
+                if entries.len() == data_params.len() =>
+            {
+                for param in data_params {
+                    let entry = entries.iter().find(|e| match &e.key {
+                        ScVal::Symbol(s) => s.0.to_utf8_string_lossy() == param.name,
+                        _ => false,
+                    })?;
+                    fields.insert(param.name.clone(), typed(&entry.val, param));
+                }
+            }
+            _ => return None,
+        }
+        Some(fields)
+    }
+}
+
+/// An event as emitted: the contract, topics and data
+#[derive(Debug, Clone, PartialEq)]
+pub struct RawEvent {
+    pub contract_id: Option<String>,
+    pub topics: Vec<ScVal>,
+    pub data: ScVal,
+}
+
+impl RawEvent {
+    /// A base64 `ContractEvent` (from transaction meta) or `DiagnosticEvent`
+    pub fn from_xdr(base64: &str) -> Result<Self, String> {
+        let base64 = base64.trim();
+        let event = ContractEvent::from_xdr_base64(base64, Limits::none())
+            .or_else(|_| DiagnosticEvent::from_xdr_base64(base64, Limits::none()).map(|d| d.event))
+            .map_err(|e| format!("not a base64 ContractEvent or DiagnosticEvent: {}", e))?;
+        let ContractEventBody::V0(body) = event.body;
+        Ok(RawEvent {
+            contract_id: event
+                .contract_id
+                .map(|Hash(hash)| encode_contract_id(&hash)),
+            topics: body.topics.to_vec(),
+            data: body.data,
+        })
+    }
+
+    /// Topics and data as RPC `getEvents` returns them: base64 `ScVal`s
+    pub fn from_parts(
+        contract_id: Option<&str>,
+        topics: &[String],
+        data: &str,
+    ) -> Result<Self, String> {
+        if let Some(id) = contract_id.filter(|id| decode_contract_id(id).is_none()) {
+            return Err(format!("`{}` is not a C... contract ID", id));
+        }
+        Ok(RawEvent {
+            contract_id: contract_id.map(str::to_string),
+            topics: topics
+                .iter()
+                .enumerate()
+                .map(|(i, topic)| scval_from_xdr(topic).map_err(|e| format!("topic {}: {}", i, e)))
+                .collect::<Result<_, _>>()?,
+            data: scval_from_xdr(data).map_err(|e| format!("data: {}", e))?,
+        })
+    }
+}
+
+/// An event decoded against a version's schemas
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DecodedEvent {
+    /// The schema it matched; `None` when none did
+    pub event: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub contract_id: Option<String>,
+    /// Named, typed values of a matched event
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub fields: Map<String, Value>,
+    /// Topics and data without types, for events no schema matched
+    pub topics: Vec<Value>,
+    pub data: Value,
+}
+
+/// Decode `raw` with the first schema whose shape it has
+pub fn decode_event(schemas: &[EventSchema], abi: &[Value], raw: &RawEvent) -> DecodedEvent {
+    let matched = schemas
+        .iter()
+        .find_map(|schema| Some((schema, schema.decode(&raw.topics, &raw.data, abi)?)));
+    DecodedEvent {
+        event: matched.as_ref().map(|(schema, _)| schema.name.clone()),
+        contract_id: raw.contract_id.clone(),
+        fields: matched.map(|(_, fields)| fields).unwrap_or_default(),
+        topics: raw.topics.iter().map(scval_to_json).collect(),
+        data: scval_to_json(&raw.data),
+    }
+}
+
+/// PUT /api/contracts/:id/versions/:version/events — replaces the version's schemas
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegisterEventSchemasRequest {
+    pub events: Vec<EventSchema>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct EventSchemasQuery {
+    pub version: Option<String>,
+}
+
+/// GET /api/contracts/:id/events
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventSchemasResponse {
+    pub contract_id: String,
+    pub version: String,
+    pub events: Vec<EventSchema>,
+}
+
+/// POST /api/contracts/:id/events/decode — either `event` (a `ContractEvent`
+/// or `DiagnosticEvent`) or `topics` and `data` as base64 `ScVal`s
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DecodeEventRequest {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub event: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub topics: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<String>,
+}
+
+impl DecodeEventRequest {
+    pub fn raw_event(&self) -> Result<RawEvent, String> {
+        match (&self.event, &self.data) {
+            (Some(event), None) if self.topics.is_empty() => RawEvent::from_xdr(event),
+            (None, Some(data)) => RawEvent::from_parts(None, &self.topics, data),
+            _ => Err("give either `event`, or `topics` and `data`".to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stellar_key::encode_account_id;
+    use crate::xdr::{json_to_scval, scval_to_xdr};
+    use serde_json::json;
+
+    fn transfer() -> EventSchema {
+        serde_json::from_value(json!({
+            "name": "transfer",
+            "params": [
+                {"name": "from", "value": {"type": "address"}, "location": "topic"},
+                {"name": "to", "value": {"type": "address"}, "location": "topic"},
+                {"name": "amount", "value": {"type": "i128"}, "location": "data"}
+            ],
+            "data_format": "single_value"
+        }))
+        .unwrap()
+    }
+
+    fn scval(value: Value, ty: Value) -> ScVal {
+        json_to_scval(&value, SpecType::new(&ty, &[])).unwrap()
+    }
+
+    #[test]
+    fn decodes_events_matching_a_schema() {
+        let from = encode_account_id(&[1u8; 32]);
+        let to = encode_account_id(&[2u8; 32]);
+        let address = json!({"type": "address"});
+        let topics = [
+            scval(json!("transfer"), json!({"type": "symbol"})),
+            scval(json!(from), address.clone()),
+            scval(json!(to), address),
+        ];
+        let data = scval(json!("250"), json!({"type": "i128"}));
+        let raw = RawEvent::from_parts(
+            None,
+            &topics
+                .iter()
+                .map(|t| scval_to_xdr(t).unwrap())
+                .collect::<Vec<_>>(),
+            &scval_to_xdr(&data).unwrap(),
+        )
+        .unwrap();
+
+        let decoded = decode_event(&[transfer()], &[], &raw);
+        assert_eq!(decoded.event.as_deref(), Some("transfer"));
+        assert_eq!(
+            Value::Object(decoded.fields),
+            json!({"from": from, "to": to, "amount": "250"})
+        );
+
+        let other = RawEvent {
+            topics: vec![scval(json!("mint"), json!({"type": "symbol"}))],
+            ..raw
+        };
+        let decoded = decode_event(&[transfer()], &[], &other);
+        assert_eq!(decoded.event, None);
+        assert_eq!(decoded.topics, vec![json!("mint")]);
+    }
+
+    #[test]
+    fn validation_rejects_what_the_host_cannot_emit() {
+        assert!(transfer().validate().is_ok());
+        let mut crowded = transfer();
+        crowded.prefix_topics = vec!["token".into(), "transfer".into()];
+        crowded.params[2].location = EventParamLocation::Topic;
+        assert!(crowded.validate().unwrap_err().contains("at most 4 topics"));
+        let mut spaced = transfer();
+        spaced.prefix_topics = vec!["two words".into()];
+        assert!(spaced.validate().is_err());
+    }
+}
@@ -1,11 +1,46 @@
 pub mod abi;
+pub mod abi_format;
+pub mod abi_schema;
+pub mod challenge;
 pub mod error;
+pub mod event_schema;
+pub mod license;
 pub mod models;
+pub mod namespace;
+pub mod problem;
+pub mod provenance;
+pub mod quality;
+pub mod repository;
+pub mod sbom;
 pub mod semver;
+pub mod stellar_key;
+pub mod support;
+pub mod token;
+pub mod toolchain;
 pub mod upgrade;
+pub mod validation;
+pub mod web_urls;
+pub mod xdr;
 
 pub use abi::*;
+pub use abi_format::*;
+pub use abi_schema::*;
+pub use challenge::*;
 pub use error::*;
+pub use event_schema::*;
+pub use license::*;
 pub use models::*;
+pub use namespace::*;
+pub use problem::*;
+pub use provenance::*;
+pub use quality::*;
+pub use sbom::*;
 pub use semver::*;
+pub use stellar_key::*;
+pub use support::*;
+pub use token::*;
+pub use toolchain::*;
 pub use upgrade::*;
+pub use validation::*;
+pub use web_urls::*;
+pub use xdr::*;
@@ -0,0 +1,214 @@
+//! SPDX license expression validation
+//! Supports simple expressions like `MIT`, `Apache-2.0 OR MIT`,
+//! `GPL-2.0-or-later WITH Classpath-exception-2.0` and `LicenseRef-*` ids.
+
+/// Commonly used SPDX license identifiers accepted by the registry
+pub const SPDX_LICENSE_IDS: &[&str] = &[
+    "0BSD",
+    "AGPL-3.0-only",
+    "AGPL-3.0-or-later",
+    "Apache-2.0",
+    "BSD-2-Clause",
+    "BSD-3-Clause",
+    "BSL-1.0",
+    "BUSL-1.1",
+    "CC0-1.0",
+    "CC-BY-4.0",
+    "CC-BY-SA-4.0",
+    "EPL-2.0",
+    "GPL-2.0-only",
+    "GPL-2.0-or-later",
+    "GPL-3.0-only",
+    "GPL-3.0-or-later",
+    "ISC",
+    "LGPL-2.1-only",
+    "LGPL-2.1-or-later",
+    "LGPL-3.0-only",
+    "LGPL-3.0-or-later",
+    "MIT",
+    "MIT-0",
+    "MPL-2.0",
+    "OSL-3.0",
+    "Unlicense",
+    "Zlib",
+];
+
+/// SPDX license exception identifiers usable after `WITH`
+pub const SPDX_EXCEPTION_IDS: &[&str] = &[
+    "Classpath-exception-2.0",
+    "GCC-exception-3.1",
+    "LLVM-exception",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token<'a> {
+    Open,
+    Close,
+    And,
+    Or,
+    With,
+    Id(&'a str),
+}
+
+fn tokenize(expr: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    for word in expr.split_whitespace() {
+        let mut rest = word;
+        while let Some(stripped) = rest.strip_prefix('(') {
+            tokens.push(Token::Open);
+            rest = stripped;
+        }
+        let mut closes = 0;
+        while let Some(stripped) = rest.strip_suffix(')') {
+            closes += 1;
+            rest = stripped;
+        }
+        if !rest.is_empty() {
+            tokens.push(match rest {
+                "AND" => Token::And,
+                "OR" => Token::Or,
+                "WITH" => Token::With,
+                id => Token::Id(id),
+            });
+        }
+        tokens.extend(std::iter::repeat_n(Token::Close, closes));
+    }
+    tokens
+}
+
+fn is_license_id(id: &str) -> bool {
+    let id = id.strip_suffix('+').unwrap_or(id);
+    (id.starts_with("LicenseRef-") && id.len() > "LicenseRef-".len())
+        || SPDX_LICENSE_IDS
+            .iter()
+            .any(|known| known.eq_ignore_ascii_case(id))
+}
+
+fn is_exception_id(id: &str) -> bool {
+    SPDX_EXCEPTION_IDS
+        .iter()
+        .any(|known| known.eq_ignore_ascii_case(id))
+}
+
+struct Parser<'a> {
+    tokens: Vec<Token<'a>>,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<&Token<'a>> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token<'a>> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    // expression := term (("AND" | "OR") term)*
+    fn expression(&mut self) -> Result<(), String> {
+        self.term()?;
+        while matches!(self.peek(), Some(Token::And) | Some(Token::Or)) {
+            self.next();
+            self.term()?;
+        }
+        Ok(())
+    }
+
+    // term := "(" expression ")" | license-id ["WITH" exception-id]
+    fn term(&mut self) -> Result<(), String> {
+        match self.next() {
+            Some(Token::Open) => {
+                self.expression()?;
+                match self.next() {
+                    Some(Token::Close) => Ok(()),
+                    _ => Err("unbalanced parentheses".to_string()),
+                }
+            }
+            Some(Token::Id(id)) => {
+                if !is_license_id(id) {
+                    return Err(format!("unknown SPDX license identifier '{}'", id));
+                }
+                if self.peek() == Some(&Token::With) {
+                    self.next();
+                    match self.next() {
+                        Some(Token::Id(exception)) if is_exception_id(exception) => {}
+                        Some(Token::Id(exception)) => {
+                            return Err(format!("unknown SPDX exception '{}'", exception))
+                        }
+                        _ => return Err("expected exception identifier after WITH".to_string()),
+                    }
+                }
+                Ok(())
+            }
+            Some(other) => Err(format!("unexpected token {:?}", other)),
+            None => Err("unexpected end of expression".to_string()),
+        }
+    }
+}
+
+/// Validate an SPDX license expression, returning a human-readable reason on failure
+pub fn validate_spdx_expression(expr: &str) -> Result<(), String> {
+    let tokens = tokenize(expr.trim());
+    if tokens.is_empty() {
+        return Err("license expression is empty".to_string());
+    }
+
+    let mut parser = Parser { tokens, pos: 0 };
+    parser.expression()?;
+    if parser.pos < parser.tokens.len() {
+        return Err("unexpected trailing tokens in license expression".to_string());
+    }
+    Ok(())
+}
+
+/// Case-insensitive POSIX regex (Postgres `~*`) matching license expressions
+/// that contain `component` as a whole term, so `MIT` finds
+/// `Apache-2.0 OR MIT` but not `MIT-0`
+pub fn license_component_pattern(component: &str) -> String {
+    let mut escaped = String::with_capacity(component.len());
+    for ch in component.trim().chars() {
+        if "\\.+*?()[]{}|^$".contains(ch) {
+            escaped.push('\\');
+        }
+        escaped.push(ch);
+    }
+    format!(r"(^|[\s(]){}($|[\s)])", escaped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_valid_expressions() {
+        assert!(validate_spdx_expression("MIT").is_ok());
+        assert!(validate_spdx_expression("Apache-2.0 OR MIT").is_ok());
+        assert!(validate_spdx_expression("(MIT AND BSD-3-Clause) OR Apache-2.0").is_ok());
+        assert!(validate_spdx_expression("GPL-2.0-or-later WITH Classpath-exception-2.0").is_ok());
+        assert!(validate_spdx_expression("LicenseRef-Proprietary").is_ok());
+    }
+
+    #[test]
+    fn rejects_invalid_expressions() {
+        assert!(validate_spdx_expression("").is_err());
+        assert!(validate_spdx_expression("NotALicense").is_err());
+        assert!(validate_spdx_expression("MIT OR").is_err());
+        assert!(validate_spdx_expression("(MIT OR Apache-2.0").is_err());
+        assert!(validate_spdx_expression("MIT WITH Bogus-exception").is_err());
+    }
+
+    #[test]
+    fn component_patterns_match_whole_terms() {
+        assert_eq!(license_component_pattern("MIT"), r"(^|[\s(])MIT($|[\s)])");
+        assert_eq!(
+            license_component_pattern(" Apache-2.0 "),
+            r"(^|[\s(])Apache-2\.0($|[\s)])"
+        );
+        assert_eq!(
+            license_component_pattern("GPL-2.0+"),
+            r"(^|[\s(])GPL-2\.0\+($|[\s)])"
+        );
+    }
+}
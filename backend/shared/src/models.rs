@@ -33,6 +33,89 @@ pub struct Contract {
     /// Per-network config: { "mainnet": { contract_id, is_verified, min_version, max_version }, ... }
     #[serde(default)]
     pub network_configs: Option<serde_json::Value>,
+    /// Well-known interfaces the latest spec implements (e.g. "sep-41")
+    #[serde(default)]
+    pub implements: Vec<String>,
+    /// SPDX license expression declared by the publisher
+    #[serde(default)]
+    pub license: Option<String>,
+    /// Publisher namespace for `@namespace/name` contracts; None for unscoped names
+    #[serde(default)]
+    pub namespace: Option<String>,
+    /// Visible only to members of `organization_id`
+    #[serde(default)]
+    pub is_private: bool,
+    /// Organization that owns the contract, if any
+    #[serde(default)]
+    pub organization_id: Option<Uuid>,
+    /// Project links keyed by label, e.g. { "homepage": "https://..." }
+    #[serde(default)]
+    pub links: serde_json::Value,
+    /// Registry contract this one was forked from, recorded at publish
+    #[serde(default)]
+    pub forked_from: Option<Uuid>,
+    /// Number of users who starred the contract
+    #[serde(default)]
+    pub star_count: i64,
+    /// 0–100 combining downloads, freshness, verification, audits and stars;
+    /// recomputed nightly
+    #[serde(default)]
+    pub quality_score: f64,
+    /// Quarantined or taken down after abuse reports
+    #[serde(default)]
+    pub moderation_status: ModerationStatus,
+    #[serde(flatten)]
+    #[sqlx(flatten)]
+    pub urls: ProjectUrls,
+}
+
+/// Repository, homepage and documentation URLs declared by the publisher
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, FromRow)]
+pub struct ProjectUrls {
+    #[serde(default)]
+    pub repository_url: Option<String>,
+    #[serde(default)]
+    pub homepage: Option<String>,
+    #[serde(default)]
+    pub documentation_url: Option<String>,
+}
+
+impl ProjectUrls {
+    /// Field name and value of each URL, in display order
+    pub fn fields(&self) -> [(&'static str, Option<&str>); 3] {
+        [
+            ("repository_url", self.repository_url.as_deref()),
+            ("homepage", self.homepage.as_deref()),
+            ("documentation_url", self.documentation_url.as_deref()),
+        ]
+    }
+
+    /// Check every URL that is set, returning the first offending field
+    pub fn validate(&self) -> Result<(), (&'static str, String)> {
+        for (field, url) in self.fields() {
+            if let Some(url) = url {
+                validate_project_url(url).map_err(|e| (field, e))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Project links must be absolute `https://` or `http://` URLs with a host, so
+/// schemes such as `javascript:` or `file:` never reach a rendered page
+pub fn validate_project_url(url: &str) -> Result<(), String> {
+    let rest = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))
+        .ok_or_else(|| "must start with https:// or http://".to_string())?;
+    let host = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    if host.is_empty() || host.starts_with('@') {
+        return Err("must include a host name".to_string());
+    }
+    if url.chars().any(|c| c.is_whitespace() || c.is_control()) {
+        return Err("must not contain whitespace".to_string());
+    }
+    Ok(())
 }
 
 /// Response for GET /contracts/:id with optional network-specific slice (Issue #43)
@@ -46,6 +129,12 @@ pub struct ContractGetResponse {
     /// When ?network= is set, that network's config slice
     #[serde(skip_serializing_if = "Option::is_none")]
     pub network_config: Option<NetworkConfig>,
+    /// Set when the contract was looked up by a deprecated former name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alias_notice: Option<String>,
+    /// Average of visible user reviews; absent until the first review
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rating: Option<ContractRating>,
 }
 
 /// Per-network config: address, verified status, min/max version (Issue #43)
@@ -60,7 +149,7 @@ pub struct NetworkConfig {
 }
 
 /// Network where the contract is deployed
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
 #[sqlx(type_name = "network_type", rename_all = "lowercase")]
 #[serde(rename_all = "lowercase")]
 pub enum Network {
@@ -156,6 +245,17 @@ pub struct Publisher {
     pub github_url: Option<String>,
     pub website: Option<String>,
     pub created_at: DateTime<Utc>,
+    /// Namespace claimed by this publisher for `@namespace/name` contracts
+    #[serde(default)]
+    pub namespace: Option<String>,
+    /// Tenant the namespace was claimed in; namespaces are unique per tenant
+    #[serde(default)]
+    pub namespace_tenant_id: Option<Uuid>,
+    /// Stellar account whose key signed an identity challenge for this publisher
+    #[serde(default)]
+    pub verified_account: Option<String>,
+    #[serde(default)]
+    pub identity_verified_at: Option<DateTime<Utc>>,
 }
 
 /// Contract interaction statistics
@@ -209,8 +309,39 @@ pub struct PublishRequest {
     // Dependencies (new field)
     #[serde(default)]
     pub dependencies: Vec<DependencyDeclaration>,
+    /// SPDX license expression (e.g. "MIT OR Apache-2.0")
+    #[serde(default)]
+    pub license: Option<String>,
+    /// Full license text, for custom `LicenseRef-*` licenses
+    #[serde(default)]
+    pub license_file: Option<String>,
+    #[serde(flatten)]
+    pub urls: ProjectUrls,
+    /// Registry contract this one is based on (UUID or @namespace/name)
+    #[serde(default)]
+    pub forked_from: Option<String>,
+}
+
+/// What `POST /api/contracts?dry_run=true` found: the request passed every
+/// check and would be published as described
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PublishPreview {
+    pub contract_id: String,
+    pub name: String,
+    pub network: Network,
+    pub publisher_address: String,
+    /// No publisher exists for the address yet; publishing creates one
+    pub creates_publisher: bool,
+    /// Publishing claims the name's namespace for the publisher
+    pub claims_namespace: bool,
 }
 
+/// Conventional file name for a version's release notes, in archives and when publishing
+pub const RELEASE_NOTES_FILE: &str = "release_notes.md";
+
+/// Largest accepted release notes, in bytes
+pub const MAX_RELEASE_NOTES_BYTES: usize = 64 * 1024;
+
 /// Request to create a new contract version with ABI
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CreateContractVersionRequest {
@@ -228,6 +359,15 @@ pub struct CreateContractVersionRequest {
     pub publisher_key: Option<String>,
     #[serde(default)]
     pub signature_algorithm: Option<String>,
+    /// Contents of the project's Cargo.lock, used for SBOM generation
+    #[serde(default)]
+    pub cargo_lock: Option<String>,
+    /// Toolchain the WASM was built with, captured by the CLI
+    #[serde(default)]
+    pub toolchain: Option<crate::toolchain::BuildToolchain>,
+    /// Optional build provenance attestation for this version
+    #[serde(default)]
+    pub provenance: Option<crate::provenance::ProvenanceInput>,
 }
 
 // ────────────────────────────────────────────────────────────────────────────
@@ -340,6 +480,7 @@ pub enum SortBy {
     Deployments,
     Interactions,
     Relevance,
+    Quality,
 }
 
 /// Sorting order
@@ -350,6 +491,33 @@ pub enum SortOrder {
     Desc,
 }
 
+/// `?sort=` keys for contract listings
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ContractSort {
+    /// Install/interaction count
+    Downloads,
+    Updated,
+    Created,
+    Name,
+    /// Average review rating; unreviewed contracts sort as 0
+    Rating,
+    /// Number of users who starred the contract
+    Stars,
+    /// The nightly quality score
+    Quality,
+}
+
+impl ContractSort {
+    /// Direction used when `?order=` is omitted: newest/most first, names A→Z
+    pub fn default_order(self) -> SortOrder {
+        match self {
+            ContractSort::Name => SortOrder::Asc,
+            _ => SortOrder::Desc,
+        }
+    }
+}
+
 /// Search/filter parameters for contracts
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ContractSearchParams {
@@ -361,11 +529,100 @@ pub struct ContractSearchParams {
     pub category: Option<String>,
     pub tags: Option<Vec<String>>,
     pub maturity: Option<MaturityLevel>,
+    /// Only contracts implementing this well-known interface (e.g. "sep-41")
+    pub implements: Option<String>,
+    /// Only contracts whose SPDX license expression has this license as a term
+    pub license: Option<String>,
+    /// Only contracts in this publisher namespace (without the leading '@')
+    pub namespace: Option<String>,
+    /// Only contracts with at least one attached third-party audit report
+    pub audited: Option<bool>,
+    /// Only contracts registered at or after this time (RFC 3339)
+    pub created_after: Option<DateTime<Utc>>,
+    /// Only contracts changed at or after this time (RFC 3339)
+    pub updated_after: Option<DateTime<Utc>>,
+    /// Only contracts with at least this many downloads (recorded interactions)
+    pub min_downloads: Option<i64>,
+    /// Only contracts whose latest uploaded WASM is at most this many bytes
+    pub max_wasm_size: Option<i64>,
+    /// Include category, tag and verification counts in the response
+    pub facets: Option<bool>,
     pub page: Option<i64>,
     #[serde(alias = "page_size")]
     pub limit: Option<i64>,
+    /// `next_cursor` from a previous page; takes precedence over `page` and `limit`
+    pub cursor: Option<String>,
     pub sort_by: Option<SortBy>,
     pub sort_order: Option<SortOrder>,
+    /// Takes precedence over `sort_by`; ties are broken by contract id
+    pub sort: Option<ContractSort>,
+    /// Direction for `sort`; defaults to [`ContractSort::default_order`]
+    pub order: Option<SortOrder>,
+}
+
+/// Filters for `GET /api/contracts/random`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RandomContractQuery {
+    pub category: Option<String>,
+    pub network: Option<Network>,
+}
+
+/// Opening and closing markers around matched terms in search highlights
+pub const HIGHLIGHT_START: &str = "<mark>";
+pub const HIGHLIGHT_END: &str = "</mark>";
+
+/// Matched fragments of a search hit, with matches wrapped in [`HIGHLIGHT_START`]/[`HIGHLIGHT_END`]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SearchHighlight {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// A contract in search results, with highlighted snippets when a query was given
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContractSearchHit {
+    #[serde(flatten)]
+    pub contract: Contract,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub highlight: Option<SearchHighlight>,
+    /// Test results attached to the latest version, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tests: Option<TestSummary>,
+}
+
+/// Contract search results, with a spelling suggestion when nothing matched exactly
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContractSearchResponse {
+    #[serde(flatten)]
+    pub results: PaginatedResponse<ContractSearchHit>,
+    /// Query with misspelled words replaced by known ones, e.g. "liquidity" for "liqudity"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub did_you_mean: Option<String>,
+    /// Present when the request set `facets=true`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub facets: Option<SearchFacets>,
+}
+
+/// "Refine by" counts across all contracts matching a search's filters
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SearchFacets {
+    pub categories: Vec<FacetCount>,
+    pub tags: Vec<FacetCount>,
+    pub verified: VerificationFacet,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, FromRow)]
+pub struct FacetCount {
+    pub value: String,
+    pub count: i64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct VerificationFacet {
+    pub verified: i64,
+    pub unverified: i64,
 }
 
 /// Pagination params for contract versions (limit/offset style)
@@ -381,6 +638,23 @@ fn default_version_limit() -> i64 {
     20
 }
 
+/// Query params for GET /api/contracts/:id/sbom
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SbomQuery {
+    /// Version to describe (defaults to the latest version)
+    pub version: Option<String>,
+    /// cyclonedx (default) or spdx
+    pub format: Option<String>,
+}
+
+/// Response for GET /api/contracts/:id/license
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContractLicenseResponse {
+    pub contract_id: String,
+    pub license: Option<String>,
+    pub license_text: Option<String>,
+}
+
 /// Paginated version response (limit/offset style per issue #32 spec)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PaginatedVersionResponse {
@@ -399,6 +673,16 @@ pub struct PaginatedResponse<T> {
     pub page: i64,
     #[serde(rename = "pages")]
     pub total_pages: i64,
+    #[serde(default)]
+    pub has_next: bool,
+    #[serde(default)]
+    pub has_prev: bool,
+    /// Pass as `?cursor=` (with the same filters) to fetch the next page
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+    /// Filters the server applied, keyed by query parameter
+    #[serde(default, skip_serializing_if = "std::collections::BTreeMap::is_empty")]
+    pub filters: std::collections::BTreeMap<String, serde_json::Value>,
 }
 
 impl<T> PaginatedResponse<T> {
@@ -408,13 +692,50 @@ impl<T> PaginatedResponse<T> {
         } else {
             0
         };
+        let has_next = page < total_pages;
         Self {
             items,
             total,
             page,
             total_pages,
+            has_next,
+            has_prev: page > 1,
+            next_cursor: has_next.then(|| PageCursor::new(page + 1, limit).encode()),
+            filters: Default::default(),
         }
     }
+
+    /// Echo the applied filters back to the client
+    pub fn with_filters(
+        mut self,
+        filters: std::collections::BTreeMap<String, serde_json::Value>,
+    ) -> Self {
+        self.filters = filters;
+        self
+    }
+}
+
+/// Position carried by `next_cursor`. Clients treat the encoded form as opaque.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PageCursor {
+    pub page: i64,
+    pub limit: i64,
+}
+
+impl PageCursor {
+    pub fn new(page: i64, limit: i64) -> Self {
+        Self { page, limit }
+    }
+
+    pub fn encode(&self) -> String {
+        format!("p{}.{}", self.page, self.limit)
+    }
+
+    pub fn decode(raw: &str) -> Option<Self> {
+        let (page, limit) = raw.strip_prefix('p')?.split_once('.')?;
+        let cursor = Self::new(page.parse().ok()?, limit.parse().ok()?);
+        (cursor.page >= 1 && cursor.limit >= 1).then_some(cursor)
+    }
 }
 
 // ═══════════════════════════════════════════════════════════════════════════
@@ -1838,6 +2159,37 @@ pub struct ContractChangelogEntry {
     pub breaking: bool,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub breaking_changes: Vec<String>,
+    /// The version this entry is compared against (the next lower semver)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub previous_version: Option<String>,
+    /// Every interface change: functions, types and events
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub changes: Vec<ChangelogChange>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub metadata_changes: Vec<MetadataChange>,
+    /// Size of the published WASM, when it was uploaded as an artifact
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wasm_size_bytes: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wasm_size_delta: Option<i64>,
+}
+
+/// One interface difference from the previous version
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ChangelogChange {
+    /// `breaking` or `non_breaking`
+    pub severity: String,
+    /// e.g. `function_added`, `type_field_removed`, `event_changed`
+    pub category: String,
+    pub message: String,
+}
+
+/// A version-level field that differs from the previous version
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MetadataChange {
+    pub field: String,
+    pub old: Option<String>,
+    pub new: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -1845,3 +2197,1879 @@ pub struct ContractChangelogResponse {
     pub contract_id: Uuid,
     pub entries: Vec<ContractChangelogEntry>,
 }
+
+// ────────────────────────────────────────────────────────────────────────────
+// Batch contract lookup
+// ────────────────────────────────────────────────────────────────────────────
+
+/// Maximum number of lookups accepted by POST /api/contracts/batch
+pub const MAX_BATCH_LOOKUP: usize = 100;
+
+/// Request body for POST /api/contracts/batch.
+/// Each entry is a registry UUID, an on-chain contract ID, a contract name,
+/// or any of those followed by `@version`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchContractLookupRequest {
+    pub ids: Vec<String>,
+    /// Also resolve bare lookups to their latest version, and return the
+    /// stored artifact hashes and support policy of every resolved version
+    #[serde(default)]
+    pub resolve_artifacts: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchContractLookupResult {
+    /// The lookup string exactly as submitted
+    pub query: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contract: Option<Contract>,
+    /// Set when the lookup pinned a version, or for `resolve_artifacts` the latest one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<ContractVersion>,
+    /// Stored files of `version`, with `resolve_artifacts`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub artifacts: Vec<ArtifactRef>,
+    /// Support policy of `version`, with `resolve_artifacts`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub support: Option<crate::support::VersionSupport>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchContractLookupResponse {
+    pub results: Vec<BatchContractLookupResult>,
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// Admin overview
+// ────────────────────────────────────────────────────────────────────────────
+
+/// A contract ranked by the storage its versions and ABIs occupy
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct LargestContract {
+    pub id: Uuid,
+    pub contract_id: String,
+    pub name: String,
+    pub version_count: i64,
+    pub abi_bytes: i64,
+}
+
+/// Versions published over trailing windows
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PublishRate {
+    pub last_hour: i64,
+    pub last_24h: i64,
+    pub last_7d: i64,
+}
+
+/// Mean latency of one route since the process started
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EndpointLatency {
+    pub method: String,
+    pub path: String,
+    pub requests: u64,
+    pub mean_ms: f64,
+}
+
+/// How a network's Soroban RPC answered its last health probe
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RpcHealthStatus {
+    Healthy,
+    /// Answering, but its latest ledger has stopped advancing
+    Lagging,
+    /// Not answering, or reporting itself unhealthy
+    Unavailable,
+    /// Not probed yet
+    Unknown,
+}
+
+/// Availability and ledger lag of one network's RPC
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RpcNetworkHealth {
+    pub network: Network,
+    pub status: RpcHealthStatus,
+    pub latest_ledger: Option<u32>,
+    /// Seconds since the latest ledger last advanced
+    pub ledger_lag_secs: Option<i64>,
+    pub latency_ms: Option<u64>,
+    pub checked_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Response for GET /api/admin/overview
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AdminOverview {
+    pub database_size_bytes: i64,
+    pub largest_contracts: Vec<LargestContract>,
+    pub publish_rate: PublishRate,
+    /// Contracts whose most recent verification attempt failed
+    pub failed_verifications: i64,
+    /// Queued work by queue name
+    pub pending_jobs: std::collections::BTreeMap<String, i64>,
+    pub slowest_endpoints: Vec<EndpointLatency>,
+    /// Soroban RPC health per network
+    #[serde(default)]
+    pub rpc: Vec<RpcNetworkHealth>,
+    pub generated_at: DateTime<Utc>,
+}
+
+/// Stored content of one kind that nothing references any more
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrphanedArtifacts {
+    /// `staged_parts` (parts of finished or expired publish sessions) or
+    /// `abis` (ABIs whose version no longer exists)
+    pub kind: String,
+    pub count: i64,
+    pub bytes: i64,
+}
+
+/// Response for GET (dry run) and POST /api/admin/gc
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArtifactGcReport {
+    /// `true` when nothing was deleted and the figures are what a run would reclaim
+    pub dry_run: bool,
+    pub orphans: Vec<OrphanedArtifacts>,
+    pub total_bytes: i64,
+    pub generated_at: DateTime<Utc>,
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// Registry feature flags
+// ────────────────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct RegistryFeatureFlag {
+    pub name: String,
+    pub description: Option<String>,
+    pub enabled: bool,
+    /// Share of subjects (0–100) the flag is on for once enabled
+    pub rollout_percentage: i16,
+    /// Environments the flag applies to; empty means all
+    pub environments: Vec<String>,
+    pub updated_by: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Body for PUT /api/admin/flags/:name; omitted fields keep their current value
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateRegistryFlagRequest {
+    pub enabled: Option<bool>,
+    pub rollout_percentage: Option<i16>,
+    pub environments: Option<Vec<String>>,
+    pub description: Option<String>,
+    /// Operator making the change, recorded in the audit trail
+    pub changed_by: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct RegistryFlagAuditEntry {
+    pub id: Uuid,
+    pub flag_name: String,
+    pub changed_by: String,
+    pub previous: Option<serde_json::Value>,
+    pub current: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// CLI releases
+// ────────────────────────────────────────────────────────────────────────────
+
+/// A downloadable CLI binary for one target triple
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct CliRelease {
+    pub version: String,
+    pub target: String,
+    pub download_url: String,
+    /// Hex SHA-256 of the binary
+    pub sha256: String,
+    /// Base64 ed25519 signature over `"{version}:{target}:{sha256}"`
+    pub signature: String,
+    pub release_notes: Option<String>,
+    pub published_at: DateTime<Utc>,
+}
+
+/// Query params for GET /api/cli/releases/latest
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CliReleaseQuery {
+    /// Rust target triple, e.g. `x86_64-unknown-linux-gnu`
+    pub target: String,
+}
+
+/// Body for POST /api/admin/cli/releases
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PublishCliReleaseRequest {
+    pub version: String,
+    pub target: String,
+    pub download_url: String,
+    pub sha256: String,
+    pub signature: String,
+    pub release_notes: Option<String>,
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// Publisher contract listing
+// ────────────────────────────────────────────────────────────────────────────
+
+/// Query params for GET /api/publishers/:id/contracts
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PublisherContractsQuery {
+    pub page: Option<i64>,
+    #[serde(alias = "page_size")]
+    pub limit: Option<i64>,
+}
+
+/// One of a publisher's contracts with its download total
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct PublisherContract {
+    #[serde(flatten)]
+    #[sqlx(flatten)]
+    pub contract: Contract,
+    pub downloads: i64,
+}
+
+/// Totals across every contract of a publisher, not just the current page
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PublisherContractSummary {
+    pub total_contracts: i64,
+    pub verified_contracts: i64,
+    /// Share of contracts that are verified, 0–100
+    pub verified_percentage: f64,
+    pub total_downloads: i64,
+}
+
+/// Response for GET /api/publishers/:id/contracts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PublisherContractsResponse {
+    pub publisher_id: Uuid,
+    /// Set when the publisher proved control of their Stellar account
+    #[serde(default)]
+    pub verified_account: Option<String>,
+    pub summary: PublisherContractSummary,
+    pub contracts: Vec<PublisherContract>,
+    pub page: i64,
+    pub pages: i64,
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// Saved searches
+// ────────────────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct SavedSearch {
+    pub id: Uuid,
+    pub owner_address: String,
+    pub name: String,
+    pub query: String,
+    pub network: Option<Network>,
+    pub category: Option<String>,
+    pub webhook_url: Option<String>,
+    pub email: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Body for POST /api/searches; saving under an existing name replaces it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateSavedSearchRequest {
+    pub name: String,
+    pub query: String,
+    #[serde(default)]
+    pub network: Option<Network>,
+    #[serde(default)]
+    pub category: Option<String>,
+    /// Receives a JSON POST for every new match
+    #[serde(default)]
+    pub webhook_url: Option<String>,
+    /// Gets an email queued (via notification_logs) for every new match
+    #[serde(default)]
+    pub email: Option<String>,
+}
+
+/// A newly published contract that matched a saved search
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct SavedSearchMatch {
+    pub id: i64,
+    pub saved_search_id: Uuid,
+    pub contract_id: Uuid,
+    pub contract_name: String,
+    pub matched_at: DateTime<Utc>,
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// Collections
+// ────────────────────────────────────────────────────────────────────────────
+
+/// A named, ordered list of contracts curated by a user
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Collection {
+    pub id: Uuid,
+    pub owner_address: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub is_public: bool,
+    /// Contracts in the collection that the caller can see
+    pub item_count: i64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A contract in a collection, in list order
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct CollectionItem {
+    pub position: i32,
+    /// Registry UUID of the contract
+    pub id: Uuid,
+    /// On-chain contract ID
+    pub contract_id: String,
+    pub namespace: Option<String>,
+    pub name: String,
+    pub network: Network,
+    pub is_verified: bool,
+    pub note: Option<String>,
+    pub added_at: DateTime<Utc>,
+}
+
+/// GET /api/collections/:id
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CollectionDetail {
+    #[serde(flatten)]
+    pub collection: Collection,
+    pub items: Vec<CollectionItem>,
+}
+
+/// Body for POST /api/collections
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateCollectionRequest {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Private (the default) collections are only visible to their owner
+    #[serde(default)]
+    pub is_public: bool,
+}
+
+/// Body for PATCH /api/collections/:id; omitted fields are left unchanged
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UpdateCollectionRequest {
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub is_public: Option<bool>,
+}
+
+/// Body for POST /api/collections/:id/items
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AddCollectionItemRequest {
+    /// Registry UUID, on-chain ID or `@namespace/name`
+    pub contract: String,
+    #[serde(default)]
+    pub note: Option<String>,
+    /// 0-based position to insert at; appended when omitted
+    #[serde(default)]
+    pub position: Option<i32>,
+}
+
+/// Body for PUT /api/collections/:id/items: registry UUIDs in their new order.
+/// Items left out keep their relative order after the listed ones.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReorderCollectionRequest {
+    pub contracts: Vec<Uuid>,
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// Stars
+// ────────────────────────────────────────────────────────────────────────────
+
+/// Response of PUT/DELETE /api/contracts/:id/star
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StarStatus {
+    pub contract_id: Uuid,
+    pub starred: bool,
+    pub star_count: i64,
+}
+
+/// A contract the signed-in user starred, newest star first
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct StarredContract {
+    #[serde(flatten)]
+    #[sqlx(flatten)]
+    pub contract: Contract,
+    pub starred_at: DateTime<Utc>,
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// Account data export and deletion
+// ────────────────────────────────────────────────────────────────────────────
+
+/// Days between DELETE /api/me and the account's data being removed
+pub const ACCOUNT_DELETION_GRACE_DAYS: i64 = 30;
+
+/// A contract the account starred
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AccountStar {
+    pub contract_id: Uuid,
+    pub starred_at: DateTime<Utc>,
+}
+
+/// An organization the account belongs to
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AccountMembership {
+    pub organization: String,
+    pub role: String,
+    pub added_at: DateTime<Utc>,
+}
+
+/// A pending account deletion; cancellable until `scheduled_for`
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AccountDeletion {
+    pub address: String,
+    pub requested_at: DateTime<Utc>,
+    pub scheduled_for: DateTime<Utc>,
+}
+
+/// GET /api/me/export: everything the registry holds about the signed-in account
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccountExport {
+    pub address: String,
+    pub exported_at: DateTime<Utc>,
+    /// Publisher profile, if the account has published
+    pub profile: Option<Publisher>,
+    /// Key metadata, including revoked and expired keys; never the secrets
+    pub api_keys: Vec<ApiKey>,
+    pub reviews: Vec<ContractReview>,
+    pub stars: Vec<AccountStar>,
+    pub saved_searches: Vec<SavedSearch>,
+    pub collections: Vec<CollectionDetail>,
+    pub organizations: Vec<AccountMembership>,
+    /// Contract changes made by the account; kept after account deletion
+    pub audit_entries: Vec<ContractAuditLog>,
+    pub pending_deletion: Option<AccountDeletion>,
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// Reviews
+// ────────────────────────────────────────────────────────────────────────────
+
+pub const MIN_REVIEW_RATING: i16 = 1;
+pub const MAX_REVIEW_RATING: i16 = 5;
+
+/// A user's rating of a contract; one per account per contract
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ContractReview {
+    pub id: i32,
+    pub contract_id: Uuid,
+    pub reviewer_address: String,
+    /// Whole stars, 1–5
+    pub rating: i16,
+    pub comment: Option<String>,
+    /// Hidden by a moderator; hidden reviews are excluded from listings and averages
+    pub hidden: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Body for POST /api/contracts/:id/reviews
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateReviewRequest {
+    pub rating: i16,
+    #[serde(default)]
+    pub comment: Option<String>,
+}
+
+/// Aggregate of a contract's visible reviews
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ContractRating {
+    pub average: f64,
+    pub count: i64,
+}
+
+/// Response for GET /api/contracts/:id/reviews
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContractReviewsResponse {
+    pub rating: Option<ContractRating>,
+    pub reviews: Vec<ContractReview>,
+    pub page: i64,
+    pub pages: i64,
+}
+
+/// Body for POST /api/contracts/:id/reviews/:review_id/flag
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FlagReviewRequest {
+    pub reason: String,
+}
+
+/// A review with unresolved abuse reports, for the moderation queue
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct FlaggedReview {
+    #[sqlx(flatten)]
+    #[serde(flatten)]
+    pub review: ContractReview,
+    pub open_flags: i64,
+    pub reasons: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReviewModerationAction {
+    /// Hide the review and close its reports
+    Hide,
+    /// Make a hidden review visible again
+    Restore,
+    /// Close the reports and leave the review visible
+    Dismiss,
+}
+
+/// Body for POST /api/admin/reviews/:review_id/moderate
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModerateReviewRequest {
+    pub action: ReviewModerationAction,
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// Abuse reports
+// ────────────────────────────────────────────────────────────────────────────
+
+/// Where a contract stands with the registry's moderators
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "moderation_status", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ModerationStatus {
+    #[default]
+    Active,
+    /// Hidden from listings and search while reports are looked into; still
+    /// reachable by ID
+    Quarantined,
+    /// Removed from the registry; its endpoints answer 410 Gone
+    TakenDown,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "abuse_reason", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum AbuseReason {
+    Malware,
+    Scam,
+    Impersonation,
+    Spam,
+    LicenseViolation,
+    Other,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "abuse_report_status", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum AbuseReportStatus {
+    Open,
+    Dismissed,
+    Quarantined,
+    TakenDown,
+}
+
+/// Body for POST /api/contracts/:id/report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportContractRequest {
+    pub reason: AbuseReason,
+    #[serde(default)]
+    pub details: Option<String>,
+    /// How moderators can reach the reporter, e.g. an email address
+    #[serde(default)]
+    pub reporter_contact: Option<String>,
+}
+
+/// Response of POST /api/contracts/:id/report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AbuseReportReceipt {
+    pub report_id: Uuid,
+    /// The reporter already had an open report on this contract; that one
+    /// is kept and this one was not recorded
+    pub duplicate: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AbuseReport {
+    pub id: Uuid,
+    pub contract_id: Uuid,
+    pub reason: AbuseReason,
+    pub details: Option<String>,
+    pub reporter_contact: Option<String>,
+    /// None for anonymous reports
+    pub reporter_address: Option<String>,
+    pub status: AbuseReportStatus,
+    pub resolution_note: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+/// A contract with open abuse reports, for GET /api/admin/reports
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModerationQueueEntry {
+    pub contract_id: Uuid,
+    /// On-chain contract address
+    pub contract_address: String,
+    pub name: String,
+    pub moderation_status: ModerationStatus,
+    pub reports: Vec<AbuseReport>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ModerationAction {
+    /// Close the reports and leave the contract as it is
+    Dismiss,
+    /// Hide the contract from listings and close its reports
+    Quarantine,
+    /// Remove the contract and close its reports
+    Takedown,
+    /// Make a quarantined or taken-down contract active again
+    Restore,
+}
+
+/// Body for POST /api/admin/contracts/:id/moderate
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModerateContractRequest {
+    pub action: ModerationAction,
+    /// Recorded on the reports this closes
+    #[serde(default)]
+    pub note: Option<String>,
+}
+
+/// Response of POST /api/admin/contracts/:id/moderate
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModerationOutcome {
+    pub contract_id: Uuid,
+    pub moderation_status: ModerationStatus,
+    /// Open reports closed by the action
+    pub resolved_reports: u64,
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// Audit attestations
+// ────────────────────────────────────────────────────────────────────────────
+
+/// A formal audit report attached to a contract by its publisher or auditor
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AuditAttestation {
+    pub id: Uuid,
+    pub contract_id: Uuid,
+    /// Audited release; None when the report covers the contract as a whole
+    pub version: Option<String>,
+    pub auditor_name: String,
+    pub auditor_url: Option<String>,
+    /// PDF or web report
+    pub report_url: String,
+    /// Hex SHA-256 of the report PDF, so readers can check they got the same file
+    pub report_sha256: Option<String>,
+    pub scope: String,
+    pub findings_summary: Option<String>,
+    pub critical_findings: i32,
+    pub high_findings: i32,
+    pub medium_findings: i32,
+    pub low_findings: i32,
+    pub audited_at: chrono::NaiveDate,
+    /// Account that attached the report
+    pub submitted_by: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Body for POST /api/contracts/:id/audits
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateAuditAttestationRequest {
+    #[serde(default)]
+    pub version: Option<String>,
+    pub auditor_name: String,
+    #[serde(default)]
+    pub auditor_url: Option<String>,
+    pub report_url: String,
+    #[serde(default)]
+    pub report_sha256: Option<String>,
+    pub scope: String,
+    #[serde(default)]
+    pub findings_summary: Option<String>,
+    #[serde(default)]
+    pub critical_findings: i32,
+    #[serde(default)]
+    pub high_findings: i32,
+    #[serde(default)]
+    pub medium_findings: i32,
+    #[serde(default)]
+    pub low_findings: i32,
+    pub audited_at: chrono::NaiveDate,
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// API keys
+// ────────────────────────────────────────────────────────────────────────────
+
+/// Permission carried by an API key. Session tokens from `/api/auth/verify`
+/// carry every scope; API keys carry only the ones they were minted with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ApiScope {
+    /// Read user-scoped data (saved searches, their matches) and, on a private
+    /// registry, everything else
+    Read,
+    /// Publish contracts and attach reviews, audit reports and saved searches
+    Publish,
+    /// Change existing contracts' metadata, publisher and status
+    PatchAdmin,
+}
+
+impl ApiScope {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ApiScope::Read => "read",
+            ApiScope::Publish => "publish",
+            ApiScope::PatchAdmin => "patch-admin",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "read" => Some(ApiScope::Read),
+            "publish" => Some(ApiScope::Publish),
+            "patch-admin" => Some(ApiScope::PatchAdmin),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for ApiScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// An API key as listed by GET /api/tokens; never includes the secret
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ApiKey {
+    pub id: Uuid,
+    pub name: String,
+    /// Leading characters of the key, e.g. `srk_a1b2c3d4`
+    pub prefix: String,
+    pub scopes: Vec<String>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub rotated_from: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Body for POST /api/tokens
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateApiKeyRequest {
+    pub name: String,
+    pub scopes: Vec<ApiScope>,
+    /// Days until the key stops working; None for no expiry
+    #[serde(default)]
+    pub expires_in_days: Option<i64>,
+}
+
+/// Body for POST /api/tokens/:id/rotate
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RotateApiKeyRequest {
+    /// Hours the replaced key keeps working (default 24)
+    #[serde(default)]
+    pub grace_period_hours: Option<i64>,
+}
+
+/// A newly minted key; `token` is shown only in this response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IssuedApiKey {
+    #[serde(flatten)]
+    pub key: ApiKey,
+    pub token: String,
+    /// On rotation, when the replaced key stops working
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub previous_key_expires_at: Option<DateTime<Utc>>,
+}
+
+/// Query for GET /api/me/usage
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ApiUsageQuery {
+    /// Only count requests made with this API key
+    #[serde(default)]
+    pub token: Option<Uuid>,
+}
+
+/// Requests to one endpoint over the usage window
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct EndpointUsage {
+    /// Method and route pattern, e.g. `GET /api/contracts/:id`
+    pub endpoint: String,
+    pub requests: i64,
+    /// 4xx/5xx responses other than 429
+    pub errors: i64,
+    pub error_rate: f64,
+    /// Requests refused with 429
+    pub rate_limited: i64,
+    /// Requests allowed per rate-limit window, as last applied
+    pub rate_limit: Option<i32>,
+    /// Most requests counted against the limit in a single window
+    pub peak_window_usage: Option<i32>,
+}
+
+/// Requests made with one API key, or with session tokens when `api_key_id` is None
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct TokenUsage {
+    pub api_key_id: Option<Uuid>,
+    pub name: Option<String>,
+    pub prefix: Option<String>,
+    pub requests: i64,
+    pub errors: i64,
+    pub rate_limited: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct DailyUsage {
+    pub day: chrono::NaiveDate,
+    pub requests: i64,
+    pub errors: i64,
+    pub rate_limited: i64,
+}
+
+/// GET /api/me/usage: the account's API calls over the last `window_days`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiUsageReport {
+    pub since: chrono::NaiveDate,
+    pub window_days: i64,
+    pub requests: i64,
+    pub errors: i64,
+    pub error_rate: f64,
+    pub rate_limited: i64,
+    /// Busiest first
+    pub endpoints: Vec<EndpointUsage>,
+    pub tokens: Vec<TokenUsage>,
+    pub daily: Vec<DailyUsage>,
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// Security patches
+// ────────────────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "patch_severity", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum PatchSeverity {
+    Critical,
+    High,
+    Medium,
+    Low,
+}
+
+/// A patch that moves contracts on `target_version` to `new_wasm_hash`
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct SecurityPatch {
+    pub id: Uuid,
+    /// Affected version or SemVer range, e.g. `>=1.0.0, <1.4.2`
+    pub target_version: String,
+    pub severity: PatchSeverity,
+    pub new_wasm_hash: String,
+    pub rollout_percentage: i32,
+    pub description: Option<String>,
+    pub created_by: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Body for POST /api/patches
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreatePatchRequest {
+    /// Affected version or SemVer range, e.g. `>=1.0.0, <1.4.2`
+    pub target_version: String,
+    pub severity: PatchSeverity,
+    pub new_wasm_hash: String,
+    #[serde(default = "default_rollout_percentage")]
+    pub rollout_percentage: i32,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+fn default_rollout_percentage() -> i32 {
+    100
+}
+
+/// Body for POST /api/patches/:id/apply
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApplyPatchRequest {
+    pub contract_id: Uuid,
+}
+
+/// Record of a patch applied to one contract
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct PatchAudit {
+    pub id: Uuid,
+    pub contract_id: Uuid,
+    pub patch_id: Uuid,
+    pub applied_at: DateTime<Utc>,
+    /// Maintainer who applied it; unknown for records older than the history
+    #[serde(default)]
+    pub applied_by: Option<String>,
+    /// 1-based order in which the contract received the patch
+    #[serde(default)]
+    pub rollout_position: Option<i32>,
+    /// Contracts on the target version when the patch was applied
+    #[serde(default)]
+    pub eligible_contracts: Option<i32>,
+}
+
+/// Sign-off from a second maintainer on a critical patch
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct PatchApproval {
+    pub id: Uuid,
+    pub patch_id: Uuid,
+    pub approver_address: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// GET /api/patches/:id — the patch plus its approval state
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PatchDetail {
+    #[serde(flatten)]
+    pub patch: SecurityPatch,
+    pub approvals: Vec<PatchApproval>,
+    /// Whether further rollout is blocked until a second maintainer approves
+    pub approval_pending: bool,
+}
+
+/// One patch applied to a contract, as shown in its patch history
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ContractPatchEntry {
+    pub patch_id: Uuid,
+    pub target_version: String,
+    pub severity: PatchSeverity,
+    pub new_wasm_hash: String,
+    pub description: Option<String>,
+    pub rollout_percentage: i32,
+    pub applied_at: DateTime<Utc>,
+    pub applied_by: Option<String>,
+    pub rollout_position: Option<i32>,
+    pub eligible_contracts: Option<i32>,
+}
+
+/// GET /api/contracts/:id/patches — patches applied to the contract, oldest first
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContractPatchHistory {
+    pub contract_id: Uuid,
+    pub items: Vec<ContractPatchEntry>,
+    pub total: usize,
+}
+
+/// A deployment running one of the versions a patch affects
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AffectedDeployment {
+    pub environment: DeploymentEnvironment,
+    pub status: DeploymentStatus,
+    pub version: String,
+    pub wasm_hash: String,
+}
+
+/// A contract with at least one published version inside a patch's range
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AffectedContract {
+    pub id: Uuid,
+    pub contract_id: String,
+    pub name: String,
+    pub network: Network,
+    pub publisher_address: String,
+    /// Published versions inside the range, oldest first
+    pub versions: Vec<String>,
+    pub deployments: Vec<AffectedDeployment>,
+}
+
+/// GET /api/patches/:id/affected — everyone the patch should reach
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PatchAffectedResponse {
+    pub patch_id: Uuid,
+    pub target_version: String,
+    pub items: Vec<AffectedContract>,
+    pub total_contracts: usize,
+    pub total_deployments: usize,
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// Staged publishing
+// ────────────────────────────────────────────────────────────────────────────
+
+/// Artifacts a publish session accepts; `wasm` and `abi` are required to commit
+pub const PUBLISH_PART_KINDS: &[&str] = &["wasm", "readme", "abi", "signature", "tests", "budgets"];
+
+/// A staging area for one version; nothing is visible until it is committed
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct PublishSession {
+    pub id: Uuid,
+    pub contract_id: Uuid,
+    pub version: String,
+    /// `open`, `committing`, `committed` or `cancelled`
+    pub status: String,
+    pub source_url: Option<String>,
+    pub commit_hash: Option<String>,
+    pub release_notes: Option<String>,
+    /// The version created by the commit
+    pub version_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// An uploaded artifact, without its content
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct StagedPart {
+    pub kind: String,
+    pub sha256: String,
+    pub size_bytes: i64,
+    pub uploaded_at: DateTime<Utc>,
+}
+
+/// GET /api/publish/sessions/:id — the session and what has been uploaded so far
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PublishSessionDetail {
+    #[serde(flatten)]
+    pub session: PublishSession,
+    pub parts: Vec<StagedPart>,
+}
+
+/// Body for POST /api/publish/sessions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreatePublishSessionRequest {
+    /// Registry UUID, on-chain ID or `@namespace/name`
+    pub contract_id: String,
+    pub version: String,
+    #[serde(default)]
+    pub source_url: Option<String>,
+    #[serde(default)]
+    pub commit_hash: Option<String>,
+    #[serde(default)]
+    pub release_notes: Option<String>,
+    #[serde(default)]
+    pub cargo_lock: Option<String>,
+    #[serde(default)]
+    pub toolchain: Option<crate::toolchain::BuildToolchain>,
+}
+
+/// Content of the `signature` part
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignaturePart {
+    pub signature: String,
+    pub publisher_key: String,
+    #[serde(default)]
+    pub signature_algorithm: Option<String>,
+}
+
+/// What a publish hook does when its check fails
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HookSeverity {
+    /// Publish anyway and report the message
+    Warn,
+    /// Refuse the publish
+    #[default]
+    Reject,
+}
+
+/// A failed publish hook check
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PublishHookFinding {
+    /// Name of the hook, e.g. `max-wasm-size`
+    pub hook: String,
+    pub severity: HookSeverity,
+    pub message: String,
+}
+
+/// POST /api/publish/sessions/:id/commit — the new version and any hook warnings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PublishCommitResult {
+    #[serde(flatten)]
+    pub version: ContractVersion,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<PublishHookFinding>,
+}
+
+/// A group whose members can see its private contracts
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Organization {
+    pub id: Uuid,
+    pub name: String,
+    pub created_by: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct OrganizationMember {
+    pub address: String,
+    /// `owner` (manages members) or `member`
+    pub role: String,
+    pub added_at: DateTime<Utc>,
+}
+
+/// GET /api/orgs/:name
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrganizationDetail {
+    #[serde(flatten)]
+    pub organization: Organization,
+    pub members: Vec<OrganizationMember>,
+}
+
+/// Body for POST /api/orgs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateOrganizationRequest {
+    pub name: String,
+}
+
+/// Body for POST /api/orgs/:name/members
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AddOrganizationMemberRequest {
+    pub address: String,
+    /// Defaults to `member`
+    #[serde(default)]
+    pub role: Option<String>,
+}
+
+/// Body for PATCH /api/contracts/:id/visibility
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateContractVisibilityRequest {
+    pub private: bool,
+    /// Owning organization; required when making a contract private
+    #[serde(default)]
+    pub organization: Option<String>,
+}
+
+/// One quota: how much is used and the configured ceiling (`None` = unlimited)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct QuotaMetric {
+    pub used: i64,
+    pub limit: Option<i64>,
+}
+
+/// GET /api/usage and GET /api/orgs/:name/usage
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuotaUsage {
+    /// `organization` or `publisher`; contracts outside an organization count
+    /// against their publisher
+    pub account_type: String,
+    /// Organization name or publisher address
+    pub account: String,
+    pub contracts: QuotaMetric,
+    pub artifact_bytes: QuotaMetric,
+    /// Versions published in the trailing 24 hours
+    pub versions_today: QuotaMetric,
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// CLI telemetry
+// ────────────────────────────────────────────────────────────────────────────
+
+/// One CLI invocation, recorded only with the user's consent. Carries no
+/// arguments, addresses or identifiers.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TelemetryEvent {
+    /// Subcommand path without arguments, e.g. `patch apply`
+    pub command: String,
+    pub duration_ms: i64,
+    pub success: bool,
+    pub cli_version: String,
+    pub recorded_at: DateTime<Utc>,
+}
+
+/// Body for POST /api/telemetry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TelemetryBatch {
+    pub events: Vec<TelemetryEvent>,
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// Registry statistics
+// ────────────────────────────────────────────────────────────────────────────
+
+/// One day of a statistics series
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct StatsPoint {
+    pub date: chrono::NaiveDate,
+    pub value: i64,
+}
+
+/// GET /api/stats/timeseries — one metric per day, oldest first, with days
+/// that have no snapshot reported as 0
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatsTimeseries {
+    pub metric: String,
+    pub range_days: i64,
+    pub points: Vec<StatsPoint>,
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// Test results
+// ────────────────────────────────────────────────────────────────────────────
+
+/// Counts from a test run attached to a version at publish time
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, FromRow)]
+pub struct TestSummary {
+    pub passed: i32,
+    pub failed: i32,
+    pub skipped: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub coverage_percent: Option<f64>,
+}
+
+impl TestSummary {
+    /// Short form for listings, e.g. `124 passed` or `120 passed, 4 failed, 87.5% coverage`
+    pub fn label(&self) -> String {
+        let mut parts = vec![format!("{} passed", self.passed)];
+        if self.failed > 0 {
+            parts.push(format!("{} failed", self.failed));
+        }
+        if self.skipped > 0 {
+            parts.push(format!("{} skipped", self.skipped));
+        }
+        if let Some(coverage) = self.coverage_percent {
+            parts.push(format!("{:.1}% coverage", coverage));
+        }
+        parts.join(", ")
+    }
+}
+
+/// One version's attached test results
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct VersionTestResults {
+    pub version: String,
+    /// `junit` or `json`, the format the report was uploaded in
+    pub format: String,
+    #[serde(flatten)]
+    #[sqlx(flatten)]
+    pub summary: TestSummary,
+    pub recorded_at: DateTime<Utc>,
+}
+
+/// GET /api/contracts/:id/tests — newest version first
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContractTestResultsResponse {
+    pub contract_id: Uuid,
+    pub versions: Vec<VersionTestResults>,
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// Simulation sandbox
+// ────────────────────────────────────────────────────────────────────────────
+
+/// Body for POST /api/contracts/:id/simulate
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimulateRequest {
+    pub function: String,
+    /// One value per parameter, in the syntax of `validate-call`: numbers and
+    /// addresses as strings, vectors and structs as JSON
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// Version whose WASM to load; defaults to the latest
+    #[serde(default)]
+    pub version: Option<String>,
+}
+
+/// An event emitted during a simulated call
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SimulationEvent {
+    pub contract_id: String,
+    pub topics: Vec<serde_json::Value>,
+    pub data: serde_json::Value,
+}
+
+/// Host budget consumed by a simulated call
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SimulationResources {
+    pub cpu_instructions: u64,
+    pub memory_bytes: u64,
+}
+
+/// Outcome of a simulated call. A call that fails inside the contract is still a
+/// completed simulation: `success` is false and `error` says why.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimulationResult {
+    pub version: String,
+    pub function: String,
+    pub success: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// The return value as base64 `ScVal` XDR
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result_xdr: Option<String>,
+    pub events: Vec<SimulationEvent>,
+    pub resources: SimulationResources,
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// Usage examples
+// ────────────────────────────────────────────────────────────────────────────
+
+/// One call of an example, with arguments in the syntax of `simulate`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ExampleStep {
+    pub function: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+}
+
+/// A named, runnable sequence of calls a publisher attached to a contract
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ContractExample {
+    pub id: Uuid,
+    pub contract_id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    /// Version whose interface the steps were validated against
+    pub version: String,
+    #[sqlx(json)]
+    pub steps: Vec<ExampleStep>,
+    pub created_by: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Body for PUT /api/contracts/:id/examples/:name
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpsertExampleRequest {
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Version to validate against; defaults to the latest
+    #[serde(default)]
+    pub version: Option<String>,
+    pub steps: Vec<ExampleStep>,
+}
+
+/// Body for POST /api/contracts/:id/examples/:name/run
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RunExampleRequest {
+    /// Version whose WASM to load; defaults to the latest
+    #[serde(default)]
+    pub version: Option<String>,
+}
+
+/// Response for POST /api/contracts/:id/examples/:name/run. Steps share one
+/// sandbox and stop at the first failure.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExampleRun {
+    pub example: String,
+    pub version: String,
+    pub success: bool,
+    pub steps: Vec<SimulationResult>,
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// Performance budgets
+// ────────────────────────────────────────────────────────────────────────────
+
+/// A function to benchmark on publish and the limits it must stay within
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FunctionBudget {
+    pub function: String,
+    /// Arguments for the benchmark call, in the syntax of `simulate`
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_cpu_instructions: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_memory_bytes: Option<u64>,
+}
+
+/// A version's `budgets` part, e.g. from `budgets.toml`:
+///
+/// ```toml
+/// max_regression_percent = 10.0
+/// on_exceed = "warn"
+///
+/// [[functions]]
+/// function = "swap"
+/// args = ["GABC...", "100"]
+/// max_cpu_instructions = 2000000
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PerformanceBudgets {
+    pub functions: Vec<FunctionBudget>,
+    /// Allowed growth over the previous version's measurements, in percent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_regression_percent: Option<f64>,
+    /// `reject` (the default) fails the publish; `warn` publishes and reports
+    #[serde(default)]
+    pub on_exceed: HookSeverity,
+}
+
+/// One function's measured cost, with the budget it was checked against
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct BenchmarkMeasurement {
+    pub function: String,
+    /// False when the benchmark call itself failed
+    pub success: bool,
+    pub cpu_instructions: i64,
+    pub memory_bytes: i64,
+    pub max_cpu_instructions: Option<i64>,
+    pub max_memory_bytes: Option<i64>,
+}
+
+/// One version's benchmark measurements
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VersionBenchmarks {
+    pub version: String,
+    pub recorded_at: DateTime<Utc>,
+    pub measurements: Vec<BenchmarkMeasurement>,
+}
+
+/// GET /api/contracts/:id/budgets — newest version first
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContractBenchmarksResponse {
+    pub contract_id: Uuid,
+    pub versions: Vec<VersionBenchmarks>,
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// Fork tracking
+// ────────────────────────────────────────────────────────────────────────────
+
+/// A contract in a fork lineage or tree
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ForkReference {
+    pub id: Uuid,
+    pub contract_id: String,
+    /// Qualified name, e.g. `@alice/amm`
+    pub name: String,
+}
+
+/// A fork and, recursively, the forks based on it
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ForkNode {
+    #[serde(flatten)]
+    pub contract: ForkReference,
+    pub created_at: DateTime<Utc>,
+    pub forks: Vec<ForkNode>,
+}
+
+/// GET /api/contracts/:id/forks
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContractForksResponse {
+    pub contract_id: Uuid,
+    /// Contracts this one derives from, nearest first
+    pub lineage: Vec<ForkReference>,
+    /// Direct forks
+    pub fork_count: usize,
+    /// Forks at any depth
+    pub total_forks: usize,
+    pub forks: Vec<ForkNode>,
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// Maintenance mode
+// ────────────────────────────────────────────────────────────────────────────
+
+/// Registry-wide read-only switch; while enabled every write gets 503
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MaintenanceStatus {
+    pub enabled: bool,
+    /// Operator notice returned with rejected writes
+    pub message: Option<String>,
+    pub retry_after_seconds: u64,
+    /// None when the state comes from configuration
+    pub updated_by: Option<String>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// Body for PUT /api/admin/maintenance
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateMaintenanceRequest {
+    pub enabled: bool,
+    #[serde(default)]
+    pub message: Option<String>,
+    /// Keeps the current value when omitted
+    #[serde(default)]
+    pub retry_after_seconds: Option<u64>,
+    pub changed_by: String,
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// Tenants
+// ────────────────────────────────────────────────────────────────────────────
+
+/// A logical registry hosted alongside others in one deployment
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Tenant {
+    pub id: Uuid,
+    /// Used in `/t/<slug>/...` paths and as the auth realm
+    pub slug: String,
+    pub name: String,
+    /// Hostnames routed to this tenant
+    pub hosts: Vec<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Body for POST /api/admin/tenants
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateTenantRequest {
+    pub slug: String,
+    pub name: String,
+    #[serde(default)]
+    pub hosts: Vec<String>,
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// Publisher identity
+// ────────────────────────────────────────────────────────────────────────────
+
+/// Response for POST /api/publishers/:id/verify-identity/challenge
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IdentityChallenge {
+    pub publisher_id: Uuid,
+    /// The `G...` account whose key must sign `message`
+    pub account: String,
+    /// Exact bytes to sign with ed25519, as UTF-8
+    pub message: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Body for POST /api/publishers/:id/verify-identity
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VerifyIdentityRequest {
+    /// Hex-encoded ed25519 signature of the challenge message
+    pub signature: String,
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// Content-addressed artifacts
+// ────────────────────────────────────────────────────────────────────────────
+
+/// A file of a contract version, immutable at `/api/artifacts/:sha256`
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ArtifactRef {
+    /// `wasm`, `abi`, `readme`, `signature`, ... or `archive` for the bundle
+    /// served by `/api/contracts/:id/archive`
+    pub kind: String,
+    pub sha256: String,
+    pub size_bytes: i64,
+}
+
+/// Response for GET /api/contracts/:id/artifacts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VersionArtifacts {
+    pub contract_id: String,
+    pub version: String,
+    pub artifacts: Vec<ArtifactRef>,
+    /// The version's support policy, when its publisher declared one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub support: Option<crate::support::VersionSupport>,
+}
+
+impl VersionArtifacts {
+    pub fn find(&self, kind: &str) -> Option<&ArtifactRef> {
+        self.artifacts.iter().find(|a| a.kind == kind)
+    }
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// Sparse index
+// ────────────────────────────────────────────────────────────────────────────
+
+/// One line of an index file: a published version of a contract. Files hold
+/// every version of a name, one JSON object per line, oldest first.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IndexEntry {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
+    pub network: Network,
+    /// Registry UUID, for the `/api/contracts/:id` endpoints
+    pub id: Uuid,
+    /// On-chain contract ID
+    pub contract_id: String,
+    pub version: String,
+    pub wasm_hash: String,
+    /// Artifact kind (`wasm`, `abi`, ...) to SHA-256, for the artifact template
+    pub artifacts: std::collections::BTreeMap<String, String>,
+    pub published_at: DateTime<Utc>,
+}
+
+/// `config.json` at the root of the index
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexConfig {
+    pub schema_version: u32,
+    /// Template for index files; `{path}` is the file path under the index root
+    pub index: String,
+    /// Template for artifact downloads; `{sha256}` comes from an entry's `artifacts`
+    pub artifact: String,
+    /// Template for version archives, with `{id}` and `{version}`
+    pub archive: String,
+    /// Base URL of the registry API
+    pub api: String,
+}
+
+/// Response for POST /api/admin/index/rebuild
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexRebuildSummary {
+    pub tenants: usize,
+    pub files: usize,
+    pub versions: usize,
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// Reindexing
+// ────────────────────────────────────────────────────────────────────────────
+
+/// Derived data a reindex can rebuild from the stored contracts and ABIs
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReindexStep {
+    /// Full-text search vectors and the "did you mean" vocabulary
+    Search,
+    /// Well-known interfaces each contract's latest ABI implements
+    Interfaces,
+    /// Dependencies declared in the latest ABIs and the dependency graph
+    Dependencies,
+}
+
+impl ReindexStep {
+    pub const ALL: [ReindexStep; 3] = [Self::Search, Self::Interfaces, Self::Dependencies];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Search => "search",
+            Self::Interfaces => "interfaces",
+            Self::Dependencies => "dependencies",
+        }
+    }
+}
+
+impl std::str::FromStr for ReindexStep {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|step| step.as_str() == s)
+            .ok_or_else(|| format!("unknown reindex step '{}'", s))
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReindexState {
+    Running,
+    Completed,
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReindexStepProgress {
+    pub step: ReindexStep,
+    /// Contracts to process; 0 until the step starts
+    pub total: i64,
+    pub processed: i64,
+    /// Contracts skipped because their stored data could not be processed
+    pub failed: i64,
+    pub finished: bool,
+}
+
+/// Response for GET and POST /api/admin/reindex
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReindexStatus {
+    pub id: Uuid,
+    pub state: ReindexState,
+    pub steps: Vec<ReindexStepProgress>,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+    pub error: Option<String>,
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// Backfills
+// ────────────────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, PartialEq, Eq)]
+#[sqlx(type_name = "backfill_state", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum BackfillState {
+    Running,
+    /// Stopped after the requested number of batches; runs again from `checkpoint`
+    Paused,
+    Completed,
+    Failed,
+}
+
+/// Response for the /api/admin/backfills endpoints
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackfillStatus {
+    pub name: String,
+    pub description: String,
+    /// None until the job first runs
+    pub state: Option<BackfillState>,
+    /// Last row the job finished
+    pub checkpoint: Option<Uuid>,
+    /// Rows examined
+    pub processed: i64,
+    /// Rows changed
+    pub updated: i64,
+    /// Rows that could not be fixed and were skipped
+    pub failed: i64,
+    pub last_error: Option<String>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub heartbeat_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// Jobs
+// ────────────────────────────────────────────────────────────────────────────
+
+/// What a job started through the API is doing
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum JobKind {
+    Verification,
+    Reindex,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum JobState {
+    Queued,
+    Running,
+    Succeeded,
+    Failed,
+}
+
+impl JobState {
+    /// Whether the job has stopped and polling it again is pointless
+    pub fn is_finished(self) -> bool {
+        matches!(self, JobState::Succeeded | JobState::Failed)
+    }
+}
+
+/// Response for GET /api/jobs/:id, the same shape for every kind of job
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobStatus {
+    pub id: Uuid,
+    pub kind: JobKind,
+    pub state: JobState,
+    /// What the job works on, e.g. the contract being verified
+    pub subject: Option<String>,
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// Dependency alerts
+// ────────────────────────────────────────────────────────────────────────────
+
+/// A vulnerability disclosed against a range of a contract's versions
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct SecurityAdvisory {
+    pub id: Uuid,
+    pub contract_id: Uuid,
+    /// Affected version or SemVer range, e.g. `>=1.0.0, <1.4.2`
+    pub affected_versions: String,
+    pub severity: PatchSeverity,
+    pub title: String,
+    pub description: Option<String>,
+    /// First version with the fix, when there is one
+    pub patched_version: Option<String>,
+    pub created_by: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Body for POST /api/contracts/:id/advisories
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateAdvisoryRequest {
+    pub affected_versions: String,
+    pub severity: PatchSeverity,
+    pub title: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub patched_version: Option<String>,
+}
+
+/// Response for POST /api/contracts/:id/advisories
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AdvisoryCreated {
+    #[serde(flatten)]
+    pub advisory: SecurityAdvisory,
+    /// Dependent contracts whose owners were alerted
+    pub alerts_created: usize,
+}
+
+/// Why a dependent contract was alerted
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "alert_source", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum AlertSource {
+    Advisory,
+    Patch,
+    /// A version the contract resolves to is at or near its supported-until date
+    EndOfLife,
+}
+
+/// An advisory, critical patch or upcoming end of life reaching a contract
+/// through its dependencies
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct DependencyAlert {
+    pub id: Uuid,
+    /// Publisher of `contract_id`, who receives the alert
+    pub owner_address: String,
+    pub source: AlertSource,
+    /// The advisory or patch that triggered the alert, or the end-of-life version
+    pub source_id: Uuid,
+    pub severity: PatchSeverity,
+    pub summary: String,
+    /// Contract the advisory or patch is about
+    pub vulnerable_contract_id: Uuid,
+    /// Its versions the advisory or patch covers
+    pub affected_versions: Vec<String>,
+    /// The alerted dependent contract
+    pub contract_id: Uuid,
+    /// Registry IDs from the vulnerable contract to `contract_id`
+    pub dependency_path: Vec<Uuid>,
+    /// Constraint `contract_id` (or the contract before it on the path) uses
+    pub version_constraint: String,
+    /// Deployments of `contract_id` registered when the alert was raised
+    pub deployments: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+    pub acknowledged_at: Option<DateTime<Utc>>,
+}
+
+/// Query params for GET /api/me/alerts
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AlertsQuery {
+    /// Include acknowledged alerts
+    #[serde(default)]
+    pub all: bool,
+}
+
+/// Where alerts are pushed besides GET /api/me/alerts
+#[derive(Debug, Clone, Default, Serialize, Deserialize, FromRow)]
+pub struct AlertSettings {
+    /// Receives a `dependency.alert` POST for each new alert
+    #[serde(default)]
+    pub webhook_url: Option<String>,
+    /// Overrides the publisher profile's email for alerts
+    #[serde(default)]
+    pub email: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pagination_flags_and_cursor() {
+        let middle = PaginatedResponse::new(vec![(); 20], 273, 2, 20);
+        assert_eq!(middle.total_pages, 14);
+        assert!(middle.has_next && middle.has_prev);
+        let cursor = PageCursor::decode(middle.next_cursor.as_deref().unwrap()).unwrap();
+        assert_eq!(cursor, PageCursor::new(3, 20));
+
+        let last = PaginatedResponse::new(vec![(); 13], 273, 14, 20);
+        assert!(!last.has_next && last.has_prev);
+        assert_eq!(last.next_cursor, None);
+
+        assert_eq!(PageCursor::decode("p0.20"), None);
+        assert_eq!(PageCursor::decode("3.20"), None);
+    }
+}
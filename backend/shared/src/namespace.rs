@@ -0,0 +1,109 @@
+//! Publisher-scoped contract names of the form `@publisher/name`.
+//! Names without a leading `@` are legacy, unscoped names.
+
+use serde::{Deserialize, Serialize};
+
+/// Maximum namespace length (matches GitHub usernames)
+pub const MAX_NAMESPACE_LENGTH: usize = 39;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContractName {
+    pub namespace: Option<String>,
+    pub name: String,
+}
+
+/// Validate a namespace: lowercase ASCII letters, digits and single hyphens,
+/// not starting or ending with a hyphen
+pub fn validate_namespace(namespace: &str) -> Result<(), String> {
+    if namespace.is_empty() {
+        return Err("namespace is empty".to_string());
+    }
+    if namespace.len() > MAX_NAMESPACE_LENGTH {
+        return Err(format!(
+            "namespace must be at most {} characters",
+            MAX_NAMESPACE_LENGTH
+        ));
+    }
+    if !namespace
+        .chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+    {
+        return Err("namespace may only contain lowercase letters, digits and '-'".to_string());
+    }
+    if namespace.starts_with('-') || namespace.ends_with('-') || namespace.contains("--") {
+        return Err("namespace may not start or end with '-' or contain '--'".to_string());
+    }
+    Ok(())
+}
+
+impl ContractName {
+    /// Parse `@namespace/name` or a plain `name`
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let value = value.trim();
+        let Some(scoped) = value.strip_prefix('@') else {
+            if value.is_empty() {
+                return Err("name is empty".to_string());
+            }
+            return Ok(ContractName {
+                namespace: None,
+                name: value.to_string(),
+            });
+        };
+
+        let (namespace, name) = scoped
+            .split_once('/')
+            .ok_or_else(|| "namespaced names must look like @publisher/name".to_string())?;
+        validate_namespace(namespace)?;
+        if name.is_empty() {
+            return Err("name after the namespace is empty".to_string());
+        }
+        if name.contains('/') || name.contains('@') {
+            return Err("name may not contain '/' or '@'".to_string());
+        }
+
+        Ok(ContractName {
+            namespace: Some(namespace.to_string()),
+            name: name.to_string(),
+        })
+    }
+
+    pub fn is_scoped(&self) -> bool {
+        self.namespace.is_some()
+    }
+}
+
+impl std::fmt::Display for ContractName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.namespace {
+            Some(ref ns) => write!(f, "@{}/{}", ns, self.name),
+            None => f.write_str(&self.name),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_scoped_and_plain_names() {
+        let scoped = ContractName::parse("@alice/token").unwrap();
+        assert_eq!(scoped.namespace.as_deref(), Some("alice"));
+        assert_eq!(scoped.name, "token");
+        assert_eq!(scoped.to_string(), "@alice/token");
+
+        let plain = ContractName::parse("Token Contract").unwrap();
+        assert!(!plain.is_scoped());
+        assert_eq!(plain.to_string(), "Token Contract");
+    }
+
+    #[test]
+    fn rejects_malformed_scoped_names() {
+        assert!(ContractName::parse("@alice").is_err());
+        assert!(ContractName::parse("@alice/").is_err());
+        assert!(ContractName::parse("@Alice/token").is_err());
+        assert!(ContractName::parse("@-alice/token").is_err());
+        assert!(ContractName::parse("@alice/a/b").is_err());
+        assert!(ContractName::parse("").is_err());
+    }
+}
@@ -0,0 +1,208 @@
+//! RFC 7807 problem details shared by the API and the CLI.
+//! Error codes are a closed taxonomy; codes the client does not know yet
+//! round-trip through `ErrorCode::Other`.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+pub const PROBLEM_JSON_CONTENT_TYPE: &str = "application/problem+json";
+pub const PROBLEM_TYPE_BASE: &str = "https://soroban-registry.dev/problems/";
+
+macro_rules! error_codes {
+    ($($variant:ident => $title:expr),+ $(,)?) => {
+        /// Machine-readable error code carried in the `code` member of a problem
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        pub enum ErrorCode {
+            $($variant,)+
+            /// A code outside the shared taxonomy
+            Other(String),
+        }
+
+        impl ErrorCode {
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $(ErrorCode::$variant => stringify!($variant),)+
+                    ErrorCode::Other(code) => code,
+                }
+            }
+
+            /// Short, human-readable summary used as the problem `title`
+            pub fn title(&self) -> &str {
+                match self {
+                    $(ErrorCode::$variant => $title,)+
+                    ErrorCode::Other(code) => code,
+                }
+            }
+        }
+
+        impl From<&str> for ErrorCode {
+            fn from(code: &str) -> Self {
+                match code {
+                    $(stringify!($variant) => ErrorCode::$variant,)+
+                    other => ErrorCode::Other(other.to_string()),
+                }
+            }
+        }
+    };
+}
+
+error_codes! {
+    ValidationError => "Request validation failed",
+    InvalidRequest => "Malformed request",
+    InvalidContractId => "Invalid contract ID",
+    InvalidVersion => "Invalid version",
+    InvalidABI => "Invalid contract ABI",
+    InvalidSignature => "Invalid signature",
+    InvalidLicense => "Invalid license expression",
+    ContractNotFound => "Contract not found",
+    ContractTakenDown => "Contract taken down",
+    VersionNotFound => "Version not found",
+    NotFound => "Resource not found",
+    VersionAlreadyExists => "Version already exists",
+    BreakingChangeWithoutMajorBump => "Breaking change without major version bump",
+    RateLimitExceeded => "Too many requests",
+    QuotaExceeded => "Quota exceeded",
+    Unauthorized => "Authentication required",
+    Forbidden => "Permission denied",
+    PreconditionFailed => "Resource was modified",
+    ChallengeRequired => "Challenge required",
+    PayloadTooLarge => "Request body too large",
+    UnsupportedMediaType => "Unsupported content type",
+    UpgradeRequired => "Client upgrade required",
+    ServiceUnavailable => "Service unavailable",
+    RpcUnavailable => "Soroban RPC unavailable",
+    MaintenanceMode => "Registry is in maintenance mode",
+    DatabaseError => "Database error",
+    InternalServerError => "Internal server error",
+}
+
+impl From<String> for ErrorCode {
+    fn from(code: String) -> Self {
+        ErrorCode::from(code.as_str())
+    }
+}
+
+impl std::fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Serialize for ErrorCode {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for ErrorCode {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(ErrorCode::from(String::deserialize(deserializer)?))
+    }
+}
+
+/// A single invalid field reported alongside a validation problem
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FieldProblem {
+    pub field: String,
+    pub message: String,
+}
+
+/// `application/problem+json` body
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProblemDetails {
+    #[serde(rename = "type")]
+    pub type_uri: String,
+    pub title: String,
+    pub status: u16,
+    pub detail: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instance: Option<String>,
+    pub code: ErrorCode,
+    #[serde(default)]
+    pub timestamp: String,
+    #[serde(default)]
+    pub correlation_id: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<FieldProblem>,
+}
+
+impl ProblemDetails {
+    pub fn new(status: u16, code: ErrorCode, detail: impl Into<String>) -> Self {
+        Self {
+            type_uri: problem_type_uri(&code),
+            title: code.title().to_string(),
+            status,
+            detail: detail.into(),
+            instance: None,
+            code,
+            timestamp: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+            correlation_id: uuid::Uuid::new_v4().to_string(),
+            errors: Vec::new(),
+        }
+    }
+}
+
+/// Problem type URI for a code, e.g. `.../problems/version-not-found`
+pub fn problem_type_uri(code: &ErrorCode) -> String {
+    let mut slug = String::new();
+    let mut prev_upper = false;
+    for ch in code.as_str().chars() {
+        let boundary = ch == '_' || ch == '-' || (ch.is_ascii_uppercase() && !prev_upper);
+        if boundary && !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+        if ch.is_ascii_alphanumeric() {
+            slug.push(ch.to_ascii_lowercase());
+        }
+        prev_upper = ch.is_ascii_uppercase();
+    }
+    format!("{}{}", PROBLEM_TYPE_BASE, slug)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codes_round_trip_including_unknown() {
+        assert_eq!(
+            ErrorCode::from("VersionNotFound"),
+            ErrorCode::VersionNotFound
+        );
+        assert_eq!(
+            ErrorCode::from("SomethingNew"),
+            ErrorCode::Other("SomethingNew".to_string())
+        );
+        let json = serde_json::to_string(&ErrorCode::InvalidABI).unwrap();
+        assert_eq!(json, "\"InvalidABI\"");
+    }
+
+    #[test]
+    fn type_uri_is_kebab_case() {
+        assert_eq!(
+            problem_type_uri(&ErrorCode::VersionNotFound),
+            "https://soroban-registry.dev/problems/version-not-found"
+        );
+        assert_eq!(
+            problem_type_uri(&ErrorCode::InvalidABI),
+            "https://soroban-registry.dev/problems/invalid-abi"
+        );
+        assert_eq!(
+            problem_type_uri(&ErrorCode::from("DB_ERROR")),
+            "https://soroban-registry.dev/problems/db-error"
+        );
+    }
+
+    #[test]
+    fn problem_serializes_rfc7807_members() {
+        let problem = ProblemDetails::new(404, ErrorCode::ContractNotFound, "no such contract");
+        let value = serde_json::to_value(&problem).unwrap();
+        assert_eq!(
+            value["type"],
+            "https://soroban-registry.dev/problems/contract-not-found"
+        );
+        assert_eq!(value["title"], "Contract not found");
+        assert_eq!(value["status"], 404);
+        assert_eq!(value["code"], "ContractNotFound");
+        assert!(value.get("errors").is_none());
+    }
+}
@@ -0,0 +1,185 @@
+//! SLSA-style build provenance attached to published contract versions.
+//! The signed payload is an in-toto v1 Statement with a SLSA v1 predicate,
+//! built deterministically by `provenance_statement` on both the CLI and API.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+pub const IN_TOTO_STATEMENT_TYPE: &str = "https://in-toto.io/Statement/v1";
+pub const SLSA_PROVENANCE_TYPE: &str = "https://slsa.dev/provenance/v1";
+
+/// Stored attestation row (DB)
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ProvenanceAttestation {
+    pub id: Uuid,
+    pub contract_id: Uuid,
+    pub version: String,
+    pub subject_digest: String,
+    pub builder_id: String,
+    pub source_repo: String,
+    pub source_commit: String,
+    pub build_parameters: Value,
+    pub statement: Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub public_key: Option<String>,
+    /// True when the signature verified against the statement at submission
+    pub verified: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Provenance details supplied by a publisher
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProvenanceInput {
+    pub builder_id: String,
+    pub source_repo: String,
+    pub source_commit: String,
+    #[serde(default)]
+    pub build_parameters: Value,
+    /// Base64 Ed25519 signature over the canonical statement JSON
+    #[serde(default)]
+    pub signature: Option<String>,
+    /// Base64 Ed25519 public key for `signature`
+    #[serde(default)]
+    pub public_key: Option<String>,
+}
+
+/// Request body for POST /api/contracts/:id/provenance
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubmitProvenanceRequest {
+    pub version: String,
+    #[serde(flatten)]
+    pub provenance: ProvenanceInput,
+}
+
+/// Query params for GET /api/contracts/:id/provenance
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProvenanceQuery {
+    pub version: Option<String>,
+}
+
+/// Response for GET /api/contracts/:id/provenance
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProvenanceResponse {
+    pub contract_id: String,
+    pub version: String,
+    pub wasm_hash: String,
+    pub attestations: Vec<ProvenanceAttestation>,
+}
+
+impl ProvenanceResponse {
+    /// A version has a valid chain when a verified attestation binds its wasm hash
+    pub fn has_valid_chain(&self) -> bool {
+        self.attestations
+            .iter()
+            .any(|a| a.verified && a.subject_digest.eq_ignore_ascii_case(&self.wasm_hash))
+    }
+}
+
+/// Build the canonical in-toto statement for a contract artifact
+pub fn provenance_statement(
+    contract_id: &str,
+    version: &str,
+    wasm_hash: &str,
+    input: &ProvenanceInput,
+) -> Value {
+    json!({
+        "_type": IN_TOTO_STATEMENT_TYPE,
+        "subject": [{
+            "name": format!("{}@{}", contract_id, version),
+            "digest": { "sha256": wasm_hash },
+        }],
+        "predicateType": SLSA_PROVENANCE_TYPE,
+        "predicate": {
+            "buildDefinition": {
+                "buildType": "https://soroban-registry.dev/build/v1",
+                "externalParameters": {
+                    "source": {
+                        "repository": input.source_repo,
+                        "commit": input.source_commit,
+                    },
+                    "parameters": input.build_parameters,
+                },
+            },
+            "runDetails": {
+                "builder": { "id": input.builder_id },
+            },
+        },
+    })
+}
+
+/// Bytes covered by an attestation signature (compact JSON, sorted keys)
+pub fn statement_bytes(statement: &Value) -> Vec<u8> {
+    serde_json::to_vec(statement).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input() -> ProvenanceInput {
+        ProvenanceInput {
+            builder_id: "https://github.com/actions/runner".to_string(),
+            source_repo: "https://github.com/example/token".to_string(),
+            source_commit: "0123456789abcdef".to_string(),
+            build_parameters: json!({ "profile": "release" }),
+            signature: None,
+            public_key: None,
+        }
+    }
+
+    fn attestation(digest: &str, verified: bool) -> ProvenanceAttestation {
+        let input = input();
+        ProvenanceAttestation {
+            id: Uuid::new_v4(),
+            contract_id: Uuid::new_v4(),
+            version: "1.0.0".to_string(),
+            subject_digest: digest.to_string(),
+            builder_id: input.builder_id,
+            source_repo: input.source_repo,
+            source_commit: input.source_commit,
+            build_parameters: input.build_parameters,
+            statement: Value::Null,
+            signature: None,
+            public_key: None,
+            verified,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn statement_binds_subject_digest_and_builder() {
+        let statement = provenance_statement("CABC", "1.0.0", "deadbeef", &input());
+        assert_eq!(statement["predicateType"], SLSA_PROVENANCE_TYPE);
+        assert_eq!(statement["subject"][0]["digest"]["sha256"], "deadbeef");
+        assert_eq!(
+            statement["predicate"]["runDetails"]["builder"]["id"],
+            "https://github.com/actions/runner"
+        );
+        assert_eq!(
+            statement_bytes(&statement),
+            statement_bytes(&provenance_statement("CABC", "1.0.0", "deadbeef", &input()))
+        );
+    }
+
+    #[test]
+    fn valid_chain_requires_verified_matching_attestation() {
+        let mut response = ProvenanceResponse {
+            contract_id: "CABC".to_string(),
+            version: "1.0.0".to_string(),
+            wasm_hash: "deadbeef".to_string(),
+            attestations: vec![
+                attestation("deadbeef", false),
+                attestation("cafebabe", true),
+            ],
+        };
+        assert!(!response.has_valid_chain());
+
+        response.attestations.push(attestation("DEADBEEF", true));
+        assert!(response.has_valid_chain());
+    }
+}
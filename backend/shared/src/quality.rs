@@ -0,0 +1,102 @@
+//! The quality score: one 0–100 number per contract combining how much it is
+//! used, how recently it was updated, verification, audits and stars. It is
+//! recomputed nightly and is the registry's default ranking.
+
+/// Days over which a download's weight falls to 1/e, so popularity decays
+/// unless the contract keeps being used
+pub const DOWNLOAD_DECAY_DAYS: f64 = 30.0;
+
+/// Days over which freshness falls to 1/e after the last update
+pub const FRESHNESS_DECAY_DAYS: f64 = 180.0;
+
+/// Decayed downloads (and stars) at which those components saturate
+const DOWNLOADS_SATURATION: f64 = 1000.0;
+const STARS_SATURATION: f64 = 100.0;
+
+const DOWNLOADS_WEIGHT: f64 = 0.30;
+const FRESHNESS_WEIGHT: f64 = 0.20;
+const VERIFIED_WEIGHT: f64 = 0.20;
+const AUDITED_WEIGHT: f64 = 0.15;
+const STARS_WEIGHT: f64 = 0.15;
+
+/// What a contract's quality score is computed from
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct QualityInputs {
+    /// Downloads, each weighted by `exp(-age_days / DOWNLOAD_DECAY_DAYS)`
+    pub decayed_downloads: f64,
+    /// Days since the contract or one of its versions last changed
+    pub days_since_update: f64,
+    pub verified: bool,
+    /// At least one third-party audit report is attached
+    pub audited: bool,
+    pub stars: i64,
+}
+
+/// 0..=1, growing logarithmically so the first uses count the most
+fn saturating_log(value: f64, saturation: f64) -> f64 {
+    ((1.0 + value.max(0.0)).ln() / (1.0 + saturation).ln()).min(1.0)
+}
+
+impl QualityInputs {
+    /// The weighted score, 0–100 rounded to two decimals
+    pub fn score(&self) -> f64 {
+        let flag = |set: bool| if set { 1.0 } else { 0.0 };
+        let score = DOWNLOADS_WEIGHT * saturating_log(self.decayed_downloads, DOWNLOADS_SATURATION)
+            + FRESHNESS_WEIGHT * (-self.days_since_update.max(0.0) / FRESHNESS_DECAY_DAYS).exp()
+            + VERIFIED_WEIGHT * flag(self.verified)
+            + AUDITED_WEIGHT * flag(self.audited)
+            + STARS_WEIGHT * saturating_log(self.stars as f64, STARS_SATURATION);
+        (score * 10_000.0).round() / 100.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scores_span_zero_to_a_hundred() {
+        let stale = QualityInputs {
+            days_since_update: 1e6,
+            ..Default::default()
+        };
+        assert_eq!(stale.score(), 0.0);
+
+        let best = QualityInputs {
+            decayed_downloads: 5000.0,
+            days_since_update: 0.0,
+            verified: true,
+            audited: true,
+            stars: 500,
+        };
+        assert_eq!(best.score(), 100.0);
+    }
+
+    #[test]
+    fn popularity_and_freshness_decay() {
+        let base = QualityInputs {
+            decayed_downloads: 50.0,
+            days_since_update: 10.0,
+            verified: true,
+            ..Default::default()
+        };
+        let older = QualityInputs {
+            days_since_update: 400.0,
+            ..base.clone()
+        };
+        let less_used = QualityInputs {
+            decayed_downloads: 5.0,
+            ..base.clone()
+        };
+        assert!(older.score() < base.score());
+        assert!(less_used.score() < base.score());
+        assert!(
+            QualityInputs {
+                audited: true,
+                ..base.clone()
+            }
+            .score()
+                > base.score()
+        );
+    }
+}
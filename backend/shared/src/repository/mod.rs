@@ -0,0 +1,214 @@
+//! Registry persistence behind a backend-neutral trait
+//!
+//! The core registry records (publishers, contracts and their versions) can be
+//! stored in Postgres or, with the `sqlite` feature, in a single SQLite file for
+//! demo and single-user deployments. Each backend keeps its own SQL in its own
+//! module; callers only see [`RegistryRepository`].
+
+pub mod postgres;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::models::{Contract, ContractVersion, Network, ProjectUrls, PublishRequest, Publisher};
+
+/// Database engine, chosen by the scheme of the configured URL
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backend {
+    Postgres,
+    Sqlite,
+}
+
+impl Backend {
+    pub fn from_url(url: &str) -> Option<Self> {
+        if url.starts_with("postgres://") || url.starts_with("postgresql://") {
+            Some(Backend::Postgres)
+        } else if url.starts_with("sqlite:") {
+            Some(Backend::Sqlite)
+        } else {
+            None
+        }
+    }
+}
+
+/// Which contracts to list, newest first
+#[derive(Debug, Clone)]
+pub struct ContractFilter {
+    /// Case-insensitive match on name or description
+    pub query: Option<String>,
+    pub network: Option<Network>,
+    pub category: Option<String>,
+    pub verified_only: bool,
+    /// Only contracts of this tenant; single-tenant backends ignore it
+    pub tenant_id: Uuid,
+    pub limit: i64,
+    pub offset: i64,
+}
+
+impl Default for ContractFilter {
+    fn default() -> Self {
+        Self {
+            query: None,
+            network: None,
+            category: None,
+            verified_only: false,
+            tenant_id: Uuid::nil(),
+            limit: 20,
+            offset: 0,
+        }
+    }
+}
+
+/// A contract as registered by a publish
+#[derive(Debug, Clone)]
+pub struct NewContract {
+    pub contract_id: String,
+    pub wasm_hash: String,
+    pub name: String,
+    pub namespace: Option<String>,
+    pub description: Option<String>,
+    pub network: Network,
+    pub category: Option<String>,
+    pub tags: Vec<String>,
+    pub license: Option<String>,
+    pub urls: ProjectUrls,
+}
+
+impl NewContract {
+    /// The registrable fields of a publish request; the WASM hash is filled in
+    /// when the first version is uploaded
+    pub fn from_request(req: &PublishRequest, name: &str, namespace: Option<&str>) -> Self {
+        Self {
+            contract_id: req.contract_id.clone(),
+            wasm_hash: "placeholder_hash".to_string(),
+            name: name.to_string(),
+            namespace: namespace.map(str::to_string),
+            description: req.description.clone(),
+            network: req.network.clone(),
+            category: req.category.clone(),
+            tags: req.tags.clone(),
+            license: req.license.clone(),
+            urls: req.urls.clone(),
+        }
+    }
+}
+
+/// A version row to append to a contract
+#[derive(Debug, Clone)]
+pub struct NewVersion {
+    pub contract: Uuid,
+    pub version: String,
+    pub wasm_hash: String,
+    pub source_url: Option<String>,
+    pub commit_hash: Option<String>,
+    pub release_notes: Option<String>,
+    /// Stored as the version's ABI when present
+    pub abi: Option<Value>,
+}
+
+/// Reads and writes of the core registry records
+#[async_trait]
+pub trait RegistryRepository: Send + Sync {
+    fn backend(&self) -> Backend;
+
+    /// Bring the schema up to date
+    async fn migrate(&self) -> Result<(), sqlx::Error>;
+
+    /// The publisher with this Stellar address, created on first use
+    async fn upsert_publisher(&self, stellar_address: &str) -> Result<Publisher, sqlx::Error>;
+
+    async fn insert_contract(
+        &self,
+        publisher: Uuid,
+        contract: &NewContract,
+    ) -> Result<Contract, sqlx::Error>;
+
+    async fn contract(&self, id: Uuid) -> Result<Option<Contract>, sqlx::Error>;
+
+    /// The contract if anonymous visitors of `tenant_id` may see it: public,
+    /// not quarantined or taken down, and in that tenant
+    async fn public_contract(
+        &self,
+        id: Uuid,
+        tenant_id: Uuid,
+    ) -> Result<Option<Contract>, sqlx::Error>;
+
+    /// Look a contract up by its on-chain address
+    async fn contract_by_address(
+        &self,
+        contract_id: &str,
+        network: &Network,
+    ) -> Result<Option<Contract>, sqlx::Error>;
+
+    /// Contracts anonymous visitors may see matching `filter`, with the total
+    /// before paging
+    async fn list_contracts(
+        &self,
+        filter: &ContractFilter,
+    ) -> Result<(Vec<Contract>, i64), sqlx::Error>;
+
+    /// Append a version and make its WASM hash the contract's current one
+    async fn insert_version(&self, version: &NewVersion) -> Result<ContractVersion, sqlx::Error>;
+
+    /// Versions of a contract, newest first
+    async fn versions(&self, contract: Uuid) -> Result<Vec<ContractVersion>, sqlx::Error>;
+
+    /// ABI of the newest version that has one
+    async fn abi(&self, contract: Uuid) -> Result<Option<Value>, sqlx::Error>;
+
+    async fn readme(&self, contract: Uuid) -> Result<Option<String>, sqlx::Error>;
+
+    /// Replace the contract-level README
+    async fn set_readme(&self, contract: Uuid, content: &str) -> Result<(), sqlx::Error>;
+}
+
+/// Open the backend named by `url`'s scheme
+pub async fn connect(
+    url: &str,
+    max_connections: u32,
+) -> Result<Arc<dyn RegistryRepository>, sqlx::Error> {
+    match Backend::from_url(url) {
+        Some(Backend::Postgres) => Ok(Arc::new(
+            postgres::PgRepository::connect(url, max_connections).await?,
+        )),
+        #[cfg(feature = "sqlite")]
+        Some(Backend::Sqlite) => Ok(Arc::new(
+            sqlite::SqliteRepository::connect(url, max_connections).await?,
+        )),
+        #[cfg(not(feature = "sqlite"))]
+        Some(Backend::Sqlite) => Err(sqlx::Error::Configuration(
+            "SQLite support was not compiled in (enable the `sqlite` feature)".into(),
+        )),
+        None => Err(sqlx::Error::Configuration(
+            format!("unsupported database URL scheme: {}", url).into(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backend_follows_the_url_scheme() {
+        assert_eq!(
+            Backend::from_url("postgres://app@db/registry"),
+            Some(Backend::Postgres)
+        );
+        assert_eq!(
+            Backend::from_url("postgresql://app@db/registry"),
+            Some(Backend::Postgres)
+        );
+        assert_eq!(
+            Backend::from_url("sqlite://registry.db?mode=rwc"),
+            Some(Backend::Sqlite)
+        );
+        assert_eq!(Backend::from_url("sqlite::memory:"), Some(Backend::Sqlite));
+        assert_eq!(Backend::from_url("mysql://db/registry"), None);
+    }
+}
@@ -0,0 +1,245 @@
+//! Postgres backend, over the schema in `database/migrations`
+
+use async_trait::async_trait;
+use serde_json::Value;
+use sqlx::postgres::{PgPool, PgPoolOptions};
+use uuid::Uuid;
+
+use super::{Backend, ContractFilter, NewContract, NewVersion, RegistryRepository};
+use crate::models::{Contract, ContractVersion, Network, Publisher};
+
+pub struct PgRepository {
+    pool: PgPool,
+}
+
+impl PgRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn connect(url: &str, max_connections: u32) -> Result<Self, sqlx::Error> {
+        let pool = PgPoolOptions::new()
+            .max_connections(max_connections)
+            .connect(url)
+            .await?;
+        Ok(Self::new(pool))
+    }
+
+    pub fn pool(&self) -> &PgPool {
+        &self.pool
+    }
+}
+
+/// Contracts anonymous visitors of the tenant bound to `tenant` may see, as in
+/// the anonymous case of the API's `Viewer::visibility_clause`
+fn public(tenant: &str) -> String {
+    format!(
+        "tenant_id = {} AND moderation_status = 'active' AND NOT is_private",
+        tenant
+    )
+}
+
+const FILTER: &str =
+    "($1::text IS NULL OR name ILIKE '%' || $1 || '%' OR description ILIKE '%' || $1 || '%')
+    AND ($2::network_type IS NULL OR network = $2)
+    AND ($3::text IS NULL OR category = $3)
+    AND (NOT $4 OR is_verified)";
+
+#[async_trait]
+impl RegistryRepository for PgRepository {
+    fn backend(&self) -> Backend {
+        Backend::Postgres
+    }
+
+    async fn migrate(&self) -> Result<(), sqlx::Error> {
+        sqlx::migrate!("../../database/migrations")
+            .run(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn upsert_publisher(&self, stellar_address: &str) -> Result<Publisher, sqlx::Error> {
+        sqlx::query_as(
+            "INSERT INTO publishers (stellar_address) VALUES ($1)
+             ON CONFLICT (stellar_address) DO UPDATE SET stellar_address = EXCLUDED.stellar_address
+             RETURNING *",
+        )
+        .bind(stellar_address)
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    async fn insert_contract(
+        &self,
+        publisher: Uuid,
+        contract: &NewContract,
+    ) -> Result<Contract, sqlx::Error> {
+        sqlx::query_as(
+            "INSERT INTO contracts (contract_id, wasm_hash, name, namespace, description, publisher_id,
+                                    network, category, tags, license,
+                                    repository_url, homepage, documentation_url)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+             RETURNING *",
+        )
+        .bind(&contract.contract_id)
+        .bind(&contract.wasm_hash)
+        .bind(&contract.name)
+        .bind(&contract.namespace)
+        .bind(&contract.description)
+        .bind(publisher)
+        .bind(&contract.network)
+        .bind(&contract.category)
+        .bind(&contract.tags)
+        .bind(&contract.license)
+        .bind(&contract.urls.repository_url)
+        .bind(&contract.urls.homepage)
+        .bind(&contract.urls.documentation_url)
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    async fn contract(&self, id: Uuid) -> Result<Option<Contract>, sqlx::Error> {
+        sqlx::query_as("SELECT * FROM contracts WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+    }
+
+    async fn public_contract(
+        &self,
+        id: Uuid,
+        tenant_id: Uuid,
+    ) -> Result<Option<Contract>, sqlx::Error> {
+        sqlx::query_as(&format!(
+            "SELECT * FROM contracts WHERE {} AND id = $2",
+            public("$1")
+        ))
+        .bind(tenant_id)
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    async fn contract_by_address(
+        &self,
+        contract_id: &str,
+        network: &Network,
+    ) -> Result<Option<Contract>, sqlx::Error> {
+        sqlx::query_as("SELECT * FROM contracts WHERE contract_id = $1 AND network = $2")
+            .bind(contract_id)
+            .bind(network)
+            .fetch_optional(&self.pool)
+            .await
+    }
+
+    async fn list_contracts(
+        &self,
+        filter: &ContractFilter,
+    ) -> Result<(Vec<Contract>, i64), sqlx::Error> {
+        let contracts = sqlx::query_as(&format!(
+            "SELECT * FROM contracts WHERE {} AND {} ORDER BY created_at DESC LIMIT $6 OFFSET $7",
+            public("$5"),
+            FILTER
+        ))
+        .bind(&filter.query)
+        .bind(&filter.network)
+        .bind(&filter.category)
+        .bind(filter.verified_only)
+        .bind(filter.tenant_id)
+        .bind(filter.limit)
+        .bind(filter.offset)
+        .fetch_all(&self.pool)
+        .await?;
+        let total = sqlx::query_scalar(&format!(
+            "SELECT COUNT(*) FROM contracts WHERE {} AND {}",
+            public("$5"),
+            FILTER
+        ))
+        .bind(&filter.query)
+        .bind(&filter.network)
+        .bind(&filter.category)
+        .bind(filter.verified_only)
+        .bind(filter.tenant_id)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok((contracts, total))
+    }
+
+    async fn insert_version(&self, version: &NewVersion) -> Result<ContractVersion, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        let created: ContractVersion = sqlx::query_as(
+            "INSERT INTO contract_versions
+                (contract_id, version, wasm_hash, source_url, commit_hash, release_notes)
+             VALUES ($1, $2, $3, $4, $5, $6)
+             RETURNING *",
+        )
+        .bind(version.contract)
+        .bind(&version.version)
+        .bind(&version.wasm_hash)
+        .bind(&version.source_url)
+        .bind(&version.commit_hash)
+        .bind(&version.release_notes)
+        .fetch_one(&mut *tx)
+        .await?;
+        if let Some(abi) = &version.abi {
+            sqlx::query(
+                "INSERT INTO contract_abis (contract_id, version, abi) VALUES ($1, $2, $3)",
+            )
+            .bind(version.contract)
+            .bind(&version.version)
+            .bind(abi)
+            .execute(&mut *tx)
+            .await?;
+        }
+        sqlx::query("UPDATE contracts SET wasm_hash = $2, updated_at = NOW() WHERE id = $1")
+            .bind(version.contract)
+            .bind(&version.wasm_hash)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        Ok(created)
+    }
+
+    async fn versions(&self, contract: Uuid) -> Result<Vec<ContractVersion>, sqlx::Error> {
+        sqlx::query_as(
+            "SELECT * FROM contract_versions WHERE contract_id = $1 ORDER BY created_at DESC",
+        )
+        .bind(contract)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    async fn abi(&self, contract: Uuid) -> Result<Option<Value>, sqlx::Error> {
+        // Contracts published before per-version ABIs keep theirs on the contract row
+        let abi: Option<Option<Value>> = sqlx::query_scalar(
+            "SELECT COALESCE(
+                 (SELECT abi FROM contract_abis WHERE contract_id = $1
+                  ORDER BY created_at DESC LIMIT 1),
+                 abi)
+             FROM contracts WHERE id = $1",
+        )
+        .bind(contract)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(abi.flatten())
+    }
+
+    async fn readme(&self, contract: Uuid) -> Result<Option<String>, sqlx::Error> {
+        sqlx::query_scalar("SELECT content FROM contract_readmes WHERE contract_id = $1")
+            .bind(contract)
+            .fetch_optional(&self.pool)
+            .await
+    }
+
+    async fn set_readme(&self, contract: Uuid, content: &str) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "INSERT INTO contract_readmes (contract_id, content) VALUES ($1, $2)
+             ON CONFLICT (contract_id) DO UPDATE SET content = EXCLUDED.content, updated_at = NOW()",
+        )
+        .bind(contract)
+        .bind(content)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+}
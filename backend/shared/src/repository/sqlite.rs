@@ -0,0 +1,543 @@
+//! SQLite backend, over the schema in `database/sqlite_migrations`
+//!
+//! SQLite has no enums, arrays or UUID type, so networks are stored as text,
+//! tags as a JSON array and ids as hyphenated UUID text. Columns the Postgres
+//! schema has but this one does not read back as their defaults.
+
+use std::str::FromStr;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePool, SqlitePoolOptions};
+use sqlx::types::Json;
+use sqlx::FromRow;
+use uuid::fmt::Hyphenated;
+use uuid::Uuid;
+
+use super::{Backend, ContractFilter, NewContract, NewVersion, RegistryRepository};
+use crate::models::{Contract, ContractVersion, Network, ProjectUrls, Publisher};
+
+pub struct SqliteRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Open (creating if needed) the database at `url`, e.g. `sqlite://registry.db`
+    pub async fn connect(url: &str, max_connections: u32) -> Result<Self, sqlx::Error> {
+        let options = SqliteConnectOptions::from_str(url)?
+            .create_if_missing(true)
+            .foreign_keys(true);
+        // Every connection to an in-memory database opens a different database
+        let max_connections = if url.contains(":memory:") {
+            1
+        } else {
+            max_connections
+        };
+        let pool = SqlitePoolOptions::new()
+            .max_connections(max_connections)
+            .connect_with(options)
+            .await?;
+        Ok(Self::new(pool))
+    }
+
+    pub fn pool(&self) -> &SqlitePool {
+        &self.pool
+    }
+}
+
+fn parse_network(value: &str) -> Result<Network, sqlx::Error> {
+    match value {
+        "mainnet" => Ok(Network::Mainnet),
+        "testnet" => Ok(Network::Testnet),
+        "futurenet" => Ok(Network::Futurenet),
+        other => Err(sqlx::Error::Decode(
+            format!("unknown network '{}'", other).into(),
+        )),
+    }
+}
+
+#[derive(FromRow)]
+struct PublisherRow {
+    id: Hyphenated,
+    stellar_address: String,
+    username: Option<String>,
+    email: Option<String>,
+    github_url: Option<String>,
+    website: Option<String>,
+    namespace: Option<String>,
+    created_at: DateTime<Utc>,
+}
+
+impl From<PublisherRow> for Publisher {
+    fn from(row: PublisherRow) -> Self {
+        Publisher {
+            id: row.id.into_uuid(),
+            stellar_address: row.stellar_address,
+            username: row.username,
+            email: row.email,
+            github_url: row.github_url,
+            website: row.website,
+            created_at: row.created_at,
+            namespace: row.namespace,
+            namespace_tenant_id: None,
+            verified_account: None,
+            identity_verified_at: None,
+        }
+    }
+}
+
+#[derive(FromRow)]
+struct ContractRow {
+    id: Hyphenated,
+    contract_id: String,
+    wasm_hash: String,
+    name: String,
+    namespace: Option<String>,
+    description: Option<String>,
+    publisher_id: Hyphenated,
+    network: String,
+    is_verified: bool,
+    category: Option<String>,
+    tags: Json<Vec<String>>,
+    license: Option<String>,
+    repository_url: Option<String>,
+    homepage: Option<String>,
+    documentation_url: Option<String>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl TryFrom<ContractRow> for Contract {
+    type Error = sqlx::Error;
+
+    fn try_from(row: ContractRow) -> Result<Self, Self::Error> {
+        Ok(Contract {
+            id: row.id.into_uuid(),
+            contract_id: row.contract_id,
+            wasm_hash: row.wasm_hash,
+            name: row.name,
+            description: row.description,
+            publisher_id: row.publisher_id.into_uuid(),
+            network: parse_network(&row.network)?,
+            is_verified: row.is_verified,
+            category: row.category,
+            tags: row.tags.0,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+            health_score: 0,
+            is_maintenance: false,
+            logical_id: None,
+            network_configs: None,
+            implements: Vec::new(),
+            license: row.license,
+            namespace: row.namespace,
+            is_private: false,
+            organization_id: None,
+            links: serde_json::Value::Object(Default::default()),
+            forked_from: None,
+            star_count: 0,
+            quality_score: 0.0,
+            moderation_status: Default::default(),
+            urls: ProjectUrls {
+                repository_url: row.repository_url,
+                homepage: row.homepage,
+                documentation_url: row.documentation_url,
+            },
+        })
+    }
+}
+
+#[derive(FromRow)]
+struct VersionRow {
+    id: Hyphenated,
+    contract_id: Hyphenated,
+    version: String,
+    wasm_hash: String,
+    source_url: Option<String>,
+    commit_hash: Option<String>,
+    release_notes: Option<String>,
+    created_at: DateTime<Utc>,
+}
+
+impl From<VersionRow> for ContractVersion {
+    fn from(row: VersionRow) -> Self {
+        ContractVersion {
+            id: row.id.into_uuid(),
+            contract_id: row.contract_id.into_uuid(),
+            version: row.version,
+            wasm_hash: row.wasm_hash,
+            source_url: row.source_url,
+            commit_hash: row.commit_hash,
+            release_notes: row.release_notes,
+            created_at: row.created_at,
+            state_schema: None,
+            signature: None,
+            publisher_key: None,
+            signature_algorithm: None,
+        }
+    }
+}
+
+const FILTER: &str =
+    "(?1 IS NULL OR name LIKE '%' || ?1 || '%' OR description LIKE '%' || ?1 || '%')
+    AND (?2 IS NULL OR network = ?2)
+    AND (?3 IS NULL OR category = ?3)
+    AND (NOT ?4 OR is_verified)";
+
+#[async_trait]
+impl RegistryRepository for SqliteRepository {
+    fn backend(&self) -> Backend {
+        Backend::Sqlite
+    }
+
+    async fn migrate(&self) -> Result<(), sqlx::Error> {
+        sqlx::migrate!("../../database/sqlite_migrations")
+            .run(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn upsert_publisher(&self, stellar_address: &str) -> Result<Publisher, sqlx::Error> {
+        let row: PublisherRow = sqlx::query_as(
+            "INSERT INTO publishers (id, stellar_address, created_at) VALUES (?1, ?2, ?3)
+             ON CONFLICT (stellar_address) DO UPDATE SET stellar_address = excluded.stellar_address
+             RETURNING *",
+        )
+        .bind(Uuid::new_v4().hyphenated())
+        .bind(stellar_address)
+        .bind(Utc::now())
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(row.into())
+    }
+
+    async fn insert_contract(
+        &self,
+        publisher: Uuid,
+        contract: &NewContract,
+    ) -> Result<Contract, sqlx::Error> {
+        let now = Utc::now();
+        let row: ContractRow = sqlx::query_as(
+            "INSERT INTO contracts (id, contract_id, wasm_hash, name, namespace, description,
+                                    publisher_id, network, category, tags, license,
+                                    repository_url, homepage, documentation_url,
+                                    created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?15)
+             RETURNING *",
+        )
+        .bind(Uuid::new_v4().hyphenated())
+        .bind(&contract.contract_id)
+        .bind(&contract.wasm_hash)
+        .bind(&contract.name)
+        .bind(&contract.namespace)
+        .bind(&contract.description)
+        .bind(publisher.hyphenated())
+        .bind(contract.network.to_string())
+        .bind(&contract.category)
+        .bind(Json(&contract.tags))
+        .bind(&contract.license)
+        .bind(&contract.urls.repository_url)
+        .bind(&contract.urls.homepage)
+        .bind(&contract.urls.documentation_url)
+        .bind(now)
+        .fetch_one(&self.pool)
+        .await?;
+        row.try_into()
+    }
+
+    async fn contract(&self, id: Uuid) -> Result<Option<Contract>, sqlx::Error> {
+        let row: Option<ContractRow> = sqlx::query_as("SELECT * FROM contracts WHERE id = ?1")
+            .bind(id.hyphenated())
+            .fetch_optional(&self.pool)
+            .await?;
+        row.map(Contract::try_from).transpose()
+    }
+
+    async fn public_contract(
+        &self,
+        id: Uuid,
+        _tenant_id: Uuid,
+    ) -> Result<Option<Contract>, sqlx::Error> {
+        // One tenant, and nothing here marks contracts private or moderates them
+        Ok(self
+            .contract(id)
+            .await?
+            .filter(|contract| !contract.is_private))
+    }
+
+    async fn contract_by_address(
+        &self,
+        contract_id: &str,
+        network: &Network,
+    ) -> Result<Option<Contract>, sqlx::Error> {
+        let row: Option<ContractRow> =
+            sqlx::query_as("SELECT * FROM contracts WHERE contract_id = ?1 AND network = ?2")
+                .bind(contract_id)
+                .bind(network.to_string())
+                .fetch_optional(&self.pool)
+                .await?;
+        row.map(Contract::try_from).transpose()
+    }
+
+    async fn list_contracts(
+        &self,
+        filter: &ContractFilter,
+    ) -> Result<(Vec<Contract>, i64), sqlx::Error> {
+        let network = filter.network.as_ref().map(Network::to_string);
+        let rows: Vec<ContractRow> = sqlx::query_as(&format!(
+            "SELECT * FROM contracts WHERE {} ORDER BY created_at DESC, rowid DESC LIMIT ?5 OFFSET ?6",
+            FILTER
+        ))
+        .bind(&filter.query)
+        .bind(&network)
+        .bind(&filter.category)
+        .bind(filter.verified_only)
+        .bind(filter.limit)
+        .bind(filter.offset)
+        .fetch_all(&self.pool)
+        .await?;
+        let total = sqlx::query_scalar(&format!("SELECT COUNT(*) FROM contracts WHERE {}", FILTER))
+            .bind(&filter.query)
+            .bind(&network)
+            .bind(&filter.category)
+            .bind(filter.verified_only)
+            .fetch_one(&self.pool)
+            .await?;
+        let contracts = rows
+            .into_iter()
+            .map(Contract::try_from)
+            .collect::<Result<_, _>>()?;
+        Ok((contracts, total))
+    }
+
+    async fn insert_version(&self, version: &NewVersion) -> Result<ContractVersion, sqlx::Error> {
+        let now = Utc::now();
+        let mut tx = self.pool.begin().await?;
+        let row: VersionRow = sqlx::query_as(
+            "INSERT INTO contract_versions
+                (id, contract_id, version, wasm_hash, source_url, commit_hash, release_notes, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+             RETURNING *",
+        )
+        .bind(Uuid::new_v4().hyphenated())
+        .bind(version.contract.hyphenated())
+        .bind(&version.version)
+        .bind(&version.wasm_hash)
+        .bind(&version.source_url)
+        .bind(&version.commit_hash)
+        .bind(&version.release_notes)
+        .bind(now)
+        .fetch_one(&mut *tx)
+        .await?;
+        if let Some(abi) = &version.abi {
+            sqlx::query(
+                "INSERT INTO contract_abis (contract_id, version, abi, created_at)
+                 VALUES (?1, ?2, ?3, ?4)",
+            )
+            .bind(version.contract.hyphenated())
+            .bind(&version.version)
+            .bind(Json(abi))
+            .bind(now)
+            .execute(&mut *tx)
+            .await?;
+        }
+        sqlx::query("UPDATE contracts SET wasm_hash = ?2, updated_at = ?3 WHERE id = ?1")
+            .bind(version.contract.hyphenated())
+            .bind(&version.wasm_hash)
+            .bind(now)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        Ok(row.into())
+    }
+
+    async fn versions(&self, contract: Uuid) -> Result<Vec<ContractVersion>, sqlx::Error> {
+        let rows: Vec<VersionRow> = sqlx::query_as(
+            "SELECT * FROM contract_versions WHERE contract_id = ?1
+             ORDER BY created_at DESC, rowid DESC",
+        )
+        .bind(contract.hyphenated())
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows.into_iter().map(ContractVersion::from).collect())
+    }
+
+    async fn abi(&self, contract: Uuid) -> Result<Option<Value>, sqlx::Error> {
+        let abi: Option<Json<Value>> = sqlx::query_scalar(
+            "SELECT abi FROM contract_abis WHERE contract_id = ?1
+             ORDER BY created_at DESC, rowid DESC LIMIT 1",
+        )
+        .bind(contract.hyphenated())
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(abi.map(|abi| abi.0))
+    }
+
+    async fn readme(&self, contract: Uuid) -> Result<Option<String>, sqlx::Error> {
+        sqlx::query_scalar("SELECT content FROM contract_readmes WHERE contract_id = ?1")
+            .bind(contract.hyphenated())
+            .fetch_optional(&self.pool)
+            .await
+    }
+
+    async fn set_readme(&self, contract: Uuid, content: &str) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "INSERT INTO contract_readmes (contract_id, content, updated_at) VALUES (?1, ?2, ?3)
+             ON CONFLICT (contract_id) DO UPDATE SET content = excluded.content,
+                                                     updated_at = excluded.updated_at",
+        )
+        .bind(contract.hyphenated())
+        .bind(content)
+        .bind(Utc::now())
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn repository() -> SqliteRepository {
+        let repository = SqliteRepository::connect("sqlite::memory:", 5)
+            .await
+            .unwrap();
+        repository.migrate().await.unwrap();
+        repository
+    }
+
+    fn new_contract(name: &str, network: Network) -> NewContract {
+        NewContract {
+            contract_id: format!("C{:0>55}", name.to_uppercase()),
+            wasm_hash: "placeholder_hash".to_string(),
+            name: name.to_string(),
+            namespace: None,
+            description: Some(format!("The {} contract", name)),
+            network,
+            category: Some("defi".to_string()),
+            tags: vec!["swap".to_string(), "amm".to_string()],
+            license: Some("MIT".to_string()),
+            urls: ProjectUrls::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn contracts_round_trip_through_sqlite() {
+        let repo = repository().await;
+        let publisher = repo.upsert_publisher("GPUBLISHER").await.unwrap();
+        assert_eq!(
+            repo.upsert_publisher("GPUBLISHER").await.unwrap().id,
+            publisher.id
+        );
+
+        let created = repo
+            .insert_contract(publisher.id, &new_contract("amm", Network::Testnet))
+            .await
+            .unwrap();
+        assert_eq!(created.tags, vec!["swap", "amm"]);
+        assert_eq!(created.network, Network::Testnet);
+
+        let fetched = repo.contract(created.id).await.unwrap().unwrap();
+        assert_eq!(fetched.contract_id, created.contract_id);
+        assert_eq!(fetched.publisher_id, publisher.id);
+        assert!(repo
+            .contract_by_address(&created.contract_id, &Network::Mainnet)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn listing_filters_and_pages() {
+        let repo = repository().await;
+        let publisher = repo.upsert_publisher("GPUBLISHER").await.unwrap();
+        for (name, network) in [
+            ("amm", Network::Testnet),
+            ("oracle", Network::Mainnet),
+            ("vault", Network::Testnet),
+        ] {
+            repo.insert_contract(publisher.id, &new_contract(name, network))
+                .await
+                .unwrap();
+        }
+
+        let (all, total) = repo
+            .list_contracts(&ContractFilter::default())
+            .await
+            .unwrap();
+        assert_eq!(total, 3);
+        assert_eq!(all[0].name, "vault");
+
+        let filter = ContractFilter {
+            network: Some(Network::Testnet),
+            limit: 1,
+            ..Default::default()
+        };
+        let (page, total) = repo.list_contracts(&filter).await.unwrap();
+        assert_eq!((page.len(), total), (1, 2));
+
+        let filter = ContractFilter {
+            query: Some("ORACLE".to_string()),
+            ..Default::default()
+        };
+        let (found, _) = repo.list_contracts(&filter).await.unwrap();
+        assert_eq!(found[0].name, "oracle");
+    }
+
+    #[tokio::test]
+    async fn versions_update_the_contract_hash() {
+        let repo = repository().await;
+        let publisher = repo.upsert_publisher("GPUBLISHER").await.unwrap();
+        let contract = repo
+            .insert_contract(publisher.id, &new_contract("amm", Network::Testnet))
+            .await
+            .unwrap();
+        for (version, hash) in [("1.0.0", "aa"), ("1.1.0", "bb")] {
+            repo.insert_version(&NewVersion {
+                contract: contract.id,
+                version: version.to_string(),
+                wasm_hash: hash.to_string(),
+                source_url: None,
+                commit_hash: None,
+                release_notes: None,
+                abi: Some(serde_json::json!([{"type": "function", "name": version}])),
+            })
+            .await
+            .unwrap();
+        }
+
+        let versions = repo.versions(contract.id).await.unwrap();
+        assert_eq!(versions[0].version, "1.1.0");
+        assert_eq!(
+            repo.contract(contract.id).await.unwrap().unwrap().wasm_hash,
+            "bb"
+        );
+        assert_eq!(
+            repo.abi(contract.id).await.unwrap().unwrap()[0]["name"],
+            "1.1.0"
+        );
+    }
+
+    #[tokio::test]
+    async fn readmes_are_replaced() {
+        let repo = repository().await;
+        let publisher = repo.upsert_publisher("GPUBLISHER").await.unwrap();
+        let contract = repo
+            .insert_contract(publisher.id, &new_contract("amm", Network::Testnet))
+            .await
+            .unwrap();
+        assert!(repo.readme(contract.id).await.unwrap().is_none());
+        repo.set_readme(contract.id, "# AMM").await.unwrap();
+        repo.set_readme(contract.id, "# AMM v2").await.unwrap();
+        assert_eq!(
+            repo.readme(contract.id).await.unwrap().as_deref(),
+            Some("# AMM v2")
+        );
+    }
+}
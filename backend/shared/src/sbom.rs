@@ -0,0 +1,286 @@
+//! Software Bill of Materials generation shared by the API and the CLI.
+//! Emits CycloneDX 1.5 or SPDX 2.3 JSON documents.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+/// SPDX identifier of the package the document describes
+const SPDX_ROOT_ID: &str = "SPDXRef-Package-0";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SbomFormat {
+    CycloneDx,
+    Spdx,
+}
+
+impl std::str::FromStr for SbomFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "cyclonedx" | "cdx" => Ok(SbomFormat::CycloneDx),
+            "spdx" => Ok(SbomFormat::Spdx),
+            other => Err(format!(
+                "Invalid SBOM format: {}. Allowed values: cyclonedx, spdx",
+                other
+            )),
+        }
+    }
+}
+
+/// Where a component comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ComponentKind {
+    /// Another contract published in the registry
+    Registry,
+    /// A Rust crate resolved through Cargo.lock
+    Crate,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SbomComponent {
+    pub kind: ComponentKind,
+    pub name: String,
+    pub version: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub checksum: Option<String>,
+}
+
+impl SbomComponent {
+    /// Package URL identifying the component
+    pub fn purl(&self) -> String {
+        match self.kind {
+            ComponentKind::Registry => {
+                format!("pkg:soroban-registry/{}@{}", self.name, self.version)
+            }
+            ComponentKind::Crate => format!("pkg:cargo/{}@{}", self.name, self.version),
+        }
+    }
+}
+
+/// Parse the `[[package]]` entries of a Cargo.lock file into crate components.
+/// Only the `name`, `version` and `checksum` keys are read; path dependencies
+/// (no `source`) belong to the project itself and are skipped.
+pub fn parse_cargo_lock(contents: &str) -> Vec<SbomComponent> {
+    let mut components = Vec::new();
+    let mut current: Option<(String, String, Option<String>, bool)> = None;
+
+    let mut flush = |entry: Option<(String, String, Option<String>, bool)>| {
+        if let Some((name, version, checksum, has_source)) = entry {
+            if has_source && !name.is_empty() {
+                components.push(SbomComponent {
+                    kind: ComponentKind::Crate,
+                    name,
+                    version,
+                    checksum,
+                });
+            }
+        }
+    };
+
+    for line in contents.lines().map(str::trim) {
+        if line == "[[package]]" {
+            flush(current.take());
+            current = Some((String::new(), String::new(), None, false));
+            continue;
+        }
+        if line.starts_with('[') {
+            flush(current.take());
+            continue;
+        }
+        let Some(entry) = current.as_mut() else {
+            continue;
+        };
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let value = value.trim().trim_matches('"').to_string();
+        match key.trim() {
+            "name" => entry.0 = value,
+            "version" => entry.1 = value,
+            "checksum" => entry.2 = Some(value),
+            "source" => entry.3 = true,
+            _ => {}
+        }
+    }
+    flush(current.take());
+
+    components
+}
+
+fn cyclonedx_component(c: &SbomComponent) -> Value {
+    let mut component = json!({
+        "type": "library",
+        "name": c.name,
+        "version": c.version,
+        "purl": c.purl(),
+    });
+    if let Some(ref checksum) = c.checksum {
+        component["hashes"] = json!([{ "alg": "SHA-256", "content": checksum }]);
+    }
+    component
+}
+
+/// Build an SBOM document describing `subject` and its components
+pub fn build_sbom(
+    format: SbomFormat,
+    subject_name: &str,
+    subject_version: &str,
+    components: &[SbomComponent],
+) -> Value {
+    let timestamp = chrono::Utc::now().to_rfc3339();
+    match format {
+        SbomFormat::CycloneDx => json!({
+            "bomFormat": "CycloneDX",
+            "specVersion": "1.5",
+            "serialNumber": format!("urn:uuid:{}", uuid::Uuid::new_v4()),
+            "version": 1,
+            "metadata": {
+                "timestamp": timestamp,
+                "tools": [{ "vendor": "Soroban Registry", "name": "soroban-registry" }],
+                "component": {
+                    "type": "application",
+                    "name": subject_name,
+                    "version": subject_version,
+                    "purl": format!("pkg:soroban-registry/{}@{}", subject_name, subject_version),
+                },
+            },
+            "components": components.iter().map(cyclonedx_component).collect::<Vec<_>>(),
+        }),
+        SbomFormat::Spdx => {
+            let root = json!({
+                "SPDXID": SPDX_ROOT_ID,
+                "name": subject_name,
+                "versionInfo": subject_version,
+                "downloadLocation": "NOASSERTION",
+                "primaryPackagePurpose": "APPLICATION",
+                "externalRefs": [{
+                    "referenceCategory": "PACKAGE-MANAGER",
+                    "referenceType": "purl",
+                    "referenceLocator": format!(
+                        "pkg:soroban-registry/{}@{}",
+                        subject_name, subject_version
+                    ),
+                }],
+            });
+            let packages: Vec<Value> = std::iter::once(root)
+                .chain(components.iter().enumerate().map(|(idx, c)| {
+                    let mut package = json!({
+                        "SPDXID": format!("SPDXRef-Package-{}", idx + 1),
+                        "name": c.name,
+                        "versionInfo": c.version,
+                        "downloadLocation": "NOASSERTION",
+                        "externalRefs": [{
+                            "referenceCategory": "PACKAGE-MANAGER",
+                            "referenceType": "purl",
+                            "referenceLocator": c.purl(),
+                        }],
+                    });
+                    if let Some(ref checksum) = c.checksum {
+                        package["checksums"] =
+                            json!([{ "algorithm": "SHA256", "checksumValue": checksum }]);
+                    }
+                    package
+                }))
+                .collect();
+            // The document describes the contract, which depends on every component
+            let relationships: Vec<Value> = std::iter::once(json!({
+                "spdxElementId": "SPDXRef-DOCUMENT",
+                "relationshipType": "DESCRIBES",
+                "relatedSpdxElement": SPDX_ROOT_ID,
+            }))
+            .chain((1..=components.len()).map(|idx| {
+                json!({
+                    "spdxElementId": SPDX_ROOT_ID,
+                    "relationshipType": "DEPENDS_ON",
+                    "relatedSpdxElement": format!("SPDXRef-Package-{}", idx),
+                })
+            }))
+            .collect();
+            json!({
+                "spdxVersion": "SPDX-2.3",
+                "dataLicense": "CC0-1.0",
+                "SPDXID": "SPDXRef-DOCUMENT",
+                "name": format!("{}-{}", subject_name, subject_version),
+                "documentNamespace": format!(
+                    "https://soroban-registry.dev/spdx/{}/{}/{}",
+                    subject_name,
+                    subject_version,
+                    uuid::Uuid::new_v4()
+                ),
+                "creationInfo": {
+                    "created": timestamp,
+                    "creators": ["Tool: soroban-registry"],
+                },
+                "packages": packages,
+                "relationships": relationships,
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOCK: &str = r#"
+version = 3
+
+[[package]]
+name = "my-contract"
+version = "0.1.0"
+dependencies = ["soroban-sdk"]
+
+[[package]]
+name = "soroban-sdk"
+version = "21.0.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+checksum = "abc123"
+"#;
+
+    #[test]
+    fn parses_cargo_lock_skipping_local_packages() {
+        let components = parse_cargo_lock(LOCK);
+        assert_eq!(components.len(), 1);
+        assert_eq!(components[0].name, "soroban-sdk");
+        assert_eq!(components[0].version, "21.0.0");
+        assert_eq!(components[0].checksum.as_deref(), Some("abc123"));
+    }
+
+    #[test]
+    fn builds_cyclonedx_and_spdx_documents() {
+        let components = parse_cargo_lock(LOCK);
+        let cdx = build_sbom(SbomFormat::CycloneDx, "token", "1.0.0", &components);
+        assert_eq!(cdx["bomFormat"], "CycloneDX");
+        assert_eq!(cdx["components"][0]["purl"], "pkg:cargo/soroban-sdk@21.0.0");
+
+        let spdx = build_sbom(SbomFormat::Spdx, "token", "1.0.0", &components);
+        assert_eq!(spdx["spdxVersion"], "SPDX-2.3");
+        assert_eq!(spdx["packages"].as_array().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn spdx_describes_the_contract_which_depends_on_its_components() {
+        let components = parse_cargo_lock(LOCK);
+        let spdx = build_sbom(SbomFormat::Spdx, "token", "1.0.0", &components);
+        assert_eq!(spdx["packages"][0]["SPDXID"], SPDX_ROOT_ID);
+        assert_eq!(spdx["packages"][0]["name"], "token");
+        assert_eq!(
+            spdx["relationships"],
+            json!([
+                {
+                    "spdxElementId": "SPDXRef-DOCUMENT",
+                    "relationshipType": "DESCRIBES",
+                    "relatedSpdxElement": SPDX_ROOT_ID,
+                },
+                {
+                    "spdxElementId": SPDX_ROOT_ID,
+                    "relationshipType": "DEPENDS_ON",
+                    "relatedSpdxElement": "SPDXRef-Package-1",
+                },
+            ])
+        );
+    }
+}
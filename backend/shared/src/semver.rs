@@ -93,3 +93,145 @@ impl VersionConstraint {
         }
     }
 }
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Comparator {
+    Any,
+    Greater(SemVer),
+    GreaterEq(SemVer),
+    Less(SemVer),
+    LessEq(SemVer),
+    Exact(SemVer),
+    Caret(SemVer),
+    Tilde(SemVer),
+}
+
+impl Comparator {
+    fn parse(s: &str) -> Option<Self> {
+        if s == "*" {
+            return Some(Comparator::Any);
+        }
+        let (make, rest): (fn(SemVer) -> Self, &str) = if let Some(rest) = s.strip_prefix(">=") {
+            (Comparator::GreaterEq, rest)
+        } else if let Some(rest) = s.strip_prefix("<=") {
+            (Comparator::LessEq, rest)
+        } else if let Some(rest) = s.strip_prefix('>') {
+            (Comparator::Greater, rest)
+        } else if let Some(rest) = s.strip_prefix('<') {
+            (Comparator::Less, rest)
+        } else if let Some(rest) = s.strip_prefix('=') {
+            (Comparator::Exact, rest)
+        } else if let Some(rest) = s.strip_prefix('^') {
+            (Comparator::Caret, rest)
+        } else if let Some(rest) = s.strip_prefix('~') {
+            (Comparator::Tilde, rest)
+        } else {
+            (Comparator::Exact, s)
+        };
+        SemVer::parse(rest.trim()).map(make)
+    }
+
+    fn matches(&self, version: &SemVer) -> bool {
+        match self {
+            Comparator::Any => true,
+            Comparator::Greater(v) => version > v,
+            Comparator::GreaterEq(v) => version >= v,
+            Comparator::Less(v) => version < v,
+            Comparator::LessEq(v) => version <= v,
+            Comparator::Exact(v) => VersionConstraint::Exact(v.clone()).matches(version),
+            Comparator::Caret(v) => VersionConstraint::Caret(v.clone()).matches(version),
+            Comparator::Tilde(v) => VersionConstraint::Tilde(v.clone()).matches(version),
+        }
+    }
+}
+
+/// A set of versions such as `>=1.0.0, <1.4.2 || ^2.0.0`: comparators separated
+/// by commas or spaces must all hold, and `||` separates alternatives. A bare
+/// version matches only itself.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionRange {
+    alternatives: Vec<Vec<Comparator>>,
+}
+
+impl VersionRange {
+    pub fn parse(s: &str) -> Option<Self> {
+        let alternatives = s
+            .split("||")
+            .map(|alternative| {
+                let mut comparators = Vec::new();
+                let mut pending_op = String::new();
+                for token in alternative.split(|c: char| c == ',' || c.is_whitespace()) {
+                    if token.is_empty() {
+                        continue;
+                    }
+                    // `>= 1.0.0` splits the operator from its version
+                    if token.chars().all(|c| "<>=^~".contains(c)) {
+                        pending_op.push_str(token);
+                        continue;
+                    }
+                    let token = std::mem::take(&mut pending_op) + token;
+                    comparators.push(Comparator::parse(&token)?);
+                }
+                if comparators.is_empty() || !pending_op.is_empty() {
+                    return None;
+                }
+                Some(comparators)
+            })
+            .collect::<Option<Vec<_>>>()?;
+        Some(VersionRange { alternatives })
+    }
+
+    pub fn matches(&self, version: &SemVer) -> bool {
+        self.alternatives
+            .iter()
+            .any(|comparators| comparators.iter().all(|c| c.matches(version)))
+    }
+
+    /// Like `matches`, for a version string; versions that are not SemVer never match
+    pub fn matches_str(&self, version: &str) -> bool {
+        SemVer::parse(version.trim()).is_some_and(|v| self.matches(&v))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matches(range: &str, version: &str) -> bool {
+        VersionRange::parse(range).unwrap().matches_str(version)
+    }
+
+    #[test]
+    fn bare_version_is_exact() {
+        assert!(matches("1.2.3", "1.2.3"));
+        assert!(!matches("1.2.3", "1.2.4"));
+    }
+
+    #[test]
+    fn comparators_are_intersected() {
+        assert!(matches(">=1.0.0, <1.4.2", "1.0.0"));
+        assert!(matches(">= 1.0.0 < 1.4.2", "1.4.1"));
+        assert!(!matches(">=1.0.0, <1.4.2", "1.4.2"));
+        assert!(!matches(">=1.0.0, <1.4.2", "0.9.9"));
+    }
+
+    #[test]
+    fn alternatives_are_united() {
+        let range = ">1.0.0, <=1.1.0 || ~2.3.0 || ^3.1.0";
+        assert!(matches(range, "1.1.0"));
+        assert!(matches(range, "2.3.9"));
+        assert!(matches(range, "3.9.0"));
+        assert!(!matches(range, "1.0.0"));
+        assert!(!matches(range, "2.4.0"));
+    }
+
+    #[test]
+    fn rejects_malformed_ranges() {
+        assert!(VersionRange::parse("").is_none());
+        assert!(VersionRange::parse(">=1.0").is_none());
+        assert!(VersionRange::parse("1.0.0 ||").is_none());
+        assert!(VersionRange::parse("1.0.0 >=").is_none());
+        assert!(!matches("*", "latest"));
+        assert!(matches("*", "0.0.1"));
+    }
+}
@@ -0,0 +1,140 @@
+//! Stellar "strkey" encoding of ed25519 keys: `G...` account IDs, `S...`
+//! secret seeds and `C...` contract IDs. Base32 of a version byte, the 32 key
+//! bytes and a CRC16-XModem checksum (little-endian).
+
+const VERSION_ACCOUNT_ID: u8 = 6 << 3;
+const VERSION_SECRET_SEED: u8 = 18 << 3;
+const VERSION_CONTRACT: u8 = 2 << 3;
+
+/// Length of an encoded key: 35 bytes in unpadded base32
+pub const STRKEY_LENGTH: usize = 56;
+
+const BASE32_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+fn crc16_xmodem(data: &[u8]) -> u16 {
+    let mut crc: u16 = 0;
+    for byte in data {
+        crc ^= (*byte as u16) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            };
+        }
+    }
+    crc
+}
+
+fn base32_encode(data: &[u8]) -> String {
+    let mut out = String::with_capacity((data.len() * 8).div_ceil(5));
+    let (mut buffer, mut bits) = (0u32, 0u32);
+    for byte in data {
+        buffer = (buffer << 8) | *byte as u32;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            out.push(BASE32_ALPHABET[((buffer >> bits) & 31) as usize] as char);
+        }
+    }
+    if bits > 0 {
+        out.push(BASE32_ALPHABET[((buffer << (5 - bits)) & 31) as usize] as char);
+    }
+    out
+}
+
+fn base32_decode(text: &str) -> Option<Vec<u8>> {
+    let mut out = Vec::with_capacity(text.len() * 5 / 8);
+    let (mut buffer, mut bits) = (0u32, 0u32);
+    for c in text.bytes() {
+        let value = BASE32_ALPHABET.iter().position(|a| *a == c)? as u32;
+        buffer = (buffer << 5) | value;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((buffer >> bits) as u8);
+        }
+    }
+    Some(out)
+}
+
+fn encode(version: u8, key: &[u8; 32]) -> String {
+    let mut payload = Vec::with_capacity(35);
+    payload.push(version);
+    payload.extend_from_slice(key);
+    let checksum = crc16_xmodem(&payload);
+    payload.extend_from_slice(&checksum.to_le_bytes());
+    base32_encode(&payload)
+}
+
+fn decode(version: u8, text: &str) -> Option<[u8; 32]> {
+    if text.len() != STRKEY_LENGTH {
+        return None;
+    }
+    let payload = base32_decode(text)?;
+    let (body, checksum) = payload.split_at(33);
+    if body[0] != version || crc16_xmodem(body).to_le_bytes() != checksum {
+        return None;
+    }
+    body[1..].try_into().ok()
+}
+
+/// `G...` account ID for an ed25519 public key
+pub fn encode_account_id(public_key: &[u8; 32]) -> String {
+    encode(VERSION_ACCOUNT_ID, public_key)
+}
+
+/// Public key of a `G...` account ID, or `None` if it is malformed or its
+/// checksum does not match
+pub fn decode_account_id(account_id: &str) -> Option<[u8; 32]> {
+    decode(VERSION_ACCOUNT_ID, account_id)
+}
+
+/// Ed25519 seed of an `S...` secret key
+pub fn decode_secret_seed(secret: &str) -> Option<[u8; 32]> {
+    decode(VERSION_SECRET_SEED, secret)
+}
+
+/// Contract hash of a `C...` contract ID
+pub fn decode_contract_id(contract_id: &str) -> Option<[u8; 32]> {
+    decode(VERSION_CONTRACT, contract_id)
+}
+
+/// `C...` contract ID for a contract hash
+pub fn encode_contract_id(hash: &[u8; 32]) -> String {
+    encode(VERSION_CONTRACT, hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encodes_the_zero_account() {
+        assert_eq!(
+            encode_account_id(&[0u8; 32]),
+            "GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAWHF"
+        );
+    }
+
+    #[test]
+    fn round_trips_and_checks_version_and_checksum() {
+        let key = [7u8; 32];
+        let account = encode_account_id(&key);
+        assert_eq!(decode_account_id(&account), Some(key));
+        assert_eq!(decode_secret_seed(&account), None);
+
+        let seed = "SADQOBYHA4DQOBYHA4DQOBYHA4DQOBYHA4DQOBYHA4DQOBYHA4DQP54X";
+        assert_eq!(decode_secret_seed(seed), Some(key));
+
+        let mut tampered = account.clone();
+        tampered.replace_range(10..11, if &account[10..11] == "A" { "B" } else { "A" });
+        assert_eq!(decode_account_id(&tampered), None);
+        assert_eq!(decode_account_id("GABC"), None);
+
+        let contract = encode_contract_id(&key);
+        assert!(contract.starts_with('C'));
+        assert_eq!(decode_contract_id(&contract), Some(key));
+        assert_eq!(decode_contract_id(&account), None);
+    }
+}
@@ -0,0 +1,167 @@
+//! Support policies publishers declare per version: how long it gets fixes
+//! and whether it is a long-term-support line. End-of-life status is computed
+//! from them against the current date.
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+/// Days before `supported_until` that a version counts as ending soon, and
+/// dependents are alerted
+pub const EOL_WARNING_DAYS: i64 = 30;
+
+/// Where a version is in its support window
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SupportStatus {
+    /// Supported past the warning window
+    Supported,
+    /// Support ends within [`EOL_WARNING_DAYS`]
+    EndingSoon,
+    /// The supported-until date has passed
+    EndOfLife,
+    /// The publisher hasn't declared a supported-until date
+    Unspecified,
+}
+
+impl SupportStatus {
+    pub fn compute(supported_until: Option<NaiveDate>, today: NaiveDate) -> Self {
+        match supported_until {
+            None => SupportStatus::Unspecified,
+            Some(until) if until < today => SupportStatus::EndOfLife,
+            Some(until) if (until - today).num_days() <= EOL_WARNING_DAYS => {
+                SupportStatus::EndingSoon
+            }
+            Some(_) => SupportStatus::Supported,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SupportStatus::Supported => "supported",
+            SupportStatus::EndingSoon => "ending soon",
+            SupportStatus::EndOfLife => "end of life",
+            SupportStatus::Unspecified => "unspecified",
+        }
+    }
+}
+
+/// Body for PUT /api/contracts/:id/versions/:version/support. Replaces the
+/// version's policy; omitting `supported_until` clears the date.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SupportPolicyRequest {
+    #[serde(default)]
+    pub supported_until: Option<NaiveDate>,
+    #[serde(default)]
+    pub lts: bool,
+}
+
+/// A version's declared policy and its status today
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VersionSupport {
+    pub version: String,
+    pub supported_until: Option<NaiveDate>,
+    pub lts: bool,
+    pub status: SupportStatus,
+    /// Days until support ends; negative once it has
+    pub days_remaining: Option<i64>,
+}
+
+impl VersionSupport {
+    pub fn new(
+        version: String,
+        supported_until: Option<NaiveDate>,
+        lts: bool,
+        today: NaiveDate,
+    ) -> Self {
+        Self {
+            version,
+            supported_until,
+            lts,
+            status: SupportStatus::compute(supported_until, today),
+            days_remaining: supported_until.map(|until| (until - today).num_days()),
+        }
+    }
+
+    /// Installing this version deserves a warning
+    pub fn needs_warning(&self) -> bool {
+        matches!(
+            self.status,
+            SupportStatus::EndOfLife | SupportStatus::EndingSoon
+        )
+    }
+
+    /// One line describing the status, e.g. `end of life since 2026-01-31`
+    pub fn describe(&self) -> String {
+        let lts = if self.lts { "LTS, " } else { "" };
+        match (self.status, self.supported_until) {
+            (SupportStatus::EndOfLife, Some(until)) => {
+                format!("{}end of life since {}", lts, until)
+            }
+            (SupportStatus::EndingSoon | SupportStatus::Supported, Some(until)) => {
+                format!("{}supported until {}", lts, until)
+            }
+            _ => format!("{}no supported-until date declared", lts),
+        }
+    }
+}
+
+/// Query params for GET /api/contracts/:id/support
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SupportQuery {
+    /// Only this version (defaults to every version)
+    pub version: Option<String>,
+}
+
+/// Response for GET /api/contracts/:id/support, newest version first
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SupportResponse {
+    pub contract_id: String,
+    pub versions: Vec<VersionSupport>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn status_follows_the_supported_until_date() {
+        let today = date("2026-06-15");
+        assert_eq!(
+            SupportStatus::compute(None, today),
+            SupportStatus::Unspecified
+        );
+        assert_eq!(
+            SupportStatus::compute(Some(date("2026-06-14")), today),
+            SupportStatus::EndOfLife
+        );
+        assert_eq!(
+            SupportStatus::compute(Some(date("2026-06-15")), today),
+            SupportStatus::EndingSoon
+        );
+        assert_eq!(
+            SupportStatus::compute(Some(date("2026-07-15")), today),
+            SupportStatus::EndingSoon
+        );
+        assert_eq!(
+            SupportStatus::compute(Some(date("2026-07-16")), today),
+            SupportStatus::Supported
+        );
+    }
+
+    #[test]
+    fn eol_versions_are_described_and_warned_about() {
+        let today = date("2026-06-15");
+        let eol = VersionSupport::new("1.0.0".into(), Some(date("2026-01-31")), true, today);
+        assert!(eol.needs_warning());
+        assert_eq!(eol.days_remaining, Some(-135));
+        assert_eq!(eol.describe(), "LTS, end of life since 2026-01-31");
+
+        let open = VersionSupport::new("2.0.0".into(), None, false, today);
+        assert!(!open.needs_warning());
+        assert_eq!(open.describe(), "no supported-until date declared");
+    }
+}
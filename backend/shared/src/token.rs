@@ -0,0 +1,121 @@
+//! SEP-41 token metadata read from the chain, and the registry's tokens as a
+//! SEP-42 asset list so wallets can use the registry as a token list source.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::models::Network;
+use crate::xdr::ScVal;
+
+/// Interface id (`contracts.implements`) of SEP-41 tokens
+pub const SEP41_INTERFACE: &str = "sep-41";
+
+/// Largest `decimals` accepted from a token, as SEP-41 wallets handle them
+pub const MAX_TOKEN_DECIMALS: u32 = 38;
+
+/// What a token's `name`, `symbol` and `decimals` functions return
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenDetails {
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u32,
+}
+
+impl TokenDetails {
+    pub fn from_scvals(name: &ScVal, symbol: &ScVal, decimals: &ScVal) -> Result<Self, String> {
+        fn text(function: &str, value: &ScVal) -> Result<String, String> {
+            match value {
+                ScVal::String(s) => Ok(s.0.to_utf8_string_lossy()),
+                ScVal::Symbol(s) => Ok(s.0.to_utf8_string_lossy()),
+                other => Err(format!("{} returned {:?}, not a string", function, other)),
+            }
+        }
+        let decimals = match decimals {
+            ScVal::U32(n) if *n <= MAX_TOKEN_DECIMALS => *n,
+            ScVal::U32(n) => return Err(format!("decimals returned {}, over the maximum", n)),
+            other => return Err(format!("decimals returned {:?}, not a u32", other)),
+        };
+        Ok(Self {
+            name: text("name", name)?,
+            symbol: text("symbol", symbol)?,
+            decimals,
+        })
+    }
+}
+
+/// GET /api/contracts/:id/token
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenMetadata {
+    /// On-chain contract address
+    pub contract_id: String,
+    pub network: Network,
+    #[serde(flatten)]
+    pub details: TokenDetails,
+    pub fetched_at: DateTime<Utc>,
+    /// The chain couldn't be reached and this is an expired cached copy
+    #[serde(default)]
+    pub stale: bool,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TokenListQuery {
+    /// Defaults to mainnet; an asset list covers one network
+    pub network: Option<Network>,
+}
+
+/// A SEP-42 asset list: GET /api/tokenlist.json
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssetList {
+    pub name: String,
+    pub provider: String,
+    pub description: String,
+    pub version: String,
+    /// `public`, `testnet` or `futurenet`
+    pub network: String,
+    pub assets: Vec<AssetListEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssetListEntry {
+    pub contract: String,
+    /// The token's symbol
+    pub code: String,
+    pub name: String,
+    pub decimals: u32,
+    /// Publisher of the contract
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub org: Option<String>,
+}
+
+/// How SEP-42 names a network
+pub fn asset_list_network(network: &Network) -> &'static str {
+    match network {
+        Network::Mainnet => "public",
+        Network::Testnet => "testnet",
+        Network::Futurenet => "futurenet",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use stellar_xdr::curr::ScString;
+
+    fn string(text: &str) -> ScVal {
+        ScVal::String(ScString(text.to_string().try_into().unwrap()))
+    }
+
+    #[test]
+    fn details_come_from_the_token_functions() {
+        let details =
+            TokenDetails::from_scvals(&string("USD Coin"), &string("USDC"), &ScVal::U32(7))
+                .unwrap();
+        assert_eq!(details.symbol, "USDC");
+        assert_eq!(details.decimals, 7);
+
+        let err =
+            TokenDetails::from_scvals(&string("x"), &ScVal::U32(1), &ScVal::U32(7)).unwrap_err();
+        assert!(err.starts_with("symbol returned"), "{}", err);
+        assert!(TokenDetails::from_scvals(&string("x"), &string("X"), &ScVal::U32(99)).is_err());
+    }
+}
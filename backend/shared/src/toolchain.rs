@@ -0,0 +1,128 @@
+//! Toolchain fingerprint recorded with a published version. The CLI captures
+//! it on `publish` so the verification pipeline and other users can rebuild
+//! the exact binary.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::sbom::parse_cargo_lock;
+
+/// Target the CLI assumes when the WASM path does not name one
+pub const DEFAULT_WASM_TARGET: &str = "wasm32-unknown-unknown";
+
+/// The compiler, SDK and build settings a version's WASM came from
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BuildToolchain {
+    /// `rustc --version` output, e.g. `rustc 1.79.0 (129f3b996 2024-06-10)`
+    pub rustc: String,
+    /// soroban-sdk version resolved in Cargo.lock
+    #[serde(default)]
+    pub soroban_sdk: Option<String>,
+    pub target: String,
+    pub profile: String,
+    /// Settings from the `[profile.<profile>]` table of Cargo.toml
+    #[serde(default)]
+    pub profile_flags: BTreeMap<String, String>,
+    /// A rust-toolchain file pins the compiler to a specific release
+    #[serde(default)]
+    pub pinned: bool,
+    /// The working tree had uncommitted changes when the WASM was published
+    #[serde(default)]
+    pub dirty: bool,
+}
+
+impl BuildToolchain {
+    /// Reasons a rebuild may not produce the same binary
+    pub fn reproducibility_warnings(&self) -> Vec<&'static str> {
+        let mut warnings = Vec::new();
+        if self.dirty {
+            warnings.push("the working tree has uncommitted changes");
+        }
+        if !self.pinned {
+            warnings.push("the Rust toolchain is not pinned by a rust-toolchain file");
+        }
+        if self.soroban_sdk.is_none() {
+            warnings.push("no soroban-sdk version was found in Cargo.lock");
+        }
+        warnings
+    }
+}
+
+/// The soroban-sdk version a Cargo.lock resolves, if it depends on one
+pub fn sdk_version_from_lock(lock: &str) -> Option<String> {
+    parse_cargo_lock(lock)
+        .into_iter()
+        .find(|component| component.name == "soroban-sdk")
+        .map(|component| component.version)
+}
+
+/// Whether a rust-toolchain channel names a specific release rather than a
+/// moving one such as `stable` or `nightly`
+pub fn is_pinned_channel(channel: &str) -> bool {
+    let channel = channel.trim();
+    !channel.is_empty() && !matches!(channel, "stable" | "beta" | "nightly")
+}
+
+/// Query params for GET /api/contracts/:id/toolchain
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolchainQuery {
+    /// Version to describe (defaults to the latest version)
+    pub version: Option<String>,
+}
+
+/// Response for GET /api/contracts/:id/toolchain
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolchainResponse {
+    pub contract_id: String,
+    pub version: String,
+    pub wasm_hash: String,
+    pub source_url: Option<String>,
+    pub commit_hash: Option<String>,
+    pub toolchain: BuildToolchain,
+    pub warnings: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sdk_version_comes_from_the_lockfile() {
+        let lock = r#"
+[[package]]
+name = "amm"
+version = "0.1.0"
+
+[[package]]
+name = "soroban-sdk"
+version = "21.2.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+checksum = "abc"
+"#;
+        assert_eq!(sdk_version_from_lock(lock).as_deref(), Some("21.2.0"));
+        assert_eq!(sdk_version_from_lock(""), None);
+    }
+
+    #[test]
+    fn moving_channels_are_not_pinned() {
+        assert!(is_pinned_channel("1.79.0"));
+        assert!(is_pinned_channel("nightly-2024-06-01"));
+        assert!(!is_pinned_channel("stable"));
+        assert!(!is_pinned_channel(" nightly "));
+
+        let toolchain = BuildToolchain {
+            rustc: "rustc 1.79.0".into(),
+            soroban_sdk: Some("21.2.0".into()),
+            pinned: true,
+            ..Default::default()
+        };
+        assert!(toolchain.reproducibility_warnings().is_empty());
+        let dirty = BuildToolchain {
+            dirty: true,
+            pinned: false,
+            ..toolchain
+        };
+        assert_eq!(dirty.reproducibility_warnings().len(), 2);
+    }
+}
@@ -0,0 +1,326 @@
+//! Publish-time metadata rules shared by the API and the CLI, so `publish`
+//! reports every problem before the request is sent and the server applies
+//! exactly the same checks when it arrives.
+
+use serde::{Deserialize, Serialize};
+
+use crate::license::validate_spdx_expression;
+use crate::models::{validate_project_url, ProjectUrls, PublishRequest};
+use crate::namespace::ContractName;
+
+/// Maximum contract name length, including any `@namespace/` prefix
+pub const MAX_CONTRACT_NAME_LENGTH: usize = 255;
+/// Maximum description length
+pub const MAX_DESCRIPTION_LENGTH: usize = 5000;
+/// Maximum number of tags on a contract
+pub const MAX_TAGS_COUNT: usize = 10;
+/// Maximum length of a single tag
+pub const MAX_TAG_LENGTH: usize = 50;
+
+/// Categories a contract can be filed under, compared case-insensitively
+pub const CONTRACT_CATEGORIES: &[&str] = &[
+    "bridge",
+    "dao",
+    "defi",
+    "dex",
+    "gaming",
+    "governance",
+    "identity",
+    "infrastructure",
+    "lending",
+    "nft",
+    "oracle",
+    "other",
+    "payment",
+    "social",
+    "staking",
+    "token",
+    "wallet",
+];
+
+/// One rule a field broke
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValidationIssue {
+    pub field: String,
+    pub message: String,
+}
+
+/// Every issue found in a request, in field order; empty when it is valid
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValidationReport {
+    pub issues: Vec<ValidationIssue>,
+}
+
+impl ValidationReport {
+    pub fn is_valid(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    pub fn add(&mut self, field: impl Into<String>, message: impl Into<String>) {
+        self.issues.push(ValidationIssue {
+            field: field.into(),
+            message: message.into(),
+        });
+    }
+
+    /// Record the error of a failed check against `field`
+    pub fn check(&mut self, field: &str, result: Result<(), String>) {
+        if let Err(message) = result {
+            self.add(field, message);
+        }
+    }
+}
+
+impl std::fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, issue) in self.issues.iter().enumerate() {
+            if i > 0 {
+                f.write_str("\n")?;
+            }
+            write!(f, "{}: {}", issue.field, issue.message)?;
+        }
+        Ok(())
+    }
+}
+
+/// Metadata fields of a publish, borrowed from wherever the caller keeps them
+#[derive(Debug, Clone, Copy)]
+pub struct PublishMetadata<'a> {
+    pub name: &'a str,
+    pub description: Option<&'a str>,
+    pub category: Option<&'a str>,
+    pub tags: &'a [String],
+    pub license: Option<&'a str>,
+    pub source_url: Option<&'a str>,
+    pub urls: &'a ProjectUrls,
+}
+
+impl PublishMetadata<'_> {
+    /// Check every field, collecting all issues rather than stopping at the first
+    pub fn validate(&self) -> ValidationReport {
+        let mut report = ValidationReport::default();
+        report.check("name", validate_contract_name(self.name));
+        if let Some(description) = self.description {
+            report.check("description", validate_description(description));
+        }
+        if let Some(category) = self.category {
+            report.check("category", validate_category(category));
+        }
+        report.check("tags", validate_contract_tags(self.tags));
+        if let Some(license) = self.license {
+            report.check("license", validate_spdx_expression(license));
+        }
+        if let Some(url) = self.source_url {
+            report.check("source_url", validate_project_url(url));
+        }
+        for (field, url) in self.urls.fields() {
+            if let Some(url) = url {
+                report.check(field, validate_project_url(url));
+            }
+        }
+        report
+    }
+}
+
+impl PublishRequest {
+    pub fn metadata(&self) -> PublishMetadata<'_> {
+        PublishMetadata {
+            name: &self.name,
+            description: self.description.as_deref(),
+            category: self.category.as_deref(),
+            tags: &self.tags,
+            license: self.license.as_deref(),
+            source_url: self.source_url.as_deref(),
+            urls: &self.urls,
+        }
+    }
+}
+
+/// Names start with a letter or digit and continue with letters, digits,
+/// spaces, `-`, `_` or `.`; scoped names also need a valid `@namespace/`
+pub fn validate_contract_name(name: &str) -> Result<(), String> {
+    if name.trim().is_empty() {
+        return Err("name is required".to_string());
+    }
+    if name.chars().count() > MAX_CONTRACT_NAME_LENGTH {
+        return Err(format!(
+            "must be at most {} characters",
+            MAX_CONTRACT_NAME_LENGTH
+        ));
+    }
+    let parsed = ContractName::parse(name)?;
+    let mut chars = parsed.name.chars();
+    if !chars.next().is_some_and(char::is_alphanumeric) {
+        return Err("name must start with a letter or digit".to_string());
+    }
+    if !chars.all(|c| c.is_alphanumeric() || matches!(c, ' ' | '-' | '_' | '.')) {
+        return Err("name may only contain letters, digits, spaces, '-', '_' and '.'".to_string());
+    }
+    Ok(())
+}
+
+pub fn validate_description(description: &str) -> Result<(), String> {
+    if description.chars().count() > MAX_DESCRIPTION_LENGTH {
+        return Err(format!(
+            "must be at most {} characters",
+            MAX_DESCRIPTION_LENGTH
+        ));
+    }
+    if contains_unsafe_markup(description) {
+        return Err("potentially unsafe content detected".to_string());
+    }
+    Ok(())
+}
+
+pub fn validate_category(category: &str) -> Result<(), String> {
+    let lowered = category.trim().to_lowercase();
+    if CONTRACT_CATEGORIES.contains(&lowered.as_str()) {
+        return Ok(());
+    }
+    Err(format!(
+        "unknown category '{}'; expected one of: {}",
+        category,
+        CONTRACT_CATEGORIES.join(", ")
+    ))
+}
+
+/// Tags are letters, digits, `-`, `_` and `.`, optionally behind a single
+/// `prefix:` such as `standard:sep-41`
+pub fn validate_contract_tags(tags: &[String]) -> Result<(), String> {
+    if tags.len() > MAX_TAGS_COUNT {
+        return Err(format!("at most {} tags are allowed", MAX_TAGS_COUNT));
+    }
+    for tag in tags {
+        if tag.is_empty() {
+            return Err("tags cannot be empty".to_string());
+        }
+        if tag.chars().count() > MAX_TAG_LENGTH {
+            return Err(format!(
+                "tag '{}' exceeds maximum length of {} characters",
+                tag, MAX_TAG_LENGTH
+            ));
+        }
+        let label = |part: &str| {
+            !part.is_empty()
+                && part
+                    .chars()
+                    .all(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        };
+        let valid = match tag.split_once(':') {
+            Some((prefix, name)) => label(prefix) && label(name),
+            None => label(tag),
+        };
+        if !valid {
+            return Err(format!(
+                "tag '{}' may only contain letters, digits, '-', '_' and '.' \
+                 with an optional 'prefix:'",
+                tag
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Script URLs, embedded elements and inline `on...=` event handlers
+fn contains_unsafe_markup(text: &str) -> bool {
+    let lowered = text.to_lowercase();
+    if ["javascript:", "<script", "<iframe", "<object", "<embed"]
+        .iter()
+        .any(|pattern| lowered.contains(pattern))
+    {
+        return true;
+    }
+    lowered.match_indices("on").any(|(start, _)| {
+        let rest = &lowered[start + 2..];
+        let handler = rest
+            .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .unwrap_or(rest.len());
+        handler > 0 && rest[handler..].trim_start().starts_with('=')
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metadata<'a>(
+        name: &'a str,
+        tags: &'a [String],
+        urls: &'a ProjectUrls,
+    ) -> PublishMetadata<'a> {
+        PublishMetadata {
+            name,
+            description: Some("Swaps tokens at the best price"),
+            category: Some("DeFi"),
+            tags,
+            license: Some("MIT"),
+            source_url: None,
+            urls,
+        }
+    }
+
+    #[test]
+    fn accepts_well_formed_metadata() {
+        let tags = vec!["amm".to_string(), "standard:sep-41".to_string()];
+        let urls = ProjectUrls {
+            homepage: Some("https://amm.example".to_string()),
+            ..Default::default()
+        };
+        assert!(metadata("My Contract", &tags, &urls).validate().is_valid());
+        assert!(metadata("@alice/token-v2", &tags, &urls)
+            .validate()
+            .is_valid());
+    }
+
+    #[test]
+    fn reports_every_issue_by_field() {
+        let tags: Vec<String> = (0..11).map(|i| format!("tag{}", i)).collect();
+        let urls = ProjectUrls {
+            repository_url: Some("ftp://example.com".to_string()),
+            ..Default::default()
+        };
+        let report = PublishMetadata {
+            description: Some("<script>alert(1)</script>"),
+            category: Some("memes"),
+            license: Some("NOT A LICENSE"),
+            ..metadata("token!", &tags, &urls)
+        }
+        .validate();
+        let fields: Vec<&str> = report.issues.iter().map(|i| i.field.as_str()).collect();
+        assert_eq!(
+            fields,
+            vec![
+                "name",
+                "description",
+                "category",
+                "tags",
+                "license",
+                "repository_url"
+            ]
+        );
+        assert!(report.to_string().starts_with("name: "));
+    }
+
+    #[test]
+    fn checks_name_and_tag_formats() {
+        assert!(validate_contract_name("Token_v1.2").is_ok());
+        assert!(validate_contract_name(" ").is_err());
+        assert!(validate_contract_name("-token").is_err());
+        assert!(validate_contract_name("@alice/<b>").is_err());
+        assert!(validate_contract_name(&"a".repeat(256)).is_err());
+
+        let tag = |t: &str| validate_contract_tags(&[t.to_string()]);
+        assert!(tag("sep-41").is_ok());
+        assert!(tag("a:b:c").is_err());
+        assert!(tag("two words").is_err());
+        assert!(tag(&"t".repeat(51)).is_err());
+    }
+
+    #[test]
+    fn detects_event_handlers_but_not_prose() {
+        assert!(contains_unsafe_markup("<img src=x onerror = alert(1)>"));
+        assert!(contains_unsafe_markup("JavaScript:void(0)"));
+        assert!(!contains_unsafe_markup("Runs on Soroban; fee = 1%"));
+        assert!(validate_category("Payment").is_ok());
+    }
+}
@@ -0,0 +1,32 @@
+//! Canonical URLs of the registry's web pages, as served by the API's built-in
+//! web UI. Shared so the CLI links to the same pages the UI renders.
+
+use uuid::Uuid;
+
+/// Path of a contract's detail page
+pub fn contract_page_path(id: &Uuid) -> String {
+    format!("/contracts/{}", id)
+}
+
+/// Absolute URL of a contract's detail page on the registry at `base_url`
+pub fn contract_page_url(base_url: &str, id: &Uuid) -> String {
+    format!(
+        "{}{}",
+        base_url.trim_end_matches('/'),
+        contract_page_path(id)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn page_urls_ignore_trailing_slashes() {
+        let id = Uuid::nil();
+        assert_eq!(
+            contract_page_url("https://registry.example/", &id),
+            "https://registry.example/contracts/00000000-0000-0000-0000-000000000000"
+        );
+    }
+}
@@ -0,0 +1,991 @@
+//! Conversion between JSON and Soroban XDR: `ScVal` values and transaction
+//! envelopes, base64-encoded as Stellar tooling exchanges them. Types come
+//! from a stored contract spec (the ABI JSON array), so structs decode to
+//! objects with their field names and enums to their case names; without a
+//! type, values map to the closest JSON shape.
+//!
+//! JSON follows the registry's argument syntax: 64-bit and smaller integers
+//! as numbers, 128-bit integers as decimal strings, 256-bit integers and
+//! bytes as hex, addresses as `G...`/`C...` strkeys.
+
+use serde_json::{json, Map, Value};
+use stellar_xdr::curr::{
+    AccountId, Duration, Hash, HostFunction, Int128Parts, Int256Parts, InvokeContractArgs,
+    InvokeHostFunctionOp, Limits, Memo, MuxedAccount, Operation, OperationBody, Preconditions,
+    PublicKey, ReadXdr, ScAddress, ScBytes, ScError, ScMap, ScMapEntry, ScString, ScSymbol, ScVec,
+    SequenceNumber, TimePoint, Transaction, TransactionExt, TransactionV1Envelope, UInt128Parts,
+    UInt256Parts, Uint256, VecM, WriteXdr,
+};
+
+pub use stellar_xdr::curr::{ScVal, TransactionEnvelope};
+
+use crate::stellar_key::{
+    decode_account_id, decode_contract_id, encode_account_id, encode_contract_id,
+};
+
+/// Deepest value nesting converted, matching the ABI's type depth limit
+const MAX_DEPTH: usize = crate::abi_schema::MAX_ABI_TYPE_DEPTH;
+
+/// What a piece of XDR holds
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum XdrKind {
+    ScVal,
+    Transaction,
+}
+
+impl std::str::FromStr for XdrKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "scval" | "value" => Ok(XdrKind::ScVal),
+            "transaction" | "tx" | "envelope" => Ok(XdrKind::Transaction),
+            other => Err(format!(
+                "unknown XDR type `{}`; expected scval or transaction",
+                other
+            )),
+        }
+    }
+}
+
+/// The spec type a value is converted with, e.g. `{"type": "vec", "element": {...}}`,
+/// and the ABI its user-defined type names resolve against
+#[derive(Debug, Clone, Copy)]
+pub struct SpecType<'a> {
+    pub ty: &'a Value,
+    pub abi: &'a [Value],
+}
+
+impl<'a> SpecType<'a> {
+    pub fn new(ty: &'a Value, abi: &'a [Value]) -> Self {
+        SpecType { ty, abi }
+    }
+
+    fn with(&self, ty: &'a Value) -> Self {
+        SpecType { ty, abi: self.abi }
+    }
+
+    fn name(&self) -> &'a str {
+        self.ty["type"].as_str().unwrap_or("")
+    }
+
+    /// The struct, enum or union entry a user-defined type name refers to
+    fn definition(&self) -> Option<&'a Value> {
+        let name = self.name();
+        self.abi
+            .iter()
+            .find(|entry| entry["type"] != "function" && entry["name"] == name)
+    }
+}
+
+/// The type of one of `function`'s parameters, or with `param` unset, of
+/// what it returns
+pub fn function_type<'a>(
+    abi: &'a [Value],
+    function: &str,
+    param: Option<&str>,
+) -> Option<&'a Value> {
+    let entry = abi
+        .iter()
+        .find(|entry| entry["type"] == "function" && entry["name"] == function)?;
+    match param {
+        Some(param) => entry["inputs"]
+            .as_array()?
+            .iter()
+            .find(|input| input["name"] == param)
+            .map(|input| &input["value"]),
+        None => entry["outputs"].as_array()?.first(),
+    }
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// Base64 XDR
+// ────────────────────────────────────────────────────────────────────────────
+
+pub fn scval_from_xdr(base64: &str) -> Result<ScVal, String> {
+    ScVal::from_xdr_base64(base64.trim(), Limits::none())
+        .map_err(|e| format!("not a base64 ScVal: {}", e))
+}
+
+pub fn scval_to_xdr(value: &ScVal) -> Result<String, String> {
+    value
+        .to_xdr_base64(Limits::none())
+        .map_err(|e| format!("value could not be encoded: {}", e))
+}
+
+pub fn transaction_from_xdr(base64: &str) -> Result<TransactionEnvelope, String> {
+    TransactionEnvelope::from_xdr_base64(base64.trim(), Limits::none())
+        .map_err(|e| format!("not a base64 transaction envelope: {}", e))
+}
+
+pub fn transaction_to_xdr(envelope: &TransactionEnvelope) -> Result<String, String> {
+    envelope
+        .to_xdr_base64(Limits::none())
+        .map_err(|e| format!("transaction could not be encoded: {}", e))
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// ScVal → JSON
+// ────────────────────────────────────────────────────────────────────────────
+
+fn address_string(address: &ScAddress) -> String {
+    match address {
+        ScAddress::Account(AccountId(PublicKey::PublicKeyTypeEd25519(Uint256(key)))) => {
+            encode_account_id(key)
+        }
+        ScAddress::Contract(Hash(hash)) => encode_contract_id(hash),
+    }
+}
+
+fn u256_hex(parts: [u64; 4]) -> String {
+    format!(
+        "0x{:016x}{:016x}{:016x}{:016x}",
+        parts[0], parts[1], parts[2], parts[3]
+    )
+}
+
+fn symbol_text(value: &ScVal) -> Option<String> {
+    match value {
+        ScVal::Symbol(s) => Some(s.0.to_utf8_string_lossy()),
+        ScVal::String(s) => Some(s.0.to_utf8_string_lossy()),
+        _ => None,
+    }
+}
+
+fn vec_items(value: &ScVal) -> Option<&[ScVal]> {
+    match value {
+        ScVal::Vec(Some(items)) => Some(items.0.as_slice()),
+        _ => None,
+    }
+}
+
+fn map_entries(value: &ScVal) -> Option<&[ScMapEntry]> {
+    match value {
+        ScVal::Map(Some(entries)) => Some(entries.0.as_slice()),
+        _ => None,
+    }
+}
+
+/// Render a value without type information. 128-bit integers become decimal
+/// strings and 256-bit integers hex strings, so no precision is lost.
+pub fn scval_to_json(value: &ScVal) -> Value {
+    match value {
+        ScVal::Bool(b) => json!(b),
+        ScVal::Void => Value::Null,
+        ScVal::U32(n) => json!(n),
+        ScVal::I32(n) => json!(n),
+        ScVal::U64(n) => json!(n),
+        ScVal::I64(n) => json!(n),
+        ScVal::Timepoint(t) => json!(t.0),
+        ScVal::Duration(d) => json!(d.0),
+        ScVal::U128(parts) => {
+            json!((u128::from(parts.hi) << 64 | u128::from(parts.lo)).to_string())
+        }
+        ScVal::I128(parts) => {
+            json!((i128::from(parts.hi) << 64 | i128::from(parts.lo)).to_string())
+        }
+        ScVal::U256(p) => json!(u256_hex([p.hi_hi, p.hi_lo, p.lo_hi, p.lo_lo])),
+        ScVal::I256(p) => json!(u256_hex([p.hi_hi as u64, p.hi_lo, p.lo_hi, p.lo_lo])),
+        ScVal::Bytes(bytes) => json!(hex::encode(bytes.as_slice())),
+        ScVal::String(_) | ScVal::Symbol(_) => json!(symbol_text(value)),
+        ScVal::Address(address) => json!(address_string(address)),
+        ScVal::Vec(items) => Value::Array(
+            items
+                .iter()
+                .flat_map(|v| v.iter())
+                .map(scval_to_json)
+                .collect(),
+        ),
+        ScVal::Map(entries) => {
+            let entries: Vec<&ScMapEntry> = entries.iter().flat_map(|m| m.iter()).collect();
+            let named = entries
+                .iter()
+                .all(|e| matches!(e.key, ScVal::Symbol(_) | ScVal::String(_)));
+            if named {
+                Value::Object(
+                    entries
+                        .iter()
+                        .map(|e| {
+                            let key = symbol_text(&e.key).unwrap_or_default();
+                            (key, scval_to_json(&e.val))
+                        })
+                        .collect(),
+                )
+            } else {
+                Value::Array(
+                    entries
+                        .iter()
+                        .map(|e| json!([scval_to_json(&e.key), scval_to_json(&e.val)]))
+                        .collect(),
+                )
+            }
+        }
+        ScVal::Error(ScError::Contract(code)) => json!({ "error": code }),
+        other => json!(format!("{:?}", other)),
+    }
+}
+
+/// Render a value as `spec` describes it. Values that don't have the shape
+/// the spec expects fall back to `scval_to_json`.
+pub fn scval_to_typed_json(value: &ScVal, spec: SpecType) -> Value {
+    typed_json(value, spec, 0).unwrap_or_else(|| scval_to_json(value))
+}
+
+fn typed_json(value: &ScVal, spec: SpecType, depth: usize) -> Option<Value> {
+    if depth > MAX_DEPTH {
+        return None;
+    }
+    let lower = spec.name().to_lowercase();
+    match (lower.as_str(), value) {
+        ("option", ScVal::Void) => Some(Value::Null),
+        ("option", _) => typed_json(value, spec.with(&spec.ty["element"]), depth + 1),
+        ("vec", _) => vec_items(value)?
+            .iter()
+            .map(|item| typed_json(item, spec.with(&spec.ty["element"]), depth + 1))
+            .collect::<Option<Vec<_>>>()
+            .map(Value::Array),
+        ("tuple", _) => {
+            let items = vec_items(value)?;
+            let elements = spec.ty["elements"].as_array()?;
+            if items.len() != elements.len() {
+                return None;
+            }
+            items
+                .iter()
+                .zip(elements)
+                .map(|(item, ty)| typed_json(item, spec.with(ty), depth + 1))
+                .collect::<Option<Vec<_>>>()
+                .map(Value::Array)
+        }
+        ("map", _) => {
+            let entries = map_entries(value)?;
+            let key_type = spec.with(&spec.ty["key"]);
+            let val_type = spec.with(&spec.ty["val"]);
+            let keys: Vec<Value> = entries
+                .iter()
+                .map(|e| typed_json(&e.key, key_type, depth + 1))
+                .collect::<Option<_>>()?;
+            let vals: Vec<Value> = entries
+                .iter()
+                .map(|e| typed_json(&e.val, val_type, depth + 1))
+                .collect::<Option<_>>()?;
+            if keys.iter().all(Value::is_string) {
+                Some(Value::Object(
+                    keys.into_iter()
+                        .map(|k| k.as_str().unwrap_or_default().to_string())
+                        .zip(vals)
+                        .collect(),
+                ))
+            } else {
+                Some(Value::Array(
+                    keys.into_iter()
+                        .zip(vals)
+                        .map(|(k, v)| json!([k, v]))
+                        .collect(),
+                ))
+            }
+        }
+        ("address", ScVal::Address(address)) => Some(json!(address_string(address))),
+        ("bool", ScVal::Bool(_))
+        | ("u32", ScVal::U32(_))
+        | ("i32", ScVal::I32(_))
+        | ("u64", ScVal::U64(_))
+        | ("i64", ScVal::I64(_))
+        | ("timepoint", ScVal::Timepoint(_))
+        | ("duration", ScVal::Duration(_))
+        | ("u128", ScVal::U128(_))
+        | ("i128", ScVal::I128(_))
+        | ("u256", ScVal::U256(_))
+        | ("i256", ScVal::I256(_))
+        | ("bytes" | "bytesn" | "bytes_n", ScVal::Bytes(_))
+        | ("string", ScVal::String(_))
+        | ("symbol", ScVal::Symbol(_))
+        | ("void" | "()", ScVal::Void)
+        | ("val" | "", _) => Some(scval_to_json(value)),
+        _ => typed_user_json(value, spec, depth),
+    }
+}
+
+fn typed_user_json(value: &ScVal, spec: SpecType, depth: usize) -> Option<Value> {
+    let def = spec.definition()?;
+    let fields = def["fields"].as_array();
+    let cases = def["cases"].as_array();
+    match def["type"].as_str()? {
+        "struct" => {
+            let fields = fields?;
+            // Tuple structs (fields "0", "1", ...) are vectors
+            if fields
+                .iter()
+                .all(|f| f["name"].as_str().is_some_and(is_index))
+            {
+                let items = vec_items(value)?;
+                return items
+                    .iter()
+                    .zip(fields)
+                    .map(|(item, field)| typed_json(item, spec.with(&field["value"]), depth + 1))
+                    .collect::<Option<Vec<_>>>()
+                    .map(Value::Array);
+            }
+            let entries = map_entries(value)?;
+            let mut object = Map::new();
+            for entry in entries {
+                let key = symbol_text(&entry.key)?;
+                let field = fields.iter().find(|f| f["name"] == key.as_str())?;
+                object.insert(
+                    key,
+                    typed_json(&entry.val, spec.with(&field["value"]), depth + 1)?,
+                );
+            }
+            Some(Value::Object(object))
+        }
+        "enum" => {
+            let ScVal::U32(n) = value else { return None };
+            case_name(cases?, *n).map(|name| json!(name))
+        }
+        "error_enum" => {
+            let ScVal::Error(ScError::Contract(n)) = value else {
+                return None;
+            };
+            case_name(cases?, *n).map(|name| json!(name))
+        }
+        "union" => {
+            let items = vec_items(value)?;
+            let name = symbol_text(items.first()?)?;
+            let case = cases?.iter().find(|c| c["name"] == name.as_str())?;
+            let case_fields = case["fields"].as_array().map(Vec::as_slice).unwrap_or(&[]);
+            if case_fields.is_empty() {
+                return Some(json!(name));
+            }
+            let values = items[1..]
+                .iter()
+                .zip(case_fields)
+                .map(|(item, field)| typed_json(item, spec.with(&field["value"]), depth + 1))
+                .collect::<Option<Vec<_>>>()?;
+            Some(Value::Object(Map::from_iter([(
+                name,
+                Value::Array(values),
+            )])))
+        }
+        _ => None,
+    }
+}
+
+fn is_index(name: &str) -> bool {
+    !name.is_empty() && name.bytes().all(|b| b.is_ascii_digit())
+}
+
+fn case_name(cases: &[Value], n: u32) -> Option<&str> {
+    cases
+        .iter()
+        .find(|case| case["value"].as_u64() == Some(u64::from(n)))
+        .and_then(|case| case["name"].as_str())
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// JSON → ScVal
+// ────────────────────────────────────────────────────────────────────────────
+
+fn symbol(name: &str) -> Result<ScVal, String> {
+    let symbol = name
+        .to_string()
+        .try_into()
+        .map_err(|_| format!("`{}` is not a valid symbol", name))?;
+    Ok(ScVal::Symbol(ScSymbol(symbol)))
+}
+
+fn vec_of(items: Vec<ScVal>) -> Result<ScVal, String> {
+    let items = items
+        .try_into()
+        .map_err(|_| "too many elements".to_string())?;
+    Ok(ScVal::Vec(Some(ScVec(items))))
+}
+
+/// Soroban requires map keys in ascending order
+fn map_of(mut entries: Vec<ScMapEntry>) -> Result<ScVal, String> {
+    entries.sort_by(|a, b| a.key.cmp(&b.key));
+    if entries.windows(2).any(|pair| pair[0].key == pair[1].key) {
+        return Err("duplicate map key".to_string());
+    }
+    let entries = entries
+        .try_into()
+        .map_err(|_| "too many entries".to_string())?;
+    Ok(ScVal::Map(Some(ScMap(entries))))
+}
+
+/// An integer given as a JSON number or decimal string
+fn integer<T: TryFrom<i128> + std::str::FromStr>(
+    value: &Value,
+    type_name: &str,
+) -> Result<T, String> {
+    let fits = || format!("expected a whole number that fits in {}", type_name);
+    match value {
+        Value::Number(n) => {
+            let wide = n
+                .as_i64()
+                .map(i128::from)
+                .or_else(|| n.as_u64().map(i128::from))
+                .ok_or_else(fits)?;
+            T::try_from(wide).map_err(|_| fits())
+        }
+        Value::String(s) => s.trim().parse::<T>().map_err(|_| fits()),
+        _ => Err(fits()),
+    }
+}
+
+fn hex_bytes(value: &Value) -> Result<Vec<u8>, String> {
+    let text = value
+        .as_str()
+        .ok_or_else(|| "expected a hex string".to_string())?;
+    let digits = text
+        .strip_prefix("0x")
+        .or_else(|| text.strip_prefix("0X"))
+        .unwrap_or(text);
+    hex::decode(digits).map_err(|_| format!("`{}` is not hex", text))
+}
+
+/// A 256-bit integer as `0x` hex (two's complement for i256) or as a decimal
+/// string within the 128-bit range
+fn int256_words(value: &Value, signed: bool) -> Result<[u64; 4], String> {
+    if let Some(hex) = value.as_str().and_then(|s| s.strip_prefix("0x")) {
+        if hex.is_empty() || hex.len() > 64 {
+            return Err("expected at most 64 hex digits".to_string());
+        }
+        let padded = format!("{:0>64}", hex);
+        let mut words = [0u64; 4];
+        for (i, word) in words.iter_mut().enumerate() {
+            *word = u64::from_str_radix(&padded[i * 16..(i + 1) * 16], 16)
+                .map_err(|_| format!("`{}` is not hex", hex))?;
+        }
+        return Ok(words);
+    }
+    let (hi, lo) = if signed {
+        let n: i128 = integer(value, "i128")?;
+        let fill = if n < 0 { u64::MAX } else { 0 };
+        ((fill, fill), n as u128)
+    } else {
+        ((0, 0), integer::<u128>(value, "u128")?)
+    };
+    Ok([hi.0, hi.1, (lo >> 64) as u64, lo as u64])
+}
+
+fn address(value: &Value) -> Result<ScAddress, String> {
+    let text = value.as_str().unwrap_or_default();
+    if let Some(key) = decode_account_id(text) {
+        return Ok(ScAddress::Account(AccountId(
+            PublicKey::PublicKeyTypeEd25519(Uint256(key)),
+        )));
+    }
+    if let Some(hash) = decode_contract_id(text) {
+        return Ok(ScAddress::Contract(Hash(hash)));
+    }
+    Err(format!("`{}` is not a G... or C... address", value))
+}
+
+/// Map entries given as a JSON object or as an array of `[key, value]` pairs
+fn json_entries(value: &Value) -> Result<Vec<(Value, &Value)>, String> {
+    match value {
+        Value::Object(object) => Ok(object
+            .iter()
+            .map(|(k, v)| (Value::String(k.clone()), v))
+            .collect()),
+        Value::Array(pairs) => pairs
+            .iter()
+            .map(|pair| match pair.as_array().map(Vec::as_slice) {
+                Some([k, v]) => Ok((k.clone(), v)),
+                _ => Err("expected [key, value] pairs".to_string()),
+            })
+            .collect(),
+        _ => Err("expected a JSON object or [key, value] pairs".to_string()),
+    }
+}
+
+/// Encode a JSON value as `spec` describes it
+pub fn json_to_scval(value: &Value, spec: SpecType) -> Result<ScVal, String> {
+    typed_scval(value, spec, 0)
+}
+
+fn typed_scval(value: &Value, spec: SpecType, depth: usize) -> Result<ScVal, String> {
+    if depth > MAX_DEPTH {
+        return Err("value is nested too deeply".to_string());
+    }
+    let mismatch = || format!("expected {}, got {}", spec.name(), value);
+    let lower = spec.name().to_lowercase();
+    let scval = match lower.as_str() {
+        "bool" => ScVal::Bool(value.as_bool().ok_or_else(mismatch)?),
+        "void" | "()" => ScVal::Void,
+        "u32" => ScVal::U32(integer(value, "u32")?),
+        "i32" => ScVal::I32(integer(value, "i32")?),
+        "u64" => ScVal::U64(integer(value, "u64")?),
+        "i64" => ScVal::I64(integer(value, "i64")?),
+        "timepoint" => ScVal::Timepoint(TimePoint(integer(value, "u64")?)),
+        "duration" => ScVal::Duration(Duration(integer(value, "u64")?)),
+        "i128" => {
+            let n: i128 = integer(value, "i128")?;
+            ScVal::I128(Int128Parts {
+                hi: (n >> 64) as i64,
+                lo: n as u64,
+            })
+        }
+        "u128" => {
+            let n: u128 = integer(value, "u128")?;
+            ScVal::U128(UInt128Parts {
+                hi: (n >> 64) as u64,
+                lo: n as u64,
+            })
+        }
+        "u256" => {
+            let [hi_hi, hi_lo, lo_hi, lo_lo] = int256_words(value, false)?;
+            ScVal::U256(UInt256Parts {
+                hi_hi,
+                hi_lo,
+                lo_hi,
+                lo_lo,
+            })
+        }
+        "i256" => {
+            let [hi_hi, hi_lo, lo_hi, lo_lo] = int256_words(value, true)?;
+            ScVal::I256(Int256Parts {
+                hi_hi: hi_hi as i64,
+                hi_lo,
+                lo_hi,
+                lo_lo,
+            })
+        }
+        "bytes" => ScVal::Bytes(ScBytes(
+            hex_bytes(value)?.try_into().map_err(|_| mismatch())?,
+        )),
+        "bytesn" | "bytes_n" => {
+            let bytes = hex_bytes(value)?;
+            let n = spec.ty["n"].as_u64().unwrap_or(32) as usize;
+            if bytes.len() != n {
+                return Err(format!("expected {} bytes, got {}", n, bytes.len()));
+            }
+            ScVal::Bytes(ScBytes(bytes.try_into().map_err(|_| mismatch())?))
+        }
+        "string" => ScVal::String(ScString(
+            value
+                .as_str()
+                .ok_or_else(mismatch)?
+                .to_string()
+                .try_into()
+                .map_err(|_| mismatch())?,
+        )),
+        "symbol" => symbol(value.as_str().ok_or_else(mismatch)?)?,
+        "address" => ScVal::Address(address(value)?),
+        "option" => match value {
+            Value::Null => ScVal::Void,
+            _ => typed_scval(value, spec.with(&spec.ty["element"]), depth + 1)?,
+        },
+        "vec" => {
+            let items = value.as_array().ok_or_else(mismatch)?;
+            let element = spec.with(&spec.ty["element"]);
+            vec_of(
+                items
+                    .iter()
+                    .enumerate()
+                    .map(|(i, item)| {
+                        typed_scval(item, element, depth + 1)
+                            .map_err(|reason| format!("[{}]: {}", i, reason))
+                    })
+                    .collect::<Result<_, _>>()?,
+            )?
+        }
+        "tuple" => {
+            let items = value.as_array().ok_or_else(mismatch)?;
+            let elements = spec.ty["elements"].as_array().ok_or_else(mismatch)?;
+            if items.len() != elements.len() {
+                return Err(format!("expected {} elements", elements.len()));
+            }
+            vec_of(
+                items
+                    .iter()
+                    .zip(elements)
+                    .map(|(item, ty)| typed_scval(item, spec.with(ty), depth + 1))
+                    .collect::<Result<_, _>>()?,
+            )?
+        }
+        "map" => {
+            let key_type = spec.with(&spec.ty["key"]);
+            let val_type = spec.with(&spec.ty["val"]);
+            map_of(
+                json_entries(value)?
+                    .into_iter()
+                    .map(|(k, v)| {
+                        Ok(ScMapEntry {
+                            key: typed_scval(&k, key_type, depth + 1)?,
+                            val: typed_scval(v, val_type, depth + 1)?,
+                        })
+                    })
+                    .collect::<Result<_, String>>()?,
+            )?
+        }
+        "val" | "" => untyped_scval(value, depth)?,
+        _ => typed_user_scval(value, spec, depth)?,
+    };
+    Ok(scval)
+}
+
+fn typed_user_scval(value: &Value, spec: SpecType, depth: usize) -> Result<ScVal, String> {
+    let def = spec
+        .definition()
+        .ok_or_else(|| format!("type `{}` is not in the contract spec", spec.name()))?;
+    let fields = def["fields"].as_array().map(Vec::as_slice).unwrap_or(&[]);
+    let cases = def["cases"].as_array().map(Vec::as_slice).unwrap_or(&[]);
+    let case_value = |value: &Value| -> Result<u32, String> {
+        let case = cases
+            .iter()
+            .find(|case| case["name"] == *value || (value.is_u64() && case["value"] == *value))
+            .ok_or_else(|| format!("{} is not a {} variant", value, spec.name()))?;
+        case["value"]
+            .as_u64()
+            .and_then(|n| u32::try_from(n).ok())
+            .ok_or_else(|| format!("{} has no discriminant", value))
+    };
+    match def["type"].as_str().unwrap_or_default() {
+        "struct"
+            if fields
+                .iter()
+                .all(|f| f["name"].as_str().is_some_and(is_index)) =>
+        {
+            let items = value.as_array().ok_or("expected a JSON array")?;
+            if items.len() != fields.len() {
+                return Err(format!("expected {} elements", fields.len()));
+            }
+            vec_of(
+                items
+                    .iter()
+                    .zip(fields)
+                    .map(|(item, field)| typed_scval(item, spec.with(&field["value"]), depth + 1))
+                    .collect::<Result<_, _>>()?,
+            )
+        }
+        "struct" => {
+            let object = value.as_object().ok_or("expected a JSON object")?;
+            if let Some(extra) = object
+                .keys()
+                .find(|key| !fields.iter().any(|f| f["name"] == key.as_str()))
+            {
+                return Err(format!("{} has no field `{}`", spec.name(), extra));
+            }
+            map_of(
+                fields
+                    .iter()
+                    .map(|field| {
+                        let name = field["name"].as_str().unwrap_or_default();
+                        let value = object
+                            .get(name)
+                            .ok_or_else(|| format!("missing field `{}`", name))?;
+                        Ok(ScMapEntry {
+                            key: symbol(name)?,
+                            val: typed_scval(value, spec.with(&field["value"]), depth + 1)
+                                .map_err(|reason| format!("{}: {}", name, reason))?,
+                        })
+                    })
+                    .collect::<Result<_, String>>()?,
+            )
+        }
+        "enum" => Ok(ScVal::U32(case_value(value)?)),
+        "error_enum" => Ok(ScVal::Error(ScError::Contract(case_value(value)?))),
+        "union" => {
+            // A unit case by name, or `{"Case": [fields...]}`
+            let (name, values) = match value {
+                Value::String(name) => (name.as_str(), &[][..]),
+                Value::Object(object) if object.len() == 1 => match object.iter().next() {
+                    Some((name, Value::Array(values))) => (name.as_str(), values.as_slice()),
+                    Some((name, single)) => (name.as_str(), std::slice::from_ref(single)),
+                    None => unreachable!("object has one entry"),
+                },
+                _ => return Err(format!("expected a {} variant", spec.name())),
+            };
+            let case = cases
+                .iter()
+                .find(|case| case["name"] == name)
+                .ok_or_else(|| format!("`{}` is not a {} variant", name, spec.name()))?;
+            let case_fields = case["fields"].as_array().map(Vec::as_slice).unwrap_or(&[]);
+            if values.len() != case_fields.len() {
+                return Err(format!("{} takes {} values", name, case_fields.len()));
+            }
+            let mut items = vec![symbol(name)?];
+            for (value, field) in values.iter().zip(case_fields) {
+                items.push(typed_scval(value, spec.with(&field["value"]), depth + 1)?);
+            }
+            vec_of(items)
+        }
+        other => Err(format!("`{}` entries are not values", other)),
+    }
+}
+
+/// Encode JSON with no type information: numbers as i64/u64, strings as
+/// strings (or addresses when they are strkeys), arrays as vectors and
+/// objects as symbol-keyed maps
+fn untyped_scval(value: &Value, depth: usize) -> Result<ScVal, String> {
+    if depth > MAX_DEPTH {
+        return Err("value is nested too deeply".to_string());
+    }
+    Ok(match value {
+        Value::Null => ScVal::Void,
+        Value::Bool(b) => ScVal::Bool(*b),
+        Value::Number(n) => match (n.as_i64(), n.as_u64()) {
+            (Some(n), _) => ScVal::I64(n),
+            (_, Some(n)) => ScVal::U64(n),
+            _ => return Err(format!("{} is not a whole number", n)),
+        },
+        Value::String(s) => match address(value) {
+            Ok(address) => ScVal::Address(address),
+            Err(_) => ScVal::String(ScString(
+                s.clone()
+                    .try_into()
+                    .map_err(|_| "string is too long".to_string())?,
+            )),
+        },
+        Value::Array(items) => vec_of(
+            items
+                .iter()
+                .map(|item| untyped_scval(item, depth + 1))
+                .collect::<Result<_, _>>()?,
+        )?,
+        Value::Object(object) => map_of(
+            object
+                .iter()
+                .map(|(k, v)| {
+                    Ok(ScMapEntry {
+                        key: symbol(k)?,
+                        val: untyped_scval(v, depth + 1)?,
+                    })
+                })
+                .collect::<Result<_, String>>()?,
+        )?,
+    })
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// Transactions
+// ────────────────────────────────────────────────────────────────────────────
+
+/// The envelope in the stellar-xdr JSON form, plus an `invocations` list of
+/// its contract calls with arguments decoded against `abi` when given
+pub fn transaction_to_json(envelope: &TransactionEnvelope, abi: Option<&[Value]>) -> Value {
+    let operations = match envelope {
+        TransactionEnvelope::TxV0(env) => env.tx.operations.as_slice(),
+        TransactionEnvelope::Tx(env) => env.tx.operations.as_slice(),
+        TransactionEnvelope::TxFeeBump(env) => match &env.tx.inner_tx {
+            stellar_xdr::curr::FeeBumpTransactionInnerTx::Tx(inner) => {
+                inner.tx.operations.as_slice()
+            }
+        },
+    };
+    let invocations: Vec<Value> = operations
+        .iter()
+        .filter_map(|op| match &op.body {
+            OperationBody::InvokeHostFunction(invoke) => match &invoke.host_function {
+                HostFunction::InvokeContract(call) => Some(call),
+                _ => None,
+            },
+            _ => None,
+        })
+        .map(|call| {
+            let function = call.function_name.0.to_utf8_string_lossy();
+            let inputs = abi
+                .and_then(|abi| {
+                    abi.iter()
+                        .find(|e| e["type"] == "function" && e["name"] == function.as_str())
+                        .map(|entry| (abi, entry))
+                })
+                .and_then(|(abi, entry)| Some((abi, entry["inputs"].as_array()?)));
+            let args: Value = match inputs {
+                Some((abi, inputs)) if inputs.len() == call.args.len() => Value::Object(
+                    inputs
+                        .iter()
+                        .zip(call.args.iter())
+                        .map(|(input, arg)| {
+                            let name = input["name"].as_str().unwrap_or_default().to_string();
+                            (
+                                name,
+                                scval_to_typed_json(arg, SpecType::new(&input["value"], abi)),
+                            )
+                        })
+                        .collect(),
+                ),
+                _ => Value::Array(call.args.iter().map(scval_to_json).collect()),
+            };
+            json!({
+                "contract": address_string(&call.contract_address),
+                "function": function,
+                "args": args,
+            })
+        })
+        .collect();
+    json!({
+        "envelope": serde_json::to_value(envelope).unwrap_or(Value::Null),
+        "invocations": invocations,
+    })
+}
+
+/// An envelope from its stellar-xdr JSON form, as `transaction_to_json`
+/// writes under `envelope`
+pub fn transaction_from_json(value: &Value) -> Result<TransactionEnvelope, String> {
+    let envelope = value.get("envelope").unwrap_or(value);
+    serde_json::from_value(envelope.clone())
+        .map_err(|e| format!("not a transaction envelope: {}", e))
+}
+
+/// An unsigned envelope calling `function` on a contract, from the all-zero
+/// account, for read-only calls simulated over RPC (which never submit it)
+pub fn invocation_transaction(
+    contract_id: &str,
+    function: &str,
+    args: Vec<ScVal>,
+) -> Result<TransactionEnvelope, String> {
+    let hash = decode_contract_id(contract_id)
+        .ok_or_else(|| format!("`{}` is not a contract address", contract_id))?;
+    let function_name = function
+        .to_string()
+        .try_into()
+        .map_err(|_| format!("`{}` is not a valid function name", function))?;
+    let call = InvokeContractArgs {
+        contract_address: ScAddress::Contract(Hash(hash)),
+        function_name: ScSymbol(function_name),
+        args: args
+            .try_into()
+            .map_err(|_| "too many arguments".to_string())?,
+    };
+    let operation = Operation {
+        source_account: None,
+        body: OperationBody::InvokeHostFunction(InvokeHostFunctionOp {
+            host_function: HostFunction::InvokeContract(call),
+            auth: VecM::default(),
+        }),
+    };
+    let tx = Transaction {
+        source_account: MuxedAccount::Ed25519(Uint256([0; 32])),
+        fee: 100,
+        seq_num: SequenceNumber(0),
+        cond: Preconditions::None,
+        memo: Memo::None,
+        operations: vec![operation]
+            .try_into()
+            .map_err(|_| "too many operations".to_string())?,
+        ext: TransactionExt::V0,
+    };
+    Ok(TransactionEnvelope::Tx(TransactionV1Envelope {
+        tx,
+        signatures: VecM::default(),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn abi() -> Vec<Value> {
+        json!([
+            {"type": "struct", "name": "Pool", "fields": [
+                {"name": "reserve", "value": {"type": "u64"}},
+                {"name": "owner", "value": {"type": "address"}}
+            ]},
+            {"type": "enum", "name": "Side", "cases": [
+                {"name": "Buy", "value": 0},
+                {"name": "Sell", "value": 1}
+            ]},
+            {"type": "union", "name": "Key", "cases": [
+                {"name": "Admin"},
+                {"name": "Balance", "fields": [{"name": "0", "value": {"type": "address"}}]}
+            ]},
+            {"type": "function", "name": "swap", "inputs": [
+                {"name": "amounts", "value": {"type": "vec", "element": {"type": "i128"}}},
+                {"name": "side", "value": {"type": "Side"}}
+            ], "outputs": [{"type": "Pool"}]}
+        ])
+        .as_array()
+        .cloned()
+        .unwrap()
+    }
+
+    fn round_trip(value: Value, ty: Value) -> Value {
+        let abi = abi();
+        let spec = SpecType::new(&ty, &abi);
+        let scval = json_to_scval(&value, spec).unwrap();
+        let decoded = scval_from_xdr(&scval_to_xdr(&scval).unwrap()).unwrap();
+        assert_eq!(decoded, scval);
+        scval_to_typed_json(&decoded, spec)
+    }
+
+    #[test]
+    fn typed_values_round_trip_through_xdr() {
+        let owner = encode_account_id(&[3u8; 32]);
+        let pool = json!({"reserve": 10, "owner": owner});
+        assert_eq!(round_trip(pool.clone(), json!({"type": "Pool"})), pool);
+        assert_eq!(
+            round_trip(
+                json!(["-5", 7]),
+                json!({"type": "vec", "element": {"type": "i128"}})
+            ),
+            json!(["-5", "7"])
+        );
+        assert_eq!(
+            round_trip(json!("Sell"), json!({"type": "Side"})),
+            json!("Sell")
+        );
+        let key = json!({"Balance": [encode_contract_id(&[9u8; 32])]});
+        assert_eq!(round_trip(key.clone(), json!({"type": "Key"})), key);
+        assert_eq!(
+            round_trip(json!("Admin"), json!({"type": "Key"})),
+            json!("Admin")
+        );
+        assert_eq!(
+            round_trip(json!("0x01"), json!({"type": "u256"})),
+            json!(format!("0x{:064x}", 1))
+        );
+    }
+
+    #[test]
+    fn mismatches_name_the_offending_value() {
+        let abi = abi();
+        let pool = json!({"type": "Pool"});
+        let err = json_to_scval(
+            &json!({"reserve": -1, "owner": "x"}),
+            SpecType::new(&pool, &abi),
+        )
+        .unwrap_err();
+        assert!(err.starts_with("reserve:"), "{}", err);
+        let err = json_to_scval(
+            &json!("Hold"),
+            SpecType::new(&json!({"type": "Side"}), &abi),
+        )
+        .unwrap_err();
+        assert!(err.contains("not a Side variant"), "{}", err);
+    }
+
+    #[test]
+    fn spec_types_come_from_functions() {
+        let abi = abi();
+        assert_eq!(
+            function_type(&abi, "swap", Some("side")),
+            Some(&json!({"type": "Side"}))
+        );
+        assert_eq!(
+            function_type(&abi, "swap", None),
+            Some(&json!({"type": "Pool"}))
+        );
+        assert!(function_type(&abi, "swap", Some("nope")).is_none());
+    }
+
+    #[test]
+    fn untyped_values_keep_their_json_shape() {
+        let value = json!({"b": [1, true, null], "a": "text"});
+        let scval = json_to_scval(&value, SpecType::new(&Value::Null, &[])).unwrap();
+        assert_eq!(scval_to_json(&scval), value);
+    }
+
+    #[test]
+    fn invocations_list_their_contract_calls() {
+        let contract = encode_contract_id(&[7u8; 32]);
+        let envelope = invocation_transaction(&contract, "decimals", vec![]).unwrap();
+        let decoded = transaction_from_xdr(&transaction_to_xdr(&envelope).unwrap()).unwrap();
+        assert_eq!(
+            transaction_to_json(&decoded, None)["invocations"],
+            json!([{"contract": contract, "function": "decimals", "args": []}])
+        );
+        assert!(invocation_transaction("GABC", "decimals", vec![]).is_err());
+    }
+}
@@ -16,4 +16,11 @@ fn main() {
         .unwrap_or_else(|| "unknown".to_string());
 
     println!("cargo:rustc-env=RUSTC_VERSION={rustc_version}");
+
+    // Target triple used by `self-update` to pick the right release binary
+    let target = std::env::var("TARGET").unwrap_or_else(|_| "unknown".to_string());
+    println!("cargo:rustc-env=BUILD_TARGET={target}");
+
+    // Release builds embed the key that signs CLI binaries
+    println!("cargo:rerun-if-env-changed=SOROBAN_REGISTRY_RELEASE_PUBKEY");
 }
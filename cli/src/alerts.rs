@@ -0,0 +1,128 @@
+//! `alerts`: advisories and critical patches that reach your contracts through
+//! their dependencies, and where the registry pushes new ones.
+
+use anyhow::Result;
+use colored::Colorize;
+use serde_json::json;
+use shared::{AlertSettings, DependencyAlert, PatchSeverity};
+
+fn alerts_url(api_url: &str) -> String {
+    format!("{}/api/me/alerts", api_url.trim_end_matches('/'))
+}
+
+async fn send(request: reqwest::RequestBuilder, action: &str) -> Result<reqwest::Response> {
+    let response = request
+        .bearer_auth(crate::http::auth_token()?)
+        .send()
+        .await?;
+    if !response.status().is_success() {
+        return Err(crate::api_errors::failure(response, format!("Failed to {}", action)).await);
+    }
+    Ok(response)
+}
+
+fn severity(severity: PatchSeverity) -> colored::ColoredString {
+    match severity {
+        PatchSeverity::Critical => "CRITICAL".red().bold(),
+        PatchSeverity::High => "HIGH".red(),
+        PatchSeverity::Medium => "MEDIUM".yellow(),
+        PatchSeverity::Low => "LOW".normal(),
+    }
+}
+
+/// `alerts [--all]`
+pub async fn list(api_url: &str, all: bool, json: bool) -> Result<()> {
+    let request = crate::http::client()
+        .get(alerts_url(api_url))
+        .query(&[("all", all)]);
+    let alerts: Vec<DependencyAlert> = send(request, "fetch alerts").await?.json().await?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&alerts)?);
+        return Ok(());
+    }
+    if alerts.is_empty() {
+        println!("{}", "No open alerts for your contracts.".green());
+        return Ok(());
+    }
+
+    println!("\n{} ({})", "Dependency Alerts".bold().cyan(), alerts.len());
+    println!("{}", "=".repeat(80).cyan());
+    for alert in &alerts {
+        let acknowledged = if alert.acknowledged_at.is_some() {
+            " (acknowledged)".bright_black().to_string()
+        } else {
+            String::new()
+        };
+        println!(
+            "\n  {} {}{}",
+            severity(alert.severity),
+            alert.summary.bold(),
+            acknowledged
+        );
+        println!(
+            "    {}: {}",
+            "Alert".bold(),
+            alert.id.to_string().bright_black()
+        );
+        println!("    {}: {}", "Your contract".bold(), alert.contract_id);
+        println!(
+            "    {}: {} ({})",
+            "Affected versions".bold(),
+            alert.affected_versions.join(", "),
+            alert.vulnerable_contract_id
+        );
+        if alert.dependency_path.len() > 2 {
+            let path: Vec<String> = alert
+                .dependency_path
+                .iter()
+                .map(|id| id.to_string())
+                .collect();
+            println!("    {}: {}", "Via".bold(), path.join(" → "));
+        }
+        println!("    {}: {}", "Constraint".bold(), alert.version_constraint);
+        for deployment in alert.deployments.as_array().into_iter().flatten() {
+            println!(
+                "    {}: {} ({})",
+                "Deployment".bold(),
+                deployment["environment"].as_str().unwrap_or("-"),
+                deployment["status"].as_str().unwrap_or("-")
+            );
+        }
+    }
+    println!(
+        "\n  Acknowledge one with {}",
+        "soroban-registry alerts --ack <id>".cyan()
+    );
+    Ok(())
+}
+
+/// `alerts --ack <id>`
+pub async fn acknowledge(api_url: &str, id: &str) -> Result<()> {
+    let request = crate::http::client().post(format!("{}/{}/acknowledge", alerts_url(api_url), id));
+    let alert: DependencyAlert = send(request, "acknowledge alert").await?.json().await?;
+    println!("{} Acknowledged: {}", "✓".green(), alert.summary);
+    Ok(())
+}
+
+/// `alerts --webhook <url> --email <address>`: replaces both delivery targets
+pub async fn configure(api_url: &str, webhook: Option<&str>, email: Option<&str>) -> Result<()> {
+    let request = crate::http::client()
+        .put(format!("{}/settings", alerts_url(api_url)))
+        .json(&json!({ "webhook_url": webhook, "email": email }));
+    let settings: AlertSettings = send(request, "update alert settings").await?.json().await?;
+    println!("{} New alerts will be pushed to:", "✓".green());
+    println!(
+        "  {}: {}",
+        "Webhook".bold(),
+        settings.webhook_url.as_deref().unwrap_or("-")
+    );
+    println!(
+        "  {}: {}",
+        "Email".bold(),
+        settings
+            .email
+            .as_deref()
+            .unwrap_or("your publisher profile's email")
+    );
+    Ok(())
+}
@@ -0,0 +1,200 @@
+use shared::{ErrorCode, ProblemDetails};
+
+use crate::cli_error::CliError;
+
+/// Actionable follow-up for well-known error codes
+fn hint(code: &ErrorCode) -> Option<&'static str> {
+    match code {
+        ErrorCode::ValidationError | ErrorCode::InvalidRequest => {
+            Some("check the listed fields and try again")
+        }
+        ErrorCode::InvalidContractId => {
+            Some("contract IDs are 56-character strkeys starting with 'C', or registry UUIDs")
+        }
+        ErrorCode::InvalidVersion => Some("versions must be valid semver, e.g. 1.2.3"),
+        ErrorCode::InvalidABI => {
+            Some("regenerate the contract spec with `soroban contract inspect`")
+        }
+        ErrorCode::InvalidSignature => Some("re-sign the artifact with the publisher key"),
+        ErrorCode::InvalidLicense => {
+            Some("use an SPDX expression such as \"MIT\" or \"Apache-2.0 OR MIT\"")
+        }
+        ErrorCode::ContractNotFound | ErrorCode::NotFound => {
+            Some("run `soroban-registry search` to find the contract")
+        }
+        ErrorCode::ContractTakenDown => {
+            Some("moderators removed this contract after abuse reports; it can no longer be used")
+        }
+        ErrorCode::VersionNotFound => Some("check the version with `soroban-registry info <id>`"),
+        ErrorCode::VersionAlreadyExists => Some("bump the version number before publishing"),
+        ErrorCode::BreakingChangeWithoutMajorBump => {
+            Some("publish as a new major version, or restore the removed interface")
+        }
+        ErrorCode::RateLimitExceeded => Some("wait a moment and retry"),
+        ErrorCode::QuotaExceeded => {
+            Some("run `soroban-registry usage` to see current usage, or ask the operator for more")
+        }
+        ErrorCode::Unauthorized => Some("check your API credentials"),
+        ErrorCode::Forbidden => Some("this operation requires additional permissions"),
+        ErrorCode::ChallengeRequired => {
+            Some("the registry wants a CAPTCHA from anonymous clients; sign in to skip it")
+        }
+        ErrorCode::PreconditionFailed => {
+            Some("someone else changed this contract; re-run to apply your edit on top")
+        }
+        ErrorCode::PayloadTooLarge => {
+            Some("the upload is larger than the registry accepts; ask the operator to raise its body limits")
+        }
+        ErrorCode::UnsupportedMediaType => {
+            Some("send JSON with Content-Type: application/json, or the raw file for uploads")
+        }
+        ErrorCode::UpgradeRequired => Some("run `soroban-registry self-update` to upgrade"),
+        ErrorCode::MaintenanceMode => {
+            Some("the registry is read-only during maintenance; reads still work")
+        }
+        ErrorCode::RpcUnavailable => {
+            Some("the network's Soroban RPC is not responding; retry once it recovers")
+        }
+        ErrorCode::ServiceUnavailable
+        | ErrorCode::DatabaseError
+        | ErrorCode::InternalServerError => {
+            Some("the registry had a problem; retry later or report the correlation ID")
+        }
+        ErrorCode::Other(_) => None,
+    }
+}
+
+/// Turn an API error body into a user-facing message.
+/// Problem+json bodies are summarised with a hint; anything else is returned as-is.
+pub fn describe(body: &str) -> String {
+    let Ok(problem) = serde_json::from_str::<ProblemDetails>(body) else {
+        return body.trim().to_string();
+    };
+
+    let mut message = format!("{} ({})", problem.detail, problem.code);
+    for field in &problem.errors {
+        message.push_str(&format!("\n  - {}: {}", field.field, field.message));
+    }
+    if let Some(hint) = hint(&problem.code) {
+        message.push_str(&format!("\n  hint: {}", hint));
+    }
+    if !problem.correlation_id.is_empty() && problem.status >= 500 {
+        message.push_str(&format!("\n  correlation id: {}", problem.correlation_id));
+    }
+    message
+}
+
+/// Classify and describe a failed API response body, prefixed with what was attempted
+pub fn classify(
+    status: reqwest::StatusCode,
+    body: &str,
+    context: impl std::fmt::Display,
+) -> CliError {
+    classify_with_retry(status, body, context, None)
+}
+
+fn classify_with_retry(
+    status: reqwest::StatusCode,
+    body: &str,
+    context: impl std::fmt::Display,
+    retry_after: Option<u64>,
+) -> CliError {
+    let code = serde_json::from_str::<ProblemDetails>(body)
+        .ok()
+        .map(|problem| problem.code);
+    let mut message = format!("{}: {}", context, describe(body));
+    if let Some(seconds) = retry_after {
+        message.push_str(&format!("\n  retry after: {}s", seconds));
+    }
+    CliError::from_response(status, code.as_ref(), message)
+}
+
+/// Error for a non-success response, carrying the exit code its status maps to
+pub async fn failure(
+    response: reqwest::Response,
+    context: impl std::fmt::Display,
+) -> anyhow::Error {
+    let status = response.status();
+    let retry_after = response
+        .headers()
+        .get(reqwest::header::RETRY_AFTER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse().ok());
+    let body = response.text().await.unwrap_or_default();
+    classify_with_retry(status, &body, context, retry_after).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn describes_problem_with_hint_and_fields() {
+        let body = r#"{
+            "type": "https://soroban-registry.dev/problems/validation-error",
+            "title": "Request validation failed",
+            "status": 400,
+            "detail": "Validation failed for field 'name'",
+            "code": "ValidationError",
+            "errors": [{"field": "name", "message": "is required"}]
+        }"#;
+        let message = describe(body);
+        assert!(message.starts_with("Validation failed for field 'name' (ValidationError)"));
+        assert!(message.contains("- name: is required"));
+        assert!(message.contains("hint:"));
+    }
+
+    #[test]
+    fn suggests_self_update_when_upgrade_required() {
+        let body = r#"{
+            "type": "https://soroban-registry.dev/problems/upgrade-required",
+            "title": "Client upgrade required",
+            "status": 426,
+            "detail": "This registry requires soroban-registry 0.4.0 or newer",
+            "code": "UpgradeRequired"
+        }"#;
+        assert!(describe(body).contains("soroban-registry self-update"));
+    }
+
+    #[test]
+    fn classifies_by_problem_code() {
+        let body = r#"{
+            "type": "https://soroban-registry.dev/problems/quota-exceeded",
+            "title": "Quota exceeded",
+            "status": 403,
+            "detail": "Publisher has reached 10 contracts",
+            "code": "QuotaExceeded"
+        }"#;
+        let err = classify(reqwest::StatusCode::FORBIDDEN, body, "Failed to publish");
+        assert_eq!(err.exit_code(), crate::cli_error::LIMITED);
+        assert!(err
+            .to_string()
+            .starts_with("Failed to publish: Publisher has reached"));
+    }
+
+    #[test]
+    fn maintenance_shows_operator_notice_and_retry() {
+        let body = r#"{
+            "type": "https://soroban-registry.dev/problems/maintenance-mode",
+            "title": "Registry is in maintenance mode",
+            "status": 503,
+            "detail": "Database upgrade until 14:00 UTC",
+            "code": "MaintenanceMode"
+        }"#;
+        let err = classify_with_retry(
+            reqwest::StatusCode::SERVICE_UNAVAILABLE,
+            body,
+            "Failed to publish",
+            Some(600),
+        );
+        assert_eq!(err.exit_code(), crate::cli_error::LIMITED);
+        let message = err.to_string();
+        assert!(message.contains("Database upgrade until 14:00 UTC"));
+        assert!(message.contains("retry after: 600s"));
+    }
+
+    #[test]
+    fn passes_through_non_problem_bodies() {
+        assert_eq!(describe("  upstream timeout\n"), "upstream timeout");
+    }
+}
@@ -3,6 +3,8 @@
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 
+use crate::conversions::{contract_path, resolve_contract_uuid};
+
 #[derive(Debug, Serialize)]
 struct CreateBackupRequest {
     include_state: bool,
@@ -34,9 +36,10 @@ struct BackupRestoration {
 }
 
 pub async fn create_backup(api_url: &str, contract_id: &str, include_state: bool) -> Result<()> {
-    let client = reqwest::Client::new();
+    let contract_id = &resolve_contract_uuid(api_url, contract_id).await?;
+    let client = crate::http::client();
     let backup: ContractBackup = client
-        .post(format!("{}/api/contracts/{}/backups", api_url, contract_id))
+        .post(format!("{}/api/contracts/{}/backups", api_url, contract_path(contract_id)))
         .json(&CreateBackupRequest { include_state })
         .send()
         .await?
@@ -51,9 +54,10 @@ pub async fn create_backup(api_url: &str, contract_id: &str, include_state: bool
 }
 
 pub async fn list_backups(api_url: &str, contract_id: &str) -> Result<()> {
-    let client = reqwest::Client::new();
+    let contract_id = &resolve_contract_uuid(api_url, contract_id).await?;
+    let client = crate::http::client();
     let backups: Vec<ContractBackup> = client
-        .get(format!("{}/api/contracts/{}/backups", api_url, contract_id))
+        .get(format!("{}/api/contracts/{}/backups", api_url, contract_path(contract_id)))
         .send()
         .await?
         .json()
@@ -72,14 +76,15 @@ pub async fn list_backups(api_url: &str, contract_id: &str) -> Result<()> {
 }
 
 pub async fn restore_backup(api_url: &str, contract_id: &str, backup_date: &str) -> Result<()> {
-    let client = reqwest::Client::new();
+    let contract_id = &resolve_contract_uuid(api_url, contract_id).await?;
+    let client = crate::http::client();
 
     println!("🔄 Restoring backup from {}...", backup_date);
 
     let restoration: BackupRestoration = client
         .post(format!(
             "{}/api/contracts/{}/backups/restore",
-            api_url, contract_id
+            api_url, contract_path(contract_id)
         ))
         .json(&RestoreBackupRequest {
             backup_date: backup_date.to_string(),
@@ -100,11 +105,12 @@ pub async fn restore_backup(api_url: &str, contract_id: &str, backup_date: &str)
 }
 
 pub async fn verify_backup(api_url: &str, contract_id: &str, backup_date: &str) -> Result<()> {
-    let client = reqwest::Client::new();
+    let contract_id = &resolve_contract_uuid(api_url, contract_id).await?;
+    let client = crate::http::client();
     client
         .post(format!(
             "{}/api/contracts/{}/backups/{}/verify",
-            api_url, contract_id, backup_date
+            api_url, contract_path(contract_id), backup_date
         ))
         .send()
         .await?;
@@ -114,11 +120,12 @@ pub async fn verify_backup(api_url: &str, contract_id: &str, backup_date: &str)
 }
 
 pub async fn backup_stats(api_url: &str, contract_id: &str) -> Result<()> {
-    let client = reqwest::Client::new();
+    let contract_id = &resolve_contract_uuid(api_url, contract_id).await?;
+    let client = crate::http::client();
     let stats: serde_json::Value = client
         .get(format!(
             "{}/api/contracts/{}/backups/stats",
-            api_url, contract_id
+            api_url, contract_path(contract_id)
         ))
         .send()
         .await?
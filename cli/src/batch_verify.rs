@@ -100,7 +100,7 @@ pub async fn run_batch_verify(
         initiated_by: initiated_by.to_string(),
     };
 
-    let client = reqwest::Client::builder()
+    let client = crate::http::client_builder()
         .timeout(std::time::Duration::from_secs(BATCH_TIMEOUT_SECS))
         .build()?;
 
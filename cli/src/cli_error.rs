@@ -0,0 +1,164 @@
+use std::fmt;
+
+use reqwest::StatusCode;
+use shared::ErrorCode;
+
+// Exit codes are part of the CLI's interface: add new ones, never renumber.
+
+/// Anything not classified below
+pub const GENERAL: i32 = 1;
+/// Bad flags or arguments (clap uses the same code for parse errors)
+pub const USAGE: i32 = 2;
+pub const NOT_FOUND: i32 = 3;
+/// Missing or rejected credentials, or insufficient permissions
+pub const AUTH: i32 = 4;
+/// The registry could not be reached or the connection failed mid-request
+pub const NETWORK: i32 = 5;
+/// The registry rejected the input
+pub const VALIDATION: i32 = 6;
+/// The request clashed with existing state (duplicate version, stale edit)
+pub const CONFLICT: i32 = 7;
+/// Rate limit, publishing quota or maintenance window hit; retrying later may succeed
+pub const LIMITED: i32 = 8;
+/// The registry failed while handling the request
+pub const SERVER: i32 = 9;
+/// Gave up waiting for a registry job; it may still finish
+pub const TIMEOUT: i32 = 10;
+
+/// A command failure that maps to a specific exit code
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CliError {
+    Usage(String),
+    NotFound(String),
+    Auth(String),
+    Network(String),
+    Validation(String),
+    Conflict(String),
+    Limited(String),
+    Server(String),
+    Timeout(String),
+}
+
+impl CliError {
+    /// Classify a failed API response by its problem code, falling back to the status
+    pub fn from_response(status: StatusCode, code: Option<&ErrorCode>, message: String) -> Self {
+        match code {
+            Some(
+                ErrorCode::RateLimitExceeded
+                | ErrorCode::QuotaExceeded
+                | ErrorCode::MaintenanceMode,
+            ) => return CliError::Limited(message),
+            Some(ErrorCode::PreconditionFailed | ErrorCode::VersionAlreadyExists) => {
+                return CliError::Conflict(message)
+            }
+            _ => {}
+        }
+        match status {
+            StatusCode::NOT_FOUND | StatusCode::GONE => CliError::NotFound(message),
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => CliError::Auth(message),
+            StatusCode::CONFLICT | StatusCode::PRECONDITION_FAILED => CliError::Conflict(message),
+            StatusCode::TOO_MANY_REQUESTS => CliError::Limited(message),
+            StatusCode::UPGRADE_REQUIRED => CliError::Usage(message),
+            s if s.is_server_error() => CliError::Server(message),
+            _ => CliError::Validation(message),
+        }
+    }
+
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            CliError::Usage(_) => USAGE,
+            CliError::NotFound(_) => NOT_FOUND,
+            CliError::Auth(_) => AUTH,
+            CliError::Network(_) => NETWORK,
+            CliError::Validation(_) => VALIDATION,
+            CliError::Conflict(_) => CONFLICT,
+            CliError::Limited(_) => LIMITED,
+            CliError::Server(_) => SERVER,
+            CliError::Timeout(_) => TIMEOUT,
+        }
+    }
+
+    fn message(&self) -> &str {
+        match self {
+            CliError::Usage(m)
+            | CliError::NotFound(m)
+            | CliError::Auth(m)
+            | CliError::Network(m)
+            | CliError::Validation(m)
+            | CliError::Conflict(m)
+            | CliError::Limited(m)
+            | CliError::Server(m)
+            | CliError::Timeout(m) => m,
+        }
+    }
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.message())
+    }
+}
+
+impl std::error::Error for CliError {}
+
+/// Exit code for a command failure: the first classifiable error in the chain wins
+pub fn exit_code(err: &anyhow::Error) -> i32 {
+    for cause in err.chain() {
+        if let Some(cli) = cause.downcast_ref::<CliError>() {
+            return cli.exit_code();
+        }
+        if let Some(http) = cause.downcast_ref::<reqwest::Error>() {
+            if let Some(status) = http.status() {
+                return CliError::from_response(status, None, String::new()).exit_code();
+            }
+            if http.is_connect() || http.is_timeout() || http.is_request() {
+                return NETWORK;
+            }
+        }
+        if let Some(io) = cause.downcast_ref::<std::io::Error>() {
+            if io.kind() == std::io::ErrorKind::NotFound {
+                return NOT_FOUND;
+            }
+        }
+    }
+    GENERAL
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn statuses_map_to_stable_codes() {
+        let code = |status| CliError::from_response(status, None, String::new()).exit_code();
+        assert_eq!(code(StatusCode::NOT_FOUND), NOT_FOUND);
+        assert_eq!(code(StatusCode::UNAUTHORIZED), AUTH);
+        assert_eq!(code(StatusCode::FORBIDDEN), AUTH);
+        assert_eq!(code(StatusCode::BAD_REQUEST), VALIDATION);
+        assert_eq!(code(StatusCode::UNPROCESSABLE_ENTITY), VALIDATION);
+        assert_eq!(code(StatusCode::CONFLICT), CONFLICT);
+        assert_eq!(code(StatusCode::TOO_MANY_REQUESTS), LIMITED);
+        assert_eq!(code(StatusCode::BAD_GATEWAY), SERVER);
+    }
+
+    #[test]
+    fn problem_codes_refine_the_status() {
+        let quota = CliError::from_response(
+            StatusCode::FORBIDDEN,
+            Some(&ErrorCode::QuotaExceeded),
+            "over quota".to_string(),
+        );
+        assert_eq!(quota, CliError::Limited("over quota".to_string()));
+    }
+
+    #[test]
+    fn classifies_through_context() {
+        let err = anyhow::Error::new(CliError::NotFound("no such contract".to_string()))
+            .context("Failed to fetch contract");
+        assert_eq!(exit_code(&err), NOT_FOUND);
+
+        let io = std::io::Error::new(std::io::ErrorKind::NotFound, "missing");
+        assert_eq!(exit_code(&anyhow::Error::new(io)), NOT_FOUND);
+        assert_eq!(exit_code(&anyhow::anyhow!("something else")), GENERAL);
+    }
+}
@@ -18,6 +18,7 @@ pub enum Network {
 
 use std::path::Path;
 
+use crate::conversions::{contract_path, resolve_contract_uuid};
 use crate::patch::{PatchManager, Severity};
 use crate::test_framework;
 
@@ -123,18 +124,27 @@ pub async fn search(
     query: &str,
     network: Network,
     verified_only: bool,
+    audited: bool,
     networks: Vec<String>,
     category: Option<&str>,
+    implements: Option<&str>,
+    license: Option<&str>,
     limit: usize,
     offset: usize,
+    sort: Option<&str>,
+    order: Option<&str>,
+    facets: bool,
+    filters: &ListingFilters,
     json: bool,
 ) -> Result<()> {
-    let client = reqwest::Client::new();
+    let client = crate::http::client();
 
     let mut url = format!(
         "{}/api/contracts?query={}&limit={}&offset={}",
         api_url, query, limit, offset
     );
+    push_sort_params(&mut url, sort, order);
+    filters.push_params(&mut url);
 
     if !networks.is_empty() {
         url.push_str(&format!("&networks={}", networks.join(",")));
@@ -146,10 +156,26 @@ pub async fn search(
         url.push_str("&verified_only=true");
     }
 
+    if audited {
+        url.push_str("&audited=true");
+    }
+
     if let Some(cat) = category {
         url.push_str(&format!("&category={}", cat));
     }
 
+    if let Some(iface) = implements {
+        url.push_str(&format!("&implements={}", iface));
+    }
+
+    if let Some(lic) = license {
+        url.push_str(&format!("&license={}", lic));
+    }
+
+    if facets {
+        url.push_str("&facets=true");
+    }
+
     let response = client
         .get(&url)
         .send()
@@ -165,19 +191,27 @@ pub async fn search(
             .map(|c| -> Result<_> {
                 Ok(serde_json::json!({
                     "id":          crate::conversions::as_str(&c["contract_id"], "contract_id")?,
-                    "name":        crate::conversions::as_str(&c["name"], "name")?,
+                    "name":        crate::conversions::contract_display_name(c),
                     "is_verified": crate::conversions::as_bool(&c["is_verified"], "is_verified")?,
                     "network":     crate::conversions::as_str(&c["network"], "network")?,
                     "category":    c["category"].as_str().unwrap_or(""),
+                    "license":     c["license"].as_str(),
+                    "highlight":   c.get("highlight"),
+                    "tests":       c.get("tests"),
                 }))
             })
             .collect::<Result<_, _>>()?;
         println!(
             "{}",
-            serde_json::to_string_pretty(&serde_json::json!({ "contracts": contracts }))?
+            serde_json::to_string_pretty(&serde_json::json!({
+                "contracts": contracts,
+                "did_you_mean": data["did_you_mean"],
+                "facets": data.get("facets"),
+            }))?
         );
         return Ok(());
     }
+    let did_you_mean = data["did_you_mean"].as_str();
 
     println!("\n{}", "Search Results:".bold().cyan());
     println!("{}", "=".repeat(80).cyan());
@@ -190,9 +224,16 @@ pub async fn search(
     if let Some(cat) = category {
         active_filters.push(format!("category: {}", cat));
     }
+    if let Some(iface) = implements {
+        active_filters.push(format!("implements: {}", iface));
+    }
+    if let Some(lic) = license {
+        active_filters.push(format!("license: {}", lic));
+    }
     if verified_only {
         active_filters.push("verified only".to_string());
     }
+    active_filters.extend(filters.describe());
     if !active_filters.is_empty() {
         println!(
             "  {} {}\n",
@@ -203,23 +244,47 @@ pub async fn search(
 
     if items.is_empty() {
         println!("{}", "No contracts found matching your filters.".yellow());
+        if let Some(suggestion) = did_you_mean {
+            println!("\n  Did you mean {}?", format!("\"{}\"", suggestion).bold());
+        }
         println!("\n{}", "Suggestions:".bold());
         println!("  • Try a broader search query");
         if category.is_some() {
             println!("  • Remove the --category filter to see all contract types");
         }
+        if implements.is_some() {
+            println!("  • Remove the --implements filter to include other interfaces");
+        }
         if !networks.is_empty() {
             println!("  • Try adding more networks: --networks mainnet,testnet,futurenet");
         }
         if verified_only {
             println!("  • Remove --verified-only to include unverified contracts");
         }
+        if filters.is_set() {
+            println!("  • Widen the date, download or size filters");
+        }
         println!("  • Use 'list' command to browse all contracts\n");
         return Ok(());
     }
+    if let Some(suggestion) = did_you_mean {
+        println!(
+            "  {} Showing close matches. Did you mean {}?\n",
+            format!("No exact matches for \"{}\".", query).yellow(),
+            format!("\"{}\"", suggestion).bold()
+        );
+    }
 
     for contract in items {
-        let name = crate::conversions::as_str(&contract["name"], "name")?;
+        let highlight = &contract["highlight"];
+        let name = match highlight["name"].as_str() {
+            Some(fragment) => {
+                let mut named = contract.clone();
+                named["name"] = json!(crate::conversions::render_highlight(fragment));
+                crate::conversions::contract_display_name(&named)
+            }
+            None => crate::conversions::contract_display_name(contract),
+        };
         let contract_id = crate::conversions::as_str(&contract["contract_id"], "contract_id")?;
         let is_verified = crate::conversions::as_bool(&contract["is_verified"], "is_verified")?;
         let network = crate::conversions::as_str(&contract["network"], "network")?;
@@ -241,19 +306,58 @@ pub async fn search(
                 print!(" | Category: {}", cat.bright_magenta());
             }
         }
+        let tests = serde_json::from_value::<shared::TestSummary>(contract["tests"].clone());
+        if let Ok(summary) = tests {
+            print!(" | {}", test_badge(&summary));
+        }
         println!();
 
-        if let Some(desc) = contract["description"].as_str() {
+        if let Some(snippet) = highlight["description"].as_str() {
+            println!("  {}", crate::conversions::render_highlight(snippet));
+        } else if let Some(desc) = contract["description"].as_str() {
             println!("  {}", desc.bright_black());
         }
     }
 
+    if let Some(breakdown) = data.get("facets") {
+        print_facets(breakdown);
+    }
+
     println!("\n{}", "=".repeat(80).cyan());
-    println!("Found {} contract(s) (offset: {})\n", items.len(), offset);
+    match crate::conversions::page_summary(&data) {
+        Some(summary) => println!("Showing {}\n", summary),
+        None => println!("Found {} contract(s) (offset: {})\n", items.len(), offset),
+    }
 
     Ok(())
 }
 
+/// "value (count)" pairs from one facet list, e.g. `DEX (12), token (4)`
+fn facet_summary(counts: &serde_json::Value) -> Option<String> {
+    let entries: Vec<String> = counts
+        .as_array()?
+        .iter()
+        .filter_map(|f| Some(format!("{} ({})", f["value"].as_str()?, f["count"].as_i64()?)))
+        .collect();
+    (!entries.is_empty()).then(|| entries.join(", "))
+}
+
+fn print_facets(facets: &serde_json::Value) {
+    println!("\n{}", "Refine by:".bold());
+    if let Some(categories) = facet_summary(&facets["categories"]) {
+        println!("  {} {}", "Category:".bold(), categories);
+    }
+    if let Some(tags) = facet_summary(&facets["tags"]) {
+        println!("  {} {}", "Tags:".bold(), tags);
+    }
+    println!(
+        "  {} {} verified, {} unverified",
+        "Status:".bold(),
+        facets["verified"]["verified"].as_i64().unwrap_or(0),
+        facets["verified"]["unverified"].as_i64().unwrap_or(0)
+    );
+}
+
 /// Analyze two contract versions or schema files for breaking changes.
 pub async fn upgrade_analyze(api_url: &str, old_id: &str, new_id: &str, json_out: bool) -> Result<()> {
     use reqwest::StatusCode;
@@ -290,7 +394,7 @@ pub async fn upgrade_analyze(api_url: &str, old_id: &str, new_id: &str, json_out
     }
 
     // Otherwise try to fetch versions from the API (assumes endpoint exists)
-    let client = reqwest::Client::new();
+    let client = crate::http::client();
     let url = format!("{}/api/contract_versions/{}", api_url, old_id);
     let old_res = client.get(&url).send().await.context("failed to fetch old version")?;
     if old_res.status() == StatusCode::NOT_FOUND {
@@ -431,8 +535,40 @@ pub async fn publish(
     category: Option<&str>,
     tags: Vec<String>,
     publisher: &str,
+    license: Option<&str>,
+    license_file: Option<&str>,
+    urls: shared::ProjectUrls,
+    forked_from: Option<&str>,
+    dry_run: bool,
 ) -> Result<()> {
-    let client = reqwest::Client::new();
+    // Same rules the registry applies, so nothing invalid is sent
+    let report = shared::PublishMetadata {
+        name,
+        description,
+        category,
+        tags: &tags,
+        license,
+        source_url: None,
+        urls: &urls,
+    }
+    .validate();
+    if !report.is_valid() {
+        eprintln!("{}", "Contract metadata is invalid:".red().bold());
+        for issue in &report.issues {
+            eprintln!("  --{}: {}", issue.field.replace('_', "-"), issue.message);
+        }
+        anyhow::bail!(
+            "Fix the {} metadata issue(s) above and publish again",
+            report.issues.len()
+        );
+    }
+    let license_text = license_file
+        .map(|path| {
+            fs::read_to_string(path).with_context(|| format!("Failed to read license file: {}", path))
+        })
+        .transpose()?;
+
+    let client = crate::http::client();
     let url = format!("{}/api/contracts", api_url);
 
     let payload = json!({
@@ -443,20 +579,57 @@ pub async fn publish(
         "category": category,
         "tags": tags,
         "publisher_address": publisher,
+        "license": license,
+        "license_file": license_text,
+        "repository_url": urls.repository_url,
+        "homepage": urls.homepage,
+        "documentation_url": urls.documentation_url,
+        "forked_from": forked_from,
     });
 
+    if dry_run {
+        crate::dry_run::banner("publish");
+        let response = client
+            .post(&url)
+            .query(&crate::dry_run::QUERY)
+            .json(&payload)
+            .send()
+            .await
+            .context("Failed to validate publish")?;
+        if !response.status().is_success() {
+            return Err(crate::api_errors::failure(response, "Publish would fail").await);
+        }
+        let preview: shared::PublishPreview = response.json().await?;
+        crate::dry_run::validated("POST", &url);
+        if preview.creates_publisher {
+            crate::dry_run::would(format!("create publisher {}", preview.publisher_address));
+        }
+        if preview.claims_namespace {
+            crate::dry_run::would(format!("claim the namespace of {}", preview.name));
+        }
+        crate::dry_run::would(format!(
+            "register {} ({}) on {}",
+            preview.name, preview.contract_id, preview.network
+        ));
+        println!();
+        return Ok(());
+    }
+
     println!("\n{}", "Publishing contract...".bold().cyan());
 
     let response = client
         .post(&url)
+        .header(
+            crate::http::IDEMPOTENCY_KEY_HEADER,
+            crate::http::idempotency_key(),
+        )
         .json(&payload)
         .send()
         .await
         .context("Failed to publish contract")?;
 
     if !response.status().is_success() {
-        let error_text = response.text().await?;
-        anyhow::bail!("Failed to publish: {}", error_text);
+        return Err(crate::api_errors::failure(response, "Failed to publish").await);
     }
 
     let contract: serde_json::Value = response.json().await?;
@@ -465,7 +638,7 @@ pub async fn publish(
     println!(
         "\n{}: {}",
         "Name".bold(),
-        crate::conversions::as_str(&contract["name"], "name")?
+        crate::conversions::contract_display_name(&contract)
     );
     println!(
         "{}: {}",
@@ -482,12 +655,421 @@ pub async fn publish(
     Ok(())
 }
 
-pub async fn list(api_url: &str, limit: usize, network: Network, json: bool,) -> Result<()> {
-    let client = reqwest::Client::new();
-    let url = format!(
+/// Compare a local contract spec with the latest published interface and warn
+/// when it breaks callers without a major version bump.
+pub async fn check_interface(
+    api_url: &str,
+    contract_id: &str,
+    abi_path: &str,
+    version: Option<&str>,
+) -> Result<()> {
+    let spec_text = fs::read_to_string(abi_path)
+        .with_context(|| format!("Failed to read contract spec: {}", abi_path))?;
+    let spec: serde_json::Value =
+        serde_json::from_str(&spec_text).context("Contract spec is not valid JSON")?;
+
+    let client = crate::http::client();
+    let url = format!("{}/api/compat/check", api_url);
+    let payload = json!({
+        "old": contract_id,
+        "new_spec": spec,
+        "new_version": version,
+    });
+
+    println!("\n{}", "Checking interface compatibility...".bold().cyan());
+
+    let response = client
+        .post(&url)
+        .json(&payload)
+        .send()
+        .await
+        .context("Failed to check interface compatibility")?;
+
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        println!(
+            "  {} no previous interface found, skipping check",
+            "ℹ".bright_blue()
+        );
+        return Ok(());
+    }
+    if !response.status().is_success() {
+        return Err(crate::api_errors::failure(response, "Compatibility check failed").await);
+    }
+
+    let report: serde_json::Value = response.json().await?;
+    let compatibility = crate::conversions::as_str(&report["compatibility"], "compatibility")?;
+    let label = match compatibility.as_str() {
+        "identical" => "identical".green(),
+        "additive" => "additive".bright_blue(),
+        _ => "breaking".red().bold(),
+    };
+    println!(
+        "  Compatibility with {}: {}",
+        report["old_version"].as_str().unwrap_or(contract_id),
+        label
+    );
+
+    if let Some(changes) = report["changes"].as_array() {
+        for change in changes {
+            let severity = crate::conversions::as_str(&change["severity"], "severity")?;
+            let message = crate::conversions::as_str(&change["message"], "message")?;
+            if severity == "breaking" {
+                println!("    {} {}", "BREAKING".red().bold(), message);
+            } else {
+                println!("    {} {}", "ADDED".green(), message);
+            }
+        }
+    }
+
+    if report["requires_major_bump"].as_bool().unwrap_or(false) {
+        println!(
+            "\n{} {}",
+            "⚠ Warning:".yellow().bold(),
+            "this version breaks the previous interface without a major version bump".yellow()
+        );
+    }
+
+    Ok(())
+}
+
+/// Warn when the registry resolved a contract through a deprecated former name
+fn print_alias_notice(contract: &serde_json::Value) {
+    if let Some(notice) = contract["alias_notice"].as_str() {
+        println!("  {} {}", "⚠".yellow(), notice.yellow());
+    }
+}
+
+/// Download a published contract's interface into `output_dir`.
+/// With `require_provenance`, refuse versions lacking a verified attestation
+/// bound to the published wasm hash.
+/// Download an artifact by content hash and check it hashes to what was asked for
+async fn fetch_artifact(
+    client: &reqwest::Client,
+    api_url: &str,
+    kind: &str,
+    sha256: &str,
+) -> Result<Vec<u8>> {
+    let url = format!("{}/api/artifacts/{}", api_url, sha256);
+    let response = client
+        .get(&url)
+        .send()
+        .await
+        .with_context(|| format!("Failed to fetch the {} artifact", kind))?;
+    if !response.status().is_success() {
+        return Err(crate::api_errors::failure(response, "Failed to fetch artifact").await);
+    }
+    let content = response.bytes().await?.to_vec();
+    crate::lockfile::verify_artifact(kind, sha256, &content)?;
+    Ok(content)
+}
+
+/// Look up many contracts (`selector` or `selector@version`) through
+/// POST /api/contracts/batch, in chunks the endpoint accepts. Each result
+/// carries its resolved version and that version's artifacts.
+async fn lookup_contracts(
+    client: &reqwest::Client,
+    api_url: &str,
+    queries: &[String],
+) -> Result<Vec<shared::BatchContractLookupResult>> {
+    let url = format!("{}/api/contracts/batch", api_url);
+    let mut results = Vec::with_capacity(queries.len());
+    for chunk in queries.chunks(shared::MAX_BATCH_LOOKUP) {
+        let response = client
+            .post(&url)
+            .json(&shared::BatchContractLookupRequest {
+                ids: chunk.to_vec(),
+                resolve_artifacts: true,
+            })
+            .send()
+            .await
+            .context("Failed to look up contracts")?;
+        if !response.status().is_success() {
+            return Err(crate::api_errors::failure(response, "Failed to look up contracts").await);
+        }
+        let batch: shared::BatchContractLookupResponse = response.json().await?;
+        results.extend(batch.results);
+    }
+    Ok(results)
+}
+
+/// Pin `contract_id` to the artifacts of a version, from the lockfile when it
+/// already has a matching entry
+async fn resolve_locked(
+    client: &reqwest::Client,
+    api_url: &str,
+    lock: &crate::lockfile::Lockfile,
+    contract_id: &str,
+    version: Option<&str>,
+    update: bool,
+) -> Result<(crate::lockfile::LockedContract, bool)> {
+    if let Some(entry) = lock.get(contract_id) {
+        if !update && version.map_or(true, |v| v == entry.version) {
+            return Ok((entry.clone(), true));
+        }
+    }
+
+    let url = format!("{}/api/contracts/{}/artifacts", api_url, contract_path(contract_id));
+    let version_query: Vec<(&str, &str)> =
+        version.map(|v| vec![("version", v)]).unwrap_or_default();
+    let response = client
+        .get(&url)
+        .query(&version_query)
+        .send()
+        .await
+        .context("Failed to resolve contract artifacts")?;
+    if !response.status().is_success() {
+        return Err(
+            crate::api_errors::failure(response, "Failed to resolve contract artifacts").await,
+        );
+    }
+    let resolved: shared::VersionArtifacts = response.json().await?;
+    if let Some(support) = &resolved.support {
+        crate::support::warn(contract_id, support);
+    }
+    let entry = crate::lockfile::LockedContract {
+        id: contract_id.to_string(),
+        version: resolved.version,
+        artifacts: resolved
+            .artifacts
+            .into_iter()
+            .map(|artifact| (artifact.kind, artifact.sha256))
+            .collect(),
+    };
+    Ok((entry, false))
+}
+
+pub async fn install(
+    api_url: &str,
+    contract_id: &str,
+    version: Option<&str>,
+    output_dir: &str,
+    require_provenance: bool,
+    update: bool,
+) -> Result<()> {
+    let client = crate::http::client();
+    let mut lock = crate::lockfile::Lockfile::load(Path::new(crate::lockfile::LOCKFILE_NAME))?;
+
+    println!("\n{}", "Installing contract...".bold().cyan());
+
+    if contract_id.starts_with('@') {
+        let url = format!("{}/api/contracts/{}", api_url, contract_path(contract_id));
+        let response = client
+            .get(&url)
+            .send()
+            .await
+            .context("Failed to resolve contract name")?;
+        if response.status().is_success() {
+            let contract: serde_json::Value = response.json().await?;
+            print_alias_notice(&contract);
+        }
+    }
+
+    let pinned = resolve_locked(&client, api_url, &lock, contract_id, version, update).await?;
+    install_pinned(
+        &client,
+        api_url,
+        &mut lock,
+        contract_id,
+        pinned,
+        output_dir,
+        require_provenance,
+    )
+    .await
+}
+
+/// Write the interface and WASM of an already resolved `entry`, pinning it in
+/// the lockfile unless it came from there (`locked`)
+async fn install_pinned(
+    client: &reqwest::Client,
+    api_url: &str,
+    lock: &mut crate::lockfile::Lockfile,
+    contract_id: &str,
+    (entry, locked): (crate::lockfile::LockedContract, bool),
+    output_dir: &str,
+    require_provenance: bool,
+) -> Result<()> {
+    if locked {
+        println!(
+            "  {} {}@{} pinned by {}",
+            "→".bright_black(),
+            contract_id,
+            entry.version,
+            crate::lockfile::LOCKFILE_NAME
+        );
+    }
+    let version_query = [("version", entry.version.as_str())];
+
+    let provenance_url = format!(
+        "{}/api/contracts/{}/provenance",
+        api_url,
+        contract_path(contract_id)
+    );
+    let response = client
+        .get(&provenance_url)
+        .query(&version_query)
+        .send()
+        .await
+        .context("Failed to fetch provenance")?;
+
+    if response.status().is_success() {
+        let provenance: shared::ProvenanceResponse = response.json().await?;
+        if provenance.has_valid_chain() {
+            println!(
+                "  {} verified provenance for {}@{}",
+                "✓".green(),
+                contract_id,
+                provenance.version
+            );
+        } else if require_provenance {
+            anyhow::bail!(
+                "{}@{} has no verified provenance attestation for wasm hash {}",
+                contract_id,
+                provenance.version,
+                provenance.wasm_hash
+            );
+        } else {
+            println!("  {} no verified provenance attestation", "⚠".yellow());
+        }
+    } else if require_provenance {
+        return Err(crate::api_errors::failure(response, "Failed to fetch provenance").await);
+    }
+
+    // The ABI comes by hash when it was published as an artifact
+    let abi: serde_json::Value = match entry.artifacts.get("abi") {
+        Some(sha256) => {
+            let content = fetch_artifact(client, api_url, "abi", sha256).await?;
+            serde_json::from_slice(&content).context("The abi artifact is not valid JSON")?
+        }
+        None => {
+            let abi_url = format!("{}/api/contracts/{}/abi", api_url, contract_path(contract_id));
+            let response = client
+                .get(&abi_url)
+                .query(&version_query)
+                .send()
+                .await
+                .context("Failed to fetch contract ABI")?;
+            if !response.status().is_success() {
+                return Err(
+                    crate::api_errors::failure(response, "Failed to fetch contract ABI").await,
+                );
+            }
+            response.json().await?
+        }
+    };
+
+    fs::create_dir_all(output_dir)
+        .with_context(|| format!("Failed to create directory {}", output_dir))?;
+    let abi_path = Path::new(output_dir).join(format!("{}.abi.json", contract_id));
+    fs::write(&abi_path, serde_json::to_string_pretty(&abi)?)
+        .with_context(|| format!("Failed to write {}", abi_path.display()))?;
+
+    println!(
+        "  {} interface written to {}",
+        "✓".green().bold(),
+        abi_path.display().to_string().bright_black()
+    );
+
+    if let Some(sha256) = entry.artifacts.get("wasm") {
+        let wasm = fetch_artifact(client, api_url, "wasm", sha256).await?;
+        let wasm_path = Path::new(output_dir).join(format!("{}.wasm", contract_id));
+        fs::write(&wasm_path, wasm)
+            .with_context(|| format!("Failed to write {}", wasm_path.display()))?;
+        println!(
+            "  {} WASM {} written to {}",
+            "✓".green().bold(),
+            &sha256[..12],
+            wasm_path.display().to_string().bright_black()
+        );
+    }
+
+    if !locked {
+        lock.upsert(entry);
+        lock.save(Path::new(crate::lockfile::LOCKFILE_NAME))?;
+        println!(
+            "  {} pinned in {}",
+            "✓".green().bold(),
+            crate::lockfile::LOCKFILE_NAME
+        );
+    }
+
+    Ok(())
+}
+
+/// Date, popularity and size filters shared by `search` and `list`
+#[derive(Debug, Default)]
+pub struct ListingFilters {
+    /// UTC timestamps ending in `Z` from [`crate::conversions::parse_since`],
+    /// so they need no escaping in a query string
+    pub created_after: Option<String>,
+    pub updated_after: Option<String>,
+    pub min_downloads: Option<u64>,
+    pub max_wasm_size: Option<u64>,
+}
+
+impl ListingFilters {
+    pub fn is_set(&self) -> bool {
+        !self.describe().is_empty()
+    }
+
+    fn push_params(&self, url: &mut String) {
+        if let Some(after) = &self.created_after {
+            url.push_str(&format!("&created_after={}", after));
+        }
+        if let Some(after) = &self.updated_after {
+            url.push_str(&format!("&updated_after={}", after));
+        }
+        if let Some(min) = self.min_downloads {
+            url.push_str(&format!("&min_downloads={}", min));
+        }
+        if let Some(max) = self.max_wasm_size {
+            url.push_str(&format!("&max_wasm_size={}", max));
+        }
+    }
+
+    fn describe(&self) -> Vec<String> {
+        let mut active = Vec::new();
+        if let Some(after) = &self.created_after {
+            active.push(format!("created after {}", after));
+        }
+        if let Some(after) = &self.updated_after {
+            active.push(format!("updated after {}", after));
+        }
+        if let Some(min) = self.min_downloads {
+            active.push(format!("≥ {} downloads", min));
+        }
+        if let Some(max) = self.max_wasm_size {
+            active.push(format!("WASM ≤ {} bytes", max));
+        }
+        active
+    }
+}
+
+/// Append `sort`/`order` query parameters when the user asked for a specific ordering
+fn push_sort_params(url: &mut String, sort: Option<&str>, order: Option<&str>) {
+    if let Some(sort) = sort {
+        url.push_str(&format!("&sort={}", sort));
+    }
+    if let Some(order) = order {
+        url.push_str(&format!("&order={}", order));
+    }
+}
+
+pub async fn list(
+    api_url: &str,
+    limit: usize,
+    network: Network,
+    sort: Option<&str>,
+    order: Option<&str>,
+    filters: &ListingFilters,
+    json: bool,
+) -> Result<()> {
+    let client = crate::http::client();
+    let mut url = format!(
         "{}/api/contracts?page_size={}&network={}",
         api_url, limit, network
     );
+    push_sort_params(&mut url, sort, order);
+    filters.push_params(&mut url);
 
     let response = client
         .get(&url)
@@ -503,7 +1085,7 @@ pub async fn list(api_url: &str, limit: usize, network: Network, json: bool,) ->
             .iter()
             .map(|c| -> Result<_> { Ok(serde_json::json!({
                 "id":          crate::conversions::as_str(&c["contract_id"], "contract_id")?,
-                "name":        crate::conversions::as_str(&c["name"], "name")?,
+                "name":        crate::conversions::contract_display_name(c),
                 "is_verified": crate::conversions::as_bool(&c["is_verified"], "is_verified")?,
                 "network":     crate::conversions::as_str(&c["network"], "network")?,
             })) })
@@ -521,7 +1103,7 @@ pub async fn list(api_url: &str, limit: usize, network: Network, json: bool,) ->
     }
 
     for (i, contract) in items.iter().enumerate() {
-        let name = crate::conversions::as_str(&contract["name"], "name")?;
+        let name = crate::conversions::contract_display_name(contract);
         let contract_id = crate::conversions::as_str(&contract["contract_id"], "contract_id")?;
         let is_verified = crate::conversions::as_bool(&contract["is_verified"], "is_verified")?;
         let network = crate::conversions::as_str(&contract["network"], "network")?;
@@ -544,11 +1126,62 @@ pub async fn list(api_url: &str, limit: usize, network: Network, json: bool,) ->
     }
 
     println!("\n{}", "=".repeat(80).cyan());
+    if let Some(summary) = crate::conversions::page_summary(&data) {
+        println!("Showing {}", summary);
+    }
     println!();
 
     Ok(())
 }
 
+pub async fn discover(
+    api_url: &str,
+    category: Option<&str>,
+    network: Network,
+    json: bool,
+) -> Result<()> {
+    let client = crate::http::client();
+    let mut query = vec![("network", network.to_string())];
+    if let Some(category) = category {
+        query.push(("category", category.to_string()));
+    }
+    let response = client
+        .get(format!("{}/api/contracts/random", api_url.trim_end_matches('/')))
+        .query(&query)
+        .send()
+        .await
+        .context("Failed to fetch a random contract")?;
+    if !response.status().is_success() {
+        return Err(crate::api_errors::failure(response, "Nothing to discover").await);
+    }
+
+    let contract: serde_json::Value = response.json().await?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&contract)?);
+        return Ok(());
+    }
+
+    let name = crate::conversions::contract_display_name(&contract);
+    let contract_id = crate::conversions::as_str(&contract["contract_id"], "contract_id")?;
+    println!("\n{} {}", name.bold(), "✓".green());
+    println!(
+        "   {} | {}",
+        contract_id.bright_black(),
+        crate::conversions::as_str(&contract["network"], "network")?.bright_blue()
+    );
+    if let Some(description) = contract["description"].as_str() {
+        println!("   {}", description);
+    }
+    if let Some(category) = contract["category"].as_str() {
+        println!("   {}: {}", "Category".bold(), category);
+    }
+    println!(
+        "\nRun {} for details.\n",
+        format!("soroban-registry info {}", contract_id).cyan()
+    );
+    Ok(())
+}
+
 fn extract_migration_id(migration: &serde_json::Value) -> Result<String> {
     let Some(migration_id) = migration["id"].as_str() else {
         eprintln!(
@@ -561,7 +1194,7 @@ fn extract_migration_id(migration: &serde_json::Value) -> Result<String> {
     Ok(migration_id.to_string())
 }
 pub async fn breaking_changes(api_url: &str, old_id: &str, new_id: &str, json: bool) -> Result<()> {
-    let client = reqwest::Client::new();
+    let client = crate::http::client();
     let url = format!(
         "{}/api/contracts/breaking-changes?old_id={}&new_id={}",
         api_url, old_id, new_id
@@ -574,8 +1207,7 @@ pub async fn breaking_changes(api_url: &str, old_id: &str, new_id: &str, json: b
         .context("Failed to fetch breaking changes")?;
 
     if !response.status().is_success() {
-        let error_text = response.text().await?;
-        anyhow::bail!("Failed to fetch breaking changes: {}", error_text);
+        return Err(crate::api_errors::failure(response, "Failed to fetch breaking changes").await);
     }
 
     let report: serde_json::Value = response.json().await?;
@@ -648,7 +1280,7 @@ pub async fn migrate(
 @@ -298,51 +309,51 @@ pub async fn migrate(
 
     // 3. Create Migration Record (Pending)
-    let client = reqwest::Client::new();
+    let client = crate::http::client();
     let create_url = format!("{}/api/migrations", api_url);
 
     let payload = json!({
@@ -666,8 +1298,7 @@ pub async fn migrate(
 
     if !response.status().is_success() {
         println!("{}", "Failed".red());
-        let err = response.text().await?;
-        anyhow::bail!("API Error: {}", err);
+        return Err(crate::api_errors::failure(response, "API error").await);
     }
 
     let migration: serde_json::Value = response.json().await?;
@@ -844,13 +1475,32 @@ pub async fn export(
         "contract directory does not exist: {}",
         contract_dir
     );
-    crate::export::create_archive(
-        source,
-        std::path::Path::new(output),
-        id,
-        "contract",
-        "testnet",
-    )?;
+    if output == crate::stream_archive::STDIO {
+        // stdout carries the archive, so the summary goes to stderr
+        let stdout = std::io::stdout().lock();
+        crate::stream_archive::write(source, stdout, id, "contract", "testnet")?;
+        eprintln!("{} Exported {} to stdout", "✓".green(), id.bright_black());
+        return Ok(());
+    }
+    if crate::stream_archive::is_stream_path(output) {
+        let file = std::fs::File::create(output)
+            .with_context(|| format!("Failed to create {}", output))?;
+        crate::stream_archive::write(
+            source,
+            std::io::BufWriter::new(file),
+            id,
+            "contract",
+            "testnet",
+        )?;
+    } else {
+        crate::export::create_archive(
+            source,
+            std::path::Path::new(output),
+            id,
+            "contract",
+            "testnet",
+        )?;
+    }
     println!("{}", "✓ Export complete!".green().bold());
     println!("  {}: {}", "Output".bold(), output);
     println!("  {}: {}", "Contract".bold(), id.bright_black());
@@ -858,20 +1508,91 @@ pub async fn export(
     Ok(())
 }
 
+/// Download an archive the registry builds from what it stores for `id`,
+/// in the same layout `export` writes, so no local source is needed
+pub async fn export_from_registry(
+    api_url: &str,
+    id: &str,
+    version: Option<&str>,
+    output: &str,
+) -> Result<()> {
+    let mut request = crate::http::client().get(format!(
+        "{}/api/contracts/{}/archive",
+        api_url.trim_end_matches('/'),
+        id
+    ));
+    if let Some(version) = version {
+        request = request.query(&[("version", version)]);
+    }
+    let response = request
+        .send()
+        .await
+        .context("Failed to download contract archive")?;
+    if !response.status().is_success() {
+        return Err(crate::api_errors::failure(response, "Failed to export contract").await);
+    }
+    let archive = response.bytes().await?;
+    if output == crate::stream_archive::STDIO {
+        use std::io::Write;
+        let mut stdout = std::io::stdout().lock();
+        stdout.write_all(&archive)?;
+        stdout.flush()?;
+        eprintln!("{} Exported {} to stdout", "✓".green(), id.bright_black());
+        return Ok(());
+    }
+    std::fs::write(output, &archive).with_context(|| format!("Failed to write {}", output))?;
+
+    println!("{}", "✓ Export complete!".green().bold());
+    println!("  {}: {}", "Output".bold(), output);
+    println!("  {}: {}", "Contract".bold(), id.bright_black());
+    if let Some(version) = version {
+        println!("  {}: {}", "Version".bold(), version);
+    }
+    println!("  {}: {} bytes\n", "Size".bold(), archive.len());
+    Ok(())
+}
+
 pub async fn import(
     api_url: &str,
     archive: &str,
     network: Network,
     output_dir: &str,
+    publish_as: Option<&str>,
+    dry_run: bool,
 ) -> Result<()> {
-    println!("\n{}", "Importing contract...".bold().cyan());
-
-    let archive_path = std::path::Path::new(archive);
-    anyhow::ensure!(archive_path.is_file(), "archive not found: {}", archive);
+    anyhow::ensure!(
+        archive == crate::stream_archive::STDIO || std::path::Path::new(archive).is_file(),
+        "archive not found: {}",
+        archive
+    );
 
     let dest = std::path::Path::new(output_dir);
 
-    let manifest = crate::import::extract_and_verify(archive_path, dest)?;
+    if dry_run {
+        crate::dry_run::banner("import");
+        // Verify in a scratch directory so nothing lands in output_dir
+        let scratch = tempfile::tempdir().context("failed to create temp dir")?;
+        let manifest = crate::import::extract_archive(archive, scratch.path())?;
+        println!("  {} integrity verified", "✓".green());
+        for entry in &manifest.contents {
+            crate::dry_run::would(format!(
+                "write {} ({} bytes)",
+                dest.join(&entry.path).display(),
+                entry.size
+            ));
+        }
+        if let Some(publisher) = publish_as {
+            let network = network.to_string();
+            crate::import::publish(api_url, scratch.path(), &manifest, &network, publisher, true)
+                .await?;
+        }
+        println!();
+        return Ok(());
+    }
+
+    println!("\n{}", "Importing contract...".bold().cyan());
+
+    let manifest = crate::import::extract_archive(archive, dest)?;
 
     println!(
         "{}",
@@ -897,6 +1618,23 @@ pub async fn import(
     );
     println!("  {}: {}", "Extracted To".bold(), output_dir);
 
+    if let Some(publisher) = publish_as {
+        println!(
+            "\n{} {}",
+            "Publishing to".bold().cyan(),
+            network.to_string().bright_blue()
+        );
+        return crate::import::publish(
+            api_url,
+            dest,
+            &manifest,
+            &network.to_string(),
+            publisher,
+            false,
+        )
+        .await;
+    }
+
     println!(
         "\n  {} To register on {}, run:",
         "→".bright_black(),
@@ -962,10 +1700,10 @@ pub async fn patch_create(
 
 /// GET /api/contracts/:id/trust-score
 pub async fn trust_score(api_url: &str, contract_id: &str, network: Network) -> Result<()> {
-    let url = format!("{}/api/contracts/{}/trust-score", api_url, contract_id);
+    let url = format!("{}/api/contracts/{}/trust-score", api_url, contract_path(contract_id));
     log::debug!("GET {}", url);
 
-    let client = reqwest::Client::new();
+    let client = crate::http::client();
     let resp = client
         .get(&url)
         .query(&[("network", network.to_string())])
@@ -974,9 +1712,7 @@ pub async fn trust_score(api_url: &str, contract_id: &str, network: Network) ->
         .context("Failed to reach registry API")?;
 
     if !resp.status().is_success() {
-        let status = resp.status();
-        let body = resp.text().await.unwrap_or_default();
-        anyhow::bail!("Failed to get trust score ({}): {}", status, body);
+        return Err(crate::api_errors::failure(resp, "Failed to get trust score").await);
     }
 
     let data: serde_json::Value = resp.json().await.context("Failed to parse trust score response")?;
@@ -1037,11 +1773,18 @@ pub async fn patch_notify(api_url: &str, patch_id: &str) -> Result<()> {
     let (patch, contracts) = PatchManager::find_vulnerable(api_url, patch_id).await?;
 
     println!(
-        "\n{} {} patch for version {}",
+        "\n{} {} patch for versions {}",
         "⚠".bold(),
         severity_colored(&patch.severity),
         patch.target_version.bold()
     );
+    if patch.approval_pending {
+        println!(
+            "  {} {}",
+            "⚠".yellow(),
+            "Awaiting a second maintainer's approval (patch approve) before wide rollout".yellow()
+        );
+    }
     println!("{}", "=".repeat(80).cyan());
 
     if contracts.is_empty() {
@@ -1050,28 +1793,58 @@ pub async fn patch_notify(api_url: &str, patch_id: &str) -> Result<()> {
     }
 
     for (i, c) in contracts.iter().enumerate() {
-        let cid = crate::conversions::as_str(&c["contract_id"], "contract_id")?;
-        let name = crate::conversions::as_str(&c["name"], "name")?;
-        let net = crate::conversions::as_str(&c["network"], "network")?;
         println!(
             "  {}. {} ({}) [{}]",
             i + 1,
-            name.bold(),
-            cid.bright_black(),
-            net.bright_blue()
+            c.name.bold(),
+            c.contract_id.bright_black(),
+            c.network.bright_blue()
         );
+        println!("     {}: {}", "Publisher".bold(), c.publisher_address);
+        println!("     {}: {}", "Versions".bold(), c.versions.join(", "));
+        for d in &c.deployments {
+            println!(
+                "     {}: {} ({}) running {}",
+                "Deployment".bold(),
+                d.environment,
+                d.status,
+                d.version
+            );
+        }
     }
 
+    let deployments: usize = contracts.iter().map(|c| c.deployments.len()).sum();
     println!("\n{}", "=".repeat(80).cyan());
-    println!("{} vulnerable contract(s) found\n", contracts.len());
+    println!(
+        "{} vulnerable contract(s), {} deployment(s) found\n",
+        contracts.len(),
+        deployments
+    );
 
     Ok(())
 }
 
-pub async fn patch_apply(api_url: &str, contract_id: &str, patch_id: &str) -> Result<()> {
+pub async fn patch_apply(
+    api_url: &str,
+    contract_id: &str,
+    patch_id: &str,
+    dry_run: bool,
+) -> Result<()> {
+    if dry_run {
+        crate::dry_run::banner("patch apply");
+        let audit = PatchManager::apply(api_url, contract_id, patch_id, true).await?;
+        crate::dry_run::validated("POST", &format!("{}/api/patches/{}/apply", api_url, patch_id));
+        crate::dry_run::would(format!(
+            "record patch {} as applied to {}",
+            audit.patch_id, audit.contract_id
+        ));
+        println!();
+        return Ok(());
+    }
+
     println!("\n{}", "Applying security patch...".bold().cyan());
 
-    let audit = PatchManager::apply(api_url, contract_id, patch_id).await?;
+    let audit = PatchManager::apply(api_url, contract_id, patch_id, false).await?;
 
     println!("{}", "✓ Patch applied successfully!".green().bold());
     println!("  {}: {}", "Contract".bold(), audit.contract_id);
@@ -1081,9 +1854,65 @@ pub async fn patch_apply(api_url: &str, contract_id: &str, patch_id: &str) -> Re
     Ok(())
 }
 
+pub async fn patch_approve(api_url: &str, patch_id: &str) -> Result<()> {
+    let approval = PatchManager::approve(api_url, patch_id).await?;
+
+    println!("{}", "✓ Patch approved!".green().bold());
+    println!("  {}: {}", "Patch".bold(), approval.patch_id);
+    println!("  {}: {}", "Approver".bold(), approval.approver_address);
+    println!(
+        "  {}\n",
+        "Rollout can now continue past the unapproved limit.".bright_black()
+    );
+
+    Ok(())
+}
+
+pub async fn patch_status(api_url: &str, contract_id: &str) -> Result<()> {
+    let history = PatchManager::history(api_url, contract_id).await?;
+
+    println!("\n{} {}", "Patch history for".bold().cyan(), contract_id.bold());
+    println!("{}", "=".repeat(80).cyan());
+
+    if history.is_empty() {
+        println!("{}\n", "No patches have been applied to this contract.".green());
+        return Ok(());
+    }
+
+    for entry in &history {
+        println!(
+            "  {} {} {} {}",
+            entry.applied_at.format("%Y-%m-%d %H:%M").to_string().bright_black(),
+            severity_colored(&entry.severity),
+            entry.patch_id,
+            format!("(from {})", entry.target_version).bright_black()
+        );
+        let stage = entry
+            .rollout_stage()
+            .unwrap_or_else(|| "position not recorded".to_string());
+        println!(
+            "      {}: {} of a {}% rollout",
+            "Stage".bold(),
+            stage,
+            entry.rollout_percentage
+        );
+        if let Some(by) = &entry.applied_by {
+            println!("      {}: {}", "Applied by".bold(), by);
+        }
+        if let Some(description) = &entry.description {
+            println!("      {}", description.bright_black());
+        }
+    }
+
+    println!("\n{}", "=".repeat(80).cyan());
+    println!("{} patch(es) applied\n", history.len());
+
+    Ok(())
+}
+
 pub async fn deps_list(api_url: &str, contract_id: &str) -> Result<()> {
-    let client = reqwest::Client::new();
-    let url = format!("{}/api/contracts/{}/dependencies", api_url, contract_id);
+    let client = crate::http::client();
+    let url = format!("{}/api/contracts/{}/dependencies", api_url, contract_path(contract_id));
 
     let response = client
         .get(&url)
@@ -1092,10 +1921,7 @@ pub async fn deps_list(api_url: &str, contract_id: &str) -> Result<()> {
         .context("Failed to fetch contract dependencies")?;
 
     if !response.status().is_success() {
-        if response.status() == reqwest::StatusCode::NOT_FOUND {
-            anyhow::bail!("Contract not found");
-        }
-        anyhow::bail!("Failed to fetch dependencies: {}", response.status());
+        return Err(crate::api_errors::failure(response, "Failed to fetch dependencies").await);
     }
 
     let items: serde_json::Value = response.json().await?;
@@ -1280,9 +2106,37 @@ pub async fn run_tests(
 
 #[cfg(test)]
 mod tests {
-    use super::extract_migration_id;
+    use super::{extract_migration_id, find_collection_item, format_byte_count};
     use serde_json::json;
 
+    #[test]
+    fn collection_items_match_any_contract_reference() {
+        let item: shared::CollectionItem = serde_json::from_value(json!({
+            "position": 0,
+            "id": "6f9d2c1e-6d1b-4f39-9a7e-0b8f3f3a1c55",
+            "contract_id": "CABC",
+            "namespace": "alice",
+            "name": "amm",
+            "network": "testnet",
+            "is_verified": true,
+            "note": null,
+            "added_at": "2026-01-01T00:00:00Z"
+        }))
+        .unwrap();
+        let items = [item];
+        for reference in ["6f9d2c1e-6d1b-4f39-9a7e-0b8f3f3a1c55", "CABC", "@alice/amm"] {
+            assert!(find_collection_item(&items, reference).is_some(), "{}", reference);
+        }
+        assert!(find_collection_item(&items, "@bob/amm").is_none());
+    }
+
+    #[test]
+    fn byte_counts_use_binary_units() {
+        assert_eq!(format_byte_count(512), "512 B");
+        assert_eq!(format_byte_count(1536), "1.5 KiB");
+        assert_eq!(format_byte_count(5 * 1024 * 1024), "5.0 MiB");
+    }
+
     #[test]
     fn extract_migration_id_returns_id_for_valid_payload() {
         let payload = json!({"id": "migration-123"});
@@ -1356,13 +2210,19 @@ pub fn incident_trigger(contract_id: &str, severity_str: &str) -> Result<()> {
 }
 
 pub async fn config_get(api_url: &str, contract_id: &str, environment: &str) -> Result<()> {
-    let client = reqwest::Client::new();
-    let url = format!("{}/api/contracts/{}/config?environment={}", api_url, contract_id, environment);
+    let contract_id = &resolve_contract_uuid(api_url, contract_id).await?;
+    let client = crate::http::client();
+    let url = format!(
+        "{}/api/contracts/{}/config?environment={}",
+        api_url,
+        contract_path(contract_id),
+        environment
+    );
 
     let response = client.get(&url).send().await.context("Failed to fetch configuration")?;
 
     if !response.status().is_success() {
-        anyhow::bail!("Failed to get config: {}", response.text().await.unwrap_or_default());
+        return Err(crate::api_errors::failure(response, "Failed to get config").await);
     }
 
     let config: serde_json::Value = response.json().await?;
@@ -1389,8 +2249,9 @@ pub async fn config_set(
     secrets_data: Option<&str>,
     created_by: &str,
 ) -> Result<()> {
-    let client = reqwest::Client::new();
-    let url = format!("{}/api/contracts/{}/config", api_url, contract_id);
+    let contract_id = &resolve_contract_uuid(api_url, contract_id).await?;
+    let client = crate::http::client();
+    let url = format!("{}/api/contracts/{}/config", api_url, contract_path(contract_id));
 
     let mut payload = json!({
         "environment": environment,
@@ -1408,7 +2269,7 @@ pub async fn config_set(
     let response = client.post(&url).json(&payload).send().await.context("Failed to set configuration")?;
 
     if !response.status().is_success() {
-        anyhow::bail!("Failed to set config: {}", response.text().await.unwrap_or_default());
+        return Err(crate::api_errors::failure(response, "Failed to set config").await);
     }
 
     let config: serde_json::Value = response.json().await?;
@@ -1422,13 +2283,19 @@ pub async fn config_set(
 }
 
 pub async fn config_history(api_url: &str, contract_id: &str, environment: &str) -> Result<()> {
-    let client = reqwest::Client::new();
-    let url = format!("{}/api/contracts/{}/config/history?environment={}", api_url, contract_id, environment);
+    let contract_id = &resolve_contract_uuid(api_url, contract_id).await?;
+    let client = crate::http::client();
+    let url = format!(
+        "{}/api/contracts/{}/config/history?environment={}",
+        api_url,
+        contract_path(contract_id),
+        environment
+    );
 
     let response = client.get(&url).send().await.context("Failed to fetch configuration history")?;
 
     if !response.status().is_success() {
-        anyhow::bail!("Failed to get config history: {}", response.text().await.unwrap_or_default());
+        return Err(crate::api_errors::failure(response, "Failed to get config history").await);
     }
 
     let configs: Vec<serde_json::Value> = response.json().await?;
@@ -1462,8 +2329,14 @@ pub async fn config_rollback(
     version: i32,
     created_by: &str,
 ) -> Result<()> {
-    let client = reqwest::Client::new();
-    let url = format!("{}/api/contracts/{}/config/rollback?environment={}", api_url, contract_id, environment);
+    let contract_id = &resolve_contract_uuid(api_url, contract_id).await?;
+    let client = crate::http::client();
+    let url = format!(
+        "{}/api/contracts/{}/config/rollback?environment={}",
+        api_url,
+        contract_path(contract_id),
+        environment
+    );
 
     let payload = json!({
         "roll_back_to_version": version,
@@ -1475,7 +2348,7 @@ pub async fn config_rollback(
     let response = client.post(&url).json(&payload).send().await.context("Failed to rollback configuration")?;
 
     if !response.status().is_success() {
-        anyhow::bail!("Failed to rollback config: {}", response.text().await.unwrap_or_default());
+        return Err(crate::api_errors::failure(response, "Failed to rollback config").await);
     }
 
     let config: serde_json::Value = response.json().await?;
@@ -1521,10 +2394,11 @@ pub async fn scan_deps(
     dependencies: &str,
     fail_on_high: bool,
 ) -> Result<()> {
+    let contract_id = &resolve_contract_uuid(api_url, contract_id).await?;
     println!("\n{}", "Scanning Dependencies...".bold().cyan());
 
-    let client = reqwest::Client::new();
-    let url = format!("{}/api/contracts/{}/scan", api_url, contract_id);
+    let client = crate::http::client();
+    let url = format!("{}/api/contracts/{}/scan", api_url, contract_path(contract_id));
 
     // Parse dependencies
     let mut deps_list = Vec::new();
@@ -1553,7 +2427,7 @@ pub async fn scan_deps(
         .context("Failed to run dependency scan")?;
 
     if !response.status().is_success() {
-        anyhow::bail!("Scan failed: {}", response.text().await.unwrap_or_default());
+        return Err(crate::api_errors::failure(response, "Scan failed").await);
     }
 
     let report: serde_json::Value = response.json().await?;
@@ -1774,8 +2648,8 @@ pub async fn validate_call(
     params: &[String],
     strict: bool,
 ) -> Result<()> {
-    let client = reqwest::Client::new();
-    let url = format!("{}/api/contracts/{}/validate-call", api_url, contract_id);
+    let client = crate::http::client();
+    let url = format!("{}/api/contracts/{}/validate-call", api_url, contract_path(contract_id));
 
     let body = json!({
         "method_name": method_name,
@@ -1881,10 +2755,10 @@ pub async fn generate_bindings(
     language: &str,
     output: Option<&str>,
 ) -> Result<()> {
-    let client = reqwest::Client::new();
+    let client = crate::http::client();
     let url = format!(
         "{}/api/contracts/{}/bindings?language={}",
-        api_url, contract_id, language
+        api_url, contract_path(contract_id), language
     );
 
     log::debug!("GET {}", url);
@@ -1923,8 +2797,8 @@ pub async fn generate_bindings(
 
 /// List functions available on a contract
 pub async fn list_functions(api_url: &str, contract_id: &str) -> Result<()> {
-    let client = reqwest::Client::new();
-    let url = format!("{}/api/contracts/{}/functions", api_url, contract_id);
+    let client = crate::http::client();
+    let url = format!("{}/api/contracts/{}/functions", api_url, contract_path(contract_id));
 
     log::debug!("GET {}", url);
 
@@ -2010,11 +2884,18 @@ pub async fn list_functions(api_url: &str, contract_id: &str) -> Result<()> {
 
 /// Fetch contract info from the registry. `id` is the contract's registry UUID.
 /// Use --network to get network-specific config (e.g. mainnet, testnet).
-pub async fn info(api_url: &str, id: &str, network: crate::config::Network) -> Result<()> {
+/// With `abi_syntax` the ABI is rendered in that syntax in place of the raw JSON.
+pub async fn info(
+    api_url: &str,
+    id: &str,
+    version: Option<&str>,
+    abi_syntax: Option<&str>,
+    network: crate::config::Network,
+) -> Result<()> {
     println!("\n{}", "Fetching contract information...".bold().cyan());
     
-    let url = format!("{}/api/contracts/{}", api_url.trim_end_matches('/'), id);
-    let client = reqwest::Client::new();
+    let url = format!("{}/api/contracts/{}", api_url.trim_end_matches('/'), contract_path(id));
+    let client = crate::http::client();
     let response = client
         .get(&url)
         .query(&[("network", network.to_string())])
@@ -2023,124 +2904,1740 @@ pub async fn info(api_url: &str, id: &str, network: crate::config::Network) -> R
 
     if response.status().is_success() {
         let contract_info: serde_json::Value = response.json().await?;
-        println!("\n{}", serde_json::to_string_pretty(&contract_info)?);
+        print_alias_notice(&contract_info);
+        println!(
+            "\n{}: {}",
+            "Name".bold(),
+            crate::conversions::contract_display_name(&contract_info)
+        );
+        println!(
+            "{}: {}",
+            "License".bold(),
+            contract_info["license"]
+                .as_str()
+                .map(|l| l.bright_magenta())
+                .unwrap_or_else(|| "unspecified".yellow())
+        );
+        let interfaces: Vec<&str> = contract_info["implements"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|id| id.as_str())
+            .collect();
+        if !interfaces.is_empty() {
+            println!("{}: {}", "Implements".bold(), interfaces.join(", ").bright_green());
+        }
+        println!(
+            "{}: {}",
+            "Rating".bold(),
+            crate::conversions::format_rating(&contract_info["rating"])
+        );
+        if let Some(stars) = contract_info["star_count"].as_i64() {
+            println!("{}: {}", "Stars".bold(), stars);
+        }
+        if let Some(quality) = contract_info["quality_score"].as_f64() {
+            println!("{}: {:.1}/100", "Quality".bold(), quality);
+        }
+        if contract_info["moderation_status"].as_str() == Some("quarantined") {
+            println!(
+                "{} {}",
+                "⚠".yellow(),
+                "Quarantined by the registry's moderators while abuse reports are reviewed"
+                    .yellow()
+            );
+        }
+        for (label, field) in [
+            ("Repository", "repository_url"),
+            ("Homepage", "homepage"),
+            ("Docs", "documentation_url"),
+        ] {
+            if let Some(url) = contract_info[field].as_str() {
+                println!("{}: {}", label.bold(), url.underline());
+            }
+        }
+        match fetch_forks(&client, api_url, id).await {
+            Ok(forks) => print_fork_summary(&forks),
+            Err(err) => log::debug!("fork tree unavailable: {}", err),
+        }
+        // Audit reports are optional provenance; don't fail `info` over them
+        match fetch_audits(&client, api_url, id).await {
+            Ok(audits) if audits.is_empty() => {
+                println!("{}: {}", "Audits".bold(), "none attached".yellow());
+            }
+            Ok(audits) => {
+                println!("{}: {}", "Audits".bold(), audits.len());
+                for audit in &audits {
+                    print_audit(audit);
+                }
+            }
+            Err(err) => log::debug!("audit reports unavailable: {}", err),
+        }
+        match fetch_test_results(&client, api_url, id, version).await {
+            Ok(results) => match results.versions.first() {
+                Some(run) => println!(
+                    "{}: {} {}",
+                    "Tests".bold(),
+                    test_badge(&run.summary),
+                    format!("(v{})", run.version).bright_black()
+                ),
+                None => println!("{}: {}", "Tests".bold(), "none attached".yellow()),
+            },
+            Err(err) => log::debug!("test results unavailable: {}", err),
+        }
+        if let Some(version) = version {
+            let changelog = fetch_changelog(api_url, id, Some(version)).await?;
+            match changelog.entries.first() {
+                Some(entry) => print_changelog_entry(entry, true),
+                None => anyhow::bail!("Version {} not found", version),
+            }
+        }
+        match abi_syntax {
+            Some(syntax) => print_abi(&client, api_url, id, version, syntax).await?,
+            None => println!("\n{}", serde_json::to_string_pretty(&contract_info)?),
+        }
     } else {
-        anyhow::bail!("Failed to fetch contract info: {}", response.status());
+        return Err(crate::api_errors::failure(response, "Failed to fetch contract info").await);
     }
 
     Ok(())
 }
 
-pub fn doc(contract_path: &str, output: &str) -> Result<()> {
-    println!("\n{}", "Generating contract documentation...".bold().cyan());
-    
-    let content = format!(
-        r#"# Contract Documentation
+/// Show a publisher's contracts with download totals and verification status
+pub async fn publisher(api_url: &str, id: &str, page: i64, limit: i64, json: bool) -> Result<()> {
+    let url = format!(
+        "{}/api/publishers/{}/contracts",
+        api_url.trim_end_matches('/'),
+        id
+    );
+    let response = crate::http::client()
+        .get(&url)
+        .query(&[("page", page), ("limit", limit)])
+        .send()
+        .await
+        .context("Failed to fetch publisher contracts")?;
 
-## Contract Path
-{}
+    if !response.status().is_success() {
+        return Err(
+            crate::api_errors::failure(response, "Failed to fetch publisher contracts").await,
+        );
+    }
+    let listing: shared::PublisherContractsResponse = response.json().await?;
 
-## Generated
-{}
+    if json {
+        println!("{}", serde_json::to_string_pretty(&listing)?);
+        return Ok(());
+    }
 
-*This is a placeholder. Full documentation generation coming soon.*
-"#,
-        contract_path,
-        chrono::Utc::now().to_rfc3339()
+    let summary = &listing.summary;
+    println!("\n{} {}", "Publisher".bold().cyan(), listing.publisher_id);
+    println!("{}", "=".repeat(80).cyan());
+    if let Some(account) = &listing.verified_account {
+        println!("  {} verified Stellar account {}", "✓".green().bold(), account);
+    }
+    println!(
+        "  Contracts: {} | Verified: {} ({:.1}%) | Downloads: {}",
+        summary.total_contracts.to_string().bold(),
+        summary.verified_contracts,
+        summary.verified_percentage,
+        summary.total_downloads.to_string().bold()
     );
 
-    fs::write(output, content)?;
-    println!("{} Documentation saved to: {}", "✓".green(), output);
+    if listing.contracts.is_empty() {
+        println!("\n{}", "No contracts published yet.".yellow());
+        return Ok(());
+    }
+
+    for entry in &listing.contracts {
+        let contract = &entry.contract;
+        let name = match &contract.namespace {
+            Some(ns) => format!("@{}/{}", ns, contract.name),
+            None => contract.name.clone(),
+        };
+        println!("\n{} {}", "●".green(), name.bold());
+        println!(
+            "  {} | Network: {} | Downloads: {}",
+            if contract.is_verified {
+                "✓ Verified".green()
+            } else {
+                "○ Unverified".yellow()
+            },
+            contract.network.to_string().bright_blue(),
+            entry.downloads
+        );
+    }
 
+    println!("\n{}", "=".repeat(80).cyan());
+    println!("Page {} of {}\n", listing.page, listing.pages.max(1));
     Ok(())
 }
 
-/// Load ABI JSON string from WASM (soroban bindings) or from a JSON file
-fn load_abi_json(contract_path: &str) -> Result<String> {
-    if contract_path.to_lowercase().ends_with(".wasm") {
-        let output = std::process::Command::new("soroban")
-            .args(["contract", "bindings", "json", "--wasm", contract_path])
-            .output()
-            .map_err(|e| anyhow::anyhow!("Failed to run soroban bindings: {}", e))?;
-        if !output.status.success() {
-            anyhow::bail!(
-                "soroban bindings failed: {}",
-                String::from_utf8_lossy(&output.stderr)
-            );
-        }
-        Ok(String::from_utf8(output.stdout)?)
-    } else {
-        Ok(fs::read_to_string(contract_path)?)
+fn searches_url(api_url: &str) -> String {
+    format!("{}/api/searches", api_url.trim_end_matches('/'))
+}
+
+/// `search --save <name>`: store the query so new matching contracts are reported
+#[allow(clippy::too_many_arguments)]
+pub async fn save_search(
+    api_url: &str,
+    name: &str,
+    query: &str,
+    network: Option<&str>,
+    category: Option<&str>,
+    webhook: Option<&str>,
+    email: Option<&str>,
+) -> Result<()> {
+    let response = crate::http::client()
+        .post(searches_url(api_url))
+        .bearer_auth(crate::http::auth_token()?)
+        .json(&json!({
+            "name": name,
+            "query": query,
+            "network": network,
+            "category": category,
+            "webhook_url": webhook,
+            "email": email,
+        }))
+        .send()
+        .await
+        .context("Failed to save search")?;
+    if !response.status().is_success() {
+        return Err(crate::api_errors::failure(response, "Failed to save search").await);
+    }
+
+    let mut channels = vec!["`searches list`"];
+    if webhook.is_some() {
+        channels.push("webhook");
     }
+    if email.is_some() {
+        channels.push("email");
+    }
+    println!(
+        "{} Saved search {} — new matches will be reported via {}",
+        "✓".green(),
+        name.bold(),
+        channels.join(", ")
+    );
+    Ok(())
 }
 
-/// Generate markdown from ContractABI
-fn abi_to_markdown(abi: &contract_abi::ContractABI) -> String {
-    let mut md = format!("# {}\n\n", abi.name);
-    if let Some(v) = &abi.version {
-        md.push_str(&format!("Version: {}\n\n", v));
+/// `searches list`: saved searches with their most recent matches
+pub async fn searches_list(api_url: &str, json: bool) -> Result<()> {
+    let client = crate::http::client();
+    let token = crate::http::auth_token()?;
+    let response = client
+        .get(searches_url(api_url))
+        .bearer_auth(&token)
+        .send()
+        .await
+        .context("Failed to fetch saved searches")?;
+    if !response.status().is_success() {
+        return Err(crate::api_errors::failure(response, "Failed to fetch saved searches").await);
     }
-    md.push_str("## Functions\n\n");
-    for func in abi.public_functions() {
-        md.push_str(&format!("### `{}`\n\n", func.name));
-        if let Some(doc) = &func.doc {
-            md.push_str(&format!("{}\n\n", doc));
+    let searches: Vec<shared::SavedSearch> = response.json().await?;
+
+    let mut listing = Vec::with_capacity(searches.len());
+    for search in searches {
+        let matches: Vec<shared::SavedSearchMatch> = client
+            .get(format!("{}/{}/matches", searches_url(api_url), search.name))
+            .query(&[("limit", 5)])
+            .bearer_auth(&token)
+            .send()
+            .await
+            .context("Failed to fetch saved search matches")?
+            .json()
+            .await
+            .unwrap_or_default();
+        listing.push((search, matches));
+    }
+
+    if json {
+        let body: Vec<_> = listing
+            .iter()
+            .map(|(search, matches)| json!({ "search": search, "recent_matches": matches }))
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&body)?);
+        return Ok(());
+    }
+
+    if listing.is_empty() {
+        println!(
+            "\n{}",
+            "No saved searches. Create one with `search <query> --save <name>`.".yellow()
+        );
+        return Ok(());
+    }
+
+    println!("\n{}", "Saved Searches".bold().cyan());
+    println!("{}", "=".repeat(80).cyan());
+    for (search, matches) in &listing {
+        let mut filters = vec![format!("\"{}\"", search.query)];
+        if let Some(network) = &search.network {
+            filters.push(format!("network: {}", network));
         }
-        md.push_str("**Parameters:**\n");
-        if func.params.is_empty() {
-            md.push_str("- None\n");
-        } else {
-            for p in &func.params {
-                md.push_str(&format!("- `{}`: `{}`\n", p.name, p.param_type.display_name()));
-            }
+        if let Some(category) = &search.category {
+            filters.push(format!("category: {}", category));
         }
-        md.push_str(&format!("\n**Returns:** `{}`\n\n", func.return_type.display_name()));
-    }
-    if !abi.errors.is_empty() {
-        md.push_str("## Errors\n\n");
-        for e in &abi.errors {
-            md.push_str(&format!("- **{}** (code {}): {}\n", e.name, e.code, e.doc.as_deref().unwrap_or("")));
+        println!("\n{} {}  {}", "●".green(), search.name.bold(), filters.join(" | "));
+        if matches.is_empty() {
+            println!("  {}", "No matches yet".bright_black());
+        }
+        for m in matches {
+            println!(
+                "  {} {} ({})",
+                "→".bright_blue(),
+                m.contract_name,
+                m.matched_at.format("%Y-%m-%d %H:%M")
+            );
         }
     }
-    md
-}
-
-/// Generate self-contained HTML with Swagger UI and inline OpenAPI spec (JSON)
-fn openapi_to_html(spec_json: &str, title: &str) -> String {
-    let spec_escaped = spec_json.replace("</script>", "<\\/script>");
-    format!(
-        r#"<!DOCTYPE html>
-<html>
-<head>
-  <meta charset="UTF-8">
-  <title>{} - API Docs</title>
-  <link rel="stylesheet" href="https://unpkg.com/swagger-ui-dist@5/swagger-ui.css">
-</head>
-<body>
-  <div id="swagger-ui"></div>
-  <script type="application/json" id="openapi-spec">{}</script>
-  <script src="https://unpkg.com/swagger-ui-dist@5/swagger-ui-bundle.js"></script>
-  <script>
-    (function() {{
-      var el = document.getElementById('openapi-spec');
-      try {{
-        var spec = JSON.parse(el.textContent);
-        SwaggerUIBundle({{ spec: spec, dom_id: '#swagger-ui' }});
-      }} catch (e) {{
-        document.getElementById('swagger-ui').innerHTML = '<p>Failed to load spec: ' + e.message + '</p>';
-      }}
-    }})();
-  </script>
-</body>
-</html>
-"#,
-        title,
-        spec_escaped
-    )
+    println!();
+    Ok(())
 }
 
-pub fn openapi(contract_path: &str, output: &str, format: &str) -> Result<()> {
-    println!("\n{}", "Generating OpenAPI documentation...".bold().cyan());
+/// `audits attach <contract> ...`: record a formal audit report against the contract
+pub async fn audits_attach(
+    api_url: &str,
+    contract: &str,
+    mut attestation: shared::CreateAuditAttestationRequest,
+    report_file: Option<&str>,
+) -> Result<()> {
+    if let Some(path) = report_file {
+        attestation.report_sha256 = Some(
+            crate::io_utils::compute_sha256_streaming(Path::new(path))
+                .with_context(|| format!("Failed to hash report {}", path))?,
+        );
+    }
+    let response = crate::http::client()
+        .post(format!(
+            "{}/api/contracts/{}/audits",
+            api_url.trim_end_matches('/'),
+            contract_path(contract)
+        ))
+        .bearer_auth(crate::http::auth_token()?)
+        .json(&attestation)
+        .send()
+        .await
+        .context("Failed to attach audit report")?;
+    if !response.status().is_success() {
+        return Err(crate::api_errors::failure(response, "Failed to attach audit report").await);
+    }
+    println!(
+        "{} Attached {} audit report to {}",
+        "✓".green(),
+        attestation.auditor_name.bold(),
+        contract.bold()
+    );
+    Ok(())
+}
+
+async fn fetch_audits(
+    client: &reqwest::Client,
+    api_url: &str,
+    contract: &str,
+) -> Result<Vec<shared::AuditAttestation>> {
+    let response = client
+        .get(format!(
+            "{}/api/contracts/{}/audits",
+            api_url.trim_end_matches('/'),
+            contract_path(contract)
+        ))
+        .send()
+        .await
+        .context("Failed to fetch audit reports")?;
+    if !response.status().is_success() {
+        return Err(crate::api_errors::failure(response, "Failed to fetch audit reports").await);
+    }
+    Ok(response.json().await?)
+}
+
+/// Attached test results, newest version first, or only `version`'s
+async fn fetch_forks(
+    client: &reqwest::Client,
+    api_url: &str,
+    contract: &str,
+) -> Result<shared::ContractForksResponse> {
+    let response = client
+        .get(format!(
+            "{}/api/contracts/{}/forks",
+            api_url.trim_end_matches('/'),
+            contract_path(contract)
+        ))
+        .send()
+        .await
+        .context("Failed to fetch forks")?;
+    if !response.status().is_success() {
+        return Err(crate::api_errors::failure(response, "Failed to fetch forks").await);
+    }
+    Ok(response.json().await?)
+}
+
+/// `info --abi`: the contract's ABI as Rust- or TypeScript-like signatures, or raw JSON
+async fn print_abi(
+    client: &reqwest::Client,
+    api_url: &str,
+    contract: &str,
+    version: Option<&str>,
+    syntax: &str,
+) -> Result<()> {
+    let mut request = client.get(format!(
+        "{}/api/contracts/{}/abi",
+        api_url.trim_end_matches('/'),
+        contract_path(contract)
+    ));
+    if let Some(version) = version {
+        request = request.query(&[("version", version)]);
+    }
+    let response = request
+        .send()
+        .await
+        .context("Failed to fetch contract ABI")?;
+    if !response.status().is_success() {
+        return Err(crate::api_errors::failure(response, "Failed to fetch contract ABI").await);
+    }
+    let body: serde_json::Value = response.json().await?;
+    if syntax == "json" {
+        println!("\n{}", serde_json::to_string_pretty(&body["abi"])?);
+        return Ok(());
+    }
+    let syntax: shared::AbiSyntax = syntax.parse().map_err(anyhow::Error::msg)?;
+    println!("\n{}", "ABI".bold().cyan());
+    print!("{}", shared::format_abi(&body["abi"], syntax));
+    Ok(())
+}
+
+/// `Forked from: @alice/amm` and the number of forks, when there are any
+fn print_fork_summary(forks: &shared::ContractForksResponse) {
+    if let Some(upstream) = forks.lineage.first() {
+        println!(
+            "{}: {} {}",
+            "Forked from".bold(),
+            upstream.name.bright_cyan(),
+            format!("({})", upstream.contract_id).bright_black()
+        );
+    }
+    if forks.total_forks > 0 {
+        println!(
+            "{}: {} direct, {} total",
+            "Forks".bold(),
+            forks.fork_count,
+            forks.total_forks
+        );
+    }
+}
+
+async fn fetch_test_results(
+    client: &reqwest::Client,
+    api_url: &str,
+    contract: &str,
+    version: Option<&str>,
+) -> Result<shared::ContractTestResultsResponse> {
+    let mut request = client.get(format!(
+        "{}/api/contracts/{}/tests",
+        api_url.trim_end_matches('/'),
+        contract_path(contract)
+    ));
+    if let Some(version) = version {
+        request = request.query(&[("version", version)]);
+    }
+    let response = request
+        .send()
+        .await
+        .context("Failed to fetch test results")?;
+    if !response.status().is_success() {
+        return Err(crate::api_errors::failure(response, "Failed to fetch test results").await);
+    }
+    Ok(response.json().await?)
+}
+
+/// `tests: 124 passed`, green when nothing failed
+fn test_badge(summary: &shared::TestSummary) -> colored::ColoredString {
+    let badge = format!("tests: {}", summary.label());
+    if summary.failed == 0 {
+        badge.green()
+    } else {
+        badge.red()
+    }
+}
+
+fn print_audit(audit: &shared::AuditAttestation) {
+    println!(
+        "  {} {} · {} · {}",
+        "●".green(),
+        audit.auditor_name.bold(),
+        audit.audited_at,
+        audit.version.as_deref().unwrap_or("all versions")
+    );
+    println!(
+        "    Findings: {} critical, {} high, {} medium, {} low",
+        audit.critical_findings.to_string().red(),
+        audit.high_findings.to_string().yellow(),
+        audit.medium_findings,
+        audit.low_findings
+    );
+    println!("    Scope: {}", audit.scope);
+    if let Some(summary) = &audit.findings_summary {
+        println!("    Summary: {}", summary);
+    }
+    println!("    Report: {}", audit.report_url.bright_blue());
+    if let Some(digest) = &audit.report_sha256 {
+        println!("    SHA-256: {}", digest.bright_black());
+    }
+}
+
+/// `audits list <contract>`
+pub async fn audits_list(api_url: &str, contract: &str, json: bool) -> Result<()> {
+    let audits = fetch_audits(&crate::http::client(), api_url, contract).await?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&audits)?);
+        return Ok(());
+    }
+    println!("\n{} {}", "Audit reports for".bold().cyan(), contract.bold());
+    println!("{}", "=".repeat(80).cyan());
+    if audits.is_empty() {
+        println!("\n{}", "No audit reports attached.".yellow());
+    }
+    for audit in &audits {
+        println!();
+        print_audit(audit);
+    }
+    println!();
+    Ok(())
+}
+
+fn tokens_url(api_url: &str) -> String {
+    format!("{}/api/tokens", api_url.trim_end_matches('/'))
+}
+
+fn print_issued_key(issued: &shared::IssuedApiKey) {
+    println!("\n  {}", issued.token.bold());
+    println!(
+        "\n  {}",
+        "Store this key now; it will not be shown again.".yellow()
+    );
+    println!("  ID: {}", issued.key.id);
+    println!("  Scopes: {}", issued.key.scopes.join(", "));
+    match issued.key.expires_at {
+        Some(expires_at) => println!("  Expires: {}", expires_at.format("%Y-%m-%d %H:%M UTC")),
+        None => println!("  Expires: never"),
+    }
+}
+
+async fn send_token_request(
+    request: reqwest::RequestBuilder,
+    action: &str,
+) -> Result<reqwest::Response> {
+    let response = request
+        .bearer_auth(crate::http::auth_token()?)
+        .send()
+        .await
+        .with_context(|| format!("Failed to {}", action))?;
+    if !response.status().is_success() {
+        return Err(crate::api_errors::failure(response, format!("Failed to {}", action)).await);
+    }
+    Ok(response)
+}
+
+async fn fetch_changelog(
+    api_url: &str,
+    contract: &str,
+    version: Option<&str>,
+) -> Result<shared::ContractChangelogResponse> {
+    let mut request = crate::http::client().get(format!(
+        "{}/api/contracts/{}/changelog",
+        api_url.trim_end_matches('/'),
+        contract_path(contract)
+    ));
+    if let Some(version) = version {
+        request = request.query(&[("version", version)]);
+    }
+    let response = request
+        .send()
+        .await
+        .context("Failed to fetch changelog")?;
+    if !response.status().is_success() {
+        return Err(crate::api_errors::failure(response, "Failed to fetch changelog").await);
+    }
+    Ok(response.json().await?)
+}
+
+/// `changelog <contract> [--version]`: generated release history, newest first
+pub async fn changelog(
+    api_url: &str,
+    contract: &str,
+    version: Option<&str>,
+    json: bool,
+) -> Result<()> {
+    let changelog = fetch_changelog(api_url, contract, version).await?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&changelog)?);
+        return Ok(());
+    }
+
+    println!("\n{} {}", "Changelog for".bold().cyan(), contract.bold());
+    println!("{}", "=".repeat(80).cyan());
+    if changelog.entries.is_empty() {
+        println!("\n{}", "No versions published yet.".yellow());
+    }
+    for entry in &changelog.entries {
+        print_changelog_entry(entry, false);
+    }
+    println!();
+    Ok(())
+}
+
+/// One changelog entry; `full_notes` prints the publisher's release notes in
+/// full instead of just their first line
+fn print_changelog_entry(entry: &shared::ContractChangelogEntry, full_notes: bool) {
+    let marker = if entry.breaking {
+        "BREAKING".red().bold().to_string()
+    } else {
+        String::new()
+    };
+    println!(
+        "\n{} {} ({}) {}",
+        "●".green(),
+        entry.version.bold(),
+        entry.created_at.format("%Y-%m-%d"),
+        marker
+    );
+    match &entry.previous_version {
+        Some(previous) => println!("  Compared with {}", previous),
+        None => println!("  Initial release"),
+    }
+    for change in &entry.changes {
+        let bullet = if change.severity == "breaking" {
+            "!".red().to_string()
+        } else {
+            "+".green().to_string()
+        };
+        println!("  {} {}", bullet, change.message);
+    }
+    for change in &entry.metadata_changes {
+        println!(
+            "  {} {}: {} → {}",
+            "~".yellow(),
+            change.field,
+            change.old.as_deref().unwrap_or("(none)"),
+            change.new.as_deref().unwrap_or("(none)")
+        );
+    }
+    if let Some(size) = entry.wasm_size_bytes {
+        let delta = entry
+            .wasm_size_delta
+            .map(|d| {
+                let sign = if d >= 0 { "+" } else { "-" };
+                format!(" ({}{})", sign, format_byte_count(d.abs()))
+            })
+            .unwrap_or_default();
+        println!("  WASM size: {}{}", format_byte_count(size), delta);
+    }
+    match &entry.release_notes {
+        Some(notes) if full_notes => {
+            println!("\n  {}", "Release notes".bold());
+            for line in notes.lines() {
+                println!("  {}", line);
+            }
+        }
+        Some(notes) => println!("  Notes: {}", notes.lines().next().unwrap_or_default()),
+        None => {}
+    }
+}
+
+/// `used / limit` for one quota, with the limit shown as "unlimited" when unset
+fn quota_line(metric: &shared::QuotaMetric, format: fn(i64) -> String) -> String {
+    match metric.limit {
+        Some(limit) => {
+            let line = format!("{} / {}", format(metric.used), format(limit));
+            if metric.used >= limit {
+                line.red().to_string()
+            } else if metric.used * 10 >= limit * 8 {
+                line.yellow().to_string()
+            } else {
+                line
+            }
+        }
+        None => format!("{} / unlimited", format(metric.used)),
+    }
+}
+
+fn format_byte_count(bytes: i64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+/// `usage [--org <name>]`: quota usage of your publisher account or an organization
+pub async fn usage(api_url: &str, org: Option<&str>, json: bool) -> Result<()> {
+    let base = api_url.trim_end_matches('/');
+    let url = match org {
+        Some(name) => format!("{}/api/orgs/{}/usage", base, name),
+        None => format!("{}/api/usage", base),
+    };
+    let usage: shared::QuotaUsage =
+        send_token_request(crate::http::client().get(url), "fetch quota usage")
+            .await?
+            .json()
+            .await?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&usage)?);
+        return Ok(());
+    }
+    println!(
+        "\n{} {} {}",
+        "Quota usage for".bold().cyan(),
+        usage.account_type,
+        usage.account.bold()
+    );
+    println!("{}", "=".repeat(80).cyan());
+    println!(
+        "  Contracts:        {}",
+        quota_line(&usage.contracts, |n| n.to_string())
+    );
+    println!(
+        "  Artifact storage: {}",
+        quota_line(&usage.artifact_bytes, format_byte_count)
+    );
+    println!(
+        "  Versions (24h):   {}",
+        quota_line(&usage.versions_today, |n| n.to_string())
+    );
+    println!();
+    Ok(())
+}
+
+/// `tokens create --name <name> --scope <scope>...`
+pub async fn tokens_create(
+    api_url: &str,
+    name: &str,
+    scopes: &[String],
+    expires_in_days: Option<i64>,
+) -> Result<()> {
+    let request = crate::http::client().post(tokens_url(api_url)).json(&json!({
+        "name": name,
+        "scopes": scopes,
+        "expires_in_days": expires_in_days,
+    }));
+    let issued: shared::IssuedApiKey = send_token_request(request, "create API key")
+        .await?
+        .json()
+        .await?;
+    println!("{} Created API key {}", "✓".green(), name.bold());
+    print_issued_key(&issued);
+    Ok(())
+}
+
+/// `tokens list`: active keys, most recent first
+pub async fn tokens_list(api_url: &str, json: bool) -> Result<()> {
+    let request = crate::http::client().get(tokens_url(api_url));
+    let keys: Vec<shared::ApiKey> = send_token_request(request, "list API keys")
+        .await?
+        .json()
+        .await?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&keys)?);
+        return Ok(());
+    }
+    if keys.is_empty() {
+        println!("\n{}", "No active API keys.".yellow());
+        return Ok(());
+    }
+    println!("\n{}", "API Keys".bold().cyan());
+    println!("{}", "=".repeat(80).cyan());
+    for key in &keys {
+        println!("\n{} {} ({}…)", "●".green(), key.name.bold(), key.prefix);
+        println!("  ID: {}", key.id);
+        println!("  Scopes: {}", key.scopes.join(", "));
+        println!(
+            "  Last used: {}",
+            key.last_used_at
+                .map(|t| t.format("%Y-%m-%d %H:%M UTC").to_string())
+                .unwrap_or_else(|| "never".to_string())
+        );
+        if let Some(expires_at) = key.expires_at {
+            println!("  Expires: {}", expires_at.format("%Y-%m-%d %H:%M UTC"));
+        }
+    }
+    println!();
+    Ok(())
+}
+
+/// `tokens rotate <id>`: mint a replacement, keeping the old key alive for the grace period
+pub async fn tokens_rotate(api_url: &str, id: &str, grace_hours: i64) -> Result<()> {
+    let request = crate::http::client()
+        .post(format!("{}/{}/rotate", tokens_url(api_url), id))
+        .json(&json!({ "grace_period_hours": grace_hours }));
+    let issued: shared::IssuedApiKey = send_token_request(request, "rotate API key")
+        .await?
+        .json()
+        .await?;
+    println!("{} Rotated API key {}", "✓".green(), issued.key.name.bold());
+    print_issued_key(&issued);
+    if let Some(old_expiry) = issued.previous_key_expires_at {
+        println!(
+            "  Previous key stops working at {}",
+            old_expiry.format("%Y-%m-%d %H:%M UTC")
+        );
+    }
+    Ok(())
+}
+
+/// `tokens revoke <id>`
+pub async fn tokens_revoke(api_url: &str, id: &str) -> Result<()> {
+    let request = crate::http::client().delete(format!("{}/{}", tokens_url(api_url), id));
+    send_token_request(request, "revoke API key").await?;
+    println!("{} Revoked API key {}", "✓".green(), id);
+    Ok(())
+}
+
+/// `tokens usage`: API calls over the last 30 days by endpoint and key
+pub async fn tokens_usage(api_url: &str, token: Option<&str>, json: bool) -> Result<()> {
+    let mut request =
+        crate::http::client().get(format!("{}/api/me/usage", api_url.trim_end_matches('/')));
+    if let Some(token) = token {
+        request = request.query(&[("token", token)]);
+    }
+    let report: shared::ApiUsageReport = send_token_request(request, "fetch API usage")
+        .await?
+        .json()
+        .await?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+
+    println!(
+        "\n{} (since {})",
+        "API Usage".bold().cyan(),
+        report.since.format("%Y-%m-%d")
+    );
+    println!("{}", "=".repeat(80).cyan());
+    println!(
+        "  Requests: {}  Errors: {} ({:.1}%)  Rate limited: {}",
+        report.requests,
+        report.errors,
+        report.error_rate * 100.0,
+        report.rate_limited
+    );
+    if report.requests == 0 {
+        println!("\n{}", "No authenticated requests in this period.".yellow());
+        return Ok(());
+    }
+
+    println!("\n{}", "By endpoint".bold());
+    for endpoint in &report.endpoints {
+        let window = match (endpoint.peak_window_usage, endpoint.rate_limit) {
+            (Some(peak), Some(limit)) => format!("  peak {}/{} per window", peak, limit),
+            _ => String::new(),
+        };
+        let rate_limited = if endpoint.rate_limited > 0 {
+            format!("  {} rate limited", endpoint.rate_limited)
+                .red()
+                .to_string()
+        } else {
+            String::new()
+        };
+        println!(
+            "  {:<50} {:>7}  {:>5.1}% errors{}{}",
+            endpoint.endpoint,
+            endpoint.requests,
+            endpoint.error_rate * 100.0,
+            window,
+            rate_limited
+        );
+    }
+
+    println!("\n{}", "By token".bold());
+    for usage in &report.tokens {
+        let label = match (&usage.name, &usage.prefix, usage.api_key_id) {
+            (Some(name), Some(prefix), _) => format!("{} ({}…)", name, prefix),
+            (_, _, Some(id)) => id.to_string(),
+            (_, _, None) => "session tokens".to_string(),
+        };
+        println!(
+            "  {:<50} {:>7}  {} errors  {} rate limited",
+            label, usage.requests, usage.errors, usage.rate_limited
+        );
+    }
+    println!();
+    Ok(())
+}
+
+/// `review add <contract> --rating <1-5>`
+pub async fn review_add(
+    api_url: &str,
+    contract: &str,
+    rating: i16,
+    comment: Option<&str>,
+) -> Result<()> {
+    let response = crate::http::client()
+        .post(format!(
+            "{}/api/contracts/{}/reviews",
+            api_url.trim_end_matches('/'),
+            contract_path(contract)
+        ))
+        .bearer_auth(crate::http::auth_token()?)
+        .json(&json!({ "rating": rating, "comment": comment }))
+        .send()
+        .await
+        .context("Failed to submit review")?;
+    if !response.status().is_success() {
+        return Err(crate::api_errors::failure(response, "Failed to submit review").await);
+    }
+    println!(
+        "{} Reviewed {} with {}",
+        "✓".green(),
+        contract.bold(),
+        crate::conversions::stars(rating).yellow()
+    );
+    Ok(())
+}
+
+/// `review list <contract>`: newest visible reviews and the average rating
+pub async fn review_list(
+    api_url: &str,
+    contract: &str,
+    page: i64,
+    limit: i64,
+    json: bool,
+) -> Result<()> {
+    let response = crate::http::client()
+        .get(format!(
+            "{}/api/contracts/{}/reviews",
+            api_url.trim_end_matches('/'),
+            contract_path(contract)
+        ))
+        .query(&[("page", page), ("limit", limit)])
+        .send()
+        .await
+        .context("Failed to fetch reviews")?;
+    if !response.status().is_success() {
+        return Err(crate::api_errors::failure(response, "Failed to fetch reviews").await);
+    }
+    let listing: shared::ContractReviewsResponse = response.json().await?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&listing)?);
+        return Ok(());
+    }
+
+    println!(
+        "\n{} {}  {}",
+        "Reviews for".bold().cyan(),
+        contract.bold(),
+        crate::conversions::format_rating(&serde_json::to_value(&listing.rating)?)
+    );
+    println!("{}", "=".repeat(80).cyan());
+    if listing.reviews.is_empty() {
+        println!("\n{}", "No reviews yet.".yellow());
+        return Ok(());
+    }
+    for review in &listing.reviews {
+        println!(
+            "\n{}  #{} {} · {}",
+            crate::conversions::stars(review.rating).yellow(),
+            review.id,
+            review.reviewer_address.bright_black(),
+            review.created_at.format("%Y-%m-%d")
+        );
+        if let Some(comment) = &review.comment {
+            println!("  {}", comment);
+        }
+    }
+    println!("\n{}", "=".repeat(80).cyan());
+    println!("Page {} of {}\n", listing.page, listing.pages.max(1));
+    Ok(())
+}
+
+/// `review flag <contract> <review-id> --reason <text>`: report abuse to moderators
+pub async fn review_flag(
+    api_url: &str,
+    contract: &str,
+    review_id: i32,
+    reason: &str,
+) -> Result<()> {
+    let response = crate::http::client()
+        .post(format!(
+            "{}/api/contracts/{}/reviews/{}/flag",
+            api_url.trim_end_matches('/'),
+            contract_path(contract),
+            review_id
+        ))
+        .bearer_auth(crate::http::auth_token()?)
+        .json(&json!({ "reason": reason }))
+        .send()
+        .await
+        .context("Failed to report review")?;
+    if !response.status().is_success() {
+        return Err(crate::api_errors::failure(response, "Failed to report review").await);
+    }
+    println!("{} Review #{} reported to moderators", "✓".green(), review_id);
+    Ok(())
+}
+
+/// `searches delete <name>`
+pub async fn searches_delete(api_url: &str, name: &str) -> Result<()> {
+    let response = crate::http::client()
+        .delete(format!("{}/{}", searches_url(api_url), name))
+        .bearer_auth(crate::http::auth_token()?)
+        .send()
+        .await
+        .context("Failed to delete saved search")?;
+    if !response.status().is_success() {
+        return Err(crate::api_errors::failure(response, "Failed to delete saved search").await);
+    }
+    println!("{} Deleted saved search {}", "✓".green(), name.bold());
+    Ok(())
+}
+
+/// `star <contract>`: star it, or take the star back with `--remove`
+pub async fn star(api_url: &str, contract: &str, remove: bool) -> Result<()> {
+    let url = format!(
+        "{}/api/contracts/{}/star",
+        api_url.trim_end_matches('/'),
+        contract_path(contract)
+    );
+    let client = crate::http::client();
+    let request = if remove {
+        client.delete(url)
+    } else {
+        client.put(url)
+    };
+    let response = request
+        .bearer_auth(crate::http::auth_token()?)
+        .send()
+        .await
+        .context("Failed to update star")?;
+    if !response.status().is_success() {
+        return Err(crate::api_errors::failure(response, "Failed to update star").await);
+    }
+    let status: shared::StarStatus = response.json().await?;
+    println!(
+        "{} {} {} ({} star{})",
+        "✓".green(),
+        if status.starred {
+            "Starred"
+        } else {
+            "Unstarred"
+        },
+        contract.bold(),
+        status.star_count,
+        if status.star_count == 1 { "" } else { "s" }
+    );
+    Ok(())
+}
+
+/// `stars`: contracts the signed-in account starred, from any machine
+pub async fn stars_list(api_url: &str, json: bool) -> Result<()> {
+    let response = crate::http::client()
+        .get(format!("{}/api/me/stars", api_url.trim_end_matches('/')))
+        .bearer_auth(crate::http::auth_token()?)
+        .send()
+        .await
+        .context("Failed to fetch starred contracts")?;
+    if !response.status().is_success() {
+        return Err(
+            crate::api_errors::failure(response, "Failed to fetch starred contracts").await,
+        );
+    }
+    let stars: Vec<shared::StarredContract> = response.json().await?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&stars)?);
+        return Ok(());
+    }
+    if stars.is_empty() {
+        println!(
+            "\n{}",
+            "No starred contracts. Star one with `star <contract>`.".yellow()
+        );
+        return Ok(());
+    }
+
+    println!("\n{}", "Starred Contracts".bold().cyan());
+    println!("{}", "=".repeat(80).cyan());
+    for star in &stars {
+        let contract = &star.contract;
+        let name = shared::ContractName {
+            namespace: contract.namespace.clone(),
+            name: contract.name.clone(),
+        };
+        println!(
+            "\n{} {} {}",
+            "★".yellow(),
+            name.to_string().bold(),
+            if contract.is_verified {
+                "✓".green()
+            } else {
+                "".normal()
+            }
+        );
+        println!(
+            "  {} | {} | starred {}",
+            contract.contract_id.bright_black(),
+            contract.network.to_string().bright_blue(),
+            star.starred_at.format("%Y-%m-%d")
+        );
+    }
+    println!();
+    Ok(())
+}
+
+/// `account export`: download everything the registry holds about the account
+pub async fn account_export(api_url: &str, output: &str) -> Result<()> {
+    let response = crate::http::client()
+        .get(format!("{}/api/me/export", api_url.trim_end_matches('/')))
+        .bearer_auth(crate::http::auth_token()?)
+        .send()
+        .await
+        .context("Failed to export account data")?;
+    if !response.status().is_success() {
+        return Err(crate::api_errors::failure(response, "Failed to export account data").await);
+    }
+    let body = response.bytes().await?;
+    let export: shared::AccountExport =
+        serde_json::from_slice(&body).context("Unexpected account export format")?;
+    std::fs::write(output, &body).with_context(|| format!("Failed to write {}", output))?;
+
+    println!("{}", "✓ Account data exported".green().bold());
+    println!("  {}: {}", "Output".bold(), output);
+    println!("  {}: {}", "Account".bold(), export.address);
+    println!("  {}: {}", "API keys".bold(), export.api_keys.len());
+    println!("  {}: {}", "Reviews".bold(), export.reviews.len());
+    println!("  {}: {}", "Stars".bold(), export.stars.len());
+    println!(
+        "  {}: {}",
+        "Saved searches".bold(),
+        export.saved_searches.len()
+    );
+    println!("  {}: {}", "Collections".bold(), export.collections.len());
+    println!(
+        "  {}: {}",
+        "Organizations".bold(),
+        export.organizations.len()
+    );
+    println!(
+        "  {}: {}",
+        "Audit entries".bold(),
+        export.audit_entries.len()
+    );
+    if let Some(deletion) = &export.pending_deletion {
+        println!(
+            "  {}: {}",
+            "Deletion scheduled".bold(),
+            deletion
+                .scheduled_for
+                .format("%Y-%m-%d %H:%M UTC")
+                .to_string()
+                .red()
+        );
+    }
+    Ok(())
+}
+
+/// Ask before scheduling deletion; `--yes` answers for scripts
+fn confirm_account_deletion() -> Result<()> {
+    if crate::interactive::assume_yes() {
+        return Ok(());
+    }
+    crate::interactive::require_terminal("Confirming account deletion", "pass --yes to confirm")?;
+    println!(
+        "{}",
+        format!(
+            "Your reviews, stars, saved searches, collections and API keys will be deleted \
+             in {} days unless you cancel with `account delete --cancel`.",
+            shared::ACCOUNT_DELETION_GRACE_DAYS
+        )
+        .yellow()
+    );
+    print!("Type 'delete' to confirm: ");
+    std::io::Write::flush(&mut std::io::stdout())?;
+    let mut answer = String::new();
+    std::io::stdin().read_line(&mut answer)?;
+    if answer.trim() != "delete" {
+        anyhow::bail!("Account deletion not confirmed");
+    }
+    Ok(())
+}
+
+/// `account delete`: schedule the account for deletion, or cancel with --cancel
+pub async fn account_delete(api_url: &str, cancel: bool) -> Result<()> {
+    if !cancel {
+        confirm_account_deletion()?;
+    }
+    let base = api_url.trim_end_matches('/');
+    let url = if cancel {
+        format!("{}/api/me/deletion", base)
+    } else {
+        format!("{}/api/me", base)
+    };
+    let response = crate::http::client()
+        .delete(url)
+        .bearer_auth(crate::http::auth_token()?)
+        .send()
+        .await
+        .context("Failed to update account deletion")?;
+    if !response.status().is_success() {
+        let message = if cancel {
+            "Failed to cancel account deletion"
+        } else {
+            "Failed to delete account"
+        };
+        return Err(crate::api_errors::failure(response, message).await);
+    }
+
+    if cancel {
+        println!("{} Account deletion cancelled", "✓".green());
+        return Ok(());
+    }
+    let deletion: shared::AccountDeletion = response.json().await?;
+    println!(
+        "{} Account {} scheduled for deletion on {}",
+        "✓".green(),
+        deletion.address.bold(),
+        deletion.scheduled_for.format("%Y-%m-%d %H:%M UTC")
+    );
+    println!(
+        "  Run `account export` to keep a copy of your data, or `account delete --cancel` to keep the account."
+    );
+    Ok(())
+}
+
+fn collections_url(api_url: &str) -> String {
+    format!("{}/api/collections", api_url.trim_end_matches('/'))
+}
+
+async fn fetch_collection(api_url: &str, id: &str) -> Result<shared::CollectionDetail> {
+    let response = crate::http::client()
+        .get(format!("{}/{}", collections_url(api_url), id))
+        .send()
+        .await
+        .context("Failed to fetch collection")?;
+    if !response.status().is_success() {
+        return Err(crate::api_errors::failure(response, "Failed to fetch collection").await);
+    }
+    Ok(response.json().await?)
+}
+
+/// Match `reference` (registry UUID, on-chain ID or `@namespace/name`) to an item
+fn find_collection_item<'a>(
+    items: &'a [shared::CollectionItem],
+    reference: &str,
+) -> Option<&'a shared::CollectionItem> {
+    items.iter().find(|item| {
+        item.id.to_string() == reference
+            || item.contract_id == reference
+            || shared::ContractName {
+                namespace: item.namespace.clone(),
+                name: item.name.clone(),
+            }
+            .to_string()
+                == reference
+    })
+}
+
+fn print_collection(detail: &shared::CollectionDetail) {
+    let collection = &detail.collection;
+    let visibility = if collection.is_public {
+        "public".green()
+    } else {
+        "private".yellow()
+    };
+    println!("\n{} ({})", collection.name.bold().cyan(), visibility);
+    println!("{}", "=".repeat(80).cyan());
+    println!("  {}: {}", "ID".bold(), collection.id);
+    println!("  {}: {}", "Owner".bold(), collection.owner_address);
+    if let Some(description) = &collection.description {
+        println!("  {}", description);
+    }
+    if detail.items.is_empty() {
+        println!("\n  {}", "No contracts yet".bright_black());
+    }
+    for (i, item) in detail.items.iter().enumerate() {
+        let name = shared::ContractName {
+            namespace: item.namespace.clone(),
+            name: item.name.clone(),
+        };
+        println!(
+            "\n{}. {} {}",
+            i + 1,
+            name.to_string().bold(),
+            if item.is_verified {
+                "✓".green()
+            } else {
+                "".normal()
+            }
+        );
+        println!(
+            "   {} | {}",
+            item.contract_id.bright_black(),
+            item.network.to_string().bright_blue()
+        );
+        if let Some(note) = &item.note {
+            println!("   {}", note.italic());
+        }
+    }
+    println!();
+}
+
+/// `collection create <name>`
+pub async fn collection_create(
+    api_url: &str,
+    name: &str,
+    description: Option<&str>,
+    public: bool,
+) -> Result<()> {
+    let response = crate::http::client()
+        .post(collections_url(api_url))
+        .bearer_auth(crate::http::auth_token()?)
+        .json(&shared::CreateCollectionRequest {
+            name: name.to_string(),
+            description: description.map(str::to_string),
+            is_public: public,
+        })
+        .send()
+        .await
+        .context("Failed to create collection")?;
+    if !response.status().is_success() {
+        return Err(crate::api_errors::failure(response, "Failed to create collection").await);
+    }
+    let detail: shared::CollectionDetail = response.json().await?;
+    println!(
+        "{} Created {} collection {} ({})",
+        "✓".green(),
+        if public { "public" } else { "private" },
+        detail.collection.name.bold(),
+        detail.collection.id
+    );
+    Ok(())
+}
+
+/// `collection list`: public collections and your own
+pub async fn collection_list(api_url: &str, owner: Option<&str>, json: bool) -> Result<()> {
+    let mut request = crate::http::client().get(collections_url(api_url));
+    if let Some(owner) = owner {
+        request = request.query(&[("owner", owner)]);
+    }
+    let response = request.send().await.context("Failed to list collections")?;
+    if !response.status().is_success() {
+        return Err(crate::api_errors::failure(response, "Failed to list collections").await);
+    }
+    let collections: Vec<shared::Collection> = response.json().await?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&collections)?);
+        return Ok(());
+    }
+    if collections.is_empty() {
+        println!(
+            "\n{}",
+            "No collections. Create one with `collection create <name>`.".yellow()
+        );
+        return Ok(());
+    }
+
+    println!("\n{}", "Collections".bold().cyan());
+    println!("{}", "=".repeat(80).cyan());
+    for collection in &collections {
+        println!(
+            "\n{} {}  {} contract{}{}",
+            "●".green(),
+            collection.name.bold(),
+            collection.item_count,
+            if collection.item_count == 1 { "" } else { "s" },
+            if collection.is_public {
+                String::new()
+            } else {
+                format!(" {}", "(private)".yellow())
+            }
+        );
+        println!(
+            "  {} | {}",
+            collection.id.to_string().bright_black(),
+            collection.owner_address.bright_black()
+        );
+    }
+    println!();
+    Ok(())
+}
+
+/// `collection show <id>`
+pub async fn collection_show(api_url: &str, id: &str, json: bool) -> Result<()> {
+    let detail = fetch_collection(api_url, id).await?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&detail)?);
+        return Ok(());
+    }
+    print_collection(&detail);
+    Ok(())
+}
+
+/// `collection update <id>`: rename, describe or change visibility
+pub async fn collection_update(
+    api_url: &str,
+    id: &str,
+    update: shared::UpdateCollectionRequest,
+) -> Result<()> {
+    let response = crate::http::client()
+        .patch(format!("{}/{}", collections_url(api_url), id))
+        .bearer_auth(crate::http::auth_token()?)
+        .json(&update)
+        .send()
+        .await
+        .context("Failed to update collection")?;
+    if !response.status().is_success() {
+        return Err(crate::api_errors::failure(response, "Failed to update collection").await);
+    }
+    let detail: shared::CollectionDetail = response.json().await?;
+    println!(
+        "{} Updated collection {}",
+        "✓".green(),
+        detail.collection.name.bold()
+    );
+    Ok(())
+}
+
+/// `collection delete <id>`
+pub async fn collection_delete(api_url: &str, id: &str) -> Result<()> {
+    let response = crate::http::client()
+        .delete(format!("{}/{}", collections_url(api_url), id))
+        .bearer_auth(crate::http::auth_token()?)
+        .send()
+        .await
+        .context("Failed to delete collection")?;
+    if !response.status().is_success() {
+        return Err(crate::api_errors::failure(response, "Failed to delete collection").await);
+    }
+    println!("{} Deleted collection {}", "✓".green(), id.bold());
+    Ok(())
+}
+
+/// `collection add <id> <contract>`
+pub async fn collection_add(
+    api_url: &str,
+    id: &str,
+    contract: &str,
+    note: Option<&str>,
+    position: Option<u64>,
+) -> Result<()> {
+    // Shown 1-based, sent 0-based
+    let position = position
+        .map(|position| i32::try_from(position.saturating_sub(1)))
+        .transpose()
+        .context("--position is too large")?;
+    let response = crate::http::client()
+        .post(format!("{}/{}/items", collections_url(api_url), id))
+        .bearer_auth(crate::http::auth_token()?)
+        .json(&shared::AddCollectionItemRequest {
+            contract: contract.to_string(),
+            note: note.map(str::to_string),
+            position,
+        })
+        .send()
+        .await
+        .context("Failed to add contract to collection")?;
+    if !response.status().is_success() {
+        return Err(
+            crate::api_errors::failure(response, "Failed to add contract to collection").await,
+        );
+    }
+    let detail: shared::CollectionDetail = response.json().await?;
+    println!(
+        "{} Added {} to {} ({} contracts)",
+        "✓".green(),
+        contract.bold(),
+        detail.collection.name.bold(),
+        detail.items.len()
+    );
+    Ok(())
+}
+
+/// `collection remove <id> <contract>`
+pub async fn collection_remove(api_url: &str, id: &str, contract: &str) -> Result<()> {
+    let response = crate::http::client()
+        .delete(format!(
+            "{}/{}/items/{}",
+            collections_url(api_url),
+            id,
+            contract_path(contract)
+        ))
+        .bearer_auth(crate::http::auth_token()?)
+        .send()
+        .await
+        .context("Failed to remove contract from collection")?;
+    if !response.status().is_success() {
+        return Err(crate::api_errors::failure(
+            response,
+            "Failed to remove contract from collection",
+        )
+        .await);
+    }
+    println!(
+        "{} Removed {} from the collection",
+        "✓".green(),
+        contract.bold()
+    );
+    Ok(())
+}
+
+/// `collection reorder <id> <contract>...`: the listed contracts move to the
+/// front in that order, the rest follow as before
+pub async fn collection_reorder(api_url: &str, id: &str, contracts: &[String]) -> Result<()> {
+    let detail = fetch_collection(api_url, id).await?;
+    let order = contracts
+        .iter()
+        .map(|reference| {
+            find_collection_item(&detail.items, reference)
+                .map(|item| item.id)
+                .ok_or_else(|| {
+                    crate::cli_error::CliError::Usage(format!(
+                        "{} is not in collection {}",
+                        reference, detail.collection.name
+                    ))
+                    .into()
+                })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let response = crate::http::client()
+        .put(format!("{}/{}/items", collections_url(api_url), id))
+        .bearer_auth(crate::http::auth_token()?)
+        .json(&shared::ReorderCollectionRequest { contracts: order })
+        .send()
+        .await
+        .context("Failed to reorder collection")?;
+    if !response.status().is_success() {
+        return Err(crate::api_errors::failure(response, "Failed to reorder collection").await);
+    }
+    let detail: shared::CollectionDetail = response.json().await?;
+    print_collection(&detail);
+    Ok(())
+}
+
+/// `install --collection <id>`: install every contract in the collection, in order
+pub async fn install_collection(
+    api_url: &str,
+    id: &str,
+    output_dir: &str,
+    require_provenance: bool,
+    update: bool,
+) -> Result<()> {
+    let detail = fetch_collection(api_url, id).await?;
+    if detail.items.is_empty() {
+        println!(
+            "{}",
+            format!("Collection {} has no contracts", detail.collection.name).yellow()
+        );
+        return Ok(());
+    }
+    println!(
+        "\n{} {} ({} contracts)",
+        "Installing collection".bold().cyan(),
+        detail.collection.name.bold(),
+        detail.items.len()
+    );
+
+    // Resolve every item, and check every version the lockfile pins, in one
+    // round trip, so a missing contract fails the install before anything is written
+    let client = crate::http::client();
+    let mut lock = crate::lockfile::Lockfile::load(Path::new(crate::lockfile::LOCKFILE_NAME))?;
+    let queries: Vec<String> = detail
+        .items
+        .iter()
+        .map(|item| match lock.get(&item.contract_id) {
+            Some(entry) if !update => format!("{}@{}", item.contract_id, entry.version),
+            _ => item.contract_id.clone(),
+        })
+        .collect();
+    let results = lookup_contracts(&client, api_url, &queries).await?;
+    let unresolved: Vec<&str> = results
+        .iter()
+        .filter_map(|result| result.error.as_deref())
+        .collect();
+    if !unresolved.is_empty() {
+        anyhow::bail!(
+            "Cannot install collection {}:\n  {}",
+            detail.collection.name,
+            unresolved.join("\n  ")
+        );
+    }
+
+    for (item, result) in detail.items.iter().zip(results) {
+        println!("\n{} {}", "Installing".bold().cyan(), item.name.bold());
+        let pinned = pin_lookup(&lock, &item.contract_id, result, update)?;
+        install_pinned(
+            &client,
+            api_url,
+            &mut lock,
+            &item.contract_id,
+            pinned,
+            output_dir,
+            require_provenance,
+        )
+        .await
+        .with_context(|| format!("Failed to install {} from the collection", item.name))?;
+    }
+    Ok(())
+}
+
+/// The lockfile entry for a batch lookup result: the existing one when the
+/// lookup confirmed its version, otherwise the resolved version's artifacts
+fn pin_lookup(
+    lock: &crate::lockfile::Lockfile,
+    contract_id: &str,
+    result: shared::BatchContractLookupResult,
+    update: bool,
+) -> Result<(crate::lockfile::LockedContract, bool)> {
+    let version = result
+        .version
+        .with_context(|| format!("{} has no published versions", contract_id))?;
+    if let Some(entry) = lock.get(contract_id) {
+        if !update && entry.version == version.version {
+            return Ok((entry.clone(), true));
+        }
+    }
+    if let Some(support) = &result.support {
+        crate::support::warn(contract_id, support);
+    }
+    let entry = crate::lockfile::LockedContract {
+        id: contract_id.to_string(),
+        version: version.version,
+        artifacts: result
+            .artifacts
+            .into_iter()
+            .map(|artifact| (artifact.kind, artifact.sha256))
+            .collect(),
+    };
+    Ok((entry, false))
+}
+
+pub fn doc(contract_path: &str, output: &str) -> Result<()> {
+    println!("\n{}", "Generating contract documentation...".bold().cyan());
+    
+    let content = format!(
+        r#"# Contract Documentation
+
+## Contract Path
+{}
+
+## Generated
+{}
+
+*This is a placeholder. Full documentation generation coming soon.*
+"#,
+        contract_path,
+        chrono::Utc::now().to_rfc3339()
+    );
+
+    fs::write(output, content)?;
+    println!("{} Documentation saved to: {}", "✓".green(), output);
+
+    Ok(())
+}
+
+/// Load ABI JSON string from WASM (soroban bindings) or from a JSON file
+pub fn load_abi_json(contract_path: &str) -> Result<String> {
+    if contract_path.to_lowercase().ends_with(".wasm") {
+        let output = std::process::Command::new("soroban")
+            .args(["contract", "bindings", "json", "--wasm", contract_path])
+            .output()
+            .map_err(|e| anyhow::anyhow!("Failed to run soroban bindings: {}", e))?;
+        if !output.status.success() {
+            anyhow::bail!(
+                "soroban bindings failed: {}",
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+        Ok(String::from_utf8(output.stdout)?)
+    } else {
+        Ok(fs::read_to_string(contract_path)?)
+    }
+}
+
+/// Generate markdown from ContractABI; `spec` is the raw ABI JSON, used for signatures
+fn abi_to_markdown(abi: &contract_abi::ContractABI, spec: &serde_json::Value) -> String {
+    let mut md = format!("# {}\n\n", abi.name);
+    if let Some(v) = &abi.version {
+        md.push_str(&format!("Version: {}\n\n", v));
+    }
+    md.push_str("## Functions\n\n");
+    for func in abi.public_functions() {
+        md.push_str(&format!("### `{}`\n\n", func.name));
+        if let Some(doc) = &func.doc {
+            md.push_str(&format!("{}\n\n", doc));
+        }
+        let entry = spec.as_array().into_iter().flatten().find(|entry| {
+            entry["type"] == "function" && entry["name"].as_str() == Some(func.name.as_str())
+        });
+        if let Some(entry) = entry {
+            md.push_str(&format!(
+                "```rust\n{}\n```\n\n",
+                shared::format_function(entry, shared::AbiSyntax::Rust)
+            ));
+        }
+        md.push_str("**Parameters:**\n");
+        if func.params.is_empty() {
+            md.push_str("- None\n");
+        } else {
+            for p in &func.params {
+                md.push_str(&format!("- `{}`: `{}`\n", p.name, p.param_type.display_name()));
+            }
+        }
+        md.push_str(&format!("\n**Returns:** `{}`\n\n", func.return_type.display_name()));
+    }
+    if !abi.errors.is_empty() {
+        md.push_str("## Errors\n\n");
+        for e in &abi.errors {
+            md.push_str(&format!("- **{}** (code {}): {}\n", e.name, e.code, e.doc.as_deref().unwrap_or("")));
+        }
+    }
+    md
+}
+
+/// Generate self-contained HTML with Swagger UI and inline OpenAPI spec (JSON)
+fn openapi_to_html(spec_json: &str, title: &str) -> String {
+    let spec_escaped = spec_json.replace("</script>", "<\\/script>");
+    format!(
+        r#"<!DOCTYPE html>
+<html>
+<head>
+  <meta charset="UTF-8">
+  <title>{} - API Docs</title>
+  <link rel="stylesheet" href="https://unpkg.com/swagger-ui-dist@5/swagger-ui.css">
+</head>
+<body>
+  <div id="swagger-ui"></div>
+  <script type="application/json" id="openapi-spec">{}</script>
+  <script src="https://unpkg.com/swagger-ui-dist@5/swagger-ui-bundle.js"></script>
+  <script>
+    (function() {{
+      var el = document.getElementById('openapi-spec');
+      try {{
+        var spec = JSON.parse(el.textContent);
+        SwaggerUIBundle({{ spec: spec, dom_id: '#swagger-ui' }});
+      }} catch (e) {{
+        document.getElementById('swagger-ui').innerHTML = '<p>Failed to load spec: ' + e.message + '</p>';
+      }}
+    }})();
+  </script>
+</body>
+</html>
+"#,
+        title,
+        spec_escaped
+    )
+}
+
+pub fn openapi(contract_path: &str, output: &str, format: &str) -> Result<()> {
+    println!("\n{}", "Generating OpenAPI documentation...".bold().cyan());
     let abi_json = load_abi_json(contract_path)?;
     let contract_name = std::path::Path::new(contract_path)
         .file_stem()
@@ -2157,7 +4654,10 @@ pub fn openapi(contract_path: &str, output: &str, format: &str) -> Result<()> {
             let doc = contract_abi::generate_openapi(&abi, Some("/invoke"));
             contract_abi::to_json(&doc).map_err(|e| anyhow::anyhow!("{}", e))?
         }
-        "markdown" | "md" => abi_to_markdown(&abi),
+        "markdown" | "md" => {
+            let spec: serde_json::Value = serde_json::from_str(&abi_json)?;
+            abi_to_markdown(&abi, &spec)
+        }
         "html" => {
             let doc = contract_abi::generate_openapi(&abi, Some("/invoke"));
             let json = contract_abi::to_json(&doc).map_err(|e| anyhow::anyhow!("{}", e))?;
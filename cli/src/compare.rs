@@ -0,0 +1,352 @@
+use anyhow::{Context, Result};
+use colored::Colorize;
+use serde_json::{json, Value};
+
+use crate::conversions::contract_path;
+
+/// Placeholder for data the registry has no record of
+const MISSING: &str = "—";
+
+/// Everything shown for one side of the comparison
+#[derive(Debug, Default)]
+struct ContractProfile {
+    contract: Value,
+    versions: Option<Vec<Value>>,
+    abi: Option<Value>,
+    audit: Option<Value>,
+    cost: Option<Value>,
+}
+
+async fn get_optional(client: &reqwest::Client, url: &str) -> Option<Value> {
+    let response = client.get(url).send().await.ok()?;
+    if !response.status().is_success() {
+        return None;
+    }
+    response.json().await.ok()
+}
+
+/// Fetch a contract and the optional extras; only the contract itself is required
+async fn fetch_profile(
+    client: &reqwest::Client,
+    api_url: &str,
+    reference: &str,
+    method: Option<&str>,
+) -> Result<ContractProfile> {
+    let base = api_url.trim_end_matches('/');
+    let response = client
+        .get(format!(
+            "{}/api/contracts/{}",
+            base,
+            contract_path(reference)
+        ))
+        .send()
+        .await
+        .with_context(|| format!("Failed to fetch contract {}", reference))?;
+    if !response.status().is_success() {
+        return Err(crate::api_errors::failure(
+            response,
+            format!("Failed to fetch contract {}", reference),
+        )
+        .await);
+    }
+    let contract: Value = response.json().await?;
+    let id = crate::conversions::as_str(&contract["id"], "id")?;
+    let contract_url = format!("{}/api/contracts/{}", base, id);
+
+    let versions_url = format!("{}/versions", contract_url);
+    let abi_url = format!("{}/abi", contract_url);
+    let audit_url = format!("{}/security-audit", contract_url);
+    let cost = async {
+        let method = method?;
+        let response = client
+            .post(format!("{}/cost-estimate", contract_url))
+            .json(&json!({ "method_name": method }))
+            .send()
+            .await
+            .ok()?;
+        if !response.status().is_success() {
+            return None;
+        }
+        response.json::<Value>().await.ok()
+    };
+    let (versions, abi, audit, cost) = tokio::join!(
+        get_optional(client, &versions_url),
+        get_optional(client, &abi_url),
+        get_optional(client, &audit_url),
+        cost,
+    );
+
+    Ok(ContractProfile {
+        contract,
+        versions: versions.and_then(|v| v.as_array().cloned()),
+        abi: abi.map(|mut body| body["abi"].take()),
+        audit,
+        cost,
+    })
+}
+
+fn text(value: &Value) -> String {
+    match value {
+        Value::String(s) if !s.is_empty() => s.clone(),
+        Value::Number(n) => n.to_string(),
+        _ => MISSING.to_string(),
+    }
+}
+
+fn interfaces(profile: &ContractProfile) -> Vec<String> {
+    profile.contract["implements"]
+        .as_array()
+        .map(|list| {
+            list.iter()
+                .filter_map(|i| i.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Exported functions in a contract spec, whichever of the two spec shapes it uses
+fn function_count(abi: &Value) -> Option<usize> {
+    if let Some(functions) = abi["functions"].as_array() {
+        return Some(functions.len());
+    }
+    abi.as_array().map(|entries| {
+        entries
+            .iter()
+            .filter(|e| e["type"] == "function" || e.get("function_v0").is_some())
+            .count()
+    })
+}
+
+fn format_bytes(bytes: usize) -> String {
+    if bytes >= 1024 {
+        format!("{:.1} KiB", bytes as f64 / 1024.0)
+    } else {
+        format!("{} B", bytes)
+    }
+}
+
+/// Labelled rows of the comparison, one value per contract
+fn comparison_rows(a: &ContractProfile, b: &ContractProfile) -> Vec<(&'static str, [String; 2])> {
+    let both = |f: &dyn Fn(&ContractProfile) -> String| [f(a), f(b)];
+    let mut rows = vec![
+        (
+            "Name",
+            both(&|p| crate::conversions::contract_display_name(&p.contract)),
+        ),
+        ("Network", both(&|p| text(&p.contract["network"]))),
+        (
+            "Verified",
+            both(&|p| match p.contract["is_verified"].as_bool() {
+                Some(true) => "yes".to_string(),
+                Some(false) => "no".to_string(),
+                None => MISSING.to_string(),
+            }),
+        ),
+        ("License", both(&|p| text(&p.contract["license"]))),
+        ("Category", both(&|p| text(&p.contract["category"]))),
+        (
+            "Interfaces",
+            both(&|p| {
+                let list = interfaces(p);
+                if list.is_empty() {
+                    MISSING.to_string()
+                } else {
+                    list.join(", ")
+                }
+            }),
+        ),
+        (
+            "Latest version",
+            both(&|p| {
+                p.versions
+                    .as_ref()
+                    .and_then(|v| v.first())
+                    .map(|v| text(&v["version"]))
+                    .unwrap_or_else(|| MISSING.to_string())
+            }),
+        ),
+        (
+            "Versions",
+            both(&|p| {
+                p.versions
+                    .as_ref()
+                    .map(|v| v.len().to_string())
+                    .unwrap_or_else(|| MISSING.to_string())
+            }),
+        ),
+        (
+            "ABI size",
+            both(&|p| {
+                p.abi
+                    .as_ref()
+                    .map(|abi| format_bytes(abi.to_string().len()))
+                    .unwrap_or_else(|| MISSING.to_string())
+            }),
+        ),
+        (
+            "Functions",
+            both(&|p| {
+                p.abi
+                    .as_ref()
+                    .and_then(function_count)
+                    .map(|n| n.to_string())
+                    .unwrap_or_else(|| MISSING.to_string())
+            }),
+        ),
+        ("Health score", both(&|p| text(&p.contract["health_score"]))),
+        ("Quality score", both(&|p| text(&p.contract["quality_score"]))),
+        (
+            "Security audit",
+            both(&|p| match &p.audit {
+                Some(audit) => format!(
+                    "{} ({})",
+                    text(&audit["audit"]["overall_score"]),
+                    text(&audit["audit"]["auditor"])
+                ),
+                None => "none on record".to_string(),
+            }),
+        ),
+    ];
+    if a.cost.is_some() || b.cost.is_some() {
+        rows.push((
+            "Est. cost",
+            both(&|p| {
+                p.cost
+                    .as_ref()
+                    .map(|c| format!("{} stroops", text(&c["total_stroops"])))
+                    .unwrap_or_else(|| MISSING.to_string())
+            }),
+        ));
+    }
+    rows.push(("Updated", both(&|p| text(&p.contract["updated_at"]))));
+    rows
+}
+
+/// `soroban-registry compare <a> <b> [--method <fn>] [--json]`
+pub async fn compare(
+    api_url: &str,
+    a: &str,
+    b: &str,
+    method: Option<&str>,
+    json: bool,
+) -> Result<()> {
+    let client = crate::http::client();
+    let (left, right) = tokio::join!(
+        fetch_profile(&client, api_url, a, method),
+        fetch_profile(&client, api_url, b, method),
+    );
+    let (left, right) = (left?, right?);
+    let rows = comparison_rows(&left, &right);
+
+    if json {
+        let table: serde_json::Map<String, Value> = rows
+            .iter()
+            .map(|(label, [l, r])| (label.to_string(), json!([l, r])))
+            .collect();
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&json!({ "contracts": [a, b], "comparison": table }))?
+        );
+        return Ok(());
+    }
+
+    let label_width = rows.iter().map(|(label, _)| label.len()).max().unwrap_or(0);
+    let column_width = rows
+        .iter()
+        .flat_map(|(_, values)| values.iter().map(|v| v.chars().count()))
+        .max()
+        .unwrap_or(0)
+        .clamp(12, 40);
+
+    println!("\n{}", "Contract Comparison".bold().cyan());
+    println!("{}", "=".repeat(label_width + 2 * column_width + 6).cyan());
+    for (label, [l, r]) in &rows {
+        let line = format!(
+            "{:<lw$}   {:<cw$}   {}",
+            label,
+            truncate(l, column_width),
+            truncate(r, column_width),
+            lw = label_width,
+            cw = column_width
+        );
+        // Rows where the two contracts differ are the ones worth reading
+        if l != r {
+            println!("{}", line.bold());
+        } else {
+            println!("{}", line.bright_black());
+        }
+    }
+    println!();
+
+    let (ia, ib) = (interfaces(&left), interfaces(&right));
+    let only_a: Vec<_> = ia.iter().filter(|i| !ib.contains(i)).cloned().collect();
+    let only_b: Vec<_> = ib.iter().filter(|i| !ia.contains(i)).cloned().collect();
+    if !only_a.is_empty() {
+        println!("  Only {} implements: {}", a.bold(), only_a.join(", "));
+    }
+    if !only_b.is_empty() {
+        println!("  Only {} implements: {}", b.bold(), only_b.join(", "));
+    }
+    Ok(())
+}
+
+fn truncate(value: &str, width: usize) -> String {
+    if value.chars().count() <= width {
+        value.to_string()
+    } else {
+        let cut: String = value.chars().take(width.saturating_sub(1)).collect();
+        format!("{}…", cut)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile(contract: Value) -> ContractProfile {
+        ContractProfile {
+            contract,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn rows_show_missing_data_and_optional_cost() {
+        let a = profile(
+            json!({"name": "amm", "namespace": "alice", "is_verified": true,
+                               "implements": ["sep-41"], "network": "mainnet"}),
+        );
+        let mut b = profile(json!({"name": "swap", "is_verified": false, "network": "mainnet"}));
+        b.versions = Some(vec![
+            json!({"version": "2.0.0"}),
+            json!({"version": "1.0.0"}),
+        ]);
+
+        let rows = comparison_rows(&a, &b);
+        let row = |label: &str| rows.iter().find(|(l, _)| *l == label).unwrap().1.clone();
+        assert_eq!(row("Name"), ["@alice/amm".to_string(), "swap".to_string()]);
+        assert_eq!(row("Verified"), ["yes".to_string(), "no".to_string()]);
+        assert_eq!(
+            row("Interfaces"),
+            ["sep-41".to_string(), MISSING.to_string()]
+        );
+        assert_eq!(
+            row("Latest version"),
+            [MISSING.to_string(), "2.0.0".to_string()]
+        );
+        assert!(rows.iter().all(|(l, _)| *l != "Est. cost"));
+    }
+
+    #[test]
+    fn counts_functions_in_either_spec_shape() {
+        assert_eq!(
+            function_count(&json!({"functions": [{"name": "swap"}]})),
+            Some(1)
+        );
+        assert_eq!(
+            function_count(&json!([{"type": "function"}, {"type": "struct"}])),
+            Some(1)
+        );
+        assert_eq!(function_count(&json!("opaque")), None);
+    }
+}
@@ -2,6 +2,7 @@
 
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fmt;
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -54,6 +55,45 @@ impl FromStr for Network {
 #[derive(Debug, Clone, Deserialize, Default)]
 struct ConfigFile {
     defaults: Option<DefaultsSection>,
+    http: Option<HttpSection>,
+    /// `[alias]`: user-defined commands, e.g. `vs = "search --verified-only"`
+    #[serde(default)]
+    alias: BTreeMap<String, AliasDefinition>,
+}
+
+/// An alias body: one string split on whitespace, or a list of arguments
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+enum AliasDefinition {
+    Line(String),
+    Args(Vec<String>),
+}
+
+impl AliasDefinition {
+    fn into_args(self) -> Vec<String> {
+        match self {
+            AliasDefinition::Line(line) => line.split_whitespace().map(str::to_string).collect(),
+            AliasDefinition::Args(args) => args,
+        }
+    }
+}
+
+/// `[http]`: network settings for corporate proxies and private registries
+#[derive(Debug, Clone, Deserialize, Default)]
+pub struct HttpSection {
+    /// Proxy for every request; without it HTTP_PROXY / HTTPS_PROXY / NO_PROXY apply
+    pub proxy: Option<String>,
+    /// Hosts that bypass `proxy`, in NO_PROXY syntax (defaults to NO_PROXY)
+    pub no_proxy: Option<String>,
+    /// PEM bundle of extra CA certificates to trust, e.g. a TLS-inspecting proxy's
+    pub ca_bundle: Option<PathBuf>,
+    /// PEM client certificate presented to registries that require mutual TLS
+    pub client_cert: Option<PathBuf>,
+    /// PEM private key for `client_cert`, when it is not in the same file
+    pub client_key: Option<PathBuf>,
+    /// Extra headers sent to the registry, e.g. for gateway authentication
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
 }
 
 #[derive(Debug, Clone, Deserialize, Default)]
@@ -61,6 +101,10 @@ struct DefaultsSection {
     network: Option<String>,
     api_base: Option<String>,
     timeout: Option<u64>,
+    /// Session token or API key attached to every request, e.g. for private registries
+    token: Option<String>,
+    /// Registry to use on a multi-tenant deployment; requests go to `<api_base>/t/<tenant>`
+    tenant: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -125,10 +169,111 @@ pub fn show_config() -> Result<()> {
         "defaults.timeout = {}",
         defaults.timeout.unwrap_or(DEFAULT_TIMEOUT_SECS)
     );
+    println!(
+        "defaults.token = {}",
+        if defaults.token.is_some() {
+            "<set>"
+        } else {
+            "<unset>"
+        }
+    );
+    println!(
+        "defaults.tenant = {}",
+        defaults.tenant.as_deref().unwrap_or("<unset>")
+    );
+
+    let http = http_settings()?;
+    println!(
+        "http.proxy = {}",
+        http.proxy
+            .as_deref()
+            .unwrap_or("<from HTTP_PROXY / HTTPS_PROXY>")
+    );
+    for (key, path) in [
+        ("ca_bundle", &http.ca_bundle),
+        ("client_cert", &http.client_cert),
+        ("client_key", &http.client_key),
+    ] {
+        match path {
+            Some(path) => println!("http.{} = {}", key, path.display()),
+            None => println!("http.{} = <unset>", key),
+        }
+    }
+    for name in http.headers.keys() {
+        println!("http.headers.{} = <set>", name);
+    }
 
     Ok(())
 }
 
+/// `[http]` from the config file; empty when there is none
+pub fn http_settings() -> Result<HttpSection> {
+    migrate_legacy_config()?;
+    match config_file_path() {
+        Some(path) if path.exists() => Ok(load_config_file(&path)?.http.unwrap_or_default()),
+        _ => Ok(HttpSection::default()),
+    }
+}
+
+/// Credential from `defaults.token`, used when `SOROBAN_REGISTRY_TOKEN` is unset.
+/// An unreadable config file is treated as having no token.
+pub fn profile_token() -> Option<String> {
+    load_defaults_section()
+        .ok()
+        .and_then(|defaults| defaults.token)
+        .map(|token| token.trim().to_string())
+        .filter(|token| !token.is_empty())
+}
+
+/// Tenant from `defaults.tenant`, used when neither `--tenant` nor
+/// `SOROBAN_REGISTRY_TENANT` is given
+pub fn profile_tenant() -> Option<String> {
+    load_defaults_section()
+        .ok()
+        .and_then(|defaults| defaults.tenant)
+        .map(|tenant| tenant.trim().to_string())
+        .filter(|tenant| !tenant.is_empty())
+}
+
+/// API base from `defaults.api_base`, used when neither `--api-url` nor
+/// `SOROBAN_REGISTRY_API_URL` is given
+pub fn profile_api_base() -> Option<String> {
+    load_defaults_section()
+        .ok()
+        .and_then(|defaults| defaults.api_base)
+        .map(|api_base| api_base.trim().to_string())
+        .filter(|api_base| !api_base.is_empty())
+}
+
+/// Aliases from `[alias]`, expanded to their arguments. An unreadable config
+/// file is treated as having none.
+pub fn aliases() -> BTreeMap<String, Vec<String>> {
+    migrate_legacy_config().ok();
+    match config_file_path() {
+        Some(path) if path.exists() => load_config_file(&path)
+            .map(|config| {
+                config
+                    .alias
+                    .into_iter()
+                    .map(|(name, definition)| (name, definition.into_args()))
+                    .collect()
+            })
+            .unwrap_or_default(),
+        _ => BTreeMap::new(),
+    }
+}
+
+/// API base for one tenant of a multi-tenant registry: `<api_base>/t/<tenant>`
+pub fn tenant_api_url(api_base: &str, tenant: &str) -> String {
+    let tenant = tenant.trim().trim_matches('/');
+    let api_base = api_base.trim_end_matches('/');
+    if tenant.is_empty() {
+        api_base.to_string()
+    } else {
+        format!("{}/t/{}", api_base, tenant)
+    }
+}
+
 pub fn edit_config() -> Result<()> {
     migrate_legacy_config()?;
     let path = config_file_path().context("Could not determine home directory")?;
@@ -147,6 +292,49 @@ pub fn edit_config() -> Result<()> {
     Ok(())
 }
 
+/// Settings chosen in `wizard setup`; `None` leaves the current value alone
+#[derive(Debug, Clone, Default)]
+pub struct DefaultsUpdate {
+    pub network: Option<String>,
+    pub api_base: Option<String>,
+    pub token: Option<String>,
+}
+
+/// Merge `update` into `[defaults]`, keeping every other key in the file
+pub fn save_defaults(update: &DefaultsUpdate) -> Result<PathBuf> {
+    migrate_legacy_config()?;
+    let path = config_file_path().context("Could not determine home directory")?;
+    save_defaults_to(&path, update)?;
+    Ok(path)
+}
+
+fn save_defaults_to(path: &Path, update: &DefaultsUpdate) -> Result<()> {
+    ensure_config_file_exists(path)?;
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read config file at {:?}", path))?;
+    let mut document: toml::Table =
+        toml::from_str(&content).with_context(|| "Failed to parse config file")?;
+    let defaults = document
+        .entry("defaults")
+        .or_insert_with(|| toml::Value::Table(toml::Table::new()))
+        .as_table_mut()
+        .context("`defaults` in the config file is not a table")?;
+
+    for (key, value) in [
+        ("network", &update.network),
+        ("api_base", &update.api_base),
+        ("token", &update.token),
+    ] {
+        if let Some(value) = value {
+            defaults.insert(key.to_string(), toml::Value::String(value.clone()));
+        }
+    }
+
+    fs::write(path, toml::to_string(&document)?)
+        .with_context(|| format!("Failed to write config to {:?}", path))?;
+    Ok(())
+}
+
 fn load_defaults_section() -> Result<DefaultsSection> {
     migrate_legacy_config()?;
     let path = match config_file_path() {
@@ -182,6 +370,21 @@ fn ensure_config_file_exists(path: &Path) -> Result<()> {
 network = "testnet"
 api_base = "http://localhost:3001"
 timeout = 30
+# token = "<session token or API key, required by private registries>"
+# tenant = "<registry slug, on deployments hosting several registries>"
+
+# [http]
+# proxy = "http://proxy.internal:3128"     # otherwise HTTP_PROXY / HTTPS_PROXY / NO_PROXY
+# ca_bundle = "/etc/ssl/certs/corporate-ca.pem"
+# client_cert = "/etc/soroban-registry/client.pem"   # mutual TLS
+# client_key = "/etc/soroban-registry/client.key"
+#
+# [http.headers]
+# X-Gateway-Token = "<token>"
+#
+# [alias]
+# vs = "search --verified-only"
+# pub-main = ["--network", "mainnet", "publish"]
 "#;
     fs::write(path, default_content)
         .with_context(|| format!("Failed to write default config to {:?}", path))?;
@@ -262,6 +465,8 @@ mod tests {
 network = "mainnet"
 api_base = "http://localhost:9000"
 timeout = 55
+token = "srk_test"
+tenant = "acme"
 "#,
         )
         .unwrap();
@@ -272,6 +477,98 @@ timeout = 55
         assert_eq!(defaults.network.as_deref(), Some("mainnet"));
         assert_eq!(defaults.api_base.as_deref(), Some("http://localhost:9000"));
         assert_eq!(defaults.timeout, Some(55));
+        assert_eq!(defaults.token.as_deref(), Some("srk_test"));
+        assert_eq!(defaults.tenant.as_deref(), Some("acme"));
+    }
+
+    #[test]
+    fn test_load_config_file_with_http_section() {
+        let dir = tempdir().unwrap();
+        let config_path = dir.path().join("config.toml");
+        fs::write(
+            &config_path,
+            r#"[http]
+proxy = "http://proxy.internal:3128"
+ca_bundle = "/etc/ssl/corporate.pem"
+
+[http.headers]
+X-Gateway-Token = "abc"
+"#,
+        )
+        .unwrap();
+
+        let http = load_config_file(&config_path).unwrap().http.unwrap();
+        assert_eq!(http.proxy.as_deref(), Some("http://proxy.internal:3128"));
+        assert_eq!(
+            http.ca_bundle.as_deref(),
+            Some(Path::new("/etc/ssl/corporate.pem"))
+        );
+        assert_eq!(http.client_cert, None);
+        assert_eq!(http.headers["X-Gateway-Token"], "abc");
+    }
+
+    #[test]
+    fn test_load_config_file_with_alias_section() {
+        let dir = tempdir().unwrap();
+        let config_path = dir.path().join("config.toml");
+        fs::write(
+            &config_path,
+            r#"[alias]
+vs = "search --verified-only"
+pub-main = ["--network", "mainnet", "publish"]
+"#,
+        )
+        .unwrap();
+
+        let aliases = load_config_file(&config_path).unwrap().alias;
+        assert_eq!(
+            aliases["vs"].clone().into_args(),
+            vec!["search", "--verified-only"]
+        );
+        assert_eq!(
+            aliases["pub-main"].clone().into_args(),
+            vec!["--network", "mainnet", "publish"]
+        );
+    }
+
+    #[test]
+    fn tenant_api_url_appends_the_tenant_path() {
+        assert_eq!(
+            tenant_api_url("http://localhost:3001/", "acme"),
+            "http://localhost:3001/t/acme"
+        );
+        assert_eq!(
+            tenant_api_url("http://localhost:3001", " "),
+            "http://localhost:3001"
+        );
+    }
+
+    #[test]
+    fn save_defaults_to_keeps_unrelated_keys() {
+        let dir = tempdir().unwrap();
+        let config_path = dir.path().join("config.toml");
+        fs::write(
+            &config_path,
+            "[defaults]\nnetwork = \"testnet\"\ntimeout = 55\ntenant = \"acme\"\n",
+        )
+        .unwrap();
+
+        let update = DefaultsUpdate {
+            network: Some("mainnet".to_string()),
+            api_base: Some("https://registry.example".to_string()),
+            token: None,
+        };
+        save_defaults_to(&config_path, &update).unwrap();
+
+        let defaults = load_config_file(&config_path).unwrap().defaults.unwrap();
+        assert_eq!(defaults.network.as_deref(), Some("mainnet"));
+        assert_eq!(
+            defaults.api_base.as_deref(),
+            Some("https://registry.example")
+        );
+        assert_eq!(defaults.timeout, Some(55));
+        assert_eq!(defaults.tenant.as_deref(), Some("acme"));
+        assert_eq!(defaults.token, None);
     }
 
     #[test]
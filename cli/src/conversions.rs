@@ -84,6 +84,158 @@ pub fn string_to_str(s: &String) -> &str {
     s.as_str()
 }
 
+/// Percent-encodes a contract reference for use as a single URL path segment,
+/// so namespaced names like `@alice/token` survive as one segment.
+pub fn contract_path(reference: &str) -> String {
+    let mut out = String::with_capacity(reference.len());
+    for byte in reference.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') {
+            out.push(byte as char);
+        } else {
+            out.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    out
+}
+
+/// Registry UUID of a contract reference (UUID, on-chain ID or `@namespace/name`),
+/// for the endpoints that only accept UUIDs.
+pub async fn resolve_contract_uuid(api_url: &str, reference: &str) -> Result<String> {
+    if uuid::Uuid::parse_str(reference).is_ok() {
+        return Ok(reference.to_string());
+    }
+    let response = crate::http::client()
+        .get(format!(
+            "{}/api/contracts/{}",
+            api_url.trim_end_matches('/'),
+            contract_path(reference)
+        ))
+        .send()
+        .await
+        .with_context(|| format!("Failed to fetch contract {}", reference))?;
+    if !response.status().is_success() {
+        return Err(crate::api_errors::failure(
+            response,
+            format!("Failed to fetch contract {}", reference),
+        )
+        .await);
+    }
+    let contract: Value = response.json().await?;
+    as_str(&contract["id"], "id")
+}
+
+/// Display name of a contract JSON object, including its `@namespace/` prefix.
+pub fn contract_display_name(contract: &Value) -> String {
+    let name = contract["name"].as_str().unwrap_or("Unknown");
+    match contract["namespace"].as_str() {
+        Some(ns) => format!("@{}/{}", ns, name),
+        None => name.to_string(),
+    }
+}
+
+/// Render a search highlight fragment for the terminal, emphasising the
+/// `<mark>`-delimited matches.
+pub fn render_highlight(fragment: &str) -> String {
+    use colored::Colorize;
+
+    let mut out = String::new();
+    let mut rest = fragment;
+    while let Some(start) = rest.find(shared::HIGHLIGHT_START) {
+        out.push_str(&rest[..start]);
+        rest = &rest[start + shared::HIGHLIGHT_START.len()..];
+        let end = rest.find(shared::HIGHLIGHT_END).unwrap_or(rest.len());
+        out.push_str(&rest[..end].yellow().bold().to_string());
+        rest = rest[end..].strip_prefix(shared::HIGHLIGHT_END).unwrap_or_default();
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Five-star bar for a whole-star rating, e.g. `★★★☆☆`
+pub fn stars(rating: i16) -> String {
+    let filled = rating.clamp(0, 5) as usize;
+    format!("{}{}", "★".repeat(filled), "☆".repeat(5 - filled))
+}
+
+/// Summarise a `{ average, count }` rating object; absent means unreviewed
+pub fn format_rating(rating: &Value) -> String {
+    match (rating["average"].as_f64(), rating["count"].as_i64()) {
+        (Some(average), Some(count)) if count > 0 => format!(
+            "{} {:.1} ({} review{})",
+            stars(average.round() as i16),
+            average,
+            count,
+            if count == 1 { "" } else { "s" }
+        ),
+        _ => "no reviews yet".to_string(),
+    }
+}
+
+/// "page 2 of 14 (273 results)" from a paginated listing; `None` when the
+/// server sent no page metadata
+pub fn page_summary(listing: &Value) -> Option<String> {
+    let page = listing["page"].as_i64()?;
+    let pages = listing["pages"].as_i64()?.max(1);
+    let total = listing["total"].as_i64()?;
+    Some(format!(
+        "page {} of {} ({} result{})",
+        page,
+        pages,
+        total,
+        if total == 1 { "" } else { "s" }
+    ))
+}
+
+/// `--created-after`/`--updated-after` value as an RFC 3339 timestamp: a relative
+/// age (`30d`, `12h`, `2w`), a date (`2024-06-01`) or a full timestamp
+pub fn parse_since(raw: &str) -> std::result::Result<String, String> {
+    use chrono::{DateTime, Duration, NaiveDate, SecondsFormat, Utc};
+
+    let raw = raw.trim();
+    if let Ok(timestamp) = DateTime::parse_from_rfc3339(raw) {
+        return Ok(timestamp
+            .with_timezone(&Utc)
+            .to_rfc3339_opts(SecondsFormat::Secs, true));
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(raw, "%Y-%m-%d") {
+        let midnight = date.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc();
+        return Ok(midnight.to_rfc3339_opts(SecondsFormat::Secs, true));
+    }
+    let unit_len = raw.chars().last().map_or(0, char::len_utf8);
+    let (amount, unit) = raw.split_at(raw.len() - unit_len);
+    let amount: i64 = amount.parse().map_err(|_| {
+        format!(
+            "'{}' is not an age like 30d, a date or an RFC 3339 timestamp",
+            raw
+        )
+    })?;
+    let age = match unit {
+        "h" => Duration::hours(amount),
+        "d" => Duration::days(amount),
+        "w" => Duration::weeks(amount),
+        _ => return Err(format!("'{}' must end in h, d or w (e.g. 30d)", raw)),
+    };
+    Ok((Utc::now() - age).to_rfc3339_opts(SecondsFormat::Secs, true))
+}
+
+/// Byte count with an optional `K`/`KiB`/`M`/`MiB` suffix (powers of 1024)
+pub fn parse_size(raw: &str) -> std::result::Result<u64, String> {
+    let raw = raw.trim();
+    let digits = raw.trim_end_matches(|c: char| c.is_ascii_alphabetic());
+    let multiplier = match raw[digits.len()..].to_ascii_lowercase().as_str() {
+        "" | "b" => 1,
+        "k" | "kb" | "kib" => 1024,
+        "m" | "mb" | "mib" => 1024 * 1024,
+        _ => return Err(format!("'{}' must be bytes, or end in KiB or MiB", raw)),
+    };
+    digits
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .and_then(|n| n.checked_mul(multiplier))
+        .ok_or_else(|| format!("'{}' is not a size like 65536 or 64KiB", raw))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -182,6 +334,35 @@ mod tests {
         assert!(as_object(&json!(null), "test").is_err());
     }
 
+    #[test]
+    fn test_contract_path() {
+        assert_eq!(contract_path("@alice/token"), "%40alice%2Ftoken");
+        assert_eq!(
+            contract_path("CDLZFC3SYJYDZT7K67VZ75HPJVIEUVNIXF47ZG2FB2RMQQVU2HHGCYSC"),
+            "CDLZFC3SYJYDZT7K67VZ75HPJVIEUVNIXF47ZG2FB2RMQQVU2HHGCYSC"
+        );
+    }
+
+    #[test]
+    fn test_contract_display_name() {
+        assert_eq!(
+            contract_display_name(&json!({"name": "token", "namespace": "alice"})),
+            "@alice/token"
+        );
+        assert_eq!(contract_display_name(&json!({"name": "token"})), "token");
+    }
+
+    #[test]
+    fn test_render_highlight() {
+        colored::control::set_override(false);
+        assert_eq!(
+            render_highlight("Uniswap-style <mark>dex</mark> with <mark>AMM</mark> pools"),
+            "Uniswap-style dex with AMM pools"
+        );
+        assert_eq!(render_highlight("no matches"), "no matches");
+        assert_eq!(render_highlight("<mark>unterminated"), "unterminated");
+    }
+
     #[test]
     fn test_str_bindings() {
         let s = "hello";
@@ -189,4 +370,47 @@ mod tests {
         assert_eq!(S, "hello");
         assert_eq!(string_to_str(&S), "hello");
     }
+
+    #[test]
+    fn test_format_rating() {
+        assert_eq!(stars(3), "★★★☆☆");
+        assert_eq!(
+            format_rating(&json!({"average": 4.4, "count": 8})),
+            "★★★★☆ 4.4 (8 reviews)"
+        );
+        assert_eq!(format_rating(&Value::Null), "no reviews yet");
+    }
+
+    #[test]
+    fn test_page_summary() {
+        assert_eq!(
+            page_summary(&json!({"page": 2, "pages": 14, "total": 273})).as_deref(),
+            Some("page 2 of 14 (273 results)")
+        );
+        assert_eq!(
+            page_summary(&json!({"page": 1, "pages": 0, "total": 0})).as_deref(),
+            Some("page 1 of 1 (0 results)")
+        );
+        assert_eq!(page_summary(&json!({"items": []})), None);
+    }
+
+    #[test]
+    fn test_parse_since_and_size() {
+        assert_eq!(parse_since("2024-06-01").unwrap(), "2024-06-01T00:00:00Z");
+        assert_eq!(
+            parse_since("2024-06-01T12:00:00+02:00").unwrap(),
+            "2024-06-01T10:00:00Z"
+        );
+        let month_ago = chrono::DateTime::parse_from_rfc3339(&parse_since("30d").unwrap()).unwrap();
+        let age = chrono::Utc::now() - month_ago.with_timezone(&chrono::Utc);
+        assert!((age.num_days() - 30).abs() <= 1);
+        assert!(parse_since("30y").is_err());
+        assert!(parse_since("yesterday").is_err());
+
+        assert_eq!(parse_size("65536").unwrap(), 65536);
+        assert_eq!(parse_size("64KiB").unwrap(), 64 * 1024);
+        assert_eq!(parse_size("2M").unwrap(), 2 * 1024 * 1024);
+        assert!(parse_size("10GB").is_err());
+        assert!(parse_size("KiB").is_err());
+    }
 }
@@ -1,6 +1,8 @@
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 
+use crate::conversions::{contract_path, resolve_contract_uuid};
+
 #[derive(Debug, Serialize)]
 struct CostEstimateRequest {
     method_name: String,
@@ -44,7 +46,8 @@ pub async fn estimate_costs(
     optimize: bool,
     forecast: bool,
 ) -> Result<()> {
-    let client = reqwest::Client::new();
+    let contract_id = &resolve_contract_uuid(api_url, contract_id).await?;
+    let client = crate::http::client();
 
     let request = CostEstimateRequest {
         method_name: method.to_string(),
@@ -54,7 +57,7 @@ pub async fn estimate_costs(
 
     // Get base estimate
     let estimate: CostEstimate = client
-        .post(format!("{}/api/contracts/{}/cost-estimate", api_url, contract_id))
+        .post(format!("{}/api/contracts/{}/cost-estimate", api_url, contract_path(contract_id)))
         .json(&request)
         .send()
         .await?
@@ -80,7 +83,11 @@ pub async fn estimate_costs(
     // Show optimization suggestions
     if optimize {
         let optimization: CostOptimization = client
-            .post(format!("{}/api/contracts/{}/cost-estimate/optimize", api_url, contract_id))
+            .post(format!(
+                "{}/api/contracts/{}/cost-estimate/optimize",
+                api_url,
+                contract_path(contract_id)
+            ))
             .json(&estimate)
             .send()
             .await?
@@ -105,7 +112,11 @@ pub async fn estimate_costs(
     // Show forecast
     if forecast {
         let forecast_data: CostForecast = client
-            .post(format!("{}/api/contracts/{}/cost-estimate/forecast", api_url, contract_id))
+            .post(format!(
+                "{}/api/contracts/{}/cost-estimate/forecast",
+                api_url,
+                contract_path(contract_id)
+            ))
             .json(&request)
             .send()
             .await?
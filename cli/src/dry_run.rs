@@ -0,0 +1,31 @@
+use std::fmt::Display;
+
+use colored::Colorize;
+
+/// Query flag that makes the registry validate a write without committing it
+pub const QUERY: [(&str, &str); 1] = [("dry_run", "true")];
+
+/// Header printed before a command lists what it would do
+pub fn banner(action: &str) {
+    println!(
+        "\n{} {}",
+        "Dry run:".bold().yellow(),
+        format!("{} (nothing will be changed)", action).bold()
+    );
+}
+
+/// A request sent with `?dry_run=true`, so the registry validates it without changing anything
+pub fn validated(method: &str, url: &str) {
+    println!(
+        "  {} {} {} {}",
+        "✓".green(),
+        method.bold(),
+        url,
+        "(validated, not committed)".bright_black()
+    );
+}
+
+/// A request or file write that was skipped
+pub fn would(step: impl Display) {
+    println!("  {} would {}", "·".bright_black(), step);
+}
@@ -0,0 +1,492 @@
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::process::Command;
+
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use reqwest::{header, StatusCode};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+
+use crate::conversions::contract_path;
+
+/// Metadata fields `edit` can change, in display order
+const FIELDS: &[&str] = &["name", "description", "category", "tags", "links", "readme"];
+
+/// The editable part of a contract, as written to the file opened in `$EDITOR`
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+struct Manifest {
+    name: String,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    category: String,
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(default)]
+    readme: String,
+    /// Label → URL, e.g. `homepage = "https://..."`
+    #[serde(default)]
+    links: BTreeMap<String, String>,
+}
+
+impl Manifest {
+    fn from_contract(contract: &Value) -> Self {
+        let text = |field: &str| contract[field].as_str().unwrap_or_default().to_string();
+        Self {
+            name: text("name"),
+            description: text("description"),
+            category: text("category"),
+            tags: serde_json::from_value(contract["tags"].clone()).unwrap_or_default(),
+            readme: text("readme"),
+            links: serde_json::from_value(contract["links"].clone()).unwrap_or_default(),
+        }
+    }
+}
+
+/// PATCH body holding only the fields that differ between the two manifests
+fn manifest_changes(before: &Manifest, after: &Manifest) -> Map<String, Value> {
+    let mut changes = Map::new();
+    if after.name != before.name {
+        changes.insert("name".to_string(), json!(after.name));
+    }
+    if after.description != before.description {
+        changes.insert("description".to_string(), json!(after.description));
+    }
+    if after.category != before.category {
+        changes.insert("category".to_string(), json!(after.category));
+    }
+    if after.tags != before.tags {
+        changes.insert("tags".to_string(), json!(after.tags));
+    }
+    if after.readme.trim() != before.readme.trim() {
+        changes.insert("readme".to_string(), json!(after.readme));
+    }
+    if after.links != before.links {
+        changes.insert("links".to_string(), json!(after.links));
+    }
+    changes
+}
+
+/// How one requested field relates to what someone else changed meanwhile
+#[derive(Debug, PartialEq, Eq)]
+enum FieldOutcome {
+    /// Nobody else touched it; the edit applies cleanly on a retry
+    Clean,
+    /// The other writer already set it to the value we wanted
+    AlreadyApplied,
+    /// Both sides changed it to different values
+    Conflict,
+}
+
+/// Three-way comparison of the fields in `ours` between the contract as we
+/// read it (`base`) and as it is now (`theirs`)
+fn classify(
+    base: &Value,
+    theirs: &Value,
+    ours: &Map<String, Value>,
+) -> Vec<(String, FieldOutcome)> {
+    FIELDS
+        .iter()
+        .filter_map(|&field| {
+            let wanted = ours.get(field)?;
+            let outcome = if &theirs[field] == wanted {
+                FieldOutcome::AlreadyApplied
+            } else if theirs[field] == base[field] {
+                FieldOutcome::Clean
+            } else {
+                FieldOutcome::Conflict
+            };
+            Some((field.to_string(), outcome))
+        })
+        .collect()
+}
+
+/// Fields someone else changed that this edit leaves alone
+fn untouched_changes(base: &Value, theirs: &Value, ours: &Map<String, Value>) -> Vec<String> {
+    FIELDS
+        .iter()
+        .filter(|&&field| !ours.contains_key(field) && theirs[field] != base[field])
+        .map(|field| field.to_string())
+        .collect()
+}
+
+fn show(value: &Value) -> String {
+    match value {
+        Value::Null => "(none)".to_string(),
+        Value::String(s) if s.contains('\n') => format!(
+            "{} … ({} lines)",
+            s.lines().next().unwrap_or_default(),
+            s.lines().count()
+        ),
+        Value::String(s) => s.clone(),
+        Value::Object(links) => links
+            .iter()
+            .map(|(label, url)| format!("{}={}", label, url.as_str().unwrap_or_default()))
+            .collect::<Vec<_>>()
+            .join(", "),
+        Value::Array(items) => items
+            .iter()
+            .filter_map(Value::as_str)
+            .collect::<Vec<_>>()
+            .join(", "),
+        other => other.to_string(),
+    }
+}
+
+async fn fetch(client: &reqwest::Client, url: &str) -> Result<(Value, Option<String>)> {
+    let response = client
+        .get(url)
+        .send()
+        .await
+        .context("Failed to fetch contract")?;
+    if !response.status().is_success() {
+        return Err(crate::api_errors::failure(response, "Failed to fetch contract").await);
+    }
+    let etag = response
+        .headers()
+        .get(header::ETAG)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
+    Ok((response.json().await?, etag))
+}
+
+/// Add the contract's current README to `contract` as its `readme` field
+async fn fetch_readme(
+    client: &reqwest::Client,
+    contract_url: &str,
+    contract: &mut Value,
+) -> Result<()> {
+    let response = client
+        .get(format!("{}/readme", contract_url))
+        .send()
+        .await
+        .context("Failed to fetch README")?;
+    if response.status() == StatusCode::NOT_FOUND {
+        contract["readme"] = Value::Null;
+        return Ok(());
+    }
+    if !response.status().is_success() {
+        return Err(crate::api_errors::failure(response, "Failed to fetch README").await);
+    }
+    let body: Value = response.json().await?;
+    contract["readme"] = body["content"].clone();
+    Ok(())
+}
+
+/// `edit <contract> [--name] [--description] [--category] [--tags]`
+///
+/// Sends the update with `If-Match`, so an edit made by another maintainer
+/// since the contract was read is reported field by field instead of overwritten.
+pub async fn edit(
+    api_url: &str,
+    contract: &str,
+    changes: Map<String, Value>,
+    if_match: Option<&str>,
+) -> Result<()> {
+    if changes.is_empty() {
+        bail!("Nothing to change; pass --name, --description, --category or --tags");
+    }
+    let client = crate::http::client();
+    let contract_url = format!(
+        "{}/api/contracts/{}",
+        api_url.trim_end_matches('/'),
+        contract_path(contract)
+    );
+
+    let (base, etag) = fetch(&client, &contract_url).await?;
+    let Some(etag) = if_match.map(str::to_string).or(etag) else {
+        bail!("The registry did not return an ETag; upgrade the registry or pass --if-match");
+    };
+    submit(
+        &client,
+        api_url,
+        &contract_url,
+        contract,
+        &base,
+        &etag,
+        changes,
+    )
+    .await
+}
+
+/// `edit <contract>` with no flags: opens the contract's metadata and README
+/// in `$EDITOR` and submits whatever changed once the editor exits
+pub async fn edit_in_editor(api_url: &str, contract: &str) -> Result<()> {
+    crate::interactive::require_terminal(
+        "Editing in $EDITOR",
+        "pass the fields to change as flags, e.g. --description",
+    )?;
+    let client = crate::http::client();
+    let contract_url = format!(
+        "{}/api/contracts/{}",
+        api_url.trim_end_matches('/'),
+        contract_path(contract)
+    );
+
+    let (mut base, etag) = fetch(&client, &contract_url).await?;
+    let Some(etag) = etag else {
+        bail!("The registry did not return an ETag; upgrade the registry to edit interactively");
+    };
+    fetch_readme(&client, &contract_url, &mut base).await?;
+    let before = Manifest::from_contract(&base);
+
+    let mut file = tempfile::Builder::new()
+        .prefix("soroban-registry-edit-")
+        .suffix(".toml")
+        .tempfile()
+        .context("Failed to create a temporary file")?;
+    writeln!(
+        file,
+        "# Editing {}. Save and quit to submit; leave unchanged to abort.",
+        contract
+    )?;
+    file.write_all(toml::to_string_pretty(&before)?.as_bytes())?;
+    file.flush()?;
+
+    let editor = std::env::var("EDITOR").unwrap_or_else(|_| "vi".to_string());
+    let status = Command::new(&editor)
+        .arg(file.path())
+        .status()
+        .with_context(|| format!("Failed to launch editor `{}`", editor))?;
+    if !status.success() {
+        bail!("Editor exited with non-zero status; nothing was changed");
+    }
+
+    let edited = std::fs::read_to_string(file.path())?;
+    let after: Manifest = toml::from_str(&edited).context("The edited manifest is not valid")?;
+    let changes = manifest_changes(&before, &after);
+    if changes.is_empty() {
+        println!("No changes; {} was left as is", contract);
+        return Ok(());
+    }
+    submit(
+        &client,
+        api_url,
+        &contract_url,
+        contract,
+        &base,
+        &etag,
+        changes,
+    )
+    .await
+}
+
+async fn submit(
+    client: &reqwest::Client,
+    api_url: &str,
+    contract_url: &str,
+    contract: &str,
+    base: &Value,
+    etag: &str,
+    changes: Map<String, Value>,
+) -> Result<()> {
+    let id = base["id"]
+        .as_str()
+        .context("Contract response is missing its id")?;
+
+    let mut request = client
+        .patch(format!(
+            "{}/api/contracts/{}",
+            api_url.trim_end_matches('/'),
+            id
+        ))
+        .header(header::IF_MATCH, etag)
+        .json(&Value::Object(changes.clone()));
+    if let Ok(token) = crate::http::auth_token() {
+        request = request.bearer_auth(token);
+    }
+    let response = request.send().await.context("Failed to update contract")?;
+
+    if response.status() == StatusCode::PRECONDITION_FAILED {
+        let (mut theirs, _) = fetch(client, contract_url).await?;
+        if changes.contains_key("readme") {
+            fetch_readme(client, contract_url, &mut theirs).await?;
+        }
+        report_conflict(base, &theirs, &changes);
+        bail!(
+            "{} was modified by someone else; nothing was changed",
+            contract
+        );
+    }
+    if !response.status().is_success() {
+        return Err(crate::api_errors::failure(response, "Failed to update contract").await);
+    }
+
+    let new_etag = response
+        .headers()
+        .get(header::ETAG)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("")
+        .to_string();
+    let updated: Value = response.json().await?;
+    println!("{} Updated {}", "✓".green(), contract.bold());
+    for field in FIELDS.iter().filter(|f| changes.contains_key(**f)) {
+        // The contract body does not carry the README; show what was sent
+        let now = if *field == "readme" {
+            &changes[*field]
+        } else {
+            &updated[*field]
+        };
+        println!(
+            "  {}: {} → {}",
+            field.bold(),
+            show(&base[*field]).bright_black(),
+            show(now)
+        );
+    }
+    if !new_etag.is_empty() {
+        println!("  {}: {}", "ETag".bold(), new_etag.bright_black());
+    }
+    Ok(())
+}
+
+fn report_conflict(base: &Value, theirs: &Value, ours: &Map<String, Value>) {
+    println!(
+        "\n{} {}",
+        "⚠".yellow(),
+        "The contract changed after it was read:".yellow().bold()
+    );
+    let outcomes = classify(base, theirs, ours);
+    for (field, outcome) in &outcomes {
+        let label = match outcome {
+            FieldOutcome::Clean => "unchanged by others".green(),
+            FieldOutcome::AlreadyApplied => "already has your value".green(),
+            FieldOutcome::Conflict => "CONFLICT".red().bold(),
+        };
+        println!("  {} [{}]", field.bold(), label);
+        if *outcome == FieldOutcome::Conflict {
+            println!(
+                "    {} {}",
+                "was:   ".bright_black(),
+                show(&base[field.as_str()])
+            );
+            println!(
+                "    {} {}",
+                "theirs:".bright_black(),
+                show(&theirs[field.as_str()])
+            );
+            println!(
+                "    {} {}",
+                "yours: ".bright_black(),
+                show(&ours[field.as_str()])
+            );
+        }
+    }
+    for field in untouched_changes(base, theirs, ours) {
+        println!(
+            "  {} [{}] {}",
+            field.bold(),
+            "changed by others".bright_black(),
+            show(&theirs[field.as_str()]).bright_black()
+        );
+    }
+    if outcomes.iter().any(|(_, o)| *o == FieldOutcome::Conflict) {
+        println!("\nResolve the conflicting fields, then re-run the edit.");
+    } else {
+        println!("\nNo overlapping edits; re-run the same command to apply yours on top.");
+    }
+}
+
+/// Build the PATCH body from the CLI flags; `--tags` is comma-separated
+pub fn metadata_changes(
+    name: Option<String>,
+    description: Option<String>,
+    category: Option<String>,
+    tags: Option<String>,
+) -> Map<String, Value> {
+    let mut changes = Map::new();
+    if let Some(name) = name {
+        changes.insert("name".to_string(), json!(name));
+    }
+    if let Some(description) = description {
+        changes.insert("description".to_string(), json!(description));
+    }
+    if let Some(category) = category {
+        changes.insert("category".to_string(), json!(category));
+    }
+    if let Some(tags) = tags {
+        let tags: Vec<&str> = tags
+            .split(',')
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+            .collect();
+        changes.insert("tags".to_string(), json!(tags));
+    }
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_each_requested_field() {
+        let base = json!({"name": "amm", "description": "old", "category": "defi", "tags": []});
+        let theirs =
+            json!({"name": "amm", "description": "theirs", "category": "dex", "tags": ["x"]});
+        let ours = metadata_changes(
+            Some("amm-v2".to_string()),
+            Some("mine".to_string()),
+            Some("dex".to_string()),
+            None,
+        );
+        assert_eq!(
+            classify(&base, &theirs, &ours),
+            vec![
+                ("name".to_string(), FieldOutcome::Clean),
+                ("description".to_string(), FieldOutcome::Conflict),
+                ("category".to_string(), FieldOutcome::AlreadyApplied),
+            ]
+        );
+        assert_eq!(untouched_changes(&base, &theirs, &ours), vec!["tags"]);
+    }
+
+    #[test]
+    fn manifest_diff_only_sends_edited_fields() {
+        let contract = json!({
+            "name": "amm",
+            "description": null,
+            "category": "defi",
+            "tags": ["amm"],
+            "links": {"homepage": "https://amm.example"},
+            "readme": "# AMM\n",
+        });
+        let before = Manifest::from_contract(&contract);
+        assert_eq!(before.description, "");
+
+        let mut after = before.clone();
+        after.readme = "# AMM".to_string();
+        assert!(manifest_changes(&before, &after).is_empty());
+
+        after.tags.push("dex".to_string());
+        after.links.insert(
+            "repository".to_string(),
+            "https://github.com/a/amm".to_string(),
+        );
+        let changes = manifest_changes(&before, &after);
+        assert_eq!(changes.len(), 2);
+        assert!(changes.contains_key("links"));
+        assert_eq!(changes["tags"], json!(["amm", "dex"]));
+    }
+
+    #[test]
+    fn manifest_round_trips_through_toml() {
+        let manifest = Manifest {
+            name: "amm".to_string(),
+            readme: "# AMM\n\nSwap \"tokens\".\n".to_string(),
+            links: [("docs".to_string(), "https://docs.example".to_string())]
+                .into_iter()
+                .collect(),
+            ..Manifest::default()
+        };
+        let text = toml::to_string_pretty(&manifest).unwrap();
+        assert_eq!(toml::from_str::<Manifest>(&text).unwrap(), manifest);
+    }
+
+    #[test]
+    fn tags_flag_is_split_and_trimmed() {
+        let changes = metadata_changes(None, None, None, Some("defi, amm,,".to_string()));
+        assert_eq!(changes["tags"], json!(["defi", "amm"]));
+    }
+}
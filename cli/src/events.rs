@@ -1,8 +1,17 @@
 #![allow(dead_code)]
 
-use anyhow::Result;
+use std::path::Path;
+
+use anyhow::{Context, Result};
 use colored::Colorize;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use shared::{
+    format_type, AbiSyntax, DecodeEventRequest, DecodedEvent, EventParamLocation, EventSchema,
+    EventSchemasResponse, RegisterEventSchemasRequest,
+};
+
+use crate::conversions::contract_path;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ContractEvent {
@@ -40,10 +49,10 @@ pub async fn query_events(
     println!("\n{}", "Contract Events".bold().cyan());
     println!("{}", "=".repeat(80).cyan());
 
-    let client = reqwest::Client::new();
+    let client = crate::http::client();
 
     if stats_only {
-        let url = format!("{}/api/contracts/{}/events/stats", api_url, contract_id);
+        let url = format!("{}/api/contracts/{}/events/stats", api_url, contract_path(contract_id));
 
         let response = client
             .get(&url)
@@ -52,8 +61,7 @@ pub async fn query_events(
             .map_err(|e| anyhow::anyhow!("Failed to fetch event stats: {}", e))?;
 
         if !response.status().is_success() {
-            let error = response.text().await?;
-            anyhow::bail!("API error: {}", error);
+            return Err(crate::api_errors::failure(response, "API error").await);
         }
 
         let stats: EventStats = response.json().await?;
@@ -94,7 +102,7 @@ pub async fn query_events(
 
     let mut url = format!(
         "{}/api/contracts/{}/events?limit={}&offset={}",
-        api_url, contract_id, limit, offset
+        api_url, contract_path(contract_id), limit, offset
     );
 
     if let Some(t) = topic {
@@ -112,8 +120,7 @@ pub async fn query_events(
         .map_err(|e| anyhow::anyhow!("Failed to fetch events: {}", e))?;
 
     if !response.status().is_success() {
-        let error = response.text().await?;
-        anyhow::bail!("API error: {}", error);
+        return Err(crate::api_errors::failure(response, "API error").await);
     }
 
     let events: Vec<ContractEvent> = response.json().await?;
@@ -190,3 +197,165 @@ pub async fn query_events(
 
     Ok(())
 }
+
+fn schemas_url(api_url: &str, contract_id: &str) -> String {
+    format!(
+        "{}/api/contracts/{}/events",
+        api_url.trim_end_matches('/'),
+        contract_path(contract_id)
+    )
+}
+
+fn print_schema(schema: &EventSchema) {
+    let topics: Vec<String> = schema
+        .params
+        .iter()
+        .filter(|p| p.location == EventParamLocation::Topic)
+        .map(|p| format!("{}: {}", p.name, format_type(&p.value, AbiSyntax::Rust)))
+        .collect();
+    let data: Vec<String> = schema
+        .params
+        .iter()
+        .filter(|p| p.location == EventParamLocation::Data)
+        .map(|p| format!("{}: {}", p.name, format_type(&p.value, AbiSyntax::Rust)))
+        .collect();
+    println!(
+        "\n  {} {}",
+        schema.name.bold(),
+        format!("[{}]", schema.prefix().join(", ")).bright_black()
+    );
+    if let Some(doc) = &schema.doc {
+        println!("    {}", doc.bright_black());
+    }
+    if !topics.is_empty() {
+        println!("    topics: {}", topics.join(", "));
+    }
+    if !data.is_empty() {
+        println!("    data:   {} ({:?})", data.join(", "), schema.data_format);
+    }
+}
+
+/// `events schemas <contract> [--version <v>]`
+pub async fn schemas(
+    api_url: &str,
+    contract_id: &str,
+    version: Option<&str>,
+    json: bool,
+) -> Result<()> {
+    let response = crate::http::client()
+        .get(schemas_url(api_url, contract_id))
+        .query(&[("version", version)])
+        .send()
+        .await?;
+    if !response.status().is_success() {
+        return Err(crate::api_errors::failure(response, "Failed to fetch event schemas").await);
+    }
+    let schemas: EventSchemasResponse = response.json().await?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&schemas)?);
+        return Ok(());
+    }
+    if schemas.events.is_empty() {
+        println!(
+            "{}@{} has no registered event schemas.",
+            schemas.contract_id, schemas.version
+        );
+        return Ok(());
+    }
+    println!(
+        "\n{} {}@{}",
+        "Events:".bold().cyan(),
+        schemas.contract_id,
+        schemas.version
+    );
+    println!("{}", "=".repeat(80).cyan());
+    for schema in &schemas.events {
+        print_schema(schema);
+    }
+    Ok(())
+}
+
+/// `events register <contract> --version <v> --file <events.json>`: the file
+/// holds a JSON array of schemas and replaces the version's
+pub async fn register(api_url: &str, contract_id: &str, version: &str, file: &Path) -> Result<()> {
+    let contents = std::fs::read_to_string(file)
+        .with_context(|| format!("Failed to read {}", file.display()))?;
+    let events: Vec<EventSchema> = serde_json::from_str(&contents)
+        .with_context(|| format!("{} must be a JSON array of event schemas", file.display()))?;
+    for event in &events {
+        event.validate().map_err(anyhow::Error::msg)?;
+    }
+    let url = format!(
+        "{}/api/contracts/{}/versions/{}/events",
+        api_url.trim_end_matches('/'),
+        contract_path(contract_id),
+        version
+    );
+    let response = crate::http::client()
+        .put(&url)
+        .bearer_auth(crate::http::auth_token()?)
+        .json(&RegisterEventSchemasRequest { events })
+        .send()
+        .await?;
+    if !response.status().is_success() {
+        return Err(crate::api_errors::failure(response, "Failed to register event schemas").await);
+    }
+    let schemas: EventSchemasResponse = response.json().await?;
+    println!(
+        "{} Registered {} event schema(s) for {}@{}",
+        "✓".green(),
+        schemas.events.len(),
+        schemas.contract_id,
+        schemas.version
+    );
+    Ok(())
+}
+
+/// `events decode <contract> <event-xdr>` or `--topic <xdr>... --data <xdr>`
+pub async fn decode(
+    api_url: &str,
+    contract_id: &str,
+    request: DecodeEventRequest,
+    json: bool,
+) -> Result<()> {
+    // Check the XDR here so typos fail before the round trip
+    request.raw_event().map_err(anyhow::Error::msg)?;
+    let response = crate::http::client()
+        .post(format!("{}/decode", schemas_url(api_url, contract_id)))
+        .json(&request)
+        .send()
+        .await?;
+    if !response.status().is_success() {
+        return Err(crate::api_errors::failure(response, "Failed to decode event").await);
+    }
+    let decoded: DecodedEvent = response.json().await?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&decoded)?);
+        return Ok(());
+    }
+    match &decoded.event {
+        Some(name) => {
+            println!("{} {}", "●".cyan(), name.bold().yellow());
+            for (field, value) in &decoded.fields {
+                println!("  {}: {}", field.bold(), value);
+            }
+        }
+        None => {
+            println!(
+                "{} {}",
+                "⚠".yellow(),
+                "No registered schema matches this event".yellow()
+            );
+            println!(
+                "  {}: {}",
+                "topics".bold(),
+                Value::from(decoded.topics.clone())
+            );
+            println!("  {}: {}", "data".bold(), decoded.data);
+        }
+    }
+    if let Some(emitter) = &decoded.contract_id {
+        println!("  {}", format!("emitted by {}", emitter).bright_black());
+    }
+    Ok(())
+}
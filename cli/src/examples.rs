@@ -0,0 +1,163 @@
+//! `examples`: runnable call sequences publishers attach to a contract, run
+//! through the registry's simulation sandbox.
+
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use shared::{ContractExample, ExampleRun, ExampleStep, RunExampleRequest, UpsertExampleRequest};
+
+use crate::conversions::contract_path;
+
+fn examples_url(api_url: &str, contract_id: &str) -> String {
+    format!(
+        "{}/api/contracts/{}/examples",
+        api_url.trim_end_matches('/'),
+        contract_path(contract_id)
+    )
+}
+
+async fn check(response: reqwest::Response, action: &str) -> Result<reqwest::Response> {
+    if !response.status().is_success() {
+        return Err(crate::api_errors::failure(response, format!("Failed to {}", action)).await);
+    }
+    Ok(response)
+}
+
+fn print_steps(steps: &[ExampleStep]) {
+    for (i, step) in steps.iter().enumerate() {
+        println!(
+            "    {}. {}({})",
+            i + 1,
+            step.function.cyan(),
+            step.args.join(", ")
+        );
+    }
+}
+
+/// `examples list <contract>`
+pub async fn list(api_url: &str, contract_id: &str, json: bool) -> Result<()> {
+    let response = crate::http::client()
+        .get(examples_url(api_url, contract_id))
+        .send()
+        .await?;
+    let examples: Vec<ContractExample> = check(response, "fetch examples").await?.json().await?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&examples)?);
+        return Ok(());
+    }
+    if examples.is_empty() {
+        println!("{} has no examples.", contract_id);
+        return Ok(());
+    }
+
+    println!("\n{} {}", "Examples:".bold().cyan(), contract_id);
+    println!("{}", "=".repeat(80).cyan());
+    for example in &examples {
+        println!(
+            "\n  {} {}",
+            example.name.bold(),
+            format!("(checked against {})", example.version).bright_black()
+        );
+        if let Some(description) = &example.description {
+            println!("    {}", description);
+        }
+        print_steps(&example.steps);
+    }
+    println!(
+        "\n  Run one with {}",
+        format!("soroban-registry examples run {} <example>", contract_id).cyan()
+    );
+    Ok(())
+}
+
+/// `examples add <contract> <example> --file <steps.json>`: the file holds a
+/// JSON array of `{"function": ..., "args": [...]}` steps
+pub async fn add(
+    api_url: &str,
+    contract_id: &str,
+    name: &str,
+    file: &Path,
+    description: Option<&str>,
+    version: Option<&str>,
+) -> Result<()> {
+    let contents = std::fs::read_to_string(file)
+        .with_context(|| format!("Failed to read {}", file.display()))?;
+    let steps: Vec<ExampleStep> = serde_json::from_str(&contents).with_context(|| {
+        format!(
+            "{} must be a JSON array of {{\"function\", \"args\"}} steps",
+            file.display()
+        )
+    })?;
+    let response = crate::http::client()
+        .put(format!("{}/{}", examples_url(api_url, contract_id), name))
+        .bearer_auth(crate::http::auth_token()?)
+        .json(&UpsertExampleRequest {
+            description: description.map(str::to_string),
+            version: version.map(str::to_string),
+            steps,
+        })
+        .send()
+        .await?;
+    let example: ContractExample = check(response, "save example").await?.json().await?;
+    println!(
+        "{} Saved example {} ({} steps, checked against {})",
+        "✓".green(),
+        example.name.bold(),
+        example.steps.len(),
+        example.version
+    );
+    Ok(())
+}
+
+/// `examples remove <contract> <example>`
+pub async fn remove(api_url: &str, contract_id: &str, name: &str) -> Result<()> {
+    let response = crate::http::client()
+        .delete(format!("{}/{}", examples_url(api_url, contract_id), name))
+        .bearer_auth(crate::http::auth_token()?)
+        .send()
+        .await?;
+    check(response, "remove example").await?;
+    println!("{} Removed example {}", "✓".green(), name);
+    Ok(())
+}
+
+/// `examples run <contract> <example>`: fails when a step does
+pub async fn run(
+    api_url: &str,
+    contract_id: &str,
+    name: &str,
+    version: Option<&str>,
+    json: bool,
+) -> Result<()> {
+    let response = crate::http::client()
+        .post(format!(
+            "{}/{}/run",
+            examples_url(api_url, contract_id),
+            name
+        ))
+        .json(&RunExampleRequest {
+            version: version.map(str::to_string),
+        })
+        .send()
+        .await?;
+    let run: ExampleRun = check(response, "run example").await?.json().await?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&run)?);
+    } else {
+        println!(
+            "\n{} {} on {}@{}",
+            "Running".bold().cyan(),
+            run.example.bold(),
+            contract_id,
+            run.version
+        );
+        for (i, step) in run.steps.iter().enumerate() {
+            crate::invoke::print_outcome(&format!("{}. {}", i + 1, step.function), step);
+        }
+    }
+    if !run.success {
+        anyhow::bail!("Example {} failed", run.example);
+    }
+    Ok(())
+}
@@ -0,0 +1,251 @@
+//! User-defined aliases from `[alias]` in the config file, and external
+//! subcommands: `soroban-registry foo` runs a `soroban-registry-foo` executable
+//! found on PATH, the way cargo and git do. Built-in commands always win.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use clap::CommandFactory;
+
+use crate::cli_error::{self, CliError};
+use crate::config::Network;
+
+const EXTENSION_PREFIX: &str = "soroban-registry-";
+/// Aliases may refer to other aliases, up to this deep
+const MAX_ALIAS_DEPTH: usize = 8;
+
+// Context handed to extensions; they can call back into the registry without
+// re-reading the config file
+pub const API_URL_ENV: &str = "SOROBAN_REGISTRY_API_URL";
+pub const NETWORK_ENV: &str = "SOROBAN_REGISTRY_NETWORK";
+pub const CONFIG_ENV: &str = "SOROBAN_REGISTRY_CONFIG";
+pub const BIN_ENV: &str = "SOROBAN_REGISTRY_BIN";
+
+/// `args` with the subcommand at `position` replaced by `expansion`
+fn splice_alias(args: &[OsString], position: usize, expansion: &[String]) -> Vec<OsString> {
+    let mut spliced = args[..position].to_vec();
+    spliced.extend(expansion.iter().map(OsString::from));
+    spliced.extend_from_slice(&args[position + 1..]);
+    spliced
+}
+
+/// Expand a leading alias in the command line. Anything clap cannot parse is
+/// returned as is, so the real parse reports it.
+pub fn resolve_aliases(
+    mut args: Vec<OsString>,
+    aliases: &BTreeMap<String, Vec<String>>,
+) -> Result<Vec<OsString>> {
+    let mut seen = Vec::new();
+    loop {
+        let Ok(matches) = crate::Cli::command().try_get_matches_from(&args) else {
+            return Ok(args);
+        };
+        let Some((name, sub)) = matches.subcommand() else {
+            return Ok(args);
+        };
+        let Some(expansion) = aliases.get(name) else {
+            return Ok(args);
+        };
+        if crate::Cli::command().find_subcommand(name).is_some() {
+            return Ok(args);
+        }
+        if seen.iter().any(|s| s == name) || seen.len() == MAX_ALIAS_DEPTH {
+            return Err(CliError::Usage(format!(
+                "alias `{}` expands to itself (via {})",
+                name,
+                seen.join(" → ")
+            ))
+            .into());
+        }
+
+        // External subcommand arguments are the tail of the command line
+        let rest = sub
+            .get_many::<OsString>("")
+            .map(|values| values.count())
+            .unwrap_or(0);
+        let position = args.len() - rest - 1;
+        seen.push(name.to_string());
+        args = splice_alias(&args, position, expansion);
+    }
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    path.metadata()
+        .map(|meta| meta.is_file() && meta.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
+
+fn search_path() -> Vec<PathBuf> {
+    std::env::var_os("PATH")
+        .map(|path| std::env::split_paths(&path).collect())
+        .unwrap_or_default()
+}
+
+fn find_extension(name: &str, dirs: &[PathBuf]) -> Option<PathBuf> {
+    let file_name = format!(
+        "{}{}{}",
+        EXTENSION_PREFIX,
+        name,
+        std::env::consts::EXE_SUFFIX
+    );
+    dirs.iter()
+        .map(|dir| dir.join(&file_name))
+        .find(|candidate| is_executable(candidate))
+}
+
+/// Names of the extensions installed in `dirs`
+fn discover_extensions(dirs: &[PathBuf]) -> BTreeSet<String> {
+    dirs.iter()
+        .filter_map(|dir| std::fs::read_dir(dir).ok())
+        .flatten()
+        .flatten()
+        .filter(|entry| is_executable(&entry.path()))
+        .filter_map(|entry| {
+            let file_name = entry.file_name().into_string().ok()?;
+            let name = file_name.strip_prefix(EXTENSION_PREFIX)?;
+            let name = name
+                .strip_suffix(std::env::consts::EXE_SUFFIX)
+                .unwrap_or(name);
+            (!name.is_empty()).then(|| name.to_string())
+        })
+        .collect()
+}
+
+/// Run `soroban-registry-<name>` with the remaining arguments and the resolved
+/// API URL, network, config path and token in its environment. A failing
+/// extension's exit code becomes ours.
+pub fn run_external(args: &[OsString], api_url: &str, network: Network) -> Result<()> {
+    let Some((name, rest)) = args.split_first() else {
+        return Ok(());
+    };
+    let name = name.to_string_lossy();
+    let program = find_extension(&name, &search_path()).ok_or_else(|| {
+        CliError::Usage(format!(
+            "no such command: `{}`; see `soroban-registry --help`, or install an \
+             extension named `{}{}` on PATH",
+            name, EXTENSION_PREFIX, name
+        ))
+    })?;
+    log::debug!("Running extension {}", program.display());
+
+    let mut command = Command::new(&program);
+    command
+        .args(rest)
+        .env(API_URL_ENV, api_url)
+        .env(NETWORK_ENV, network.to_string());
+    if let Some(path) = crate::config::config_file_path() {
+        command.env(CONFIG_ENV, path);
+    }
+    if let Some(token) = crate::http::configured_token() {
+        command.env(crate::http::TOKEN_ENV, token);
+    }
+    if let Ok(exe) = std::env::current_exe() {
+        command.env(BIN_ENV, exe);
+    }
+
+    let status = command
+        .status()
+        .with_context(|| format!("Failed to run extension {}", program.display()))?;
+    if !status.success() {
+        std::process::exit(status.code().unwrap_or(cli_error::GENERAL));
+    }
+    Ok(())
+}
+
+/// Print configured aliases and installed extensions
+pub fn list() -> Result<()> {
+    let aliases = crate::config::aliases();
+    let extensions = discover_extensions(&search_path());
+    if aliases.is_empty() && extensions.is_empty() {
+        println!("No aliases or extensions. Define aliases under [alias] in the config file,");
+        println!(
+            "or install executables named `{}<name>` on PATH.",
+            EXTENSION_PREFIX
+        );
+        return Ok(());
+    }
+    if !aliases.is_empty() {
+        println!("Aliases:");
+        for (name, expansion) in &aliases {
+            println!("  {:<16} {}", name, expansion.join(" "));
+        }
+    }
+    if !extensions.is_empty() {
+        println!("Extensions:");
+        for name in &extensions {
+            println!("  {}", name);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<OsString> {
+        line.split_whitespace().map(OsString::from).collect()
+    }
+
+    fn aliases(entries: &[(&str, &str)]) -> BTreeMap<String, Vec<String>> {
+        entries
+            .iter()
+            .map(|(name, line)| {
+                (
+                    name.to_string(),
+                    line.split_whitespace().map(str::to_string).collect(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn expands_aliases_in_place() {
+        let aliases = aliases(&[("vs", "search --verified-only"), ("v", "vs")]);
+        assert_eq!(
+            resolve_aliases(args("soroban-registry --network mainnet v token"), &aliases).unwrap(),
+            args("soroban-registry --network mainnet search --verified-only token")
+        );
+    }
+
+    #[test]
+    fn builtins_win_and_loops_are_refused() {
+        let shadowing = aliases(&[("search", "info")]);
+        assert_eq!(
+            resolve_aliases(args("soroban-registry search token"), &shadowing).unwrap(),
+            args("soroban-registry search token")
+        );
+
+        let looping = aliases(&[("a", "b"), ("b", "a")]);
+        assert!(resolve_aliases(args("soroban-registry a"), &looping).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn finds_executable_extensions_on_the_path() {
+        use std::os::unix::fs::PermissionsExt;
+        let dir = tempfile::tempdir().unwrap();
+        let script = dir.path().join("soroban-registry-audit-pro");
+        std::fs::write(&script, "#!/bin/sh\n").unwrap();
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+        std::fs::write(dir.path().join("soroban-registry-notes"), "").unwrap();
+
+        let dirs = vec![dir.path().to_path_buf()];
+        assert_eq!(find_extension("audit-pro", &dirs), Some(script));
+        assert_eq!(find_extension("notes", &dirs), None);
+        assert_eq!(
+            discover_extensions(&dirs).into_iter().collect::<Vec<_>>(),
+            vec!["audit-pro"]
+        );
+    }
+}
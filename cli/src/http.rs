@@ -0,0 +1,266 @@
+use std::sync::OnceLock;
+
+use anyhow::Context;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, AUTHORIZATION};
+
+use crate::config::HttpSection;
+
+/// Version of the running binary, reported to the registry on every request
+const CLIENT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Header the registry uses to enforce its minimum supported CLI version
+const CLIENT_VERSION_HEADER: &str = "x-client-version";
+
+/// Header that lets the registry recognise a retried write and replay its first response
+pub const IDEMPOTENCY_KEY_HEADER: &str = "Idempotency-Key";
+
+/// Environment variable holding a session JWT from `/api/auth/verify` or an API key
+pub const TOKEN_ENV: &str = "SOROBAN_REGISTRY_TOKEN";
+
+/// `[http]` settings, loaded and checked once by [`configure`]
+static TRANSPORT: OnceLock<Transport> = OnceLock::new();
+
+/// Proxy, trust roots, client certificate and extra headers from `[http]`
+#[derive(Clone, Default)]
+struct Transport {
+    proxy: Option<reqwest::Proxy>,
+    root_certificates: Vec<reqwest::Certificate>,
+    identity: Option<reqwest::Identity>,
+    headers: HeaderMap,
+}
+
+impl Transport {
+    fn load(settings: &HttpSection) -> anyhow::Result<Self> {
+        let proxy = match settings.proxy.as_deref() {
+            Some(url) => {
+                let no_proxy = match settings.no_proxy.as_deref() {
+                    Some(list) => reqwest::NoProxy::from_string(list),
+                    None => reqwest::NoProxy::from_env(),
+                };
+                Some(
+                    reqwest::Proxy::all(url)
+                        .with_context(|| format!("Invalid http.proxy '{}'", url))?
+                        .no_proxy(no_proxy),
+                )
+            }
+            None => None,
+        };
+
+        let root_certificates = match &settings.ca_bundle {
+            Some(path) => {
+                let pem = std::fs::read(path)
+                    .with_context(|| format!("Failed to read http.ca_bundle {:?}", path))?;
+                reqwest::Certificate::from_pem_bundle(&pem)
+                    .with_context(|| format!("http.ca_bundle {:?} is not a PEM bundle", path))?
+            }
+            None => Vec::new(),
+        };
+
+        let identity = match &settings.client_cert {
+            Some(cert) => {
+                let mut pem = std::fs::read(cert)
+                    .with_context(|| format!("Failed to read http.client_cert {:?}", cert))?;
+                if let Some(key) = &settings.client_key {
+                    pem.push(b'\n');
+                    pem.extend(
+                        std::fs::read(key)
+                            .with_context(|| format!("Failed to read http.client_key {:?}", key))?,
+                    );
+                }
+                Some(reqwest::Identity::from_pem(&pem).context(
+                    "http.client_cert must hold a PEM certificate and private key \
+                     (or set http.client_key)",
+                )?)
+            }
+            None => None,
+        };
+
+        let mut headers = HeaderMap::new();
+        for (name, value) in &settings.headers {
+            let name = HeaderName::from_bytes(name.as_bytes())
+                .with_context(|| format!("Invalid header name in http.headers: '{}'", name))?;
+            let mut value = HeaderValue::from_str(value)
+                .with_context(|| format!("Invalid value for http.headers.{}", name))?;
+            value.set_sensitive(true);
+            headers.insert(name, value);
+        }
+
+        Ok(Transport {
+            proxy,
+            root_certificates,
+            identity,
+            headers,
+        })
+    }
+}
+
+/// Load `[http]` from the config file; called at startup so a bad path or
+/// certificate fails the command up front rather than on its first request
+pub fn configure() -> anyhow::Result<()> {
+    let transport = Transport::load(&crate::config::http_settings()?)?;
+    let _ = TRANSPORT.set(transport);
+    Ok(())
+}
+
+fn transport() -> &'static Transport {
+    TRANSPORT.get_or_init(Transport::default)
+}
+
+/// Client builder preconfigured with the CLI's `User-Agent` and `X-Client-Version`,
+/// plus the configured credential, client certificate and `[http.headers]` so
+/// private registries work transparently
+pub fn client_builder() -> reqwest::ClientBuilder {
+    let transport = transport();
+    let mut builder = anonymous_client_builder().default_headers(transport.headers.clone());
+    if let Some(identity) = &transport.identity {
+        builder = builder.identity(identity.clone());
+    }
+    if let Some(value) = configured_token().and_then(|token| bearer(&token)) {
+        let mut headers = base_headers();
+        headers.insert(AUTHORIZATION, value);
+        builder = builder.default_headers(headers);
+    }
+    builder
+}
+
+/// Like [`client_builder`] but never sends credentials; for downloads from
+/// hosts other than the registry. Proxy and CA settings still apply.
+fn anonymous_client_builder() -> reqwest::ClientBuilder {
+    let transport = transport();
+    let mut builder = reqwest::Client::builder()
+        .user_agent(format!("soroban-registry/{}", CLIENT_VERSION))
+        .default_headers(base_headers());
+    if let Some(proxy) = &transport.proxy {
+        builder = builder.proxy(proxy.clone());
+    }
+    for certificate in &transport.root_certificates {
+        builder = builder.add_root_certificate(certificate.clone());
+    }
+    builder
+}
+
+fn base_headers() -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        CLIENT_VERSION_HEADER,
+        HeaderValue::from_static(CLIENT_VERSION),
+    );
+    headers
+}
+
+fn bearer(token: &str) -> Option<HeaderValue> {
+    let mut value = HeaderValue::from_str(&format!("Bearer {}", token)).ok()?;
+    value.set_sensitive(true);
+    Some(value)
+}
+
+/// Drop-in for `reqwest::Client::new()` that identifies the CLI to the registry
+pub fn client() -> reqwest::Client {
+    client_builder()
+        .build()
+        .expect("failed to initialise the HTTP client")
+}
+
+/// Client without the configured credential, for third-party hosts
+pub fn anonymous_client() -> reqwest::Client {
+    anonymous_client_builder()
+        .build()
+        .expect("failed to initialise the HTTP client")
+}
+
+/// `SOROBAN_REGISTRY_TOKEN`, falling back to `defaults.token` in the config file
+pub(crate) fn configured_token() -> Option<String> {
+    match std::env::var(TOKEN_ENV) {
+        Ok(token) if !token.trim().is_empty() => Some(token.trim().to_string()),
+        _ => crate::config::profile_token(),
+    }
+}
+
+/// Bearer token for user-scoped endpoints such as saved searches
+pub fn auth_token() -> anyhow::Result<String> {
+    match configured_token() {
+        Some(token) => Ok(token),
+        None => anyhow::bail!(
+            "{} is not set and the config has no defaults.token; \
+             use a session token from /api/auth/verify or an API key",
+            TOKEN_ENV
+        ),
+    }
+}
+
+/// Fresh key for one logical write; reuse it when retrying that same write
+pub fn idempotency_key() -> String {
+    uuid::Uuid::new_v4().to_string()
+}
+
+/// The proof-of-work challenge in a 428 response: its token and difficulty
+fn pow_challenge(response: &reqwest::Response) -> Option<(String, u32)> {
+    let header = |name| response.headers().get(name)?.to_str().ok();
+    if response.status() != reqwest::StatusCode::PRECONDITION_REQUIRED
+        || shared::ChallengeKind::parse(header(shared::CHALLENGE_HEADER)?)
+            != Some(shared::ChallengeKind::Pow)
+    {
+        return None;
+    }
+    let difficulty = header(shared::CHALLENGE_DIFFICULTY_HEADER)?.parse().ok()?;
+    if difficulty > shared::MAX_POW_DIFFICULTY {
+        return None;
+    }
+    Some((
+        header(shared::CHALLENGE_TOKEN_HEADER)?.to_string(),
+        difficulty,
+    ))
+}
+
+/// Send an anonymous write, answering the registry's proof-of-work challenge
+/// if it asks for one. CAPTCHA challenges can't be answered from a terminal;
+/// their 428 is returned as is.
+pub async fn send_with_challenge(
+    request: reqwest::RequestBuilder,
+) -> anyhow::Result<reqwest::Response> {
+    let retry = request.try_clone();
+    let response = request.send().await?;
+    let (Some(retry), Some((token, difficulty))) = (retry, pow_challenge(&response)) else {
+        return Ok(response);
+    };
+    log::debug!(
+        "answering a proof-of-work challenge (difficulty {})",
+        difficulty
+    );
+    let solution = {
+        let token = token.clone();
+        tokio::task::spawn_blocking(move || shared::solve_pow(&token, difficulty)).await?
+    };
+    Ok(retry
+        .header(shared::CHALLENGE_TOKEN_HEADER, token)
+        .header(shared::CHALLENGE_SOLUTION_HEADER, solution)
+        .send()
+        .await?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transport_checks_headers_and_files_up_front() {
+        let mut settings = HttpSection::default();
+        settings
+            .headers
+            .insert("X-Gateway-Token".to_string(), "abc".to_string());
+        let transport = Transport::load(&settings).unwrap();
+        assert!(transport.headers["x-gateway-token"].is_sensitive());
+
+        settings
+            .headers
+            .insert("bad header".to_string(), "abc".to_string());
+        assert!(Transport::load(&settings).is_err());
+
+        let missing = HttpSection {
+            ca_bundle: Some("/nonexistent/ca.pem".into()),
+            ..Default::default()
+        };
+        let err = Transport::load(&missing).err().unwrap();
+        assert!(err.to_string().contains("http.ca_bundle"));
+    }
+}
@@ -0,0 +1,149 @@
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use shared::{IdentityChallenge, Publisher};
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use crate::keystore::Key;
+
+/// How the identity challenge gets signed
+pub enum IdentitySigner {
+    /// A stored key or a secret from the environment
+    Key(Key),
+    /// An external program, e.g. a hardware wallet bridge. It receives the message
+    /// on stdin and the account in `SOROBAN_REGISTRY_SIGN_ACCOUNT`, and prints the
+    /// hex ed25519 signature.
+    Command(String),
+}
+
+impl IdentitySigner {
+    fn sign(&self, account: &str, message: &str) -> Result<String> {
+        match self {
+            IdentitySigner::Key(key) => sign_with_key(key, account, message),
+            IdentitySigner::Command(command) => sign_with_command(command, account, message),
+        }
+    }
+}
+
+fn sign_with_key(key: &Key, account: &str, message: &str) -> Result<String> {
+    let key_account = key.account();
+    if key_account != account {
+        bail!(
+            "the key belongs to {} but the publisher's account is {}",
+            key_account,
+            account
+        );
+    }
+    Ok(hex::encode(key.sign(message.as_bytes())?))
+}
+
+fn sign_with_command(command: &str, account: &str, message: &str) -> Result<String> {
+    let mut parts = command.split_whitespace();
+    let program = parts.next().context("--signer is empty")?;
+    let mut child = Command::new(program)
+        .args(parts)
+        .env("SOROBAN_REGISTRY_SIGN_ACCOUNT", account)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("Failed to run signer `{}`", program))?;
+    child
+        .stdin
+        .take()
+        .context("signer stdin is unavailable")?
+        .write_all(message.as_bytes())?;
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        bail!("signer `{}` exited with {}", program, output.status);
+    }
+
+    let signature = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if signature.len() != 128 || hex::decode(&signature).is_err() {
+        bail!(
+            "signer `{}` must print a hex-encoded 64-byte signature",
+            program
+        );
+    }
+    Ok(signature)
+}
+
+/// Prove control of the publisher's Stellar account: fetch a challenge, sign it
+/// and submit the signature
+pub async fn verify_identity(
+    api_url: &str,
+    publisher_id: &str,
+    signer: IdentitySigner,
+) -> Result<()> {
+    let base = format!(
+        "{}/api/publishers/{}/verify-identity",
+        api_url.trim_end_matches('/'),
+        publisher_id
+    );
+    let client = crate::http::client();
+
+    let response = client
+        .post(format!("{}/challenge", base))
+        .send()
+        .await
+        .context("Failed to request an identity challenge")?;
+    if !response.status().is_success() {
+        return Err(crate::api_errors::failure(
+            response,
+            "Failed to request an identity challenge",
+        )
+        .await);
+    }
+    let challenge: IdentityChallenge = response.json().await?;
+
+    println!(
+        "Signing identity challenge for {}...",
+        challenge.account.bright_blue()
+    );
+    let signature = signer.sign(&challenge.account, &challenge.message)?;
+
+    let response = client
+        .post(&base)
+        .json(&serde_json::json!({ "signature": signature }))
+        .send()
+        .await
+        .context("Failed to submit the identity signature")?;
+    if !response.status().is_success() {
+        return Err(crate::api_errors::failure(response, "Identity verification failed").await);
+    }
+    let publisher: Publisher = response.json().await?;
+
+    println!(
+        "{} Verified Stellar account {}",
+        "✓".green().bold(),
+        publisher
+            .verified_account
+            .unwrap_or(challenge.account)
+            .bold()
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::{Signature, SigningKey, Verifier};
+
+    const SECRET: &str = "SADQOBYHA4DQOBYHA4DQOBYHA4DQOBYHA4DQOBYHA4DQOBYHA4DQP54X";
+
+    #[test]
+    fn key_signs_for_its_own_account_only() {
+        let key = SigningKey::from_bytes(&[7u8; 32]);
+        let account = shared::encode_account_id(key.verifying_key().as_bytes());
+        let secret = Key::from_secret(SECRET).unwrap();
+
+        let signature = sign_with_key(&secret, &account, "challenge").unwrap();
+        let bytes: [u8; 64] = hex::decode(signature).unwrap().try_into().unwrap();
+        assert!(key
+            .verifying_key()
+            .verify(b"challenge", &Signature::from_bytes(&bytes))
+            .is_ok());
+
+        let other = shared::encode_account_id(&[1u8; 32]);
+        assert!(sign_with_key(&secret, &other, "challenge").is_err());
+    }
+}
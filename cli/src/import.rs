@@ -1,17 +1,23 @@
 use std::fs::{self, File};
-use std::io::BufReader;
+use std::io::{BufReader, Read};
 use std::path::Path;
 
 use anyhow::{bail, Context, Result};
 use chrono::Utc;
+use colored::Colorize;
+use serde_json::{json, Value};
+use shared::{ErrorCode, ProblemDetails};
 
 use crate::io_utils::{compute_sha256_streaming, extract_tar_gz};
 use crate::manifest::{AuditEntry, ExportManifest};
+use crate::push::{LocalPart, PushOptions};
 
 pub fn extract_and_verify(archive_path: &Path, output_dir: &Path) -> Result<ExportManifest> {
     let tmp_dir = tempfile::tempdir().context("failed to create temp dir")?;
+    let mut progress = crate::progress::Operation::start("import", Some(3));
 
     extract_tar_gz(archive_path, tmp_dir.path())?;
+    progress.step("unpack", fs::metadata(archive_path).ok().map(|m| m.len()));
 
     let manifest_path = tmp_dir.path().join("manifest.json");
     let inner_path = tmp_dir.path().join("contract.tar.gz");
@@ -31,6 +37,7 @@ pub fn extract_and_verify(archive_path: &Path, output_dir: &Path) -> Result<Expo
             computed_hash
         );
     }
+    progress.step("verify", fs::metadata(&inner_path).ok().map(|m| m.len()));
 
     manifest.audit_trail.push(AuditEntry {
         action: "import_verified".into(),
@@ -40,6 +47,11 @@ pub fn extract_and_verify(archive_path: &Path, output_dir: &Path) -> Result<Expo
 
     fs::create_dir_all(output_dir)?;
     extract_tar_gz(&inner_path, output_dir)?;
+    progress.step(
+        "extract",
+        Some(manifest.contents.iter().map(|e| e.size).sum()),
+    );
+    progress.finish();
 
     manifest.audit_trail.push(AuditEntry {
         action: "import_extracted".into(),
@@ -49,3 +61,252 @@ pub fn extract_and_verify(archive_path: &Path, output_dir: &Path) -> Result<Expo
 
     Ok(manifest)
 }
+
+/// Unpack and verify `archive` into `output_dir`, whichever layout it has: a
+/// streamed `.tar.zst` (read in one pass) or the nested `.tar.gz`. `-` reads
+/// stdin; a `.tar.gz` arriving there is spooled to a temp file first.
+pub fn extract_archive(archive: &str, output_dir: &Path) -> Result<ExportManifest> {
+    let mut input: Box<dyn Read> = if archive == crate::stream_archive::STDIO {
+        Box::new(std::io::stdin().lock())
+    } else {
+        Box::new(File::open(archive).with_context(|| format!("archive not found: {}", archive))?)
+    };
+    let mut magic = [0u8; 4];
+    let read = read_up_to(&mut input, &mut magic)?;
+    let input = std::io::Cursor::new(magic[..read].to_vec()).chain(input);
+
+    if crate::stream_archive::has_zstd_magic(&magic[..read]) {
+        return crate::stream_archive::read(BufReader::new(input), output_dir);
+    }
+    if archive != crate::stream_archive::STDIO {
+        return extract_and_verify(Path::new(archive), output_dir);
+    }
+    let mut spooled = tempfile::NamedTempFile::new().context("failed to create temp file")?;
+    std::io::copy(&mut BufReader::new(input), &mut spooled)?;
+    extract_and_verify(spooled.path(), output_dir)
+}
+
+/// Fill `buf` unless the input ends first; returns how much was read
+fn read_up_to(input: &mut impl Read, buf: &mut [u8]) -> Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match input.read(&mut buf[filled..])? {
+            0 => break,
+            n => filled += n,
+        }
+    }
+    Ok(filled)
+}
+
+/// Registry metadata written by `export --from-registry`; local source exports lack it
+const METADATA_FILE: &str = "metadata.json";
+const MANIFEST_SCHEMA_VERSION: &str = "1.0";
+
+/// Metadata of an extracted registry archive, checked against its manifest
+pub fn read_registry_metadata(dir: &Path, manifest: &ExportManifest) -> Result<Value> {
+    if manifest.schema_version != MANIFEST_SCHEMA_VERSION {
+        bail!(
+            "unsupported archive schema {} (expected {})",
+            manifest.schema_version,
+            MANIFEST_SCHEMA_VERSION
+        );
+    }
+    let path = dir.join(METADATA_FILE);
+    if !path.is_file() {
+        bail!(
+            "archive has no {}; re-export it with --from-registry",
+            METADATA_FILE
+        );
+    }
+    let metadata: Value = serde_json::from_reader(BufReader::new(File::open(&path)?))
+        .with_context(|| format!("invalid {}", METADATA_FILE))?;
+    let contract_id = metadata["contract"]["contract_id"]
+        .as_str()
+        .unwrap_or_default();
+    if contract_id != manifest.contract_id {
+        bail!(
+            "{} describes contract {:?} but the manifest names {}",
+            METADATA_FILE,
+            contract_id,
+            manifest.contract_id
+        );
+    }
+    Ok(metadata)
+}
+
+/// `POST /api/contracts` body re-registering `contract` on `network`
+fn registration_payload(contract: &Value, network: &str, publisher: &str) -> Value {
+    let name = match contract["namespace"].as_str() {
+        Some(namespace) => format!("@{}/{}", namespace, contract["name"].as_str().unwrap_or("")),
+        None => contract["name"].as_str().unwrap_or("").to_string(),
+    };
+    json!({
+        "contract_id": contract["contract_id"],
+        "name": name,
+        "description": contract["description"],
+        "network": network,
+        "category": contract["category"],
+        "tags": contract["tags"],
+        "publisher_address": publisher,
+        "license": contract["license"],
+        "repository_url": contract["repository_url"],
+        "homepage": contract["homepage"],
+        "documentation_url": contract["documentation_url"],
+    })
+}
+
+/// Publish-session parts present in the extracted archive
+fn archive_parts(dir: &Path) -> Vec<LocalPart> {
+    [
+        ("wasm", "contract.wasm"),
+        ("abi", "abi.json"),
+        ("readme", "README.md"),
+        ("signature", "contract.wasm.sig"),
+    ]
+    .into_iter()
+    .map(|(kind, file)| LocalPart {
+        kind,
+        path: dir.join(file),
+    })
+    .filter(|part| part.path.is_file())
+    .collect()
+}
+
+/// `import --publish`: register the archived contract on the target registry
+/// (reusing an existing registration) and publish its version through a
+/// staged session, so a version that already exists there is refused.
+/// With `dry_run` the registration is only validated and nothing is staged.
+pub async fn publish(
+    api_url: &str,
+    dir: &Path,
+    manifest: &ExportManifest,
+    network: &str,
+    publisher: &str,
+    dry_run: bool,
+) -> Result<()> {
+    let metadata = read_registry_metadata(dir, manifest)?;
+    let url = format!("{}/api/contracts", api_url.trim_end_matches('/'));
+
+    let request = crate::http::client().post(&url);
+    let request = if dry_run {
+        request.query(&crate::dry_run::QUERY)
+    } else {
+        request.header(
+            crate::http::IDEMPOTENCY_KEY_HEADER,
+            crate::http::idempotency_key(),
+        )
+    };
+    let response = request
+        .json(&registration_payload(
+            &metadata["contract"],
+            network,
+            publisher,
+        ))
+        .send()
+        .await
+        .context("Failed to register contract")?;
+    if response.status().is_success() && dry_run {
+        crate::dry_run::validated("POST", &url);
+        crate::dry_run::would(format!("register {} on {}", manifest.contract_id, network));
+    } else if response.status().is_success() {
+        println!(
+            "{} Registered {} on {}",
+            "✓".green(),
+            manifest.contract_id,
+            network
+        );
+    } else {
+        let status = response.status();
+        let body = response.text().await?;
+        let already_registered = serde_json::from_str::<ProblemDetails>(&body)
+            .is_ok_and(|p| p.code == ErrorCode::from("ContractAlreadyRegistered"));
+        if !already_registered {
+            return Err(
+                crate::api_errors::classify(status, &body, "Failed to register contract").into(),
+            );
+        }
+        println!(
+            "  {} {} is already registered on {}",
+            "·".bright_black(),
+            manifest.contract_id,
+            network
+        );
+    }
+
+    let version = &metadata["version"];
+    let Some(number) = version["version"].as_str() else {
+        println!(
+            "  {} archive has no published version; nothing else to push",
+            "·".bright_black()
+        );
+        return Ok(());
+    };
+    // An edited release_notes.md in the archive wins over the exported metadata
+    let notes_file = dir.join(shared::RELEASE_NOTES_FILE);
+    let release_notes = if notes_file.is_file() {
+        Some(crate::push::read_release_notes(&notes_file)?)
+    } else {
+        version["release_notes"].as_str().map(str::to_string)
+    };
+    let options = PushOptions {
+        source_url: version["source_url"].as_str().map(str::to_string),
+        commit_hash: version["commit_hash"].as_str().map(str::to_string),
+        release_notes,
+        cargo_lock: None,
+        toolchain: None,
+    };
+    if dry_run {
+        for part in archive_parts(dir) {
+            let size = fs::metadata(&part.path)?.len();
+            crate::dry_run::would(format!("stage {} ({} bytes)", part.kind, size));
+        }
+        crate::dry_run::would(format!("commit version {} in a publish session", number));
+        return Ok(());
+    }
+    crate::push::push(
+        api_url,
+        &manifest.contract_id,
+        number,
+        archive_parts(dir),
+        options,
+        false,
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registration_keeps_the_namespace_and_targets_the_new_network() {
+        let contract = json!({
+            "contract_id": "CABC",
+            "name": "amm",
+            "namespace": "acme",
+            "network": "testnet",
+            "tags": ["dex"],
+            "homepage": "https://acme.example",
+        });
+        let payload = registration_payload(&contract, "mainnet", "GPUB");
+        assert_eq!(payload["name"], "@acme/amm");
+        assert_eq!(payload["network"], "mainnet");
+        assert_eq!(payload["publisher_address"], "GPUB");
+        assert_eq!(payload["homepage"], "https://acme.example");
+    }
+
+    #[test]
+    fn metadata_must_match_the_manifest() {
+        let dir = tempfile::tempdir().unwrap();
+        let manifest = ExportManifest::new("CABC".into(), "amm".into(), "testnet".into());
+        assert!(read_registry_metadata(dir.path(), &manifest).is_err());
+
+        let metadata = json!({ "contract": { "contract_id": "COTHER" } });
+        fs::write(dir.path().join(METADATA_FILE), metadata.to_string()).unwrap();
+        assert!(read_registry_metadata(dir.path(), &manifest).is_err());
+
+        let metadata = json!({ "contract": { "contract_id": "CABC" } });
+        fs::write(dir.path().join(METADATA_FILE), metadata.to_string()).unwrap();
+        assert!(read_registry_metadata(dir.path(), &manifest).is_ok());
+    }
+}
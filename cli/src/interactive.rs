@@ -0,0 +1,78 @@
+use std::io::IsTerminal;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::cli_error::CliError;
+
+/// Set by the global `--yes` / `--non-interactive` flag
+static ASSUME_YES: AtomicBool = AtomicBool::new(false);
+
+pub fn set_assume_yes(yes: bool) {
+    ASSUME_YES.store(yes, Ordering::Relaxed);
+}
+
+/// Whether prompts take their default and confirmations are accepted
+pub fn assume_yes() -> bool {
+    ASSUME_YES.load(Ordering::Relaxed)
+}
+
+/// Someone can answer a prompt: not running with `--yes`, and stdin is a terminal
+pub fn can_prompt() -> bool {
+    !assume_yes() && std::io::stdin().is_terminal()
+}
+
+/// Answer for a prompt when no one can type one: `Ok(None)` means ask as usual,
+/// `--yes` takes `default`, and anything else is a usage error naming the input
+pub fn unattended_answer(label: &str, default: Option<String>) -> anyhow::Result<Option<String>> {
+    if can_prompt() {
+        return Ok(None);
+    }
+    match (default, assume_yes()) {
+        (Some(default), true) => Ok(Some(default)),
+        (None, true) => Err(CliError::Usage(format!(
+            "'{}' has no default, so --yes cannot answer it; pass the value as a flag instead",
+            label
+        ))
+        .into()),
+        (_, false) => Err(CliError::Usage(format!(
+            "'{}' needs input but stdin is not a terminal; pass --yes to accept defaults",
+            label
+        ))
+        .into()),
+    }
+}
+
+/// Fail fast before work that needs a person at the keyboard, such as an
+/// editor session; `alternative` tells CI users what to do instead
+pub fn require_terminal(what: &str, alternative: &str) -> anyhow::Result<()> {
+    if can_prompt() {
+        return Ok(());
+    }
+    let reason = if assume_yes() {
+        "--yes was given"
+    } else {
+        "stdin is not a terminal"
+    };
+    Err(CliError::Usage(format!(
+        "{} needs interactive input but {}; {}",
+        what, reason, alternative
+    ))
+    .into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn yes_takes_defaults_and_rejects_required_input() {
+        set_assume_yes(true);
+        assert_eq!(
+            unattended_answer("Network", Some("testnet".to_string())).unwrap(),
+            Some("testnet".to_string())
+        );
+        let err = unattended_answer("Contract name", None).unwrap_err();
+        assert_eq!(crate::cli_error::exit_code(&err), crate::cli_error::USAGE);
+        assert!(require_terminal("Editing", "use --file").is_err());
+        set_assume_yes(false);
+    }
+}
@@ -0,0 +1,514 @@
+//! `invoke`: call a function of a published contract in the registry's
+//! simulation sandbox. Without arguments it walks through each parameter of
+//! the stored spec, checking input against the parameter's type, and prints
+//! the equivalent one-liner for reuse.
+
+use std::io::{self, Write};
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use serde_json::Value;
+use shared::{format_type, AbiSyntax, SimulateRequest, SimulationResult};
+
+use crate::conversions::contract_path;
+
+/// The stored spec of the latest (or a given) version
+pub async fn fetch_abi(
+    api_url: &str,
+    contract_id: &str,
+    version: Option<&str>,
+) -> Result<Vec<Value>> {
+    let url = format!(
+        "{}/api/contracts/{}/abi",
+        api_url.trim_end_matches('/'),
+        contract_path(contract_id)
+    );
+    let response = crate::http::client()
+        .get(&url)
+        .query(&[("version", version)])
+        .send()
+        .await
+        .context("Failed to fetch contract ABI")?;
+    if !response.status().is_success() {
+        return Err(crate::api_errors::failure(response, "Failed to fetch contract ABI").await);
+    }
+    let body: Value = response.json().await?;
+    Ok(body["abi"].as_array().cloned().unwrap_or_default())
+}
+
+fn functions(abi: &[Value]) -> impl Iterator<Item = &Value> {
+    abi.iter().filter(|entry| entry["type"] == "function")
+}
+
+fn integer<T: std::str::FromStr>(input: &str, type_name: &str) -> Result<(), String> {
+    input
+        .parse::<T>()
+        .map(|_| ())
+        .map_err(|_| format!("expected a whole number that fits in {}", type_name))
+}
+
+fn hex_bytes(input: &str) -> Result<Vec<u8>, String> {
+    let digits = input
+        .strip_prefix("0x")
+        .or_else(|| input.strip_prefix("0X"))
+        .unwrap_or(input);
+    hex::decode(digits).map_err(|_| "expected hex bytes, e.g. 0xdeadbeef".to_string())
+}
+
+/// Text of a JSON element as a standalone argument: strings unquoted
+fn element_text(element: &Value) -> String {
+    match element {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// A user-defined type's definition in the spec
+fn definition<'a>(abi: &'a [Value], name: &str) -> Option<&'a Value> {
+    abi.iter()
+        .find(|entry| entry["type"] != "function" && entry["name"] == name)
+}
+
+/// Check `input` against a spec type, in the argument syntax of `simulate`:
+/// numbers and addresses as plain text, vectors, maps and structs as JSON.
+/// Types this can't check are left to the registry.
+fn check_arg(input: &str, ty: &Value, abi: &[Value]) -> Result<(), String> {
+    let input = input.trim();
+    let name = ty["type"].as_str().unwrap_or("");
+    match name.to_lowercase().as_str() {
+        "bool" => match input {
+            "true" | "false" | "1" | "0" => Ok(()),
+            _ => Err("expected true or false".to_string()),
+        },
+        "u32" => integer::<u32>(input, "u32"),
+        "i32" => integer::<i32>(input, "i32"),
+        "u64" | "timepoint" | "duration" => integer::<u64>(input, "u64"),
+        "i64" => integer::<i64>(input, "i64"),
+        "u128" => integer::<u128>(input, "u128"),
+        "i128" => integer::<i128>(input, "i128"),
+        "u256" | "i256" => {
+            let digits = match input.strip_prefix('-') {
+                Some(digits) if name.eq_ignore_ascii_case("i256") => digits,
+                _ => input,
+            };
+            if !digits.is_empty()
+                && digits.len() <= 78
+                && digits.bytes().all(|b| b.is_ascii_digit())
+            {
+                Ok(())
+            } else {
+                Err(format!("expected a whole number that fits in {}", name))
+            }
+        }
+        "symbol" => {
+            if input.is_empty()
+                || input.len() > 32
+                || !input.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+            {
+                Err("expected a symbol: up to 32 of a-z, A-Z, 0-9 and _".to_string())
+            } else {
+                Ok(())
+            }
+        }
+        "string" | "val" | "void" | "()" => Ok(()),
+        "address" => {
+            if shared::decode_account_id(input).is_some()
+                || shared::decode_contract_id(input).is_some()
+            {
+                Ok(())
+            } else {
+                Err("expected a G... account or C... contract address".to_string())
+            }
+        }
+        "bytes" => hex_bytes(input).map(|_| ()),
+        "bytesn" | "bytes_n" => {
+            let n = ty["n"].as_u64().unwrap_or(32) as usize;
+            match hex_bytes(input)? {
+                bytes if bytes.len() == n => Ok(()),
+                bytes => Err(format!("expected {} bytes, got {}", n, bytes.len())),
+            }
+        }
+        "option" => match input {
+            "" | "null" => Ok(()),
+            _ => check_arg(input, &ty["element"], abi),
+        },
+        "vec" => {
+            let elements: Vec<Value> = serde_json::from_str(input)
+                .map_err(|_| "expected a JSON array, e.g. [\"1\", \"2\"]".to_string())?;
+            for (i, element) in elements.iter().enumerate() {
+                check_arg(&element_text(element), &ty["element"], abi)
+                    .map_err(|reason| format!("element {}: {}", i, reason))?;
+            }
+            Ok(())
+        }
+        "map" => serde_json::from_str::<serde_json::Map<String, Value>>(input)
+            .map(|_| ())
+            .map_err(|_| "expected a JSON object".to_string()),
+        _ => match definition(abi, name) {
+            Some(def) if def["type"] == "struct" => {
+                let object: serde_json::Map<String, Value> = serde_json::from_str(input)
+                    .map_err(|_| format!("expected a JSON object for {}", name))?;
+                for field in def["fields"].as_array().into_iter().flatten() {
+                    let field_name = field["name"].as_str().unwrap_or("");
+                    let value = object
+                        .get(field_name)
+                        .ok_or_else(|| format!("missing field `{}`", field_name))?;
+                    check_arg(&element_text(value), &field["value"], abi)
+                        .map_err(|reason| format!("{}: {}", field_name, reason))?;
+                }
+                Ok(())
+            }
+            Some(def) if def["type"] == "enum" || def["type"] == "error_enum" => {
+                let known = def["cases"].as_array().into_iter().flatten().any(|case| {
+                    case["name"] == input
+                        || case["value"].as_u64().map(|v| v.to_string()).as_deref() == Some(input)
+                });
+                if known {
+                    Ok(())
+                } else {
+                    Err(format!("expected one of the {} variants", name))
+                }
+            }
+            _ => Ok(()),
+        },
+    }
+}
+
+/// POSIX-shell quoting for the printed one-liner
+fn shell_quote(arg: &str) -> String {
+    let plain = !arg.is_empty()
+        && arg
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./:@=+,".contains(c));
+    if plain {
+        arg.to_string()
+    } else {
+        format!("'{}'", arg.replace('\'', "'\\''"))
+    }
+}
+
+/// The command that repeats this call without prompts
+fn one_liner(contract_id: &str, function: &str, args: &[String], version: Option<&str>) -> String {
+    let mut parts = vec![
+        "soroban-registry".to_string(),
+        "invoke".to_string(),
+        shell_quote(contract_id),
+        shell_quote(function),
+    ];
+    parts.extend(args.iter().map(|arg| shell_quote(arg)));
+    if let Some(version) = version {
+        parts.push(format!("--version {}", shell_quote(version)));
+    }
+    parts.join(" ")
+}
+
+fn prompt(label: &str) -> Result<String> {
+    print!("{}: ", label.bold());
+    io::stdout().flush().ok();
+    let mut buf = String::new();
+    if io::stdin().read_line(&mut buf)? == 0 {
+        anyhow::bail!("Input ended before every argument was given");
+    }
+    Ok(buf.trim().to_string())
+}
+
+/// Ask until the answer checks out against `ty`
+fn prompt_checked(label: &str, ty: &Value, abi: &[Value]) -> Result<String> {
+    loop {
+        let answer = prompt(label)?;
+        match check_arg(&answer, ty, abi) {
+            Ok(()) => return Ok(answer),
+            Err(reason) => println!("  {} {}", "✗".red(), reason.red()),
+        }
+    }
+}
+
+/// Vectors are entered one element per line and sent as a JSON array
+fn prompt_vec(name: &str, ty: &Value, abi: &[Value]) -> Result<String> {
+    let element_type = format_type(&ty["element"], AbiSyntax::Rust);
+    println!(
+        "  {}",
+        format!(
+            "Enter {} elements one per line; blank to finish",
+            element_type
+        )
+        .bright_black()
+    );
+    let mut elements = Vec::new();
+    loop {
+        let answer = prompt(&format!("{}[{}]", name, elements.len()))?;
+        if answer.is_empty() {
+            break;
+        }
+        match check_arg(&answer, &ty["element"], abi) {
+            // Nested JSON stays JSON; everything else is a string element
+            Ok(()) => elements.push(
+                serde_json::from_str::<Value>(&answer)
+                    .ok()
+                    .filter(|value| value.is_array() || value.is_object())
+                    .unwrap_or(Value::String(answer)),
+            ),
+            Err(reason) => println!("  {} {}", "✗".red(), reason.red()),
+        }
+    }
+    Ok(Value::Array(elements).to_string())
+}
+
+/// Walk through `function`'s parameters
+fn build_args(abi: &[Value], function: &Value) -> Result<Vec<String>> {
+    println!(
+        "\n{} {}",
+        "Arguments for".bold().cyan(),
+        shared::format_function(function, AbiSyntax::Rust).bold()
+    );
+    let mut args = Vec::new();
+    for param in function["inputs"].as_array().into_iter().flatten() {
+        let name = param["name"].as_str().unwrap_or("arg");
+        let ty = &param["value"];
+        if let Some(doc) = param["doc"].as_str().filter(|doc| !doc.trim().is_empty()) {
+            println!("  {}", doc.trim().bright_black());
+        }
+        let value = if ty["type"]
+            .as_str()
+            .is_some_and(|t| t.eq_ignore_ascii_case("vec"))
+        {
+            prompt_vec(name, ty, abi)?
+        } else {
+            let label = format!("{}: {}", name, format_type(ty, AbiSyntax::Rust));
+            prompt_checked(&label, ty, abi)?
+        };
+        args.push(value);
+    }
+    Ok(args)
+}
+
+fn choose_function(abi: &[Value]) -> Result<&Value> {
+    let available: Vec<&Value> = functions(abi).collect();
+    if available.is_empty() {
+        anyhow::bail!("The contract's spec has no functions");
+    }
+    println!("\n{}", "Functions".bold().cyan());
+    for (i, function) in available.iter().enumerate() {
+        println!(
+            "  {}. {}",
+            i + 1,
+            shared::format_function(function, AbiSyntax::Rust)
+        );
+    }
+    loop {
+        let answer = prompt("Function (number or name)")?;
+        let chosen = match answer.parse::<usize>() {
+            Ok(n) if (1..=available.len()).contains(&n) => Some(available[n - 1]),
+            _ => available
+                .iter()
+                .copied()
+                .find(|f| f["name"] == answer.as_str()),
+        };
+        match chosen {
+            Some(function) => return Ok(function),
+            None => println!("  {} no function {}", "✗".red(), answer),
+        }
+    }
+}
+
+/// Print a sandbox call's result, events and resource use
+pub fn print_outcome(label: &str, step: &SimulationResult) {
+    if step.success {
+        let result = step
+            .result
+            .as_ref()
+            .map(|value| value.to_string())
+            .unwrap_or_else(|| "()".to_string());
+        println!("  {} {} → {}", "✓".green(), label, result);
+        if let Some(xdr) = &step.result_xdr {
+            println!("       {} {}", "xdr".bright_black(), xdr);
+        }
+    } else {
+        println!(
+            "  {} {}: {}",
+            "✗".red(),
+            label,
+            step.error.as_deref().unwrap_or("failed").red()
+        );
+    }
+    for event in &step.events {
+        println!(
+            "       {} {} {}",
+            "event".bright_black(),
+            Value::from(event.topics.clone()),
+            event.data
+        );
+    }
+    println!(
+        "       {}",
+        format!(
+            "{} CPU instructions, {} bytes",
+            step.resources.cpu_instructions, step.resources.memory_bytes
+        )
+        .bright_black()
+    );
+}
+
+/// `invoke <contract> [function] [args...]`
+pub async fn run(
+    api_url: &str,
+    contract_id: &str,
+    function: Option<&str>,
+    mut args: Vec<String>,
+    version: Option<&str>,
+    json: bool,
+) -> Result<()> {
+    let function = if function.is_none() || args.is_empty() {
+        let abi = fetch_abi(api_url, contract_id, version).await?;
+        let entry = match function {
+            Some(name) => functions(&abi)
+                .find(|f| f["name"] == name)
+                .with_context(|| format!("{} has no function {}", contract_id, name))?,
+            None => {
+                crate::interactive::require_terminal(
+                    "Choosing a function",
+                    "pass the function name after the contract",
+                )?;
+                choose_function(&abi)?
+            }
+        };
+        let takes_args = entry["inputs"]
+            .as_array()
+            .is_some_and(|inputs| !inputs.is_empty());
+        if takes_args {
+            crate::interactive::require_terminal(
+                "Building arguments",
+                "pass them after the function name",
+            )?;
+            args = build_args(&abi, entry)?;
+        }
+        let name = entry["name"].as_str().unwrap_or_default().to_string();
+        if takes_args || function.is_none() {
+            println!(
+                "\n  {}\n  {}",
+                "Run it again with:".bright_black(),
+                one_liner(contract_id, &name, &args, version).cyan()
+            );
+        }
+        name
+    } else {
+        function.unwrap_or_default().to_string()
+    };
+
+    let url = format!(
+        "{}/api/contracts/{}/simulate",
+        api_url.trim_end_matches('/'),
+        contract_path(contract_id)
+    );
+    let response = crate::http::client()
+        .post(&url)
+        .json(&SimulateRequest {
+            function: function.clone(),
+            args,
+            version: version.map(str::to_string),
+        })
+        .send()
+        .await
+        .context("Failed to invoke contract")?;
+    if !response.status().is_success() {
+        return Err(crate::api_errors::failure(response, "Failed to invoke contract").await);
+    }
+    let result: SimulationResult = response.json().await?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&result)?);
+    } else {
+        println!(
+            "\n{} {}@{} (sandbox)",
+            "Invoked".bold().cyan(),
+            contract_id,
+            result.version
+        );
+        print_outcome(&function, &result);
+    }
+    if !result.success {
+        anyhow::bail!("{} failed", function);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn abi() -> Vec<Value> {
+        json!([
+            {"type": "struct", "name": "Order", "fields": [
+                {"name": "amount", "value": {"type": "i128"}},
+                {"name": "side", "value": {"type": "Side"}}
+            ]},
+            {"type": "enum", "name": "Side", "cases": [
+                {"name": "Buy", "value": 0},
+                {"name": "Sell", "value": 1}
+            ]}
+        ])
+        .as_array()
+        .cloned()
+        .unwrap()
+    }
+
+    fn check(input: &str, ty: Value) -> Result<(), String> {
+        check_arg(input, &ty, &abi())
+    }
+
+    #[test]
+    fn scalars_are_checked_by_type() {
+        assert!(check(
+            "-170141183460469231731687303715884105728",
+            json!({"type": "i128"})
+        )
+        .is_ok());
+        assert!(check(
+            "170141183460469231731687303715884105728",
+            json!({"type": "i128"})
+        )
+        .is_err());
+        assert!(check("-1", json!({"type": "u32"})).is_err());
+        assert!(check("swap_exact", json!({"type": "symbol"})).is_ok());
+        assert!(check("swap exact", json!({"type": "symbol"})).is_err());
+        assert!(check(&"a".repeat(33), json!({"type": "symbol"})).is_err());
+
+        let account = shared::encode_account_id(&[1u8; 32]);
+        let contract = shared::encode_contract_id(&[1u8; 32]);
+        assert!(check(&account, json!({"type": "address"})).is_ok());
+        assert!(check(&contract, json!({"type": "Address"})).is_ok());
+        assert!(check(&account.replace('G', "C"), json!({"type": "address"})).is_err());
+        assert!(check("0xdead", json!({"type": "bytesn", "n": 2})).is_ok());
+        assert!(check("0xdead", json!({"type": "bytesn", "n": 4})).is_err());
+    }
+
+    #[test]
+    fn vectors_and_structs_are_checked_element_by_element() {
+        let amounts = json!({"type": "vec", "element": {"type": "i128"}});
+        assert!(check(r#"["1", 2, "-3"]"#, amounts.clone()).is_ok());
+        let err = check(r#"["1", "x"]"#, amounts.clone()).unwrap_err();
+        assert!(err.starts_with("element 1"), "{}", err);
+        assert!(check("1, 2", amounts).is_err());
+
+        let order = json!({"type": "Order"});
+        assert!(check(r#"{"amount": "5", "side": "Buy"}"#, order.clone()).is_ok());
+        assert!(check(r#"{"amount": "5", "side": "Hold"}"#, order.clone()).is_err());
+        assert_eq!(
+            check(r#"{"side": "Sell"}"#, order).unwrap_err(),
+            "missing field `amount`"
+        );
+    }
+
+    #[test]
+    fn one_liner_quotes_what_the_shell_would_split() {
+        let args = vec![
+            r#"["1","2"]"#.to_string(),
+            "it's".to_string(),
+            "100".to_string(),
+        ];
+        assert_eq!(
+            one_liner("@alice/amm", "swap", &args, Some("1.2.0")),
+            r#"soroban-registry invoke @alice/amm swap '["1","2"]' 'it'\''s' 100 --version 1.2.0"#
+        );
+    }
+}
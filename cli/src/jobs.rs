@@ -0,0 +1,156 @@
+//! Registry jobs (verifications, reindexes) polled through `GET /api/jobs/:id`:
+//! `--wait` on the commands that start one, and `jobs <id>` to check later.
+
+use std::io::{IsTerminal, Write};
+use std::time::{Duration, Instant};
+
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use shared::{JobState, JobStatus};
+
+use crate::cli_error::CliError;
+
+/// How long `--wait` blocks unless `--timeout` says otherwise
+pub const DEFAULT_TIMEOUT_SECS: u64 = 600;
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+const SPINNER: [char; 4] = ['|', '/', '-', '\\'];
+
+fn state_label(state: JobState) -> &'static str {
+    match state {
+        JobState::Queued => "queued",
+        JobState::Running => "running",
+        JobState::Succeeded => "succeeded",
+        JobState::Failed => "failed",
+    }
+}
+
+pub async fn fetch(api_url: &str, id: &str) -> Result<JobStatus> {
+    let url = format!("{}/api/jobs/{}", api_url.trim_end_matches('/'), id);
+    let response = crate::http::client()
+        .get(&url)
+        .send()
+        .await
+        .context("Failed to fetch job status")?;
+    if !response.status().is_success() {
+        return Err(crate::api_errors::failure(response, "Failed to fetch job status").await);
+    }
+    response.json().await.context("Failed to parse job status")
+}
+
+/// Poll `id` until it finishes, with a spinner on a terminal; gives up with
+/// a timeout error after `timeout`
+pub async fn wait(api_url: &str, id: &str, timeout: Duration) -> Result<JobStatus> {
+    let started = Instant::now();
+    let spin = std::io::stderr().is_terminal();
+    let progress = crate::progress::Operation::start("job", None);
+    let mut last_state = None;
+    let mut frame = 0;
+    loop {
+        let job = fetch(api_url, id).await?;
+        if last_state != Some(job.state) {
+            progress.status(state_label(job.state));
+            last_state = Some(job.state);
+        }
+        if spin {
+            eprint!(
+                "\r{} {:?} {} ({}s)  ",
+                SPINNER[frame % SPINNER.len()].to_string().cyan(),
+                job.kind,
+                state_label(job.state),
+                started.elapsed().as_secs()
+            );
+            let _ = std::io::stderr().flush();
+            frame += 1;
+        }
+        if job.state.is_finished() {
+            if spin {
+                eprint!("\r\x1b[2K");
+            }
+            progress.finish();
+            return Ok(job);
+        }
+        if started.elapsed() >= timeout {
+            if spin {
+                eprintln!();
+            }
+            return Err(CliError::Timeout(format!(
+                "job {} is still {} after {}s; check it later with `soroban-registry jobs {}`",
+                id,
+                state_label(job.state),
+                timeout.as_secs(),
+                id
+            ))
+            .into());
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+}
+
+fn print_job(job: &JobStatus) {
+    let state = match job.state {
+        JobState::Succeeded => state_label(job.state).green(),
+        JobState::Failed => state_label(job.state).red(),
+        _ => state_label(job.state).yellow(),
+    };
+    println!("  {}: {}", "Job".bold(), job.id.to_string().bright_black());
+    println!("  {}: {:?}", "Kind".bold(), job.kind);
+    println!("  {}: {}", "State".bold(), state);
+    if let Some(subject) = &job.subject {
+        println!("  {}: {}", "Subject".bold(), subject);
+    }
+    println!("  {}: {}", "Started".bold(), job.created_at);
+    if let Some(finished) = job.finished_at {
+        println!("  {}: {}", "Finished".bold(), finished);
+    }
+    if let Some(error) = &job.error {
+        println!("  {}: {}", "Error".bold(), error.red());
+    }
+}
+
+/// Report a finished job; a failed job fails the command
+pub fn report(job: &JobStatus, json: bool) -> Result<()> {
+    if json {
+        println!("{}", serde_json::to_string_pretty(job)?);
+    } else {
+        print_job(job);
+    }
+    if job.state == JobState::Failed {
+        bail!(
+            "job {} failed: {}",
+            job.id,
+            job.error.as_deref().unwrap_or("no reason given")
+        );
+    }
+    Ok(())
+}
+
+/// `jobs <id>`: show a job's state, or block until it finishes with `--wait`
+pub async fn show(api_url: &str, id: &str, wait_for: Option<Duration>, json: bool) -> Result<()> {
+    let job = match wait_for {
+        Some(timeout) => wait(api_url, id, timeout).await?,
+        None => fetch(api_url, id).await?,
+    };
+    report(&job, json)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn job_status_round_trips_in_the_api_shape() {
+        let job: JobStatus = serde_json::from_value(serde_json::json!({
+            "id": "6f1c2a9e-3b7d-4c5e-9a1f-0d2e3c4b5a69",
+            "kind": "verification",
+            "state": "failed",
+            "subject": "CABC",
+            "error": "bytecode mismatch",
+            "created_at": "2026-01-01T00:00:00Z",
+            "finished_at": null,
+        }))
+        .unwrap();
+        assert!(job.state.is_finished());
+        assert!(report(&job, true).is_err());
+        assert_eq!(state_label(job.state), "failed");
+    }
+}
@@ -0,0 +1,533 @@
+use anyhow::{bail, Context, Result};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use chacha20poly1305::{
+    aead::{Aead, KeyInit, Payload},
+    ChaCha20Poly1305, Key as CipherKey, Nonce,
+};
+use chrono::{DateTime, Utc};
+use colored::Colorize;
+use ed25519_dalek::{Signer, SigningKey};
+use rand::{rngs::OsRng, RngCore};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Passphrase for local keys; prompted for when unset
+pub const PASSPHRASE_ENV: &str = "SOROBAN_REGISTRY_KEY_PASSPHRASE";
+
+/// Secret used by commands given no stored key, and by `keys import` instead
+/// of prompting
+pub const SECRET_KEY_ENV: &str = "SOROBAN_REGISTRY_SECRET_KEY";
+
+const KEYS_DIR_NAME: &str = "keys";
+const MAX_NAME_LENGTH: usize = 64;
+
+/// PBKDF2-HMAC-SHA256 rounds for new keys; stored per key so it can be raised
+const PBKDF2_ROUNDS: u32 = 600_000;
+const SALT_LENGTH: usize = 16;
+const NONCE_LENGTH: usize = 12;
+
+/// How a key's secret is held
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum KeyMaterial {
+    /// Ed25519 seed sealed with ChaCha20-Poly1305 under a passphrase-derived key
+    Local {
+        rounds: u32,
+        salt: String,
+        nonce: String,
+        ciphertext: String,
+    },
+    /// Account `m/44'/148'/<account_index>'` of the Stellar app on a Ledger
+    Ledger { account_index: u32 },
+}
+
+/// A key file in `~/.soroban-registry/keys/<name>.json`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredKey {
+    pub name: String,
+    /// `G...` account of the key
+    pub account: String,
+    pub created_at: DateTime<Utc>,
+    #[serde(flatten)]
+    material: KeyMaterial,
+}
+
+impl StoredKey {
+    fn kind(&self) -> &'static str {
+        match self.material {
+            KeyMaterial::Local { .. } => "local",
+            KeyMaterial::Ledger { .. } => "ledger",
+        }
+    }
+}
+
+/// A key ready to sign
+pub enum Key {
+    Local(SigningKey),
+    Ledger {
+        account_index: u32,
+        public_key: [u8; 32],
+    },
+}
+
+/// Seed of a secret given by the user: an `S...` Stellar secret or a base64
+/// Ed25519 seed
+fn parse_seed(secret: &str) -> Result<[u8; 32]> {
+    let secret = secret.trim();
+    if secret.starts_with('S') {
+        return shared::decode_secret_seed(secret)
+            .context("Invalid Stellar secret key (expected S... with a valid checksum)");
+    }
+    BASE64
+        .decode(secret)
+        .context("Invalid private key format (expected S... or base64)")?
+        .as_slice()
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("Private key must be 32 bytes"))
+}
+
+impl Key {
+    /// A local key from a secret passed on the command line or environment
+    pub fn from_secret(secret: &str) -> Result<Key> {
+        Ok(Key::Local(SigningKey::from_bytes(&parse_seed(secret)?)))
+    }
+
+    pub fn public_key(&self) -> [u8; 32] {
+        match self {
+            Key::Local(key) => key.verifying_key().to_bytes(),
+            Key::Ledger { public_key, .. } => *public_key,
+        }
+    }
+
+    /// `G...` account of the key
+    pub fn account(&self) -> String {
+        shared::encode_account_id(&self.public_key())
+    }
+
+    /// Whether signatures cover the SHA-256 digest of the message rather than
+    /// the message itself. The Stellar Ledger app only signs 32-byte hashes.
+    pub fn signs_digests(&self) -> bool {
+        matches!(self, Key::Ledger { .. })
+    }
+
+    /// Ed25519 signature of `message`, or of its SHA-256 digest for Ledger keys
+    pub fn sign(&self, message: &[u8]) -> Result<[u8; 64]> {
+        match self {
+            Key::Local(key) => Ok(key.sign(message).to_bytes()),
+            Key::Ledger { account_index, .. } => {
+                println!(
+                    "{}",
+                    "Confirm the signature on your Ledger device...".bright_black()
+                );
+                let digest: [u8; 32] = Sha256::digest(message).into();
+                crate::ledger::sign_hash(*account_index, &digest)
+            }
+        }
+    }
+}
+
+fn keys_dir() -> Result<PathBuf> {
+    crate::config::config_file_path()
+        .and_then(|config| config.parent().map(|dir| dir.join(KEYS_DIR_NAME)))
+        .context("Could not determine home directory")
+}
+
+fn validate_name(name: &str) -> Result<()> {
+    let valid = !name.is_empty()
+        && name.len() <= MAX_NAME_LENGTH
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !valid {
+        bail!(
+            "Key names are 1-{} letters, digits, '-' or '_'",
+            MAX_NAME_LENGTH
+        );
+    }
+    Ok(())
+}
+
+fn key_path(dir: &Path, name: &str) -> Result<PathBuf> {
+    validate_name(name)?;
+    Ok(dir.join(format!("{}.json", name)))
+}
+
+fn derive_cipher(passphrase: &str, salt: &[u8], rounds: u32) -> ChaCha20Poly1305 {
+    let mut key = [0u8; 32];
+    pbkdf2::pbkdf2_hmac::<Sha256>(passphrase.as_bytes(), salt, rounds, &mut key);
+    ChaCha20Poly1305::new(CipherKey::from_slice(&key))
+}
+
+/// Encrypt a seed; the account is authenticated alongside it so a key file
+/// cannot be edited to claim another account
+fn seal(seed: &[u8; 32], account: &str, passphrase: &str, rounds: u32) -> Result<KeyMaterial> {
+    let mut salt = [0u8; SALT_LENGTH];
+    let mut nonce = [0u8; NONCE_LENGTH];
+    OsRng.fill_bytes(&mut salt);
+    OsRng.fill_bytes(&mut nonce);
+
+    let ciphertext = derive_cipher(passphrase, &salt, rounds)
+        .encrypt(
+            Nonce::from_slice(&nonce),
+            Payload {
+                msg: seed,
+                aad: account.as_bytes(),
+            },
+        )
+        .map_err(|_| anyhow::anyhow!("Failed to encrypt the key"))?;
+    Ok(KeyMaterial::Local {
+        rounds,
+        salt: hex::encode(salt),
+        nonce: hex::encode(nonce),
+        ciphertext: hex::encode(ciphertext),
+    })
+}
+
+fn unseal(
+    stored: &StoredKey,
+    rounds: u32,
+    salt: &str,
+    nonce: &str,
+    ciphertext: &str,
+    passphrase: &str,
+) -> Result<SigningKey> {
+    let salt = hex::decode(salt).context("Corrupt key file (salt)")?;
+    let nonce = hex::decode(nonce).context("Corrupt key file (nonce)")?;
+    let ciphertext = hex::decode(ciphertext).context("Corrupt key file (ciphertext)")?;
+    if nonce.len() != NONCE_LENGTH {
+        bail!("Corrupt key file (nonce)");
+    }
+
+    let seed = derive_cipher(passphrase, &salt, rounds)
+        .decrypt(
+            Nonce::from_slice(&nonce),
+            Payload {
+                msg: &ciphertext,
+                aad: stored.account.as_bytes(),
+            },
+        )
+        .map_err(|_| anyhow::anyhow!("Wrong passphrase for key '{}'", stored.name))?;
+    let seed: [u8; 32] = seed
+        .as_slice()
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("Corrupt key file (seed length)"))?;
+    Ok(SigningKey::from_bytes(&seed))
+}
+
+fn passphrase(prompt: &str, confirm: bool) -> Result<String> {
+    if let Ok(passphrase) = std::env::var(PASSPHRASE_ENV) {
+        return Ok(passphrase);
+    }
+    crate::interactive::require_terminal(
+        "Reading the key passphrase",
+        &format!("set {}", PASSPHRASE_ENV),
+    )?;
+    let passphrase = rpassword::prompt_password(prompt).context("Failed to read passphrase")?;
+    if passphrase.is_empty() {
+        bail!("The passphrase must not be empty");
+    }
+    if confirm && rpassword::prompt_password("Repeat passphrase: ")? != passphrase {
+        bail!("Passphrases do not match");
+    }
+    Ok(passphrase)
+}
+
+fn write_key(dir: &Path, stored: &StoredKey) -> Result<PathBuf> {
+    let path = key_path(dir, &stored.name)?;
+    if path.exists() {
+        bail!(
+            "A key named '{}' already exists at {}",
+            stored.name,
+            path.display()
+        );
+    }
+    fs::create_dir_all(dir).with_context(|| format!("Failed to create directory {:?}", dir))?;
+    fs::write(&path, serde_json::to_string_pretty(stored)?)
+        .with_context(|| format!("Failed to write {:?}", path))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o600))?;
+    }
+    Ok(path)
+}
+
+fn read_key(dir: &Path, name: &str) -> Result<StoredKey> {
+    let path = key_path(dir, name)?;
+    let raw = fs::read_to_string(&path).with_context(|| {
+        format!(
+            "No key named '{}'; create one with `soroban-registry keys generate {}`",
+            name, name
+        )
+    })?;
+    serde_json::from_str(&raw).with_context(|| format!("Corrupt key file {:?}", path))
+}
+
+/// A stored key's metadata, without unlocking it
+pub fn read(name: &str) -> Result<StoredKey> {
+    read_key(&keys_dir()?, name)
+}
+
+/// Unlock a stored key for signing, prompting for its passphrase if needed
+pub fn load(name: &str) -> Result<Key> {
+    let stored = read(name)?;
+    match &stored.material {
+        KeyMaterial::Local {
+            rounds,
+            salt,
+            nonce,
+            ciphertext,
+        } => {
+            let passphrase = passphrase(&format!("Passphrase for key '{}': ", name), false)?;
+            let key = unseal(&stored, *rounds, salt, nonce, ciphertext, &passphrase)?;
+            Ok(Key::Local(key))
+        }
+        KeyMaterial::Ledger { account_index } => Ok(Key::Ledger {
+            account_index: *account_index,
+            public_key: shared::decode_account_id(&stored.account)
+                .context("Corrupt key file (account)")?,
+        }),
+    }
+}
+
+/// The key a command signs with: a stored key by name, else a secret given on
+/// the command line or in `SOROBAN_REGISTRY_SECRET_KEY`
+pub fn resolve(name: Option<&str>, secret: Option<&str>) -> Result<Key> {
+    if let Some(name) = name {
+        return load(name);
+    }
+    match secret
+        .map(str::to_string)
+        .or_else(|| std::env::var(SECRET_KEY_ENV).ok())
+    {
+        Some(secret) => Key::from_secret(&secret),
+        None => bail!(
+            "Pass --key <name> (see `soroban-registry keys list`) or set {}",
+            SECRET_KEY_ENV
+        ),
+    }
+}
+
+fn store_local(name: &str, key: &SigningKey) -> Result<StoredKey> {
+    let dir = keys_dir()?;
+    if key_path(&dir, name)?.exists() {
+        bail!("A key named '{}' already exists", name);
+    }
+    let account = shared::encode_account_id(key.verifying_key().as_bytes());
+    let passphrase = passphrase(&format!("New passphrase for key '{}': ", name), true)?;
+    let stored = StoredKey {
+        name: name.to_string(),
+        material: seal(&key.to_bytes(), &account, &passphrase, PBKDF2_ROUNDS)?,
+        account,
+        created_at: Utc::now(),
+    };
+    write_key(&dir, &stored)?;
+    Ok(stored)
+}
+
+fn print_stored(action: &str, stored: &StoredKey) {
+    println!(
+        "{} {} key '{}'",
+        "✓".green().bold(),
+        action,
+        stored.name.bold()
+    );
+    println!("  {}: {}", "Account".bold(), stored.account.bright_cyan());
+}
+
+/// `keys generate` — create a new local key
+pub fn generate(name: &str) -> Result<()> {
+    validate_name(name)?;
+    let key = SigningKey::generate(&mut OsRng);
+    let stored = store_local(name, &key)?;
+    print_stored("Generated", &stored);
+    Ok(())
+}
+
+/// `keys import` — store an existing secret, or register a Ledger account
+pub fn import(name: &str, ledger: bool, account_index: u32) -> Result<()> {
+    validate_name(name)?;
+    let stored = if ledger {
+        let dir = keys_dir()?;
+        let public_key = crate::ledger::public_key(account_index)?;
+        let stored = StoredKey {
+            name: name.to_string(),
+            account: shared::encode_account_id(&public_key),
+            created_at: Utc::now(),
+            material: KeyMaterial::Ledger { account_index },
+        };
+        write_key(&dir, &stored)?;
+        stored
+    } else {
+        let secret = match std::env::var(SECRET_KEY_ENV) {
+            Ok(secret) => secret,
+            Err(_) => {
+                crate::interactive::require_terminal(
+                    "Reading the secret key",
+                    &format!("set {}", SECRET_KEY_ENV),
+                )?;
+                rpassword::prompt_password("Secret key (S... or base64): ")
+                    .context("Failed to read secret key")?
+            }
+        };
+        store_local(name, &SigningKey::from_bytes(&parse_seed(&secret)?))?
+    };
+    print_stored("Imported", &stored);
+    Ok(())
+}
+
+/// `keys list`
+pub fn list(json: bool) -> Result<()> {
+    let dir = keys_dir()?;
+    let mut keys: Vec<StoredKey> = match fs::read_dir(&dir) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "json"))
+            .filter_map(|entry| {
+                let raw = fs::read_to_string(entry.path()).ok()?;
+                serde_json::from_str(&raw).ok()
+            })
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+    keys.sort_by(|a, b| a.name.cmp(&b.name));
+
+    if json {
+        let entries: Vec<serde_json::Value> = keys
+            .iter()
+            .map(|key| {
+                serde_json::json!({
+                    "name": key.name,
+                    "account": key.account,
+                    "kind": key.kind(),
+                    "created_at": key.created_at,
+                })
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&entries)?);
+        return Ok(());
+    }
+
+    if keys.is_empty() {
+        println!("{}", "No keys stored.".yellow());
+        println!("Create one with `soroban-registry keys generate <name>`");
+        return Ok(());
+    }
+    println!("\n{}", "Keys".bold().cyan());
+    println!("{}", "=".repeat(80).cyan());
+    for key in &keys {
+        println!(
+            "  {:<20} {:<7} {}  {}",
+            key.name.bold(),
+            key.kind(),
+            key.account.bright_cyan(),
+            key.created_at.format("%Y-%m-%d").to_string().bright_black()
+        );
+    }
+    println!();
+    Ok(())
+}
+
+/// `keys export-public` — print the public half of a key
+pub fn export_public(name: &str, format: &str) -> Result<()> {
+    let stored = read(name)?;
+    let public_key =
+        shared::decode_account_id(&stored.account).context("Corrupt key file (account)")?;
+    match format {
+        "stellar" => println!("{}", stored.account),
+        "base64" => println!("{}", BASE64.encode(public_key)),
+        "hex" => println!("{}", hex::encode(public_key)),
+        other => bail!("Unknown format '{}' (use stellar, base64 or hex)", other),
+    }
+    Ok(())
+}
+
+/// `keys sign` — sign a message or file and print the signature
+pub fn sign(name: &str, message: &[u8], encoding: &str) -> Result<()> {
+    let key = load(name)?;
+    if key.signs_digests() {
+        eprintln!(
+            "{}",
+            "Note: Ledger keys sign the SHA-256 digest of the message".yellow()
+        );
+    }
+    let signature = key.sign(message)?;
+    match encoding {
+        "hex" => println!("{}", hex::encode(signature)),
+        "base64" => println!("{}", BASE64.encode(signature)),
+        other => bail!("Unknown encoding '{}' (use hex or base64)", other),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stored(material: KeyMaterial, account: &str) -> StoredKey {
+        StoredKey {
+            name: "release".to_string(),
+            account: account.to_string(),
+            created_at: Utc::now(),
+            material,
+        }
+    }
+
+    #[test]
+    fn sealed_keys_need_the_passphrase_and_account() {
+        let key = SigningKey::from_bytes(&[5u8; 32]);
+        let account = shared::encode_account_id(key.verifying_key().as_bytes());
+        let material = seal(&key.to_bytes(), &account, "hunter2", 10).unwrap();
+        let KeyMaterial::Local {
+            rounds,
+            salt,
+            nonce,
+            ciphertext,
+        } = &material
+        else {
+            panic!("expected a local key");
+        };
+
+        let own = stored(material.clone(), &account);
+        let unsealed = unseal(&own, *rounds, salt, nonce, ciphertext, "hunter2").unwrap();
+        assert_eq!(unsealed.to_bytes(), key.to_bytes());
+        assert!(unseal(&own, *rounds, salt, nonce, ciphertext, "wrong").is_err());
+
+        let other = stored(material.clone(), &shared::encode_account_id(&[1u8; 32]));
+        assert!(unseal(&other, *rounds, salt, nonce, ciphertext, "hunter2").is_err());
+    }
+
+    #[test]
+    fn key_files_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let account = shared::encode_account_id(&[2u8; 32]);
+        let key = stored(KeyMaterial::Ledger { account_index: 3 }, &account);
+        write_key(dir.path(), &key).unwrap();
+        assert!(write_key(dir.path(), &key).is_err());
+
+        let read = read_key(dir.path(), "release").unwrap();
+        assert_eq!(read.account, account);
+        assert_eq!(read.kind(), "ledger");
+        assert!(read_key(dir.path(), "missing").is_err());
+    }
+
+    #[test]
+    fn parses_stellar_and_base64_secrets() {
+        let stellar =
+            Key::from_secret("SADQOBYHA4DQOBYHA4DQOBYHA4DQOBYHA4DQOBYHA4DQOBYHA4DQP54X").unwrap();
+        let base64 = Key::from_secret(&BASE64.encode([7u8; 32])).unwrap();
+        assert_eq!(stellar.public_key(), base64.public_key());
+        assert!(Key::from_secret("SABC").is_err());
+        assert!(Key::from_secret(&BASE64.encode([7u8; 16])).is_err());
+    }
+
+    #[test]
+    fn names_are_file_safe() {
+        assert!(validate_name("release-2024_a").is_ok());
+        assert!(validate_name("").is_err());
+        assert!(validate_name("../escape").is_err());
+        assert!(validate_name(&"a".repeat(65)).is_err());
+    }
+}
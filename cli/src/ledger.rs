@@ -0,0 +1,114 @@
+use anyhow::Result;
+
+const HARDENED: u32 = 0x8000_0000;
+
+/// BIP-32 path `m/44'/148'/<account_index>'` as the Stellar app expects it: a
+/// component count followed by big-endian components
+fn derivation_path(account_index: u32) -> Vec<u8> {
+    let components = [44 | HARDENED, 148 | HARDENED, account_index | HARDENED];
+    let mut path = vec![components.len() as u8];
+    for component in components {
+        path.extend_from_slice(&component.to_be_bytes());
+    }
+    path
+}
+
+/// APDUs to the Stellar app over USB HID
+#[cfg(feature = "ledger")]
+mod device {
+    use anyhow::{bail, Context, Result};
+    use ledger_apdu::APDUCommand;
+    use ledger_transport_hid::{hidapi::HidApi, TransportNativeHID};
+
+    const CLA: u8 = 0xe0;
+    const INS_GET_PUBLIC_KEY: u8 = 0x02;
+    const INS_SIGN_HASH: u8 = 0x08;
+
+    const SW_OK: u16 = 0x9000;
+    const SW_REJECTED: u16 = 0x6985;
+    const SW_HASH_SIGNING_DISABLED: u16 = 0x6c66;
+    const SW_APP_NOT_OPEN: &[u16] = &[0x6d00, 0x6e00, 0x6511];
+
+    fn exchange(ins: u8, data: Vec<u8>) -> Result<Vec<u8>> {
+        let hid = HidApi::new().context("Failed to access USB devices")?;
+        let transport = TransportNativeHID::new(&hid)
+            .context("No Ledger device found; connect it and unlock it")?;
+        let answer = transport
+            .exchange(&APDUCommand {
+                cla: CLA,
+                ins,
+                p1: 0x00,
+                p2: 0x00,
+                data,
+            })
+            .context("Failed to talk to the Ledger device")?;
+
+        match answer.retcode() {
+            SW_OK => Ok(answer.data().to_vec()),
+            SW_REJECTED => bail!("Rejected on the Ledger device"),
+            SW_HASH_SIGNING_DISABLED => {
+                bail!("Enable \"Hash signing\" in the Stellar app settings on the Ledger")
+            }
+            code if SW_APP_NOT_OPEN.contains(&code) => {
+                bail!("Open the Stellar app on the Ledger device")
+            }
+            code => bail!("Ledger device returned status {:#06x}", code),
+        }
+    }
+
+    pub fn public_key(path: Vec<u8>) -> Result<[u8; 32]> {
+        exchange(INS_GET_PUBLIC_KEY, path)?
+            .get(..32)
+            .and_then(|bytes| bytes.try_into().ok())
+            .context("Unexpected public key from the Ledger device")
+    }
+
+    pub fn sign_hash(mut data: Vec<u8>, hash: &[u8; 32]) -> Result<[u8; 64]> {
+        data.extend_from_slice(hash);
+        exchange(INS_SIGN_HASH, data)?
+            .get(..64)
+            .and_then(|bytes| bytes.try_into().ok())
+            .context("Unexpected signature from the Ledger device")
+    }
+}
+
+/// Builds without the `ledger` feature explain how to get it
+#[cfg(not(feature = "ledger"))]
+mod device {
+    use anyhow::{bail, Result};
+
+    const UNAVAILABLE: &str =
+        "Ledger support is not built in; reinstall with `cargo install --path cli --features ledger`";
+
+    pub fn public_key(_path: Vec<u8>) -> Result<[u8; 32]> {
+        bail!(UNAVAILABLE)
+    }
+
+    pub fn sign_hash(_path: Vec<u8>, _hash: &[u8; 32]) -> Result<[u8; 64]> {
+        bail!(UNAVAILABLE)
+    }
+}
+
+/// Public key of a Stellar account on the device
+pub fn public_key(account_index: u32) -> Result<[u8; 32]> {
+    device::public_key(derivation_path(account_index))
+}
+
+/// Sign a 32-byte hash with a device account; the user confirms on the device
+pub fn sign_hash(account_index: u32, hash: &[u8; 32]) -> Result<[u8; 64]> {
+    device::sign_hash(derivation_path(account_index), hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encodes_the_stellar_derivation_path() {
+        assert_eq!(
+            derivation_path(0),
+            vec![3, 0x80, 0, 0, 44, 0x80, 0, 0, 148, 0x80, 0, 0, 0]
+        );
+        assert_eq!(&derivation_path(2)[9..], &[0x80, 0, 0, 2]);
+    }
+}
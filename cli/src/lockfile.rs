@@ -0,0 +1,131 @@
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+/// Written next to the project, like Cargo.lock
+pub const LOCKFILE_NAME: &str = "soroban-registry.lock";
+
+const LOCKFILE_VERSION: u32 = 1;
+const HEADER: &str = "# Written by `soroban-registry install`. Pins each contract to the exact\n\
+                      # artifacts it resolved to; commit it for reproducible installs.\n\n";
+
+/// One installed contract, pinned by artifact hash
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LockedContract {
+    /// As given to `install`: UUID, on-chain ID or `@namespace/name`
+    pub id: String,
+    pub version: String,
+    /// Artifact kind (`wasm`, `abi`, `archive`, ...) to SHA-256
+    pub artifacts: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Lockfile {
+    pub version: u32,
+    #[serde(default, rename = "contract")]
+    pub contracts: Vec<LockedContract>,
+}
+
+impl Default for Lockfile {
+    fn default() -> Self {
+        Self {
+            version: LOCKFILE_VERSION,
+            contracts: Vec::new(),
+        }
+    }
+}
+
+impl Lockfile {
+    /// The lockfile at `path`, or an empty one if there is none yet
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let raw = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let lock: Lockfile =
+            toml::from_str(&raw).with_context(|| format!("Invalid lockfile {}", path.display()))?;
+        if lock.version > LOCKFILE_VERSION {
+            bail!(
+                "{} was written by a newer CLI (lockfile version {}); update with `self-update`",
+                path.display(),
+                lock.version
+            );
+        }
+        Ok(lock)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let body = toml::to_string_pretty(self).context("Failed to encode lockfile")?;
+        fs::write(path, format!("{}{}", HEADER, body))
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    pub fn get(&self, id: &str) -> Option<&LockedContract> {
+        self.contracts.iter().find(|c| c.id == id)
+    }
+
+    /// Add or replace the entry for `entry.id`, keeping entries sorted
+    pub fn upsert(&mut self, entry: LockedContract) {
+        self.contracts.retain(|c| c.id != entry.id);
+        self.contracts.push(entry);
+        self.contracts.sort_by(|a, b| a.id.cmp(&b.id));
+    }
+}
+
+/// Fail unless `content` hashes to `sha256`
+pub fn verify_artifact(kind: &str, sha256: &str, content: &[u8]) -> Result<()> {
+    let actual = hex::encode(Sha256::digest(content));
+    if !actual.eq_ignore_ascii_case(sha256) {
+        bail!(
+            "The {} artifact does not match its locked hash: expected {}, got {}",
+            kind,
+            sha256,
+            actual
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(id: &str, version: &str) -> LockedContract {
+        LockedContract {
+            id: id.to_string(),
+            version: version.to_string(),
+            artifacts: BTreeMap::from([("wasm".to_string(), "ab".repeat(32))]),
+        }
+    }
+
+    #[test]
+    fn round_trips_and_replaces_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(LOCKFILE_NAME);
+        let mut lock = Lockfile::load(&path).unwrap();
+        lock.upsert(entry("@b/amm", "1.0.0"));
+        lock.upsert(entry("@a/token", "2.0.0"));
+        lock.upsert(entry("@b/amm", "1.1.0"));
+        lock.save(&path).unwrap();
+
+        let read = Lockfile::load(&path).unwrap();
+        assert_eq!(read, lock);
+        assert_eq!(read.contracts[0].id, "@a/token");
+        assert_eq!(read.get("@b/amm").unwrap().version, "1.1.0");
+        assert!(fs::read_to_string(&path)
+            .unwrap()
+            .starts_with("# Written by"));
+    }
+
+    #[test]
+    fn rejects_content_that_does_not_match_the_hash() {
+        let hash = hex::encode(Sha256::digest(b"\0asm"));
+        assert!(verify_artifact("wasm", &hash, b"\0asm").is_ok());
+        assert!(verify_artifact("wasm", &hash.to_uppercase(), b"\0asm").is_ok());
+        assert!(verify_artifact("wasm", &hash, b"\0asm!").is_err());
+    }
+}
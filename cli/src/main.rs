@@ -1,32 +1,66 @@
 #![allow(unused_variables)]
 
+mod alerts;
+mod api_errors;
 mod backup;
 mod batch_verify;
+mod cli_error;
 mod commands;
+mod compare;
 mod config;
 mod conversions;
 mod coverage;
+mod dry_run;
+mod edit;
 mod events;
+mod examples;
 mod export;
+mod extensions;
 mod formal_verification;
 mod fuzz;
+mod http;
+mod identity;
 mod import;
 mod incident;
+mod interactive;
+mod invoke;
 mod io_utils;
+mod jobs;
+mod keystore;
+mod ledger;
+mod lockfile;
 mod manifest;
 mod migration;
 mod multisig;
+mod open;
+mod optimize;
 mod package_signing;
 mod patch;
 mod release_notes;
 mod profiler;
+mod progress;
+mod push;
+mod report;
+mod sbom;
+mod self_update;
+mod serve;
 mod sla;
+mod stats;
+mod stream_archive;
+mod support;
+mod telemetry;
 mod test_framework;
+mod toolchain;
+mod verify_source;
 mod webhook;
 mod wizard;
+mod workspace;
+mod xdr;
+
+use std::ffi::OsString;
 
 use anyhow::Result;
-use clap::{Parser, Subcommand};
+use clap::{CommandFactory, FromArgMatches, Parser, Subcommand};
 use patch::Severity;
 
 /// Soroban Registry CLI — discover, publish, verify, and deploy Soroban contracts
@@ -41,14 +75,39 @@ pub struct Cli {
     )]
     pub api_url: String,
 
+    /// Registry to use on a deployment hosting several (defaults to `defaults.tenant`)
+    #[arg(long, global = true, env = "SOROBAN_REGISTRY_TENANT")]
+    pub tenant: Option<String>,
+
     /// Stellar network to use (mainnet | testnet | futurenet)
     #[arg(long, global = true)]
     pub network: Option<String>,
 
+    /// Never prompt: take defaults, accept confirmations and fail when input is
+    /// required (for CI; prompts also fail fast when stdin is not a terminal)
+    #[arg(
+        long,
+        short = 'y',
+        visible_alias = "non-interactive",
+        global = true,
+        env = "SOROBAN_REGISTRY_NON_INTERACTIVE"
+    )]
+    pub yes: bool,
+
     /// Enable verbose output (shows HTTP requests, responses, and debug info)
     #[arg(long, short = 'v', global = true)]
     pub verbose: bool,
 
+    /// Progress reporting: human, or json to also stream JSON-lines events on
+    /// stderr for long operations (publish uploads, imports)
+    #[arg(
+        long,
+        global = true,
+        default_value = "human",
+        env = "SOROBAN_REGISTRY_PROGRESS"
+    )]
+    pub progress: progress::ProgressFormat,
+
     #[command(subcommand)]
     pub command: Commands,
 }
@@ -62,18 +121,140 @@ pub enum Commands {
         /// Only show verified contracts
         #[arg(long)]
         verified_only: bool,
+        /// Only show contracts with an attached third-party audit report
+        #[arg(long)]
+        audited: bool,
         /// Filter by one or more networks (comma-separated: mainnet,testnet,futurenet)
         #[arg(long)]
         networks: Option<String>,
         /// Filter by contract category (e.g. DEX, token, lending, oracle)
         #[arg(long)]
         category: Option<String>,
+        /// Only show contracts implementing a well-known interface (e.g. sep-41)
+        #[arg(long)]
+        implements: Option<String>,
+        /// Filter by SPDX license expression (e.g. MIT)
+        #[arg(long)]
+        license: Option<String>,
         /// Maximum number of results to return
         #[arg(long, default_value = "20")]
         limit: usize,
         /// Number of results to skip (for pagination)
         #[arg(long, default_value = "0")]
         offset: usize,
+        /// Sort by quality, downloads, updated, created, name, rating or stars
+        #[arg(long, value_parser = ["quality", "downloads", "updated", "created", "name", "rating", "stars"])]
+        sort: Option<String>,
+        /// Sort direction (defaults to desc, or asc for name)
+        #[arg(long, value_parser = ["asc", "desc"])]
+        order: Option<String>,
+        /// Show per-category, per-tag and verification counts for refining the search
+        #[arg(long)]
+        facets: bool,
+        /// Only contracts registered since an age (30d, 12h, 2w), date or timestamp
+        #[arg(long, value_parser = conversions::parse_since)]
+        created_after: Option<String>,
+        /// Only contracts updated since an age (30d, 12h, 2w), date or timestamp
+        #[arg(long, value_parser = conversions::parse_since)]
+        updated_after: Option<String>,
+        /// Only contracts downloaded at least this many times
+        #[arg(long)]
+        min_downloads: Option<u64>,
+        /// Only contracts whose WASM is at most this size (bytes, or e.g. 64KiB)
+        #[arg(long, value_parser = conversions::parse_size)]
+        max_wasm_size: Option<u64>,
+        /// Output results as machine-readable JSON
+        #[arg(long)]
+        json: bool,
+        /// Save this query under a name and get notified of new matches
+        /// (requires SOROBAN_REGISTRY_TOKEN)
+        #[arg(long, value_name = "NAME")]
+        save: Option<String>,
+        /// With --save: POST each new match to this URL
+        #[arg(long, requires = "save")]
+        webhook: Option<String>,
+        /// With --save: email each new match to this address
+        #[arg(long, requires = "save")]
+        email: Option<String>,
+    },
+
+    /// Rate contracts and read other users' reviews
+    Review {
+        #[command(subcommand)]
+        command: ReviewCommands,
+    },
+
+    /// Attach or list formal third-party audit reports
+    Audits {
+        #[command(subcommand)]
+        command: AuditsCommands,
+    },
+
+    /// Manage scoped API keys (requires a session token in SOROBAN_REGISTRY_TOKEN)
+    Tokens {
+        #[command(subcommand)]
+        command: TokensCommands,
+    },
+
+    /// Export or delete your account's data (requires a session token)
+    Account {
+        #[command(subcommand)]
+        command: AccountCommands,
+    },
+
+    /// Star a contract, or remove your star with --remove
+    Star {
+        /// Contract registry UUID, on-chain ID or @namespace/name
+        contract: String,
+        #[arg(long)]
+        remove: bool,
+    },
+
+    /// Report a contract to the registry's moderators, e.g. as malware or a scam
+    Report {
+        /// Contract registry UUID, on-chain ID or @namespace/name
+        contract: String,
+
+        #[arg(long, value_parser = report::REASONS)]
+        reason: String,
+
+        /// What is wrong with the contract; required for `--reason other`
+        #[arg(long)]
+        details: Option<String>,
+
+        /// How moderators can reach you, e.g. an email address
+        #[arg(long)]
+        contact: Option<String>,
+
+        /// Output the report receipt as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// List the contracts you starred
+    Stars {
+        /// Output results as machine-readable JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Manage saved searches
+    Searches {
+        #[command(subcommand)]
+        command: SearchesCommands,
+    },
+
+    /// Curate named lists of contracts and share them
+    Collection {
+        #[command(subcommand)]
+        command: CollectionCommands,
+    },
+
+    /// Show publishing quota usage for your account or an organization
+    Usage {
+        /// Organization name; defaults to your own publisher account
+        #[arg(long)]
+        org: Option<String>,
         /// Output results as machine-readable JSON
         #[arg(long)]
         json: bool,
@@ -83,17 +264,128 @@ pub enum Commands {
     Info {
         /// Contract registry UUID (use --network for network-specific config)
         contract_id: String,
+        /// Also show this version's changes and full release notes
+        #[arg(long)]
+        version: Option<String>,
+        /// Show the ABI as function signatures instead of the raw contract JSON
+        #[arg(long)]
+        abi: bool,
+        /// How --abi renders the ABI
+        #[arg(long, default_value = "rust", value_parser = ["rust", "typescript", "json"])]
+        syntax: String,
+    },
+
+    /// Open a contract's registry page in the default browser
+    Open {
+        /// Contract name (`@ns/name`), registry UUID or on-chain ID
+        contract: String,
+        /// Open the contract's source repository instead
+        #[arg(long, conflicts_with = "home")]
+        repo: bool,
+        /// Open the contract's homepage instead
+        #[arg(long)]
+        home: bool,
+        /// Print the URL instead of opening it
+        #[arg(long)]
+        print: bool,
+    },
+
+    /// Show what changed between versions: interface, metadata and WASM size
+    Changelog {
+        /// Contract name (`@ns/name`), registry UUID or on-chain ID
+        contract: String,
+        /// Only this version's entry, e.g. 1.3.0
+        #[arg(long)]
+        version: Option<String>,
+        /// Output results as machine-readable JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Edit a contract's metadata and README; with no flags, opens them in $EDITOR
+    Edit {
+        /// Contract registry UUID or @namespace/name
+        contract: String,
+        #[arg(long)]
+        name: Option<String>,
+        #[arg(long)]
+        description: Option<String>,
+        #[arg(long)]
+        category: Option<String>,
+        /// Comma-separated tags; replaces the current list
+        #[arg(long)]
+        tags: Option<String>,
+        /// Only apply if the contract still has this ETag (defaults to the one just read)
+        #[arg(long)]
+        if_match: Option<String>,
+    },
+
+    /// Stage a version's files in a publish session and commit them together;
+    /// re-run to resume an interrupted push
+    Push {
+        /// Contract registry UUID or @namespace/name
+        contract: String,
+        #[arg(long)]
+        version: String,
+        /// Compiled contract WASM
+        #[arg(long)]
+        wasm: std::path::PathBuf,
+        /// Contract spec as JSON
+        #[arg(long)]
+        abi: std::path::PathBuf,
+        #[arg(long)]
+        readme: Option<std::path::PathBuf>,
+        /// JSON file with `signature`, `publisher_key` and optional `signature_algorithm`
+        #[arg(long)]
+        signature: Option<std::path::PathBuf>,
+        /// Test results to attach: JUnit XML, or JSON with `passed`, `failed`,
+        /// `skipped` and `coverage`
+        #[arg(long)]
+        tests: Option<std::path::PathBuf>,
+        /// Performance budgets (TOML or JSON): functions benchmarked on publish and
+        /// their CPU and memory limits
+        #[arg(long)]
+        budgets: Option<std::path::PathBuf>,
+        #[arg(long)]
+        source_url: Option<String>,
+        #[arg(long)]
+        commit_hash: Option<String>,
+        #[arg(long, conflicts_with = "notes")]
+        release_notes: Option<String>,
+        /// Markdown file with release notes; ./release_notes.md is used when present
+        #[arg(long)]
+        notes: Option<std::path::PathBuf>,
+        /// Cargo.lock to attach for SBOM generation
+        #[arg(long)]
+        cargo_lock: Option<std::path::PathBuf>,
+        /// Discard the open session for this version instead of committing
+        #[arg(long)]
+        cancel: bool,
+    },
+
+    /// Compare two contracts side by side (metadata, interfaces, sizes, costs, audits)
+    Compare {
+        /// First contract (registry UUID or @namespace/name)
+        a: String,
+        /// Second contract (registry UUID or @namespace/name)
+        b: String,
+        /// Also compare the estimated cost of invoking this method on each
+        #[arg(long)]
+        method: Option<String>,
+        /// Output the comparison as machine-readable JSON
+        #[arg(long)]
+        json: bool,
     },
 
     /// Publish a new contract to the registry
     Publish {
         /// On-chain contract ID
-        #[arg(long)]
-        contract_id: String,
+        #[arg(long, required_unless_present = "all")]
+        contract_id: Option<String>,
 
-        /// Human-readable contract name
-        #[arg(long)]
-        name: String,
+        /// Contract name, optionally scoped to your namespace as @publisher/name
+        #[arg(long, required_unless_present = "all")]
+        name: Option<String>,
 
         /// Optional description
         #[arg(long)]
@@ -112,8 +404,112 @@ pub enum Commands {
         tags: Option<String>,
 
         /// Publisher Stellar address
+        #[arg(long, required_unless_present = "all")]
+        publisher: Option<String>,
+
+        /// SPDX license expression (e.g. "MIT OR Apache-2.0")
+        #[arg(long)]
+        license: Option<String>,
+
+        /// Path to a license file to upload alongside the expression
+        #[arg(long)]
+        license_file: Option<String>,
+
+        /// Source repository URL (https://)
+        #[arg(long)]
+        repository_url: Option<String>,
+
+        /// Project homepage URL
+        #[arg(long)]
+        homepage: Option<String>,
+
+        /// Documentation URL
+        #[arg(long)]
+        documentation_url: Option<String>,
+
+        /// Registry contract this one is based on (UUID or @namespace/name)
+        #[arg(long)]
+        forked_from: Option<String>,
+
+        /// Check the interface against the previous version before publishing
+        #[arg(long)]
+        check: bool,
+
+        /// Version being published (used by --check for the semver bump rule)
+        #[arg(long)]
+        version: Option<String>,
+
+        /// Path to the contract spec JSON to compare (required with --check)
+        #[arg(long)]
+        abi: Option<String>,
+
+        /// Validate the publish on the registry and print what it would do, without publishing
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Publish every contract in this Cargo workspace that has unpublished changes,
+        /// dependencies first (names come from soroban-registry.toml)
+        #[arg(long, conflicts_with_all = ["contract_id", "name", "publisher", "check"])]
+        all: bool,
+    },
+
+    /// Show which contracts in this Cargo workspace have unpublished changes
+    Status {
+        /// Output results as machine-readable JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// List the workspace contracts whose sources changed since a git ref, for CI
+    Changed {
+        /// Commit, branch or tag to compare against, e.g. origin/main
+        #[arg(long)]
+        since: String,
+        /// Output results as machine-readable JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Run a private registry in this process (SQLite database, no Postgres needed)
+    Serve {
+        /// Address to listen on
+        #[arg(long, default_value = "127.0.0.1:3001")]
+        bind: std::net::SocketAddr,
+        /// Directory holding the registry database (default: ~/.soroban-registry/registry)
+        #[arg(long)]
+        data_dir: Option<std::path::PathBuf>,
+        /// Load sample data on startup (demo)
+        #[arg(long)]
+        seed: Option<serve::Seed>,
+    },
+
+    /// Show a publisher's contracts with download and verification totals
+    Publisher {
+        /// Publisher registry UUID
+        id: String,
+        /// Page of contracts to show
+        #[arg(long, default_value = "1")]
+        page: i64,
+        /// Contracts per page
+        #[arg(long, default_value = "20")]
+        limit: i64,
+        /// Output results as machine-readable JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Prove control of a publisher's Stellar account by signing a registry challenge
+    VerifyIdentity {
+        /// Publisher registry UUID
+        publisher_id: String,
+        /// Stored key to sign with (see `keys list`); defaults to the secret key in
+        /// SOROBAN_REGISTRY_SECRET_KEY
         #[arg(long)]
-        publisher: String,
+        key: Option<String>,
+        /// Program that signs instead, e.g. a hardware wallet bridge: it reads the
+        /// message on stdin and prints the hex signature
+        #[arg(long, conflicts_with = "key")]
+        signer: Option<String>,
     },
 
     /// List recent contracts
@@ -121,11 +517,39 @@ pub enum Commands {
         /// Maximum number of contracts to show
         #[arg(long, default_value = "10")]
         limit: usize,
+        /// Sort by quality, downloads, updated, created, name, rating or stars
+        #[arg(long, value_parser = ["quality", "downloads", "updated", "created", "name", "rating", "stars"])]
+        sort: Option<String>,
+        /// Sort direction (defaults to desc, or asc for name)
+        #[arg(long, value_parser = ["asc", "desc"])]
+        order: Option<String>,
+        /// Only contracts registered since an age (30d, 12h, 2w), date or timestamp
+        #[arg(long, value_parser = conversions::parse_since)]
+        created_after: Option<String>,
+        /// Only contracts updated since an age (30d, 12h, 2w), date or timestamp
+        #[arg(long, value_parser = conversions::parse_since)]
+        updated_after: Option<String>,
+        /// Only contracts downloaded at least this many times
+        #[arg(long)]
+        min_downloads: Option<u64>,
+        /// Only contracts whose WASM is at most this size (bytes, or e.g. 64KiB)
+        #[arg(long, value_parser = conversions::parse_size)]
+        max_wasm_size: Option<u64>,
         /// Output results as machine-readable JSON
         #[arg(long)]
         json: bool,
     },
 
+    /// Show a random verified contract from the registry
+    Discover {
+        /// Only pick from this category
+        #[arg(long)]
+        category: Option<String>,
+        /// Output the contract as machine-readable JSON
+        #[arg(long)]
+        json: bool,
+    },
+
     /// Detect breaking changes between contract versions
     BreakingChanges {
         /// Old contract identifier (UUID or contract_id@version)
@@ -161,23 +585,47 @@ pub enum Commands {
         #[arg(long)]
         id: String,
 
-        /// Output archive path
+        /// Output archive path; `-` streams a .tar.zst archive to stdout, and a
+        /// path ending in .tar.zst writes the same streamed layout
         #[arg(long, default_value = "contract-export.tar.gz")]
         output: String,
 
         /// Path to contract source directory
         #[arg(long, default_value = ".")]
         contract_dir: String,
+
+        /// Build the archive on the registry from its metadata, WASM, ABI, README
+        /// and docs instead of packaging a local source directory
+        #[arg(long, conflicts_with = "contract_dir")]
+        from_registry: bool,
+
+        /// With --from-registry: version to package (defaults to the latest)
+        #[arg(long, requires = "from_registry")]
+        version: Option<String>,
     },
 
     /// Import a contract from an archive
     Import {
-        /// Path to the archive file
+        /// Path to the archive file (.tar.gz or .tar.zst), or `-` to read stdin
         archive: String,
 
         /// Directory to extract into
         #[arg(long, default_value = "./imported")]
         output_dir: String,
+
+        /// After extracting, publish the contract and its version to the target
+        /// registry (needs an archive from `export --from-registry` and
+        /// SOROBAN_REGISTRY_TOKEN)
+        #[arg(long, requires = "publisher")]
+        publish: bool,
+
+        /// With --publish: publisher address to register the contract under
+        #[arg(long)]
+        publisher: Option<String>,
+
+        /// Verify the archive and validate the registration, but write and publish nothing
+        #[arg(long)]
+        dry_run: bool,
     },
 
     /// Generate documentation from a contract WASM
@@ -190,6 +638,29 @@ pub enum Commands {
         output: String,
     },
 
+    /// Report what makes a contract WASM larger than it needs to be, with
+    /// estimated savings; --apply strips it and runs wasm-opt when installed
+    Optimize {
+        /// Path to the contract WASM
+        wasm: std::path::PathBuf,
+
+        /// Write an optimized copy and compare its size and upload cost
+        #[arg(long)]
+        apply: bool,
+
+        /// Where --apply writes the result (defaults to <name>.optimized.wasm)
+        #[arg(long, short = 'o', requires = "apply")]
+        output: Option<std::path::PathBuf>,
+
+        /// Only strip custom sections; don't run wasm-opt
+        #[arg(long, requires = "apply")]
+        no_wasm_opt: bool,
+
+        /// Output the report as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
     /// Generate OpenAPI 3.0 spec from contract ABI
     Openapi {
         /// Path to contract WASM file or ABI JSON file
@@ -204,8 +675,12 @@ pub enum Commands {
         format: String,
     },
 
-    /// Launch the interactive setup wizard
-    Wizard {},
+    /// Launch the interactive assistant (setup, publish, migrate or deploy)
+    Wizard {
+        /// Flow to run; a menu is shown when omitted
+        #[arg(value_parser = wizard::FLOW_NAMES)]
+        flow: Option<String>,
+    },
 
     /// Show command history
     History {
@@ -363,9 +838,13 @@ pub enum Commands {
         /// Path to the package file to sign
         package: String,
 
-        /// Private key (base64-encoded Ed25519)
+        /// Stored key to sign with (see `keys list`)
         #[arg(long)]
-        private_key: String,
+        key: Option<String>,
+
+        /// Private key (base64-encoded Ed25519 or S...) instead of a stored key
+        #[arg(long, conflicts_with = "key")]
+        private_key: Option<String>,
 
         /// Contract ID
         #[arg(long)]
@@ -420,42 +899,522 @@ pub enum Commands {
         public_key: String,
     },
 
+    /// Submit contract source to the registry for verification
+    VerifySource {
+        /// On-chain contract ID
+        contract_id: String,
+
+        /// Path to the contract source
+        #[arg(long)]
+        source: std::path::PathBuf,
+
+        /// Compiler version the deployed WASM was built with
+        #[arg(long)]
+        compiler_version: String,
+
+        /// Build parameters as JSON (e.g. '{"profile": "release"}')
+        #[arg(long)]
+        build_params: Option<String>,
+
+        /// Block until the verification finishes, with a status spinner
+        #[arg(long)]
+        wait: bool,
+
+        /// Seconds --wait blocks before giving up (exit code 10)
+        #[arg(long, default_value_t = jobs::DEFAULT_TIMEOUT_SECS, requires = "wait")]
+        timeout: u64,
+    },
+
+    /// Show the state of a registry job, such as a submitted verification
+    Jobs {
+        /// Job ID printed when the job was started
+        id: String,
+
+        /// Block until the job finishes
+        #[arg(long)]
+        wait: bool,
+
+        /// Seconds --wait blocks before giving up (exit code 10)
+        #[arg(long, default_value_t = jobs::DEFAULT_TIMEOUT_SECS, requires = "wait")]
+        timeout: u64,
+
+        /// Output the job as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Advisories, critical patches and upcoming end-of-life dates that reach
+    /// your contracts through their dependencies
+    Alerts {
+        /// Include acknowledged alerts
+        #[arg(long)]
+        all: bool,
+
+        /// Acknowledge an alert instead of listing them
+        #[arg(long, conflicts_with_all = ["all", "webhook", "email"])]
+        ack: Option<String>,
+
+        /// Push new alerts to this URL (replaces the current settings)
+        #[arg(long, conflicts_with = "all")]
+        webhook: Option<String>,
+
+        /// Email new alerts here instead of your publisher profile's address
+        #[arg(long, conflicts_with = "all")]
+        email: Option<String>,
+
+        /// Output alerts as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Show a contract's per-version support policy and end-of-life status,
+    /// or declare one for a version you published
+    Support {
+        /// Contract ID or name
+        contract_id: String,
+
+        /// Only this version; required to declare a policy
+        #[arg(long)]
+        version: Option<String>,
+
+        /// Last day the version is supported (YYYY-MM-DD); replaces its policy
+        #[arg(long, requires = "version")]
+        until: Option<chrono::NaiveDate>,
+
+        /// Mark the version as long-term support; replaces its policy
+        #[arg(long, requires = "version")]
+        lts: bool,
+
+        /// Output the policies as JSON
+        #[arg(long, conflicts_with_all = ["until", "lts"])]
+        json: bool,
+    },
+
+    /// Runnable usage examples attached to a contract
+    Examples {
+        #[command(subcommand)]
+        action: ExamplesCommands,
+    },
+
+    /// Call a contract function in the registry's simulation sandbox. Without
+    /// arguments, prompts for each parameter and prints the equivalent command
+    Invoke {
+        /// Contract ID or name
+        contract_id: String,
+
+        /// Function to call; chosen interactively when omitted
+        function: Option<String>,
+
+        /// One value per parameter: numbers and addresses as text, vectors and
+        /// structs as JSON
+        #[arg(allow_hyphen_values = true)]
+        args: Vec<String>,
+
+        /// Version to call; defaults to the latest
+        #[arg(long)]
+        version: Option<String>,
+
+        /// Output the result as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Convert between JSON and base64 XDR for values and transactions, typed
+    /// by a contract's spec
+    Xdr {
+        #[command(subcommand)]
+        action: XdrCommands,
+    },
+
+    /// Event schemas registered per version, and decoding of raw on-chain events
+    Events {
+        #[command(subcommand)]
+        action: EventsCommands,
+    },
+
     /// Manage signing keys and signatures
     Keys {
         #[command(subcommand)]
         action: KeysCommands,
     },
 
-    /// Verify multiple contracts in a single atomic batch (all succeed or all rollback)
-    BatchVerify {
-        /// Comma-separated list of contract IDs to verify.
-        /// Optionally suffix with @version (e.g. abc123@1.0.0,def456)
+    /// Verify multiple contracts in a single atomic batch (all succeed or all rollback)
+    BatchVerify {
+        /// Comma-separated list of contract IDs to verify.
+        /// Optionally suffix with @version (e.g. abc123@1.0.0,def456)
+        #[arg(long)]
+        contracts: String,
+
+        /// Stellar address or username initiating the batch (recorded in audit log)
+        #[arg(long)]
+        initiated_by: String,
+
+        /// Output results as machine-readable JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Manage webhooks for contract lifecycle events
+    Webhook {
+        #[command(subcommand)]
+        action: WebhookCommands,
+    },
+
+    /// Auto-generate and manage release notes for contract versions
+    ReleaseNotes {
+        #[command(subcommand)]
+        action: ReleaseNotesCommands,
+    },
+
+    /// Generate a CycloneDX or SPDX SBOM of a project's dependencies
+    Sbom {
+        /// Project directory containing Cargo.toml and Cargo.lock
+        #[arg(long, default_value = ".")]
+        project_dir: String,
+
+        /// Registry contract ID whose registry dependencies are included
+        #[arg(long)]
+        contract_id: Option<String>,
+
+        /// Version to describe (defaults to the Cargo.toml version)
+        #[arg(long)]
+        version: Option<String>,
+
+        /// SBOM format: cyclonedx or spdx
+        #[arg(long, default_value = "cyclonedx")]
+        format: String,
+
+        /// Write the SBOM to this file instead of stdout
+        #[arg(long, short = 'o')]
+        output: Option<String>,
+
+        /// Download the server-generated SBOM for the published version
+        #[arg(long)]
+        remote: bool,
+    },
+
+    /// Install a published contract's interface and WASM into a local directory,
+    /// pinned by hash in soroban-registry.lock
+    Install {
+        /// Contract registry ID (UUID or on-chain ID)
+        #[arg(required_unless_present = "collection")]
+        contract_id: Option<String>,
+
+        /// Install every contract in this collection instead, in list order
+        #[arg(long, conflicts_with_all = ["contract_id", "version"])]
+        collection: Option<String>,
+
+        /// Version to install (defaults to the latest)
+        #[arg(long)]
+        version: Option<String>,
+
+        /// Directory to write the contract interface into
+        #[arg(long, default_value = "contracts")]
+        output_dir: String,
+
+        /// Refuse versions without a verified build provenance attestation
+        #[arg(long)]
+        require_provenance: bool,
+
+        /// Resolve the latest version again instead of the one in soroban-registry.lock
+        #[arg(long)]
+        update: bool,
+    },
+
+    /// Update this CLI to the latest release
+    SelfUpdate {
+        /// Only report whether an update is available
+        #[arg(long)]
+        check: bool,
+    },
+
+    /// Show registry totals, or a daily time series with --chart
+    Stats {
+        /// Series to chart: contracts, publishers, publishes, versions or downloads
+        #[arg(long, default_value = "publishes")]
+        metric: String,
+
+        /// How far back to chart, e.g. 30d, 90d or 12w
+        #[arg(long, default_value = "90d")]
+        range: String,
+
+        /// Render the series as a sparkline
+        #[arg(long)]
+        chart: bool,
+
+        /// Output results as machine-readable JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Opt in or out of anonymous usage metrics
+    Telemetry {
+        #[command(subcommand)]
+        command: TelemetryCommands,
+    },
+
+    /// List command aliases from the config file and installed extensions
+    Extensions,
+
+    /// An alias or a `soroban-registry-<name>` extension on PATH
+    #[command(external_subcommand)]
+    External(Vec<OsString>),
+}
+
+#[derive(Debug, Subcommand)]
+pub enum TelemetryCommands {
+    /// Send anonymous usage metrics, including those recorded locally so far
+    Enable,
+    /// Stop recording and delete locally recorded metrics
+    Disable,
+    /// Show whether telemetry is enabled and what is queued
+    Status {
+        /// Output results as machine-readable JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+/// Sub-commands for the `release-notes` group
+#[derive(Debug, Subcommand)]
+pub enum ReviewCommands {
+    /// Rate a contract 1-5 (one review per account; requires SOROBAN_REGISTRY_TOKEN)
+    Add {
+        /// Contract registry UUID or @namespace/name
+        contract: String,
+        /// Whole stars, 1-5
+        #[arg(long, value_parser = clap::value_parser!(i16).range(1..=5))]
+        rating: i16,
+        /// Optional written review
+        #[arg(long)]
+        comment: Option<String>,
+    },
+    /// Show a contract's reviews, newest first
+    List {
+        /// Contract registry UUID or @namespace/name
+        contract: String,
+        #[arg(long, default_value = "1")]
+        page: i64,
+        #[arg(long, default_value = "20")]
+        limit: i64,
+        /// Output results as machine-readable JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Report an abusive review to the moderators
+    Flag {
+        /// Contract registry UUID or @namespace/name
+        contract: String,
+        /// Review number shown by `review list`
+        review_id: i32,
+        #[arg(long)]
+        reason: String,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum AuditsCommands {
+    /// Attach an audit report to a contract (requires SOROBAN_REGISTRY_TOKEN)
+    Attach {
+        /// Contract registry UUID or @namespace/name
+        contract: String,
+        /// Audit firm or individual auditor
+        #[arg(long)]
+        auditor: String,
+        /// Link to the report (PDF or web page)
+        #[arg(long)]
+        report_url: String,
+        /// What was reviewed, e.g. "src/ at v1.2.0, excluding tests"
+        #[arg(long)]
+        scope: String,
+        /// Date the audit was completed (YYYY-MM-DD)
+        #[arg(long)]
+        date: chrono::NaiveDate,
+        /// Audited version; omit when the report covers the whole contract
+        #[arg(long)]
+        version: Option<String>,
+        /// Auditor's website
+        #[arg(long)]
+        auditor_url: Option<String>,
+        /// Local copy of the report PDF, hashed so readers can check their download
+        #[arg(long)]
+        report_file: Option<String>,
+        /// Short summary of the findings
+        #[arg(long)]
+        summary: Option<String>,
+        #[arg(long, default_value = "0")]
+        critical: i32,
+        #[arg(long, default_value = "0")]
+        high: i32,
+        #[arg(long, default_value = "0")]
+        medium: i32,
+        #[arg(long, default_value = "0")]
+        low: i32,
+    },
+    /// List the audit reports attached to a contract
+    List {
+        /// Contract registry UUID or @namespace/name
+        contract: String,
+        /// Output results as machine-readable JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum TokensCommands {
+    /// Create an API key; the key is printed once
+    Create {
+        /// Label to recognise the key by, e.g. "ci"
+        #[arg(long)]
+        name: String,
+        /// Scopes to grant (repeatable)
+        #[arg(long = "scope", required = true, value_parser = ["read", "publish", "patch-admin"])]
+        scopes: Vec<String>,
+        /// Days until the key expires; omit for no expiry
+        #[arg(long)]
+        expires_in_days: Option<i64>,
+    },
+    /// List active keys with their last-used time
+    List {
+        /// Output results as machine-readable JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Replace a key; the old one keeps working for the grace period
+    Rotate {
+        /// Key ID from `tokens list`
+        id: String,
+        /// Hours the old key stays valid
+        #[arg(long, default_value = "24")]
+        grace_hours: i64,
+    },
+    /// Revoke a key immediately
+    Revoke {
+        /// Key ID from `tokens list`
+        id: String,
+    },
+    /// API calls over the last 30 days by endpoint and key, with error rates
+    /// and rate-limit headroom
+    Usage {
+        /// Only count requests made with this key (ID from `tokens list`)
+        #[arg(long)]
+        token: Option<String>,
+        /// Output results as machine-readable JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum AccountCommands {
+    /// Download everything the registry holds about your account as JSON
+    Export {
+        /// File to write the export to
+        #[arg(long, default_value = "account-export.json")]
+        output: String,
+    },
+    /// Schedule your account for deletion after a 30-day grace period
+    Delete {
+        /// Cancel a pending deletion instead
+        #[arg(long)]
+        cancel: bool,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum SearchesCommands {
+    /// List your saved searches and their latest matches
+    List {
+        /// Output results as machine-readable JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Delete a saved search
+    Delete {
+        /// Name the search was saved under
+        name: String,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum CollectionCommands {
+    /// Create a collection (private unless --public)
+    Create {
+        name: String,
         #[arg(long)]
-        contracts: String,
-
-        /// Stellar address or username initiating the batch (recorded in audit log)
+        description: Option<String>,
+        /// Let anyone see the collection
         #[arg(long)]
-        initiated_by: String,
-
+        public: bool,
+    },
+    /// List public collections and your own
+    List {
+        /// Only collections owned by this address
+        #[arg(long)]
+        owner: Option<String>,
         /// Output results as machine-readable JSON
         #[arg(long)]
         json: bool,
     },
-
-    /// Manage webhooks for contract lifecycle events
-    Webhook {
-        #[command(subcommand)]
-        action: WebhookCommands,
+    /// Show the contracts in a collection, in order
+    Show {
+        /// Collection ID
+        id: String,
+        /// Output results as machine-readable JSON
+        #[arg(long)]
+        json: bool,
     },
-
-    /// Auto-generate and manage release notes for contract versions
-    ReleaseNotes {
-        #[command(subcommand)]
-        action: ReleaseNotesCommands,
+    /// Rename, describe or change the visibility of a collection
+    Update {
+        /// Collection ID
+        id: String,
+        #[arg(long)]
+        name: Option<String>,
+        #[arg(long)]
+        description: Option<String>,
+        /// Let anyone see the collection
+        #[arg(long, conflicts_with = "private")]
+        public: bool,
+        /// Only you can see the collection
+        #[arg(long)]
+        private: bool,
+    },
+    /// Delete a collection
+    Delete {
+        /// Collection ID
+        id: String,
+    },
+    /// Add a contract to a collection
+    Add {
+        /// Collection ID
+        id: String,
+        /// Contract registry UUID, on-chain ID or @namespace/name
+        contract: String,
+        /// Why the contract is in the list
+        #[arg(long)]
+        note: Option<String>,
+        /// 1-based position to insert at (defaults to the end)
+        #[arg(long, value_parser = clap::value_parser!(u64).range(1..))]
+        position: Option<u64>,
+    },
+    /// Remove a contract from a collection
+    Remove {
+        /// Collection ID
+        id: String,
+        /// Contract registry UUID, on-chain ID or @namespace/name
+        contract: String,
+    },
+    /// Move contracts to the front of a collection in the given order
+    Reorder {
+        /// Collection ID
+        id: String,
+        /// Contracts in their new order; the others follow as before
+        #[arg(required = true)]
+        contracts: Vec<String>,
     },
 }
 
-/// Sub-commands for the `release-notes` group
 #[derive(Debug, Subcommand)]
 pub enum ReleaseNotesCommands {
     /// Auto-generate release notes from code diff and changelog
@@ -651,10 +1610,14 @@ pub enum MultisigCommands {
     /// Sign a deployment proposal (add your approval)
     Sign {
         proposal_id: String,
-        #[arg(long)]
-        signer: String,
+        #[arg(long, required_unless_present = "key")]
+        signer: Option<String>,
         #[arg(long)]
         signature_data: Option<String>,
+        /// Stored key to approve with; its account is the signer and it signs the
+        /// approval as the signature data
+        #[arg(long, conflicts_with_all = ["signer", "signature_data"])]
+        key: Option<String>,
     },
 
     /// Execute an approved deployment proposal
@@ -698,6 +1661,7 @@ pub enum IncidentCommands {
 pub enum PatchCommands {
     /// Create a new security patch
     Create {
+        /// Affected version or SemVer range, e.g. ">=1.0.0, <1.4.2"
         #[arg(long)]
         version: String,
         #[arg(long)]
@@ -718,6 +1682,19 @@ pub enum PatchCommands {
         contract_id: String,
         #[arg(long)]
         patch_id: String,
+        /// Run the rollout checks on the registry without recording the patch
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Approve a critical patch created by another maintainer
+    Approve {
+        #[arg(long)]
+        patch_id: String,
+    },
+    /// Show the patches applied to a contract and where in each rollout
+    Status {
+        /// Contract ID
+        contract_id: String,
     },
     /// Manage contract dependencies
     Deps {
@@ -735,10 +1712,227 @@ pub enum DepsCommands {
     },
 }
 
+#[derive(Debug, Subcommand)]
+pub enum ExamplesCommands {
+    /// List a contract's examples and their steps
+    List {
+        /// Contract ID or name
+        contract_id: String,
+
+        /// Output the examples as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Run an example's steps in one sandbox, stopping at the first failure
+    Run {
+        /// Contract ID or name
+        contract_id: String,
+
+        /// Example name
+        example: String,
+
+        /// Version to run against (defaults to the latest)
+        #[arg(long)]
+        version: Option<String>,
+
+        /// Output the run as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Attach or replace an example on a contract you published
+    Add {
+        /// Contract ID or name
+        contract_id: String,
+
+        /// Example name: a-z, 0-9, '-' and '_'
+        example: String,
+
+        /// JSON array of steps, e.g. [{"function": "mint", "args": ["100"]}]
+        #[arg(long)]
+        file: std::path::PathBuf,
+
+        #[arg(long)]
+        description: Option<String>,
+
+        /// Version whose interface to check the steps against (defaults to the latest)
+        #[arg(long)]
+        version: Option<String>,
+    },
+    /// Remove an example from a contract you published
+    Remove {
+        /// Contract ID or name
+        contract_id: String,
+
+        /// Example name
+        example: String,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum XdrCommands {
+    /// Encode JSON as base64 XDR
+    Encode {
+        /// JSON value or transaction envelope; - reads stdin
+        input: String,
+
+        /// What the XDR holds: scval or transaction
+        #[arg(long, default_value = "scval")]
+        kind: shared::XdrKind,
+
+        /// Contract whose spec types the value (or a transaction's calls)
+        #[arg(long)]
+        contract: Option<String>,
+
+        /// Version of the contract's spec; defaults to the latest
+        #[arg(long, requires = "contract")]
+        version: Option<String>,
+
+        /// Type the value as this function's return value...
+        #[arg(long, requires = "contract")]
+        function: Option<String>,
+
+        /// ...or as this parameter of it
+        #[arg(long, requires = "function")]
+        param: Option<String>,
+
+        /// Type the value by name instead, e.g. i128 or a struct in the spec
+        #[arg(long, conflicts_with = "function")]
+        type_name: Option<String>,
+    },
+    /// Decode base64 XDR to JSON
+    Decode {
+        /// Base64 XDR; - reads stdin
+        input: String,
+
+        /// What the XDR holds: scval or transaction
+        #[arg(long, default_value = "scval")]
+        kind: shared::XdrKind,
+
+        /// Contract whose spec types the value (or a transaction's calls)
+        #[arg(long)]
+        contract: Option<String>,
+
+        /// Version of the contract's spec; defaults to the latest
+        #[arg(long, requires = "contract")]
+        version: Option<String>,
+
+        /// Type the value as this function's return value...
+        #[arg(long, requires = "contract")]
+        function: Option<String>,
+
+        /// ...or as this parameter of it
+        #[arg(long, requires = "function")]
+        param: Option<String>,
+
+        /// Type the value by name instead, e.g. i128 or a struct in the spec
+        #[arg(long, conflicts_with = "function")]
+        type_name: Option<String>,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum EventsCommands {
+    /// Show the event schemas of a version (by default the newest with any)
+    Schemas {
+        /// Contract ID or name
+        contract_id: String,
+
+        #[arg(long)]
+        version: Option<String>,
+
+        /// Output the schemas as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Register a version's event schemas, replacing any it has
+    Register {
+        /// Contract ID or name
+        contract_id: String,
+
+        #[arg(long)]
+        version: String,
+
+        /// JSON array of schemas: {"name", "prefix_topics", "params", "data_format"}
+        #[arg(long)]
+        file: std::path::PathBuf,
+    },
+    /// Decode a raw event: a base64 ContractEvent or DiagnosticEvent, or its
+    /// topics and data as base64 ScVals (as RPC getEvents returns them)
+    Decode {
+        /// Contract ID or name
+        contract_id: String,
+
+        /// Base64 ContractEvent or DiagnosticEvent XDR
+        #[arg(required_unless_present = "data", conflicts_with_all = ["topic", "data"])]
+        event: Option<String>,
+
+        /// A topic as base64 ScVal XDR; repeat in order
+        #[arg(long, requires = "data")]
+        topic: Vec<String>,
+
+        /// The data as base64 ScVal XDR
+        #[arg(long)]
+        data: Option<String>,
+
+        /// Version whose schemas to decode with
+        #[arg(long)]
+        version: Option<String>,
+
+        /// Output the decoded event as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
 #[derive(Debug, Subcommand)]
 pub enum KeysCommands {
-    /// Generate a new Ed25519 keypair for signing
-    Generate {},
+    /// Generate a new Ed25519 key, stored encrypted under a passphrase
+    Generate {
+        /// Name to refer to the key by
+        name: String,
+    },
+
+    /// Store an existing secret key, or register an account on a Ledger device
+    Import {
+        /// Name to refer to the key by
+        name: String,
+        /// Use an account of the Stellar app on a connected Ledger instead of a secret.
+        /// Without it the secret is read from SOROBAN_REGISTRY_SECRET_KEY or prompted for.
+        #[arg(long)]
+        ledger: bool,
+        /// Ledger account index, for the path m/44'/148'/<index>'
+        #[arg(long, default_value = "0", requires = "ledger")]
+        account_index: u32,
+    },
+
+    /// List stored keys
+    List {
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Print a key's public key
+    ExportPublic {
+        name: String,
+        /// stellar (G...), base64 or hex
+        #[arg(long, default_value = "stellar")]
+        format: String,
+    },
+
+    /// Sign a message or file with a stored key and print the signature
+    Sign {
+        name: String,
+        /// Message to sign
+        #[arg(long, conflicts_with = "file", required_unless_present = "file")]
+        message: Option<String>,
+        /// File whose contents to sign
+        #[arg(long)]
+        file: Option<String>,
+        /// hex or base64
+        #[arg(long, default_value = "hex")]
+        encoding: String,
+    },
 
     /// Revoke a signature
     Revoke {
@@ -881,8 +2075,26 @@ pub enum MigrateCommands {
 }
 
 #[tokio::main]
-async fn main() -> Result<()> {
-    let cli = Cli::parse();
+async fn main() {
+    if let Err(err) = run().await {
+        eprintln!("Error: {:?}", err);
+        std::process::exit(cli_error::exit_code(&err));
+    }
+}
+
+async fn run() -> Result<()> {
+    let args = extensions::resolve_aliases(std::env::args_os().collect(), &config::aliases())?;
+    let matches = Cli::command().get_matches_from(args);
+    let mut cli = Cli::from_arg_matches(&matches).unwrap_or_else(|err| err.exit());
+    // `defaults.api_base` applies unless --api-url or SOROBAN_REGISTRY_API_URL was given
+    if matches.value_source("api_url") == Some(clap::parser::ValueSource::DefaultValue) {
+        if let Some(api_base) = config::profile_api_base() {
+            cli.api_url = api_base;
+        }
+    }
+    if let Some(tenant) = cli.tenant.clone().or_else(config::profile_tenant) {
+        cli.api_url = config::tenant_api_url(&cli.api_url, &tenant);
+    }
 
     // ── Initialise logger ─────────────────────────────────────────────────────
     // --verbose / -v  →  DEBUG level (shows HTTP calls, payloads, timing)
@@ -894,9 +2106,27 @@ async fn main() -> Result<()> {
         .format_module_path(cli.verbose) // show module path only in verbose
         .init();
 
+    interactive::set_assume_yes(cli.yes);
+    progress::set_format(cli.progress);
+    http::configure()?;
+
     log::debug!("Verbose mode enabled");
     log::debug!("API URL: {}", cli.api_url);
 
+    // ── Run the command, recording usage per the telemetry consent ────────────
+    // Extension names are third-party and not reported
+    let command = match cli.command {
+        Commands::External(_) => "extension".to_string(),
+        _ => telemetry::command_name(&matches),
+    };
+    let api_url = cli.api_url.clone();
+    let started = std::time::Instant::now();
+    let result = execute(cli).await;
+    telemetry::record(&api_url, &command, started.elapsed(), result.is_ok()).await;
+    result
+}
+
+async fn execute(cli: Cli) -> Result<()> {
     // ── Resolve network ───────────────────────────────────────────────────────
     let cfg_network = config::resolve_network(cli.network)?;
     let mut net_str = cfg_network.to_string();
@@ -908,38 +2138,422 @@ async fn main() -> Result<()> {
         Commands::Search {
             query,
             verified_only,
+            audited,
             networks,
             category,
+            implements,
+            license,
             limit,
             offset,
+            sort,
+            order,
+            facets,
+            created_after,
+            updated_after,
+            min_downloads,
+            max_wasm_size,
+            json,
+            save,
+            webhook,
+            email,
+        } => {
+            let networks_vec: Vec<String> = networks
+                .map(|n| n.split(',').map(|s| s.trim().to_string()).collect())
+                .unwrap_or_default();
+            if let Some(name) = save.as_deref() {
+                log::debug!("Command: search --save | name={:?}", name);
+                // A saved search matches a single network, or all of them
+                let saved_network = match networks_vec.as_slice() {
+                    [only] => Some(only.as_str()),
+                    _ => None,
+                };
+                commands::save_search(
+                    &cli.api_url,
+                    name,
+                    &query,
+                    saved_network,
+                    category.as_deref(),
+                    webhook.as_deref(),
+                    email.as_deref(),
+                )
+                .await?;
+            }
+            log::debug!(
+                "Command: search | query={:?} verified_only={} networks={:?} category={:?}",
+                query,
+                verified_only,
+                networks_vec,
+                category
+            );
+            commands::search(
+                &cli.api_url,
+                &query,
+                network,
+                verified_only,
+                audited,
+                networks_vec,
+                category.as_deref(),
+                implements.as_deref(),
+                license.as_deref(),
+                limit,
+                offset,
+                sort.as_deref(),
+                order.as_deref(),
+                facets,
+                &commands::ListingFilters {
+                    created_after,
+                    updated_after,
+                    min_downloads,
+                    max_wasm_size,
+                },
+                json,
+            )
+            .await?;
+        }
+        Commands::Review { command } => match command {
+            ReviewCommands::Add {
+                contract,
+                rating,
+                comment,
+            } => {
+                log::debug!("Command: review add | contract={} rating={}", contract, rating);
+                commands::review_add(&cli.api_url, &contract, rating, comment.as_deref()).await?;
+            }
+            ReviewCommands::List {
+                contract,
+                page,
+                limit,
+                json,
+            } => {
+                log::debug!("Command: review list | contract={} page={}", contract, page);
+                commands::review_list(&cli.api_url, &contract, page, limit, json).await?;
+            }
+            ReviewCommands::Flag {
+                contract,
+                review_id,
+                reason,
+            } => {
+                log::debug!("Command: review flag | contract={} review={}", contract, review_id);
+                commands::review_flag(&cli.api_url, &contract, review_id, &reason).await?;
+            }
+        },
+        Commands::Audits { command } => match command {
+            AuditsCommands::Attach {
+                contract,
+                auditor,
+                report_url,
+                scope,
+                date,
+                version,
+                auditor_url,
+                report_file,
+                summary,
+                critical,
+                high,
+                medium,
+                low,
+            } => {
+                log::debug!("Command: audits attach | contract={} auditor={}", contract, auditor);
+                let attestation = shared::CreateAuditAttestationRequest {
+                    version,
+                    auditor_name: auditor,
+                    auditor_url,
+                    report_url,
+                    report_sha256: None,
+                    scope,
+                    findings_summary: summary,
+                    critical_findings: critical,
+                    high_findings: high,
+                    medium_findings: medium,
+                    low_findings: low,
+                    audited_at: date,
+                };
+                commands::audits_attach(
+                    &cli.api_url,
+                    &contract,
+                    attestation,
+                    report_file.as_deref(),
+                )
+                .await?;
+            }
+            AuditsCommands::List { contract, json } => {
+                log::debug!("Command: audits list | contract={}", contract);
+                commands::audits_list(&cli.api_url, &contract, json).await?;
+            }
+        },
+        Commands::Tokens { command } => match command {
+            TokensCommands::Create {
+                name,
+                scopes,
+                expires_in_days,
+            } => {
+                log::debug!("Command: tokens create | name={} scopes={:?}", name, scopes);
+                commands::tokens_create(&cli.api_url, &name, &scopes, expires_in_days).await?;
+            }
+            TokensCommands::List { json } => {
+                log::debug!("Command: tokens list");
+                commands::tokens_list(&cli.api_url, json).await?;
+            }
+            TokensCommands::Rotate { id, grace_hours } => {
+                log::debug!("Command: tokens rotate | id={} grace_hours={}", id, grace_hours);
+                commands::tokens_rotate(&cli.api_url, &id, grace_hours).await?;
+            }
+            TokensCommands::Revoke { id } => {
+                log::debug!("Command: tokens revoke | id={}", id);
+                commands::tokens_revoke(&cli.api_url, &id).await?;
+            }
+            TokensCommands::Usage { token, json } => {
+                log::debug!("Command: tokens usage | token={:?}", token);
+                commands::tokens_usage(&cli.api_url, token.as_deref(), json).await?;
+            }
+        },
+        Commands::Account { command } => match command {
+            AccountCommands::Export { output } => {
+                log::debug!("Command: account export | output={}", output);
+                commands::account_export(&cli.api_url, &output).await?;
+            }
+            AccountCommands::Delete { cancel } => {
+                log::debug!("Command: account delete | cancel={}", cancel);
+                commands::account_delete(&cli.api_url, cancel).await?;
+            }
+        },
+        Commands::Star { contract, remove } => {
+            log::debug!("Command: star | contract={} remove={}", contract, remove);
+            commands::star(&cli.api_url, &contract, remove).await?;
+        }
+        Commands::Report {
+            contract,
+            reason,
+            details,
+            contact,
+            json,
+        } => {
+            log::debug!("Command: report | contract={} reason={}", contract, reason);
+            report::report(&cli.api_url, &contract, &reason, details, contact, json).await?;
+        }
+        Commands::Stars { json } => {
+            log::debug!("Command: stars");
+            commands::stars_list(&cli.api_url, json).await?;
+        }
+        Commands::Searches { command } => match command {
+            SearchesCommands::List { json } => {
+                log::debug!("Command: searches list");
+                commands::searches_list(&cli.api_url, json).await?;
+            }
+            SearchesCommands::Delete { name } => {
+                log::debug!("Command: searches delete | name={}", name);
+                commands::searches_delete(&cli.api_url, &name).await?;
+            }
+        },
+        Commands::Collection { command } => match command {
+            CollectionCommands::Create {
+                name,
+                description,
+                public,
+            } => {
+                log::debug!("Command: collection create | name={} public={}", name, public);
+                commands::collection_create(&cli.api_url, &name, description.as_deref(), public)
+                    .await?;
+            }
+            CollectionCommands::List { owner, json } => {
+                log::debug!("Command: collection list | owner={:?}", owner);
+                commands::collection_list(&cli.api_url, owner.as_deref(), json).await?;
+            }
+            CollectionCommands::Show { id, json } => {
+                log::debug!("Command: collection show | id={}", id);
+                commands::collection_show(&cli.api_url, &id, json).await?;
+            }
+            CollectionCommands::Update {
+                id,
+                name,
+                description,
+                public,
+                private,
+            } => {
+                log::debug!("Command: collection update | id={}", id);
+                let is_public = match (public, private) {
+                    (true, _) => Some(true),
+                    (_, true) => Some(false),
+                    _ => None,
+                };
+                commands::collection_update(
+                    &cli.api_url,
+                    &id,
+                    shared::UpdateCollectionRequest {
+                        name,
+                        description,
+                        is_public,
+                    },
+                )
+                .await?;
+            }
+            CollectionCommands::Delete { id } => {
+                log::debug!("Command: collection delete | id={}", id);
+                commands::collection_delete(&cli.api_url, &id).await?;
+            }
+            CollectionCommands::Add {
+                id,
+                contract,
+                note,
+                position,
+            } => {
+                log::debug!("Command: collection add | id={} contract={}", id, contract);
+                commands::collection_add(&cli.api_url, &id, &contract, note.as_deref(), position)
+                    .await?;
+            }
+            CollectionCommands::Remove { id, contract } => {
+                log::debug!("Command: collection remove | id={} contract={}", id, contract);
+                commands::collection_remove(&cli.api_url, &id, &contract).await?;
+            }
+            CollectionCommands::Reorder { id, contracts } => {
+                log::debug!("Command: collection reorder | id={} contracts={:?}", id, contracts);
+                commands::collection_reorder(&cli.api_url, &id, &contracts).await?;
+            }
+        },
+        Commands::Usage { org, json } => {
+            log::debug!("Command: usage | org={:?}", org);
+            commands::usage(&cli.api_url, org.as_deref(), json).await?;
+        }
+        Commands::Changelog {
+            contract,
+            version,
             json,
         } => {
-            let networks_vec: Vec<String> = networks
-                .map(|n| n.split(',').map(|s| s.trim().to_string()).collect())
-                .unwrap_or_default();
-            log::debug!(
-                "Command: search | query={:?} verified_only={} networks={:?} category={:?}",
-                query,
-                verified_only,
-                networks_vec,
-                category
-            );
-            commands::search(
+            log::debug!("Command: changelog | contract={} version={:?}", contract, version);
+            commands::changelog(&cli.api_url, &contract, version.as_deref(), json).await?;
+        }
+        Commands::Info {
+            contract_id,
+            version,
+            abi,
+            syntax,
+        } => {
+            log::debug!("Command: info | contract_id={} version={:?}", contract_id, version);
+            commands::info(
                 &cli.api_url,
-                &query,
-                network,
-                verified_only,
-                networks_vec,
-                category.as_deref(),
-                limit,
-                offset,
-                json,
+                &contract_id,
+                version.as_deref(),
+                abi.then_some(syntax.as_str()),
+                cfg_network,
             )
             .await?;
         }
-        Commands::Info { contract_id } => {
-            log::debug!("Command: info | contract_id={}", contract_id);
-            commands::info(&cli.api_url, &contract_id, cfg_network).await?;
+        Commands::Open {
+            contract,
+            repo,
+            home,
+            print,
+        } => {
+            log::debug!("Command: open | contract={} repo={} home={}", contract, repo, home);
+            let target = if repo {
+                open::Target::Repository
+            } else if home {
+                open::Target::Homepage
+            } else {
+                open::Target::Registry
+            };
+            open::open(&cli.api_url, &contract, target, print, cfg_network).await?;
+        }
+        Commands::Edit {
+            contract,
+            name,
+            description,
+            category,
+            tags,
+            if_match,
+        } => {
+            log::debug!("Command: edit | contract={}", contract);
+            let changes = edit::metadata_changes(name, description, category, tags);
+            if changes.is_empty() && if_match.is_none() {
+                edit::edit_in_editor(&cli.api_url, &contract).await?;
+            } else {
+                edit::edit(&cli.api_url, &contract, changes, if_match.as_deref()).await?;
+            }
+        }
+        Commands::Push {
+            contract,
+            version,
+            wasm,
+            abi,
+            readme,
+            signature,
+            tests,
+            budgets,
+            source_url,
+            commit_hash,
+            release_notes,
+            notes,
+            cargo_lock,
+            cancel,
+        } => {
+            log::debug!("Command: push | contract={} version={}", contract, version);
+            let default_notes = std::path::Path::new(shared::RELEASE_NOTES_FILE);
+            let notes = match notes {
+                Some(path) => Some(path),
+                None if release_notes.is_none() && !cancel && default_notes.is_file() => {
+                    println!("Attaching release notes from {}", default_notes.display());
+                    Some(default_notes.to_path_buf())
+                }
+                None => None,
+            };
+            let release_notes = match notes {
+                Some(path) => Some(push::read_release_notes(&path)?),
+                None => release_notes,
+            };
+            let toolchain = if cancel {
+                None
+            } else {
+                let lock = cargo_lock
+                    .as_deref()
+                    .and_then(|path| std::fs::read_to_string(path).ok());
+                toolchain::capture(&std::env::current_dir()?, &wasm, lock.as_deref())
+            };
+            if let Some(toolchain) = &toolchain {
+                toolchain::warn(toolchain);
+            }
+            let mut parts = vec![
+                push::LocalPart {
+                    kind: "wasm",
+                    path: wasm,
+                },
+                push::LocalPart {
+                    kind: "abi",
+                    path: abi,
+                },
+            ];
+            if let Some(path) = readme {
+                parts.push(push::LocalPart {
+                    kind: "readme",
+                    path,
+                });
+            }
+            if let Some(path) = signature {
+                parts.push(push::LocalPart {
+                    kind: "signature",
+                    path,
+                });
+            }
+            if let Some(path) = tests {
+                parts.push(push::LocalPart { kind: "tests", path });
+            }
+            if let Some(path) = budgets {
+                parts.push(push::LocalPart {
+                    kind: "budgets",
+                    path,
+                });
+            }
+            let options = push::PushOptions {
+                source_url,
+                commit_hash,
+                release_notes,
+                cargo_lock,
+                toolchain,
+            };
+            push::push(&cli.api_url, &contract, &version, parts, options, cancel).await?;
+        }
+        Commands::Compare { a, b, method, json } => {
+            log::debug!("Command: compare | a={} b={} method={:?}", a, b, method);
+            compare::compare(&cli.api_url, &a, &b, method.as_deref(), json).await?;
         }
         Commands::Publish {
             contract_id,
@@ -949,16 +2563,48 @@ async fn main() -> Result<()> {
             category,
             tags,
             publisher,
+            license,
+            license_file,
+            repository_url,
+            homepage,
+            documentation_url,
+            forked_from,
+            check,
+            version,
+            abi,
+            dry_run,
+            all,
         } => {
+            if all {
+                log::debug!("Command: publish --all | dry_run={}", dry_run);
+                workspace::publish_all(&cli.api_url, network, dry_run).await?;
+                return Ok(());
+            }
+            let (Some(contract_id), Some(name), Some(publisher)) = (contract_id, name, publisher)
+            else {
+                return Err(cli_error::CliError::Usage(
+                    "--contract-id, --name and --publisher are required without --all".to_string(),
+                )
+                .into());
+            };
             let tags_vec = tags
                 .map(|t| t.split(',').map(|s| s.trim().to_string()).collect())
                 .unwrap_or_default();
             log::debug!(
-                "Command: publish | contract_id={} name={} tags={:?}",
+                "Command: publish | contract_id={} name={} tags={:?} check={} dry_run={}",
                 contract_id,
                 name,
-                tags_vec
+                tags_vec,
+                check,
+                dry_run
             );
+            if check {
+                let abi_path = abi
+                    .as_deref()
+                    .ok_or_else(|| anyhow::anyhow!("--check requires --abi <spec.json>"))?;
+                commands::check_interface(&cli.api_url, &contract_id, abi_path, version.as_deref())
+                    .await?;
+            }
             commands::publish(
                 &cli.api_url,
                 &contract_id,
@@ -968,12 +2614,90 @@ async fn main() -> Result<()> {
                 category.as_deref(),
                 tags_vec,
                 &publisher,
+                license.as_deref(),
+                license_file.as_deref(),
+                shared::ProjectUrls {
+                    repository_url,
+                    homepage,
+                    documentation_url,
+                },
+                forked_from.as_deref(),
+                dry_run,
+            )
+            .await?;
+        }
+        Commands::Status { json } => {
+            log::debug!("Command: status | json={}", json);
+            workspace::status(&cli.api_url, json).await?;
+        }
+        Commands::Changed { since, json } => {
+            log::debug!("Command: changed | since={}", since);
+            workspace::changed(&since, json)?;
+        }
+        Commands::Serve {
+            bind,
+            data_dir,
+            seed,
+        } => {
+            log::debug!("Command: serve | bind={} seed={:?}", bind, seed);
+            serve::run(bind, data_dir, seed).await?;
+        }
+        Commands::Publisher {
+            id,
+            page,
+            limit,
+            json,
+        } => {
+            log::debug!("Command: publisher | id={} page={} limit={}", id, page, limit);
+            commands::publisher(&cli.api_url, &id, page, limit, json).await?;
+        }
+        Commands::VerifyIdentity {
+            publisher_id,
+            key,
+            signer,
+        } => {
+            log::debug!(
+                "Command: verify-identity | publisher_id={} key={:?} signer={:?}",
+                publisher_id,
+                key,
+                signer
+            );
+            let signer = match signer {
+                Some(command) => identity::IdentitySigner::Command(command),
+                None => identity::IdentitySigner::Key(keystore::resolve(key.as_deref(), None)?),
+            };
+            identity::verify_identity(&cli.api_url, &publisher_id, signer).await?;
+        }
+        Commands::List {
+            limit,
+            sort,
+            order,
+            created_after,
+            updated_after,
+            min_downloads,
+            max_wasm_size,
+            json,
+        } => {
+            log::debug!("Command: list | limit={} sort={:?} order={:?}", limit, sort, order);
+            commands::list(
+                &cli.api_url,
+                limit,
+                network,
+                sort.as_deref(),
+                order.as_deref(),
+                &commands::ListingFilters {
+                    created_after,
+                    updated_after,
+                    min_downloads,
+                    max_wasm_size,
+                },
+                json,
             )
             .await?;
         }
-        Commands::List { limit, json } => {
-            log::debug!("Command: list | limit={}", limit);
-            commands::list(&cli.api_url, limit, network, json).await?;
+        Commands::Discover { category, json } => {
+            log::debug!("Command: discover | category={:?}", category);
+            commands::discover(&cli.api_url, category.as_deref(), network, json).await?;
         }
         Commands::BreakingChanges { old_id, new_id, json } => {
             log::debug!("Command: breaking-changes | old={} new={}", old_id, new_id);
@@ -1027,20 +2751,39 @@ async fn main() -> Result<()> {
             id,
             output,
             contract_dir,
+            from_registry,
+            version,
         } => {
-            log::debug!("Command: export | id={} output={}", id, output);
-            commands::export(&cli.api_url, &id, &output, &contract_dir).await?;
+            log::debug!(
+                "Command: export | id={} output={} from_registry={}",
+                id,
+                output,
+                from_registry
+            );
+            if from_registry {
+                commands::export_from_registry(&cli.api_url, &id, version.as_deref(), &output)
+                    .await?;
+            } else {
+                commands::export(&cli.api_url, &id, &output, &contract_dir).await?;
+            }
         }
         Commands::Import {
             archive,
             output_dir,
+            publish,
+            publisher,
+            dry_run,
         } => {
             log::debug!(
-                "Command: import | archive={} output_dir={}",
+                "Command: import | archive={} output_dir={} publish={} dry_run={}",
                 archive,
-                output_dir
+                output_dir,
+                publish,
+                dry_run
             );
-            commands::import(&cli.api_url, &archive, network, &output_dir).await?;
+            let publisher = publisher.as_deref().filter(|_| publish);
+            commands::import(&cli.api_url, &archive, network, &output_dir, publisher, dry_run)
+                .await?;
         }
         Commands::Doc {
             contract_path,
@@ -1053,6 +2796,20 @@ async fn main() -> Result<()> {
             );
             commands::doc(&contract_path, &output)?;
         }
+        Commands::Optimize {
+            wasm,
+            apply,
+            output,
+            no_wasm_opt,
+            json,
+        } => {
+            log::debug!(
+                "Command: optimize | wasm={} apply={}",
+                wasm.display(),
+                apply
+            );
+            optimize::run(&wasm, apply, output, !no_wasm_opt, json)?;
+        }
         Commands::Openapi {
             contract_path,
             output,
@@ -1066,9 +2823,9 @@ async fn main() -> Result<()> {
             );
             commands::openapi(&contract_path, &output, &format)?;
         }
-        Commands::Wizard {} => {
-            log::debug!("Command: wizard");
-            wizard::run(&cli.api_url).await?;
+        Commands::Wizard { flow } => {
+            log::debug!("Command: wizard | flow={:?}", flow);
+            wizard::run(&cli.api_url, flow.as_deref()).await?;
         }
         Commands::History { search, limit } => {
             log::debug!("Command: history | search={:?} limit={}", search, limit);
@@ -1117,13 +2874,23 @@ async fn main() -> Result<()> {
             PatchCommands::Apply {
                 contract_id,
                 patch_id,
+                dry_run,
             } => {
                 log::debug!(
-                    "Command: patch apply | contract_id={} patch_id={}",
+                    "Command: patch apply | contract_id={} patch_id={} dry_run={}",
                     contract_id,
-                    patch_id
+                    patch_id,
+                    dry_run
                 );
-                commands::patch_apply(&cli.api_url, &contract_id, &patch_id).await?;
+                commands::patch_apply(&cli.api_url, &contract_id, &patch_id, dry_run).await?;
+            }
+            PatchCommands::Approve { patch_id } => {
+                log::debug!("Command: patch approve | patch_id={}", patch_id);
+                commands::patch_approve(&cli.api_url, &patch_id).await?;
+            }
+            PatchCommands::Status { contract_id } => {
+                log::debug!("Command: patch status | contract_id={}", contract_id);
+                commands::patch_status(&cli.api_url, &contract_id).await?;
             }
             PatchCommands::Deps { command } => match command {
                 DepsCommands::List { contract_id } => {
@@ -1188,8 +2955,17 @@ async fn main() -> Result<()> {
                 proposal_id,
                 signer,
                 signature_data,
+                key,
             } => {
                 log::debug!("Command: multisig sign | proposal_id={}", proposal_id);
+                let (signer, signature_data) = match key {
+                    Some(name) => {
+                        let key = keystore::load(&name)?;
+                        let approval = multisig::approval_signature(&key, &proposal_id)?;
+                        (key.account(), Some(approval))
+                    }
+                    None => (signer.unwrap_or_default(), signature_data),
+                };
                 multisig::sign_proposal(
                     &cli.api_url,
                     &proposal_id,
@@ -1370,6 +3146,7 @@ async fn main() -> Result<()> {
         }
         Commands::Sign {
             package,
+            key,
             private_key,
             contract_id,
             version,
@@ -1381,10 +3158,11 @@ async fn main() -> Result<()> {
                 contract_id,
                 version
             );
+            let key = keystore::resolve(key.as_deref(), private_key.as_deref())?;
             package_signing::sign_package(
                 &cli.api_url,
                 &package,
-                &private_key,
+                &key,
                 &contract_id,
                 &version,
                 expires_at.as_deref(),
@@ -1432,10 +3210,272 @@ async fn main() -> Result<()> {
                 &public_key,
             )?;
         }
+        Commands::VerifySource {
+            contract_id,
+            source,
+            compiler_version,
+            build_params,
+            wait,
+            timeout,
+        } => {
+            log::debug!(
+                "Command: verify-source | contract_id={} source={} wait={}",
+                contract_id,
+                source.display(),
+                wait
+            );
+            verify_source::submit(
+                &cli.api_url,
+                &contract_id,
+                &source,
+                &compiler_version,
+                build_params.as_deref(),
+                wait.then(|| std::time::Duration::from_secs(timeout)),
+            )
+            .await?;
+        }
+        Commands::Jobs {
+            id,
+            wait,
+            timeout,
+            json,
+        } => {
+            log::debug!("Command: jobs | id={} wait={}", id, wait);
+            jobs::show(
+                &cli.api_url,
+                &id,
+                wait.then(|| std::time::Duration::from_secs(timeout)),
+                json,
+            )
+            .await?;
+        }
+        Commands::Alerts {
+            all,
+            ack,
+            webhook,
+            email,
+            json,
+        } => {
+            log::debug!("Command: alerts | all={} ack={:?}", all, ack);
+            if let Some(id) = ack {
+                alerts::acknowledge(&cli.api_url, &id).await?;
+            } else if webhook.is_some() || email.is_some() {
+                alerts::configure(&cli.api_url, webhook.as_deref(), email.as_deref()).await?;
+            } else {
+                alerts::list(&cli.api_url, all, json).await?;
+            }
+        }
+        Commands::Support {
+            contract_id,
+            version,
+            until,
+            lts,
+            json,
+        } => {
+            log::debug!(
+                "Command: support | contract_id={} version={:?}",
+                contract_id,
+                version
+            );
+            match version {
+                Some(version) if until.is_some() || lts => {
+                    support::set(&cli.api_url, &contract_id, &version, until, lts).await?;
+                }
+                version => {
+                    support::show(&cli.api_url, &contract_id, version.as_deref(), json).await?;
+                }
+            }
+        }
+        Commands::Examples { action } => match action {
+            ExamplesCommands::List { contract_id, json } => {
+                log::debug!("Command: examples list | contract_id={}", contract_id);
+                examples::list(&cli.api_url, &contract_id, json).await?;
+            }
+            ExamplesCommands::Run {
+                contract_id,
+                example,
+                version,
+                json,
+            } => {
+                log::debug!(
+                    "Command: examples run | contract_id={} example={}",
+                    contract_id,
+                    example
+                );
+                examples::run(&cli.api_url, &contract_id, &example, version.as_deref(), json)
+                    .await?;
+            }
+            ExamplesCommands::Add {
+                contract_id,
+                example,
+                file,
+                description,
+                version,
+            } => {
+                log::debug!(
+                    "Command: examples add | contract_id={} example={}",
+                    contract_id,
+                    example
+                );
+                examples::add(
+                    &cli.api_url,
+                    &contract_id,
+                    &example,
+                    &file,
+                    description.as_deref(),
+                    version.as_deref(),
+                )
+                .await?;
+            }
+            ExamplesCommands::Remove {
+                contract_id,
+                example,
+            } => {
+                log::debug!(
+                    "Command: examples remove | contract_id={} example={}",
+                    contract_id,
+                    example
+                );
+                examples::remove(&cli.api_url, &contract_id, &example).await?;
+            }
+        },
+        Commands::Invoke {
+            contract_id,
+            function,
+            args,
+            version,
+            json,
+        } => {
+            log::debug!(
+                "Command: invoke | contract_id={} function={:?} args={}",
+                contract_id,
+                function,
+                args.len()
+            );
+            invoke::run(
+                &cli.api_url,
+                &contract_id,
+                function.as_deref(),
+                args,
+                version.as_deref(),
+                json,
+            )
+            .await?;
+        }
+        Commands::Xdr { action } => match action {
+            XdrCommands::Encode {
+                input,
+                kind,
+                contract,
+                version,
+                function,
+                param,
+                type_name,
+            } => {
+                log::debug!("Command: xdr encode | kind={:?} contract={:?}", kind, contract);
+                let context = xdr::TypeContext {
+                    contract_id: contract.as_deref(),
+                    version: version.as_deref(),
+                    function: function.as_deref(),
+                    param: param.as_deref(),
+                    type_name: type_name.as_deref(),
+                };
+                xdr::encode(&cli.api_url, &input, kind, &context).await?;
+            }
+            XdrCommands::Decode {
+                input,
+                kind,
+                contract,
+                version,
+                function,
+                param,
+                type_name,
+            } => {
+                log::debug!("Command: xdr decode | kind={:?} contract={:?}", kind, contract);
+                let context = xdr::TypeContext {
+                    contract_id: contract.as_deref(),
+                    version: version.as_deref(),
+                    function: function.as_deref(),
+                    param: param.as_deref(),
+                    type_name: type_name.as_deref(),
+                };
+                xdr::decode(&cli.api_url, &input, kind, &context).await?;
+            }
+        },
+        Commands::Events { action } => match action {
+            EventsCommands::Schemas {
+                contract_id,
+                version,
+                json,
+            } => {
+                log::debug!("Command: events schemas | contract_id={}", contract_id);
+                events::schemas(&cli.api_url, &contract_id, version.as_deref(), json).await?;
+            }
+            EventsCommands::Register {
+                contract_id,
+                version,
+                file,
+            } => {
+                log::debug!(
+                    "Command: events register | contract_id={} version={}",
+                    contract_id,
+                    version
+                );
+                events::register(&cli.api_url, &contract_id, &version, &file).await?;
+            }
+            EventsCommands::Decode {
+                contract_id,
+                event,
+                topic,
+                data,
+                version,
+                json,
+            } => {
+                log::debug!("Command: events decode | contract_id={}", contract_id);
+                let request = shared::DecodeEventRequest {
+                    version,
+                    event,
+                    topics: topic,
+                    data,
+                };
+                events::decode(&cli.api_url, &contract_id, request, json).await?;
+            }
+        },
         Commands::Keys { action } => match action {
-            KeysCommands::Generate {} => {
-                log::debug!("Command: keys generate");
-                package_signing::generate_keypair()?;
+            KeysCommands::Generate { name } => {
+                log::debug!("Command: keys generate | name={}", name);
+                keystore::generate(&name)?;
+            }
+            KeysCommands::Import {
+                name,
+                ledger,
+                account_index,
+            } => {
+                log::debug!("Command: keys import | name={} ledger={}", name, ledger);
+                keystore::import(&name, ledger, account_index)?;
+            }
+            KeysCommands::List { json } => {
+                log::debug!("Command: keys list");
+                keystore::list(json)?;
+            }
+            KeysCommands::ExportPublic { name, format } => {
+                log::debug!("Command: keys export-public | name={}", name);
+                keystore::export_public(&name, &format)?;
+            }
+            KeysCommands::Sign {
+                name,
+                message,
+                file,
+                encoding,
+            } => {
+                log::debug!("Command: keys sign | name={} file={:?}", name, file);
+                let message = match (message, file) {
+                    (Some(message), _) => message.into_bytes(),
+                    (None, Some(file)) => std::fs::read(&file)
+                        .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", file, e))?,
+                    (None, None) => unreachable!("clap requires --message or --file"),
+                };
+                keystore::sign(&name, &message, &encoding)?;
             }
             KeysCommands::Revoke {
                 signature_id,
@@ -1606,6 +3646,113 @@ async fn main() -> Result<()> {
                 release_notes::list(&cli.api_url, &contract_id, json).await?;
             }
         },
+        Commands::Sbom {
+            project_dir,
+            contract_id,
+            version,
+            format,
+            output,
+            remote,
+        } => {
+            log::debug!(
+                "Command: sbom | project_dir={} contract_id={:?} format={} remote={}",
+                project_dir,
+                contract_id,
+                format,
+                remote
+            );
+            sbom::run(
+                &cli.api_url,
+                &project_dir,
+                contract_id.as_deref(),
+                version.as_deref(),
+                &format,
+                output.as_deref(),
+                remote,
+            )
+            .await?;
+        }
+        Commands::Install {
+            contract_id,
+            collection,
+            version,
+            output_dir,
+            require_provenance,
+            update,
+        } => {
+            log::debug!(
+                "Command: install | contract_id={:?} collection={:?} version={:?} require_provenance={} update={}",
+                contract_id,
+                collection,
+                version,
+                require_provenance,
+                update
+            );
+            match (collection, contract_id) {
+                (Some(collection), _) => {
+                    commands::install_collection(
+                        &cli.api_url,
+                        &collection,
+                        &output_dir,
+                        require_provenance,
+                        update,
+                    )
+                    .await?;
+                }
+                (None, Some(contract_id)) => {
+                    commands::install(
+                        &cli.api_url,
+                        &contract_id,
+                        version.as_deref(),
+                        &output_dir,
+                        require_provenance,
+                        update,
+                    )
+                    .await?;
+                }
+                (None, None) => unreachable!("clap requires a contract ID or --collection"),
+            }
+        }
+        Commands::SelfUpdate { check } => {
+            log::debug!("Command: self-update | check={}", check);
+            self_update::self_update(&cli.api_url, check).await?;
+        }
+        Commands::Stats {
+            metric,
+            range,
+            chart,
+            json,
+        } => {
+            log::debug!(
+                "Command: stats | metric={} range={} chart={}",
+                metric,
+                range,
+                chart
+            );
+            stats::show(&cli.api_url, &metric, &range, chart, json).await?;
+        }
+        Commands::Telemetry { command } => match command {
+            TelemetryCommands::Enable => {
+                log::debug!("Command: telemetry enable");
+                telemetry::enable(&cli.api_url).await?;
+            }
+            TelemetryCommands::Disable => {
+                log::debug!("Command: telemetry disable");
+                telemetry::disable()?;
+            }
+            TelemetryCommands::Status { json } => {
+                log::debug!("Command: telemetry status");
+                telemetry::status(json)?;
+            }
+        },
+        Commands::Extensions => {
+            log::debug!("Command: extensions");
+            extensions::list()?;
+        }
+        Commands::External(args) => {
+            log::debug!("Command: external | args={:?}", args);
+            extensions::run_external(&args, &cli.api_url, cfg_network)?;
+        }
     }
 
     Ok(())
@@ -611,6 +611,11 @@ fn snapshot_path(contract_id: &str) -> PathBuf {
         .join(format!("{}.json", contract_id))
 }
 
+/// Whether a snapshot for `contract_id` is saved in the working directory
+pub fn snapshot_exists(contract_id: &str) -> bool {
+    snapshot_path(contract_id).is_file()
+}
+
 fn load_snapshot(contract_id: &str) -> Result<ContractSnapshot> {
     let path = snapshot_path(contract_id);
     let data = fs::read_to_string(&path).with_context(|| {
@@ -17,7 +17,7 @@ pub async fn create_policy(
     expiry_secs: Option<u32>,
     created_by: &str,
 ) -> Result<()> {
-    let client = reqwest::Client::new();
+    let client = crate::http::client();
     let url = format!("{}/api/multisig/policies", api_url);
 
     let payload = json!({
@@ -38,8 +38,7 @@ pub async fn create_policy(
         .context("Failed to reach registry API")?;
 
     if !response.status().is_success() {
-        let err = response.text().await?;
-        anyhow::bail!("API error: {}", err);
+        return Err(crate::api_errors::failure(response, "API error").await);
     }
 
     let policy: serde_json::Value = response.json().await?;
@@ -95,7 +94,7 @@ pub async fn create_proposal(
     proposer: &str,
     description: Option<&str>,
 ) -> Result<()> {
-    let client = reqwest::Client::new();
+    let client = crate::http::client();
     let url = format!("{}/api/contracts/deploy-proposal", api_url);
 
     let payload = json!({
@@ -118,8 +117,7 @@ pub async fn create_proposal(
         .context("Failed to create deployment proposal")?;
 
     if !response.status().is_success() {
-        let err = response.text().await?;
-        anyhow::bail!("API error: {}", err);
+        return Err(crate::api_errors::failure(response, "API error").await);
     }
 
     let proposal: serde_json::Value = response.json().await?;
@@ -170,13 +168,23 @@ pub async fn create_proposal(
 // Sign a proposal
 // ─────────────────────────────────────────────────────────────────────────────
 
+/// Hex signature of a proposal approval, recorded as the signature data when
+/// approving with a stored key
+pub fn approval_signature(key: &crate::keystore::Key, proposal_id: &str) -> Result<String> {
+    let message = format!(
+        "soroban-registry deployment approval\nproposal: {}",
+        proposal_id
+    );
+    Ok(hex::encode(key.sign(message.as_bytes())?))
+}
+
 pub async fn sign_proposal(
     api_url: &str,
     proposal_id: &str,
     signer_address: &str,
     signature_data: Option<&str>,
 ) -> Result<()> {
-    let client = reqwest::Client::new();
+    let client = crate::http::client();
     let url = format!("{}/api/contracts/{}/sign", api_url, proposal_id);
 
     let payload = json!({
@@ -235,7 +243,7 @@ pub async fn sign_proposal(
 // ─────────────────────────────────────────────────────────────────────────────
 
 pub async fn execute_proposal(api_url: &str, proposal_id: &str) -> Result<()> {
-    let client = reqwest::Client::new();
+    let client = crate::http::client();
     let url = format!("{}/api/contracts/{}/execute", api_url, proposal_id);
 
     println!("\n{}", "Executing deployment proposal...".bold().cyan());
@@ -281,7 +289,7 @@ pub async fn execute_proposal(api_url: &str, proposal_id: &str) -> Result<()> {
 // ─────────────────────────────────────────────────────────────────────────────
 
 pub async fn proposal_info(api_url: &str, proposal_id: &str) -> Result<()> {
-    let client = reqwest::Client::new();
+    let client = crate::http::client();
     let url = format!("{}/api/contracts/{}/proposal", api_url, proposal_id);
 
     let response = client
@@ -291,8 +299,7 @@ pub async fn proposal_info(api_url: &str, proposal_id: &str) -> Result<()> {
         .context("Failed to fetch proposal info")?;
 
     if !response.status().is_success() {
-        let err = response.text().await?;
-        anyhow::bail!("API error: {}", err);
+        return Err(crate::api_errors::failure(response, "API error").await);
     }
 
     let data: serde_json::Value = response.json().await?;
@@ -410,7 +417,7 @@ pub async fn list_proposals(
     status_filter: Option<&str>,
     limit: usize,
 ) -> Result<()> {
-    let client = reqwest::Client::new();
+    let client = crate::http::client();
     let mut url = format!("{}/api/multisig/proposals?limit={}", api_url, limit);
     if let Some(s) = status_filter {
         url.push_str(&format!("&status={}", s));
@@ -423,8 +430,7 @@ pub async fn list_proposals(
         .context("Failed to list proposals")?;
 
     if !response.status().is_success() {
-        let err = response.text().await?;
-        anyhow::bail!("API error: {}", err);
+        return Err(crate::api_errors::failure(response, "API error").await);
     }
 
     let data: serde_json::Value = response.json().await?;
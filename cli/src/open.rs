@@ -0,0 +1,117 @@
+use std::process::Command;
+
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use serde_json::Value;
+
+use crate::conversions::contract_path;
+
+/// Which page of a contract `open` shows
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Target {
+    /// The contract's page on the registry's web UI
+    Registry,
+    Repository,
+    Homepage,
+}
+
+/// URL for `target`, from the contract as returned by the API
+fn target_url(api_url: &str, contract: &Value, target: Target) -> Result<String> {
+    let name = crate::conversions::contract_display_name(contract);
+    match target {
+        Target::Registry => {
+            let id = contract["id"]
+                .as_str()
+                .and_then(|id| uuid::Uuid::parse_str(id).ok())
+                .context("Registry response is missing the contract's id")?;
+            Ok(shared::contract_page_url(api_url, &id))
+        }
+        Target::Repository => match contract["repository_url"].as_str() {
+            Some(url) => Ok(url.to_string()),
+            None => bail!("{} does not declare a repository URL", name),
+        },
+        Target::Homepage => match contract["homepage"].as_str() {
+            Some(url) => Ok(url.to_string()),
+            None => bail!("{} does not declare a homepage", name),
+        },
+    }
+}
+
+/// Hand `url` to the platform's default browser
+fn launch_browser(url: &str) -> Result<()> {
+    let mut command = if cfg!(target_os = "macos") {
+        Command::new("open")
+    } else if cfg!(windows) {
+        let mut command = Command::new("cmd");
+        command.args(["/C", "start", ""]);
+        command
+    } else {
+        Command::new("xdg-open")
+    };
+    let status = command
+        .arg(url)
+        .status()
+        .context("Failed to launch a browser")?;
+    if !status.success() {
+        bail!("The browser launcher exited with {}", status);
+    }
+    Ok(())
+}
+
+/// Resolve `id` and open its registry page (or repository/homepage) in the
+/// default browser; `print_only` just prints the URL
+pub async fn open(
+    api_url: &str,
+    id: &str,
+    target: Target,
+    print_only: bool,
+    network: crate::config::Network,
+) -> Result<()> {
+    let url = format!(
+        "{}/api/contracts/{}",
+        api_url.trim_end_matches('/'),
+        contract_path(id)
+    );
+    let response = crate::http::client()
+        .get(&url)
+        .query(&[("network", network.to_string())])
+        .send()
+        .await?;
+    if !response.status().is_success() {
+        return Err(crate::api_errors::failure(response, "Failed to resolve contract").await);
+    }
+    let contract: Value = response.json().await?;
+    let page = target_url(api_url, &contract, target)?;
+
+    if print_only {
+        println!("{}", page);
+        return Ok(());
+    }
+    println!("{} Opening {}", "→".cyan(), page.bold());
+    launch_browser(&page)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn picks_the_requested_page() {
+        let contract = json!({
+            "id": "6f1c2a9e-3b7d-4c5e-9a1f-0d2e3c4b5a69",
+            "name": "amm",
+            "namespace": "alice",
+            "repository_url": "https://github.com/alice/amm",
+        });
+        assert_eq!(
+            target_url("http://localhost:3001/", &contract, Target::Registry).unwrap(),
+            "http://localhost:3001/contracts/6f1c2a9e-3b7d-4c5e-9a1f-0d2e3c4b5a69"
+        );
+        assert_eq!(
+            target_url("http://localhost:3001", &contract, Target::Repository).unwrap(),
+            "https://github.com/alice/amm"
+        );
+        assert!(target_url("http://localhost:3001", &contract, Target::Homepage).is_err());
+    }
+}
@@ -0,0 +1,557 @@
+//! `optimize <wasm>`: find what makes a contract binary bigger than it needs to
+//! be and estimate what fixing it saves. `--apply` makes the safe changes:
+//! strip custom sections the host never reads, then run `wasm-opt` if it is
+//! installed, and compares the result with the original.
+
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use serde::Serialize;
+
+const WASM_HEADER: &[u8] = b"\0asm\x01\0\0\0";
+const CUSTOM_SECTION: u8 = 0;
+const EXPORT_SECTION: u8 = 7;
+const DATA_SECTION: u8 = 11;
+
+/// Custom sections Soroban reads (the interface and build metadata); always kept
+const CONTRACT_SECTIONS: [&str; 3] = ["contractspecv0", "contractenvmetav0", "contractmetav0"];
+/// Data segments at least this big are worth a look
+const LARGE_DATA_SEGMENT: usize = 4096;
+
+// The registry's approximate per-KB rates for storing and shipping a WASM upload
+const STORAGE_COST_PER_KB: u64 = 50_000; // stroops
+const BANDWIDTH_COST_PER_KB: u64 = 10_000; // stroops
+
+/// Cursor over a module's bytes; every read fails cleanly on truncated input
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn byte(&mut self) -> Result<u8> {
+        let byte = *self
+            .bytes
+            .get(self.pos)
+            .context("unexpected end of module")?;
+        self.pos += 1;
+        Ok(byte)
+    }
+
+    /// LEB128, unsigned or signed; only its length matters here
+    fn leb(&mut self) -> Result<u64> {
+        let mut result = 0u64;
+        for shift in (0..70).step_by(7) {
+            let byte = self.byte()?;
+            result |= u64::from(byte & 0x7f) << shift.min(63);
+            if byte & 0x80 == 0 {
+                return Ok(result);
+            }
+        }
+        bail!("integer too long")
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        u32::try_from(self.leb()?).context("integer out of range")
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|end| *end <= self.bytes.len())
+            .context("unexpected end of module")?;
+        let slice = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(slice)
+    }
+
+    fn name(&mut self) -> Result<String> {
+        let len = self.u32()? as usize;
+        String::from_utf8(self.take(len)?.to_vec()).context("name is not UTF-8")
+    }
+
+    /// Constant offset expression of an active data segment
+    fn const_expr(&mut self) -> Result<()> {
+        match self.byte()? {
+            // i32.const, i64.const, global.get
+            0x41 | 0x42 | 0x23 => {
+                self.leb()?;
+            }
+            op => bail!("unsupported data segment offset (opcode {:#04x})", op),
+        }
+        match self.byte()? {
+            0x0b => Ok(()),
+            _ => bail!("unsupported data segment offset expression"),
+        }
+    }
+}
+
+/// A top-level section; `range` covers its id and size as well as its contents
+struct Section<'a> {
+    id: u8,
+    /// Name of a custom section
+    name: Option<String>,
+    range: Range<usize>,
+    contents: &'a [u8],
+}
+
+impl Section<'_> {
+    fn is_strippable(&self) -> bool {
+        matches!(&self.name, Some(name) if !CONTRACT_SECTIONS.contains(&name.as_str()))
+    }
+}
+
+fn sections(bytes: &[u8]) -> Result<Vec<Section<'_>>> {
+    if !bytes.starts_with(WASM_HEADER) {
+        bail!("not a WebAssembly 1.0 module");
+    }
+    let mut reader = Reader {
+        bytes,
+        pos: WASM_HEADER.len(),
+    };
+    let mut sections = Vec::new();
+    while reader.pos < bytes.len() {
+        let start = reader.pos;
+        let id = reader.byte()?;
+        let len = reader.u32()? as usize;
+        let contents = reader.take(len)?;
+        let name = if id == CUSTOM_SECTION {
+            Some(
+                Reader {
+                    bytes: contents,
+                    pos: 0,
+                }
+                .name()?,
+            )
+        } else {
+            None
+        };
+        sections.push(Section {
+            id,
+            name,
+            range: start..reader.pos,
+            contents,
+        });
+    }
+    Ok(sections)
+}
+
+/// Something worth changing, and roughly what it would save
+#[derive(Debug, Serialize, PartialEq)]
+struct Finding {
+    kind: &'static str,
+    detail: String,
+    savings_bytes: usize,
+    /// Whether `--apply` fixes it
+    fixed_by_apply: bool,
+}
+
+fn custom_section_finding(name: &str, size: usize) -> Finding {
+    let (kind, detail) = if name.starts_with(".debug") {
+        (
+            "debug_info",
+            format!(
+                "`{}` holds DWARF debug info; build with --release and debug = false",
+                name
+            ),
+        )
+    } else if name == "name" {
+        (
+            "symbol_names",
+            "`name` maps functions to symbols; set strip = true in the release profile".to_string(),
+        )
+    } else {
+        (
+            "custom_section",
+            format!("custom section `{}` is never read by the host", name),
+        )
+    };
+    Finding {
+        kind,
+        detail,
+        savings_bytes: size,
+        fixed_by_apply: true,
+    }
+}
+
+/// Exports the host can never use: it only calls functions, so exported
+/// globals and tables (`__data_end`, `__heap_base`) are linker leftovers
+fn unused_exports(contents: &[u8]) -> Result<Vec<Finding>> {
+    let mut reader = Reader {
+        bytes: contents,
+        pos: 0,
+    };
+    let mut findings = Vec::new();
+    for _ in 0..reader.u32()? {
+        let start = reader.pos;
+        let name = reader.name()?;
+        let kind = reader.byte()?;
+        reader.u32()?;
+        let what = match kind {
+            0x01 => "table",
+            0x03 => "global",
+            _ => continue,
+        };
+        findings.push(Finding {
+            kind: "unused_export",
+            detail: format!(
+                "{} `{}` is exported but the host only calls functions; drop it at link time",
+                what, name
+            ),
+            savings_bytes: reader.pos - start,
+            fixed_by_apply: false,
+        });
+    }
+    Ok(findings)
+}
+
+/// Large data segments; trailing zeros in them are free to drop, since memory
+/// starts out zeroed
+fn large_data_segments(contents: &[u8]) -> Result<Vec<Finding>> {
+    let mut reader = Reader {
+        bytes: contents,
+        pos: 0,
+    };
+    let mut findings = Vec::new();
+    for index in 0..reader.u32()? {
+        let active = match reader.u32()? {
+            0 => {
+                reader.const_expr()?;
+                true
+            }
+            1 => false,
+            2 => {
+                reader.u32()?;
+                reader.const_expr()?;
+                true
+            }
+            flags => bail!("unknown data segment kind {}", flags),
+        };
+        let len = reader.u32()? as usize;
+        let data = reader.take(len)?;
+        if len < LARGE_DATA_SEGMENT {
+            continue;
+        }
+        let trailing_zeros = if active {
+            data.iter().rev().take_while(|byte| **byte == 0).count()
+        } else {
+            0
+        };
+        let mut detail = format!(
+            "data segment {} is {} bytes; look for large constant tables or embedded strings",
+            index, len
+        );
+        if trailing_zeros > 0 {
+            detail.push_str(&format!(", {} of them trailing zeros", trailing_zeros));
+        }
+        findings.push(Finding {
+            kind: "large_data_segment",
+            detail,
+            savings_bytes: trailing_zeros,
+            fixed_by_apply: false,
+        });
+    }
+    Ok(findings)
+}
+
+fn analyze(bytes: &[u8]) -> Result<Vec<Finding>> {
+    let mut findings = Vec::new();
+    let mut processed_by_wasm_opt = false;
+    for section in sections(bytes)? {
+        match (section.id, &section.name) {
+            (CUSTOM_SECTION, Some(name)) => {
+                if name == "producers" {
+                    processed_by_wasm_opt = section
+                        .contents
+                        .windows(b"wasm-opt".len())
+                        .any(|window| window == b"wasm-opt");
+                }
+                if section.is_strippable() {
+                    findings.push(custom_section_finding(name, section.range.len()));
+                }
+            }
+            (EXPORT_SECTION, _) => findings.extend(unused_exports(section.contents)?),
+            (DATA_SECTION, _) => findings.extend(large_data_segments(section.contents)?),
+            _ => {}
+        }
+    }
+    if !processed_by_wasm_opt {
+        findings.push(Finding {
+            kind: "not_optimized",
+            detail: "no sign of wasm-opt in the producers section; `wasm-opt -Oz` usually shrinks \
+                     code further"
+                .to_string(),
+            savings_bytes: 0,
+            fixed_by_apply: true,
+        });
+    }
+    Ok(findings)
+}
+
+/// `bytes` without the custom sections the host never reads
+fn strip_custom_sections(bytes: &[u8]) -> Result<Vec<u8>> {
+    let mut stripped = WASM_HEADER.to_vec();
+    for section in sections(bytes)? {
+        if !section.is_strippable() {
+            stripped.extend_from_slice(&bytes[section.range]);
+        }
+    }
+    Ok(stripped)
+}
+
+/// Approximate stroops to upload a WASM of `size` bytes
+fn upload_cost(size: usize) -> u64 {
+    let kb = (size as u64).div_ceil(1024);
+    kb * (STORAGE_COST_PER_KB + BANDWIDTH_COST_PER_KB)
+}
+
+/// Run `wasm-opt -Oz` in place; false when it is not installed
+fn run_wasm_opt(path: &Path) -> Result<bool> {
+    let installed = Command::new("wasm-opt")
+        .arg("--version")
+        .output()
+        .is_ok_and(|output| output.status.success());
+    if !installed {
+        return Ok(false);
+    }
+    let status = Command::new("wasm-opt")
+        .arg("-Oz")
+        .arg(path)
+        .arg("-o")
+        .arg(path)
+        .status()
+        .context("Failed to run wasm-opt")?;
+    if !status.success() {
+        bail!("wasm-opt exited with {}", status);
+    }
+    Ok(true)
+}
+
+fn contract_sections(bytes: &[u8]) -> Result<Vec<String>> {
+    Ok(sections(bytes)?
+        .into_iter()
+        .filter_map(|section| section.name)
+        .filter(|name| CONTRACT_SECTIONS.contains(&name.as_str()))
+        .collect())
+}
+
+#[derive(Debug, Serialize)]
+struct Applied {
+    output: PathBuf,
+    size_bytes: usize,
+    upload_cost_stroops: u64,
+    wasm_opt: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct Report {
+    path: PathBuf,
+    size_bytes: usize,
+    upload_cost_stroops: u64,
+    estimated_savings_bytes: usize,
+    findings: Vec<Finding>,
+    applied: Option<Applied>,
+}
+
+fn default_output(wasm: &Path) -> PathBuf {
+    let stem = wasm
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "contract".to_string());
+    wasm.with_file_name(format!("{}.optimized.wasm", stem))
+}
+
+fn apply(bytes: &[u8], output: &Path, use_wasm_opt: bool) -> Result<Applied> {
+    std::fs::write(output, strip_custom_sections(bytes)?)
+        .with_context(|| format!("Failed to write {}", output.display()))?;
+    let wasm_opt = use_wasm_opt && run_wasm_opt(output)?;
+
+    let optimized = std::fs::read(output)?;
+    if contract_sections(&optimized)? != contract_sections(bytes)? {
+        bail!(
+            "{} lost its contract spec or metadata; keeping the original",
+            output.display()
+        );
+    }
+    Ok(Applied {
+        output: output.to_path_buf(),
+        size_bytes: optimized.len(),
+        upload_cost_stroops: upload_cost(optimized.len()),
+        wasm_opt,
+    })
+}
+
+fn print_report(report: &Report) {
+    println!("\n{}", "WASM Size Report".bold().cyan());
+    println!("{}", "=".repeat(60).cyan());
+    println!("  {}: {}", "File".bold(), report.path.display());
+    println!(
+        "  {}: {} bytes (~{} stroops to upload)",
+        "Size".bold(),
+        report.size_bytes,
+        report.upload_cost_stroops
+    );
+
+    if report.findings.is_empty() {
+        println!("\n  {} Nothing to trim", "✓".green());
+    } else {
+        println!();
+        for finding in &report.findings {
+            let marker = if finding.fixed_by_apply {
+                "●".yellow()
+            } else {
+                "○".bright_black()
+            };
+            let savings = if finding.savings_bytes > 0 {
+                format!(" (-{} bytes)", finding.savings_bytes)
+            } else {
+                String::new()
+            };
+            println!("  {} {}{}", marker, finding.detail, savings.green());
+        }
+        println!(
+            "\n  {}: {} bytes; {} marks what --apply fixes",
+            "Estimated savings".bold(),
+            report.estimated_savings_bytes,
+            "●".yellow()
+        );
+    }
+
+    if let Some(applied) = &report.applied {
+        let saved = report.size_bytes.saturating_sub(applied.size_bytes);
+        println!(
+            "\n  {} Wrote {}{}",
+            "✓".green(),
+            applied.output.display(),
+            if applied.wasm_opt {
+                " (stripped, wasm-opt -Oz)"
+            } else {
+                " (stripped; install wasm-opt to shrink code too)"
+            }
+        );
+        println!(
+            "  {}: {} → {} bytes (-{:.1}%)",
+            "Size".bold(),
+            report.size_bytes,
+            applied.size_bytes,
+            saved as f64 * 100.0 / report.size_bytes.max(1) as f64
+        );
+        println!(
+            "  {}: {} → {} stroops",
+            "Upload cost".bold(),
+            report.upload_cost_stroops,
+            applied.upload_cost_stroops
+        );
+    }
+    println!();
+}
+
+pub fn run(
+    wasm: &Path,
+    apply_changes: bool,
+    output: Option<PathBuf>,
+    use_wasm_opt: bool,
+    json: bool,
+) -> Result<()> {
+    let bytes =
+        std::fs::read(wasm).with_context(|| format!("Failed to read {}", wasm.display()))?;
+    let findings = analyze(&bytes)?;
+    let applied = if apply_changes {
+        let output = output.unwrap_or_else(|| default_output(wasm));
+        Some(apply(&bytes, &output, use_wasm_opt)?)
+    } else {
+        None
+    };
+    let report = Report {
+        path: wasm.to_path_buf(),
+        size_bytes: bytes.len(),
+        upload_cost_stroops: upload_cost(bytes.len()),
+        estimated_savings_bytes: findings.iter().map(|f| f.savings_bytes).sum(),
+        findings,
+        applied,
+    };
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print_report(&report);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn section(id: u8, contents: &[u8]) -> Vec<u8> {
+        let mut bytes = vec![id, contents.len() as u8];
+        bytes.extend_from_slice(contents);
+        bytes
+    }
+
+    fn custom(name: &str, payload: &[u8]) -> Vec<u8> {
+        let mut contents = vec![name.len() as u8];
+        contents.extend_from_slice(name.as_bytes());
+        contents.extend_from_slice(payload);
+        section(CUSTOM_SECTION, &contents)
+    }
+
+    fn module() -> Vec<u8> {
+        let mut bytes = WASM_HEADER.to_vec();
+        // Exports: function `hello`, global `__data_end`
+        let mut exports = vec![2, 5];
+        exports.extend_from_slice(b"hello");
+        exports.extend_from_slice(&[0x00, 0]);
+        exports.push(10);
+        exports.extend_from_slice(b"__data_end");
+        exports.extend_from_slice(&[0x03, 0]);
+        bytes.extend(section(EXPORT_SECTION, &exports));
+        bytes.extend(custom("contractspecv0", &[0, 0, 0, 0]));
+        bytes.extend(custom(".debug_info", &[1; 40]));
+        bytes.extend(custom("name", &[2; 10]));
+        bytes
+    }
+
+    #[test]
+    fn reports_strippable_sections_and_unused_exports() {
+        let findings = analyze(&module()).unwrap();
+        let kinds: Vec<_> = findings.iter().map(|f| f.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                "unused_export",
+                "debug_info",
+                "symbol_names",
+                "not_optimized"
+            ]
+        );
+        // Section id, size, name length and name, then the payload
+        assert_eq!(findings[1].savings_bytes, 2 + 1 + 11 + 40);
+    }
+
+    #[test]
+    fn stripping_keeps_the_contract_spec() {
+        let original = module();
+        let stripped = strip_custom_sections(&original).unwrap();
+        assert_eq!(
+            contract_sections(&stripped).unwrap(),
+            vec!["contractspecv0"]
+        );
+        assert!(analyze(&stripped)
+            .unwrap()
+            .iter()
+            .all(|f| f.kind != "debug_info" && f.kind != "symbol_names"));
+        assert_eq!(original.len() - stripped.len(), 54 + 2 + 1 + 4 + 10);
+    }
+
+    #[test]
+    fn upload_cost_rounds_up_to_whole_kilobytes() {
+        assert_eq!(upload_cost(1), 60_000);
+        assert_eq!(upload_cost(2048), 120_000);
+        assert_eq!(upload_cost(2049), 180_000);
+    }
+}
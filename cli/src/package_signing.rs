@@ -2,18 +2,18 @@ use anyhow::{bail, Context, Result};
 use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
 use chrono::Utc;
 use colored::Colorize;
-use ed25519_dalek::{Signer, SigningKey};
-use rand::rngs::OsRng;
 use serde_json::json;
 use sha2::{Digest, Sha256};
 use std::fs;
 use std::io::Read;
 use std::path::Path;
 
+use crate::keystore::Key;
+
 pub async fn sign_package(
     api_url: &str,
     package_path: &str,
-    private_key: &str,
+    key: &Key,
     contract_id: &str,
     version: &str,
     expires_at: Option<&str>,
@@ -26,16 +26,18 @@ pub async fn sign_package(
     println!("  {}: {}", "Package".bold(), package_path.bright_black());
     println!("  {}: {}", "Hash".bold(), package_hash.bright_black());
 
-    let signing_key = decode_private_key(private_key)?;
-    let verifying_key = signing_key.verifying_key();
-    let public_key_bytes = verifying_key.to_bytes();
-    let public_key_b64 = BASE64.encode(public_key_bytes);
+    if key.signs_digests() {
+        bail!(
+            "The registry verifies package signatures over the full message; \
+             sign packages with a local key"
+        );
+    }
+    let public_key_b64 = BASE64.encode(key.public_key());
 
     let message = create_signing_message(&package_hash, contract_id, version);
-    let signature = signing_key.sign(&message);
-    let signature_b64 = BASE64.encode(signature.to_bytes());
+    let signature_b64 = BASE64.encode(key.sign(&message)?);
 
-    let signing_address = derive_stellar_address(&public_key_bytes);
+    let signing_address = key.account();
 
     println!(
         "  {}: {}",
@@ -45,7 +47,7 @@ pub async fn sign_package(
     println!("  {}: {}", "Contract ID".bold(), contract_id.bright_black());
     println!("  {}: {}", "Version".bold(), version);
 
-    let client = reqwest::Client::new();
+    let client = crate::http::client();
     let url = format!("{}/api/signatures", api_url);
 
     let expires_dt = expires_at
@@ -73,8 +75,7 @@ pub async fn sign_package(
         .context("Failed to reach registry API")?;
 
     if !response.status().is_success() {
-        let err = response.text().await?;
-        bail!("API error: {}", err);
+        return Err(crate::api_errors::failure(response, "API error").await);
     }
 
     let result: serde_json::Value = response.json().await?;
@@ -115,7 +116,7 @@ pub async fn verify_package(
     println!("  {}: {}", "Package".bold(), package_path.bright_black());
     println!("  {}: {}", "Hash".bold(), package_hash.bright_black());
 
-    let client = reqwest::Client::new();
+    let client = crate::http::client();
 
     if let Some(sig_b64) = signature_arg {
         verify_with_signature(
@@ -211,8 +212,7 @@ async fn verify_from_registry(
         .context("Failed to reach registry API")?;
 
     if !response.status().is_success() {
-        let err = response.text().await?;
-        bail!("Failed to lookup signature: {}", err);
+        return Err(crate::api_errors::failure(response, "Failed to lookup signature").await);
     }
 
     let result: serde_json::Value = response.json().await?;
@@ -293,7 +293,7 @@ pub async fn revoke_signature(
 ) -> Result<()> {
     println!("\n{}", "Revoking signature...".bold().cyan());
 
-    let client = reqwest::Client::new();
+    let client = crate::http::client();
     let url = format!("{}/api/signatures/{}/revoke", api_url, signature_id);
 
     let payload = json!({
@@ -309,8 +309,7 @@ pub async fn revoke_signature(
         .context("Failed to reach registry API")?;
 
     if !response.status().is_success() {
-        let err = response.text().await?;
-        bail!("Failed to revoke signature: {}", err);
+        return Err(crate::api_errors::failure(response, "Failed to revoke signature").await);
     }
 
     println!("{}", "✓ Signature revoked successfully!".green().bold());
@@ -330,7 +329,7 @@ pub async fn get_chain_of_custody(api_url: &str, contract_id: &str) -> Result<()
     println!("\n{}", "Chain of Custody".bold().cyan());
     println!("{}", "=".repeat(70).cyan());
 
-    let client = reqwest::Client::new();
+    let client = crate::http::client();
     let url = format!("{}/api/signatures/custody/{}", api_url, contract_id);
 
     let response = client
@@ -340,8 +339,7 @@ pub async fn get_chain_of_custody(api_url: &str, contract_id: &str) -> Result<()
         .context("Failed to reach registry API")?;
 
     if !response.status().is_success() {
-        let err = response.text().await?;
-        bail!("Failed to get chain of custody: {}", err);
+        return Err(crate::api_errors::failure(response, "Failed to get chain of custody").await);
     }
 
     let result: serde_json::Value = response.json().await?;
@@ -393,7 +391,7 @@ pub async fn get_transparency_log(
     println!("\n{}", "Transparency Log".bold().cyan());
     println!("{}", "=".repeat(70).cyan());
 
-    let client = reqwest::Client::new();
+    let client = crate::http::client();
     let mut url = format!("{}/api/signatures/transparency?limit={}", api_url, limit);
 
     if let Some(cid) = contract_id {
@@ -410,8 +408,7 @@ pub async fn get_transparency_log(
         .context("Failed to reach registry API")?;
 
     if !response.status().is_success() {
-        let err = response.text().await?;
-        bail!("Failed to get transparency log: {}", err);
+        return Err(crate::api_errors::failure(response, "Failed to get transparency log").await);
     }
 
     let result: serde_json::Value = response.json().await?;
@@ -460,43 +457,6 @@ pub async fn get_transparency_log(
     Ok(())
 }
 
-pub fn generate_keypair() -> Result<()> {
-    println!("\n{}", "Generating Ed25519 keypair...".bold().cyan());
-
-    let mut csprng = OsRng;
-    let signing_key: SigningKey = SigningKey::generate(&mut csprng);
-    let verifying_key = signing_key.verifying_key();
-
-    let secret_bytes = signing_key.to_bytes();
-    let public_bytes = verifying_key.to_bytes();
-
-    let secret_b64 = BASE64.encode(secret_bytes);
-    let public_b64 = BASE64.encode(public_bytes);
-    let stellar_address = derive_stellar_address(&public_bytes);
-
-    println!("{}", "\n✓ Keypair generated successfully!".green().bold());
-    println!();
-    println!("  {} (keep this secret!):", "Private Key".bold().red());
-    println!("  {}", secret_b64.bright_red());
-    println!();
-    println!("  {}:", "Public Key".bold().green());
-    println!("  {}", public_b64.bright_green());
-    println!();
-    println!("  {}:", "Stellar Address".bold().cyan());
-    println!("  {}", stellar_address.bright_cyan());
-    println!();
-    println!(
-        "  {} Use the private key to sign packages:",
-        "→".bright_black()
-    );
-    println!(
-        "  soroban-registry sign package.tar.gz --private-key {} --contract-id <ID> --version <VERSION>\n",
-        secret_b64.bright_red()
-    );
-
-    Ok(())
-}
-
 fn read_package_file(path: &str) -> Result<Vec<u8>> {
     let path = Path::new(path);
     if !path.exists() {
@@ -517,39 +477,10 @@ fn compute_hash(data: &[u8]) -> String {
     format!("{:x}", hasher.finalize())
 }
 
-fn decode_private_key(key: &str) -> Result<SigningKey> {
-    let bytes = BASE64
-        .decode(key)
-        .context("Invalid private key format (expected base64)")?;
-
-    let bytes: [u8; 32] = bytes
-        .as_slice()
-        .try_into()
-        .map_err(|_| anyhow::anyhow!("Private key must be 32 bytes"))?;
-
-    Ok(SigningKey::from_bytes(&bytes))
-}
-
 fn create_signing_message(hash: &str, contract_id: &str, version: &str) -> Vec<u8> {
     format!("{}:{}:{}", contract_id, version, hash).into_bytes()
 }
 
-fn derive_stellar_address(public_key_bytes: &[u8; 32]) -> String {
-    use ripemd::Ripemd160;
-    use sha2::{Digest as _, Sha256};
-
-    let sha256_hash = Sha256::digest(public_key_bytes);
-    let ripemd_hash = Ripemd160::digest(&sha256_hash);
-
-    let mut versioned = vec![0x00];
-    versioned.extend_from_slice(&ripemd_hash);
-
-    let checksum = Sha256::digest(&Sha256::digest(&versioned));
-    versioned.extend_from_slice(&checksum[..4]);
-
-    bs58::encode(&versioned).into_string()
-}
-
 /// Verify a contract binary locally against an Ed25519 signature and public key.
 /// This does not contact the registry API and is suitable for offline verification.
 pub fn verify_contract_local(
@@ -52,6 +52,17 @@ pub struct SecurityPatch {
     pub rollout_percentage: u8,
     pub description: Option<String>,
     pub created_at: DateTime<Utc>,
+    /// Critical patch still waiting on a second maintainer's approval
+    #[serde(default)]
+    pub approval_pending: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PatchApproval {
+    pub id: Uuid,
+    pub patch_id: Uuid,
+    pub approver_address: String,
+    pub created_at: DateTime<Utc>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -60,6 +71,70 @@ pub struct PatchAudit {
     pub contract_id: Uuid,
     pub patch_id: Uuid,
     pub applied_at: DateTime<Utc>,
+    #[serde(default)]
+    pub applied_by: Option<String>,
+    #[serde(default)]
+    pub rollout_position: Option<u32>,
+    #[serde(default)]
+    pub eligible_contracts: Option<u32>,
+}
+
+/// One entry of a contract's patch history
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContractPatch {
+    pub patch_id: Uuid,
+    pub target_version: String,
+    pub severity: Severity,
+    pub new_wasm_hash: String,
+    pub description: Option<String>,
+    pub rollout_percentage: u8,
+    pub applied_at: DateTime<Utc>,
+    pub applied_by: Option<String>,
+    pub rollout_position: Option<u32>,
+    pub eligible_contracts: Option<u32>,
+}
+
+impl ContractPatch {
+    /// Where in the rollout the contract got the patch, e.g. "contract 3 of 40 (8%)"
+    pub fn rollout_stage(&self) -> Option<String> {
+        let (position, eligible) = (self.rollout_position?, self.eligible_contracts?);
+        if eligible == 0 {
+            return None;
+        }
+        let percent = (position as f64 * 100.0 / eligible as f64).round() as u32;
+        Some(format!(
+            "contract {} of {} ({}%)",
+            position, eligible, percent
+        ))
+    }
+}
+
+/// A deployment running an affected version
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AffectedDeployment {
+    pub environment: String,
+    pub status: String,
+    pub version: String,
+    pub wasm_hash: String,
+}
+
+/// A contract with a published version inside a patch's range
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AffectedContract {
+    pub id: Uuid,
+    pub contract_id: String,
+    pub name: String,
+    pub network: String,
+    pub publisher_address: String,
+    pub versions: Vec<String>,
+    #[serde(default)]
+    pub deployments: Vec<AffectedDeployment>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct AffectedResponse {
+    items: Vec<AffectedContract>,
+    total_contracts: usize,
 }
 
 pub struct PatchManager;
@@ -80,7 +155,7 @@ impl PatchManager {
         severity: Severity,
         rollout: u8,
     ) -> Result<SecurityPatch> {
-        let client = reqwest::Client::new();
+        let client = crate::http::client();
         let payload = serde_json::json!({
             "target_version": version,
             "severity": severity,
@@ -90,12 +165,33 @@ impl PatchManager {
 
         let resp = client
             .post(format!("{}/api/patches", api_url))
+            .bearer_auth(crate::http::auth_token()?)
+            .header(
+                crate::http::IDEMPOTENCY_KEY_HEADER,
+                crate::http::idempotency_key(),
+            )
             .json(&payload)
             .send()
             .await?;
 
         if !resp.status().is_success() {
-            bail!("failed to create patch: {}", resp.text().await?);
+            return Err(crate::api_errors::failure(resp, "failed to create patch").await);
+        }
+
+        Ok(resp.json().await?)
+    }
+
+    /// Contracts with a published version in the patch's range, computed by the registry
+    async fn affected(api_url: &str, patch_id: &str) -> Result<AffectedResponse> {
+        let resp = crate::http::client()
+            .get(format!("{}/api/patches/{}/affected", api_url, patch_id))
+            .send()
+            .await?;
+
+        if !resp.status().is_success() {
+            return Err(
+                crate::api_errors::failure(resp, "failed to list affected contracts").await,
+            );
         }
 
         Ok(resp.json().await?)
@@ -104,8 +200,8 @@ impl PatchManager {
     pub async fn find_vulnerable(
         api_url: &str,
         patch_id: &str,
-    ) -> Result<(SecurityPatch, Vec<serde_json::Value>)> {
-        let client = reqwest::Client::new();
+    ) -> Result<(SecurityPatch, Vec<AffectedContract>)> {
+        let client = crate::http::client();
 
         let patch_resp = client
             .get(format!("{}/api/patches/{}", api_url, patch_id))
@@ -113,27 +209,25 @@ impl PatchManager {
             .await?;
 
         if !patch_resp.status().is_success() {
-            bail!("patch not found: {}", patch_id);
+            let context = format!("patch {} not found", patch_id);
+            return Err(crate::api_errors::failure(patch_resp, context).await);
         }
 
         let patch: SecurityPatch = patch_resp.json().await?;
+        let affected = Self::affected(api_url, patch_id).await?;
 
-        let contracts_resp = client
-            .get(format!(
-                "{}/api/contracts?wasm_hash={}",
-                api_url, patch.target_version
-            ))
-            .send()
-            .await?;
-
-        let data: serde_json::Value = contracts_resp.json().await?;
-        let contracts = data["items"].as_array().cloned().unwrap_or_default();
-
-        Ok((patch, contracts))
+        Ok((patch, affected.items))
     }
 
-    pub async fn apply(api_url: &str, contract_id: &str, patch_id: &str) -> Result<PatchAudit> {
-        let client = reqwest::Client::new();
+    /// Record the patch against `contract_id`; with `dry_run` the registry runs
+    /// every rollout check and rolls the record back
+    pub async fn apply(
+        api_url: &str,
+        contract_id: &str,
+        patch_id: &str,
+        dry_run: bool,
+    ) -> Result<PatchAudit> {
+        let client = crate::http::client();
 
         let patch_resp = client
             .get(format!("{}/api/patches/{}", api_url, patch_id))
@@ -141,7 +235,8 @@ impl PatchManager {
             .await?;
 
         if !patch_resp.status().is_success() {
-            bail!("patch not found: {}", patch_id);
+            let context = format!("patch {} not found", patch_id);
+            return Err(crate::api_errors::failure(patch_resp, context).await);
         }
 
         let patch: SecurityPatch = patch_resp.json().await?;
@@ -154,16 +249,7 @@ impl PatchManager {
         let audits_data: serde_json::Value = audits_resp.json().await?;
         let applied = audits_data["total"].as_u64().unwrap_or(0) as usize;
 
-        let contracts_resp = client
-            .get(format!(
-                "{}/api/contracts?wasm_hash={}",
-                api_url, patch.target_version
-            ))
-            .send()
-            .await?;
-
-        let contracts_data: serde_json::Value = contracts_resp.json().await?;
-        let total = contracts_data["total"].as_u64().unwrap_or(0) as usize;
+        let total = Self::affected(api_url, patch_id).await?.total_contracts;
 
         if !Self::check_rollout(applied, total, patch.rollout_percentage) {
             bail!(
@@ -180,14 +266,55 @@ impl PatchManager {
             "patch_id": patch_id,
         });
 
-        let resp = client
+        let mut request = client
             .post(format!("{}/api/patches/{}/apply", api_url, patch_id))
-            .json(&payload)
+            .bearer_auth(crate::http::auth_token()?);
+        request = if dry_run {
+            request.query(&crate::dry_run::QUERY)
+        } else {
+            request.header(
+                crate::http::IDEMPOTENCY_KEY_HEADER,
+                crate::http::idempotency_key(),
+            )
+        };
+        let resp = request.json(&payload).send().await?;
+
+        if !resp.status().is_success() {
+            return Err(crate::api_errors::failure(resp, "failed to apply patch").await);
+        }
+
+        Ok(resp.json().await?)
+    }
+
+    /// Patches applied to `contract_id`, oldest first
+    pub async fn history(api_url: &str, contract_id: &str) -> Result<Vec<ContractPatch>> {
+        let resp = crate::http::client()
+            .get(format!(
+                "{}/api/contracts/{}/patches",
+                api_url,
+                crate::conversions::contract_path(contract_id)
+            ))
+            .send()
+            .await?;
+
+        if !resp.status().is_success() {
+            return Err(crate::api_errors::failure(resp, "failed to fetch patch history").await);
+        }
+
+        let data: serde_json::Value = resp.json().await?;
+        Ok(serde_json::from_value(data["items"].clone())?)
+    }
+
+    /// Sign off on a critical patch someone else created
+    pub async fn approve(api_url: &str, patch_id: &str) -> Result<PatchApproval> {
+        let resp = crate::http::client()
+            .post(format!("{}/api/patches/{}/approve", api_url, patch_id))
+            .bearer_auth(crate::http::auth_token()?)
             .send()
             .await?;
 
         if !resp.status().is_success() {
-            bail!("failed to apply patch: {}", resp.text().await?);
+            return Err(crate::api_errors::failure(resp, "failed to approve patch").await);
         }
 
         Ok(resp.json().await?)
@@ -255,4 +382,23 @@ mod tests {
         assert!(PatchManager::check_rollout(0, 1, 1));
         assert!(!PatchManager::check_rollout(1, 1, 1));
     }
+
+    #[test]
+    fn rollout_stage_needs_both_counts() {
+        let mut entry = ContractPatch {
+            patch_id: Uuid::nil(),
+            target_version: "1.0.0".to_string(),
+            severity: Severity::High,
+            new_wasm_hash: "a".repeat(64),
+            description: None,
+            rollout_percentage: 50,
+            applied_at: Utc::now(),
+            applied_by: None,
+            rollout_position: Some(3),
+            eligible_contracts: Some(40),
+        };
+        assert_eq!(entry.rollout_stage().unwrap(), "contract 3 of 40 (8%)");
+        entry.eligible_contracts = None;
+        assert_eq!(entry.rollout_stage(), None);
+    }
 }
@@ -0,0 +1,204 @@
+//! Progress of long operations (publish uploads, imports, waiting on jobs).
+//! With `--progress json` every step is also written to stderr as one JSON
+//! object per line, so GUIs and CI wrappers can follow along without scraping
+//! the human output on stdout.
+
+use std::io::Write;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Instant;
+
+use anyhow::bail;
+use serde::Serialize;
+
+/// How progress is reported, from the global `--progress` flag
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ProgressFormat {
+    /// Only the usual human-readable output
+    #[default]
+    Human,
+    /// Human output plus JSON lines on stderr
+    Json,
+}
+
+impl std::str::FromStr for ProgressFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s {
+            "human" => Ok(ProgressFormat::Human),
+            "json" => Ok(ProgressFormat::Json),
+            other => bail!(
+                "Unknown progress format '{}' (expected: human, json)",
+                other
+            ),
+        }
+    }
+}
+
+static JSON_EVENTS: AtomicBool = AtomicBool::new(false);
+
+pub fn set_format(format: ProgressFormat) {
+    JSON_EVENTS.store(format == ProgressFormat::Json, Ordering::Relaxed);
+}
+
+fn json_events() -> bool {
+    JSON_EVENTS.load(Ordering::Relaxed)
+}
+
+/// One line of `--progress json` output
+#[derive(Debug, Serialize, PartialEq)]
+#[serde(tag = "event", rename_all = "snake_case")]
+enum Event<'a> {
+    Start {
+        operation: &'a str,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        total: Option<u64>,
+    },
+    Step {
+        operation: &'a str,
+        step: &'a str,
+        current: u64,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        total: Option<u64>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        bytes: Option<u64>,
+    },
+    Status {
+        operation: &'a str,
+        status: &'a str,
+        elapsed_ms: u128,
+    },
+    Done {
+        operation: &'a str,
+        elapsed_ms: u128,
+    },
+    Failed {
+        operation: &'a str,
+        elapsed_ms: u128,
+    },
+}
+
+fn emit(event: &Event) {
+    if !json_events() {
+        return;
+    }
+    if let Ok(line) = serde_json::to_string(event) {
+        let mut stderr = std::io::stderr().lock();
+        let _ = writeln!(stderr, "{}", line);
+    }
+}
+
+/// A long operation being reported. Dropping it without calling `finish`
+/// (an early `?` return) reports it as failed.
+pub struct Operation {
+    name: &'static str,
+    total: Option<u64>,
+    current: u64,
+    started: Instant,
+    finished: bool,
+}
+
+impl Operation {
+    /// Start `name`; `total` is the number of steps, when known up front
+    pub fn start(name: &'static str, total: Option<u64>) -> Self {
+        emit(&Event::Start {
+            operation: name,
+            total,
+        });
+        Operation {
+            name,
+            total,
+            current: 0,
+            started: Instant::now(),
+            finished: false,
+        }
+    }
+
+    /// One step done; `bytes` is how much it transferred or wrote
+    pub fn step(&mut self, step: &str, bytes: Option<u64>) {
+        self.current += 1;
+        emit(&Event::Step {
+            operation: self.name,
+            step,
+            current: self.current,
+            total: self.total,
+            bytes,
+        });
+    }
+
+    /// The latest state of something being waited on, such as a job
+    pub fn status(&self, status: &str) {
+        emit(&Event::Status {
+            operation: self.name,
+            status,
+            elapsed_ms: self.started.elapsed().as_millis(),
+        });
+    }
+
+    pub fn finish(mut self) {
+        self.finished = true;
+        emit(&Event::Done {
+            operation: self.name,
+            elapsed_ms: self.started.elapsed().as_millis(),
+        });
+    }
+}
+
+impl Drop for Operation {
+    fn drop(&mut self) {
+        if !self.finished {
+            emit(&Event::Failed {
+                operation: self.name,
+                elapsed_ms: self.started.elapsed().as_millis(),
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_formats() {
+        assert_eq!(
+            "json".parse::<ProgressFormat>().unwrap(),
+            ProgressFormat::Json
+        );
+        assert_eq!(
+            "human".parse::<ProgressFormat>().unwrap(),
+            ProgressFormat::Human
+        );
+        assert!("xml".parse::<ProgressFormat>().is_err());
+    }
+
+    #[test]
+    fn events_are_tagged_and_omit_unknowns() {
+        let step = Event::Step {
+            operation: "publish",
+            step: "wasm",
+            current: 1,
+            total: Some(3),
+            bytes: Some(1024),
+        };
+        assert_eq!(
+            serde_json::to_value(&step).unwrap(),
+            serde_json::json!({
+                "event": "step",
+                "operation": "publish",
+                "step": "wasm",
+                "current": 1,
+                "total": 3,
+                "bytes": 1024,
+            })
+        );
+        let start = Event::Start {
+            operation: "import",
+            total: None,
+        };
+        assert_eq!(
+            serde_json::to_string(&start).unwrap(),
+            r#"{"event":"start","operation":"import"}"#
+        );
+    }
+}
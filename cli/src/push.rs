@@ -0,0 +1,271 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use reqwest::StatusCode;
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+
+/// A file to stage under one of the session's part kinds
+pub struct LocalPart {
+    pub kind: &'static str,
+    pub path: PathBuf,
+}
+
+/// Version metadata sent when the session is opened
+pub struct PushOptions {
+    pub source_url: Option<String>,
+    pub commit_hash: Option<String>,
+    pub release_notes: Option<String>,
+    pub cargo_lock: Option<PathBuf>,
+    /// Toolchain the WASM was built with, when it was captured
+    pub toolchain: Option<shared::BuildToolchain>,
+}
+
+/// Read a release notes file, enforcing the registry's size limit up front
+pub fn read_release_notes(path: &Path) -> Result<String> {
+    let notes = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read release notes {}", path.display()))?;
+    if notes.len() > shared::MAX_RELEASE_NOTES_BYTES {
+        bail!(
+            "{} is {} bytes; release notes are limited to {} bytes",
+            path.display(),
+            notes.len(),
+            shared::MAX_RELEASE_NOTES_BYTES
+        );
+    }
+    Ok(notes)
+}
+
+/// Kinds whose local content differs from what the session already holds
+fn parts_to_upload<'a>(local: &'a [(&'static str, String)], staged: &Value) -> Vec<&'a str> {
+    local
+        .iter()
+        .filter(|(kind, sha)| {
+            !staged.as_array().is_some_and(|parts| {
+                parts
+                    .iter()
+                    .any(|p| p["kind"] == *kind && p["sha256"].as_str() == Some(sha.as_str()))
+            })
+        })
+        .map(|(kind, _)| *kind)
+        .collect()
+}
+
+/// Performance budgets written as TOML, converted to the JSON the registry takes
+fn budgets_json(toml: &str) -> Result<Vec<u8>> {
+    let budgets: shared::PerformanceBudgets = toml::from_str(toml)?;
+    Ok(serde_json::to_vec(&budgets)?)
+}
+
+/// A part's content as uploaded
+fn read_part(part: &LocalPart) -> Result<Vec<u8>> {
+    let bytes = std::fs::read(&part.path)
+        .with_context(|| format!("Failed to read {}", part.path.display()))?;
+    if part.kind == "budgets" && part.path.extension().is_some_and(|ext| ext == "toml") {
+        let text = String::from_utf8(bytes).context("Budgets file is not UTF-8")?;
+        return budgets_json(&text)
+            .with_context(|| format!("{} is not a valid budgets file", part.path.display()));
+    }
+    if part.kind == "abi" {
+        check_abi(&part.path, &bytes)?;
+    }
+    Ok(bytes)
+}
+
+/// Run the registry's ABI schema check locally so problems surface before upload
+fn check_abi(path: &Path, bytes: &[u8]) -> Result<()> {
+    let abi: Value = serde_json::from_slice(bytes)
+        .with_context(|| format!("{} is not valid JSON", path.display()))?;
+    if let Err(problems) = shared::validate_abi(&abi) {
+        let listed: Vec<String> = problems
+            .iter()
+            .map(|problem| format!("  {}: {}", problem.field, problem.message))
+            .collect();
+        bail!(
+            "{} does not match the ABI schema:\n{}",
+            path.display(),
+            listed.join("\n")
+        );
+    }
+    Ok(())
+}
+
+async fn check(response: reqwest::Response, action: &str) -> Result<Value> {
+    if !response.status().is_success() {
+        return Err(crate::api_errors::failure(response, format!("Failed to {}", action)).await);
+    }
+    if response.status() == StatusCode::NO_CONTENT {
+        return Ok(Value::Null);
+    }
+    Ok(response.json().await?)
+}
+
+/// `push <contract> --version <v> --wasm <file> --abi <file> [--readme] [--budgets] [--cancel]`
+///
+/// Stages every part in a publish session and commits them together, so a
+/// failed upload never leaves a half-published version. Re-running the same
+/// command resumes the session and only uploads parts that changed.
+pub async fn push(
+    api_url: &str,
+    contract: &str,
+    version: &str,
+    parts: Vec<LocalPart>,
+    options: PushOptions,
+    cancel: bool,
+) -> Result<()> {
+    let client = crate::http::client();
+    let token = crate::http::auth_token()?;
+    let base_url = api_url.trim_end_matches('/');
+
+    let cargo_lock = options
+        .cargo_lock
+        .map(|path| {
+            std::fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read {}", path.display()))
+        })
+        .transpose()?;
+    let response = client
+        .post(format!("{}/api/publish/sessions", base_url))
+        .bearer_auth(&token)
+        .json(&json!({
+            "contract_id": contract,
+            "version": version,
+            "source_url": options.source_url,
+            "commit_hash": options.commit_hash,
+            "release_notes": options.release_notes,
+            "cargo_lock": cargo_lock,
+            "toolchain": options.toolchain,
+        }))
+        .send()
+        .await
+        .context("Failed to open publish session")?;
+    let resumed = response.status() == StatusCode::OK;
+    let session = check(response, "open publish session").await?;
+    let id = session["id"]
+        .as_str()
+        .context("Session response is missing its id")?
+        .to_string();
+    let session_url = format!("{}/api/publish/sessions/{}", base_url, id);
+    println!(
+        "{} {} session {}",
+        "→".cyan(),
+        if resumed { "Resuming" } else { "Opened" },
+        id.bright_black()
+    );
+
+    if cancel {
+        let response = client
+            .post(format!("{}/cancel", session_url))
+            .bearer_auth(&token)
+            .send()
+            .await
+            .context("Failed to cancel publish session")?;
+        check(response, "cancel publish session").await?;
+        println!("{} Cancelled; staged parts were discarded", "✓".green());
+        return Ok(());
+    }
+
+    let mut contents = Vec::with_capacity(parts.len());
+    for part in &parts {
+        let bytes = read_part(part)?;
+        contents.push((part.kind, hex::encode(Sha256::digest(&bytes)), bytes));
+    }
+    let hashes: Vec<(&'static str, String)> = contents
+        .iter()
+        .map(|(kind, sha, _)| (*kind, sha.clone()))
+        .collect();
+    let pending = parts_to_upload(&hashes, &session["parts"]);
+
+    let mut progress = crate::progress::Operation::start("publish", Some(pending.len() as u64 + 1));
+    for (kind, _, bytes) in &contents {
+        if !pending.contains(kind) {
+            println!("  {} {} (already staged)", "·".bright_black(), kind);
+            continue;
+        }
+        let response = client
+            .put(format!("{}/parts/{}", session_url, kind))
+            .bearer_auth(&token)
+            .body(bytes.clone())
+            .send()
+            .await
+            .with_context(|| format!("Failed to upload {}; re-run to resume", kind))?;
+        check(response, &format!("upload {}", kind)).await?;
+        println!("  {} {} ({} bytes)", "✓".green(), kind, bytes.len());
+        progress.step(kind, Some(bytes.len() as u64));
+    }
+
+    let response = client
+        .post(format!("{}/commit", session_url))
+        .bearer_auth(&token)
+        .send()
+        .await
+        .context("Failed to commit publish session; re-run to resume")?;
+    let created = check(response, "commit publish session").await?;
+    progress.step("commit", None);
+    progress.finish();
+    println!(
+        "{} Published {} {}",
+        "✓".green(),
+        contract.bold(),
+        created["version"].as_str().unwrap_or(version)
+    );
+    if let Some(version_id) = created["id"].as_str() {
+        println!("  {}: {}", "Version ID".bold(), version_id.bright_black());
+    }
+    for warning in created["warnings"].as_array().into_iter().flatten() {
+        println!(
+            "  {} [{}] {}",
+            "⚠".yellow(),
+            warning["hook"].as_str().unwrap_or("publish hook"),
+            warning["message"].as_str().unwrap_or_default()
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_changed_or_missing_parts_are_uploaded() {
+        let local = vec![
+            ("wasm", "aa".to_string()),
+            ("abi", "bb".to_string()),
+            ("readme", "cc".to_string()),
+        ];
+        let staged = json!([
+            {"kind": "wasm", "sha256": "aa", "size_bytes": 4},
+            {"kind": "abi", "sha256": "old", "size_bytes": 2}
+        ]);
+        assert_eq!(parts_to_upload(&local, &staged), vec!["abi", "readme"]);
+        assert_eq!(parts_to_upload(&local, &Value::Null).len(), 3);
+    }
+
+    #[test]
+    fn abi_is_checked_against_the_schema_before_upload() {
+        let path = Path::new("abi.json");
+        assert!(check_abi(path, br#"[{"type":"function","name":"swap"}]"#).is_ok());
+        let err = check_abi(path, br#"[{"type":"function"}]"#).unwrap_err();
+        assert!(err.to_string().contains("abi[0].name"), "{}", err);
+        assert!(check_abi(path, b"not json").is_err());
+    }
+
+    #[test]
+    fn toml_budgets_are_sent_as_json() {
+        let toml = r#"
+            max_regression_percent = 10.0
+            on_exceed = "warn"
+
+            [[functions]]
+            function = "swap"
+            args = ["GABC", "100"]
+            max_cpu_instructions = 2000000
+        "#;
+        let sent: Value = serde_json::from_slice(&budgets_json(toml).unwrap()).unwrap();
+        assert_eq!(sent["on_exceed"], "warn");
+        assert_eq!(sent["functions"][0]["max_cpu_instructions"], 2000000);
+        assert!(budgets_json("functions = 3").is_err());
+    }
+}
@@ -5,6 +5,8 @@ use colored::Colorize;
 use serde::{Deserialize, Serialize};
 use std::fs;
 
+use crate::conversions::contract_path;
+
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FunctionChange {
@@ -84,11 +86,11 @@ pub async fn generate(
         "contract_address": contract_address,
     });
 
-    let client = reqwest::Client::new();
+    let client = crate::http::client();
     let resp = client
         .post(format!(
             "{}/api/contracts/{}/release-notes/generate",
-            api_url, contract_id
+            api_url, contract_path(contract_id)
         ))
         .json(&body)
         .send()
@@ -96,9 +98,7 @@ pub async fn generate(
         .context("Failed to connect to registry API")?;
 
     if !resp.status().is_success() {
-        let status = resp.status();
-        let text = resp.text().await.unwrap_or_default();
-        anyhow::bail!("API returned {}: {}", status, text);
+        return Err(crate::api_errors::failure(resp, "API request failed").await);
     }
 
     let notes: ReleaseNotesResponse = resp
@@ -122,20 +122,18 @@ pub async fn view(
     version: &str,
     json_output: bool,
 ) -> Result<()> {
-    let client = reqwest::Client::new();
+    let client = crate::http::client();
     let resp = client
         .get(format!(
             "{}/api/contracts/{}/release-notes/{}",
-            api_url, contract_id, version
+            api_url, contract_path(contract_id), version
         ))
         .send()
         .await
         .context("Failed to connect to registry API")?;
 
     if !resp.status().is_success() {
-        let status = resp.status();
-        let text = resp.text().await.unwrap_or_default();
-        anyhow::bail!("API returned {}: {}", status, text);
+        return Err(crate::api_errors::failure(resp, "API request failed").await);
     }
 
     let notes: ReleaseNotesResponse = resp
@@ -167,7 +165,10 @@ pub async fn edit(
     } else if let Some(t) = notes_text {
         t.to_string()
     } else {
-        anyhow::bail!("Either --file or --text must be provided for editing release notes");
+        return Err(crate::cli_error::CliError::Usage(
+            "Either --file or --text must be provided for editing release notes".to_string(),
+        )
+        .into());
     };
 
     println!("{}", "Updating release notes...".bold().cyan());
@@ -176,11 +177,11 @@ pub async fn edit(
         "notes_text": text,
     });
 
-    let client = reqwest::Client::new();
+    let client = crate::http::client();
     let resp = client
         .put(format!(
             "{}/api/contracts/{}/release-notes/{}",
-            api_url, contract_id, version
+            api_url, contract_path(contract_id), version
         ))
         .json(&body)
         .send()
@@ -188,9 +189,7 @@ pub async fn edit(
         .context("Failed to connect to registry API")?;
 
     if !resp.status().is_success() {
-        let status = resp.status();
-        let text = resp.text().await.unwrap_or_default();
-        anyhow::bail!("API returned {}: {}", status, text);
+        return Err(crate::api_errors::failure(resp, "API request failed").await);
     }
 
     let notes: ReleaseNotesResponse = resp
@@ -226,11 +225,11 @@ pub async fn publish(
         "update_version_record": !skip_version_update,
     });
 
-    let client = reqwest::Client::new();
+    let client = crate::http::client();
     let resp = client
         .post(format!(
             "{}/api/contracts/{}/release-notes/{}/publish",
-            api_url, contract_id, version
+            api_url, contract_path(contract_id), version
         ))
         .json(&body)
         .send()
@@ -238,9 +237,7 @@ pub async fn publish(
         .context("Failed to connect to registry API")?;
 
     if !resp.status().is_success() {
-        let status = resp.status();
-        let text = resp.text().await.unwrap_or_default();
-        anyhow::bail!("API returned {}: {}", status, text);
+        return Err(crate::api_errors::failure(resp, "API request failed").await);
     }
 
     let notes: ReleaseNotesResponse = resp
@@ -273,20 +270,18 @@ pub async fn list(
     contract_id: &str,
     json_output: bool,
 ) -> Result<()> {
-    let client = reqwest::Client::new();
+    let client = crate::http::client();
     let resp = client
         .get(format!(
             "{}/api/contracts/{}/release-notes",
-            api_url, contract_id
+            api_url, contract_path(contract_id)
         ))
         .send()
         .await
         .context("Failed to connect to registry API")?;
 
     if !resp.status().is_success() {
-        let status = resp.status();
-        let text = resp.text().await.unwrap_or_default();
-        anyhow::bail!("API returned {}: {}", status, text);
+        return Err(crate::api_errors::failure(resp, "API request failed").await);
     }
 
     let all_notes: Vec<ReleaseNotesResponse> = resp
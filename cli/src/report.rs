@@ -0,0 +1,92 @@
+//! `report`: send an abuse report about a contract to the registry's
+//! moderators. Signed-in reports are tied to the account; anonymous ones are
+//! accepted too, answering the registry's challenge when it has one.
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use shared::{AbuseReason, AbuseReportReceipt, ReportContractRequest};
+
+use crate::conversions::contract_path;
+
+/// Values accepted by `--reason`
+pub const REASONS: [&str; 6] = [
+    "malware",
+    "scam",
+    "impersonation",
+    "spam",
+    "license_violation",
+    "other",
+];
+
+fn parse_reason(reason: &str) -> Result<AbuseReason> {
+    serde_json::from_value(serde_json::Value::String(reason.to_string()))
+        .with_context(|| format!("unknown reason `{}`", reason))
+}
+
+/// `report <contract> --reason <reason> [--details ..] [--contact ..]`
+pub async fn report(
+    api_url: &str,
+    contract_id: &str,
+    reason: &str,
+    details: Option<String>,
+    contact: Option<String>,
+    json: bool,
+) -> Result<()> {
+    let request = ReportContractRequest {
+        reason: parse_reason(reason)?,
+        details,
+        reporter_contact: contact,
+    };
+    let response = crate::http::send_with_challenge(
+        crate::http::client()
+            .post(format!(
+                "{}/api/contracts/{}/report",
+                api_url.trim_end_matches('/'),
+                contract_path(contract_id)
+            ))
+            .json(&request),
+    )
+    .await?;
+    if !response.status().is_success() {
+        return Err(crate::api_errors::failure(response, "Failed to report contract").await);
+    }
+    let receipt: AbuseReportReceipt = response.json().await?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&receipt)?);
+        return Ok(());
+    }
+
+    if receipt.duplicate {
+        println!(
+            "{} You already reported {}; your open report {} is with the moderators",
+            "ℹ".cyan(),
+            contract_id.bold(),
+            receipt.report_id
+        );
+    } else {
+        println!(
+            "{} Reported {} to the registry's moderators (report {})",
+            "✓".green(),
+            contract_id.bold(),
+            receipt.report_id
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_listed_reason_parses() {
+        for reason in REASONS {
+            assert!(parse_reason(reason).is_ok(), "{}", reason);
+        }
+        assert_eq!(
+            parse_reason("license_violation").unwrap(),
+            AbuseReason::LicenseViolation
+        );
+        assert!(parse_reason("rude").is_err());
+    }
+}
@@ -0,0 +1,167 @@
+#![allow(dead_code)]
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use shared::{build_sbom, parse_cargo_lock, ComponentKind, SbomComponent, SbomFormat};
+use std::fs;
+use std::path::Path;
+
+use crate::conversions::contract_path;
+
+/// Generate an SBOM for the project in `project_dir`.
+///
+/// Registry dependencies are fetched from the API when `contract_id` is set;
+/// crate dependencies are read from the project's Cargo.lock. With `remote`
+/// the server-generated SBOM for the published version is downloaded instead.
+pub async fn run(
+    api_url: &str,
+    project_dir: &str,
+    contract_id: Option<&str>,
+    version: Option<&str>,
+    format: &str,
+    output: Option<&str>,
+    remote: bool,
+) -> Result<()> {
+    let sbom_format: SbomFormat = format.parse().map_err(|e: String| anyhow::anyhow!(e))?;
+
+    let document = if remote {
+        let id = contract_id.context("--remote requires --contract-id")?;
+        fetch_remote(api_url, id, version, format).await?
+    } else {
+        let (name, package_version) = read_package_info(Path::new(project_dir))?;
+        let version = version.map(str::to_string).unwrap_or(package_version);
+
+        let mut components = match contract_id {
+            Some(id) => fetch_registry_dependencies(api_url, id).await?,
+            None => Vec::new(),
+        };
+
+        let lock_path = Path::new(project_dir).join("Cargo.lock");
+        if lock_path.exists() {
+            let lock = fs::read_to_string(&lock_path)
+                .with_context(|| format!("Failed to read {}", lock_path.display()))?;
+            components.extend(parse_cargo_lock(&lock));
+        } else {
+            log::warn!("No Cargo.lock found in {}; crate dependencies omitted", project_dir);
+        }
+
+        build_sbom(sbom_format, &name, &version, &components)
+    };
+
+    let rendered = serde_json::to_string_pretty(&document)?;
+    match output {
+        Some(path) => {
+            fs::write(path, &rendered).with_context(|| format!("Failed to write {}", path))?;
+            println!(
+                "{} SBOM written to {}",
+                "✓".green().bold(),
+                path.bright_black()
+            );
+        }
+        None => println!("{}", rendered),
+    }
+
+    Ok(())
+}
+
+async fn fetch_remote(
+    api_url: &str,
+    contract_id: &str,
+    version: Option<&str>,
+    format: &str,
+) -> Result<serde_json::Value> {
+    let client = crate::http::client();
+    let url = format!("{}/api/contracts/{}/sbom", api_url, contract_path(contract_id));
+
+    let mut query = vec![("format", format.to_string())];
+    if let Some(v) = version {
+        query.push(("version", v.to_string()));
+    }
+
+    let response = client
+        .get(&url)
+        .query(&query)
+        .send()
+        .await
+        .context("Failed to fetch SBOM")?;
+
+    if !response.status().is_success() {
+        return Err(crate::api_errors::failure(response, "Failed to fetch SBOM").await);
+    }
+
+    Ok(response.json().await?)
+}
+
+async fn fetch_registry_dependencies(api_url: &str, contract_id: &str) -> Result<Vec<SbomComponent>> {
+    let client = crate::http::client();
+    let url = format!("{}/api/contracts/{}/dependencies", api_url, contract_path(contract_id));
+
+    let response = client
+        .get(&url)
+        .send()
+        .await
+        .context("Failed to fetch contract dependencies")?;
+
+    if !response.status().is_success() {
+        return Err(crate::api_errors::failure(response, "Failed to fetch dependencies").await);
+    }
+
+    let data: serde_json::Value = response.json().await?;
+    let deps = data["dependencies"].as_array().cloned().unwrap_or_default();
+
+    deps.iter()
+        .map(|dep| {
+            Ok(SbomComponent {
+                kind: ComponentKind::Registry,
+                name: crate::conversions::as_str(&dep["dependency_name"], "dependency_name")?,
+                version: crate::conversions::as_str(
+                    &dep["version_constraint"],
+                    "version_constraint",
+                )?,
+                checksum: None,
+            })
+        })
+        .collect()
+}
+
+/// Read `[package] name` and `version` from the project's Cargo.toml
+fn read_package_info(project_dir: &Path) -> Result<(String, String)> {
+    let manifest_path = project_dir.join("Cargo.toml");
+    let contents = fs::read_to_string(&manifest_path)
+        .with_context(|| format!("Failed to read {}", manifest_path.display()))?;
+    let manifest: toml::Value = toml::from_str(&contents)
+        .with_context(|| format!("Failed to parse {}", manifest_path.display()))?;
+
+    let package = manifest
+        .get("package")
+        .context("Cargo.toml has no [package] section")?;
+    let name = package
+        .get("name")
+        .and_then(|v| v.as_str())
+        .context("Cargo.toml package has no name")?;
+    let version = package
+        .get("version")
+        .and_then(|v| v.as_str())
+        .unwrap_or("0.0.0");
+
+    Ok((name.to_string(), version.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_package_name_and_version() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("Cargo.toml"),
+            "[package]\nname = \"token\"\nversion = \"1.2.3\"\n",
+        )
+        .unwrap();
+
+        let (name, version) = read_package_info(dir.path()).unwrap();
+        assert_eq!(name, "token");
+        assert_eq!(version, "1.2.3");
+    }
+}
@@ -0,0 +1,279 @@
+use anyhow::{bail, Context, Result};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use colored::Colorize;
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use sha2::{Digest, Sha256};
+use shared::{CliRelease, SemVer};
+use std::io::Write;
+use std::path::Path;
+
+/// Version of the running binary
+const CURRENT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Target triple this binary was built for
+const BUILD_TARGET: &str = env!("BUILD_TARGET");
+
+/// Hex ed25519 public key that signs release binaries, embedded by release builds
+const RELEASE_PUBKEY: Option<&str> = option_env!("SOROBAN_REGISTRY_RELEASE_PUBKEY");
+
+fn is_newer(candidate: &str, current: &str) -> bool {
+    match (SemVer::parse(candidate), SemVer::parse(current)) {
+        (Some(candidate), Some(current)) => candidate > current,
+        _ => false,
+    }
+}
+
+/// The message a release is signed over: `"{version}:{target}:{sha256}"`, so a
+/// signature can't be replayed for an older version or another target
+fn release_message(release: &CliRelease) -> String {
+    format!(
+        "{}:{}:{}",
+        release.version,
+        release.target,
+        release.sha256.to_ascii_lowercase()
+    )
+}
+
+/// Check that the release is newer than the running binary and built for its
+/// target, that `binary` hashes to the published SHA-256, and that the release
+/// signature covers its version, target and hash
+fn verify_release(binary: &[u8], release: &CliRelease, pubkey_hex: &str) -> Result<()> {
+    if !is_newer(&release.version, CURRENT_VERSION) {
+        bail!(
+            "refusing to install {}: it is not newer than the installed {}",
+            release.version,
+            CURRENT_VERSION
+        );
+    }
+    if release.target != BUILD_TARGET {
+        bail!(
+            "release is built for {}, but this binary is {}",
+            release.target,
+            BUILD_TARGET
+        );
+    }
+
+    let digest = Sha256::digest(binary);
+    if hex::encode(digest) != release.sha256.to_ascii_lowercase() {
+        bail!(
+            "downloaded binary hash {} does not match published hash {}",
+            hex::encode(digest),
+            release.sha256
+        );
+    }
+
+    let key_bytes: [u8; 32] = hex::decode(pubkey_hex)
+        .context("embedded release key is not valid hex")?
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("embedded release key must be 32 bytes"))?;
+    let key = VerifyingKey::from_bytes(&key_bytes).context("invalid embedded release key")?;
+
+    let sig_bytes: [u8; 64] = BASE64
+        .decode(release.signature.trim())
+        .context("release signature is not valid base64")?
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("release signature must be 64 bytes"))?;
+    key.verify(
+        release_message(release).as_bytes(),
+        &Signature::from_bytes(&sig_bytes),
+    )
+    .context("release signature does not verify against the embedded release key")
+}
+
+/// Write `binary` next to `exe` and rename it into place, so an interrupted
+/// update never leaves a half-written executable behind
+fn replace_executable(exe: &Path, binary: &[u8]) -> Result<()> {
+    let dir = exe
+        .parent()
+        .context("cannot determine the directory of the running executable")?;
+    let mut staged = tempfile::NamedTempFile::new_in(dir)
+        .with_context(|| format!("cannot write to {} (try running with sudo)", dir.display()))?;
+    staged.write_all(binary)?;
+    staged.as_file().sync_all()?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(staged.path(), std::fs::Permissions::from_mode(0o755))?;
+    }
+
+    // Windows can't overwrite a running executable, but it can rename it
+    #[cfg(windows)]
+    {
+        let old = exe.with_extension("old.exe");
+        let _ = std::fs::remove_file(&old);
+        std::fs::rename(exe, &old).context("failed to move the running executable aside")?;
+    }
+
+    staged
+        .persist(exe)
+        .map_err(|err| err.error)
+        .context("failed to swap in the new executable")?;
+    Ok(())
+}
+
+/// `soroban-registry self-update [--check]`
+pub async fn self_update(api_url: &str, check_only: bool) -> Result<()> {
+    let client = crate::http::client();
+    let url = format!("{}/api/cli/releases/latest", api_url.trim_end_matches('/'));
+    let response = client
+        .get(&url)
+        .query(&[("target", BUILD_TARGET)])
+        .send()
+        .await
+        .context("Failed to check for CLI updates")?;
+
+    if !response.status().is_success() {
+        return Err(crate::api_errors::failure(response, "Failed to check for CLI updates").await);
+    }
+    let release: CliRelease = response.json().await?;
+
+    if !is_newer(&release.version, CURRENT_VERSION) {
+        println!(
+            "{} soroban-registry {} is up to date",
+            "✓".green(),
+            CURRENT_VERSION
+        );
+        return Ok(());
+    }
+
+    println!(
+        "{} soroban-registry {} is available (installed: {})",
+        "↑".cyan(),
+        release.version.bold(),
+        CURRENT_VERSION
+    );
+    if let Some(notes) = &release.release_notes {
+        println!("\n{}\n", notes);
+    }
+    if check_only {
+        println!(
+            "Run {} to install it.",
+            "soroban-registry self-update".bold()
+        );
+        return Ok(());
+    }
+
+    let Some(pubkey) = RELEASE_PUBKEY else {
+        bail!(
+            "this build has no embedded release signing key, so updates can't be verified; \
+             reinstall from an official release"
+        );
+    };
+
+    println!("Downloading {}...", release.download_url);
+    // The binary is hosted elsewhere; don't hand it the registry credential
+    let response = crate::http::anonymous_client()
+        .get(&release.download_url)
+        .send()
+        .await
+        .context("Failed to download the release binary")?;
+    if !response.status().is_success() {
+        bail!(
+            "Failed to download the release binary: {}",
+            response.status()
+        );
+    }
+    let binary = response.bytes().await?;
+
+    verify_release(&binary, &release, pubkey)?;
+    println!("{} hash and signature verified", "✓".green());
+
+    let exe = std::env::current_exe().context("cannot locate the running executable")?;
+    replace_executable(&exe, &binary)?;
+
+    println!(
+        "{} updated soroban-registry {} → {}",
+        "✓".green(),
+        CURRENT_VERSION,
+        release.version
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::{Signer, SigningKey};
+
+    fn signed_release(version: &str, binary: &[u8], key: &SigningKey) -> CliRelease {
+        let mut release = CliRelease {
+            version: version.to_string(),
+            target: BUILD_TARGET.to_string(),
+            download_url: "https://example.com/cli".to_string(),
+            sha256: hex::encode(Sha256::digest(binary)),
+            signature: String::new(),
+            release_notes: None,
+            published_at: chrono::Utc::now(),
+        };
+        release.signature =
+            BASE64.encode(key.sign(release_message(&release).as_bytes()).to_bytes());
+        release
+    }
+
+    #[test]
+    fn compares_versions_semantically() {
+        assert!(is_newer("0.10.0", "0.9.3"));
+        assert!(!is_newer("0.1.0", "0.1.0"));
+        assert!(!is_newer("nightly", "0.1.0"));
+    }
+
+    #[test]
+    fn verifies_hash_and_signature() {
+        let key = SigningKey::from_bytes(&[7u8; 32]);
+        let pubkey = hex::encode(key.verifying_key().to_bytes());
+        let release = signed_release("9.9.9", b"binary", &key);
+
+        assert!(verify_release(b"binary", &release, &pubkey).is_ok());
+        assert!(verify_release(b"tampered", &release, &pubkey).is_err());
+
+        let other = hex::encode(
+            SigningKey::from_bytes(&[8u8; 32])
+                .verifying_key()
+                .to_bytes(),
+        );
+        assert!(verify_release(b"binary", &release, &other).is_err());
+    }
+
+    #[test]
+    fn signature_is_bound_to_version_and_target() {
+        let key = SigningKey::from_bytes(&[7u8; 32]);
+        let pubkey = hex::encode(key.verifying_key().to_bytes());
+        let release = signed_release("9.9.9", b"binary", &key);
+
+        // A validly signed release can't be relabelled as another version
+        let relabelled = CliRelease {
+            version: "9.9.10".to_string(),
+            ..release.clone()
+        };
+        assert!(verify_release(b"binary", &relabelled, &pubkey).is_err());
+
+        // nor served to another target
+        let retargeted = CliRelease {
+            target: "not-this-target".to_string(),
+            ..release
+        };
+        assert!(verify_release(b"binary", &retargeted, &pubkey).is_err());
+    }
+
+    #[test]
+    fn refuses_downgrades_and_reinstalls() {
+        let key = SigningKey::from_bytes(&[7u8; 32]);
+        let pubkey = hex::encode(key.verifying_key().to_bytes());
+
+        let same = signed_release(CURRENT_VERSION, b"binary", &key);
+        assert!(verify_release(b"binary", &same, &pubkey).is_err());
+
+        let older = signed_release("0.0.1", b"binary", &key);
+        assert!(verify_release(b"binary", &older, &pubkey).is_err());
+    }
+
+    #[test]
+    fn replaces_executable_atomically() {
+        let dir = tempfile::tempdir().unwrap();
+        let exe = dir.path().join("soroban-registry");
+        std::fs::write(&exe, b"old").unwrap();
+        replace_executable(&exe, b"new").unwrap();
+        assert_eq!(std::fs::read(&exe).unwrap(), b"new");
+    }
+}
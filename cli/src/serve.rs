@@ -0,0 +1,113 @@
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+
+const DATA_DIR_NAME: &str = "registry";
+const DATABASE_FILE_NAME: &str = "registry.db";
+
+/// `~/.soroban-registry/registry`, next to the config file
+pub fn default_data_dir() -> Result<PathBuf> {
+    crate::config::config_file_path()
+        .and_then(|config| config.parent().map(|dir| dir.join(DATA_DIR_NAME)))
+        .context("Could not determine home directory")
+}
+
+fn database_url(data_dir: &Path) -> String {
+    format!("sqlite://{}", data_dir.join(DATABASE_FILE_NAME).display())
+}
+
+/// Sample data `--seed` can load
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Seed {
+    Demo,
+}
+
+impl std::str::FromStr for Seed {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "demo" => Ok(Seed::Demo),
+            other => bail!("Unknown seed '{}' (expected: demo)", other),
+        }
+    }
+}
+
+/// Run a private registry in this process: the API and web UI over a SQLite
+/// database in `data_dir`, until Ctrl+C
+pub async fn run(bind: SocketAddr, data_dir: Option<PathBuf>, seed: Option<Seed>) -> Result<()> {
+    let data_dir = match data_dir {
+        Some(dir) => dir,
+        None => default_data_dir()?,
+    };
+    std::fs::create_dir_all(&data_dir)
+        .with_context(|| format!("Failed to create data directory {}", data_dir.display()))?;
+    embedded::run(bind, &database_url(&data_dir), seed).await
+}
+
+#[cfg(feature = "serve")]
+mod embedded {
+    use std::net::SocketAddr;
+
+    use anyhow::Result;
+    use colored::Colorize;
+
+    use super::Seed;
+
+    pub async fn run(bind: SocketAddr, database_url: &str, seed: Option<Seed>) -> Result<()> {
+        let repository = shared::repository::connect(database_url, 5).await?;
+        repository.migrate().await?;
+        if seed == Some(Seed::Demo) {
+            let added = api::lite::seed_demo(repository.as_ref()).await?;
+            println!("{} Loaded {} demo contract(s)", "✓".green(), added);
+        }
+
+        println!(
+            "{} Registry running at {} ({})",
+            "✓".green(),
+            format!("http://{}", bind).bold(),
+            database_url
+        );
+        println!(
+            "  Point the CLI at it with {}",
+            format!("--api-url http://{}", bind).cyan()
+        );
+        api::lite::run(api::lite::router(repository, true), bind).await
+    }
+}
+
+/// Builds without the `serve` feature explain how to get it
+#[cfg(not(feature = "serve"))]
+mod embedded {
+    use std::net::SocketAddr;
+
+    use anyhow::{bail, Result};
+
+    use super::Seed;
+
+    pub async fn run(_bind: SocketAddr, _database_url: &str, _seed: Option<Seed>) -> Result<()> {
+        bail!(
+            "The embedded registry is not built in; reinstall with `cargo install --path cli --features serve`"
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_seeds() {
+        assert_eq!("demo".parse::<Seed>().unwrap(), Seed::Demo);
+        assert!("production".parse::<Seed>().is_err());
+    }
+
+    #[test]
+    fn database_lives_in_the_data_dir() {
+        assert_eq!(
+            database_url(Path::new("/srv/registry")),
+            "sqlite:///srv/registry/registry.db"
+        );
+    }
+}
@@ -0,0 +1,112 @@
+use anyhow::{Context, Result};
+use colored::Colorize;
+use serde_json::Value;
+use shared::StatsTimeseries;
+
+const SPARK_LEVELS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// One block character per value, scaled between the smallest and largest value
+pub fn sparkline(values: &[i64]) -> String {
+    let (Some(min), Some(max)) = (values.iter().min(), values.iter().max()) else {
+        return String::new();
+    };
+    let span = (max - min) as f64;
+    values
+        .iter()
+        .map(|value| {
+            if span == 0.0 {
+                return if *max == 0 {
+                    SPARK_LEVELS[0]
+                } else {
+                    SPARK_LEVELS[3]
+                };
+            }
+            let level = ((value - min) as f64 / span * (SPARK_LEVELS.len() - 1) as f64).round();
+            SPARK_LEVELS[level as usize]
+        })
+        .collect()
+}
+
+/// `stats`: current registry totals, or with `chart` a daily series as a sparkline
+pub async fn show(api_url: &str, metric: &str, range: &str, chart: bool, json: bool) -> Result<()> {
+    let base = api_url.trim_end_matches('/');
+    if !chart {
+        let response = crate::http::client()
+            .get(format!("{}/api/stats", base))
+            .send()
+            .await
+            .context("Failed to reach registry")?;
+        if !response.status().is_success() {
+            return Err(crate::api_errors::failure(response, "Failed to fetch stats").await);
+        }
+        let totals: Value = response.json().await.context("Invalid stats response")?;
+        if json {
+            println!("{}", serde_json::to_string_pretty(&totals)?);
+            return Ok(());
+        }
+        println!("\n{}", "Registry Statistics".bold().cyan());
+        println!("{}", "=".repeat(80).cyan());
+        for (label, key) in [
+            ("Contracts", "total_contracts"),
+            ("Verified", "verified_contracts"),
+            ("Publishers", "total_publishers"),
+        ] {
+            println!("{}: {}", label.bold(), totals[key].as_i64().unwrap_or(0));
+        }
+        println!();
+        return Ok(());
+    }
+
+    let response = crate::http::client()
+        .get(format!("{}/api/stats/timeseries", base))
+        .query(&[("metric", metric), ("range", range)])
+        .send()
+        .await
+        .context("Failed to reach registry")?;
+    if !response.status().is_success() {
+        return Err(crate::api_errors::failure(response, "Failed to fetch stats").await);
+    }
+    let series: StatsTimeseries = response.json().await.context("Invalid stats response")?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&series)?);
+        return Ok(());
+    }
+
+    let values: Vec<i64> = series.points.iter().map(|p| p.value).collect();
+    println!(
+        "\n{} {}",
+        series.metric.bold().cyan(),
+        format!("(last {} days)", series.range_days).bright_black()
+    );
+    println!("{}", sparkline(&values).green());
+    if let (Some(first), Some(last)) = (series.points.first(), series.points.last()) {
+        println!(
+            "{} → {}  min {}  max {}  latest {}",
+            first.date,
+            last.date,
+            values.iter().min().unwrap_or(&0),
+            values.iter().max().unwrap_or(&0),
+            last.value
+        );
+    }
+    println!();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sparkline_spans_the_full_scale() {
+        assert_eq!(sparkline(&[0, 7, 14]), "▁▅█");
+        assert_eq!(sparkline(&[3, 1, 2]), "█▁▅");
+    }
+
+    #[test]
+    fn flat_and_empty_series() {
+        assert_eq!(sparkline(&[]), "");
+        assert_eq!(sparkline(&[0, 0]), "▁▁");
+        assert_eq!(sparkline(&[5, 5]), "▄▄");
+    }
+}
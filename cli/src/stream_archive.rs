@@ -0,0 +1,285 @@
+//! `.tar.zst` archives written and read in a single pass, so `export --output -`
+//! and `import -` can go through a pipe without a temp file. The contract's
+//! files come first under `contract/`, then `manifest.json`, whose `sha256`
+//! covers the file contents in archive order.
+
+use std::fs::{self, File};
+use std::io::{BufReader, Read, Write};
+use std::path::{Component, Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, TimeZone, Utc};
+use sha2::{Digest, Sha256};
+use tar::{Archive, Builder, Header};
+
+use crate::io_utils::BUF_SIZE;
+use crate::manifest::{AuditEntry, ExportManifest, ManifestEntry};
+
+/// Archive path meaning stdin (`import`) or stdout (`export`)
+pub const STDIO: &str = "-";
+const CONTRACT_PREFIX: &str = "contract/";
+const MANIFEST_NAME: &str = "manifest.json";
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+
+/// Whether `path` names a streamed archive rather than the nested `.tar.gz` layout
+pub fn is_stream_path(path: &str) -> bool {
+    path == STDIO || path.ends_with(".tar.zst") || path.ends_with(".tzst")
+}
+
+pub fn has_zstd_magic(bytes: &[u8]) -> bool {
+    bytes.starts_with(&ZSTD_MAGIC)
+}
+
+/// Reader that hashes everything read through it
+struct HashingReader<'a, R> {
+    inner: R,
+    hasher: &'a mut Sha256,
+}
+
+impl<R: Read> Read for HashingReader<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.hasher.update(&buf[..n]);
+        Ok(n)
+    }
+}
+
+/// Files under `dir`, relative and in a stable order
+fn contract_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(current) = pending.pop() {
+        for entry in
+            fs::read_dir(&current).with_context(|| format!("reading {}", current.display()))?
+        {
+            let path = entry?.path();
+            if path.is_dir() {
+                pending.push(path);
+            } else {
+                files.push(path.strip_prefix(dir).unwrap_or(&path).to_path_buf());
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+fn modified_at(metadata: &fs::Metadata) -> DateTime<Utc> {
+    metadata
+        .modified()
+        .ok()
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .and_then(|d| {
+            Utc.timestamp_opt(d.as_secs() as i64, d.subsec_nanos())
+                .single()
+        })
+        .unwrap_or_else(Utc::now)
+}
+
+/// Write `contract_dir` to `out` as a zstd-compressed tar, compressing as it goes
+pub fn write<W: Write>(
+    contract_dir: &Path,
+    out: W,
+    contract_id: &str,
+    name: &str,
+    network: &str,
+) -> Result<ExportManifest> {
+    let mut manifest = ExportManifest::new(contract_id.into(), name.into(), network.into());
+    let mut builder = Builder::new(zstd::Encoder::new(out, 0)?);
+    let mut hasher = Sha256::new();
+
+    for rel in contract_files(contract_dir)? {
+        let path = contract_dir.join(&rel);
+        let metadata = fs::metadata(&path)?;
+        let archive_path = rel.to_string_lossy().replace('\\', "/");
+        manifest.contents.push(ManifestEntry {
+            path: archive_path.clone(),
+            size: metadata.len(),
+            modified_at: modified_at(&metadata),
+        });
+
+        let mut header = Header::new_gnu();
+        header.set_size(metadata.len());
+        header.set_mode(0o644);
+        header.set_cksum();
+        let reader = HashingReader {
+            inner: BufReader::with_capacity(BUF_SIZE, File::open(&path)?),
+            hasher: &mut hasher,
+        };
+        builder.append_data(
+            &mut header,
+            format!("{}{}", CONTRACT_PREFIX, archive_path),
+            reader,
+        )?;
+    }
+
+    manifest.sha256 = hex::encode(hasher.finalize());
+    let manifest_json = serde_json::to_vec_pretty(&manifest)?;
+    let mut header = Header::new_gnu();
+    header.set_size(manifest_json.len() as u64);
+    header.set_mode(0o644);
+    header.set_cksum();
+    builder.append_data(&mut header, MANIFEST_NAME, manifest_json.as_slice())?;
+
+    builder.into_inner()?.finish()?.flush()?;
+    Ok(manifest)
+}
+
+/// `contract/<path>` as a relative path that stays inside the output directory
+fn entry_destination(archive_path: &Path) -> Result<PathBuf> {
+    let rel = archive_path
+        .strip_prefix(CONTRACT_PREFIX)
+        .with_context(|| format!("unexpected archive entry {}", archive_path.display()))?;
+    if rel.as_os_str().is_empty() || !rel.components().all(|c| matches!(c, Component::Normal(_))) {
+        bail!("unsafe archive entry {}", archive_path.display());
+    }
+    Ok(rel.to_path_buf())
+}
+
+/// Read a streamed archive from `input` into `output_dir`. Files are unpacked
+/// next to `output_dir` first and only moved in once the manifest checks out.
+pub fn read<R: Read>(input: R, output_dir: &Path) -> Result<ExportManifest> {
+    let parent = match output_dir.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    fs::create_dir_all(parent)?;
+    let staging = tempfile::tempdir_in(parent).context("failed to create staging dir")?;
+
+    let mut progress = crate::progress::Operation::start("import", None);
+    let mut hasher = Sha256::new();
+    let mut unpacked: Vec<(String, u64)> = Vec::new();
+    let mut manifest: Option<ExportManifest> = None;
+
+    let mut archive = Archive::new(zstd::Decoder::new(input).context("not a zstd stream")?);
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let archive_path = entry.path()?.into_owned();
+        if manifest.is_some() {
+            bail!(
+                "invalid archive: {} follows manifest.json",
+                archive_path.display()
+            );
+        }
+        if archive_path == Path::new(MANIFEST_NAME) {
+            manifest = Some(serde_json::from_reader(&mut entry).context("invalid manifest.json")?);
+            continue;
+        }
+
+        let rel = entry_destination(&archive_path)?;
+        let dest_path = staging.path().join(&rel);
+        if let Some(dir) = dest_path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut reader = HashingReader {
+            inner: &mut entry,
+            hasher: &mut hasher,
+        };
+        let size = std::io::copy(&mut reader, &mut File::create(&dest_path)?)?;
+        let rel = rel.to_string_lossy().replace('\\', "/");
+        progress.step(&rel, Some(size));
+        unpacked.push((rel, size));
+    }
+
+    let mut manifest = manifest.context("invalid archive: missing manifest.json")?;
+    let computed_hash = hex::encode(hasher.finalize());
+    if computed_hash != manifest.sha256 {
+        bail!(
+            "integrity check failed: expected {} got {}",
+            manifest.sha256,
+            computed_hash
+        );
+    }
+    let listed: Vec<(String, u64)> = manifest
+        .contents
+        .iter()
+        .map(|entry| (entry.path.clone(), entry.size))
+        .collect();
+    if listed != unpacked {
+        bail!("integrity check failed: archive contents do not match manifest.json");
+    }
+    manifest.audit_trail.push(AuditEntry {
+        action: "import_verified".into(),
+        timestamp: Utc::now(),
+        actor: "soroban-registry-cli".into(),
+    });
+    progress.step("verify", None);
+
+    for (rel, _) in &unpacked {
+        let dest_path = output_dir.join(rel);
+        if let Some(dir) = dest_path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::rename(staging.path().join(rel), &dest_path)
+            .with_context(|| format!("Failed to write {}", dest_path.display()))?;
+    }
+    manifest.audit_trail.push(AuditEntry {
+        action: "import_extracted".into(),
+        timestamp: Utc::now(),
+        actor: "soroban-registry-cli".into(),
+    });
+    progress.finish();
+    Ok(manifest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_through_a_pipe() {
+        let source = tempfile::tempdir().unwrap();
+        fs::create_dir_all(source.path().join("src")).unwrap();
+        fs::write(source.path().join("Cargo.toml"), "[package]\n").unwrap();
+        fs::write(source.path().join("src/lib.rs"), "#![no_std]\n").unwrap();
+
+        let mut stream = Vec::new();
+        let written = write(source.path(), &mut stream, "CABC", "amm", "testnet").unwrap();
+        assert!(has_zstd_magic(&stream));
+
+        let target = tempfile::tempdir().unwrap();
+        let out = target.path().join("imported");
+        let read_back = read(stream.as_slice(), &out).unwrap();
+        assert_eq!(read_back.sha256, written.sha256);
+        assert_eq!(
+            fs::read_to_string(out.join("src/lib.rs")).unwrap(),
+            "#![no_std]\n"
+        );
+    }
+
+    #[test]
+    fn tampered_or_escaping_entries_are_refused() {
+        let source = tempfile::tempdir().unwrap();
+        fs::write(source.path().join("lib.rs"), "fn a() {}").unwrap();
+        let mut stream = Vec::new();
+        write(source.path(), &mut stream, "CABC", "amm", "testnet").unwrap();
+
+        // Re-pack with the file changed but the original manifest kept
+        let mut original = Archive::new(zstd::Decoder::new(stream.as_slice()).unwrap());
+        let mut builder = Builder::new(zstd::Encoder::new(Vec::new(), 0).unwrap());
+        for entry in original.entries().unwrap() {
+            let mut entry = entry.unwrap();
+            let path = entry.path().unwrap().into_owned();
+            let mut data = Vec::new();
+            entry.read_to_end(&mut data).unwrap();
+            if path == Path::new("contract/lib.rs") {
+                data = b"fn b() {}".to_vec();
+            }
+            let mut header = Header::new_gnu();
+            header.set_size(data.len() as u64);
+            header.set_cksum();
+            builder
+                .append_data(&mut header, path, data.as_slice())
+                .unwrap();
+        }
+        let tampered = builder.into_inner().unwrap().finish().unwrap();
+        let target = tempfile::tempdir().unwrap();
+        let out = target.path().join("imported");
+        assert!(read(tampered.as_slice(), &out).is_err());
+        assert!(!out.join("lib.rs").exists());
+
+        assert!(entry_destination(Path::new("contract/../../etc/passwd")).is_err());
+        assert!(entry_destination(Path::new("other/lib.rs")).is_err());
+        assert!(entry_destination(Path::new("contract/src/lib.rs")).is_ok());
+    }
+}
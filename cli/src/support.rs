@@ -0,0 +1,126 @@
+//! `support`: the supported-until date and LTS flag publishers declare per
+//! version, and the end-of-life status the registry computes from them.
+
+use anyhow::Result;
+use chrono::NaiveDate;
+use colored::Colorize;
+use shared::{SupportPolicyRequest, SupportResponse, SupportStatus, VersionSupport};
+
+use crate::conversions::contract_path;
+
+fn support_url(api_url: &str, contract_id: &str) -> String {
+    format!(
+        "{}/api/contracts/{}/support",
+        api_url.trim_end_matches('/'),
+        contract_path(contract_id)
+    )
+}
+
+pub fn status_label(support: &VersionSupport) -> colored::ColoredString {
+    match support.status {
+        SupportStatus::EndOfLife => support.status.as_str().red().bold(),
+        SupportStatus::EndingSoon => support.status.as_str().yellow(),
+        SupportStatus::Supported => support.status.as_str().green(),
+        SupportStatus::Unspecified => support.status.as_str().bright_black(),
+    }
+}
+
+/// Warn that a resolved version is at or near end of life
+pub fn warn(contract_id: &str, support: &VersionSupport) {
+    if support.needs_warning() {
+        println!(
+            "  {} {}@{} is {}",
+            "⚠".yellow(),
+            contract_id,
+            support.version,
+            support.describe().yellow()
+        );
+    }
+}
+
+/// `support <contract> [--version <v>]`
+pub async fn show(
+    api_url: &str,
+    contract_id: &str,
+    version: Option<&str>,
+    json: bool,
+) -> Result<()> {
+    let response = crate::http::client()
+        .get(support_url(api_url, contract_id))
+        .query(&[("version", version)])
+        .send()
+        .await?;
+    if !response.status().is_success() {
+        return Err(crate::api_errors::failure(response, "Failed to fetch support policies").await);
+    }
+    let support: SupportResponse = response.json().await?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&support)?);
+        return Ok(());
+    }
+
+    println!(
+        "\n{} {}",
+        "Support Policy:".bold().cyan(),
+        support.contract_id
+    );
+    println!("{}", "=".repeat(80).cyan());
+    for version in &support.versions {
+        let lts = if version.lts {
+            " LTS".cyan().to_string()
+        } else {
+            String::new()
+        };
+        let until = version
+            .supported_until
+            .map(|until| until.to_string())
+            .unwrap_or_else(|| "-".to_string());
+        println!(
+            "  {:<12} {:<12} {}{}",
+            version.version.bold(),
+            until,
+            status_label(version),
+            lts
+        );
+    }
+    Ok(())
+}
+
+/// `support <contract> --version <v> --until <date> [--lts]`: replaces the
+/// version's policy
+pub async fn set(
+    api_url: &str,
+    contract_id: &str,
+    version: &str,
+    supported_until: Option<NaiveDate>,
+    lts: bool,
+) -> Result<()> {
+    let url = format!(
+        "{}/api/contracts/{}/versions/{}/support",
+        api_url.trim_end_matches('/'),
+        contract_path(contract_id),
+        version
+    );
+    let response = crate::http::client()
+        .put(&url)
+        .bearer_auth(crate::http::auth_token()?)
+        .json(&SupportPolicyRequest {
+            supported_until,
+            lts,
+        })
+        .send()
+        .await?;
+    if !response.status().is_success() {
+        return Err(crate::api_errors::failure(response, "Failed to set support policy").await);
+    }
+    let support: VersionSupport = response.json().await?;
+    println!(
+        "{} {}@{}: {} ({})",
+        "✓".green(),
+        contract_id,
+        support.version,
+        support.describe(),
+        status_label(&support)
+    );
+    Ok(())
+}
@@ -0,0 +1,254 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use shared::{TelemetryBatch, TelemetryEvent};
+
+const STATE_FILE_NAME: &str = "telemetry.json";
+/// Events are sent once this many have accumulated
+const FLUSH_THRESHOLD: usize = 20;
+/// Oldest events are dropped beyond this, so the file stays small while undecided
+const MAX_PENDING: usize = 200;
+const FLUSH_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Consent and the events not sent yet, kept next to the config file
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct TelemetryState {
+    /// `None` until the user runs `telemetry enable` or `telemetry disable`;
+    /// until then events are only kept locally
+    enabled: Option<bool>,
+    #[serde(default)]
+    pending: Vec<TelemetryEvent>,
+}
+
+impl TelemetryState {
+    /// Queue an event; returns whether the queue should be sent now
+    fn record(&mut self, event: TelemetryEvent) -> bool {
+        if self.enabled == Some(false) {
+            return false;
+        }
+        self.pending.push(event);
+        if self.pending.len() > MAX_PENDING {
+            let excess = self.pending.len() - MAX_PENDING;
+            self.pending.drain(..excess);
+        }
+        self.enabled == Some(true) && self.pending.len() >= FLUSH_THRESHOLD
+    }
+}
+
+fn state_path() -> Option<PathBuf> {
+    crate::config::config_file_path()
+        .and_then(|config| config.parent().map(|dir| dir.join(STATE_FILE_NAME)))
+}
+
+/// A missing or unreadable state file means no consent and nothing queued
+fn load(path: &Path) -> TelemetryState {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save(path: &Path, state: &TelemetryState) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory {:?}", parent))?;
+    }
+    fs::write(path, serde_json::to_vec_pretty(state)?)
+        .with_context(|| format!("Failed to write {:?}", path))
+}
+
+/// Subcommand path of an invocation, e.g. `patch apply`; arguments are never included
+pub fn command_name(matches: &clap::ArgMatches) -> String {
+    let mut words = Vec::new();
+    let mut current = matches;
+    while let Some((name, sub)) = current.subcommand() {
+        words.push(name);
+        current = sub;
+    }
+    words.join(" ")
+}
+
+// Sent without credentials so events cannot be tied to an account
+async fn send(api_url: &str, events: &[TelemetryEvent]) -> Result<()> {
+    let response = crate::http::send_with_challenge(
+        crate::http::anonymous_client()
+            .post(format!("{}/api/telemetry", api_url.trim_end_matches('/')))
+            .timeout(FLUSH_TIMEOUT)
+            .json(&TelemetryBatch {
+                events: events.to_vec(),
+            }),
+    )
+    .await
+    .context("Failed to send telemetry")?;
+    if !response.status().is_success() {
+        return Err(crate::api_errors::failure(response, "Failed to send telemetry").await);
+    }
+    Ok(())
+}
+
+/// Record one invocation after it finishes. Never fails the command: problems
+/// are only logged.
+pub async fn record(api_url: &str, command: &str, duration: Duration, success: bool) {
+    if command.is_empty() || command.starts_with("telemetry") {
+        return;
+    }
+    let Some(path) = state_path() else {
+        return;
+    };
+    let mut state = load(&path);
+    if state.enabled == Some(false) {
+        return;
+    }
+    let event = TelemetryEvent {
+        command: command.to_string(),
+        duration_ms: duration.as_millis().try_into().unwrap_or(i64::MAX),
+        success,
+        cli_version: env!("CARGO_PKG_VERSION").to_string(),
+        recorded_at: Utc::now(),
+    };
+    if state.record(event) {
+        match send(api_url, &state.pending).await {
+            Ok(()) => state.pending.clear(),
+            Err(err) => log::debug!("telemetry: flush failed: {}", err),
+        }
+    }
+    if let Err(err) = save(&path, &state) {
+        log::debug!("telemetry: {}", err);
+    }
+}
+
+fn required_state_path() -> Result<PathBuf> {
+    state_path().context("Could not determine home directory")
+}
+
+/// `telemetry enable`: give consent and send what was recorded locally so far
+pub async fn enable(api_url: &str) -> Result<()> {
+    let path = required_state_path()?;
+    let mut state = load(&path);
+    state.enabled = Some(true);
+    if !state.pending.is_empty() {
+        match send(api_url, &state.pending).await {
+            Ok(()) => {
+                println!("Sent {} locally recorded event(s).", state.pending.len());
+                state.pending.clear();
+            }
+            Err(err) => log::debug!("telemetry: flush failed: {}", err),
+        }
+    }
+    save(&path, &state)?;
+    println!("{}", "✓ Telemetry enabled. Thank you!".green().bold());
+    print_collected_fields();
+    Ok(())
+}
+
+/// `telemetry disable`: withdraw consent and delete everything recorded locally
+pub fn disable() -> Result<()> {
+    let path = required_state_path()?;
+    let mut state = load(&path);
+    let removed = state.pending.len();
+    state.enabled = Some(false);
+    state.pending.clear();
+    save(&path, &state)?;
+    println!("{}", "✓ Telemetry disabled.".green().bold());
+    println!("  Removed {} locally recorded event(s).", removed);
+    Ok(())
+}
+
+/// `telemetry status`: consent, what is queued and where it is kept
+pub fn status(json: bool) -> Result<()> {
+    let path = required_state_path()?;
+    let state = load(&path);
+    if json {
+        let status = serde_json::json!({
+            "enabled": state.enabled,
+            "pending_events": state.pending.len(),
+            "file": path,
+        });
+        println!("{}", serde_json::to_string_pretty(&status)?);
+        return Ok(());
+    }
+
+    let consent = match state.enabled {
+        Some(true) => "enabled".green().to_string(),
+        Some(false) => "disabled".yellow().to_string(),
+        None => "not decided — events stay on this machine".to_string(),
+    };
+    println!("{}: {}", "Telemetry".bold(), consent);
+    println!("{}: {}", "Queued events".bold(), state.pending.len());
+    println!("{}: {}", "Stored in".bold(), path.display());
+    print_collected_fields();
+    Ok(())
+}
+
+fn print_collected_fields() {
+    println!(
+        "  {}",
+        "Collected: command name, duration, success or failure, CLI version. \
+         Never arguments, addresses, tokens or the registry URL."
+            .bright_black()
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event() -> TelemetryEvent {
+        TelemetryEvent {
+            command: "search".to_string(),
+            duration_ms: 5,
+            success: true,
+            cli_version: "0.1.0".to_string(),
+            recorded_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn nothing_is_sent_before_consent() {
+        let mut state = TelemetryState::default();
+        for _ in 0..MAX_PENDING + 10 {
+            assert!(!state.record(event()));
+        }
+        assert_eq!(state.pending.len(), MAX_PENDING);
+    }
+
+    #[test]
+    fn consent_flushes_in_batches() {
+        let mut state = TelemetryState {
+            enabled: Some(true),
+            pending: Vec::new(),
+        };
+        let flushes = (0..FLUSH_THRESHOLD)
+            .filter(|_| state.record(event()))
+            .count();
+        assert_eq!(flushes, 1);
+    }
+
+    #[test]
+    fn disabled_records_nothing() {
+        let mut state = TelemetryState {
+            enabled: Some(false),
+            pending: Vec::new(),
+        };
+        assert!(!state.record(event()));
+        assert!(state.pending.is_empty());
+    }
+
+    #[test]
+    fn command_name_omits_arguments() {
+        use clap::{Arg, Command};
+        let cli = Command::new("soroban-registry").subcommand(
+            Command::new("patch")
+                .subcommand(Command::new("apply").arg(Arg::new("id").long("patch-id"))),
+        );
+        let matches = cli
+            .try_get_matches_from(["soroban-registry", "patch", "apply", "--patch-id", "p1"])
+            .unwrap();
+        assert_eq!(command_name(&matches), "patch apply");
+    }
+}
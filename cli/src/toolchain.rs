@@ -0,0 +1,201 @@
+//! Toolchain fingerprint sent with `push`: the rustc and soroban-sdk versions,
+//! target, profile settings, whether the compiler is pinned and whether the
+//! working tree was clean. Others rebuild from it to reproduce the binary.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::process::Command;
+
+use colored::Colorize;
+use shared::{is_pinned_channel, sdk_version_from_lock, BuildToolchain, DEFAULT_WASM_TARGET};
+
+/// Capture the toolchain for `wasm`, built in the project at `project_dir`.
+/// Returns `None` when rustc can't be run, e.g. publishing a prebuilt binary.
+pub fn capture(
+    project_dir: &Path,
+    wasm: &Path,
+    cargo_lock: Option<&str>,
+) -> Option<BuildToolchain> {
+    let rustc = command_output(project_dir, "rustc", &["--version"])?;
+    let (target, profile) = target_and_profile(wasm);
+    let root = cargo_root(project_dir);
+
+    let soroban_sdk = match cargo_lock {
+        Some(lock) => sdk_version_from_lock(lock),
+        None => root
+            .as_deref()
+            .and_then(|root| fs::read_to_string(root.join("Cargo.lock")).ok())
+            .and_then(|lock| sdk_version_from_lock(&lock)),
+    };
+    let profile_flags = root
+        .as_deref()
+        .map(|root| profile_flags(root, &profile))
+        .unwrap_or_default();
+    let dirty = command_output(project_dir, "git", &["status", "--porcelain"])
+        .is_some_and(|status| !status.is_empty());
+
+    Some(BuildToolchain {
+        rustc,
+        soroban_sdk,
+        target,
+        profile,
+        profile_flags,
+        pinned: pinned_channel(project_dir).is_some(),
+        dirty,
+    })
+}
+
+/// Print why the binary may not be reproducible from what is being published
+pub fn warn(toolchain: &BuildToolchain) {
+    for warning in toolchain.reproducibility_warnings() {
+        println!(
+            "{} Not reproducible as published: {}",
+            "⚠".yellow(),
+            warning
+        );
+    }
+    if !toolchain.pinned {
+        println!(
+            "  Pin it with a rust-toolchain.toml containing {}",
+            "[toolchain] channel = \"<version>\"".cyan()
+        );
+    }
+}
+
+/// Trimmed stdout of a command run in `dir`, if it ran and succeeded
+fn command_output(dir: &Path, program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program)
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .map_err(|err| log::debug!("Could not run {}: {}", program, err))
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Target triple and profile from a `target/<triple>/<profile>/x.wasm` path
+fn target_and_profile(wasm: &Path) -> (String, String) {
+    let parts: Vec<&str> = wasm
+        .components()
+        .filter_map(|c| match c {
+            Component::Normal(part) => part.to_str(),
+            _ => None,
+        })
+        .collect();
+    match parts.iter().rposition(|part| *part == "target") {
+        Some(i) if parts.len() == i + 4 => (parts[i + 1].to_string(), parts[i + 2].to_string()),
+        _ => (DEFAULT_WASM_TARGET.to_string(), "release".to_string()),
+    }
+}
+
+/// The directory whose Cargo.toml profiles apply: the workspace root, or the
+/// nearest package when it isn't in a workspace
+fn cargo_root(start: &Path) -> Option<PathBuf> {
+    crate::workspace::find_root(start).ok().or_else(|| {
+        start
+            .ancestors()
+            .find(|dir| dir.join("Cargo.toml").is_file())
+            .map(Path::to_path_buf)
+    })
+}
+
+/// `[profile.<profile>]` settings, with strings unquoted
+fn profile_flags(root: &Path, profile: &str) -> BTreeMap<String, String> {
+    let Some(cargo) = fs::read_to_string(root.join("Cargo.toml"))
+        .ok()
+        .and_then(|contents| contents.parse::<toml::Table>().ok())
+    else {
+        return BTreeMap::new();
+    };
+    cargo
+        .get("profile")
+        .and_then(|profiles| profiles.get(profile))
+        .and_then(toml::Value::as_table)
+        .map(|table| {
+            table
+                .iter()
+                .map(|(key, value)| {
+                    let value = match value {
+                        toml::Value::String(s) => s.clone(),
+                        other => other.to_string(),
+                    };
+                    (key.clone(), value)
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// The release a rust-toolchain file at or above `dir` pins, if any. Like
+/// rustup, the nearest file wins and `rust-toolchain` beats `rust-toolchain.toml`.
+fn pinned_channel(dir: &Path) -> Option<String> {
+    dir.ancestors().find_map(|dir| {
+        let channel = if let Ok(contents) = fs::read_to_string(dir.join("rust-toolchain")) {
+            toolchain_file_channel(&contents).or_else(|| Some(contents.trim().to_string()))
+        } else if let Ok(contents) = fs::read_to_string(dir.join("rust-toolchain.toml")) {
+            toolchain_file_channel(&contents)
+        } else {
+            return None;
+        };
+        Some(channel.filter(|channel| is_pinned_channel(channel)))
+    })?
+}
+
+fn toolchain_file_channel(contents: &str) -> Option<String> {
+    let file: toml::Table = contents.parse().ok()?;
+    file.get("toolchain")?
+        .get("channel")?
+        .as_str()
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn target_and_profile_come_from_the_build_path() {
+        assert_eq!(
+            target_and_profile(Path::new(
+                "/work/amm/target/wasm32v1-none/release-with-logs/amm.wasm"
+            )),
+            ("wasm32v1-none".to_string(), "release-with-logs".to_string())
+        );
+        assert_eq!(
+            target_and_profile(Path::new("dist/amm.wasm")),
+            (DEFAULT_WASM_TARGET.to_string(), "release".to_string())
+        );
+    }
+
+    #[test]
+    fn reads_profile_flags_and_pinned_channel() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("Cargo.toml"),
+            "[package]\nname = \"amm\"\n\n[profile.release]\nopt-level = \"z\"\nlto = true\ncodegen-units = 1\n",
+        )
+        .unwrap();
+        let flags = profile_flags(dir.path(), "release");
+        assert_eq!(flags["opt-level"], "z");
+        assert_eq!(flags["lto"], "true");
+        assert_eq!(flags["codegen-units"], "1");
+        assert!(profile_flags(dir.path(), "dev").is_empty());
+
+        let member = dir.path().join("contracts/amm");
+        fs::create_dir_all(&member).unwrap();
+        assert_eq!(pinned_channel(&member), None);
+        fs::write(dir.path().join("rust-toolchain"), "stable\n").unwrap();
+        assert_eq!(pinned_channel(&member), None);
+        fs::remove_file(dir.path().join("rust-toolchain")).unwrap();
+        fs::write(
+            dir.path().join("rust-toolchain.toml"),
+            "[toolchain]\nchannel = \"1.79.0\"\ntargets = [\"wasm32-unknown-unknown\"]\n",
+        )
+        .unwrap();
+        assert_eq!(pinned_channel(&member).as_deref(), Some("1.79.0"));
+    }
+}
@@ -0,0 +1,64 @@
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use serde_json::{json, Value};
+
+/// Submit `source` for verification of an on-chain contract. Prints the job
+/// to check later, or with `wait` blocks until it finishes or `timeout` passes.
+pub async fn submit(
+    api_url: &str,
+    contract_id: &str,
+    source: &Path,
+    compiler_version: &str,
+    build_params: Option<&str>,
+    wait: Option<Duration>,
+) -> Result<()> {
+    let source_code = std::fs::read_to_string(source)
+        .with_context(|| format!("Failed to read {}", source.display()))?;
+    let build_params: Value = match build_params {
+        Some(params) => serde_json::from_str(params).context("--build-params must be JSON")?,
+        None => json!({}),
+    };
+
+    let url = format!("{}/api/contracts/verify", api_url.trim_end_matches('/'));
+    let response = crate::http::client()
+        .post(&url)
+        .json(&json!({
+            "contract_id": contract_id,
+            "source_code": source_code,
+            "build_params": build_params,
+            "compiler_version": compiler_version,
+        }))
+        .send()
+        .await
+        .context("Failed to submit verification")?;
+    if !response.status().is_success() {
+        return Err(crate::api_errors::failure(response, "Failed to submit verification").await);
+    }
+    let submitted: Value = response.json().await?;
+    let job_id = submitted["job_id"]
+        .as_str()
+        .or_else(|| submitted["verification_id"].as_str())
+        .context("Verification response is missing its job id")?;
+    println!(
+        "{} Submitted verification of {} (job {})",
+        "→".cyan(),
+        contract_id.bold(),
+        job_id.bright_black()
+    );
+
+    match wait {
+        Some(timeout) => {
+            let job = crate::jobs::wait(api_url, job_id, timeout).await?;
+            crate::jobs::report(&job, false)?;
+            println!("{} {} is verified", "✓".green(), contract_id.bold());
+        }
+        None => println!(
+            "  Check on it with {}",
+            format!("soroban-registry jobs {}", job_id).cyan()
+        ),
+    }
+    Ok(())
+}
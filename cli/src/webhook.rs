@@ -211,7 +211,7 @@ pub async fn create_webhook(
     events: Vec<String>,
     secret_key: Option<&str>,
 ) -> Result<()> {
-    let client = reqwest::Client::new();
+    let client = crate::http::client();
 
     // Generate a secret key if not provided
     let secret = secret_key
@@ -237,8 +237,7 @@ pub async fn create_webhook(
         .context("Failed to reach registry API")?;
 
     if !response.status().is_success() {
-        let err = response.text().await?;
-        anyhow::bail!("API error: {}", err);
+        return Err(crate::api_errors::failure(response, "API error").await);
     }
 
     let webhook: WebhookSubscription = response.json().await?;
@@ -261,7 +260,7 @@ pub async fn create_webhook(
 
 /// List all webhook subscriptions.
 pub async fn list_webhooks(api_url: &str) -> Result<()> {
-    let client = reqwest::Client::new();
+    let client = crate::http::client();
 
     let response = client
         .get(format!("{}/api/webhooks", api_url))
@@ -270,8 +269,7 @@ pub async fn list_webhooks(api_url: &str) -> Result<()> {
         .context("Failed to reach registry API")?;
 
     if !response.status().is_success() {
-        let err = response.text().await?;
-        anyhow::bail!("API error: {}", err);
+        return Err(crate::api_errors::failure(response, "API error").await);
     }
 
     let webhooks: Vec<WebhookSubscription> = response.json().await?;
@@ -301,7 +299,7 @@ pub async fn list_webhooks(api_url: &str) -> Result<()> {
 
 /// Delete a webhook by ID.
 pub async fn delete_webhook(api_url: &str, webhook_id: &str) -> Result<()> {
-    let client = reqwest::Client::new();
+    let client = crate::http::client();
 
     let response = client
         .delete(format!("{}/api/webhooks/{}", api_url, webhook_id))
@@ -310,8 +308,7 @@ pub async fn delete_webhook(api_url: &str, webhook_id: &str) -> Result<()> {
         .context("Failed to reach registry API")?;
 
     if !response.status().is_success() {
-        let err = response.text().await?;
-        anyhow::bail!("API error: {}", err);
+        return Err(crate::api_errors::failure(response, "API error").await);
     }
 
     println!(
@@ -325,7 +322,7 @@ pub async fn delete_webhook(api_url: &str, webhook_id: &str) -> Result<()> {
 
 /// Send a test event to a webhook.
 pub async fn test_webhook(api_url: &str, webhook_id: &str) -> Result<()> {
-    let client = reqwest::Client::new();
+    let client = crate::http::client();
 
     let response = client
         .post(format!("{}/api/webhooks/{}/test", api_url, webhook_id))
@@ -334,8 +331,7 @@ pub async fn test_webhook(api_url: &str, webhook_id: &str) -> Result<()> {
         .context("Failed to reach registry API")?;
 
     if !response.status().is_success() {
-        let err = response.text().await?;
-        anyhow::bail!("API error: {}", err);
+        return Err(crate::api_errors::failure(response, "API error").await);
     }
 
     println!(
@@ -350,7 +346,7 @@ pub async fn test_webhook(api_url: &str, webhook_id: &str) -> Result<()> {
 
 /// View delivery logs for a webhook, including dead-letter entries.
 pub async fn webhook_logs(api_url: &str, webhook_id: &str, limit: usize) -> Result<()> {
-    let client = reqwest::Client::new();
+    let client = crate::http::client();
 
     let response = client
         .get(format!(
@@ -362,8 +358,7 @@ pub async fn webhook_logs(api_url: &str, webhook_id: &str, limit: usize) -> Resu
         .context("Failed to reach registry API")?;
 
     if !response.status().is_success() {
-        let err = response.text().await?;
-        anyhow::bail!("API error: {}", err);
+        return Err(crate::api_errors::failure(response, "API error").await);
     }
 
     let deliveries: Vec<WebhookDelivery> = response.json().await?;
@@ -411,7 +406,7 @@ pub async fn webhook_logs(api_url: &str, webhook_id: &str, limit: usize) -> Resu
 
 /// Manually retry a dead-letter delivery.
 pub async fn retry_delivery(api_url: &str, delivery_id: &str) -> Result<()> {
-    let client = reqwest::Client::new();
+    let client = crate::http::client();
 
     let response = client
         .post(format!("{}/api/webhook-deliveries/{}/retry", api_url, delivery_id))
@@ -420,8 +415,7 @@ pub async fn retry_delivery(api_url: &str, delivery_id: &str) -> Result<()> {
         .context("Failed to reach registry API")?;
 
     if !response.status().is_success() {
-        let err = response.text().await?;
-        anyhow::bail!("API error: {}", err);
+        return Err(crate::api_errors::failure(response, "API error").await);
     }
 
     println!(
@@ -6,9 +6,349 @@ use std::io::{self, BufRead, BufReader, Write};
 use std::path::{Path, PathBuf};
 use std::process::Command;
 
+use crate::cli_error::CliError;
+use crate::commands::Network;
+use crate::config::{self, DefaultsUpdate};
+
 const HISTORY_FILE_NAME: &str = "deployments.ndjson";
 
-pub async fn run(_api_url: &str) -> Result<()> {
+/// Flows accepted by `wizard <flow>`, in menu order
+pub const FLOW_NAMES: [&str; 4] = ["setup", "publish", "migrate", "deploy"];
+
+const FLOW_SUMMARIES: [&str; 4] = [
+    "First-run configuration: API URL, network and credentials",
+    "Guided publish with every metadata field checked as you go",
+    "Plan a state migration between two contract snapshots",
+    "Instantiate a contract from a local WASM file",
+];
+
+const NETWORKS: [&str; 3] = ["mainnet", "testnet", "futurenet"];
+
+pub async fn run(api_url: &str, flow: Option<&str>) -> Result<()> {
+    if !crate::interactive::assume_yes() {
+        crate::interactive::require_terminal(
+            "The wizard",
+            "pass --yes to accept every default, or run the equivalent command directly",
+        )?;
+    }
+    let flow = match flow {
+        Some(flow) => flow.to_string(),
+        None => choose_flow()?,
+    };
+    match flow.as_str() {
+        "setup" => setup().await,
+        "publish" => publish(api_url).await,
+        "migrate" => migrate(),
+        "deploy" => deploy(),
+        other => anyhow::bail!(
+            "Unknown wizard flow '{}'. Choose one of: {}",
+            other,
+            FLOW_NAMES.join(", ")
+        ),
+    }
+}
+
+fn choose_flow() -> Result<String> {
+    println!("\n{}", "Soroban Registry Wizard".bold().cyan());
+    println!("{}", "=".repeat(80).cyan());
+    for (i, (name, summary)) in FLOW_NAMES.iter().zip(FLOW_SUMMARIES).enumerate() {
+        println!("  {}. {:<8} {}", i + 1, name.bold(), summary.bright_black());
+    }
+    loop {
+        let answer = prompt("Choose a flow (number or name)", Some("setup".into()))?;
+        if let Some(name) = answer
+            .trim()
+            .parse::<usize>()
+            .ok()
+            .and_then(|n| n.checked_sub(1))
+            .and_then(|i| FLOW_NAMES.get(i))
+        {
+            return Ok(name.to_string());
+        }
+        match complete(&answer, &FLOW_NAMES) {
+            Ok(name) => return Ok(name),
+            Err(matches) => reject(&no_completion(&answer, &matches, &FLOW_NAMES))?,
+        }
+    }
+}
+
+async fn setup() -> Result<()> {
+    println!("\n{}", "First-Run Setup".bold().cyan());
+    println!("{}", "=".repeat(80).cyan());
+    let current = config::resolve_runtime_config(None, None, None)?;
+
+    let api_base = prompt_checked("Registry API URL", Some(current.api_base), |s| {
+        shared::validate_project_url(s.trim())
+    })?;
+    let api_base = api_base.trim().trim_end_matches('/').to_string();
+
+    let network = prompt_completion(
+        "Default network [mainnet|testnet|futurenet|auto]",
+        Some(current.network.to_string()),
+        &["mainnet", "testnet", "futurenet", "auto"],
+    )?;
+
+    let has_token = config::profile_token().is_some();
+    println!(
+        "{}",
+        "Private registries need a session token from /api/auth/verify or an API key."
+            .bright_black()
+    );
+    let token_label = if has_token {
+        "API token (input hidden, blank keeps the saved one): "
+    } else {
+        "API token (input hidden, blank to skip): "
+    };
+    let token = if crate::interactive::can_prompt() {
+        rpassword::prompt_password(token_label).context("Failed to read token")?
+    } else {
+        String::new()
+    };
+    let token = Some(token.trim().to_string()).filter(|t| !t.is_empty());
+
+    match check_registry(&api_base).await {
+        Ok(()) => println!("{}", "✓ Registry reachable".green().bold()),
+        Err(e) => println!("{} {}", "! Registry not reachable:".yellow().bold(), e),
+    }
+
+    let update = DefaultsUpdate {
+        network: Some(network.clone()),
+        api_base: Some(api_base.clone()),
+        token: token.clone(),
+    };
+    println!("\n{}", "Configuration Preview".bold().cyan());
+    println!("{}", "-".repeat(80).cyan());
+    println!("{}: {}", "API URL".bold(), api_base.bright_blue());
+    println!("{}: {}", "Network".bold(), network.bright_blue());
+    println!(
+        "{}: {}",
+        "Token".bold(),
+        match (&token, has_token) {
+            (Some(_), _) => "new token",
+            (None, true) => "unchanged",
+            (None, false) => "none",
+        }
+        .bright_black()
+    );
+    println!("{}", "-".repeat(80).cyan());
+
+    if !confirm("Save to the config file? [y/N]", false)? {
+        println!("{}", "Nothing saved.".yellow());
+    } else {
+        let path = config::save_defaults(&update)?;
+        println!(
+            "{} {}",
+            "✓ Saved".green().bold(),
+            path.display().to_string().bright_black()
+        );
+    }
+
+    let mut lines = vec![format!(
+        "export SOROBAN_REGISTRY_API_URL={}",
+        shell_quote(&api_base)
+    )];
+    if token.is_some() {
+        lines.push("export SOROBAN_REGISTRY_TOKEN=<token>".to_string());
+    }
+    lines.push(format!("soroban-registry --network {} <command>", network));
+    print_equivalent("Equivalent without the config file", &lines);
+    Ok(())
+}
+
+async fn publish(api_url: &str) -> Result<()> {
+    println!("\n{}", "Guided Publish".bold().cyan());
+    println!("{}", "=".repeat(80).cyan());
+    println!(
+        "{}",
+        "Optional fields can be left blank. Every answer is checked with the registry's rules."
+            .bright_black()
+    );
+
+    let contract_id = prompt_checked("On-chain contract ID", None, |s| {
+        if s.trim().is_empty() {
+            Err("contract ID is required".to_string())
+        } else {
+            Ok(())
+        }
+    })?;
+    let name = prompt_checked(
+        "Contract name (optionally @publisher/name)",
+        None,
+        shared::validate_contract_name,
+    )?;
+    let description = prompt_optional("Description", shared::validate_description)?;
+
+    let default_network = config::resolve_network(None)
+        .map(|n| n.to_string())
+        .ok()
+        .filter(|n| NETWORKS.contains(&n.as_str()))
+        .unwrap_or_else(|| "testnet".to_string());
+    let network = prompt_completion(
+        "Network [mainnet|testnet|futurenet]",
+        Some(default_network),
+        &NETWORKS,
+    )?;
+
+    println!(
+        "{} {}",
+        "Categories:".bright_black(),
+        shared::CONTRACT_CATEGORIES.join(", ").bright_black()
+    );
+    let category = prompt_optional_completion("Category", shared::CONTRACT_CATEGORIES)?;
+
+    let known_tags = fetch_known_tags(api_url).await;
+    if !known_tags.is_empty() {
+        println!(
+            "{}",
+            "End a tag with * to complete it from tags already in use (e.g. def*).".bright_black()
+        );
+    }
+    let tags = prompt_tags(&known_tags)?;
+
+    let publisher = prompt_with_validation(
+        "Publisher address (G...) or stored key name",
+        None::<String>,
+        |s: &str| {
+            let s = s.trim();
+            shared::decode_account_id(s).is_some() || crate::keystore::read(s).is_ok()
+        },
+        "Invalid publisher. Provide a Stellar address (G...) or a name from `keys list`.",
+    )?;
+    let publisher = match crate::keystore::read(publisher.trim()) {
+        Ok(stored) => stored.account,
+        Err(_) => publisher.trim().to_string(),
+    };
+
+    let license = prompt_optional(
+        "SPDX license expression (e.g. MIT OR Apache-2.0)",
+        shared::validate_spdx_expression,
+    )?;
+    let urls = shared::ProjectUrls {
+        repository_url: prompt_optional("Repository URL", shared::validate_project_url)?,
+        homepage: prompt_optional("Homepage URL", shared::validate_project_url)?,
+        documentation_url: prompt_optional("Documentation URL", shared::validate_project_url)?,
+    };
+
+    let mut args = vec![
+        ("--contract-id", Some(contract_id.clone())),
+        ("--name", Some(name.clone())),
+        ("--description", description.clone()),
+        ("--category", category.clone()),
+        ("--tags", Some(tags.join(",")).filter(|t| !t.is_empty())),
+        ("--publisher", Some(publisher.clone())),
+        ("--license", license.clone()),
+    ];
+    for (field, url) in urls.fields() {
+        let flag = match field {
+            "repository_url" => "--repository-url",
+            "homepage" => "--homepage",
+            _ => "--documentation-url",
+        };
+        args.push((flag, url.map(str::to_string)));
+    }
+    let command = command_line(&format!("--network {} publish", network), &args);
+    print_equivalent("Equivalent command", &[command]);
+
+    let network: Network = network.parse()?;
+    let dry_run = !confirm("Publish now? (No runs a dry-run instead) [y/N]", false)?;
+    crate::commands::publish(
+        api_url,
+        &contract_id,
+        &name,
+        description.as_deref(),
+        network,
+        category.as_deref(),
+        tags,
+        &publisher,
+        license.as_deref(),
+        None,
+        urls,
+        None,
+        dry_run,
+    )
+    .await?;
+    if dry_run {
+        println!(
+            "\n{}",
+            "Nothing was published; run the equivalent command above when ready.".bright_black()
+        );
+    }
+    Ok(())
+}
+
+fn migrate() -> Result<()> {
+    println!("\n{}", "Migration Planner".bold().cyan());
+    println!("{}", "=".repeat(80).cyan());
+    println!(
+        "{}",
+        "Snapshots are read from .soroban-registry/contracts/<id>.json in this directory."
+            .bright_black()
+    );
+
+    let snapshot = |s: &str| {
+        if crate::migration::snapshot_exists(s.trim()) {
+            Ok(())
+        } else {
+            Err(format!(
+                "no snapshot at .soroban-registry/contracts/{}.json",
+                s.trim()
+            ))
+        }
+    };
+    let old_id = prompt_checked("Current contract ID", None, snapshot)?;
+    let new_id = prompt_checked("Target contract ID", None, snapshot)?;
+    let (old_id, new_id) = (old_id.trim().to_string(), new_id.trim().to_string());
+
+    crate::migration::preview(&old_id, &new_id)?;
+    let safe = match crate::migration::validate(&old_id, &new_id) {
+        Ok(()) => true,
+        Err(e) => {
+            println!("{} {}", "!".yellow().bold(), e);
+            false
+        }
+    };
+
+    let mut commands = vec![
+        command_line(
+            "migrate preview",
+            &[("", Some(old_id.clone())), ("", Some(new_id.clone()))],
+        ),
+        command_line(
+            "migrate validate",
+            &[("", Some(old_id.clone())), ("", Some(new_id.clone()))],
+        ),
+    ];
+    if confirm("Generate a migration script template? [y/N]", false)? {
+        let language =
+            prompt_completion("Language [rust|js]", Some("rust".into()), &["rust", "js"])?;
+        let output = prompt("Output file (blank for the default name)", Some("".into()))?;
+        let output = Some(output.trim().to_string()).filter(|o| !o.is_empty());
+        crate::migration::generate_template(&old_id, &new_id, &language, output.as_deref())?;
+        commands.push(command_line(
+            "migrate generate",
+            &[
+                ("", Some(old_id.clone())),
+                ("", Some(new_id.clone())),
+                ("--language", Some(language)),
+                ("--output", output),
+            ],
+        ));
+    }
+    commands.push(command_line(
+        "migrate apply",
+        &[("", Some(old_id.clone())), ("", Some(new_id))],
+    ));
+    print_equivalent("Equivalent commands", &commands);
+    if !safe {
+        println!(
+            "{}",
+            "Resolve the validation findings before running `migrate apply`.".yellow()
+        );
+    }
+    Ok(())
+}
+
+fn deploy() -> Result<()> {
     println!("\n{}", "Contract Instantiation Wizard".bold().cyan());
     println!("{}", "=".repeat(80).cyan());
 
@@ -25,14 +365,20 @@ pub async fn run(_api_url: &str) -> Result<()> {
     )?;
 
     let signer = prompt_with_validation(
-        "Enter signer address or secret (starts with G… or S…)",
+        "Enter signer address, secret (starts with G… or S…) or stored key name",
         None::<String>,
         |s: &str| {
             let s = s.trim();
-            (s.starts_with('G') || s.starts_with('S')) && s.len() >= 56
+            ((s.starts_with('G') || s.starts_with('S')) && s.len() >= 56)
+                || crate::keystore::read(s).is_ok()
         },
-        "Invalid signer. Provide a Stellar address (G...) or secret (S...).",
+        "Invalid signer. Provide a Stellar address (G...), secret (S...) or a name from `keys list`.",
     )?;
+    // A stored key stands in for its account, so no secret is typed or recorded
+    let signer = match crate::keystore::read(signer.trim()) {
+        Ok(stored) => stored.account,
+        Err(_) => signer,
+    };
 
     let wasm_path = prompt_with_validation(
         "Path to contract WASM (.wasm)",
@@ -302,6 +648,10 @@ fn mask_secret(s: &str) -> String {
 }
 
 fn prompt(label: &str, default: Option<String>) -> Result<String> {
+    if let Some(answer) = crate::interactive::unattended_answer(label, default.clone())? {
+        println!("{}: {}", label.bold(), answer);
+        return Ok(answer);
+    }
     print!(
         "{}{}: ",
         label.bold(),
@@ -336,17 +686,223 @@ where
             return Ok(value);
         }
 
-        println!("{}", format!("Error: {}", error_msg).red());
+        reject(error_msg)?;
     }
 }
 
 fn confirm(label: &str, default_yes: bool) -> Result<bool> {
+    if crate::interactive::assume_yes() {
+        println!("{}: {}", label.bold(), "yes (--yes)".bright_black());
+        return Ok(true);
+    }
     let default = if default_yes { "Y" } else { "N" };
     let ans = prompt(label, Some(default.into()))?;
     let ans_l = ans.to_lowercase();
     Ok(matches!(ans_l.as_str(), "y" | "yes"))
 }
 
+/// Prompt until `check` accepts the answer, showing the rule it broke
+fn prompt_checked<F>(label: &str, default: Option<String>, check: F) -> Result<String>
+where
+    F: Fn(&str) -> std::result::Result<(), String>,
+{
+    loop {
+        let value = prompt(label, default.clone())?;
+        match check(&value) {
+            Ok(()) => return Ok(value),
+            Err(e) => reject(&e)?,
+        }
+    }
+}
+
+/// Like [`prompt_checked`], but a blank answer skips the field
+fn prompt_optional<F>(label: &str, check: F) -> Result<Option<String>>
+where
+    F: Fn(&str) -> std::result::Result<(), String>,
+{
+    let value = prompt_checked(label, None, |s| {
+        if s.trim().is_empty() {
+            Ok(())
+        } else {
+            check(s.trim())
+        }
+    })?;
+    Ok(Some(value.trim().to_string()).filter(|v| !v.is_empty()))
+}
+
+/// Prompt for one of `options`, completing a unique prefix of it
+fn prompt_completion(label: &str, default: Option<String>, options: &[&str]) -> Result<String> {
+    loop {
+        let value = prompt(label, default.clone())?;
+        match complete(&value, options) {
+            Ok(option) => return Ok(option),
+            Err(matches) => reject(&no_completion(&value, &matches, options))?,
+        }
+    }
+}
+
+fn prompt_optional_completion(label: &str, options: &[&str]) -> Result<Option<String>> {
+    loop {
+        let value = prompt(label, Some("".into()))?;
+        if value.trim().is_empty() {
+            return Ok(None);
+        }
+        match complete(&value, options) {
+            Ok(option) => return Ok(Some(option)),
+            Err(matches) => reject(&no_completion(&value, &matches, options))?,
+        }
+    }
+}
+
+/// Comma-separated tags; `fragment*` completes from `known` tags
+fn prompt_tags(known: &[String]) -> Result<Vec<String>> {
+    'prompt: loop {
+        let value = prompt("Tags (comma-separated)", Some("".into()))?;
+        let mut tags = Vec::new();
+        for tag in value.split(',').map(str::trim).filter(|t| !t.is_empty()) {
+            match tag.strip_suffix('*') {
+                Some(fragment) => match complete(fragment, known) {
+                    Ok(found) => {
+                        println!("  {} {}", "→".bright_black(), found.bright_blue());
+                        tags.push(found);
+                    }
+                    Err(matches) => {
+                        reject(&no_completion(fragment, &matches, known))?;
+                        continue 'prompt;
+                    }
+                },
+                None => tags.push(tag.to_string()),
+            }
+        }
+        match shared::validate_contract_tags(&tags) {
+            Ok(()) => return Ok(tags),
+            Err(e) => reject(&e)?,
+        }
+    }
+}
+
+/// Resolve `input` against `options`, ignoring case: an exact match or the only
+/// option it prefixes. Otherwise returns every option it prefixes (maybe none).
+fn complete<S: AsRef<str>>(input: &str, options: &[S]) -> std::result::Result<String, Vec<String>> {
+    let needle = input.trim().to_lowercase();
+    if let Some(exact) = options.iter().find(|o| o.as_ref().to_lowercase() == needle) {
+        return Ok(exact.as_ref().to_string());
+    }
+    let matches: Vec<String> = options
+        .iter()
+        .map(|o| o.as_ref())
+        .filter(|o| !needle.is_empty() && o.to_lowercase().starts_with(&needle))
+        .map(str::to_string)
+        .collect();
+    match matches.as_slice() {
+        [only] => Ok(only.clone()),
+        _ => Err(matches),
+    }
+}
+
+fn no_completion<S: AsRef<str>>(input: &str, matches: &[String], options: &[S]) -> String {
+    if matches.is_empty() {
+        let options: Vec<&str> = options.iter().map(|o| o.as_ref()).collect();
+        format!(
+            "'{}' matches nothing. Options: {}",
+            input.trim(),
+            options.join(", ")
+        )
+    } else {
+        format!("'{}' is ambiguous: {}", input.trim(), matches.join(", "))
+    }
+}
+
+/// Show why an answer was rejected so it can be re-entered; when no one can
+/// re-enter it, the rejection is the error
+fn reject(message: &str) -> Result<()> {
+    if !crate::interactive::can_prompt() {
+        return Err(CliError::Usage(message.to_string()).into());
+    }
+    println!("{}", format!("Error: {}", message).red());
+    Ok(())
+}
+
+/// Tags already used on the registry, most common first; empty when offline
+async fn fetch_known_tags(api_url: &str) -> Vec<String> {
+    let url = format!(
+        "{}/api/contracts?facets=true&limit=1",
+        api_url.trim_end_matches('/')
+    );
+    let response = match crate::http::client().get(&url).send().await {
+        Ok(response) if response.status().is_success() => response,
+        Ok(response) => {
+            log::debug!("Tag suggestions unavailable: HTTP {}", response.status());
+            return Vec::new();
+        }
+        Err(e) => {
+            log::debug!("Tag suggestions unavailable: {}", e);
+            return Vec::new();
+        }
+    };
+    let data: serde_json::Value = response.json().await.unwrap_or_default();
+    data["facets"]["tags"]
+        .as_array()
+        .map(|tags| {
+            tags.iter()
+                .filter_map(|t| t["value"].as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+async fn check_registry(api_base: &str) -> Result<()> {
+    let url = format!("{}/health", api_base.trim_end_matches('/'));
+    let response = crate::http::anonymous_client()
+        .get(&url)
+        .timeout(std::time::Duration::from_secs(5))
+        .send()
+        .await
+        .context("no response")?;
+    if !response.status().is_success() {
+        anyhow::bail!("HTTP {}", response.status());
+    }
+    Ok(())
+}
+
+/// `soroban-registry <subcommand>` followed by each set flag; an empty flag
+/// name marks a positional argument
+fn command_line(subcommand: &str, args: &[(&str, Option<String>)]) -> String {
+    let mut line = format!("soroban-registry {}", subcommand);
+    for (flag, value) in args {
+        let Some(value) = value else { continue };
+        if !flag.is_empty() {
+            line.push(' ');
+            line.push_str(flag);
+        }
+        line.push(' ');
+        line.push_str(&shell_quote(value));
+    }
+    line
+}
+
+/// Single-quote `value` for POSIX shells unless it is plainly safe
+fn shell_quote(value: &str) -> String {
+    let plain = !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_.,/:@=+".contains(c));
+    if plain {
+        value.to_string()
+    } else {
+        format!("'{}'", value.replace('\'', "'\\''"))
+    }
+}
+
+fn print_equivalent(title: &str, lines: &[String]) {
+    println!("\n{}", title.bold().cyan());
+    println!("{}", "-".repeat(80).cyan());
+    for line in lines {
+        println!("{}", line);
+    }
+    println!("{}", "-".repeat(80).cyan());
+}
+
 fn detect_soroban() -> bool {
     Command::new(if cfg!(windows) { "where" } else { "which" })
         .arg("soroban")
@@ -391,3 +947,46 @@ fn home_dir() -> Option<PathBuf> {
         std::env::var_os("HOME").map(PathBuf::from)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn complete_prefers_exact_then_unique_prefix() {
+        let options = ["dao", "defi", "dex", "token"];
+        assert_eq!(complete("DeFi", &options), Ok("defi".to_string()));
+        assert_eq!(complete("to", &options), Ok("token".to_string()));
+        assert_eq!(
+            complete("de", &options),
+            Err(vec!["defi".to_string(), "dex".to_string()])
+        );
+        assert_eq!(complete("memes", &options), Err(vec![]));
+        assert_eq!(complete("", &options), Err(vec![]));
+    }
+
+    #[test]
+    fn command_line_quotes_only_when_needed() {
+        let line = command_line(
+            "publish",
+            &[
+                ("--name", Some("@alice/amm".to_string())),
+                ("--description", Some("Alice's AMM".to_string())),
+                ("--category", None),
+                ("--tags", Some("amm,standard:sep-41".to_string())),
+            ],
+        );
+        assert_eq!(
+            line,
+            "soroban-registry publish --name @alice/amm \
+             --description 'Alice'\\''s AMM' --tags amm,standard:sep-41"
+        );
+        assert_eq!(
+            command_line(
+                "migrate apply",
+                &[("", Some("v1".into())), ("", Some("v2".into()))]
+            ),
+            "soroban-registry migrate apply v1 v2"
+        );
+    }
+}
@@ -0,0 +1,1002 @@
+//! Cargo workspaces holding several Soroban contracts. Members are mapped to
+//! registry names in `soroban-registry.toml` at the workspace root:
+//!
+//! ```toml
+//! publisher = "GABC..."        # used to register members on first publish
+//!
+//! [members.amm]
+//! name = "@alice/amm"
+//! contract_id = "CDLZ..."      # on-chain ID, only needed until it is registered
+//! ```
+//!
+//! `status` compares each member's built WASM with its latest published
+//! version; `publish --all` pushes the changed ones, dependencies first;
+//! `changed --since <ref>` lists members whose sources git reports as changed.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::conversions::contract_path;
+
+/// Written at the workspace root, next to the workspace Cargo.toml
+pub const WORKSPACE_MANIFEST_NAME: &str = "soroban-registry.toml";
+
+/// Where `stellar contract build` puts release WASM, newest toolchain first
+const WASM_TARGETS: &[&str] = &["wasm32v1-none", "wasm32-unknown-unknown"];
+
+/// Files at the workspace root that change how every contract builds
+const WORKSPACE_BUILD_FILES: &[&str] = &[
+    "Cargo.toml",
+    "Cargo.lock",
+    "rust-toolchain.toml",
+    "rust-toolchain",
+];
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WorkspaceManifest {
+    /// Publisher address used when a member is registered for the first time
+    pub publisher: Option<String>,
+    /// Cargo package name to its registry settings
+    #[serde(default)]
+    pub members: BTreeMap<String, MemberConfig>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MemberConfig {
+    /// Registry name, `@namespace/name`
+    pub name: String,
+    /// On-chain contract ID, used to register the contract on first publish
+    pub contract_id: Option<String>,
+    /// Built WASM relative to the workspace root; defaults to the cargo target dir
+    pub wasm: Option<PathBuf>,
+    /// Contract spec JSON relative to the workspace root; generated from the WASM when omitted
+    pub abi: Option<PathBuf>,
+}
+
+/// A workspace member that builds a Soroban contract
+#[derive(Debug, Clone)]
+pub struct Member {
+    pub package: String,
+    pub version: String,
+    /// Other contract members this one depends on
+    pub depends_on: Vec<String>,
+    /// Directories its build reads, relative to the workspace root: its own
+    /// first, then those of local path dependencies
+    pub sources: Vec<PathBuf>,
+    pub config: Option<MemberConfig>,
+    pub wasm: PathBuf,
+}
+
+#[derive(Debug)]
+pub struct Workspace {
+    pub root: PathBuf,
+    pub manifest: WorkspaceManifest,
+    /// In publish order: every member comes after the members it depends on
+    pub members: Vec<Member>,
+}
+
+/// Where a member stands relative to the registry
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum MemberState {
+    /// Not listed in soroban-registry.toml, so it is never published
+    Unmapped,
+    /// No WASM at the expected path yet
+    NotBuilt,
+    /// The registry has no contract under the mapped name
+    NotRegistered,
+    /// The built WASM is what the latest version was published from
+    UpToDate { version: String },
+    /// The WASM changed but the crate version is still the published one
+    NeedsVersionBump { version: String },
+    /// A new version is ready to publish
+    Unpublished { published: Option<String> },
+}
+
+impl MemberState {
+    fn describe(&self) -> String {
+        match self {
+            MemberState::Unmapped => format!("not in {}", WORKSPACE_MANIFEST_NAME),
+            MemberState::NotBuilt => "not built (run `stellar contract build`)".to_string(),
+            MemberState::NotRegistered => "not in the registry yet".to_string(),
+            MemberState::UpToDate { version } => format!("up to date ({})", version),
+            MemberState::NeedsVersionBump { version } => {
+                format!("changed since {}; bump the version", version)
+            }
+            MemberState::Unpublished { published: None } => "unpublished".to_string(),
+            MemberState::Unpublished {
+                published: Some(version),
+            } => format!("unpublished changes (latest {})", version),
+        }
+    }
+}
+
+fn read_cargo_toml(dir: &Path) -> Result<toml::Table> {
+    let path = dir.join("Cargo.toml");
+    let contents =
+        fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+    toml::from_str(&contents).with_context(|| format!("Failed to parse {}", path.display()))
+}
+
+/// The nearest directory at or above `start` whose Cargo.toml has a `[workspace]`
+pub fn find_root(start: &Path) -> Result<PathBuf> {
+    for dir in start.ancestors() {
+        if dir.join("Cargo.toml").is_file() && read_cargo_toml(dir)?.contains_key("workspace") {
+            return Ok(dir.to_path_buf());
+        }
+    }
+    bail!("{} is not inside a Cargo workspace", start.display())
+}
+
+/// Member directories named by `[workspace] members`; only `dir/*` globs are expanded
+fn member_dirs(root: &Path, workspace: &toml::Table) -> Result<Vec<PathBuf>> {
+    let patterns = |key: &str| -> Vec<String> {
+        workspace
+            .get(key)
+            .and_then(|v| v.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|v| v.as_str().map(str::to_string))
+            .collect()
+    };
+    let excluded: BTreeSet<PathBuf> = patterns("exclude").iter().map(|p| root.join(p)).collect();
+    let mut dirs = Vec::new();
+    for pattern in patterns("members") {
+        if let Some(parent) = pattern.strip_suffix("/*") {
+            let parent = root.join(parent);
+            let entries = fs::read_dir(&parent)
+                .with_context(|| format!("Failed to read {}", parent.display()))?;
+            let mut found: Vec<PathBuf> = entries
+                .filter_map(|entry| entry.ok().map(|e| e.path()))
+                .filter(|dir| dir.join("Cargo.toml").is_file())
+                .collect();
+            found.sort();
+            dirs.extend(found);
+        } else if pattern.contains(['*', '?', '[']) {
+            bail!("Unsupported workspace member pattern '{}'", pattern);
+        } else {
+            dirs.push(root.join(pattern));
+        }
+    }
+    dirs.retain(|dir| !excluded.contains(dir));
+    Ok(dirs)
+}
+
+/// A `cdylib` depending on `soroban-sdk`
+fn is_contract(cargo: &toml::Table) -> bool {
+    let cdylib = cargo
+        .get("lib")
+        .and_then(|lib| lib.get("crate-type"))
+        .and_then(|types| types.as_array())
+        .is_some_and(|types| types.iter().any(|t| t.as_str() == Some("cdylib")));
+    let sdk = cargo
+        .get("dependencies")
+        .and_then(|deps| deps.as_table())
+        .is_some_and(|deps| deps.contains_key("soroban-sdk"));
+    cdylib && sdk
+}
+
+/// Package names of `[dependencies]`, following `package = "..."` renames
+fn dependency_names(cargo: &toml::Table) -> Vec<String> {
+    cargo
+        .get("dependencies")
+        .and_then(|deps| deps.as_table())
+        .into_iter()
+        .flatten()
+        .map(|(key, spec)| {
+            spec.get("package")
+                .and_then(|p| p.as_str())
+                .unwrap_or(key)
+                .to_string()
+        })
+        .collect()
+}
+
+/// `path` with `.` and `..` resolved without touching the filesystem
+fn normalize(path: &Path) -> PathBuf {
+    let mut out = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                out.pop();
+            }
+            other => out.push(other),
+        }
+    }
+    out
+}
+
+/// Directories of `path = "..."` dependencies, including those inherited
+/// from `[workspace.dependencies]` with `workspace = true`
+fn path_dependencies(
+    cargo: &toml::Table,
+    dir: &Path,
+    root: &Path,
+    workspace: &toml::Table,
+) -> Vec<PathBuf> {
+    let workspace_deps = workspace.get("dependencies").and_then(|d| d.as_table());
+    cargo
+        .get("dependencies")
+        .and_then(|deps| deps.as_table())
+        .into_iter()
+        .flatten()
+        .filter_map(|(key, spec)| {
+            if let Some(path) = spec.get("path").and_then(|p| p.as_str()) {
+                return Some(normalize(&dir.join(path)));
+            }
+            if spec.get("workspace").and_then(|w| w.as_bool()) == Some(true) {
+                let path = workspace_deps?.get(key)?.get("path")?.as_str()?;
+                return Some(normalize(&root.join(path)));
+            }
+            None
+        })
+        .collect()
+}
+
+fn default_wasm(root: &Path, package: &str) -> PathBuf {
+    let file = format!("{}.wasm", package.replace('-', "_"));
+    let candidates: Vec<PathBuf> = WASM_TARGETS
+        .iter()
+        .map(|target| root.join("target").join(target).join("release").join(&file))
+        .collect();
+    candidates
+        .iter()
+        .find(|path| path.is_file())
+        .unwrap_or(&candidates[0])
+        .clone()
+}
+
+/// Order `members` so each comes after those it depends on; ties go alphabetically
+fn publish_order(members: Vec<Member>) -> Result<Vec<Member>> {
+    let mut remaining: BTreeMap<String, Member> = members
+        .into_iter()
+        .map(|member| (member.package.clone(), member))
+        .collect();
+    let mut ordered = Vec::with_capacity(remaining.len());
+    while !remaining.is_empty() {
+        let ready: Vec<String> = remaining
+            .values()
+            .filter(|member| member.depends_on.iter().all(|d| !remaining.contains_key(d)))
+            .map(|member| member.package.clone())
+            .collect();
+        if ready.is_empty() {
+            let cycle: Vec<&str> = remaining.keys().map(String::as_str).collect();
+            bail!(
+                "Workspace contracts depend on each other in a cycle: {}",
+                cycle.join(", ")
+            );
+        }
+        for package in ready {
+            if let Some(member) = remaining.remove(&package) {
+                ordered.push(member);
+            }
+        }
+    }
+    Ok(ordered)
+}
+
+/// The workspace containing `start`, with its contract members in publish order
+pub fn load(start: &Path) -> Result<Workspace> {
+    let root = normalize(&find_root(start)?);
+    let root_cargo = read_cargo_toml(&root)?;
+    let workspace = root_cargo
+        .get("workspace")
+        .and_then(|w| w.as_table())
+        .context("Cargo.toml [workspace] is not a table")?;
+    let workspace_version = workspace
+        .get("package")
+        .and_then(|p| p.get("version"))
+        .and_then(|v| v.as_str());
+
+    let manifest_path = root.join(WORKSPACE_MANIFEST_NAME);
+    let mut manifest: WorkspaceManifest = if manifest_path.is_file() {
+        let raw = fs::read_to_string(&manifest_path)
+            .with_context(|| format!("Failed to read {}", manifest_path.display()))?;
+        toml::from_str(&raw).with_context(|| format!("Invalid {}", manifest_path.display()))?
+    } else {
+        WorkspaceManifest::default()
+    };
+
+    let mut members = Vec::new();
+    for dir in member_dirs(&root, workspace)? {
+        let cargo = read_cargo_toml(&dir)?;
+        if !is_contract(&cargo) {
+            continue;
+        }
+        let package = cargo.get("package");
+        let name = package
+            .and_then(|p| p.get("name"))
+            .and_then(|n| n.as_str())
+            .with_context(|| format!("{}/Cargo.toml package has no name", dir.display()))?;
+        // `version.workspace = true` inherits `[workspace.package] version`
+        let version = match package.and_then(|p| p.get("version")) {
+            Some(toml::Value::String(version)) => version.as_str(),
+            Some(_) => workspace_version.unwrap_or("0.0.0"),
+            None => "0.0.0",
+        };
+        let config = manifest.members.remove(name);
+        let wasm = match config.as_ref().and_then(|c| c.wasm.as_ref()) {
+            Some(path) => root.join(path),
+            None => default_wasm(&root, name),
+        };
+        let sources = std::iter::once(normalize(&dir))
+            .chain(path_dependencies(&cargo, &dir, &root, workspace))
+            .filter_map(|path| path.strip_prefix(&root).ok().map(Path::to_path_buf))
+            .collect();
+        members.push(Member {
+            package: name.to_string(),
+            version: version.to_string(),
+            depends_on: dependency_names(&cargo),
+            sources,
+            config,
+            wasm,
+        });
+    }
+    if let Some(unknown) = manifest.members.keys().next() {
+        bail!(
+            "{} maps '{}', which is not a contract in this workspace",
+            WORKSPACE_MANIFEST_NAME,
+            unknown
+        );
+    }
+    if members.is_empty() {
+        bail!(
+            "No Soroban contracts found in the workspace at {}",
+            root.display()
+        );
+    }
+
+    let contracts: BTreeSet<String> = members.iter().map(|m| m.package.clone()).collect();
+    for member in &mut members {
+        member
+            .depends_on
+            .retain(|dep| dep != &member.package && contracts.contains(dep));
+    }
+    Ok(Workspace {
+        root,
+        manifest,
+        members: publish_order(members)?,
+    })
+}
+
+/// Compare the local build with the latest published version (`(version, wasm_hash)`)
+fn classify(local_version: &str, local_hash: &str, latest: Option<(&str, &str)>) -> MemberState {
+    match latest {
+        None => MemberState::Unpublished { published: None },
+        Some((version, hash)) if hash.eq_ignore_ascii_case(local_hash) => MemberState::UpToDate {
+            version: version.to_string(),
+        },
+        Some((version, _)) if version == local_version => MemberState::NeedsVersionBump {
+            version: version.to_string(),
+        },
+        Some((version, _)) => MemberState::Unpublished {
+            published: Some(version.to_string()),
+        },
+    }
+}
+
+async fn member_state(
+    client: &reqwest::Client,
+    api_url: &str,
+    member: &Member,
+) -> Result<MemberState> {
+    let Some(config) = &member.config else {
+        return Ok(MemberState::Unmapped);
+    };
+    if !member.wasm.is_file() {
+        return Ok(MemberState::NotBuilt);
+    }
+    let wasm = fs::read(&member.wasm)
+        .with_context(|| format!("Failed to read {}", member.wasm.display()))?;
+    let local_hash = hex::encode(Sha256::digest(&wasm));
+
+    let base = api_url.trim_end_matches('/');
+    let response = client
+        .get(format!(
+            "{}/api/contracts/{}",
+            base,
+            contract_path(&config.name)
+        ))
+        .send()
+        .await
+        .with_context(|| format!("Failed to look up {}", config.name))?;
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(MemberState::NotRegistered);
+    }
+    if !response.status().is_success() {
+        return Err(crate::api_errors::failure(
+            response,
+            format!("Failed to look up {}", config.name),
+        )
+        .await);
+    }
+    let contract: serde_json::Value = response.json().await?;
+    let id = crate::conversions::as_str(&contract["id"], "id")?;
+
+    let response = client
+        .get(format!("{}/api/contracts/{}/versions", base, id))
+        .send()
+        .await
+        .with_context(|| format!("Failed to fetch versions of {}", config.name))?;
+    if !response.status().is_success() {
+        return Err(crate::api_errors::failure(
+            response,
+            format!("Failed to fetch versions of {}", config.name),
+        )
+        .await);
+    }
+    // Newest first
+    let versions: Vec<shared::ContractVersion> = response.json().await?;
+    let latest = versions
+        .first()
+        .map(|v| (v.version.as_str(), v.wasm_hash.as_str()));
+    Ok(classify(&member.version, &local_hash, latest))
+}
+
+/// Why `changed` lists a member
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "reason", rename_all = "snake_case")]
+pub enum ChangeReason {
+    /// A file in its own directory or a local path dependency changed
+    Sources { file: PathBuf },
+    /// A workspace contract it depends on changed
+    Dependency { dependency: String },
+    /// A workspace-wide build file changed
+    Workspace { file: PathBuf },
+}
+
+impl ChangeReason {
+    fn describe(&self) -> String {
+        match self {
+            ChangeReason::Sources { file } => format!("{} changed", file.display()),
+            ChangeReason::Dependency { dependency } => format!("depends on {}", dependency),
+            ChangeReason::Workspace { file } => format!("workspace {} changed", file.display()),
+        }
+    }
+}
+
+/// Members affected by `files` (relative to the workspace root), in publish order
+fn changed_members<'a>(
+    workspace: &'a Workspace,
+    files: &[PathBuf],
+) -> Vec<(&'a Member, ChangeReason)> {
+    let touching = |dir: &Path| files.iter().find(|file| file.starts_with(dir)).cloned();
+    let build_file = files.iter().find(|file| {
+        WORKSPACE_BUILD_FILES
+            .iter()
+            .any(|name| file.as_path() == Path::new(name))
+    });
+
+    let mut changed: Vec<(&Member, ChangeReason)> = Vec::new();
+    for member in &workspace.members {
+        let dependency = member
+            .depends_on
+            .iter()
+            .find(|dep| changed.iter().any(|(m, _)| &m.package == *dep));
+        let reason = if let Some(file) = member.sources.first().and_then(|dir| touching(dir)) {
+            ChangeReason::Sources { file }
+        } else if let Some(dependency) = dependency {
+            ChangeReason::Dependency {
+                dependency: dependency.clone(),
+            }
+        } else if let Some(file) = member.sources.iter().skip(1).find_map(|dir| touching(dir)) {
+            ChangeReason::Sources { file }
+        } else if let Some(file) = build_file {
+            ChangeReason::Workspace { file: file.clone() }
+        } else {
+            continue;
+        };
+        changed.push((member, reason));
+    }
+    changed
+}
+
+fn git(root: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(root)
+        .args(args)
+        .output()
+        .context("Failed to run git; is it installed?")?;
+    if !output.status.success() {
+        bail!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Files changed since `since`, relative to `root`: committed, staged and
+/// unstaged edits under it, plus untracked files
+fn git_changed_files(root: &Path, since: &str) -> Result<Vec<PathBuf>> {
+    git(
+        root,
+        &["rev-parse", "--verify", &format!("{}^{{commit}}", since)],
+    )
+    .with_context(|| format!("'{}' is not a commit in this repository", since))?;
+    let diff = git(root, &["diff", "--name-only", "--relative", since, "--"])?;
+    let untracked = git(root, &["ls-files", "--others", "--exclude-standard"])?;
+    Ok(diff
+        .lines()
+        .chain(untracked.lines())
+        .filter(|line| !line.is_empty())
+        .map(PathBuf::from)
+        .collect())
+}
+
+#[derive(Serialize)]
+struct ChangedMember<'a> {
+    package: &'a str,
+    version: &'a str,
+    registry_name: Option<&'a str>,
+    #[serde(flatten)]
+    reason: &'a ChangeReason,
+}
+
+/// `changed --since <ref>`: workspace contracts with source changes since a git ref
+pub fn changed(since: &str, json: bool) -> Result<()> {
+    let workspace = load(&std::env::current_dir()?)?;
+    let files = git_changed_files(&workspace.root, since)?;
+    let changed = changed_members(&workspace, &files);
+
+    if json {
+        let report: Vec<ChangedMember> = changed
+            .iter()
+            .map(|(member, reason)| ChangedMember {
+                package: &member.package,
+                version: &member.version,
+                registry_name: member.config.as_ref().map(|c| c.name.as_str()),
+                reason,
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+
+    if changed.is_empty() {
+        println!("No contracts changed since {}", since);
+        return Ok(());
+    }
+    println!(
+        "\n{} of {} contracts changed since {}",
+        changed.len(),
+        workspace.members.len(),
+        since.bold()
+    );
+    for (member, reason) in &changed {
+        let name = member
+            .config
+            .as_ref()
+            .map(|c| c.name.as_str())
+            .unwrap_or("-");
+        println!(
+            "  {} {:<20} {:<24} {}",
+            "●".yellow(),
+            member.package.bold(),
+            name,
+            reason.describe().bright_black()
+        );
+    }
+    Ok(())
+}
+
+#[derive(Serialize)]
+struct MemberStatus<'a> {
+    package: &'a str,
+    version: &'a str,
+    registry_name: Option<&'a str>,
+    depends_on: &'a [String],
+    #[serde(flatten)]
+    state: &'a MemberState,
+}
+
+async fn states(api_url: &str, workspace: &Workspace) -> Result<Vec<MemberState>> {
+    let client = crate::http::client();
+    let mut states = Vec::with_capacity(workspace.members.len());
+    for member in &workspace.members {
+        states.push(member_state(&client, api_url, member).await?);
+    }
+    Ok(states)
+}
+
+/// `status`: each contract in the workspace and whether it has unpublished changes
+pub async fn status(api_url: &str, json: bool) -> Result<()> {
+    let workspace = load(&std::env::current_dir()?)?;
+    let states = states(api_url, &workspace).await?;
+
+    if json {
+        let report: Vec<MemberStatus> = workspace
+            .members
+            .iter()
+            .zip(&states)
+            .map(|(member, state)| MemberStatus {
+                package: &member.package,
+                version: &member.version,
+                registry_name: member.config.as_ref().map(|c| c.name.as_str()),
+                depends_on: &member.depends_on,
+                state,
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+
+    println!(
+        "\n{} {} ({} contracts)",
+        "Workspace".bold().cyan(),
+        workspace.root.display(),
+        workspace.members.len()
+    );
+    for (member, state) in workspace.members.iter().zip(&states) {
+        let marker = match state {
+            MemberState::UpToDate { .. } => "✓".green(),
+            MemberState::Unpublished { .. } | MemberState::NotRegistered => "●".yellow(),
+            MemberState::Unmapped => "·".bright_black(),
+            MemberState::NotBuilt | MemberState::NeedsVersionBump { .. } => "⚠".red(),
+        };
+        let name = member
+            .config
+            .as_ref()
+            .map(|c| c.name.as_str())
+            .unwrap_or("-");
+        println!(
+            "  {} {:<20} {:<10} {:<24} {}",
+            marker,
+            member.package.bold(),
+            member.version,
+            name,
+            state.describe()
+        );
+    }
+    let pending = states
+        .iter()
+        .filter(|s| {
+            matches!(
+                s,
+                MemberState::Unpublished { .. } | MemberState::NotRegistered
+            )
+        })
+        .count();
+    if pending > 0 {
+        println!(
+            "\n{} to publish; run `soroban-registry publish --all`",
+            pending
+        );
+    }
+    Ok(())
+}
+
+/// The spec to upload for `member`: its configured ABI, or one generated from the WASM
+fn abi_path(workspace: &Workspace, member: &Member, config: &MemberConfig) -> Result<PathBuf> {
+    if let Some(abi) = &config.abi {
+        return Ok(workspace.root.join(abi));
+    }
+    let json = crate::commands::load_abi_json(&member.wasm.to_string_lossy())?;
+    let dir = workspace.root.join("target").join("soroban-registry");
+    fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    let path = dir.join(format!("{}.abi.json", member.package));
+    fs::write(&path, json).with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(path)
+}
+
+/// `publish --all`: register and push every contract with unpublished changes,
+/// each after the workspace contracts it depends on. Stops at the first failure
+/// so nothing is published ahead of a dependency.
+pub async fn publish_all(
+    api_url: &str,
+    network: crate::commands::Network,
+    dry_run: bool,
+) -> Result<()> {
+    let workspace = load(&std::env::current_dir()?)?;
+    let states = states(api_url, &workspace).await?;
+
+    let mut problems = Vec::new();
+    let mut plan = Vec::new();
+    for (member, state) in workspace.members.iter().zip(&states) {
+        match state {
+            MemberState::NotBuilt | MemberState::NeedsVersionBump { .. } => {
+                problems.push(format!("{}: {}", member.package, state.describe()));
+            }
+            MemberState::NotRegistered => {
+                let config = member.config.as_ref();
+                if config.and_then(|c| c.contract_id.as_ref()).is_none()
+                    || workspace.manifest.publisher.is_none()
+                {
+                    problems.push(format!(
+                        "{}: not in the registry yet; set its contract_id and a publisher in {}",
+                        member.package, WORKSPACE_MANIFEST_NAME
+                    ));
+                }
+                plan.push((member, state));
+            }
+            MemberState::Unpublished { .. } => plan.push((member, state)),
+            MemberState::Unmapped => println!(
+                "  {} {} is not in {}; skipped",
+                "·".bright_black(),
+                member.package,
+                WORKSPACE_MANIFEST_NAME
+            ),
+            MemberState::UpToDate { .. } => {}
+        }
+    }
+    if !problems.is_empty() {
+        bail!("Cannot publish the workspace:\n  {}", problems.join("\n  "));
+    }
+    if plan.is_empty() {
+        println!("{} Every contract is up to date", "✓".green());
+        return Ok(());
+    }
+
+    println!("\n{}", "Publish order".bold().cyan());
+    for (i, (member, _)) in plan.iter().enumerate() {
+        println!("  {}. {} {}", i + 1, member.package.bold(), member.version);
+    }
+    if dry_run {
+        println!("\n{} Nothing was published (--dry-run)", "→".cyan());
+        return Ok(());
+    }
+
+    let cargo_lock = workspace.root.join("Cargo.lock");
+    for (member, state) in plan {
+        let Some(config) = &member.config else {
+            continue;
+        };
+        if *state == MemberState::NotRegistered {
+            if let (Some(contract_id), Some(publisher)) =
+                (&config.contract_id, &workspace.manifest.publisher)
+            {
+                crate::commands::publish(
+                    api_url,
+                    contract_id,
+                    &config.name,
+                    None,
+                    network,
+                    None,
+                    Vec::new(),
+                    publisher,
+                    None,
+                    None,
+                    shared::ProjectUrls::default(),
+                    None,
+                    false,
+                )
+                .await
+                .with_context(|| format!("Failed to register {}", config.name))?;
+            }
+        }
+        let parts = vec![
+            crate::push::LocalPart {
+                kind: "wasm",
+                path: member.wasm.clone(),
+            },
+            crate::push::LocalPart {
+                kind: "abi",
+                path: abi_path(&workspace, member, config)?,
+            },
+        ];
+        let toolchain = crate::toolchain::capture(&workspace.root, &member.wasm, None);
+        if let Some(toolchain) = &toolchain {
+            crate::toolchain::warn(toolchain);
+        }
+        let options = crate::push::PushOptions {
+            source_url: None,
+            commit_hash: None,
+            release_notes: None,
+            cargo_lock: cargo_lock.is_file().then(|| cargo_lock.clone()),
+            toolchain,
+        };
+        crate::push::push(
+            api_url,
+            &config.name,
+            &member.version,
+            parts,
+            options,
+            false,
+        )
+        .await
+        .with_context(|| {
+            format!(
+                "Publishing {} failed; contracts after it were not published",
+                member.package
+            )
+        })?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTRACT: &str =
+        "[lib]\ncrate-type = [\"cdylib\"]\n\n[dependencies]\nsoroban-sdk = \"22\"\n";
+
+    fn write(path: &Path, contents: &str) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+    }
+
+    fn workspace() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write(
+            &root.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"contracts/*\", \"tools\"]\n\n[workspace.package]\nversion = \"0.4.0\"\n",
+        );
+        write(
+            &root.join("contracts/token/Cargo.toml"),
+            &format!(
+                "[package]\nname = \"token\"\nversion = \"1.0.0\"\n{}",
+                CONTRACT
+            ),
+        );
+        write(
+            &root.join("contracts/amm/Cargo.toml"),
+            &format!(
+                "[package]\nname = \"amm\"\nversion.workspace = true\n{}token = {{ path = \"../token\" }}\n",
+                CONTRACT
+            ),
+        );
+        write(
+            &root.join("contracts/router/Cargo.toml"),
+            &format!(
+                "[package]\nname = \"router\"\nversion = \"2.0.0\"\n{}pool = {{ path = \"../amm\", package = \"amm\" }}\n",
+                CONTRACT
+            ),
+        );
+        write(
+            &root.join("tools/Cargo.toml"),
+            "[package]\nname = \"tools\"\nversion = \"0.1.0\"\n\n[dependencies]\nsoroban-sdk = \"22\"\n",
+        );
+        write(
+            &root.join(WORKSPACE_MANIFEST_NAME),
+            "publisher = \"GABC\"\n\n[members.amm]\nname = \"@alice/amm\"\nwasm = \"out/amm.wasm\"\n",
+        );
+        dir
+    }
+
+    #[test]
+    fn finds_contract_members_in_dependency_order() {
+        let dir = workspace();
+        let ws = load(&dir.path().join("contracts/amm")).unwrap();
+        assert_eq!(ws.root, dir.path());
+        let packages: Vec<&str> = ws.members.iter().map(|m| m.package.as_str()).collect();
+        assert_eq!(packages, ["token", "amm", "router"]);
+
+        let amm = &ws.members[1];
+        assert_eq!(amm.version, "0.4.0");
+        assert_eq!(amm.depends_on, ["token"]);
+        assert_eq!(amm.config.as_ref().unwrap().name, "@alice/amm");
+        assert_eq!(amm.wasm, dir.path().join("out/amm.wasm"));
+        assert_eq!(ws.members[2].depends_on, ["amm"]);
+        assert!(ws.members[0].config.is_none());
+        assert!(ws.members[0]
+            .wasm
+            .ends_with("target/wasm32v1-none/release/token.wasm"));
+    }
+
+    #[test]
+    fn rejects_unknown_members_and_cycles() {
+        let dir = workspace();
+        write(
+            &dir.path().join(WORKSPACE_MANIFEST_NAME),
+            "[members.tools]\nname = \"@alice/tools\"\n",
+        );
+        let err = load(dir.path()).unwrap_err();
+        assert!(err.to_string().contains("'tools'"), "{}", err);
+
+        let member = |package: &str, dep: &str| Member {
+            package: package.to_string(),
+            version: "1.0.0".to_string(),
+            depends_on: vec![dep.to_string()],
+            sources: Vec::new(),
+            config: None,
+            wasm: PathBuf::new(),
+        };
+        let err = publish_order(vec![member("a", "b"), member("b", "a")]).unwrap_err();
+        assert!(err.to_string().contains("cycle: a, b"), "{}", err);
+    }
+
+    #[test]
+    fn changes_propagate_to_dependent_contracts() {
+        let dir = workspace();
+        write(
+            &dir.path().join("contracts/router/Cargo.toml"),
+            &format!(
+                "[package]\nname = \"router\"\nversion = \"2.0.0\"\n{}amm = {{ path = \"../amm\" }}\ncommon = {{ path = \"../../libs/common\" }}\n",
+                CONTRACT
+            ),
+        );
+        let ws = load(dir.path()).unwrap();
+        let router = ws.members.iter().find(|m| m.package == "router").unwrap();
+        assert_eq!(
+            router.sources,
+            [
+                PathBuf::from("contracts/router"),
+                PathBuf::from("contracts/amm"),
+                PathBuf::from("libs/common")
+            ]
+        );
+
+        let packages = |files: &[&str]| -> Vec<(String, ChangeReason)> {
+            let files: Vec<PathBuf> = files.iter().map(PathBuf::from).collect();
+            changed_members(&ws, &files)
+                .into_iter()
+                .map(|(member, reason)| (member.package.clone(), reason))
+                .collect()
+        };
+        assert_eq!(
+            packages(&["README.md", "contracts/router/src/lib.rs"]),
+            [(
+                "router".to_string(),
+                ChangeReason::Sources {
+                    file: PathBuf::from("contracts/router/src/lib.rs")
+                }
+            )]
+        );
+        assert_eq!(
+            packages(&["contracts/token/src/lib.rs"]),
+            [
+                (
+                    "token".to_string(),
+                    ChangeReason::Sources {
+                        file: PathBuf::from("contracts/token/src/lib.rs")
+                    }
+                ),
+                (
+                    "amm".to_string(),
+                    ChangeReason::Dependency {
+                        dependency: "token".to_string()
+                    }
+                ),
+                (
+                    "router".to_string(),
+                    ChangeReason::Dependency {
+                        dependency: "amm".to_string()
+                    }
+                ),
+            ]
+        );
+        assert_eq!(packages(&["libs/common/src/lib.rs"])[0].0, "router");
+        assert_eq!(packages(&["Cargo.lock"]).len(), 3);
+        assert!(packages(&["docs/index.md", "contracts/tokenizer/x.rs"]).is_empty());
+    }
+
+    #[test]
+    fn classifies_against_the_latest_published_version() {
+        assert_eq!(
+            classify("1.0.0", "aa", None),
+            MemberState::Unpublished { published: None }
+        );
+        assert_eq!(
+            classify("1.1.0", "AA", Some(("1.0.0", "aa"))),
+            MemberState::UpToDate {
+                version: "1.0.0".to_string()
+            }
+        );
+        assert_eq!(
+            classify("1.0.0", "bb", Some(("1.0.0", "aa"))),
+            MemberState::NeedsVersionBump {
+                version: "1.0.0".to_string()
+            }
+        );
+        assert_eq!(
+            classify("1.1.0", "bb", Some(("1.0.0", "aa"))),
+            MemberState::Unpublished {
+                published: Some("1.0.0".to_string())
+            }
+        );
+    }
+}
@@ -0,0 +1,108 @@
+//! `xdr encode` / `xdr decode`: convert between JSON and base64 XDR for
+//! `ScVal` values and transaction envelopes. A contract's stored spec gives
+//! values their types, so structs and enums read as they do in the source.
+
+use std::io::Read;
+
+use anyhow::{Context, Result};
+use serde_json::Value;
+use shared::{SpecType, XdrKind};
+
+/// Where a value's type comes from: a contract function's parameter or
+/// return value, or a type named directly
+pub struct TypeContext<'a> {
+    pub contract_id: Option<&'a str>,
+    pub version: Option<&'a str>,
+    pub function: Option<&'a str>,
+    pub param: Option<&'a str>,
+    pub type_name: Option<&'a str>,
+}
+
+/// The argument itself, or stdin for `-`
+fn read_input(input: &str) -> Result<String> {
+    if input != "-" {
+        return Ok(input.to_string());
+    }
+    let mut buf = String::new();
+    std::io::stdin()
+        .read_to_string(&mut buf)
+        .context("Failed to read stdin")?;
+    Ok(buf.trim().to_string())
+}
+
+async fn load_abi(api_url: &str, context: &TypeContext<'_>) -> Result<Vec<Value>> {
+    match context.contract_id {
+        Some(contract_id) => crate::invoke::fetch_abi(api_url, contract_id, context.version).await,
+        None => Ok(Vec::new()),
+    }
+}
+
+/// The spec type `context` names; `null` (no type) when it names none
+fn resolve_type(abi: &[Value], context: &TypeContext<'_>) -> Result<Value> {
+    if let Some(name) = context.type_name {
+        return Ok(serde_json::json!({ "type": name }));
+    }
+    match context.function {
+        Some(function) => shared::function_type(abi, function, context.param)
+            .cloned()
+            .with_context(|| match context.param {
+                Some(param) => format!("{} has no parameter {}", function, param),
+                None => format!(
+                    "{} is not a function of the contract, or returns nothing",
+                    function
+                ),
+            }),
+        None => Ok(Value::Null),
+    }
+}
+
+/// `xdr encode <json>`: prints base64 XDR
+pub async fn encode(
+    api_url: &str,
+    input: &str,
+    kind: XdrKind,
+    context: &TypeContext<'_>,
+) -> Result<()> {
+    let json: Value =
+        serde_json::from_str(&read_input(input)?).context("Input is not valid JSON")?;
+    let encoded = match kind {
+        XdrKind::ScVal => {
+            let abi = load_abi(api_url, context).await?;
+            let ty = resolve_type(&abi, context)?;
+            let value = shared::json_to_scval(&json, SpecType::new(&ty, &abi))
+                .map_err(|reason| anyhow::anyhow!("Cannot encode value: {}", reason))?;
+            shared::scval_to_xdr(&value)
+        }
+        XdrKind::Transaction => {
+            shared::transaction_from_json(&json).and_then(|tx| shared::transaction_to_xdr(&tx))
+        }
+    }
+    .map_err(anyhow::Error::msg)?;
+    println!("{}", encoded);
+    Ok(())
+}
+
+/// `xdr decode <base64>`: prints JSON
+pub async fn decode(
+    api_url: &str,
+    input: &str,
+    kind: XdrKind,
+    context: &TypeContext<'_>,
+) -> Result<()> {
+    let input = read_input(input)?;
+    let abi = load_abi(api_url, context).await?;
+    let decoded = match kind {
+        XdrKind::ScVal => {
+            let value = shared::scval_from_xdr(&input).map_err(anyhow::Error::msg)?;
+            let ty = resolve_type(&abi, context)?;
+            shared::scval_to_typed_json(&value, SpecType::new(&ty, &abi))
+        }
+        XdrKind::Transaction => {
+            let envelope = shared::transaction_from_xdr(&input).map_err(anyhow::Error::msg)?;
+            let abi = context.contract_id.map(|_| abi.as_slice());
+            shared::transaction_to_json(&envelope, abi)
+        }
+    };
+    println!("{}", serde_json::to_string_pretty(&decoded)?);
+    Ok(())
+}
@@ -0,0 +1,4 @@
+-- Well-known interface fingerprints matched at publish time (e.g. 'sep41')
+ALTER TABLE contracts ADD COLUMN implements TEXT[] NOT NULL DEFAULT '{}';
+
+CREATE INDEX idx_contracts_implements ON contracts USING GIN (implements);
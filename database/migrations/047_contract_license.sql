@@ -0,0 +1,5 @@
+-- SPDX license metadata for published contracts
+ALTER TABLE contracts ADD COLUMN license VARCHAR(255);
+ALTER TABLE contracts ADD COLUMN license_text TEXT;
+
+CREATE INDEX idx_contracts_license ON contracts(LOWER(license));
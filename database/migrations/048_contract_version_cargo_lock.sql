@@ -0,0 +1,2 @@
+-- Cargo.lock uploaded with each version, used to generate SBOMs
+ALTER TABLE contract_versions ADD COLUMN cargo_lock TEXT;
@@ -0,0 +1,18 @@
+-- SLSA-style build provenance attestations for published contract versions
+CREATE TABLE contract_provenance (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    contract_id UUID NOT NULL REFERENCES contracts(id) ON DELETE CASCADE,
+    version VARCHAR(50) NOT NULL,
+    subject_digest VARCHAR(64) NOT NULL,
+    builder_id TEXT NOT NULL,
+    source_repo TEXT NOT NULL,
+    source_commit VARCHAR(64) NOT NULL,
+    build_parameters JSONB NOT NULL DEFAULT '{}',
+    statement JSONB NOT NULL,
+    signature TEXT,
+    public_key TEXT,
+    verified BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_contract_provenance_version ON contract_provenance(contract_id, version);
@@ -0,0 +1,2 @@
+-- Keyset ordering used by the NDJSON catalog stream
+CREATE INDEX IF NOT EXISTS idx_contracts_updated_at_id ON contracts(updated_at, id);
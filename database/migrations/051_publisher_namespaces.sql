@@ -0,0 +1,10 @@
+-- Publisher-scoped contract names (@namespace/name)
+ALTER TABLE publishers ADD COLUMN namespace VARCHAR(39);
+ALTER TABLE publishers ADD CONSTRAINT publishers_namespace_key UNIQUE (namespace);
+
+ALTER TABLE contracts ADD COLUMN namespace VARCHAR(39);
+
+-- Names are unique per namespace and network; unscoped (legacy) names are not constrained
+CREATE UNIQUE INDEX idx_contracts_namespace_name_network
+    ON contracts(namespace, name, network)
+    WHERE namespace IS NOT NULL;
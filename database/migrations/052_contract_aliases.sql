@@ -0,0 +1,12 @@
+-- Former names of renamed contracts, kept so lookups by the old name still resolve
+CREATE TABLE contract_aliases (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    contract_id UUID NOT NULL REFERENCES contracts(id) ON DELETE CASCADE,
+    namespace VARCHAR(39),
+    name TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE UNIQUE INDEX idx_contract_aliases_name
+    ON contract_aliases((COALESCE(namespace, '')), name);
+CREATE INDEX idx_contract_aliases_contract_id ON contract_aliases(contract_id);
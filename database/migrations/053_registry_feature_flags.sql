@@ -0,0 +1,26 @@
+-- Registry-wide feature flags, toggled at runtime by operators
+CREATE TABLE registry_feature_flags (
+    name VARCHAR(64) PRIMARY KEY,
+    description TEXT,
+    enabled BOOLEAN NOT NULL DEFAULT FALSE,
+    rollout_percentage SMALLINT NOT NULL DEFAULT 100
+        CHECK (rollout_percentage BETWEEN 0 AND 100),
+    -- Environments the flag applies to; empty means every environment
+    environments TEXT[] NOT NULL DEFAULT '{}',
+    updated_by VARCHAR(255),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Every change to a flag, with the state before and after
+CREATE TABLE registry_feature_flag_audit (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    flag_name VARCHAR(64) NOT NULL,
+    changed_by VARCHAR(255) NOT NULL,
+    previous JSONB,
+    current JSONB NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_registry_feature_flag_audit_flag
+    ON registry_feature_flag_audit(flag_name, created_at DESC);
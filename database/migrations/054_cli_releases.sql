@@ -0,0 +1,16 @@
+-- Published CLI binaries, one row per version and target triple
+CREATE TABLE cli_releases (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    version VARCHAR(50) NOT NULL,
+    target VARCHAR(100) NOT NULL,
+    download_url TEXT NOT NULL,
+    -- Hex SHA-256 of the binary
+    sha256 VARCHAR(64) NOT NULL,
+    -- Base64 ed25519 signature over the raw SHA-256 digest
+    signature TEXT NOT NULL,
+    release_notes TEXT,
+    published_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (version, target)
+);
+
+CREATE INDEX idx_cli_releases_target ON cli_releases(target, published_at DESC);
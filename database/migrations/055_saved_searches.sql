@@ -0,0 +1,29 @@
+-- Search queries saved by signed-in users, re-run against every newly published contract
+CREATE TABLE saved_searches (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    -- Account the search belongs to (JWT subject)
+    owner_address VARCHAR(64) NOT NULL,
+    name VARCHAR(64) NOT NULL,
+    query TEXT NOT NULL,
+    network network_type,
+    category VARCHAR(100),
+    -- Delivery targets; matches are always recorded for polling
+    webhook_url TEXT,
+    email TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (owner_address, name)
+);
+
+-- One row per contract that matched a saved search, doubling as its event feed
+CREATE TABLE saved_search_matches (
+    id BIGSERIAL PRIMARY KEY,
+    saved_search_id UUID NOT NULL REFERENCES saved_searches(id) ON DELETE CASCADE,
+    contract_id UUID NOT NULL REFERENCES contracts(id) ON DELETE CASCADE,
+    -- 'delivered' or 'failed' once a webhook has been attempted; NULL without a webhook
+    webhook_status VARCHAR(20),
+    matched_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (saved_search_id, contract_id)
+);
+
+CREATE INDEX idx_saved_search_matches_search
+    ON saved_search_matches(saved_search_id, id DESC);
@@ -0,0 +1,20 @@
+-- Tie reviews to the signed-in account (JWT subject) instead of an unused user UUID,
+-- allow one review per account per contract, and make ratings whole stars.
+ALTER TABLE reviews ADD COLUMN reviewer_address VARCHAR(64);
+ALTER TABLE reviews ALTER COLUMN user_id DROP NOT NULL;
+ALTER TABLE reviews ALTER COLUMN version DROP NOT NULL;
+ALTER TABLE reviews ALTER COLUMN rating TYPE SMALLINT USING ROUND(rating)::SMALLINT;
+
+CREATE UNIQUE INDEX idx_reviews_contract_reviewer
+    ON reviews(contract_id, reviewer_address)
+    WHERE reviewer_address IS NOT NULL;
+CREATE INDEX idx_reviews_contract_visible ON reviews(contract_id) WHERE NOT is_flagged;
+
+-- Abuse reports; is_flagged on the review means a moderator has hidden it
+ALTER TABLE review_flags ADD COLUMN reporter_address VARCHAR(64);
+ALTER TABLE review_flags ALTER COLUMN user_id DROP NOT NULL;
+
+CREATE UNIQUE INDEX idx_review_flags_reporter
+    ON review_flags(review_id, reporter_address)
+    WHERE reporter_address IS NOT NULL;
+CREATE INDEX idx_review_flags_open ON review_flags(review_id) WHERE NOT resolved;
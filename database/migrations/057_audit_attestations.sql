@@ -0,0 +1,26 @@
+-- Formal (human) security audit reports attached to contracts, as provenance
+-- alongside the automated checks. Findings are the auditor's own counts.
+CREATE TABLE audit_attestations (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    contract_id UUID NOT NULL REFERENCES contracts(id) ON DELETE CASCADE,
+    -- Audited release; NULL when the report covers the contract as a whole
+    version VARCHAR(50),
+    auditor_name VARCHAR(255) NOT NULL,
+    auditor_url TEXT,
+    -- Link to the PDF or web report, with an optional digest of the PDF
+    report_url TEXT NOT NULL,
+    report_sha256 CHAR(64),
+    scope TEXT NOT NULL,
+    findings_summary TEXT,
+    critical_findings INTEGER NOT NULL DEFAULT 0 CHECK (critical_findings >= 0),
+    high_findings INTEGER NOT NULL DEFAULT 0 CHECK (high_findings >= 0),
+    medium_findings INTEGER NOT NULL DEFAULT 0 CHECK (medium_findings >= 0),
+    low_findings INTEGER NOT NULL DEFAULT 0 CHECK (low_findings >= 0),
+    audited_at DATE NOT NULL,
+    -- Account (JWT subject) that attached the report
+    submitted_by VARCHAR(64) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_audit_attestations_contract
+    ON audit_attestations(contract_id, audited_at DESC);
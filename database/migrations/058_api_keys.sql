@@ -0,0 +1,20 @@
+-- Long-lived, scoped API keys minted by a signed-in account. Only a SHA-256 of
+-- the key is stored; the plaintext is shown once at creation/rotation.
+CREATE TABLE api_keys (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    owner_address VARCHAR(64) NOT NULL,
+    name VARCHAR(64) NOT NULL,
+    -- Leading characters of the key, for recognising it in listings
+    prefix VARCHAR(16) NOT NULL,
+    key_hash CHAR(64) NOT NULL UNIQUE,
+    -- Any of 'read', 'publish', 'patch-admin'
+    scopes TEXT[] NOT NULL,
+    expires_at TIMESTAMPTZ,
+    last_used_at TIMESTAMPTZ,
+    revoked_at TIMESTAMPTZ,
+    -- Key this one replaced via rotation
+    rotated_from UUID REFERENCES api_keys(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_api_keys_owner ON api_keys(owner_address) WHERE revoked_at IS NULL;
@@ -0,0 +1,13 @@
+-- Two-person rule for critical patches: past the unapproved rollout limit,
+-- `patch apply` needs a sign-off from a maintainer other than the creator.
+ALTER TABLE security_patches ADD COLUMN created_by VARCHAR(64);
+
+CREATE TABLE patch_approvals (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    patch_id UUID NOT NULL REFERENCES security_patches(id) ON DELETE CASCADE,
+    approver_address VARCHAR(64) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE(patch_id, approver_address)
+);
+
+CREATE INDEX idx_patch_approvals_patch_id ON patch_approvals(patch_id);
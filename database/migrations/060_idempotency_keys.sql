@@ -0,0 +1,18 @@
+-- Stored responses for requests sent with an `Idempotency-Key` header, so a
+-- retried publish/patch/migration returns the original result. Rows older
+-- than 24 hours are ignored and purged by the hourly aggregation task.
+CREATE TABLE idempotency_keys (
+    idempotency_key VARCHAR(255) NOT NULL,
+    -- SHA-256 of the Authorization header, so keys are per caller
+    caller_hash CHAR(64) NOT NULL,
+    -- SHA-256 of method, path and body; a reused key with a different request is rejected
+    request_hash CHAR(64) NOT NULL,
+    -- NULL while the original request is still running
+    status_code SMALLINT,
+    content_type TEXT,
+    response_body BYTEA,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (idempotency_key, caller_hash)
+);
+
+CREATE INDEX idx_idempotency_keys_created_at ON idempotency_keys(created_at);
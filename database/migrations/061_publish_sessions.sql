@@ -0,0 +1,42 @@
+-- Staged publishing: artifacts are uploaded to a session one by one, then a
+-- commit creates the version in a single transaction (or a cancel drops them).
+CREATE TABLE publish_sessions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    contract_id UUID NOT NULL REFERENCES contracts(id) ON DELETE CASCADE,
+    version VARCHAR(50) NOT NULL,
+    owner_address VARCHAR(64) NOT NULL,
+    -- open -> committing -> committed, or open -> cancelled
+    status VARCHAR(16) NOT NULL DEFAULT 'open'
+        CHECK (status IN ('open', 'committing', 'committed', 'cancelled')),
+    source_url TEXT,
+    commit_hash VARCHAR(64),
+    release_notes TEXT,
+    cargo_lock TEXT,
+    version_id UUID REFERENCES contract_versions(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    expires_at TIMESTAMPTZ NOT NULL DEFAULT NOW() + INTERVAL '24 hours'
+);
+
+-- At most one live session per account, contract and version, so a rerun resumes it
+CREATE UNIQUE INDEX idx_publish_sessions_open
+    ON publish_sessions(contract_id, version, owner_address)
+    WHERE status IN ('open', 'committing');
+
+CREATE TABLE publish_session_parts (
+    session_id UUID NOT NULL REFERENCES publish_sessions(id) ON DELETE CASCADE,
+    kind VARCHAR(16) NOT NULL CHECK (kind IN ('wasm', 'readme', 'abi', 'signature')),
+    content BYTEA NOT NULL,
+    sha256 CHAR(64) NOT NULL,
+    uploaded_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (session_id, kind)
+);
+
+-- Files kept with a published version (the WASM and README from its session)
+CREATE TABLE contract_version_artifacts (
+    version_id UUID NOT NULL REFERENCES contract_versions(id) ON DELETE CASCADE,
+    kind VARCHAR(16) NOT NULL,
+    content BYTEA NOT NULL,
+    sha256 CHAR(64) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (version_id, kind)
+);
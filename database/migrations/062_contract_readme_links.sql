@@ -0,0 +1,9 @@
+-- Post-publish metadata: project links on the contract and a contract-level
+-- README that can be edited without publishing a new version.
+ALTER TABLE contracts ADD COLUMN links JSONB NOT NULL DEFAULT '{}'::jsonb;
+
+CREATE TABLE contract_readmes (
+    contract_id UUID PRIMARY KEY REFERENCES contracts(id) ON DELETE CASCADE,
+    content TEXT NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,5 @@
+-- Repository, homepage and documentation links shown on a contract's page
+ALTER TABLE contracts
+    ADD COLUMN repository_url TEXT,
+    ADD COLUMN homepage TEXT,
+    ADD COLUMN documentation_url TEXT;
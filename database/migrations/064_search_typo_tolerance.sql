@@ -0,0 +1,23 @@
+-- Typo-tolerant search: trigram indexes for fuzzy name/description matching
+-- and a vocabulary of indexed words for "did you mean" suggestions.
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+CREATE INDEX IF NOT EXISTS idx_contracts_name_trgm
+    ON contracts USING gin (name gin_trgm_ops);
+CREATE INDEX IF NOT EXISTS idx_contracts_description_trgm
+    ON contracts USING gin (description gin_trgm_ops);
+
+-- Refreshed hourly by the aggregation task
+CREATE MATERIALIZED VIEW contract_search_terms AS
+SELECT DISTINCT term
+FROM contracts c,
+     LATERAL regexp_split_to_table(
+         LOWER(c.name || ' ' || COALESCE(c.category, '') || ' ' ||
+               array_to_string(c.tags, ' ') || ' ' || COALESCE(c.description, '')),
+         '[^a-z0-9]+'
+     ) AS term
+WHERE length(term) >= 3;
+
+CREATE UNIQUE INDEX idx_contract_search_terms_term ON contract_search_terms (term);
+CREATE INDEX idx_contract_search_terms_trgm
+    ON contract_search_terms USING gin (term gin_trgm_ops);
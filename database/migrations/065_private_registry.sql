@@ -0,0 +1,42 @@
+-- Private registries: organizations own private contracts, which only their
+-- members can see (in search, info, ABI, archives and streams).
+CREATE TABLE organizations (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name VARCHAR(64) NOT NULL UNIQUE,
+    created_by VARCHAR(64) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE organization_members (
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    address VARCHAR(64) NOT NULL,
+    role VARCHAR(16) NOT NULL DEFAULT 'member' CHECK (role IN ('owner', 'member')),
+    added_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (organization_id, address)
+);
+
+CREATE INDEX idx_organization_members_address ON organization_members(address);
+
+-- A private contract whose organization is deleted stays hidden from everyone
+ALTER TABLE contracts
+    ADD COLUMN organization_id UUID REFERENCES organizations(id) ON DELETE SET NULL,
+    ADD COLUMN is_private BOOLEAN NOT NULL DEFAULT FALSE;
+
+CREATE INDEX idx_contracts_private_org ON contracts(organization_id) WHERE is_private;
+
+-- "Did you mean" suggestions must not reveal words from private contracts
+DROP MATERIALIZED VIEW contract_search_terms;
+
+CREATE MATERIALIZED VIEW contract_search_terms AS
+SELECT DISTINCT term
+FROM contracts c,
+     LATERAL regexp_split_to_table(
+         LOWER(c.name || ' ' || COALESCE(c.category, '') || ' ' ||
+               array_to_string(c.tags, ' ') || ' ' || COALESCE(c.description, '')),
+         '[^a-z0-9]+'
+     ) AS term
+WHERE length(term) >= 3 AND NOT c.is_private;
+
+CREATE UNIQUE INDEX idx_contract_search_terms_term ON contract_search_terms (term);
+CREATE INDEX idx_contract_search_terms_trgm
+    ON contract_search_terms USING gin (term gin_trgm_ops);
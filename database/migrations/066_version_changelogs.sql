@@ -0,0 +1,11 @@
+-- Changelogs generated when a version is published: the interface diff against
+-- the previous version, changed version metadata and the WASM size delta.
+CREATE TABLE contract_version_changelogs (
+    version_id UUID PRIMARY KEY REFERENCES contract_versions(id) ON DELETE CASCADE,
+    previous_version VARCHAR(50),
+    interface_changes JSONB NOT NULL DEFAULT '[]',
+    metadata_changes JSONB NOT NULL DEFAULT '[]',
+    wasm_size_bytes BIGINT,
+    wasm_size_delta BIGINT,
+    generated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,14 @@
+-- Anonymous CLI usage metrics sent by users who opted in with
+-- `soroban-registry telemetry enable`. Rows carry no user, address or
+-- argument data and are purged after 90 days.
+CREATE TABLE cli_telemetry_events (
+    id BIGSERIAL PRIMARY KEY,
+    command VARCHAR(64) NOT NULL,
+    duration_ms BIGINT NOT NULL,
+    success BOOLEAN NOT NULL,
+    cli_version VARCHAR(32) NOT NULL,
+    recorded_at TIMESTAMPTZ NOT NULL,
+    received_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_cli_telemetry_events_received_at ON cli_telemetry_events (received_at);
@@ -0,0 +1,13 @@
+-- Daily registry statistics for GET /api/stats/timeseries. Totals are as of the
+-- end of the day; the other columns count what happened during it. Written by
+-- the hourly aggregation job; days before the first run are backfilled from
+-- creation timestamps.
+CREATE TABLE stats_daily (
+    date DATE PRIMARY KEY,
+    contracts BIGINT NOT NULL DEFAULT 0,
+    publishers BIGINT NOT NULL DEFAULT 0,
+    publishes BIGINT NOT NULL DEFAULT 0,
+    versions BIGINT NOT NULL DEFAULT 0,
+    downloads BIGINT NOT NULL DEFAULT 0,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,16 @@
+-- Test run results attached to a version at publish time. The uploaded report
+-- (JUnit XML or a JSON summary) is kept as the version's `tests` artifact; the
+-- counts parsed from it are stored here for listings and CI dashboards.
+ALTER TABLE publish_session_parts DROP CONSTRAINT publish_session_parts_kind_check;
+ALTER TABLE publish_session_parts ADD CONSTRAINT publish_session_parts_kind_check
+    CHECK (kind IN ('wasm', 'readme', 'abi', 'signature', 'tests'));
+
+CREATE TABLE version_test_results (
+    version_id UUID PRIMARY KEY REFERENCES contract_versions(id) ON DELETE CASCADE,
+    format VARCHAR(8) NOT NULL CHECK (format IN ('junit', 'json')),
+    passed INTEGER NOT NULL CHECK (passed >= 0),
+    failed INTEGER NOT NULL CHECK (failed >= 0),
+    skipped INTEGER NOT NULL CHECK (skipped >= 0),
+    coverage_percent DOUBLE PRECISION CHECK (coverage_percent BETWEEN 0 AND 100),
+    recorded_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
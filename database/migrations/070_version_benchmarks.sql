@@ -0,0 +1,18 @@
+-- Performance budgets declared in a version's `budgets` part are benchmarked in
+-- the simulation sandbox at publish time. One row per benchmarked function, so
+-- the next version can be compared against these measurements.
+ALTER TABLE publish_session_parts DROP CONSTRAINT publish_session_parts_kind_check;
+ALTER TABLE publish_session_parts ADD CONSTRAINT publish_session_parts_kind_check
+    CHECK (kind IN ('wasm', 'readme', 'abi', 'signature', 'tests', 'budgets'));
+
+CREATE TABLE version_benchmarks (
+    version_id UUID NOT NULL REFERENCES contract_versions(id) ON DELETE CASCADE,
+    function VARCHAR(64) NOT NULL,
+    success BOOLEAN NOT NULL,
+    cpu_instructions BIGINT NOT NULL CHECK (cpu_instructions >= 0),
+    memory_bytes BIGINT NOT NULL CHECK (memory_bytes >= 0),
+    max_cpu_instructions BIGINT,
+    max_memory_bytes BIGINT,
+    recorded_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (version_id, function)
+);
@@ -0,0 +1,5 @@
+-- Contracts based on another registry contract record it at publish, so copied
+-- AMM and token code can be traced back to where it came from.
+ALTER TABLE contracts ADD COLUMN forked_from UUID REFERENCES contracts(id) ON DELETE SET NULL;
+
+CREATE INDEX idx_contracts_forked_from ON contracts(forked_from) WHERE forked_from IS NOT NULL;
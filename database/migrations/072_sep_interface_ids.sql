@@ -0,0 +1,4 @@
+-- Interface ids follow the SEP spelling ("sep-41"). Specs are re-matched, and
+-- newly added interfaces detected, the next time a version is published.
+UPDATE contracts SET implements = array_replace(implements, 'sep41', 'sep-41')
+WHERE 'sep41' = ANY(implements);
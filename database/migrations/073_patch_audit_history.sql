@@ -0,0 +1,6 @@
+-- Who applied each patch and where in the rollout it landed, so a contract's
+-- patch history can be shown to consumers. Rows recorded before this are NULL.
+ALTER TABLE patch_audits
+    ADD COLUMN applied_by VARCHAR(64),
+    ADD COLUMN rollout_position INTEGER,
+    ADD COLUMN eligible_contracts INTEGER;
@@ -0,0 +1,3 @@
+-- target_version now holds a SemVer range such as ">=1.0.0, <1.4.2"; existing
+-- exact versions remain valid ranges.
+ALTER TABLE security_patches ALTER COLUMN target_version TYPE VARCHAR(100);
@@ -0,0 +1,34 @@
+-- Logical registries hosted in one deployment. Existing rows, and requests that
+-- name no tenant, belong to the default "public" registry.
+CREATE TABLE tenants (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    slug VARCHAR(32) NOT NULL UNIQUE,
+    name VARCHAR(128) NOT NULL,
+    -- Hostnames routed to this tenant, e.g. registry.acme.internal
+    hosts TEXT[] NOT NULL DEFAULT '{}',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_tenants_hosts ON tenants USING GIN (hosts);
+
+INSERT INTO tenants (id, slug, name)
+VALUES ('00000000-0000-0000-0000-000000000000', 'public', 'Public registry');
+
+-- Top-level records carry their tenant; versions, artifacts, reviews and other
+-- per-contract rows belong to the tenant of their contract.
+ALTER TABLE contracts
+    ADD COLUMN tenant_id UUID NOT NULL DEFAULT '00000000-0000-0000-0000-000000000000'
+    REFERENCES tenants(id);
+ALTER TABLE organizations
+    ADD COLUMN tenant_id UUID NOT NULL DEFAULT '00000000-0000-0000-0000-000000000000'
+    REFERENCES tenants(id);
+ALTER TABLE api_keys
+    ADD COLUMN tenant_id UUID NOT NULL DEFAULT '00000000-0000-0000-0000-000000000000'
+    REFERENCES tenants(id);
+ALTER TABLE security_patches
+    ADD COLUMN tenant_id UUID NOT NULL DEFAULT '00000000-0000-0000-0000-000000000000'
+    REFERENCES tenants(id);
+
+CREATE INDEX idx_contracts_tenant_id ON contracts(tenant_id);
+CREATE INDEX idx_organizations_tenant_id ON organizations(tenant_id);
+CREATE INDEX idx_security_patches_tenant_id ON security_patches(tenant_id);
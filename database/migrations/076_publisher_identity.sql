@@ -0,0 +1,13 @@
+-- Publishers prove control of their Stellar account by signing a challenge
+-- with the account key; the verified account is shown as a badge.
+ALTER TABLE publishers
+    ADD COLUMN verified_account VARCHAR(56),
+    ADD COLUMN identity_verified_at TIMESTAMPTZ;
+
+-- One outstanding challenge per publisher; a new request replaces it and a
+-- successful verification consumes it.
+CREATE TABLE publisher_identity_challenges (
+    publisher_id UUID PRIMARY KEY REFERENCES publishers(id) ON DELETE CASCADE,
+    nonce VARCHAR(64) NOT NULL,
+    expires_at TIMESTAMPTZ NOT NULL
+);
@@ -0,0 +1,14 @@
+-- Artifacts are also served by content hash from GET /api/artifacts/:sha256
+CREATE INDEX idx_contract_version_artifacts_sha256 ON contract_version_artifacts(sha256);
+
+-- Every distinct archive served for a contract, so its hash stays fetchable.
+-- A version's archive changes when the contract's metadata or README does.
+CREATE TABLE contract_archives (
+    sha256 CHAR(64) PRIMARY KEY,
+    contract_id UUID NOT NULL REFERENCES contracts(id) ON DELETE CASCADE,
+    version_id UUID REFERENCES contract_versions(id) ON DELETE CASCADE,
+    content BYTEA NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_contract_archives_version ON contract_archives(version_id, created_at DESC);
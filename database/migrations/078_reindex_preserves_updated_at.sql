@@ -0,0 +1,13 @@
+-- Reindex jobs rewrite contract rows to regenerate derived columns (search
+-- vectors, implemented interfaces). Those rows have not really changed, so a
+-- transaction can set registry.preserve_updated_at to keep their timestamps.
+CREATE OR REPLACE FUNCTION update_updated_at_column()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF current_setting('registry.preserve_updated_at', true) = 'on' THEN
+        RETURN NEW;
+    END IF;
+    NEW.updated_at = NOW();
+    RETURN NEW;
+END;
+$$ language 'plpgsql';
@@ -0,0 +1,17 @@
+-- Progress of resumable backfill jobs over legacy rows. `checkpoint` is the
+-- last row a job finished, so an interrupted or paused run picks up after it.
+CREATE TYPE backfill_state AS ENUM ('running', 'paused', 'completed', 'failed');
+
+CREATE TABLE backfill_jobs (
+    name VARCHAR(64) PRIMARY KEY,
+    state backfill_state NOT NULL,
+    checkpoint UUID,
+    processed BIGINT NOT NULL DEFAULT 0,
+    updated BIGINT NOT NULL DEFAULT 0,
+    failed BIGINT NOT NULL DEFAULT 0,
+    last_error TEXT,
+    started_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    -- Bumped after every batch; a running job that stops bumping it has died
+    heartbeat_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    completed_at TIMESTAMPTZ
+);
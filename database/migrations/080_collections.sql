@@ -0,0 +1,30 @@
+-- Curated, ordered lists of contracts ("DeFi starter kit", "audited oracles")
+-- kept by signed-in users. Private collections are only visible to their owner.
+CREATE TABLE collections (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    tenant_id UUID NOT NULL DEFAULT '00000000-0000-0000-0000-000000000000'
+        REFERENCES tenants(id),
+    -- Account the collection belongs to (JWT subject)
+    owner_address VARCHAR(64) NOT NULL,
+    name VARCHAR(64) NOT NULL,
+    description TEXT,
+    is_public BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (tenant_id, owner_address, name)
+);
+
+CREATE INDEX idx_collections_public ON collections(tenant_id, updated_at DESC)
+    WHERE is_public;
+
+CREATE TABLE collection_items (
+    collection_id UUID NOT NULL REFERENCES collections(id) ON DELETE CASCADE,
+    contract_id UUID NOT NULL REFERENCES contracts(id) ON DELETE CASCADE,
+    -- 0-based; rewritten as a whole when the list is reordered
+    position INTEGER NOT NULL,
+    note TEXT,
+    added_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (collection_id, contract_id)
+);
+
+CREATE INDEX idx_collection_items_order ON collection_items(collection_id, position);
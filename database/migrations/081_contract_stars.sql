@@ -0,0 +1,35 @@
+-- Stars (bookmarks) users put on contracts. `contracts.star_count` is kept in
+-- step by trigger so listings can show and sort by it without a join.
+CREATE TABLE contract_stars (
+    contract_id UUID NOT NULL REFERENCES contracts(id) ON DELETE CASCADE,
+    -- Account that starred the contract (JWT subject)
+    user_address VARCHAR(64) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (contract_id, user_address)
+);
+
+CREATE INDEX idx_contract_stars_user ON contract_stars(user_address, created_at DESC);
+
+ALTER TABLE contracts ADD COLUMN star_count BIGINT NOT NULL DEFAULT 0;
+
+CREATE INDEX idx_contracts_star_count ON contracts(star_count DESC);
+
+-- A star is not a change to the contract, so updated_at is left alone
+CREATE OR REPLACE FUNCTION update_contract_star_count()
+RETURNS TRIGGER AS $$
+DECLARE
+    preserve TEXT := COALESCE(current_setting('registry.preserve_updated_at', true), '');
+BEGIN
+    PERFORM set_config('registry.preserve_updated_at', 'on', true);
+    IF TG_OP = 'INSERT' THEN
+        UPDATE contracts SET star_count = star_count + 1 WHERE id = NEW.contract_id;
+    ELSE
+        UPDATE contracts SET star_count = GREATEST(star_count - 1, 0) WHERE id = OLD.contract_id;
+    END IF;
+    PERFORM set_config('registry.preserve_updated_at', preserve, true);
+    RETURN NULL;
+END;
+$$ language 'plpgsql';
+
+CREATE TRIGGER contract_stars_count AFTER INSERT OR DELETE ON contract_stars
+    FOR EACH ROW EXECUTE FUNCTION update_contract_star_count();
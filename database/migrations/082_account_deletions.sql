@@ -0,0 +1,13 @@
+-- Accounts scheduled for deletion. Their data stays until `scheduled_for` so
+-- the request can be cancelled; an hourly job then removes it.
+CREATE TABLE account_deletions (
+    tenant_id UUID NOT NULL DEFAULT '00000000-0000-0000-0000-000000000000'
+        REFERENCES tenants(id),
+    -- Account being deleted (JWT subject)
+    address VARCHAR(64) NOT NULL,
+    requested_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    scheduled_for TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (tenant_id, address)
+);
+
+CREATE INDEX idx_account_deletions_due ON account_deletions(scheduled_for);
@@ -0,0 +1,22 @@
+-- Daily per-token API usage behind GET /api/me/usage. Rows are upserted from
+-- in-memory counters about once a minute and kept for 30 days.
+CREATE TABLE api_usage (
+    tenant_id UUID NOT NULL DEFAULT '00000000-0000-0000-0000-000000000000'
+        REFERENCES tenants(id),
+    owner_address VARCHAR(64) NOT NULL,
+    -- NULL for requests made with a session token
+    api_key_id UUID REFERENCES api_keys(id) ON DELETE CASCADE,
+    -- Method and route pattern, e.g. `GET /api/contracts/:id`
+    endpoint VARCHAR(255) NOT NULL,
+    day DATE NOT NULL,
+    requests BIGINT NOT NULL DEFAULT 0,
+    -- 4xx/5xx responses other than 429
+    errors BIGINT NOT NULL DEFAULT 0,
+    rate_limited BIGINT NOT NULL DEFAULT 0,
+    -- Per-window limit last applied, and the most requests seen in one window
+    rate_limit INTEGER,
+    peak_window_usage INTEGER,
+    UNIQUE NULLS NOT DISTINCT (tenant_id, owner_address, api_key_id, endpoint, day)
+);
+
+CREATE INDEX idx_api_usage_owner_day ON api_usage(tenant_id, owner_address, day);
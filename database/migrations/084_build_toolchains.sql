@@ -0,0 +1,4 @@
+-- Toolchain fingerprint captured by the CLI on publish (rustc, soroban-sdk,
+-- target, profile flags), served by GET /api/contracts/:id/toolchain
+ALTER TABLE contract_versions ADD COLUMN build_toolchain JSONB;
+ALTER TABLE publish_sessions ADD COLUMN build_toolchain JSONB;
@@ -0,0 +1,49 @@
+-- Advisories disclosed against a contract's versions. Creating one, or a
+-- critical security patch, alerts the owners of every contract depending on an
+-- affected version (GET /api/me/alerts).
+CREATE TABLE security_advisories (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    contract_id UUID NOT NULL REFERENCES contracts(id) ON DELETE CASCADE,
+    affected_versions VARCHAR(100) NOT NULL,
+    severity patch_severity NOT NULL,
+    title VARCHAR(255) NOT NULL,
+    description TEXT,
+    patched_version VARCHAR(50),
+    created_by VARCHAR(64),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_security_advisories_contract ON security_advisories(contract_id, created_at);
+
+CREATE TYPE alert_source AS ENUM ('advisory', 'patch');
+
+CREATE TABLE dependency_alerts (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    owner_address VARCHAR(64) NOT NULL,
+    source alert_source NOT NULL,
+    -- security_advisories.id or security_patches.id
+    source_id UUID NOT NULL,
+    severity patch_severity NOT NULL,
+    summary TEXT NOT NULL,
+    vulnerable_contract_id UUID NOT NULL REFERENCES contracts(id) ON DELETE CASCADE,
+    affected_versions TEXT[] NOT NULL,
+    contract_id UUID NOT NULL REFERENCES contracts(id) ON DELETE CASCADE,
+    -- Contract IDs from the vulnerable contract to the alerted one
+    dependency_path UUID[] NOT NULL,
+    version_constraint VARCHAR(100) NOT NULL,
+    deployments JSONB NOT NULL DEFAULT '[]',
+    webhook_status VARCHAR(20),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    acknowledged_at TIMESTAMPTZ,
+    UNIQUE (source_id, vulnerable_contract_id, contract_id)
+);
+
+CREATE INDEX idx_dependency_alerts_owner ON dependency_alerts(owner_address, created_at DESC);
+
+-- Per-account delivery targets for alerts
+CREATE TABLE alert_settings (
+    owner_address VARCHAR(64) PRIMARY KEY,
+    webhook_url TEXT,
+    email VARCHAR(255),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
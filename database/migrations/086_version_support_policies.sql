@@ -0,0 +1,16 @@
+-- Support policies publishers declare per version. End-of-life status is
+-- computed from supported_until; dependents of versions reaching it are
+-- alerted through dependency_alerts.
+CREATE TABLE version_support_policies (
+    version_id UUID PRIMARY KEY REFERENCES contract_versions(id) ON DELETE CASCADE,
+    supported_until DATE,
+    lts BOOLEAN NOT NULL DEFAULT FALSE,
+    updated_by VARCHAR(64),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_version_support_policies_until
+    ON version_support_policies(supported_until) WHERE supported_until IS NOT NULL;
+
+-- For end-of-life alerts source_id is the contract_versions.id
+ALTER TYPE alert_source ADD VALUE 'end_of_life';
@@ -0,0 +1,16 @@
+-- Runnable usage examples publishers attach to a contract: named sequences of
+-- calls with sample arguments, validated against a version's interface and
+-- run through the simulation sandbox.
+CREATE TABLE contract_examples (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    contract_id UUID NOT NULL REFERENCES contracts(id) ON DELETE CASCADE,
+    name VARCHAR(64) NOT NULL,
+    description TEXT,
+    version VARCHAR(50) NOT NULL,
+    -- [{"function": "...", "args": ["..."]}, ...]
+    steps JSONB NOT NULL,
+    created_by VARCHAR(64) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (contract_id, name)
+);
@@ -0,0 +1,11 @@
+-- Event schemas publishers register per version, used to decode raw
+-- on-chain events into named, typed fields.
+CREATE TABLE contract_event_schemas (
+    version_id UUID NOT NULL REFERENCES contract_versions(id) ON DELETE CASCADE,
+    name VARCHAR(60) NOT NULL,
+    -- {"name", "prefix_topics", "params", "data_format", "doc"}
+    schema JSONB NOT NULL,
+    updated_by VARCHAR(64) NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (version_id, name)
+);
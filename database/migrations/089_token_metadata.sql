@@ -0,0 +1,12 @@
+-- SEP-41 token metadata (name, symbol, decimals) read from the chain for
+-- contracts implementing the interface, cached so wallets using the registry
+-- as a token list source do not hit the network's RPC on every request.
+CREATE TABLE token_metadata (
+    contract_id UUID PRIMARY KEY REFERENCES contracts(id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    symbol TEXT NOT NULL,
+    decimals INTEGER NOT NULL CHECK (decimals >= 0),
+    fetched_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_token_metadata_fetched_at ON token_metadata(fetched_at);
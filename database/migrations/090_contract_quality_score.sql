@@ -0,0 +1,8 @@
+-- Quality score: one 0-100 number per contract combining time-decayed
+-- downloads, how recently it was updated, verification, audits and stars.
+-- Recomputed nightly by the API and used as the default ranking.
+ALTER TABLE contracts
+    ADD COLUMN quality_score DOUBLE PRECISION NOT NULL DEFAULT 0.0,
+    ADD COLUMN quality_scored_at TIMESTAMPTZ;
+
+CREATE INDEX idx_contracts_quality_score ON contracts(quality_score DESC);
@@ -0,0 +1,41 @@
+-- Abuse reports against contracts and the moderation state they lead to.
+-- Quarantined contracts are hidden from listings but stay reachable by ID;
+-- taken-down contracts answer 410 Gone.
+CREATE TYPE moderation_status AS ENUM ('active', 'quarantined', 'taken_down');
+
+ALTER TABLE contracts
+    ADD COLUMN moderation_status moderation_status NOT NULL DEFAULT 'active';
+
+CREATE INDEX idx_contracts_moderated ON contracts(moderation_status)
+    WHERE moderation_status <> 'active';
+
+CREATE TYPE abuse_reason AS ENUM (
+    'malware', 'scam', 'impersonation', 'spam', 'license_violation', 'other'
+);
+
+-- 'open' until a moderator acts; then the action that closed it
+CREATE TYPE abuse_report_status AS ENUM ('open', 'dismissed', 'quarantined', 'taken_down');
+
+CREATE TABLE abuse_reports (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    contract_id UUID NOT NULL REFERENCES contracts(id) ON DELETE CASCADE,
+    reason abuse_reason NOT NULL,
+    details TEXT,
+    reporter_contact TEXT,
+    -- Signed-in reporter, if any
+    reporter_address VARCHAR(64),
+    -- Who reported, for deduplication and rate limiting: the account address,
+    -- or a hash of the IP for anonymous reports
+    reporter_key TEXT NOT NULL,
+    status abuse_report_status NOT NULL DEFAULT 'open',
+    resolution_note TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    resolved_at TIMESTAMPTZ
+);
+
+-- One open report per reporter and contract
+CREATE UNIQUE INDEX idx_abuse_reports_open_reporter
+    ON abuse_reports(contract_id, reporter_key)
+    WHERE status = 'open';
+CREATE INDEX idx_abuse_reports_reporter_recent ON abuse_reports(reporter_key, created_at);
+CREATE INDEX idx_abuse_reports_open ON abuse_reports(contract_id) WHERE status = 'open';
@@ -0,0 +1,34 @@
+-- Namespaces and @namespace/name contract names are unique per tenant rather
+-- than across the deployment. A publisher's namespace belongs to the tenant it
+-- was claimed in: that of its oldest contract in it, else the default tenant.
+ALTER TABLE publishers ADD COLUMN namespace_tenant_id UUID REFERENCES tenants(id);
+
+UPDATE publishers p
+SET namespace_tenant_id = COALESCE(
+    (SELECT c.tenant_id FROM contracts c
+     WHERE c.publisher_id = p.id AND c.namespace = p.namespace
+     ORDER BY c.created_at LIMIT 1),
+    '00000000-0000-0000-0000-000000000000'
+)
+WHERE p.namespace IS NOT NULL;
+
+ALTER TABLE publishers DROP CONSTRAINT publishers_namespace_key;
+ALTER TABLE publishers
+    ADD CONSTRAINT publishers_namespace_key UNIQUE (namespace_tenant_id, namespace);
+
+DROP INDEX idx_contracts_namespace_name_network;
+CREATE UNIQUE INDEX idx_contracts_namespace_name_network
+    ON contracts(tenant_id, namespace, name, network)
+    WHERE namespace IS NOT NULL;
+
+-- Old names kept after a rename resolve only within their tenant
+ALTER TABLE contract_aliases
+    ADD COLUMN tenant_id UUID NOT NULL DEFAULT '00000000-0000-0000-0000-000000000000'
+    REFERENCES tenants(id);
+
+UPDATE contract_aliases a SET tenant_id = c.tenant_id
+FROM contracts c WHERE c.id = a.contract_id;
+
+DROP INDEX idx_contract_aliases_name;
+CREATE UNIQUE INDEX idx_contract_aliases_name
+    ON contract_aliases(tenant_id, (COALESCE(namespace, '')), name);
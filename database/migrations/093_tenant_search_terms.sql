@@ -0,0 +1,17 @@
+-- "Did you mean" suggestions only draw on words from the searching tenant's
+-- visible contracts, never from other tenants or moderated-away listings
+DROP MATERIALIZED VIEW contract_search_terms;
+
+CREATE MATERIALIZED VIEW contract_search_terms AS
+SELECT DISTINCT c.tenant_id, term
+FROM contracts c,
+     LATERAL regexp_split_to_table(
+         LOWER(c.name || ' ' || COALESCE(c.category, '') || ' ' ||
+               array_to_string(c.tags, ' ') || ' ' || COALESCE(c.description, '')),
+         '[^a-z0-9]+'
+     ) AS term
+WHERE length(term) >= 3 AND NOT c.is_private AND c.moderation_status = 'active';
+
+CREATE UNIQUE INDEX idx_contract_search_terms_term ON contract_search_terms (tenant_id, term);
+CREATE INDEX idx_contract_search_terms_trgm
+    ON contract_search_terms USING gin (term gin_trgm_ops);
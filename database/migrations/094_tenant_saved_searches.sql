@@ -0,0 +1,9 @@
+-- Saved searches belong to the tenant they were saved in, so they only match
+-- that tenant's contracts and erasing an account in one tenant keeps the rest
+ALTER TABLE saved_searches
+    ADD COLUMN tenant_id UUID NOT NULL DEFAULT '00000000-0000-0000-0000-000000000000'
+    REFERENCES tenants(id);
+
+ALTER TABLE saved_searches DROP CONSTRAINT saved_searches_owner_address_name_key;
+ALTER TABLE saved_searches
+    ADD CONSTRAINT saved_searches_tenant_owner_name_key UNIQUE (tenant_id, owner_address, name);
@@ -0,0 +1,52 @@
+-- Core registry records for SQLite deployments. Mirrors the Postgres columns the
+-- repository reads; ids are hyphenated UUID text, tags a JSON array.
+
+CREATE TABLE publishers (
+    id TEXT PRIMARY KEY,
+    stellar_address TEXT NOT NULL UNIQUE,
+    username TEXT,
+    email TEXT,
+    github_url TEXT,
+    website TEXT,
+    namespace TEXT UNIQUE,
+    created_at TEXT NOT NULL
+);
+
+CREATE TABLE contracts (
+    id TEXT PRIMARY KEY,
+    contract_id TEXT NOT NULL,
+    wasm_hash TEXT NOT NULL,
+    name TEXT NOT NULL,
+    namespace TEXT,
+    description TEXT,
+    publisher_id TEXT NOT NULL REFERENCES publishers(id) ON DELETE CASCADE,
+    network TEXT NOT NULL CHECK (network IN ('mainnet', 'testnet', 'futurenet')),
+    is_verified INTEGER NOT NULL DEFAULT 0,
+    category TEXT,
+    tags TEXT NOT NULL DEFAULT '[]',
+    license TEXT,
+    repository_url TEXT,
+    homepage TEXT,
+    documentation_url TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    UNIQUE (contract_id, network)
+);
+
+CREATE INDEX idx_contracts_publisher_id ON contracts(publisher_id);
+CREATE INDEX idx_contracts_network ON contracts(network);
+CREATE INDEX idx_contracts_category ON contracts(category);
+
+CREATE TABLE contract_versions (
+    id TEXT PRIMARY KEY,
+    contract_id TEXT NOT NULL REFERENCES contracts(id) ON DELETE CASCADE,
+    version TEXT NOT NULL,
+    wasm_hash TEXT NOT NULL,
+    source_url TEXT,
+    commit_hash TEXT,
+    release_notes TEXT,
+    created_at TEXT NOT NULL,
+    UNIQUE (contract_id, version)
+);
+
+CREATE INDEX idx_contract_versions_contract_id ON contract_versions(contract_id);
@@ -0,0 +1,15 @@
+-- Version ABIs (JSON text) and contract-level READMEs, as shown by the web UI
+
+CREATE TABLE contract_abis (
+    contract_id TEXT NOT NULL REFERENCES contracts(id) ON DELETE CASCADE,
+    version TEXT NOT NULL,
+    abi TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    PRIMARY KEY (contract_id, version)
+);
+
+CREATE TABLE contract_readmes (
+    contract_id TEXT PRIMARY KEY REFERENCES contracts(id) ON DELETE CASCADE,
+    content TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
@@ -34,6 +34,10 @@ export interface Contract {
   tags: string[];
   popularity_score?: number;
   downloads?: number;
+  /** Average of user reviews; absent until the first review */
+  rating?: { average: number; count: number };
+  /** Number of users who starred the contract */
+  star_count?: number;
   // Image fields for contract logo/icon
   logo_url?: string;
   created_at: string;
@@ -43,6 +47,8 @@ export interface Contract {
   logical_id?: string;
   /** Per-network configs: { mainnet: {...}, testnet: {...} } */
   network_configs?: Record<Network, NetworkConfig>;
+  /** Search matches wrapped in <mark>…</mark>; only present on search results */
+  highlight?: { name?: string; description?: string };
 }
 
 /** GET /contracts/:id response when ?network= is used (Issue #43) */
@@ -140,12 +146,32 @@ export interface Publisher {
   created_at: string;
 }
 
+/** GET /api/publishers/:id/contracts — one page plus totals across all contracts */
+export interface PublisherContractsResponse {
+  publisher_id: string;
+  summary: {
+    total_contracts: number;
+    verified_contracts: number;
+    verified_percentage: number;
+    total_downloads: number;
+  };
+  contracts: (Contract & { downloads: number })[];
+  page: number;
+  pages: number;
+}
+
 export interface PaginatedResponse<T> {
   items: T[];
   total: number;
   page: number;
   page_size: number;
   total_pages: number;
+  has_next?: boolean;
+  has_prev?: boolean;
+  /** Pass back as `cursor` (with the same filters) for the next page */
+  next_cursor?: string;
+  /** Filters the server applied, keyed by query parameter */
+  filters?: Record<string, unknown>;
 }
 
 export interface DependencyTreeNode {
@@ -177,7 +203,7 @@ export interface ContractSearchParams {
   maturity?: 'alpha' | 'beta' | 'stable' | 'mature' | 'legacy';
   page?: number;
   page_size?: number;
-  sort_by?: 'name' | 'created_at' | 'updated_at' | 'popularity' | 'deployments' | 'interactions' | 'relevance' | 'downloads';
+  sort_by?: 'name' | 'created_at' | 'updated_at' | 'popularity' | 'deployments' | 'interactions' | 'relevance' | 'downloads' | 'rating' | 'stars';
   sort_order?: 'asc' | 'desc';
 }
 
@@ -494,17 +520,22 @@ export const api = {
     );
     if (params?.author) queryParams.append("author", params.author);
     params?.tags?.forEach((tag) => queryParams.append("tag", tag));
-    // Backend expects sort_by without underscores: createdat, updatedat, popularity, deployments, interactions, relevance
-    if (params?.sort_by) {
-      const backendSortBy =
-        params.sort_by === 'created_at' ? 'createdat'
-        : params.sort_by === 'updated_at' ? 'updatedat'
-        : params.sort_by === 'name' ? 'createdat'
-        : params.sort_by === 'downloads' ? 'interactions'
-        : params.sort_by;
-      queryParams.append("sort_by", backendSortBy);
+    // name/created/updated/downloads/rating/stars use ?sort=&order= (stable, id tie-break);
+    // the remaining keys go through the older sort_by/sort_order pair
+    const sortKey =
+      params?.sort_by === 'created_at' ? 'created'
+      : params?.sort_by === 'updated_at' ? 'updated'
+      : params?.sort_by === 'name' || params?.sort_by === 'downloads' || params?.sort_by === 'rating'
+          || params?.sort_by === 'stars'
+        ? params.sort_by
+      : undefined;
+    if (sortKey) {
+      queryParams.append("sort", sortKey);
+      if (params?.sort_order) queryParams.append("order", params.sort_order);
+    } else {
+      if (params?.sort_by) queryParams.append("sort_by", params.sort_by);
+      if (params?.sort_order) queryParams.append("sort_order", params.sort_order);
     }
-    if (params?.sort_order) queryParams.append("sort_order", params.sort_order);
     if (params?.page) queryParams.append("page", String(params.page));
     if (params?.page_size)
       queryParams.append("page_size", String(params.page_size));
@@ -820,15 +851,36 @@ export const api = {
     );
   },
 
-  async getPublisherContracts(id: string): Promise<Contract[]> {
+  async getPublisherContracts(
+    id: string,
+    params?: { page?: number; page_size?: number },
+  ): Promise<PublisherContractsResponse> {
     if (USE_MOCKS) {
-      return Promise.resolve(
-        MOCK_CONTRACTS.filter((c) => c.publisher_id === id),
-      );
+      const contracts = MOCK_CONTRACTS.filter((c) => c.publisher_id === id);
+      const verified = contracts.filter((c) => c.is_verified).length;
+      return Promise.resolve({
+        publisher_id: id,
+        summary: {
+          total_contracts: contracts.length,
+          verified_contracts: verified,
+          verified_percentage: contracts.length
+            ? Math.round((verified * 1000) / contracts.length) / 10
+            : 0,
+          total_downloads: 0,
+        },
+        contracts: contracts.map((c) => ({ ...c, downloads: 0 })),
+        page: 1,
+        pages: 1,
+      });
     }
 
-    return handleApiCall<Contract[]>(
-      () => fetch(`${API_URL}/api/publishers/${id}/contracts`),
+    const queryParams = new URLSearchParams();
+    if (params?.page) queryParams.append("page", String(params.page));
+    if (params?.page_size)
+      queryParams.append("page_size", String(params.page_size));
+
+    return handleApiCall<PublisherContractsResponse>(
+      () => fetch(`${API_URL}/api/publishers/${id}/contracts?${queryParams}`),
       `/api/publishers/${id}/contracts`
     );
   },